@@ -0,0 +1,1299 @@
+//! ACME Server Adapter for Internal Issuance
+//!
+//! Exposes an intermediate CA managed by cim-keys over ACME (RFC 8555) so
+//! internal services can obtain and renew TLS certificates with standard
+//! clients (certbot, lego, cert-manager, Caddy).
+//!
+//! The adapter is a protocol engine, not an HTTP server: the host maps each
+//! HTTP exchange to an [`AcmeRequest`], calls [`AcmeServer::handle`], and
+//! writes back the [`AcmeResponse`]. This keeps the CA key inside the
+//! process that owns it and lets the host pick its own listener and TLS.
+//!
+//! ```text
+//! client ──JWS──▶ host HTTP listener ──AcmeRequest──▶ AcmeServer
+//!                                                       │ verify nonce, url, signature
+//!                                                       │ ChallengeValidator (http-01 / dns-01)
+//!                                                       ▼
+//!                                   crypto::issue_certificate (intermediate CA)
+//!                                                       │
+//!                                   CertificateGenerated / CertificateSigned events
+//! ```
+//!
+//! Challenge validation goes through the [`ChallengeValidator`] port; the
+//! [`StubChallengeValidator`] checks responses the test or host published
+//! to it instead of reaching the network.
+//!
+//! Only `dns` identifiers are supported. Wildcard identifiers are offered
+//! the dns-01 challenge only, as required by RFC 8555 §7.1.3.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use rcgen::{CertificateSigningRequestParams, SanType};
+use rustls::pki_types::CertificateSigningRequestDer;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::crypto::x509::{issue_certificate, IssuedCertificate, LeafCertificateParams};
+use crate::domain::PolicyConstraint;
+use crate::events::{CertificateEvents, DomainEvent};
+use crate::types::KeyPurpose;
+
+/// How long pending orders and authorizations stay open
+const ORDER_LIFETIME_HOURS: i64 = 24;
+
+/// Issued nonces kept for checking; older ones are forgotten and rejected
+const MAX_OUTSTANDING_NONCES: usize = 4096;
+
+// ============================================================================
+// Challenge validation port
+// ============================================================================
+
+/// Checks that an ACME client controls an identifier
+///
+/// Implementations fetch `http://{domain}/.well-known/acme-challenge/{token}`
+/// or look up the `_acme-challenge.{domain}` TXT record, whichever suits the
+/// internal network.
+#[async_trait]
+pub trait ChallengeValidator: Send + Sync {
+    /// The resource at the http-01 well-known path must equal `key_authorization`
+    async fn validate_http01(&self, domain: &str, token: &str, key_authorization: &str) -> Result<(), String>;
+
+    /// A TXT record at `_acme-challenge.{domain}` must equal `txt_value`
+    async fn validate_dns01(&self, domain: &str, txt_value: &str) -> Result<(), String>;
+}
+
+/// Challenge validator that checks responses published to it in memory
+///
+/// Stands in for the network in tests and single-host deployments: the
+/// client side publishes what it would serve or put in DNS, and validation
+/// compares against that.
+#[derive(Debug, Default)]
+pub struct StubChallengeValidator {
+    http01: std::sync::Mutex<HashMap<(String, String), String>>,
+    dns01: std::sync::Mutex<HashMap<String, String>>,
+}
+
+impl StubChallengeValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `key_authorization` for `token` on `domain`
+    pub fn publish_http01(&self, domain: &str, token: &str, key_authorization: &str) {
+        self.http01
+            .lock()
+            .expect("http-01 stub lock poisoned")
+            .insert((domain.to_ascii_lowercase(), token.to_string()), key_authorization.to_string());
+    }
+
+    /// Set the `_acme-challenge` TXT record for `domain`
+    pub fn publish_dns01(&self, domain: &str, txt_value: &str) {
+        self.dns01
+            .lock()
+            .expect("dns-01 stub lock poisoned")
+            .insert(domain.to_ascii_lowercase(), txt_value.to_string());
+    }
+}
+
+#[async_trait]
+impl ChallengeValidator for StubChallengeValidator {
+    async fn validate_http01(&self, domain: &str, token: &str, key_authorization: &str) -> Result<(), String> {
+        let served = self
+            .http01
+            .lock()
+            .expect("http-01 stub lock poisoned")
+            .get(&(domain.to_ascii_lowercase(), token.to_string()))
+            .cloned();
+        match served {
+            Some(body) if body == key_authorization => Ok(()),
+            Some(_) => Err(format!("Wrong key authorization served by {}", domain)),
+            None => Err(format!("No challenge response served by {}", domain)),
+        }
+    }
+
+    async fn validate_dns01(&self, domain: &str, txt_value: &str) -> Result<(), String> {
+        let record = self.dns01.lock().expect("dns-01 stub lock poisoned").get(&domain.to_ascii_lowercase()).cloned();
+        match record {
+            Some(value) if value == txt_value => Ok(()),
+            Some(_) => Err(format!("Wrong TXT record at _acme-challenge.{}", domain)),
+            None => Err(format!("No TXT record at _acme-challenge.{}", domain)),
+        }
+    }
+}
+
+// ============================================================================
+// Transport types
+// ============================================================================
+
+/// HTTP method of an ACME request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcmeMethod {
+    Get,
+    Head,
+    Post,
+}
+
+/// An HTTP request addressed to the ACME server
+#[derive(Debug, Clone)]
+pub struct AcmeRequest {
+    pub method: AcmeMethod,
+    /// Path below `AcmeServerConfig::base_url`, e.g. `/new-order`
+    pub path: String,
+    /// Flattened JWS for POST requests, empty otherwise
+    pub body: Vec<u8>,
+}
+
+/// The HTTP response to send back
+#[derive(Debug, Clone)]
+pub struct AcmeResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl AcmeResponse {
+    /// First header named `name` (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    /// The body parsed as JSON
+    pub fn json(&self) -> Option<Value> {
+        serde_json::from_slice(&self.body).ok()
+    }
+
+    fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+}
+
+/// ACME error types (RFC 8555 §6.7)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcmeErrorType {
+    AccountDoesNotExist,
+    BadCsr,
+    BadNonce,
+    BadSignatureAlgorithm,
+    IncorrectResponse,
+    Malformed,
+    OrderNotReady,
+    RejectedIdentifier,
+    Unauthorized,
+    UnsupportedIdentifier,
+}
+
+impl AcmeErrorType {
+    fn urn(&self) -> &'static str {
+        match self {
+            AcmeErrorType::AccountDoesNotExist => "urn:ietf:params:acme:error:accountDoesNotExist",
+            AcmeErrorType::BadCsr => "urn:ietf:params:acme:error:badCSR",
+            AcmeErrorType::BadNonce => "urn:ietf:params:acme:error:badNonce",
+            AcmeErrorType::BadSignatureAlgorithm => "urn:ietf:params:acme:error:badSignatureAlgorithm",
+            AcmeErrorType::IncorrectResponse => "urn:ietf:params:acme:error:incorrectResponse",
+            AcmeErrorType::Malformed => "urn:ietf:params:acme:error:malformed",
+            AcmeErrorType::OrderNotReady => "urn:ietf:params:acme:error:orderNotReady",
+            AcmeErrorType::RejectedIdentifier => "urn:ietf:params:acme:error:rejectedIdentifier",
+            AcmeErrorType::Unauthorized => "urn:ietf:params:acme:error:unauthorized",
+            AcmeErrorType::UnsupportedIdentifier => "urn:ietf:params:acme:error:unsupportedIdentifier",
+        }
+    }
+}
+
+/// An ACME problem document
+#[derive(Debug, Clone, Error)]
+#[error("{}: {detail}", .kind.urn())]
+pub struct AcmeProblem {
+    pub kind: AcmeErrorType,
+    pub detail: String,
+    pub status: u16,
+}
+
+impl AcmeProblem {
+    fn new(kind: AcmeErrorType, status: u16, detail: impl Into<String>) -> Self {
+        Self { kind, detail: detail.into(), status }
+    }
+
+    fn malformed(detail: impl Into<String>) -> Self {
+        Self::new(AcmeErrorType::Malformed, 400, detail)
+    }
+
+    fn not_found(what: &str) -> Self {
+        Self::new(AcmeErrorType::Malformed, 404, format!("No such {}", what))
+    }
+
+    fn unauthorized(detail: impl Into<String>) -> Self {
+        Self::new(AcmeErrorType::Unauthorized, 403, detail)
+    }
+
+    fn to_json(&self) -> Value {
+        json!({ "type": self.kind.urn(), "detail": self.detail, "status": self.status })
+    }
+}
+
+// ============================================================================
+// JWS verification
+// ============================================================================
+
+/// Account public key (RFC 7517 JWK, ES256 / EdDSA / RS256)
+#[derive(Debug, Clone, Deserialize)]
+struct Jwk {
+    kty: String,
+    crv: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+}
+
+fn b64(data: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(data)
+}
+
+fn unb64(field: &str, value: &str) -> Result<Vec<u8>, AcmeProblem> {
+    URL_SAFE_NO_PAD
+        .decode(value)
+        .map_err(|_| AcmeProblem::malformed(format!("{} is not base64url", field)))
+}
+
+impl Jwk {
+    fn member<'a>(value: &'a Option<String>, name: &str) -> Result<&'a str, AcmeProblem> {
+        value.as_deref().ok_or_else(|| AcmeProblem::malformed(format!("JWK is missing '{}'", name)))
+    }
+
+    /// RFC 7638 thumbprint: SHA-256 over the required members in lexical order
+    fn thumbprint(&self) -> Result<String, AcmeProblem> {
+        let canonical = match self.kty.as_str() {
+            "EC" => format!(
+                r#"{{"crv":"{}","kty":"EC","x":"{}","y":"{}"}}"#,
+                Self::member(&self.crv, "crv")?,
+                Self::member(&self.x, "x")?,
+                Self::member(&self.y, "y")?
+            ),
+            "OKP" => format!(
+                r#"{{"crv":"{}","kty":"OKP","x":"{}"}}"#,
+                Self::member(&self.crv, "crv")?,
+                Self::member(&self.x, "x")?
+            ),
+            "RSA" => format!(
+                r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#,
+                Self::member(&self.e, "e")?,
+                Self::member(&self.n, "n")?
+            ),
+            other => return Err(AcmeProblem::new(AcmeErrorType::BadSignatureAlgorithm, 400, format!("Unsupported key type {}", other))),
+        };
+        Ok(b64(&Sha256::digest(canonical.as_bytes())))
+    }
+
+    fn verify(&self, alg: &str, message: &[u8], signature: &[u8]) -> Result<(), AcmeProblem> {
+        use ring::signature::{self, UnparsedPublicKey};
+
+        let bad_alg = || AcmeProblem::new(
+            AcmeErrorType::BadSignatureAlgorithm,
+            400,
+            format!("Algorithm {} does not match a {} account key", alg, self.kty),
+        );
+        let result = match (alg, self.kty.as_str(), self.crv.as_deref()) {
+            ("ES256", "EC", Some("P-256")) => {
+                let mut point = vec![0x04];
+                point.extend(unb64("x", Self::member(&self.x, "x")?)?);
+                point.extend(unb64("y", Self::member(&self.y, "y")?)?);
+                UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, point).verify(message, signature)
+            }
+            ("EdDSA", "OKP", Some("Ed25519")) => {
+                let x = unb64("x", Self::member(&self.x, "x")?)?;
+                UnparsedPublicKey::new(&signature::ED25519, x).verify(message, signature)
+            }
+            ("RS256", "RSA", _) => {
+                let key = signature::RsaPublicKeyComponents {
+                    n: unb64("n", Self::member(&self.n, "n")?)?,
+                    e: unb64("e", Self::member(&self.e, "e")?)?,
+                };
+                key.verify(&signature::RSA_PKCS1_2048_8192_SHA256, message, signature)
+            }
+            _ => return Err(bad_alg()),
+        };
+        result.map_err(|_| AcmeProblem::malformed("JWS signature verification failed"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct FlattenedJws {
+    protected: String,
+    payload: String,
+    signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProtectedHeader {
+    alg: String,
+    nonce: Option<String>,
+    url: Option<String>,
+    jwk: Option<Jwk>,
+    kid: Option<String>,
+}
+
+/// A verified JWS: who signed it and what they sent
+struct SignedRequest {
+    /// Set when signed with `kid`
+    account_id: Option<String>,
+    /// Set when signed with an embedded `jwk` (new-account only)
+    jwk: Option<Jwk>,
+    /// Empty for POST-as-GET
+    payload: Vec<u8>,
+}
+
+impl SignedRequest {
+    fn account_id(&self) -> Result<&str, AcmeProblem> {
+        self.account_id
+            .as_deref()
+            .ok_or_else(|| AcmeProblem::malformed("Request must be signed with an account 'kid'"))
+    }
+
+    fn payload_json<T: for<'de> Deserialize<'de>>(&self) -> Result<T, AcmeProblem> {
+        serde_json::from_slice(&self.payload).map_err(|e| AcmeProblem::malformed(format!("Invalid payload: {}", e)))
+    }
+}
+
+// ============================================================================
+// Server state
+// ============================================================================
+
+/// Issuance settings for the ACME endpoint
+#[derive(Debug, Clone)]
+pub struct AcmeServerConfig {
+    /// Externally visible URL of the ACME endpoint, without trailing slash
+    pub base_url: String,
+    /// Intermediate CA that signs ACME certificates
+    pub issuer_ca_id: Uuid,
+    /// Intermediate certificate followed by its chain to the root (PEM)
+    pub issuer_chain_pem: String,
+    /// Policy applied to every issuance (name constraints, max validity)
+    pub constraints: Vec<PolicyConstraint>,
+    /// Requested validity of issued certificates
+    pub validity_days: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pending,
+    Ready,
+    Processing,
+    Valid,
+    Invalid,
+}
+
+impl Status {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Pending => "pending",
+            Status::Ready => "ready",
+            Status::Processing => "processing",
+            Status::Valid => "valid",
+            Status::Invalid => "invalid",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChallengeType {
+    Http01,
+    Dns01,
+}
+
+impl ChallengeType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ChallengeType::Http01 => "http-01",
+            ChallengeType::Dns01 => "dns-01",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Account {
+    jwk: Jwk,
+    thumbprint: String,
+    contact: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Order {
+    account_id: String,
+    identifiers: Vec<String>,
+    authorization_ids: Vec<String>,
+    status: Status,
+    expires: DateTime<Utc>,
+    certificate_id: Option<String>,
+    error: Option<AcmeProblem>,
+}
+
+#[derive(Debug, Clone)]
+struct Authorization {
+    account_id: String,
+    /// Base domain, without the `*.` of a wildcard
+    domain: String,
+    wildcard: bool,
+    status: Status,
+    expires: DateTime<Utc>,
+    challenge_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Challenge {
+    authorization_id: String,
+    kind: ChallengeType,
+    token: String,
+    status: Status,
+    validated: Option<DateTime<Utc>>,
+    error: Option<AcmeProblem>,
+}
+
+/// Nonces handed out and not yet used, oldest first
+///
+/// Bounded by [`MAX_OUTSTANDING_NONCES`]: clients that fetch nonces without
+/// using them cannot grow it, and a client presenting a forgotten nonce
+/// gets `badNonce` and retries with the fresh one in that response.
+#[derive(Default)]
+struct NonceStore {
+    issued: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl NonceStore {
+    fn insert(&mut self, nonce: String) {
+        self.issued.insert(nonce.clone());
+        self.order.push_back(nonce);
+        while self.order.len() > MAX_OUTSTANDING_NONCES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.issued.remove(&oldest);
+            }
+        }
+    }
+
+    /// Use up `nonce`, returning whether it was outstanding
+    fn take(&mut self, nonce: &str) -> bool {
+        self.issued.remove(nonce)
+    }
+}
+
+#[derive(Default)]
+struct AcmeState {
+    nonces: NonceStore,
+    accounts: HashMap<String, Account>,
+    orders: HashMap<String, Order>,
+    authorizations: HashMap<String, Authorization>,
+    challenges: HashMap<String, Challenge>,
+    /// Certificate chains by certificate ID
+    certificates: HashMap<String, (String, String)>,
+    events: Vec<DomainEvent>,
+}
+
+fn random_token() -> String {
+    use ring::rand::SecureRandom;
+    let mut bytes = [0u8; 16];
+    ring::rand::SystemRandom::new()
+        .fill(&mut bytes)
+        .expect("system random number generator failed");
+    b64(&bytes)
+}
+
+fn new_id() -> String {
+    Uuid::now_v7().simple().to_string()
+}
+
+/// ACME protocol engine backed by a cim-keys intermediate CA
+pub struct AcmeServer<S: rcgen::SigningKey, V: ChallengeValidator> {
+    config: AcmeServerConfig,
+    signer: S,
+    validator: V,
+    state: Mutex<AcmeState>,
+}
+
+impl<S: rcgen::SigningKey + Send + Sync, V: ChallengeValidator> AcmeServer<S, V> {
+    /// Create a server issuing from the CA whose key `signer` holds
+    pub fn new(config: AcmeServerConfig, signer: S, validator: V) -> Self {
+        Self { config, signer, validator, state: Mutex::new(AcmeState::default()) }
+    }
+
+    /// The challenge validator, e.g. to publish stub responses
+    pub fn validator(&self) -> &V {
+        &self.validator
+    }
+
+    /// URL of the directory resource clients are configured with
+    pub fn directory_url(&self) -> String {
+        self.url("/directory")
+    }
+
+    /// Take the certificate events produced by issuance since the last call
+    ///
+    /// The host applies these to the projection and publishes them, exactly
+    /// as for certificates issued from the GUI or CLI.
+    pub async fn drain_events(&self) -> Vec<DomainEvent> {
+        std::mem::take(&mut self.state.lock().await.events)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url, path)
+    }
+
+    /// Handle one HTTP exchange
+    pub async fn handle(&self, request: AcmeRequest) -> AcmeResponse {
+        let response = match self.route(&request).await {
+            Ok(response) => response,
+            Err(problem) => AcmeResponse {
+                status: problem.status,
+                headers: vec![("Content-Type".to_string(), "application/problem+json".to_string())],
+                body: problem.to_json().to_string().into_bytes(),
+            },
+        };
+        let response = response.with_header("Link", format!("<{}>;rel=\"index\"", self.directory_url()));
+        if request.method == AcmeMethod::Post || request.path == "/new-nonce" {
+            let nonce = self.new_nonce().await;
+            response.with_header("Replay-Nonce", nonce).with_header("Cache-Control", "no-store")
+        } else {
+            response
+        }
+    }
+
+    async fn route(&self, request: &AcmeRequest) -> Result<AcmeResponse, AcmeProblem> {
+        let segments: Vec<&str> = request.path.trim_start_matches('/').split('/').collect();
+        match (request.method, segments.as_slice()) {
+            (AcmeMethod::Get, ["directory"]) => Ok(Self::json_response(200, self.directory())),
+            (AcmeMethod::Head, ["new-nonce"]) => Ok(Self::empty_response(200)),
+            (AcmeMethod::Get, ["new-nonce"]) => Ok(Self::empty_response(204)),
+            (AcmeMethod::Post, _) => {
+                let signed = self.verify_jws(&request.path, &request.body).await?;
+                match segments.as_slice() {
+                    ["new-account"] => self.new_account(signed).await,
+                    ["acct", id] => self.get_account(&signed, id).await,
+                    ["acct", id, "orders"] => self.list_orders(&signed, id).await,
+                    ["new-order"] => self.new_order(signed).await,
+                    ["order", id] => self.get_order(&signed, id).await,
+                    ["order", id, "finalize"] => self.finalize(signed, id).await,
+                    ["authz", id] => self.get_authorization(&signed, id).await,
+                    ["chall", id] => self.respond_to_challenge(&signed, id).await,
+                    ["cert", id] => self.get_certificate(&signed, id).await,
+                    _ => Err(AcmeProblem::not_found("resource")),
+                }
+            }
+            _ => Err(AcmeProblem::new(AcmeErrorType::Malformed, 405, "Method not allowed")),
+        }
+    }
+
+    fn json_response(status: u16, body: Value) -> AcmeResponse {
+        AcmeResponse {
+            status,
+            headers: vec![("Content-Type".to_string(), "application/json".to_string())],
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    fn empty_response(status: u16) -> AcmeResponse {
+        AcmeResponse { status, headers: Vec::new(), body: Vec::new() }
+    }
+
+    fn directory(&self) -> Value {
+        json!({
+            "newNonce": self.url("/new-nonce"),
+            "newAccount": self.url("/new-account"),
+            "newOrder": self.url("/new-order"),
+            "meta": { "externalAccountRequired": false },
+        })
+    }
+
+    async fn new_nonce(&self) -> String {
+        let nonce = random_token();
+        self.state.lock().await.nonces.insert(nonce.clone());
+        nonce
+    }
+
+    /// Check nonce, url and signature (RFC 8555 §6.2-6.5)
+    async fn verify_jws(&self, path: &str, body: &[u8]) -> Result<SignedRequest, AcmeProblem> {
+        let jws: FlattenedJws = serde_json::from_slice(body)
+            .map_err(|_| AcmeProblem::malformed("Request body must be a flattened JWS"))?;
+        let header: ProtectedHeader = serde_json::from_slice(&unb64("protected", &jws.protected)?)
+            .map_err(|e| AcmeProblem::malformed(format!("Invalid protected header: {}", e)))?;
+
+        if header.url.as_deref() != Some(self.url(path).as_str()) {
+            return Err(AcmeProblem::unauthorized("JWS 'url' does not match the request URL"));
+        }
+
+        let mut state = self.state.lock().await;
+        let nonce = header.nonce.as_deref().unwrap_or_default();
+        if !state.nonces.take(nonce) {
+            return Err(AcmeProblem::new(AcmeErrorType::BadNonce, 400, "Unknown or reused nonce"));
+        }
+
+        let (jwk, account_id) = match (header.jwk, header.kid) {
+            (Some(jwk), None) if path == "/new-account" => (jwk, None),
+            (None, Some(kid)) if path != "/new-account" => {
+                let id = kid
+                    .strip_prefix(&self.url("/acct/"))
+                    .ok_or_else(|| AcmeProblem::new(AcmeErrorType::AccountDoesNotExist, 400, "Unknown account"))?;
+                let account = state
+                    .accounts
+                    .get(id)
+                    .ok_or_else(|| AcmeProblem::new(AcmeErrorType::AccountDoesNotExist, 400, "Unknown account"))?;
+                (account.jwk.clone(), Some(id.to_string()))
+            }
+            _ => return Err(AcmeProblem::malformed("new-account requires 'jwk', all other requests 'kid'")),
+        };
+        drop(state);
+
+        let signing_input = format!("{}.{}", jws.protected, jws.payload);
+        jwk.verify(&header.alg, signing_input.as_bytes(), &unb64("signature", &jws.signature)?)?;
+
+        Ok(SignedRequest {
+            jwk: account_id.is_none().then_some(jwk),
+            account_id,
+            payload: unb64("payload", &jws.payload)?,
+        })
+    }
+
+    // ------------------------------------------------------------------------
+    // Accounts
+    // ------------------------------------------------------------------------
+
+    async fn new_account(&self, signed: SignedRequest) -> Result<AcmeResponse, AcmeProblem> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct NewAccount {
+            #[serde(default)]
+            contact: Vec<String>,
+            #[serde(default)]
+            only_return_existing: bool,
+        }
+        let payload: NewAccount = signed.payload_json()?;
+        let jwk = signed.jwk.ok_or_else(|| AcmeProblem::malformed("new-account requires 'jwk'"))?;
+        let thumbprint = jwk.thumbprint()?;
+
+        let mut state = self.state.lock().await;
+        if let Some((id, account)) = state.accounts.iter().find(|(_, a)| a.thumbprint == thumbprint) {
+            return Ok(Self::json_response(200, self.account_json(id, account)).with_header("Location", self.url(&format!("/acct/{}", id))));
+        }
+        if payload.only_return_existing {
+            return Err(AcmeProblem::new(AcmeErrorType::AccountDoesNotExist, 400, "No account for this key"));
+        }
+
+        let id = new_id();
+        let account = Account { jwk, thumbprint, contact: payload.contact };
+        let body = self.account_json(&id, &account);
+        state.accounts.insert(id.clone(), account);
+        Ok(Self::json_response(201, body).with_header("Location", self.url(&format!("/acct/{}", id))))
+    }
+
+    fn account_json(&self, id: &str, account: &Account) -> Value {
+        json!({
+            "status": "valid",
+            "contact": account.contact,
+            "orders": self.url(&format!("/acct/{}/orders", id)),
+        })
+    }
+
+    async fn get_account(&self, signed: &SignedRequest, id: &str) -> Result<AcmeResponse, AcmeProblem> {
+        if signed.account_id()? != id {
+            return Err(AcmeProblem::unauthorized("Account belongs to another key"));
+        }
+        let state = self.state.lock().await;
+        let account = state.accounts.get(id).ok_or_else(|| AcmeProblem::not_found("account"))?;
+        Ok(Self::json_response(200, self.account_json(id, account)))
+    }
+
+    async fn list_orders(&self, signed: &SignedRequest, id: &str) -> Result<AcmeResponse, AcmeProblem> {
+        if signed.account_id()? != id {
+            return Err(AcmeProblem::unauthorized("Account belongs to another key"));
+        }
+        let state = self.state.lock().await;
+        let orders: Vec<String> = state
+            .orders
+            .iter()
+            .filter(|(_, o)| o.account_id == id)
+            .map(|(order_id, _)| self.url(&format!("/order/{}", order_id)))
+            .collect();
+        Ok(Self::json_response(200, json!({ "orders": orders })))
+    }
+
+    // ------------------------------------------------------------------------
+    // Orders and authorizations
+    // ------------------------------------------------------------------------
+
+    async fn new_order(&self, signed: SignedRequest) -> Result<AcmeResponse, AcmeProblem> {
+        #[derive(Deserialize)]
+        struct Identifier {
+            #[serde(rename = "type")]
+            kind: String,
+            value: String,
+        }
+        #[derive(Deserialize)]
+        struct NewOrder {
+            identifiers: Vec<Identifier>,
+        }
+        let account_id = signed.account_id()?.to_string();
+        let payload: NewOrder = signed.payload_json()?;
+        if payload.identifiers.is_empty() {
+            return Err(AcmeProblem::malformed("Order has no identifiers"));
+        }
+
+        let mut identifiers = Vec::new();
+        for identifier in payload.identifiers {
+            if identifier.kind != "dns" {
+                return Err(AcmeProblem::new(
+                    AcmeErrorType::UnsupportedIdentifier,
+                    400,
+                    format!("Identifier type '{}' is not supported", identifier.kind),
+                ));
+            }
+            let name = identifier.value.to_ascii_lowercase();
+            if !identifiers.contains(&name) {
+                identifiers.push(name);
+            }
+        }
+
+        // Refuse names the CA could never sign before any validation work
+        let violations = crate::crypto::x509::name_constraint_violations(&identifiers, &self.config.constraints);
+        if let Some((name, reason)) = violations.first() {
+            return Err(AcmeProblem::new(AcmeErrorType::RejectedIdentifier, 403, format!("{}: {}", name, reason)));
+        }
+
+        let expires = Utc::now() + Duration::hours(ORDER_LIFETIME_HOURS);
+        let mut state = self.state.lock().await;
+        let mut authorization_ids = Vec::new();
+        for name in &identifiers {
+            let (domain, wildcard) = match name.strip_prefix("*.") {
+                Some(base) => (base.to_string(), true),
+                None => (name.clone(), false),
+            };
+            let authorization_id = new_id();
+            let kinds: &[ChallengeType] = if wildcard {
+                &[ChallengeType::Dns01]
+            } else {
+                &[ChallengeType::Http01, ChallengeType::Dns01]
+            };
+            let mut challenge_ids = Vec::new();
+            for kind in kinds {
+                let challenge_id = new_id();
+                state.challenges.insert(challenge_id.clone(), Challenge {
+                    authorization_id: authorization_id.clone(),
+                    kind: *kind,
+                    token: random_token(),
+                    status: Status::Pending,
+                    validated: None,
+                    error: None,
+                });
+                challenge_ids.push(challenge_id);
+            }
+            state.authorizations.insert(authorization_id.clone(), Authorization {
+                account_id: account_id.clone(),
+                domain,
+                wildcard,
+                status: Status::Pending,
+                expires,
+                challenge_ids,
+            });
+            authorization_ids.push(authorization_id);
+        }
+
+        let order_id = new_id();
+        let order = Order {
+            account_id,
+            identifiers,
+            authorization_ids,
+            status: Status::Pending,
+            expires,
+            certificate_id: None,
+            error: None,
+        };
+        let body = self.order_json(&order_id, &order);
+        state.orders.insert(order_id.clone(), order);
+        Ok(Self::json_response(201, body).with_header("Location", self.url(&format!("/order/{}", order_id))))
+    }
+
+    fn order_json(&self, id: &str, order: &Order) -> Value {
+        let mut body = json!({
+            "status": order.status.as_str(),
+            "expires": order.expires.to_rfc3339(),
+            "identifiers": order.identifiers.iter().map(|v| json!({ "type": "dns", "value": v })).collect::<Vec<_>>(),
+            "authorizations": order.authorization_ids.iter().map(|a| self.url(&format!("/authz/{}", a))).collect::<Vec<_>>(),
+            "finalize": self.url(&format!("/order/{}/finalize", id)),
+        });
+        if let Some(cert_id) = &order.certificate_id {
+            body["certificate"] = json!(self.url(&format!("/cert/{}", cert_id)));
+        }
+        if let Some(error) = &order.error {
+            body["error"] = error.to_json();
+        }
+        body
+    }
+
+    fn challenge_json(&self, id: &str, challenge: &Challenge) -> Value {
+        let mut body = json!({
+            "type": challenge.kind.as_str(),
+            "url": self.url(&format!("/chall/{}", id)),
+            "status": challenge.status.as_str(),
+            "token": challenge.token,
+        });
+        if let Some(validated) = challenge.validated {
+            body["validated"] = json!(validated.to_rfc3339());
+        }
+        if let Some(error) = &challenge.error {
+            body["error"] = error.to_json();
+        }
+        body
+    }
+
+    fn authorization_json(&self, state: &AcmeState, authz: &Authorization) -> Value {
+        let challenges: Vec<Value> = authz
+            .challenge_ids
+            .iter()
+            .filter_map(|id| state.challenges.get(id).map(|c| self.challenge_json(id, c)))
+            .collect();
+        let mut body = json!({
+            "status": authz.status.as_str(),
+            "expires": authz.expires.to_rfc3339(),
+            "identifier": { "type": "dns", "value": authz.domain },
+            "challenges": challenges,
+        });
+        if authz.wildcard {
+            body["wildcard"] = json!(true);
+        }
+        body
+    }
+
+    async fn get_order(&self, signed: &SignedRequest, id: &str) -> Result<AcmeResponse, AcmeProblem> {
+        let account_id = signed.account_id()?;
+        let state = self.state.lock().await;
+        let order = state.orders.get(id).ok_or_else(|| AcmeProblem::not_found("order"))?;
+        if order.account_id != account_id {
+            return Err(AcmeProblem::unauthorized("Order belongs to another account"));
+        }
+        Ok(Self::json_response(200, self.order_json(id, order)))
+    }
+
+    async fn get_authorization(&self, signed: &SignedRequest, id: &str) -> Result<AcmeResponse, AcmeProblem> {
+        let account_id = signed.account_id()?;
+        let state = self.state.lock().await;
+        let authz = state.authorizations.get(id).ok_or_else(|| AcmeProblem::not_found("authorization"))?;
+        if authz.account_id != account_id {
+            return Err(AcmeProblem::unauthorized("Authorization belongs to another account"));
+        }
+        Ok(Self::json_response(200, self.authorization_json(&state, authz)))
+    }
+
+    // ------------------------------------------------------------------------
+    // Challenges
+    // ------------------------------------------------------------------------
+
+    /// Validate a challenge the client says is ready (RFC 8555 §7.5.1)
+    ///
+    /// Validation runs before the response is sent, so the returned
+    /// challenge is already `valid` or `invalid`.
+    async fn respond_to_challenge(&self, signed: &SignedRequest, id: &str) -> Result<AcmeResponse, AcmeProblem> {
+        let account_id = signed.account_id()?;
+        let (kind, token, domain, authorization_id, thumbprint) = {
+            let state = self.state.lock().await;
+            let challenge = state.challenges.get(id).ok_or_else(|| AcmeProblem::not_found("challenge"))?;
+            let authz = &state.authorizations[&challenge.authorization_id];
+            if authz.account_id != account_id {
+                return Err(AcmeProblem::unauthorized("Challenge belongs to another account"));
+            }
+            if challenge.status != Status::Pending || authz.status != Status::Pending {
+                return Ok(Self::json_response(200, self.challenge_json(id, challenge)));
+            }
+            (
+                challenge.kind,
+                challenge.token.clone(),
+                authz.domain.clone(),
+                challenge.authorization_id.clone(),
+                state.accounts[account_id].thumbprint.clone(),
+            )
+        };
+
+        // RFC 8555 §8.1
+        let key_authorization = format!("{}.{}", token, thumbprint);
+        let outcome = match kind {
+            ChallengeType::Http01 => self.validator.validate_http01(&domain, &token, &key_authorization).await,
+            ChallengeType::Dns01 => {
+                let txt = b64(&Sha256::digest(key_authorization.as_bytes()));
+                self.validator.validate_dns01(&domain, &txt).await
+            }
+        };
+
+        let mut state = self.state.lock().await;
+        let now = Utc::now();
+        let (status, error) = match outcome {
+            Ok(()) => (Status::Valid, None),
+            Err(detail) => (Status::Invalid, Some(AcmeProblem::new(AcmeErrorType::IncorrectResponse, 403, detail))),
+        };
+        let challenge = state.challenges.get_mut(id).expect("challenge checked above");
+        challenge.status = status;
+        challenge.validated = (status == Status::Valid).then_some(now);
+        challenge.error = error.clone();
+        let body = self.challenge_json(id, challenge);
+
+        // One valid challenge settles the authorization; a failed one invalidates it
+        if let Some(authz) = state.authorizations.get_mut(&authorization_id) {
+            authz.status = status;
+        }
+        let AcmeState { orders, authorizations, .. } = &mut *state;
+        for order in orders.values_mut().filter(|o| o.status == Status::Pending && o.authorization_ids.contains(&authorization_id)) {
+            if status == Status::Invalid {
+                order.status = Status::Invalid;
+                order.error = error.clone();
+            } else if order.authorization_ids.iter().all(|a| authorizations.get(a).is_some_and(|z| z.status == Status::Valid)) {
+                order.status = Status::Ready;
+            }
+        }
+
+        Ok(Self::json_response(200, body))
+    }
+
+    // ------------------------------------------------------------------------
+    // Finalization
+    // ------------------------------------------------------------------------
+
+    /// Check the CSR names against the order and issue from the intermediate CA
+    async fn finalize(&self, signed: SignedRequest, id: &str) -> Result<AcmeResponse, AcmeProblem> {
+        #[derive(Deserialize)]
+        struct Finalize {
+            csr: String,
+        }
+        let account_id = signed.account_id()?.to_string();
+        let payload: Finalize = signed.payload_json()?;
+
+        let identifiers = {
+            let mut state = self.state.lock().await;
+            let order = state.orders.get_mut(id).ok_or_else(|| AcmeProblem::not_found("order"))?;
+            if order.account_id != account_id {
+                return Err(AcmeProblem::unauthorized("Order belongs to another account"));
+            }
+            if order.status != Status::Ready {
+                return Err(AcmeProblem::new(
+                    AcmeErrorType::OrderNotReady,
+                    403,
+                    format!("Order is {}, not ready", order.status.as_str()),
+                ));
+            }
+            order.status = Status::Processing;
+            order.identifiers.clone()
+        };
+
+        let result = self.issue(&payload.csr, &identifiers, id);
+
+        let mut state = self.state.lock().await;
+        let order = state.orders.get_mut(id).expect("order checked above");
+        match result {
+            Ok(issued) => {
+                let cert_id = issued.generation_event.cert_id.simple().to_string();
+                order.status = Status::Valid;
+                order.certificate_id = Some(cert_id.clone());
+                let body = self.order_json(id, order);
+                state.certificates.insert(cert_id, (account_id, issued.chain_pem));
+                state.events.push(DomainEvent::Certificate(CertificateEvents::CertificateGenerated(issued.generation_event)));
+                state.events.push(DomainEvent::Certificate(CertificateEvents::CertificateSigned(issued.signing_event)));
+                Ok(Self::json_response(200, body).with_header("Location", self.url(&format!("/order/{}", id))))
+            }
+            Err(problem) => {
+                // A bad CSR can be corrected and resubmitted
+                order.status = Status::Ready;
+                Err(problem)
+            }
+        }
+    }
+
+    fn issue(&self, csr_b64: &str, identifiers: &[String], order_id: &str) -> Result<IssuedCertificate, AcmeProblem> {
+        let bad_csr = |detail: String| AcmeProblem::new(AcmeErrorType::BadCsr, 400, detail);
+        let der = unb64("csr", csr_b64)?;
+        let csr = CertificateSigningRequestParams::from_der(&CertificateSigningRequestDer::from(der.as_slice()))
+            .map_err(|e| bad_csr(format!("Invalid CSR: {}", e)))?;
+
+        let mut requested = Vec::new();
+        for san in &csr.params.subject_alt_names {
+            match san {
+                SanType::DnsName(name) => requested.push(name.as_str().to_ascii_lowercase()),
+                other => return Err(bad_csr(format!("CSR contains a non-DNS name {:?}", other))),
+            }
+        }
+        requested.sort();
+        requested.dedup();
+        let mut ordered = identifiers.to_vec();
+        ordered.sort();
+        if requested != ordered {
+            return Err(bad_csr(format!(
+                "CSR names [{}] do not match the order identifiers [{}]",
+                requested.join(", "),
+                ordered.join(", ")
+            )));
+        }
+
+        // The subject CN ends up in the certificate too, so it must be validated
+        let csr_pem = pem::encode(&pem::Pem::new("CERTIFICATE REQUEST", der));
+        let (common_name, _) = crate::crypto::x509::csr_constrained_names(&csr_pem).map_err(bad_csr)?;
+        if !ordered.contains(&common_name.to_ascii_lowercase()) {
+            return Err(bad_csr(format!("CSR common name {} is not an order identifier", common_name)));
+        }
+
+        let correlation_id = Uuid::parse_str(order_id).unwrap_or_else(|_| Uuid::now_v7());
+        issue_certificate(
+            &csr_pem,
+            LeafCertificateParams {
+                purpose: KeyPurpose::Authentication,
+                validity_days: self.config.validity_days,
+                key_id: Uuid::now_v7(),
+            },
+            &self.config.issuer_chain_pem,
+            &self.signer,
+            &self.config.constraints,
+            self.config.issuer_ca_id,
+            correlation_id,
+            None,
+        )
+        .map_err(bad_csr)
+    }
+
+    async fn get_certificate(&self, signed: &SignedRequest, id: &str) -> Result<AcmeResponse, AcmeProblem> {
+        let account_id = signed.account_id()?;
+        let state = self.state.lock().await;
+        let (owner, chain_pem) = state.certificates.get(id).ok_or_else(|| AcmeProblem::not_found("certificate"))?;
+        if owner != account_id {
+            return Err(AcmeProblem::unauthorized("Certificate belongs to another account"));
+        }
+        Ok(AcmeResponse {
+            status: 200,
+            headers: vec![("Content-Type".to_string(), "application/pem-certificate-chain".to_string())],
+            body: chain_pem.clone().into_bytes(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::seed_derivation::derive_master_seed;
+    use crate::crypto::x509::{create_intermediate_ca, generate_root_ca, IntermediateCAParams, RootCAParams};
+    use ring::signature::{EcdsaKeyPair, KeyPair as _, ECDSA_P256_SHA256_FIXED_SIGNING};
+
+    const BASE: &str = "https://ca.internal/acme";
+
+    fn test_server(constraints: Vec<PolicyConstraint>) -> AcmeServer<rcgen::KeyPair, StubChallengeValidator> {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_seed = master_seed.derive_child("root-ca");
+        let (root, _) = generate_root_ca(&root_seed, RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = rcgen::KeyPair::from_pem(&root.private_key_pem).unwrap();
+        let intermediate = create_intermediate_ca(
+            &root_seed.derive_child("intermediate-acme"),
+            IntermediateCAParams::default(),
+            &root.certificate_pem,
+            &root_key,
+            &[],
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let signer = rcgen::KeyPair::from_pem(&intermediate.certificate.private_key_pem).unwrap();
+
+        AcmeServer::new(
+            AcmeServerConfig {
+                base_url: BASE.to_string(),
+                issuer_ca_id: intermediate.generation_event.cert_id,
+                issuer_chain_pem: intermediate.chain_pem,
+                constraints,
+                validity_days: 30,
+            },
+            signer,
+            StubChallengeValidator::new(),
+        )
+    }
+
+    /// Minimal ACME client signing with ES256
+    struct Client {
+        key: EcdsaKeyPair,
+        kid: Option<String>,
+    }
+
+    impl Client {
+        fn new() -> Self {
+            let rng = ring::rand::SystemRandom::new();
+            let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+            let key = EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng).unwrap();
+            Self { key, kid: None }
+        }
+
+        fn jwk(&self) -> Value {
+            let point = self.key.public_key().as_ref();
+            json!({ "kty": "EC", "crv": "P-256", "x": b64(&point[1..33]), "y": b64(&point[33..]) })
+        }
+
+        fn thumbprint(&self) -> String {
+            serde_json::from_value::<Jwk>(self.jwk()).unwrap().thumbprint().unwrap()
+        }
+
+        async fn post<V: ChallengeValidator>(
+            &self,
+            server: &AcmeServer<rcgen::KeyPair, V>,
+            path: &str,
+            payload: Option<Value>,
+        ) -> AcmeResponse {
+            let nonce = server
+                .handle(AcmeRequest { method: AcmeMethod::Head, path: "/new-nonce".to_string(), body: vec![] })
+                .await
+                .header("Replay-Nonce")
+                .unwrap()
+                .to_string();
+            let mut protected = json!({ "alg": "ES256", "nonce": nonce, "url": format!("{}{}", BASE, path) });
+            match &self.kid {
+                Some(kid) => protected["kid"] = json!(kid),
+                None => protected["jwk"] = self.jwk(),
+            }
+            let protected = b64(protected.to_string().as_bytes());
+            let payload = payload.map(|p| b64(p.to_string().as_bytes())).unwrap_or_default();
+            let rng = ring::rand::SystemRandom::new();
+            let signature = self.key.sign(&rng, format!("{}.{}", protected, payload).as_bytes()).unwrap();
+            let body = json!({ "protected": protected, "payload": payload, "signature": b64(signature.as_ref()) });
+            server
+                .handle(AcmeRequest { method: AcmeMethod::Post, path: path.to_string(), body: body.to_string().into_bytes() })
+                .await
+        }
+
+        async fn register<V: ChallengeValidator>(&mut self, server: &AcmeServer<rcgen::KeyPair, V>) {
+            let response = self.post(server, "/new-account", Some(json!({ "termsOfServiceAgreed": true }))).await;
+            assert_eq!(response.status, 201);
+            self.kid = Some(response.header("Location").unwrap().to_string());
+        }
+    }
+
+    fn path_of(url: &str) -> &str {
+        url.strip_prefix(BASE).unwrap()
+    }
+
+    fn csr_der(names: &[&str]) -> String {
+        let key = rcgen::KeyPair::generate().unwrap();
+        let mut params = rcgen::CertificateParams::new(names.iter().map(|s| s.to_string()).collect::<Vec<_>>()).unwrap();
+        params.distinguished_name.push(rcgen::DnType::CommonName, names[0]);
+        b64(params.serialize_request(&key).unwrap().der())
+    }
+
+    #[tokio::test]
+    async fn test_http01_order_issues_certificate_from_intermediate() {
+        let server = test_server(vec![]);
+        let mut client = Client::new();
+        client.register(&server).await;
+
+        let order = client
+            .post(&server, "/new-order", Some(json!({ "identifiers": [{ "type": "dns", "value": "api.svc.internal" }] })))
+            .await;
+        assert_eq!(order.status, 201);
+        let order_url = order.header("Location").unwrap().to_string();
+        let order = order.json().unwrap();
+
+        let authz = client.post(&server, path_of(order["authorizations"][0].as_str().unwrap()), None).await.json().unwrap();
+        let challenge = authz["challenges"].as_array().unwrap().iter().find(|c| c["type"] == "http-01").unwrap();
+        let token = challenge["token"].as_str().unwrap();
+        server
+            .validator()
+            .publish_http01("api.svc.internal", token, &format!("{}.{}", token, client.thumbprint()));
+
+        let challenge = client.post(&server, path_of(challenge["url"].as_str().unwrap()), Some(json!({}))).await.json().unwrap();
+        assert_eq!(challenge["status"], "valid");
+        assert_eq!(client.post(&server, path_of(&order_url), None).await.json().unwrap()["status"], "ready");
+
+        // A CSR for other names is refused and the order stays ready
+        let wrong = client
+            .post(&server, path_of(order["finalize"].as_str().unwrap()), Some(json!({ "csr": csr_der(&["db.svc.internal"]) })))
+            .await;
+        assert_eq!(wrong.json().unwrap()["type"], "urn:ietf:params:acme:error:badCSR");
+
+        let finalized = client
+            .post(&server, path_of(order["finalize"].as_str().unwrap()), Some(json!({ "csr": csr_der(&["api.svc.internal"]) })))
+            .await
+            .json()
+            .unwrap();
+        assert_eq!(finalized["status"], "valid");
+
+        let certificate = client.post(&server, path_of(finalized["certificate"].as_str().unwrap()), None).await;
+        assert_eq!(certificate.header("Content-Type"), Some("application/pem-certificate-chain"));
+        assert_eq!(String::from_utf8(certificate.body).unwrap().matches("BEGIN CERTIFICATE").count(), 3);
+
+        let events = server.drain_events().await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], DomainEvent::Certificate(CertificateEvents::CertificateGenerated(e)) if e.issuer == Some(server.config.issuer_ca_id)));
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_uses_dns01_and_wrong_response_invalidates_order() {
+        let server = test_server(vec![]);
+        let mut client = Client::new();
+        client.register(&server).await;
+
+        let order = client
+            .post(&server, "/new-order", Some(json!({ "identifiers": [{ "type": "dns", "value": "*.svc.internal" }] })))
+            .await;
+        let order_url = order.header("Location").unwrap().to_string();
+        let authz_url = order.json().unwrap()["authorizations"][0].as_str().unwrap().to_string();
+        let authz = client.post(&server, path_of(&authz_url), None).await.json().unwrap();
+        assert_eq!(authz["wildcard"], true);
+        assert_eq!(authz["identifier"]["value"], "svc.internal");
+        let challenges = authz["challenges"].as_array().unwrap();
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(challenges[0]["type"], "dns-01");
+
+        server.validator().publish_dns01("svc.internal", "not-the-digest");
+        let challenge = client.post(&server, path_of(challenges[0]["url"].as_str().unwrap()), Some(json!({}))).await.json().unwrap();
+        assert_eq!(challenge["status"], "invalid");
+        assert_eq!(challenge["error"]["type"], "urn:ietf:params:acme:error:incorrectResponse");
+        assert_eq!(client.post(&server, path_of(&order_url), None).await.json().unwrap()["status"], "invalid");
+    }
+
+    #[tokio::test]
+    async fn test_rejects_replayed_nonce_and_out_of_policy_names() {
+        let server = test_server(vec![PolicyConstraint::NameConstraints {
+            permitted: vec!["svc.internal".to_string()],
+            excluded: vec![],
+        }]);
+        let mut client = Client::new();
+        client.register(&server).await;
+
+        let rejected = client
+            .post(&server, "/new-order", Some(json!({ "identifiers": [{ "type": "dns", "value": "example.com" }] })))
+            .await;
+        assert_eq!(rejected.status, 403);
+        assert_eq!(rejected.json().unwrap()["type"], "urn:ietf:params:acme:error:rejectedIdentifier");
+
+        // Replaying a request reuses its nonce
+        let replay = {
+            let nonce = server.new_nonce().await;
+            let protected = b64(json!({ "alg": "ES256", "nonce": nonce, "url": format!("{}/new-order", BASE), "kid": client.kid }).to_string().as_bytes());
+            let payload = b64(json!({ "identifiers": [{ "type": "dns", "value": "a.svc.internal" }] }).to_string().as_bytes());
+            let signature = client.key.sign(&ring::rand::SystemRandom::new(), format!("{}.{}", protected, payload).as_bytes()).unwrap();
+            json!({ "protected": protected, "payload": payload, "signature": b64(signature.as_ref()) }).to_string().into_bytes()
+        };
+        let request = AcmeRequest { method: AcmeMethod::Post, path: "/new-order".to_string(), body: replay };
+        assert_eq!(server.handle(request.clone()).await.status, 201);
+        let second = server.handle(request).await;
+        assert_eq!(second.json().unwrap()["type"], "urn:ietf:params:acme:error:badNonce");
+    }
+
+    #[test]
+    fn test_nonce_store_forgets_the_oldest_nonces() {
+        let mut nonces = NonceStore::default();
+        for i in 0..MAX_OUTSTANDING_NONCES + 10 {
+            nonces.insert(i.to_string());
+        }
+        assert_eq!(nonces.issued.len(), MAX_OUTSTANDING_NONCES);
+        assert_eq!(nonces.order.len(), MAX_OUTSTANDING_NONCES);
+
+        assert!(!nonces.take("0"));
+        assert!(nonces.take(&(MAX_OUTSTANDING_NONCES + 9).to_string()));
+        assert!(!nonces.take(&(MAX_OUTSTANDING_NONCES + 9).to_string()));
+    }
+}
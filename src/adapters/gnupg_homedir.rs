@@ -0,0 +1,540 @@
+//! GnuPG homedir import adapter
+//!
+//! Reads an existing GnuPG home directory so organizations migrating to
+//! cim-keys can ingest the keys they already have.
+//!
+//! ```text
+//! ~/.gnupg/
+//! ├── pubring.kbx            # Keybox: OpenPGP keyblocks + fingerprints + user IDs
+//! └── private-keys-v1.d/
+//!     └── {KEYGRIP}.key      # Secret key material (gpg-agent format)
+//! ```
+//!
+//! The keybox is parsed directly (no gpg binary needed on the air-gapped
+//! machine). Each OpenPGP blob yields its raw keyblock, fingerprints, user IDs
+//! and, for version 2 blobs, keygrips which are matched against
+//! `private-keys-v1.d` to tell whether secret material is present. Version 1
+//! blobs carry no keygrips; their certificates are reported as having an
+//! unknown secret status rather than as public-only.
+//!
+//! Keyblocks are imported through `GpgPort`, and every imported certificate
+//! produces a `KeyImported` event mapped to its owning Person by e-mail.
+//!
+//! **Category Theory Perspective:**
+//! - **Source Category**: GnuPG homedir (keybox blobs)
+//! - **Target Category**: Domain (imported key events)
+//! - **Functor**: GnupgHomedirAdapter maps keybox blobs to KeyImported events
+
+use chrono::Utc;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::domain::KeyOwnership;
+use crate::events::key::KeyImportedEvent;
+use crate::events::{DomainEvent, KeyEvents};
+use crate::ports::gpg::{GpgError, GpgKeyId, GpgPort};
+use crate::types::{ImportSource, KeyFormat, KeyMetadata};
+use crate::value_objects::ActorId;
+
+/// Keybox blob type for the file header
+const KBX_BLOB_TYPE_HEADER: u8 = 1;
+/// Keybox blob type for OpenPGP keyblocks
+const KBX_BLOB_TYPE_OPENPGP: u8 = 2;
+/// Key flag in version 2 blobs: a 32-byte (v5) fingerprint is in use
+const KBX_KEY_FLAG_FPR32: u16 = 0x0080;
+
+/// A certificate found in the GnuPG keybox
+#[derive(Debug, Clone)]
+pub struct GnupgCertificate {
+    /// Primary key fingerprint (uppercase hex)
+    pub fingerprint: String,
+    /// Subkey fingerprints (uppercase hex)
+    pub subkey_fingerprints: Vec<String>,
+    /// User IDs in keybox order
+    pub user_ids: Vec<String>,
+    /// Keygrips of primary and subkeys (version 2 blobs only)
+    pub keygrips: Vec<String>,
+    /// Whether secret material for any of the keys was found (always
+    /// false for version 1 blobs, see `GnupgHomedirScan::unknown_secret`)
+    pub has_secret: bool,
+    /// Raw OpenPGP keyblock (binary transferable public key)
+    pub keyblock: Vec<u8>,
+}
+
+impl GnupgCertificate {
+    /// First e-mail address found in the user IDs (lowercased)
+    pub fn primary_email(&self) -> Option<String> {
+        self.user_ids.iter().find_map(|uid| {
+            let start = uid.find('<')?;
+            let end = uid[start..].find('>')? + start;
+            Some(uid[start + 1..end].trim().to_lowercase())
+        })
+    }
+
+    /// 64-bit key ID of the primary key
+    pub fn key_id(&self) -> &str {
+        let len = self.fingerprint.len();
+        &self.fingerprint[len.saturating_sub(16)..]
+    }
+}
+
+/// Result of scanning a GnuPG homedir
+#[derive(Debug, Clone, Default)]
+pub struct GnupgHomedirScan {
+    pub homedir: PathBuf,
+    pub certificates: Vec<GnupgCertificate>,
+    /// Keygrips found in private-keys-v1.d
+    pub secret_keygrips: Vec<String>,
+    /// Fingerprints of certificates from version 1 blobs, which carry no
+    /// keygrips; whether their secret keys are present is unknown
+    pub unknown_secret: Vec<String>,
+}
+
+/// Adapter reading an existing GnuPG home directory
+#[derive(Debug, Clone)]
+pub struct GnupgHomedirAdapter {
+    homedir: PathBuf,
+}
+
+impl GnupgHomedirAdapter {
+    /// Create an adapter for the given homedir (e.g. `~/.gnupg`)
+    pub fn new(homedir: impl Into<PathBuf>) -> Self {
+        Self {
+            homedir: homedir.into(),
+        }
+    }
+
+    /// Path of the homedir being read
+    pub fn homedir(&self) -> &Path {
+        &self.homedir
+    }
+
+    /// Scan the homedir for certificates and secret keys
+    pub fn scan(&self) -> Result<GnupgHomedirScan, GpgError> {
+        let pubring = self.homedir.join("pubring.kbx");
+        if !pubring.exists() {
+            if self.homedir.join("pubring.gpg").exists() {
+                return Err(GpgError::ImportFailed(
+                    "Legacy pubring.gpg found; run `gpg --list-keys` with GnuPG 2.1+ to migrate to pubring.kbx".to_string(),
+                ));
+            }
+            return Err(GpgError::ImportFailed(format!(
+                "No pubring.kbx in {}",
+                self.homedir.display()
+            )));
+        }
+
+        let data = std::fs::read(&pubring)
+            .map_err(|e| GpgError::ImportFailed(format!("Failed to read {}: {}", pubring.display(), e)))?;
+
+        let secret_keygrips = self.read_secret_keygrips()?;
+        let mut certificates = parse_keybox(&data)?;
+        let mut unknown_secret = Vec::new();
+        for cert in &mut certificates {
+            if cert.keygrips.is_empty() {
+                unknown_secret.push(cert.fingerprint.clone());
+                continue;
+            }
+            cert.has_secret = cert
+                .keygrips
+                .iter()
+                .any(|grip| secret_keygrips.contains(grip));
+        }
+
+        Ok(GnupgHomedirScan {
+            homedir: self.homedir.clone(),
+            certificates,
+            secret_keygrips,
+            unknown_secret,
+        })
+    }
+
+    fn read_secret_keygrips(&self) -> Result<Vec<String>, GpgError> {
+        let dir = self.homedir.join("private-keys-v1.d");
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let entries = std::fs::read_dir(&dir)
+            .map_err(|e| GpgError::ImportFailed(format!("Failed to read {}: {}", dir.display(), e)))?;
+
+        let mut keygrips: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.strip_suffix(".key").map(|grip| grip.to_uppercase())
+            })
+            .collect();
+        keygrips.sort();
+
+        Ok(keygrips)
+    }
+}
+
+/// Parse a keybox file into certificates
+pub fn parse_keybox(data: &[u8]) -> Result<Vec<GnupgCertificate>, GpgError> {
+    let mut certificates = Vec::new();
+    let mut offset = 0usize;
+
+    while offset < data.len() {
+        let blob_len = read_u32(data, offset)? as usize;
+        if blob_len < 5 || offset + blob_len > data.len() {
+            return Err(GpgError::ImportFailed(format!(
+                "Invalid keybox blob length {} at offset {}",
+                blob_len, offset
+            )));
+        }
+        let blob = &data[offset..offset + blob_len];
+
+        match blob[4] {
+            KBX_BLOB_TYPE_OPENPGP => certificates.push(parse_openpgp_blob(blob)?),
+            KBX_BLOB_TYPE_HEADER => {}
+            _ => {} // X.509 and unknown blobs are not GPG keys
+        }
+
+        offset += blob_len;
+    }
+
+    Ok(certificates)
+}
+
+fn parse_openpgp_blob(blob: &[u8]) -> Result<GnupgCertificate, GpgError> {
+    let version = read_u8(blob, 5)?;
+    let keyblock_offset = read_u32(blob, 8)? as usize;
+    let keyblock_len = read_u32(blob, 12)? as usize;
+    let nkeys = read_u16(blob, 16)? as usize;
+    let keyinfo_size = read_u16(blob, 18)? as usize;
+
+    if nkeys == 0 {
+        return Err(GpgError::ImportFailed("Keybox blob without keys".to_string()));
+    }
+
+    let mut fingerprints = Vec::with_capacity(nkeys);
+    let mut keygrips = Vec::new();
+    let mut pos = 20usize;
+
+    for _ in 0..nkeys {
+        let info = slice(blob, pos, keyinfo_size)?;
+        match version {
+            1 => fingerprints.push(hex::encode_upper(slice(info, 0, 20)?)),
+            _ => {
+                let flags = read_u16(info, 32)?;
+                let fpr_len = if flags & KBX_KEY_FLAG_FPR32 != 0 { 32 } else { 20 };
+                fingerprints.push(hex::encode_upper(slice(info, 0, fpr_len)?));
+                keygrips.push(hex::encode_upper(slice(info, 36, 20)?));
+            }
+        }
+        pos += keyinfo_size;
+    }
+
+    // Serial number (skipped)
+    let serial_len = read_u16(blob, pos)? as usize;
+    pos += 2 + serial_len;
+
+    let nuids = read_u16(blob, pos)? as usize;
+    let uidinfo_size = read_u16(blob, pos + 2)? as usize;
+    pos += 4;
+
+    let mut user_ids = Vec::with_capacity(nuids);
+    for _ in 0..nuids {
+        let uid_offset = read_u32(blob, pos)? as usize;
+        let uid_len = read_u32(blob, pos + 4)? as usize;
+        user_ids.push(String::from_utf8_lossy(slice(blob, uid_offset, uid_len)?).to_string());
+        pos += uidinfo_size;
+    }
+
+    let keyblock = slice(blob, keyblock_offset, keyblock_len)?.to_vec();
+    let fingerprint = fingerprints.remove(0);
+
+    Ok(GnupgCertificate {
+        fingerprint,
+        subkey_fingerprints: fingerprints,
+        user_ids,
+        keygrips,
+        has_secret: false,
+        keyblock,
+    })
+}
+
+fn slice(data: &[u8], offset: usize, len: usize) -> Result<&[u8], GpgError> {
+    data.get(offset..offset + len)
+        .ok_or_else(|| GpgError::ImportFailed(format!("Truncated keybox blob at offset {}", offset)))
+}
+
+fn read_u8(data: &[u8], offset: usize) -> Result<u8, GpgError> {
+    Ok(slice(data, offset, 1)?[0])
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, GpgError> {
+    let bytes = slice(data, offset, 2)?;
+    Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, GpgError> {
+    let bytes = slice(data, offset, 4)?;
+    Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// A certificate imported from the homedir
+#[derive(Debug, Clone)]
+pub struct ImportedGnupgKey {
+    pub key_id: Uuid,
+    pub gpg_key_id: GpgKeyId,
+    pub fingerprint: String,
+    pub owner: Option<KeyOwnership>,
+}
+
+/// Outcome of importing a homedir
+#[derive(Debug, Clone)]
+pub struct GnupgImportResult {
+    pub imported: Vec<ImportedGnupgKey>,
+    /// Fingerprints of certificates with no matching Person
+    pub unmapped: Vec<String>,
+    /// Fingerprints of certificates whose secret keys could not be checked
+    /// (version 1 blobs); their secret keys need a separate export
+    pub unknown_secret: Vec<String>,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Import every certificate of a scanned homedir through `GpgPort`
+///
+/// `owners` maps lowercased e-mail addresses to the Person owning the key.
+/// Certificates without a matching e-mail are still imported, but are
+/// reported in `unmapped` so an operator can assign them. Certificates of
+/// unknown secret status are recorded with `has_secret = "unknown"` and
+/// reported in `unknown_secret`.
+///
+/// Emits:
+/// - KeyImportedEvent (one per certificate)
+pub async fn import_homedir(
+    port: &dyn GpgPort,
+    scan: &GnupgHomedirScan,
+    owners: &HashMap<String, KeyOwnership>,
+    imported_by: ActorId,
+    correlation_id: Uuid,
+) -> Result<GnupgImportResult, GpgError> {
+    let source_path = scan.homedir.join("pubring.kbx").to_string_lossy().to_string();
+    let mut imported = Vec::new();
+    let mut unmapped = Vec::new();
+    let mut unknown_secret = Vec::new();
+    let mut events = Vec::new();
+
+    for cert in &scan.certificates {
+        let gpg_key_id = port.import_key(&cert.keyblock).await?;
+        let owner = cert
+            .primary_email()
+            .and_then(|email| owners.get(&email).cloned());
+        if owner.is_none() {
+            unmapped.push(cert.fingerprint.clone());
+        }
+
+        let mut attributes = HashMap::new();
+        attributes.insert("gpg_fingerprint".to_string(), cert.fingerprint.clone());
+        attributes.insert("gpg_key_id".to_string(), gpg_key_id.0.clone());
+        let has_secret = if scan.unknown_secret.contains(&cert.fingerprint) {
+            unknown_secret.push(cert.fingerprint.clone());
+            "unknown".to_string()
+        } else {
+            cert.has_secret.to_string()
+        };
+        attributes.insert("has_secret".to_string(), has_secret);
+
+        let key_id = Uuid::now_v7();
+        events.push(DomainEvent::Key(KeyEvents::KeyImported(KeyImportedEvent {
+            key_id,
+            source: ImportSource::File {
+                path: source_path.clone(),
+            },
+            format: KeyFormat::GpgBinary,
+            imported_at: Utc::now(),
+            imported_by: imported_by.clone(),
+            metadata: KeyMetadata {
+                label: cert
+                    .user_ids
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| cert.key_id().to_string()),
+                description: Some("Imported from GnuPG homedir".to_string()),
+                tags: vec!["gpg".to_string(), "imported".to_string()],
+                attributes,
+                jwt_kid: None,
+                jwt_alg: None,
+                jwt_use: None,
+                hardware_serial: None,
+                tpm: None,
+            },
+            ownership: owner.clone(),
+            correlation_id,
+            causation_id: None,
+        })));
+
+        imported.push(ImportedGnupgKey {
+            key_id,
+            gpg_key_id,
+            fingerprint: cert.fingerprint.clone(),
+            owner,
+        });
+    }
+
+    Ok(GnupgImportResult {
+        imported,
+        unmapped,
+        unknown_secret,
+        events,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::MockGpgAdapter;
+    use crate::domain::KeyOwnerRole;
+
+    /// Build a keybox OpenPGP blob (version 1 blobs drop the keygrip)
+    fn openpgp_blob(version: u8, fingerprint: [u8; 20], keygrip: [u8; 20], uid: &str, keyblock: &[u8]) -> Vec<u8> {
+        let keyinfo_size = if version == 1 { 28u16 } else { 56u16 };
+        let header_len = 20 + keyinfo_size as usize + 2 + 4 + 12;
+        let uid_offset = header_len;
+        let keyblock_offset = uid_offset + uid.len();
+        let total = keyblock_offset + keyblock.len();
+
+        let mut blob = Vec::new();
+        blob.extend_from_slice(&(total as u32).to_be_bytes());
+        blob.push(KBX_BLOB_TYPE_OPENPGP);
+        blob.push(version);
+        blob.extend_from_slice(&0u16.to_be_bytes()); // flags
+        blob.extend_from_slice(&(keyblock_offset as u32).to_be_bytes());
+        blob.extend_from_slice(&(keyblock.len() as u32).to_be_bytes());
+        blob.extend_from_slice(&1u16.to_be_bytes()); // nkeys
+        blob.extend_from_slice(&keyinfo_size.to_be_bytes());
+        if version == 1 {
+            // key info: b20 fpr, u32 keyid offset, u16 flags, u16 rfu
+            blob.extend_from_slice(&fingerprint);
+            blob.extend_from_slice(&0u32.to_be_bytes());
+            blob.extend_from_slice(&0u16.to_be_bytes());
+            blob.extend_from_slice(&0u16.to_be_bytes());
+        } else {
+            // key info: b32 fpr, u16 flags, u16 rfu, b20 keygrip
+            blob.extend_from_slice(&fingerprint);
+            blob.extend_from_slice(&[0u8; 12]);
+            blob.extend_from_slice(&0u16.to_be_bytes());
+            blob.extend_from_slice(&0u16.to_be_bytes());
+            blob.extend_from_slice(&keygrip);
+        }
+        blob.extend_from_slice(&0u16.to_be_bytes()); // serial len
+        blob.extend_from_slice(&1u16.to_be_bytes()); // nuids
+        blob.extend_from_slice(&12u16.to_be_bytes()); // uid info size
+        blob.extend_from_slice(&(uid_offset as u32).to_be_bytes());
+        blob.extend_from_slice(&(uid.len() as u32).to_be_bytes());
+        blob.extend_from_slice(&[0u8; 4]);
+        blob.extend_from_slice(uid.as_bytes());
+        blob.extend_from_slice(keyblock);
+        assert_eq!(blob.len(), total);
+        blob
+    }
+
+    fn header_blob() -> Vec<u8> {
+        let mut blob = Vec::new();
+        blob.extend_from_slice(&32u32.to_be_bytes());
+        blob.push(KBX_BLOB_TYPE_HEADER);
+        blob.push(1);
+        blob.extend_from_slice(&[0u8; 2]);
+        blob.extend_from_slice(b"KBXf");
+        blob.extend_from_slice(&[0u8; 20]);
+        blob
+    }
+
+    fn write_homedir(dir: &Path) {
+        let mut kbx = header_blob();
+        kbx.extend(openpgp_blob(2, [0xAA; 20], [0x11; 20], "Alice <Alice@Example.com>", &[0x99, 0x00, 0x33]));
+        kbx.extend(openpgp_blob(2, [0xBB; 20], [0x22; 20], "Bob <bob@example.com>", &[0x99, 0x00, 0x33]));
+        std::fs::write(dir.join("pubring.kbx"), kbx).unwrap();
+
+        let private = dir.join("private-keys-v1.d");
+        std::fs::create_dir_all(&private).unwrap();
+        std::fs::write(private.join(format!("{}.key", hex::encode_upper([0x11u8; 20]))), b"secret").unwrap();
+    }
+
+    #[test]
+    fn test_scan_reads_certificates_and_secret_presence() {
+        let dir = tempfile::tempdir().unwrap();
+        write_homedir(dir.path());
+
+        let scan = GnupgHomedirAdapter::new(dir.path()).scan().unwrap();
+        assert_eq!(scan.certificates.len(), 2);
+
+        let alice = &scan.certificates[0];
+        assert_eq!(alice.fingerprint, "AA".repeat(20));
+        assert_eq!(alice.primary_email().as_deref(), Some("alice@example.com"));
+        assert!(alice.has_secret);
+        assert!(!scan.certificates[1].has_secret);
+        assert!(scan.unknown_secret.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_version_1_blobs_report_unknown_secret_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut kbx = header_blob();
+        kbx.extend(openpgp_blob(1, [0xCC; 20], [0; 20], "Carol <carol@example.com>", &[0x99, 0x00, 0x33]));
+        std::fs::write(dir.path().join("pubring.kbx"), kbx).unwrap();
+
+        let scan = GnupgHomedirAdapter::new(dir.path()).scan().unwrap();
+        assert_eq!(scan.certificates[0].fingerprint, "CC".repeat(20));
+        assert_eq!(scan.unknown_secret, vec!["CC".repeat(20)]);
+
+        let port = MockGpgAdapter::new();
+        let result = import_homedir(&port, &scan, &HashMap::new(), ActorId::system("gnupg-import"), Uuid::now_v7())
+            .await
+            .unwrap();
+        assert_eq!(result.unknown_secret, vec!["CC".repeat(20)]);
+        match &result.events[0] {
+            DomainEvent::Key(KeyEvents::KeyImported(evt)) => {
+                assert_eq!(evt.metadata.attributes.get("has_secret").map(String::as_str), Some("unknown"));
+            }
+            _ => panic!("Expected KeyImported event"),
+        }
+    }
+
+    #[test]
+    fn test_scan_without_keybox_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(GnupgHomedirAdapter::new(dir.path()).scan().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_maps_owners_by_email() {
+        let dir = tempfile::tempdir().unwrap();
+        write_homedir(dir.path());
+        let scan = GnupgHomedirAdapter::new(dir.path()).scan().unwrap();
+
+        let alice_id = Uuid::now_v7();
+        let mut owners = HashMap::new();
+        owners.insert(
+            "alice@example.com".to_string(),
+            KeyOwnership {
+                person_id: alice_id,
+                organization_id: Uuid::now_v7(),
+                role: KeyOwnerRole::Developer,
+                delegations: vec![],
+            },
+        );
+
+        let port = MockGpgAdapter::new();
+        let result = import_homedir(&port, &scan, &owners, ActorId::system("gnupg-import"), Uuid::now_v7())
+            .await
+            .unwrap();
+
+        assert_eq!(result.imported.len(), 2);
+        assert_eq!(result.events.len(), 2);
+        assert_eq!(result.unmapped, vec!["BB".repeat(20)]);
+
+        match &result.events[0] {
+            DomainEvent::Key(KeyEvents::KeyImported(evt)) => {
+                assert_eq!(evt.ownership.as_ref().map(|o| o.person_id), Some(alice_id));
+                assert!(matches!(evt.format, KeyFormat::GpgBinary));
+            }
+            _ => panic!("Expected KeyImported event"),
+        }
+    }
+}
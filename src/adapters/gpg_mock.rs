@@ -15,8 +15,19 @@ use chrono::{Duration, Utc};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+use crate::crypto::SecretBytes;
 use crate::ports::gpg::*;
 use crate::ports::yubikey::SecureString;
+use crate::types::OpenPgpCardSlot;
+
+/// Marker prefix for mock passphrase-protected secret key exports
+const MOCK_TSK_MAGIC: &[u8] = b"MOCK-TSK-S2K\0";
+
+/// Marker prefix for secret keys diverted to a card (GnuPG S2K mode 1002)
+const MOCK_CARD_STUB_MAGIC: &[u8] = b"GNU-DIVERT-TO-CARD\0";
+
+/// Marker prefix for mock password-based (SKESK) messages
+const MOCK_SKESK_MAGIC: &[u8] = b"MOCK-SKESK\0";
 
 /// Mock GPG adapter for testing
 ///
@@ -68,6 +79,34 @@ impl MockGpgAdapter {
         GpgKeyId(id)
     }
 
+    /// Short passphrase verifier stored alongside a mock secret key export
+    fn passphrase_check(passphrase: &[u8]) -> [u8; 8] {
+        use sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(passphrase);
+        let mut check = [0u8; 8];
+        check.copy_from_slice(&digest[..8]);
+        check
+    }
+
+    /// Passphrase-derived keystream (mock S2K + cipher)
+    fn symmetric_keystream(passphrase: &[u8], salt: &[u8], len: usize) -> Vec<u8> {
+        use sha2::{Digest, Sha256};
+
+        let mut stream = Vec::with_capacity(len + 32);
+        let mut counter = 0u32;
+        while stream.len() < len {
+            let mut hasher = Sha256::new();
+            hasher.update(passphrase);
+            hasher.update(salt);
+            hasher.update(counter.to_be_bytes());
+            stream.extend_from_slice(&hasher.finalize());
+            counter += 1;
+        }
+        stream.truncate(len);
+        stream
+    }
+
     fn generate_mock_keypair(&self, user_id: &str, key_type: GpgKeyType) -> GpgKeypair {
         let key_id = self.next_key_id();
 
@@ -98,7 +137,7 @@ impl MockGpgAdapter {
         GpgKeypair {
             key_id: key_id.clone(),
             public_key,
-            private_key,
+            private_key: private_key.into(),
             fingerprint,
             user_id: user_id.to_string(),
         }
@@ -203,7 +242,7 @@ impl GpgPort for MockGpgAdapter {
         let keypair = GpgKeypair {
             key_id: key_id.clone(),
             public_key: key_data.to_vec(),
-            private_key: Vec::new(), // Public key only
+            private_key: SecretBytes::default(), // Public key only
             fingerprint: format!("{:040X}", 0),
             user_id: "Imported Key".to_string(),
         };
@@ -252,17 +291,41 @@ impl GpgPort for MockGpgAdapter {
         key_id: &GpgKeyId,
         passphrase: &SecureString,
     ) -> Result<Vec<u8>, GpgError> {
+        if passphrase.as_bytes().is_empty() {
+            return Err(GpgError::InvalidPassphrase);
+        }
+
         let keys = self.keys.read().unwrap();
         let keypair = keys
             .get(key_id)
             .ok_or_else(|| GpgError::KeyNotFound(key_id.0.clone()))?;
 
-        // Mock encryption with passphrase (just XOR for testing)
-        let mut encrypted = keypair.private_key.clone();
-        let pass_bytes = passphrase.as_bytes();
-        for (i, byte) in encrypted.iter_mut().enumerate() {
-            *byte ^= pass_bytes[i % pass_bytes.len()];
+        if keypair.private_key.is_empty() {
+            return Err(GpgError::ExportFailed(format!(
+                "Key {} has no secret key material",
+                key_id.0
+            )));
         }
+        if keypair.private_key.starts_with(MOCK_CARD_STUB_MAGIC) {
+            return Err(GpgError::ExportFailed(format!(
+                "Secret key {} lives on a card and cannot be exported",
+                key_id.0
+            )));
+        }
+
+        // Mock S2K envelope: magic || passphrase check || key id || XOR'd secret
+        let pass_bytes = passphrase.as_bytes();
+        let mut exported = MOCK_TSK_MAGIC.to_vec();
+        exported.extend_from_slice(&Self::passphrase_check(pass_bytes));
+        exported.push(key_id.0.len() as u8);
+        exported.extend_from_slice(key_id.0.as_bytes());
+        exported.extend(
+            keypair
+                .private_key
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| byte ^ pass_bytes[i % pass_bytes.len()]),
+        );
 
         // Store passphrase for later verification
         self.passphrases.write().unwrap().insert(
@@ -270,7 +333,74 @@ impl GpgPort for MockGpgAdapter {
             String::from_utf8_lossy(pass_bytes).to_string(),
         );
 
-        Ok(encrypted)
+        Ok(exported)
+    }
+
+    /// **Functor Mapping**: (encrypted TSK, passphrase) → KeyId
+    async fn import_private_key(
+        &self,
+        key_data: &[u8],
+        passphrase: &SecureString,
+    ) -> Result<GpgKeyId, GpgError> {
+        let pass_bytes = passphrase.as_bytes();
+        if pass_bytes.is_empty() {
+            return Err(GpgError::InvalidPassphrase);
+        }
+
+        let body = key_data
+            .strip_prefix(MOCK_TSK_MAGIC)
+            .ok_or_else(|| GpgError::ImportFailed("Not a passphrase-protected secret key".to_string()))?;
+
+        if body.len() < 9 {
+            return Err(GpgError::ImportFailed("Truncated secret key".to_string()));
+        }
+        let (check, rest) = body.split_at(8);
+        if check != Self::passphrase_check(pass_bytes) {
+            return Err(GpgError::InvalidPassphrase);
+        }
+
+        let id_len = rest[0] as usize;
+        if rest.len() < 1 + id_len {
+            return Err(GpgError::ImportFailed("Truncated secret key".to_string()));
+        }
+        let key_id = GpgKeyId(String::from_utf8_lossy(&rest[1..1 + id_len]).to_string());
+        let private_key = SecretBytes::new(
+            rest[1 + id_len..]
+                .iter()
+                .enumerate()
+                .map(|(i, byte)| byte ^ pass_bytes[i % pass_bytes.len()])
+                .collect(),
+        );
+
+        let mut keys = self.keys.write().unwrap();
+        if let Some(existing) = keys.get_mut(&key_id) {
+            existing.private_key = private_key;
+            return Ok(key_id);
+        }
+
+        let keypair = GpgKeypair {
+            key_id: key_id.clone(),
+            public_key: vec![0x99, 0x00, 0x40],
+            private_key,
+            fingerprint: format!("{:040X}", 0),
+            user_id: "Imported Key".to_string(),
+        };
+        keys.insert(key_id.clone(), keypair);
+
+        self.key_info.write().unwrap().insert(
+            key_id.clone(),
+            GpgKeyInfo {
+                key_id: key_id.clone(),
+                fingerprint: format!("{:040X}", 0),
+                user_ids: vec!["Imported Key".to_string()],
+                creation_time: Utc::now().timestamp(),
+                expiration_time: None,
+                is_revoked: false,
+                is_expired: false,
+            },
+        );
+
+        Ok(key_id)
     }
 
     /// **Functor Mapping**: (key, data) → Signature
@@ -366,6 +496,61 @@ impl GpgPort for MockGpgAdapter {
         Ok(self.mock_decrypt(encrypted_data))
     }
 
+    /// **Functor Mapping**: (passphrase, data) → CiphertextMessage
+    async fn encrypt_symmetric(
+        &self,
+        data: &[u8],
+        passphrase: &SecureString,
+    ) -> Result<Vec<u8>, GpgError> {
+        let pass_bytes = passphrase.as_bytes();
+        if pass_bytes.is_empty() {
+            return Err(GpgError::InvalidPassphrase);
+        }
+
+        // Format: magic || salt(8) || check(8) || data ^ keystream
+        let salt: [u8; 8] = rand::random();
+        let mut salted = salt.to_vec();
+        salted.extend_from_slice(pass_bytes);
+        let keystream = Self::symmetric_keystream(pass_bytes, &salt, data.len());
+
+        let mut encrypted = MOCK_SKESK_MAGIC.to_vec();
+        encrypted.extend_from_slice(&salt);
+        encrypted.extend_from_slice(&Self::passphrase_check(&salted));
+        encrypted.extend(data.iter().zip(keystream).map(|(byte, k)| byte ^ k));
+
+        Ok(encrypted)
+    }
+
+    /// **Functor Mapping**: (passphrase, ciphertext) → PlaintextMessage
+    async fn decrypt_symmetric(
+        &self,
+        encrypted_data: &[u8],
+        passphrase: &SecureString,
+    ) -> Result<Vec<u8>, GpgError> {
+        let pass_bytes = passphrase.as_bytes();
+        if pass_bytes.is_empty() {
+            return Err(GpgError::InvalidPassphrase);
+        }
+
+        let body = encrypted_data
+            .strip_prefix(MOCK_SKESK_MAGIC)
+            .ok_or_else(|| GpgError::DecryptionFailed("Not a password-encrypted message".to_string()))?;
+        if body.len() < 16 {
+            return Err(GpgError::DecryptionFailed("Truncated message".to_string()));
+        }
+
+        let (salt, rest) = body.split_at(8);
+        let (check, ciphertext) = rest.split_at(8);
+        let mut salted = salt.to_vec();
+        salted.extend_from_slice(pass_bytes);
+        if check != Self::passphrase_check(&salted) {
+            return Err(GpgError::InvalidPassphrase);
+        }
+
+        let keystream = Self::symmetric_keystream(pass_bytes, salt, ciphertext.len());
+        Ok(ciphertext.iter().zip(keystream).map(|(byte, k)| byte ^ k).collect())
+    }
+
     async fn list_keys(&self, secret: bool) -> Result<Vec<GpgKeyInfo>, GpgError> {
         let info_map = self.key_info.read().unwrap();
         let keys = self.keys.read().unwrap();
@@ -387,6 +572,43 @@ impl GpgPort for MockGpgAdapter {
         Ok(result)
     }
 
+    /// **Functor Mapping**: (key, expiration) → UpdatedCertificate
+    async fn set_expiration(
+        &self,
+        key_id: &GpgKeyId,
+        expires_in_days: Option<u32>,
+    ) -> Result<Vec<u8>, GpgError> {
+        let keys = self.keys.read().unwrap();
+        let keypair = keys
+            .get(key_id)
+            .ok_or_else(|| GpgError::KeyNotFound(key_id.0.clone()))?;
+        if keypair.private_key.is_empty() {
+            return Err(GpgError::SignatureFailed(format!(
+                "No secret key to re-sign bindings of {}",
+                key_id.0
+            )));
+        }
+
+        let mut info_map = self.key_info.write().unwrap();
+        let info = info_map
+            .get_mut(key_id)
+            .ok_or_else(|| GpgError::KeyNotFound(key_id.0.clone()))?;
+        if info.is_revoked {
+            return Err(GpgError::InvalidKey(format!("Key {} is revoked", key_id.0)));
+        }
+
+        info.expiration_time =
+            expires_in_days.map(|days| (Utc::now() + Duration::days(days as i64)).timestamp());
+        info.is_expired = false;
+
+        // Mock certificate: public key || binding signature (0x88 tag)
+        let mut certificate = keypair.public_key.clone();
+        certificate.extend_from_slice(&[0x88, 0x00, 0x40]);
+        certificate.extend_from_slice(&info.expiration_time.unwrap_or(0).to_be_bytes());
+
+        Ok(certificate)
+    }
+
     async fn revoke_key(
         &self,
         key_id: &GpgKeyId,
@@ -403,6 +625,98 @@ impl GpgPort for MockGpgAdapter {
         Ok(vec![0x88, 0x00, 0x20, reason as u8])
     }
 
+    /// **Functor Mapping**: (key, card slot) → CardStub
+    async fn move_to_card(
+        &self,
+        key_id: &GpgKeyId,
+        slot: OpenPgpCardSlot,
+        card_serial: &str,
+        admin_pin: &SecureString,
+    ) -> Result<GpgCardStub, GpgError> {
+        // OpenPGP card admin PINs are at least 8 characters
+        if admin_pin.as_bytes().len() < 8 {
+            return Err(GpgError::InvalidPassphrase);
+        }
+        if card_serial.trim().is_empty() {
+            return Err(GpgError::OperationError("Card serial is required".to_string()));
+        }
+
+        let mut keys = self.keys.write().unwrap();
+        let keypair = keys
+            .get_mut(key_id)
+            .ok_or_else(|| GpgError::KeyNotFound(key_id.0.clone()))?;
+
+        if keypair.private_key.is_empty() {
+            return Err(GpgError::InvalidKey(format!(
+                "Key {} has no secret key material",
+                key_id.0
+            )));
+        }
+        if keypair.private_key.starts_with(MOCK_CARD_STUB_MAGIC) {
+            return Err(GpgError::InvalidKey(format!(
+                "Key {} is already on a card",
+                key_id.0
+            )));
+        }
+
+        // Replace the secret key with a divert-to-card stub
+        let mut stub = MOCK_CARD_STUB_MAGIC.to_vec();
+        stub.extend_from_slice(card_serial.as_bytes());
+        keypair.private_key = stub.into();
+
+        Ok(GpgCardStub {
+            key_id: key_id.clone(),
+            slot,
+            card_serial: card_serial.to_string(),
+        })
+    }
+
+    /// **Functor Mapping**: (certifier, target, user ID) → CertificationSignature
+    async fn certify_key(
+        &self,
+        certifier_key_id: &GpgKeyId,
+        target_key_id: &GpgKeyId,
+        user_id: &str,
+        trust: Option<&GpgTrustSignature>,
+    ) -> Result<Vec<u8>, GpgError> {
+        if certifier_key_id == target_key_id {
+            return Err(GpgError::SignatureFailed("A key cannot certify itself".to_string()));
+        }
+
+        let keys = self.keys.read().unwrap();
+        let certifier = keys
+            .get(certifier_key_id)
+            .ok_or_else(|| GpgError::KeyNotFound(certifier_key_id.0.clone()))?;
+        if certifier.private_key.is_empty() {
+            return Err(GpgError::SignatureFailed(format!(
+                "No secret key for certifier {}",
+                certifier_key_id.0
+            )));
+        }
+        if !keys.contains_key(target_key_id) {
+            return Err(GpgError::KeyNotFound(target_key_id.0.clone()));
+        }
+
+        let info = self.key_info.read().unwrap();
+        if let Some(target_info) = info.get(target_key_id) {
+            if !target_info.user_ids.iter().any(|uid| uid == user_id) {
+                return Err(GpgError::InvalidKey(format!(
+                    "User ID {} not found on {}",
+                    user_id, target_key_id.0
+                )));
+            }
+        }
+
+        // Mock signature: tag || positive certification (0x13) || [depth, amount]
+        let mut signature = vec![0x88, 0x13];
+        if let Some(trust) = trust {
+            signature.extend_from_slice(&[trust.depth, trust.amount]);
+        }
+        signature.extend_from_slice(certifier_key_id.0.as_bytes());
+
+        Ok(signature)
+    }
+
     async fn add_subkey(
         &self,
         master_key_id: &GpgKeyId,
@@ -517,4 +831,189 @@ mod tests {
         assert_eq!(secret_keys.len(), 1);
         assert_eq!(secret_keys[0].key_id, full_keypair.key_id);
     }
+
+    #[tokio::test]
+    async fn test_secret_key_export_import_roundtrip() {
+        let adapter = MockGpgAdapter::new();
+
+        let keypair = adapter
+            .generate_keypair("secret@example.com", GpgKeyType::Eddsa, 256, None)
+            .await
+            .unwrap();
+
+        let passphrase = SecureString::new("correct horse battery staple");
+        let exported = adapter
+            .export_private_key(&keypair.key_id, &passphrase)
+            .await
+            .unwrap();
+
+        // Secret material must not appear in the clear
+        assert!(!exported.ends_with(&keypair.private_key));
+
+        let fresh = MockGpgAdapter::new();
+        let imported = fresh.import_private_key(&exported, &passphrase).await.unwrap();
+        assert_eq!(imported, keypair.key_id);
+
+        let secret_keys = fresh.list_keys(true).await.unwrap();
+        assert_eq!(secret_keys.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_secret_key_import_rejects_wrong_passphrase() {
+        let adapter = MockGpgAdapter::new();
+
+        let keypair = adapter
+            .generate_keypair("secret@example.com", GpgKeyType::Eddsa, 256, None)
+            .await
+            .unwrap();
+
+        let exported = adapter
+            .export_private_key(&keypair.key_id, &SecureString::new("right"))
+            .await
+            .unwrap();
+
+        let result = adapter
+            .import_private_key(&exported, &SecureString::new("wrong"))
+            .await;
+        assert!(matches!(result, Err(GpgError::InvalidPassphrase)));
+    }
+
+    #[tokio::test]
+    async fn test_secret_key_export_requires_passphrase() {
+        let adapter = MockGpgAdapter::new();
+
+        let keypair = adapter
+            .generate_keypair("secret@example.com", GpgKeyType::Rsa, 2048, None)
+            .await
+            .unwrap();
+
+        let result = adapter
+            .export_private_key(&keypair.key_id, &SecureString::new(""))
+            .await;
+        assert!(matches!(result, Err(GpgError::InvalidPassphrase)));
+    }
+
+    #[tokio::test]
+    async fn test_symmetric_encryption_roundtrip() {
+        // decrypt_symmetric(encrypt_symmetric(m, p), p) = m, no keys needed
+        let adapter = MockGpgAdapter::new();
+        let passphrase = SecureString::new("bootstrap bundle passphrase");
+
+        let plaintext = b"initial bootstrap bundle";
+        let ciphertext = adapter.encrypt_symmetric(plaintext, &passphrase).await.unwrap();
+        assert_ne!(&ciphertext[..], &plaintext[..]);
+
+        let decrypted = adapter.decrypt_symmetric(&ciphertext, &passphrase).await.unwrap();
+        assert_eq!(decrypted, plaintext);
+
+        let wrong = adapter
+            .decrypt_symmetric(&ciphertext, &SecureString::new("guess"))
+            .await;
+        assert!(matches!(wrong, Err(GpgError::InvalidPassphrase)));
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_with_symmetric_format() {
+        use crate::types::EncryptionFormat;
+
+        let adapter = MockGpgAdapter::new();
+        let passphrase = SecureString::new("ceremony");
+
+        let ciphertext = adapter
+            .encrypt_with_format(EncryptionFormat::PgpSymmetric, &[], Some(&passphrase), b"bundle")
+            .await
+            .unwrap();
+        assert_eq!(
+            adapter.decrypt_symmetric(&ciphertext, &passphrase).await.unwrap(),
+            b"bundle"
+        );
+
+        let missing = adapter
+            .encrypt_with_format(EncryptionFormat::PgpSymmetric, &[], None, b"bundle")
+            .await;
+        assert!(matches!(missing, Err(GpgError::InvalidPassphrase)));
+    }
+
+    #[tokio::test]
+    async fn test_set_expiration_extends_key() {
+        let adapter = MockGpgAdapter::new();
+
+        let keypair = adapter
+            .generate_keypair("expiring@example.com", GpgKeyType::Eddsa, 256, Some(30))
+            .await
+            .unwrap();
+
+        let certificate = adapter
+            .set_expiration(&keypair.key_id, Some(365 * 2))
+            .await
+            .unwrap();
+        assert!(certificate.starts_with(&keypair.public_key));
+
+        let info = adapter.list_keys(false).await.unwrap().pop().unwrap();
+        let expires = info.expiration_time.unwrap();
+        assert!(expires > (Utc::now() + Duration::days(700)).timestamp());
+
+        adapter.set_expiration(&keypair.key_id, None).await.unwrap();
+        let info = adapter.list_keys(false).await.unwrap().pop().unwrap();
+        assert!(info.expiration_time.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_move_to_card_leaves_stub() {
+        let adapter = MockGpgAdapter::new();
+        let admin_pin = SecureString::new("12345678");
+
+        let keypair = adapter
+            .generate_keypair("card@example.com", GpgKeyType::Eddsa, 256, None)
+            .await
+            .unwrap();
+
+        let stub = adapter
+            .move_to_card(&keypair.key_id, OpenPgpCardSlot::Signature, "12345678", &admin_pin)
+            .await
+            .unwrap();
+        assert_eq!(stub.card_serial, "12345678");
+
+        // Key is still listed as secret, but the material is on the card
+        assert_eq!(adapter.list_keys(true).await.unwrap().len(), 1);
+        let export = adapter
+            .export_private_key(&keypair.key_id, &SecureString::new("passphrase"))
+            .await;
+        assert!(matches!(export, Err(GpgError::ExportFailed(_))));
+
+        let again = adapter
+            .move_to_card(&keypair.key_id, OpenPgpCardSlot::Signature, "12345678", &admin_pin)
+            .await;
+        assert!(matches!(again, Err(GpgError::InvalidKey(_))));
+    }
+
+    #[tokio::test]
+    async fn test_certify_key_with_trust_signature() {
+        let adapter = MockGpgAdapter::new();
+
+        let root = adapter
+            .generate_keypair("Root <root@example.com>", GpgKeyType::Eddsa, 256, None)
+            .await
+            .unwrap();
+        let employee = adapter
+            .generate_keypair("Alice <alice@example.com>", GpgKeyType::Eddsa, 256, None)
+            .await
+            .unwrap();
+
+        let trust = GpgTrustSignature {
+            depth: 1,
+            amount: 120,
+            scope: None,
+        };
+        let signature = adapter
+            .certify_key(&root.key_id, &employee.key_id, "Alice <alice@example.com>", Some(&trust))
+            .await
+            .unwrap();
+        assert_eq!(&signature[..4], &[0x88, 0x13, 1, 120]);
+
+        let self_cert = adapter
+            .certify_key(&root.key_id, &root.key_id, "Root <root@example.com>", None)
+            .await;
+        assert!(self_cert.is_err());
+    }
 }
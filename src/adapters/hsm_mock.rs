@@ -0,0 +1,191 @@
+//! Mock HSM adapter for testing
+//!
+//! This adapter implements the HsmPort trait using in-memory tokens.
+//! Ed25519 keys are real (ed25519-dalek), so signatures verify against the
+//! returned public key; other algorithms are reported as unsupported.
+//!
+//! **Category Theory Perspective:**
+//! - **Source Category**: PKCS#11 tokens (simulated)
+//! - **Target Category**: Domain (hardware-backed CA keys)
+//! - **Functor**: MockHsmAdapter maps simulated tokens to domain operations
+
+use async_trait::async_trait;
+use ed25519_dalek::{Signer, SigningKey};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::ports::hsm::{HsmError, HsmKeyAlgorithm, HsmKeyPair, HsmKeyRef, HsmPort, HsmToken};
+use crate::ports::yubikey::SecureString;
+
+/// Default user PIN of simulated tokens
+pub const MOCK_HSM_PIN: &str = "1234";
+
+/// Mock HSM adapter for testing
+#[derive(Clone)]
+pub struct MockHsmAdapter {
+    /// Simulated tokens with their user PINs
+    tokens: Arc<RwLock<Vec<(HsmToken, String)>>>,
+
+    /// Keys per (slot, label)
+    keys: Arc<RwLock<HashMap<(u64, String), (HsmKeyPair, SigningKey)>>>,
+}
+
+impl MockHsmAdapter {
+    /// Create an adapter without tokens
+    pub fn new() -> Self {
+        Self {
+            tokens: Arc::new(RwLock::new(Vec::new())),
+            keys: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Add a simulated token with user PIN `pin`
+    pub fn add_token(&self, token: HsmToken, pin: &str) {
+        self.tokens.write().unwrap().push((token, pin.to_string()));
+    }
+
+    fn login(&self, slot_id: u64, pin: &SecureString) -> Result<HsmToken, HsmError> {
+        let tokens = self.tokens.read().unwrap();
+        let (token, expected) = tokens
+            .iter()
+            .find(|(token, _)| token.slot_id == slot_id)
+            .ok_or(HsmError::TokenNotFound(slot_id))?;
+        if pin.as_bytes() != expected.as_bytes() {
+            return Err(HsmError::LoginFailed("CKR_PIN_INCORRECT".to_string()));
+        }
+        Ok(token.clone())
+    }
+}
+
+impl Default for MockHsmAdapter {
+    /// One SoftHSM-like token in slot 0
+    fn default() -> Self {
+        let adapter = Self::new();
+        adapter.add_token(
+            HsmToken {
+                slot_id: 0,
+                label: "cim-keys-ca".to_string(),
+                serial: "0123456789abcdef".to_string(),
+                manufacturer: "Mock".to_string(),
+                model: "SoftHSM v2".to_string(),
+            },
+            MOCK_HSM_PIN,
+        );
+        adapter
+    }
+}
+
+#[async_trait]
+impl HsmPort for MockHsmAdapter {
+    async fn list_tokens(&self) -> Result<Vec<HsmToken>, HsmError> {
+        Ok(self.tokens.read().unwrap().iter().map(|(token, _)| token.clone()).collect())
+    }
+
+    async fn generate_key_pair(
+        &self,
+        slot_id: u64,
+        label: &str,
+        algorithm: HsmKeyAlgorithm,
+        pin: &SecureString,
+    ) -> Result<HsmKeyPair, HsmError> {
+        let token = self.login(slot_id, pin)?;
+        if algorithm != HsmKeyAlgorithm::Ed25519 {
+            return Err(HsmError::UnsupportedAlgorithm(algorithm));
+        }
+
+        let mut keys = self.keys.write().unwrap();
+        let slot_key = (slot_id, label.to_string());
+        if keys.contains_key(&slot_key) {
+            return Err(HsmError::KeyExists(label.to_string()));
+        }
+
+        let signing_key = SigningKey::from_bytes(&rand::random());
+        let key_pair = HsmKeyPair {
+            key_ref: HsmKeyRef {
+                token_serial: token.serial,
+                slot_id,
+                label: label.to_string(),
+                object_id: hex::encode(rand::random::<[u8; 8]>()),
+            },
+            algorithm,
+            public_key: signing_key.verifying_key().to_bytes().to_vec(),
+        };
+        keys.insert(slot_key, (key_pair.clone(), signing_key));
+        Ok(key_pair)
+    }
+
+    async fn find_key_pair(
+        &self,
+        slot_id: u64,
+        label: &str,
+        pin: &SecureString,
+    ) -> Result<Option<HsmKeyPair>, HsmError> {
+        self.login(slot_id, pin)?;
+        Ok(self
+            .keys
+            .read()
+            .unwrap()
+            .get(&(slot_id, label.to_string()))
+            .map(|(key_pair, _)| key_pair.clone()))
+    }
+
+    async fn sign(&self, key: &HsmKeyRef, data: &[u8], pin: &SecureString) -> Result<Vec<u8>, HsmError> {
+        self.login(key.slot_id, pin)?;
+        let keys = self.keys.read().unwrap();
+        let (key_pair, signing_key) = keys
+            .get(&(key.slot_id, key.label.clone()))
+            .filter(|(key_pair, _)| key_pair.key_ref == *key)
+            .ok_or_else(|| HsmError::KeyNotFound(key.label.clone()))?;
+        debug_assert_eq!(key_pair.algorithm, HsmKeyAlgorithm::Ed25519);
+        Ok(signing_key.sign(data).to_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    #[tokio::test]
+    async fn test_generated_key_signs_and_is_found_by_label() {
+        let adapter = MockHsmAdapter::default();
+        let pin = SecureString::new(MOCK_HSM_PIN);
+
+        let key_pair = adapter
+            .generate_key_pair(0, "root-ca", HsmKeyAlgorithm::Ed25519, &pin)
+            .await
+            .unwrap();
+        assert_eq!(key_pair.key_ref.token_serial, "0123456789abcdef");
+        assert!(matches!(
+            adapter.generate_key_pair(0, "root-ca", HsmKeyAlgorithm::Ed25519, &pin).await,
+            Err(HsmError::KeyExists(_))
+        ));
+
+        let found = adapter.find_key_pair(0, "root-ca", &pin).await.unwrap().unwrap();
+        assert_eq!(found.key_ref, key_pair.key_ref);
+
+        let signature = adapter.sign(&key_pair.key_ref, b"tbs certificate", &pin).await.unwrap();
+        let public_key = VerifyingKey::from_bytes(&key_pair.public_key.try_into().unwrap()).unwrap();
+        let signature = Signature::from_slice(&signature).unwrap();
+        assert!(public_key.verify(b"tbs certificate", &signature).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_pin_and_missing_token_rejected() {
+        let adapter = MockHsmAdapter::default();
+        assert!(matches!(
+            adapter.find_key_pair(0, "root-ca", &SecureString::new("0000")).await,
+            Err(HsmError::LoginFailed(_))
+        ));
+        assert!(matches!(
+            adapter.list_tokens().await.unwrap()[..],
+            [HsmToken { slot_id: 0, .. }]
+        ));
+        assert!(matches!(
+            adapter
+                .generate_key_pair(7, "root-ca", HsmKeyAlgorithm::Ed25519, &SecureString::new(MOCK_HSM_PIN))
+                .await,
+            Err(HsmError::TokenNotFound(7))
+        ));
+    }
+}
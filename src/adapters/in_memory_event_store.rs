@@ -0,0 +1,174 @@
+//! In-memory event and snapshot store adapters for testing
+//!
+//! [`InMemoryEventStore`] implements [`EventStore`] with the same sequence
+//! semantics as the JetStream adapter: one store-wide sequence, and an
+//! aggregate's version is the sequence of its last event.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use crate::events::EventEnvelope;
+use crate::ports::event_store::{EventStore, EventStoreError, ExpectedSequence, StoredEvent};
+use crate::ports::snapshot_store::{AggregateSnapshot, SnapshotStore};
+
+#[derive(Default)]
+struct State {
+    last_sequence: u64,
+    streams: HashMap<Uuid, Vec<StoredEvent>>,
+}
+
+/// In-memory event store for testing
+#[derive(Clone, Default)]
+pub struct InMemoryEventStore {
+    state: Arc<RwLock<State>>,
+}
+
+impl InMemoryEventStore {
+    /// Create an empty event store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total number of stored events (for testing/debugging)
+    pub fn len(&self) -> usize {
+        self.state.read().unwrap().streams.values().map(Vec::len).sum()
+    }
+
+    /// Whether no events are stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn append(
+        &self,
+        aggregate_id: Uuid,
+        expected: ExpectedSequence,
+        events: &[EventEnvelope],
+    ) -> Result<u64, EventStoreError> {
+        let mut state = self.state.write()
+            .map_err(|e| EventStoreError::StorageError(format!("Lock poisoned: {}", e)))?;
+        let state = &mut *state;
+
+        let stream = state.streams.entry(aggregate_id).or_default();
+        let version = stream.last().map_or(0, |e| e.sequence);
+        if let Some(expected) = expected.sequence() {
+            if expected != version {
+                return Err(EventStoreError::ConcurrencyConflict { aggregate_id, expected });
+            }
+        }
+
+        for envelope in events {
+            state.last_sequence += 1;
+            stream.push(StoredEvent {
+                sequence: state.last_sequence,
+                envelope: envelope.clone(),
+            });
+        }
+        Ok(stream.last().map_or(0, |e| e.sequence))
+    }
+
+    async fn load_after(
+        &self,
+        aggregate_id: Uuid,
+        sequence: u64,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let state = self.state.read()
+            .map_err(|e| EventStoreError::StorageError(format!("Lock poisoned: {}", e)))?;
+        Ok(state.streams
+            .get(&aggregate_id)
+            .map(|stream| stream.iter().filter(|e| e.sequence > sequence).cloned().collect())
+            .unwrap_or_default())
+    }
+}
+
+/// In-memory snapshot store for testing
+#[derive(Clone, Default)]
+pub struct InMemorySnapshotStore {
+    snapshots: Arc<RwLock<HashMap<Uuid, AggregateSnapshot>>>,
+}
+
+impl InMemorySnapshotStore {
+    /// Create an empty snapshot store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for InMemorySnapshotStore {
+    async fn save(&self, snapshot: &AggregateSnapshot) -> Result<(), EventStoreError> {
+        self.snapshots.write()
+            .map_err(|e| EventStoreError::StorageError(format!("Lock poisoned: {}", e)))?
+            .insert(snapshot.aggregate_id, snapshot.clone());
+        Ok(())
+    }
+
+    async fn load_latest(&self, aggregate_id: Uuid) -> Result<Option<AggregateSnapshot>, EventStoreError> {
+        Ok(self.snapshots.read()
+            .map_err(|e| EventStoreError::StorageError(format!("Lock poisoned: {}", e)))?
+            .get(&aggregate_id)
+            .cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{DomainEvent, KeyEvents, KeyStoredOfflineEvent};
+
+    fn stored_offline(key_id: Uuid) -> EventEnvelope {
+        let correlation_id = Uuid::now_v7();
+        EventEnvelope::new(
+            DomainEvent::Key(KeyEvents::KeyStoredOffline(KeyStoredOfflineEvent {
+                key_id,
+                partition_id: Uuid::now_v7(),
+                encrypted: true,
+                stored_at: chrono::Utc::now(),
+                checksum: "sha256:00".to_string(),
+                correlation_id,
+                causation_id: None,
+            })),
+            correlation_id,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_append_and_replay_per_aggregate() {
+        let store = InMemoryEventStore::new();
+        let (a, b) = (Uuid::now_v7(), Uuid::now_v7());
+
+        let v1 = store.append(a, ExpectedSequence::NoEvents, &[stored_offline(a), stored_offline(a)]).await.unwrap();
+        store.append(b, ExpectedSequence::NoEvents, &[stored_offline(b)]).await.unwrap();
+        let v2 = store.append(a, ExpectedSequence::Exact(v1), &[stored_offline(a)]).await.unwrap();
+
+        let events = store.load(a).await.unwrap();
+        assert_eq!(events.iter().map(|e| e.sequence).collect::<Vec<_>>(), vec![1, 2, 4]);
+        assert_eq!(store.version(a).await.unwrap(), v2);
+        assert_eq!(store.load_after(a, 2).await.unwrap().len(), 1);
+        assert_eq!(store.load(b).await.unwrap().len(), 1);
+        assert!(store.load(Uuid::now_v7()).await.unwrap().is_empty());
+        assert_eq!(store.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_stale_expected_sequence_is_rejected() {
+        let store = InMemoryEventStore::new();
+        let key_id = Uuid::now_v7();
+        let version = store.append(key_id, ExpectedSequence::NoEvents, &[stored_offline(key_id)]).await.unwrap();
+
+        // Two writers loaded the same version; the second loses
+        store.append(key_id, ExpectedSequence::Exact(version), &[stored_offline(key_id)]).await.unwrap();
+        let err = store.append(key_id, ExpectedSequence::Exact(version), &[stored_offline(key_id)]).await.unwrap_err();
+        assert!(matches!(err, EventStoreError::ConcurrencyConflict { expected, .. } if expected == version));
+
+        assert!(store.append(key_id, ExpectedSequence::NoEvents, &[]).await.is_err());
+        assert!(store.append(key_id, ExpectedSequence::Any, &[stored_offline(key_id)]).await.is_ok());
+        assert_eq!(store.load(key_id).await.unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,287 @@
+//! JetStream event store adapter
+//!
+//! Implements [`EventStore`] on a single JetStream stream with one subject
+//! per aggregate:
+//!
+//! ```text
+//! stream CIM_KEYS_EVENTS  (subjects: cim.keys.events.>)
+//!   cim.keys.events.{aggregate_id}   ← one aggregate's events, in order
+//! ```
+//!
+//! Optimistic concurrency uses the server-side
+//! `Nats-Expected-Last-Subject-Sequence` check, so two writers racing on
+//! the same aggregate cannot both succeed. Each event is also published
+//! with `Nats-Msg-Id` set to its event ID, letting JetStream drop
+//! redelivered appends inside the duplicate window.
+//!
+//! [`JetStreamSnapshotStore`] keeps the latest snapshot per aggregate in a
+//! KV bucket keyed by aggregate ID.
+
+use async_nats::jetstream::{self, consumer, context::PublishErrorKind, kv, stream};
+use async_trait::async_trait;
+use futures::StreamExt;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::events::EventEnvelope;
+use crate::ports::event_store::{EventStore, EventStoreError, ExpectedSequence, StoredEvent};
+use crate::ports::snapshot_store::{AggregateSnapshot, SnapshotStore};
+
+/// Stream and subject names for the event store
+#[derive(Debug, Clone)]
+pub struct JetStreamEventStoreConfig {
+    /// JetStream stream holding all aggregates
+    pub stream_name: String,
+    /// Subject prefix; aggregates live at `{prefix}.{aggregate_id}`
+    pub subject_prefix: String,
+    /// Number of stream replicas
+    pub replicas: usize,
+}
+
+impl JetStreamEventStoreConfig {
+    /// Subject holding an aggregate's events
+    pub fn subject(&self, aggregate_id: Uuid) -> String {
+        format!("{}.{}", self.subject_prefix, aggregate_id)
+    }
+}
+
+impl Default for JetStreamEventStoreConfig {
+    fn default() -> Self {
+        Self {
+            stream_name: "CIM_KEYS_EVENTS".to_string(),
+            subject_prefix: "cim.keys.events".to_string(),
+            replicas: 1,
+        }
+    }
+}
+
+/// Event store backed by a NATS JetStream stream
+pub struct JetStreamEventStore {
+    jetstream: jetstream::Context,
+    config: JetStreamEventStoreConfig,
+}
+
+impl JetStreamEventStore {
+    /// Create an event store on a connected JetStream context
+    ///
+    /// Call [`JetStreamEventStore::ensure_stream`] once before use.
+    pub fn new(jetstream: jetstream::Context, config: JetStreamEventStoreConfig) -> Self {
+        Self { jetstream, config }
+    }
+
+    /// Create the event stream if it does not exist
+    ///
+    /// Events are kept on file storage without age or count limits: the
+    /// stream is the system of record.
+    pub async fn ensure_stream(&self) -> Result<(), EventStoreError> {
+        self.jetstream
+            .get_or_create_stream(stream::Config {
+                name: self.config.stream_name.clone(),
+                subjects: vec![format!("{}.>", self.config.subject_prefix)],
+                retention: stream::RetentionPolicy::Limits,
+                storage: stream::StorageType::File,
+                num_replicas: self.config.replicas,
+                description: Some("CIM key management event store".to_string()),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| EventStoreError::ConnectionError(format!("Failed to create event stream: {}", e)))?;
+
+        info!("Event store stream ready: {}", self.config.stream_name);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventStore for JetStreamEventStore {
+    /// Events are published one at a time, each expecting the sequence of
+    /// the one before. A conflict on the first event leaves the stream
+    /// untouched; a failure part-way through leaves the earlier events
+    /// stored, and the returned error names the version they reached.
+    async fn append(
+        &self,
+        aggregate_id: Uuid,
+        expected: ExpectedSequence,
+        events: &[EventEnvelope],
+    ) -> Result<u64, EventStoreError> {
+        let subject = self.config.subject(aggregate_id);
+        let mut expected_sequence = expected.sequence();
+
+        if events.is_empty() {
+            let version = self.version(aggregate_id).await?;
+            return match expected_sequence {
+                Some(expected) if expected != version => {
+                    Err(EventStoreError::ConcurrencyConflict { aggregate_id, expected })
+                }
+                _ => Ok(version),
+            };
+        }
+
+        let mut version = 0;
+        for envelope in events {
+            let payload = serde_json::to_vec(envelope)
+                .map_err(|e| EventStoreError::SerializationError(e.to_string()))?;
+
+            let mut headers = async_nats::HeaderMap::new();
+            headers.insert("Nats-Msg-Id", envelope.event_id.to_string().as_str());
+            headers.insert("CIM-Correlation-Id", envelope.correlation_id.to_string().as_str());
+            if let Some(causation_id) = envelope.causation_id {
+                headers.insert("CIM-Causation-Id", causation_id.to_string().as_str());
+            }
+            headers.insert("CIM-Aggregate-Type", envelope.aggregate_type());
+            if let Some(expected) = expected_sequence {
+                headers.insert("Nats-Expected-Last-Subject-Sequence", expected.to_string().as_str());
+            }
+
+            let publish_error = |e: jetstream::context::PublishError| match e.kind() {
+                PublishErrorKind::WrongLastSequence => EventStoreError::ConcurrencyConflict {
+                    aggregate_id,
+                    expected: expected_sequence.unwrap_or_default(),
+                },
+                _ => EventStoreError::StorageError(format!(
+                    "Failed to append to {} (stored up to version {}): {}",
+                    subject, version, e
+                )),
+            };
+            let ack = self.jetstream
+                .publish_with_headers(subject.clone(), headers, payload.into())
+                .await
+                .map_err(publish_error)?
+                .await
+                .map_err(publish_error)?;
+
+            debug!("Appended {} to {} (seq: {})", envelope.event_id, subject, ack.sequence);
+            version = ack.sequence;
+            expected_sequence = Some(ack.sequence);
+        }
+
+        Ok(version)
+    }
+
+    async fn load_after(
+        &self,
+        aggregate_id: Uuid,
+        sequence: u64,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let stream = self.jetstream
+            .get_stream(&self.config.stream_name)
+            .await
+            .map_err(|e| EventStoreError::ConnectionError(format!("Event stream unavailable: {}", e)))?;
+
+        // Ephemeral consumer over the aggregate's subject; the server
+        // removes it once idle
+        let mut consumer: consumer::PullConsumer = stream
+            .create_consumer(consumer::pull::Config {
+                filter_subject: self.config.subject(aggregate_id),
+                deliver_policy: consumer::DeliverPolicy::ByStartSequence {
+                    start_sequence: sequence + 1,
+                },
+                ack_policy: consumer::AckPolicy::None,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| EventStoreError::StorageError(format!("Failed to create replay consumer: {}", e)))?;
+
+        let pending = consumer.info()
+            .await
+            .map_err(|e| EventStoreError::StorageError(format!("Failed to read replay consumer: {}", e)))?
+            .num_pending;
+        if pending == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut messages = consumer.messages()
+            .await
+            .map_err(|e| EventStoreError::StorageError(format!("Failed to replay events: {}", e)))?;
+
+        let mut events = Vec::with_capacity(pending as usize);
+        while (events.len() as u64) < pending {
+            let message = messages.next()
+                .await
+                .ok_or_else(|| EventStoreError::StorageError("Replay ended early".to_string()))?
+                .map_err(|e| EventStoreError::StorageError(format!("Failed to replay events: {}", e)))?;
+            let sequence = message.info()
+                .map_err(|e| EventStoreError::StorageError(format!("Malformed replay message: {}", e)))?
+                .stream_sequence;
+            let envelope: EventEnvelope = serde_json::from_slice(&message.payload)
+                .map_err(|e| EventStoreError::SerializationError(format!("Event at sequence {}: {}", sequence, e)))?;
+            events.push(StoredEvent { sequence, envelope });
+        }
+
+        Ok(events)
+    }
+}
+
+/// Snapshot store backed by a JetStream KV bucket
+pub struct JetStreamSnapshotStore {
+    bucket: kv::Store,
+}
+
+impl JetStreamSnapshotStore {
+    /// Default bucket name
+    pub const BUCKET: &'static str = "CIM_KEYS_SNAPSHOTS";
+
+    /// Open the snapshot bucket, creating it if needed
+    ///
+    /// Only the latest snapshot per aggregate is kept.
+    pub async fn open(jetstream: &jetstream::Context, bucket: &str) -> Result<Self, EventStoreError> {
+        let bucket = match jetstream.get_key_value(bucket).await {
+            Ok(store) => store,
+            Err(_) => jetstream
+                .create_key_value(kv::Config {
+                    bucket: bucket.to_string(),
+                    description: "CIM key management aggregate snapshots".to_string(),
+                    history: 1,
+                    storage: stream::StorageType::File,
+                    ..Default::default()
+                })
+                .await
+                .map_err(|e| EventStoreError::ConnectionError(format!("Failed to create snapshot bucket: {}", e)))?,
+        };
+        Ok(Self { bucket })
+    }
+}
+
+#[async_trait]
+impl SnapshotStore for JetStreamSnapshotStore {
+    async fn save(&self, snapshot: &AggregateSnapshot) -> Result<(), EventStoreError> {
+        let payload = serde_json::to_vec(snapshot)
+            .map_err(|e| EventStoreError::SerializationError(e.to_string()))?;
+        self.bucket
+            .put(snapshot.aggregate_id.to_string(), payload.into())
+            .await
+            .map_err(|e| EventStoreError::StorageError(format!("Failed to save snapshot: {}", e)))?;
+        debug!("Saved snapshot of {} at {}", snapshot.aggregate_id, snapshot.sequence);
+        Ok(())
+    }
+
+    async fn load_latest(&self, aggregate_id: Uuid) -> Result<Option<AggregateSnapshot>, EventStoreError> {
+        let Some(payload) = self.bucket
+            .get(aggregate_id.to_string())
+            .await
+            .map_err(|e| EventStoreError::StorageError(format!("Failed to load snapshot: {}", e)))?
+        else {
+            return Ok(None);
+        };
+        serde_json::from_slice(&payload)
+            .map(Some)
+            .map_err(|e| EventStoreError::SerializationError(format!("Snapshot of {}: {}", aggregate_id, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_captures_aggregate_subjects() {
+        let config = JetStreamEventStoreConfig::default();
+        let aggregate_id = Uuid::now_v7();
+        let subject = config.subject(aggregate_id);
+
+        assert_eq!(config.stream_name, "CIM_KEYS_EVENTS");
+        assert!(subject.starts_with("cim.keys.events."));
+        // One token per aggregate, so `{prefix}.>` captures every aggregate
+        assert_eq!(subject.split('.').count(), 4);
+    }
+}
@@ -0,0 +1,202 @@
+//! LUKS2 adapter using cryptsetup
+//!
+//! This adapter implements the EncryptedVolumePort trait with the
+//! `cryptsetup`, `mkfs.ext4`, `mount` and `umount` tools, and therefore
+//! needs root. Volumes are LUKS2 with the argon2id key derivation, so
+//! brute-forcing a lost card's passphrase is memory-hard.
+
+use async_trait::async_trait;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::ports::encrypted_volume::{EncryptedVolumeError, EncryptedVolumePort, OpenVolume, VolumeKey};
+
+/// cryptsetup exit code for a passphrase or keyfile that opens no keyslot
+const EXIT_WRONG_KEY: i32 = 2;
+
+/// Encrypted volume adapter using cryptsetup
+#[derive(Clone, Default)]
+pub struct CryptsetupLuksAdapter;
+
+impl CryptsetupLuksAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl EncryptedVolumePort for CryptsetupLuksAdapter {
+    async fn format(&self, device: &Path, key: &VolumeKey, label: &str) -> Result<String, EncryptedVolumeError> {
+        check_device(device)?;
+        run_with_key(&mut luks_format_command(device, label), key)?;
+        let luks_uuid = luks_uuid(device)?;
+
+        // Create the filesystem inside the new volume
+        let mapper_name = mapper_name(&luks_uuid);
+        luks_open(device, key, &mapper_name)?;
+        let mkfs = run(Command::new("mkfs.ext4").args(["-q", "-L", label]).arg(mapper_path(&mapper_name)));
+        let close = run(Command::new("cryptsetup").arg("close").arg(&mapper_name));
+        mkfs.and(close)?;
+
+        Ok(luks_uuid)
+    }
+
+    async fn open(
+        &self,
+        device: &Path,
+        key: &VolumeKey,
+        mount_point: &Path,
+    ) -> Result<OpenVolume, EncryptedVolumeError> {
+        check_device(device)?;
+        if run(Command::new("cryptsetup").arg("isLuks").arg(device)).is_err() {
+            return Err(EncryptedVolumeError::NotEncrypted(device.display().to_string()));
+        }
+        let luks_uuid = luks_uuid(device)?;
+        let mapper_name = mapper_name(&luks_uuid);
+        luks_open(device, key, &mapper_name)?;
+
+        let mounted = fs::create_dir_all(mount_point)
+            .map_err(|e| EncryptedVolumeError::CommandFailed(format!("Failed to create mount point: {}", e)))
+            .and_then(|()| run(Command::new("mount").arg(mapper_path(&mapper_name)).arg(mount_point)));
+        if let Err(e) = mounted {
+            // Do not leave the volume unlocked
+            let _ = run(Command::new("cryptsetup").arg("close").arg(&mapper_name));
+            return Err(e);
+        }
+
+        Ok(OpenVolume {
+            device: device.to_path_buf(),
+            luks_uuid,
+            mapper_name,
+            mount_point: mount_point.to_path_buf(),
+        })
+    }
+
+    async fn close(&self, volume: &OpenVolume) -> Result<(), EncryptedVolumeError> {
+        run(Command::new("sync").arg("--file-system").arg(&volume.mount_point))?;
+        run(Command::new("umount").arg(&volume.mount_point))?;
+        run(Command::new("cryptsetup").arg("close").arg(&volume.mapper_name))?;
+        Ok(())
+    }
+}
+
+fn luks_format_command(device: &Path, label: &str) -> Command {
+    let mut command = Command::new("cryptsetup");
+    command
+        .args(["luksFormat", "--batch-mode", "--type", "luks2", "--pbkdf", "argon2id"])
+        .args(["--label", label])
+        .arg(device);
+    command
+}
+
+fn luks_open(device: &Path, key: &VolumeKey, mapper_name: &str) -> Result<(), EncryptedVolumeError> {
+    let mut command = Command::new("cryptsetup");
+    command.args(["open", "--type", "luks2"]).arg(device).arg(mapper_name);
+    run_with_key(&mut command, key).map(|_| ()).map_err(|e| {
+        if last_exit_code(&e) == Some(EXIT_WRONG_KEY) {
+            EncryptedVolumeError::WrongKey(device.display().to_string())
+        } else {
+            e
+        }
+    })
+}
+
+fn luks_uuid(device: &Path) -> Result<String, EncryptedVolumeError> {
+    Ok(run(Command::new("cryptsetup").arg("luksUUID").arg(device))?.trim().to_string())
+}
+
+/// Device-mapper name of a volume, stable for the same card
+fn mapper_name(luks_uuid: &str) -> String {
+    format!("cim-keys-{}", &luks_uuid[..luks_uuid.len().min(8)])
+}
+
+fn mapper_path(mapper_name: &str) -> PathBuf {
+    Path::new("/dev/mapper").join(mapper_name)
+}
+
+fn check_device(device: &Path) -> Result<(), EncryptedVolumeError> {
+    if device.exists() {
+        Ok(())
+    } else {
+        Err(EncryptedVolumeError::DeviceNotFound(device.display().to_string()))
+    }
+}
+
+/// Run a cryptsetup command that takes `key`; passphrases go through
+/// stdin so they never show up in the process list
+fn run_with_key(command: &mut Command, key: &VolumeKey) -> Result<String, EncryptedVolumeError> {
+    match key {
+        VolumeKey::Passphrase(passphrase) => {
+            command.args(["--key-file", "-"]);
+            run_with_stdin(command, Some(passphrase.as_bytes()))
+        }
+        VolumeKey::Keyfile(path) => {
+            command.arg("--key-file").arg(path);
+            run(command)
+        }
+    }
+}
+
+fn run(command: &mut Command) -> Result<String, EncryptedVolumeError> {
+    run_with_stdin(command, None)
+}
+
+/// Run a command, returning its stdout; failures carry the exit code as
+/// `exit N:` so callers can tell them apart
+fn run_with_stdin(command: &mut Command, stdin: Option<&[u8]>) -> Result<String, EncryptedVolumeError> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let failed = |e: std::io::Error| EncryptedVolumeError::CommandFailed(format!("Failed to run {}: {}", program, e));
+
+    let mut child = command
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(failed)?;
+    if let (Some(bytes), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(bytes).map_err(failed)?;
+    }
+    let output = child.wait_with_output().map_err(failed)?;
+
+    if !output.status.success() {
+        return Err(EncryptedVolumeError::CommandFailed(format!(
+            "exit {}: {}: {}",
+            output.status.code().unwrap_or(-1),
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn last_exit_code(error: &EncryptedVolumeError) -> Option<i32> {
+    match error {
+        EncryptedVolumeError::CommandFailed(message) => message.strip_prefix("exit ")?.split(':').next()?.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_uses_luks2_with_argon2id() {
+        let command = luks_format_command(Path::new("/dev/sdb1"), "CIMKEYS");
+        let args: Vec<_> = command.get_args().map(|arg| arg.to_string_lossy().into_owned()).collect();
+
+        assert_eq!(
+            args,
+            ["luksFormat", "--batch-mode", "--type", "luks2", "--pbkdf", "argon2id", "--label", "CIMKEYS", "/dev/sdb1"]
+        );
+    }
+
+    #[test]
+    fn test_wrong_key_is_recognised_by_exit_code() {
+        let error = EncryptedVolumeError::CommandFailed("exit 2: cryptsetup: No key available".to_string());
+        assert_eq!(last_exit_code(&error), Some(EXIT_WRONG_KEY));
+        assert_eq!(mapper_name("5e2c7a10-aaaa-bbbb"), "cim-keys-5e2c7a10");
+    }
+}
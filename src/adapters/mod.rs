@@ -9,35 +9,84 @@
 
 pub mod nsc;
 pub mod in_memory;
+pub mod in_memory_event_store;
+pub mod saga_store;
+pub mod signed_event_store;
 pub mod yubikey_mock;
 pub mod yubikey_cli;
 pub mod yubikey_hardware;
 pub mod x509_mock;
 pub mod x509_rcgen;
 pub mod gpg_mock;
+pub mod gnupg_homedir;
 pub mod ssh_mock;
+pub mod ssh_keys;
 pub mod nats_publisher_stub;
 pub mod nats_client;
+pub mod removable_media_lsblk;
+pub mod luks_cryptsetup;
+pub mod seeded_rng;
+pub mod hsm_mock;
+pub mod tpm_mock;
+pub mod policy_import;
+#[cfg(feature = "nats-client")]
+pub mod jetstream_event_store;
+#[cfg(feature = "acme-server")]
+pub mod acme_server;
+#[cfg(feature = "neo4j")]
+pub mod neo4j_bolt;
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11_hsm;
+#[cfg(feature = "tpm")]
+pub mod tpm_esapi;
 
 pub use nsc::NscAdapter;
 pub use in_memory::InMemoryStorageAdapter;
+pub use in_memory_event_store::{InMemoryEventStore, InMemorySnapshotStore};
+pub use saga_store::{FileSagaStore, InMemorySagaStore};
+pub use signed_event_store::SignedEventStore;
 pub use yubikey_mock::MockYubiKeyAdapter;
 pub use yubikey_cli::YubiKeyCliAdapter;
 pub use yubikey_hardware::YubiKeyHardwareAdapter;
 pub use x509_mock::MockX509Adapter;
 pub use x509_rcgen::RcgenX509Adapter;
 pub use gpg_mock::MockGpgAdapter;
+pub use gnupg_homedir::{GnupgHomedirAdapter, GnupgHomedirScan, GnupgCertificate, import_homedir};
 pub use ssh_mock::MockSshKeyAdapter;
+pub use ssh_keys::SshKeysAdapter;
 pub use nats_publisher_stub::{EventEnvelope, PublisherConfig, build_subject, extract_event_type};
 pub use nats_client::{NatsClientAdapter, NatsClientError};
+pub use removable_media_lsblk::LsblkMediaAdapter;
+pub use luks_cryptsetup::CryptsetupLuksAdapter;
+pub use seeded_rng::SeededCryptoRng;
+pub use hsm_mock::MockHsmAdapter;
+pub use tpm_mock::MockTpmAdapter;
+pub use policy_import::{PolicyImport, PolicyImportAdapter, PolicyImportError, PolicyLanguage, UntranslatableRule};
 
 // Export JetStreamAdapter when nats-client feature is enabled
 #[cfg(feature = "nats-client")]
 pub use nats_client::{JetStreamAdapter, JetStreamSubscriptionImpl};
+#[cfg(feature = "nats-client")]
+pub use jetstream_event_store::{JetStreamEventStore, JetStreamEventStoreConfig, JetStreamSnapshotStore};
+
+#[cfg(feature = "acme-server")]
+pub use acme_server::{
+    AcmeServer, AcmeServerConfig, AcmeRequest, AcmeResponse, AcmeMethod,
+    AcmeProblem, AcmeErrorType, ChallengeValidator, StubChallengeValidator,
+};
+
+#[cfg(feature = "neo4j")]
+pub use neo4j_bolt::Neo4jBoltAdapter;
+
+#[cfg(feature = "pkcs11")]
+pub use pkcs11_hsm::Pkcs11HsmAdapter;
+
+#[cfg(feature = "tpm")]
+pub use tpm_esapi::TssEsapiTpmAdapter;
 
 // TODO: Implement real adapters for production use
 // - FileSystemStorageAdapter for StoragePort
 // - ✅ YubiKeyHardwareAdapter for YubiKeyPort (real hardware via PC/SC)
 // - ✅ RcgenX509Adapter for X509Port (using rcgen crate)
 // - SequoiaGpgAdapter for GpgPort (using sequoia-openpgp crate)
-// - SshKeysAdapter for SshKeyPort (using ssh-key crate)
\ No newline at end of file
+// - ✅ SshKeysAdapter for SshKeyPort (using ssh-key crate)
\ No newline at end of file
@@ -318,6 +318,10 @@ impl NatsClientAdapter {
             DomainEvent::Relationship(_) => "relationship",
             DomainEvent::Manifest(_) => "manifest",
             DomainEvent::Saga(_) => "saga",
+            DomainEvent::ThresholdSigning(_) => "threshold.signing",
+            DomainEvent::Approval(_) => "approval",
+            DomainEvent::Authorization(_) => "authorization",
+            DomainEvent::Maintenance(_) => "maintenance",
         }
     }
 
@@ -911,8 +915,11 @@ mod tests {
                 jwt_kid: None,
                 jwt_alg: None,
                 jwt_use: None,
+                hardware_serial: None,
+                tpm: None,
             },
             ownership: None,
+            expires_at: None,
             correlation_id: Uuid::now_v7(),
             causation_id: Some(test_event_id), // A4: Self-reference for root event
         }));
@@ -0,0 +1,348 @@
+//! Neo4j Bolt adapter
+//!
+//! Implements [`Neo4jPort`] against a running Neo4j database through
+//! neo4rs, so the `CypherBatch` of the graph projection can be applied
+//! directly instead of being exported as a `.cypher` file:
+//!
+//! ```text
+//! DomainGraphData → CypherBatch → Neo4jBoltAdapter::execute_batch
+//!                                   └─ one transaction, committed or rolled back
+//! ```
+//!
+//! The batch queries use `MERGE` on node IDs and set properties
+//! afterwards, so re-running a projection updates the graph in place.
+//! The adapter keeps a [`ProjectionStatus`] of the connection and the last
+//! sync for the GUI to show.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use neo4rs::{
+    BoltBoolean, BoltFloat, BoltInteger, BoltList, BoltMap, BoltNull, BoltString, BoltType, ConfigBuilder, Graph,
+    Query, Row, Txn,
+};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::ports::neo4j::{
+    CypherBatch, CypherQuery, CypherValue, DatabaseInfo, ExecutionResult, Neo4jConfig, Neo4jError, Neo4jPort,
+    Neo4jTransaction, QueryResult, Record,
+};
+use crate::projection::ProjectionStatus;
+
+/// Neo4j adapter speaking Bolt to a live database
+pub struct Neo4jBoltAdapter {
+    graph: Graph,
+    status: RwLock<ProjectionStatus>,
+}
+
+impl Neo4jBoltAdapter {
+    /// Connect to the database described by `config`
+    pub async fn connect(config: &Neo4jConfig) -> Result<Self, Neo4jError> {
+        let mut builder = ConfigBuilder::default()
+            .uri(config.uri.as_str())
+            .user(config.username.as_str())
+            .password(config.password.as_str());
+        if let Some(database) = &config.database {
+            builder = builder.db(database.as_str());
+        }
+        if let Some(max_connections) = config.max_connections {
+            builder = builder.max_connections(max_connections as usize);
+        }
+        let bolt_config = builder.build().map_err(map_error)?;
+
+        let connecting = Graph::connect(bolt_config);
+        let graph = match config.connection_timeout_ms {
+            Some(ms) => tokio::time::timeout(Duration::from_millis(ms), connecting)
+                .await
+                .map_err(|_| Neo4jError::Timeout(format!("Connecting to {} took over {} ms", config.uri, ms)))?,
+            None => connecting.await,
+        }
+        .map_err(map_error)?;
+
+        info!("Connected to Neo4j at {}", config.uri);
+        Ok(Self { graph, status: RwLock::new(ProjectionStatus::Connected) })
+    }
+
+    /// Connection and sync status, e.g. `Projected { items_count }` after
+    /// the last successful batch
+    pub fn status(&self) -> ProjectionStatus {
+        self.status.read().map(|s| s.clone()).unwrap_or(ProjectionStatus::NotConfigured)
+    }
+
+    fn set_status(&self, status: ProjectionStatus) {
+        if let Ok(mut current) = self.status.write() {
+            *current = status;
+        }
+    }
+
+    async fn run_batch(&self, batch: &CypherBatch) -> Result<(), Neo4jError> {
+        let mut txn = self.graph.start_txn().await.map_err(map_error)?;
+        for (index, query) in batch.queries.iter().enumerate() {
+            if let Err(e) = txn.run(to_bolt_query(query)).await {
+                warn!("Query {} of the batch failed, rolling back: {}", index, e);
+                // The server drops the transaction on failure; the original
+                // error is the one worth reporting
+                let _ = txn.rollback().await;
+                return Err(map_error(e));
+            }
+        }
+        txn.commit().await.map_err(map_error)
+    }
+}
+
+#[async_trait]
+impl Neo4jPort for Neo4jBoltAdapter {
+    async fn execute_batch(&self, batch: &CypherBatch) -> Result<ExecutionResult, Neo4jError> {
+        self.set_status(ProjectionStatus::Syncing);
+        let started = Instant::now();
+
+        match self.run_batch(batch).await {
+            Ok(()) => {
+                debug!("Applied {} queries to Neo4j", batch.len());
+                self.set_status(ProjectionStatus::Projected { items_count: batch.len() });
+                // Bolt `run` discards the summary, so only the query count
+                // and timing are known
+                Ok(ExecutionResult {
+                    queries_executed: batch.len(),
+                    execution_time_ms: started.elapsed().as_millis() as u64,
+                    ..ExecutionResult::default()
+                })
+            }
+            Err(e) => {
+                self.set_status(ProjectionStatus::Error { message: e.to_string() });
+                Err(e)
+            }
+        }
+    }
+
+    async fn execute(&self, query: &CypherQuery) -> Result<QueryResult, Neo4jError> {
+        let mut stream = self.graph.execute(to_bolt_query(query)).await.map_err(map_error)?;
+        let mut rows = Vec::new();
+        while let Some(row) = stream.next().await.map_err(map_error)? {
+            rows.push(row);
+        }
+        query_result(rows)
+    }
+
+    async fn health_check(&self) -> Result<(), Neo4jError> {
+        match self.graph.run(neo4rs::query("RETURN 1")).await {
+            Ok(()) => {
+                if self.status().is_error() {
+                    self.set_status(ProjectionStatus::Connected);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                let e = map_error(e);
+                self.set_status(ProjectionStatus::Error { message: e.to_string() });
+                Err(e)
+            }
+        }
+    }
+
+    async fn database_info(&self) -> Result<DatabaseInfo, Neo4jError> {
+        let components = self
+            .execute(&CypherQuery::new(
+                "CALL dbms.components() YIELD name, versions, edition RETURN name, versions[0] AS version, edition",
+            ))
+            .await?;
+        let counts = self
+            .execute(&CypherQuery::new(
+                "MATCH (n) WITH count(n) AS nodes OPTIONAL MATCH ()-[r]->() RETURN nodes, count(r) AS relationships",
+            ))
+            .await?;
+
+        let component = components
+            .records
+            .first()
+            .ok_or_else(|| Neo4jError::BackendError("dbms.components() returned nothing".to_string()))?;
+        let count = |key: &str| match counts.records.first().and_then(|r| r.get(key)) {
+            Some(CypherValue::Int(n)) => u64::try_from(*n).ok(),
+            _ => None,
+        };
+
+        Ok(DatabaseInfo {
+            name: string_value(component.get("name")),
+            version: string_value(component.get("version")),
+            edition: string_value(component.get("edition")),
+            node_count: count("nodes"),
+            relationship_count: count("relationships"),
+        })
+    }
+
+    async fn begin_transaction(&self) -> Result<Box<dyn Neo4jTransaction>, Neo4jError> {
+        let txn = self.graph.start_txn().await.map_err(map_error)?;
+        Ok(Box::new(BoltTransaction { txn: Mutex::new(txn) }))
+    }
+}
+
+/// Explicit transaction on a pooled Bolt connection
+struct BoltTransaction {
+    // Txn is Send but not Sync; the port requires both
+    txn: Mutex<Txn>,
+}
+
+#[async_trait]
+impl Neo4jTransaction for BoltTransaction {
+    async fn execute(&mut self, query: &CypherQuery) -> Result<QueryResult, Neo4jError> {
+        let txn = self.txn.get_mut();
+        let mut stream = txn.execute(to_bolt_query(query)).await.map_err(map_error)?;
+        let mut rows = Vec::new();
+        while let Some(row) = stream.next(txn.handle()).await.map_err(map_error)? {
+            rows.push(row);
+        }
+        query_result(rows)
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), Neo4jError> {
+        self.txn.into_inner().commit().await.map_err(map_error)
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), Neo4jError> {
+        self.txn.into_inner().rollback().await.map_err(map_error)
+    }
+}
+
+fn to_bolt_query(query: &CypherQuery) -> Query {
+    query
+        .parameters
+        .iter()
+        .fold(neo4rs::query(&query.query), |bolt, (name, value)| bolt.param(name, to_bolt(value)))
+}
+
+fn to_bolt(value: &CypherValue) -> BoltType {
+    match value {
+        CypherValue::Null => BoltType::Null(BoltNull),
+        CypherValue::Bool(b) => BoltType::Boolean(BoltBoolean::new(*b)),
+        CypherValue::Int(i) => BoltType::Integer(BoltInteger::new(*i)),
+        CypherValue::Float(f) => BoltType::Float(BoltFloat::new(*f)),
+        CypherValue::String(s) => BoltType::String(BoltString::new(s)),
+        CypherValue::List(items) => BoltType::List(BoltList { value: items.iter().map(to_bolt).collect() }),
+        CypherValue::Map(map) => BoltType::Map(BoltMap {
+            value: map.iter().map(|(k, v)| (BoltString::new(k), to_bolt(v))).collect(),
+        }),
+        // Sent as a temporal value so it compares with datetime() in queries
+        CypherValue::DateTime(dt) => match chrono::DateTime::parse_from_rfc3339(dt) {
+            Ok(parsed) => parsed.into(),
+            Err(_) => BoltType::String(BoltString::new(dt)),
+        },
+    }
+}
+
+fn from_bolt(value: BoltType) -> CypherValue {
+    match value {
+        BoltType::Null(_) => CypherValue::Null,
+        BoltType::Boolean(b) => CypherValue::Bool(b.value),
+        BoltType::Integer(i) => CypherValue::Int(i.value),
+        BoltType::Float(f) => CypherValue::Float(f.value),
+        BoltType::String(s) => CypherValue::String(s.value),
+        BoltType::List(list) => CypherValue::List(list.value.into_iter().map(from_bolt).collect()),
+        BoltType::Map(map) => {
+            CypherValue::Map(map.value.into_iter().map(|(k, v)| (k.value, from_bolt(v))).collect())
+        }
+        BoltType::DateTime(_) | BoltType::LocalDateTime(_) | BoltType::DateTimeZoneId(_) => {
+            CypherValue::DateTime(value.to_string())
+        }
+        // Nodes, paths, points and the other temporal types have no
+        // CypherValue counterpart
+        other => CypherValue::String(other.to_string()),
+    }
+}
+
+fn query_result(rows: Vec<Row>) -> Result<QueryResult, Neo4jError> {
+    let records = rows
+        .iter()
+        .map(|row| {
+            row.to::<HashMap<String, BoltType>>()
+                .map(|values| Record { values: values.into_iter().map(|(k, v)| (k, from_bolt(v))).collect() })
+                .map_err(|e| Neo4jError::SerializationError(e.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut columns: Vec<String> = records.first().map(|r| r.values.keys().cloned().collect()).unwrap_or_default();
+    columns.sort();
+
+    Ok(QueryResult { rows_affected: records.len(), columns, records })
+}
+
+fn string_value(value: Option<&CypherValue>) -> String {
+    match value {
+        Some(CypherValue::String(s)) => s.clone(),
+        Some(other) => other.to_cypher_literal(),
+        None => String::new(),
+    }
+}
+
+fn map_error(error: neo4rs::Error) -> Neo4jError {
+    match error {
+        neo4rs::Error::Neo4j(e) => {
+            let message = format!("{}: {}", e.code(), e.message());
+            match e.code() {
+                code if code.starts_with("Neo.ClientError.Security") => Neo4jError::AuthenticationFailed(message),
+                code if code.starts_with("Neo.ClientError.Statement") => Neo4jError::SyntaxError(message),
+                code if code.starts_with("Neo.ClientError.Schema.ConstraintValidationFailed") => {
+                    Neo4jError::ConstraintViolation(message)
+                }
+                code if code.starts_with("Neo.ClientError.Database.DatabaseNotFound") => {
+                    Neo4jError::DatabaseNotFound(message)
+                }
+                code if code.starts_with("Neo.ClientError.Transaction") || code.starts_with("Neo.TransientError") => {
+                    Neo4jError::TransactionFailed(message)
+                }
+                _ => Neo4jError::BackendError(message),
+            }
+        }
+        neo4rs::Error::AuthenticationError(message) => Neo4jError::AuthenticationFailed(message),
+        neo4rs::Error::IOError { detail } => Neo4jError::IoError(detail.to_string()),
+        e @ (neo4rs::Error::ConnectionError
+        | neo4rs::Error::UrlParseError(_)
+        | neo4rs::Error::UnsupportedScheme(_)
+        | neo4rs::Error::InvalidDnsName(_)
+        | neo4rs::Error::InvalidConfig) => Neo4jError::ConnectionFailed(e.to_string()),
+        e @ neo4rs::Error::DeserializationError(_) => Neo4jError::SerializationError(e.to_string()),
+        e => Neo4jError::BackendError(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::neo4j::GraphNode;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_values_round_trip_through_bolt() {
+        let value = CypherValue::Map(HashMap::from([
+            ("name".to_string(), CypherValue::from("Alice")),
+            ("active".to_string(), CypherValue::from(true)),
+            ("tags".to_string(), CypherValue::List(vec![CypherValue::from(1i64), CypherValue::Null])),
+        ]));
+
+        match from_bolt(to_bolt(&value)) {
+            CypherValue::Map(map) => {
+                assert!(matches!(map.get("name"), Some(CypherValue::String(s)) if s == "Alice"));
+                assert!(matches!(map.get("active"), Some(CypherValue::Bool(true))));
+                assert!(matches!(map.get("tags"), Some(CypherValue::List(items)) if items.len() == 2));
+            }
+            other => panic!("Expected a map, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_datetime_is_sent_as_temporal() {
+        let bolt = to_bolt(&CypherValue::DateTime("2025-01-01T00:00:00+00:00".to_string()));
+        assert!(matches!(bolt, BoltType::DateTime(_)));
+    }
+
+    #[test]
+    fn test_node_query_carries_parameters() {
+        let id = Uuid::now_v7();
+        let query = GraphNode::new(id, "Person").with_property("name", "Alice").to_merge_query();
+        let bolt = to_bolt_query(&query);
+
+        assert!(bolt.has_param_key("id"));
+        assert!(bolt.has_param_key("properties"));
+    }
+}
@@ -0,0 +1,357 @@
+//! PKCS#11 HSM adapter
+//!
+//! Implements [`HsmPort`] through a PKCS#11 module (`cryptoki`), e.g. a
+//! network HSM vendor library or SoftHSM v2
+//! (`/usr/lib/softhsm/libsofthsm2.so`) for rehearsal ceremonies.
+//!
+//! Keys are generated as token objects with `CKA_SENSITIVE` set and
+//! `CKA_EXTRACTABLE` cleared, so the private key never leaves the token.
+//! Both halves share a `CKA_LABEL` and a random `CKA_ID`; lookups go by
+//! label, signing by label and ID.
+//!
+//! PKCS#11 calls block, so every operation runs on the blocking pool with
+//! its own session, logged in as the user for the duration of the call.
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+use rsa::pkcs1::EncodeRsaPublicKey;
+use rsa::{BigUint, RsaPublicKey};
+
+use crate::ports::hsm::{HsmError, HsmKeyAlgorithm, HsmKeyPair, HsmKeyRef, HsmPort, HsmToken};
+use crate::ports::yubikey::SecureString;
+
+/// DER OID 1.3.101.112 (Ed25519)
+const ED25519_PARAMS: &[u8] = &[0x06, 0x03, 0x2B, 0x65, 0x70];
+/// DER OID 1.2.840.10045.3.1.7 (P-256)
+const P256_PARAMS: &[u8] = &[0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+/// DER OID 1.3.132.0.34 (P-384)
+const P384_PARAMS: &[u8] = &[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x22];
+
+/// HSM adapter over a PKCS#11 module
+#[derive(Clone)]
+pub struct Pkcs11HsmAdapter {
+    context: Pkcs11,
+}
+
+impl Pkcs11HsmAdapter {
+    /// Load and initialize the PKCS#11 module at `module`
+    pub fn new(module: impl AsRef<Path>) -> Result<Self, HsmError> {
+        let context = Pkcs11::new(module.as_ref()).map_err(|e| HsmError::ModuleLoadFailed(e.to_string()))?;
+        context
+            .initialize(CInitializeArgs::OsThreads)
+            .map_err(|e| HsmError::ModuleLoadFailed(e.to_string()))?;
+        Ok(Self { context })
+    }
+
+    /// Run `operation` in a user session on the token in `slot_id`
+    async fn with_session<T, F>(&self, slot_id: u64, pin: &SecureString, operation: F) -> Result<T, HsmError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Session, &HsmToken) -> Result<T, HsmError> + Send + 'static,
+    {
+        let context = self.context.clone();
+        let pin = AuthPin::new(
+            std::str::from_utf8(pin.as_bytes())
+                .map_err(|_| HsmError::LoginFailed("PIN is not UTF-8".to_string()))?
+                .into(),
+        );
+        tokio::task::spawn_blocking(move || {
+            let slot = context
+                .get_slots_with_token()
+                .map_err(pkcs11_error)?
+                .into_iter()
+                .find(|slot| slot.id() == slot_id)
+                .ok_or(HsmError::TokenNotFound(slot_id))?;
+            let token = token_info(&context, slot_id, slot)?;
+
+            let session = context.open_rw_session(slot).map_err(pkcs11_error)?;
+            session
+                .login(UserType::User, Some(&pin))
+                .map_err(|e| HsmError::LoginFailed(e.to_string()))?;
+            let result = operation(&session, &token);
+            let _ = session.logout();
+            result
+        })
+        .await
+        .map_err(|e| HsmError::Pkcs11(e.to_string()))?
+    }
+}
+
+fn pkcs11_error(error: cryptoki::error::Error) -> HsmError {
+    HsmError::Pkcs11(error.to_string())
+}
+
+fn token_info(context: &Pkcs11, slot_id: u64, slot: cryptoki::slot::Slot) -> Result<HsmToken, HsmError> {
+    let info = context.get_token_info(slot).map_err(pkcs11_error)?;
+    Ok(HsmToken {
+        slot_id,
+        label: info.label().trim().to_string(),
+        serial: info.serial_number().trim().to_string(),
+        manufacturer: info.manufacturer_id().trim().to_string(),
+        model: info.model().trim().to_string(),
+    })
+}
+
+/// Contents of a DER OCTET STRING (CKA_EC_POINT is wrapped in one)
+fn octet_string_contents(der: &[u8]) -> Option<&[u8]> {
+    let (&tag, rest) = der.split_first()?;
+    if tag != 0x04 {
+        return None;
+    }
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = match first {
+        len @ 0..=0x7F => (len as usize, rest),
+        0x81 => (*rest.first()? as usize, &rest[1..]),
+        0x82 => (u16::from_be_bytes([*rest.first()?, *rest.get(1)?]) as usize, &rest[2..]),
+        _ => return None,
+    };
+    (rest.len() == len).then_some(rest)
+}
+
+fn find_one(session: &Session, template: &[Attribute]) -> Result<Option<ObjectHandle>, HsmError> {
+    Ok(session.find_objects(template).map_err(pkcs11_error)?.into_iter().next())
+}
+
+fn attribute(session: &Session, object: ObjectHandle, kind: AttributeType) -> Result<Attribute, HsmError> {
+    session
+        .get_attributes(object, &[kind])
+        .map_err(pkcs11_error)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| HsmError::Pkcs11(format!("Attribute {:?} missing", kind)))
+}
+
+/// Algorithm and encoded public key of a public key object
+fn read_public_key(session: &Session, public: ObjectHandle) -> Result<(HsmKeyAlgorithm, Vec<u8>), HsmError> {
+    let key_type = match attribute(session, public, AttributeType::KeyType)? {
+        Attribute::KeyType(key_type) => key_type,
+        other => return Err(HsmError::Pkcs11(format!("Unexpected {:?}", other))),
+    };
+
+    if key_type == KeyType::RSA {
+        let modulus = match attribute(session, public, AttributeType::Modulus)? {
+            Attribute::Modulus(modulus) => modulus,
+            other => return Err(HsmError::Pkcs11(format!("Unexpected {:?}", other))),
+        };
+        let exponent = match attribute(session, public, AttributeType::PublicExponent)? {
+            Attribute::PublicExponent(exponent) => exponent,
+            other => return Err(HsmError::Pkcs11(format!("Unexpected {:?}", other))),
+        };
+        let algorithm = match modulus.len() * 8 {
+            3072 => HsmKeyAlgorithm::Rsa3072,
+            4096 => HsmKeyAlgorithm::Rsa4096,
+            bits => return Err(HsmError::Pkcs11(format!("Unsupported RSA key size {}", bits))),
+        };
+        let der = RsaPublicKey::new(BigUint::from_bytes_be(&modulus), BigUint::from_bytes_be(&exponent))
+            .map_err(|e| HsmError::Pkcs11(e.to_string()))?
+            .to_pkcs1_der()
+            .map_err(|e| HsmError::Pkcs11(e.to_string()))?;
+        return Ok((algorithm, der.as_bytes().to_vec()));
+    }
+
+    let params = match attribute(session, public, AttributeType::EcParams)? {
+        Attribute::EcParams(params) => params,
+        other => return Err(HsmError::Pkcs11(format!("Unexpected {:?}", other))),
+    };
+    let algorithm = match params.as_slice() {
+        ED25519_PARAMS => HsmKeyAlgorithm::Ed25519,
+        // Some tokens encode the curve as PrintableString "edwards25519"
+        [0x13, 0x0C, name @ ..] if name == b"edwards25519" => HsmKeyAlgorithm::Ed25519,
+        P256_PARAMS => HsmKeyAlgorithm::EcdsaP256,
+        P384_PARAMS => HsmKeyAlgorithm::EcdsaP384,
+        _ => return Err(HsmError::Pkcs11(format!("Unsupported curve {}", hex::encode(&params)))),
+    };
+    let point = match attribute(session, public, AttributeType::EcPoint)? {
+        Attribute::EcPoint(point) => point,
+        other => return Err(HsmError::Pkcs11(format!("Unexpected {:?}", other))),
+    };
+    let point = octet_string_contents(&point).map(<[u8]>::to_vec).unwrap_or(point);
+    Ok((algorithm, point))
+}
+
+#[async_trait]
+impl HsmPort for Pkcs11HsmAdapter {
+    async fn list_tokens(&self) -> Result<Vec<HsmToken>, HsmError> {
+        let context = self.context.clone();
+        tokio::task::spawn_blocking(move || {
+            context
+                .get_slots_with_token()
+                .map_err(pkcs11_error)?
+                .into_iter()
+                .map(|slot| token_info(&context, slot.id(), slot))
+                .collect()
+        })
+        .await
+        .map_err(|e| HsmError::Pkcs11(e.to_string()))?
+    }
+
+    async fn generate_key_pair(
+        &self,
+        slot_id: u64,
+        label: &str,
+        algorithm: HsmKeyAlgorithm,
+        pin: &SecureString,
+    ) -> Result<HsmKeyPair, HsmError> {
+        let label = label.to_string();
+        self.with_session(slot_id, pin, move |session, token| {
+            let existing = find_one(session, &[Attribute::Label(label.as_bytes().to_vec())])?;
+            if existing.is_some() {
+                return Err(HsmError::KeyExists(label));
+            }
+
+            let object_id: [u8; 16] = rand::random();
+            let (mechanism, key_params) = match algorithm {
+                HsmKeyAlgorithm::Ed25519 => {
+                    (Mechanism::EccEdwardsKeyPairGen, vec![Attribute::EcParams(ED25519_PARAMS.to_vec())])
+                }
+                HsmKeyAlgorithm::EcdsaP256 => (Mechanism::EccKeyPairGen, vec![Attribute::EcParams(P256_PARAMS.to_vec())]),
+                HsmKeyAlgorithm::EcdsaP384 => (Mechanism::EccKeyPairGen, vec![Attribute::EcParams(P384_PARAMS.to_vec())]),
+                HsmKeyAlgorithm::Rsa3072 | HsmKeyAlgorithm::Rsa4096 => {
+                    let bits: u64 = if algorithm == HsmKeyAlgorithm::Rsa3072 { 3072 } else { 4096 };
+                    (
+                        Mechanism::RsaPkcsKeyPairGen,
+                        vec![
+                            Attribute::ModulusBits(bits.into()),
+                            Attribute::PublicExponent(vec![0x01, 0x00, 0x01]),
+                        ],
+                    )
+                }
+            };
+
+            let common = [
+                Attribute::Token(true),
+                Attribute::Label(label.as_bytes().to_vec()),
+                Attribute::Id(object_id.to_vec()),
+            ];
+            let mut public_template = common.to_vec();
+            public_template.push(Attribute::Verify(true));
+            public_template.extend(key_params);
+            let mut private_template = common.to_vec();
+            private_template.extend([
+                Attribute::Private(true),
+                Attribute::Sensitive(true),
+                Attribute::Extractable(false),
+                Attribute::Sign(true),
+            ]);
+
+            let (public, _private) = session
+                .generate_key_pair(&mechanism, &public_template, &private_template)
+                .map_err(|e| HsmError::GenerationFailed(e.to_string()))?;
+            let (_, public_key) = read_public_key(session, public)?;
+
+            Ok(HsmKeyPair {
+                key_ref: HsmKeyRef {
+                    token_serial: token.serial.clone(),
+                    slot_id,
+                    label,
+                    object_id: hex::encode(object_id),
+                },
+                algorithm,
+                public_key,
+            })
+        })
+        .await
+    }
+
+    async fn find_key_pair(
+        &self,
+        slot_id: u64,
+        label: &str,
+        pin: &SecureString,
+    ) -> Result<Option<HsmKeyPair>, HsmError> {
+        let label = label.to_string();
+        self.with_session(slot_id, pin, move |session, token| {
+            let Some(public) = find_one(
+                session,
+                &[
+                    Attribute::Class(ObjectClass::PUBLIC_KEY),
+                    Attribute::Label(label.as_bytes().to_vec()),
+                ],
+            )?
+            else {
+                return Ok(None);
+            };
+            let object_id = match attribute(session, public, AttributeType::Id)? {
+                Attribute::Id(id) => id,
+                other => return Err(HsmError::Pkcs11(format!("Unexpected {:?}", other))),
+            };
+            let (algorithm, public_key) = read_public_key(session, public)?;
+
+            Ok(Some(HsmKeyPair {
+                key_ref: HsmKeyRef {
+                    token_serial: token.serial.clone(),
+                    slot_id,
+                    label,
+                    object_id: hex::encode(object_id),
+                },
+                algorithm,
+                public_key,
+            }))
+        })
+        .await
+    }
+
+    async fn sign(&self, key: &HsmKeyRef, data: &[u8], pin: &SecureString) -> Result<Vec<u8>, HsmError> {
+        let key = key.clone();
+        let data = data.to_vec();
+        self.with_session(key.slot_id, pin, move |session, token| {
+            if token.serial != key.token_serial {
+                return Err(HsmError::KeyNotFound(format!(
+                    "{} (slot {} holds token {}, not {})",
+                    key.label, key.slot_id, token.serial, key.token_serial
+                )));
+            }
+            let object_id = hex::decode(&key.object_id).map_err(|e| HsmError::KeyNotFound(e.to_string()))?;
+            let private = find_one(
+                session,
+                &[
+                    Attribute::Class(ObjectClass::PRIVATE_KEY),
+                    Attribute::Label(key.label.as_bytes().to_vec()),
+                    Attribute::Id(object_id),
+                ],
+            )?
+            .ok_or_else(|| HsmError::KeyNotFound(key.label.clone()))?;
+
+            let mechanism = match attribute(session, private, AttributeType::KeyType)? {
+                Attribute::KeyType(key_type) if key_type == KeyType::EC_EDWARDS => Mechanism::Eddsa,
+                Attribute::KeyType(key_type) if key_type == KeyType::RSA => Mechanism::Sha256RsaPkcs,
+                Attribute::KeyType(key_type) if key_type == KeyType::EC => {
+                    match attribute(session, private, AttributeType::EcParams)? {
+                        Attribute::EcParams(params) if params == P384_PARAMS => Mechanism::EcdsaSha384,
+                        _ => Mechanism::EcdsaSha256,
+                    }
+                }
+                other => return Err(HsmError::SigningFailed(format!("Unsupported key {:?}", other))),
+            };
+            session
+                .sign(&mechanism, private, &data)
+                .map_err(|e| HsmError::SigningFailed(e.to_string()))
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ec_point_octet_string_unwrapped() {
+        let mut der = vec![0x04, 0x20];
+        der.extend([0xAB; 32]);
+        assert_eq!(octet_string_contents(&der), Some(&[0xAB; 32][..]));
+
+        let mut long = vec![0x04, 0x81, 0x61];
+        long.extend([0x04; 0x61]);
+        assert_eq!(octet_string_contents(&long).map(<[u8]>::len), Some(0x61));
+
+        assert_eq!(octet_string_contents(&[0x04, 0x05, 0x00]), None);
+        assert_eq!(octet_string_contents(&[0x30, 0x00]), None);
+    }
+}
@@ -0,0 +1,610 @@
+//! Rego / Cedar policy import adapter
+//!
+//! Organizations that already keep authorization rules in OPA Rego or
+//! Cedar can import them instead of re-entering them. Each translatable
+//! rule becomes a domain `Policy`, ready to be bound to people, units or
+//! roles with a `PolicyBinding`.
+//!
+//! ## Supported Subset
+//!
+//! ```text
+//! // Cedar
+//! @id("Key generation")
+//! permit (principal, action in [Action::"CanGenerateKeys"], resource)
+//! when { context.mfa_verified == true && context.witness_count >= 2 };
+//!
+//! # Rego
+//! package cim.keys
+//!
+//! allow if {
+//!     input.action == "CanSignCode"
+//!     input.context.yubikey_present == true
+//! }
+//! ```
+//!
+//! - `permit`/`allow` rules grant claims, `forbid`/`deny` rules deny them.
+//!   Both land at the same priority, where a deny wins, as in both languages.
+//! - Actions name a `PolicyClaim` variant (`CanGenerateKeys`) or an action
+//!   registered with `with_action_claim`.
+//! - Conditions test `context` attributes, joined with `&&` (Cedar) or one
+//!   per line (Rego): `mfa_verified == true`, `yubikey_present == true`,
+//!   `witness_count >= N` and `clearance >= "Secret"`.
+//! - A Cedar principal scope is kept in the policy's metadata; whom the
+//!   policy applies to is decided by binding it.
+//!
+//! Anything else makes the whole rule untranslatable. Dropping part of a
+//! rule would change what it allows, so such rules are reported in
+//! `PolicyImport::untranslatable` instead of being imported.
+//!
+//! **Category Theory Perspective:**
+//! - **Source Category**: Rego / Cedar rules
+//! - **Target Category**: Domain (Policy, PolicyClaim, PolicyCondition)
+//! - **Functor**: PolicyImportAdapter maps each supported rule to a Policy
+
+use std::collections::HashMap;
+
+use crate::domain::ids::BootstrapPersonId;
+use crate::domain::{Policy, PolicyClaim, PolicyCondition, SecurityClearance};
+
+/// Source language of imported policies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolicyLanguage {
+    Rego,
+    Cedar,
+}
+
+impl std::fmt::Display for PolicyLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyLanguage::Rego => write!(f, "Rego"),
+            PolicyLanguage::Cedar => write!(f, "Cedar"),
+        }
+    }
+}
+
+/// Errors that stop an import as a whole
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyImportError {
+    #[error("{language} syntax error: {reason}")]
+    Syntax {
+        language: PolicyLanguage,
+        reason: String,
+    },
+}
+
+/// A rule that could not be translated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UntranslatableRule {
+    /// Rule name (`@id`) or position (`rule 3`)
+    pub rule: String,
+    /// Rule text as written
+    pub source: String,
+    pub reason: String,
+}
+
+/// Result of importing a policy document
+#[derive(Debug, Clone, Default)]
+pub struct PolicyImport {
+    pub policies: Vec<Policy>,
+    pub untranslatable: Vec<UntranslatableRule>,
+}
+
+/// A rule reduced to the parts the domain model can express
+struct ParsedRule {
+    label: String,
+    source: String,
+    grants: bool,
+    actions: Vec<String>,
+    /// Conjunction of `(attribute, operator, value)` comparisons
+    comparisons: Vec<String>,
+    metadata: Vec<(String, String)>,
+}
+
+/// Adapter translating Rego and Cedar policies into domain policies
+#[derive(Debug, Clone)]
+pub struct PolicyImportAdapter {
+    created_by: BootstrapPersonId,
+    action_claims: HashMap<String, PolicyClaim>,
+    priority: i32,
+}
+
+impl PolicyImportAdapter {
+    /// Create an adapter; imported policies are recorded as created by `created_by`
+    pub fn new(created_by: BootstrapPersonId) -> Self {
+        Self {
+            created_by,
+            action_claims: HashMap::new(),
+            priority: 0,
+        }
+    }
+
+    /// Translate an action that is not a `PolicyClaim` name
+    pub fn with_action_claim(mut self, action: impl Into<String>, claim: PolicyClaim) -> Self {
+        self.action_claims.insert(action.into(), claim);
+        self
+    }
+
+    /// Priority given to every imported policy
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Import a policy document
+    pub fn import(&self, language: PolicyLanguage, source: &str) -> Result<PolicyImport, PolicyImportError> {
+        let (rules, mut untranslatable) = match language {
+            PolicyLanguage::Rego => parse_rego(source)?,
+            PolicyLanguage::Cedar => parse_cedar(source)?,
+        };
+
+        let mut policies = Vec::new();
+        for rule in rules {
+            match self.translate(language, &rule) {
+                Ok(policy) => policies.push(policy),
+                Err(reason) => untranslatable.push(UntranslatableRule {
+                    rule: rule.label,
+                    source: rule.source,
+                    reason,
+                }),
+            }
+        }
+        Ok(PolicyImport { policies, untranslatable })
+    }
+
+    fn translate(&self, language: PolicyLanguage, rule: &ParsedRule) -> Result<Policy, String> {
+        if rule.actions.is_empty() {
+            return Err("rule does not name an action".to_string());
+        }
+        let description = format!("Imported from {} ({})", language, if rule.grants { "grant" } else { "deny" });
+        let mut policy = Policy::new(rule.label.clone(), description, self.created_by).with_priority(self.priority);
+
+        for action in &rule.actions {
+            let claim = self
+                .claim_for(action)
+                .ok_or_else(|| format!("action `{}` does not map to a claim", action))?;
+            policy = if rule.grants {
+                policy.with_claim(claim)
+            } else {
+                policy.with_denied_claim(claim)
+            };
+        }
+        for comparison in &rule.comparisons {
+            let condition = translate_condition(comparison)
+                .ok_or_else(|| format!("unsupported condition `{}`", comparison))?;
+            policy = policy.with_condition(condition);
+        }
+
+        policy.metadata.insert("import.language".to_string(), language.to_string());
+        for (key, value) in &rule.metadata {
+            policy.metadata.insert(key.clone(), value.clone());
+        }
+        Ok(policy)
+    }
+
+    fn claim_for(&self, action: &str) -> Option<PolicyClaim> {
+        self.action_claims
+            .get(action)
+            .cloned()
+            .or_else(|| serde_json::from_value(serde_json::Value::String(action.to_string())).ok())
+    }
+}
+
+// ============================================================================
+// CONDITIONS
+// ============================================================================
+
+/// Translate a `context` comparison, already stripped of its prefix
+fn translate_condition(comparison: &str) -> Option<PolicyCondition> {
+    let compact: String = comparison.split_whitespace().collect();
+    let (attribute, operator, value) = [">=", "=="]
+        .iter()
+        .find_map(|op| compact.split_once(op).map(|(a, v)| (a, *op, v)))?;
+
+    match (attribute, operator, value) {
+        ("mfa_verified", "==", "true") => Some(PolicyCondition::MFAEnabled(true)),
+        ("yubikey_present", "==", "true") => Some(PolicyCondition::YubiKeyRequired(true)),
+        ("witness_count", ">=", count) => Some(PolicyCondition::RequiresWitness {
+            count: count.parse().ok()?,
+            witness_clearance: None,
+        }),
+        ("clearance", ">=", level) => {
+            let level: SecurityClearance = serde_json::from_str(level).ok()?;
+            Some(PolicyCondition::MinimumSecurityClearance(level))
+        }
+        _ => None,
+    }
+}
+
+// ============================================================================
+// CEDAR
+// ============================================================================
+
+fn parse_cedar(source: &str) -> Result<(Vec<ParsedRule>, Vec<UntranslatableRule>), PolicyImportError> {
+    let syntax = |reason: String| PolicyImportError::Syntax {
+        language: PolicyLanguage::Cedar,
+        reason,
+    };
+    let text = strip_comments(source, "//");
+    let mut rules = Vec::new();
+    let mut untranslatable = Vec::new();
+
+    let statements = split_top_level(&text, ';').map_err(syntax)?;
+    for (index, statement) in statements.iter().map(|s| s.trim()).filter(|s| !s.is_empty()).enumerate() {
+        let mut label = format!("rule {}", index + 1);
+        let mut rest = statement;
+
+        // Annotations: @id("...") names the policy
+        while let Some(annotation) = rest.strip_prefix('@') {
+            let open = annotation.find('(').ok_or_else(|| syntax(format!("malformed annotation in {}", label)))?;
+            let close = matching_close(annotation, open).ok_or_else(|| syntax(format!("unclosed annotation in {}", label)))?;
+            if annotation[..open].trim() == "id" {
+                if let Some(id) = quoted_literals(&annotation[open + 1..close]).into_iter().next() {
+                    label = id;
+                }
+            }
+            rest = annotation[close + 1..].trim_start();
+        }
+
+        let open = rest.find('(').ok_or_else(|| syntax(format!("missing scope in {}", label)))?;
+        let grants = match rest[..open].trim() {
+            "permit" => true,
+            "forbid" => false,
+            other => return Err(syntax(format!("unknown effect `{}` in {}", other, label))),
+        };
+        let close = matching_close(rest, open).ok_or_else(|| syntax(format!("unclosed scope in {}", label)))?;
+        let scope = split_top_level(&rest[open + 1..close], ',').map_err(syntax)?;
+
+        let mut rule = ParsedRule {
+            label: label.clone(),
+            source: statement.to_string(),
+            grants,
+            actions: Vec::new(),
+            comparisons: Vec::new(),
+            metadata: Vec::new(),
+        };
+        match cedar_rule(&mut rule, &scope, &rest[close + 1..]) {
+            Ok(()) => rules.push(rule),
+            Err(reason) => untranslatable.push(UntranslatableRule {
+                rule: label,
+                source: statement.to_string(),
+                reason,
+            }),
+        }
+    }
+    Ok((rules, untranslatable))
+}
+
+/// Fill in a Cedar rule from its scope and condition clauses
+fn cedar_rule(rule: &mut ParsedRule, scope: &[String], clauses: &str) -> Result<(), String> {
+    let [principal, action, resource] = scope else {
+        return Err(format!("scope has {} parts, expected principal, action, resource", scope.len()));
+    };
+
+    let principal = principal.trim();
+    if principal != "principal" {
+        rule.metadata.push(("cedar.principal".to_string(), principal.to_string()));
+    }
+    if resource.trim() != "resource" {
+        return Err("resource constraints have no equivalent".to_string());
+    }
+    let action = action.trim();
+    if !(action.starts_with("action") && action.contains("Action::")) {
+        return Err("action must be constrained to named actions".to_string());
+    }
+    rule.actions = quoted_literals(action);
+
+    let mut rest = clauses.trim();
+    while !rest.is_empty() {
+        let open = rest.find('{').ok_or_else(|| format!("unexpected `{}`", rest))?;
+        let close = matching_close(rest, open).ok_or("unclosed condition clause")?;
+        match rest[..open].trim() {
+            "when" => {
+                for conjunct in rest[open + 1..close].split("&&") {
+                    let conjunct = conjunct.trim();
+                    let attribute = conjunct
+                        .strip_prefix("context.")
+                        .ok_or_else(|| format!("unsupported condition `{}`", conjunct))?;
+                    rule.comparisons.push(attribute.to_string());
+                }
+            }
+            "unless" => return Err("`unless` clauses are not supported".to_string()),
+            other => return Err(format!("unknown clause `{}`", other)),
+        }
+        rest = rest[close + 1..].trim();
+    }
+    Ok(())
+}
+
+// ============================================================================
+// REGO
+// ============================================================================
+
+fn parse_rego(source: &str) -> Result<(Vec<ParsedRule>, Vec<UntranslatableRule>), PolicyImportError> {
+    let syntax = |reason: String| PolicyImportError::Syntax {
+        language: PolicyLanguage::Rego,
+        reason,
+    };
+    let text = strip_comments(source, "#");
+    let mut rules = Vec::new();
+    let mut untranslatable = Vec::new();
+    let mut package = None;
+    let mut rest = text.as_str();
+    let mut index = 0;
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        let line_end = rest.find('\n').unwrap_or(rest.len());
+        let line = rest[..line_end].trim();
+
+        if let Some(name) = line.strip_prefix("package ") {
+            package = Some(name.trim().to_string());
+            rest = &rest[line_end..];
+            continue;
+        }
+        if line.starts_with("import ") || line.starts_with("default ") {
+            rest = &rest[line_end..];
+            continue;
+        }
+
+        index += 1;
+        let label = format!("{} rule {}", package.as_deref().unwrap_or("rego"), index);
+        let Some(open) = rest[..line_end].find('{') else {
+            untranslatable.push(UntranslatableRule {
+                rule: label,
+                source: line.to_string(),
+                reason: "only `allow`/`deny` rules with a body are supported".to_string(),
+            });
+            rest = &rest[line_end..];
+            continue;
+        };
+        let close = matching_close(rest, open).ok_or_else(|| syntax(format!("unclosed body in {}", label)))?;
+        let source = rest[..=close].trim().to_string();
+        let head: String = rest[..open].split_whitespace().collect::<Vec<_>>().join(" ");
+        let body = &rest[open + 1..close];
+        rest = &rest[close + 1..];
+
+        let grants = match head.as_str() {
+            "allow" | "allow if" => true,
+            "deny" | "deny if" => false,
+            _ => {
+                untranslatable.push(UntranslatableRule {
+                    rule: label,
+                    source,
+                    reason: format!("rule head `{}` is not `allow` or `deny`", head),
+                });
+                continue;
+            }
+        };
+
+        let mut rule = ParsedRule {
+            label: label.clone(),
+            source: source.clone(),
+            grants,
+            actions: Vec::new(),
+            comparisons: Vec::new(),
+            metadata: package.iter().map(|p| ("rego.package".to_string(), p.clone())).collect(),
+        };
+        match rego_body(&mut rule, body) {
+            Ok(()) => rules.push(rule),
+            Err(reason) => untranslatable.push(UntranslatableRule { rule: label, source, reason }),
+        }
+    }
+    Ok((rules, untranslatable))
+}
+
+/// Fill in a Rego rule from its body, one expression per line or `;`
+fn rego_body(rule: &mut ParsedRule, body: &str) -> Result<(), String> {
+    for expression in body.split(['\n', ';']).map(str::trim).filter(|e| !e.is_empty()) {
+        let compact: String = expression.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let Some(action) = compact.strip_prefix("input.action == ") {
+            rule.actions.extend(quoted_literals(action));
+        } else if let Some(actions) = compact.strip_prefix("input.action in ") {
+            if !actions.starts_with('{') {
+                return Err(format!("unsupported expression `{}`", expression));
+            }
+            rule.actions.extend(quoted_literals(actions));
+        } else if let Some(attribute) = compact.strip_prefix("input.context.") {
+            rule.comparisons.push(attribute.to_string());
+        } else {
+            return Err(format!("unsupported expression `{}`", expression));
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// LEXICAL HELPERS
+// ============================================================================
+
+/// Remove line comments starting with `marker`, leaving string literals intact
+fn strip_comments(source: &str, marker: &str) -> String {
+    source
+        .lines()
+        .map(|line| {
+            let mut in_string = false;
+            let mut escaped = false;
+            for (i, c) in line.char_indices() {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' if in_string => escaped = true,
+                    '"' => in_string = !in_string,
+                    _ if !in_string && line[i..].starts_with(marker) => return &line[..i],
+                    _ => {}
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Split on `separator` outside brackets and string literals
+fn split_top_level(text: &str, separator: char) -> Result<Vec<String>, String> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.checked_sub(1).ok_or("unbalanced closing bracket")?,
+            c if c == separator && depth == 0 => {
+                parts.push(text[start..i].to_string());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    if in_string || depth != 0 {
+        return Err("unterminated string or bracket".to_string());
+    }
+    parts.push(text[start..].to_string());
+    Ok(parts)
+}
+
+/// Index of the bracket closing the one at `open`
+fn matching_close(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text[open..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Contents of the double-quoted literals in `text`
+fn quoted_literals(text: &str) -> Vec<String> {
+    text.split('"').skip(1).step_by(2).map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter() -> PolicyImportAdapter {
+        PolicyImportAdapter::new(BootstrapPersonId::new())
+    }
+
+    #[test]
+    fn test_cedar_permit_and_forbid() {
+        let source = r#"
+            // Key generation needs MFA and two witnesses
+            @id("Key generation")
+            permit (
+                principal in Role::"security-admin",
+                action in [Action::"CanGenerateKeys", Action::"rotate"],
+                resource
+            )
+            when { context.mfa_verified == true && context.witness_count >= 2 };
+
+            forbid (principal, action == Action::"CanExportKeys", resource);
+        "#;
+        let import = adapter()
+            .with_action_claim("rotate", PolicyClaim::CanRotateKeys)
+            .import(PolicyLanguage::Cedar, source)
+            .unwrap();
+
+        assert!(import.untranslatable.is_empty(), "{:?}", import.untranslatable);
+        assert_eq!(import.policies.len(), 2);
+
+        let permit = &import.policies[0];
+        assert_eq!(permit.name, "Key generation");
+        assert_eq!(permit.claims, vec![PolicyClaim::CanGenerateKeys, PolicyClaim::CanRotateKeys]);
+        assert_eq!(permit.conditions.len(), 2);
+        assert_eq!(
+            permit.metadata.get("cedar.principal").map(String::as_str),
+            Some("principal in Role::\"security-admin\"")
+        );
+
+        let forbid = &import.policies[1];
+        assert!(forbid.claims.is_empty());
+        assert_eq!(forbid.denied_claims, vec![PolicyClaim::CanExportKeys]);
+    }
+
+    #[test]
+    fn test_cedar_unsupported_rules_are_reported() {
+        let source = r#"
+            permit (principal, action == Action::"CanSignCode", resource == Repo::"core");
+            permit (principal, action == Action::"CanSignCode", resource) unless { context.on_call };
+            permit (principal, action == Action::"launch", resource);
+        "#;
+        let import = adapter().import(PolicyLanguage::Cedar, source).unwrap();
+
+        assert!(import.policies.is_empty());
+        let reasons: Vec<&str> = import.untranslatable.iter().map(|r| r.reason.as_str()).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "resource constraints have no equivalent",
+                "`unless` clauses are not supported",
+                "action `launch` does not map to a claim",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rego_allow_and_deny() {
+        let source = r#"
+            package cim.keys
+            import rego.v1
+
+            default allow := false
+
+            allow if {
+                input.action in {"CanSignCode", "CanAccessStaging"}
+                input.context.clearance >= "Confidential"
+            }
+
+            deny if {
+                input.action == "CanAccessProduction"   # never without a key
+                input.context.yubikey_present == true
+            }
+
+            is_admin if { input.user.role == "admin" }
+        "#;
+        let import = adapter().import(PolicyLanguage::Rego, source).unwrap();
+
+        assert_eq!(import.policies.len(), 2);
+        let allow = &import.policies[0];
+        assert_eq!(allow.claims, vec![PolicyClaim::CanSignCode, PolicyClaim::CanAccessStaging]);
+        assert!(matches!(
+            allow.conditions[..],
+            [PolicyCondition::MinimumSecurityClearance(SecurityClearance::Confidential)]
+        ));
+        assert_eq!(allow.metadata.get("rego.package").map(String::as_str), Some("cim.keys"));
+
+        assert_eq!(import.policies[1].denied_claims, vec![PolicyClaim::CanAccessProduction]);
+
+        assert_eq!(import.untranslatable.len(), 1);
+        assert!(import.untranslatable[0].reason.contains("is_admin"));
+    }
+
+    #[test]
+    fn test_unbalanced_source_is_a_syntax_error() {
+        let result = adapter().import(PolicyLanguage::Cedar, "permit (principal, action, resource");
+        assert!(matches!(result, Err(PolicyImportError::Syntax { .. })));
+    }
+}
@@ -0,0 +1,270 @@
+//! Removable media adapter using lsblk and udisksctl
+//!
+//! This adapter implements the RemovableMediaPort trait on Linux. Devices
+//! are discovered with `lsblk`, which reads the udev database, and mounted
+//! and unmounted with `udisksctl`, so an unprivileged operator can prepare
+//! a card the same way the desktop would.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::ports::removable_media::{MediaRequirements, RemovableDevice, RemovableMediaError, RemovableMediaPort};
+
+/// Columns requested from lsblk
+const LSBLK_COLUMNS: &str = "NAME,TYPE,RM,HOTPLUG,RO,SIZE,FSTYPE,LABEL,UUID,MOUNTPOINT,FSAVAIL,MODEL,SERIAL";
+
+/// Removable media adapter using lsblk and udisksctl
+#[derive(Clone, Default)]
+pub struct LsblkMediaAdapter;
+
+impl LsblkMediaAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Current state of the device at `path`
+    fn refresh(&self, path: &Path) -> Result<RemovableDevice, RemovableMediaError> {
+        list_removable()?
+            .into_iter()
+            .find(|device| device.path == path)
+            .ok_or(RemovableMediaError::NoDeviceFound)
+    }
+}
+
+#[async_trait]
+impl RemovableMediaPort for LsblkMediaAdapter {
+    async fn list_devices(&self) -> Result<Vec<RemovableDevice>, RemovableMediaError> {
+        let devices = list_removable()?;
+        if devices.is_empty() {
+            Err(RemovableMediaError::NoDeviceFound)
+        } else {
+            Ok(devices)
+        }
+    }
+
+    async fn prepare_export(
+        &self,
+        device: &RemovableDevice,
+        requirements: &MediaRequirements,
+    ) -> Result<PathBuf, RemovableMediaError> {
+        let mut device = self.refresh(&device.path)?;
+        device.check_format(requirements)?;
+
+        if device.mount_point.is_none() {
+            let output = run(Command::new("udisksctl")
+                .arg("mount")
+                .arg("--block-device")
+                .arg(&device.path)
+                .arg("--no-user-interaction"))?;
+            tracing::info!("{}", output.trim());
+            device = self.refresh(&device.path)?;
+        }
+
+        device.check_free_space(requirements)?;
+        device
+            .mount_point
+            .ok_or_else(|| RemovableMediaError::NotMounted(device.path.display().to_string()))
+    }
+
+    async fn finish_export(&self, device: &RemovableDevice) -> Result<(), RemovableMediaError> {
+        let device = self.refresh(&device.path)?;
+        let Some(mount_point) = &device.mount_point else {
+            return Ok(());
+        };
+
+        run(Command::new("sync").arg("--file-system").arg(mount_point))?;
+        run(Command::new("udisksctl")
+            .arg("unmount")
+            .arg("--block-device")
+            .arg(&device.path)
+            .arg("--no-user-interaction"))?;
+
+        // Powering off fails while another partition of the disk is still
+        // mounted; the unmounted filesystem is safe either way
+        if let Err(e) = run(Command::new("udisksctl")
+            .arg("power-off")
+            .arg("--block-device")
+            .arg(&device.disk)
+            .arg("--no-user-interaction"))
+        {
+            tracing::warn!("Could not power off {}: {}", device.disk.display(), e);
+        }
+        Ok(())
+    }
+}
+
+/// Run a command, returning its stdout
+fn run(command: &mut Command) -> Result<String, RemovableMediaError> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .output()
+        .map_err(|e| RemovableMediaError::CommandFailed(format!("Failed to run {}: {}", program, e)))?;
+    if !output.status.success() {
+        return Err(RemovableMediaError::CommandFailed(format!(
+            "{}: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn list_removable() -> Result<Vec<RemovableDevice>, RemovableMediaError> {
+    let output = run(Command::new("lsblk").args(["--json", "--bytes", "--paths", "--output", LSBLK_COLUMNS]))?;
+    parse_lsblk(&output)
+}
+
+#[derive(Debug, Deserialize)]
+struct LsblkOutput {
+    blockdevices: Vec<LsblkDevice>,
+}
+
+/// One lsblk row; older util-linux versions print flags and sizes as strings
+#[derive(Debug, Deserialize)]
+struct LsblkDevice {
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    rm: Value,
+    #[serde(default)]
+    hotplug: Value,
+    #[serde(default)]
+    ro: Value,
+    #[serde(default)]
+    size: Value,
+    fstype: Option<String>,
+    label: Option<String>,
+    uuid: Option<String>,
+    mountpoint: Option<String>,
+    #[serde(default)]
+    fsavail: Value,
+    model: Option<String>,
+    serial: Option<String>,
+    #[serde(default)]
+    children: Vec<LsblkDevice>,
+}
+
+/// Removable partitions, and removable disks without a partition table
+fn parse_lsblk(json: &str) -> Result<Vec<RemovableDevice>, RemovableMediaError> {
+    let output: LsblkOutput =
+        serde_json::from_str(json).map_err(|e| RemovableMediaError::ParseError(format!("Invalid lsblk output: {}", e)))?;
+
+    let mut devices = Vec::new();
+    for disk in output.blockdevices {
+        if disk.kind != "disk" || !(flag(&disk.rm) || flag(&disk.hotplug)) {
+            continue;
+        }
+        if disk.children.is_empty() {
+            devices.push(device(&disk, &disk));
+        } else {
+            devices.extend(disk.children.iter().filter(|part| part.kind == "part").map(|part| device(&disk, part)));
+        }
+    }
+    Ok(devices)
+}
+
+fn device(disk: &LsblkDevice, part: &LsblkDevice) -> RemovableDevice {
+    let text = |value: &Option<String>| value.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(String::from);
+    RemovableDevice {
+        path: PathBuf::from(&part.name),
+        disk: PathBuf::from(&disk.name),
+        model: text(&disk.model),
+        serial: text(&disk.serial),
+        size_bytes: bytes(&part.size).unwrap_or(0),
+        filesystem: text(&part.fstype),
+        label: text(&part.label),
+        uuid: text(&part.uuid),
+        mount_point: text(&part.mountpoint).map(PathBuf::from),
+        free_bytes: bytes(&part.fsavail),
+        read_only: flag(&disk.ro) || flag(&part.ro),
+    }
+}
+
+fn flag(value: &Value) -> bool {
+    match value {
+        Value::Bool(flag) => *flag,
+        Value::String(s) => s == "1",
+        Value::Number(n) => n.as_u64() == Some(1),
+        _ => false,
+    }
+}
+
+fn bytes(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LSBLK: &str = r#"{
+        "blockdevices": [
+            {"name": "/dev/nvme0n1", "type": "disk", "rm": false, "hotplug": false, "ro": false,
+             "size": 512110190592, "fstype": null, "label": null, "uuid": null, "mountpoint": null,
+             "fsavail": null, "model": "Samsung SSD 980", "serial": "S64", "children": [
+                {"name": "/dev/nvme0n1p1", "type": "part", "rm": false, "hotplug": false, "ro": false,
+                 "size": 536870912, "fstype": "vfat", "label": null, "uuid": "A1B2", "mountpoint": "/boot",
+                 "fsavail": 400000000, "model": null, "serial": null}
+            ]},
+            {"name": "/dev/sdb", "type": "disk", "rm": true, "hotplug": true, "ro": false,
+             "size": 31914983424, "fstype": null, "label": null, "uuid": null, "mountpoint": null,
+             "fsavail": null, "model": "SD/MMC Reader   ", "serial": "000000001", "children": [
+                {"name": "/dev/sdb1", "type": "part", "rm": true, "hotplug": true, "ro": false,
+                 "size": 31913934848, "fstype": "ext4", "label": "CIMKEYS", "uuid": "5e2c",
+                 "mountpoint": "/run/media/op/CIMKEYS", "fsavail": 30000000000, "model": null, "serial": null}
+            ]}
+        ]
+    }"#;
+
+    #[test]
+    fn test_parse_lists_removable_partitions() {
+        let devices = parse_lsblk(LSBLK).unwrap();
+        assert_eq!(devices.len(), 1);
+
+        let card = &devices[0];
+        assert_eq!(card.path, PathBuf::from("/dev/sdb1"));
+        assert_eq!(card.disk, PathBuf::from("/dev/sdb"));
+        assert_eq!(card.model.as_deref(), Some("SD/MMC Reader"));
+        assert_eq!(card.filesystem.as_deref(), Some("ext4"));
+        assert_eq!(card.mount_point, Some(PathBuf::from("/run/media/op/CIMKEYS")));
+        assert_eq!(card.free_bytes, Some(30_000_000_000));
+
+        let requirements = MediaRequirements::for_export(1024);
+        card.check_format(&requirements).unwrap();
+        card.check_free_space(&requirements).unwrap();
+        let too_big = MediaRequirements::for_export(20_000_000_000);
+        assert!(matches!(
+            card.check_free_space(&too_big),
+            Err(RemovableMediaError::InsufficientSpace { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_accepts_string_columns() {
+        // util-linux before 2.33 prints flags and sizes as strings
+        let devices = parse_lsblk(
+            r#"{"blockdevices": [
+                {"name": "/dev/sdc", "type": "disk", "rm": "1", "hotplug": "1", "ro": "0", "size": "8000000000",
+                 "fstype": null, "label": null, "uuid": null, "mountpoint": null, "fsavail": null,
+                 "model": "Card", "serial": null}
+            ]}"#,
+        )
+        .unwrap();
+
+        let card = &devices[0];
+        assert_eq!(card.path, card.disk);
+        assert_eq!(card.size_bytes, 8_000_000_000);
+        assert!(matches!(
+            card.check_format(&MediaRequirements::default()),
+            Err(RemovableMediaError::UnsupportedFilesystem { .. })
+        ));
+    }
+}
@@ -0,0 +1,230 @@
+//! Saga store adapters
+//!
+//! [`InMemorySagaStore`] is for tests. [`FileSagaStore`] keeps one JSON
+//! file per saga in a directory, typically on the encrypted volume of the
+//! air-gapped machine, so a ceremony survives a power cycle:
+//!
+//! ```text
+//! <dir>/<saga_id>.json   { record, events }
+//! ```
+//!
+//! Files are written to a temporary name, synced and renamed over the old
+//! one, so a crash mid-write leaves the previous checkpoint intact.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use tokio::io::AsyncWriteExt;
+use uuid::Uuid;
+
+use crate::events::SagaEvents;
+use crate::ports::saga_store::{SagaRecord, SagaStore, SagaStoreError};
+
+/// A saga's state and event history, as stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredSaga {
+    record: SagaRecord,
+    events: Vec<SagaEvents>,
+}
+
+impl StoredSaga {
+    /// Apply a save on top of what is stored, checking the version
+    fn advance(stored: Option<Self>, record: &SagaRecord, events: &[SagaEvents]) -> Result<Self, SagaStoreError> {
+        let (version, mut history) = stored.map_or((0, Vec::new()), |s| (s.record.version, s.events));
+        SagaStoreError::check_version(record.saga_id, version, record.version)?;
+        history.extend_from_slice(events);
+        Ok(Self { record: record.clone(), events: history })
+    }
+}
+
+/// In-memory saga store for testing
+#[derive(Clone, Default)]
+pub struct InMemorySagaStore {
+    sagas: Arc<RwLock<HashMap<Uuid, StoredSaga>>>,
+}
+
+impl InMemorySagaStore {
+    /// Create an empty saga store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+fn poisoned(e: impl std::fmt::Display) -> SagaStoreError {
+    SagaStoreError::StorageError(format!("Lock poisoned: {}", e))
+}
+
+#[async_trait]
+impl SagaStore for InMemorySagaStore {
+    async fn save(&self, record: &SagaRecord, events: &[SagaEvents]) -> Result<(), SagaStoreError> {
+        let mut sagas = self.sagas.write().map_err(poisoned)?;
+        let stored = StoredSaga::advance(sagas.get(&record.saga_id).cloned(), record, events)?;
+        sagas.insert(record.saga_id, stored);
+        Ok(())
+    }
+
+    async fn load(&self, saga_id: Uuid) -> Result<Option<SagaRecord>, SagaStoreError> {
+        Ok(self.sagas.read().map_err(poisoned)?
+            .get(&saga_id)
+            .map(|s| s.record.clone()))
+    }
+
+    async fn events(&self, saga_id: Uuid) -> Result<Vec<SagaEvents>, SagaStoreError> {
+        Ok(self.sagas.read().map_err(poisoned)?
+            .get(&saga_id)
+            .map(|s| s.events.clone())
+            .unwrap_or_default())
+    }
+
+    async fn incomplete(&self) -> Result<Vec<SagaRecord>, SagaStoreError> {
+        let mut records: Vec<SagaRecord> = self.sagas.read().map_err(poisoned)?
+            .values()
+            .filter(|s| !s.record.terminal)
+            .map(|s| s.record.clone())
+            .collect();
+        records.sort_by_key(|r| r.updated_at);
+        Ok(records)
+    }
+
+    async fn delete(&self, saga_id: Uuid) -> Result<(), SagaStoreError> {
+        self.sagas.write().map_err(poisoned)?.remove(&saga_id);
+        Ok(())
+    }
+}
+
+/// Directory-backed saga store
+pub struct FileSagaStore {
+    dir: PathBuf,
+    /// Serializes read-modify-write of saga files within this process
+    write_lock: tokio::sync::Mutex<()>,
+}
+
+impl FileSagaStore {
+    /// Store sagas under `dir`, creating it if needed
+    pub async fn open(dir: impl AsRef<Path>) -> Result<Self, SagaStoreError> {
+        let dir = dir.as_ref().to_path_buf();
+        tokio::fs::create_dir_all(&dir).await.map_err(storage)?;
+        Ok(Self { dir, write_lock: tokio::sync::Mutex::new(()) })
+    }
+
+    fn path(&self, saga_id: Uuid) -> PathBuf {
+        self.dir.join(format!("{}.json", saga_id))
+    }
+
+    async fn read(&self, saga_id: Uuid) -> Result<Option<StoredSaga>, SagaStoreError> {
+        match tokio::fs::read(self.path(saga_id)).await {
+            Ok(data) => serde_json::from_slice(&data)
+                .map(Some)
+                .map_err(|e| SagaStoreError::SerializationError(e.to_string())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(storage(e)),
+        }
+    }
+
+    async fn write(&self, stored: &StoredSaga) -> Result<(), SagaStoreError> {
+        let data = serde_json::to_vec_pretty(stored)
+            .map_err(|e| SagaStoreError::SerializationError(e.to_string()))?;
+        let path = self.path(stored.record.saga_id);
+        let tmp = path.with_extension("json.tmp");
+
+        let mut file = tokio::fs::File::create(&tmp).await.map_err(storage)?;
+        file.write_all(&data).await.map_err(storage)?;
+        file.sync_all().await.map_err(storage)?;
+        drop(file);
+        tokio::fs::rename(&tmp, &path).await.map_err(storage)
+    }
+}
+
+fn storage(e: std::io::Error) -> SagaStoreError {
+    SagaStoreError::StorageError(e.to_string())
+}
+
+#[async_trait]
+impl SagaStore for FileSagaStore {
+    async fn save(&self, record: &SagaRecord, events: &[SagaEvents]) -> Result<(), SagaStoreError> {
+        let _guard = self.write_lock.lock().await;
+        let stored = StoredSaga::advance(self.read(record.saga_id).await?, record, events)?;
+        self.write(&stored).await
+    }
+
+    async fn load(&self, saga_id: Uuid) -> Result<Option<SagaRecord>, SagaStoreError> {
+        Ok(self.read(saga_id).await?.map(|s| s.record))
+    }
+
+    async fn events(&self, saga_id: Uuid) -> Result<Vec<SagaEvents>, SagaStoreError> {
+        Ok(self.read(saga_id).await?.map(|s| s.events).unwrap_or_default())
+    }
+
+    async fn incomplete(&self) -> Result<Vec<SagaRecord>, SagaStoreError> {
+        let mut entries = tokio::fs::read_dir(&self.dir).await.map_err(storage)?;
+        let mut records = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(storage)? {
+            let saga_id = entry.path()
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|id| Uuid::parse_str(id).ok());
+            let Some(saga_id) = saga_id else { continue };
+            if let Some(stored) = self.read(saga_id).await? {
+                if !stored.record.terminal {
+                    records.push(stored.record);
+                }
+            }
+        }
+        records.sort_by_key(|r| r.updated_at);
+        Ok(records)
+    }
+
+    async fn delete(&self, saga_id: Uuid) -> Result<(), SagaStoreError> {
+        let _guard = self.write_lock.lock().await;
+        match tokio::fs::remove_file(self.path(saga_id)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(storage(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn record(saga_id: Uuid, version: u64, terminal: bool) -> SagaRecord {
+        SagaRecord {
+            saga_id,
+            saga_type: "PersonOnboarding".to_string(),
+            correlation_id: saga_id,
+            version,
+            current_step: "GeneratingKey".to_string(),
+            status: "Generating key".to_string(),
+            terminal,
+            updated_at: Utc::now(),
+            state: serde_json::json!({ "step": version }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_store_survives_reopen_and_rejects_stale_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let (running, finished) = (Uuid::now_v7(), Uuid::now_v7());
+        {
+            let store = FileSagaStore::open(dir.path()).await.unwrap();
+            store.save(&record(running, 1, false), &[]).await.unwrap();
+            store.save(&record(running, 2, false), &[]).await.unwrap();
+            store.save(&record(finished, 1, true), &[]).await.unwrap();
+
+            let stale = store.save(&record(running, 2, false), &[]).await;
+            assert_eq!(stale, Err(SagaStoreError::VersionConflict { saga_id: running, stored: 2, saving: 2 }));
+        }
+
+        let reopened = FileSagaStore::open(dir.path()).await.unwrap();
+        let incomplete = reopened.incomplete().await.unwrap();
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].saga_id, running);
+        assert_eq!(incomplete[0].version, 2);
+        assert_eq!(reopened.load(finished).await.unwrap().map(|r| r.terminal), Some(true));
+    }
+}
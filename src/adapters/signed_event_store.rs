@@ -0,0 +1,136 @@
+//! Signing event store decorator
+//!
+//! Wraps any [`EventStore`]: envelopes are signed on append and every
+//! loaded envelope must verify before it reaches an aggregate or
+//! projection. The wrapped store never sees an unsigned event from this
+//! adapter, and an event edited in the backing store fails the load.
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::events::{EventEnvelope, EventSigner, EventVerifier};
+use crate::ports::event_store::{EventStore, EventStoreError, ExpectedSequence, StoredEvent};
+
+/// Event store that signs on append and verifies on load
+pub struct SignedEventStore<E> {
+    inner: E,
+    signer: EventSigner,
+    verifier: EventVerifier,
+}
+
+impl<E: EventStore> SignedEventStore<E> {
+    /// Wrap `inner`, signing with `signer`
+    ///
+    /// The signer's own key is added to `verifier`; trust any other keys
+    /// that wrote to the stream (earlier ceremonies, rotated operators)
+    /// before passing it in.
+    pub fn new(inner: E, signer: EventSigner, verifier: EventVerifier) -> Self {
+        let verifier = verifier.trust(signer.key_id(), signer.verifying_key());
+        Self { inner, signer, verifier }
+    }
+
+    /// The wrapped store
+    pub fn inner(&self) -> &E {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<E: EventStore> EventStore for SignedEventStore<E> {
+    async fn append(
+        &self,
+        aggregate_id: Uuid,
+        expected: ExpectedSequence,
+        events: &[EventEnvelope],
+    ) -> Result<u64, EventStoreError> {
+        let signed = events
+            .iter()
+            .map(|envelope| self.signer.sign(envelope.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.inner.append(aggregate_id, expected, &signed).await
+    }
+
+    async fn load_after(
+        &self,
+        aggregate_id: Uuid,
+        sequence: u64,
+    ) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let events = self.inner.load_after(aggregate_id, sequence).await?;
+        for event in &events {
+            self.verifier.verify(&event.envelope)?;
+        }
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryEventStore;
+    use crate::events::{DomainEvent, EventSignatureError, KeyEvents, KeyStoredOfflineEvent};
+    use ed25519_dalek::SigningKey;
+
+    fn signer() -> EventSigner {
+        EventSigner::new(Uuid::now_v7(), SigningKey::from_bytes(&rand::random()))
+    }
+
+    fn stored_offline(key_id: Uuid, checksum: &str) -> EventEnvelope {
+        let correlation_id = Uuid::now_v7();
+        EventEnvelope::new(
+            DomainEvent::Key(KeyEvents::KeyStoredOffline(KeyStoredOfflineEvent {
+                key_id,
+                partition_id: Uuid::now_v7(),
+                encrypted: true,
+                stored_at: chrono::Utc::now(),
+                checksum: checksum.to_string(),
+                correlation_id,
+                causation_id: None,
+            })),
+            correlation_id,
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_appended_events_are_signed_and_verified() {
+        let backing = InMemoryEventStore::new();
+        let signer = signer();
+        let signer_key_id = signer.key_id();
+        let store = SignedEventStore::new(backing.clone(), signer, EventVerifier::new());
+        let key_id = Uuid::now_v7();
+
+        store.append(key_id, ExpectedSequence::NoEvents, &[stored_offline(key_id, "sha256:00")]).await.unwrap();
+
+        let raw = backing.load(key_id).await.unwrap();
+        assert_eq!(raw[0].envelope.signature.as_ref().unwrap().signer_key_id, signer_key_id);
+        assert_eq!(store.load(key_id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_foreign_events_fail_the_load() {
+        let backing = InMemoryEventStore::new();
+        let store = SignedEventStore::new(backing.clone(), signer(), EventVerifier::new());
+        let key_id = Uuid::now_v7();
+        store.append(key_id, ExpectedSequence::NoEvents, &[stored_offline(key_id, "sha256:00")]).await.unwrap();
+
+        // Written straight to the backing stream, bypassing the signer
+        let rogue = signer();
+        let forged = rogue.sign(stored_offline(key_id, "sha256:ff")).unwrap();
+        backing.append(key_id, ExpectedSequence::Any, &[forged]).await.unwrap();
+
+        let err = store.load(key_id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EventStoreError::SignatureError(EventSignatureError::UnknownSigner { key_id, .. }) if key_id == rogue.key_id()
+        ));
+
+        // Unsigned events are rejected the same way
+        let other_key = Uuid::now_v7();
+        backing.append(other_key, ExpectedSequence::NoEvents, &[stored_offline(other_key, "sha256:00")]).await.unwrap();
+        assert!(matches!(
+            store.load(other_key).await,
+            Err(EventStoreError::SignatureError(EventSignatureError::Unsigned { .. }))
+        ));
+        assert_eq!(store.inner().len(), 3);
+    }
+}
@@ -0,0 +1,478 @@
+//! SSH key adapter backed by the `ssh-key` crate
+//!
+//! Real OpenSSH key handling for production use:
+//! - Private keys in the OpenSSH format, optionally encrypted with
+//!   bcrypt-pbkdf + aes256-ctr (what `ssh-keygen -p` produces)
+//! - Signatures in the SSHSIG format (`ssh-keygen -Y sign`)
+//! - SHA-256 fingerprints
+//!
+//! `SshPrivateKey::data` holds the unencrypted OpenSSH private key and
+//! `SshPublicKey::data` the RFC 4253 wire encoding of the public key.
+//!
+//! **Category Theory Perspective:**
+//! - **Source Category**: OpenSSH (keys, SSHSIG signatures)
+//! - **Target Category**: Domain (authentication operations)
+//! - **Functor**: SshKeysAdapter maps ssh-key operations to domain operations
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ssh_key::private::{KeypairData, RsaKeypair};
+use ssh_key::public::{EcdsaPublicKey, Ed25519PublicKey};
+use ssh_key::{
+    private, public, Algorithm, EcdsaCurve, HashAlg, LineEnding, PrivateKey, PublicKey, SshSig,
+};
+
+use crate::ports::rng::{CryptoRngPort, OsCryptoRng};
+use crate::ports::ssh::*;
+use crate::ports::yubikey::{FidoAlgorithm, FidoCredential, SecureString};
+
+/// SSHSIG namespace used for domain signatures
+const SIGNATURE_NAMESPACE: &str = "cim-keys";
+
+/// Default RSA modulus size
+const DEFAULT_RSA_BITS: usize = 4096;
+
+/// SSH key adapter using the `ssh-key` crate
+#[derive(Debug, Clone)]
+pub struct SshKeysAdapter {
+    rng: Arc<dyn CryptoRngPort>,
+}
+
+impl Default for SshKeysAdapter {
+    fn default() -> Self {
+        Self {
+            rng: Arc::new(OsCryptoRng),
+        }
+    }
+}
+
+impl SshKeysAdapter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw key generation and encryption randomness from `rng`
+    pub fn with_rng(mut self, rng: Arc<dyn CryptoRngPort>) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    fn key_type(algorithm: &Algorithm) -> Result<SshKeyType, SshError> {
+        match algorithm {
+            Algorithm::Ed25519 => Ok(SshKeyType::Ed25519),
+            Algorithm::Ecdsa { .. } => Ok(SshKeyType::Ecdsa),
+            Algorithm::Rsa { .. } => Ok(SshKeyType::Rsa),
+            Algorithm::Dsa => Ok(SshKeyType::Dsa),
+            Algorithm::SkEd25519 => Ok(SshKeyType::Ed25519Sk),
+            Algorithm::SkEcdsaSha2NistP256 => Ok(SshKeyType::EcdsaSk),
+            other => Err(SshError::InvalidKey(format!("Unsupported algorithm {}", other))),
+        }
+    }
+
+    fn to_public(key: &PublicKey) -> Result<SshPublicKey, SshError> {
+        let comment = key.comment();
+        Ok(SshPublicKey {
+            key_type: Self::key_type(&key.algorithm())?,
+            data: key
+                .to_bytes()
+                .map_err(|e| SshError::ExportFailed(e.to_string()))?,
+            comment: (!comment.is_empty()).then(|| comment.to_string()),
+        })
+    }
+
+    fn to_private(key: &PrivateKey) -> Result<SshPrivateKey, SshError> {
+        let openssh = key
+            .to_openssh(LineEnding::LF)
+            .map_err(|e| SshError::ExportFailed(e.to_string()))?;
+        Ok(SshPrivateKey {
+            key_type: Self::key_type(&key.algorithm())?,
+            data: openssh.as_bytes().into(),
+            public_key: Self::to_public(key.public_key())?,
+            is_encrypted: false,
+        })
+    }
+
+    fn decode_private(private_key: &SshPrivateKey) -> Result<PrivateKey, SshError> {
+        let key = PrivateKey::from_openssh(&private_key.data)
+            .map_err(|e| SshError::ParsingFailed(e.to_string()))?;
+        if key.is_encrypted() {
+            return Err(SshError::InvalidKey(
+                "Private key must be decrypted with parse_private_key first".to_string(),
+            ));
+        }
+        Ok(key)
+    }
+
+    fn decode_public(public_key: &SshPublicKey) -> Result<PublicKey, SshError> {
+        let mut key = PublicKey::from_bytes(&public_key.data)
+            .map_err(|e| SshError::ParsingFailed(e.to_string()))?;
+        if let Some(comment) = &public_key.comment {
+            key.set_comment(comment.as_str());
+        }
+        Ok(key)
+    }
+
+    fn passphrase_str(passphrase: &SecureString) -> Result<&str, SshError> {
+        let passphrase =
+            std::str::from_utf8(passphrase.as_bytes()).map_err(|_| SshError::InvalidPassphrase)?;
+        if passphrase.is_empty() {
+            return Err(SshError::InvalidPassphrase);
+        }
+        Ok(passphrase)
+    }
+}
+
+#[async_trait]
+impl SshKeyPort for SshKeysAdapter {
+    /// **Functor Mapping**: (key_type, bits) → Keypair
+    async fn generate_keypair(
+        &self,
+        key_type: SshKeyType,
+        bits: Option<u32>,
+        comment: Option<String>,
+    ) -> Result<SshKeypair, SshError> {
+        let generation_failed = |e: ssh_key::Error| SshError::GenerationFailed(e.to_string());
+        let mut rng = self.rng.rng();
+
+        let mut key = match key_type {
+            SshKeyType::Ed25519 => {
+                PrivateKey::random(&mut rng, Algorithm::Ed25519).map_err(generation_failed)?
+            }
+            SshKeyType::Ecdsa => {
+                let curve = match bits.unwrap_or(256) {
+                    256 => EcdsaCurve::NistP256,
+                    384 => EcdsaCurve::NistP384,
+                    521 => EcdsaCurve::NistP521,
+                    other => {
+                        return Err(SshError::GenerationFailed(format!(
+                            "Unsupported ECDSA key size {}",
+                            other
+                        )))
+                    }
+                };
+                PrivateKey::random(&mut rng, Algorithm::Ecdsa { curve }).map_err(generation_failed)?
+            }
+            SshKeyType::Rsa => {
+                let bits = bits.map(|b| b as usize).unwrap_or(DEFAULT_RSA_BITS);
+                let keypair = RsaKeypair::random(&mut rng, bits).map_err(generation_failed)?;
+                PrivateKey::new(KeypairData::from(keypair), "").map_err(generation_failed)?
+            }
+            other => return Err(SshError::UnsupportedKeyType(other)),
+        };
+
+        if let Some(comment) = &comment {
+            key.set_comment(comment.as_str());
+        }
+
+        let private_key = Self::to_private(&key)?;
+        Ok(SshKeypair {
+            public_key: private_key.public_key.clone(),
+            private_key,
+            comment,
+        })
+    }
+
+    /// **Functor Mapping**: FidoCredential → Keypair
+    async fn security_key_keypair(
+        &self,
+        credential: &FidoCredential,
+        comment: Option<String>,
+    ) -> Result<SshKeypair, SshError> {
+        let invalid = |e: ssh_key::Error| SshError::InvalidKey(e.to_string());
+        let application = credential.application.clone();
+
+        let keypair = match credential.algorithm {
+            FidoAlgorithm::EdDsa => {
+                let public_key = Ed25519PublicKey::try_from(credential.public_key.as_slice())
+                    .map_err(invalid)?;
+                KeypairData::SkEd25519(
+                    private::SkEd25519::new(
+                        public::SkEd25519::new(public_key, application),
+                        credential.flags,
+                        credential.credential_id.clone(),
+                    )
+                    .map_err(invalid)?,
+                )
+            }
+            FidoAlgorithm::Es256 => {
+                let ec_point = match EcdsaPublicKey::from_sec1_bytes(&credential.public_key)
+                    .map_err(invalid)?
+                {
+                    EcdsaPublicKey::NistP256(point) => point,
+                    _ => {
+                        return Err(SshError::InvalidKey(
+                            "Security key credential is not a P-256 key".to_string(),
+                        ))
+                    }
+                };
+                KeypairData::SkEcdsaSha2NistP256(
+                    private::SkEcdsaSha2NistP256::new(
+                        public::SkEcdsaSha2NistP256::new(ec_point, application),
+                        credential.flags,
+                        credential.credential_id.clone(),
+                    )
+                    .map_err(invalid)?,
+                )
+            }
+        };
+
+        let key = PrivateKey::new(keypair, comment.clone().unwrap_or_default())
+            .map_err(|e| SshError::GenerationFailed(e.to_string()))?;
+        let private_key = Self::to_private(&key)?;
+        Ok(SshKeypair {
+            public_key: private_key.public_key.clone(),
+            private_key,
+            comment,
+        })
+    }
+
+    /// **Functor Mapping**: bytes → PublicKey (object construction)
+    async fn parse_public_key(&self, key_data: &[u8]) -> Result<SshPublicKey, SshError> {
+        let key = match std::str::from_utf8(key_data) {
+            Ok(text) => PublicKey::from_openssh(text.trim()),
+            Err(_) => PublicKey::from_bytes(key_data),
+        }
+        .map_err(|e| SshError::ParsingFailed(e.to_string()))?;
+
+        Self::to_public(&key)
+    }
+
+    /// **Functor Mapping**: (bytes, passphrase) → PrivateKey (object construction)
+    ///
+    /// Encrypted keys are decrypted with the passphrase; the returned key
+    /// holds the decrypted material.
+    async fn parse_private_key(
+        &self,
+        key_data: &[u8],
+        passphrase: Option<&SecureString>,
+    ) -> Result<SshPrivateKey, SshError> {
+        let key = PrivateKey::from_openssh(key_data)
+            .map_err(|e| SshError::ParsingFailed(e.to_string()))?;
+
+        let key = if key.is_encrypted() {
+            let passphrase = passphrase.ok_or(SshError::InvalidPassphrase)?;
+            key.decrypt(Self::passphrase_str(passphrase)?)
+                .map_err(|_| SshError::InvalidPassphrase)?
+        } else {
+            key
+        };
+
+        Self::to_private(&key)
+    }
+
+    /// **Functor Mapping**: (key, data) → Signature
+    async fn sign(
+        &self,
+        private_key: &SshPrivateKey,
+        data: &[u8],
+    ) -> Result<SshSignature, SshError> {
+        let key = Self::decode_private(private_key)?;
+        let signature = key
+            .sign(SIGNATURE_NAMESPACE, HashAlg::Sha512, data)
+            .map_err(|e| SshError::SignatureFailed(e.to_string()))?;
+        let pem = signature
+            .to_pem(LineEnding::LF)
+            .map_err(|e| SshError::SignatureFailed(e.to_string()))?;
+
+        Ok(SshSignature {
+            algorithm: signature.algorithm().to_string(),
+            data: pem.into_bytes(),
+        })
+    }
+
+    /// **Functor Mapping**: (key, data, signature) → bool
+    async fn verify(
+        &self,
+        public_key: &SshPublicKey,
+        data: &[u8],
+        signature: &SshSignature,
+    ) -> Result<bool, SshError> {
+        let key = Self::decode_public(public_key)?;
+        let signature = SshSig::from_pem(&signature.data)
+            .map_err(|e| SshError::VerificationFailed(e.to_string()))?;
+
+        Ok(key.verify(SIGNATURE_NAMESPACE, data, &signature).is_ok())
+    }
+
+    /// **Functor Mapping**: PublicKey → String (serialization)
+    async fn format_authorized_key(
+        &self,
+        public_key: &SshPublicKey,
+        comment: Option<String>,
+    ) -> Result<String, SshError> {
+        let mut key = Self::decode_public(public_key)?;
+        if let Some(comment) = comment {
+            key.set_comment(comment);
+        }
+        key.to_openssh()
+            .map_err(|e| SshError::ExportFailed(e.to_string()))
+    }
+
+    /// **Functor Mapping**: (PrivateKey, passphrase) → bytes
+    ///
+    /// With a passphrase the key is encrypted with bcrypt-pbkdf and
+    /// aes256-ctr; an empty passphrase is rejected. Only the OpenSSH
+    /// format is supported.
+    async fn export_private_key(
+        &self,
+        private_key: &SshPrivateKey,
+        passphrase: Option<&SecureString>,
+        format: SshPrivateKeyFormat,
+    ) -> Result<Vec<u8>, SshError> {
+        if format != SshPrivateKeyFormat::OpenSsh {
+            return Err(SshError::ExportFailed(format!(
+                "{:?} private key export is not supported",
+                format
+            )));
+        }
+
+        let key = Self::decode_private(private_key)?;
+        let key = match passphrase {
+            Some(passphrase) => key
+                .encrypt(&mut self.rng.rng(), Self::passphrase_str(passphrase)?)
+                .map_err(|e| SshError::EncryptionFailed(e.to_string()))?,
+            None => key,
+        };
+
+        let openssh = key
+            .to_openssh(LineEnding::LF)
+            .map_err(|e| SshError::ExportFailed(e.to_string()))?;
+        Ok(openssh.as_bytes().to_vec())
+    }
+
+    /// **Functor Mapping**: PublicKey → bytes
+    async fn export_public_key(
+        &self,
+        public_key: &SshPublicKey,
+        format: SshPublicKeyFormat,
+    ) -> Result<Vec<u8>, SshError> {
+        match format {
+            SshPublicKeyFormat::OpenSsh => Ok(self
+                .format_authorized_key(public_key, None)
+                .await?
+                .into_bytes()),
+            SshPublicKeyFormat::Rfc4253 => Ok(public_key.data.clone()),
+            SshPublicKeyFormat::Pkcs8 => Err(SshError::ExportFailed(
+                "PKCS#8 public key export is not supported".to_string(),
+            )),
+        }
+    }
+
+    /// **Functor Mapping**: PublicKey → Fingerprint
+    async fn get_fingerprint(
+        &self,
+        public_key: &SshPublicKey,
+        hash_type: FingerprintHashType,
+    ) -> Result<String, SshError> {
+        match hash_type {
+            FingerprintHashType::Sha256 => Ok(Self::decode_public(public_key)?
+                .fingerprint(HashAlg::Sha256)
+                .to_string()),
+            FingerprintHashType::Md5 => Err(SshError::InvalidFingerprint(
+                "MD5 fingerprints are not supported".to_string(),
+            )),
+        }
+    }
+
+    async fn convert_key_format(
+        &self,
+        _private_key: &SshPrivateKey,
+        format: KeyConversionFormat,
+    ) -> Result<Vec<u8>, SshError> {
+        Err(SshError::ConversionFailed(format!(
+            "Conversion to {:?} is not supported",
+            format
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_encrypted_export_roundtrip() {
+        let adapter = SshKeysAdapter::new();
+        let keypair = adapter
+            .generate_keypair(SshKeyType::Ed25519, None, Some("alice@example.com".to_string()))
+            .await
+            .unwrap();
+        let passphrase = SecureString::new("correct horse battery staple");
+
+        let exported = adapter
+            .export_private_key(&keypair.private_key, Some(&passphrase), SshPrivateKeyFormat::OpenSsh)
+            .await
+            .unwrap();
+        assert!(PrivateKey::from_openssh(&exported).unwrap().is_encrypted());
+
+        let imported = adapter.parse_private_key(&exported, Some(&passphrase)).await.unwrap();
+        assert_eq!(imported.public_key.data, keypair.public_key.data);
+
+        let wrong = adapter
+            .parse_private_key(&exported, Some(&SecureString::new("wrong")))
+            .await;
+        assert!(matches!(wrong, Err(SshError::InvalidPassphrase)));
+        let missing = adapter.parse_private_key(&exported, None).await;
+        assert!(matches!(missing, Err(SshError::InvalidPassphrase)));
+    }
+
+    #[tokio::test]
+    async fn test_sign_verify_roundtrip() {
+        // verify(sign(m)) = valid
+        let adapter = SshKeysAdapter::new();
+        let keypair = adapter
+            .generate_keypair(SshKeyType::Ed25519, None, None)
+            .await
+            .unwrap();
+
+        let signature = adapter.sign(&keypair.private_key, b"message").await.unwrap();
+        assert!(adapter.verify(&keypair.public_key, b"message", &signature).await.unwrap());
+        assert!(!adapter.verify(&keypair.public_key, b"tampered", &signature).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_empty_passphrase_rejected() {
+        let adapter = SshKeysAdapter::new();
+        let keypair = adapter
+            .generate_keypair(SshKeyType::Ed25519, None, None)
+            .await
+            .unwrap();
+
+        let result = adapter
+            .export_private_key(&keypair.private_key, Some(&SecureString::new("")), SshPrivateKeyFormat::OpenSsh)
+            .await;
+        assert!(matches!(result, Err(SshError::InvalidPassphrase)));
+    }
+
+    #[tokio::test]
+    async fn test_security_key_keypair_exports_sk_key() {
+        let adapter = SshKeysAdapter::new();
+        let signing_key = ssh_key::private::Ed25519Keypair::random(&mut rand::rngs::OsRng);
+        let credential = FidoCredential {
+            serial: "12345678".to_string(),
+            algorithm: FidoAlgorithm::EdDsa,
+            application: FidoCredential::DEFAULT_APPLICATION.to_string(),
+            credential_id: vec![0x42; 64],
+            public_key: signing_key.public.as_ref().to_vec(),
+            flags: FidoCredential::USER_PRESENCE_REQUIRED,
+        };
+
+        let keypair = adapter.security_key_keypair(&credential, None).await.unwrap();
+        assert_eq!(keypair.public_key.key_type, SshKeyType::Ed25519Sk);
+
+        let authorized_key = adapter
+            .format_authorized_key(&keypair.public_key, None)
+            .await
+            .unwrap();
+        assert!(authorized_key.starts_with("sk-ssh-ed25519@openssh.com "));
+
+        let passphrase = SecureString::new("key handle protection");
+        let exported = adapter
+            .export_private_key(&keypair.private_key, Some(&passphrase), SshPrivateKeyFormat::OpenSsh)
+            .await
+            .unwrap();
+        let imported = adapter.parse_private_key(&exported, Some(&passphrase)).await.unwrap();
+        assert_eq!(imported.key_type, SshKeyType::Ed25519Sk);
+    }
+}
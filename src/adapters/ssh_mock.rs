@@ -15,7 +15,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 use crate::ports::ssh::*;
-use crate::ports::yubikey::SecureString;
+use crate::ports::yubikey::{FidoAlgorithm, FidoCredential, SecureString};
 
 /// Mock SSH key adapter for testing
 ///
@@ -96,7 +96,7 @@ impl MockSshKeyAdapter {
 
         let private_key = SshPrivateKey {
             key_type,
-            data: private_data,
+            data: private_data.into(),
             public_key: public_key.clone(),
             is_encrypted: false,
         };
@@ -204,6 +204,60 @@ impl SshKeyPort for MockSshKeyAdapter {
         Ok(keypair)
     }
 
+    /// **Functor Mapping**: FidoCredential → Keypair
+    async fn security_key_keypair(
+        &self,
+        credential: &FidoCredential,
+        comment: Option<String>,
+    ) -> Result<SshKeypair, SshError> {
+        fn put_string(buf: &mut Vec<u8>, value: &[u8]) {
+            buf.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            buf.extend_from_slice(value);
+        }
+
+        // RFC 4253 wire encoding per PROTOCOL.u2f
+        let mut public_data = Vec::new();
+        let key_type = match credential.algorithm {
+            FidoAlgorithm::EdDsa => {
+                put_string(&mut public_data, b"sk-ssh-ed25519@openssh.com");
+                SshKeyType::Ed25519Sk
+            }
+            FidoAlgorithm::Es256 => {
+                put_string(&mut public_data, b"sk-ecdsa-sha2-nistp256@openssh.com");
+                put_string(&mut public_data, b"nistp256");
+                SshKeyType::EcdsaSk
+            }
+        };
+        put_string(&mut public_data, &credential.public_key);
+        put_string(&mut public_data, credential.application.as_bytes());
+
+        let public_key = SshPublicKey {
+            key_type,
+            data: public_data,
+            comment: comment.clone(),
+        };
+
+        // Mock private key: flags followed by the key handle
+        let mut private_data = vec![credential.flags];
+        private_data.extend_from_slice(&credential.credential_id);
+
+        let keypair = SshKeypair {
+            public_key: public_key.clone(),
+            private_key: SshPrivateKey {
+                key_type,
+                data: private_data.into(),
+                public_key,
+                is_encrypted: false,
+            },
+            comment,
+        };
+
+        let fingerprint = self.calculate_fingerprint(&keypair.public_key, FingerprintHashType::Sha256);
+        self.keypairs.write().unwrap().insert(fingerprint, keypair.clone());
+
+        Ok(keypair)
+    }
+
     /// **Functor Mapping**: bytes → PublicKey (object construction)
     async fn parse_public_key(&self, key_data: &[u8]) -> Result<SshPublicKey, SshError> {
         if key_data.len() < 4 {
@@ -249,7 +303,7 @@ impl SshKeyPort for MockSshKeyAdapter {
 
         Ok(SshPrivateKey {
             key_type,
-            data: key_data.to_vec(),
+            data: key_data.into(),
             public_key,
             is_encrypted: passphrase.is_some(),
         })
@@ -301,7 +355,8 @@ impl SshKeyPort for MockSshKeyAdapter {
             SshKeyType::Ed25519 => "ssh-ed25519",
             SshKeyType::Ecdsa => "ecdsa-sha2-nistp256",
             SshKeyType::Dsa => "ssh-dss",
-            _ => "unknown",
+            SshKeyType::Ed25519Sk => "sk-ssh-ed25519@openssh.com",
+            SshKeyType::EcdsaSk => "sk-ecdsa-sha2-nistp256@openssh.com",
         };
 
         let encoded = BASE64.encode(&public_key.data);
@@ -319,14 +374,14 @@ impl SshKeyPort for MockSshKeyAdapter {
     ) -> Result<Vec<u8>, SshError> {
         // Apply passphrase encryption if provided (XOR for mock)
         let key_data = if let Some(pass) = passphrase {
-            let mut encrypted = private_key.data.clone();
+            let mut encrypted = private_key.data.to_vec();
             let pass_bytes = pass.as_bytes();
             for (i, byte) in encrypted.iter_mut().enumerate() {
                 *byte ^= pass_bytes[i % pass_bytes.len()];
             }
             encrypted
         } else {
-            private_key.data.clone()
+            private_key.data.to_vec()
         };
 
         match format {
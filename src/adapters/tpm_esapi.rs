@@ -0,0 +1,441 @@
+//! TPM 2.0 adapter (tss-esapi)
+//!
+//! Implements [`TpmPort`] against the local TPM through the TSS2 ESAPI,
+//! by default via the kernel resource manager (`/dev/tpmrm0`).
+//!
+//! - The EK is recreated from the TCG default RSA template, so its name is
+//!   stable for the life of the TPM.
+//! - The AK is kept at a persistent handle (default `0x81010002`) and
+//!   created there on first use.
+//! - Keys are created under the owner storage root key (recreated from the
+//!   standard template, so it need not be persisted) with `fixedTPM`,
+//!   `fixedParent` and `userWithAuth` cleared: the only way to use them is a
+//!   policy session satisfying their `PolicyPCR` digest.
+//!
+//! TSS calls block, so every operation runs on the blocking pool with its
+//! own ESAPI context, flushing the transient objects it loaded.
+
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use rsa::pkcs1::EncodeRsaPublicKey;
+use rsa::{BigUint, RsaPublicKey};
+use sha2::{Digest as _, Sha256};
+use tss_esapi::abstraction::{ak, ek, AsymmetricAlgorithm};
+use tss_esapi::attributes::ObjectAttributesBuilder;
+use tss_esapi::constants::tss::{TPM2_RH_NULL, TPM2_ST_HASHCHECK};
+use tss_esapi::constants::SessionType;
+use tss_esapi::handles::{KeyHandle, ObjectHandle, PersistentTpmHandle, SessionHandle, TpmHandle};
+use tss_esapi::interface_types::algorithm::{HashingAlgorithm, PublicAlgorithm, SignatureSchemeAlgorithm};
+use tss_esapi::interface_types::dynamic_handles::Persistent;
+use tss_esapi::interface_types::ecc::EccCurve;
+use tss_esapi::interface_types::key_bits::RsaKeyBits;
+use tss_esapi::interface_types::resource_handles::{Hierarchy, Provision};
+use tss_esapi::interface_types::session_handles::PolicySession;
+use tss_esapi::structures::{
+    CreateKeyResult, Digest, EccPoint, EccScheme, HashScheme, HashcheckTicket, KeyDerivationFunctionScheme, PcrSelectionList,
+    PcrSelectionListBuilder, PcrSlot, Private, Public, PublicBuilder, PublicEccParametersBuilder, PublicKeyRsa,
+    PublicRsaParametersBuilder, RsaExponent, RsaScheme, Signature, SignatureScheme, SymmetricDefinition,
+    SymmetricDefinitionObject,
+};
+use tss_esapi::tcti_ldr::{DeviceConfig, TctiNameConf};
+use tss_esapi::traits::{Marshall, UnMarshall};
+use tss_esapi::tss2_esys::TPMT_TK_HASHCHECK;
+use tss_esapi::Context;
+
+use crate::ports::tpm::{
+    PcrBank, PcrSelection, PcrValue, TpmError, TpmIdentity, TpmKey, TpmKeyAlgorithm, TpmPort,
+};
+use crate::types::TpmKeyReference;
+
+/// Conventional persistent handle of the attestation key
+pub const DEFAULT_AK_HANDLE: u32 = 0x8101_0002;
+
+/// TPM adapter over the TSS2 ESAPI
+#[derive(Debug, Clone)]
+pub struct TssEsapiTpmAdapter {
+    tcti: TctiNameConf,
+    ak_handle: u32,
+}
+
+impl TssEsapiTpmAdapter {
+    /// Use the TPM reached through `tcti`
+    pub fn new(tcti: TctiNameConf) -> Self {
+        Self { tcti, ak_handle: DEFAULT_AK_HANDLE }
+    }
+
+    /// Use `TPM2TOOLS_TCTI`/`TCTI`/`TEST_TCTI` if set, the kernel resource
+    /// manager otherwise
+    pub fn from_environment() -> Result<Self, TpmError> {
+        let tcti = match TctiNameConf::from_environment_variable() {
+            Ok(tcti) => tcti,
+            Err(_) => TctiNameConf::Device(
+                DeviceConfig::from_str("/dev/tpmrm0").map_err(|e| TpmError::Unavailable(e.to_string()))?,
+            ),
+        };
+        Ok(Self::new(tcti))
+    }
+
+    /// Keep the attestation key at `handle` instead of `0x81010002`
+    pub fn with_ak_handle(mut self, handle: u32) -> Self {
+        self.ak_handle = handle;
+        self
+    }
+
+    /// Run `operation` with a fresh ESAPI context on the blocking pool
+    async fn with_context<T, F>(&self, operation: F) -> Result<T, TpmError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Context, u32) -> Result<T, TpmError> + Send + 'static,
+    {
+        let tcti = self.tcti.clone();
+        let ak_handle = self.ak_handle;
+        tokio::task::spawn_blocking(move || {
+            let mut context = Context::new(tcti).map_err(|e| TpmError::Unavailable(e.to_string()))?;
+            operation(&mut context, ak_handle)
+        })
+        .await
+        .map_err(|e| TpmError::Tss(e.to_string()))?
+    }
+}
+
+fn tss_error(error: tss_esapi::Error) -> TpmError {
+    TpmError::Tss(error.to_string())
+}
+
+fn flush(context: &mut Context, handle: KeyHandle) {
+    let _ = context.flush_context(handle.into());
+}
+
+/// Name of a loaded object, hex
+fn object_name(context: &mut Context, handle: KeyHandle) -> Result<String, TpmError> {
+    let (_, name, _) = context.read_public(handle).map_err(tss_error)?;
+    Ok(hex::encode(name.value()))
+}
+
+fn identity(context: &mut Context, ak_handle: u32) -> Result<TpmIdentity, TpmError> {
+    let ek = ek::create_ek_object(context, AsymmetricAlgorithm::Rsa, None).map_err(tss_error)?;
+    let identity = object_name(context, ek).and_then(|ek_name| {
+        Ok(TpmIdentity {
+            ek_name,
+            ak_name: attestation_key_name(context, ek, ak_handle)?,
+        })
+    });
+    flush(context, ek);
+    identity
+}
+
+/// Name of the persistent AK, creating it under `ek` if missing
+fn attestation_key_name(context: &mut Context, ek: KeyHandle, ak_handle: u32) -> Result<String, TpmError> {
+    let persistent = PersistentTpmHandle::new(ak_handle).map_err(tss_error)?;
+    if let Ok(handle) = context.tr_from_tpm_public(TpmHandle::Persistent(persistent)) {
+        return object_name(context, KeyHandle::from(handle));
+    }
+
+    let created = ak::create_ak(
+        context,
+        ek,
+        HashingAlgorithm::Sha256,
+        SignatureSchemeAlgorithm::RsaSsa,
+        None,
+        None,
+    )
+    .map_err(tss_error)?;
+    let loaded = ak::load_ak(context, ek, None, created.out_private, created.out_public).map_err(tss_error)?;
+    let persisted = context.execute_with_nullauth_session(|ctx| {
+        ctx.evict_control(Provision::Owner, ObjectHandle::from(loaded), Persistent::Persistent(persistent))
+    });
+    flush(context, loaded);
+    object_name(context, KeyHandle::from(persisted.map_err(tss_error)?))
+}
+
+/// Owner storage root key from the standard template
+fn storage_root_key(context: &mut Context) -> Result<KeyHandle, TpmError> {
+    let public = tss_esapi::utils::create_restricted_decryption_rsa_public(
+        SymmetricDefinitionObject::AES_128_CFB,
+        RsaKeyBits::Rsa2048,
+        RsaExponent::default(),
+    )
+    .map_err(tss_error)?;
+    context
+        .execute_with_nullauth_session(|ctx| ctx.create_primary(Hierarchy::Owner, public, None, None, None, None))
+        .map(|result| result.key_handle)
+        .map_err(tss_error)
+}
+
+fn pcr_selection_list(selection: &PcrSelection) -> Result<PcrSelectionList, TpmError> {
+    let bank = match selection.bank {
+        PcrBank::Sha256 => HashingAlgorithm::Sha256,
+    };
+    let slots = selection
+        .pcrs
+        .iter()
+        .map(|&index| {
+            if index >= 24 {
+                return Err(TpmError::InvalidPcrSelection(format!("PCR {} does not exist", index)));
+            }
+            PcrSlot::try_from(1u32 << index).map_err(tss_error)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    PcrSelectionListBuilder::new()
+        .with_selection(bank, &slots)
+        .build()
+        .map_err(|e| TpmError::InvalidPcrSelection(e.to_string()))
+}
+
+/// Start a policy (or trial) session and apply PolicyPCR over the current
+/// values of `selection`
+fn pcr_policy_session(
+    context: &mut Context,
+    session_type: SessionType,
+    selection: &PcrSelection,
+) -> Result<PolicySession, TpmError> {
+    let session = context
+        .start_auth_session(
+            None,
+            None,
+            None,
+            session_type,
+            SymmetricDefinition::AES_128_CFB,
+            HashingAlgorithm::Sha256,
+        )
+        .map_err(tss_error)?
+        .ok_or_else(|| TpmError::Tss("No session returned".to_string()))?;
+    let session = PolicySession::try_from(session).map_err(tss_error)?;
+    context
+        .policy_pcr(session, Digest::default(), pcr_selection_list(selection)?)
+        .map_err(tss_error)?;
+    Ok(session)
+}
+
+/// Digest a PolicyPCR session over the current PCR values produces
+fn current_policy_digest(context: &mut Context, selection: &PcrSelection) -> Result<Digest, TpmError> {
+    let session = pcr_policy_session(context, SessionType::Trial, selection)?;
+    let digest = context.policy_get_digest(session);
+    let _ = context.flush_context(SessionHandle::from(session).into());
+    digest.map_err(tss_error)
+}
+
+/// Create a key from `template` under `srk`, returning it with its name
+fn create_key(context: &mut Context, srk: KeyHandle, template: Public) -> Result<(CreateKeyResult, String), TpmError> {
+    let created = context
+        .execute_with_nullauth_session(|ctx| ctx.create(srk, template, None, None, None, None))
+        .map_err(|e| TpmError::GenerationFailed(e.to_string()))?;
+    let key_handle = context
+        .execute_with_nullauth_session(|ctx| ctx.load(srk, created.out_private.clone(), created.out_public.clone()))
+        .map_err(tss_error)?;
+    let key_name = object_name(context, key_handle);
+    flush(context, key_handle);
+    Ok((created, key_name?))
+}
+
+fn key_template(algorithm: TpmKeyAlgorithm, policy_digest: Digest) -> Result<Public, TpmError> {
+    let attributes = ObjectAttributesBuilder::new()
+        .with_fixed_tpm(true)
+        .with_fixed_parent(true)
+        .with_sensitive_data_origin(true)
+        .with_user_with_auth(false)
+        .with_sign_encrypt(true)
+        .build()
+        .map_err(tss_error)?;
+    let builder = PublicBuilder::new()
+        .with_name_hashing_algorithm(HashingAlgorithm::Sha256)
+        .with_object_attributes(attributes)
+        .with_auth_policy(policy_digest);
+
+    let builder = match algorithm {
+        TpmKeyAlgorithm::EcdsaP256 => builder
+            .with_public_algorithm(PublicAlgorithm::Ecc)
+            .with_ecc_parameters(
+                PublicEccParametersBuilder::new()
+                    .with_ecc_scheme(EccScheme::EcDsa(HashScheme::new(HashingAlgorithm::Sha256)))
+                    .with_curve(EccCurve::NistP256)
+                    .with_is_signing_key(true)
+                    .with_is_decryption_key(false)
+                    .with_restricted(false)
+                    .with_key_derivation_function_scheme(KeyDerivationFunctionScheme::Null)
+                    .build()
+                    .map_err(tss_error)?,
+            )
+            .with_ecc_unique_identifier(EccPoint::default()),
+        TpmKeyAlgorithm::Rsa2048 => builder
+            .with_public_algorithm(PublicAlgorithm::Rsa)
+            .with_rsa_parameters(
+                PublicRsaParametersBuilder::new()
+                    .with_scheme(RsaScheme::RsaSsa(HashScheme::new(HashingAlgorithm::Sha256)))
+                    .with_key_bits(RsaKeyBits::Rsa2048)
+                    .with_exponent(RsaExponent::default())
+                    .with_is_signing_key(true)
+                    .with_is_decryption_key(false)
+                    .with_restricted(false)
+                    .build()
+                    .map_err(tss_error)?,
+            )
+            .with_rsa_unique_identifier(PublicKeyRsa::default()),
+    };
+    builder.build().map_err(tss_error)
+}
+
+/// SEC1 point or PKCS#1 DER of a key's public area
+fn encode_public_key(public: &Public) -> Result<Vec<u8>, TpmError> {
+    match public {
+        Public::Ecc { unique, .. } => {
+            let mut point = vec![0x04];
+            point.extend(left_pad(unique.x().value(), 32));
+            point.extend(left_pad(unique.y().value(), 32));
+            Ok(point)
+        }
+        Public::Rsa { unique, .. } => {
+            let key = RsaPublicKey::new(BigUint::from_bytes_be(unique.value()), BigUint::from(65537u32))
+                .map_err(|e| TpmError::GenerationFailed(e.to_string()))?;
+            key.to_pkcs1_der()
+                .map(|der| der.as_bytes().to_vec())
+                .map_err(|e| TpmError::GenerationFailed(e.to_string()))
+        }
+        _ => Err(TpmError::GenerationFailed("Unexpected public area".to_string())),
+    }
+}
+
+fn left_pad(value: &[u8], len: usize) -> Vec<u8> {
+    let mut padded = vec![0u8; len.saturating_sub(value.len())];
+    padded.extend_from_slice(value);
+    padded
+}
+
+#[async_trait]
+impl TpmPort for TssEsapiTpmAdapter {
+    async fn identity(&self) -> Result<TpmIdentity, TpmError> {
+        self.with_context(identity).await
+    }
+
+    async fn read_pcrs(&self, selection: &PcrSelection) -> Result<Vec<PcrValue>, TpmError> {
+        let selection = selection.clone();
+        self.with_context(move |context, _| {
+            let mut indices = selection.pcrs.clone();
+            indices.sort_unstable();
+            indices.dedup();
+
+            // TPM2_PCR_Read returns at most 8 digests per call
+            let mut values = Vec::with_capacity(indices.len());
+            for chunk in indices.chunks(8) {
+                let chunk_selection = PcrSelection { bank: selection.bank, pcrs: chunk.to_vec() };
+                let (_, _, digests) = context
+                    .pcr_read(pcr_selection_list(&chunk_selection)?)
+                    .map_err(tss_error)?;
+                values.extend(chunk.iter().zip(digests.value()).map(|(&index, digest)| PcrValue {
+                    index,
+                    value: hex::encode(digest.value()),
+                }));
+            }
+            Ok(values)
+        })
+        .await
+    }
+
+    async fn generate_key(
+        &self,
+        label: &str,
+        algorithm: TpmKeyAlgorithm,
+        selection: &PcrSelection,
+    ) -> Result<TpmKey, TpmError> {
+        let label = label.to_string();
+        let selection = selection.clone();
+        self.with_context(move |context, ak_handle| {
+            let identity = identity(context, ak_handle)?;
+            let policy_digest = current_policy_digest(context, &selection)?;
+            let template = key_template(algorithm, policy_digest.clone())?;
+
+            let srk = storage_root_key(context)?;
+            let created = create_key(context, srk, template);
+            flush(context, srk);
+            let (created, key_name) = created?;
+
+            Ok(TpmKey {
+                label,
+                algorithm,
+                public_key: encode_public_key(&created.out_public)?,
+                public_blob: created.out_public.marshall().map_err(tss_error)?,
+                private_blob: created.out_private.value().to_vec(),
+                reference: TpmKeyReference {
+                    ek_name: identity.ek_name,
+                    ak_name: identity.ak_name,
+                    key_name,
+                    pcr_bank: selection.bank.as_str().to_string(),
+                    pcrs: selection.pcrs.clone(),
+                    policy_digest: hex::encode(policy_digest.value()),
+                },
+                selection,
+            })
+        })
+        .await
+    }
+
+    async fn sign(&self, key: &TpmKey, data: &[u8]) -> Result<Vec<u8>, TpmError> {
+        let key = key.clone();
+        let digest = Sha256::digest(data).to_vec();
+        self.with_context(move |context, ak_handle| {
+            if identity(context, ak_handle)?.ek_name != key.reference.ek_name {
+                return Err(TpmError::KeyNotLoadable(key.label.clone()));
+            }
+            // Checked up front so a changed boot state is reported as such,
+            // not as a TPM_RC_POLICY_FAIL from TPM2_Sign
+            if hex::encode(current_policy_digest(context, &key.selection)?.value()) != key.reference.policy_digest {
+                return Err(TpmError::PolicyCheckFailed(key.label.clone()));
+            }
+
+            let public = Public::unmarshall(&key.public_blob).map_err(|_| TpmError::KeyNotLoadable(key.label.clone()))?;
+            let private = Private::try_from(key.private_blob.clone())
+                .map_err(|_| TpmError::KeyNotLoadable(key.label.clone()))?;
+            let srk = storage_root_key(context)?;
+            let loaded = context.execute_with_nullauth_session(|ctx| ctx.load(srk, private, public));
+            flush(context, srk);
+            let key_handle = loaded.map_err(|_| TpmError::KeyNotLoadable(key.label.clone()))?;
+
+            let session = pcr_policy_session(context, SessionType::Policy, &key.selection)?;
+            let validation = HashcheckTicket::try_from(TPMT_TK_HASHCHECK {
+                tag: TPM2_ST_HASHCHECK,
+                hierarchy: TPM2_RH_NULL,
+                digest: Default::default(),
+            })
+            .map_err(tss_error)?;
+            let signature = Digest::try_from(digest).map_err(tss_error).and_then(|digest| {
+                context
+                    .execute_with_session(Some(session.into()), |ctx| {
+                        ctx.sign(key_handle, digest, SignatureScheme::Null, validation)
+                    })
+                    .map_err(|e| TpmError::SigningFailed(e.to_string()))
+            });
+            let _ = context.flush_context(SessionHandle::from(session).into());
+            flush(context, key_handle);
+
+            match signature? {
+                Signature::EcDsa(signature) => {
+                    let mut raw = left_pad(signature.signature_r().value(), 32);
+                    raw.extend(left_pad(signature.signature_s().value(), 32));
+                    Ok(raw)
+                }
+                Signature::RsaSsa(signature) => Ok(signature.signature().value().to_vec()),
+                other => Err(TpmError::SigningFailed(format!("Unexpected signature {:?}", other))),
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_left_pad() {
+        assert_eq!(left_pad(&[1, 2], 4), vec![0, 0, 1, 2]);
+        assert_eq!(left_pad(&[1, 2, 3, 4], 4), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_pcr_selection_rejects_missing_pcrs() {
+        assert!(pcr_selection_list(&PcrSelection::boot_state()).is_ok());
+        let selection = PcrSelection { bank: PcrBank::Sha256, pcrs: vec![24] };
+        assert!(matches!(pcr_selection_list(&selection), Err(TpmError::InvalidPcrSelection(_))));
+    }
+}
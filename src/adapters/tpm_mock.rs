@@ -0,0 +1,186 @@
+//! Mock TPM adapter for testing
+//!
+//! Simulates a TPM with 24 SHA-256 PCRs. Keys are real P-256 keys (so
+//! signatures verify), "wrapped" by keeping them in memory under the name
+//! returned in the private blob. The policy digest is SHA-256 over the
+//! selected PCR values, not the real TPM2_PolicyPCR computation, but it
+//! fails the same way once a PCR is extended.
+//!
+//! **Category Theory Perspective:**
+//! - **Source Category**: TPM 2.0 (simulated)
+//! - **Target Category**: Domain (machine identities)
+//! - **Functor**: MockTpmAdapter maps simulated TPM operations to domain operations
+
+use async_trait::async_trait;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::ports::tpm::{
+    PcrBank, PcrSelection, PcrValue, TpmError, TpmIdentity, TpmKey, TpmKeyAlgorithm, TpmPort,
+};
+use crate::types::TpmKeyReference;
+
+/// Number of simulated PCRs
+const PCR_COUNT: u8 = 24;
+
+/// Bank of the simulated PCRs
+pub const MOCK_PCR_BANK: PcrBank = PcrBank::Sha256;
+
+/// Mock TPM adapter for testing
+#[derive(Clone)]
+pub struct MockTpmAdapter {
+    identity: TpmIdentity,
+    pcrs: Arc<RwLock<[[u8; 32]; PCR_COUNT as usize]>>,
+    /// Keys by object name
+    keys: Arc<RwLock<HashMap<String, SigningKey>>>,
+}
+
+impl MockTpmAdapter {
+    pub fn new() -> Self {
+        Self {
+            identity: TpmIdentity {
+                ek_name: format!("000b{}", hex::encode(Sha256::digest(b"mock-ek"))),
+                ak_name: format!("000b{}", hex::encode(Sha256::digest(b"mock-ak"))),
+            },
+            pcrs: Arc::new(RwLock::new([[0u8; 32]; PCR_COUNT as usize])),
+            keys: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Extend PCR `index` with `measurement`, as a boot component would
+    pub fn extend_pcr(&self, index: u8, measurement: &[u8]) {
+        let mut pcrs = self.pcrs.write().unwrap();
+        let pcr = &mut pcrs[index as usize];
+        *pcr = Sha256::new()
+            .chain_update(&*pcr)
+            .chain_update(Sha256::digest(measurement))
+            .finalize()
+            .into();
+    }
+
+    fn policy_digest(&self, selection: &PcrSelection) -> Result<String, TpmError> {
+        let pcrs = self.pcrs.read().unwrap();
+        let mut hasher = Sha256::new();
+        for &index in &selection.pcrs {
+            let value = pcrs
+                .get(index as usize)
+                .ok_or_else(|| TpmError::InvalidPcrSelection(format!("PCR {} does not exist", index)))?;
+            hasher.update(value);
+        }
+        Ok(hex::encode(hasher.finalize()))
+    }
+}
+
+impl Default for MockTpmAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TpmPort for MockTpmAdapter {
+    async fn identity(&self) -> Result<TpmIdentity, TpmError> {
+        Ok(self.identity.clone())
+    }
+
+    async fn read_pcrs(&self, selection: &PcrSelection) -> Result<Vec<PcrValue>, TpmError> {
+        let pcrs = self.pcrs.read().unwrap();
+        selection
+            .pcrs
+            .iter()
+            .map(|&index| {
+                pcrs.get(index as usize)
+                    .map(|value| PcrValue { index, value: hex::encode(value) })
+                    .ok_or_else(|| TpmError::InvalidPcrSelection(format!("PCR {} does not exist", index)))
+            })
+            .collect()
+    }
+
+    async fn generate_key(
+        &self,
+        label: &str,
+        algorithm: TpmKeyAlgorithm,
+        selection: &PcrSelection,
+    ) -> Result<TpmKey, TpmError> {
+        if algorithm != TpmKeyAlgorithm::EcdsaP256 {
+            return Err(TpmError::GenerationFailed(format!("{:?} is not simulated", algorithm)));
+        }
+        let policy_digest = self.policy_digest(selection)?;
+
+        let signing_key = SigningKey::random(&mut rand::rngs::OsRng);
+        let public_key = signing_key.verifying_key().to_encoded_point(false).as_bytes().to_vec();
+        let key_name = format!("000b{}", hex::encode(Sha256::digest(&public_key)));
+        self.keys.write().unwrap().insert(key_name.clone(), signing_key);
+
+        Ok(TpmKey {
+            label: label.to_string(),
+            algorithm,
+            public_blob: public_key.clone(),
+            private_blob: key_name.as_bytes().to_vec(),
+            public_key,
+            selection: selection.clone(),
+            reference: TpmKeyReference {
+                ek_name: self.identity.ek_name.clone(),
+                ak_name: self.identity.ak_name.clone(),
+                key_name,
+                pcr_bank: selection.bank.as_str().to_string(),
+                pcrs: selection.pcrs.clone(),
+                policy_digest,
+            },
+        })
+    }
+
+    async fn sign(&self, key: &TpmKey, data: &[u8]) -> Result<Vec<u8>, TpmError> {
+        if key.reference.ek_name != self.identity.ek_name {
+            return Err(TpmError::KeyNotLoadable(key.label.clone()));
+        }
+        if self.policy_digest(&key.selection)? != key.reference.policy_digest {
+            return Err(TpmError::PolicyCheckFailed(key.label.clone()));
+        }
+        let keys = self.keys.read().unwrap();
+        let signing_key = keys
+            .get(&key.reference.key_name)
+            .ok_or_else(|| TpmError::KeyNotLoadable(key.label.clone()))?;
+        let signature: Signature = signing_key.sign(data);
+        Ok(signature.to_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::signature::Verifier;
+    use p256::ecdsa::VerifyingKey;
+
+    #[tokio::test]
+    async fn test_key_signs_until_pcrs_change() {
+        let tpm = MockTpmAdapter::new();
+        tpm.extend_pcr(7, b"secure boot enabled");
+        let key = tpm
+            .generate_key("workstation-01", TpmKeyAlgorithm::EcdsaP256, &PcrSelection::boot_state())
+            .await
+            .unwrap();
+        assert_eq!(key.reference.ek_name, tpm.identity().await.unwrap().ek_name);
+        assert_eq!(key.reference.pcrs, vec![0, 2, 4, 7]);
+
+        let signature = tpm.sign(&key, b"challenge").await.unwrap();
+        let verifying_key = VerifyingKey::from_sec1_bytes(&key.public_key).unwrap();
+        let signature = Signature::from_slice(&signature).unwrap();
+        assert!(verifying_key.verify(b"challenge", &signature).is_ok());
+
+        // Booting something else breaks the policy
+        tpm.extend_pcr(4, b"unexpected boot loader");
+        assert!(matches!(tpm.sign(&key, b"challenge").await, Err(TpmError::PolicyCheckFailed(_))));
+
+        // PCRs outside the selection do not matter
+        let key = tpm
+            .generate_key("service-01", TpmKeyAlgorithm::EcdsaP256, &PcrSelection { bank: MOCK_PCR_BANK, pcrs: vec![7] })
+            .await
+            .unwrap();
+        tpm.extend_pcr(10, b"ima measurement");
+        assert!(tpm.sign(&key, b"challenge").await.is_ok());
+    }
+}
@@ -88,7 +88,7 @@ impl MockX509Adapter {
 
         PrivateKey {
             algorithm: algorithm.to_string(),
-            der: der.clone(),
+            der: der.into(),
             pem,
         }
     }
@@ -120,6 +120,11 @@ impl MockX509Adapter {
             not_after,
             is_ca,
             key_algorithm: "RSA-2048".to_string(),
+            san: vec![],
+            key_usage: vec![],
+            extended_key_usage: vec![],
+            extended_key_usage_oids: vec![],
+            path_len_constraint: None,
         }
     }
 }
@@ -266,7 +271,7 @@ impl X509Port for MockX509Adapter {
         let not_before = now.timestamp();
         let not_after = (now + Duration::days(validity_days as i64)).timestamp();
 
-        let cert = self.generate_mock_certificate(
+        let mut cert = self.generate_mock_certificate(
             subject,
             &parent_ca_cert.subject,
             serial.clone(),
@@ -274,13 +279,7 @@ impl X509Port for MockX509Adapter {
             not_after,
             true, // is_ca
         );
-
-        // Note: In real implementation, path_len_constraint would be used in BasicConstraints extension
-        // For mock, we log it for testing purposes
-        if let Some(path_len) = path_len_constraint {
-            // Would set basicConstraints CA:TRUE, pathlen:path_len
-            tracing::debug!("Setting path length constraint: {}", path_len);
-        }
+        cert.path_len_constraint = path_len_constraint;
 
         // Store certificate and key
         self.certificates.write().unwrap().insert(serial.clone(), cert.clone());
@@ -320,7 +319,7 @@ impl X509Port for MockX509Adapter {
         let not_before = now.timestamp();
         let not_after = (now + Duration::days(validity_days as i64)).timestamp();
 
-        let cert = self.generate_mock_certificate(
+        let mut cert = self.generate_mock_certificate(
             subject,
             &ca_cert.subject,
             serial.clone(),
@@ -328,18 +327,10 @@ impl X509Port for MockX509Adapter {
             not_after,
             false, // Not a CA
         );
-
-        // Note: In real implementation, these would be added as X.509 extensions
-        // For mock, we log them for testing purposes
-        if !san.is_empty() {
-            tracing::debug!("Adding SAN entries: {:?}", san);
-        }
-        if !key_usage.is_empty() {
-            tracing::debug!("Adding key usage: {:?}", key_usage);
-        }
-        if !extended_key_usage.is_empty() {
-            tracing::debug!("Adding extended key usage: {:?}", extended_key_usage);
-        }
+        cert.san = san.iter().map(|name| format!("DNS:{}", name)).collect();
+        cert.extended_key_usage_oids = extended_key_usage.iter().map(|eku| eku.oid().to_string()).collect();
+        cert.key_usage = key_usage;
+        cert.extended_key_usage = extended_key_usage;
 
         // Store certificate and key
         self.certificates.write().unwrap().insert(serial, cert.clone());
@@ -389,6 +380,11 @@ impl X509Port for MockX509Adapter {
             not_after: (Utc::now() + Duration::days(365)).timestamp(),
             is_ca: false,
             key_algorithm: "RSA-2048".to_string(),
+            san: vec![],
+            key_usage: vec![],
+            extended_key_usage: vec![],
+            extended_key_usage_oids: vec![],
+            path_len_constraint: None,
         })
     }
 
@@ -69,6 +69,115 @@ impl RcgenX509Adapter {
             ExtendedKeyUsage::OcspSigning => Some(ExtendedKeyUsagePurpose::OcspSigning),
         }).collect()
     }
+
+    /// Convert an X.509 name back into a CertificateSubject
+    fn name_to_subject(name: &x509_parser::x509::X509Name) -> CertificateSubject {
+        fn first<'a>(
+            mut attrs: impl Iterator<Item = &'a x509_parser::x509::AttributeTypeAndValue<'a>>,
+        ) -> Option<String> {
+            attrs.next().and_then(|attr| attr.as_str().ok()).map(str::to_string)
+        }
+
+        CertificateSubject {
+            common_name: first(name.iter_common_name()).unwrap_or_default(),
+            organization: first(name.iter_organization()),
+            organizational_unit: first(name.iter_organizational_unit()),
+            country: first(name.iter_country()),
+            state: first(name.iter_state_or_province()),
+            locality: first(name.iter_locality()),
+            email: first(name.iter_email()),
+        }
+    }
+
+    /// Describe the subject public key the way generated certificates do
+    fn key_algorithm_name(spki: &x509_parser::x509::SubjectPublicKeyInfo) -> String {
+        use x509_parser::public_key::PublicKey;
+
+        if spki.algorithm.algorithm == x509_parser::oid_registry::OID_SIG_ED25519 {
+            return "Ed25519".to_string();
+        }
+        match spki.parsed() {
+            Ok(PublicKey::RSA(rsa)) => format!("RSA-{}", rsa.key_size()),
+            Ok(PublicKey::EC(point)) => format!("ECDSA-P{}", point.key_size()),
+            _ => spki.algorithm.algorithm.to_id_string(),
+        }
+    }
+
+    /// Build a Certificate from DER, reading every field from the encoding
+    fn certificate_from_der(der: Vec<u8>, pem: String) -> Result<Certificate, X509Error> {
+        use x509_parser::prelude::FromDer;
+
+        let (_, cert) = x509_parser::certificate::X509Certificate::from_der(&der)
+            .map_err(|e| X509Error::ParsingError(format!("Failed to parse certificate: {}", e)))?;
+        let extension_error = |e: x509_parser::error::X509Error| {
+            X509Error::ParsingError(format!("Malformed certificate extension: {}", e))
+        };
+
+        let (is_ca, path_len_constraint) = match cert.basic_constraints().map_err(extension_error)? {
+            Some(bc) => (bc.value.ca, bc.value.path_len_constraint),
+            None => (false, None),
+        };
+
+        let mut key_usage = Vec::new();
+        if let Some(ku) = cert.key_usage().map_err(extension_error)? {
+            let ku = ku.value;
+            let flags = [
+                (ku.digital_signature(), KeyUsage::DigitalSignature),
+                (ku.non_repudiation(), KeyUsage::NonRepudiation),
+                (ku.key_encipherment(), KeyUsage::KeyEncipherment),
+                (ku.data_encipherment(), KeyUsage::DataEncipherment),
+                (ku.key_agreement(), KeyUsage::KeyAgreement),
+                (ku.key_cert_sign(), KeyUsage::KeyCertSign),
+                (ku.crl_sign(), KeyUsage::CrlSign),
+                (ku.encipher_only(), KeyUsage::EncipherOnly),
+                (ku.decipher_only(), KeyUsage::DecipherOnly),
+            ];
+            key_usage = flags.into_iter().filter(|(set, _)| *set).map(|(_, usage)| usage).collect();
+        }
+
+        let mut extended_key_usage = Vec::new();
+        let mut extended_key_usage_oids = Vec::new();
+        if let Some(eku) = cert.extended_key_usage().map_err(extension_error)? {
+            let eku = eku.value;
+            let known = [
+                (eku.server_auth, ExtendedKeyUsage::ServerAuth),
+                (eku.client_auth, ExtendedKeyUsage::ClientAuth),
+                (eku.code_signing, ExtendedKeyUsage::CodeSigning),
+                (eku.email_protection, ExtendedKeyUsage::EmailProtection),
+                (eku.time_stamping, ExtendedKeyUsage::TimeStamping),
+                (eku.ocsp_signing, ExtendedKeyUsage::OcspSigning),
+            ];
+            extended_key_usage = known.into_iter().filter(|(set, _)| *set).map(|(_, usage)| usage).collect();
+            extended_key_usage_oids = extended_key_usage.iter().map(|usage| usage.oid().to_string()).collect();
+            if eku.any {
+                extended_key_usage_oids.push("2.5.29.37.0".to_string());
+            }
+            extended_key_usage_oids.extend(eku.other.iter().map(|oid| oid.to_id_string()));
+        }
+
+        let san = cert
+            .subject_alternative_name()
+            .map_err(extension_error)?
+            .map(|san| san.value.general_names.iter().map(crate::crypto::rfc5280::general_name_to_string).collect())
+            .unwrap_or_default();
+
+        Ok(Certificate {
+            subject: Self::name_to_subject(cert.subject()),
+            issuer: Self::name_to_subject(cert.issuer()),
+            serial: cert.raw_serial().to_vec(),
+            not_before: cert.validity().not_before.timestamp(),
+            not_after: cert.validity().not_after.timestamp(),
+            is_ca,
+            key_algorithm: Self::key_algorithm_name(cert.public_key()),
+            san,
+            key_usage,
+            extended_key_usage,
+            extended_key_usage_oids,
+            path_len_constraint,
+            der,
+            pem,
+        })
+    }
 }
 
 impl Default for RcgenX509Adapter {
@@ -128,6 +237,11 @@ impl X509Port for RcgenX509Adapter {
             not_after: not_after.unix_timestamp(),
             is_ca: true,
             key_algorithm: "Ed25519".to_string(),
+            san: vec![],
+            key_usage: vec![KeyUsage::KeyCertSign, KeyUsage::CrlSign, KeyUsage::DigitalSignature],
+            extended_key_usage: vec![],
+            extended_key_usage_oids: vec![],
+            path_len_constraint: None,
         })
     }
 
@@ -236,6 +350,11 @@ impl X509Port for RcgenX509Adapter {
             not_after: not_after.unix_timestamp(),
             is_ca: true,
             key_algorithm: "Ed25519".to_string(),
+            san: vec![],
+            key_usage: vec![KeyUsage::KeyCertSign, KeyUsage::CrlSign, KeyUsage::DigitalSignature],
+            extended_key_usage: vec![],
+            extended_key_usage_oids: vec![],
+            path_len_constraint,
         })
     }
 
@@ -295,6 +414,11 @@ impl X509Port for RcgenX509Adapter {
             not_after: not_after.unix_timestamp(),
             is_ca: false,
             key_algorithm: "Ed25519".to_string(),
+            san: san.iter().map(|name| format!("DNS:{}", name)).collect(),
+            extended_key_usage_oids: extended_key_usage.iter().map(|eku| eku.oid().to_string()).collect(),
+            key_usage,
+            extended_key_usage,
+            path_len_constraint: None,
         })
     }
 
@@ -303,8 +427,6 @@ impl X509Port for RcgenX509Adapter {
             return Err(X509Error::ParsingError("Certificate data is empty".to_string()));
         }
 
-        // For now, just convert DER to PEM or vice versa
-        // TODO: Actually parse the certificate structure
         let (der, pem) = if cert_data.starts_with(b"-----BEGIN") {
             // It's PEM, convert to DER
             let pem_str = std::str::from_utf8(cert_data)
@@ -318,33 +440,7 @@ impl X509Port for RcgenX509Adapter {
             (cert_data.to_vec(), pem_str)
         };
 
-        Ok(Certificate {
-            der,
-            pem,
-            subject: CertificateSubject {
-                common_name: "".to_string(),
-                organization: None,
-                organizational_unit: None,
-                country: None,
-                state: None,
-                locality: None,
-                email: None,
-            },
-            issuer: CertificateSubject {
-                common_name: "".to_string(),
-                organization: None,
-                organizational_unit: None,
-                country: None,
-                state: None,
-                locality: None,
-                email: None,
-            },
-            serial: vec![0],
-            not_before: 0,
-            not_after: 0,
-            is_ca: false,
-            key_algorithm: "unknown".to_string(),
-        })
+        Self::certificate_from_der(der, pem)
     }
 
     async fn verify_chain(
@@ -393,3 +489,57 @@ impl X509Port for RcgenX509Adapter {
         Err(X509Error::OperationError("Not implemented - use MockX509Adapter".to_string()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_certificate_reads_extensions() {
+        let key_pair = RcgenKeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(vec!["api.example.com".to_string()]).unwrap();
+        params.distinguished_name = RcgenX509Adapter::subject_to_dn(&CertificateSubject {
+            common_name: "Issuing CA".to_string(),
+            organization: Some("Example Org".to_string()),
+            organizational_unit: None,
+            country: Some("US".to_string()),
+            state: None,
+            locality: None,
+            email: None,
+        });
+        params.subject_alt_names.push(SanType::IpAddress("192.0.2.10".parse().unwrap()));
+        params.subject_alt_names.push(SanType::Rfc822Name("pki@example.com".try_into().unwrap()));
+        params.is_ca = IsCa::Ca(BasicConstraints::Constrained(1));
+        params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+        params.extended_key_usages = vec![
+            ExtendedKeyUsagePurpose::ServerAuth,
+            ExtendedKeyUsagePurpose::Other(vec![1, 3, 6, 1, 4, 1, 311, 20, 2, 2]),
+        ];
+        let not_before = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        params.not_before = not_before;
+        params.not_after = not_before + Duration::days(30);
+        let cert = params.self_signed(&key_pair).unwrap();
+
+        let parsed = RcgenX509Adapter::new().parse_certificate(cert.pem().as_bytes()).await.unwrap();
+
+        assert_eq!(parsed.subject.common_name, "Issuing CA");
+        assert_eq!(parsed.subject.country.as_deref(), Some("US"));
+        assert_eq!(parsed.issuer, parsed.subject);
+        assert_eq!(parsed.not_before, 1_700_000_000);
+        assert_eq!(parsed.not_after - parsed.not_before, 30 * 86_400);
+        assert!(parsed.is_ca);
+        assert_eq!(parsed.path_len_constraint, Some(1));
+        assert_eq!(parsed.key_algorithm, "ECDSA-P256");
+        assert_eq!(
+            parsed.san,
+            vec!["DNS:api.example.com", "IP:192.0.2.10", "email:pki@example.com"]
+        );
+        assert_eq!(parsed.key_usage, vec![KeyUsage::KeyCertSign, KeyUsage::CrlSign]);
+        assert_eq!(parsed.extended_key_usage, vec![ExtendedKeyUsage::ServerAuth]);
+        assert_eq!(parsed.extended_key_usage_oids, vec!["1.3.6.1.5.5.7.3.1", "1.3.6.1.4.1.311.20.2.2"]);
+
+        // DER input yields the same certificate
+        let from_der = RcgenX509Adapter::new().parse_certificate(cert.der()).await.unwrap();
+        assert_eq!(from_der.serial, parsed.serial);
+    }
+}
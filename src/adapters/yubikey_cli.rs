@@ -3,11 +3,12 @@
 //! This adapter implements the YubiKeyPort trait using the ykman CLI tool.
 
 use async_trait::async_trait;
-use std::process::Command;
+use std::io::Write;
+use std::process::{Command, Stdio};
 
 use crate::ports::yubikey::{
-    KeyAlgorithm, PivSlot, PublicKey, SecureString, Signature, YubiKeyDevice, YubiKeyError,
-    YubiKeyPort,
+    FidoAlgorithm, FidoCredential, KeyAlgorithm, PivSlot, PublicKey, SecureString, Signature,
+    YubiKeyDevice, YubiKeyError, YubiKeyPort,
 };
 
 /// YubiKey adapter using ykman CLI
@@ -177,6 +178,71 @@ impl YubiKeyPort for YubiKeyCliAdapter {
         Ok(())
     }
 
+    async fn read_certificate(
+        &self,
+        serial: &str,
+        slot: PivSlot,
+    ) -> Result<Option<Vec<u8>>, YubiKeyError> {
+        let slot_id = match slot {
+            PivSlot::Authentication => "9a",
+            PivSlot::Signature => "9c",
+            PivSlot::KeyManagement => "9d",
+            PivSlot::CardAuth => "9e",
+            PivSlot::Retired(n) => return Err(YubiKeyError::NotSupported(format!("Retired slot {} not yet implemented", n))),
+        };
+
+        let output = Command::new("ykman")
+            .args(["--device", serial])
+            .args(["piv", "certificates", "export"])
+            .args(["--format", "PEM"])
+            .arg(slot_id)
+            .arg("-")  // Output to stdout
+            .output()
+            .map_err(|e| YubiKeyError::OperationError(format!("Failed to export certificate: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("No certificate found") {
+                return Ok(None);
+            }
+            return Err(YubiKeyError::OperationError(format!("Certificate export failed: {}", stderr)));
+        }
+
+        Ok(Some(output.stdout))
+    }
+
+    async fn delete_certificate(
+        &self,
+        serial: &str,
+        slot: PivSlot,
+        management_key: &SecureString,
+    ) -> Result<(), YubiKeyError> {
+        let slot_id = match slot {
+            PivSlot::Authentication => "9a",
+            PivSlot::Signature => "9c",
+            PivSlot::KeyManagement => "9d",
+            PivSlot::CardAuth => "9e",
+            PivSlot::Retired(n) => return Err(YubiKeyError::NotSupported(format!("Retired slot {} not yet implemented", n))),
+        };
+
+        let mgmt_key_str = String::from_utf8_lossy(management_key.as_bytes());
+
+        let output = Command::new("ykman")
+            .args(["--device", serial])
+            .args(["piv", "certificates", "delete"])
+            .args(["--management-key", &mgmt_key_str])
+            .arg(slot_id)
+            .output()
+            .map_err(|e| YubiKeyError::OperationError(format!("Failed to delete certificate: {}", e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(YubiKeyError::OperationError(format!("Certificate deletion failed: {}", stderr)));
+        }
+
+        Ok(())
+    }
+
     async fn sign_with_slot(
         &self,
         _serial: &str,
@@ -301,4 +367,107 @@ impl YubiKeyPort for YubiKeyCliAdapter {
     ) -> Result<(), YubiKeyError> {
         Err(YubiKeyError::NotSupported("CCC setting not yet implemented".to_string()))
     }
+
+    /// Enrolls the credential with `ssh-keygen -t *-sk`, which talks to the
+    /// authenticator through libfido2. The PIN is written to its stdin when
+    /// it asks for one; touch is still prompted on the device.
+    async fn make_fido_credential(
+        &self,
+        serial: &str,
+        algorithm: FidoAlgorithm,
+        application: &str,
+        pin: Option<&SecureString>,
+    ) -> Result<FidoCredential, YubiKeyError> {
+        // ssh-keygen enrolls on the first authenticator it finds
+        let devices = self.list_devices().await?;
+        if !devices.iter().any(|d| d.serial == serial) {
+            return Err(YubiKeyError::DeviceNotFound(serial.to_string()));
+        }
+        if devices.len() > 1 {
+            return Err(YubiKeyError::OperationError(format!(
+                "Connect only YubiKey {} to enroll a FIDO credential",
+                serial
+            )));
+        }
+
+        let key_type = match algorithm {
+            FidoAlgorithm::EdDsa => "ed25519-sk",
+            FidoAlgorithm::Es256 => "ecdsa-sk",
+        };
+
+        let key_path = std::env::temp_dir().join(format!("yubikey_sk_{}", uuid::Uuid::now_v7()));
+
+        let mut command = Command::new("ssh-keygen");
+        command
+            .args(["-q", "-t", key_type])
+            .args(["-O", &format!("application={}", application)])
+            .args(["-N", "", "-C", ""])
+            .arg("-f")
+            .arg(&key_path);
+        if pin.is_some() {
+            command.args(["-O", "verify-required"]);
+        }
+        // Without a terminal ssh-keygen reads the PIN prompt from stdin;
+        // keep it from asking a GUI askpass instead
+        command
+            .env_remove("DISPLAY")
+            .env("SSH_ASKPASS_REQUIRE", "never")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let output = command
+            .spawn()
+            .and_then(|mut child| {
+                if let (Some(mut stdin), Some(pin)) = (child.stdin.take(), pin) {
+                    stdin.write_all(pin.as_bytes())?;
+                    stdin.write_all(b"\n")?;
+                }
+                child.wait_with_output()
+            })
+            .map_err(|e| YubiKeyError::OperationError(format!("Failed to run ssh-keygen: {}", e)));
+        let key_file = std::fs::read(&key_path);
+
+        // Clean up temp files
+        let _ = std::fs::remove_file(&key_path);
+        let _ = std::fs::remove_file(key_path.with_extension("pub"));
+
+        let output = output?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(YubiKeyError::OperationError(format!("FIDO enrollment failed: {}", stderr)));
+        }
+
+        let key_file = key_file
+            .map_err(|e| YubiKeyError::OperationError(format!("Failed to read enrolled key: {}", e)))?;
+        let key = ssh_key::PrivateKey::from_openssh(&key_file)
+            .map_err(|e| YubiKeyError::OperationError(format!("Invalid enrolled key: {}", e)))?;
+
+        let (public_key, credential_id, flags) = match key.key_data() {
+            ssh_key::private::KeypairData::SkEd25519(sk) => (
+                sk.public().public_key().as_ref().to_vec(),
+                sk.key_handle().to_vec(),
+                sk.flags(),
+            ),
+            ssh_key::private::KeypairData::SkEcdsaSha2NistP256(sk) => (
+                sk.public().ec_point().as_bytes().to_vec(),
+                sk.key_handle().to_vec(),
+                sk.flags(),
+            ),
+            _ => {
+                return Err(YubiKeyError::OperationError(
+                    "ssh-keygen did not produce a security key".to_string(),
+                ))
+            }
+        };
+
+        Ok(FidoCredential {
+            serial: serial.to_string(),
+            algorithm,
+            application: application.to_string(),
+            credential_id,
+            public_key,
+            flags,
+        })
+    }
 }
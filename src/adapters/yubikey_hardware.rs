@@ -15,8 +15,8 @@ use yubikey::YubiKey;
 
 #[cfg(feature = "yubikey-support")]
 use crate::ports::yubikey::{
-    KeyAlgorithm, PivSlot, PublicKey, SecureString, Signature, YubiKeyDevice, YubiKeyError,
-    YubiKeyPort,
+    FidoAlgorithm, FidoCredential, KeyAlgorithm, PivSlot, PublicKey, SecureString, Signature,
+    YubiKeyDevice, YubiKeyError, YubiKeyPort,
 };
 
 /// YubiKey adapter using direct hardware access via PC/SC
@@ -87,6 +87,23 @@ impl YubiKeyPort for YubiKeyHardwareAdapter {
         Err(YubiKeyError::NotSupported("Certificate import not yet implemented - use CLI adapter".to_string()))
     }
 
+    async fn read_certificate(
+        &self,
+        _serial: &str,
+        _slot: PivSlot,
+    ) -> Result<Option<Vec<u8>>, YubiKeyError> {
+        Err(YubiKeyError::NotSupported("Certificate read not yet implemented - use CLI adapter".to_string()))
+    }
+
+    async fn delete_certificate(
+        &self,
+        _serial: &str,
+        _slot: PivSlot,
+        _management_key: &SecureString,
+    ) -> Result<(), YubiKeyError> {
+        Err(YubiKeyError::NotSupported("Certificate deletion not yet implemented - use CLI adapter".to_string()))
+    }
+
     async fn sign_with_slot(
         &self,
         _serial: &str,
@@ -148,6 +165,17 @@ impl YubiKeyPort for YubiKeyHardwareAdapter {
     ) -> Result<(), YubiKeyError> {
         Err(YubiKeyError::NotSupported("CCC setting not yet implemented - use CLI adapter".to_string()))
     }
+
+    async fn make_fido_credential(
+        &self,
+        _serial: &str,
+        _algorithm: FidoAlgorithm,
+        _application: &str,
+        _pin: Option<&SecureString>,
+    ) -> Result<FidoCredential, YubiKeyError> {
+        // FIDO2 is a CTAP (HID) applet, not reachable over PC/SC
+        Err(YubiKeyError::NotSupported("FIDO credentials are not available over PC/SC - use CLI adapter".to_string()))
+    }
 }
 
 // Stub implementation when yubikey-support feature is not enabled
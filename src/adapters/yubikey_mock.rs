@@ -14,8 +14,8 @@ use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
 use crate::ports::yubikey::{
-    KeyAlgorithm, PivSlot, PublicKey, SecureString, Signature, YubiKeyDevice, YubiKeyError,
-    YubiKeyPort,
+    FidoAlgorithm, FidoCredential, KeyAlgorithm, PivSlot, PublicKey, SecureString, Signature,
+    YubiKeyDevice, YubiKeyError, YubiKeyPort,
 };
 
 /// Mock YubiKey adapter for testing
@@ -35,6 +35,10 @@ pub struct MockYubiKeyAdapter {
     /// Keys stored in slots (device_serial -> slot -> key)
     keys: Arc<RwLock<HashMap<String, HashMap<PivSlot, PublicKey>>>>,
 
+    /// Private halves of P-256 slot keys, so slot signatures verify
+    /// (device_serial -> slot -> key)
+    signing_keys: Arc<RwLock<HashMap<String, HashMap<PivSlot, p256::ecdsa::SigningKey>>>>,
+
     /// Certificates in slots (device_serial -> slot -> cert)
     certificates: Arc<RwLock<HashMap<String, HashMap<PivSlot, Vec<u8>>>>>,
 
@@ -51,6 +55,7 @@ impl MockYubiKeyAdapter {
         Self {
             devices: Arc::new(RwLock::new(Vec::new())),
             keys: Arc::new(RwLock::new(HashMap::new())),
+            signing_keys: Arc::new(RwLock::new(HashMap::new())),
             certificates: Arc::new(RwLock::new(HashMap::new())),
             pins: Arc::new(RwLock::new(HashMap::new())),
             management_keys: Arc::new(RwLock::new(HashMap::new())),
@@ -74,6 +79,7 @@ impl MockYubiKeyAdapter {
     pub fn clear(&self) {
         self.devices.write().unwrap().clear();
         self.keys.write().unwrap().clear();
+        self.signing_keys.write().unwrap().clear();
         self.certificates.write().unwrap().clear();
         self.pins.write().unwrap().clear();
         self.management_keys.write().unwrap().clear();
@@ -95,6 +101,66 @@ impl MockYubiKeyAdapter {
         }
     }
 
+    /// Generate a real public key so the credential can be encoded as an
+    /// OpenSSH security key; the private half is discarded like on-device
+    fn generate_fido_public_key(&self, algorithm: FidoAlgorithm) -> Result<Vec<u8>, YubiKeyError> {
+        use ring::signature::{EcdsaKeyPair, Ed25519KeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+
+        let rng = ring::rand::SystemRandom::new();
+        let failed = || YubiKeyError::OperationError("FIDO key generation failed".to_string());
+
+        match algorithm {
+            FidoAlgorithm::EdDsa => {
+                let pkcs8 = Ed25519KeyPair::generate_pkcs8(&rng).map_err(|_| failed())?;
+                let keypair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).map_err(|_| failed())?;
+                Ok(keypair.public_key().as_ref().to_vec())
+            }
+            FidoAlgorithm::Es256 => {
+                let alg = &ECDSA_P256_SHA256_FIXED_SIGNING;
+                let pkcs8 = EcdsaKeyPair::generate_pkcs8(alg, &rng).map_err(|_| failed())?;
+                let keypair = EcdsaKeyPair::from_pkcs8(alg, pkcs8.as_ref(), &rng).map_err(|_| failed())?;
+                Ok(keypair.public_key().as_ref().to_vec())
+            }
+        }
+    }
+
+    /// Generate a real P-256 key; `data` holds the uncompressed point and
+    /// `spki` the SubjectPublicKeyInfo DER, as read back from a device
+    fn generate_p256_key(&self) -> Result<(PublicKey, p256::ecdsa::SigningKey), YubiKeyError> {
+        use p256::pkcs8::EncodePublicKey;
+
+        let signing_key = p256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let verifying_key = signing_key.verifying_key();
+        let spki = verifying_key
+            .to_public_key_der()
+            .map_err(|e| YubiKeyError::OperationError(format!("SPKI encoding failed: {}", e)))?;
+
+        let public_key = PublicKey {
+            algorithm: KeyAlgorithm::EccP256,
+            data: verifying_key.to_encoded_point(false).as_bytes().to_vec(),
+            spki: spki.as_bytes().to_vec(),
+        };
+        Ok((public_key, signing_key))
+    }
+
+    /// Sign a host-computed digest the way a PIV card does: shorter input
+    /// is left-padded to the field size, the signature is DER encoded
+    fn sign_p256_digest(signing_key: &p256::ecdsa::SigningKey, data: &[u8]) -> Result<Signature, YubiKeyError> {
+        use p256::ecdsa::signature::hazmat::PrehashSigner;
+
+        let mut digest = [0u8; 32];
+        let input = &data[..data.len().min(32)];
+        digest[32 - input.len()..].copy_from_slice(input);
+
+        let signature: p256::ecdsa::Signature = signing_key
+            .sign_prehash(&digest)
+            .map_err(|e| YubiKeyError::OperationError(format!("Signing failed: {}", e)))?;
+        Ok(Signature {
+            algorithm: KeyAlgorithm::EccP256,
+            data: signature.to_der().as_bytes().to_vec(),
+        })
+    }
+
     fn generate_mock_signature(&self, algorithm: KeyAlgorithm, data: &[u8]) -> Signature {
         // Generate deterministic mock signature
         let sig_data = match algorithm {
@@ -162,8 +228,22 @@ impl YubiKeyPort for MockYubiKeyAdapter {
             return Err(YubiKeyError::DeviceNotFound(serial.to_string()));
         }
 
-        // Generate mock public key
-        let public_key = self.generate_mock_public_key(algorithm);
+        // P-256 keys are real so CSRs and certificates built on them verify
+        let public_key = if algorithm == KeyAlgorithm::EccP256 {
+            let (public_key, signing_key) = self.generate_p256_key()?;
+            self.signing_keys
+                .write()
+                .unwrap()
+                .entry(serial.to_string())
+                .or_default()
+                .insert(slot, signing_key);
+            public_key
+        } else {
+            if let Some(slots) = self.signing_keys.write().unwrap().get_mut(serial) {
+                slots.remove(&slot);
+            }
+            self.generate_mock_public_key(algorithm)
+        };
 
         // Store in simulated slot
         self.keys
@@ -233,8 +313,51 @@ impl YubiKeyPort for MockYubiKeyAdapter {
             .and_then(|slots| slots.get(&slot).cloned())
             .ok_or_else(|| YubiKeyError::OperationError("No key in slot".to_string()))?;
 
-        // Generate mock signature
-        Ok(self.generate_mock_signature(key.algorithm, data))
+        let signing_key = self
+            .signing_keys
+            .read()
+            .unwrap()
+            .get(serial)
+            .and_then(|slots| slots.get(&slot).cloned());
+
+        match signing_key {
+            Some(signing_key) => Self::sign_p256_digest(&signing_key, data),
+            None => Ok(self.generate_mock_signature(key.algorithm, data)),
+        }
+    }
+
+    /// **Functor Mapping**: (device, slot) → Option<Certificate>
+    async fn read_certificate(
+        &self,
+        serial: &str,
+        slot: PivSlot,
+    ) -> Result<Option<Vec<u8>>, YubiKeyError> {
+        if !self.devices.read().unwrap().iter().any(|d| d.serial == serial) {
+            return Err(YubiKeyError::DeviceNotFound(serial.to_string()));
+        }
+
+        Ok(self
+            .certificates
+            .read()
+            .unwrap()
+            .get(serial)
+            .and_then(|slots| slots.get(&slot).cloned()))
+    }
+
+    /// **Functor Mapping**: (device, slot) → ()
+    async fn delete_certificate(
+        &self,
+        serial: &str,
+        slot: PivSlot,
+        management_key: &SecureString,
+    ) -> Result<(), YubiKeyError> {
+        // Like key generation, the simulated device authenticates with the PIN
+        self.verify_pin(serial, management_key).await?;
+
+        if let Some(slots) = self.certificates.write().unwrap().get_mut(serial) {
+            slots.remove(&slot);
+        }
+        Ok(())
     }
 
     /// **Functor Mapping**: (device, pin) → bool
@@ -300,6 +423,7 @@ impl YubiKeyPort for MockYubiKeyAdapter {
     async fn reset_piv(&self, serial: &str) -> Result<(), YubiKeyError> {
         // Clear all slots for this device
         self.keys.write().unwrap().remove(serial);
+        self.signing_keys.write().unwrap().remove(serial);
         self.certificates.write().unwrap().remove(serial);
 
         // Reset to default PIN and management key
@@ -356,6 +480,49 @@ impl YubiKeyPort for MockYubiKeyAdapter {
         self.verify_pin(serial, pin).await?;
         Ok(())
     }
+
+    /// **Functor Mapping**: (device, algorithm, application) → FidoCredential
+    async fn make_fido_credential(
+        &self,
+        serial: &str,
+        algorithm: FidoAlgorithm,
+        application: &str,
+        pin: Option<&SecureString>,
+    ) -> Result<FidoCredential, YubiKeyError> {
+        use ring::rand::SecureRandom;
+
+        if !self.devices.read().unwrap().iter().any(|d| d.serial == serial) {
+            return Err(YubiKeyError::DeviceNotFound(serial.to_string()));
+        }
+
+        if !application.starts_with(FidoCredential::DEFAULT_APPLICATION) {
+            return Err(YubiKeyError::OperationError(format!(
+                "FIDO application {} must start with \"ssh:\"",
+                application
+            )));
+        }
+
+        // The simulated device shares one PIN between PIV and FIDO
+        let mut flags = FidoCredential::USER_PRESENCE_REQUIRED;
+        if let Some(pin) = pin {
+            self.verify_pin(serial, pin).await?;
+            flags |= FidoCredential::USER_VERIFICATION_REQUIRED;
+        }
+
+        let mut credential_id = vec![0u8; 64];
+        ring::rand::SystemRandom::new()
+            .fill(&mut credential_id)
+            .map_err(|_| YubiKeyError::OperationError("Key handle generation failed".to_string()))?;
+
+        Ok(FidoCredential {
+            serial: serial.to_string(),
+            algorithm,
+            application: application.to_string(),
+            credential_id,
+            public_key: self.generate_fido_public_key(algorithm)?,
+            flags,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -423,4 +590,64 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_p256_slot_signs_digest_and_stores_certificate() {
+        use p256::ecdsa::signature::hazmat::PrehashVerifier;
+        use p256::pkcs8::DecodePublicKey;
+        use sha2::{Digest, Sha256};
+
+        let adapter = MockYubiKeyAdapter::default();
+        let pin = SecureString::new("123456");
+
+        let public_key = adapter
+            .generate_key_in_slot("12345678", PivSlot::Signature, KeyAlgorithm::EccP256, &pin)
+            .await
+            .unwrap();
+        assert_eq!(public_key.data.len(), 65);
+
+        let digest = Sha256::digest(b"certificate request");
+        let signature = adapter
+            .sign_with_slot("12345678", PivSlot::Signature, &digest, &pin)
+            .await
+            .unwrap();
+
+        let verifying_key = p256::ecdsa::VerifyingKey::from_public_key_der(&public_key.spki).unwrap();
+        let signature = p256::ecdsa::Signature::from_der(&signature.data).unwrap();
+        assert!(verifying_key.verify_prehash(&digest, &signature).is_ok());
+
+        // Certificates read back until deleted; the key stays
+        let cert = vec![0x30, 0x82];
+        adapter.import_certificate("12345678", PivSlot::Signature, &cert, &pin).await.unwrap();
+        assert_eq!(adapter.read_certificate("12345678", PivSlot::Signature).await.unwrap(), Some(cert));
+
+        adapter.delete_certificate("12345678", PivSlot::Signature, &pin).await.unwrap();
+        assert_eq!(adapter.read_certificate("12345678", PivSlot::Signature).await.unwrap(), None);
+        assert!(adapter.sign_with_slot("12345678", PivSlot::Signature, &digest, &pin).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fido_credential_requires_ssh_application() {
+        let adapter = MockYubiKeyAdapter::default();
+        let pin = SecureString::new("123456");
+
+        let credential = adapter
+            .make_fido_credential("12345678", FidoAlgorithm::EdDsa, "ssh:", Some(&pin))
+            .await
+            .unwrap();
+        assert_eq!(credential.public_key.len(), 32);
+        assert!(credential.requires_user_verification());
+
+        let credential = adapter
+            .make_fido_credential("12345678", FidoAlgorithm::Es256, "ssh:cim-keys", None)
+            .await
+            .unwrap();
+        assert_eq!(credential.public_key.len(), 65);
+        assert!(!credential.requires_user_verification());
+
+        let result = adapter
+            .make_fido_credential("12345678", FidoAlgorithm::EdDsa, "https://example.com", None)
+            .await;
+        assert!(result.is_err());
+    }
 }
@@ -9,8 +9,24 @@
 
 use cim_domain::AggregateRoot;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+use crate::domain::aggregates::KeyState;
+use crate::domain::approval::{ApprovalRule, PendingApproval, SensitiveCommandKind};
+use crate::domain::pki::CertificateStatus;
+use crate::domain::SeparationOfDutiesRule;
+use crate::domain::KeyId;
+use crate::events::{DomainEvent, EventEnvelope};
+use crate::ports::event_store::{EventStore, EventStoreError, ExpectedSequence, StoredEvent};
+use crate::ports::snapshot_store::{AggregateSnapshot, SnapshotStore};
+
+/// Aggregate type recorded in snapshots
+const AGGREGATE_TYPE: &str = "KeyManagement";
+
+/// Serialized layout of [`KeyManagementAggregate`] in snapshots
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
 /// Key management aggregate root
 ///
 /// This is a pure functional aggregate that processes commands and emits events.
@@ -19,6 +35,39 @@ use uuid::Uuid;
 pub struct KeyManagementAggregate {
     pub id: Uuid,  // Aggregate ID
     pub version: u64,
+    /// Security policy enforced while handling export commands
+    #[serde(default)]
+    pub security: crate::config::SecurityConfig,
+    /// Organization policies that constrain command handling
+    #[serde(default)]
+    pub policies: Vec<crate::domain::Policy>,
+    /// Keys generated for the organization, folded from key events
+    #[serde(default)]
+    pub keys: BTreeMap<Uuid, KeyState>,
+    /// Certificate lifecycle, folded from certificate events
+    #[serde(default)]
+    pub certificates: BTreeMap<Uuid, CertificateStatus>,
+    /// Sensitive commands that must be approved before they run
+    #[serde(default)]
+    pub approval_rules: Vec<ApprovalRule>,
+    /// Parked sensitive commands, folded from approval events
+    #[serde(default)]
+    pub approvals: BTreeMap<Uuid, PendingApproval>,
+    /// Claims that no entity may hold together
+    #[serde(default)]
+    pub separation_rules: Vec<SeparationOfDutiesRule>,
+    /// Policy bindings by relationship ID: (policy, governed entity)
+    #[serde(default)]
+    pub policy_bindings: BTreeMap<Uuid, (Uuid, Uuid)>,
+    /// Role assignments by person, then role: the role's required policies
+    #[serde(default)]
+    pub role_assignments: BTreeMap<Uuid, BTreeMap<Uuid, Vec<Uuid>>>,
+    /// Events applied since the last snapshot (not part of the state)
+    #[serde(skip)]
+    pub events_since_snapshot: u64,
+    /// Claims each command type requires (configuration, not state)
+    #[serde(skip)]
+    pub authorizer: crate::commands::CommandAuthorizer,
 }
 
 impl KeyManagementAggregate {
@@ -27,20 +76,432 @@ impl KeyManagementAggregate {
         Self {
             id,
             version: 0,
+            security: crate::config::SecurityConfig::default(),
+            policies: Vec::new(),
+            keys: BTreeMap::new(),
+            certificates: BTreeMap::new(),
+            approval_rules: Vec::new(),
+            approvals: BTreeMap::new(),
+            separation_rules: Vec::new(),
+            policy_bindings: BTreeMap::new(),
+            role_assignments: BTreeMap::new(),
+            events_since_snapshot: 0,
+            authorizer: crate::commands::CommandAuthorizer::default(),
+        }
+    }
+
+    /// Aggregate of one organization on a multi-tenant workstation
+    ///
+    /// The organization ID is the aggregate ID, so each organization's
+    /// events form their own stream in the event store and snapshots.
+    pub fn for_organization(organization_id: Uuid) -> Self {
+        Self::new(organization_id)
+    }
+
+    /// Rebuild an aggregate by replaying its whole event stream
+    pub fn replay(id: Uuid, events: &[StoredEvent]) -> Self {
+        let mut aggregate = Self::new(id);
+        for event in events {
+            aggregate.apply(event);
+        }
+        aggregate
+    }
+
+    /// Fold a stored event into the aggregate
+    ///
+    /// The version becomes the event's store sequence, so it can be passed
+    /// straight back as `ExpectedSequence::Exact` on the next append.
+    /// Events this aggregate does not track only advance the version.
+    pub fn apply(&mut self, event: &StoredEvent) {
+        use crate::commands::organization::RelationshipType;
+        use crate::events::{
+            ApprovalEvents, CertificateEvents, KeyEvents, OrganizationEvents, PersonEvents, RelationshipEvents,
+        };
+
+        match &event.envelope.event {
+            DomainEvent::Key(KeyEvents::KeyGenerated(e)) => {
+                self.keys.insert(e.key_id, KeyState {
+                    id: KeyId::from_uuid(e.key_id),
+                    algorithm: e.algorithm.clone(),
+                    purpose: e.purpose,
+                    owner_person_id: e.ownership.as_ref().map(|o| o.person_id),
+                    revoked: false,
+                });
+            }
+            DomainEvent::Key(KeyEvents::KeyRevoked(e)) => {
+                if let Some(key) = self.keys.get_mut(&e.key_id) {
+                    key.revoked = true;
+                }
+            }
+            DomainEvent::Certificate(CertificateEvents::CertificateGenerated(e)) => {
+                self.certificates.insert(e.cert_id, CertificateStatus::Active);
+            }
+            DomainEvent::Certificate(CertificateEvents::CertificateRevoked(e)) => {
+                self.certificates.insert(e.cert_id, CertificateStatus::Revoked);
+            }
+            DomainEvent::Certificate(CertificateEvents::CertificateRenewed(e)) => {
+                self.certificates.remove(&e.old_cert_id);
+            }
+            DomainEvent::Organization(OrganizationEvents::PolicySuperseded(e)) => {
+                let recorded = self
+                    .policies
+                    .iter()
+                    .any(|p| p.id == e.successor.id && p.version == e.successor.version);
+                if !recorded {
+                    self.policies.push(e.successor.clone());
+                }
+            }
+            DomainEvent::Relationship(RelationshipEvents::RelationshipEstablished(e))
+                if matches!(e.relationship_type, RelationshipType::PolicyGovernsEntity) =>
+            {
+                self.policy_bindings.insert(e.relationship_id, (e.from_id, e.to_id));
+            }
+            DomainEvent::Relationship(RelationshipEvents::RelationshipTerminated(e)) => {
+                self.policy_bindings.remove(&e.relationship_id);
+            }
+            DomainEvent::Person(PersonEvents::RoleAssigned(e)) => {
+                self.role_assignments
+                    .entry(e.person_id)
+                    .or_default()
+                    .insert(e.role_id, e.required_policies.clone());
+            }
+            DomainEvent::Person(PersonEvents::RoleRemoved(e)) => {
+                if let Some(roles) = self.role_assignments.get_mut(&e.person_id) {
+                    roles.remove(&e.role_id);
+                }
+            }
+            DomainEvent::Approval(ApprovalEvents::ApprovalRequested(e)) => {
+                self.approvals.insert(e.approval_id, PendingApproval::from_requested(e));
+            }
+            DomainEvent::Approval(e) => {
+                if let Some(approval) = self.approvals.get_mut(&cim_domain::DomainEvent::aggregate_id(e)) {
+                    approval.apply(e);
+                }
+            }
+            _ => {}
+        }
+        self.version = event.sequence;
+        self.events_since_snapshot += 1;
+    }
+
+    /// Serialize the aggregate's current state as a snapshot
+    pub fn to_snapshot(&self) -> Result<AggregateSnapshot, EventStoreError> {
+        Ok(AggregateSnapshot {
+            aggregate_id: self.id,
+            aggregate_type: AGGREGATE_TYPE.to_string(),
+            sequence: self.version,
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            taken_at: chrono::Utc::now(),
+            state: serde_json::to_value(self)
+                .map_err(|e| EventStoreError::SerializationError(e.to_string()))?,
+        })
+    }
+
+    /// Restore an aggregate from a snapshot
+    ///
+    /// Returns `None` for snapshots of another aggregate type or format
+    /// version, which callers treat as no snapshot at all.
+    pub fn from_snapshot(snapshot: &AggregateSnapshot) -> Result<Option<Self>, EventStoreError> {
+        if snapshot.aggregate_type != AGGREGATE_TYPE || snapshot.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Ok(None);
+        }
+        let aggregate: Self = serde_json::from_value(snapshot.state.clone())
+            .map_err(|e| EventStoreError::SerializationError(e.to_string()))?;
+        if aggregate.id != snapshot.aggregate_id || aggregate.version != snapshot.sequence {
+            return Err(EventStoreError::SerializationError(format!(
+                "Snapshot of {} at {} holds aggregate {} at {}",
+                snapshot.aggregate_id, snapshot.sequence, aggregate.id, aggregate.version
+            )));
+        }
+        Ok(Some(aggregate))
+    }
+
+    /// Use the given security policy when handling commands
+    pub fn with_security_config(mut self, security: crate::config::SecurityConfig) -> Self {
+        self.security = security;
+        self
+    }
+
+    /// Enforce the given organization policies when handling commands
+    pub fn with_policies(mut self, policies: Vec<crate::domain::Policy>) -> Self {
+        self.policies = policies;
+        self
+    }
+
+    /// Require the claims of the given table when authorizing commands
+    pub fn with_authorizer(mut self, authorizer: crate::commands::CommandAuthorizer) -> Self {
+        self.authorizer = authorizer;
+        self
+    }
+
+    /// Require approvals before the given kinds of sensitive command run
+    pub fn with_approval_rules(mut self, rules: Vec<ApprovalRule>) -> Self {
+        self.approval_rules = rules;
+        self
+    }
+
+    /// Refuse policy bindings and role assignments that break these rules
+    pub fn with_separation_rules(mut self, rules: Vec<SeparationOfDutiesRule>) -> Self {
+        self.separation_rules = rules;
+        self
+    }
+
+    /// Evaluate the policies bound to the acting person in `context`
+    ///
+    /// Policies come from the person's policy bindings and roles; their
+    /// conditions are checked against `context`.
+    pub fn evaluate_actor(&self, context: &crate::domain::PolicyEvaluationContext) -> crate::domain::PolicyEvaluation {
+        use crate::domain::{PolicyBinding, PolicyEntityType};
+
+        let person_id = context.person_id;
+        let bound = self
+            .policy_bindings
+            .iter()
+            .filter(|(_, (_, entity))| *entity == person_id)
+            .map(|(relationship_id, (policy_id, _))| (*relationship_id, *policy_id));
+        let from_roles = self
+            .role_assignments
+            .get(&person_id)
+            .into_iter()
+            .flat_map(|roles| roles.iter())
+            .flat_map(|(role_id, policies)| policies.iter().map(move |policy_id| (*role_id, *policy_id)));
+        let bindings: Vec<PolicyBinding> = bound
+            .chain(from_roles)
+            .map(|(id, policy_id)| PolicyBinding {
+                id,
+                policy_id,
+                entity_id: person_id,
+                entity_type: PolicyEntityType::Person,
+                bound_at: context.current_time,
+                bound_by: self.id,
+                active: true,
+            })
+            .collect();
+
+        crate::domain::evaluate_policies(&self.policies, &bindings, person_id, PolicyEntityType::Person, context)
+    }
+
+    /// Check the lifecycle transition a command makes against the actor's claims
+    ///
+    /// Revoking a key and suspending, deactivating or archiving a person go
+    /// through the state machine's [`TransitionGuard`]; a refusal becomes a
+    /// `TransitionDenied` event. Like command authorization, nothing is
+    /// guarded while no policies are in force, nor for entities the
+    /// projection holds no state for.
+    ///
+    /// [`TransitionGuard`]: crate::state_machines::TransitionGuard
+    fn guard_transition(
+        &self,
+        command: &crate::commands::KeyCommand,
+        projection: &crate::projections::OfflineKeyProjection,
+        actor: &crate::domain::PolicyEvaluation,
+    ) -> Option<DomainEvent> {
+        use crate::commands::KeyCommand;
+        use crate::state_machines::{KeyState, PersonState, TransitionGuard};
+
+        if crate::domain::effective_policies(&self.policies, actor.evaluated_at).is_empty() {
+            return None;
         }
+
+        let person_state = |person_id: Uuid| {
+            projection
+                .get_people()
+                .iter()
+                .find(|p| p.person_id == person_id)
+                .and_then(|p| p.state.clone())
+        };
+
+        let denied = match command {
+            KeyCommand::RevokeKey(cmd) => {
+                let from = projection
+                    .get_keys()
+                    .iter()
+                    .find(|k| k.key_id == cmd.key_id)
+                    .and_then(|k| k.state.clone())?;
+                let to = KeyState::Revoked {
+                    reason: (&cmd.reason).into(),
+                    revoked_at: cmd.timestamp,
+                    revoked_by: cmd.revoked_by,
+                };
+                TransitionGuard::<KeyState>::new().authorize(cmd.key_id, &from, &to, actor)
+            }
+            KeyCommand::SuspendPerson(cmd) => {
+                let from = person_state(cmd.person_id)?;
+                let to = PersonState::Suspended {
+                    reason: cmd.reason.clone(),
+                    suspended_at: cmd.timestamp,
+                    suspended_by: cmd.suspended_by,
+                    previous_roles: from.roles().map(<[Uuid]>::to_vec).unwrap_or_default(),
+                };
+                TransitionGuard::<PersonState>::new().authorize(cmd.person_id, &from, &to, actor)
+            }
+            KeyCommand::DeactivatePerson(cmd) => {
+                let from = person_state(cmd.person_id)?;
+                let to = PersonState::Deactivated {
+                    reason: cmd.reason.clone(),
+                    deactivated_at: cmd.timestamp,
+                    deactivated_by: cmd.deactivated_by,
+                };
+                TransitionGuard::<PersonState>::new().authorize(cmd.person_id, &from, &to, actor)
+            }
+            KeyCommand::ArchivePerson(cmd) => {
+                let from = person_state(cmd.person_id)?;
+                let to = PersonState::Archived {
+                    archived_at: cmd.timestamp,
+                    archived_by: cmd.archived_by,
+                    retention_policy_id: None,
+                };
+                TransitionGuard::<PersonState>::new().authorize(cmd.person_id, &from, &to, actor)
+            }
+            _ => return None,
+        };
+
+        let denied = denied.err()?;
+        tracing::warn!("Denied {}: missing {:?}", denied, denied.missing_claims);
+        let command_id = command.command_id();
+        Some(DomainEvent::Authorization(
+            crate::events::AuthorizationEvents::TransitionDenied(denied.to_event(command_id, Some(command_id))),
+        ))
+    }
+
+    /// Claims an entity holds through its policy bindings and roles,
+    /// regardless of the policies' conditions
+    pub fn held_claims(&self, entity_id: Uuid) -> Vec<crate::domain::PolicyClaim> {
+        let bound = self
+            .policy_bindings
+            .values()
+            .filter(|(_, entity)| *entity == entity_id)
+            .map(|(policy_id, _)| *policy_id);
+        let from_roles = self
+            .role_assignments
+            .get(&entity_id)
+            .into_iter()
+            .flat_map(|roles| roles.values().flatten().copied());
+        self.claims_of(bound.chain(from_roles))
+    }
+
+    /// Claims granted by the latest versions of the given policies
+    fn claims_of(&self, policy_ids: impl IntoIterator<Item = Uuid>) -> Vec<crate::domain::PolicyClaim> {
+        let mut claims = Vec::new();
+        for policy in policy_ids.into_iter().filter_map(|id| self.latest_policy(id).ok()) {
+            for claim in &policy.claims {
+                if !claims.contains(claim) {
+                    claims.push(claim.clone());
+                }
+            }
+        }
+        claims
+    }
+
+    fn pending_approval(&self, approval_id: Uuid) -> Result<&PendingApproval, KeyManagementError> {
+        self.approvals
+            .get(&approval_id)
+            .ok_or_else(|| KeyManagementError::NotFound(format!("Approval {}", approval_id)))
+    }
+
+    /// Latest recorded version of a policy
+    fn latest_policy(&self, policy_id: Uuid) -> Result<&crate::domain::Policy, KeyManagementError> {
+        self.policies
+            .iter()
+            .filter(|p| p.id.as_uuid() == policy_id)
+            .max_by_key(|p| p.version)
+            .ok_or_else(|| KeyManagementError::NotFound(format!("Policy {}", policy_id)))
+    }
+
+    /// Security configuration tightened by the policies in force now
+    ///
+    /// A policy that grants `CanExportKeys` only with
+    /// `PolicyCondition::RequiresEncryptedExport` overrides
+    /// `allow_unencrypted_secret_export`.
+    pub fn effective_security(&self) -> crate::config::SecurityConfig {
+        let mut security = self.security;
+        let in_force = crate::domain::effective_policies(&self.policies, chrono::Utc::now());
+        if in_force.iter().any(|p| p.requires_encrypted_export()) {
+            security.allow_unencrypted_secret_export = false;
+        }
+        security
     }
 
     /// Handle a command by routing to the appropriate handler
     ///
     /// Routes KeyCommand variants to their corresponding handler functions.
     /// Each handler validates the command and emits domain events.
+    ///
+    /// Commands are idempotent: a command whose ID the projection has
+    /// already recorded (see `OfflineKeyProjection::record_processed_command`)
+    /// emits no events, so a retry after a crash cannot generate keys twice.
+    ///
+    /// Every command is first checked against the claims the policies of
+    /// `actor` grant (see `evaluate_actor` and `CommandAuthorizer`). A
+    /// command the actor may not issue emits only a `CommandDenied` event
+    /// naming the missing claims.
+    ///
+    /// A sensitive command covered by an approval rule is refused; it must
+    /// be parked with `RequestApproval` and submitted again wrapped in
+    /// `ExecuteApprovedCommand` once quorum is reached.
     pub async fn handle_command(
         &self,
         command: crate::commands::KeyCommand,
-        _projection: &crate::projections::OfflineKeyProjection,
+        projection: &crate::projections::OfflineKeyProjection,
         _nats_port: Option<()>,
-        #[cfg(feature = "policy")]
-        _policy_engine: Option<()>,
+        actor: &crate::domain::PolicyEvaluationContext,
+    ) -> Result<Vec<crate::events::DomainEvent>, KeyManagementError> {
+        use crate::commands::KeyCommand;
+
+        if projection.was_processed(command.command_id()) {
+            tracing::debug!("Skipping already processed command {}", command.command_id());
+            return Ok(Vec::new());
+        }
+
+        let evaluation = self.evaluate_actor(actor);
+        if let Err(denied) = self.authorizer.authorize(&command, &evaluation, &self.policies) {
+            tracing::warn!(
+                "Denied {} {} for {}: missing {:?}",
+                denied.command_type, denied.command_id, denied.actor_id, denied.missing_claims
+            );
+            return Ok(vec![DomainEvent::Authorization(
+                crate::events::AuthorizationEvents::CommandDenied(*denied),
+            )]);
+        }
+
+        if let KeyCommand::ExecuteApprovedCommand(cmd) = command {
+            let approval = self.pending_approval(cmd.approval_id)?;
+            let executed = crate::commands::approval::authorize_approved_command(&cmd, approval)?;
+            // Approval does not lift the lifecycle guard of the inner command
+            if let Some(denied) = self.guard_transition(&cmd.command, projection, &evaluation) {
+                return Ok(vec![denied]);
+            }
+            let mut events = vec![executed];
+            events.extend(self.execute(*cmd.command, projection, &evaluation).await?);
+            return Ok(events);
+        }
+
+        if let Some(kind) = SensitiveCommandKind::of(&command) {
+            if let Some(rule) = self.approval_rules.iter().find(|r| r.kind == kind) {
+                return Err(KeyManagementError::PolicyViolation(format!(
+                    "{} requires {} approvals; submit it with RequestApproval",
+                    kind, rule.required_approvals
+                )));
+            }
+        }
+
+        if let Some(denied) = self.guard_transition(&command, projection, &evaluation) {
+            return Ok(vec![denied]);
+        }
+
+        self.execute(command, projection, &evaluation).await
+    }
+
+    /// Run a command that has passed deduplication and approval checks
+    ///
+    /// `actor` is the evaluation of the acting person; approval decisions
+    /// are checked against its claims. Commands that depend on projected
+    /// state (a key's current expiration) read it from `projection`.
+    async fn execute(
+        &self,
+        command: crate::commands::KeyCommand,
+        projection: &crate::projections::OfflineKeyProjection,
+        actor: &crate::domain::PolicyEvaluation,
     ) -> Result<Vec<crate::events::DomainEvent>, KeyManagementError> {
         use crate::commands::KeyCommand;
 
@@ -84,6 +545,7 @@ impl KeyManagementAggregate {
                     purpose: key_purpose,
                     algorithm: Some(crate::events::KeyAlgorithm::Ed25519),
                     owner_context: key_context,
+                    expires_in_days: None,
                     correlation_id,
                     causation_id: Some(*cmd.command_id.as_uuid()), // A4: Causation from parent command
                 };
@@ -146,6 +608,7 @@ impl KeyManagementAggregate {
                     purpose: key_purpose,
                     algorithm: Some(crate::events::KeyAlgorithm::Ed25519), // Ed25519 for SSH
                     owner_context: key_context,
+                    expires_in_days: None,
                     correlation_id: Uuid::now_v7(),
                     causation_id: Some(*cmd.command_id.as_uuid()), // A4: Causation from parent command
                 };
@@ -165,6 +628,9 @@ impl KeyManagementAggregate {
                     .map_err(|e| KeyManagementError::ProjectionError(e))?;
                 Ok(result.events)
             }
+            KeyCommand::RevokeKey(cmd) => {
+                crate::commands::pki::handle_revoke_key(cmd).map_err(KeyManagementError::InvalidCommand)
+            }
             KeyCommand::CreateOrganization(cmd) => {
                 crate::commands::organization::handle_create_organization(cmd).await
             }
@@ -180,12 +646,84 @@ impl KeyManagementAggregate {
             KeyCommand::CreateServiceAccount(cmd) => {
                 crate::commands::organization::handle_create_service_account(cmd).await
             }
+            KeyCommand::SuspendPerson(cmd) => {
+                crate::commands::organization::handle_suspend_person(cmd).await
+            }
+            KeyCommand::ArchivePerson(cmd) => {
+                crate::commands::organization::handle_archive_person(cmd).await
+            }
+            KeyCommand::DeactivatePerson(cmd) => {
+                crate::commands::organization::handle_deactivate_person(cmd).await
+            }
+            KeyCommand::TerminateRelationship(cmd) => {
+                crate::commands::organization::handle_terminate_relationship(cmd).await
+            }
+            KeyCommand::UnbindPolicy(cmd) => {
+                crate::commands::organization::handle_unbind_policy(cmd).await
+            }
+            KeyCommand::BindPolicy(cmd) => {
+                let policy = self.latest_policy(cmd.policy_id)?;
+                let held = self.held_claims(cmd.entity_id);
+                crate::commands::organization::handle_bind_policy(cmd, policy, &held, &self.separation_rules).await
+            }
+            KeyCommand::AssignRole(cmd) => {
+                for policy_id in &cmd.required_policies {
+                    self.latest_policy(*policy_id)?;
+                }
+                let granted = self.claims_of(cmd.required_policies.iter().copied());
+                let held = self.held_claims(cmd.person_id);
+                crate::commands::organization::handle_assign_role(cmd, &granted, &held, &self.separation_rules).await
+            }
+            KeyCommand::SupersedePolicy(cmd) => {
+                let current = self.latest_policy(cmd.policy_id)?;
+                crate::commands::organization::handle_supersede_policy(cmd, current).await
+            }
             KeyCommand::CreateDelegation(cmd) => {
                 crate::commands::delegation::handle_create_delegation(cmd).await
             }
             KeyCommand::RevokeDelegation(cmd) => {
                 crate::commands::delegation::handle_revoke_delegation(cmd).await
             }
+            KeyCommand::ExportGpgSecretKey(cmd) => {
+                crate::commands::gpg::handle_export_gpg_secret_key(cmd, &self.effective_security())
+            }
+            KeyCommand::ExportSshPrivateKey(cmd) => {
+                crate::commands::ssh::handle_export_ssh_private_key(cmd, &self.effective_security())
+            }
+            KeyCommand::GenerateSshSecurityKey(cmd) => {
+                crate::commands::ssh::handle_generate_ssh_security_key(cmd)
+            }
+            KeyCommand::ImportSshPublicKey(cmd) => {
+                crate::commands::ssh::handle_import_ssh_public_key(cmd, &self.effective_security())
+            }
+            KeyCommand::RecordKeyExpirationExtension(cmd) => {
+                let key = projection
+                    .get_keys()
+                    .iter()
+                    .find(|k| k.key_id == cmd.key_id)
+                    .ok_or_else(|| KeyManagementError::NotFound(format!("Key {}", cmd.key_id)))?;
+                crate::commands::gpg::handle_record_key_expiration_extension(cmd, key.expires_at)
+            }
+            KeyCommand::MoveGpgKeyToCard(cmd) => {
+                crate::commands::gpg::handle_move_gpg_key_to_card(cmd)
+            }
+            KeyCommand::CertifyGpgKey(cmd) => {
+                crate::commands::gpg::handle_certify_gpg_key(cmd)
+            }
+            KeyCommand::RequestApproval(cmd) => {
+                crate::commands::approval::handle_request_approval(cmd, &self.approval_rules, actor)
+            }
+            KeyCommand::GrantApproval(cmd) => {
+                let approval = self.pending_approval(cmd.approval_id)?;
+                crate::commands::approval::handle_grant_approval(cmd, approval, actor)
+            }
+            KeyCommand::RejectApproval(cmd) => {
+                let approval = self.pending_approval(cmd.approval_id)?;
+                crate::commands::approval::handle_reject_approval(cmd, approval, actor)
+            }
+            KeyCommand::ExecuteApprovedCommand(_) => Err(KeyManagementError::InvalidCommand(
+                "Approved commands cannot be nested".to_string(),
+            )),
         }
     }
 }
@@ -231,6 +769,84 @@ impl AggregateRoot for KeyManagementAggregate {
     }
 }
 
+/// Loads and saves [`KeyManagementAggregate`]s through an event store,
+/// snapshotting to bound replay time
+///
+/// With `snapshot_every` set, a snapshot is written once that many events
+/// have been applied since the last one; [`KeyManagementRepository::snapshot`]
+/// takes one on demand. Loading starts from the latest usable snapshot and
+/// replays only the events after it.
+pub struct KeyManagementRepository<E, S> {
+    events: E,
+    snapshots: S,
+    snapshot_every: Option<u64>,
+}
+
+impl<E: EventStore, S: SnapshotStore> KeyManagementRepository<E, S> {
+    /// Repository that snapshots only on demand
+    pub fn new(events: E, snapshots: S) -> Self {
+        Self {
+            events,
+            snapshots,
+            snapshot_every: None,
+        }
+    }
+
+    /// Snapshot automatically every `events` applied events
+    pub fn with_snapshot_every(mut self, events: u64) -> Self {
+        self.snapshot_every = (events > 0).then_some(events);
+        self
+    }
+
+    /// Rehydrate an aggregate from its latest snapshot and later events
+    pub async fn load(&self, id: Uuid) -> Result<KeyManagementAggregate, EventStoreError> {
+        let snapshot = match self.snapshots.load_latest(id).await? {
+            Some(snapshot) => KeyManagementAggregate::from_snapshot(&snapshot)?,
+            None => None,
+        };
+        let mut aggregate = snapshot.unwrap_or_else(|| KeyManagementAggregate::new(id));
+
+        for event in self.events.load_after(id, aggregate.version).await? {
+            aggregate.apply(&event);
+        }
+        self.snapshot_if_due(&mut aggregate).await?;
+        Ok(aggregate)
+    }
+
+    /// Append events emitted for `aggregate` and fold them in
+    ///
+    /// Fails with `ConcurrencyConflict` if the stream moved past the
+    /// aggregate's version since it was loaded.
+    pub async fn save(
+        &self,
+        aggregate: &mut KeyManagementAggregate,
+        events: &[EventEnvelope],
+    ) -> Result<(), EventStoreError> {
+        let from = aggregate.version;
+        self.events
+            .append(aggregate.id, ExpectedSequence::Exact(from), events)
+            .await?;
+        for event in self.events.load_after(aggregate.id, from).await? {
+            aggregate.apply(&event);
+        }
+        self.snapshot_if_due(aggregate).await
+    }
+
+    /// Snapshot the aggregate now
+    pub async fn snapshot(&self, aggregate: &mut KeyManagementAggregate) -> Result<(), EventStoreError> {
+        self.snapshots.save(&aggregate.to_snapshot()?).await?;
+        aggregate.events_since_snapshot = 0;
+        Ok(())
+    }
+
+    async fn snapshot_if_due(&self, aggregate: &mut KeyManagementAggregate) -> Result<(), EventStoreError> {
+        match self.snapshot_every {
+            Some(every) if aggregate.events_since_snapshot >= every => self.snapshot(aggregate).await,
+            _ => Ok(()),
+        }
+    }
+}
+
 // TODO: Re-implement command handlers to work with new modular command structure
 // For now, command handlers are standalone functions in src/commands/
 // - nats_identity.rs: handle_create_nats_operator, handle_create_nats_account, handle_create_nats_user
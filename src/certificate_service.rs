@@ -9,8 +9,20 @@ use rcgen::{
     SanType, BasicConstraints, IsCa, KeyUsagePurpose,
     ExtendedKeyUsagePurpose, KeyPair, Issuer,
 };
+use chrono::Utc;
 use time::{Duration, OffsetDateTime};
-use crate::events::certificate::CertificateGeneratedEvent;
+use uuid::Uuid;
+use crate::crypto::pkcs12::build_pkcs12;
+use crate::crypto::timestamp::{
+    generate_timestamp_authority_certificate, TimestampAuthorityCertificate, TimestampAuthorityParams,
+};
+use crate::crypto::x509::{
+    csr_constrained_names, issue_certificate, name_constraint_violations, IssuedCertificate,
+    LeafCertificateParams,
+};
+use crate::domain::{Person, PolicyCA, PolicyClaim, PolicyConstraint, PolicyEvaluation, PolicyPurpose};
+use crate::types::KeyPurpose;
+use crate::events::certificate::{CertificateGeneratedEvent, CertificateIssuanceRejectedEvent};
 
 /// Result of certificate generation
 pub struct GeneratedCertificate {
@@ -277,4 +289,532 @@ pub fn generate_leaf_certificate_from_event(
         public_key_pem: String::new(),
         fingerprint,
     })
-}
\ No newline at end of file
+}
+
+/// Issue a certificate for a CSR through a policy CA
+///
+/// The policy CA's `NameConstraints` apply to the CSR subject and SANs, and
+/// its remaining constraints (`KeyUsageRestriction`, `ValidityPeriodMax`)
+/// are enforced by `issue_certificate`. A refused request yields a
+/// `CertificateIssuanceRejected` event naming the offending names, for the
+/// caller to record.
+#[allow(clippy::too_many_arguments)]
+pub fn issue_certificate_from_policy_ca<S: rcgen::SigningKey>(
+    csr_pem: &str,
+    params: LeafCertificateParams,
+    policy_ca: &PolicyCA,
+    issuer_chain_pem: &str,
+    issuer_signer: S,
+    issuer_ca_id: Uuid,
+    correlation_id: Uuid,
+    causation_id: Option<Uuid>,
+) -> Result<IssuedCertificate, Box<CertificateIssuanceRejectedEvent>> {
+    let reject = |subject: String, rejected_names: Vec<String>, reason: String| {
+        Box::new(CertificateIssuanceRejectedEvent {
+            rejection_id: Uuid::now_v7(),
+            issuer_ca_id,
+            policy_ca: policy_ca.name.clone(),
+            subject,
+            rejected_names,
+            reason,
+            rejected_at: Utc::now(),
+            correlation_id,
+            causation_id,
+        })
+    };
+
+    let (common_name, names) = csr_constrained_names(csr_pem)
+        .map_err(|reason| reject(String::new(), Vec::new(), reason))?;
+
+    let violations = name_constraint_violations(&names, &policy_ca.constraints);
+    if !violations.is_empty() {
+        let reason = violations.iter().map(|(_, r)| r.as_str()).collect::<Vec<_>>().join("; ");
+        let rejected_names = violations.into_iter().map(|(name, _)| name).collect();
+        return Err(reject(common_name, rejected_names, reason));
+    }
+
+    issue_certificate(
+        csr_pem,
+        params,
+        issuer_chain_pem,
+        issuer_signer,
+        &policy_ca.constraints,
+        issuer_ca_id,
+        correlation_id,
+        causation_id,
+    )
+    .map_err(|reason| reject(common_name, Vec::new(), reason))
+}
+
+/// S/MIME certificate issued to a person, with its PKCS#12 export
+pub struct SmimeCertificate {
+    /// The issued certificate, chain and events
+    pub issued: IssuedCertificate,
+    /// The person's new private key (PKCS#8 PEM)
+    pub private_key_pem: String,
+    /// Passphrase-protected PKCS#12 bundle for the person's mail client
+    pub pkcs12: Vec<u8>,
+}
+
+/// Issue an S/MIME certificate for a person through an email encryption
+/// policy CA
+///
+/// A fresh key pair is generated for the person and certified with the
+/// emailProtection profile: the subject is the person's name and their
+/// email address is the sole rfc822Name SAN, so the policy CA's
+/// `NameConstraints` apply to the mail domain. The certificate, chain and
+/// key are bundled into a PKCS#12 protected by `pkcs12_passphrase`.
+#[allow(clippy::too_many_arguments)]
+pub fn issue_smime_certificate<S: rcgen::SigningKey>(
+    person: &Person,
+    policy_ca: &PolicyCA,
+    validity_days: u32,
+    issuer_chain_pem: &str,
+    issuer_signer: S,
+    issuer_ca_id: Uuid,
+    pkcs12_passphrase: &str,
+    correlation_id: Uuid,
+    causation_id: Option<Uuid>,
+) -> Result<SmimeCertificate, Box<CertificateIssuanceRejectedEvent>> {
+    let reject = |reason: String| {
+        Box::new(CertificateIssuanceRejectedEvent {
+            rejection_id: Uuid::now_v7(),
+            issuer_ca_id,
+            policy_ca: policy_ca.name.clone(),
+            subject: person.name.clone(),
+            rejected_names: vec![person.email.clone()],
+            reason,
+            rejected_at: Utc::now(),
+            correlation_id,
+            causation_id,
+        })
+    };
+
+    if !matches!(policy_ca.purpose, PolicyPurpose::EmailEncryption) {
+        return Err(reject(format!(
+            "Policy CA {} issues {:?} certificates, not S/MIME",
+            policy_ca.name, policy_ca.purpose
+        )));
+    }
+    let (local_part, domain) = person.email.split_once('@').unwrap_or_default();
+    if local_part.is_empty() || !domain.contains('.') {
+        return Err(reject(format!("{} is not a valid email address", person.email)));
+    }
+
+    let key_pair = KeyPair::generate()
+        .map_err(|e| reject(format!("Failed to generate key pair: {}", e)))?;
+    let mut csr_params = CertificateParams::new(Vec::new())
+        .map_err(|e| reject(format!("Failed to create certificate params: {}", e)))?;
+    csr_params.distinguished_name.push(DnType::CommonName, person.name.as_str());
+    csr_params.subject_alt_names = vec![SanType::Rfc822Name(
+        person.email.as_str().try_into()
+            .map_err(|e| reject(format!("Invalid email address {}: {}", person.email, e)))?,
+    )];
+    let csr_pem = csr_params
+        .serialize_request(&key_pair)
+        .and_then(|csr| csr.pem())
+        .map_err(|e| reject(format!("Failed to create certificate signing request: {}", e)))?;
+
+    let params = LeafCertificateParams {
+        purpose: KeyPurpose::Encryption,
+        validity_days,
+        key_id: Uuid::now_v7(),
+    };
+    let issued = issue_certificate_from_policy_ca(
+        &csr_pem,
+        params,
+        policy_ca,
+        issuer_chain_pem,
+        issuer_signer,
+        issuer_ca_id,
+        correlation_id,
+        causation_id,
+    )?;
+
+    let private_key_pem = key_pair.serialize_pem();
+    let friendly_name = format!("{} <{}>", person.name, person.email);
+    let pkcs12 = build_pkcs12(
+        &issued.certificate_pem,
+        &issued.chain_pem,
+        &private_key_pem,
+        pkcs12_passphrase,
+        &friendly_name,
+    )
+    .map_err(reject)?;
+
+    Ok(SmimeCertificate {
+        issued,
+        private_key_pem,
+        pkcs12,
+    })
+}
+
+/// Issue a code-signing certificate for a CSR through a code signing
+/// policy CA
+///
+/// Only subjects whose policy evaluation grants `CanSignCode` are certified.
+/// The certificate carries the codeSigning EKU and digitalSignature only;
+/// signatures made with it should be timestamped by a TSA from
+/// [`issue_timestamp_authority_certificate`] so they verify after expiry.
+#[allow(clippy::too_many_arguments)]
+pub fn issue_code_signing_certificate<S: rcgen::SigningKey>(
+    csr_pem: &str,
+    key_id: Uuid,
+    validity_days: u32,
+    subject_claims: &PolicyEvaluation,
+    policy_ca: &PolicyCA,
+    issuer_chain_pem: &str,
+    issuer_signer: S,
+    issuer_ca_id: Uuid,
+    correlation_id: Uuid,
+    causation_id: Option<Uuid>,
+) -> Result<IssuedCertificate, Box<CertificateIssuanceRejectedEvent>> {
+    let reject = |reason: String| {
+        Box::new(CertificateIssuanceRejectedEvent {
+            rejection_id: Uuid::now_v7(),
+            issuer_ca_id,
+            policy_ca: policy_ca.name.clone(),
+            subject: subject_claims.entity_id.to_string(),
+            rejected_names: Vec::new(),
+            reason,
+            rejected_at: Utc::now(),
+            correlation_id,
+            causation_id,
+        })
+    };
+
+    if !matches!(policy_ca.purpose, PolicyPurpose::CodeSigning) {
+        return Err(reject(format!(
+            "Policy CA {} issues {:?} certificates, not code signing",
+            policy_ca.name, policy_ca.purpose
+        )));
+    }
+    if !subject_claims.granted_claims.contains(&PolicyClaim::CanSignCode) {
+        return Err(reject(format!(
+            "Subject {} lacks the CanSignCode claim",
+            subject_claims.entity_id
+        )));
+    }
+
+    let params = LeafCertificateParams {
+        purpose: KeyPurpose::Signing,
+        validity_days,
+        key_id,
+    };
+    issue_certificate_from_policy_ca(
+        csr_pem,
+        params,
+        policy_ca,
+        issuer_chain_pem,
+        issuer_signer,
+        issuer_ca_id,
+        correlation_id,
+        causation_id,
+    )
+}
+
+/// Issue a time-stamp authority certificate through a timestamp policy CA
+///
+/// The TSA certificate is capped by the policy CA's `ValidityPeriodMax`.
+/// `issuer_cert_pem` is the certificate of the key backing the policy CA.
+#[allow(clippy::too_many_arguments)]
+pub fn issue_timestamp_authority_certificate<S: rcgen::SigningKey>(
+    params: TimestampAuthorityParams,
+    policy_ca: &PolicyCA,
+    issuer_cert_pem: &str,
+    issuer_signer: S,
+    issuer_ca_id: Uuid,
+    correlation_id: Uuid,
+    causation_id: Option<Uuid>,
+) -> Result<TimestampAuthorityCertificate, Box<CertificateIssuanceRejectedEvent>> {
+    let reject = |reason: String| {
+        Box::new(CertificateIssuanceRejectedEvent {
+            rejection_id: Uuid::now_v7(),
+            issuer_ca_id,
+            policy_ca: policy_ca.name.clone(),
+            subject: params.common_name.clone(),
+            rejected_names: Vec::new(),
+            reason,
+            rejected_at: Utc::now(),
+            correlation_id,
+            causation_id,
+        })
+    };
+
+    if !matches!(policy_ca.purpose, PolicyPurpose::TimestampAuthority) {
+        return Err(reject(format!(
+            "Policy CA {} issues {:?} certificates, not time-stamp authorities",
+            policy_ca.name, policy_ca.purpose
+        )));
+    }
+
+    let max_days = policy_ca.constraints.iter().find_map(|c| match c {
+        PolicyConstraint::ValidityPeriodMax { days } => Some(*days),
+        _ => None,
+    });
+    let validity_days = max_days.map_or(params.validity_days, |max| params.validity_days.min(max));
+
+    generate_timestamp_authority_certificate(
+        TimestampAuthorityParams { validity_days, ..params.clone() },
+        issuer_cert_pem,
+        issuer_signer,
+        issuer_ca_id,
+        correlation_id,
+        causation_id,
+    )
+    .map_err(reject)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::seed_derivation::derive_master_seed;
+    use crate::crypto::x509::{create_intermediate_ca, generate_root_ca, IntermediateCAParams, RootCAParams};
+    use crate::domain::{PolicyConstraint, PolicyPurpose};
+    use crate::types::KeyPurpose;
+
+    fn csr(common_name: &str, dns_names: &[&str]) -> String {
+        let key = KeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(dns_names.iter().map(|n| n.to_string()).collect::<Vec<_>>()).unwrap();
+        params.distinguished_name.push(DnType::CommonName, common_name);
+        params.serialize_request(&key).unwrap().pem().unwrap()
+    }
+
+    #[test]
+    fn test_policy_ca_name_constraints_reject_with_event() {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_seed = master_seed.derive_child("root-ca");
+        let (root, _) = generate_root_ca(&root_seed, RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = KeyPair::from_pem(&root.private_key_pem).unwrap();
+        let intermediate = create_intermediate_ca(
+            &root_seed.derive_child("devices"),
+            IntermediateCAParams::default(),
+            &root.certificate_pem,
+            &root_key,
+            &[],
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let intermediate_key = KeyPair::from_pem(&intermediate.certificate.private_key_pem).unwrap();
+
+        let policy_ca = PolicyCA {
+            name: "Device Authentication CA".to_string(),
+            purpose: PolicyPurpose::DeviceAuthentication,
+            constraints: vec![PolicyConstraint::NameConstraints {
+                permitted: vec!["devices.example.com".to_string()],
+                excluded: vec!["lab.devices.example.com".to_string()],
+            }],
+        };
+        let params = || LeafCertificateParams {
+            purpose: KeyPurpose::Authentication,
+            validity_days: 30,
+            key_id: Uuid::now_v7(),
+        };
+        let issue = |csr_pem: &str| {
+            issue_certificate_from_policy_ca(
+                csr_pem,
+                params(),
+                &policy_ca,
+                &intermediate.chain_pem,
+                &intermediate_key,
+                Uuid::now_v7(),
+                Uuid::now_v7(),
+                None,
+            )
+        };
+
+        assert!(issue(&csr("printer-1", &["printer-1.devices.example.com"])).is_ok());
+
+        let rejected = issue(&csr("printer-2", &["printer-2.devices.example.com", "printer-2.lab.devices.example.com"])).unwrap_err();
+        assert_eq!(rejected.policy_ca, "Device Authentication CA");
+        assert_eq!(rejected.rejected_names, vec!["printer-2.lab.devices.example.com"]);
+        assert!(rejected.reason.contains("excluded"));
+
+        // A host name in the subject is constrained even without SANs
+        let rejected = issue(&csr("printer.example.net", &[])).unwrap_err();
+        assert_eq!(rejected.subject, "printer.example.net");
+        assert_eq!(rejected.rejected_names, vec!["printer.example.net"]);
+        assert!(rejected.reason.contains("not permitted"));
+    }
+
+    #[test]
+    fn test_smime_certificate_for_person() {
+        use crate::domain::ids::BootstrapOrgId;
+        use p12_keystore::{KeyStore, Pkcs12ImportPolicy};
+        use x509_parser::prelude::*;
+
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_seed = master_seed.derive_child("root-ca");
+        let (root, _) = generate_root_ca(&root_seed, RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = KeyPair::from_pem(&root.private_key_pem).unwrap();
+        let intermediate = create_intermediate_ca(
+            &root_seed.derive_child("email"),
+            IntermediateCAParams::default(),
+            &root.certificate_pem,
+            &root_key,
+            &[],
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let intermediate_key = KeyPair::from_pem(&intermediate.certificate.private_key_pem).unwrap();
+
+        let email_ca = PolicyCA {
+            name: "Email Encryption CA".to_string(),
+            purpose: PolicyPurpose::EmailEncryption,
+            constraints: vec![PolicyConstraint::NameConstraints {
+                permitted: vec!["example.com".to_string()],
+                excluded: vec![],
+            }],
+        };
+        let issue = |person: &Person, policy_ca: &PolicyCA| {
+            issue_smime_certificate(
+                person,
+                policy_ca,
+                365,
+                &intermediate.chain_pem,
+                &intermediate_key,
+                Uuid::now_v7(),
+                "correct horse",
+                Uuid::now_v7(),
+                None,
+            )
+        };
+
+        let alice = Person::new("Alice Smith", "alice@example.com", BootstrapOrgId::new());
+        let smime = issue(&alice, &email_ca).unwrap();
+        assert_eq!(smime.issued.san, vec!["email:alice@example.com".to_string()]);
+
+        let (_, pem) = parse_x509_pem(smime.issued.certificate_pem.as_bytes()).unwrap();
+        let cert = pem.parse_x509().unwrap();
+        let eku = cert.extended_key_usage().unwrap().unwrap().value;
+        assert!(eku.email_protection && !eku.server_auth);
+        let san = cert.subject_alternative_name().unwrap().unwrap().value;
+        assert!(matches!(san.general_names.as_slice(), [GeneralName::RFC822Name("alice@example.com")]));
+
+        let keystore = KeyStore::from_pkcs12(&smime.pkcs12, "correct horse", Pkcs12ImportPolicy::Strict).unwrap();
+        let (alias, chain) = keystore.private_key_chain().unwrap();
+        assert_eq!(alias, "Alice Smith <alice@example.com>");
+        assert_eq!(chain.certs().len(), 3);
+
+        // The mail domain is subject to the policy CA's name constraints
+        let mallory = Person::new("Mallory", "mallory@example.net", BootstrapOrgId::new());
+        let rejected = issue(&mallory, &email_ca).err().unwrap();
+        assert_eq!(rejected.rejected_names, vec!["example.net"]);
+
+        // Only email encryption policy CAs issue S/MIME certificates
+        let code_ca = PolicyCA { purpose: PolicyPurpose::CodeSigning, ..email_ca.clone() };
+        let rejected = issue(&alice, &code_ca).err().unwrap();
+        assert!(rejected.reason.contains("not S/MIME"));
+    }
+
+    #[test]
+    fn test_code_signing_requires_claim_and_timestamps_signatures() {
+        use crate::crypto::timestamp::{verify_timestamp_token, TimestampAuthority};
+        use crate::domain::PolicyEntityType;
+        use rcgen::SigningKey;
+        use x509_parser::prelude::*;
+
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_seed = master_seed.derive_child("root-ca");
+        let (root, _) = generate_root_ca(&root_seed, RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = KeyPair::from_pem(&root.private_key_pem).unwrap();
+        let intermediate = create_intermediate_ca(
+            &root_seed.derive_child("code"),
+            IntermediateCAParams::default(),
+            &root.certificate_pem,
+            &root_key,
+            &[],
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let intermediate_key = KeyPair::from_pem(&intermediate.certificate.private_key_pem).unwrap();
+
+        let code_ca = PolicyCA {
+            name: "Code Signing CA".to_string(),
+            purpose: PolicyPurpose::CodeSigning,
+            constraints: vec![],
+        };
+        let evaluation = |claims: Vec<PolicyClaim>| PolicyEvaluation {
+            entity_id: Uuid::now_v7(),
+            entity_type: PolicyEntityType::Person,
+            active_policies: vec![],
+            policy_versions: vec![],
+            inactive_policies: vec![],
+            granted_claims: claims,
+            denied_claims: vec![],
+            evaluated_at: Utc::now(),
+        };
+        let developer_key = KeyPair::generate().unwrap();
+        let mut csr_params = CertificateParams::new(Vec::new()).unwrap();
+        csr_params.distinguished_name.push(DnType::CommonName, "Alice Smith");
+        let csr_pem = csr_params.serialize_request(&developer_key).unwrap().pem().unwrap();
+        let issue = |claims: &PolicyEvaluation, policy_ca: &PolicyCA| {
+            issue_code_signing_certificate(
+                &csr_pem,
+                Uuid::now_v7(),
+                365,
+                claims,
+                policy_ca,
+                &intermediate.chain_pem,
+                &intermediate_key,
+                Uuid::now_v7(),
+                Uuid::now_v7(),
+                None,
+            )
+        };
+
+        let rejected = issue(&evaluation(vec![PolicyClaim::CanSignCertificates]), &code_ca).err().unwrap();
+        assert!(rejected.reason.contains("CanSignCode"));
+        let email_ca = PolicyCA { purpose: PolicyPurpose::EmailEncryption, ..code_ca.clone() };
+        let rejected = issue(&evaluation(vec![PolicyClaim::CanSignCode]), &email_ca).err().unwrap();
+        assert!(rejected.reason.contains("not code signing"));
+
+        let issued = issue(&evaluation(vec![PolicyClaim::CanSignCode]), &code_ca).unwrap();
+        let (_, pem) = parse_x509_pem(issued.certificate_pem.as_bytes()).unwrap();
+        let cert = pem.parse_x509().unwrap();
+        let eku = cert.extended_key_usage().unwrap().unwrap().value;
+        assert!(eku.code_signing && !eku.server_auth);
+
+        // A TSA from the timestamp policy CA, capped by its validity limit
+        let tsa_ca = PolicyCA {
+            name: "Timestamp CA".to_string(),
+            purpose: PolicyPurpose::TimestampAuthority,
+            constraints: vec![PolicyConstraint::ValidityPeriodMax { days: 90 }],
+        };
+        let tsa = issue_timestamp_authority_certificate(
+            TimestampAuthorityParams::default(),
+            &tsa_ca,
+            &intermediate.certificate.certificate_pem,
+            &intermediate_key,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let (_, pem) = parse_x509_pem(tsa.certificate_pem.as_bytes()).unwrap();
+        let validity = pem.parse_x509().unwrap().validity().time_to_expiration().unwrap();
+        assert!(validity.whole_days() <= 90);
+        assert!(issue_timestamp_authority_certificate(
+            TimestampAuthorityParams::default(),
+            &code_ca,
+            &intermediate.certificate.certificate_pem,
+            &intermediate_key,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .is_err());
+
+        let signature = developer_key.sign(b"release artifact").unwrap();
+        let tsa_key = KeyPair::from_pem(&tsa.private_key_pem).unwrap();
+        let authority = TimestampAuthority::new(&tsa.certificate_pem, &tsa_key, "1.2.3.4.1").unwrap();
+        let token = authority.timestamp(&signature, None).unwrap();
+        assert!(verify_timestamp_token(&token.token_der, &signature, &tsa.certificate_pem).is_ok());
+    }
+}
@@ -0,0 +1,530 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Approval Commands
+//!
+//! Commands for the PendingApproval aggregate (see `domain::approval`).
+//! A sensitive command covered by an `ApprovalRule` is parked with
+//! `RequestApproval`. Qualified people approve it with `GrantApproval` or
+//! veto it with `RejectApproval`. Once quorum is reached it runs through
+//! `ExecuteApprovedCommand`.
+//!
+//! The person named in a command (requester, approver, rejecter) must be
+//! the acting person, and their claims come from evaluating their policy
+//! bindings (`KeyManagementAggregate::evaluate_actor`), never from the
+//! command itself.
+
+use chrono::Utc;
+use cim_domain::{Command, EntityId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::aggregate::KeyManagementError;
+use crate::commands::KeyCommand;
+use crate::domain::approval::{
+    command_digest, ApprovalRule, ApprovalStatus, PendingApproval, SensitiveCommandKind,
+};
+use crate::domain::PolicyEvaluation;
+use crate::events::approval::{
+    ApprovalEvents, ApprovalGrantedEvent, ApprovalQuorumReachedEvent, ApprovalRejectedEvent,
+    ApprovalRequestedEvent, ApprovedCommandExecutedEvent,
+};
+use crate::events::DomainEvent;
+
+/// Command to park a sensitive command until it is approved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestApproval {
+    pub command_id: Uuid,
+    pub approval_id: Uuid,
+    /// The sensitive command; only its digest is recorded
+    pub command: Box<KeyCommand>,
+    pub requested_by: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl Command for RequestApproval {
+    type Aggregate = PendingApproval;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.approval_id))
+    }
+}
+
+impl RequestApproval {
+    pub fn new(command: KeyCommand, requested_by: Uuid) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            approval_id: Uuid::now_v7(),
+            command: Box::new(command),
+            requested_by,
+            correlation_id: command_id,
+            causation_id: None,
+        }
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Command to approve a parked command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrantApproval {
+    pub command_id: Uuid,
+    pub approval_id: Uuid,
+    pub approver_id: Uuid,
+    pub comment: Option<String>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl Command for GrantApproval {
+    type Aggregate = PendingApproval;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.approval_id))
+    }
+}
+
+impl GrantApproval {
+    pub fn new(approval_id: Uuid, approver_id: Uuid) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            approval_id,
+            approver_id,
+            comment: None,
+            correlation_id: command_id,
+            causation_id: None,
+        }
+    }
+
+    /// Record why the approver agreed
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+}
+
+/// Command to reject a parked command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectApproval {
+    pub command_id: Uuid,
+    pub approval_id: Uuid,
+    pub rejected_by: Uuid,
+    pub reason: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl Command for RejectApproval {
+    type Aggregate = PendingApproval;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.approval_id))
+    }
+}
+
+impl RejectApproval {
+    pub fn new(approval_id: Uuid, rejected_by: Uuid, reason: impl Into<String>) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            approval_id,
+            rejected_by,
+            reason: reason.into(),
+            correlation_id: command_id,
+            causation_id: None,
+        }
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+}
+
+/// Command to execute a command once its approval reached quorum
+///
+/// The command must be resubmitted exactly as it was approved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecuteApprovedCommand {
+    pub command_id: Uuid,
+    pub approval_id: Uuid,
+    pub command: Box<KeyCommand>,
+    pub correlation_id: Uuid,
+}
+
+impl Command for ExecuteApprovedCommand {
+    type Aggregate = PendingApproval;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.approval_id))
+    }
+}
+
+impl ExecuteApprovedCommand {
+    pub fn new(approval_id: Uuid, command: KeyCommand) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            approval_id,
+            command: Box::new(command),
+            correlation_id: command_id,
+        }
+    }
+}
+
+// ============================================================================
+// Command Handlers
+// ============================================================================
+
+/// Refuse a command naming someone other than the acting person
+fn require_actor(named: Uuid, actor: &PolicyEvaluation, role: &str) -> Result<(), KeyManagementError> {
+    if named != actor.entity_id {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "{} {} is not the acting person {}",
+            role, named, actor.entity_id
+        )));
+    }
+    Ok(())
+}
+
+/// Handle RequestApproval command
+///
+/// The parked command must be sensitive and covered by one of `rules`, and
+/// `requester` (the acting person) must be the one named as requester.
+///
+/// Emits:
+/// - ApprovalRequestedEvent
+pub fn handle_request_approval(
+    cmd: RequestApproval,
+    rules: &[ApprovalRule],
+    requester: &PolicyEvaluation,
+) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    require_actor(cmd.requested_by, requester, "Requester")?;
+    let kind = SensitiveCommandKind::of(&cmd.command).ok_or_else(|| {
+        KeyManagementError::InvalidCommand("Command does not require approval".to_string())
+    })?;
+    let rule = rules.iter().find(|r| r.kind == kind).ok_or_else(|| {
+        KeyManagementError::InvalidCommand(format!("No approval rule covers {}", kind))
+    })?;
+    if rule.required_approvals == 0 {
+        return Err(KeyManagementError::InvalidCommand(format!(
+            "Approval rule for {} requires no approvals",
+            kind
+        )));
+    }
+
+    let command_digest = command_digest(&cmd.command).map_err(KeyManagementError::InvalidCommand)?;
+
+    Ok(vec![DomainEvent::Approval(ApprovalEvents::ApprovalRequested(
+        ApprovalRequestedEvent {
+            approval_id: cmd.approval_id,
+            command_id: cmd.command.command_id(),
+            command_kind: kind,
+            command_digest,
+            required_approvals: rule.required_approvals,
+            approver_claims: rule.approver_claims.clone(),
+            requested_by: cmd.requested_by,
+            requested_at: Utc::now(),
+            correlation_id: cmd.correlation_id,
+            causation_id: cmd.causation_id,
+        },
+    ))])
+}
+
+/// Handle GrantApproval command
+///
+/// The approver must be the acting person, whose evaluated claims
+/// (`approver`) must include every required claim. They must not be the
+/// requester and count only once.
+///
+/// Emits:
+/// - ApprovalGrantedEvent
+/// - ApprovalQuorumReachedEvent (when this approval completes the quorum)
+pub fn handle_grant_approval(
+    cmd: GrantApproval,
+    approval: &PendingApproval,
+    approver: &PolicyEvaluation,
+) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    require_actor(cmd.approver_id, approver, "Approver")?;
+    if approval.status != ApprovalStatus::Pending {
+        return Err(KeyManagementError::InvalidCommand(format!(
+            "Approval {} is {:?}, not pending",
+            approval.approval_id, approval.status
+        )));
+    }
+    if cmd.approver_id == approval.requested_by {
+        return Err(KeyManagementError::PolicyViolation(
+            "The requester cannot approve their own command".to_string(),
+        ));
+    }
+    if approval.approvers.contains(&cmd.approver_id) {
+        return Err(KeyManagementError::AlreadyExists(format!(
+            "{} already approved {}",
+            cmd.approver_id, approval.approval_id
+        )));
+    }
+    if !approval.qualifies(&approver.granted_claims) {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "{} lacks the claims required to approve {}",
+            cmd.approver_id, approval.kind
+        )));
+    }
+
+    let mut approvers = approval.approvers.clone();
+    approvers.push(cmd.approver_id);
+    let now = Utc::now();
+
+    let mut events = vec![DomainEvent::Approval(ApprovalEvents::ApprovalGranted(
+        ApprovalGrantedEvent {
+            approval_id: approval.approval_id,
+            approver_id: cmd.approver_id,
+            approvals: approvers.len() as u16,
+            comment: cmd.comment,
+            granted_at: now,
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        },
+    ))];
+
+    if approvers.len() >= usize::from(approval.required_approvals) {
+        events.push(DomainEvent::Approval(ApprovalEvents::ApprovalQuorumReached(
+            ApprovalQuorumReachedEvent {
+                approval_id: approval.approval_id,
+                approvers,
+                reached_at: now,
+                correlation_id: cmd.correlation_id,
+                causation_id: Some(cmd.command_id),
+            },
+        )));
+    }
+
+    Ok(events)
+}
+
+/// Handle RejectApproval command
+///
+/// A single qualified approver can veto; the requester can withdraw. The
+/// rejecting person must be the acting person (`rejecter`).
+///
+/// Emits:
+/// - ApprovalRejectedEvent
+pub fn handle_reject_approval(
+    cmd: RejectApproval,
+    approval: &PendingApproval,
+    rejecter: &PolicyEvaluation,
+) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    require_actor(cmd.rejected_by, rejecter, "Rejecter")?;
+    if approval.status != ApprovalStatus::Pending {
+        return Err(KeyManagementError::InvalidCommand(format!(
+            "Approval {} is {:?}, not pending",
+            approval.approval_id, approval.status
+        )));
+    }
+    if cmd.rejected_by != approval.requested_by && !approval.qualifies(&rejecter.granted_claims) {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "{} lacks the claims required to reject {}",
+            cmd.rejected_by, approval.kind
+        )));
+    }
+    if cmd.reason.trim().is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "A rejection reason is required".to_string(),
+        ));
+    }
+
+    Ok(vec![DomainEvent::Approval(ApprovalEvents::ApprovalRejected(
+        ApprovalRejectedEvent {
+            approval_id: approval.approval_id,
+            rejected_by: cmd.rejected_by,
+            reason: cmd.reason,
+            rejected_at: Utc::now(),
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        },
+    ))])
+}
+
+/// Check that an approved command may run, before it is executed
+///
+/// The resubmitted command must be the one that was approved, byte for
+/// byte in its canonical form.
+///
+/// Emits:
+/// - ApprovedCommandExecutedEvent
+pub fn authorize_approved_command(
+    cmd: &ExecuteApprovedCommand,
+    approval: &PendingApproval,
+) -> Result<DomainEvent, KeyManagementError> {
+    if approval.status != ApprovalStatus::Approved {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "Approval {} is {:?}, not approved",
+            approval.approval_id, approval.status
+        )));
+    }
+    let digest = command_digest(&cmd.command).map_err(KeyManagementError::InvalidCommand)?;
+    if cmd.command.command_id() != approval.command_id || digest != approval.command_digest {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "Command does not match the one approved by {}",
+            approval.approval_id
+        )));
+    }
+
+    Ok(DomainEvent::Approval(ApprovalEvents::ApprovedCommandExecuted(
+        ApprovedCommandExecutedEvent {
+            approval_id: approval.approval_id,
+            command_id: approval.command_id,
+            executed_at: Utc::now(),
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        },
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::organization::UnbindPolicy;
+    use crate::domain::{PolicyClaim, PolicyEntityType};
+
+    fn actor(person_id: Uuid, granted_claims: Vec<PolicyClaim>) -> PolicyEvaluation {
+        PolicyEvaluation {
+            entity_id: person_id,
+            entity_type: PolicyEntityType::Person,
+            active_policies: Vec::new(),
+            policy_versions: Vec::new(),
+            inactive_policies: Vec::new(),
+            granted_claims,
+            denied_claims: Vec::new(),
+            evaluated_at: Utc::now(),
+        }
+    }
+
+    fn approver() -> PolicyEvaluation {
+        actor(Uuid::now_v7(), vec![PolicyClaim::CanManagePolicies])
+    }
+
+    fn grant(approval: &PendingApproval, approver: &PolicyEvaluation) -> Result<Vec<DomainEvent>, KeyManagementError> {
+        handle_grant_approval(GrantApproval::new(approval.approval_id, approver.entity_id), approval, approver)
+    }
+
+    fn unbind() -> KeyCommand {
+        KeyCommand::UnbindPolicy(UnbindPolicy {
+            command_id: Uuid::now_v7(),
+            relationship_id: Uuid::now_v7(),
+            policy_id: Uuid::now_v7(),
+            entity_id: Uuid::now_v7(),
+            unbound_by: "admin".to_string(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+            timestamp: Utc::now(),
+        })
+    }
+
+    fn requested(command: &KeyCommand, requester: Uuid) -> PendingApproval {
+        let rules = [ApprovalRule::new(SensitiveCommandKind::PolicyChange, 2)
+            .with_approver_claim(PolicyClaim::CanManagePolicies)];
+        let events =
+            handle_request_approval(RequestApproval::new(command.clone(), requester), &rules, &actor(requester, vec![]))
+                .unwrap();
+        match &events[0] {
+            DomainEvent::Approval(ApprovalEvents::ApprovalRequested(e)) => PendingApproval::from_requested(e),
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    fn fold(approval: &mut PendingApproval, events: &[DomainEvent]) {
+        for event in events {
+            if let DomainEvent::Approval(e) = event {
+                approval.apply(e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quorum_then_execute() {
+        let command = unbind();
+        let mut approval = requested(&command, Uuid::now_v7());
+
+        let first = grant(&approval, &approver()).unwrap();
+        assert_eq!(first.len(), 1);
+        fold(&mut approval, &first);
+
+        let execute = ExecuteApprovedCommand::new(approval.approval_id, command.clone());
+        assert!(authorize_approved_command(&execute, &approval).is_err());
+
+        let second = grant(&approval, &approver()).unwrap();
+        assert_eq!(second.len(), 2);
+        fold(&mut approval, &second);
+        assert_eq!(approval.status, ApprovalStatus::Approved);
+
+        assert!(authorize_approved_command(&ExecuteApprovedCommand::new(approval.approval_id, unbind()), &approval).is_err());
+        assert!(authorize_approved_command(&execute, &approval).is_ok());
+    }
+
+    #[test]
+    fn test_requester_and_unqualified_cannot_approve() {
+        let requester = Uuid::now_v7();
+        let approval = requested(&unbind(), requester);
+
+        let own = actor(requester, vec![PolicyClaim::CanManagePolicies]);
+        assert!(matches!(grant(&approval, &own), Err(KeyManagementError::PolicyViolation(_))));
+
+        let unqualified = actor(Uuid::now_v7(), vec![PolicyClaim::CanExportKeys]);
+        assert!(matches!(grant(&approval, &unqualified), Err(KeyManagementError::PolicyViolation(_))));
+    }
+
+    #[test]
+    fn test_approver_must_be_the_acting_person() {
+        let approval = requested(&unbind(), Uuid::now_v7());
+
+        // An unqualified actor naming a fresh approver ID gets nowhere
+        let unqualified = actor(Uuid::now_v7(), vec![]);
+        let forged = GrantApproval::new(approval.approval_id, Uuid::now_v7());
+        assert!(matches!(
+            handle_grant_approval(forged, &approval, &unqualified),
+            Err(KeyManagementError::PolicyViolation(_))
+        ));
+
+        let reject = RejectApproval::new(approval.approval_id, Uuid::now_v7(), "forged");
+        assert!(handle_reject_approval(reject, &approval, &unqualified).is_err());
+
+        let request = RequestApproval::new(unbind(), Uuid::now_v7());
+        let rules = [ApprovalRule::new(SensitiveCommandKind::PolicyChange, 1)];
+        assert!(handle_request_approval(request, &rules, &unqualified).is_err());
+    }
+
+    #[test]
+    fn test_rejected_approval_accepts_no_more_grants() {
+        let mut approval = requested(&unbind(), Uuid::now_v7());
+        let rejecter = approver();
+
+        let rejected = handle_reject_approval(
+            RejectApproval::new(approval.approval_id, rejecter.entity_id, "not this quarter"),
+            &approval,
+            &rejecter,
+        )
+        .unwrap();
+        fold(&mut approval, &rejected);
+
+        assert!(grant(&approval, &approver()).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Command Authorization
+//!
+//! Every `KeyCommand` is checked against the claims its actor's policies
+//! grant before it runs (see `KeyManagementAggregate::handle_command`).
+//! [`CommandAuthorizer`] holds the claims each command type requires and
+//! refuses a command with a `CommandDenied` event naming the missing ones.
+//!
+//! An organization with no policies in force is still being bootstrapped:
+//! nothing can grant claims yet, so its operator may issue any command
+//! until the first policy takes effect.
+
+use chrono::Utc;
+use std::collections::HashMap;
+
+use crate::commands::KeyCommand;
+use crate::domain::{effective_policies, Policy, PolicyClaim, PolicyEvaluation};
+use crate::events::authorization::CommandDeniedEvent;
+
+/// Claims each command type requires unless overridden
+fn default_command_claims() -> HashMap<&'static str, Vec<PolicyClaim>> {
+    use PolicyClaim::*;
+
+    HashMap::from([
+        ("GenerateRootCA", vec![CanGenerateKeys, CanSignCertificates]),
+        ("GenerateCertificate", vec![CanSignCertificates]),
+        ("GenerateSshKey", vec![CanGenerateKeys]),
+        ("ProvisionYubiKey", vec![CanGenerateKeys]),
+        ("ExportKeys", vec![CanExportKeys]),
+        ("RevokeKey", vec![CanRevokeKeys]),
+        ("CreateOrganization", vec![CanManageOrganization]),
+        ("CreatePerson", vec![CanCreateAccounts]),
+        ("CreateLocation", vec![CanManageOrganization]),
+        ("CreateOrganizationalUnit", vec![CanManageOrganization]),
+        ("CreateServiceAccount", vec![CanCreateAccounts]),
+        ("SuspendPerson", vec![CanDisableAccounts]),
+        ("ArchivePerson", vec![CanDeleteAccounts]),
+        ("DeactivatePerson", vec![CanDisableAccounts]),
+        ("TerminateRelationship", vec![CanManageOrganization]),
+        ("UnbindPolicy", vec![CanManagePolicies]),
+        ("SupersedePolicy", vec![CanManagePolicies]),
+        ("BindPolicy", vec![CanManagePolicies]),
+        ("AssignRole", vec![CanAssignRoles]),
+        ("CreateDelegation", vec![CanDelegateKeys]),
+        ("RevokeDelegation", vec![CanDelegateKeys]),
+        ("ExportGpgSecretKey", vec![CanExportKeys]),
+        ("RecordKeyExpirationExtension", vec![CanRotateKeys]),
+        ("MoveGpgKeyToCard", vec![CanGenerateKeys]),
+        ("CertifyGpgKey", vec![CanSignCertificates]),
+        ("ExportSshPrivateKey", vec![CanExportKeys]),
+        ("GenerateSshSecurityKey", vec![CanGenerateKeys]),
+        ("ImportSshPublicKey", vec![CanImportData]),
+    ])
+}
+
+/// Claims required per command type, and the check against an actor
+#[derive(Debug, Clone)]
+pub struct CommandAuthorizer {
+    command_claims: HashMap<&'static str, Vec<PolicyClaim>>,
+}
+
+impl Default for CommandAuthorizer {
+    fn default() -> Self {
+        Self { command_claims: default_command_claims() }
+    }
+}
+
+impl CommandAuthorizer {
+    /// Require `claims` for a command type instead of the default
+    pub fn require_claims(&mut self, command_type: &'static str, claims: Vec<PolicyClaim>) {
+        self.command_claims.insert(command_type, claims);
+    }
+
+    /// Claims an actor needs to issue a command
+    ///
+    /// Requesting approval needs the claims of the parked command. Approval
+    /// decisions need none here: which claims qualify depends on the
+    /// approval rule, so the approval handlers check them against this same
+    /// `actor` evaluation (never against claims carried by the command). An
+    /// approved command was authorized when it was requested.
+    pub fn required_claims(&self, command: &KeyCommand) -> Vec<PolicyClaim> {
+        match command {
+            KeyCommand::RequestApproval(cmd) => self.required_claims(&cmd.command),
+            _ => self
+                .command_claims
+                .get(command.command_type())
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Check a command against the claims granted to its actor
+    ///
+    /// On refusal, the returned event lists the missing claims and the unmet
+    /// conditions of those `policies` that would have granted them.
+    pub fn authorize(
+        &self,
+        command: &KeyCommand,
+        actor: &PolicyEvaluation,
+        policies: &[Policy],
+    ) -> Result<(), Box<CommandDeniedEvent>> {
+        let in_force = effective_policies(policies, actor.evaluated_at);
+        if in_force.is_empty() {
+            return Ok(());
+        }
+
+        let required_claims = self.required_claims(command);
+        let missing_claims: Vec<PolicyClaim> = required_claims
+            .iter()
+            .filter(|claim| !actor.granted_claims.contains(claim))
+            .cloned()
+            .collect();
+        if missing_claims.is_empty() {
+            return Ok(());
+        }
+
+        let failing_conditions = actor
+            .inactive_policies
+            .iter()
+            .filter_map(|(policy_id, reasons)| {
+                in_force
+                    .iter()
+                    .find(|p| p.id.as_uuid() == *policy_id)
+                    .filter(|p| p.claims.iter().any(|c| missing_claims.contains(c)))
+                    .map(|p| (p, reasons))
+            })
+            .flat_map(|(policy, reasons)| reasons.iter().map(move |r| format!("{}: {}", policy.name, r)))
+            .collect();
+
+        let command_id = command.command_id();
+        Err(Box::new(CommandDeniedEvent {
+            command_id,
+            command_type: command.command_type().to_string(),
+            actor_id: actor.entity_id,
+            required_claims,
+            missing_claims,
+            failing_conditions,
+            denied_at: Utc::now(),
+            correlation_id: command_id,
+            causation_id: Some(command_id),
+        }))
+    }
+}
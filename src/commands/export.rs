@@ -8,6 +8,7 @@ use chrono::Utc;
 use std::path::PathBuf;
 use uuid::Uuid;
 
+use crate::crypto::age_encryption::{encrypt_armored, AgeProtection, PersonRecipient};
 use crate::domain::{KeyContext, Organization};
 use crate::events::DomainEvent;
 use crate::value_objects::{ActorId, Certificate, ExportFormat, NKeyPair, NatsJwt, PublicKey};
@@ -19,12 +20,17 @@ use crate::value_objects::{ActorId, Certificate, ExportFormat, NKeyPair, NatsJwt
 /// Command to export keys and certificates to encrypted storage
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ExportToEncryptedStorage {
+    pub command_id: Uuid,
     pub output_directory: PathBuf,
     pub organization: Organization,
     pub keys: Vec<KeyExportItem>,
     pub certificates: Vec<CertificateExportItem>,
     pub nats_identities: Vec<NatsIdentityExportItem>,
     pub include_manifest: bool,
+    /// People NATS credentials are age-encrypted to; empty writes them in
+    /// plaintext and relies on the output directory being encrypted
+    #[serde(default)]
+    pub encrypt_to: Vec<PersonRecipient>,
     pub correlation_id: Uuid,
     pub causation_id: Option<Uuid>,
 }
@@ -91,7 +97,8 @@ pub fn handle_export_to_encrypted_storage(
     let mut total_bytes = 0u64;
 
     // Step 1: Validate output directory
-    validate_export_directory(&cmd.output_directory)?;
+    validate_export_directory(&cmd.output_directory, !cmd.encrypt_to.is_empty())?;
+    let protection = (!cmd.encrypt_to.is_empty()).then(|| AgeProtection::to_people(&cmd.encrypt_to));
 
     // Step 2: Export keys
     for key_item in &cmd.keys {
@@ -149,7 +156,7 @@ pub fn handle_export_to_encrypted_storage(
     // Step 4: Export NATS configurations
     for nats_item in &cmd.nats_identities {
         // Export NATS credentials to file in standard .creds format
-        let bytes_written = export_nats_credentials_to_file(nats_item)?;
+        let bytes_written = export_nats_credentials_to_file(nats_item, protection.as_ref())?;
         total_bytes += bytes_written;
 
         // Emit event with proper correlation tracking
@@ -212,7 +219,9 @@ pub fn handle_export_to_encrypted_storage(
 // ============================================================================
 
 /// Validate export directory exists and is writable
-fn validate_export_directory(path: &PathBuf) -> Result<(), String> {
+///
+/// `encrypted` says whether secrets are age-encrypted before being written.
+fn validate_export_directory(path: &PathBuf, encrypted: bool) -> Result<(), String> {
     use std::fs;
 
     // Check if directory exists, create it if not
@@ -244,13 +253,13 @@ fn validate_export_directory(path: &PathBuf) -> Result<(), String> {
         }
     }
 
-    // Warn if not on encrypted partition (security best practice)
-    if !path.to_string_lossy().starts_with("/mnt/encrypted") {
+    // Plaintext secrets should only go to an encrypted partition
+    if !encrypted && !path.to_string_lossy().starts_with("/mnt/encrypted") {
         eprintln!(
             "WARNING: Export directory {} is not on encrypted partition",
             path.display()
         );
-        eprintln!("         Consider using an encrypted partition for key material");
+        eprintln!("         Consider using an encrypted partition or age recipients for key material");
     }
 
     Ok(())
@@ -371,7 +380,13 @@ fn export_certificate_to_file(cert_item: &CertificateExportItem) -> Result<u64,
 }
 
 /// Export NATS credentials to file in standard .creds format
-fn export_nats_credentials_to_file(nats_item: &NatsIdentityExportItem) -> Result<u64, String> {
+///
+/// With `protection` the file is the armored age encryption of the .creds
+/// content.
+fn export_nats_credentials_to_file(
+    nats_item: &NatsIdentityExportItem,
+    protection: Option<&AgeProtection>,
+) -> Result<u64, String> {
     use std::fs;
 
     // Ensure parent directory exists
@@ -400,6 +415,11 @@ fn export_nats_credentials_to_file(nats_item: &NatsIdentityExportItem) -> Result
         identity_type_upper
     );
 
+    let credentials_content = match protection {
+        Some(protection) => encrypt_armored(credentials_content.as_bytes(), protection)?,
+        None => credentials_content,
+    };
+
     // Step 2: Write to destination_path with restricted permissions
     fs::write(&nats_item.destination_path, &credentials_content)
         .map_err(|e| format!("Failed to write NATS credentials to {}: {}", nats_item.destination_path.display(), e))?;
@@ -525,12 +545,14 @@ mod tests {
         // A4: Generate test command_id for causation tracking
         let test_command_id = Uuid::now_v7();
         let cmd = ExportToEncryptedStorage {
+            command_id: Uuid::now_v7(),
             output_directory: test_dir.clone(),
             organization: org,
             keys: vec![],
             certificates: vec![],
             nats_identities: vec![],
             include_manifest: true,
+            encrypt_to: vec![],
             correlation_id: Uuid::now_v7(),
             causation_id: Some(test_command_id), // A4: Self-reference for root command
         };
@@ -564,12 +586,14 @@ mod tests {
         // A4: Generate test command_id for causation tracking
         let test_command_id = Uuid::now_v7();
         let cmd = ExportToEncryptedStorage {
+            command_id: Uuid::now_v7(),
             output_directory: PathBuf::from("/tmp/test"),
             organization: org,
             keys: vec![],
             certificates: vec![],
             nats_identities: vec![],
             include_manifest: false,
+            encrypt_to: vec![],
             correlation_id: Uuid::now_v7(),
             causation_id: Some(test_command_id), // A4: Self-reference for root command
         };
@@ -0,0 +1,731 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! GPG Key Commands
+//!
+//! Commands for OpenPGP key material owned by the Key aggregate.
+//! The actual OpenPGP operations happen through `GpgPort`; these handlers
+//! validate the request against the security configuration and emit the
+//! events that record what happened.
+
+use chrono::{DateTime, Utc};
+use cim_domain::{Command, EntityId};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::aggregate::KeyManagementError;
+use crate::config::SecurityConfig;
+use crate::events::{DomainEvent, KeyEvents};
+use crate::domain::KeyOwnerRole;
+use crate::events::key::{
+    GpgKeyCertifiedEvent, KeyExpirationExtendedEvent, KeyExportedEvent, KeyMovedToCardEvent,
+};
+use crate::types::{ExportDestination, KeyFormat, KeyMetadata, OpenPgpCardSlot, TrustLevel};
+use crate::value_objects::ActorId;
+
+/// Marker type for GPG key commands (Key aggregate)
+#[derive(Debug, Clone, Copy)]
+pub struct GpgKeyAggregate;
+
+/// Command to export a GPG transferable secret key (TSK)
+///
+/// The passphrase itself never travels in the command; only whether the
+/// export will be passphrase protected. The aggregate refuses unprotected
+/// exports unless `SecurityConfig::allow_unencrypted_secret_export` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportGpgSecretKey {
+    pub command_id: Uuid,
+    pub key_id: Uuid,
+    pub gpg_key_id: String,
+    pub passphrase_protected: bool,
+    pub destination: ExportDestination,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Command for ExportGpgSecretKey {
+    type Aggregate = GpgKeyAggregate;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.key_id))
+    }
+}
+
+impl ExportGpgSecretKey {
+    /// Create a passphrase-protected export command
+    pub fn new(key_id: Uuid, gpg_key_id: impl Into<String>, destination: ExportDestination) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            key_id,
+            gpg_key_id: gpg_key_id.into(),
+            passphrase_protected: true,
+            destination,
+            requested_by: ActorId::system("gpg-export"),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Request an export without passphrase protection
+    pub fn unprotected(mut self) -> Self {
+        self.passphrase_protected = false;
+        self
+    }
+
+    /// Set the actor requesting the export
+    pub fn with_requested_by(mut self, actor: ActorId) -> Self {
+        self.requested_by = actor;
+        self
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Command recording that the expiration of a GPG key was extended (or
+/// removed)
+///
+/// Keys generated with `PgpConfig.expiration` expire on schedule. The
+/// self-signature and subkey binding signatures are re-issued with the
+/// later expiration through `GpgPort::set_expiration`; this command only
+/// records it, like `MoveGpgKeyToCard` records a keytocard transfer. The
+/// current expiration is taken from the projection, not from the caller.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordKeyExpirationExtension {
+    pub command_id: Uuid,
+    pub key_id: Uuid,
+    pub fingerprint: String,
+    /// New expiration; `None` means the key no longer expires
+    pub new_expires_at: Option<DateTime<Utc>>,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Command for RecordKeyExpirationExtension {
+    type Aggregate = GpgKeyAggregate;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.key_id))
+    }
+}
+
+impl RecordKeyExpirationExtension {
+    /// Create a command recording the key's extension to `new_expires_at`
+    pub fn new(key_id: Uuid, fingerprint: impl Into<String>, new_expires_at: Option<DateTime<Utc>>) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            key_id,
+            fingerprint: fingerprint.into(),
+            new_expires_at,
+            requested_by: ActorId::system("gpg-expiration"),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Set the actor requesting the extension
+    pub fn with_requested_by(mut self, actor: ActorId) -> Self {
+        self.requested_by = actor;
+        self
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Command recording that GPG subkeys were moved to a YubiKey (keytocard)
+///
+/// The transfer itself happens through `GpgPort::move_to_card`, one call per
+/// slot; this command binds the key to the token in the domain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveGpgKeyToCard {
+    pub command_id: Uuid,
+    pub key_id: Uuid,
+    pub fingerprint: String,
+    pub yubikey_serial: String,
+    pub slots: Vec<OpenPgpCardSlot>,
+    /// Current key metadata; `hardware_serial` is set by the handler
+    pub metadata: KeyMetadata,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Command for MoveGpgKeyToCard {
+    type Aggregate = GpgKeyAggregate;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.key_id))
+    }
+}
+
+impl MoveGpgKeyToCard {
+    /// Create a command moving the signing, encryption and authentication
+    /// subkeys to the given YubiKey
+    pub fn new(
+        key_id: Uuid,
+        fingerprint: impl Into<String>,
+        yubikey_serial: impl Into<String>,
+        metadata: KeyMetadata,
+    ) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            key_id,
+            fingerprint: fingerprint.into(),
+            yubikey_serial: yubikey_serial.into(),
+            slots: vec![
+                OpenPgpCardSlot::Signature,
+                OpenPgpCardSlot::Encryption,
+                OpenPgpCardSlot::Authentication,
+            ],
+            metadata,
+            requested_by: ActorId::system("gpg-keytocard"),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Restrict the transfer to the given slots
+    pub fn with_slots(mut self, slots: Vec<OpenPgpCardSlot>) -> Self {
+        self.slots = slots;
+        self
+    }
+
+    /// Set the actor requesting the transfer
+    pub fn with_requested_by(mut self, actor: ActorId) -> Self {
+        self.requested_by = actor;
+        self
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Command for one Person's key to certify another's (web of trust)
+///
+/// The web of trust is rooted at the Root Authority: its certification key
+/// may issue trust signatures of any depth. Anyone else may only certify
+/// when they were made an introducer (`certifier_trust_depth >= 1`) and
+/// may only delegate strictly less depth than they hold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertifyGpgKey {
+    pub command_id: Uuid,
+    pub certifier_key_id: Uuid,
+    pub certifier_person_id: Uuid,
+    pub certifier_role: KeyOwnerRole,
+    /// Trust depth the certifier was granted (None = not an introducer)
+    pub certifier_trust_depth: Option<u8>,
+    pub subject_key_id: Uuid,
+    pub subject_person_id: Uuid,
+    pub subject_user_id: String,
+    /// Unknown = plain certification; Marginal/Full = trust signature
+    pub trust_level: TrustLevel,
+    pub trust_depth: u8,
+    pub scope: Option<String>,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Command for CertifyGpgKey {
+    type Aggregate = GpgKeyAggregate;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.subject_key_id))
+    }
+}
+
+impl CertifyGpgKey {
+    /// Create a plain certification of `subject_user_id`
+    pub fn new(
+        certifier_key_id: Uuid,
+        certifier_person_id: Uuid,
+        certifier_role: KeyOwnerRole,
+        subject_key_id: Uuid,
+        subject_person_id: Uuid,
+        subject_user_id: impl Into<String>,
+    ) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            certifier_key_id,
+            certifier_person_id,
+            certifier_role,
+            certifier_trust_depth: None,
+            subject_key_id,
+            subject_person_id,
+            subject_user_id: subject_user_id.into(),
+            trust_level: TrustLevel::Unknown,
+            trust_depth: 0,
+            scope: None,
+            requested_by: ActorId::person(certifier_person_id),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Make this a trust signature delegating `depth` levels of introducer trust
+    pub fn with_trust(mut self, trust_level: TrustLevel, depth: u8) -> Self {
+        self.trust_level = trust_level;
+        self.trust_depth = depth;
+        self
+    }
+
+    /// Limit the delegated trust to user IDs matching `scope` (e.g. the org domain)
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    /// Set the trust depth the certifier itself was granted
+    pub fn with_certifier_trust_depth(mut self, depth: u8) -> Self {
+        self.certifier_trust_depth = Some(depth);
+        self
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+// ============================================================================
+// Command Handlers
+// ============================================================================
+
+/// Handle ExportGpgSecretKey command
+///
+/// Emits:
+/// - KeyExportedEvent (include_private = true, GPG ASCII armor)
+pub fn handle_export_gpg_secret_key(
+    cmd: ExportGpgSecretKey,
+    security: &SecurityConfig,
+) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    if cmd.gpg_key_id.trim().is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "GPG key ID is required".to_string(),
+        ));
+    }
+
+    if !cmd.passphrase_protected && !security.allow_unencrypted_secret_export {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "Unencrypted secret key export of {} is forbidden by security configuration",
+            cmd.gpg_key_id
+        )));
+    }
+
+    let event = DomainEvent::Key(KeyEvents::KeyExported(KeyExportedEvent {
+        key_id: cmd.key_id,
+        format: KeyFormat::GpgAsciiArmor,
+        include_private: true,
+        exported_at: cmd.timestamp,
+        exported_by: cmd.requested_by,
+        destination: cmd.destination,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(vec![event])
+}
+
+/// Handle RecordKeyExpirationExtension command
+///
+/// `current_expires_at` is the key's expiration as projected. The new
+/// expiration must lie in the future and after the current one;
+/// shortening a key's lifetime is a revocation concern, not an extension.
+///
+/// Emits:
+/// - KeyExpirationExtendedEvent
+pub fn handle_record_key_expiration_extension(
+    cmd: RecordKeyExpirationExtension,
+    current_expires_at: Option<DateTime<Utc>>,
+) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    if cmd.fingerprint.trim().is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "Key fingerprint is required".to_string(),
+        ));
+    }
+
+    if let Some(new_expires_at) = cmd.new_expires_at {
+        if new_expires_at <= cmd.timestamp {
+            return Err(KeyManagementError::InvalidCommand(format!(
+                "New expiration {} is not in the future",
+                new_expires_at
+            )));
+        }
+        if current_expires_at.is_some_and(|current| new_expires_at <= current) {
+            return Err(KeyManagementError::InvalidCommand(format!(
+                "New expiration {} does not extend the current expiration",
+                new_expires_at
+            )));
+        }
+    }
+
+    let event = DomainEvent::Key(KeyEvents::KeyExpirationExtended(KeyExpirationExtendedEvent {
+        key_id: cmd.key_id,
+        fingerprint: cmd.fingerprint,
+        previous_expires_at: current_expires_at,
+        new_expires_at: cmd.new_expires_at,
+        extended_at: cmd.timestamp,
+        extended_by: cmd.requested_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(vec![event])
+}
+
+/// Handle MoveGpgKeyToCard command
+///
+/// A key bound to one token cannot be re-bound to another; the secret
+/// material no longer exists outside the first token.
+///
+/// Emits:
+/// - KeyMovedToCardEvent (metadata.hardware_serial = YubiKey serial)
+pub fn handle_move_gpg_key_to_card(
+    cmd: MoveGpgKeyToCard,
+) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    if cmd.yubikey_serial.trim().is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "YubiKey serial is required".to_string(),
+        ));
+    }
+
+    if cmd.slots.is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "At least one OpenPGP card slot is required".to_string(),
+        ));
+    }
+
+    let mut slots = cmd.slots.clone();
+    slots.sort_by_key(|slot| *slot as u8);
+    slots.dedup();
+    if slots.len() != cmd.slots.len() {
+        return Err(KeyManagementError::InvalidCommand(
+            "Each OpenPGP card slot can hold only one key".to_string(),
+        ));
+    }
+
+    if let Some(existing) = &cmd.metadata.hardware_serial {
+        if existing != &cmd.yubikey_serial {
+            return Err(KeyManagementError::AlreadyExists(format!(
+                "Key {} is already bound to YubiKey {}",
+                cmd.key_id, existing
+            )));
+        }
+    }
+
+    let mut metadata = cmd.metadata;
+    metadata.hardware_serial = Some(cmd.yubikey_serial.clone());
+
+    let event = DomainEvent::Key(KeyEvents::KeyMovedToCard(KeyMovedToCardEvent {
+        key_id: cmd.key_id,
+        fingerprint: cmd.fingerprint,
+        yubikey_serial: cmd.yubikey_serial,
+        slots: cmd.slots,
+        metadata,
+        moved_at: cmd.timestamp,
+        moved_by: cmd.requested_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(vec![event])
+}
+
+/// Handle CertifyGpgKey command
+///
+/// Emits:
+/// - GpgKeyCertifiedEvent
+pub fn handle_certify_gpg_key(cmd: CertifyGpgKey) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    if cmd.certifier_key_id == cmd.subject_key_id {
+        return Err(KeyManagementError::InvalidCommand(
+            "A key cannot certify itself".to_string(),
+        ));
+    }
+
+    if cmd.subject_user_id.trim().is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "Subject user ID is required".to_string(),
+        ));
+    }
+
+    let trust_amount = match cmd.trust_level {
+        TrustLevel::Unknown if cmd.trust_depth == 0 => None,
+        TrustLevel::Unknown => {
+            return Err(KeyManagementError::InvalidCommand(
+                "Trust depth requires a Marginal or Full trust level".to_string(),
+            ))
+        }
+        TrustLevel::Marginal => Some(60),
+        TrustLevel::Full => Some(120),
+        TrustLevel::Never | TrustLevel::Ultimate => {
+            return Err(KeyManagementError::InvalidCommand(format!(
+                "{:?} trust cannot be delegated by certification",
+                cmd.trust_level
+            )))
+        }
+    };
+
+    // Root Authority holds unlimited depth; everyone else what they were granted
+    let certifier_depth = match cmd.certifier_role {
+        KeyOwnerRole::RootAuthority => u8::MAX,
+        _ => cmd.certifier_trust_depth.unwrap_or(0),
+    };
+    if certifier_depth == 0 {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "Person {} is not a trusted introducer",
+            cmd.certifier_person_id
+        )));
+    }
+    if cmd.trust_depth >= certifier_depth {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "Trust depth {} exceeds what certifier {} may delegate",
+            cmd.trust_depth, cmd.certifier_person_id
+        )));
+    }
+
+    let event = DomainEvent::Key(KeyEvents::GpgKeyCertified(GpgKeyCertifiedEvent {
+        certification_id: Uuid::now_v7(),
+        certifier_key_id: cmd.certifier_key_id,
+        certifier_person_id: cmd.certifier_person_id,
+        subject_key_id: cmd.subject_key_id,
+        subject_person_id: cmd.subject_person_id,
+        subject_user_id: cmd.subject_user_id,
+        trust_level: cmd.trust_level,
+        trust_depth: cmd.trust_depth,
+        trust_amount,
+        scope: cmd.scope,
+        certified_at: cmd.timestamp,
+        certified_by: cmd.requested_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(vec![event])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn destination() -> ExportDestination {
+        ExportDestination::File {
+            path: "/mnt/encrypted/gpg/alice.tsk.asc".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_protected_export_emits_key_exported() {
+        let key_id = Uuid::now_v7();
+        let cmd = ExportGpgSecretKey::new(key_id, "0000000000000001", destination());
+
+        let events = handle_export_gpg_secret_key(cmd, &SecurityConfig::default()).unwrap();
+        assert_eq!(events.len(), 1);
+
+        match &events[0] {
+            DomainEvent::Key(KeyEvents::KeyExported(evt)) => {
+                assert_eq!(evt.key_id, key_id);
+                assert!(evt.include_private);
+                assert!(matches!(evt.format, KeyFormat::GpgAsciiArmor));
+            }
+            _ => panic!("Expected KeyExported event"),
+        }
+    }
+
+    #[test]
+    fn test_unprotected_export_forbidden_by_default() {
+        let cmd = ExportGpgSecretKey::new(Uuid::now_v7(), "0000000000000001", destination())
+            .unprotected();
+
+        let result = handle_export_gpg_secret_key(cmd, &SecurityConfig::default());
+        assert!(matches!(result, Err(KeyManagementError::PolicyViolation(_))));
+    }
+
+    #[test]
+    fn test_unprotected_export_allowed_when_configured() {
+        let cmd = ExportGpgSecretKey::new(Uuid::now_v7(), "0000000000000001", destination())
+            .unprotected();
+        let security = SecurityConfig {
+            allow_unencrypted_secret_export: true,
+            ..SecurityConfig::default()
+        };
+
+        assert!(handle_export_gpg_secret_key(cmd, &security).is_ok());
+    }
+
+    #[test]
+    fn test_record_key_expiration_extension_emits_event() {
+        let key_id = Uuid::now_v7();
+        let current = Utc::now() + chrono::Duration::days(10);
+        let new = Utc::now() + chrono::Duration::days(365);
+        let cmd = RecordKeyExpirationExtension::new(key_id, "A".repeat(40), Some(new));
+
+        let events = handle_record_key_expiration_extension(cmd, Some(current)).unwrap();
+        match &events[0] {
+            DomainEvent::Key(KeyEvents::KeyExpirationExtended(evt)) => {
+                assert_eq!(evt.key_id, key_id);
+                assert_eq!(evt.previous_expires_at, Some(current));
+                assert_eq!(evt.new_expires_at, Some(new));
+            }
+            _ => panic!("Expected KeyExpirationExtended event"),
+        }
+    }
+
+    #[test]
+    fn test_record_key_expiration_extension_rejects_shortening() {
+        let current = Utc::now() + chrono::Duration::days(365);
+        let new = Utc::now() + chrono::Duration::days(30);
+        let cmd = RecordKeyExpirationExtension::new(Uuid::now_v7(), "A".repeat(40), Some(new));
+
+        let result = handle_record_key_expiration_extension(cmd, Some(current));
+        assert!(matches!(result, Err(KeyManagementError::InvalidCommand(_))));
+    }
+
+    fn gpg_metadata() -> KeyMetadata {
+        KeyMetadata {
+            label: "alice@example.com".to_string(),
+            description: None,
+            tags: vec!["gpg".to_string()],
+            attributes: Default::default(),
+            jwt_kid: None,
+            jwt_alg: None,
+            jwt_use: None,
+            hardware_serial: None,
+            tpm: None,
+        }
+    }
+
+    #[test]
+    fn test_move_to_card_records_hardware_serial() {
+        let key_id = Uuid::now_v7();
+        let cmd = MoveGpgKeyToCard::new(key_id, "A".repeat(40), "12345678", gpg_metadata());
+
+        let events = handle_move_gpg_key_to_card(cmd).unwrap();
+        match &events[0] {
+            DomainEvent::Key(KeyEvents::KeyMovedToCard(evt)) => {
+                assert_eq!(evt.key_id, key_id);
+                assert_eq!(evt.metadata.hardware_serial.as_deref(), Some("12345678"));
+                assert_eq!(evt.slots.len(), 3);
+            }
+            _ => panic!("Expected KeyMovedToCard event"),
+        }
+    }
+
+    #[test]
+    fn test_move_to_card_rejects_rebinding() {
+        let mut metadata = gpg_metadata();
+        metadata.hardware_serial = Some("87654321".to_string());
+        let cmd = MoveGpgKeyToCard::new(Uuid::now_v7(), "A".repeat(40), "12345678", metadata);
+
+        let result = handle_move_gpg_key_to_card(cmd);
+        assert!(matches!(result, Err(KeyManagementError::AlreadyExists(_))));
+    }
+
+    #[test]
+    fn test_root_authority_issues_trust_signature() {
+        let cmd = CertifyGpgKey::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            KeyOwnerRole::RootAuthority,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Alice <alice@example.com>",
+        )
+        .with_trust(TrustLevel::Full, 1)
+        .with_scope("<[^>]+[@.]example\\.com>$");
+
+        let events = handle_certify_gpg_key(cmd).unwrap();
+        match &events[0] {
+            DomainEvent::Key(KeyEvents::GpgKeyCertified(evt)) => {
+                assert_eq!(evt.trust_depth, 1);
+                assert_eq!(evt.trust_amount, Some(120));
+            }
+            _ => panic!("Expected GpgKeyCertified event"),
+        }
+    }
+
+    #[test]
+    fn test_introducer_cannot_delegate_own_depth() {
+        let introducer = CertifyGpgKey::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            KeyOwnerRole::SecurityAdmin,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Bob <bob@example.com>",
+        )
+        .with_certifier_trust_depth(1);
+
+        // Depth-1 introducer may certify, but not create another introducer
+        assert!(handle_certify_gpg_key(introducer.clone()).is_ok());
+        let delegated = introducer.with_trust(TrustLevel::Full, 1);
+        assert!(matches!(
+            handle_certify_gpg_key(delegated),
+            Err(KeyManagementError::PolicyViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_non_introducer_cannot_certify() {
+        let cmd = CertifyGpgKey::new(
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            KeyOwnerRole::Developer,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            "Carol <carol@example.com>",
+        );
+
+        assert!(matches!(
+            handle_certify_gpg_key(cmd),
+            Err(KeyManagementError::PolicyViolation(_))
+        ));
+    }
+}
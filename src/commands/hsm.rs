@@ -0,0 +1,219 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! HSM Key Commands
+//!
+//! Commands for CA keys held in a PKCS#11 hardware security module instead
+//! of a YubiKey. Keys are generated through `HsmPort::generate_key_pair`;
+//! these handlers validate the result and emit the same events as the
+//! YubiKey path: a hardware-backed `KeyGeneratedEvent` whose metadata
+//! names the token serial, slot and label.
+
+use chrono::{DateTime, Utc};
+use cim_domain::{Command, EntityId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::aggregate::KeyManagementError;
+use crate::domain::KeyOwnership;
+use crate::events::key::KeyGeneratedEvent;
+use crate::events::{DomainEvent, KeyEvents};
+use crate::ports::hsm::{HsmKeyAlgorithm, HsmKeyPair, HsmKeyRef};
+use crate::types::{KeyMetadata, KeyPurpose};
+use crate::value_objects::ActorId;
+
+/// Metadata attribute holding the PKCS#11 slot ID
+pub const HSM_SLOT_ATTRIBUTE: &str = "hsm_slot";
+/// Metadata attribute holding the key label on the token
+pub const HSM_LABEL_ATTRIBUTE: &str = "hsm_label";
+/// Metadata attribute holding the CKA_ID of the key objects
+pub const HSM_OBJECT_ID_ATTRIBUTE: &str = "hsm_object_id";
+
+/// Marker type for HSM key commands (Key aggregate)
+#[derive(Debug, Clone, Copy)]
+pub struct HsmKeyAggregate;
+
+/// Command to record a CA key generated on an HSM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateHsmKey {
+    pub command_id: Uuid,
+    pub key_id: Uuid,
+    pub key_ref: HsmKeyRef,
+    pub algorithm: HsmKeyAlgorithm,
+    /// Public key as returned by the HSM, hex
+    pub public_key: String,
+    pub purpose: KeyPurpose,
+    pub ownership: Option<KeyOwnership>,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Command for GenerateHsmKey {
+    type Aggregate = HsmKeyAggregate;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.key_id))
+    }
+}
+
+impl GenerateHsmKey {
+    /// Create a command from a CA key pair generated on the HSM
+    pub fn new(key_pair: &HsmKeyPair) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            key_id: Uuid::now_v7(),
+            key_ref: key_pair.key_ref.clone(),
+            algorithm: key_pair.algorithm,
+            public_key: hex::encode(&key_pair.public_key),
+            purpose: KeyPurpose::CertificateAuthority,
+            ownership: None,
+            requested_by: ActorId::system("hsm-key"),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Set the person and organization owning the key
+    pub fn with_ownership(mut self, ownership: KeyOwnership) -> Self {
+        self.ownership = Some(ownership);
+        self
+    }
+
+    /// Set the actor requesting the key
+    pub fn with_requested_by(mut self, actor: ActorId) -> Self {
+        self.requested_by = actor;
+        self
+    }
+
+    /// Set correlation ID for tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Handle GenerateHsmKey command
+///
+/// Emits:
+/// - KeyGeneratedEvent (hardware backed, metadata.hardware_serial = token
+///   serial, slot and label in metadata.attributes)
+pub fn handle_generate_hsm_key(cmd: GenerateHsmKey) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    if cmd.key_ref.token_serial.trim().is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "HSM token serial is required".to_string(),
+        ));
+    }
+
+    if cmd.key_ref.label.trim().is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "HSM key label is required".to_string(),
+        ));
+    }
+
+    if cmd.public_key.is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "HSM public key is required".to_string(),
+        ));
+    }
+
+    if cmd.purpose != KeyPurpose::CertificateAuthority {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "HSM keys are reserved for certificate authorities, not {:?}",
+            cmd.purpose
+        )));
+    }
+
+    let mut attributes = HashMap::new();
+    attributes.insert(HSM_SLOT_ATTRIBUTE.to_string(), cmd.key_ref.slot_id.to_string());
+    attributes.insert(HSM_LABEL_ATTRIBUTE.to_string(), cmd.key_ref.label.clone());
+    attributes.insert(HSM_OBJECT_ID_ATTRIBUTE.to_string(), cmd.key_ref.object_id.clone());
+    attributes.insert("public_key".to_string(), cmd.public_key);
+
+    let event = DomainEvent::Key(KeyEvents::KeyGenerated(KeyGeneratedEvent {
+        key_id: cmd.key_id,
+        algorithm: cmd.algorithm.key_algorithm(),
+        purpose: cmd.purpose,
+        generated_at: cmd.timestamp,
+        generated_by: cmd.requested_by,
+        hardware_backed: true,
+        metadata: KeyMetadata {
+            label: cmd.key_ref.label.clone(),
+            description: Some(format!(
+                "CA key on HSM token {} (slot {})",
+                cmd.key_ref.token_serial, cmd.key_ref.slot_id
+            )),
+            tags: vec!["hsm".to_string(), "pkcs11".to_string()],
+            attributes,
+            jwt_kid: None,
+            jwt_alg: None,
+            jwt_use: None,
+            hardware_serial: Some(cmd.key_ref.token_serial),
+            tpm: None,
+        },
+        ownership: cmd.ownership,
+        expires_at: None,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(vec![event])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::hsm_mock::{MockHsmAdapter, MOCK_HSM_PIN};
+    use crate::ports::hsm::HsmPort;
+    use crate::ports::yubikey::SecureString;
+
+    #[tokio::test]
+    async fn test_hsm_key_recorded_as_hardware_backed() {
+        let hsm = MockHsmAdapter::default();
+        let key_pair = hsm
+            .generate_key_pair(0, "root-ca", HsmKeyAlgorithm::Ed25519, &SecureString::new(MOCK_HSM_PIN))
+            .await
+            .unwrap();
+
+        let events = handle_generate_hsm_key(GenerateHsmKey::new(&key_pair)).unwrap();
+        match &events[..] {
+            [DomainEvent::Key(KeyEvents::KeyGenerated(evt))] => {
+                assert!(evt.hardware_backed);
+                assert_eq!(evt.metadata.hardware_serial.as_deref(), Some("0123456789abcdef"));
+                assert_eq!(evt.metadata.attributes[HSM_SLOT_ATTRIBUTE], "0");
+                assert_eq!(evt.metadata.attributes[HSM_LABEL_ATTRIBUTE], "root-ca");
+                assert_eq!(evt.purpose, KeyPurpose::CertificateAuthority);
+            }
+            _ => panic!("Expected a single KeyGenerated event"),
+        }
+    }
+
+    #[test]
+    fn test_non_ca_purpose_rejected() {
+        let key_pair = HsmKeyPair {
+            key_ref: HsmKeyRef {
+                token_serial: "0123456789abcdef".to_string(),
+                slot_id: 0,
+                label: "signing".to_string(),
+                object_id: "01".to_string(),
+            },
+            algorithm: HsmKeyAlgorithm::EcdsaP256,
+            public_key: vec![0x04; 65],
+        };
+        let mut cmd = GenerateHsmKey::new(&key_pair);
+        cmd.purpose = KeyPurpose::Signing;
+        assert!(matches!(
+            handle_generate_hsm_key(cmd),
+            Err(KeyManagementError::PolicyViolation(_))
+        ));
+    }
+}
@@ -7,7 +7,9 @@
 pub use super::pki::{
     GenerateKeyPair,
     KeyPairGenerated,
+    RevokeKey,
     handle_generate_key_pair,
+    handle_revoke_key,
 };
 
 // TODO: Future refactoring
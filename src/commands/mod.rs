@@ -31,6 +31,14 @@ pub mod nats_user;
 pub mod relationship;
 pub mod manifest;
 pub mod delegation;
+pub mod gpg;
+pub mod ssh;
+pub mod hsm;
+pub mod tpm;
+pub mod seed_custody;
+pub mod undo;
+pub mod approval;
+pub mod authorization;
 
 // Re-export command types
 pub use nats_identity::{
@@ -38,6 +46,22 @@ pub use nats_identity::{
     NatsAccountCreated, NatsInfrastructureBootstrapped, NatsOperatorCreated, NatsUserCreated,
 };
 
+pub use nats_operator::{
+    NatsSigningKeyRotationStep, handle_introduce_nats_signing_key, handle_reissue_nats_jwt,
+    handle_retire_nats_signing_key, resign_nats_issuer_jwt,
+};
+
+pub use nats_user::{
+    EdgeSiteCredentialsIssued, IssueEdgeSiteCredentials, handle_issue_edge_site_credentials,
+};
+
+pub use nats_account::{
+    DeclareNatsAccountExport, DeclareNatsAccountImport, NatsAccountExportDeclared, NatsAccountImportDeclared,
+    NatsAccountLimitsSet, NatsUserRevoked, RevokeNatsUser, SetNatsAccountLimits,
+    handle_declare_nats_account_export, handle_declare_nats_account_import, handle_revoke_nats_user,
+    handle_set_nats_account_limits, issue_nats_activation,
+};
+
 pub use yubikey::{
     ConfigureYubiKeySecurity, ProvisionYubiKeySlot, YubiKeySecurityConfigured,
     YubiKeySlotProvisioned,
@@ -45,7 +69,7 @@ pub use yubikey::{
 
 pub use pki::{
     GenerateCertificate, GenerateKeyPair, GenerateRootCA, CertificateGenerated, KeyPairGenerated,
-    RootCAGenerated,
+    RevokeKey, RootCAGenerated,
 };
 
 pub use export::{ExportToEncryptedStorage, ExportCompleted};
@@ -59,6 +83,36 @@ pub use delegation::{
     handle_create_delegation, handle_revoke_delegation,
 };
 
+pub use gpg::{
+    CertifyGpgKey, ExportGpgSecretKey, MoveGpgKeyToCard, RecordKeyExpirationExtension,
+    handle_certify_gpg_key, handle_export_gpg_secret_key, handle_move_gpg_key_to_card,
+    handle_record_key_expiration_extension,
+};
+
+pub use seed_custody::{
+    AssignedShare, CreateMasterSeed, CustodyGroup, MasterSeedSplitResult, RecoverMasterSeed, SplitMasterSeed,
+    handle_create_master_seed, handle_recover_master_seed, handle_split_master_seed,
+};
+
+pub use undo::{compensate, is_reversible, CompensatingCommand};
+
+pub use authorization::CommandAuthorizer;
+
+pub use approval::{
+    ExecuteApprovedCommand, GrantApproval, RejectApproval, RequestApproval,
+    authorize_approved_command, handle_grant_approval, handle_reject_approval,
+    handle_request_approval,
+};
+
+pub use ssh::{
+    ExportSshPrivateKey, GenerateSshSecurityKey, ImportSshPublicKey, RegenerateSshKeyFromSeed,
+    handle_export_ssh_private_key, handle_generate_ssh_security_key,
+    handle_import_ssh_public_key, handle_regenerate_ssh_key_from_seed,
+};
+
+pub use hsm::{GenerateHsmKey, handle_generate_hsm_key};
+pub use tpm::{GenerateTpmKey, handle_generate_tpm_key};
+
 // Legacy command wrapper for backward compatibility with GUI and tests
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum KeyCommand {
@@ -68,6 +122,7 @@ pub enum KeyCommand {
     GenerateSshKey(GenerateSshKeyCommand),
     ProvisionYubiKey(ProvisionYubiKeySlot),
     ExportKeys(ExportToEncryptedStorage),
+    RevokeKey(RevokeKey),
 
     // Organizational domain operations
     CreateOrganization(organization::CreateOrganization),
@@ -75,10 +130,115 @@ pub enum KeyCommand {
     CreateLocation(organization::CreateLocation),
     CreateOrganizationalUnit(organization::CreateOrganizationalUnit),
     CreateServiceAccount(organization::CreateServiceAccount),
+    SuspendPerson(organization::SuspendPerson),
+    ArchivePerson(organization::ArchivePerson),
+
+    // Compensating operations (undo)
+    DeactivatePerson(organization::DeactivatePerson),
+    TerminateRelationship(organization::TerminateRelationship),
+    UnbindPolicy(organization::UnbindPolicy),
+    SupersedePolicy(organization::SupersedePolicy),
+    BindPolicy(organization::BindPolicy),
+    AssignRole(organization::AssignRole),
 
     // Delegation operations
     CreateDelegation(delegation::CreateDelegation),
     RevokeDelegation(delegation::RevokeDelegation),
+
+    // GPG key operations
+    ExportGpgSecretKey(gpg::ExportGpgSecretKey),
+    RecordKeyExpirationExtension(gpg::RecordKeyExpirationExtension),
+    MoveGpgKeyToCard(gpg::MoveGpgKeyToCard),
+    CertifyGpgKey(gpg::CertifyGpgKey),
+
+    // SSH key operations
+    ExportSshPrivateKey(ssh::ExportSshPrivateKey),
+    GenerateSshSecurityKey(ssh::GenerateSshSecurityKey),
+    ImportSshPublicKey(ssh::ImportSshPublicKey),
+
+    // Approval of sensitive commands
+    RequestApproval(approval::RequestApproval),
+    GrantApproval(approval::GrantApproval),
+    RejectApproval(approval::RejectApproval),
+    ExecuteApprovedCommand(approval::ExecuteApprovedCommand),
+}
+
+impl KeyCommand {
+    /// Variant name, used to look up the claims a command requires
+    pub fn command_type(&self) -> &'static str {
+        match self {
+            KeyCommand::GenerateRootCA(_) => "GenerateRootCA",
+            KeyCommand::GenerateCertificate(_) => "GenerateCertificate",
+            KeyCommand::GenerateSshKey(_) => "GenerateSshKey",
+            KeyCommand::ProvisionYubiKey(_) => "ProvisionYubiKey",
+            KeyCommand::ExportKeys(_) => "ExportKeys",
+            KeyCommand::RevokeKey(_) => "RevokeKey",
+            KeyCommand::CreateOrganization(_) => "CreateOrganization",
+            KeyCommand::CreatePerson(_) => "CreatePerson",
+            KeyCommand::CreateLocation(_) => "CreateLocation",
+            KeyCommand::CreateOrganizationalUnit(_) => "CreateOrganizationalUnit",
+            KeyCommand::CreateServiceAccount(_) => "CreateServiceAccount",
+            KeyCommand::SuspendPerson(_) => "SuspendPerson",
+            KeyCommand::ArchivePerson(_) => "ArchivePerson",
+            KeyCommand::DeactivatePerson(_) => "DeactivatePerson",
+            KeyCommand::TerminateRelationship(_) => "TerminateRelationship",
+            KeyCommand::UnbindPolicy(_) => "UnbindPolicy",
+            KeyCommand::SupersedePolicy(_) => "SupersedePolicy",
+            KeyCommand::BindPolicy(_) => "BindPolicy",
+            KeyCommand::AssignRole(_) => "AssignRole",
+            KeyCommand::CreateDelegation(_) => "CreateDelegation",
+            KeyCommand::RevokeDelegation(_) => "RevokeDelegation",
+            KeyCommand::ExportGpgSecretKey(_) => "ExportGpgSecretKey",
+            KeyCommand::RecordKeyExpirationExtension(_) => "RecordKeyExpirationExtension",
+            KeyCommand::MoveGpgKeyToCard(_) => "MoveGpgKeyToCard",
+            KeyCommand::CertifyGpgKey(_) => "CertifyGpgKey",
+            KeyCommand::ExportSshPrivateKey(_) => "ExportSshPrivateKey",
+            KeyCommand::GenerateSshSecurityKey(_) => "GenerateSshSecurityKey",
+            KeyCommand::ImportSshPublicKey(_) => "ImportSshPublicKey",
+            KeyCommand::RequestApproval(_) => "RequestApproval",
+            KeyCommand::GrantApproval(_) => "GrantApproval",
+            KeyCommand::RejectApproval(_) => "RejectApproval",
+            KeyCommand::ExecuteApprovedCommand(_) => "ExecuteApprovedCommand",
+        }
+    }
+
+    /// ID of the wrapped command, used to deduplicate retries
+    pub fn command_id(&self) -> uuid::Uuid {
+        match self {
+            KeyCommand::GenerateRootCA(cmd) => cmd.command_id,
+            KeyCommand::GenerateCertificate(cmd) => *cmd.command_id.as_uuid(),
+            KeyCommand::GenerateSshKey(cmd) => *cmd.command_id.as_uuid(),
+            KeyCommand::ProvisionYubiKey(cmd) => cmd.command_id,
+            KeyCommand::ExportKeys(cmd) => cmd.command_id,
+            KeyCommand::RevokeKey(cmd) => cmd.command_id,
+            KeyCommand::CreateOrganization(cmd) => cmd.command_id,
+            KeyCommand::CreatePerson(cmd) => cmd.command_id,
+            KeyCommand::CreateLocation(cmd) => cmd.command_id,
+            KeyCommand::CreateOrganizationalUnit(cmd) => cmd.command_id,
+            KeyCommand::CreateServiceAccount(cmd) => cmd.command_id,
+            KeyCommand::SuspendPerson(cmd) => cmd.command_id,
+            KeyCommand::ArchivePerson(cmd) => cmd.command_id,
+            KeyCommand::DeactivatePerson(cmd) => cmd.command_id,
+            KeyCommand::TerminateRelationship(cmd) => cmd.command_id,
+            KeyCommand::UnbindPolicy(cmd) => cmd.command_id,
+            KeyCommand::SupersedePolicy(cmd) => cmd.command_id,
+            KeyCommand::BindPolicy(cmd) => cmd.command_id,
+            KeyCommand::AssignRole(cmd) => cmd.command_id,
+            KeyCommand::CreateDelegation(cmd) => cmd.command_id,
+            KeyCommand::RevokeDelegation(cmd) => cmd.command_id,
+            KeyCommand::ExportGpgSecretKey(cmd) => cmd.command_id,
+            KeyCommand::RecordKeyExpirationExtension(cmd) => cmd.command_id,
+            KeyCommand::MoveGpgKeyToCard(cmd) => cmd.command_id,
+            KeyCommand::CertifyGpgKey(cmd) => cmd.command_id,
+            KeyCommand::ExportSshPrivateKey(cmd) => cmd.command_id,
+            KeyCommand::GenerateSshSecurityKey(cmd) => cmd.command_id,
+            KeyCommand::ImportSshPublicKey(cmd) => cmd.command_id,
+            KeyCommand::RequestApproval(cmd) => cmd.command_id,
+            KeyCommand::GrantApproval(cmd) => cmd.command_id,
+            KeyCommand::RejectApproval(cmd) => cmd.command_id,
+            KeyCommand::ExecuteApprovedCommand(cmd) => cmd.command_id,
+        }
+    }
 }
 
 // Legacy command structures for backward compatibility
@@ -1,7 +1,20 @@
 //! NATS Account Aggregate Commands
 //!
 //! Commands for the NATS Account aggregate root.
-//! Currently re-exports from nats_identity.rs - full migration pending.
+//! Account creation is re-exported from nats_identity.rs - full migration pending.
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::nats::{
+    AccountClaimData, AccountLimitPolicy, AccountLimits, ActivationClaimData, ClaimExport, ClaimImport, NatsClaims,
+};
+use crate::domain::{NatsAccountId, NatsSecurityAggregate, NatsUserId};
+use crate::events::nats_account::{
+    NatsAccountExportDeclaredEvent, NatsAccountImportDeclaredEvent, NatsAccountLimitsSetEvent, NatsUserRevokedEvent,
+};
+use crate::events::{DomainEvent, NatsAccountEvents};
+use crate::value_objects::{NKeyPair, NKeyType};
 
 // Re-export NATS account commands from nats_identity module
 pub use super::nats_identity::{
@@ -9,9 +22,887 @@ pub use super::nats_identity::{
     NatsAccountCreated,
 };
 
-// TODO: Future refactoring
-// - Add UpdateNatsAccount command
-// - Add SetNatsPermissions command
-// - Add SuspendNatsAccount command
-// - Add ReactivateNatsAccount command
-// - Align with NatsAccountEvents from events/nats_account.rs
+// ============================================================================
+// Command: Set NATS Account Limits
+// ============================================================================
+
+/// Command to set an account's limits and JetStream quotas
+///
+/// The limits are checked against the organization's policy, then encoded
+/// into a new account JWT signed by the operator.
+#[derive(Debug, Clone)]
+pub struct SetNatsAccountLimits {
+    pub account_id: Uuid,
+    pub account_name: String,
+    pub account_public_key: String,
+    /// Account signing keys to keep in the re-signed JWT
+    pub signing_keys: Vec<String>,
+    pub limits: AccountLimits,
+    pub policy: AccountLimitPolicy,
+    /// Operator identity or signing key
+    pub operator_nkey: NKeyPair,
+    pub set_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl SetNatsAccountLimits {
+    /// Create a command checked against the default policy
+    pub fn new(
+        account_id: Uuid,
+        account_name: impl Into<String>,
+        account_public_key: impl Into<String>,
+        limits: AccountLimits,
+        operator_nkey: NKeyPair,
+    ) -> Self {
+        Self {
+            account_id,
+            account_name: account_name.into(),
+            account_public_key: account_public_key.into(),
+            signing_keys: Vec::new(),
+            limits,
+            policy: AccountLimitPolicy::default(),
+            operator_nkey,
+            set_by: "cim-keys-account-limits".to_string(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }
+    }
+
+    /// Set the organization policy the limits must satisfy
+    pub fn with_policy(mut self, policy: AccountLimitPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Keep an account signing key in the JWT
+    pub fn with_signing_key(mut self, public_key: impl Into<String>) -> Self {
+        self.signing_keys.push(public_key.into());
+        self
+    }
+
+    /// Set who is setting the limits
+    pub fn with_set_by(mut self, set_by: impl Into<String>) -> Self {
+        self.set_by = set_by.into();
+        self
+    }
+
+    /// Set correlation ID for tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Result of setting NATS account limits
+#[derive(Debug, Clone)]
+pub struct NatsAccountLimitsSet {
+    pub account_jwt: String,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Handle SetNatsAccountLimits command
+///
+/// Emits:
+/// - NatsAccountLimitsSetEvent (limits and the re-signed account JWT)
+pub fn handle_set_nats_account_limits(cmd: SetNatsAccountLimits) -> Result<NatsAccountLimitsSet, String> {
+    if cmd.operator_nkey.key_type != NKeyType::Operator {
+        return Err("Account limits must be signed by an operator key".to_string());
+    }
+
+    cmd.policy
+        .validate(&cmd.limits)
+        .map_err(|violations| format!("Account limits violate organization policy: {}", violations.join("; ")))?;
+
+    let claims = cmd.signing_keys.iter().fold(
+        NatsClaims::account(
+            cmd.account_name.clone(),
+            &cmd.account_public_key,
+            cmd.operator_nkey.public_key_string(),
+        )
+        .with_limits(cmd.limits.to_claim_limits()),
+        |claims, key| claims.with_signing_key(key.clone()),
+    );
+    let account_jwt = claims.encode(&cmd.operator_nkey).map_err(|e| e.to_string())?;
+
+    let event = DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountLimitsSet(NatsAccountLimitsSetEvent {
+        account_id: cmd.account_id,
+        public_key: cmd.account_public_key,
+        limits: cmd.limits,
+        account_jwt: account_jwt.clone(),
+        set_at: Utc::now(),
+        set_by: cmd.set_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(NatsAccountLimitsSet {
+        account_jwt,
+        events: vec![event],
+    })
+}
+
+// ============================================================================
+// Command: Revoke NATS User
+// ============================================================================
+
+/// Command to revoke a user through its issuing account
+///
+/// The account JWT is re-signed with the user in its `revocations` map;
+/// servers loading it reject user JWTs issued up to the revocation time.
+#[derive(Debug, Clone)]
+pub struct RevokeNatsUser {
+    pub account_id: Uuid,
+    pub user_id: Uuid,
+    pub user_public_key: String,
+    /// Current account JWT; its limits, signing keys and earlier
+    /// revocations carry over
+    pub account_jwt: String,
+    /// Operator identity or signing key
+    pub operator_nkey: NKeyPair,
+    pub reason: String,
+    pub revoked_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl RevokeNatsUser {
+    pub fn new(
+        account_id: Uuid,
+        user_id: Uuid,
+        user_public_key: impl Into<String>,
+        account_jwt: impl Into<String>,
+        operator_nkey: NKeyPair,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            account_id,
+            user_id,
+            user_public_key: user_public_key.into(),
+            account_jwt: account_jwt.into(),
+            operator_nkey,
+            reason: reason.into(),
+            revoked_by: "cim-keys-user-revocation".to_string(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }
+    }
+
+    /// Set who is revoking the user
+    pub fn with_revoked_by(mut self, revoked_by: impl Into<String>) -> Self {
+        self.revoked_by = revoked_by.into();
+        self
+    }
+
+    /// Set correlation ID for tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Result of revoking a NATS user
+#[derive(Debug, Clone)]
+pub struct NatsUserRevoked {
+    pub account_jwt: String,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Handle RevokeNatsUser command
+///
+/// Both the account JWT's issuer and `operator_nkey` must be keys the
+/// aggregate records for the operator (its identity key or one of its
+/// signing keys), and the JWT and user key must be the ones recorded for
+/// `account_id` and `user_id`.
+///
+/// Emits:
+/// - NatsUserRevokedEvent (recorded by the issuing account, with the
+///   re-signed account JWT)
+pub fn handle_revoke_nats_user(
+    cmd: RevokeNatsUser,
+    aggregate: &NatsSecurityAggregate,
+) -> Result<NatsUserRevoked, String> {
+    if cmd.operator_nkey.key_type != NKeyType::Operator {
+        return Err("Account JWTs must be signed by an operator key".to_string());
+    }
+    if cmd.reason.trim().is_empty() {
+        return Err("Revocation reason is required".to_string());
+    }
+    let operator_key = cmd.operator_nkey.public_key_string();
+    if !aggregate.is_operator_key(operator_key) {
+        return Err(format!("{} is not a key of operator {}", operator_key, aggregate.name));
+    }
+
+    let account_id = NatsAccountId::from_uuid(cmd.account_id);
+    let user_id = NatsUserId::from_uuid(cmd.user_id);
+    aggregate.can_revoke_user(account_id, user_id)?;
+    if aggregate.users.get(&user_id).is_none_or(|user| user.public_key != cmd.user_public_key) {
+        return Err(format!("{} is not the public key of user {}", cmd.user_public_key, user_id));
+    }
+    if aggregate.is_user_revoked(account_id, &cmd.user_public_key) {
+        return Err(format!("User {} is already revoked", cmd.user_public_key));
+    }
+    if !cmd.user_public_key.starts_with(NKeyType::User.prefix())
+        || nkeys::KeyPair::from_public_key(&cmd.user_public_key).is_err()
+    {
+        return Err(format!("Invalid user public key: {}", cmd.user_public_key));
+    }
+
+    let claims = NatsClaims::<AccountClaimData>::decode(&cmd.account_jwt).map_err(|e| e.to_string())?;
+    if !aggregate.is_operator_key(&claims.iss) {
+        return Err(format!("Account JWT is issued by {}, not by the operator", claims.iss));
+    }
+    if aggregate.accounts.get(&account_id).is_none_or(|account| account.public_key != claims.sub) {
+        return Err(format!("Account JWT is for {}, not for account {}", claims.sub, account_id));
+    }
+    let revoked_at = Utc::now();
+    let account_public_key = claims.sub.clone();
+    let account_jwt = claims
+        .with_revocation(cmd.user_public_key.clone(), revoked_at)
+        .with_issued_at(revoked_at)
+        .encode(&cmd.operator_nkey)
+        .map_err(|e| e.to_string())?;
+
+    let event = DomainEvent::NatsAccount(NatsAccountEvents::NatsUserRevoked(NatsUserRevokedEvent {
+        account_id: cmd.account_id,
+        account_public_key,
+        user_id: cmd.user_id,
+        user_public_key: cmd.user_public_key,
+        reason: cmd.reason,
+        revoked_at,
+        revoked_by: cmd.revoked_by,
+        account_jwt: account_jwt.clone(),
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(NatsUserRevoked {
+        account_jwt,
+        events: vec![event],
+    })
+}
+
+// ============================================================================
+// Command: Declare NATS Account Export
+// ============================================================================
+
+/// Command to share subjects of an account with other accounts
+///
+/// The export is added to the account JWT, which is re-signed by the
+/// operator. A private export (`token_req`) can only be imported with an
+/// activation JWT from [`issue_nats_activation`].
+#[derive(Debug, Clone)]
+pub struct DeclareNatsAccountExport {
+    pub account_id: Uuid,
+    /// Current account JWT; its limits, revocations, exports and imports
+    /// carry over
+    pub account_jwt: String,
+    pub export: ClaimExport,
+    /// Operator identity or signing key
+    pub operator_nkey: NKeyPair,
+    pub declared_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl DeclareNatsAccountExport {
+    pub fn new(account_id: Uuid, account_jwt: impl Into<String>, export: ClaimExport, operator_nkey: NKeyPair) -> Self {
+        Self {
+            account_id,
+            account_jwt: account_jwt.into(),
+            export,
+            operator_nkey,
+            declared_by: "cim-keys-account-export".to_string(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }
+    }
+
+    /// Set who is declaring the export
+    pub fn with_declared_by(mut self, declared_by: impl Into<String>) -> Self {
+        self.declared_by = declared_by.into();
+        self
+    }
+
+    /// Set correlation ID for tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Result of declaring a NATS account export
+#[derive(Debug, Clone)]
+pub struct NatsAccountExportDeclared {
+    pub account_jwt: String,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Handle DeclareNatsAccountExport command
+///
+/// Emits:
+/// - NatsAccountExportDeclaredEvent (export and the re-signed account JWT)
+pub fn handle_declare_nats_account_export(
+    cmd: DeclareNatsAccountExport,
+) -> Result<NatsAccountExportDeclared, String> {
+    if cmd.operator_nkey.key_type != NKeyType::Operator {
+        return Err("Account JWTs must be signed by an operator key".to_string());
+    }
+    check_subject(&cmd.export.subject)?;
+
+    let claims = NatsClaims::<AccountClaimData>::decode(&cmd.account_jwt).map_err(|e| e.to_string())?;
+    for existing in &claims.nats.exports {
+        if subject_covers(&existing.subject, &cmd.export.subject) || subject_covers(&cmd.export.subject, &existing.subject) {
+            return Err(format!(
+                "Export {} overlaps existing export {} of {}",
+                cmd.export.subject, existing.subject, claims.name
+            ));
+        }
+    }
+
+    let declared_at = Utc::now();
+    let public_key = claims.sub.clone();
+    let account_jwt = claims
+        .with_export(cmd.export.clone())
+        .with_issued_at(declared_at)
+        .encode(&cmd.operator_nkey)
+        .map_err(|e| e.to_string())?;
+
+    let event = DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountExportDeclared(NatsAccountExportDeclaredEvent {
+        account_id: cmd.account_id,
+        public_key,
+        export: cmd.export,
+        account_jwt: account_jwt.clone(),
+        declared_at,
+        declared_by: cmd.declared_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(NatsAccountExportDeclared {
+        account_jwt,
+        events: vec![event],
+    })
+}
+
+/// Activation JWT letting `importer_public_key` import a private export
+///
+/// Signed by the exporting account or one of its signing keys.
+pub fn issue_nats_activation(
+    exporter_nkey: &NKeyPair,
+    exporter_public_key: &str,
+    importer_public_key: &str,
+    export: &ClaimExport,
+) -> Result<String, String> {
+    if !export.token_req {
+        return Err(format!("Export {} is public and needs no activation", export.subject));
+    }
+    NatsClaims::activation(export.subject.clone(), export.export_type, importer_public_key, exporter_public_key)
+        .encode(exporter_nkey)
+        .map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Command: Declare NATS Account Import
+// ============================================================================
+
+/// Command to use subjects exported by another account
+///
+/// The import is checked against the exporting account's JWT (and the
+/// activation JWT for private exports), then added to the importing
+/// account JWT, which is re-signed by the operator.
+#[derive(Debug, Clone)]
+pub struct DeclareNatsAccountImport {
+    pub account_id: Uuid,
+    /// Current JWT of the importing account
+    pub account_jwt: String,
+    /// JWT of the exporting account
+    pub exporter_jwt: String,
+    pub import: ClaimImport,
+    /// Operator identity or signing key
+    pub operator_nkey: NKeyPair,
+    pub declared_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl DeclareNatsAccountImport {
+    pub fn new(
+        account_id: Uuid,
+        account_jwt: impl Into<String>,
+        exporter_jwt: impl Into<String>,
+        import: ClaimImport,
+        operator_nkey: NKeyPair,
+    ) -> Self {
+        Self {
+            account_id,
+            account_jwt: account_jwt.into(),
+            exporter_jwt: exporter_jwt.into(),
+            import,
+            operator_nkey,
+            declared_by: "cim-keys-account-import".to_string(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }
+    }
+
+    /// Set who is declaring the import
+    pub fn with_declared_by(mut self, declared_by: impl Into<String>) -> Self {
+        self.declared_by = declared_by.into();
+        self
+    }
+
+    /// Set correlation ID for tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Result of declaring a NATS account import
+#[derive(Debug, Clone)]
+pub struct NatsAccountImportDeclared {
+    pub account_jwt: String,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Handle DeclareNatsAccountImport command
+///
+/// Emits:
+/// - NatsAccountImportDeclaredEvent (import and the re-signed account JWT)
+pub fn handle_declare_nats_account_import(
+    cmd: DeclareNatsAccountImport,
+) -> Result<NatsAccountImportDeclared, String> {
+    if cmd.operator_nkey.key_type != NKeyType::Operator {
+        return Err("Account JWTs must be signed by an operator key".to_string());
+    }
+
+    let claims = NatsClaims::<AccountClaimData>::decode(&cmd.account_jwt).map_err(|e| e.to_string())?;
+    let exporter = NatsClaims::<AccountClaimData>::decode(&cmd.exporter_jwt).map_err(|e| e.to_string())?;
+    if cmd.import.account != exporter.sub {
+        return Err(format!("Import names account {} but the exporter JWT is {}", cmd.import.account, exporter.sub));
+    }
+    if exporter.sub == claims.sub {
+        return Err("An account cannot import its own export".to_string());
+    }
+
+    check_subject(&cmd.import.subject)?;
+    if let Some(local_subject) = &cmd.import.local_subject {
+        check_subject(local_subject)?;
+    }
+    let export = exporter
+        .nats
+        .exports
+        .iter()
+        .filter(|export| export.export_type == cmd.import.export_type)
+        .find(|export| subject_covers(&export.subject, &cmd.import.subject))
+        .ok_or_else(|| {
+            format!(
+                "{} exports no {:?} covering {}",
+                exporter.name, cmd.import.export_type, cmd.import.subject
+            )
+        })?;
+
+    if export.token_req {
+        let token = cmd
+            .import
+            .token
+            .as_deref()
+            .ok_or_else(|| format!("Export {} of {} requires an activation token", export.subject, exporter.name))?;
+        let activation = NatsClaims::<ActivationClaimData>::decode(token).map_err(|e| e.to_string())?;
+        if activation.sub != claims.sub
+            || activation.exporter_public_key() != exporter.sub
+            || activation.nats.kind != export.export_type
+            || !subject_covers(&activation.nats.subject, &cmd.import.subject)
+        {
+            return Err(format!("Activation token does not grant {} {} to {}", exporter.name, cmd.import.subject, claims.name));
+        }
+        if activation.iss != exporter.sub && !exporter.nats.signing_keys.contains(&activation.iss) {
+            return Err(format!("Activation token signer {} is not a key of {}", activation.iss, exporter.name));
+        }
+    }
+
+    if claims
+        .nats
+        .imports
+        .iter()
+        .any(|existing| existing.account == cmd.import.account && existing.subject == cmd.import.subject)
+    {
+        return Err(format!("{} already imports {} from {}", claims.name, cmd.import.subject, exporter.name));
+    }
+
+    let declared_at = Utc::now();
+    let public_key = claims.sub.clone();
+    let account_jwt = claims
+        .with_import(cmd.import.clone())
+        .with_issued_at(declared_at)
+        .encode(&cmd.operator_nkey)
+        .map_err(|e| e.to_string())?;
+
+    let event = DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountImportDeclared(NatsAccountImportDeclaredEvent {
+        account_id: cmd.account_id,
+        public_key,
+        import: cmd.import,
+        account_jwt: account_jwt.clone(),
+        declared_at,
+        declared_by: cmd.declared_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(NatsAccountImportDeclared {
+        account_jwt,
+        events: vec![event],
+    })
+}
+
+/// A NATS subject: dot-separated non-empty tokens, `>` only last
+fn check_subject(subject: &str) -> Result<(), String> {
+    let tokens: Vec<&str> = subject.split('.').collect();
+    let valid = tokens.iter().enumerate().all(|(i, token)| {
+        !token.is_empty() && !token.contains(char::is_whitespace) && (*token != ">" || i == tokens.len() - 1)
+    });
+    if !valid {
+        return Err(format!("Invalid subject: {:?}", subject));
+    }
+    Ok(())
+}
+
+/// Whether every subject matched by `subject` is matched by `pattern`
+fn subject_covers(pattern: &str, subject: &str) -> bool {
+    let mut subject_tokens = subject.split('.');
+    for token in pattern.split('.') {
+        match (token, subject_tokens.next()) {
+            (">", Some(_)) => return true,
+            ("*", Some(other)) if other != ">" => {}
+            (literal, Some(other)) if literal == other => {}
+            _ => return false,
+        }
+    }
+    subject_tokens.next().is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::aggregates::{NatsAccountState, NatsUserState};
+    use crate::domain::{BootstrapOrgId, NatsOperatorId};
+    use crate::projection::nscstore::{AccountCredentials, DomainNatsCredentials, OperatorCredentials, UserCredentials};
+    use std::collections::HashMap;
+
+    fn jetstream_limits() -> AccountLimits {
+        AccountLimits {
+            max_jetstream_memory: Some(256 * 1024 * 1024),
+            max_jetstream_storage: Some(10 * 1024 * 1024 * 1024),
+            max_streams: Some(20),
+            max_consumers: Some(100),
+            ..AccountLimits::default()
+        }
+    }
+
+    #[test]
+    fn test_limits_encoded_into_account_jwt() {
+        let operator = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let account = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let policy = AccountLimitPolicy {
+            ceilings: AccountLimits {
+                max_streams: Some(50),
+                ..AccountLimitPolicy::permissive().ceilings
+            },
+            allow_jetstream: true,
+        };
+
+        let cmd = SetNatsAccountLimits::new(
+            Uuid::now_v7(),
+            "engineering",
+            account.public_key_string(),
+            jetstream_limits(),
+            operator,
+        )
+        .with_policy(policy);
+        let result = handle_set_nats_account_limits(cmd).unwrap();
+
+        let claims = NatsClaims::<AccountClaimData>::decode(&result.account_jwt).unwrap();
+        assert_eq!(claims.nats.limits.conn, 100);
+        assert_eq!(claims.nats.limits.nats.data, -1);
+        assert_eq!(claims.nats.limits.jetstream.streams, 20);
+        assert_eq!(claims.nats.limits.jetstream.disk_storage, 10 * 1024 * 1024 * 1024);
+        assert!(matches!(
+            &result.events[..],
+            [DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountLimitsSet(_))]
+        ));
+    }
+
+    #[test]
+    fn test_limits_checked_against_policy() {
+        let operator = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let account = NKeyPair::generate(NKeyType::Account, None).unwrap();
+
+        // The default policy has no JetStream and caps connections at 100
+        let limits = AccountLimits {
+            max_connections: Some(500),
+            ..jetstream_limits()
+        };
+        let err = handle_set_nats_account_limits(SetNatsAccountLimits::new(
+            Uuid::now_v7(),
+            "engineering",
+            account.public_key_string(),
+            limits,
+            operator.clone(),
+        ))
+        .unwrap_err();
+        assert!(err.contains("JetStream is not allowed"));
+        assert!(err.contains("max_connections 500 exceeds organization limit 100"));
+
+        // Without JetStream the unset quotas are not required
+        assert!(handle_set_nats_account_limits(SetNatsAccountLimits::new(
+            Uuid::now_v7(),
+            "engineering",
+            account.public_key_string(),
+            AccountLimits::default(),
+            operator,
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn test_revoked_user_recorded_by_account() {
+        let operator = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let account = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let user = NKeyPair::generate(NKeyType::User, None).unwrap();
+
+        let account_jwt = NatsClaims::account("engineering", account.public_key_string(), operator.public_key_string())
+            .with_limits(AccountLimits::default().to_claim_limits())
+            .encode(&operator)
+            .unwrap();
+        let user_claims = NatsClaims::user("alice", user.public_key_string(), account.public_key_string())
+            .with_issued_at(Utc::now() - chrono::Duration::minutes(5));
+
+        let mut aggregate = NatsSecurityAggregate::new(NatsOperatorId::new(), BootstrapOrgId::new(), "cowboyai".to_string());
+        aggregate.public_key = operator.public_key_string().to_string();
+        let (account_id, user_id) = (NatsAccountId::new(), NatsUserId::new());
+        aggregate.accounts.insert(account_id, NatsAccountState {
+            id: account_id,
+            name: "engineering".to_string(),
+            public_key: account.public_key_string().to_string(),
+            unit_id: None,
+            is_system_account: false,
+            revocations: HashMap::new(),
+            signing_keys: Vec::new(),
+        });
+        aggregate.users.insert(user_id, NatsUserState {
+            id: user_id,
+            name: "alice".to_string(),
+            public_key: user.public_key_string().to_string(),
+            account_id,
+            person_id: None,
+            is_service_account: false,
+        });
+
+        let cmd = RevokeNatsUser::new(
+            account_id.as_uuid(),
+            user_id.as_uuid(),
+            user.public_key_string(),
+            account_jwt.clone(),
+            operator.clone(),
+            "laptop stolen",
+        );
+        let result = handle_revoke_nats_user(cmd, &aggregate).unwrap();
+
+        // Limits carry over, the user is revoked
+        let claims = NatsClaims::<AccountClaimData>::decode(&result.account_jwt).unwrap();
+        assert_eq!(claims.nats.limits.conn, 100);
+        assert!(claims.revokes(&user_claims));
+
+        for event in &result.events {
+            aggregate.apply(event).unwrap();
+        }
+        assert!(aggregate.is_user_revoked(account_id, user.public_key_string()));
+
+        // Revoking again is rejected
+        let again = RevokeNatsUser::new(
+            account_id.as_uuid(),
+            user_id.as_uuid(),
+            user.public_key_string(),
+            result.account_jwt.clone(),
+            operator.clone(),
+            "laptop stolen",
+        );
+        assert!(handle_revoke_nats_user(again, &aggregate).is_err());
+
+        // A JWT from another operator (even re-signed with that operator's
+        // key), for another account or a key of another user are refused
+        let other_operator = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let other_account = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let other_user = NKeyPair::generate(NKeyType::User, None).unwrap();
+        let foreign = NatsClaims::account("engineering", account.public_key_string(), other_operator.public_key_string())
+            .encode(&other_operator)
+            .unwrap();
+        let misbound = NatsClaims::account("sales", other_account.public_key_string(), operator.public_key_string())
+            .encode(&operator)
+            .unwrap();
+        for (user_public_key, jwt, operator_nkey) in [
+            (other_user.public_key_string(), result.account_jwt.clone(), &operator),
+            (user.public_key_string(), foreign.clone(), &operator),
+            (user.public_key_string(), foreign, &other_operator),
+            (user.public_key_string(), misbound, &operator),
+        ] {
+            let mut fresh = aggregate.clone();
+            fresh.accounts.get_mut(&account_id).unwrap().revocations.clear();
+            let cmd = RevokeNatsUser::new(
+                account_id.as_uuid(),
+                user_id.as_uuid(),
+                user_public_key,
+                jwt,
+                operator_nkey.clone(),
+                "laptop stolen",
+            );
+            assert!(handle_revoke_nats_user(cmd, &fresh).is_err());
+        }
+
+        // The NSC store gets the new account JWT and loses the user
+        let mut credentials = DomainNatsCredentials {
+            organization_id: Uuid::now_v7(),
+            organization_name: "Cowboy AI".to_string(),
+            operator: OperatorCredentials {
+                name: "cowboyai".to_string(),
+                jwt: String::new(),
+                public_key: operator.public_key_string().to_string(),
+                signing_keys: vec![],
+                system_account: None,
+            },
+            accounts: HashMap::from([(
+                "engineering".to_string(),
+                AccountCredentials {
+                    name: "engineering".to_string(),
+                    jwt: account_jwt,
+                    public_key: account.public_key_string().to_string(),
+                    operator_public_key: operator.public_key_string().to_string(),
+                    signing_keys: vec![],
+                },
+            )]),
+            users: HashMap::from([(
+                "engineering".to_string(),
+                vec![UserCredentials {
+                    name: "alice".to_string(),
+                    jwt: user_claims.encode(&account).unwrap(),
+                    public_key: user.public_key_string().to_string(),
+                    seed: None,
+                    account_public_key: account.public_key_string().to_string(),
+                    person_id: None,
+                }],
+            )]),
+            generated_at: Utc::now(),
+        };
+        let DomainEvent::NatsAccount(NatsAccountEvents::NatsUserRevoked(event)) = &result.events[0] else {
+            panic!("Expected NatsUserRevoked");
+        };
+        assert!(credentials.apply_user_revocation(event));
+        assert_eq!(credentials.accounts["engineering"].jwt, result.account_jwt);
+        assert!(credentials.users["engineering"].is_empty());
+    }
+
+    #[test]
+    fn test_private_service_export_imported_with_activation() {
+        use crate::domain::nats::ExportType;
+
+        let operator = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let billing = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let engineering = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let account_jwt = |name: &str, account: &NKeyPair| {
+            NatsClaims::account(name, account.public_key_string(), operator.public_key_string())
+                .encode(&operator)
+                .unwrap()
+        };
+
+        let export = ClaimExport {
+            name: "invoices".to_string(),
+            subject: "billing.invoices.>".to_string(),
+            export_type: ExportType::Service,
+            token_req: true,
+        };
+        let exported = handle_declare_nats_account_export(DeclareNatsAccountExport::new(
+            Uuid::now_v7(),
+            account_jwt("billing", &billing),
+            export.clone(),
+            operator.clone(),
+        ))
+        .unwrap();
+        let claims = NatsClaims::<AccountClaimData>::decode(&exported.account_jwt).unwrap();
+        assert_eq!(claims.nats.exports, vec![export.clone()]);
+
+        // Overlapping exports are rejected
+        let overlapping = ClaimExport {
+            subject: "billing.invoices.eu".to_string(),
+            ..export.clone()
+        };
+        assert!(handle_declare_nats_account_export(DeclareNatsAccountExport::new(
+            Uuid::now_v7(),
+            exported.account_jwt.clone(),
+            overlapping,
+            operator.clone(),
+        ))
+        .is_err());
+
+        let mut import = ClaimImport {
+            name: "invoices".to_string(),
+            subject: "billing.invoices.eu".to_string(),
+            account: billing.public_key_string().to_string(),
+            token: None,
+            local_subject: Some("invoices.eu".to_string()),
+            export_type: ExportType::Service,
+        };
+        let declare = |import: ClaimImport| {
+            handle_declare_nats_account_import(DeclareNatsAccountImport::new(
+                Uuid::now_v7(),
+                account_jwt("engineering", &engineering),
+                exported.account_jwt.clone(),
+                import,
+                operator.clone(),
+            ))
+        };
+
+        // A private export needs an activation for the importer
+        assert!(declare(import.clone()).unwrap_err().contains("requires an activation token"));
+        let stranger = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        import.token = Some(
+            issue_nats_activation(&billing, billing.public_key_string(), stranger.public_key_string(), &export).unwrap(),
+        );
+        assert!(declare(import.clone()).is_err());
+
+        import.token = Some(
+            issue_nats_activation(&billing, billing.public_key_string(), engineering.public_key_string(), &export).unwrap(),
+        );
+        let imported = declare(import.clone()).unwrap();
+        let claims = NatsClaims::<AccountClaimData>::decode(&imported.account_jwt).unwrap();
+        assert_eq!(claims.nats.imports, vec![import]);
+        assert!(matches!(
+            &imported.events[..],
+            [DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountImportDeclared(_))]
+        ));
+    }
+}
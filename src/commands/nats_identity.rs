@@ -8,9 +8,12 @@
 use chrono::Utc;
 use uuid::Uuid;
 
+use std::collections::HashMap;
+
 use crate::domain::{AccountIdentity, Organization, UserIdentity};
-use crate::domain_projections::NatsProjection;
+use crate::domain_projections::{NatsProjection, SubjectPermissionProjection, SYSTEM_ACCOUNT_NAME};
 use crate::events::DomainEvent;
+use crate::projection::{AccountCredentials, DomainNatsCredentials, OperatorCredentials, UserCredentials};
 use crate::value_objects::{
     AccountLimits, NatsCredential, NatsJwt, NKeyPair, Permissions, UserLimits,
 };
@@ -152,6 +155,7 @@ pub struct CreateNatsUser {
     pub user: UserIdentity,
     pub organization: Organization,
     pub account_nkey: NKeyPair,
+    /// Explicit permissions; derived from the org chart when `None`
     pub permissions: Option<Permissions>,
     pub limits: Option<UserLimits>,
     pub correlation_id: Uuid,
@@ -273,11 +277,14 @@ pub fn handle_create_nats_user(cmd: CreateNatsUser) -> Result<NatsUserCreated, S
     }
 
     // Step 3: Project user identity to NATS user (US-021: collects projection events)
+    let permissions = cmd.permissions.unwrap_or_else(|| {
+        SubjectPermissionProjection::project_user_identity_permissions(&cmd.organization, &cmd.user)
+    });
     let identity = NatsProjection::project_user_identity(
         &cmd.user,
         &cmd.organization,
         &cmd.account_nkey,
-        cmd.permissions,
+        Some(permissions),
         cmd.limits,
         cmd.correlation_id,
         cmd.causation_id,
@@ -323,19 +330,74 @@ pub struct BootstrapNatsInfrastructure {
 /// Complete NATS infrastructure result
 #[derive(Debug, Clone)]
 pub struct NatsInfrastructureBootstrapped {
+    /// Operator; its JWT names the system account
     pub operator: NatsOperatorCreated,
+    /// System account (`SYS`)
+    pub system_account: NatsAccountCreated,
+    /// Monitoring user of the system account
+    pub monitoring_user: NatsUserCreated,
     pub accounts: Vec<NatsAccountCreated>,
     pub users: Vec<NatsUserCreated>,
     pub events: Vec<DomainEvent>,
 }
 
+impl NatsInfrastructureBootstrapped {
+    /// Credentials for the resolver configuration of the servers
+    ///
+    /// Holds the operator, the system account and its monitoring user.
+    /// Unit accounts are pushed to a full resolver through the system
+    /// account once the servers are running.
+    pub fn resolver_credentials(&self, organization: &Organization) -> DomainNatsCredentials {
+        let operator_key = self.operator.operator_nkey.public_key_string().to_string();
+        let system_key = self.system_account.account_nkey.public_key_string().to_string();
+
+        let mut accounts = HashMap::new();
+        accounts.insert(SYSTEM_ACCOUNT_NAME.to_string(), AccountCredentials {
+            name: SYSTEM_ACCOUNT_NAME.to_string(),
+            jwt: self.system_account.account_jwt.token().to_string(),
+            public_key: system_key.clone(),
+            operator_public_key: operator_key.clone(),
+            signing_keys: Vec::new(),
+        });
+
+        let mut users = HashMap::new();
+        users.insert(SYSTEM_ACCOUNT_NAME.to_string(), vec![UserCredentials {
+            name: crate::domain_projections::MONITORING_USER_NAME.to_string(),
+            jwt: self.monitoring_user.user_jwt.token().to_string(),
+            public_key: self.monitoring_user.user_nkey.public_key_string().to_string(),
+            seed: Some(self.monitoring_user.user_nkey.seed_string().to_string()),
+            account_public_key: system_key.clone(),
+            person_id: None,
+        }]);
+
+        DomainNatsCredentials {
+            organization_id: organization.id.as_uuid(),
+            organization_name: organization.name.clone(),
+            operator: OperatorCredentials {
+                name: organization.name.clone(),
+                jwt: self.operator.operator_jwt.token().to_string(),
+                public_key: operator_key,
+                signing_keys: Vec::new(),
+                system_account: Some(system_key),
+            },
+            accounts,
+            users,
+            generated_at: Utc::now(),
+        }
+    }
+}
+
 /// Handle BootstrapNatsInfrastructure command
 ///
 /// This is the organization-centric projection that extracts all identities
 /// from the organizational structure.
 ///
+/// Every bootstrap includes the system account (`SYS`) with a monitoring
+/// user, named in the operator JWT so the servers' resolver can use it.
+///
 /// Emits:
 /// - All operator, account, and user events
+/// - NatsOperatorUpdatedEvent (system account designated)
 /// - Complete event stream for entire infrastructure
 ///
 /// User Story: US-011
@@ -352,9 +414,38 @@ pub fn handle_bootstrap_nats_infrastructure(
         correlation_id: cmd.correlation_id,
         causation_id: Some(bootstrap_command_id), // A4: Reference parent bootstrap command
     };
-    let operator = handle_create_nats_operator(operator_cmd)?;
+    let mut operator = handle_create_nats_operator(operator_cmd)?;
     all_events.extend(operator.events.clone());
 
+    // Step 1b: System account with its monitoring user; the operator JWT
+    // is re-issued to name it
+    let system_account = create_system_account(&operator, cmd.correlation_id)?;
+    all_events.extend(system_account.events.clone());
+
+    let monitoring_user = create_monitoring_user(&system_account, cmd.correlation_id)?;
+    all_events.extend(monitoring_user.events.clone());
+
+    let designation = NatsProjection::project_operator_system_account(
+        &cmd.organization,
+        &operator.operator_nkey,
+        &system_account.account_nkey,
+        cmd.correlation_id,
+    )?;
+    all_events.extend(designation.events);
+    all_events.push(DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsOperatorUpdated(
+        crate::events::nats_operator::NatsOperatorUpdatedEvent {
+            operator_id: operator.operator_nkey.id,
+            field_name: "system_account".to_string(),
+            old_value: None,
+            new_value: system_account.account_nkey.public_key_string().to_string(),
+            updated_at: Utc::now(),
+            updated_by: "cim-keys-system-bootstrap".to_string(),
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(system_account.account_nkey.id),
+        },
+    )));
+    operator.operator_jwt = designation.jwt;
+
     // Step 2: Create accounts for all organizational units
     let mut accounts = Vec::new();
     for unit in &cmd.organization.units {
@@ -412,12 +503,70 @@ pub fn handle_bootstrap_nats_infrastructure(
 
     Ok(NatsInfrastructureBootstrapped {
         operator,
+        system_account,
+        monitoring_user,
         accounts,
         users,
         events: all_events,
     })
 }
 
+/// Create the system account (`SYS`) signed by the operator
+fn create_system_account(operator: &NatsOperatorCreated, correlation_id: Uuid) -> Result<NatsAccountCreated, String> {
+    let identity = NatsProjection::project_system_account(
+        &operator.operator_nkey,
+        correlation_id,
+        Some(operator.operator_nkey.id),
+    )?;
+
+    let mut events = identity.events;
+    events.push(DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountCreated(crate::events::nats_account::NatsAccountCreatedEvent {
+        account_id: identity.nkey.id,
+        operator_id: operator.operator_nkey.id,
+        name: SYSTEM_ACCOUNT_NAME.to_string(),
+        public_key: identity.nkey.public_key_string().to_string(),
+        is_system: true,
+        created_by: "cim-keys-system-bootstrap".to_string(),
+        organization_unit_id: None,
+        correlation_id,
+        causation_id: Some(operator.operator_nkey.id),
+    })));
+
+    Ok(NatsAccountCreated {
+        account_nkey: identity.nkey,
+        account_jwt: identity.jwt,
+        events,
+    })
+}
+
+/// Create the monitoring user of the system account
+fn create_monitoring_user(system_account: &NatsAccountCreated, correlation_id: Uuid) -> Result<NatsUserCreated, String> {
+    let identity = NatsProjection::project_monitoring_user(
+        &system_account.account_nkey,
+        correlation_id,
+        Some(system_account.account_nkey.id),
+    )?;
+
+    let mut events = identity.events;
+    events.push(DomainEvent::NatsUser(crate::events::NatsUserEvents::NatsUserCreated(crate::events::nats_user::NatsUserCreatedEvent {
+        user_id: identity.nkey.id,
+        account_id: system_account.account_nkey.id,
+        name: crate::domain_projections::MONITORING_USER_NAME.to_string(),
+        public_key: identity.nkey.public_key_string().to_string(),
+        created_by: "cim-keys-system-bootstrap".to_string(),
+        person_id: None,
+        correlation_id,
+        causation_id: Some(system_account.account_nkey.id),
+    })));
+
+    Ok(NatsUserCreated {
+        user_nkey: identity.nkey,
+        user_jwt: identity.jwt,
+        credential: identity.credential,
+        events,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -606,4 +755,48 @@ mod tests {
             .iter()
             .any(|e| matches!(e, DomainEvent::NatsUser(crate::events::NatsUserEvents::NatsUserCreated(_)))));
     }
+
+    #[test]
+    fn test_bootstrap_creates_system_account_for_resolver() {
+        use crate::domain::nats::{AccountClaimData, NatsClaims, OperatorClaimData, UserClaimData};
+        use crate::projection::{nats_resolver, Projection};
+
+        let org = Organization {
+            id: BootstrapOrgId::new(),
+            name: "Test Org".to_string(),
+            display_name: "Test Organization".to_string(),
+            description: None,
+            parent_id: None,
+            units: vec![],
+            metadata: Default::default(),
+        };
+
+        let result = handle_bootstrap_nats_infrastructure(BootstrapNatsInfrastructure {
+            organization: org.clone(),
+            correlation_id: Uuid::now_v7(),
+        })
+        .unwrap();
+
+        let system_key = result.system_account.account_nkey.public_key_string();
+        let operator = NatsClaims::<OperatorClaimData>::decode(result.operator.operator_jwt.token()).unwrap();
+        assert_eq!(operator.nats.system_account.as_deref(), Some(system_key));
+        let account = NatsClaims::<AccountClaimData>::decode(result.system_account.account_jwt.token()).unwrap();
+        assert_eq!(account.iss, operator.sub);
+
+        // The monitoring user watches $SYS but cannot act on it
+        let monitor = NatsClaims::<UserClaimData>::decode(result.monitoring_user.user_jwt.token()).unwrap();
+        assert_eq!(monitor.iss, system_key);
+        assert!(monitor.nats.permissions.subscribe.allow.contains(&"$SYS.>".to_string()));
+        assert!(!monitor.nats.permissions.publish.allow.contains(&"$SYS.>".to_string()));
+
+        assert!(result.events.iter().any(|e| matches!(
+            e,
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountCreated(created)) if created.is_system
+        )));
+
+        let resolver = nats_resolver().project(result.resolver_credentials(&org)).unwrap();
+        let conf = &resolver.files[0].content;
+        assert!(conf.contains(&format!("system_account: \"{}\"", system_key)));
+        assert!(conf.contains(&format!("{}: \"", system_key)));
+    }
 }
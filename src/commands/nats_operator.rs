@@ -3,6 +3,19 @@
 //! Commands for the NATS Operator aggregate root.
 //! Currently re-exports from nats_identity.rs - full migration pending.
 
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::nats::claims::NKEY_JWT_ALGORITHM;
+use crate::domain::nats::{AccountClaimData, NatsClaims, OperatorClaimData, UserClaimData};
+use crate::domain::sagas::{RotateNatsSigningKeySaga, SigningKeyRotationState};
+use crate::events::nats_operator::{
+    JwtClaimsCreatedEvent, JwtSignedEvent, NatsSigningKeyGeneratedEvent, NatsSigningKeyRetiredEvent,
+};
+use crate::events::{DomainEvent, NatsOperatorEvents};
+use crate::types::NatsEntityType;
+use crate::value_objects::{NKeyPair, NKeyType};
+
 // Re-export NATS operator commands from nats_identity module
 pub use super::nats_identity::{
     CreateNatsOperator,
@@ -15,3 +28,356 @@ pub use super::nats_identity::{
 // - Add ExportNatsConfig command
 // - Add GenerateNKey command
 // - Align with NatsOperatorEvents from events/nats_operator.rs
+
+// ============================================================================
+// Command: Rotate NATS Signing Key
+// ============================================================================
+//
+// Each handler performs one step of a `RotateNatsSigningKeySaga` and
+// advances it. On error the caller fails the saga and compensates,
+// re-signing the issuer JWT with `resign_nats_issuer_jwt` (which lists only
+// the old key once the saga is compensating).
+
+/// Result of one signing key rotation step
+#[derive(Debug, Clone)]
+pub struct NatsSigningKeyRotationStep {
+    /// Issuer JWT (introduce/retire) or re-issued downstream JWT
+    pub jwt: String,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Re-sign the issuer JWT with the signing keys the saga currently trusts
+///
+/// Signing keys outside the rotation are kept. Operator JWTs are signed by
+/// the operator identity key, account JWTs by an operator key.
+pub fn resign_nats_issuer_jwt(
+    saga: &RotateNatsSigningKeySaga,
+    issuer_jwt: &str,
+    issuer_signer: &NKeyPair,
+) -> Result<String, String> {
+    let rotating = [
+        Some(saga.request.old_signing_key.as_str()),
+        saga.artifacts.new_signing_key.as_deref(),
+    ];
+    let signing_keys = |current: &[String]| -> Vec<String> {
+        current
+            .iter()
+            .filter(|key| !rotating.contains(&Some(key.as_str())))
+            .map(String::as_str)
+            .chain(saga.signing_keys())
+            .map(str::to_string)
+            .collect()
+    };
+
+    let jwt = match saga.request.issuer_type {
+        NatsEntityType::Operator => {
+            let mut claims = NatsClaims::<OperatorClaimData>::decode(issuer_jwt).map_err(|e| e.to_string())?;
+            claims.nats.signing_keys = signing_keys(&claims.nats.signing_keys);
+            check_issuer(&claims.sub, saga)?;
+            claims.with_issued_at(Utc::now()).encode(issuer_signer)
+        }
+        NatsEntityType::Account => {
+            let mut claims = NatsClaims::<AccountClaimData>::decode(issuer_jwt).map_err(|e| e.to_string())?;
+            claims.nats.signing_keys = signing_keys(&claims.nats.signing_keys);
+            check_issuer(&claims.sub, saga)?;
+            claims.with_issued_at(Utc::now()).encode(issuer_signer)
+        }
+        NatsEntityType::User => return Err("Users have no signing keys".to_string()),
+    };
+    jwt.map_err(|e| e.to_string())
+}
+
+/// Introduce the new signing key alongside the old one
+///
+/// Emits:
+/// - NatsSigningKeyGeneratedEvent
+/// - JwtClaimsCreatedEvent + JwtSignedEvent (issuer JWT listing both keys)
+pub fn handle_introduce_nats_signing_key(
+    saga: &mut RotateNatsSigningKeySaga,
+    new_key: &NKeyPair,
+    issuer_jwt: &str,
+    issuer_signer: &NKeyPair,
+) -> Result<NatsSigningKeyRotationStep, String> {
+    if saga.state != SigningKeyRotationState::GeneratingSigningKey {
+        return Err(format!("Cannot introduce a signing key in state {}", saga.current_step_name()));
+    }
+    if new_key.key_type != signing_key_type(&saga.request.issuer_type)? {
+        return Err(format!(
+            "{:?} key cannot sign for a {:?}",
+            new_key.key_type, saga.request.issuer_type
+        ));
+    }
+
+    saga.record_new_signing_key(new_key.public_key_string());
+    saga.advance(); // PublishingIssuer
+    let jwt = resign_nats_issuer_jwt(saga, issuer_jwt, issuer_signer)?;
+    let now = Utc::now();
+    saga.record_issuer_published(now);
+    saga.advance(); // ReissuingDownstream
+
+    let mut events = vec![DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyGenerated(
+        NatsSigningKeyGeneratedEvent {
+            key_id: new_key.id,
+            entity_id: saga.request.issuer_id,
+            entity_type: saga.request.issuer_type,
+            public_key: new_key.public_key_string().to_string(),
+            generated_at: new_key.seed.generated_at,
+            correlation_id: saga.correlation_id,
+            causation_id: Some(saga.saga_id),
+        },
+    ))];
+    events.extend(jwt_signed_events(saga, &saga.request.issuer_public_key, &jwt, issuer_signer, now));
+
+    Ok(NatsSigningKeyRotationStep { jwt, events })
+}
+
+/// Re-issue one downstream JWT signed by the new signing key
+///
+/// Accounts of a rotating operator, users of a rotating account. Claims are
+/// carried over; only the signer (and `iat`) changes.
+///
+/// Emits:
+/// - JwtClaimsCreatedEvent + JwtSignedEvent (re-issued JWT)
+pub fn handle_reissue_nats_jwt(
+    saga: &mut RotateNatsSigningKeySaga,
+    downstream_jwt: &str,
+    new_key: &NKeyPair,
+) -> Result<NatsSigningKeyRotationStep, String> {
+    if saga.state != SigningKeyRotationState::ReissuingDownstream {
+        return Err(format!("Cannot re-issue JWTs in state {}", saga.current_step_name()));
+    }
+    if saga.artifacts.new_signing_key.as_deref() != Some(new_key.public_key_string()) {
+        return Err("JWTs must be re-issued by the new signing key".to_string());
+    }
+
+    let now = Utc::now();
+    let (subject, jwt) = match saga.request.issuer_type {
+        NatsEntityType::Operator => {
+            let claims = NatsClaims::<AccountClaimData>::decode(downstream_jwt).map_err(|e| e.to_string())?;
+            (claims.sub.clone(), claims.with_issued_at(now).encode(new_key))
+        }
+        NatsEntityType::Account => {
+            let mut claims = NatsClaims::<UserClaimData>::decode(downstream_jwt).map_err(|e| e.to_string())?;
+            if claims.account_public_key() != saga.request.issuer_public_key {
+                return Err(format!("User {} was not issued by the rotating account", claims.sub));
+            }
+            // Re-bind to the account so the new signing key lands in issuer_account
+            claims.iss = saga.request.issuer_public_key.clone();
+            claims.nats.issuer_account = None;
+            (claims.sub.clone(), claims.with_issued_at(now).encode(new_key))
+        }
+        NatsEntityType::User => return Err("Users have no signing keys".to_string()),
+    };
+    let jwt = jwt.map_err(|e| e.to_string())?;
+
+    let entity_id = saga
+        .pending_downstream()
+        .into_iter()
+        .find(|downstream| downstream.subject_public_key == subject)
+        .map(|downstream| downstream.entity_id)
+        .ok_or_else(|| format!("{} is not pending re-issue in this rotation", subject))?;
+    saga.record_reissued(entity_id);
+    saga.advance(); // GracePeriod once every JWT is re-issued
+
+    let events = jwt_signed_events(saga, &subject, &jwt, new_key, now);
+    Ok(NatsSigningKeyRotationStep { jwt, events })
+}
+
+/// Retire the old signing key once the grace period has elapsed
+///
+/// Emits:
+/// - JwtClaimsCreatedEvent + JwtSignedEvent (issuer JWT listing the new key only)
+/// - NatsSigningKeyRetiredEvent
+pub fn handle_retire_nats_signing_key(
+    saga: &mut RotateNatsSigningKeySaga,
+    issuer_jwt: &str,
+    issuer_signer: &NKeyPair,
+    now: DateTime<Utc>,
+) -> Result<NatsSigningKeyRotationStep, String> {
+    if saga.state != SigningKeyRotationState::RetiringOldKey && !saga.expire_grace_period(now) {
+        return Err(match saga.artifacts.grace_ends_at {
+            Some(ends) if saga.state == SigningKeyRotationState::GracePeriod => {
+                format!("Old signing key stays trusted until {}", ends)
+            }
+            _ => format!("Cannot retire the old signing key in state {}", saga.current_step_name()),
+        });
+    }
+
+    let jwt = resign_nats_issuer_jwt(saga, issuer_jwt, issuer_signer)?;
+    saga.record_old_key_retired();
+    saga.advance(); // Completed
+
+    let mut events = jwt_signed_events(saga, &saga.request.issuer_public_key, &jwt, issuer_signer, now);
+    events.push(DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyRetired(
+        NatsSigningKeyRetiredEvent {
+            entity_id: saga.request.issuer_id,
+            entity_type: saga.request.issuer_type,
+            public_key: saga.request.old_signing_key.clone(),
+            replaced_by: saga.artifacts.new_signing_key.clone(),
+            reason: saga.request.reason.clone(),
+            retired_at: now,
+            correlation_id: saga.correlation_id,
+            causation_id: Some(saga.saga_id),
+        },
+    )));
+
+    Ok(NatsSigningKeyRotationStep { jwt, events })
+}
+
+/// Key type of signing keys for an issuer
+fn signing_key_type(issuer_type: &NatsEntityType) -> Result<NKeyType, String> {
+    match issuer_type {
+        NatsEntityType::Operator => Ok(NKeyType::Operator),
+        NatsEntityType::Account => Ok(NKeyType::Account),
+        NatsEntityType::User => Err("Users have no signing keys".to_string()),
+    }
+}
+
+fn check_issuer(subject: &str, saga: &RotateNatsSigningKeySaga) -> Result<(), String> {
+    if subject != saga.request.issuer_public_key {
+        return Err(format!("JWT of {} is not the issuer JWT of this rotation", subject));
+    }
+    Ok(())
+}
+
+/// Claims and signature events recording a (re-)signed JWT
+fn jwt_signed_events(
+    saga: &RotateNatsSigningKeySaga,
+    subject: &str,
+    jwt: &str,
+    signer: &NKeyPair,
+    signed_at: DateTime<Utc>,
+) -> Vec<DomainEvent> {
+    let claims_id = Uuid::now_v7();
+    vec![
+        DomainEvent::NatsOperator(NatsOperatorEvents::JwtClaimsCreated(JwtClaimsCreatedEvent {
+            claims_id,
+            issuer: signer.public_key_string().to_string(),
+            subject: subject.to_string(),
+            audience: None,
+            permissions: format!("signing_keys={}", saga.signing_keys().join(",")),
+            not_before: signed_at,
+            expires_at: None,
+            correlation_id: saga.correlation_id,
+            causation_id: Some(saga.saga_id),
+        })),
+        DomainEvent::NatsOperator(NatsOperatorEvents::JwtSigned(JwtSignedEvent {
+            jwt_id: Uuid::now_v7(),
+            claims_id,
+            signed_by: signer.id,
+            signer_public_key: signer.public_key_string().to_string(),
+            jwt_token: jwt.to_string(),
+            signature_algorithm: NKEY_JWT_ALGORITHM.to_string(),
+            signature_verification_data: None,
+            signed_at,
+            correlation_id: saga.correlation_id,
+            causation_id: Some(saga.saga_id),
+        })),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::sagas::{DownstreamJwt, SigningKeyRotationRequest};
+    use chrono::Duration;
+
+    #[test]
+    fn test_account_signing_key_rotation() {
+        let operator = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let account = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let old_signer = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let new_signer = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let user = NKeyPair::generate(NKeyType::User, None).unwrap();
+
+        let account_jwt = NatsClaims::account("engineering", account.public_key_string(), operator.public_key_string())
+            .with_signing_key(old_signer.public_key_string())
+            .encode(&operator)
+            .unwrap();
+        let user_jwt = NatsClaims::user("alice", user.public_key_string(), account.public_key_string())
+            .encode(&old_signer)
+            .unwrap();
+
+        let mut saga = RotateNatsSigningKeySaga::new(SigningKeyRotationRequest {
+            issuer_id: Uuid::now_v7(),
+            issuer_type: NatsEntityType::Account,
+            issuer_public_key: account.public_key_string().to_string(),
+            old_signing_key: old_signer.public_key_string().to_string(),
+            downstream: vec![DownstreamJwt {
+                entity_id: Uuid::now_v7(),
+                name: "alice".to_string(),
+                subject_public_key: user.public_key_string().to_string(),
+            }],
+            grace_hours: 24,
+            reason: "Scheduled rotation".to_string(),
+        });
+        saga.start().unwrap();
+
+        let introduced = handle_introduce_nats_signing_key(&mut saga, &new_signer, &account_jwt, &operator).unwrap();
+        let claims = NatsClaims::<AccountClaimData>::decode(&introduced.jwt).unwrap();
+        assert_eq!(
+            claims.nats.signing_keys,
+            [old_signer.public_key_string(), new_signer.public_key_string()]
+        );
+
+        let reissued = handle_reissue_nats_jwt(&mut saga, &user_jwt, &new_signer).unwrap();
+        let claims = NatsClaims::<UserClaimData>::decode(&reissued.jwt).unwrap();
+        assert_eq!(claims.iss, new_signer.public_key_string());
+        assert_eq!(claims.account_public_key(), account.public_key_string());
+        assert_eq!(saga.state, SigningKeyRotationState::GracePeriod);
+
+        // The old key stays trusted until the grace period ends
+        assert!(handle_retire_nats_signing_key(&mut saga, &introduced.jwt, &operator, Utc::now()).is_err());
+        let retired = handle_retire_nats_signing_key(
+            &mut saga,
+            &introduced.jwt,
+            &operator,
+            Utc::now() + Duration::hours(25),
+        )
+        .unwrap();
+        let claims = NatsClaims::<AccountClaimData>::decode(&retired.jwt).unwrap();
+        assert_eq!(claims.nats.signing_keys, [new_signer.public_key_string()]);
+        assert_eq!(saga.state, SigningKeyRotationState::Completed);
+        assert!(matches!(
+            retired.events.last(),
+            Some(DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyRetired(e)))
+                if e.public_key == old_signer.public_key_string()
+        ));
+    }
+
+    #[test]
+    fn test_reissue_rejects_jwt_outside_rotation() {
+        let operator = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let old_signer = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let new_signer = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let account = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let stranger = NKeyPair::generate(NKeyType::Account, None).unwrap();
+
+        let operator_jwt = NatsClaims::operator("cowboyai", operator.public_key_string())
+            .with_signing_key(old_signer.public_key_string())
+            .encode(&operator)
+            .unwrap();
+        let stranger_jwt = NatsClaims::account("stranger", stranger.public_key_string(), operator.public_key_string())
+            .encode(&old_signer)
+            .unwrap();
+
+        let mut saga = RotateNatsSigningKeySaga::new(SigningKeyRotationRequest {
+            issuer_id: Uuid::now_v7(),
+            issuer_type: NatsEntityType::Operator,
+            issuer_public_key: operator.public_key_string().to_string(),
+            old_signing_key: old_signer.public_key_string().to_string(),
+            downstream: vec![DownstreamJwt {
+                entity_id: Uuid::now_v7(),
+                name: "engineering".to_string(),
+                subject_public_key: account.public_key_string().to_string(),
+            }],
+            grace_hours: 24,
+            reason: "Compromised signing key".to_string(),
+        });
+        saga.start().unwrap();
+        handle_introduce_nats_signing_key(&mut saga, &new_signer, &operator_jwt, &operator).unwrap();
+
+        assert!(handle_reissue_nats_jwt(&mut saga, &stranger_jwt, &new_signer).is_err());
+        assert_eq!(saga.pending_downstream().len(), 1);
+    }
+}
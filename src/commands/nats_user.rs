@@ -1,7 +1,18 @@
 //! NATS User Aggregate Commands
 //!
 //! Commands for the NATS User aggregate root.
-//! Currently re-exports from nats_identity.rs - full migration pending.
+//! User creation is re-exported from nats_identity.rs - full migration pending.
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::crypto::x509::{generate_server_certificate, ServerCertParams};
+use crate::crypto::MasterSeed;
+use crate::domain_projections::{NatsProjection, SubjectPermissionProjection};
+use crate::events::nats_user::{EdgeSiteCredentialsIssuedEvent, NatsUserCreatedEvent};
+use crate::events::{CertificateEvents, DomainEvent, NatsUserEvents};
+use crate::projection::nats_edge::{EdgeGateway, EdgeSiteBundle};
+use crate::value_objects::{NKeyPair, NKeyType, Permissions};
 
 // Re-export NATS user commands from nats_identity module
 pub use super::nats_identity::{
@@ -11,6 +22,159 @@ pub use super::nats_identity::{
     NatsInfrastructureBootstrapped,
 };
 
+// ============================================================================
+// Command: Issue Edge Site Credentials
+// ============================================================================
+
+/// Command to issue the NATS identity and TLS material of an edge site
+///
+/// An edge site is a Location running its own servers that connect to the
+/// hub as a leafnode, and optionally join the super-cluster as a gateway.
+#[derive(Debug, Clone)]
+pub struct IssueEdgeSiteCredentials {
+    pub location_id: Uuid,
+    pub location_name: String,
+    pub account_id: Uuid,
+    pub account_name: String,
+    /// Account identity key the leafnode user is signed with
+    pub account_nkey: NKeyPair,
+    /// Subjects the site may exchange with the hub; the whole account when `None`
+    pub permissions: Option<Permissions>,
+    /// Hub leafnode URLs (`tls://hub.example.com:7422`)
+    pub leafnode_remotes: Vec<String>,
+    pub gateway: Option<EdgeGateway>,
+    /// DNS names and addresses of the site's servers; the first is the common name
+    pub server_names: Vec<String>,
+    pub organization: String,
+    pub master_seed: MasterSeed,
+    pub intermediate_ca_cert_pem: String,
+    pub intermediate_ca_key_pem: String,
+    pub intermediate_ca_id: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// Result of issuing edge site credentials
+#[derive(Debug, Clone)]
+pub struct EdgeSiteCredentialsIssued {
+    pub user_nkey: NKeyPair,
+    /// Input of [`crate::projection::EdgeSiteProjection`]
+    pub bundle: EdgeSiteBundle,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Handle IssueEdgeSiteCredentials command
+///
+/// Emits:
+/// - NKeyGenerated, JwtClaimsCreated, JwtSigned (leafnode user)
+/// - NatsUserCreatedEvent
+/// - CertificateGeneratedEvent, CertificateSignedEvent (server certificate)
+/// - EdgeSiteCredentialsIssuedEvent (ties both to the Location)
+pub fn handle_issue_edge_site_credentials(
+    cmd: IssueEdgeSiteCredentials,
+) -> Result<EdgeSiteCredentialsIssued, String> {
+    if cmd.account_nkey.key_type != NKeyType::Account {
+        return Err("Leafnode users must be signed by an account key".to_string());
+    }
+    if cmd.leafnode_remotes.is_empty() {
+        return Err(format!("Edge site {} has no hub leafnode URL", cmd.location_name));
+    }
+    let Some(common_name) = cmd.server_names.first().cloned() else {
+        return Err(format!("Edge site {} has no server names", cmd.location_name));
+    };
+
+    let user_name = format!("leafnode-{}", SubjectPermissionProjection::subject_token(&cmd.location_name));
+    let permissions = cmd.permissions.clone().unwrap_or(Permissions {
+        pub_allow: None,
+        pub_deny: None,
+        sub_allow: None,
+        sub_deny: None,
+    });
+    let identity = NatsProjection::project_leafnode_user(
+        &cmd.account_nkey,
+        &user_name,
+        &permissions,
+        cmd.correlation_id,
+        cmd.causation_id,
+    )?;
+    let credential = identity
+        .credential
+        .ok_or_else(|| format!("No credentials projected for {}", user_name))?;
+    let user_id = identity.nkey.id;
+    let user_public_key = identity.nkey.public_key_string().to_string();
+
+    let mut events = identity.events;
+    events.push(DomainEvent::NatsUser(NatsUserEvents::NatsUserCreated(NatsUserCreatedEvent {
+        user_id,
+        account_id: cmd.account_id,
+        name: user_name,
+        public_key: user_public_key.clone(),
+        created_by: "cim-keys-edge-site".to_string(),
+        person_id: None,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    })));
+
+    let params = ServerCertParams {
+        common_name: common_name.clone(),
+        san_entries: cmd.server_names.clone(),
+        organization: cmd.organization.clone(),
+        organizational_unit: Some(cmd.location_name.clone()),
+        ..Default::default()
+    };
+    let (certificate, generated, signed) = generate_server_certificate(
+        &cmd.master_seed.derive_child(&format!("server-{}", common_name)),
+        params,
+        &cmd.intermediate_ca_cert_pem,
+        &cmd.intermediate_ca_key_pem,
+        cmd.intermediate_ca_id,
+        cmd.correlation_id,
+        Some(user_id),
+    )?;
+    let certificate_id = generated.cert_id;
+    events.push(DomainEvent::Certificate(CertificateEvents::CertificateGenerated(generated)));
+    events.push(DomainEvent::Certificate(CertificateEvents::CertificateSigned(signed)));
+
+    let account_public_key = cmd.account_nkey.public_key_string().to_string();
+    events.push(DomainEvent::NatsUser(NatsUserEvents::EdgeSiteCredentialsIssued(EdgeSiteCredentialsIssuedEvent {
+        user_id,
+        location_id: cmd.location_id,
+        location_name: cmd.location_name.clone(),
+        account_id: cmd.account_id,
+        account_public_key: account_public_key.clone(),
+        user_public_key: user_public_key.clone(),
+        certificate_id,
+        certificate_fingerprint: certificate.fingerprint.clone(),
+        server_names: cmd.server_names.clone(),
+        issued_at: Utc::now(),
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    })));
+
+    let bundle = EdgeSiteBundle {
+        location_id: cmd.location_id,
+        location_name: cmd.location_name,
+        account_name: cmd.account_name,
+        account_public_key,
+        user_public_key,
+        user_jwt: credential.jwt.token().to_string(),
+        user_seed: credential.seed.seed().to_string(),
+        certificate_pem: certificate.certificate_pem,
+        private_key_pem: certificate.private_key_pem,
+        ca_certificate_pem: cmd.intermediate_ca_cert_pem,
+        certificate_fingerprint: certificate.fingerprint,
+        server_names: cmd.server_names,
+        leafnode_remotes: cmd.leafnode_remotes,
+        gateway: cmd.gateway,
+    };
+
+    Ok(EdgeSiteCredentialsIssued {
+        user_nkey: identity.nkey,
+        bundle,
+        events,
+    })
+}
+
 // TODO: Future refactoring
 // - Add UpdateNatsUser command
 // - Add SetUserPermissions command
@@ -19,3 +183,99 @@ pub use super::nats_identity::{
 // - Add CreateServiceAccount command
 // - Add CreateAgent command
 // - Align with NatsUserEvents from events/nats_user.rs
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::age_encryption::{decrypt_with_passphrase, AgeProtection};
+    use crate::crypto::derive_master_seed;
+    use crate::crypto::x509::{generate_intermediate_ca, generate_root_ca, IntermediateCAParams, RootCAParams};
+    use crate::domain::nats::{NatsClaims, UserClaimData, CONNECTION_TYPE_LEAFNODE};
+    use crate::projection::{nats_edge, GatewayRemote, Projection};
+    use age::secrecy::SecretString;
+
+    #[test]
+    fn test_edge_site_bundle_contains_leafnode_identity_and_tls() {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_seed = master_seed.derive_child("root-ca");
+        let (root_ca, _) = generate_root_ca(&root_seed, RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let (intermediate_ca, _, _) = generate_intermediate_ca(
+            &root_seed.derive_child("intermediate-edge"),
+            IntermediateCAParams::default(),
+            &root_ca.certificate_pem,
+            &root_ca.private_key_pem,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+
+        let account_nkey = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let location_id = Uuid::now_v7();
+        let issued = handle_issue_edge_site_credentials(IssueEdgeSiteCredentials {
+            location_id,
+            location_name: "Austin Warehouse".to_string(),
+            account_id: Uuid::now_v7(),
+            account_name: "logistics".to_string(),
+            account_nkey: account_nkey.clone(),
+            permissions: None,
+            leafnode_remotes: vec!["tls://hub.cowboy.ai:7422".to_string()],
+            gateway: Some(EdgeGateway {
+                name: "austin".to_string(),
+                port: 7222,
+                remotes: vec![GatewayRemote {
+                    name: "hub".to_string(),
+                    urls: vec!["tls://hub.cowboy.ai:7222".to_string()],
+                }],
+            }),
+            server_names: vec!["nats.austin.cowboy.ai".to_string()],
+            organization: "Cowboy AI".to_string(),
+            master_seed,
+            intermediate_ca_cert_pem: intermediate_ca.certificate_pem.clone(),
+            intermediate_ca_key_pem: intermediate_ca.private_key_pem.clone(),
+            intermediate_ca_id: Uuid::now_v7(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        })
+        .unwrap();
+
+        let claims = NatsClaims::<UserClaimData>::decode(&issued.bundle.user_jwt).unwrap();
+        assert_eq!(claims.name, "leafnode-austin-warehouse");
+        assert_eq!(claims.iss, account_nkey.public_key_string());
+        assert_eq!(claims.nats.allowed_connection_types, [CONNECTION_TYPE_LEAFNODE]);
+        let Some(DomainEvent::NatsUser(NatsUserEvents::EdgeSiteCredentialsIssued(event))) = issued.events.last() else {
+            panic!("expected EdgeSiteCredentialsIssued last");
+        };
+        assert_eq!(event.location_id, location_id);
+        assert_eq!(event.certificate_fingerprint, issued.bundle.certificate_fingerprint);
+
+        let passphrase = SecretString::from("edge site passphrase".to_string());
+        let export = nats_edge()
+            .with_protection(AgeProtection::Passphrase(passphrase.clone()))
+            .project(issued.bundle.clone())
+            .unwrap();
+        let paths: Vec<String> = export.files.iter().map(|f| f.path.display().to_string()).collect();
+        assert_eq!(
+            paths,
+            [
+                "edge/austin-warehouse/site.json",
+                "edge/austin-warehouse/leafnode.conf",
+                "edge/austin-warehouse/leafnode.creds.age",
+                "edge/austin-warehouse/tls/server.pem",
+                "edge/austin-warehouse/tls/server-key.pem.age",
+                "edge/austin-warehouse/tls/ca.pem",
+            ]
+        );
+
+        let conf = &export.files[1].content;
+        assert!(conf.contains("urls: [\"tls://hub.cowboy.ai:7422\"]"));
+        assert!(conf.contains("credentials: \"/etc/nats/leafnode.creds\""));
+        assert!(conf.contains("{ name: \"hub\", urls: [\"tls://hub.cowboy.ai:7222\"] }"));
+
+        let creds = decrypt_with_passphrase(&export.files[2].content, &passphrase).unwrap();
+        let creds = String::from_utf8(creds).unwrap();
+        assert!(creds.contains(&issued.bundle.user_jwt));
+        assert!(creds.contains(&issued.bundle.user_seed));
+        assert!(!export.files[0].content.contains(&issued.bundle.user_seed));
+    }
+}
@@ -163,6 +163,130 @@ pub enum RelationshipType {
     AssignedTo,
 }
 
+// ============================================================================
+// Reversal Commands
+// ============================================================================
+
+/// Command to deactivate a person, revoking their access permanently
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeactivatePerson {
+    pub command_id: Uuid,
+    pub person_id: Uuid,
+    pub reason: String,
+    pub deactivated_by: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Command to suspend a person, withdrawing their access until reinstated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuspendPerson {
+    pub command_id: Uuid,
+    pub person_id: Uuid,
+    pub reason: String,
+    pub suspended_by: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Command to archive a deactivated person for retention
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivePerson {
+    pub command_id: Uuid,
+    pub person_id: Uuid,
+    pub reason: String,
+    pub archived_by: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Command to end a relationship between entities
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminateRelationship {
+    pub command_id: Uuid,
+    pub relationship_id: Uuid,
+    pub reason: String,
+    pub terminated_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Command to stop a policy from governing an entity
+///
+/// A binding is a `PolicyGovernsEntity` relationship, so unbinding
+/// terminates that relationship.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnbindPolicy {
+    pub command_id: Uuid,
+    pub relationship_id: Uuid,
+    pub policy_id: Uuid,
+    pub entity_id: Uuid,
+    pub unbound_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Command to make a policy govern an entity
+///
+/// The binding is a `PolicyGovernsEntity` relationship from the policy to
+/// the entity, identified by `relationship_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindPolicy {
+    pub command_id: Uuid,
+    pub relationship_id: Uuid,
+    pub policy_id: Uuid,
+    pub entity_id: Uuid,
+    pub entity_type: crate::domain::PolicyEntityType,
+    pub bound_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Command to assign a role to a person
+///
+/// The person gains the claims of the role's required policies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssignRole {
+    pub command_id: Uuid,
+    pub person_id: Uuid,
+    pub role_id: Uuid,
+    pub role_name: String,
+    /// Policies the role requires (`Role::required_policies`)
+    pub required_policies: Vec<Uuid>,
+    pub assigned_by: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Command to record a new version of a policy
+///
+/// The current version stays in force until `effective_from`; evaluations
+/// at earlier instants keep using it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupersedePolicy {
+    pub command_id: Uuid,
+    pub policy_id: Uuid,
+    /// Version being superseded, to detect concurrent changes
+    pub expected_version: u32,
+    pub claims: Vec<crate::domain::PolicyClaim>,
+    pub denied_claims: Vec<crate::domain::PolicyClaim>,
+    pub conditions: Vec<crate::domain::PolicyCondition>,
+    pub priority: i32,
+    pub effective_from: DateTime<Utc>,
+    pub effective_until: Option<DateTime<Utc>>,
+    pub superseded_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
 // ============================================================================
 // Command Handlers
 // ============================================================================
@@ -339,3 +463,323 @@ pub async fn handle_establish_relationship(
 
     Ok(vec![event])
 }
+
+/// Handle DeactivatePerson command
+pub async fn handle_deactivate_person(
+    cmd: DeactivatePerson,
+) -> Result<Vec<DomainEvent>, crate::aggregate::KeyManagementError> {
+    // Validate command
+    if cmd.reason.is_empty() {
+        return Err(crate::aggregate::KeyManagementError::InvalidCommand(
+            "Deactivation reason is required".to_string(),
+        ));
+    }
+
+    // Emit PersonDeactivated event
+    let event = DomainEvent::Person(crate::events::PersonEvents::PersonDeactivated(
+        crate::events::person::PersonDeactivatedEvent {
+            person_id: cmd.person_id,
+            reason: cmd.reason,
+            deactivated_at: cmd.timestamp,
+            deactivated_by: cmd.deactivated_by,
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        }
+    ));
+
+    Ok(vec![event])
+}
+
+/// Handle SuspendPerson command
+pub async fn handle_suspend_person(
+    cmd: SuspendPerson,
+) -> Result<Vec<DomainEvent>, crate::aggregate::KeyManagementError> {
+    if cmd.reason.is_empty() {
+        return Err(crate::aggregate::KeyManagementError::InvalidCommand(
+            "Suspension reason is required".to_string(),
+        ));
+    }
+
+    let event = DomainEvent::Person(crate::events::PersonEvents::PersonSuspended(
+        crate::events::person::PersonSuspendedEvent {
+            person_id: cmd.person_id,
+            reason: cmd.reason,
+            suspended_at: cmd.timestamp,
+            suspended_by: cmd.suspended_by,
+            expected_return: None,
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        }
+    ));
+
+    Ok(vec![event])
+}
+
+/// Handle ArchivePerson command
+pub async fn handle_archive_person(
+    cmd: ArchivePerson,
+) -> Result<Vec<DomainEvent>, crate::aggregate::KeyManagementError> {
+    if cmd.reason.is_empty() {
+        return Err(crate::aggregate::KeyManagementError::InvalidCommand(
+            "Archival reason is required".to_string(),
+        ));
+    }
+
+    let event = DomainEvent::Person(crate::events::PersonEvents::PersonArchived(
+        crate::events::person::PersonArchivedEvent {
+            person_id: cmd.person_id,
+            reason: cmd.reason,
+            archived_at: cmd.timestamp,
+            archived_by: cmd.archived_by,
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        }
+    ));
+
+    Ok(vec![event])
+}
+
+/// Handle TerminateRelationship command
+pub async fn handle_terminate_relationship(
+    cmd: TerminateRelationship,
+) -> Result<Vec<DomainEvent>, crate::aggregate::KeyManagementError> {
+    // Validate command
+    if cmd.reason.is_empty() {
+        return Err(crate::aggregate::KeyManagementError::InvalidCommand(
+            "Termination reason is required".to_string(),
+        ));
+    }
+
+    // Emit RelationshipTerminated event
+    let event = DomainEvent::Relationship(crate::events::RelationshipEvents::RelationshipTerminated(crate::events::relationship::RelationshipTerminatedEvent {
+        relationship_id: cmd.relationship_id,
+        reason: cmd.reason,
+        terminated_at: cmd.timestamp,
+        terminated_by: cmd.terminated_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: Some(cmd.command_id),
+    }));
+
+    Ok(vec![event])
+}
+
+/// Handle BindPolicy command
+///
+/// `held_claims` are the claims the entity already holds through its
+/// bindings and roles. A binding that would break a separation-of-duties
+/// rule emits `SeparationOfDutiesViolated` instead of the binding.
+pub async fn handle_bind_policy(
+    cmd: BindPolicy,
+    policy: &crate::domain::Policy,
+    held_claims: &[crate::domain::PolicyClaim],
+    rules: &[crate::domain::SeparationOfDutiesRule],
+) -> Result<Vec<DomainEvent>, crate::aggregate::KeyManagementError> {
+    if policy.id.as_uuid() != cmd.policy_id {
+        return Err(crate::aggregate::KeyManagementError::InvalidCommand(format!(
+            "Policy {} is not {}",
+            policy.id.as_uuid(),
+            cmd.policy_id
+        )));
+    }
+
+    let violations = separation_of_duties_violations(
+        rules,
+        held_claims,
+        &policy.claims,
+        SeparationContext {
+            command_id: cmd.command_id,
+            entity_id: cmd.entity_id,
+            entity_type: cmd.entity_type,
+            assignment: format!("policy {}", policy.name),
+            detected_at: cmd.timestamp,
+            correlation_id: cmd.correlation_id,
+        },
+    );
+    if !violations.is_empty() {
+        return Ok(violations);
+    }
+
+    let event = DomainEvent::Relationship(crate::events::RelationshipEvents::RelationshipEstablished(
+        crate::events::relationship::RelationshipEstablishedEvent {
+            relationship_id: cmd.relationship_id,
+            from_id: cmd.policy_id,
+            to_id: cmd.entity_id,
+            relationship_type: RelationshipType::PolicyGovernsEntity,
+            established_at: cmd.timestamp,
+            established_by: cmd.bound_by,
+            valid_from: cmd.timestamp,
+            valid_until: None,
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        },
+    ));
+
+    Ok(vec![event])
+}
+
+/// Handle AssignRole command
+///
+/// `granted_claims` are the claims of the role's required policies and
+/// `held_claims` those the person already holds. An assignment that would
+/// break a separation-of-duties rule emits `SeparationOfDutiesViolated`
+/// instead of `RoleAssigned`.
+pub async fn handle_assign_role(
+    cmd: AssignRole,
+    granted_claims: &[crate::domain::PolicyClaim],
+    held_claims: &[crate::domain::PolicyClaim],
+    rules: &[crate::domain::SeparationOfDutiesRule],
+) -> Result<Vec<DomainEvent>, crate::aggregate::KeyManagementError> {
+    if cmd.role_name.is_empty() {
+        return Err(crate::aggregate::KeyManagementError::InvalidCommand(
+            "Role name cannot be empty".to_string(),
+        ));
+    }
+
+    let violations = separation_of_duties_violations(
+        rules,
+        held_claims,
+        granted_claims,
+        SeparationContext {
+            command_id: cmd.command_id,
+            entity_id: cmd.person_id,
+            entity_type: crate::domain::PolicyEntityType::Person,
+            assignment: format!("role {}", cmd.role_name),
+            detected_at: cmd.timestamp,
+            correlation_id: cmd.correlation_id,
+        },
+    );
+    if !violations.is_empty() {
+        return Ok(violations);
+    }
+
+    let event = DomainEvent::Person(crate::events::PersonEvents::RoleAssigned(
+        crate::events::person::RoleAssignedEvent {
+            person_id: cmd.person_id,
+            role_id: cmd.role_id,
+            role_name: cmd.role_name,
+            required_policies: cmd.required_policies,
+            assigned_at: cmd.timestamp,
+            assigned_by: cmd.assigned_by,
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        },
+    ));
+
+    Ok(vec![event])
+}
+
+/// Who an assignment is for, to describe separation-of-duties violations
+struct SeparationContext {
+    command_id: Uuid,
+    entity_id: Uuid,
+    entity_type: crate::domain::PolicyEntityType,
+    assignment: String,
+    detected_at: DateTime<Utc>,
+    correlation_id: Uuid,
+}
+
+/// One `SeparationOfDutiesViolated` per rule the assignment would break
+fn separation_of_duties_violations(
+    rules: &[crate::domain::SeparationOfDutiesRule],
+    held_claims: &[crate::domain::PolicyClaim],
+    added_claims: &[crate::domain::PolicyClaim],
+    context: SeparationContext,
+) -> Vec<DomainEvent> {
+    use crate::events::authorization::{AuthorizationEvents, SeparationOfDutiesViolatedEvent};
+
+    crate::domain::separation_violations(rules, held_claims, added_claims)
+        .into_iter()
+        .map(|rule| {
+            DomainEvent::Authorization(AuthorizationEvents::SeparationOfDutiesViolated(
+                SeparationOfDutiesViolatedEvent {
+                    command_id: context.command_id,
+                    entity_id: context.entity_id,
+                    entity_type: context.entity_type,
+                    rule_id: rule.id,
+                    rule_name: rule.name.clone(),
+                    conflicting_claims: vec![rule.claim_a.clone(), rule.claim_b.clone()],
+                    assignment: context.assignment.clone(),
+                    detected_at: context.detected_at,
+                    correlation_id: context.correlation_id,
+                    causation_id: Some(context.command_id),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Handle SupersedePolicy command
+///
+/// `current` is the latest recorded version of the policy. The successor
+/// must start after it did.
+pub async fn handle_supersede_policy(
+    cmd: SupersedePolicy,
+    current: &crate::domain::Policy,
+) -> Result<Vec<DomainEvent>, crate::aggregate::KeyManagementError> {
+    use crate::aggregate::KeyManagementError;
+
+    if current.id.as_uuid() != cmd.policy_id {
+        return Err(KeyManagementError::InvalidCommand(format!(
+            "Policy {} is not {}",
+            current.id.as_uuid(),
+            cmd.policy_id
+        )));
+    }
+    if current.version != cmd.expected_version {
+        return Err(KeyManagementError::InvalidCommand(format!(
+            "Policy {} is at version {}, not {}",
+            cmd.policy_id, current.version, cmd.expected_version
+        )));
+    }
+    if current.effective_from.is_some_and(|from| cmd.effective_from <= from) {
+        return Err(KeyManagementError::InvalidCommand(format!(
+            "Version {} of policy {} must take effect after version {}",
+            current.version + 1,
+            cmd.policy_id,
+            current.version
+        )));
+    }
+    if cmd.effective_until.is_some_and(|until| until <= cmd.effective_from) {
+        return Err(KeyManagementError::InvalidCommand(
+            "Policy version must end after it takes effect".to_string(),
+        ));
+    }
+
+    let mut successor = current.next_version(cmd.effective_from);
+    successor.claims = cmd.claims;
+    successor.denied_claims = cmd.denied_claims;
+    successor.conditions = cmd.conditions;
+    successor.priority = cmd.priority;
+    successor.effective_until = cmd.effective_until;
+
+    let event = DomainEvent::Organization(crate::events::OrganizationEvents::PolicySuperseded(
+        crate::events::organization::PolicySupersededEvent {
+            policy_id: cmd.policy_id,
+            superseded_version: current.version,
+            successor,
+            superseded_at: cmd.timestamp,
+            superseded_by: cmd.superseded_by,
+            correlation_id: cmd.correlation_id,
+            causation_id: Some(cmd.command_id),
+        },
+    ));
+
+    Ok(vec![event])
+}
+
+/// Handle UnbindPolicy command
+pub async fn handle_unbind_policy(
+    cmd: UnbindPolicy,
+) -> Result<Vec<DomainEvent>, crate::aggregate::KeyManagementError> {
+    // Emit RelationshipTerminated event for the binding
+    let event = DomainEvent::Relationship(crate::events::RelationshipEvents::RelationshipTerminated(crate::events::relationship::RelationshipTerminatedEvent {
+        relationship_id: cmd.relationship_id,
+        reason: format!("Policy {} unbound from {}", cmd.policy_id, cmd.entity_id),
+        terminated_at: cmd.timestamp,
+        terminated_by: cmd.unbound_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: Some(cmd.command_id),
+    }));
+
+    Ok(vec![event])
+}
@@ -5,13 +5,18 @@
 
 // Re-export person-related commands from organization module
 pub use super::organization::{
+    ArchivePerson,
     CreatePerson,
+    DeactivatePerson,
+    SuspendPerson,
+    handle_archive_person,
     handle_create_person,
+    handle_deactivate_person,
+    handle_suspend_person,
 };
 
 // TODO: Future refactoring
 // - Add UpdatePerson command
-// - Add DeactivatePerson command
 // - Add AssignRole, RemoveRole commands
 // - Add SSH/GPG key generation for person
 // - Align with PersonEvents from events/person.rs
@@ -7,9 +7,19 @@
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::domain::{KeyContext, KeyOwnership, Organization};
+use crate::crypto::crl::{generate_crl, CrlEntry};
+use crate::crypto::seed_derivation::MasterSeed;
+use crate::crypto::x509::{
+    create_intermediate_ca, issue_certificate, IntermediateCAParams, LeafCertificateParams,
+    X509Certificate,
+};
+use crate::domain::{
+    KeyContext, KeyOwnership, Organization, OrganizationUnit, OrganizationalPKI, PolicyClaim,
+    PolicyConstraint, PolicyEntityType, PolicyEvaluation,
+};
 use crate::domain_projections::CertificateRequestProjection;
 use crate::events::DomainEvent;
+use crate::ports::rng::{CryptoRngPort, OsCryptoRng};
 use crate::types::{KeyAlgorithm, KeyPurpose};
 use crate::value_objects::{
     ActorId, Certificate, CertificateSubject, PublicKey, Validity,
@@ -28,10 +38,20 @@ pub struct GenerateKeyPair {
     pub purpose: crate::value_objects::AuthKeyPurpose,
     pub algorithm: Option<KeyAlgorithm>,
     pub owner_context: KeyContext,
+    /// Days until the key expires (None = no expiration)
+    pub expires_in_days: Option<u32>,
     pub correlation_id: Uuid,
     pub causation_id: Option<Uuid>,
 }
 
+impl GenerateKeyPair {
+    /// Expire the key after the lifetime configured for PGP keys
+    pub fn with_pgp_expiration(mut self, pgp: &crate::domain::bootstrap::PgpConfig) -> Self {
+        self.expires_in_days = pgp.expiration_days();
+        self
+    }
+}
+
 /// Result of generating key pair
 #[derive(Debug, Clone)]
 pub struct KeyPairGenerated {
@@ -51,6 +71,14 @@ pub struct KeyPairGenerated {
 ///
 /// User Story: US-012, US-013
 pub fn handle_generate_key_pair(cmd: GenerateKeyPair) -> Result<KeyPairGenerated, String> {
+    handle_generate_key_pair_with_rng(cmd, &OsCryptoRng)
+}
+
+/// [`handle_generate_key_pair`] drawing the key from `rng`
+pub fn handle_generate_key_pair_with_rng(
+    cmd: GenerateKeyPair,
+    rng: &dyn CryptoRngPort,
+) -> Result<KeyPairGenerated, String> {
     let mut events = Vec::new();
 
     // Step 1: Determine algorithm (from command or purpose recommendation)
@@ -70,7 +98,7 @@ pub fn handle_generate_key_pair(cmd: GenerateKeyPair) -> Result<KeyPairGenerated
     // Generate Ed25519 key pair
     use ed25519_dalek::SigningKey;
 
-    let signing_key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+    let signing_key = SigningKey::from_bytes(&rng.array());
     let verifying_key = signing_key.verifying_key();
 
     // Convert to bytes
@@ -83,6 +111,7 @@ pub fn handle_generate_key_pair(cmd: GenerateKeyPair) -> Result<KeyPairGenerated
     };
 
     // Step 3: Emit key generated event
+    let generated_at = Utc::now();
     let key_event = crate::events::key::KeyGeneratedEvent {
         key_id,
         algorithm: algorithm.clone(),
@@ -92,7 +121,7 @@ pub fn handle_generate_key_pair(cmd: GenerateKeyPair) -> Result<KeyPairGenerated
             crate::value_objects::AuthKeyPurpose::GpgEncryption => KeyPurpose::Encryption,
             _ => KeyPurpose::Authentication,
         },
-        generated_at: Utc::now(),
+        generated_at,
         generated_by: ActorId::system("pki-keygen"),
         hardware_backed: false,
         metadata: crate::types::KeyMetadata {
@@ -103,8 +132,13 @@ pub fn handle_generate_key_pair(cmd: GenerateKeyPair) -> Result<KeyPairGenerated
             jwt_kid: None,
             jwt_alg: None,
             jwt_use: None,
+            hardware_serial: None,
+            tpm: None,
         },
         ownership: Some(cmd.owner_context.actor.clone()),
+        expires_at: cmd
+            .expires_in_days
+            .map(|days| generated_at + chrono::Duration::days(i64::from(days))),
         correlation_id: cmd.correlation_id,
         causation_id: cmd.causation_id,
     };
@@ -125,6 +159,7 @@ pub fn handle_generate_key_pair(cmd: GenerateKeyPair) -> Result<KeyPairGenerated
 /// Command to generate root CA certificate
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct GenerateRootCA {
+    pub command_id: Uuid,
     pub organization: Organization,
     pub validity_years: u32,
     pub algorithm: KeyAlgorithm,
@@ -185,6 +220,7 @@ pub fn handle_generate_root_ca(cmd: GenerateRootCA) -> Result<RootCAGenerated, S
                 },
             ],
         },
+        expires_in_days: None,
         correlation_id: cmd.correlation_id,
         causation_id: Some(command_id), // A4: Causation from parent command
     })?;
@@ -295,6 +331,7 @@ pub fn handle_generate_root_ca(cmd: GenerateRootCA) -> Result<RootCAGenerated, S
         validity,
         basic_constraints: BasicConstraints::ca(),
         issuer: None, // Self-signed root CA
+        signature_algorithm: crate::crypto::x509::signature_algorithm_of(rcgen_key_pair.algorithm()),
         correlation_id: cmd.correlation_id,
         causation_id: Some(key_pair.key_id), // Certificate caused by key generation
     };
@@ -492,6 +529,7 @@ pub fn handle_generate_certificate(cmd: GenerateCertificate) -> Result<Certifica
         validity,
         basic_constraints: BasicConstraints::end_entity(),
         issuer: Some(cmd.ca_id), // The CA that issued this certificate
+        signature_algorithm: crate::crypto::x509::signature_algorithm_of(rcgen_key_pair.algorithm()),
         correlation_id: cmd.correlation_id,
         causation_id: cmd.causation_id,
     };
@@ -514,6 +552,315 @@ pub fn handle_generate_certificate(cmd: GenerateCertificate) -> Result<Certifica
     })
 }
 
+// ============================================================================
+// Command: Create Intermediate CA (US-017)
+// ============================================================================
+
+/// Command to create an intermediate CA for an organization unit
+#[derive(Debug, Clone)]
+pub struct CreateIntermediateCA {
+    pub organization: Organization,
+    pub unit: OrganizationUnit,
+    pub root_ca_id: Uuid,
+    /// Root CA certificate (PEM); its subject becomes the issuer
+    pub root_ca_certificate_pem: String,
+    /// Country code of the subject (ISO 3166 alpha-2, e.g. "US")
+    pub country: Option<String>,
+    pub validity_years: u32,
+    /// How many CA levels may exist below the intermediate (0 = leaf only)
+    pub pathlen: u8,
+    /// Policy constraints applied to the issued intermediate
+    pub constraints: Vec<PolicyConstraint>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// Result of creating an intermediate CA
+#[derive(Debug, Clone)]
+pub struct IntermediateCACreated {
+    pub cert_id: Uuid,
+    pub unit_id: Uuid,
+    pub certificate: X509Certificate,
+    /// Intermediate followed by root (PEM)
+    pub chain_pem: String,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Handle CreateIntermediateCA command
+///
+/// Signs the unit's intermediate CA with the root key and records the
+/// unit → certificate mapping in `pki.intermediate_cas`. The root key may
+/// live on a YubiKey (see `ExternalCaSigner`); the intermediate key is
+/// derived from `master_seed` under `intermediate-{unit id}`, so it can be
+/// rebuilt from the master seed and renaming the unit does not change it.
+///
+/// Emits:
+/// - CertificateGeneratedEvent
+/// - CertificateSignedEvent
+///
+/// User Story: US-017
+pub fn handle_create_intermediate_ca<S: rcgen::SigningKey>(
+    cmd: CreateIntermediateCA,
+    master_seed: &MasterSeed,
+    root_signer: S,
+    pki: &mut OrganizationalPKI,
+) -> Result<IntermediateCACreated, String> {
+    if pki.root_ca_org_id != cmd.organization.id.as_uuid() {
+        return Err(format!(
+            "PKI root belongs to organization {}, not {}",
+            pki.root_ca_org_id,
+            cmd.organization.id.as_uuid()
+        ));
+    }
+    if !cmd.organization.units.iter().any(|unit| unit.id == cmd.unit.id) {
+        return Err(format!(
+            "Unit '{}' is not part of organization '{}'",
+            cmd.unit.name, cmd.organization.name
+        ));
+    }
+
+    let seed_path = format!("intermediate-{}", cmd.unit.id.as_uuid());
+    let intermediate_seed = master_seed.derive_child(&seed_path);
+    let params = IntermediateCAParams {
+        organization: cmd.organization.name.clone(),
+        organizational_unit: cmd.unit.name.clone(),
+        common_name: format!("{} {} Intermediate CA", cmd.organization.name, cmd.unit.name),
+        country: cmd.country.clone(),
+        validity_years: cmd.validity_years,
+        pathlen: cmd.pathlen,
+        ..Default::default()
+    };
+
+    let mut intermediate = create_intermediate_ca(
+        &intermediate_seed,
+        params,
+        &cmd.root_ca_certificate_pem,
+        root_signer,
+        &cmd.constraints,
+        cmd.root_ca_id,
+        cmd.correlation_id,
+        cmd.causation_id,
+    )?;
+
+    intermediate.certificate.seed_path = seed_path;
+    let cert_id = intermediate.generation_event.cert_id;
+    let unit_id = cmd.unit.id.as_uuid();
+    pki.register_intermediate_ca(unit_id, cert_id);
+
+    let events = vec![
+        DomainEvent::Certificate(crate::events::CertificateEvents::CertificateGenerated(intermediate.generation_event)),
+        DomainEvent::Certificate(crate::events::CertificateEvents::CertificateSigned(intermediate.signing_event)),
+    ];
+
+    Ok(IntermediateCACreated {
+        cert_id,
+        unit_id,
+        certificate: intermediate.certificate,
+        chain_pem: intermediate.chain_pem,
+        events,
+    })
+}
+
+// ============================================================================
+// Command: Issue Certificate from CSR (US-017)
+// ============================================================================
+
+/// Command to issue a leaf certificate for a submitted CSR
+#[derive(Debug, Clone)]
+pub struct IssueCertificate {
+    /// PKCS#10 request (PEM) submitted by the admin
+    pub csr_pem: String,
+    /// Key the CSR was created for
+    pub key_id: Uuid,
+    pub purpose: KeyPurpose,
+    pub validity_days: u32,
+    pub issuer_ca_id: Uuid,
+    /// Issuing CA certificate, optionally followed by its chain (PEM)
+    pub issuer_chain_pem: String,
+    /// Owner of the issuing CA key
+    pub issuer_key_owner: KeyOwnership,
+    /// Policy constraints of the issuing CA
+    pub constraints: Vec<PolicyConstraint>,
+    pub requested_by: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// Result of issuing a certificate
+#[derive(Debug, Clone)]
+pub struct CertificateIssued {
+    pub cert_id: Uuid,
+    pub certificate_pem: String,
+    /// Leaf followed by the issuer chain (PEM)
+    pub chain_pem: String,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Handle IssueCertificate command
+///
+/// Signs the CSR with the issuing CA's key. Only a key whose owner holds
+/// `PolicyClaim::CanSignCertificates` may act as issuer; `owner_claims` is
+/// the policy evaluation for `cmd.issuer_key_owner`. Validity, EKUs and SANs
+/// are checked against `cmd.constraints` (see `crypto::x509::issue_certificate`).
+///
+/// Emits:
+/// - CertificateGeneratedEvent
+/// - CertificateSignedEvent
+///
+/// User Story: US-017
+pub fn handle_issue_certificate<S: rcgen::SigningKey>(
+    cmd: IssueCertificate,
+    issuer_signer: S,
+    owner_claims: &PolicyEvaluation,
+) -> Result<CertificateIssued, String> {
+    if owner_claims.entity_type != PolicyEntityType::Person
+        || owner_claims.entity_id != cmd.issuer_key_owner.person_id
+    {
+        return Err(format!(
+            "Policy evaluation is for {:?} {}, not the issuer key owner {}",
+            owner_claims.entity_type, owner_claims.entity_id, cmd.issuer_key_owner.person_id
+        ));
+    }
+    if !owner_claims.granted_claims.contains(&PolicyClaim::CanSignCertificates) {
+        return Err(format!(
+            "Issuer key owner {} does not hold CanSignCertificates",
+            cmd.issuer_key_owner.person_id
+        ));
+    }
+
+    let issued = issue_certificate(
+        &cmd.csr_pem,
+        LeafCertificateParams {
+            purpose: cmd.purpose,
+            validity_days: cmd.validity_days,
+            key_id: cmd.key_id,
+        },
+        &cmd.issuer_chain_pem,
+        issuer_signer,
+        &cmd.constraints,
+        cmd.issuer_ca_id,
+        cmd.correlation_id,
+        cmd.causation_id,
+    )?;
+
+    let cert_id = issued.generation_event.cert_id;
+    let events = vec![
+        DomainEvent::Certificate(crate::events::CertificateEvents::CertificateGenerated(issued.generation_event)),
+        DomainEvent::Certificate(crate::events::CertificateEvents::CertificateSigned(issued.signing_event)),
+    ];
+
+    Ok(CertificateIssued {
+        cert_id,
+        certificate_pem: issued.certificate_pem,
+        chain_pem: issued.chain_pem,
+        events,
+    })
+}
+
+// ============================================================================
+// Command: Revoke Key
+// ============================================================================
+
+/// Command to revoke a key
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RevokeKey {
+    pub command_id: Uuid,
+    pub key_id: Uuid,
+    pub reason: crate::types::RevocationReason,
+    pub revoked_by: Uuid,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: chrono::DateTime<Utc>,
+}
+
+/// Handle RevokeKey command
+///
+/// Emits:
+/// - KeyRevokedEvent
+pub fn handle_revoke_key(cmd: RevokeKey) -> Result<Vec<DomainEvent>, String> {
+    let event = crate::events::key::KeyRevokedEvent {
+        key_id: cmd.key_id,
+        reason: cmd.reason,
+        revoked_at: cmd.timestamp,
+        revoked_by: ActorId::person(cmd.revoked_by),
+        correlation_id: cmd.correlation_id,
+        causation_id: Some(cmd.command_id),
+    };
+
+    Ok(vec![DomainEvent::Key(crate::events::KeyEvents::KeyRevoked(event))])
+}
+
+// ============================================================================
+// Command: Publish CRL
+// ============================================================================
+
+/// Command to publish a certificate revocation list for an issuing CA
+///
+/// `revocations` and `crl_number` normally come from the manifest
+/// (`KeyManifest::revocations_for_issuer` / `next_crl_number`).
+#[derive(Debug, Clone)]
+pub struct PublishCrl {
+    pub issuer_ca_id: Uuid,
+    /// Issuing CA certificate (PEM)
+    pub issuer_cert_pem: String,
+    pub revocations: Vec<CrlEntry>,
+    pub crl_number: u64,
+    /// Days until relying parties should expect the next CRL
+    pub next_update_days: u32,
+    pub published_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// Result of publishing a CRL
+#[derive(Debug, Clone)]
+pub struct CrlPublished {
+    pub crl_id: Uuid,
+    pub crl_pem: String,
+    pub events: Vec<DomainEvent>,
+}
+
+/// Handle PublishCrl command
+///
+/// Signs a CRL listing `cmd.revocations` with the issuing CA's key. The
+/// issuer certificate must be a CA with the cRLSign key usage.
+///
+/// Emits:
+/// - CrlPublishedEvent
+pub fn handle_publish_crl<S: rcgen::SigningKey>(
+    cmd: PublishCrl,
+    issuer_signer: S,
+) -> Result<CrlPublished, String> {
+    let crl = generate_crl(
+        &cmd.issuer_cert_pem,
+        issuer_signer,
+        &cmd.revocations,
+        cmd.crl_number,
+        Utc::now(),
+        cmd.next_update_days,
+    )?;
+
+    let crl_id = Uuid::now_v7();
+    let event = crate::events::CrlPublishedEvent {
+        crl_id,
+        issuer_ca_id: cmd.issuer_ca_id,
+        crl_number: crl.crl_number,
+        this_update: crl.this_update,
+        next_update: crl.next_update,
+        revoked_cert_ids: crl.revoked_cert_ids,
+        crl_pem: crl.crl_pem.clone(),
+        published_by: cmd.published_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    };
+
+    Ok(CrlPublished {
+        crl_id,
+        crl_pem: crl.crl_pem,
+        events: vec![DomainEvent::Certificate(crate::events::CertificateEvents::CrlPublished(event))],
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -537,6 +884,7 @@ mod tests {
                 nats_identity: None,
                 audit_requirements: vec![],
             },
+            expires_in_days: None,
             correlation_id: Uuid::now_v7(),
             causation_id: Some(test_command_id), // A4: Self-reference for root command
         };
@@ -560,6 +908,7 @@ mod tests {
         };
 
         let cmd = GenerateRootCA {
+            command_id: Uuid::now_v7(),
             organization: org.clone(),
             validity_years: 10,
             algorithm: KeyAlgorithm::Ed25519, // Use Ed25519 instead of EcdsaP384
@@ -575,4 +924,171 @@ mod tests {
         );
         assert!(result.certificate.subject.common_name.contains("Root CA"));
     }
+
+    #[test]
+    fn test_create_intermediate_ca_maps_unit_to_certificate() {
+        use crate::crypto::seed_derivation::derive_master_seed;
+        use crate::crypto::x509::{generate_root_ca, RootCAParams};
+        use crate::domain::OrganizationUnitType;
+
+        let engineering = OrganizationUnit::new("Engineering", OrganizationUnitType::Department);
+        let org = Organization {
+            id: BootstrapOrgId::new(),
+            name: "Test Org".to_string(),
+            display_name: "Test Organization".to_string(),
+            description: None,
+            parent_id: None,
+            units: vec![engineering.clone()],
+            metadata: Default::default(),
+        };
+        let mut pki = OrganizationalPKI {
+            root_ca_org_id: org.id.as_uuid(),
+            intermediate_cas: vec![],
+            policy_cas: vec![],
+            cross_certifications: vec![],
+        };
+
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let (root_ca, _) = generate_root_ca(&master_seed.derive_child("root-ca"), RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = rcgen::KeyPair::from_pem(&root_ca.private_key_pem).unwrap();
+
+        let cmd = CreateIntermediateCA {
+            organization: org,
+            unit: engineering.clone(),
+            root_ca_id: Uuid::now_v7(),
+            root_ca_certificate_pem: root_ca.certificate_pem.clone(),
+            country: Some("NL".to_string()),
+            validity_years: 3,
+            pathlen: 0,
+            constraints: vec![PolicyConstraint::MaxPathLength(0)],
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        };
+
+        let first = handle_create_intermediate_ca(cmd.clone(), &master_seed, &root_key, &mut pki).unwrap();
+        assert_eq!(first.events.len(), 2);
+        assert_eq!(first.certificate.seed_path, format!("intermediate-{}", engineering.id.as_uuid()));
+        let (_, parsed) = x509_parser::pem::parse_x509_pem(first.certificate.certificate_pem.as_bytes()).unwrap();
+        let subject = parsed.parse_x509().unwrap().subject().to_string();
+        assert!(subject.contains("C=NL"), "{}", subject);
+        assert_eq!(pki.intermediate_ca_for(engineering.id.as_uuid()), Some(first.cert_id));
+
+        // Re-issuing for the same unit replaces the mapping and keeps the key
+        let second = handle_create_intermediate_ca(cmd.clone(), &master_seed, &root_key, &mut pki).unwrap();
+        assert_eq!(pki.intermediate_cas, vec![(engineering.id.as_uuid(), second.cert_id)]);
+        let spki = |pem: &str| {
+            let (_, pem) = x509_parser::pem::parse_x509_pem(pem.as_bytes()).unwrap();
+            pem.parse_x509().unwrap().public_key().raw.to_vec()
+        };
+        assert_eq!(spki(&first.certificate.certificate_pem), spki(&second.certificate.certificate_pem));
+        assert_eq!(first.certificate.public_key_bytes, second.certificate.public_key_bytes);
+
+        // Policy violations leave the mapping untouched
+        let too_deep = CreateIntermediateCA { pathlen: 1, ..cmd };
+        assert!(handle_create_intermediate_ca(too_deep, &master_seed, &root_key, &mut pki).is_err());
+        assert_eq!(pki.intermediate_ca_for(engineering.id.as_uuid()), Some(second.cert_id));
+    }
+
+    #[test]
+    fn test_issue_certificate_requires_can_sign_certificates() {
+        use crate::crypto::seed_derivation::derive_master_seed;
+        use crate::crypto::x509::{generate_intermediate_ca, generate_root_ca, RootCAParams};
+
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_seed = master_seed.derive_child("root-ca");
+        let (root_ca, _) = generate_root_ca(&root_seed, RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let (intermediate, _, _) = generate_intermediate_ca(
+            &root_seed.derive_child("intermediate-ops"),
+            IntermediateCAParams::default(),
+            &root_ca.certificate_pem,
+            &root_ca.private_key_pem,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        ).unwrap();
+        let intermediate_key = rcgen::KeyPair::from_pem(&intermediate.private_key_pem).unwrap();
+
+        let csr_key = rcgen::KeyPair::generate().unwrap();
+        let mut csr_params = rcgen::CertificateParams::new(vec!["api.example.com".to_string()]).unwrap();
+        csr_params.distinguished_name.push(rcgen::DnType::CommonName, "api.example.com");
+        let csr_pem = csr_params.serialize_request(&csr_key).unwrap().pem().unwrap();
+
+        let admin = Uuid::now_v7();
+        let cmd = IssueCertificate {
+            csr_pem,
+            key_id: Uuid::now_v7(),
+            purpose: KeyPurpose::Authentication,
+            validity_days: 90,
+            issuer_ca_id: Uuid::now_v7(),
+            issuer_chain_pem: format!("{}{}", intermediate.certificate_pem, root_ca.certificate_pem),
+            issuer_key_owner: KeyOwnership {
+                person_id: admin,
+                organization_id: Uuid::now_v7(),
+                role: crate::domain::KeyOwnerRole::SecurityAdmin,
+                delegations: vec![],
+            },
+            constraints: vec![],
+            requested_by: admin,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        };
+
+        let evaluation = |claims: Vec<PolicyClaim>| PolicyEvaluation {
+            entity_id: admin,
+            entity_type: PolicyEntityType::Person,
+            active_policies: vec![],
+            policy_versions: vec![],
+            inactive_policies: vec![],
+            granted_claims: claims,
+            denied_claims: vec![],
+            evaluated_at: Utc::now(),
+        };
+
+        let denied = handle_issue_certificate(cmd.clone(), &intermediate_key, &evaluation(vec![PolicyClaim::CanSignCode]));
+        assert!(denied.unwrap_err().contains("CanSignCertificates"));
+
+        let issued = handle_issue_certificate(cmd, &intermediate_key, &evaluation(vec![PolicyClaim::CanSignCertificates])).unwrap();
+        assert_eq!(issued.events.len(), 2);
+        assert_eq!(issued.chain_pem.matches("BEGIN CERTIFICATE").count(), 3);
+    }
+
+    #[test]
+    fn test_publish_crl_emits_crl_published() {
+        use crate::crypto::seed_derivation::derive_master_seed;
+        use crate::crypto::x509::{generate_root_ca, RootCAParams};
+        use crate::state_machines::certificate::RevocationReason;
+
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let (root_ca, _) = generate_root_ca(&master_seed.derive_child("root-ca"), RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = rcgen::KeyPair::from_pem(&root_ca.private_key_pem).unwrap();
+
+        let root_ca_id = Uuid::now_v7();
+        let revoked = Uuid::now_v7();
+        let cmd = PublishCrl {
+            issuer_ca_id: root_ca_id,
+            issuer_cert_pem: root_ca.certificate_pem,
+            revocations: vec![CrlEntry {
+                cert_id: revoked,
+                revoked_at: Utc::now(),
+                reason: RevocationReason::Superseded,
+            }],
+            crl_number: 2,
+            next_update_days: 30,
+            published_by: ActorId::system("crl-publisher"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        };
+
+        let published = handle_publish_crl(cmd, &root_key).unwrap();
+        assert!(published.crl_pem.contains("BEGIN X509 CRL"));
+        match published.events.as_slice() {
+            [DomainEvent::Certificate(crate::events::CertificateEvents::CrlPublished(e))] => {
+                assert_eq!(e.crl_id, published.crl_id);
+                assert_eq!(e.issuer_ca_id, root_ca_id);
+                assert_eq!(e.crl_number, 2);
+                assert_eq!(e.revoked_cert_ids, vec![revoked]);
+            }
+            other => panic!("unexpected events: {:?}", other),
+        }
+    }
 }
@@ -6,13 +6,16 @@
 // Re-export relationship-related commands from organization module
 pub use super::organization::{
     EstablishRelationship,
+    TerminateRelationship,
+    UnbindPolicy,
     handle_establish_relationship,
+    handle_terminate_relationship,
+    handle_unbind_policy,
     RelationshipType,
 };
 
 // TODO: Future refactoring
 // - Add ModifyRelationship command
-// - Add TerminateRelationship command
 // - Add ValidateAccountability command
 // - Add TrustEstablishment commands
 // - Align with RelationshipEvents from events/relationship.rs
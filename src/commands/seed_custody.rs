@@ -0,0 +1,516 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Master Seed Custody Commands
+//!
+//! Commands for creating a random master seed at a key ceremony, for
+//! sharing it among backup holders and for recombining it.
+//!
+//! The seed is split into groups of shares (see `crypto::shamir`), each
+//! group made up of people holding the `BackupHolder` role. Shares are
+//! returned to the caller to be printed or written to tokens; the events
+//! only record which person received which share ID.
+
+use chrono::{DateTime, Utc};
+use cim_domain::{Command, EntityId};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::aggregate::KeyManagementError;
+use crate::crypto::entropy::EntropyMixer;
+use crate::crypto::seed_derivation::MasterSeed;
+use crate::crypto::shamir::{GroupShare, GroupSpec};
+use crate::domain::{KeyOwnerRole, KeyOwnership};
+use crate::events::key::{
+    MasterSeedDerivedEvent, MasterSeedRecoveredEvent, MasterSeedSplitEvent, SeedShareAssignedEvent, SeedShareGroupRecord,
+};
+use crate::events::{DomainEvent, KeyEvents};
+use crate::value_objects::ActorId;
+
+/// Marker type for master seed custody commands (Key aggregate)
+#[derive(Debug, Clone, Copy)]
+pub struct MasterSeedAggregate;
+
+/// Command to create a random master seed from ceremony entropy
+///
+/// Carries the collected entropy, so like `RecoverMasterSeed` it is never
+/// serialized.
+#[derive(Debug, Clone)]
+pub struct CreateMasterSeed {
+    pub command_id: Uuid,
+    pub seed_id: Uuid,
+    pub organization_id: Uuid,
+    pub entropy: EntropyMixer,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl CreateMasterSeed {
+    /// Create a command mixing the sources collected in `entropy`
+    pub fn new(organization_id: Uuid, entropy: EntropyMixer) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            seed_id: Uuid::now_v7(),
+            organization_id,
+            entropy,
+            requested_by: ActorId::system("seed-ceremony"),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Set the actor running the ceremony
+    pub fn with_requested_by(mut self, actor: ActorId) -> Self {
+        self.requested_by = actor;
+        self
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+}
+
+/// Backup holders sharing one group share of the master seed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustodyGroup {
+    pub name: String,
+    /// Holders of the group needed to reconstruct its share
+    pub member_threshold: u8,
+    /// One share per holder, in order
+    pub holders: Vec<KeyOwnership>,
+}
+
+/// Command to split the master seed among backup holders
+///
+/// Any `group_threshold` groups recover the seed, each group with at
+/// least `member_threshold` of its holders.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitMasterSeed {
+    pub command_id: Uuid,
+    pub split_id: Uuid,
+    pub organization_id: Uuid,
+    pub group_threshold: u8,
+    pub groups: Vec<CustodyGroup>,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Command for SplitMasterSeed {
+    type Aggregate = MasterSeedAggregate;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.split_id))
+    }
+}
+
+impl SplitMasterSeed {
+    /// Create a command splitting the seed among `groups`
+    pub fn new(organization_id: Uuid, group_threshold: u8, groups: Vec<CustodyGroup>) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            split_id: Uuid::now_v7(),
+            organization_id,
+            group_threshold,
+            groups,
+            requested_by: ActorId::system("seed-custody"),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Set the actor running the split
+    pub fn with_requested_by(mut self, actor: ActorId) -> Self {
+        self.requested_by = actor;
+        self
+    }
+
+    /// Set correlation ID for event chain tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// A share and the backup holder it is meant for
+#[derive(Debug, Clone)]
+pub struct AssignedShare {
+    pub person_id: Uuid,
+    pub group_name: String,
+    pub share: GroupShare,
+}
+
+/// Result of splitting the master seed
+#[derive(Debug, Clone)]
+pub struct MasterSeedSplitResult {
+    /// `MasterSeedSplit` followed by one `SeedShareAssigned` per share
+    pub events: Vec<DomainEvent>,
+    /// Shares to hand out; they are not recorded anywhere else
+    pub shares: Vec<AssignedShare>,
+}
+
+/// Command to recombine the master seed from backup holders' shares
+///
+/// Carries share data, so unlike other commands it is never serialized.
+#[derive(Debug, Clone)]
+pub struct RecoverMasterSeed {
+    pub command_id: Uuid,
+    /// Split the shares were issued by
+    pub split_id: Uuid,
+    pub shares: Vec<GroupShare>,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl RecoverMasterSeed {
+    /// Create a command recovering the seed of `split_id` from `shares`
+    pub fn new(split_id: Uuid, shares: Vec<GroupShare>) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            split_id,
+            shares,
+            requested_by: ActorId::system("seed-recovery"),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Set the actor running the recovery
+    pub fn with_requested_by(mut self, actor: ActorId) -> Self {
+        self.requested_by = actor;
+        self
+    }
+}
+
+/// Set ID carried by every share of the split `split_id`
+///
+/// Binds the shares to their split, so recovery can refuse shares of
+/// another one before recombining anything.
+pub fn split_set_id(split_id: Uuid) -> u32 {
+    let digest = Sha256::digest(split_id.as_bytes());
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// Handle CreateMasterSeed command
+///
+/// Fails if a source failed its health tests or the sources were credited
+/// with less than 256 bits.
+///
+/// Emits:
+/// - MasterSeedDerivedEvent with the entropy report (never the seed)
+pub fn handle_create_master_seed(
+    cmd: CreateMasterSeed,
+) -> Result<(MasterSeed, Vec<DomainEvent>), KeyManagementError> {
+    let (seed, report) = cmd
+        .entropy
+        .mix(&cmd.organization_id.to_string())
+        .map_err(|e| KeyManagementError::PolicyViolation(format!("Ceremony entropy rejected: {}", e)))?;
+
+    let event = DomainEvent::Key(KeyEvents::MasterSeedDerived(MasterSeedDerivedEvent {
+        seed_id: cmd.seed_id,
+        organization_id: cmd.organization_id,
+        entropy: report,
+        derived_at: cmd.timestamp,
+        derived_by: cmd.requested_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok((seed, vec![event]))
+}
+
+/// Handle SplitMasterSeed command
+///
+/// Every holder must be a `BackupHolder` of the organization, and nobody
+/// may hold more than one share of the same split.
+///
+/// Emits:
+/// - MasterSeedSplitEvent
+/// - SeedShareAssignedEvent per holder
+pub fn handle_split_master_seed(
+    cmd: SplitMasterSeed,
+    master_seed: &MasterSeed,
+) -> Result<MasterSeedSplitResult, KeyManagementError> {
+    let mut names = HashSet::new();
+    let mut holders = HashSet::new();
+    let mut specs = Vec::with_capacity(cmd.groups.len());
+    for group in &cmd.groups {
+        if group.name.trim().is_empty() || !names.insert(group.name.as_str()) {
+            return Err(KeyManagementError::InvalidCommand(format!(
+                "Custody group names must be unique and non-empty, got {:?}",
+                group.name
+            )));
+        }
+        for holder in &group.holders {
+            if holder.role != KeyOwnerRole::BackupHolder {
+                return Err(KeyManagementError::PolicyViolation(format!(
+                    "Person {} is a {}, only backup holders receive seed shares",
+                    holder.person_id, holder.role
+                )));
+            }
+            if holder.organization_id != cmd.organization_id {
+                return Err(KeyManagementError::InvalidCommand(format!(
+                    "Person {} belongs to another organization",
+                    holder.person_id
+                )));
+            }
+            if !holders.insert(holder.person_id) {
+                return Err(KeyManagementError::PolicyViolation(format!(
+                    "Person {} would hold more than one share",
+                    holder.person_id
+                )));
+            }
+        }
+        let members = u8::try_from(group.holders.len()).map_err(|_| {
+            KeyManagementError::InvalidCommand(format!("Custody group {} has too many holders", group.name))
+        })?;
+        specs.push(GroupSpec::new(group.member_threshold, members));
+    }
+
+    let mut group_shares = master_seed
+        .split_groups(cmd.group_threshold, &specs)
+        .map_err(|e| KeyManagementError::InvalidCommand(e.to_string()))?;
+    let split_set_id = split_set_id(cmd.split_id);
+    for share in group_shares.iter_mut().flatten() {
+        share.set_id = split_set_id;
+    }
+    let set_id = format!("{:08x}", split_set_id);
+
+    let mut events = vec![DomainEvent::Key(KeyEvents::MasterSeedSplit(MasterSeedSplitEvent {
+        split_id: cmd.split_id,
+        organization_id: cmd.organization_id,
+        set_id,
+        group_threshold: cmd.group_threshold,
+        groups: cmd
+            .groups
+            .iter()
+            .zip(&specs)
+            .map(|(group, spec)| SeedShareGroupRecord {
+                name: group.name.clone(),
+                member_threshold: spec.threshold,
+                member_count: spec.members,
+            })
+            .collect(),
+        split_at: cmd.timestamp,
+        split_by: cmd.requested_by.clone(),
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }))];
+
+    let mut shares = Vec::new();
+    for (group, members) in cmd.groups.iter().zip(group_shares) {
+        for (holder, share) in group.holders.iter().zip(members) {
+            events.push(DomainEvent::Key(KeyEvents::SeedShareAssigned(SeedShareAssignedEvent {
+                split_id: cmd.split_id,
+                share_id: share.share_id(),
+                group_name: group.name.clone(),
+                person_id: holder.person_id,
+                assigned_at: cmd.timestamp,
+                assigned_by: cmd.requested_by.clone(),
+                correlation_id: cmd.correlation_id,
+                causation_id: cmd.causation_id,
+            })));
+            shares.push(AssignedShare {
+                person_id: holder.person_id,
+                group_name: group.name.clone(),
+                share,
+            });
+        }
+    }
+
+    Ok(MasterSeedSplitResult { events, shares })
+}
+
+/// Handle RecoverMasterSeed command
+///
+/// Every share must belong to `cmd.split_id`; a wrong share among them
+/// fails the digest check of `crypto::shamir` rather than yielding a
+/// wrong seed.
+///
+/// Emits:
+/// - MasterSeedRecoveredEvent listing the share IDs presented
+pub fn handle_recover_master_seed(
+    cmd: RecoverMasterSeed,
+) -> Result<(MasterSeed, Vec<DomainEvent>), KeyManagementError> {
+    let set_id = split_set_id(cmd.split_id);
+    if let Some(foreign) = cmd.shares.iter().find(|share| share.set_id != set_id) {
+        return Err(KeyManagementError::InvalidCommand(format!(
+            "Share {} does not belong to split {}",
+            foreign.share_id(),
+            cmd.split_id
+        )));
+    }
+
+    let seed = MasterSeed::from_group_shares(&cmd.shares)
+        .map_err(|e| KeyManagementError::CryptoError(format!("Master seed recovery failed: {}", e)))?;
+
+    let event = DomainEvent::Key(KeyEvents::MasterSeedRecovered(MasterSeedRecoveredEvent {
+        split_id: cmd.split_id,
+        share_ids: cmd.shares.iter().map(GroupShare::share_id).collect(),
+        recovered_at: cmd.timestamp,
+        recovered_by: cmd.requested_by,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok((seed, vec![event]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::create_key_ownership;
+
+    fn group(name: &str, threshold: u8, organization_id: Uuid, holders: usize) -> CustodyGroup {
+        CustodyGroup {
+            name: name.to_string(),
+            member_threshold: threshold,
+            holders: (0..holders)
+                .map(|_| create_key_ownership(Uuid::now_v7(), organization_id, KeyOwnerRole::BackupHolder))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_split_records_holders_and_recovers() {
+        let organization_id = Uuid::now_v7();
+        let seed = MasterSeed::from_bytes([42u8; 32]);
+        let cmd = SplitMasterSeed::new(
+            organization_id,
+            2,
+            vec![group("board", 2, organization_id, 3), group("engineering", 2, organization_id, 2)],
+        );
+        let split_id = cmd.split_id;
+        let board: Vec<Uuid> = cmd.groups[0].holders.iter().map(|h| h.person_id).collect();
+
+        let result = handle_split_master_seed(cmd, &seed).unwrap();
+        assert_eq!(result.events.len(), 6);
+        assert_eq!(result.shares.len(), 5);
+        let DomainEvent::Key(KeyEvents::MasterSeedSplit(split)) = &result.events[0] else {
+            panic!("Expected MasterSeedSplit first");
+        };
+        assert_eq!(split.groups[0].member_count, 3);
+        let DomainEvent::Key(KeyEvents::SeedShareAssigned(assigned)) = &result.events[1] else {
+            panic!("Expected SeedShareAssigned");
+        };
+        assert_eq!(assigned.person_id, board[0]);
+        assert_eq!(assigned.share_id, result.shares[0].share.share_id());
+        assert!(assigned.share_id.starts_with(&split.set_id));
+
+        // Share data never reaches the event log
+        let logged = serde_json::to_string(&result.events).unwrap();
+        for assigned in &result.shares {
+            let data = assigned.share.to_text().rsplit(':').nth(1).unwrap().to_string();
+            assert!(!logged.contains(&data));
+        }
+
+        // Two board members and both engineers
+        let shares = [0, 2, 3, 4].iter().map(|&i| result.shares[i].share.clone()).collect();
+        let (recovered, events) = handle_recover_master_seed(RecoverMasterSeed::new(split_id, shares)).unwrap();
+        assert_eq!(recovered.as_bytes(), seed.as_bytes());
+        match &events[..] {
+            [DomainEvent::Key(KeyEvents::MasterSeedRecovered(evt))] => assert_eq!(evt.share_ids.len(), 4),
+            _ => panic!("Expected a single MasterSeedRecovered event"),
+        }
+
+        let too_few = vec![result.shares[0].share.clone(), result.shares[3].share.clone()];
+        assert!(handle_recover_master_seed(RecoverMasterSeed::new(split_id, too_few)).is_err());
+    }
+
+    #[test]
+    fn test_recovery_refuses_foreign_and_wrong_shares() {
+        let organization_id = Uuid::now_v7();
+        let seed = MasterSeed::from_bytes([42u8; 32]);
+        let split = |seed: &MasterSeed| {
+            let cmd = SplitMasterSeed::new(organization_id, 1, vec![group("board", 2, organization_id, 3)]);
+            let split_id = cmd.split_id;
+            let shares: Vec<GroupShare> =
+                handle_split_master_seed(cmd, seed).unwrap().shares.into_iter().map(|a| a.share).collect();
+            (split_id, shares)
+        };
+        let (split_id, shares) = split(&seed);
+        let (_, other) = split(&MasterSeed::from_bytes([7u8; 32]));
+        assert!(shares.iter().all(|share| share.set_id == split_set_id(split_id)));
+
+        // A share of another split is refused before recombining
+        let mixed = vec![shares[0].clone(), other[1].clone()];
+        assert!(matches!(
+            handle_recover_master_seed(RecoverMasterSeed::new(split_id, mixed)),
+            Err(KeyManagementError::InvalidCommand(_))
+        ));
+
+        // Relabelled to pass the set ID check, it still fails the digest
+        let mut relabelled = other[1].clone();
+        relabelled.set_id = split_set_id(split_id);
+        let wrong = vec![shares[0].clone(), relabelled];
+        let error = handle_recover_master_seed(RecoverMasterSeed::new(split_id, wrong)).unwrap_err();
+        assert!(matches!(error, KeyManagementError::CryptoError(_)));
+        assert!(error.to_string().contains("do not recombine"));
+    }
+
+    #[test]
+    fn test_created_seed_event_carries_report_only() {
+        use crate::adapters::seeded_rng::SeededCryptoRng;
+
+        let dice: Vec<u8> = (0..60).map(|i| (i % 6 + 1) as u8).collect();
+        let entropy = EntropyMixer::new()
+            .with_os_entropy(&SeededCryptoRng::from_label("ceremony"))
+            .with_dice_rolls(&dice, 6)
+            .unwrap();
+        let (seed, events) = handle_create_master_seed(CreateMasterSeed::new(Uuid::now_v7(), entropy)).unwrap();
+
+        let DomainEvent::Key(KeyEvents::MasterSeedDerived(derived)) = &events[0] else {
+            panic!("Expected MasterSeedDerived");
+        };
+        assert!(derived.entropy.passed());
+        assert_eq!(derived.entropy.sources.len(), 2);
+        let serialized = serde_json::to_string(&events).unwrap();
+        assert!(!serialized.contains(&hex::encode(seed.as_bytes())));
+
+        let starved = CreateMasterSeed::new(Uuid::now_v7(), EntropyMixer::new().with_dice_rolls(&dice, 6).unwrap());
+        assert!(matches!(
+            handle_create_master_seed(starved),
+            Err(KeyManagementError::PolicyViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_only_distinct_backup_holders_receive_shares() {
+        let organization_id = Uuid::now_v7();
+        let seed = MasterSeed::from_bytes([42u8; 32]);
+
+        let mut developer = group("board", 2, organization_id, 2);
+        developer.holders[1].role = KeyOwnerRole::Developer;
+        let cmd = SplitMasterSeed::new(organization_id, 1, vec![developer]);
+        assert!(matches!(handle_split_master_seed(cmd, &seed), Err(KeyManagementError::PolicyViolation(_))));
+
+        let board = group("board", 2, organization_id, 2);
+        let mut twice = group("engineering", 1, organization_id, 1);
+        twice.holders[0].person_id = board.holders[0].person_id;
+        let cmd = SplitMasterSeed::new(organization_id, 1, vec![board, twice]);
+        assert!(matches!(handle_split_master_seed(cmd, &seed), Err(KeyManagementError::PolicyViolation(_))));
+
+        let cmd = SplitMasterSeed::new(organization_id, 3, vec![group("board", 2, organization_id, 2)]);
+        assert!(matches!(handle_split_master_seed(cmd, &seed), Err(KeyManagementError::InvalidCommand(_))));
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! TPM Key Commands
+//!
+//! Commands for workstation and service identities whose keys live in the
+//! machine's TPM 2.0. Keys are generated through `TpmPort::generate_key`;
+//! these handlers validate the result and emit a hardware-backed
+//! `KeyGeneratedEvent` whose `metadata.tpm` records the EK/AK names, the
+//! key's TPM name and the PCR policy it is bound to.
+
+use chrono::{DateTime, Utc};
+use cim_domain::{Command, EntityId};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::aggregate::KeyManagementError;
+use crate::domain::KeyOwnership;
+use crate::events::key::KeyGeneratedEvent;
+use crate::events::{DomainEvent, KeyEvents};
+use crate::ports::tpm::{TpmKey, TpmKeyAlgorithm};
+use crate::types::{KeyMetadata, KeyPurpose, TpmKeyReference};
+use crate::value_objects::ActorId;
+
+/// Marker type for TPM key commands (Key aggregate)
+#[derive(Debug, Clone, Copy)]
+pub struct TpmKeyAggregate;
+
+/// Command to record a workstation or service key generated in a TPM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateTpmKey {
+    pub command_id: Uuid,
+    pub key_id: Uuid,
+    /// Workstation or service the key identifies
+    pub label: String,
+    pub algorithm: TpmKeyAlgorithm,
+    /// Public key as returned by the TPM, hex
+    pub public_key: String,
+    pub reference: TpmKeyReference,
+    pub purpose: KeyPurpose,
+    pub ownership: Option<KeyOwnership>,
+    pub requested_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Command for GenerateTpmKey {
+    type Aggregate = TpmKeyAggregate;
+
+    fn aggregate_id(&self) -> Option<EntityId<Self::Aggregate>> {
+        Some(EntityId::from_uuid(self.key_id))
+    }
+}
+
+impl GenerateTpmKey {
+    /// Create a command from a key generated in the TPM
+    pub fn new(key: &TpmKey) -> Self {
+        let command_id = Uuid::now_v7();
+        Self {
+            command_id,
+            key_id: Uuid::now_v7(),
+            label: key.label.clone(),
+            algorithm: key.algorithm,
+            public_key: hex::encode(&key.public_key),
+            reference: key.reference.clone(),
+            purpose: KeyPurpose::Authentication,
+            ownership: None,
+            requested_by: ActorId::system("tpm-key"),
+            correlation_id: command_id,
+            causation_id: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Set the purpose (Authentication by default)
+    pub fn with_purpose(mut self, purpose: KeyPurpose) -> Self {
+        self.purpose = purpose;
+        self
+    }
+
+    /// Set the person and organization owning the key
+    pub fn with_ownership(mut self, ownership: KeyOwnership) -> Self {
+        self.ownership = Some(ownership);
+        self
+    }
+
+    /// Set the actor requesting the key
+    pub fn with_requested_by(mut self, actor: ActorId) -> Self {
+        self.requested_by = actor;
+        self
+    }
+
+    /// Set correlation ID for tracking
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Set causation ID linking to what triggered this
+    pub fn with_causation_id(mut self, causation_id: Uuid) -> Self {
+        self.causation_id = Some(causation_id);
+        self
+    }
+}
+
+/// Handle GenerateTpmKey command
+///
+/// Emits:
+/// - KeyGeneratedEvent (hardware backed, metadata.tpm = EK/AK names, key
+///   name and PCR policy)
+pub fn handle_generate_tpm_key(cmd: GenerateTpmKey) -> Result<Vec<DomainEvent>, KeyManagementError> {
+    if cmd.label.trim().is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "TPM key label is required".to_string(),
+        ));
+    }
+
+    if cmd.reference.ek_name.is_empty() || cmd.reference.key_name.is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "TPM EK and key names are required".to_string(),
+        ));
+    }
+
+    if cmd.public_key.is_empty() {
+        return Err(KeyManagementError::InvalidCommand(
+            "TPM public key is required".to_string(),
+        ));
+    }
+
+    // An empty selection would be an unbound key usable under any boot state
+    if cmd.reference.pcrs.is_empty() {
+        return Err(KeyManagementError::PolicyViolation(
+            "TPM keys must be bound to at least one PCR".to_string(),
+        ));
+    }
+
+    if matches!(cmd.purpose, KeyPurpose::CertificateAuthority | KeyPurpose::Encryption) {
+        return Err(KeyManagementError::PolicyViolation(format!(
+            "TPM keys identify workstations and services, not {:?}",
+            cmd.purpose
+        )));
+    }
+
+    let mut attributes = HashMap::new();
+    attributes.insert("public_key".to_string(), cmd.public_key);
+
+    let event = DomainEvent::Key(KeyEvents::KeyGenerated(KeyGeneratedEvent {
+        key_id: cmd.key_id,
+        algorithm: cmd.algorithm.key_algorithm(),
+        purpose: cmd.purpose,
+        generated_at: cmd.timestamp,
+        generated_by: cmd.requested_by,
+        hardware_backed: true,
+        metadata: KeyMetadata {
+            label: cmd.label.clone(),
+            description: Some(format!(
+                "TPM key for {} bound to {} PCRs {:?}",
+                cmd.label, cmd.reference.pcr_bank, cmd.reference.pcrs
+            )),
+            tags: vec!["tpm".to_string()],
+            attributes,
+            jwt_kid: None,
+            jwt_alg: None,
+            jwt_use: None,
+            hardware_serial: None,
+            tpm: Some(cmd.reference),
+        },
+        ownership: cmd.ownership,
+        expires_at: None,
+        correlation_id: cmd.correlation_id,
+        causation_id: cmd.causation_id,
+    }));
+
+    Ok(vec![event])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::tpm_mock::MockTpmAdapter;
+    use crate::ports::tpm::{PcrSelection, TpmPort};
+
+    #[tokio::test]
+    async fn test_tpm_key_recorded_with_references() {
+        let tpm = MockTpmAdapter::new();
+        let key = tpm
+            .generate_key("build-server-01", TpmKeyAlgorithm::EcdsaP256, &PcrSelection::boot_state())
+            .await
+            .unwrap();
+
+        let events = handle_generate_tpm_key(GenerateTpmKey::new(&key)).unwrap();
+        match &events[..] {
+            [DomainEvent::Key(KeyEvents::KeyGenerated(evt))] => {
+                assert!(evt.hardware_backed);
+                let reference = evt.metadata.tpm.as_ref().unwrap();
+                assert_eq!(reference.ek_name, tpm.identity().await.unwrap().ek_name);
+                assert_eq!(reference.pcrs, vec![0, 2, 4, 7]);
+                assert_eq!(evt.purpose, KeyPurpose::Authentication);
+            }
+            _ => panic!("Expected a single KeyGenerated event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unbound_key_rejected() {
+        let tpm = MockTpmAdapter::new();
+        let key = tpm
+            .generate_key("build-server-01", TpmKeyAlgorithm::EcdsaP256, &PcrSelection::boot_state())
+            .await
+            .unwrap();
+
+        let mut cmd = GenerateTpmKey::new(&key);
+        cmd.reference.pcrs.clear();
+        assert!(matches!(
+            handle_generate_tpm_key(cmd),
+            Err(KeyManagementError::PolicyViolation(_))
+        ));
+    }
+}
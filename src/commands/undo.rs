@@ -0,0 +1,233 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Undo via Compensating Commands
+//!
+//! Events are never removed or edited. Undoing an event means issuing the
+//! command whose events cancel its effect:
+//!
+//! | Event                                       | Compensating command    |
+//! |---------------------------------------------|-------------------------|
+//! | `PersonCreated`                             | `DeactivatePerson`      |
+//! | `RelationshipEstablished` (policy binding)  | `UnbindPolicy`          |
+//! | `RelationshipEstablished` (any other type)  | `TerminateRelationship` |
+//!
+//! The compensating command stays in the undone event's correlation and
+//! names the undone event as its causation, so the undo is traceable in the
+//! causation tree. Convert it into a [`KeyCommand`] to run it through the
+//! aggregate like any other command.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::organization::{DeactivatePerson, RelationshipType, TerminateRelationship, UnbindPolicy};
+use super::KeyCommand;
+use crate::events::{DomainEvent, EventEnvelope, PersonEvents, RelationshipEvents};
+
+/// Command that reverses the effect of an earlier event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum CompensatingCommand {
+    DeactivatePerson(DeactivatePerson),
+    UnbindPolicy(UnbindPolicy),
+    TerminateRelationship(TerminateRelationship),
+}
+
+impl CompensatingCommand {
+    pub fn command_id(&self) -> Uuid {
+        match self {
+            CompensatingCommand::DeactivatePerson(cmd) => cmd.command_id,
+            CompensatingCommand::UnbindPolicy(cmd) => cmd.command_id,
+            CompensatingCommand::TerminateRelationship(cmd) => cmd.command_id,
+        }
+    }
+
+    /// Short label for an undo action, e.g. in a confirmation dialog
+    pub fn description(&self) -> String {
+        match self {
+            CompensatingCommand::DeactivatePerson(cmd) => format!("Deactivate person {}", cmd.person_id),
+            CompensatingCommand::UnbindPolicy(cmd) => {
+                format!("Unbind policy {} from {}", cmd.policy_id, cmd.entity_id)
+            }
+            CompensatingCommand::TerminateRelationship(cmd) => {
+                format!("Remove relationship {}", cmd.relationship_id)
+            }
+        }
+    }
+}
+
+impl From<CompensatingCommand> for KeyCommand {
+    fn from(cmd: CompensatingCommand) -> Self {
+        match cmd {
+            CompensatingCommand::DeactivatePerson(cmd) => KeyCommand::DeactivatePerson(cmd),
+            CompensatingCommand::UnbindPolicy(cmd) => KeyCommand::UnbindPolicy(cmd),
+            CompensatingCommand::TerminateRelationship(cmd) => KeyCommand::TerminateRelationship(cmd),
+        }
+    }
+}
+
+/// Whether [`compensate`] can undo this event
+pub fn is_reversible(event: &DomainEvent) -> bool {
+    matches!(
+        event,
+        DomainEvent::Person(PersonEvents::PersonCreated(_))
+            | DomainEvent::Relationship(RelationshipEvents::RelationshipEstablished(_))
+    )
+}
+
+/// Compensating command for the event in `envelope`, issued by `actor_id`
+///
+/// Returns `None` for events that cannot be undone this way.
+pub fn compensate(envelope: &EventEnvelope, actor_id: Uuid) -> Option<CompensatingCommand> {
+    let correlation_id = envelope.correlation_id;
+    let causation_id = Some(envelope.event_id);
+    let timestamp = Utc::now();
+
+    match &envelope.event {
+        DomainEvent::Person(PersonEvents::PersonCreated(e)) => {
+            Some(CompensatingCommand::DeactivatePerson(DeactivatePerson {
+                command_id: Uuid::now_v7(),
+                person_id: e.person_id,
+                reason: format!("Undo creation of {}", e.name),
+                deactivated_by: actor_id,
+                correlation_id,
+                causation_id,
+                timestamp,
+            }))
+        }
+        DomainEvent::Relationship(RelationshipEvents::RelationshipEstablished(e)) => {
+            Some(match e.relationship_type {
+                RelationshipType::PolicyGovernsEntity => CompensatingCommand::UnbindPolicy(UnbindPolicy {
+                    command_id: Uuid::now_v7(),
+                    relationship_id: e.relationship_id,
+                    policy_id: e.from_id,
+                    entity_id: e.to_id,
+                    unbound_by: actor_id.to_string(),
+                    correlation_id,
+                    causation_id,
+                    timestamp,
+                }),
+                _ => CompensatingCommand::TerminateRelationship(TerminateRelationship {
+                    command_id: Uuid::now_v7(),
+                    relationship_id: e.relationship_id,
+                    reason: "Undo relationship".to_string(),
+                    terminated_by: actor_id.to_string(),
+                    correlation_id,
+                    causation_id,
+                    timestamp,
+                }),
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::organization::{
+        handle_create_person, handle_deactivate_person, handle_establish_relationship, CreatePerson,
+        EstablishRelationship,
+    };
+    use crate::events::person::PersonCreatedEvent;
+
+    fn envelope(event: DomainEvent) -> EventEnvelope {
+        EventEnvelope::new(event, Uuid::now_v7(), None)
+    }
+
+    #[tokio::test]
+    async fn test_undo_person_created_deactivates() {
+        let events = handle_create_person(CreatePerson {
+            command_id: Uuid::now_v7(),
+            person_id: Uuid::now_v7(),
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+            title: None,
+            department: None,
+            organization_id: None,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+            timestamp: Utc::now(),
+        })
+        .await
+        .unwrap();
+        let created = envelope(events[0].clone());
+        let DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent { person_id, .. })) = &created.event
+        else {
+            unreachable!()
+        };
+
+        let admin = Uuid::now_v7();
+        let undo = compensate(&created, admin).unwrap();
+        let undone = handle_deactivate_person(match undo {
+            CompensatingCommand::DeactivatePerson(cmd) => cmd,
+            other => panic!("Expected DeactivatePerson, got {:?}", other),
+        })
+        .await
+        .unwrap();
+
+        match &undone[0] {
+            DomainEvent::Person(PersonEvents::PersonDeactivated(evt)) => {
+                assert_eq!(evt.person_id, *person_id);
+                assert_eq!(evt.deactivated_by, admin);
+                assert_eq!(evt.correlation_id, created.correlation_id);
+            }
+            _ => panic!("Expected PersonDeactivated event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_undo_relationship_by_type() {
+        let establish = |relationship_type| EstablishRelationship {
+            command_id: Uuid::now_v7(),
+            from_id: Uuid::now_v7(),
+            to_id: Uuid::now_v7(),
+            relationship_type,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+            timestamp: Utc::now(),
+        };
+
+        let binding = envelope(
+            handle_establish_relationship(establish(RelationshipType::PolicyGovernsEntity))
+                .await
+                .unwrap()
+                .remove(0),
+        );
+        let undo = compensate(&binding, Uuid::now_v7()).unwrap();
+        assert!(matches!(&undo, CompensatingCommand::UnbindPolicy(cmd) if cmd.causation_id == Some(binding.event_id)));
+        assert!(matches!(KeyCommand::from(undo), KeyCommand::UnbindPolicy(_)));
+
+        let membership = envelope(
+            handle_establish_relationship(establish(RelationshipType::MemberOf))
+                .await
+                .unwrap()
+                .remove(0),
+        );
+        let DomainEvent::Relationship(RelationshipEvents::RelationshipEstablished(established)) = &membership.event
+        else {
+            unreachable!()
+        };
+        match compensate(&membership, Uuid::now_v7()) {
+            Some(CompensatingCommand::TerminateRelationship(cmd)) => {
+                assert_eq!(cmd.relationship_id, established.relationship_id)
+            }
+            other => panic!("Expected TerminateRelationship, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_irreversible_events_have_no_compensation() {
+        let deactivated = DomainEvent::Person(PersonEvents::PersonDeactivated(
+            crate::events::person::PersonDeactivatedEvent {
+                person_id: Uuid::now_v7(),
+                reason: "left".to_string(),
+                deactivated_at: Utc::now(),
+                deactivated_by: Uuid::now_v7(),
+                correlation_id: Uuid::now_v7(),
+                causation_id: None,
+            },
+        ));
+        assert!(!is_reversible(&deactivated));
+        assert!(compensate(&envelope(deactivated), Uuid::now_v7()).is_none());
+    }
+}
@@ -180,6 +180,7 @@ pub fn handle_configure_yubikey_security(
 /// Command to provision a YubiKey PIV slot
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ProvisionYubiKeySlot {
+    pub command_id: Uuid,
     pub yubikey_serial: String,
     pub slot: PivSlot,
     pub person: Person,
@@ -292,6 +293,7 @@ pub fn handle_provision_yubikey_slot(
         validity,
         basic_constraints: BasicConstraints::end_entity(),
         issuer: None, // Would be set to CA cert ID in real implementation
+        signature_algorithm: None,
         correlation_id: cmd.correlation_id,
         causation_id: Some(key_id), // Certificate caused by key generation
     };
@@ -432,6 +434,7 @@ pub fn handle_provision_complete_yubikey(
         };
 
         let provision_cmd = ProvisionYubiKeySlot {
+            command_id: Uuid::now_v7(),
             yubikey_serial: cmd.yubikey_serial.clone(),
             slot: *slot,
             person: cmd.person.clone(),
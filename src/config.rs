@@ -13,6 +13,8 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+use crate::crypto::kdf::KdfParams;
+
 /// NATS server URL - HARDCODED for air-gapped offline operation
 ///
 /// This system is designed to run completely offline with NATS running locally
@@ -31,6 +33,10 @@ pub struct Config {
 
     /// Operational mode
     pub mode: OperationalMode,
+
+    /// Security policy for key material handling
+    #[serde(default)]
+    pub security: SecurityConfig,
 }
 
 impl Default for Config {
@@ -39,6 +45,7 @@ impl Default for Config {
             nats: NatsConfig::default(),
             storage: StorageConfig::default(),
             mode: OperationalMode::Offline,
+            security: SecurityConfig::default(),
         }
     }
 }
@@ -141,6 +148,42 @@ impl Default for StorageConfig {
     }
 }
 
+/// Security policy for key material handling
+///
+/// These settings are enforced by the `KeyManagementAggregate` when it
+/// processes export and import commands, so a relaxed GUI or CLI cannot
+/// bypass them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityConfig {
+    /// Allow secret key material to be exported without passphrase protection
+    ///
+    /// Defaults to `false`: every secret key export must be encrypted.
+    #[serde(default)]
+    pub allow_unencrypted_secret_export: bool,
+
+    /// Smallest RSA modulus accepted for imported SSH public keys
+    #[serde(default = "default_min_ssh_rsa_bits")]
+    pub min_ssh_rsa_bits: u32,
+
+    /// Argon2id cost for new passphrase-derived keys (see `crypto::kdf`)
+    #[serde(default)]
+    pub kdf: KdfParams,
+}
+
+fn default_min_ssh_rsa_bits() -> u32 {
+    3072
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            allow_unencrypted_secret_export: false,
+            min_ssh_rsa_bits: default_min_ssh_rsa_bits(),
+            kdf: KdfParams::default(),
+        }
+    }
+}
+
 /// Operational mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OperationalMode {
@@ -241,6 +284,7 @@ impl Config {
                 backup_dir: Some(PathBuf::from("/backup/cim-keys")),
             },
             mode: OperationalMode::Hybrid,
+            security: SecurityConfig::default(),
         };
 
         example.save(path)?;
@@ -292,6 +336,35 @@ mod tests {
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_security_config_defaults_to_forbidding_unencrypted_export() {
+        let config = Config::default();
+        assert!(!config.security.allow_unencrypted_secret_export);
+
+        // Older config files without a [security] table still parse
+        let toml_str = r#"
+            mode = "Offline"
+
+            [nats]
+            enabled = false
+            stream_name = "CIM_GRAPH_EVENTS"
+            object_store_bucket = "cim-graph-payloads"
+            source_id = "cim-keys"
+            subject_prefix = "cim.graph"
+            connection_timeout_secs = 5
+            enable_jetstream = true
+            enable_ipld = true
+            max_retries = 3
+
+            [storage]
+            offline_events_dir = "./offline-events"
+            keys_output_dir = "./keys-output"
+            enable_backup = false
+        "#;
+        let parsed: Config = toml::from_str(toml_str).unwrap();
+        assert!(!parsed.security.allow_unencrypted_secret_export);
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config::default();
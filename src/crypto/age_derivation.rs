@@ -0,0 +1,220 @@
+//! Deterministic age identity derivation from the master seed
+//!
+//! Admins decrypt deployment secrets (agenix, sops-nix) with an age
+//! identity. Deriving it from the `MasterSeed` means the identity never
+//! has to be stored next to the secrets it opens: it is regenerated from
+//! the master passphrase on the machine doing the deployment.
+//!
+//! ## Derivation Path
+//!
+//! ```text
+//! cim-keys/age/v1/{organization}/{person}  → X25519
+//! ```
+//!
+//! An age X25519 identity is any 32 bytes (clamping happens when it is
+//! used), so the derived child seed is the identity as-is.
+
+use std::str::FromStr;
+
+use bech32::{Bech32, Hrp};
+use der::zeroize::Zeroize;
+
+use super::seed_derivation::MasterSeed;
+
+/// Derivation scheme version, part of every path
+const AGE_DERIVATION_VERSION: &str = "v1";
+
+/// Bech32 prefix of age identities, upper-cased when encoded
+const SECRET_KEY_HRP: &str = "age-secret-key-";
+
+/// Bech32 prefix of age X25519 recipients
+const RECIPIENT_HRP: &str = "age";
+
+/// Derivation path for an admin's age identity
+///
+/// Components must be non-empty and must not contain `/`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AgeDerivationPath {
+    pub organization: String,
+    pub person: String,
+}
+
+impl AgeDerivationPath {
+    /// Create a validated derivation path
+    pub fn new(organization: impl Into<String>, person: impl Into<String>) -> Result<Self, String> {
+        let path = Self {
+            organization: organization.into(),
+            person: person.into(),
+        };
+
+        for (name, value) in [("organization", &path.organization), ("person", &path.person)] {
+            if value.is_empty() {
+                return Err(format!("age derivation path {} must not be empty", name));
+            }
+            if value.contains('/') {
+                return Err(format!("age derivation path {} must not contain '/'", name));
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// HKDF info string for this path
+    pub fn to_info(&self) -> String {
+        format!(
+            "cim-keys/age/{}/{}/{}",
+            AGE_DERIVATION_VERSION, self.organization, self.person
+        )
+    }
+}
+
+impl std::fmt::Display for AgeDerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_info())
+    }
+}
+
+/// An age X25519 identity derived for one path
+///
+/// Security: the secret key is zeroized on drop and redacted from Debug.
+#[derive(Clone)]
+pub struct DerivedAgeKey {
+    pub path: AgeDerivationPath,
+    /// Recipient (`age1...`) that secrets are encrypted to
+    pub recipient: String,
+    secret_key: [u8; 32],
+}
+
+impl DerivedAgeKey {
+    /// Identity in age key file format (`AGE-SECRET-KEY-1...`)
+    ///
+    /// Write it to the admin's `keys.txt` only for the duration of a
+    /// deployment.
+    pub fn to_identity_string(&self) -> String {
+        encode_bech32(SECRET_KEY_HRP, &self.secret_key).to_uppercase()
+    }
+
+    /// Identity usable with the `age` crate for decryption
+    pub fn identity(&self) -> age::x25519::Identity {
+        let mut encoded = self.to_identity_string();
+        let identity = age::x25519::Identity::from_str(&encoded).expect("derived identity is valid Bech32");
+        encoded.zeroize();
+        identity
+    }
+}
+
+impl std::fmt::Debug for DerivedAgeKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedAgeKey")
+            .field("path", &self.path)
+            .field("recipient", &self.recipient)
+            .field("secret_key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Zeroize for DerivedAgeKey {
+    fn zeroize(&mut self) {
+        self.secret_key.zeroize();
+    }
+}
+
+impl Drop for DerivedAgeKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Derive an admin's age identity from the master seed
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let path = AgeDerivationPath::new("cowboyai", "alice")?;
+/// let key = derive_age_key(&master_seed, &path);
+/// println!("{}", key.recipient); // age1...
+/// ```
+pub fn derive_age_key(master_seed: &MasterSeed, path: &AgeDerivationPath) -> DerivedAgeKey {
+    let seed = master_seed.derive_child(&path.to_info());
+    let mut key = DerivedAgeKey {
+        path: path.clone(),
+        recipient: String::new(),
+        secret_key: *seed.as_bytes(),
+    };
+    key.recipient = key.identity().to_public().to_string();
+    key
+}
+
+/// Convert an `ssh-ed25519` public key to the age recipient of the same
+/// key pair, as `ssh-to-age` does
+///
+/// sops only encrypts to `age1...` recipients, so NixOS hosts identified
+/// by their SSH host key need this form in `.sops.yaml`; sops-nix derives
+/// the matching identity from `/etc/ssh/ssh_host_ed25519_key` on the host.
+pub fn ssh_ed25519_to_age_recipient(openssh_public: &str) -> Result<String, String> {
+    let public = ssh_key::PublicKey::from_openssh(openssh_public.trim())
+        .map_err(|e| format!("Invalid SSH public key: {}", e))?;
+    let ed25519 = public
+        .key_data()
+        .ed25519()
+        .ok_or_else(|| format!("{} keys have no age equivalent", public.algorithm()))?;
+    let verifying = ed25519_dalek::VerifyingKey::from_bytes(&ed25519.0)
+        .map_err(|e| format!("Invalid Ed25519 public key: {}", e))?;
+    Ok(encode_bech32(RECIPIENT_HRP, &verifying.to_montgomery().to_bytes()))
+}
+
+fn encode_bech32(hrp: &str, data: &[u8]) -> String {
+    let hrp = Hrp::parse(hrp).expect("age HRPs are valid");
+    bech32::encode::<Bech32>(hrp, data).expect("32 bytes fit in a Bech32 string")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::ssh_derivation::{derive_ssh_key, SshDerivationPath};
+    use sha2::Digest;
+
+    fn test_seed() -> MasterSeed {
+        MasterSeed::from_bytes([7u8; 32])
+    }
+
+    fn alice() -> AgeDerivationPath {
+        AgeDerivationPath::new("cowboyai", "alice").unwrap()
+    }
+
+    #[test]
+    fn test_derivation_is_deterministic_and_path_separated() {
+        let first = derive_age_key(&test_seed(), &alice());
+        let second = derive_age_key(&test_seed(), &alice());
+        let bob = derive_age_key(&test_seed(), &AgeDerivationPath::new("cowboyai", "bob").unwrap());
+
+        assert_eq!(first.recipient, second.recipient);
+        assert_ne!(first.recipient, bob.recipient);
+        assert!(first.recipient.starts_with("age1"));
+        assert!(first.to_identity_string().starts_with("AGE-SECRET-KEY-1"));
+        assert_eq!(alice().to_info(), "cim-keys/age/v1/cowboyai/alice");
+        assert!(format!("{:?}", first).contains("<redacted>"));
+    }
+
+    #[test]
+    fn test_derived_identity_decrypts_for_its_recipient() {
+        let key = derive_age_key(&test_seed(), &alice());
+        let recipient = age::x25519::Recipient::from_str(&key.recipient).unwrap();
+
+        let ciphertext = age::encrypt(&recipient, b"db-password").unwrap();
+        assert_eq!(age::decrypt(&key.identity(), &ciphertext).unwrap(), b"db-password");
+    }
+
+    #[test]
+    fn test_ssh_host_key_converts_to_age_recipient() {
+        let host = derive_ssh_key(&test_seed(), &SshDerivationPath::new("cowboyai", "web-1", "host").unwrap());
+        let recipient = ssh_ed25519_to_age_recipient(&host.to_openssh_public("root@web-1").unwrap()).unwrap();
+
+        // The host's X25519 scalar is the first half of SHA-512 of its Ed25519 seed
+        let scalar: [u8; 32] = sha2::Sha512::digest(host.secret_key_bytes())[..32].try_into().unwrap();
+        let host_identity =
+            age::x25519::Identity::from_str(&encode_bech32(SECRET_KEY_HRP, &scalar).to_uppercase()).unwrap();
+        assert_eq!(recipient, host_identity.to_public().to_string());
+        assert!(ssh_ed25519_to_age_recipient("ssh-ed25519 not-base64").is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! age encryption of exported artifacts
+//!
+//! Secret files leaving the ceremony machine (NATS credentials, key
+//! metadata, recovery material) are encrypted with age instead of relying
+//! on the medium being an encrypted partition. Two modes are supported:
+//!
+//! - **Recipients**: X25519 `age1...` recipients (usually from
+//!   [`super::age_derivation::derive_age_key`]) or `ssh-ed25519` keys.
+//!   Every recipient can decrypt on their own.
+//! - **Passphrase**: scrypt, for holders without a key of their own, such
+//!   as a backup holder opening their recovery bundle.
+//!
+//! Output is always ASCII-armored so it can be stored as text next to the
+//! plaintext artifacts of an export.
+
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+use age::armor::{ArmoredReader, ArmoredWriter, Format};
+use age::secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::kdf::{DerivedKey, KdfPurpose};
+
+/// File name suffix of age-encrypted artifacts
+pub const AGE_FILE_SUFFIX: &str = ".age";
+
+/// A person able to open age-encrypted artifacts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonRecipient {
+    pub person_id: Uuid,
+    /// `age1...` recipient or `ssh-ed25519` public key
+    pub recipient: String,
+}
+
+impl PersonRecipient {
+    pub fn new(person_id: Uuid, recipient: impl Into<String>) -> Self {
+        Self {
+            person_id,
+            recipient: recipient.into(),
+        }
+    }
+}
+
+/// Who can decrypt an artifact
+///
+/// Security: the passphrase is redacted from Debug and zeroized on drop.
+#[derive(Debug, Clone)]
+pub enum AgeProtection {
+    /// Any one of these recipients (`age1...` or `ssh-ed25519`)
+    Recipients(Vec<String>),
+    /// Anyone knowing the passphrase (scrypt)
+    Passphrase(SecretString),
+}
+
+impl AgeProtection {
+    /// Protection for a set of people
+    pub fn to_people(people: &[PersonRecipient]) -> Self {
+        AgeProtection::Recipients(people.iter().map(|p| p.recipient.clone()).collect())
+    }
+
+    /// Protection by passphrase
+    pub fn passphrase(passphrase: impl Into<String>) -> Self {
+        AgeProtection::Passphrase(SecretString::from(passphrase.into()))
+    }
+
+    /// Protection by a key derived for [`KdfPurpose::AgePassphrase`]
+    pub fn derived(key: &DerivedKey) -> Result<Self, String> {
+        key.age_passphrase()
+            .map(AgeProtection::Passphrase)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Parse an `age1...` recipient or an `ssh-ed25519`/`ssh-rsa` public key
+pub fn parse_recipient(recipient: &str) -> Result<Box<dyn age::Recipient>, String> {
+    let recipient = recipient.trim();
+    if recipient.starts_with("age1") {
+        age::x25519::Recipient::from_str(recipient)
+            .map(|r| Box::new(r) as Box<dyn age::Recipient>)
+            .map_err(|e| format!("Invalid age recipient: {}", e))
+    } else {
+        age::ssh::Recipient::from_str(recipient)
+            .map(|r| Box::new(r) as Box<dyn age::Recipient>)
+            .map_err(|e| format!("Invalid SSH recipient: {:?}", e))
+    }
+}
+
+/// Encrypt `plaintext` to already parsed recipients, armored
+pub fn encrypt_to_recipients(plaintext: &[u8], recipients: &[Box<dyn age::Recipient>]) -> Result<String, String> {
+    let encryptor = age::Encryptor::with_recipients(recipients.iter().map(|r| r.as_ref()))
+        .map_err(|e| format!("Failed to encrypt: {}", e))?;
+    write_armored(encryptor, plaintext)
+}
+
+/// Encrypt `plaintext` as `protection` allows, armored
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let key = derive_age_key(&master_seed, &AgeDerivationPath::new("cowboyai", "alice")?);
+/// let armored = encrypt_armored(b"secret", &AgeProtection::Recipients(vec![key.recipient.clone()]))?;
+/// assert_eq!(decrypt_with_identity(&armored, &key.identity())?, b"secret");
+/// ```
+pub fn encrypt_armored(plaintext: &[u8], protection: &AgeProtection) -> Result<String, String> {
+    match protection {
+        AgeProtection::Recipients(recipients) => {
+            if recipients.is_empty() {
+                return Err("Encryption needs at least one recipient".to_string());
+            }
+            let parsed = recipients
+                .iter()
+                .map(|r| parse_recipient(r))
+                .collect::<Result<Vec<_>, _>>()?;
+            encrypt_to_recipients(plaintext, &parsed)
+        }
+        AgeProtection::Passphrase(passphrase) => {
+            write_armored(age::Encryptor::with_user_passphrase(passphrase.clone()), plaintext)
+        }
+    }
+}
+
+/// Decrypt an armored file with an identity (X25519 or SSH)
+pub fn decrypt_with_identity(armored: &str, identity: &dyn age::Identity) -> Result<Vec<u8>, String> {
+    decrypt(armored, identity)
+}
+
+/// Decrypt an armored file encrypted with a passphrase
+pub fn decrypt_with_passphrase(armored: &str, passphrase: &SecretString) -> Result<Vec<u8>, String> {
+    decrypt(armored, &age::scrypt::Identity::new(passphrase.clone()))
+}
+
+fn write_armored(encryptor: age::Encryptor, plaintext: &[u8]) -> Result<String, String> {
+    let failed = |e: &dyn std::fmt::Display| format!("Failed to encrypt: {}", e);
+
+    let mut armored = Vec::new();
+    let output = ArmoredWriter::wrap_output(&mut armored, Format::AsciiArmor).map_err(|e| failed(&e))?;
+    let mut writer = encryptor.wrap_output(output).map_err(|e| failed(&e))?;
+    writer.write_all(plaintext).map_err(|e| failed(&e))?;
+    writer
+        .finish()
+        .and_then(|output| output.finish())
+        .map_err(|e| failed(&e))?;
+    String::from_utf8(armored).map_err(|e| failed(&e))
+}
+
+fn decrypt(armored: &str, identity: &dyn age::Identity) -> Result<Vec<u8>, String> {
+    let failed = |e: &dyn std::fmt::Display| format!("Failed to decrypt: {}", e);
+
+    let decryptor = age::Decryptor::new(ArmoredReader::new(armored.as_bytes())).map_err(|e| failed(&e))?;
+    let mut reader = decryptor.decrypt(std::iter::once(identity)).map_err(|e| failed(&e))?;
+    let mut plaintext = Vec::new();
+    reader.read_to_end(&mut plaintext).map_err(|e| failed(&e))?;
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::age_derivation::{derive_age_key, AgeDerivationPath};
+    use crate::crypto::seed_derivation::MasterSeed;
+
+    fn key(person: &str) -> crate::crypto::age_derivation::DerivedAgeKey {
+        derive_age_key(
+            &MasterSeed::from_bytes([7u8; 32]),
+            &AgeDerivationPath::new("cowboyai", person).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_every_recipient_decrypts() {
+        let (alice, bob, carol) = (key("alice"), key("bob"), key("carol"));
+        let protection = AgeProtection::to_people(&[
+            PersonRecipient::new(Uuid::now_v7(), alice.recipient.clone()),
+            PersonRecipient::new(Uuid::now_v7(), bob.recipient.clone()),
+        ]);
+
+        let armored = encrypt_armored(b"operator.creds", &protection).unwrap();
+        assert!(armored.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+        assert_eq!(
+            decrypt_with_identity(&armored, &alice.identity()).unwrap(),
+            b"operator.creds"
+        );
+        assert_eq!(
+            decrypt_with_identity(&armored, &bob.identity()).unwrap(),
+            b"operator.creds"
+        );
+        assert!(decrypt_with_identity(&armored, &carol.identity()).is_err());
+
+        assert!(encrypt_armored(b"x", &AgeProtection::Recipients(vec![])).is_err());
+        assert!(encrypt_armored(b"x", &AgeProtection::Recipients(vec!["age1nope".to_string()])).is_err());
+    }
+
+    #[test]
+    fn test_passphrase_mode_round_trips() {
+        let protection = AgeProtection::passphrase("correct horse battery staple");
+        let armored = encrypt_armored(b"share", &protection).unwrap();
+
+        let right = SecretString::from("correct horse battery staple".to_string());
+        let wrong = SecretString::from("wrong".to_string());
+        assert_eq!(decrypt_with_passphrase(&armored, &right).unwrap(), b"share");
+        assert!(decrypt_with_passphrase(&armored, &wrong).is_err());
+        assert!(format!("{:?}", protection).contains("REDACTED"));
+    }
+}
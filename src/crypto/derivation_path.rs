@@ -0,0 +1,535 @@
+//! Derivation path scheme shared by all seed-derived keys
+//!
+//! Every key derived from the `MasterSeed` sits at a path naming who it
+//! belongs to and what it is for:
+//!
+//! ```text
+//! cim-keys/{family}/v1/{organization}/{person}/{purpose}[/{index}]
+//!
+//! family   ssh | gpg | nats | tls
+//! person   person, service or host the key belongs to
+//! purpose  what the key is for (login, identity, operator, server, ...)
+//! index    rotation counter within the purpose, omitted while 0
+//! ```
+//!
+//! The path string is the HKDF info the key seed is derived with, so a
+//! recorded path is enough to derive the key again. SSH and GPG paths are
+//! the ones of [`super::ssh_derivation`] and [`super::gpg_derivation`];
+//! NATS nkeys and TLS key seeds are derived directly at their path.
+//!
+//! A [`DerivationRegistry`] maps key IDs to paths and the public identity
+//! the key had when it was derived, so an audit can re-derive every key
+//! from the seed and report the ones that no longer match.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::gpg_derivation::{derive_gpg_keyset, GpgDerivationPath};
+use super::key_generation::{generate_keypair_from_seed, KeyPair};
+use super::seed_derivation::MasterSeed;
+use super::ssh_derivation::{derive_ssh_key, SshDerivationPath};
+
+/// Derivation scheme version, part of every path
+const DERIVATION_VERSION: &str = "v1";
+
+const PATH_PREFIX: &str = "cim-keys";
+
+/// Kind of key a path derives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyFamily {
+    /// OpenSSH Ed25519 key
+    Ssh,
+    /// OpenPGP keyset (primary + subkeys)
+    Gpg,
+    /// NATS nkey (operator, account, user, ...)
+    Nats,
+    /// Seed of a TLS/X.509 key
+    Tls,
+}
+
+impl KeyFamily {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyFamily::Ssh => "ssh",
+            KeyFamily::Gpg => "gpg",
+            KeyFamily::Nats => "nats",
+            KeyFamily::Tls => "tls",
+        }
+    }
+}
+
+impl std::fmt::Display for KeyFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for KeyFamily {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ssh" => Ok(KeyFamily::Ssh),
+            "gpg" => Ok(KeyFamily::Gpg),
+            "nats" => Ok(KeyFamily::Nats),
+            "tls" => Ok(KeyFamily::Tls),
+            other => Err(format!("Unknown key family {:?}", other)),
+        }
+    }
+}
+
+/// Where a key sits in the derivation hierarchy
+///
+/// Components must be non-empty and must not contain `/`. Serialized as
+/// its path string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DerivationPath {
+    pub family: KeyFamily,
+    pub organization: String,
+    pub person: String,
+    pub purpose: String,
+    pub index: u32,
+}
+
+impl DerivationPath {
+    /// Create a validated path with index 0
+    pub fn new(
+        family: KeyFamily,
+        organization: impl Into<String>,
+        person: impl Into<String>,
+        purpose: impl Into<String>,
+    ) -> Result<Self, String> {
+        let path = Self {
+            family,
+            organization: organization.into(),
+            person: person.into(),
+            purpose: purpose.into(),
+            index: 0,
+        };
+
+        for (name, value) in [
+            ("organization", &path.organization),
+            ("person", &path.person),
+            ("purpose", &path.purpose),
+        ] {
+            if value.is_empty() {
+                return Err(format!("Derivation path {} must not be empty", name));
+            }
+            if value.contains('/') {
+                return Err(format!("Derivation path {} must not contain '/'", name));
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// The `index`-th key of the same purpose
+    pub fn with_index(mut self, index: u32) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// The path of the next key of the same purpose, for rotation
+    pub fn next(&self) -> Self {
+        self.clone().with_index(self.index + 1)
+    }
+
+    /// HKDF info string for this path
+    pub fn to_info(&self) -> String {
+        let info = format!(
+            "{}/{}/{}/{}/{}/{}",
+            PATH_PREFIX,
+            self.family,
+            DERIVATION_VERSION,
+            self.organization,
+            self.person,
+            self.purpose
+        );
+        match self.index {
+            0 => info,
+            index => format!("{}/{}", info, index),
+        }
+    }
+
+    /// Key seed at this path
+    pub fn derive_seed(&self, master_seed: &MasterSeed) -> MasterSeed {
+        master_seed.derive_child(&self.to_info())
+    }
+
+    /// The same path as an SSH derivation path
+    pub fn to_ssh_path(&self) -> Result<SshDerivationPath, String> {
+        self.require_family(KeyFamily::Ssh)?;
+        Ok(
+            SshDerivationPath::new(&self.organization, &self.person, &self.purpose)?
+                .with_index(self.index),
+        )
+    }
+
+    /// The same path as an OpenPGP derivation path
+    pub fn to_gpg_path(&self) -> Result<GpgDerivationPath, String> {
+        self.require_family(KeyFamily::Gpg)?;
+        Ok(
+            GpgDerivationPath::new(&self.organization, &self.person, &self.purpose)?
+                .with_index(self.index),
+        )
+    }
+
+    fn require_family(&self, family: KeyFamily) -> Result<(), String> {
+        if self.family == family {
+            Ok(())
+        } else {
+            Err(format!("{} is not a {} path", self, family))
+        }
+    }
+}
+
+impl std::fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_info())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = String;
+
+    /// Parse a recorded path string
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split('/').collect();
+        let (family, version, organization, person, purpose, index) = match parts[..] {
+            [PATH_PREFIX, family, version, organization, person, purpose] => {
+                (family, version, organization, person, purpose, None)
+            }
+            [PATH_PREFIX, family, version, organization, person, purpose, index] => {
+                (family, version, organization, person, purpose, Some(index))
+            }
+            _ => return Err(format!("{:?} is not a cim-keys derivation path", s)),
+        };
+        if version != DERIVATION_VERSION {
+            return Err(format!(
+                "Unsupported derivation scheme version {:?}",
+                version
+            ));
+        }
+
+        let index = match index {
+            // Index 0 is written without a suffix, so "/0" has no canonical form
+            Some(index) => index
+                .parse::<u32>()
+                .ok()
+                .filter(|&index| index > 0)
+                .ok_or_else(|| format!("Invalid derivation index {:?}", index))?,
+            None => 0,
+        };
+        Ok(Self::new(family.parse()?, organization, person, purpose)?.with_index(index))
+    }
+}
+
+impl TryFrom<String> for DerivationPath {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<DerivationPath> for String {
+    fn from(path: DerivationPath) -> Self {
+        path.to_info()
+    }
+}
+
+impl From<&SshDerivationPath> for DerivationPath {
+    fn from(path: &SshDerivationPath) -> Self {
+        Self {
+            family: KeyFamily::Ssh,
+            organization: path.organization.clone(),
+            person: path.person.clone(),
+            purpose: path.purpose.clone(),
+            index: path.index,
+        }
+    }
+}
+
+impl From<&GpgDerivationPath> for DerivationPath {
+    fn from(path: &GpgDerivationPath) -> Self {
+        Self {
+            family: KeyFamily::Gpg,
+            organization: path.organization.clone(),
+            person: path.person.clone(),
+            purpose: path.purpose.clone(),
+            index: path.index,
+        }
+    }
+}
+
+/// Derive a NATS nkey of `kind` at `path`
+pub fn derive_nats_key(
+    master_seed: &MasterSeed,
+    path: &DerivationPath,
+    kind: nkeys::KeyPairType,
+) -> Result<nkeys::KeyPair, String> {
+    path.require_family(KeyFamily::Nats)?;
+    let seed = path.derive_seed(master_seed);
+    nkeys::KeyPair::new_from_raw(kind, *seed.as_bytes())
+        .map_err(|e| format!("Failed to derive nkey: {}", e))
+}
+
+/// Derive the Ed25519 key pair of a TLS key at `path`
+///
+/// Certificate generation takes the seed itself ([`DerivationPath::derive_seed`]);
+/// this is the key pair it records as `public_key_bytes`.
+pub fn derive_tls_keypair(
+    master_seed: &MasterSeed,
+    path: &DerivationPath,
+) -> Result<KeyPair, String> {
+    path.require_family(KeyFamily::Tls)?;
+    Ok(generate_keypair_from_seed(&path.derive_seed(master_seed)))
+}
+
+// ============================================================================
+// REGISTRY
+// ============================================================================
+
+/// A derived key as recorded when it was issued
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivationRecord {
+    pub key_id: Uuid,
+    pub path: DerivationPath,
+    /// SSH/GPG fingerprint, NATS public nkey or hex TLS public key
+    pub public_id: String,
+    /// OpenPGP creation time, part of the GPG fingerprint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl DerivationRecord {
+    /// Derive the key at the recorded path again and return its identity
+    /// in the form it was recorded in
+    pub fn derive_public_id(&self, master_seed: &MasterSeed) -> Result<String, String> {
+        public_id(master_seed, self)
+    }
+}
+
+/// A key whose path no longer derives its recorded identity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationMismatch {
+    pub key_id: Uuid,
+    pub path: DerivationPath,
+    pub expected: String,
+    /// Identity derived now, or why it could not be derived
+    pub found: Result<String, String>,
+}
+
+/// Paths of all keys derived from one master seed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivationRegistry {
+    records: BTreeMap<Uuid, DerivationRecord>,
+}
+
+impl DerivationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the path a key was derived at
+    ///
+    /// A path can only be registered once: two keys at the same path would
+    /// be the same key.
+    pub fn register(&mut self, record: DerivationRecord) -> Result<(), String> {
+        if let Some(existing) = self
+            .records
+            .values()
+            .find(|existing| existing.path == record.path && existing.key_id != record.key_id)
+        {
+            return Err(format!(
+                "{} is already used by key {}",
+                record.path, existing.key_id
+            ));
+        }
+        self.records.insert(record.key_id, record);
+        Ok(())
+    }
+
+    pub fn get(&self, key_id: Uuid) -> Option<&DerivationRecord> {
+        self.records.get(&key_id)
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = &DerivationRecord> {
+        self.records.values()
+    }
+
+    /// Next unused index for a purpose, for rotating to a new key
+    pub fn next_path(&self, path: &DerivationPath) -> DerivationPath {
+        let used = self
+            .records
+            .values()
+            .filter(|record| {
+                DerivationPath {
+                    index: 0,
+                    ..record.path.clone()
+                } == DerivationPath {
+                    index: 0,
+                    ..path.clone()
+                }
+            })
+            .map(|record| record.path.index + 1)
+            .max()
+            .unwrap_or(0);
+        path.clone().with_index(used)
+    }
+
+    /// Re-derive every registered key and report the ones whose identity
+    /// differs from the recorded one
+    pub fn audit(&self, master_seed: &MasterSeed) -> Vec<DerivationMismatch> {
+        self.records
+            .values()
+            .filter_map(|record| {
+                let found = public_id(master_seed, record);
+                if found.as_deref() == Ok(record.public_id.as_str()) {
+                    None
+                } else {
+                    Some(DerivationMismatch {
+                        key_id: record.key_id,
+                        path: record.path.clone(),
+                        expected: record.public_id.clone(),
+                        found,
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+/// Identity of the key at `record.path`, in the form it was recorded in
+fn public_id(master_seed: &MasterSeed, record: &DerivationRecord) -> Result<String, String> {
+    match record.path.family {
+        KeyFamily::Ssh => Ok(derive_ssh_key(master_seed, &record.path.to_ssh_path()?).fingerprint),
+        KeyFamily::Gpg => {
+            let created_at = record
+                .created_at
+                .ok_or_else(|| "GPG record has no creation time".to_string())?;
+            let keyset = derive_gpg_keyset(master_seed, &record.path.to_gpg_path()?, created_at)?;
+            Ok(keyset.fingerprint().to_string())
+        }
+        KeyFamily::Nats => {
+            // The recorded key's prefix says which kind of nkey it is
+            let recorded = nkeys::KeyPair::from_public_key(&record.public_id)
+                .map_err(|e| format!("Recorded nkey is invalid: {}", e))?;
+            Ok(derive_nats_key(master_seed, &record.path, recorded.key_pair_type())?.public_key())
+        }
+        KeyFamily::Tls => Ok(hex::encode(
+            derive_tls_keypair(master_seed, &record.path)?.public_key_bytes(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_seed() -> MasterSeed {
+        MasterSeed::from_bytes([7u8; 32])
+    }
+
+    #[test]
+    fn test_paths_round_trip_and_match_family_paths() {
+        let path = DerivationPath::new(KeyFamily::Ssh, "cowboyai", "alice", "login").unwrap();
+        let ssh = SshDerivationPath::new("cowboyai", "alice", "login").unwrap();
+        assert_eq!(path.to_info(), ssh.to_info());
+        assert_eq!(
+            path.next().to_info(),
+            "cim-keys/ssh/v1/cowboyai/alice/login/1"
+        );
+        assert_eq!(
+            path.next().to_ssh_path().unwrap().to_info(),
+            ssh.with_index(1).to_info()
+        );
+        assert!(path.to_gpg_path().is_err());
+
+        for text in [
+            "cim-keys/nats/v1/cowboyai/orders/account",
+            "cim-keys/tls/v1/cowboyai/web-1/server/3",
+        ] {
+            let parsed: DerivationPath = text.parse().unwrap();
+            assert_eq!(parsed.to_string(), text);
+            assert_eq!(
+                serde_json::to_string(&parsed).unwrap(),
+                format!("\"{}\"", text)
+            );
+        }
+        assert!("cim-keys/ssh/v1/cowboyai/alice/login/0"
+            .parse::<DerivationPath>()
+            .is_err());
+        assert!("cim-keys/ssh/v2/cowboyai/alice/login"
+            .parse::<DerivationPath>()
+            .is_err());
+        assert!("cim-keys/ssh/v1/cowboyai/alice"
+            .parse::<DerivationPath>()
+            .is_err());
+    }
+
+    #[test]
+    fn test_registry_audit_rederives_every_family() {
+        let seed = test_seed();
+        let ssh = DerivationPath::new(KeyFamily::Ssh, "cowboyai", "alice", "login").unwrap();
+        let gpg = DerivationPath::new(KeyFamily::Gpg, "cowboyai", "alice", "identity").unwrap();
+        let nats = DerivationPath::new(KeyFamily::Nats, "cowboyai", "orders", "account").unwrap();
+        let tls = DerivationPath::new(KeyFamily::Tls, "cowboyai", "web-1", "server").unwrap();
+        let created_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+
+        let account = derive_nats_key(&seed, &nats, nkeys::KeyPairType::Account).unwrap();
+        assert!(account.public_key().starts_with('A'));
+        let mut registry = DerivationRegistry::new();
+        let records = [
+            (
+                ssh.clone(),
+                derive_ssh_key(&seed, &ssh.to_ssh_path().unwrap()).fingerprint,
+                None,
+            ),
+            (
+                gpg.clone(),
+                derive_gpg_keyset(&seed, &gpg.to_gpg_path().unwrap(), created_at)
+                    .unwrap()
+                    .fingerprint()
+                    .to_string(),
+                Some(created_at),
+            ),
+            (nats.clone(), account.public_key(), None),
+            (
+                tls.clone(),
+                hex::encode(derive_tls_keypair(&seed, &tls).unwrap().public_key_bytes()),
+                None,
+            ),
+        ];
+        for (path, public_id, created_at) in records {
+            registry
+                .register(DerivationRecord {
+                    key_id: Uuid::now_v7(),
+                    path,
+                    public_id,
+                    created_at,
+                })
+                .unwrap();
+        }
+        assert!(registry.audit(&seed).is_empty());
+
+        // Another seed derives none of them
+        assert_eq!(registry.audit(&MasterSeed::from_bytes([8u8; 32])).len(), 4);
+
+        // A path is never reused; rotation moves to the next index
+        let reused = DerivationRecord {
+            key_id: Uuid::now_v7(),
+            path: ssh.clone(),
+            public_id: String::new(),
+            created_at: None,
+        };
+        assert!(registry.register(reused).is_err());
+        assert_eq!(registry.next_path(&ssh).index, 1);
+    }
+}
@@ -0,0 +1,413 @@
+//! Ceremony entropy collection and mixing
+//!
+//! A randomly generated master seed (as opposed to one derived from a
+//! passphrase) is only as good as the randomness that went into it. At a
+//! key ceremony the seed is mixed from several independent sources, so a
+//! single broken or backdoored source cannot weaken it:
+//!
+//! ```text
+//! OS CSPRNG (512 bytes)  ─┐
+//! Dice rolls             ─┼─ health tests ─ HKDF-SHA256 ─→ MasterSeed
+//! Hardware TRNG (opt.)   ─┘                              └→ EntropyReport
+//! ```
+//!
+//! Every source goes through the SP 800-90B continuous health tests
+//! (repetition count and adaptive proportion) and is credited with a
+//! conservative amount of min-entropy. Mixing fails unless all tests pass
+//! and the sources together are credited with at least 256 bits.
+//!
+//! The [`EntropyReport`] records, per source, the samples taken, the bits
+//! credited, the test results and a SHA-256 commitment to the raw input.
+//! Witnesses keeping their input (the dice transcript, say) can check it
+//! was included; mixing the same inputs reproduces the same seed. The
+//! report never contains the seed, so it can go into the event log.
+//! A commitment to a low-entropy input can be brute-forced, which is why
+//! the seed's secrecy rests on the combined credit, not on any one source.
+
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::secure_memory::SecretBytes;
+use super::seed_derivation::MasterSeed;
+use crate::ports::rng::CryptoRngPort;
+
+/// Min-entropy the mixed sources must be credited with
+pub const REQUIRED_ENTROPY_BITS: f64 = 256.0;
+
+/// Bytes drawn from the OS CSPRNG (enough for the adaptive proportion test)
+pub const OS_SAMPLE_BYTES: usize = 512;
+
+/// HKDF salt of the mix
+const MIX_SALT: &[u8] = b"cim-keys/ceremony-entropy/v1";
+
+/// SP 800-90B adaptive proportion test window for non-binary samples
+const APT_WINDOW: usize = 512;
+
+/// False positive rate of the health tests (2^-20, as recommended)
+const HEALTH_TEST_ALPHA_BITS: f64 = 20.0;
+
+/// Kind of entropy source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntropySourceKind {
+    /// Operating system CSPRNG
+    OperatingSystem,
+    /// Physical dice rolled by the ceremony officers
+    Dice,
+    /// Hardware random number generator (YubiKey, HSM, TPM, USB TRNG)
+    HardwareTrng,
+}
+
+/// SP 800-90B continuous health test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HealthTest {
+    RepetitionCount,
+    AdaptiveProportion,
+}
+
+/// Outcome of one health test on one source
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthTestResult {
+    pub test: HealthTest,
+    pub passed: bool,
+    /// Observed statistic and cutoff, for the ceremony record
+    pub detail: String,
+}
+
+/// What one source contributed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceReport {
+    pub kind: EntropySourceKind,
+    /// Human readable source name (device, "d6", ...)
+    pub name: String,
+    /// Samples taken (bytes or rolls)
+    pub samples: usize,
+    /// Min-entropy credited to the source, capped at 256 bits
+    pub credited_bits: f64,
+    /// SHA-256 commitment to the raw input, hex
+    pub commitment: String,
+    pub health_tests: Vec<HealthTestResult>,
+}
+
+/// Record of how a master seed's entropy was collected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntropyReport {
+    pub sources: Vec<SourceReport>,
+    /// Sum of the sources' credit, capped at 256 bits
+    pub total_credited_bits: f64,
+    /// Mixing function
+    pub mixing: String,
+}
+
+impl EntropyReport {
+    /// Whether every health test passed and enough entropy was credited
+    pub fn passed(&self) -> bool {
+        self.total_credited_bits >= REQUIRED_ENTROPY_BITS
+            && self.sources.iter().flat_map(|s| &s.health_tests).all(|t| t.passed)
+    }
+}
+
+/// Entropy collection errors
+#[derive(Debug, Error)]
+pub enum EntropyError {
+    #[error("No entropy sources")]
+    NoSources,
+
+    #[error("Invalid dice roll: {0}")]
+    InvalidDiceRoll(String),
+
+    #[error("Health test {test:?} failed for {source_name}: {detail}")]
+    HealthTestFailed {
+        source_name: String,
+        test: HealthTest,
+        detail: String,
+    },
+
+    #[error("Insufficient entropy: {credited:.1} bits credited, {required:.0} required")]
+    InsufficientEntropy { credited: f64, required: f64 },
+}
+
+/// Raw input of one source
+#[derive(Debug, Clone)]
+struct CollectedSource {
+    kind: EntropySourceKind,
+    name: String,
+    samples: SecretBytes,
+    /// Min-entropy per sample, bits
+    entropy_per_sample: f64,
+}
+
+impl CollectedSource {
+    fn report(&self) -> SourceReport {
+        let mut commitment = Sha256::new();
+        commitment.update(b"cim-keys/entropy-commitment/v1");
+        commitment.update(self.name.as_bytes());
+        commitment.update(&self.samples[..]);
+
+        SourceReport {
+            kind: self.kind,
+            name: self.name.clone(),
+            samples: self.samples.len(),
+            credited_bits: (self.samples.len() as f64 * self.entropy_per_sample).min(REQUIRED_ENTROPY_BITS),
+            commitment: hex::encode(commitment.finalize()),
+            health_tests: vec![
+                repetition_count_test(&self.samples, self.entropy_per_sample),
+                adaptive_proportion_test(&self.samples, self.entropy_per_sample),
+            ],
+        }
+    }
+}
+
+/// Collects entropy from several sources and mixes it into a master seed
+#[derive(Debug, Clone, Default)]
+pub struct EntropyMixer {
+    sources: Vec<CollectedSource>,
+}
+
+impl EntropyMixer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw [`OS_SAMPLE_BYTES`] from the OS CSPRNG, credited at 8 bits per byte
+    pub fn with_os_entropy(mut self, rng: &dyn CryptoRngPort) -> Self {
+        let mut samples = SecretBytes::zeroed(OS_SAMPLE_BYTES);
+        rng.fill_bytes(&mut samples);
+        self.sources.push(CollectedSource {
+            kind: EntropySourceKind::OperatingSystem,
+            name: "os".to_string(),
+            samples,
+            entropy_per_sample: 8.0,
+        });
+        self
+    }
+
+    /// Add rolls of fair `sides`-sided dice (values `1..=sides`), credited
+    /// at log2(sides) bits per roll
+    pub fn with_dice_rolls(mut self, rolls: &[u8], sides: u8) -> Result<Self, EntropyError> {
+        if sides < 2 {
+            return Err(EntropyError::InvalidDiceRoll(format!("a die needs at least 2 sides, got {}", sides)));
+        }
+        if let Some(roll) = rolls.iter().find(|&&roll| roll == 0 || roll > sides) {
+            return Err(EntropyError::InvalidDiceRoll(format!("{} is not a face of a d{}", roll, sides)));
+        }
+        self.sources.push(CollectedSource {
+            kind: EntropySourceKind::Dice,
+            name: format!("d{}", sides),
+            samples: SecretBytes::from_slice(rolls),
+            entropy_per_sample: f64::from(sides).log2(),
+        });
+        Ok(self)
+    }
+
+    /// Draw `bytes` from a hardware TRNG, credited at `entropy_per_byte`
+    /// bits per byte (the device's assessed min-entropy, at most 8)
+    pub fn with_hardware_trng(
+        mut self,
+        name: &str,
+        rng: &dyn CryptoRngPort,
+        bytes: usize,
+        entropy_per_byte: f64,
+    ) -> Self {
+        let mut samples = SecretBytes::zeroed(bytes);
+        rng.fill_bytes(&mut samples);
+        self.sources.push(CollectedSource {
+            kind: EntropySourceKind::HardwareTrng,
+            name: name.to_string(),
+            samples,
+            entropy_per_sample: entropy_per_byte.clamp(0.0, 8.0),
+        });
+        self
+    }
+
+    /// Report on the collected entropy without mixing it
+    pub fn report(&self) -> EntropyReport {
+        let sources: Vec<SourceReport> = self.sources.iter().map(CollectedSource::report).collect();
+        let total = sources.iter().map(|s| s.credited_bits).sum::<f64>();
+        EntropyReport {
+            sources,
+            total_credited_bits: total.min(REQUIRED_ENTROPY_BITS),
+            mixing: "HKDF-SHA256".to_string(),
+        }
+    }
+
+    /// Mix the sources into a master seed for `organization_id`
+    ///
+    /// Fails if a health test failed or less than 256 bits were credited.
+    pub fn mix(&self, organization_id: &str) -> Result<(MasterSeed, EntropyReport), EntropyError> {
+        if self.sources.is_empty() {
+            return Err(EntropyError::NoSources);
+        }
+
+        let report = self.report();
+        for source in &report.sources {
+            if let Some(failed) = source.health_tests.iter().find(|t| !t.passed) {
+                return Err(EntropyError::HealthTestFailed {
+                    source_name: source.name.clone(),
+                    test: failed.test,
+                    detail: failed.detail.clone(),
+                });
+            }
+        }
+        if report.total_credited_bits < REQUIRED_ENTROPY_BITS {
+            return Err(EntropyError::InsufficientEntropy {
+                credited: report.total_credited_bits,
+                required: REQUIRED_ENTROPY_BITS,
+            });
+        }
+
+        // Length-prefixed so no source can shift bytes into another
+        let ikm_len = self.sources.iter().map(|s| 8 + s.samples.len()).sum();
+        let mut ikm = SecretBytes::zeroed(ikm_len);
+        let mut offset = 0;
+        for source in &self.sources {
+            ikm[offset..offset + 8].copy_from_slice(&(source.samples.len() as u64).to_be_bytes());
+            ikm[offset + 8..offset + 8 + source.samples.len()].copy_from_slice(&source.samples);
+            offset += 8 + source.samples.len();
+        }
+
+        let hkdf = Hkdf::<Sha256>::new(Some(MIX_SALT), &ikm);
+        let mut seed = [0u8; 32];
+        hkdf.expand(organization_id.as_bytes(), &mut seed)
+            .expect("32 bytes is a valid HKDF output length");
+
+        Ok((MasterSeed::from_bytes(seed), report))
+    }
+}
+
+/// SP 800-90B §4.4.1: fail on a run of identical samples whose probability
+/// at the claimed min-entropy is below 2^-20
+fn repetition_count_test(samples: &[u8], entropy_per_sample: f64) -> HealthTestResult {
+    let cutoff = ((HEALTH_TEST_ALPHA_BITS / entropy_per_sample).ceil() as usize).saturating_add(1);
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous = None;
+    for &sample in samples {
+        if previous == Some(sample) {
+            run += 1;
+        } else {
+            run = 1;
+            previous = Some(sample);
+        }
+        longest = longest.max(run);
+    }
+    HealthTestResult {
+        test: HealthTest::RepetitionCount,
+        passed: longest < cutoff,
+        detail: format!("longest run {} (cutoff {})", longest, cutoff),
+    }
+}
+
+/// SP 800-90B §4.4.2: fail when the first sample of a 512-sample window
+/// recurs more often than the claimed min-entropy allows
+///
+/// Short inputs (dice) are reported as skipped.
+fn adaptive_proportion_test(samples: &[u8], entropy_per_sample: f64) -> HealthTestResult {
+    if samples.len() < APT_WINDOW {
+        return HealthTestResult {
+            test: HealthTest::AdaptiveProportion,
+            passed: true,
+            detail: format!("skipped, {} samples (window {})", samples.len(), APT_WINDOW),
+        };
+    }
+
+    let cutoff = adaptive_proportion_cutoff(2f64.powf(-entropy_per_sample));
+
+    let worst = samples
+        .chunks_exact(APT_WINDOW)
+        .map(|window| window.iter().filter(|&&s| s == window[0]).count())
+        .max()
+        .unwrap_or(0);
+    HealthTestResult {
+        test: HealthTest::AdaptiveProportion,
+        passed: worst < cutoff,
+        detail: format!("max first-sample count {} (cutoff {})", worst, cutoff),
+    }
+}
+
+/// Smallest count of the first sample in a window that has probability
+/// at most 2^-20 for samples repeating with probability `p`
+fn adaptive_proportion_cutoff(p: f64) -> usize {
+    if p >= 1.0 {
+        return APT_WINDOW + 1;
+    }
+    // Binomial(W - 1, p) for the other samples of the window
+    let n = APT_WINDOW - 1;
+    let alpha = 2f64.powf(-HEALTH_TEST_ALPHA_BITS);
+    let mut pmf = (1.0 - p).powi(n as i32);
+    let mut cdf = pmf;
+    let mut k = 0;
+    while 1.0 - cdf > alpha && k < n {
+        k += 1;
+        pmf *= (n - k + 1) as f64 / k as f64 * p / (1.0 - p);
+        cdf += pmf;
+    }
+    // P(others > k) <= alpha, counting the first sample itself
+    k + 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::seeded_rng::SeededCryptoRng;
+    use crate::ports::rng::OsCryptoRng;
+
+    /// Source that returns the same byte forever
+    #[derive(Debug)]
+    struct StuckRng;
+
+    impl CryptoRngPort for StuckRng {
+        fn fill_bytes(&self, dest: &mut [u8]) {
+            dest.fill(0xAA);
+        }
+    }
+
+    fn dice() -> Vec<u8> {
+        (0..100).map(|i| (i * 7 % 6 + 1) as u8).collect()
+    }
+
+    #[test]
+    fn test_mix_reports_sources_and_is_reproducible() {
+        let mix = || {
+            EntropyMixer::new()
+                .with_os_entropy(&SeededCryptoRng::from_label("ceremony"))
+                .with_dice_rolls(&dice(), 6)
+                .unwrap()
+                .mix("org-1")
+                .unwrap()
+        };
+        let (seed, report) = mix();
+        assert!(report.passed());
+        assert_eq!(report.sources.len(), 2);
+        assert_eq!(report.sources[0].credited_bits, 256.0);
+        assert!((report.sources[1].credited_bits - 100.0 * 6f64.log2()).abs() < 1e-9);
+        assert_eq!(report.total_credited_bits, 256.0);
+
+        let (again, again_report) = mix();
+        assert_eq!(seed.as_bytes(), again.as_bytes());
+        assert_eq!(report, again_report);
+
+        let serialized = serde_json::to_string(&report).unwrap();
+        assert!(!serialized.contains(&hex::encode(seed.as_bytes())));
+    }
+
+    #[test]
+    fn test_stuck_source_fails_health_tests() {
+        let result = EntropyMixer::new()
+            .with_os_entropy(&OsCryptoRng)
+            .with_hardware_trng("stuck", &StuckRng, 64, 8.0)
+            .mix("org-1");
+        assert!(matches!(
+            result,
+            Err(EntropyError::HealthTestFailed { test: HealthTest::RepetitionCount, .. })
+        ));
+    }
+
+    #[test]
+    fn test_dice_alone_insufficient_and_invalid_rolls_rejected() {
+        let mixer = EntropyMixer::new().with_dice_rolls(&dice(), 6).unwrap();
+        assert!(matches!(mixer.mix("org-1"), Err(EntropyError::InsufficientEntropy { .. })));
+        assert!(EntropyMixer::new().with_dice_rolls(&[1, 7], 6).is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! Envelope encryption to key-agreement keys
+//!
+//! An ECIES-style scheme for sealing arbitrary payloads to one or more
+//! recipients identified by their `KeyId`:
+//!
+//! ```text
+//! Random content key (256-bit)
+//!   ↓ AES-256-GCM, random 96-bit nonce
+//! Payload ciphertext
+//!
+//! Per recipient:
+//!   Ephemeral X25519 secret × recipient X25519 public key
+//!     ↓ HKDF-SHA256 (salt: ephemeral ‖ recipient public key,
+//!     ↓              info: "cim-keys/envelope/v1")
+//!   Key-encryption key
+//!     ↓ AES-256-GCM
+//!   Wrapped content key
+//! ```
+//!
+//! One ephemeral key is used per envelope; each recipient's wrapping key
+//! is still distinct because the recipient public key is part of both the
+//! shared secret and the HKDF salt.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
+
+use super::key_generation::KeyAgreementKeyPair;
+use crate::domain::ids::KeyId;
+use crate::ports::rng::{CryptoRngPort, OsCryptoRng};
+
+/// HKDF info for wrapping keys; also bound into every ciphertext
+const ENVELOPE_CONTEXT: &str = "cim-keys/envelope/v1";
+
+/// Algorithm tag written into envelopes
+const ALGORITHM: &str = "x25519-hkdf-sha256-aes-256-gcm";
+
+/// A payload sealed to one or more key-agreement keys
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Envelope {
+    /// Always `x25519-hkdf-sha256-aes-256-gcm`
+    pub encryption: String,
+    /// Ephemeral X25519 public key, base64
+    pub ephemeral_public_key: String,
+    pub recipients: Vec<EnvelopeRecipient>,
+    /// 96-bit payload nonce, base64
+    pub nonce: String,
+    /// Payload ciphertext and tag, base64
+    pub ciphertext: String,
+}
+
+/// The content key wrapped for one recipient
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvelopeRecipient {
+    pub key_id: KeyId,
+    /// Nonce followed by the wrapped content key and tag, base64
+    pub wrapped_key: String,
+}
+
+/// Seals payloads to recipients named by key ID
+pub trait Encryptor: Send + Sync {
+    /// Encrypt `plaintext` so that any one of `recipients` can open it
+    fn encrypt(&self, plaintext: &[u8], recipients: &[KeyId]) -> Result<Envelope, EnvelopeError>;
+}
+
+/// [`Encryptor`] over known X25519 public keys
+#[derive(Debug, Clone)]
+pub struct X25519Encryptor {
+    public_keys: HashMap<KeyId, X25519PublicKey>,
+    rng: Arc<dyn CryptoRngPort>,
+}
+
+impl Default for X25519Encryptor {
+    fn default() -> Self {
+        Self {
+            public_keys: HashMap::new(),
+            rng: Arc::new(OsCryptoRng),
+        }
+    }
+}
+
+impl X25519Encryptor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw content keys, nonces and ephemeral keys from `rng`
+    pub fn with_rng(mut self, rng: Arc<dyn CryptoRngPort>) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Register the key-agreement public key of `key_id`
+    pub fn with_recipient(mut self, key_id: KeyId, public_key: [u8; 32]) -> Self {
+        self.add_recipient(key_id, public_key);
+        self
+    }
+
+    /// Register the key-agreement public key of `key_id`
+    pub fn add_recipient(&mut self, key_id: KeyId, public_key: [u8; 32]) {
+        self.public_keys.insert(key_id, X25519PublicKey::from(public_key));
+    }
+}
+
+impl Encryptor for X25519Encryptor {
+    fn encrypt(&self, plaintext: &[u8], recipients: &[KeyId]) -> Result<Envelope, EnvelopeError> {
+        if recipients.is_empty() {
+            return Err(EnvelopeError::NoRecipients);
+        }
+        let public_keys = recipients
+            .iter()
+            .map(|key_id| {
+                self.public_keys
+                    .get(key_id)
+                    .map(|public_key| (*key_id, public_key))
+                    .ok_or(EnvelopeError::UnknownRecipient(*key_id))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rng = self.rng.as_ref();
+        let content_key: [u8; 32] = rng.array();
+        let nonce: [u8; 12] = rng.array();
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&content_key))
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: ENVELOPE_CONTEXT.as_bytes() })
+            .map_err(|_| EnvelopeError::EncryptionFailed)?;
+
+        let ephemeral = StaticSecret::random_from_rng(rng.rng());
+        let ephemeral_public = X25519PublicKey::from(&ephemeral);
+        let recipients = public_keys
+            .into_iter()
+            .map(|(key_id, public_key)| {
+                let shared = ephemeral.diffie_hellman(public_key);
+                if !shared.was_contributory() {
+                    return Err(EnvelopeError::InvalidPublicKey(key_id));
+                }
+                let wrapping = wrapping_cipher(shared.as_bytes(), &ephemeral_public, public_key);
+                let wrap_nonce: [u8; 12] = rng.array();
+                let mut wrapped = wrap_nonce.to_vec();
+                wrapped.extend(
+                    wrapping
+                        .encrypt(Nonce::from_slice(&wrap_nonce), content_key.as_slice())
+                        .map_err(|_| EnvelopeError::EncryptionFailed)?,
+                );
+                Ok(EnvelopeRecipient {
+                    key_id,
+                    wrapped_key: STANDARD.encode(wrapped),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Envelope {
+            encryption: ALGORITHM.to_string(),
+            ephemeral_public_key: STANDARD.encode(ephemeral_public.as_bytes()),
+            recipients,
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+    }
+}
+
+impl Envelope {
+    /// Decrypt the payload as recipient `key_id`
+    pub fn open(&self, key_id: KeyId, key: &KeyAgreementKeyPair) -> Result<Vec<u8>, EnvelopeError> {
+        if self.encryption != ALGORITHM {
+            return Err(EnvelopeError::UnsupportedAlgorithm(self.encryption.clone()));
+        }
+        let recipient = self
+            .recipients
+            .iter()
+            .find(|r| r.key_id == key_id)
+            .ok_or(EnvelopeError::NotARecipient(key_id))?;
+
+        let ephemeral_public = X25519PublicKey::from(decode_array::<32>(&self.ephemeral_public_key, "ephemeral key")?);
+        let wrapped = decode(&recipient.wrapped_key, "wrapped key")?;
+        if wrapped.len() < 12 {
+            return Err(EnvelopeError::Malformed("wrapped key too short".to_string()));
+        }
+        let (wrap_nonce, wrapped_key) = wrapped.split_at(12);
+
+        let shared = key.diffie_hellman(&ephemeral_public);
+        let content_key = wrapping_cipher(shared.as_bytes(), &ephemeral_public, &key.public_key)
+            .decrypt(Nonce::from_slice(wrap_nonce), wrapped_key)
+            .map_err(|_| EnvelopeError::DecryptionFailed)?;
+        if content_key.len() != 32 {
+            return Err(EnvelopeError::Malformed("content key must be 256 bits".to_string()));
+        }
+
+        let nonce = decode_array::<12>(&self.nonce, "nonce")?;
+        let ciphertext = decode(&self.ciphertext, "ciphertext")?;
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&content_key))
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: ENVELOPE_CONTEXT.as_bytes() })
+            .map_err(|_| EnvelopeError::DecryptionFailed)
+    }
+
+    /// Key IDs able to open the envelope
+    pub fn recipient_ids(&self) -> impl Iterator<Item = KeyId> + '_ {
+        self.recipients.iter().map(|r| r.key_id)
+    }
+}
+
+fn wrapping_cipher(shared: &[u8; 32], ephemeral: &X25519PublicKey, recipient: &X25519PublicKey) -> Aes256Gcm {
+    let mut salt = ephemeral.as_bytes().to_vec();
+    salt.extend_from_slice(recipient.as_bytes());
+    let mut key = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(ENVELOPE_CONTEXT.as_bytes(), &mut key)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+}
+
+fn decode(value: &str, what: &str) -> Result<Vec<u8>, EnvelopeError> {
+    STANDARD
+        .decode(value)
+        .map_err(|e| EnvelopeError::Malformed(format!("invalid {}: {}", what, e)))
+}
+
+fn decode_array<const N: usize>(value: &str, what: &str) -> Result<[u8; N], EnvelopeError> {
+    decode(value, what)?
+        .try_into()
+        .map_err(|_| EnvelopeError::Malformed(format!("invalid {} length", what)))
+}
+
+/// Errors for envelope encryption
+#[derive(Debug, thiserror::Error)]
+pub enum EnvelopeError {
+    #[error("Envelope needs at least one recipient")]
+    NoRecipients,
+
+    #[error("No key-agreement public key known for key {0}")]
+    UnknownRecipient(KeyId),
+
+    #[error("Key {0} has a low-order X25519 public key")]
+    InvalidPublicKey(KeyId),
+
+    #[error("Key {0} is not a recipient of this envelope")]
+    NotARecipient(KeyId),
+
+    #[error("Unsupported envelope encryption: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Failed to encrypt envelope")]
+    EncryptionFailed,
+
+    #[error("Failed to decrypt envelope: wrong key or modified envelope")]
+    DecryptionFailed,
+
+    #[error("Malformed envelope: {0}")]
+    Malformed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::seed_derivation::MasterSeed;
+
+    fn key(byte: u8) -> KeyAgreementKeyPair {
+        KeyAgreementKeyPair::from_seed(&MasterSeed::from_bytes([byte; 32]))
+    }
+
+    #[test]
+    fn test_every_recipient_opens_envelope() {
+        let (alice, bob, carol) = (key(1), key(2), key(3));
+        let (alice_id, bob_id, carol_id) = (KeyId::new(), KeyId::new(), KeyId::new());
+        let encryptor = X25519Encryptor::new()
+            .with_recipient(alice_id, alice.public_key_bytes())
+            .with_recipient(bob_id, bob.public_key_bytes())
+            .with_recipient(carol_id, carol.public_key_bytes());
+
+        let envelope = encryptor.encrypt(b"operator seed", &[alice_id, bob_id]).unwrap();
+        assert_eq!(envelope.open(alice_id, &alice).unwrap(), b"operator seed");
+        assert_eq!(envelope.open(bob_id, &bob).unwrap(), b"operator seed");
+        assert!(matches!(envelope.open(carol_id, &carol), Err(EnvelopeError::NotARecipient(_))));
+        // Claiming another recipient's slot does not help
+        assert!(matches!(envelope.open(alice_id, &carol), Err(EnvelopeError::DecryptionFailed)));
+
+        let json = serde_json::to_string(&envelope).unwrap();
+        assert!(!json.contains("operator seed"));
+        let parsed: Envelope = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.recipient_ids().collect::<Vec<_>>(), vec![alice_id, bob_id]);
+    }
+
+    #[test]
+    fn test_unknown_recipients_and_tampering_fail() {
+        let alice = key(1);
+        let alice_id = KeyId::new();
+        let encryptor = X25519Encryptor::new().with_recipient(alice_id, alice.public_key_bytes());
+
+        assert!(matches!(encryptor.encrypt(b"x", &[]), Err(EnvelopeError::NoRecipients)));
+        assert!(matches!(encryptor.encrypt(b"x", &[KeyId::new()]), Err(EnvelopeError::UnknownRecipient(_))));
+        let low_order = X25519Encryptor::new().with_recipient(alice_id, [0u8; 32]);
+        assert!(matches!(low_order.encrypt(b"x", &[alice_id]), Err(EnvelopeError::InvalidPublicKey(_))));
+
+        let mut envelope = encryptor.encrypt(b"payload", &[alice_id]).unwrap();
+        let mut ciphertext = STANDARD.decode(&envelope.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        envelope.ciphertext = STANDARD.encode(ciphertext);
+        assert!(matches!(envelope.open(alice_id, &alice), Err(EnvelopeError::DecryptionFailed)));
+    }
+}
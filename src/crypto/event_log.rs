@@ -0,0 +1,229 @@
+//! Event log encryption at rest
+//!
+//! Event files written to an SD card name people, keys and NATS entities,
+//! so an offline projection can encrypt them with AES-256-GCM under a key
+//! derived from the master seed:
+//!
+//! ```text
+//! Master Seed
+//!   ↓ HKDF-SHA256 (info: "cim-keys/event-log/v1")
+//! Event log key (256-bit)
+//!   ↓ AES-256-GCM, random 96-bit nonce per file
+//! {"encryption": "aes-256-gcm", "nonce": "...", "ciphertext": "..."}
+//! ```
+//!
+//! Encrypted files keep their `.json` name and ordering, and are told apart
+//! from plaintext events by the `encryption` field. The file name (event
+//! timestamp and ID) is bound into each ciphertext, so files cannot be
+//! renamed or swapped without failing to decrypt.
+//!
+//! With a key configured, plaintext files are rejected: anyone holding the
+//! card could otherwise slip in a forged event. A log that was started
+//! unencrypted is replayed with [`EventLogCipher::with_plaintext_migration`].
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use super::MasterSeed;
+
+/// HKDF info for the event log key; also bound into every ciphertext
+const EVENT_LOG_CONTEXT: &str = "cim-keys/event-log/v1";
+
+/// Algorithm tag written into encrypted event files
+const ALGORITHM: &str = "aes-256-gcm";
+
+/// Encrypted event file as written to disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedEventFile {
+    /// Always `aes-256-gcm`
+    pub encryption: String,
+    /// 96-bit nonce, base64
+    pub nonce: String,
+    /// Ciphertext and tag, base64
+    pub ciphertext: String,
+}
+
+/// Encrypts and decrypts event log files
+#[derive(Clone)]
+pub struct EventLogCipher {
+    cipher: Aes256Gcm,
+    /// Accept plaintext files, while migrating a log started unencrypted
+    accept_plaintext: bool,
+}
+
+impl std::fmt::Debug for EventLogCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventLogCipher")
+            .field("accept_plaintext", &self.accept_plaintext)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventLogCipher {
+    /// Cipher keyed from the master seed of the ceremony
+    pub fn from_master_seed(seed: &MasterSeed) -> Self {
+        let key = seed.derive_child(EVENT_LOG_CONTEXT);
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key.as_bytes())),
+            accept_plaintext: false,
+        }
+    }
+
+    /// Also accept plaintext event files when reading
+    ///
+    /// Only for replaying a log that was started before encryption was
+    /// enabled; the plaintext files are not authenticated.
+    pub fn with_plaintext_migration(mut self) -> Self {
+        self.accept_plaintext = true;
+        self
+    }
+
+    /// Encrypt a serialized event into the on-disk JSON form, bound to the
+    /// name of the file it is written to
+    pub fn encrypt(&self, plaintext: &[u8], file_name: &str) -> Result<String, EventLogCryptoError> {
+        let nonce: [u8; 12] = rand::random();
+        let aad = associated_data(file_name);
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: &aad })
+            .map_err(|_| EventLogCryptoError::EncryptionFailed)?;
+
+        serde_json::to_string_pretty(&EncryptedEventFile {
+            encryption: ALGORITHM.to_string(),
+            nonce: STANDARD.encode(nonce),
+            ciphertext: STANDARD.encode(ciphertext),
+        })
+        .map_err(|e| EventLogCryptoError::Malformed(e.to_string()))
+    }
+
+    /// Decrypt an encrypted event file's contents, read from `file_name`
+    pub fn decrypt(&self, file: &EncryptedEventFile, file_name: &str) -> Result<Vec<u8>, EventLogCryptoError> {
+        if file.encryption != ALGORITHM {
+            return Err(EventLogCryptoError::UnsupportedAlgorithm(file.encryption.clone()));
+        }
+        let nonce: [u8; 12] = STANDARD
+            .decode(&file.nonce)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| EventLogCryptoError::Malformed("invalid nonce".to_string()))?;
+        let ciphertext = STANDARD
+            .decode(&file.ciphertext)
+            .map_err(|e| EventLogCryptoError::Malformed(format!("invalid ciphertext: {}", e)))?;
+
+        let aad = associated_data(file_name);
+        self.cipher
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &aad })
+            .map_err(|_| EventLogCryptoError::DecryptionFailed)
+    }
+}
+
+/// Associated data of an event file: the log context and the file name
+fn associated_data(file_name: &str) -> Vec<u8> {
+    format!("{}/{}", EVENT_LOG_CONTEXT, file_name).into_bytes()
+}
+
+/// Contents of the event file `file_name`: plaintext JSON or an encrypted
+/// wrapper
+///
+/// `cipher` may be `None` for logs that were never encrypted; an encrypted
+/// file then fails with [`EventLogCryptoError::KeyRequired`]. With a cipher,
+/// a plaintext file fails with [`EventLogCryptoError::Unencrypted`] unless
+/// the cipher was built [`with_plaintext_migration`](EventLogCipher::with_plaintext_migration).
+pub fn open_event_file(
+    content: &str,
+    file_name: &str,
+    cipher: Option<&EventLogCipher>,
+) -> Result<String, EventLogCryptoError> {
+    let Ok(file) = serde_json::from_str::<EncryptedEventFile>(content) else {
+        return match cipher {
+            Some(cipher) if !cipher.accept_plaintext => Err(EventLogCryptoError::Unencrypted),
+            _ => Ok(content.to_string()),
+        };
+    };
+    let cipher = cipher.ok_or(EventLogCryptoError::KeyRequired)?;
+    String::from_utf8(cipher.decrypt(&file, file_name)?)
+        .map_err(|e| EventLogCryptoError::Malformed(e.to_string()))
+}
+
+/// Errors for event log encryption
+#[derive(Debug, thiserror::Error)]
+pub enum EventLogCryptoError {
+    #[error("Event is encrypted and no event log key was provided")]
+    KeyRequired,
+
+    #[error("Event is not encrypted although the event log is")]
+    Unencrypted,
+
+    #[error("Unsupported event encryption: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Failed to encrypt event")]
+    EncryptionFailed,
+
+    #[error("Failed to decrypt event: wrong key or modified file")]
+    DecryptionFailed,
+
+    #[error("Malformed encrypted event: {0}")]
+    Malformed(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAME: &str = "1700000000000000000_018f0000-0000-7000-8000-000000000001.json";
+
+    fn cipher(byte: u8) -> EventLogCipher {
+        EventLogCipher::from_master_seed(&MasterSeed::from_bytes([byte; 32]))
+    }
+
+    #[test]
+    fn test_round_trip_hides_plaintext() {
+        let event = r#"{"aggregate":"Person","event":{"name":"Alice Johnson"}}"#;
+        let file = cipher(7).encrypt(event.as_bytes(), NAME).unwrap();
+
+        assert!(!file.contains("Alice"));
+        assert_eq!(open_event_file(&file, NAME, Some(&cipher(7))).unwrap(), event);
+        // Plaintext events pass through untouched in an unencrypted log
+        assert_eq!(open_event_file(event, NAME, None).unwrap(), event);
+    }
+
+    #[test]
+    fn test_plaintext_rejected_unless_migrating() {
+        let forged = r#"{"aggregate":"Person","event":{"name":"Mallory"}}"#;
+        assert!(matches!(
+            open_event_file(forged, NAME, Some(&cipher(7))),
+            Err(EventLogCryptoError::Unencrypted)
+        ));
+        let migrating = cipher(7).with_plaintext_migration();
+        assert_eq!(open_event_file(forged, NAME, Some(&migrating)).unwrap(), forged);
+    }
+
+    #[test]
+    fn test_renamed_file_fails() {
+        let file = cipher(7).encrypt(b"{}", NAME).unwrap();
+        let other = "1700000000000000001_018f0000-0000-7000-8000-000000000002.json";
+        assert!(matches!(
+            open_event_file(&file, other, Some(&cipher(7))),
+            Err(EventLogCryptoError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_wrong_key_or_tampering_fails() {
+        let file = cipher(7).encrypt(b"{}", NAME).unwrap();
+        assert!(matches!(
+            open_event_file(&file, NAME, Some(&cipher(8))),
+            Err(EventLogCryptoError::DecryptionFailed)
+        ));
+        assert!(matches!(open_event_file(&file, NAME, None), Err(EventLogCryptoError::KeyRequired)));
+
+        let mut parsed: EncryptedEventFile = serde_json::from_str(&file).unwrap();
+        let mut bytes = STANDARD.decode(&parsed.ciphertext).unwrap();
+        bytes[0] ^= 1;
+        parsed.ciphertext = STANDARD.encode(bytes);
+        assert!(matches!(cipher(7).decrypt(&parsed, NAME), Err(EventLogCryptoError::DecryptionFailed)));
+    }
+}
@@ -0,0 +1,273 @@
+//! Canonical key fingerprints
+//!
+//! Every key family has its own fingerprint and its own way of printing
+//! it. [`KeyFingerprint`] computes them from public key material, parses
+//! their usual textual forms and compares them in constant time, so code
+//! that matches keys does not compare hex strings that differ only in case
+//! or separators.
+//!
+//! | Kind                 | Digest                                      | Canonical text          |
+//! |----------------------|---------------------------------------------|-------------------------|
+//! | `SshSha256`          | SHA-256 of the RFC 4253 public key blob     | `SHA256:<base64>`       |
+//! | `OpenPgpV4`          | SHA-1 of `0x99 ‖ len16 ‖ key packet body`   | 40 upper-case hex       |
+//! | `OpenPgpV5`          | SHA-256 of `0x9A ‖ len32 ‖ key packet body` | 64 upper-case hex       |
+//! | `X509SubjectKeyId`   | SHA-256 of subjectPublicKey, first 160 bits | `AB:CD:…` (20 bytes)    |
+//! | `NKeyPublic`         | the nkey public key itself                  | `U…`, `A…`, `O…`        |
+//!
+//! The SKID uses RFC 7093 method 1, as rcgen does for the certificates
+//! issued here.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Compare two byte strings in time independent of their contents
+///
+/// Lengths are not secret and are compared first.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Fingerprint scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FingerprintKind {
+    SshSha256,
+    OpenPgpV4,
+    OpenPgpV5,
+    X509SubjectKeyId,
+    NKeyPublic,
+}
+
+impl FingerprintKind {
+    /// Digest length in bytes, `None` for nkeys
+    fn digest_len(&self) -> Option<usize> {
+        match self {
+            FingerprintKind::SshSha256 | FingerprintKind::OpenPgpV5 => Some(32),
+            FingerprintKind::OpenPgpV4 | FingerprintKind::X509SubjectKeyId => Some(20),
+            FingerprintKind::NKeyPublic => None,
+        }
+    }
+}
+
+/// Fingerprint parsing errors
+#[derive(Debug, Error)]
+pub enum FingerprintError {
+    #[error("Malformed {kind:?} fingerprint: {value}")]
+    Malformed { kind: FingerprintKind, value: String },
+
+    #[error("{kind:?} fingerprint must be {expected} bytes, got {actual}")]
+    WrongLength {
+        kind: FingerprintKind,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// Fingerprint of a public key, compared in constant time
+#[derive(Debug, Clone)]
+pub struct KeyFingerprint {
+    kind: FingerprintKind,
+    bytes: Vec<u8>,
+}
+
+impl KeyFingerprint {
+    /// OpenSSH SHA-256 fingerprint of an RFC 4253 public key blob
+    pub fn ssh_sha256(public_key_blob: &[u8]) -> Self {
+        Self {
+            kind: FingerprintKind::SshSha256,
+            bytes: Sha256::digest(public_key_blob).to_vec(),
+        }
+    }
+
+    /// OpenPGP v4 fingerprint of a public key packet body
+    pub fn openpgp_v4(key_packet_body: &[u8]) -> Self {
+        let mut preimage = Vec::with_capacity(3 + key_packet_body.len());
+        preimage.push(0x99);
+        preimage.extend_from_slice(&(key_packet_body.len() as u16).to_be_bytes());
+        preimage.extend_from_slice(key_packet_body);
+
+        let digest = ring::digest::digest(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY, &preimage);
+        Self {
+            kind: FingerprintKind::OpenPgpV4,
+            bytes: digest.as_ref().to_vec(),
+        }
+    }
+
+    /// OpenPGP v5 fingerprint of a public key packet body
+    pub fn openpgp_v5(key_packet_body: &[u8]) -> Self {
+        let digest = Sha256::new()
+            .chain_update([0x9A])
+            .chain_update((key_packet_body.len() as u32).to_be_bytes())
+            .chain_update(key_packet_body)
+            .finalize();
+        Self {
+            kind: FingerprintKind::OpenPgpV5,
+            bytes: digest.to_vec(),
+        }
+    }
+
+    /// X.509 subject key identifier of a subjectPublicKey (the BIT STRING
+    /// contents of the SubjectPublicKeyInfo)
+    pub fn x509_skid(subject_public_key: &[u8]) -> Self {
+        Self {
+            kind: FingerprintKind::X509SubjectKeyId,
+            bytes: Sha256::digest(subject_public_key)[..20].to_vec(),
+        }
+    }
+
+    /// NATS nkey public key (`U…`, `A…`, `O…`), checksum verified
+    pub fn nkey_public(public_key: &str) -> Result<Self, FingerprintError> {
+        let key = nkeys::KeyPair::from_public_key(public_key.trim()).map_err(|_| FingerprintError::Malformed {
+            kind: FingerprintKind::NKeyPublic,
+            value: public_key.to_string(),
+        })?;
+        Ok(Self {
+            kind: FingerprintKind::NKeyPublic,
+            bytes: key.public_key().into_bytes(),
+        })
+    }
+
+    /// Parse the textual form of a `kind` fingerprint
+    ///
+    /// Accepts the canonical form as well as lower case, `0x` prefixes and
+    /// space or colon separators for the hex kinds.
+    pub fn parse(kind: FingerprintKind, value: &str) -> Result<Self, FingerprintError> {
+        let malformed = || FingerprintError::Malformed {
+            kind,
+            value: value.to_string(),
+        };
+
+        let bytes = match kind {
+            FingerprintKind::NKeyPublic => return Self::nkey_public(value),
+            FingerprintKind::SshSha256 => {
+                let encoded = value.trim().strip_prefix("SHA256:").ok_or_else(malformed)?;
+                base64::engine::general_purpose::STANDARD_NO_PAD
+                    .decode(encoded.trim_end_matches('='))
+                    .map_err(|_| malformed())?
+            }
+            _ => {
+                let value = value.trim();
+                let value = value.strip_prefix("0x").unwrap_or(value);
+                let digits: String = value.chars().filter(|c| !matches!(c, ' ' | ':')).collect();
+                hex::decode(digits).map_err(|_| malformed())?
+            }
+        };
+
+        let expected = kind.digest_len().expect("nkeys handled above");
+        if bytes.len() != expected {
+            return Err(FingerprintError::WrongLength {
+                kind,
+                expected,
+                actual: bytes.len(),
+            });
+        }
+        Ok(Self { kind, bytes })
+    }
+
+    pub fn kind(&self) -> FingerprintKind {
+        self.kind
+    }
+
+    /// Digest bytes (the public key text for nkeys)
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Whether `value` parses as a `self.kind()` fingerprint equal to this one
+    pub fn matches(&self, value: &str) -> bool {
+        Self::parse(self.kind, value).is_ok_and(|other| other == *self)
+    }
+}
+
+impl PartialEq for KeyFingerprint {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && constant_time_eq(&self.bytes, &other.bytes)
+    }
+}
+
+impl Eq for KeyFingerprint {}
+
+impl Hash for KeyFingerprint {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.bytes.hash(state);
+    }
+}
+
+impl fmt::Display for KeyFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            FingerprintKind::SshSha256 => write!(
+                f,
+                "SHA256:{}",
+                base64::engine::general_purpose::STANDARD_NO_PAD.encode(&self.bytes)
+            ),
+            FingerprintKind::OpenPgpV4 | FingerprintKind::OpenPgpV5 => {
+                write!(f, "{}", hex::encode_upper(&self.bytes))
+            }
+            FingerprintKind::X509SubjectKeyId => {
+                let octets: Vec<String> = self.bytes.iter().map(|b| format!("{:02X}", b)).collect();
+                write!(f, "{}", octets.join(":"))
+            }
+            FingerprintKind::NKeyPublic => write!(f, "{}", String::from_utf8_lossy(&self.bytes)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssh_fingerprint_matches_ssh_key_crate() {
+        let keypair = ssh_key::private::Ed25519Keypair::from_seed(&[9u8; 32]);
+        let public_key = ssh_key::PublicKey::from(keypair.public);
+        let blob = public_key.to_bytes().unwrap();
+        let expected = public_key.fingerprint(ssh_key::HashAlg::Sha256).to_string();
+
+        let fingerprint = KeyFingerprint::ssh_sha256(&blob);
+        assert_eq!(fingerprint.to_string(), expected);
+        assert!(fingerprint.matches(&expected));
+        assert!(!fingerprint.matches("SHA256:AAAA"));
+    }
+
+    #[test]
+    fn test_hex_forms_compare_equal() {
+        let fingerprint = KeyFingerprint::openpgp_v4(b"public key packet body");
+        let canonical = fingerprint.to_string();
+        assert_eq!(canonical.len(), 40);
+
+        let spaced = canonical
+            .to_lowercase()
+            .as_bytes()
+            .chunks(4)
+            .map(|c| std::str::from_utf8(c).unwrap())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert!(fingerprint.matches(&spaced));
+        assert!(fingerprint.matches(&format!("0x{}", canonical)));
+
+        // Same digest, different scheme
+        let skid = KeyFingerprint::parse(FingerprintKind::X509SubjectKeyId, &canonical).unwrap();
+        assert_ne!(skid, fingerprint);
+        assert_eq!(skid.to_string().len(), 59);
+
+        assert!(matches!(
+            KeyFingerprint::parse(FingerprintKind::OpenPgpV5, &canonical),
+            Err(FingerprintError::WrongLength { expected: 32, actual: 20, .. })
+        ));
+    }
+
+    #[test]
+    fn test_nkey_checksum_verified() {
+        let user = nkeys::KeyPair::new_user().public_key();
+        assert_eq!(KeyFingerprint::nkey_public(&user).unwrap().to_string(), user);
+
+        let mut corrupted = user.clone();
+        corrupted.replace_range(5..6, if &user[5..6] == "A" { "B" } else { "A" });
+        assert!(KeyFingerprint::nkey_public(&corrupted).is_err());
+    }
+}
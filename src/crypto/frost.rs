@@ -0,0 +1,475 @@
+//! FROST threshold Ed25519 signing for root CA operations
+//!
+//! The root CA key is never held in one place: a distributed key
+//! generation (DKG) among the root authorities and backup holders gives
+//! each of them a share, and any `min_signers` of them can jointly produce
+//! a standard Ed25519 signature (RFC 9591, FROST(Ed25519, SHA-512)).
+//!
+//! ```text
+//! DKG (every participant)                 Signing (min_signers participants)
+//!   part 1 → round 1 package (broadcast)    commit → commitments (to coordinator)
+//!   part 2 → round 2 packages (per peer)    coordinator → signing package
+//!   part 3 → FrostKeyShare                  sign → signature share (to coordinator)
+//!                                           coordinator → aggregate → Ed25519 signature
+//! ```
+//!
+//! Participants are persons; their FROST identifiers are derived from
+//! their person IDs, so packages are addressed by `Uuid` throughout. Only
+//! `RootAuthority` and `BackupHolder` persons may take part.
+//!
+//! The aggregated signature verifies as plain Ed25519 under the group
+//! public key, so a root CA certificate can be signed through
+//! [`super::x509::ExternalCaSigner`] with a closure that runs a
+//! [`ThresholdSigningSession`] over the TBS bytes.
+
+use std::collections::BTreeMap;
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use frost_ed25519 as frost;
+use frost_ed25519::keys::dkg::{self, round1, round2};
+use frost_ed25519::keys::{KeyPackage, PublicKeyPackage};
+use frost_ed25519::{Identifier, SigningPackage};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::bootstrap::KeyOwnerRole;
+
+pub use frost_ed25519::round1::{SigningCommitments, SigningNonces};
+pub use frost_ed25519::round2::SignatureShare;
+
+/// A person taking part in threshold signing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdParticipant {
+    pub person_id: Uuid,
+    pub role: KeyOwnerRole,
+}
+
+impl ThresholdParticipant {
+    pub fn new(person_id: Uuid, role: KeyOwnerRole) -> Self {
+        Self { person_id, role }
+    }
+}
+
+/// Who holds shares of a threshold key and how many must sign
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThresholdPolicy {
+    /// Signatures need this many participants
+    pub min_signers: u16,
+    pub participants: Vec<ThresholdParticipant>,
+}
+
+impl ThresholdPolicy {
+    /// `min_signers`-of-`participants.len()` policy
+    ///
+    /// Participants must be distinct root authorities or backup holders,
+    /// and `2 <= min_signers <= participants.len()`.
+    pub fn new(min_signers: u16, participants: Vec<ThresholdParticipant>) -> Result<Self, FrostError> {
+        if let Some(p) = participants
+            .iter()
+            .find(|p| !matches!(p.role, KeyOwnerRole::RootAuthority | KeyOwnerRole::BackupHolder))
+        {
+            return Err(FrostError::IneligibleParticipant(p.person_id));
+        }
+        let mut ids: Vec<Uuid> = participants.iter().map(|p| p.person_id).collect();
+        ids.sort();
+        ids.dedup();
+        if ids.len() != participants.len() {
+            return Err(FrostError::InvalidPolicy("participants must be distinct".to_string()));
+        }
+        if min_signers < 2 || usize::from(min_signers) > participants.len() {
+            return Err(FrostError::InvalidPolicy(format!(
+                "threshold {} of {} participants (need 2 <= threshold <= participants)",
+                min_signers,
+                participants.len()
+            )));
+        }
+        Ok(Self { min_signers, participants })
+    }
+
+    /// Total number of share holders
+    pub fn max_signers(&self) -> u16 {
+        self.participants.len() as u16
+    }
+
+    /// Whether `person_id` holds a share under this policy
+    pub fn contains(&self, person_id: Uuid) -> bool {
+        self.participants.iter().any(|p| p.person_id == person_id)
+    }
+
+    /// FROST identifier of a participant
+    pub fn identifier(&self, person_id: Uuid) -> Result<Identifier, FrostError> {
+        if !self.contains(person_id) {
+            return Err(FrostError::UnknownParticipant(person_id));
+        }
+        identifier_of(person_id)
+    }
+
+    fn person_of(&self, identifier: &Identifier) -> Result<Uuid, FrostError> {
+        for p in &self.participants {
+            if identifier_of(p.person_id)? == *identifier {
+                return Ok(p.person_id);
+            }
+        }
+        Err(FrostError::Protocol("package addressed to an unknown identifier".to_string()))
+    }
+
+    fn by_identifier<T: Clone>(&self, packages: &BTreeMap<Uuid, T>) -> Result<BTreeMap<Identifier, T>, FrostError> {
+        packages
+            .iter()
+            .map(|(person_id, package)| Ok((self.identifier(*person_id)?, package.clone())))
+            .collect()
+    }
+}
+
+fn identifier_of(person_id: Uuid) -> Result<Identifier, FrostError> {
+    Identifier::derive(person_id.as_bytes()).map_err(|e| FrostError::Protocol(e.to_string()))
+}
+
+// ============================================================================
+// DISTRIBUTED KEY GENERATION
+// ============================================================================
+
+/// One participant's side of the DKG
+///
+/// Security: holds this participant's secret polynomial until
+/// [`Self::finish`]; never serialize or share it.
+pub struct DkgParticipant {
+    person_id: Uuid,
+    policy: ThresholdPolicy,
+    round1_secret: Option<round1::SecretPackage>,
+    round2_secret: Option<round2::SecretPackage>,
+    round1_packages: BTreeMap<Identifier, round1::Package>,
+}
+
+impl std::fmt::Debug for DkgParticipant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DkgParticipant")
+            .field("person_id", &self.person_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DkgParticipant {
+    /// DKG part 1: returns the round 1 package to broadcast to every peer
+    pub fn start<R: RngCore + CryptoRng>(
+        policy: ThresholdPolicy,
+        person_id: Uuid,
+        rng: &mut R,
+    ) -> Result<(Self, round1::Package), FrostError> {
+        let identifier = policy.identifier(person_id)?;
+        let (secret, package) = dkg::part1(identifier, policy.max_signers(), policy.min_signers, rng)
+            .map_err(|e| FrostError::Protocol(e.to_string()))?;
+        let participant = Self {
+            person_id,
+            policy,
+            round1_secret: Some(secret),
+            round2_secret: None,
+            round1_packages: BTreeMap::new(),
+        };
+        Ok((participant, package))
+    }
+
+    pub fn person_id(&self) -> Uuid {
+        self.person_id
+    }
+
+    /// DKG part 2: takes every peer's round 1 package, returns one round 2
+    /// package per peer (to be sent to that peer only)
+    pub fn round2(
+        &mut self,
+        round1_packages: &BTreeMap<Uuid, round1::Package>,
+    ) -> Result<BTreeMap<Uuid, round2::Package>, FrostError> {
+        let received = self.peer_packages(round1_packages)?;
+        let secret = self.round1_secret.take().ok_or(FrostError::OutOfOrder("round 2"))?;
+        let (secret, outgoing) = dkg::part2(secret, &received).map_err(|e| FrostError::Protocol(e.to_string()))?;
+        self.round1_packages = received;
+        self.round2_secret = Some(secret);
+        outgoing
+            .into_iter()
+            .map(|(identifier, package)| Ok((self.policy.person_of(&identifier)?, package)))
+            .collect()
+    }
+
+    /// DKG part 3: takes the round 2 packages addressed to this participant
+    pub fn finish(self, round2_packages: &BTreeMap<Uuid, round2::Package>) -> Result<FrostKeyShare, FrostError> {
+        let secret = self.round2_secret.as_ref().ok_or(FrostError::OutOfOrder("finish"))?;
+        let received = self.peer_packages(round2_packages)?;
+        let (key_package, public_key_package) = dkg::part3(secret, &self.round1_packages, &received)
+            .map_err(|e| FrostError::Protocol(e.to_string()))?;
+        Ok(FrostKeyShare {
+            person_id: self.person_id,
+            key_package,
+            public_key_package,
+        })
+    }
+
+    /// Packages from every other participant, keyed by identifier
+    fn peer_packages<T: Clone>(&self, packages: &BTreeMap<Uuid, T>) -> Result<BTreeMap<Identifier, T>, FrostError> {
+        let peers: BTreeMap<Uuid, T> = packages
+            .iter()
+            .filter(|(person_id, _)| **person_id != self.person_id)
+            .map(|(person_id, package)| (*person_id, package.clone()))
+            .collect();
+        if let Some(missing) = self
+            .policy
+            .participants
+            .iter()
+            .map(|p| p.person_id)
+            .find(|id| *id != self.person_id && !peers.contains_key(id))
+        {
+            return Err(FrostError::MissingParticipant(missing));
+        }
+        self.policy.by_identifier(&peers)
+    }
+}
+
+/// A participant's share of the threshold key, output of the DKG
+///
+/// Security: contains the secret signing share.
+pub struct FrostKeyShare {
+    pub person_id: Uuid,
+    key_package: KeyPackage,
+    public_key_package: PublicKeyPackage,
+}
+
+impl std::fmt::Debug for FrostKeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FrostKeyShare")
+            .field("person_id", &self.person_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FrostKeyShare {
+    /// Group public key (Ed25519) the aggregated signatures verify under
+    pub fn group_public_key(&self) -> Result<[u8; 32], FrostError> {
+        group_public_key(&self.public_key_package)
+    }
+
+    /// Public data the coordinator needs to aggregate signature shares
+    pub fn public_key_package(&self) -> &PublicKeyPackage {
+        &self.public_key_package
+    }
+
+    /// Signing round 1: fresh nonces (kept) and commitments (sent to the coordinator)
+    ///
+    /// Nonces must be used for exactly one signature share.
+    pub fn commit<R: RngCore + CryptoRng>(&self, rng: &mut R) -> (SigningNonces, SigningCommitments) {
+        frost::round1::commit(self.key_package.signing_share(), rng)
+    }
+
+    /// Signing round 2: this participant's share of the signature
+    pub fn sign(&self, signing_package: &SigningPackage, nonces: SigningNonces) -> Result<SignatureShare, FrostError> {
+        frost::round2::sign(signing_package, &nonces, &self.key_package).map_err(|e| FrostError::Protocol(e.to_string()))
+    }
+}
+
+fn group_public_key(package: &PublicKeyPackage) -> Result<[u8; 32], FrostError> {
+    package
+        .verifying_key()
+        .serialize()
+        .map_err(|e| FrostError::Protocol(e.to_string()))?
+        .try_into()
+        .map_err(|_| FrostError::Protocol("group public key is not 32 bytes".to_string()))
+}
+
+// ============================================================================
+// SIGNING
+// ============================================================================
+
+/// Coordinator of one threshold signature over `message`
+#[derive(Debug, Clone)]
+pub struct ThresholdSigningSession {
+    policy: ThresholdPolicy,
+    message: Vec<u8>,
+    commitments: BTreeMap<Uuid, SigningCommitments>,
+    shares: BTreeMap<Uuid, SignatureShare>,
+}
+
+impl ThresholdSigningSession {
+    pub fn new(policy: ThresholdPolicy, message: impl Into<Vec<u8>>) -> Self {
+        Self {
+            policy,
+            message: message.into(),
+            commitments: BTreeMap::new(),
+            shares: BTreeMap::new(),
+        }
+    }
+
+    /// Round 1: record a signer's commitments
+    pub fn add_commitments(&mut self, person_id: Uuid, commitments: SigningCommitments) -> Result<(), FrostError> {
+        self.policy.identifier(person_id)?;
+        if !self.shares.is_empty() {
+            return Err(FrostError::OutOfOrder("commitments after signing started"));
+        }
+        self.commitments.insert(person_id, commitments);
+        Ok(())
+    }
+
+    /// Persons who committed, i.e. the signers of this session
+    pub fn signers(&self) -> Vec<Uuid> {
+        self.commitments.keys().copied().collect()
+    }
+
+    /// Signing package to hand to every signer, once enough have committed
+    pub fn signing_package(&self) -> Result<SigningPackage, FrostError> {
+        if self.commitments.len() < usize::from(self.policy.min_signers) {
+            return Err(FrostError::BelowThreshold {
+                have: self.commitments.len(),
+                need: self.policy.min_signers,
+            });
+        }
+        Ok(SigningPackage::new(self.policy.by_identifier(&self.commitments)?, &self.message))
+    }
+
+    /// Round 2: record a signer's signature share
+    pub fn add_signature_share(&mut self, person_id: Uuid, share: SignatureShare) -> Result<(), FrostError> {
+        if !self.commitments.contains_key(&person_id) {
+            return Err(FrostError::UnknownParticipant(person_id));
+        }
+        self.shares.insert(person_id, share);
+        Ok(())
+    }
+
+    /// Aggregate the signature shares into an Ed25519 signature
+    pub fn aggregate(&self, public_key_package: &PublicKeyPackage) -> Result<[u8; 64], FrostError> {
+        if let Some(missing) = self.signers().into_iter().find(|id| !self.shares.contains_key(id)) {
+            return Err(FrostError::MissingParticipant(missing));
+        }
+        let signing_package = self.signing_package()?;
+        let shares = self.policy.by_identifier(&self.shares)?;
+        let signature = frost::aggregate(&signing_package, &shares, public_key_package)
+            .map_err(|e| FrostError::Protocol(e.to_string()))?;
+        signature
+            .serialize()
+            .map_err(|e| FrostError::Protocol(e.to_string()))?
+            .try_into()
+            .map_err(|_| FrostError::Protocol("signature is not 64 bytes".to_string()))
+    }
+}
+
+/// Verify an aggregated threshold signature as plain Ed25519
+pub fn verify_threshold_signature(group_public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    VerifyingKey::from_bytes(group_public_key)
+        .map(|key| key.verify(message, &Signature::from_bytes(signature)).is_ok())
+        .unwrap_or(false)
+}
+
+/// Errors for threshold key generation and signing
+#[derive(Debug, thiserror::Error)]
+pub enum FrostError {
+    #[error("Invalid threshold policy: {0}")]
+    InvalidPolicy(String),
+
+    #[error("Person {0} is neither a root authority nor a backup holder")]
+    IneligibleParticipant(Uuid),
+
+    #[error("Person {0} is not a participant")]
+    UnknownParticipant(Uuid),
+
+    #[error("Missing package from participant {0}")]
+    MissingParticipant(Uuid),
+
+    #[error("Only {have} signers committed, {need} required")]
+    BelowThreshold { have: usize, need: u16 },
+
+    #[error("Step out of order: {0}")]
+    OutOfOrder(&'static str),
+
+    #[error("FROST protocol error: {0}")]
+    Protocol(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::OsRng;
+
+    fn policy(min_signers: u16, n: usize) -> ThresholdPolicy {
+        let mut participants = vec![ThresholdParticipant::new(Uuid::now_v7(), KeyOwnerRole::RootAuthority)];
+        participants.extend((1..n).map(|_| ThresholdParticipant::new(Uuid::now_v7(), KeyOwnerRole::BackupHolder)));
+        ThresholdPolicy::new(min_signers, participants).unwrap()
+    }
+
+    fn run_dkg(policy: &ThresholdPolicy) -> Vec<FrostKeyShare> {
+        let mut participants = Vec::new();
+        let mut round1 = BTreeMap::new();
+        for p in &policy.participants {
+            let (participant, package) = DkgParticipant::start(policy.clone(), p.person_id, &mut OsRng).unwrap();
+            round1.insert(p.person_id, package);
+            participants.push(participant);
+        }
+
+        let mut inboxes: BTreeMap<Uuid, BTreeMap<Uuid, round2::Package>> = BTreeMap::new();
+        for participant in &mut participants {
+            for (to, package) in participant.round2(&round1).unwrap() {
+                inboxes.entry(to).or_default().insert(participant.person_id(), package);
+            }
+        }
+
+        participants
+            .into_iter()
+            .map(|p| {
+                let inbox = inboxes.remove(&p.person_id()).unwrap();
+                p.finish(&inbox).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_any_threshold_of_participants_signs() {
+        let policy = policy(2, 3);
+        let shares = run_dkg(&policy);
+        let group_key = shares[0].group_public_key().unwrap();
+        assert!(shares.iter().all(|s| s.group_public_key().unwrap() == group_key));
+
+        let tbs = b"root CA tbsCertificate";
+        let mut session = ThresholdSigningSession::new(policy.clone(), tbs.to_vec());
+        let signers = [&shares[0], &shares[2]];
+        let nonces: Vec<_> = signers
+            .iter()
+            .map(|share| {
+                let (nonces, commitments) = share.commit(&mut OsRng);
+                session.add_commitments(share.person_id, commitments).unwrap();
+                nonces
+            })
+            .collect();
+
+        let signing_package = session.signing_package().unwrap();
+        for (share, nonces) in signers.iter().zip(nonces) {
+            let signature_share = share.sign(&signing_package, nonces).unwrap();
+            session.add_signature_share(share.person_id, signature_share).unwrap();
+        }
+
+        let signature = session.aggregate(shares[1].public_key_package()).unwrap();
+        assert!(verify_threshold_signature(&group_key, tbs, &signature));
+        assert!(!verify_threshold_signature(&group_key, b"other", &signature));
+    }
+
+    #[test]
+    fn test_below_threshold_and_ineligible_participants_rejected() {
+        let policy = policy(2, 3);
+        let shares = run_dkg(&policy);
+        let mut session = ThresholdSigningSession::new(policy.clone(), b"msg".to_vec());
+        let (_, commitments) = shares[0].commit(&mut OsRng);
+        session.add_commitments(shares[0].person_id, commitments).unwrap();
+        assert!(matches!(
+            session.signing_package(),
+            Err(FrostError::BelowThreshold { have: 1, need: 2 })
+        ));
+        let (_, other) = shares[1].commit(&mut OsRng);
+        assert!(matches!(
+            session.add_commitments(Uuid::now_v7(), other),
+            Err(FrostError::UnknownParticipant(_))
+        ));
+
+        let developer = ThresholdParticipant::new(Uuid::now_v7(), KeyOwnerRole::Developer);
+        assert!(matches!(
+            ThresholdPolicy::new(2, vec![policy.participants[0].clone(), developer]),
+            Err(FrostError::IneligibleParticipant(_))
+        ));
+        assert!(ThresholdPolicy::new(4, policy.participants.clone()).is_err());
+        assert!(ThresholdPolicy::new(1, policy.participants).is_err());
+    }
+}
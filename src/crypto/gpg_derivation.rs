@@ -0,0 +1,433 @@
+//! Deterministic OpenPGP key derivation from the master seed
+//!
+//! GPG keys generated through a GnuPG backend use OS randomness, which means
+//! a lost keyring cannot be rebuilt from the master passphrase. This module
+//! derives the OpenPGP key material itself from the `MasterSeed`, so the
+//! whole keyring can be reconstructed from backup.
+//!
+//! ## Derivation Path
+//!
+//! ```text
+//! cim-keys/gpg/v1/{organization}/{person}/{purpose}[/{index}]
+//!   ├─ /primary         → Ed25519 (certify + sign)
+//!   ├─ /authentication  → Ed25519 (authenticate)
+//!   └─ /encryption      → Cv25519 (encrypt communications + storage)
+//! ```
+//!
+//! The index is omitted while it is 0 (see [`super::derivation_path`]).
+//!
+//! The encryption subkey is the X25519 (Montgomery) form of an Ed25519 key
+//! derived at its own path, so all three keys are independent. Its secret
+//! is stored clamped, as OpenPGP implementations expect for Cv25519.
+//!
+//! OpenPGP v4 fingerprints cover the key creation time. The creation time
+//! must therefore be recorded next to the derivation path; the same path and
+//! creation time always produce the same fingerprints.
+
+use chrono::{DateTime, TimeZone, Utc};
+use der::zeroize::Zeroize;
+use ed25519_dalek::SigningKey;
+
+use super::fingerprint::KeyFingerprint;
+use super::seed_derivation::MasterSeed;
+
+/// OpenPGP key flag: may certify other keys
+pub const KEY_FLAG_CERTIFY: u8 = 0x01;
+/// OpenPGP key flag: may sign data
+pub const KEY_FLAG_SIGN: u8 = 0x02;
+/// OpenPGP key flag: may encrypt communications
+pub const KEY_FLAG_ENCRYPT_COMMUNICATIONS: u8 = 0x04;
+/// OpenPGP key flag: may encrypt storage
+pub const KEY_FLAG_ENCRYPT_STORAGE: u8 = 0x08;
+/// OpenPGP key flag: may be used for authentication
+pub const KEY_FLAG_AUTHENTICATE: u8 = 0x20;
+
+/// OID for Ed25519 in OpenPGP (1.3.6.1.4.1.11591.15.1)
+const OID_ED25519: &[u8] = &[0x2B, 0x06, 0x01, 0x04, 0x01, 0xDA, 0x47, 0x0F, 0x01];
+/// OID for Curve25519 in OpenPGP (1.3.6.1.4.1.3029.1.5.1)
+const OID_CV25519: &[u8] = &[0x2B, 0x06, 0x01, 0x04, 0x01, 0x97, 0x55, 0x01, 0x05, 0x01];
+
+/// Derivation scheme version, part of every path
+const GPG_DERIVATION_VERSION: &str = "v1";
+
+/// OpenPGP public key algorithm of a derived key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenPgpAlgorithm {
+    /// EdDSA over Ed25519 (algorithm 22)
+    Ed25519,
+    /// ECDH over Curve25519 (algorithm 18)
+    Cv25519,
+}
+
+impl OpenPgpAlgorithm {
+    /// OpenPGP public key algorithm ID
+    pub fn algorithm_id(&self) -> u8 {
+        match self {
+            OpenPgpAlgorithm::Ed25519 => 22,
+            OpenPgpAlgorithm::Cv25519 => 18,
+        }
+    }
+}
+
+/// Derivation path for an OpenPGP keyset
+///
+/// Components must be non-empty and must not contain `/`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GpgDerivationPath {
+    pub organization: String,
+    pub person: String,
+    pub purpose: String,
+    /// Rotation index within the purpose, 0 for the first keyset
+    pub index: u32,
+}
+
+impl GpgDerivationPath {
+    /// Create a validated derivation path
+    pub fn new(
+        organization: impl Into<String>,
+        person: impl Into<String>,
+        purpose: impl Into<String>,
+    ) -> Result<Self, String> {
+        let path = Self {
+            organization: organization.into(),
+            person: person.into(),
+            purpose: purpose.into(),
+            index: 0,
+        };
+
+        for (name, value) in [
+            ("organization", &path.organization),
+            ("person", &path.person),
+            ("purpose", &path.purpose),
+        ] {
+            if value.is_empty() {
+                return Err(format!("GPG derivation path {} must not be empty", name));
+            }
+            if value.contains('/') {
+                return Err(format!("GPG derivation path {} must not contain '/'", name));
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Derive the `index`-th keyset of the purpose, e.g. after a rotation
+    pub fn with_index(mut self, index: u32) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// HKDF info string for this path
+    pub fn to_info(&self) -> String {
+        let info = format!(
+            "cim-keys/gpg/{}/{}/{}/{}",
+            GPG_DERIVATION_VERSION, self.organization, self.person, self.purpose
+        );
+        match self.index {
+            0 => info,
+            index => format!("{}/{}", info, index),
+        }
+    }
+
+    fn component_info(&self, component: &str) -> String {
+        format!("{}/{}", self.to_info(), component)
+    }
+}
+
+impl std::fmt::Display for GpgDerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_info())
+    }
+}
+
+/// A single derived OpenPGP key (primary or subkey)
+///
+/// Security: the secret bytes are zeroized on drop and redacted from Debug.
+#[derive(Clone)]
+pub struct DerivedGpgKey {
+    /// Public key algorithm
+    pub algorithm: OpenPgpAlgorithm,
+    /// OpenPGP key flags (`KEY_FLAG_*`)
+    pub key_flags: u8,
+    /// Public key (Ed25519 point or X25519 u-coordinate)
+    pub public_key: [u8; 32],
+    /// v4 fingerprint (40 uppercase hex characters)
+    pub fingerprint: String,
+    /// Secret key (Ed25519 seed or clamped X25519 scalar)
+    secret_key: [u8; 32],
+}
+
+impl DerivedGpgKey {
+    /// Secret key bytes (use with caution!)
+    pub fn secret_key_bytes(&self) -> &[u8; 32] {
+        &self.secret_key
+    }
+
+    /// 64-bit key ID (low 16 hex characters of the fingerprint)
+    pub fn key_id(&self) -> &str {
+        &self.fingerprint[self.fingerprint.len() - 16..]
+    }
+
+    /// Check whether this key carries the given key flag
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.key_flags & flag == flag
+    }
+}
+
+impl std::fmt::Debug for DerivedGpgKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedGpgKey")
+            .field("algorithm", &self.algorithm)
+            .field("key_flags", &self.key_flags)
+            .field("fingerprint", &self.fingerprint)
+            .field("secret_key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Zeroize for DerivedGpgKey {
+    fn zeroize(&mut self) {
+        self.secret_key.zeroize();
+    }
+}
+
+impl Drop for DerivedGpgKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Complete OpenPGP keyset derived for one path
+#[derive(Debug, Clone)]
+pub struct DerivedGpgKeyset {
+    pub path: GpgDerivationPath,
+    pub creation_time: DateTime<Utc>,
+    /// Ed25519 primary key (certify + sign)
+    pub primary: DerivedGpgKey,
+    /// Ed25519 authentication subkey
+    pub authentication: DerivedGpgKey,
+    /// Cv25519 encryption subkey
+    pub encryption: DerivedGpgKey,
+}
+
+impl DerivedGpgKeyset {
+    /// Primary key fingerprint (identifies the whole certificate)
+    pub fn fingerprint(&self) -> &str {
+        &self.primary.fingerprint
+    }
+}
+
+/// Derive a complete OpenPGP keyset from the master seed
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let path = GpgDerivationPath::new("cowboyai", "alice", "identity")?;
+/// let keyset = derive_gpg_keyset(&master_seed, &path, created_at)?;
+/// println!("fingerprint: {}", keyset.fingerprint());
+/// ```
+pub fn derive_gpg_keyset(
+    master_seed: &MasterSeed,
+    path: &GpgDerivationPath,
+    creation_time: DateTime<Utc>,
+) -> Result<DerivedGpgKeyset, String> {
+    let timestamp = u32::try_from(creation_time.timestamp())
+        .map_err(|_| format!("Creation time {} is outside the OpenPGP range", creation_time))?;
+
+    // Truncate to whole seconds so the recorded time round-trips exactly
+    let creation_time = Utc
+        .timestamp_opt(timestamp as i64, 0)
+        .single()
+        .ok_or_else(|| "Invalid creation time".to_string())?;
+
+    let primary = derive_ed25519(
+        master_seed,
+        &path.component_info("primary"),
+        KEY_FLAG_CERTIFY | KEY_FLAG_SIGN,
+        timestamp,
+    );
+    let authentication = derive_ed25519(
+        master_seed,
+        &path.component_info("authentication"),
+        KEY_FLAG_AUTHENTICATE,
+        timestamp,
+    );
+    let encryption = derive_cv25519(
+        master_seed,
+        &path.component_info("encryption"),
+        KEY_FLAG_ENCRYPT_COMMUNICATIONS | KEY_FLAG_ENCRYPT_STORAGE,
+        timestamp,
+    );
+
+    Ok(DerivedGpgKeyset {
+        path: path.clone(),
+        creation_time,
+        primary,
+        authentication,
+        encryption,
+    })
+}
+
+fn derive_ed25519(master_seed: &MasterSeed, info: &str, key_flags: u8, timestamp: u32) -> DerivedGpgKey {
+    let seed = master_seed.derive_child(info);
+    let signing_key = SigningKey::from_bytes(seed.as_bytes());
+    let public_key = signing_key.verifying_key().to_bytes();
+
+    DerivedGpgKey {
+        algorithm: OpenPgpAlgorithm::Ed25519,
+        key_flags,
+        public_key,
+        fingerprint: v4_fingerprint(OpenPgpAlgorithm::Ed25519, &public_key, timestamp),
+        secret_key: signing_key.to_bytes(),
+    }
+}
+
+fn derive_cv25519(master_seed: &MasterSeed, info: &str, key_flags: u8, timestamp: u32) -> DerivedGpgKey {
+    let seed = master_seed.derive_child(info);
+    let signing_key = SigningKey::from_bytes(seed.as_bytes());
+    let public_key = signing_key.verifying_key().to_montgomery().to_bytes();
+
+    DerivedGpgKey {
+        algorithm: OpenPgpAlgorithm::Cv25519,
+        key_flags,
+        public_key,
+        fingerprint: v4_fingerprint(OpenPgpAlgorithm::Cv25519, &public_key, timestamp),
+        secret_key: clamp_x25519(signing_key.to_scalar_bytes()),
+    }
+}
+
+/// Clamp an X25519 scalar (RFC 7748 §5); the public key is unchanged
+fn clamp_x25519(mut scalar: [u8; 32]) -> [u8; 32] {
+    scalar[0] &= 248;
+    scalar[31] &= 127;
+    scalar[31] |= 64;
+    scalar
+}
+
+/// Serialize a v4 public key packet body (RFC 4880 §5.5.2, RFC 6637)
+fn public_key_packet_body(algorithm: OpenPgpAlgorithm, public_key: &[u8; 32], timestamp: u32) -> Vec<u8> {
+    let mut body = vec![4u8];
+    body.extend_from_slice(&timestamp.to_be_bytes());
+    body.push(algorithm.algorithm_id());
+
+    let oid = match algorithm {
+        OpenPgpAlgorithm::Ed25519 => OID_ED25519,
+        OpenPgpAlgorithm::Cv25519 => OID_CV25519,
+    };
+    body.push(oid.len() as u8);
+    body.extend_from_slice(oid);
+
+    // MPI: 0x40 prefix + 32-byte point = 263 bits
+    body.extend_from_slice(&263u16.to_be_bytes());
+    body.push(0x40);
+    body.extend_from_slice(public_key);
+
+    if algorithm == OpenPgpAlgorithm::Cv25519 {
+        // KDF parameters: SHA2-256, AES-128
+        body.extend_from_slice(&[0x03, 0x01, 0x08, 0x07]);
+    }
+
+    body
+}
+
+/// Compute the OpenPGP v4 fingerprint: SHA-1(0x99 || len || body)
+fn v4_fingerprint(algorithm: OpenPgpAlgorithm, public_key: &[u8; 32], timestamp: u32) -> String {
+    let body = public_key_packet_body(algorithm, public_key, timestamp);
+    KeyFingerprint::openpgp_v4(&body).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_seed() -> MasterSeed {
+        MasterSeed::from_bytes([7u8; 32])
+    }
+
+    fn created_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_keyset_derivation_is_deterministic() {
+        let path = GpgDerivationPath::new("cowboyai", "alice", "identity").unwrap();
+
+        let first = derive_gpg_keyset(&test_seed(), &path, created_at()).unwrap();
+        let second = derive_gpg_keyset(&test_seed(), &path, created_at()).unwrap();
+
+        assert_eq!(first.fingerprint(), second.fingerprint());
+        assert_eq!(first.encryption.public_key, second.encryption.public_key);
+        assert_eq!(
+            first.authentication.secret_key_bytes(),
+            second.authentication.secret_key_bytes()
+        );
+    }
+
+    #[test]
+    fn test_different_paths_produce_different_keys() {
+        let alice = GpgDerivationPath::new("cowboyai", "alice", "identity").unwrap();
+        let bob = GpgDerivationPath::new("cowboyai", "bob", "identity").unwrap();
+
+        let alice_keys = derive_gpg_keyset(&test_seed(), &alice, created_at()).unwrap();
+        let bob_keys = derive_gpg_keyset(&test_seed(), &bob, created_at()).unwrap();
+
+        assert_ne!(alice_keys.primary.public_key, bob_keys.primary.public_key);
+        assert_ne!(alice_keys.fingerprint(), bob_keys.fingerprint());
+    }
+
+    #[test]
+    fn test_subkeys_are_independent_with_correct_flags() {
+        let path = GpgDerivationPath::new("cowboyai", "alice", "identity").unwrap();
+        let keyset = derive_gpg_keyset(&test_seed(), &path, created_at()).unwrap();
+
+        assert_ne!(keyset.primary.public_key, keyset.authentication.public_key);
+        assert_eq!(keyset.encryption.algorithm, OpenPgpAlgorithm::Cv25519);
+        assert!(keyset.primary.has_flag(KEY_FLAG_CERTIFY | KEY_FLAG_SIGN));
+        assert!(keyset.authentication.has_flag(KEY_FLAG_AUTHENTICATE));
+        assert!(keyset.encryption.has_flag(KEY_FLAG_ENCRYPT_COMMUNICATIONS));
+        assert!(!keyset.encryption.has_flag(KEY_FLAG_SIGN));
+    }
+
+    #[test]
+    fn test_encryption_secret_is_clamped_and_matches_its_public_key() {
+        let path = GpgDerivationPath::new("cowboyai", "alice", "identity").unwrap();
+        let keyset = derive_gpg_keyset(&test_seed(), &path, created_at()).unwrap();
+        let secret = *keyset.encryption.secret_key_bytes();
+
+        assert_eq!(secret, clamp_x25519(secret));
+        let public = x25519_dalek::PublicKey::from(&x25519_dalek::StaticSecret::from(secret));
+        assert_eq!(public.to_bytes(), keyset.encryption.public_key);
+    }
+
+    #[test]
+    fn test_fingerprint_depends_on_creation_time() {
+        let path = GpgDerivationPath::new("cowboyai", "alice", "identity").unwrap();
+
+        let first = derive_gpg_keyset(&test_seed(), &path, created_at()).unwrap();
+        let later = derive_gpg_keyset(&test_seed(), &path, created_at() + chrono::Duration::days(1)).unwrap();
+
+        // Same key material, different v4 fingerprint
+        assert_eq!(first.primary.public_key, later.primary.public_key);
+        assert_ne!(first.fingerprint(), later.fingerprint());
+        assert_eq!(first.fingerprint().len(), 40);
+        assert_eq!(first.primary.key_id().len(), 16);
+    }
+
+    #[test]
+    fn test_path_validation() {
+        assert!(GpgDerivationPath::new("", "alice", "identity").is_err());
+        assert!(GpgDerivationPath::new("cowboyai", "alice/bob", "identity").is_err());
+        assert_eq!(
+            GpgDerivationPath::new("cowboyai", "alice", "identity").unwrap().to_info(),
+            "cim-keys/gpg/v1/cowboyai/alice/identity"
+        );
+    }
+
+    #[test]
+    fn test_secret_key_redacted_in_debug() {
+        let path = GpgDerivationPath::new("cowboyai", "alice", "identity").unwrap();
+        let keyset = derive_gpg_keyset(&test_seed(), &path, created_at()).unwrap();
+
+        assert!(format!("{:?}", keyset.primary).contains("<redacted>"));
+    }
+}
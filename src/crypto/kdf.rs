@@ -0,0 +1,262 @@
+//! Passphrase hardening with Argon2id and HKDF
+//!
+//! Every secret derived from a human passphrase goes through this module:
+//!
+//! ```text
+//! Passphrase + KdfSalt (random 128-bit salt, Argon2id cost, purpose)
+//!   ↓ Argon2id (cost from the salt record)
+//! Passphrase key (256-bit)
+//!   ↓ HKDF-SHA256 (info: "cim-keys/kdf/v1/{purpose}")
+//! DerivedKey ── storage encryption (KEK, see `key_wrap`)
+//!            ── LUKS keyfile (see `VolumeKey::keyfile_from`)
+//!            ── age scrypt passphrase (see `age_encryption`)
+//! ```
+//!
+//! New salts take their Argon2id cost from [`SecurityConfig::kdf`]; each
+//! [`KdfSalt`] records the cost it was created with, so raising the
+//! configured cost never locks out existing material. Salts are public and
+//! kept in the projection's manifest (`KeyManifest::kdf_salts`).
+//!
+//! [`SecurityConfig::kdf`]: crate::config::SecurityConfig::kdf
+
+use age::secrecy::SecretString;
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+use der::zeroize::Zeroize;
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::ports::rng::{CryptoRngPort, OsCryptoRng};
+
+/// Salt length in bytes (Argon2 recommends at least 16)
+const SALT_LEN: usize = 16;
+
+/// Argon2id cost parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfParams {
+    /// Memory cost in KiB
+    pub memory_kib: u32,
+    /// Number of passes
+    pub iterations: u32,
+    /// Degree of parallelism
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    /// Production cost: 1 GiB, 10 passes, 4 lanes
+    fn default() -> Self {
+        Self {
+            memory_kib: 1_048_576,
+            iterations: 10,
+            parallelism: 4,
+        }
+    }
+}
+
+impl KdfParams {
+    /// Reduced cost (64 MiB, 3 passes) for tests only
+    pub fn testing() -> Self {
+        Self {
+            memory_kib: 65_536,
+            iterations: 3,
+            parallelism: 4,
+        }
+    }
+
+    /// Argon2id instance producing 32-byte outputs
+    pub fn argon2(&self) -> Result<Argon2<'static>, KdfError> {
+        let params = Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+            .map_err(|e| KdfError::InvalidParams(e.to_string()))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
+/// What a passphrase-derived key is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KdfPurpose {
+    /// Key-encryption key for private keys at rest
+    StorageEncryption,
+    /// Keyfile unlocking a LUKS2 volume
+    LuksKeyfile,
+    /// Passphrase for age scrypt recipients
+    AgePassphrase,
+}
+
+impl KdfPurpose {
+    /// HKDF info string
+    fn info(&self) -> &'static str {
+        match self {
+            KdfPurpose::StorageEncryption => "cim-keys/kdf/v1/storage-encryption",
+            KdfPurpose::LuksKeyfile => "cim-keys/kdf/v1/luks-keyfile",
+            KdfPurpose::AgePassphrase => "cim-keys/kdf/v1/age-passphrase",
+        }
+    }
+}
+
+impl std::fmt::Display for KdfPurpose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.info()["cim-keys/kdf/v1/".len()..])
+    }
+}
+
+/// Public salt and cost needed to re-derive a key from its passphrase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfSalt {
+    pub purpose: KdfPurpose,
+    /// Random salt, base64
+    pub salt: String,
+    /// Argon2id cost the key was derived with
+    pub params: KdfParams,
+    pub created_at: DateTime<Utc>,
+}
+
+impl KdfSalt {
+    /// Fresh random salt for `purpose` at the given cost
+    pub fn generate(purpose: KdfPurpose, params: KdfParams) -> Self {
+        Self::generate_with(purpose, params, &OsCryptoRng)
+    }
+
+    /// Salt for `purpose` drawn from `rng`
+    pub fn generate_with(purpose: KdfPurpose, params: KdfParams, rng: &dyn CryptoRngPort) -> Self {
+        let salt: [u8; SALT_LEN] = rng.array();
+        Self {
+            purpose,
+            salt: STANDARD.encode(salt),
+            params,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Derive this salt's key from `passphrase`
+    pub fn derive(&self, passphrase: &str) -> Result<DerivedKey, KdfError> {
+        let salt = STANDARD
+            .decode(&self.salt)
+            .map_err(|e| KdfError::InvalidSalt(e.to_string()))?;
+        if salt.len() < SALT_LEN {
+            return Err(KdfError::InvalidSalt(format!("{} bytes, need {}", salt.len(), SALT_LEN)));
+        }
+
+        let mut hardened = [0u8; 32];
+        self.params
+            .argon2()?
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut hardened)
+            .map_err(|e| KdfError::Derivation(e.to_string()))?;
+
+        let mut key = [0u8; 32];
+        Hkdf::<Sha256>::new(None, &hardened)
+            .expand(self.purpose.info().as_bytes(), &mut key)
+            .expect("32 bytes is a valid HKDF output length");
+        hardened.zeroize();
+
+        Ok(DerivedKey { purpose: self.purpose, key })
+    }
+}
+
+/// 256-bit key derived from a passphrase for one purpose
+///
+/// Security: zeroized on drop; Debug redacts the key.
+pub struct DerivedKey {
+    purpose: KdfPurpose,
+    key: [u8; 32],
+}
+
+impl std::fmt::Debug for DerivedKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedKey")
+            .field("purpose", &self.purpose)
+            .field("key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Drop for DerivedKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl DerivedKey {
+    pub fn purpose(&self) -> KdfPurpose {
+        self.purpose
+    }
+
+    /// Key bytes, after checking the key was derived for `purpose`
+    pub fn bytes_for(&self, purpose: KdfPurpose) -> Result<&[u8; 32], KdfError> {
+        if self.purpose != purpose {
+            return Err(KdfError::WrongPurpose {
+                expected: purpose,
+                actual: self.purpose,
+            });
+        }
+        Ok(&self.key)
+    }
+
+    /// Passphrase for age scrypt encryption and decryption
+    ///
+    /// The derived key is base64-encoded, so age's own scrypt runs over a
+    /// full-entropy input rather than the human passphrase.
+    pub fn age_passphrase(&self) -> Result<SecretString, KdfError> {
+        let key = self.bytes_for(KdfPurpose::AgePassphrase)?;
+        Ok(SecretString::from(STANDARD.encode(key)))
+    }
+}
+
+/// Errors for passphrase key derivation
+#[derive(Debug, thiserror::Error)]
+pub enum KdfError {
+    #[error("Invalid Argon2 parameters: {0}")]
+    InvalidParams(String),
+
+    #[error("Invalid salt: {0}")]
+    InvalidSalt(String),
+
+    #[error("Argon2 derivation failed: {0}")]
+    Derivation(String),
+
+    #[error("Key was derived for {actual}, not {expected}")]
+    WrongPurpose { expected: KdfPurpose, actual: KdfPurpose },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_salt_same_key_and_purposes_are_separated() {
+        let salt = KdfSalt::generate(KdfPurpose::StorageEncryption, KdfParams::testing());
+        let a = salt.derive("correct horse battery staple").unwrap();
+        let b = salt.derive("correct horse battery staple").unwrap();
+        let wrong = salt.derive("incorrect horse").unwrap();
+        assert_eq!(a.bytes_for(KdfPurpose::StorageEncryption).unwrap(), b.bytes_for(KdfPurpose::StorageEncryption).unwrap());
+        assert_ne!(a.bytes_for(KdfPurpose::StorageEncryption).unwrap(), wrong.bytes_for(KdfPurpose::StorageEncryption).unwrap());
+
+        // Same random salt bytes under another purpose give an unrelated key
+        let luks = KdfSalt { purpose: KdfPurpose::LuksKeyfile, ..salt.clone() }
+            .derive("correct horse battery staple")
+            .unwrap();
+        assert_ne!(luks.bytes_for(KdfPurpose::LuksKeyfile).unwrap(), a.bytes_for(KdfPurpose::StorageEncryption).unwrap());
+        assert!(matches!(luks.age_passphrase(), Err(KdfError::WrongPurpose { .. })));
+        assert!(format!("{:?}", a).contains("redacted"));
+
+        // Salts round-trip through the manifest
+        let json = serde_json::to_string(&salt).unwrap();
+        assert!(json.contains("\"storage-encryption\""));
+        assert_eq!(serde_json::from_str::<KdfSalt>(&json).unwrap(), salt);
+    }
+
+    #[test]
+    fn test_invalid_salt_and_params_rejected() {
+        let mut salt = KdfSalt::generate(KdfPurpose::AgePassphrase, KdfParams::testing());
+        salt.salt = STANDARD.encode([0u8; 4]);
+        assert!(matches!(salt.derive("x"), Err(KdfError::InvalidSalt(_))));
+
+        let salt = KdfSalt::generate(
+            KdfPurpose::AgePassphrase,
+            KdfParams { memory_kib: 1, iterations: 0, parallelism: 1 },
+        );
+        assert!(matches!(salt.derive("x"), Err(KdfError::InvalidParams(_))));
+    }
+}
@@ -1,7 +1,9 @@
 //! Deterministic key generation from seeds
 
 use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer};
+use x25519_dalek::{PublicKey as X25519PublicKey, SharedSecret, StaticSecret};
 use super::seed_derivation::MasterSeed;
+use crate::ports::rng::{CryptoRngPort, OsCryptoRng};
 
 /// A cryptographic keypair
 #[derive(Clone)]
@@ -42,6 +44,69 @@ impl KeyPair {
     }
 }
 
+/// An X25519 key-agreement keypair
+///
+/// Used to receive envelopes (see `crypto::envelope`). The secret is
+/// zeroized on drop.
+#[derive(Clone)]
+pub struct KeyAgreementKeyPair {
+    secret: StaticSecret,
+    /// X25519 public key
+    pub public_key: X25519PublicKey,
+}
+
+impl KeyAgreementKeyPair {
+    /// Generate a keypair from a seed
+    ///
+    /// This is deterministic - same seed always produces same keypair
+    pub fn from_seed(seed: &MasterSeed) -> Self {
+        let secret = StaticSecret::from(*seed.as_bytes());
+        let public_key = X25519PublicKey::from(&secret);
+        Self { secret, public_key }
+    }
+
+    /// Generate a random keypair
+    pub fn generate() -> Self {
+        Self::generate_with(&OsCryptoRng)
+    }
+
+    /// Generate a keypair from `rng`
+    pub fn generate_with(rng: &dyn CryptoRngPort) -> Self {
+        let secret = StaticSecret::random_from_rng(rng.rng());
+        let public_key = X25519PublicKey::from(&secret);
+        Self { secret, public_key }
+    }
+
+    /// Get public key bytes
+    pub fn public_key_bytes(&self) -> [u8; 32] {
+        self.public_key.to_bytes()
+    }
+
+    /// X25519 shared secret with another public key
+    pub fn diffie_hellman(&self, their_public: &X25519PublicKey) -> SharedSecret {
+        self.secret.diffie_hellman(their_public)
+    }
+}
+
+impl std::fmt::Debug for KeyAgreementKeyPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyAgreementKeyPair")
+            .field("public_key", &hex::encode(self.public_key_bytes()))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Generate a key-agreement keypair from seed
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let keypair = generate_key_agreement_keypair_from_seed(&seed.derive_child("alice-key-agreement"));
+/// ```
+pub fn generate_key_agreement_keypair_from_seed(seed: &MasterSeed) -> KeyAgreementKeyPair {
+    KeyAgreementKeyPair::from_seed(seed)
+}
+
 /// Generate a keypair from seed
 ///
 /// This is a convenience wrapper around KeyPair::from_seed
@@ -109,4 +174,20 @@ mod tests {
         // Different purposes = different keypairs
         assert_ne!(root_ca_keypair.public_key_bytes(), user_alice_keypair.public_key_bytes());
     }
+
+    #[test]
+    fn test_key_agreement_keypairs_share_secret() {
+        let master = derive_master_seed("test passphrase", "org-123").unwrap();
+        let alice = generate_key_agreement_keypair_from_seed(&master.derive_child("alice"));
+        let bob = KeyAgreementKeyPair::generate();
+
+        assert_eq!(
+            alice.public_key_bytes(),
+            generate_key_agreement_keypair_from_seed(&master.derive_child("alice")).public_key_bytes()
+        );
+        assert_eq!(
+            alice.diffie_hellman(&bob.public_key).as_bytes(),
+            bob.diffie_hellman(&alice.public_key).as_bytes()
+        );
+    }
 }
@@ -0,0 +1,230 @@
+//! AES key wrapping (RFC 3394) for private keys at rest
+//!
+//! Private keys written to local storage are wrapped under a
+//! key-encryption key (KEK) derived from the master passphrase instead of
+//! being stored in plaintext:
+//!
+//! ```text
+//! Master Passphrase + Organization ID
+//!   ↓ Argon2id (derive_master_seed)
+//! Master Seed
+//!   ↓ HKDF-SHA256 (info: "cim-keys/kek/v1")
+//! KEK (256-bit)
+//!   ↓ AES-256 key wrap (RFC 3394)
+//! {"wrapping": "aes-256-kw", "wrapped_key": "..."}
+//! ```
+//!
+//! Key wrap is deterministic and carries its own integrity check, so no
+//! nonce is stored and a wrong passphrase is detected on unwrap. The key
+//! material must be a multiple of 8 bytes and at least 16 bytes long, which
+//! holds for raw Ed25519, X25519 and P-256 private keys.
+
+use aes::cipher::generic_array::GenericArray;
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes256;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+
+use super::kdf::{DerivedKey, KdfPurpose};
+use super::seed_derivation::{derive_master_seed, MasterSeed};
+
+/// HKDF info for the key-encryption key
+const KEK_CONTEXT: &str = "cim-keys/kek/v1";
+
+/// Algorithm tag written into wrapped key files
+const ALGORITHM: &str = "aes-256-kw";
+
+/// Default initial value from RFC 3394 section 2.2.3.1
+const DEFAULT_IV: [u8; 8] = [0xA6; 8];
+
+/// A wrapped private key as written to disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WrappedKey {
+    /// Always `aes-256-kw`
+    pub wrapping: String,
+    /// Wrapped key material, base64
+    pub wrapped_key: String,
+}
+
+/// Key-encryption key wrapping private keys at rest
+#[derive(Clone)]
+pub struct KeyEncryptionKey {
+    cipher: Aes256,
+}
+
+impl std::fmt::Debug for KeyEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeyEncryptionKey").finish_non_exhaustive()
+    }
+}
+
+impl KeyEncryptionKey {
+    /// KEK derived from the master seed of the ceremony
+    pub fn from_master_seed(seed: &MasterSeed) -> Self {
+        let key = seed.derive_child(KEK_CONTEXT);
+        Self {
+            cipher: Aes256::new(GenericArray::from_slice(key.as_bytes())),
+        }
+    }
+
+    /// KEK derived from the master passphrase (Argon2id, then HKDF)
+    pub fn from_passphrase(passphrase: &str, organization_id: &str) -> Result<Self, KeyWrapError> {
+        derive_master_seed(passphrase, organization_id)
+            .map(|seed| Self::from_master_seed(&seed))
+            .map_err(KeyWrapError::KeyDerivation)
+    }
+
+    /// KEK from a key derived for [`KdfPurpose::StorageEncryption`]
+    pub fn from_derived_key(key: &DerivedKey) -> Result<Self, KeyWrapError> {
+        let bytes = key
+            .bytes_for(KdfPurpose::StorageEncryption)
+            .map_err(|e| KeyWrapError::KeyDerivation(e.to_string()))?;
+        Ok(Self {
+            cipher: Aes256::new(GenericArray::from_slice(bytes)),
+        })
+    }
+
+    /// Wrap key material (RFC 3394 section 2.2.1)
+    pub fn wrap(&self, key: &[u8]) -> Result<Vec<u8>, KeyWrapError> {
+        if key.len() < 16 || key.len() % 8 != 0 {
+            return Err(KeyWrapError::InvalidKeyLength(key.len()));
+        }
+        let mut a = DEFAULT_IV;
+        let mut r: Vec<[u8; 8]> = key.chunks_exact(8).map(|c| c.try_into().unwrap()).collect();
+        let n = r.len();
+
+        for j in 0..6 {
+            for (i, r_i) in r.iter_mut().enumerate() {
+                let mut block = Self::block(&a, r_i);
+                self.cipher.encrypt_block(&mut block);
+                let t = (n * j + i + 1) as u64;
+                a = xor_counter(&block[..8], t);
+                r_i.copy_from_slice(&block[8..]);
+            }
+        }
+
+        let mut wrapped = a.to_vec();
+        r.iter().for_each(|r_i| wrapped.extend_from_slice(r_i));
+        Ok(wrapped)
+    }
+
+    /// Unwrap key material, checking its integrity (RFC 3394 section 2.2.2)
+    pub fn unwrap(&self, wrapped: &[u8]) -> Result<Vec<u8>, KeyWrapError> {
+        if wrapped.len() < 24 || wrapped.len() % 8 != 0 {
+            return Err(KeyWrapError::InvalidKeyLength(wrapped.len()));
+        }
+        let mut a: [u8; 8] = wrapped[..8].try_into().unwrap();
+        let mut r: Vec<[u8; 8]> = wrapped[8..].chunks_exact(8).map(|c| c.try_into().unwrap()).collect();
+        let n = r.len();
+
+        for j in (0..6).rev() {
+            for (i, r_i) in r.iter_mut().enumerate().rev() {
+                let t = (n * j + i + 1) as u64;
+                let mut block = Self::block(&xor_counter(&a, t), r_i);
+                self.cipher.decrypt_block(&mut block);
+                a.copy_from_slice(&block[..8]);
+                r_i.copy_from_slice(&block[8..]);
+            }
+        }
+
+        if a != DEFAULT_IV {
+            return Err(KeyWrapError::IntegrityCheckFailed);
+        }
+        Ok(r.concat())
+    }
+
+    /// Wrap a private key into its on-disk form
+    pub fn wrap_key(&self, key: &[u8]) -> Result<WrappedKey, KeyWrapError> {
+        Ok(WrappedKey {
+            wrapping: ALGORITHM.to_string(),
+            wrapped_key: STANDARD.encode(self.wrap(key)?),
+        })
+    }
+
+    /// Unwrap a private key from its on-disk form
+    pub fn unwrap_key(&self, wrapped: &WrappedKey) -> Result<Vec<u8>, KeyWrapError> {
+        if wrapped.wrapping != ALGORITHM {
+            return Err(KeyWrapError::UnsupportedAlgorithm(wrapped.wrapping.clone()));
+        }
+        let bytes = STANDARD
+            .decode(&wrapped.wrapped_key)
+            .map_err(|e| KeyWrapError::Malformed(e.to_string()))?;
+        self.unwrap(&bytes)
+    }
+
+    fn block(a: &[u8; 8], r: &[u8; 8]) -> GenericArray<u8, aes::cipher::consts::U16> {
+        let mut block = GenericArray::default();
+        block[..8].copy_from_slice(a);
+        block[8..].copy_from_slice(r);
+        block
+    }
+}
+
+fn xor_counter(a: &[u8], t: u64) -> [u8; 8] {
+    let a = u64::from_be_bytes(a.try_into().unwrap());
+    (a ^ t).to_be_bytes()
+}
+
+/// Errors for key wrapping
+#[derive(Debug, thiserror::Error)]
+pub enum KeyWrapError {
+    #[error("Key material must be a multiple of 8 bytes and at least 16 bytes, got {0}")]
+    InvalidKeyLength(usize),
+
+    #[error("Unwrapped key failed the integrity check: wrong passphrase or modified file")]
+    IntegrityCheckFailed,
+
+    #[error("Unsupported key wrapping: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Malformed wrapped key: {0}")]
+    Malformed(String),
+
+    #[error("Failed to derive key-encryption key: {0}")]
+    KeyDerivation(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kek_from_bytes(bytes: &[u8]) -> KeyEncryptionKey {
+        KeyEncryptionKey {
+            cipher: Aes256::new(GenericArray::from_slice(bytes)),
+        }
+    }
+
+    #[test]
+    fn test_rfc3394_vectors() {
+        let kek = kek_from_bytes(&hex::decode("000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F").unwrap());
+
+        // Section 4.3: 128 bits of key data with a 256-bit KEK
+        let key = hex::decode("00112233445566778899AABBCCDDEEFF").unwrap();
+        let wrapped = kek.wrap(&key).unwrap();
+        assert_eq!(hex::encode_upper(&wrapped), "64E8C3F9CE0F5BA263E9777905818A2A93C8191E7D6E8AE7");
+        assert_eq!(kek.unwrap(&wrapped).unwrap(), key);
+
+        // Section 4.6: 256 bits of key data with a 256-bit KEK
+        let key = hex::decode("00112233445566778899AABBCCDDEEFF000102030405060708090A0B0C0D0E0F").unwrap();
+        let wrapped = kek.wrap(&key).unwrap();
+        assert_eq!(
+            hex::encode_upper(&wrapped),
+            "28C9F404C4B810F4CBCCB35CFB87F8263F5786E2D80ED326CBC7F0E71A99F43BFB988B9B7A02DD21"
+        );
+        assert_eq!(kek.unwrap(&wrapped).unwrap(), key);
+    }
+
+    #[test]
+    fn test_wrong_kek_and_bad_lengths_fail() {
+        let kek = KeyEncryptionKey::from_master_seed(&MasterSeed::from_bytes([1u8; 32]));
+        let other = KeyEncryptionKey::from_master_seed(&MasterSeed::from_bytes([2u8; 32]));
+
+        let wrapped = kek.wrap_key(&[0x42; 32]).unwrap();
+        assert_eq!(kek.unwrap_key(&wrapped).unwrap(), vec![0x42; 32]);
+        assert!(matches!(other.unwrap_key(&wrapped), Err(KeyWrapError::IntegrityCheckFailed)));
+
+        assert!(matches!(kek.wrap(&[0u8; 8]), Err(KeyWrapError::InvalidKeyLength(8))));
+        assert!(matches!(kek.wrap(&[0u8; 33]), Err(KeyWrapError::InvalidKeyLength(33))));
+        assert!(matches!(kek.unwrap(&[0u8; 20]), Err(KeyWrapError::InvalidKeyLength(20))));
+    }
+}
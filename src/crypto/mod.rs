@@ -13,9 +13,27 @@
 //! ├─ Root CA Seed
 //! ├─ Intermediate CA Seeds (one per OU)
 //! ├─ User Key Seeds (one per person)
+//! ├─ OpenPGP Keysets (org/person/purpose)
+//! ├─ SSH Keys (org/person/purpose)
+//! ├─ age Identities (org/person)
 //! └─ NATS Credential Seeds
 //! ```
 //!
+//! SSH, OpenPGP, NATS and TLS keys share one path scheme,
+//! `cim-keys/{family}/v1/{org}/{person}/{purpose}[/{index}]`, described in
+//! [`derivation_path`].
+//!
+//! Private keys kept on local storage are wrapped (RFC 3394) under a
+//! key-encryption key derived from the same master seed, see [`key_wrap`].
+//! Private key material in memory lives in [`SecretBytes`], which is
+//! zeroized on drop and locked out of swap where possible.
+//!
+//! Ceremonies that generate a random seed instead of deriving one mix OS,
+//! dice and hardware randomness with [`entropy::EntropyMixer`].
+//!
+//! NATS nkey seeds are kept wrapped in storage by [`nkey_store::NKeySeedStore`];
+//! events carry only the public keys.
+//!
 //! ## Security Properties
 //!
 //! - **Deterministic**: Same passphrase always produces same keys
@@ -27,14 +45,92 @@ pub mod seed_derivation;
 pub mod key_generation;
 pub mod passphrase;
 pub mod x509;
+pub mod crl;
+pub mod ocsp;
+pub mod pkcs12;
+pub mod timestamp;
 pub mod rfc5280;
+pub mod transparency;
+pub mod gpg_derivation;
+pub mod ssh_ca;
+pub mod ssh_derivation;
+pub mod age_derivation;
+pub mod age_encryption;
+pub mod envelope;
+pub mod key_wrap;
+pub mod frost;
+pub mod kdf;
+pub mod post_quantum;
+pub mod derivation_path;
+pub mod event_log;
+pub mod shamir;
+pub mod secure_memory;
+pub mod entropy;
+pub mod fingerprint;
+pub mod nkey_store;
 
+pub use secure_memory::SecretBytes;
+pub use fingerprint::{FingerprintError, FingerprintKind, KeyFingerprint, constant_time_eq};
 pub use seed_derivation::{MasterSeed, derive_master_seed, derive_child_seed};
-pub use key_generation::{KeyPair, generate_keypair_from_seed};
+pub use entropy::{
+    EntropyError, EntropyMixer, EntropyReport, EntropySourceKind, HealthTest, HealthTestResult, SourceReport,
+};
+pub use key_generation::{
+    KeyAgreementKeyPair, KeyPair, generate_key_agreement_keypair_from_seed, generate_keypair_from_seed,
+};
+pub use envelope::{Encryptor, Envelope, EnvelopeError, EnvelopeRecipient, X25519Encryptor};
+pub use key_wrap::{KeyEncryptionKey, KeyWrapError, WrappedKey};
+pub use nkey_store::{NKeySeedStore, NKeyStoreError};
+pub use kdf::{DerivedKey, KdfError, KdfParams, KdfPurpose, KdfSalt};
+pub use frost::{
+    DkgParticipant, FrostError, FrostKeyShare, ThresholdParticipant, ThresholdPolicy, ThresholdSigningSession,
+    verify_threshold_signature,
+};
 pub use passphrase::{PassphraseStrength, validate_passphrase};
 pub use x509::{
     X509Certificate, RootCAParams, IntermediateCAParams, ServerCertParams,
-    generate_root_ca, generate_intermediate_ca, generate_server_certificate,
+    IntermediateCA, ExternalCaSigner, LeafCertificateParams, IssuedCertificate,
+    generate_root_ca, generate_intermediate_ca, create_intermediate_ca, generate_server_certificate,
+    issue_certificate,
+};
+pub use crl::{CrlEntry, GeneratedCrl, generate_crl, parse_revocation_reason};
+pub use ocsp::{
+    OcspCertStatus, OcspResponder, OcspResponderParams, OcspResponderCertificate,
+    generate_ocsp_responder_certificate,
+};
+pub use pkcs12::build_pkcs12;
+pub use timestamp::{
+    TimestampAuthority, TimestampAuthorityCertificate, TimestampAuthorityParams, TimestampToken,
+    generate_timestamp_authority_certificate, verify_timestamp_token,
+};
+pub use transparency::{MerkleHash, leaf_hash, merkle_root, inclusion_proof, verify_inclusion};
+pub use shamir::{
+    GroupShare, GroupSpec, SecretShare, ShamirError, combine_group_shares, combine_shares, split_secret,
+    split_secret_groups,
+};
+pub use event_log::{EncryptedEventFile, EventLogCipher, EventLogCryptoError, open_event_file};
+pub use gpg_derivation::{
+    GpgDerivationPath, DerivedGpgKey, DerivedGpgKeyset, OpenPgpAlgorithm, derive_gpg_keyset,
+};
+pub use ssh_derivation::{
+    SshDerivationPath, DerivedSshKey, derive_ssh_key, regenerate_from_seed,
+};
+pub use age_derivation::{
+    AgeDerivationPath, DerivedAgeKey, derive_age_key, ssh_ed25519_to_age_recipient,
+};
+pub use age_encryption::{
+    AgeProtection, PersonRecipient, decrypt_with_identity, decrypt_with_passphrase, encrypt_armored,
+};
+pub use post_quantum::{
+    HybridCertificate, MlDsaKeyPair, MlKemKeyPair, issue_hybrid_certificate, ml_kem_encapsulate,
+    verify_hybrid_certificate, verify_ml_dsa,
+};
+pub use derivation_path::{
+    DerivationMismatch, DerivationPath, DerivationRecord, DerivationRegistry, KeyFamily,
+    derive_nats_key, derive_tls_keypair,
+};
+pub use ssh_ca::{
+    SshCaManager, UserCertificateRequest, HostCertificateRequest, IssuedSshCertificate,
 };
 pub use rfc5280::{
     validate_certificate, validate_certificate_der,
@@ -0,0 +1,160 @@
+//! NATS nkey seeds at rest
+//!
+//! Operator, account and user seeds are written through [`StoragePort`],
+//! wrapped under the key-encryption key (see [`super::key_wrap`]), one file
+//! per key:
+//!
+//! ```text
+//! nats/nkeys/{public_key}.nk.json
+//! {"key_type": "User", "public_key": "U…", "generated_at": "…",
+//!  "wrapping": "aes-256-kw", "wrapped_key": "…"}
+//! ```
+//!
+//! Only the public key appears in events (`NKeyGeneratedEvent`); the seed
+//! is read back from storage when something has to be signed. Seeds are
+//! 58 ASCII characters, so they are NUL-padded to the 8-byte multiple key
+//! wrap requires.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::key_wrap::{KeyEncryptionKey, KeyWrapError, WrappedKey};
+use crate::ports::storage::{StorageError, StoragePort};
+use crate::value_objects::{NKeyPair, NKeyPublic, NKeySeed, NKeyType};
+
+/// Directory holding wrapped nkey seeds
+pub const NKEY_SEED_DIR: &str = "nats/nkeys";
+
+/// On-storage form of one seed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredNKeySeed {
+    key_type: NKeyType,
+    public_key: String,
+    generated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    seed: WrappedKey,
+}
+
+/// Seed storage errors
+#[derive(Debug, Error)]
+pub enum NKeyStoreError {
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("Key wrap error: {0}")]
+    Wrap(#[from] KeyWrapError),
+
+    #[error("Malformed nkey seed file {path}: {reason}")]
+    Malformed { path: String, reason: String },
+
+    #[error("Seed stored for {0} belongs to another public key")]
+    PublicKeyMismatch(String),
+}
+
+/// Wrapped nkey seeds behind a storage port
+pub struct NKeySeedStore<'a> {
+    storage: &'a dyn StoragePort,
+    kek: &'a KeyEncryptionKey,
+}
+
+impl<'a> NKeySeedStore<'a> {
+    pub fn new(storage: &'a dyn StoragePort, kek: &'a KeyEncryptionKey) -> Self {
+        Self { storage, kek }
+    }
+
+    /// Path of the seed of `public_key`
+    pub fn seed_path(public_key: &str) -> String {
+        format!("{}/{}.nk.json", NKEY_SEED_DIR, public_key)
+    }
+
+    /// Wrap and write the seed of `key`, returning its path
+    pub async fn store(&self, key: &NKeyPair) -> Result<String, NKeyStoreError> {
+        let mut padded = key.seed_string().as_bytes().to_vec();
+        padded.resize(padded.len().div_ceil(8) * 8, 0);
+        let wrapped = self.kek.wrap_key(&padded);
+        der::zeroize::Zeroize::zeroize(&mut padded);
+
+        let stored = StoredNKeySeed {
+            key_type: key.key_type,
+            public_key: key.public_key_string().to_string(),
+            generated_at: key.seed.generated_at,
+            seed: wrapped?,
+        };
+        let path = Self::seed_path(&stored.public_key);
+        let json = serde_json::to_vec_pretty(&stored).map_err(|e| NKeyStoreError::Malformed {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+
+        self.storage.create_dir_all(NKEY_SEED_DIR).await?;
+        self.storage.write(&path, &json).await?;
+        self.storage.sync(&path).await?;
+        Ok(path)
+    }
+
+    /// Read and unwrap the seed of `public_key`
+    pub async fn load(&self, public_key: &str) -> Result<NKeyPair, NKeyStoreError> {
+        let path = Self::seed_path(public_key);
+        let malformed = |reason: String| NKeyStoreError::Malformed {
+            path: path.clone(),
+            reason,
+        };
+
+        let bytes = self.storage.read(&path).await?;
+        let stored: StoredNKeySeed = serde_json::from_slice(&bytes).map_err(|e| malformed(e.to_string()))?;
+        let mut padded = self.kek.unwrap_key(&stored.seed)?;
+        let seed = std::str::from_utf8(&padded)
+            .map(|seed| seed.trim_end_matches('\0').to_string())
+            .map_err(|e| malformed(e.to_string()));
+        der::zeroize::Zeroize::zeroize(&mut padded);
+        let seed = seed?;
+
+        let derived = nkeys::KeyPair::from_seed(&seed)
+            .map_err(|e| malformed(e.to_string()))?
+            .public_key();
+        if derived != public_key || stored.public_key != public_key {
+            return Err(NKeyStoreError::PublicKeyMismatch(public_key.to_string()));
+        }
+
+        Ok(NKeyPair::new(
+            stored.key_type,
+            NKeySeed::new(stored.key_type, seed, stored.generated_at),
+            NKeyPublic::new(stored.key_type, stored.public_key),
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::in_memory::InMemoryStorageAdapter;
+    use crate::adapters::seeded_rng::SeededCryptoRng;
+    use crate::crypto::seed_derivation::MasterSeed;
+
+    #[tokio::test]
+    async fn test_seed_round_trips_wrapped() {
+        let storage = InMemoryStorageAdapter::new();
+        let kek = KeyEncryptionKey::from_master_seed(&MasterSeed::from_bytes([3u8; 32]));
+        let store = NKeySeedStore::new(&storage, &kek);
+
+        let user = NKeyPair::generate_with(NKeyType::User, None, &SeededCryptoRng::from_label("user")).unwrap();
+        assert!(user.public_key_string().starts_with('U'));
+        assert!(user.seed_string().starts_with("SU"));
+
+        let path = store.store(&user).await.unwrap();
+        let on_disk = String::from_utf8(storage.read(&path).await.unwrap()).unwrap();
+        assert!(!on_disk.contains(user.seed_string()));
+
+        let loaded = store.load(user.public_key_string()).await.unwrap();
+        assert_eq!(loaded.seed_string(), user.seed_string());
+        assert_eq!(loaded.key_type, NKeyType::User);
+
+        let other_kek = KeyEncryptionKey::from_master_seed(&MasterSeed::from_bytes([4u8; 32]));
+        assert!(matches!(
+            NKeySeedStore::new(&storage, &other_kek).load(user.public_key_string()).await,
+            Err(NKeyStoreError::Wrap(_))
+        ));
+    }
+}
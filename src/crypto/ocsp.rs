@@ -0,0 +1,589 @@
+//! OCSP Responder (RFC 6960)
+//!
+//! Online revocation status for certificates issued by a CIM CA, answered
+//! from the same event-sourced state that produces CRLs.
+//!
+//! ```text
+//! Issuing CA (offline)
+//!   └─ OCSP Responder certificate (delegated, online)
+//!        ├─ keyUsage: digitalSignature
+//!        ├─ extendedKeyUsage: OCSPSigning
+//!        └─ id-pkix-ocsp-nocheck (clients don't check the responder's status)
+//!
+//! OCSPRequest (DER)
+//!   ↓ CertID → serial → cert_id
+//! status lookup (projection: Good / Revoked / Unknown)
+//!   ↓
+//! BasicOCSPResponse signed by the responder key
+//! ```
+//!
+//! The issuing CA key only signs the responder certificate; responses are
+//! signed by the responder key, which can live on the online host.
+
+use chrono::{DateTime, Utc};
+use der::asn1::{BitString, GeneralizedTime, Null, ObjectIdentifier, OctetString};
+use der::oid::db::{rfc5280, rfc5912, rfc6960, rfc8410};
+use der::{Decode, Encode};
+use rcgen::{
+    CertificateParams, CustomExtension, DistinguishedName, DnType, ExtendedKeyUsagePurpose,
+    IsCa, Issuer, KeyPair as RcgenKeyPair, KeyUsagePurpose,
+};
+use x509_cert::spki::AlgorithmIdentifierOwned;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+use x509_cert::ext::pkix::{CrlReason, ExtendedKeyUsage as EkuExtension};
+use x509_cert::ext::{Extension, Extensions};
+use x509_cert::Certificate as X509CertDer;
+use x509_ocsp::{
+    BasicOcspResponse, CertId, CertStatus, OcspGeneralizedTime, OcspRequest, OcspResponse,
+    ResponderId, ResponseData, RevokedInfo, SingleResponse, Version,
+};
+
+use super::x509::{
+    calculate_fingerprint, certificate_id_from_serial, certificate_serial, certificate_validity,
+    issuer_path_len, signature_algorithm_name, signature_algorithm_of,
+};
+use crate::state_machines::certificate::RevocationReason;
+use crate::value_objects::x509::{
+    BasicConstraints as BasicConstraintsVO, CommonName, ExtendedKeyUsage, KeyUsage,
+    OrganizationName, SubjectName,
+};
+
+/// id-pkix-ocsp-nocheck (1.3.6.1.5.5.7.48.1.5)
+const OCSP_NOCHECK_OID: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 48, 1, 5];
+
+/// Revocation status of a single certificate
+#[derive(Debug, Clone, PartialEq)]
+pub enum OcspCertStatus {
+    Good,
+    Revoked {
+        revoked_at: DateTime<Utc>,
+        reason: RevocationReason,
+    },
+    /// Not a certificate this responder knows about
+    Unknown,
+}
+
+/// Parameters for an OCSP responder certificate
+#[derive(Debug, Clone)]
+pub struct OcspResponderParams {
+    pub organization: String,
+    pub common_name: String,
+    /// Responder certificates are short-lived since they are never revoked
+    pub validity_days: u32,
+}
+
+impl Default for OcspResponderParams {
+    fn default() -> Self {
+        Self {
+            organization: "CowboyAI".to_string(),
+            common_name: "CowboyAI OCSP Responder".to_string(),
+            validity_days: 30,
+        }
+    }
+}
+
+/// Delegated OCSP responder certificate and its signing key
+#[derive(Debug, Clone)]
+pub struct OcspResponderCertificate {
+    pub certificate_pem: String,
+    pub private_key_pem: String,
+    pub fingerprint: String,
+    pub generation_event: crate::events::CertificateGeneratedEvent,
+    pub signing_event: crate::events::CertificateSignedEvent,
+}
+
+/// Issue a delegated OCSP responder certificate under `issuer_cert_pem`
+///
+/// The responder gets a fresh P-256 key. Its certificate carries the
+/// OCSPSigning EKU and id-pkix-ocsp-nocheck, so relying parties accept its
+/// responses for certificates of this issuer without a status check on the
+/// responder itself; keep `validity_days` short and reissue instead.
+pub fn generate_ocsp_responder_certificate<S: rcgen::SigningKey>(
+    params: OcspResponderParams,
+    issuer_cert_pem: &str,
+    issuer_signer: S,
+    issuer_ca_id: Uuid,
+    correlation_id: Uuid,
+    causation_id: Option<Uuid>,
+) -> Result<OcspResponderCertificate, String> {
+    issuer_path_len(issuer_cert_pem)?;
+    if params.validity_days == 0 {
+        return Err("OCSP responder validity must be at least one day".to_string());
+    }
+
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::CommonName, params.common_name.clone());
+    dn.push(DnType::OrganizationName, params.organization.clone());
+
+    let mut cert_params = CertificateParams::new(vec![])
+        .map_err(|e| format!("Failed to create certificate params: {}", e))?;
+    cert_params.distinguished_name = dn;
+    cert_params.is_ca = IsCa::ExplicitNoCa;
+    cert_params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+    cert_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::OcspSigning];
+    cert_params.custom_extensions = vec![CustomExtension::from_oid_content(
+        OCSP_NOCHECK_OID,
+        Null.to_der().map_err(|e| format!("Failed to encode ocsp-nocheck: {}", e))?,
+    )];
+    cert_params.use_authority_key_identifier_extension = true;
+
+    let cert_id = Uuid::now_v7();
+    let not_before = OffsetDateTime::now_utc();
+    let not_after = not_before + Duration::days(params.validity_days as i64);
+    cert_params.not_before = not_before;
+    cert_params.not_after = not_after;
+    cert_params.serial_number = Some(certificate_serial(cert_id));
+
+    let key_pair = RcgenKeyPair::generate()
+        .map_err(|e| format!("Failed to generate key pair: {}", e))?;
+
+    let issuer_algorithm = signature_algorithm_of(issuer_signer.algorithm());
+    let signature_algorithm = signature_algorithm_name(issuer_algorithm, issuer_signer.algorithm());
+    let issuer = Issuer::from_ca_cert_pem(issuer_cert_pem, issuer_signer)
+        .map_err(|e| format!("Failed to load issuer CA: {}", e))?;
+    let cert = cert_params.signed_by(&key_pair, &issuer)
+        .map_err(|e| format!("Failed to sign OCSP responder certificate: {}", e))?;
+
+    let validity = certificate_validity(not_before, not_after)?;
+
+    let generation_event = crate::events::CertificateGeneratedEvent {
+        cert_id,
+        key_id: Uuid::now_v7(),
+        subject_name: SubjectName::new(CommonName::new_unchecked(&params.common_name))
+            .with_organization(OrganizationName::new_unchecked(&params.organization)),
+        subject_alt_name: None,
+        key_usage: KeyUsage::ocsp_signing(),
+        extended_key_usage: Some(ExtendedKeyUsage::ocsp_signing()),
+        validity,
+        basic_constraints: BasicConstraintsVO::end_entity(),
+        issuer: Some(issuer_ca_id),
+        signature_algorithm: issuer_algorithm,
+        correlation_id,
+        causation_id,
+    };
+
+    let signing_event = crate::events::CertificateSignedEvent {
+        cert_id,
+        signed_by: issuer_ca_id,
+        signature_algorithm,
+        signed_at: Utc::now(),
+        correlation_id,
+        causation_id: Some(cert_id), // Signing caused by certificate generation
+    };
+
+    Ok(OcspResponderCertificate {
+        certificate_pem: cert.pem(),
+        private_key_pem: key_pair.serialize_pem(),
+        fingerprint: calculate_fingerprint(cert.der()),
+        generation_event,
+        signing_event,
+    })
+}
+
+/// Answers OCSP requests for one issuing CA
+///
+/// Either the CA signs responses itself ([`OcspResponder::issuer_signed`])
+/// or a delegated responder certificate does ([`OcspResponder::delegated`]).
+/// Certificate status comes from a lookup over the projection, e.g.
+/// `KeyManifest::ocsp_status`.
+pub struct OcspResponder<S> {
+    issuer_name_der: Vec<u8>,
+    issuer_key_bits: Vec<u8>,
+    responder_key_hash: Vec<u8>,
+    responder_cert: Option<X509CertDer>,
+    signer: S,
+    signature_algorithm: AlgorithmIdentifierOwned,
+    next_update: chrono::Duration,
+}
+
+pub(crate) fn parse_certificate(pem: &str) -> Result<X509CertDer, String> {
+    let (_, pem) = x509_parser::pem::parse_x509_pem(pem.as_bytes())
+        .map_err(|e| format!("Failed to parse certificate PEM: {}", e))?;
+    X509CertDer::from_der(&pem.contents)
+        .map_err(|e| format!("Failed to parse certificate: {}", e))
+}
+
+pub(crate) fn signature_algorithm_identifier(alg: &rcgen::SignatureAlgorithm) -> Result<AlgorithmIdentifierOwned, String> {
+    let (oid, null_params) = if alg == &rcgen::PKCS_ECDSA_P256_SHA256 {
+        (rfc5912::ECDSA_WITH_SHA_256, false)
+    } else if alg == &rcgen::PKCS_ECDSA_P384_SHA384 {
+        (rfc5912::ECDSA_WITH_SHA_384, false)
+    } else if alg == &rcgen::PKCS_ED25519 {
+        (rfc8410::ID_ED_25519, false)
+    } else if alg == &rcgen::PKCS_RSA_SHA256 {
+        (rfc5912::SHA_256_WITH_RSA_ENCRYPTION, true)
+    } else if alg == &rcgen::PKCS_RSA_SHA384 {
+        (rfc5912::SHA_384_WITH_RSA_ENCRYPTION, true)
+    } else if alg == &rcgen::PKCS_RSA_SHA512 {
+        (rfc5912::SHA_512_WITH_RSA_ENCRYPTION, true)
+    } else {
+        return Err(format!("Unsupported signature algorithm: {:?}", alg));
+    };
+    Ok(AlgorithmIdentifierOwned {
+        oid,
+        parameters: null_params.then(der::Any::null),
+    })
+}
+
+/// Hash with the algorithm named in a CertID (SHA-1 or SHA-256)
+fn cert_id_digest(hash_algorithm: &ObjectIdentifier, data: &[u8]) -> Option<Vec<u8>> {
+    let algorithm = if *hash_algorithm == rfc5912::ID_SHA_1 {
+        &ring::digest::SHA1_FOR_LEGACY_USE_ONLY
+    } else if *hash_algorithm == rfc5912::ID_SHA_256 {
+        &ring::digest::SHA256
+    } else {
+        return None;
+    };
+    Some(ring::digest::digest(algorithm, data).as_ref().to_vec())
+}
+
+fn generalized_time(at: DateTime<Utc>) -> Result<OcspGeneralizedTime, String> {
+    let secs = u64::try_from(at.timestamp()).map_err(|_| format!("Time before epoch: {}", at))?;
+    GeneralizedTime::from_unix_duration(std::time::Duration::from_secs(secs))
+        .map(OcspGeneralizedTime)
+        .map_err(|e| format!("Invalid time {}: {}", at, e))
+}
+
+fn crl_reason(reason: &RevocationReason) -> CrlReason {
+    match reason {
+        RevocationReason::Unspecified => CrlReason::Unspecified,
+        RevocationReason::KeyCompromise => CrlReason::KeyCompromise,
+        RevocationReason::CACompromise => CrlReason::CaCompromise,
+        RevocationReason::AffiliationChanged => CrlReason::AffiliationChanged,
+        RevocationReason::Superseded => CrlReason::Superseded,
+        RevocationReason::CessationOfOperation => CrlReason::CessationOfOperation,
+        RevocationReason::CertificateHold => CrlReason::CertificateHold,
+        RevocationReason::RemoveFromCRL => CrlReason::RemoveFromCRL,
+        RevocationReason::PrivilegeWithdrawn => CrlReason::PrivilegeWithdrawn,
+        RevocationReason::AACompromise => CrlReason::AaCompromise,
+    }
+}
+
+impl<S: rcgen::SigningKey> OcspResponder<S> {
+    /// Responder whose responses are signed directly by the issuing CA key
+    pub fn issuer_signed(issuer_cert_pem: &str, issuer_signer: S) -> Result<Self, String> {
+        issuer_path_len(issuer_cert_pem)?;
+        let issuer = parse_certificate(issuer_cert_pem)?;
+        Self::build(&issuer, &issuer, None, issuer_signer)
+    }
+
+    /// Responder using a delegated certificate from
+    /// [`generate_ocsp_responder_certificate`]
+    ///
+    /// Fails unless the responder certificate was issued by the CA and
+    /// carries the OCSPSigning EKU.
+    pub fn delegated(issuer_cert_pem: &str, responder_cert_pem: &str, responder_signer: S) -> Result<Self, String> {
+        issuer_path_len(issuer_cert_pem)?;
+        let issuer = parse_certificate(issuer_cert_pem)?;
+        let responder = parse_certificate(responder_cert_pem)?;
+
+        if responder.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+            return Err("OCSP responder certificate was not issued by this CA".to_string());
+        }
+        let ocsp_signing = responder
+            .tbs_certificate
+            .extensions
+            .iter()
+            .flatten()
+            .filter(|ext| ext.extn_id == rfc5280::ID_CE_EXT_KEY_USAGE)
+            .filter_map(|ext| EkuExtension::from_der(ext.extn_value.as_bytes()).ok())
+            .any(|eku| eku.0.contains(&rfc5280::ID_KP_OCSP_SIGNING));
+        if !ocsp_signing {
+            return Err("OCSP responder certificate lacks the OCSPSigning extended key usage".to_string());
+        }
+
+        Self::build(&issuer, &responder, Some(responder.clone()), responder_signer)
+    }
+
+    fn build(
+        issuer: &X509CertDer,
+        signing_cert: &X509CertDer,
+        responder_cert: Option<X509CertDer>,
+        signer: S,
+    ) -> Result<Self, String> {
+        let signing_key_bits = signing_cert.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes();
+        if signing_key_bits != signer.der_bytes() {
+            return Err("Signing key does not match the responder certificate".to_string());
+        }
+
+        Ok(Self {
+            issuer_name_der: issuer
+                .tbs_certificate
+                .subject
+                .to_der()
+                .map_err(|e| format!("Failed to encode issuer name: {}", e))?,
+            issuer_key_bits: issuer.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes().to_vec(),
+            responder_key_hash: ring::digest::digest(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY, signing_key_bits)
+                .as_ref()
+                .to_vec(),
+            responder_cert,
+            signature_algorithm: signature_algorithm_identifier(signer.algorithm())?,
+            signer,
+            next_update: chrono::Duration::hours(24),
+        })
+    }
+
+    /// How long relying parties may cache a response (default 24 hours)
+    pub fn with_next_update(mut self, next_update: chrono::Duration) -> Self {
+        self.next_update = next_update;
+        self
+    }
+
+    /// Answer a DER-encoded OCSPRequest
+    ///
+    /// Always returns a DER-encoded OCSPResponse: undecodable requests get
+    /// `malformedRequest` and signing failures `internalError`. Certificates
+    /// of other issuers are answered `unknown`. A request nonce is echoed.
+    pub fn respond(&self, request_der: &[u8], status_of: impl Fn(Uuid) -> OcspCertStatus) -> Vec<u8> {
+        let response = match OcspRequest::from_der(request_der) {
+            Ok(request) => self
+                .sign_response(&request, status_of, Utc::now())
+                .unwrap_or_else(|_| OcspResponse::internal_error()),
+            Err(_) => OcspResponse::malformed_request(),
+        };
+        response.to_der().unwrap_or_default()
+    }
+
+    fn status_for(&self, cert_id: &CertId, status_of: &impl Fn(Uuid) -> OcspCertStatus) -> OcspCertStatus {
+        let hash_oid = &cert_id.hash_algorithm.oid;
+        let ours = cert_id_digest(hash_oid, &self.issuer_name_der).as_deref() == Some(cert_id.issuer_name_hash.as_bytes())
+            && cert_id_digest(hash_oid, &self.issuer_key_bits).as_deref() == Some(cert_id.issuer_key_hash.as_bytes());
+        if !ours {
+            return OcspCertStatus::Unknown;
+        }
+        certificate_id_from_serial(cert_id.serial_number.as_bytes())
+            .map_or(OcspCertStatus::Unknown, status_of)
+    }
+
+    fn sign_response(
+        &self,
+        request: &OcspRequest,
+        status_of: impl Fn(Uuid) -> OcspCertStatus,
+        now: DateTime<Utc>,
+    ) -> Result<OcspResponse, String> {
+        let this_update = generalized_time(now)?;
+        let next_update = generalized_time(now + self.next_update)?;
+
+        let mut responses = Vec::with_capacity(request.tbs_request.request_list.len());
+        for single in &request.tbs_request.request_list {
+            let cert_status = match self.status_for(&single.req_cert, &status_of) {
+                OcspCertStatus::Good => CertStatus::good(),
+                OcspCertStatus::Revoked { revoked_at, reason } => CertStatus::revoked(RevokedInfo {
+                    revocation_time: generalized_time(revoked_at)?,
+                    revocation_reason: Some(crl_reason(&reason)),
+                }),
+                OcspCertStatus::Unknown => CertStatus::unknown(),
+            };
+            responses.push(SingleResponse {
+                cert_id: single.req_cert.clone(),
+                cert_status,
+                this_update,
+                next_update: Some(next_update),
+                single_extensions: None,
+            });
+        }
+
+        let response_extensions: Option<Extensions> = request
+            .tbs_request
+            .request_extensions
+            .as_ref()
+            .and_then(|exts| exts.iter().find(|ext| ext.extn_id == rfc6960::ID_PKIX_OCSP_NONCE))
+            .map(|nonce| {
+                vec![Extension {
+                    extn_id: rfc6960::ID_PKIX_OCSP_NONCE,
+                    critical: false,
+                    extn_value: nonce.extn_value.clone(),
+                }]
+            });
+
+        let tbs_response_data = ResponseData {
+            version: Version::V1,
+            responder_id: ResponderId::ByKey(
+                OctetString::new(self.responder_key_hash.clone())
+                    .map_err(|e| format!("Failed to encode responder key hash: {}", e))?,
+            ),
+            produced_at: this_update,
+            responses,
+            response_extensions,
+        };
+        let tbs_der = tbs_response_data
+            .to_der()
+            .map_err(|e| format!("Failed to encode OCSP response data: {}", e))?;
+        let signature = self.signer
+            .sign(&tbs_der)
+            .map_err(|e| format!("Failed to sign OCSP response: {}", e))?;
+
+        let basic = BasicOcspResponse {
+            tbs_response_data,
+            signature_algorithm: self.signature_algorithm.clone(),
+            signature: BitString::from_bytes(&signature)
+                .map_err(|e| format!("Failed to encode OCSP signature: {}", e))?,
+            certs: self.responder_cert.clone().map(|cert| vec![cert]),
+        };
+        OcspResponse::successful(basic).map_err(|e| format!("Failed to encode OCSP response: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::seed_derivation::derive_master_seed;
+    use crate::crypto::x509::{generate_root_ca, RootCAParams};
+    use x509_ocsp::{OcspResponseStatus, Request, TbsRequest};
+
+    fn test_root() -> (String, RcgenKeyPair) {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let (root, _) = generate_root_ca(
+            &master_seed.derive_child("root-ca"),
+            RootCAParams::default(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let key = RcgenKeyPair::from_pem(&root.private_key_pem).unwrap();
+        (root.certificate_pem, key)
+    }
+
+    fn request_for(issuer_pem: &str, cert_ids: &[Uuid], nonce: Option<&[u8]>) -> Vec<u8> {
+        let issuer = parse_certificate(issuer_pem).unwrap();
+        let name_hash = cert_id_digest(&rfc5912::ID_SHA_1, &issuer.tbs_certificate.subject.to_der().unwrap()).unwrap();
+        let key_hash = cert_id_digest(
+            &rfc5912::ID_SHA_1,
+            issuer.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes(),
+        )
+        .unwrap();
+        let request_list = cert_ids
+            .iter()
+            .map(|id| Request {
+                req_cert: CertId {
+                    hash_algorithm: AlgorithmIdentifierOwned { oid: rfc5912::ID_SHA_1, parameters: Some(der::Any::null()) },
+                    issuer_name_hash: OctetString::new(name_hash.clone()).unwrap(),
+                    issuer_key_hash: OctetString::new(key_hash.clone()).unwrap(),
+                    serial_number: x509_cert::serial_number::SerialNumber::new(certificate_serial(*id).as_ref()).unwrap(),
+                },
+                single_request_extensions: None,
+            })
+            .collect();
+        let request_extensions = nonce.map(|n| {
+            vec![Extension {
+                extn_id: rfc6960::ID_PKIX_OCSP_NONCE,
+                critical: false,
+                extn_value: OctetString::new(OctetString::new(n).unwrap().to_der().unwrap()).unwrap(),
+            }]
+        });
+        OcspRequest {
+            tbs_request: TbsRequest {
+                version: Version::V1,
+                requestor_name: None,
+                request_list,
+                request_extensions,
+            },
+            optional_signature: None,
+        }
+        .to_der()
+        .unwrap()
+    }
+
+    fn basic_response(der: &[u8]) -> BasicOcspResponse {
+        let response = OcspResponse::from_der(der).unwrap();
+        assert_eq!(response.response_status, OcspResponseStatus::Successful);
+        BasicOcspResponse::from_der(response.response_bytes.unwrap().response.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_generate_ocsp_responder_certificate() {
+        let (root_pem, root_key) = test_root();
+        let responder = generate_ocsp_responder_certificate(
+            OcspResponderParams::default(),
+            &root_pem,
+            &root_key,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+
+        let cert = parse_certificate(&responder.certificate_pem).unwrap();
+        let extensions = cert.tbs_certificate.extensions.unwrap();
+        assert!(extensions.iter().any(|ext| ext.extn_id == rfc6960::ID_PKIX_OCSP_NOCHECK));
+        assert!(extensions.iter().any(|ext| ext.extn_id == rfc5280::ID_CE_EXT_KEY_USAGE));
+        assert_eq!(
+            certificate_id_from_serial(cert.tbs_certificate.serial_number.as_bytes()),
+            Some(responder.generation_event.cert_id)
+        );
+
+        // Certificates without the OCSPSigning EKU can't act as responders
+        assert!(OcspResponder::delegated(&root_pem, &root_pem, &root_key).is_err());
+    }
+
+    #[test]
+    fn test_delegated_responder_answers_from_status_lookup() {
+        let (root_pem, root_key) = test_root();
+        let responder_cert = generate_ocsp_responder_certificate(
+            OcspResponderParams::default(),
+            &root_pem,
+            &root_key,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let responder_key = RcgenKeyPair::from_pem(&responder_cert.private_key_pem).unwrap();
+        let responder = OcspResponder::delegated(&root_pem, &responder_cert.certificate_pem, &responder_key).unwrap();
+
+        let good = Uuid::now_v7();
+        let revoked = Uuid::now_v7();
+        let unknown = Uuid::now_v7();
+        let revoked_at = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let status_of = |id: Uuid| {
+            if id == good {
+                OcspCertStatus::Good
+            } else if id == revoked {
+                OcspCertStatus::Revoked { revoked_at, reason: RevocationReason::KeyCompromise }
+            } else {
+                OcspCertStatus::Unknown
+            }
+        };
+
+        let der = responder.respond(&request_for(&root_pem, &[good, revoked, unknown], Some(b"nonce-123")), status_of);
+        let basic = basic_response(&der);
+        let statuses: Vec<_> = basic.tbs_response_data.responses.iter().map(|r| r.cert_status).collect();
+        assert_eq!(statuses[0], CertStatus::good());
+        match statuses[1] {
+            CertStatus::Revoked(info) => {
+                assert_eq!(info.revocation_reason, Some(CrlReason::KeyCompromise));
+                assert_eq!(info.revocation_time, generalized_time(revoked_at).unwrap());
+            }
+            other => panic!("expected revoked, got {:?}", other),
+        }
+        assert_eq!(statuses[2], CertStatus::unknown());
+        assert_eq!(basic.nonce().unwrap().0.as_bytes(), b"nonce-123");
+        assert_eq!(basic.certs.as_ref().map(Vec::len), Some(1));
+
+        // Signed by the responder key
+        let tbs = basic.tbs_response_data.to_der().unwrap();
+        ring::signature::UnparsedPublicKey::new(&ring::signature::ECDSA_P256_SHA256_ASN1, responder_key.public_key_raw())
+            .verify(&tbs, basic.signature.raw_bytes())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_responder_rejects_foreign_issuer_and_garbage() {
+        let (root_pem, root_key) = test_root();
+        let responder = OcspResponder::issuer_signed(&root_pem, &root_key).unwrap();
+
+        let other_root = rcgen::KeyPair::generate().unwrap();
+        let mut other_params = CertificateParams::new(vec![]).unwrap();
+        other_params.is_ca = IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        let other_pem = other_params.self_signed(&other_root).unwrap().pem();
+
+        let der = responder.respond(&request_for(&other_pem, &[Uuid::now_v7()], None), |_| OcspCertStatus::Good);
+        let basic = basic_response(&der);
+        assert_eq!(basic.tbs_response_data.responses[0].cert_status, CertStatus::unknown());
+        assert!(basic.certs.is_none());
+
+        let malformed = OcspResponse::from_der(&responder.respond(b"not ocsp", |_| OcspCertStatus::Good)).unwrap();
+        assert_eq!(malformed.response_status, OcspResponseStatus::MalformedRequest);
+    }
+}
@@ -0,0 +1,133 @@
+//! PKCS#12 bundles for issued certificates
+//!
+//! Packages a leaf certificate, its issuing chain and the private key into a
+//! passphrase-protected `.p12`, the import format expected by browsers, Java
+//! keystores and the Windows certificate store.
+//!
+//! ```text
+//! cert.pem + chain.pem + private key (PKCS#8 PEM)
+//!   ↓ key must match the leaf certificate
+//! PKCS#12 (PBES2: PBKDF2-HMAC-SHA256 + AES-256-CBC, HMAC-SHA256 MAC)
+//! ```
+
+use p12_keystore::{Certificate, KeyStore, KeyStoreEntry, PrivateKey, PrivateKeyChain};
+use rcgen::PublicKeyData;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+/// Iterations for both the key derivation and the integrity MAC
+const PKCS12_ITERATIONS: u32 = 600_000;
+
+fn pem_certificates(pem: &str) -> Result<Vec<Vec<u8>>, String> {
+    x509_parser::pem::Pem::iter_from_buffer(pem.as_bytes())
+        .map(|block| {
+            let block = block.map_err(|e| format!("Failed to parse certificate PEM: {}", e))?;
+            if block.label != "CERTIFICATE" {
+                return Err(format!("Unexpected PEM block '{}' in certificate chain", block.label));
+            }
+            Ok(block.contents)
+        })
+        .collect()
+}
+
+/// Build a PKCS#12 bundle for an issued certificate
+///
+/// `chain_pem` lists the issuing chain leaf first, root last; a copy of the
+/// leaf at the head of the chain (as written by `store_certificate_chain`)
+/// is skipped. The private key must be PKCS#8 PEM and belong to the leaf.
+/// `friendly_name` is the alias shown by importing applications.
+pub fn build_pkcs12(
+    certificate_pem: &str,
+    chain_pem: &str,
+    private_key_pem: &str,
+    passphrase: &str,
+    friendly_name: &str,
+) -> Result<Vec<u8>, String> {
+    if passphrase.trim().is_empty() {
+        return Err("A passphrase is required to protect the PKCS#12 private key".to_string());
+    }
+
+    let leaf_der = pem_certificates(certificate_pem)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No certificate found in leaf PEM".to_string())?;
+    let (_, leaf) = X509Certificate::from_der(&leaf_der)
+        .map_err(|e| format!("Failed to parse certificate: {}", e))?;
+
+    let key_pair = rcgen::KeyPair::from_pem(private_key_pem)
+        .map_err(|e| format!("Failed to parse private key: {}", e))?;
+    if key_pair.subject_public_key_info() != leaf.public_key().raw {
+        return Err("Private key does not match the certificate".to_string());
+    }
+
+    let mut certs = vec![Certificate::from_der(&leaf_der)
+        .map_err(|e| format!("Failed to load certificate: {}", e))?];
+    for der in pem_certificates(chain_pem)? {
+        if der == leaf_der {
+            continue;
+        }
+        certs.push(Certificate::from_der(&der).map_err(|e| format!("Failed to load chain certificate: {}", e))?);
+    }
+
+    let key = PrivateKey::from_der(&key_pair.serialize_der())
+        .map_err(|e| format!("Failed to load private key: {}", e))?;
+    let local_key_id = ring::digest::digest(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY, &leaf_der);
+    let chain = PrivateKeyChain::new(local_key_id.as_ref(), key, certs);
+
+    let mut keystore = KeyStore::new();
+    keystore.add_entry(friendly_name, KeyStoreEntry::PrivateKeyChain(chain));
+    keystore
+        .writer(passphrase)
+        .encryption_iterations(PKCS12_ITERATIONS)
+        .mac_iterations(PKCS12_ITERATIONS)
+        .write()
+        .map_err(|e| format!("Failed to encode PKCS#12: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::seed_derivation::derive_master_seed;
+    use crate::crypto::x509::{generate_intermediate_ca, generate_root_ca, IntermediateCAParams, RootCAParams};
+    use p12_keystore::Pkcs12ImportPolicy;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_build_pkcs12_round_trips_key_and_chain() {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_seed = master_seed.derive_child("root-ca");
+        let (root, _) = generate_root_ca(&root_seed, RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let (intermediate, _, _) = generate_intermediate_ca(
+            &root_seed.derive_child("intermediate"),
+            IntermediateCAParams::default(),
+            &root.certificate_pem,
+            &root.private_key_pem,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let chain_pem = format!("{}{}", intermediate.certificate_pem, root.certificate_pem);
+
+        let p12 = build_pkcs12(&intermediate.certificate_pem, &chain_pem, &intermediate.private_key_pem, "correct horse", "intermediate").unwrap();
+
+        let keystore = KeyStore::from_pkcs12(&p12, "correct horse", Pkcs12ImportPolicy::Strict).unwrap();
+        let (alias, chain) = keystore.private_key_chain().unwrap();
+        assert_eq!(alias, "intermediate");
+        // Leaf first, the duplicated leaf in chain.pem is dropped
+        assert_eq!(chain.certs().len(), 2);
+        assert_eq!(chain.certs()[0].as_der(), pem_certificates(&intermediate.certificate_pem).unwrap()[0]);
+
+        assert!(KeyStore::from_pkcs12(&p12, "wrong", Pkcs12ImportPolicy::Strict).is_err());
+    }
+
+    #[test]
+    fn test_build_pkcs12_rejects_empty_passphrase_and_foreign_key() {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let (root, _) = generate_root_ca(&master_seed.derive_child("root-ca"), RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let other_key = rcgen::KeyPair::generate().unwrap().serialize_pem();
+
+        assert!(build_pkcs12(&root.certificate_pem, "", &root.private_key_pem, " ", "root").is_err());
+        let err = build_pkcs12(&root.certificate_pem, "", &other_key, "correct horse", "root").unwrap_err();
+        assert!(err.contains("does not match"));
+    }
+}
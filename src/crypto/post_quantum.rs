@@ -0,0 +1,412 @@
+//! Post-quantum keys (ML-KEM-768, ML-DSA-65) and hybrid certificates
+//!
+//! Both key types are derived from a `MasterSeed` like every other key,
+//! so a PQ credential can be regenerated from the ceremony passphrase:
+//!
+//! ```text
+//! seed.derive_child("{path}")
+//!   ├─ ML-DSA-65: ξ = seed                        (FIPS 204 KeyGen_internal)
+//!   └─ ML-KEM-768: d = child("ml-kem/d"), z = child("ml-kem/z")  (FIPS 203)
+//! ```
+//!
+//! ## Hybrid Certificates
+//!
+//! Relying parties without PQ support must keep working, so PQ keys are
+//! certified in "catalyst" hybrid certificates (ITU-T X.509 (2019) §9.8):
+//! an ordinary classical certificate that additionally carries
+//!
+//! - `subjectAltPublicKeyInfo` (2.5.29.72): the subject's ML-DSA-65 key
+//! - `altSignatureAlgorithm` (2.5.29.73): ML-DSA-65
+//! - `altSignatureValue` (2.5.29.74): the issuer's ML-DSA-65 signature
+//!   over the preTBSCertificate (the TBS without its `signature` field and
+//!   without the `altSignatureValue` extension)
+//!
+//! All three extensions are non-critical, so classical verifiers ignore
+//! them.
+
+use der::asn1::{BitString, ObjectIdentifier};
+use der::{Decode, Encode, Sequence};
+use ml_dsa::signature::{Signer, Verifier};
+use ml_dsa::{KeyGen, MlDsa65};
+use ml_kem::kem::{Decapsulate, Encapsulate};
+use ml_kem::{EncodedSizeUser, KemCore, MlKem768};
+use rcgen::{CertificateParams, CustomExtension, Issuer};
+use x509_cert::certificate::Version;
+use x509_cert::ext::Extensions;
+use x509_cert::name::Name;
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+use x509_cert::time::Validity;
+use x509_cert::Certificate as X509CertDer;
+
+use super::seed_derivation::MasterSeed;
+use super::x509::{calculate_fingerprint, certificate_serial};
+
+/// id-ml-dsa-65 (FIPS 204, NIST CSOR)
+pub const ML_DSA_65_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.18");
+
+/// id-alg-ml-kem-768 (FIPS 203, NIST CSOR)
+pub const ML_KEM_768_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.4.2");
+
+const SUBJECT_ALT_PUBLIC_KEY_INFO_OID: &[u64] = &[2, 5, 29, 72];
+const ALT_SIGNATURE_ALGORITHM_OID: &[u64] = &[2, 5, 29, 73];
+const ALT_SIGNATURE_VALUE_OID: &[u64] = &[2, 5, 29, 74];
+
+// ============================================================================
+// ML-DSA-65
+// ============================================================================
+
+/// ML-DSA-65 signing key pair
+pub struct MlDsaKeyPair {
+    key_pair: ml_dsa::KeyPair<MlDsa65>,
+}
+
+impl MlDsaKeyPair {
+    /// Derive the key pair from a seed (the seed is FIPS 204's ξ)
+    pub fn from_seed(seed: &MasterSeed) -> Self {
+        Self {
+            key_pair: MlDsa65::key_gen_internal(&(*seed.as_bytes()).into()),
+        }
+    }
+
+    /// Encoded public key (1952 bytes)
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.key_pair.verifying_key().encode().to_vec()
+    }
+
+    /// DER SubjectPublicKeyInfo of the public key
+    pub fn public_key_der(&self) -> Result<Vec<u8>, String> {
+        spki_der(ML_DSA_65_OID, &self.public_key_bytes())
+    }
+
+    /// Sign `message` (3309 bytes, empty context)
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let signature: ml_dsa::Signature<MlDsa65> = self.key_pair.signing_key().sign(message);
+        signature.encode().to_vec()
+    }
+}
+
+impl std::fmt::Debug for MlDsaKeyPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MlDsaKeyPair")
+            .field("public_key", &hex::encode(&self.public_key_bytes()[..16]))
+            .field("signing_key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Verify an ML-DSA-65 signature made by [`MlDsaKeyPair::sign`]
+pub fn verify_ml_dsa(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<(), String> {
+    let encoded_key = ml_dsa::EncodedVerifyingKey::<MlDsa65>::try_from(public_key)
+        .map_err(|_| format!("ML-DSA-65 public key must be 1952 bytes, got {}", public_key.len()))?;
+    let verifying_key = ml_dsa::VerifyingKey::<MlDsa65>::decode(&encoded_key);
+    let encoded_signature = ml_dsa::EncodedSignature::<MlDsa65>::try_from(signature)
+        .map_err(|_| format!("ML-DSA-65 signature must be 3309 bytes, got {}", signature.len()))?;
+    let signature = ml_dsa::Signature::<MlDsa65>::decode(&encoded_signature)
+        .ok_or_else(|| "Malformed ML-DSA-65 signature".to_string())?;
+    verifying_key
+        .verify(message, &signature)
+        .map_err(|_| "ML-DSA-65 signature verification failed".to_string())
+}
+
+// ============================================================================
+// ML-KEM-768
+// ============================================================================
+
+type MlKemDecapsulationKey = <MlKem768 as KemCore>::DecapsulationKey;
+type MlKemEncapsulationKey = <MlKem768 as KemCore>::EncapsulationKey;
+
+/// ML-KEM-768 key encapsulation key pair
+pub struct MlKemKeyPair {
+    decapsulation_key: MlKemDecapsulationKey,
+    encapsulation_key: MlKemEncapsulationKey,
+}
+
+impl MlKemKeyPair {
+    /// Derive the key pair from a seed (FIPS 203's d and z are child seeds)
+    pub fn from_seed(seed: &MasterSeed) -> Self {
+        let d = seed.derive_child("ml-kem/d");
+        let z = seed.derive_child("ml-kem/z");
+        let (decapsulation_key, encapsulation_key) =
+            MlKem768::generate_deterministic(&(*d.as_bytes()).into(), &(*z.as_bytes()).into());
+        Self {
+            decapsulation_key,
+            encapsulation_key,
+        }
+    }
+
+    /// Encoded encapsulation (public) key (1184 bytes)
+    pub fn public_key_bytes(&self) -> Vec<u8> {
+        self.encapsulation_key.as_bytes().to_vec()
+    }
+
+    /// DER SubjectPublicKeyInfo of the encapsulation key
+    pub fn public_key_der(&self) -> Result<Vec<u8>, String> {
+        spki_der(ML_KEM_768_OID, &self.public_key_bytes())
+    }
+
+    /// Recover the shared secret of a ciphertext from [`ml_kem_encapsulate`]
+    pub fn decapsulate(&self, ciphertext: &[u8]) -> Result<[u8; 32], String> {
+        let ciphertext = ml_kem::Ciphertext::<MlKem768>::try_from(ciphertext)
+            .map_err(|_| format!("ML-KEM-768 ciphertext must be 1088 bytes, got {}", ciphertext.len()))?;
+        let shared = self
+            .decapsulation_key
+            .decapsulate(&ciphertext)
+            .map_err(|_| "ML-KEM-768 decapsulation failed".to_string())?;
+        Ok(shared.into())
+    }
+}
+
+impl std::fmt::Debug for MlKemKeyPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MlKemKeyPair")
+            .field("public_key", &hex::encode(&self.public_key_bytes()[..16]))
+            .field("decapsulation_key", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Encapsulate a fresh shared secret to an ML-KEM-768 public key
+///
+/// Returns the ciphertext to send and the 32-byte shared secret.
+pub fn ml_kem_encapsulate(public_key: &[u8]) -> Result<(Vec<u8>, [u8; 32]), String> {
+    let encoded = ml_kem::Encoded::<MlKemEncapsulationKey>::try_from(public_key)
+        .map_err(|_| format!("ML-KEM-768 public key must be 1184 bytes, got {}", public_key.len()))?;
+    let encapsulation_key = MlKemEncapsulationKey::from_bytes(&encoded);
+    let (ciphertext, shared) = encapsulation_key
+        .encapsulate(&mut rand::rngs::OsRng)
+        .map_err(|_| "ML-KEM-768 encapsulation failed".to_string())?;
+    Ok((ciphertext.to_vec(), shared.into()))
+}
+
+fn spki_der(oid: ObjectIdentifier, public_key: &[u8]) -> Result<Vec<u8>, String> {
+    SubjectPublicKeyInfoOwned {
+        algorithm: AlgorithmIdentifierOwned { oid, parameters: None },
+        subject_public_key: BitString::from_bytes(public_key)
+            .map_err(|e| format!("Failed to encode public key: {}", e))?,
+    }
+    .to_der()
+    .map_err(|e| format!("Failed to encode SubjectPublicKeyInfo: {}", e))
+}
+
+// ============================================================================
+// HYBRID CERTIFICATES
+// ============================================================================
+
+/// TBSCertificate without its `signature` field, signed by `altSignatureValue`
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct PreTbsCertificate {
+    #[asn1(context_specific = "0", default = "Default::default")]
+    version: Version,
+    serial_number: SerialNumber,
+    issuer: Name,
+    validity: Validity,
+    subject_public_key_info: SubjectPublicKeyInfoOwned,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    issuer_unique_id: Option<BitString>,
+    #[asn1(context_specific = "2", tag_mode = "IMPLICIT", optional = "true")]
+    subject_unique_id: Option<BitString>,
+    #[asn1(context_specific = "3", tag_mode = "EXPLICIT", optional = "true")]
+    extensions: Option<Extensions>,
+}
+
+/// A classical certificate carrying an ML-DSA-65 key and signature
+#[derive(Clone, Debug)]
+pub struct HybridCertificate {
+    pub certificate_pem: String,
+    /// Certificate fingerprint (SHA-256)
+    pub fingerprint: String,
+    /// Subject's ML-DSA-65 public key, as in `subjectAltPublicKeyInfo`
+    pub alt_public_key: Vec<u8>,
+}
+
+/// Issue a hybrid certificate for `params`
+///
+/// The classical signature is made by `issuer` as for any other
+/// certificate; `issuer_alt_key` adds the ML-DSA-65 alternative signature.
+/// A certificate without a serial number gets a fresh one, since the
+/// certificate is signed twice and both passes must produce the same TBS.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let issuer = Issuer::from_ca_cert_pem(&intermediate_pem, &intermediate_key)?;
+/// let issuer_pq = MlDsaKeyPair::from_seed(&intermediate_seed.derive_child("ml-dsa"));
+/// let subject_pq = MlDsaKeyPair::from_seed(&server_seed.derive_child("ml-dsa"));
+/// let hybrid = issue_hybrid_certificate(params, &server_key, &subject_pq.public_key_bytes(), &issuer, &issuer_pq)?;
+/// ```
+pub fn issue_hybrid_certificate<S: rcgen::SigningKey>(
+    mut params: CertificateParams,
+    subject_key: &impl rcgen::PublicKeyData,
+    subject_alt_public_key: &[u8],
+    issuer: &Issuer<'_, S>,
+    issuer_alt_key: &MlDsaKeyPair,
+) -> Result<HybridCertificate, String> {
+    if params.serial_number.is_none() {
+        params.serial_number = Some(certificate_serial(uuid::Uuid::now_v7()));
+    }
+    let algorithm = AlgorithmIdentifierOwned { oid: ML_DSA_65_OID, parameters: None }
+        .to_der()
+        .map_err(|e| format!("Failed to encode altSignatureAlgorithm: {}", e))?;
+    params.custom_extensions.push(CustomExtension::from_oid_content(
+        SUBJECT_ALT_PUBLIC_KEY_INFO_OID,
+        spki_der(ML_DSA_65_OID, subject_alt_public_key)?,
+    ));
+    params
+        .custom_extensions
+        .push(CustomExtension::from_oid_content(ALT_SIGNATURE_ALGORITHM_OID, algorithm));
+
+    // First pass fixes the TBS the alternative signature covers
+    let unsigned = params
+        .signed_by(subject_key, issuer)
+        .map_err(|e| format!("Failed to sign certificate: {}", e))?;
+    let pre_tbs = pre_tbs_certificate(unsigned.der())?;
+    let alt_signature = BitString::from_bytes(&issuer_alt_key.sign(&pre_tbs))
+        .and_then(|bits| bits.to_der())
+        .map_err(|e| format!("Failed to encode altSignatureValue: {}", e))?;
+    params
+        .custom_extensions
+        .push(CustomExtension::from_oid_content(ALT_SIGNATURE_VALUE_OID, alt_signature));
+
+    let cert = params
+        .signed_by(subject_key, issuer)
+        .map_err(|e| format!("Failed to sign certificate: {}", e))?;
+    if pre_tbs_certificate(cert.der())? != pre_tbs {
+        return Err("Certificate changed between signing passes".to_string());
+    }
+
+    Ok(HybridCertificate {
+        certificate_pem: cert.pem(),
+        fingerprint: calculate_fingerprint(cert.der()),
+        alt_public_key: subject_alt_public_key.to_vec(),
+    })
+}
+
+/// Verify the ML-DSA-65 alternative signature of a hybrid certificate
+///
+/// Returns the subject's ML-DSA-65 public key. The classical signature is
+/// not checked here; verify it as for any other certificate.
+pub fn verify_hybrid_certificate(cert_der: &[u8], issuer_alt_public_key: &[u8]) -> Result<Vec<u8>, String> {
+    let cert = X509CertDer::from_der(cert_der).map_err(|e| format!("Failed to parse certificate: {}", e))?;
+    let extensions = cert.tbs_certificate.extensions.as_deref().unwrap_or_default();
+    let extension = |oid: &[u64]| {
+        let oid = oid_of(oid);
+        extensions
+            .iter()
+            .find(|ext| ext.extn_id == oid)
+            .map(|ext| ext.extn_value.as_bytes())
+            .ok_or_else(|| format!("Certificate has no {} extension", oid))
+    };
+
+    let algorithm = AlgorithmIdentifierOwned::from_der(extension(ALT_SIGNATURE_ALGORITHM_OID)?)
+        .map_err(|e| format!("Invalid altSignatureAlgorithm: {}", e))?;
+    if algorithm.oid != ML_DSA_65_OID {
+        return Err(format!("Unsupported alternative signature algorithm {}", algorithm.oid));
+    }
+    let signature = BitString::from_der(extension(ALT_SIGNATURE_VALUE_OID)?)
+        .map_err(|e| format!("Invalid altSignatureValue: {}", e))?;
+    let subject_key = SubjectPublicKeyInfoOwned::from_der(extension(SUBJECT_ALT_PUBLIC_KEY_INFO_OID)?)
+        .map_err(|e| format!("Invalid subjectAltPublicKeyInfo: {}", e))?;
+
+    verify_ml_dsa(issuer_alt_public_key, &pre_tbs_certificate(cert_der)?, signature.raw_bytes())?;
+    Ok(subject_key.subject_public_key.raw_bytes().to_vec())
+}
+
+/// DER preTBSCertificate of a certificate
+fn pre_tbs_certificate(cert_der: &[u8]) -> Result<Vec<u8>, String> {
+    let tbs = X509CertDer::from_der(cert_der)
+        .map_err(|e| format!("Failed to parse certificate: {}", e))?
+        .tbs_certificate;
+    let alt_signature_value = oid_of(ALT_SIGNATURE_VALUE_OID);
+    PreTbsCertificate {
+        version: tbs.version,
+        serial_number: tbs.serial_number,
+        issuer: tbs.issuer,
+        validity: tbs.validity,
+        subject_public_key_info: tbs.subject_public_key_info,
+        issuer_unique_id: tbs.issuer_unique_id,
+        subject_unique_id: tbs.subject_unique_id,
+        extensions: tbs
+            .extensions
+            .map(|extensions| extensions.into_iter().filter(|ext| ext.extn_id != alt_signature_value).collect()),
+    }
+    .to_der()
+    .map_err(|e| format!("Failed to encode preTBSCertificate: {}", e))
+}
+
+fn oid_of(arcs: &[u64]) -> ObjectIdentifier {
+    let dotted = arcs.iter().map(u64::to_string).collect::<Vec<_>>().join(".");
+    ObjectIdentifier::new_unwrap(&dotted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcgen::{BasicConstraints, DnType, IsCa, KeyPair as RcgenKeyPair};
+
+    fn seed(byte: u8) -> MasterSeed {
+        MasterSeed::from_bytes([byte; 32])
+    }
+
+    #[test]
+    fn test_pq_keys_are_deterministic_and_work() {
+        let signer = MlDsaKeyPair::from_seed(&seed(1));
+        assert_eq!(signer.public_key_bytes(), MlDsaKeyPair::from_seed(&seed(1)).public_key_bytes());
+        assert_ne!(signer.public_key_bytes(), MlDsaKeyPair::from_seed(&seed(2)).public_key_bytes());
+
+        let signature = signer.sign(b"ceremony transcript");
+        assert!(verify_ml_dsa(&signer.public_key_bytes(), b"ceremony transcript", &signature).is_ok());
+        assert!(verify_ml_dsa(&signer.public_key_bytes(), b"other transcript", &signature).is_err());
+        assert!(signer.public_key_der().unwrap().len() > signer.public_key_bytes().len());
+
+        let kem = MlKemKeyPair::from_seed(&seed(1));
+        assert_eq!(kem.public_key_bytes(), MlKemKeyPair::from_seed(&seed(1)).public_key_bytes());
+        let (ciphertext, shared) = ml_kem_encapsulate(&kem.public_key_bytes()).unwrap();
+        assert_eq!(kem.decapsulate(&ciphertext).unwrap(), shared);
+        assert!(format!("{:?}", kem).contains("<redacted>"));
+    }
+
+    #[test]
+    fn test_hybrid_certificate_carries_verifiable_pq_signature() {
+        let root_key = RcgenKeyPair::generate().unwrap();
+        let mut root_params = CertificateParams::new(vec![]).unwrap();
+        root_params.distinguished_name.push(DnType::CommonName, "Hybrid Root");
+        root_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let root_pq = MlDsaKeyPair::from_seed(&seed(1));
+        let root_issuer = Issuer::new(root_params.clone(), &root_key);
+        let root = issue_hybrid_certificate(
+            root_params,
+            &root_key,
+            &root_pq.public_key_bytes(),
+            &root_issuer,
+            &root_pq,
+        )
+        .unwrap();
+
+        let leaf_key = RcgenKeyPair::generate().unwrap();
+        let leaf_pq = MlDsaKeyPair::from_seed(&seed(2));
+        let issuer = Issuer::from_ca_cert_pem(&root.certificate_pem, &root_key).unwrap();
+        let leaf = issue_hybrid_certificate(
+            CertificateParams::new(vec!["pq.example.com".to_string()]).unwrap(),
+            &leaf_key,
+            &leaf_pq.public_key_bytes(),
+            &issuer,
+            &root_pq,
+        )
+        .unwrap();
+
+        let der = pem::parse(&leaf.certificate_pem).unwrap().into_contents();
+        assert_eq!(verify_hybrid_certificate(&der, &root_pq.public_key_bytes()).unwrap(), leaf_pq.public_key_bytes());
+        assert!(verify_hybrid_certificate(&der, &leaf_pq.public_key_bytes()).is_err());
+
+        // Classical parsers still read it; the PQ extensions are not critical
+        let (_, parsed) = x509_parser::parse_x509_certificate(&der).unwrap();
+        let hybrid: Vec<_> = parsed
+            .extensions()
+            .iter()
+            .filter(|ext| ["2.5.29.72", "2.5.29.73", "2.5.29.74"].contains(&ext.oid.to_id_string().as_str()))
+            .collect();
+        assert_eq!(hybrid.len(), 3);
+        assert!(hybrid.iter().all(|ext| !ext.critical));
+    }
+}
@@ -504,6 +504,34 @@ fn validate_subject_alt_name(cert: &X509Certificate, result: &mut Rfc5280Validat
     }
 }
 
+/// Render a GeneralName with an OpenSSL-style type prefix
+///
+/// `DNS:`, `IP:`, `email:`, `URI:` and `DirName:` keep names of different
+/// types distinguishable; IP addresses are rendered in their textual form.
+pub(crate) fn general_name_to_string(name: &GeneralName) -> String {
+    match name {
+        GeneralName::DNSName(dns) => format!("DNS:{}", dns),
+        GeneralName::RFC822Name(email) => format!("email:{}", email),
+        GeneralName::URI(uri) => format!("URI:{}", uri),
+        GeneralName::DirectoryName(dn) => format!("DirName:{}", dn),
+        GeneralName::RegisteredID(oid) => format!("RID:{}", oid),
+        GeneralName::IPAddress(bytes) => {
+            let addr = match bytes.len() {
+                4 => <[u8; 4]>::try_from(*bytes).ok().map(std::net::IpAddr::from),
+                16 => <[u8; 16]>::try_from(*bytes).ok().map(std::net::IpAddr::from),
+                _ => None,
+            };
+            match addr {
+                Some(addr) => format!("IP:{}", addr),
+                None => format!("IP:{}", hex::encode(bytes)),
+            }
+        }
+        GeneralName::OtherName(oid, _) => format!("othername:{}", oid),
+        GeneralName::X400Address(_) => "X400Name".to_string(),
+        GeneralName::EDIPartyName(_) => "EdiPartyName".to_string(),
+    }
+}
+
 /// Convert ASN1Time to DateTime<Utc>
 fn asn1_time_to_datetime(time: &x509_parser::time::ASN1Time) -> Option<DateTime<Utc>> {
     chrono::DateTime::from_timestamp(time.timestamp(), 0)
@@ -605,7 +633,7 @@ fn extract_metadata(cert: &X509Certificate, der_bytes: &[u8]) -> CertificateMeta
         .find(|ext| ext.oid == oid_registry::OID_X509_EXT_SUBJECT_ALT_NAME)
         .and_then(|ext| {
             if let ParsedExtension::SubjectAlternativeName(san) = ext.parsed_extension() {
-                Some(san.general_names.iter().map(|gn| format!("{:?}", gn)).collect())
+                Some(san.general_names.iter().map(general_name_to_string).collect())
             } else {
                 None
             }
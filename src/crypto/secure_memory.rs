@@ -0,0 +1,271 @@
+//! Memory handling for private key material
+//!
+//! [`SecretBytes`] is the owned buffer for every private key the ports hand
+//! around (SSH, OpenPGP and X.509 private keys, the master seed). It
+//!
+//! - zeroizes its contents on drop (with a compiler fence, unlike a plain loop),
+//! - locks its pages into RAM where the platform allows, so the key is never
+//!   written to swap,
+//! - redacts itself in `Debug` output.
+//!
+//! Locking is best-effort: `mlock` can fail under `RLIMIT_MEMLOCK` or in
+//! sandboxes, in which case the buffer is still zeroized but may be swapped.
+//! [`SecretBytes::is_locked`] reports which case applies.
+//!
+//! `mlock` does not nest: one `munlock` releases a page for every buffer on
+//! it, and small buffers share pages. Locks are therefore counted per page
+//! (`PageLock`); a page is unlocked only when the last buffer on it goes.
+
+use std::ops::{Deref, DerefMut};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::BTreeMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{Mutex, PoisonError};
+
+use der::zeroize::Zeroize;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::fingerprint::constant_time_eq;
+
+/// Heap buffer that is memory-locked while alive and zeroized on drop
+pub struct SecretBytes {
+    bytes: Box<[u8]>,
+    // Dropped after `bytes` is zeroized, which unlocks the pages
+    #[cfg(not(target_arch = "wasm32"))]
+    lock: Option<PageLock>,
+}
+
+impl SecretBytes {
+    /// Take ownership of `bytes`
+    ///
+    /// The contents are copied into an exactly-sized buffer and the original
+    /// allocation (including spare capacity) is zeroized.
+    pub fn new(mut bytes: Vec<u8>) -> Self {
+        let secret = Self::from_slice(&bytes);
+        bytes.zeroize();
+        secret
+    }
+
+    /// Copy `bytes` into a new secret buffer
+    pub fn from_slice(bytes: &[u8]) -> Self {
+        Self::locked(bytes.into())
+    }
+
+    /// Zero-filled buffer of `len` bytes, to be written in place
+    pub fn zeroed(len: usize) -> Self {
+        Self::locked(vec![0u8; len].into_boxed_slice())
+    }
+
+    /// Whether the buffer is locked into RAM
+    pub fn is_locked(&self) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.lock.is_some()
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            false
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn locked(bytes: Box<[u8]>) -> Self {
+        let lock = PageLock::acquire(&bytes);
+        Self { bytes, lock }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn locked(bytes: Box<[u8]>) -> Self {
+        Self { bytes }
+    }
+}
+
+/// Number of live buffers on each locked page, keyed by page address
+#[cfg(not(target_arch = "wasm32"))]
+static PAGE_LOCKS: Mutex<BTreeMap<usize, usize>> = Mutex::new(BTreeMap::new());
+
+/// Hold on the pages under one buffer, counted in [`PAGE_LOCKS`]
+#[cfg(not(target_arch = "wasm32"))]
+struct PageLock {
+    pages: Vec<usize>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PageLock {
+    /// Lock the pages under `bytes`, or none of them if any fails
+    fn acquire(bytes: &[u8]) -> Option<Self> {
+        let (first, last) = (bytes.first()?, bytes.last()?);
+        let page_size = region::page::size();
+        let first = region::page::floor(first as *const u8) as usize;
+        let last = region::page::floor(last as *const u8) as usize;
+        let pages: Vec<usize> = (first..=last).step_by(page_size).collect();
+
+        let mut counts = PAGE_LOCKS.lock().unwrap_or_else(PoisonError::into_inner);
+        for (i, &page) in pages.iter().enumerate() {
+            if !counts.contains_key(&page) {
+                match region::lock(page as *const u8, page_size) {
+                    // The count decides when to unlock, not the guard
+                    Ok(guard) => std::mem::forget(guard),
+                    Err(_) => {
+                        Self::release(&mut counts, &pages[..i]);
+                        return None;
+                    }
+                }
+            }
+            *counts.entry(page).or_insert(0) += 1;
+        }
+        Some(Self { pages })
+    }
+
+    fn release(counts: &mut BTreeMap<usize, usize>, pages: &[usize]) {
+        for page in pages {
+            let Some(count) = counts.get_mut(page) else { continue };
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(page);
+                let _ = region::unlock(*page as *const u8, region::page::size());
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for PageLock {
+    fn drop(&mut self) {
+        let mut counts = PAGE_LOCKS.lock().unwrap_or_else(PoisonError::into_inner);
+        Self::release(&mut counts, &self.pages);
+    }
+}
+
+impl Deref for SecretBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl DerefMut for SecretBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.bytes
+    }
+}
+
+impl AsRef<[u8]> for SecretBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<&[u8]> for SecretBytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self::from_slice(bytes)
+    }
+}
+
+impl Default for SecretBytes {
+    fn default() -> Self {
+        Self::zeroed(0)
+    }
+}
+
+impl Clone for SecretBytes {
+    fn clone(&self) -> Self {
+        Self::from_slice(&self.bytes)
+    }
+}
+
+impl PartialEq for SecretBytes {
+    /// Constant-time comparison of the contents
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.bytes, &other.bytes)
+    }
+}
+
+impl Eq for SecretBytes {}
+
+impl std::fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretBytes(<redacted {} bytes>)", self.bytes.len())
+    }
+}
+
+impl Zeroize for SecretBytes {
+    fn zeroize(&mut self) {
+        self.bytes.as_mut().zeroize();
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Serialized like `Vec<u8>`, so existing documents keep their format
+impl Serialize for SecretBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.bytes.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<u8>::deserialize(deserializer).map(SecretBytes::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_bytes_behaves_like_a_byte_slice() {
+        let mut secret = SecretBytes::from(vec![1u8, 2, 3]);
+        assert_eq!(&secret[..], &[1, 2, 3]);
+        assert!(secret.starts_with(&[1, 2]));
+        secret[0] = 9;
+        assert_eq!(secret.to_vec(), vec![9, 2, 3]);
+        assert_eq!(secret.clone(), secret);
+        assert_ne!(SecretBytes::from_slice(&[9, 2, 4]), secret);
+
+        secret.zeroize();
+        assert!(secret.iter().all(|b| *b == 0));
+        assert!(!SecretBytes::zeroed(0).is_locked());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_page_stays_locked_while_another_buffer_uses_it() {
+        let first = SecretBytes::from_slice(&[1u8; 16]);
+        let second = SecretBytes::from_slice(&[2u8; 16]);
+        if !(first.is_locked() && second.is_locked()) {
+            return; // mlock unavailable here
+        }
+        let pages = |secret: &SecretBytes| secret.lock.as_ref().unwrap().pages.clone();
+        let second_pages = pages(&second);
+
+        drop(first);
+        let counts = PAGE_LOCKS.lock().unwrap();
+        assert!(second_pages.iter().all(|page| counts.contains_key(page)));
+    }
+
+    #[test]
+    fn test_secret_bytes_redacts_debug_and_serializes_as_vec() {
+        let secret = SecretBytes::from_slice(b"private key");
+        let debug = format!("{:?}", secret);
+        assert!(debug.contains("redacted 11 bytes"));
+        assert!(!debug.contains("private"));
+
+        let json = serde_json::to_string(&secret).unwrap();
+        assert_eq!(json, serde_json::to_string(&b"private key".to_vec()).unwrap());
+        assert_eq!(serde_json::from_str::<SecretBytes>(&json).unwrap(), secret);
+    }
+}
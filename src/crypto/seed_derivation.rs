@@ -3,13 +3,13 @@
 //! This module implements the first step of our deterministic key hierarchy:
 //! converting a user passphrase into a cryptographically strong master seed.
 
-use argon2::{
-    Argon2, Algorithm, Version, Params,
-    password_hash::{PasswordHasher, SaltString},
-};
+use argon2::password_hash::{PasswordHasher, SaltString};
 use hkdf::Hkdf;
 use sha2::Sha256;
 
+use super::kdf::KdfParams;
+use super::secure_memory::SecretBytes;
+
 // Use der's re-export of zeroize (available as transitive dependency)
 use der::zeroize::Zeroize;
 
@@ -17,12 +17,12 @@ use der::zeroize::Zeroize;
 ///
 /// This is the root of our entire key hierarchy. All keys are derived from this.
 ///
-/// Security: Implements `Zeroize` to securely clear memory when dropped,
-/// preventing the seed from remaining in memory after use.
+/// Security: Held in [`SecretBytes`], so the seed is locked out of swap
+/// where possible and zeroized when dropped.
 ///
 /// Note: Debug implementation redacts the actual seed bytes for security.
 #[derive(Clone)]
-pub struct MasterSeed([u8; 32]);
+pub struct MasterSeed(SecretBytes);
 
 impl std::fmt::Debug for MasterSeed {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -38,21 +38,19 @@ impl Zeroize for MasterSeed {
     }
 }
 
-impl Drop for MasterSeed {
-    fn drop(&mut self) {
-        self.zeroize();
-    }
-}
-
 impl MasterSeed {
     /// Create a master seed from raw bytes
-    pub fn from_bytes(bytes: [u8; 32]) -> Self {
-        MasterSeed(bytes)
+    ///
+    /// The caller's copy is zeroized once moved into secure memory.
+    pub fn from_bytes(mut bytes: [u8; 32]) -> Self {
+        let seed = MasterSeed(SecretBytes::from_slice(&bytes));
+        bytes.zeroize();
+        seed
     }
 
     /// Get the seed bytes (for derivation operations)
     pub fn as_bytes(&self) -> &[u8; 32] {
-        &self.0
+        (&self.0[..]).try_into().expect("master seed is 32 bytes")
     }
 
     /// Derive a child seed using HKDF
@@ -60,15 +58,14 @@ impl MasterSeed {
     /// This creates a cryptographically independent seed for specific purposes.
     /// The `info` parameter provides domain separation.
     ///
-    /// Security: The child seed buffer is zeroized after use via ZeroizeOnDrop.
+    /// Security: The child seed is expanded directly into secure memory.
     pub fn derive_child(&self, info: &str) -> MasterSeed {
         let hkdf = Hkdf::<Sha256>::new(None, self.as_bytes());
-        let mut child_seed = [0u8; 32];
+        let mut child_seed = SecretBytes::zeroed(32);
         hkdf.expand(info.as_bytes(), &mut child_seed)
             .expect("32 bytes is a valid HKDF output length");
 
-        // child_seed will be zeroized when MasterSeed is dropped (ZeroizeOnDrop)
-        MasterSeed::from_bytes(child_seed)
+        MasterSeed(child_seed)
     }
 }
 
@@ -116,30 +113,16 @@ pub fn derive_master_seed(passphrase: &str, organization_id: &str) -> Result<Mas
     let salt = SaltString::from_b64(&salt_b64)
         .map_err(|e| format!("Failed to create salt: {}", e))?;
 
-    // Configure Argon2id with strong parameters
+    // Argon2id cost is shared with the kdf module.
     // For testing: Reduced params (64 MB memory, 3 iterations)
     // For production: Use 1 GB memory and 10 iterations
     #[cfg(test)]
-    let params = Params::new(
-        65_536,     // 64 MB memory (for faster tests)
-        3,          // 3 iterations (for faster tests)
-        4,          // 4 threads
-        Some(32),   // 32-byte output
-    ).map_err(|e| format!("Invalid Argon2 params: {}", e))?;
+    let params = KdfParams::testing();
 
     #[cfg(not(test))]
-    let params = Params::new(
-        1_048_576,  // 1 GB memory (production)
-        10,         // 10 iterations (production)
-        4,          // 4 threads
-        Some(32),   // 32-byte output
-    ).map_err(|e| format!("Invalid Argon2 params: {}", e))?;
-
-    let argon2 = Argon2::new(
-        Algorithm::Argon2id,
-        Version::V0x13,
-        params,
-    );
+    let params = KdfParams::default();
+
+    let argon2 = params.argon2().map_err(|e| e.to_string())?;
 
     // Derive the master seed
     let password_hash = argon2
@@ -151,10 +134,7 @@ pub fn derive_master_seed(passphrase: &str, organization_id: &str) -> Result<Mas
         .hash
         .ok_or("No hash produced")?;
 
-    let mut seed_bytes = [0u8; 32];
-    seed_bytes.copy_from_slice(hash_bytes.as_bytes());
-
-    Ok(MasterSeed::from_bytes(seed_bytes))
+    Ok(MasterSeed(SecretBytes::from_slice(hash_bytes.as_bytes())))
 }
 
 /// Derive a child seed from master seed using HKDF
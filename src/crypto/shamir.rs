@@ -0,0 +1,695 @@
+//! Shamir secret sharing of the master seed
+//!
+//! The master seed is split into `n` shares so that any `k` of them
+//! recover it and fewer reveal nothing about it. Shares are handed to
+//! different custodians, usually on paper (see
+//! `projection::paper_backup`), giving a recovery path that does not
+//! depend on the SD card.
+//!
+//! Each byte of the secret is shared independently with a random
+//! polynomial of degree `k - 1` over GF(2^8), evaluated at `x = 1..=n`.
+//!
+//! ## Integrity
+//!
+//! Interpolation recombines any `k` points, so a share from another
+//! split or with valid check digits but wrong data would silently yield a
+//! wrong secret. As in SLIP-39, a 4-byte digest of the secret is shared
+//! along with it: the shared value is `secret || SHA-256(secret)[..4]`,
+//! and recombination fails with [`ShamirError::DigestMismatch`] unless
+//! the digest matches. Fewer than `k` shares reveal nothing about the
+//! digest either, since its bytes are shared like any other.
+//!
+//! ## Groups
+//!
+//! For custody by backup holders the seed can also be shared in two
+//! levels, following the group scheme of SLIP-39: the seed is split into
+//! one share per group with a group threshold, and each group share is
+//! split again among the group's members with a member threshold.
+//! Recovery needs `group_threshold` groups, each contributing at least its
+//! `member_threshold` shares, so e.g. "2 of the 3 board members, or 3 of
+//! the 5 engineers" can be expressed as groups. Only the group structure
+//! is taken from SLIP-39; shares use the text form below, not its word
+//! list.
+//!
+//! ## Text Form
+//!
+//! Shares are printed and scanned as
+//!
+//! ```text
+//! cimkeys-share:v1:{set_id}:{threshold}:{index}:{hex data}:{check}
+//! ```
+//!
+//! where `set_id` ties together the shares of one split and `check` is
+//! the first 8 hex digits of SHA-256 over everything before it, catching
+//! transcription errors before they become a wrong seed. Group shares
+//! carry their position in both levels:
+//!
+//! ```text
+//! cimkeys-share:v2:{set_id}:{group_threshold}:{group_count}:{group}:{member_threshold}:{member}:{hex data}:{check}
+//! ```
+
+use der::zeroize::Zeroize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::seed_derivation::MasterSeed;
+use crate::ports::rng::{CryptoRngPort, OsCryptoRng};
+
+const TEXT_PREFIX: &str = "cimkeys-share:v1";
+
+const GROUP_TEXT_PREFIX: &str = "cimkeys-share:v2";
+
+/// Bytes of the secret's digest shared along with it
+const DIGEST_LEN: usize = 4;
+
+/// One share of a split secret
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretShare {
+    /// Random ID shared by all shares of one split
+    pub set_id: u32,
+    /// Shares needed to recover the secret
+    pub threshold: u8,
+    /// x-coordinate of the share, `1..=n`
+    pub index: u8,
+    data: Vec<u8>,
+}
+
+impl std::fmt::Debug for SecretShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretShare")
+            .field("set_id", &self.set_id)
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("data", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Drop for SecretShare {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl SecretShare {
+    /// Text form for printing and QR codes
+    pub fn to_text(&self) -> String {
+        let body = format!(
+            "{}:{:08x}:{}:{}:{}",
+            TEXT_PREFIX,
+            self.set_id,
+            self.threshold,
+            self.index,
+            hex::encode(&self.data)
+        );
+        let check = text_check(&body);
+        format!("{}:{}", body, check)
+    }
+
+    /// Parse the text form, verifying its check digits
+    pub fn from_text(text: &str) -> Result<Self, ShamirError> {
+        let text = text.trim();
+        let invalid = |reason: &str| ShamirError::InvalidEncoding(reason.to_string());
+
+        let (body, check) = text.rsplit_once(':').ok_or_else(|| invalid("missing check digits"))?;
+        if !check.eq_ignore_ascii_case(&text_check(body)) {
+            return Err(invalid("check digits do not match"));
+        }
+        let fields = body
+            .strip_prefix(TEXT_PREFIX)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| invalid("not a cim-keys share"))?;
+        let [set_id, threshold, index, data] = fields.split(':').collect::<Vec<_>>()[..] else {
+            return Err(invalid("wrong number of fields"));
+        };
+
+        let share = SecretShare {
+            set_id: u32::from_str_radix(set_id, 16).map_err(|_| invalid("bad set ID"))?,
+            threshold: threshold.parse().map_err(|_| invalid("bad threshold"))?,
+            index: index.parse().map_err(|_| invalid("bad index"))?,
+            data: hex::decode(data).map_err(|_| invalid("bad share data"))?,
+        };
+        if share.index == 0 || share.threshold < 2 {
+            return Err(invalid("index and threshold out of range"));
+        }
+        Ok(share)
+    }
+}
+
+fn text_check(body: &str) -> String {
+    hex::encode(&Sha256::digest(body.as_bytes())[..4])
+}
+
+fn secret_digest(secret: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut digest = [0u8; DIGEST_LEN];
+    digest.copy_from_slice(&Sha256::digest(secret)[..DIGEST_LEN]);
+    digest
+}
+
+/// The value actually shared: the secret followed by its digest
+fn with_digest(secret: &[u8]) -> Vec<u8> {
+    let mut shared = Vec::with_capacity(secret.len() + DIGEST_LEN);
+    shared.extend_from_slice(secret);
+    shared.extend_from_slice(&secret_digest(secret));
+    shared
+}
+
+/// Split a recombined value into the secret, checking its digest
+fn verify_digest(mut shared: Vec<u8>) -> Result<Vec<u8>, ShamirError> {
+    let Some(len) = shared.len().checked_sub(DIGEST_LEN) else {
+        shared.zeroize();
+        return Err(ShamirError::DigestMismatch);
+    };
+    let matches = secret_digest(&shared[..len]) == shared[len..];
+    shared[len..].zeroize();
+    shared.truncate(len);
+    if !matches {
+        shared.zeroize();
+        return Err(ShamirError::DigestMismatch);
+    }
+    Ok(shared)
+}
+
+/// Shamir secret sharing errors
+#[derive(Debug, Error)]
+pub enum ShamirError {
+    #[error("Threshold {threshold} is not valid for {shares} shares")]
+    InvalidThreshold { threshold: u8, shares: u8 },
+
+    #[error("Group threshold {threshold} must be between 1 and the group count {groups}")]
+    InvalidGroupThreshold { threshold: u8, groups: usize },
+
+    #[error("{needed} groups needed, {found} complete")]
+    NotEnoughGroups { needed: u8, found: usize },
+
+    #[error("{needed} shares needed, {found} given")]
+    NotEnoughShares { needed: u8, found: usize },
+
+    #[error("Shares belong to different splits")]
+    MismatchedShares,
+
+    #[error("Share {0} given twice")]
+    DuplicateShare(u8),
+
+    #[error("Shares do not recombine to the split secret: one of them is wrong or from another split")]
+    DigestMismatch,
+
+    #[error("Invalid share encoding: {0}")]
+    InvalidEncoding(String),
+}
+
+/// Split `secret` into `shares` shares, any `threshold` of which recover it
+pub fn split_secret(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<SecretShare>, ShamirError> {
+    split_secret_with_rng(secret, threshold, shares, &OsCryptoRng)
+}
+
+/// [`split_secret`] drawing the polynomials and set ID from `rng`
+pub fn split_secret_with_rng(
+    secret: &[u8],
+    threshold: u8,
+    shares: u8,
+    rng: &dyn CryptoRngPort,
+) -> Result<Vec<SecretShare>, ShamirError> {
+    if threshold < 2 || threshold > shares {
+        return Err(ShamirError::InvalidThreshold { threshold, shares });
+    }
+
+    let set_id = rng.next_u32();
+    let mut shared = with_digest(secret);
+    let split = split_bytes(&shared, threshold, shares, rng);
+    shared.zeroize();
+    Ok(split
+        .into_iter()
+        .zip(1..=shares)
+        .map(|(data, index)| SecretShare { set_id, threshold, index, data })
+        .collect())
+}
+
+/// Evaluate a random polynomial of degree `threshold - 1` per byte of
+/// `secret` at `x = 1..=count`
+///
+/// A threshold of 1 gives every share a copy of the secret.
+fn split_bytes(secret: &[u8], threshold: u8, count: u8, rng: &dyn CryptoRngPort) -> Vec<Vec<u8>> {
+    let mut result = vec![Vec::with_capacity(secret.len()); count as usize];
+    let mut coefficients = vec![0u8; threshold as usize];
+    for &byte in secret {
+        coefficients[0] = byte;
+        rng.fill_bytes(&mut coefficients[1..]);
+        for (data, x) in result.iter_mut().zip(1..=count) {
+            // Horner's rule, highest coefficient first
+            let y = coefficients.iter().rev().fold(0u8, |acc, &c| gf_mul(acc, x) ^ c);
+            data.push(y);
+        }
+    }
+    coefficients.zeroize();
+    result
+}
+
+/// Interpolate the polynomial through `points` at `x = 0`
+///
+/// Points must have distinct, non-zero x and equally long data.
+fn interpolate(points: &[(u8, &[u8])]) -> Vec<u8> {
+    // Lagrange basis polynomials evaluated at x = 0
+    let basis: Vec<u8> = points
+        .iter()
+        .map(|&(x, _)| {
+            points
+                .iter()
+                .filter(|&&(other, _)| other != x)
+                .fold(1u8, |acc, &(other, _)| gf_mul(acc, gf_div(other, other ^ x)))
+        })
+        .collect();
+
+    let len = points.first().map_or(0, |(_, data)| data.len());
+    (0..len)
+        .map(|byte| points.iter().zip(&basis).fold(0u8, |acc, ((_, data), &l)| acc ^ gf_mul(data[byte], l)))
+        .collect()
+}
+
+/// Recover a secret from at least `threshold` shares of one split
+///
+/// Fails with [`ShamirError::DigestMismatch`] if a share is wrong.
+pub fn combine_shares(shares: &[SecretShare]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares { needed: 2, found: 0 })?;
+    if shares.iter().any(|s| {
+        s.set_id != first.set_id || s.threshold != first.threshold || s.data.len() != first.data.len()
+    }) {
+        return Err(ShamirError::MismatchedShares);
+    }
+    for (i, share) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|s| s.index == share.index) {
+            return Err(ShamirError::DuplicateShare(share.index));
+        }
+    }
+    if shares.len() < first.threshold as usize {
+        return Err(ShamirError::NotEnoughShares { needed: first.threshold, found: shares.len() });
+    }
+
+    let points: Vec<(u8, &[u8])> = shares[..first.threshold as usize]
+        .iter()
+        .map(|share| (share.index, share.data.as_slice()))
+        .collect();
+    verify_digest(interpolate(&points))
+}
+
+// ============================================================================
+// GROUP SHARES
+// ============================================================================
+
+/// Threshold and size of one group in a two-level split
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupSpec {
+    /// Members needed to reconstruct the group's share
+    pub threshold: u8,
+    pub members: u8,
+}
+
+impl GroupSpec {
+    pub fn new(threshold: u8, members: u8) -> Self {
+        Self { threshold, members }
+    }
+}
+
+/// One member's share of a two-level split
+#[derive(Clone, PartialEq, Eq)]
+pub struct GroupShare {
+    /// Random ID shared by all shares of one split
+    pub set_id: u32,
+    /// Groups needed to recover the secret
+    pub group_threshold: u8,
+    pub group_count: u8,
+    /// Group of this share, `1..=group_count`
+    pub group_index: u8,
+    /// Members of the group needed to recover the group's share
+    pub member_threshold: u8,
+    /// Member of this share within its group, `1..=members`
+    pub member_index: u8,
+    data: Vec<u8>,
+}
+
+impl std::fmt::Debug for GroupShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GroupShare")
+            .field("share_id", &self.share_id())
+            .field("group_threshold", &self.group_threshold)
+            .field("member_threshold", &self.member_threshold)
+            .field("data", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Drop for GroupShare {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl GroupShare {
+    /// Public identifier of the share, safe to record in events
+    /// (`{set_id}-g{group}-m{member}`)
+    pub fn share_id(&self) -> String {
+        format!("{:08x}-g{}-m{}", self.set_id, self.group_index, self.member_index)
+    }
+
+    /// Text form for printing and QR codes
+    pub fn to_text(&self) -> String {
+        let body = format!(
+            "{}:{:08x}:{}:{}:{}:{}:{}:{}",
+            GROUP_TEXT_PREFIX,
+            self.set_id,
+            self.group_threshold,
+            self.group_count,
+            self.group_index,
+            self.member_threshold,
+            self.member_index,
+            hex::encode(&self.data)
+        );
+        let check = text_check(&body);
+        format!("{}:{}", body, check)
+    }
+
+    /// Parse the text form, verifying its check digits
+    pub fn from_text(text: &str) -> Result<Self, ShamirError> {
+        let text = text.trim();
+        let invalid = |reason: &str| ShamirError::InvalidEncoding(reason.to_string());
+
+        let (body, check) = text.rsplit_once(':').ok_or_else(|| invalid("missing check digits"))?;
+        if !check.eq_ignore_ascii_case(&text_check(body)) {
+            return Err(invalid("check digits do not match"));
+        }
+        let fields = body
+            .strip_prefix(GROUP_TEXT_PREFIX)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .ok_or_else(|| invalid("not a cim-keys group share"))?;
+        let [set_id, group_threshold, group_count, group_index, member_threshold, member_index, data] =
+            fields.split(':').collect::<Vec<_>>()[..]
+        else {
+            return Err(invalid("wrong number of fields"));
+        };
+
+        let number = |field: &str, name: &str| field.parse::<u8>().map_err(|_| invalid(&format!("bad {}", name)));
+        let share = GroupShare {
+            set_id: u32::from_str_radix(set_id, 16).map_err(|_| invalid("bad set ID"))?,
+            group_threshold: number(group_threshold, "group threshold")?,
+            group_count: number(group_count, "group count")?,
+            group_index: number(group_index, "group")?,
+            member_threshold: number(member_threshold, "member threshold")?,
+            member_index: number(member_index, "member")?,
+            data: hex::decode(data).map_err(|_| invalid("bad share data"))?,
+        };
+        let in_range = share.group_threshold >= 1
+            && share.group_threshold <= share.group_count
+            && (1..=share.group_count).contains(&share.group_index)
+            && share.member_threshold >= 1
+            && share.member_index >= 1;
+        if !in_range {
+            return Err(invalid("group or member out of range"));
+        }
+        Ok(share)
+    }
+}
+
+/// Split `secret` into groups of member shares
+///
+/// Any `group_threshold` groups recover the secret, where a group counts
+/// once `threshold` of its members combine their shares. As in SLIP-39, a
+/// member threshold of 1 is only allowed for single-member groups. Shares
+/// are returned per group, in the order of `groups`.
+pub fn split_secret_groups(
+    secret: &[u8],
+    group_threshold: u8,
+    groups: &[GroupSpec],
+) -> Result<Vec<Vec<GroupShare>>, ShamirError> {
+    split_secret_groups_with_rng(secret, group_threshold, groups, &OsCryptoRng)
+}
+
+/// [`split_secret_groups`] drawing the polynomials and set ID from `rng`
+pub fn split_secret_groups_with_rng(
+    secret: &[u8],
+    group_threshold: u8,
+    groups: &[GroupSpec],
+    rng: &dyn CryptoRngPort,
+) -> Result<Vec<Vec<GroupShare>>, ShamirError> {
+    let group_count = u8::try_from(groups.len())
+        .ok()
+        .filter(|&count| group_threshold >= 1 && group_threshold <= count)
+        .ok_or(ShamirError::InvalidGroupThreshold { threshold: group_threshold, groups: groups.len() })?;
+    for group in groups {
+        let valid = match group.threshold {
+            0 => false,
+            1 => group.members == 1,
+            threshold => threshold <= group.members,
+        };
+        if !valid {
+            return Err(ShamirError::InvalidThreshold { threshold: group.threshold, shares: group.members });
+        }
+    }
+
+    let set_id = rng.next_u32();
+    let mut shared = with_digest(secret);
+    let mut group_secrets = split_bytes(&shared, group_threshold, group_count, rng);
+    shared.zeroize();
+    let result = groups
+        .iter()
+        .zip(&group_secrets)
+        .zip(1..=group_count)
+        .map(|((group, group_secret), group_index)| {
+            split_bytes(group_secret, group.threshold, group.members, rng)
+                .into_iter()
+                .zip(1..=group.members)
+                .map(|(data, member_index)| GroupShare {
+                    set_id,
+                    group_threshold,
+                    group_count,
+                    group_index,
+                    member_threshold: group.threshold,
+                    member_index,
+                    data,
+                })
+                .collect()
+        })
+        .collect();
+    group_secrets.zeroize();
+    Ok(result)
+}
+
+/// Recover a secret from shares of a two-level split
+///
+/// Shares of groups that fall short of their member threshold are ignored
+/// as long as enough other groups are complete. Fails with
+/// [`ShamirError::DigestMismatch`] if a share is wrong.
+pub fn combine_group_shares(shares: &[GroupShare]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughGroups { needed: 1, found: 0 })?;
+    if shares.iter().any(|s| {
+        s.set_id != first.set_id
+            || s.group_threshold != first.group_threshold
+            || s.group_count != first.group_count
+            || s.data.len() != first.data.len()
+    }) {
+        return Err(ShamirError::MismatchedShares);
+    }
+
+    let mut group_secrets: Vec<(u8, Vec<u8>)> = Vec::new();
+    for group_index in 1..=first.group_count {
+        let members: Vec<&GroupShare> = shares.iter().filter(|s| s.group_index == group_index).collect();
+        let Some(member) = members.first() else { continue };
+        if members.iter().any(|s| s.member_threshold != member.member_threshold) {
+            return Err(ShamirError::MismatchedShares);
+        }
+        for (i, share) in members.iter().enumerate() {
+            if members[..i].iter().any(|s| s.member_index == share.member_index) {
+                return Err(ShamirError::DuplicateShare(share.member_index));
+            }
+        }
+        if members.len() < member.member_threshold as usize {
+            continue;
+        }
+        let points: Vec<(u8, &[u8])> = members[..member.member_threshold as usize]
+            .iter()
+            .map(|share| (share.member_index, share.data.as_slice()))
+            .collect();
+        group_secrets.push((group_index, interpolate(&points)));
+    }
+
+    if group_secrets.len() < first.group_threshold as usize {
+        return Err(ShamirError::NotEnoughGroups { needed: first.group_threshold, found: group_secrets.len() });
+    }
+    let points: Vec<(u8, &[u8])> = group_secrets[..first.group_threshold as usize]
+        .iter()
+        .map(|(index, data)| (*index, data.as_slice()))
+        .collect();
+    let secret = interpolate(&points);
+    for (_, data) in &mut group_secrets {
+        data.zeroize();
+    }
+    verify_digest(secret)
+}
+
+impl MasterSeed {
+    /// Split the seed into `shares` shares, any `threshold` of which
+    /// recover it
+    pub fn split(&self, threshold: u8, shares: u8) -> Result<Vec<SecretShare>, ShamirError> {
+        split_secret(self.as_bytes(), threshold, shares)
+    }
+
+    /// Recover a seed split with [`split`](Self::split)
+    pub fn from_shares(shares: &[SecretShare]) -> Result<Self, ShamirError> {
+        let mut secret = combine_shares(shares)?;
+        let seed = <[u8; 32]>::try_from(secret.as_slice())
+            .map_err(|_| ShamirError::InvalidEncoding(format!("{} byte secret is not a seed", secret.len())));
+        secret.zeroize();
+        seed.map(MasterSeed::from_bytes)
+    }
+
+    /// Split the seed into groups of member shares (see
+    /// [`split_secret_groups`])
+    pub fn split_groups(&self, group_threshold: u8, groups: &[GroupSpec]) -> Result<Vec<Vec<GroupShare>>, ShamirError> {
+        split_secret_groups(self.as_bytes(), group_threshold, groups)
+    }
+
+    /// Recover a seed split with [`split_groups`](Self::split_groups)
+    pub fn from_group_shares(shares: &[GroupShare]) -> Result<Self, ShamirError> {
+        let mut secret = combine_group_shares(shares)?;
+        let seed = <[u8; 32]>::try_from(secret.as_slice())
+            .map_err(|_| ShamirError::InvalidEncoding(format!("{} byte secret is not a seed", secret.len())));
+        secret.zeroize();
+        seed.map(MasterSeed::from_bytes)
+    }
+}
+
+/// Multiplication in GF(2^8) with the AES polynomial x^8 + x^4 + x^3 + x + 1
+///
+/// Constant time: no branches or table lookups depend on the operands.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Division in GF(2^8); `b` is never zero for distinct share indices
+fn gf_div(a: u8, b: u8) -> u8 {
+    // b^254 = b^-1 since the multiplicative group has order 255
+    let mut inverse = 1u8;
+    let mut power = b;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            inverse = gf_mul(inverse, power);
+        }
+        power = gf_mul(power, power);
+        exponent >>= 1;
+    }
+    gf_mul(a, inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_threshold_shares_recover_the_seed() {
+        let seed = MasterSeed::from_bytes(rand::random());
+        let shares = seed.split(3, 5).unwrap();
+
+        for (a, b, c) in [(0, 1, 2), (4, 2, 0), (1, 3, 4)] {
+            let subset = [shares[a].clone(), shares[b].clone(), shares[c].clone()];
+            assert_eq!(MasterSeed::from_shares(&subset).unwrap().as_bytes(), seed.as_bytes());
+        }
+        assert!(matches!(
+            MasterSeed::from_shares(&shares[..2]),
+            Err(ShamirError::NotEnoughShares { needed: 3, found: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_text_form_roundtrip_and_check_digits() {
+        let shares = split_secret(b"recovery", 2, 3).unwrap();
+        let text = shares[1].to_text();
+        assert_eq!(SecretShare::from_text(&text).unwrap(), shares[1]);
+
+        // One mistyped digit
+        let mut typo = text.clone().into_bytes();
+        let position = text.len() - 12;
+        typo[position] = if typo[position] == b'0' { b'1' } else { b'0' };
+        assert!(SecretShare::from_text(&String::from_utf8(typo).unwrap()).is_err());
+
+        let other_split = split_secret(b"recovery", 2, 3).unwrap();
+        assert!(matches!(
+            combine_shares(&[shares[0].clone(), other_split[1].clone()]),
+            Err(ShamirError::MismatchedShares)
+        ));
+    }
+
+    #[test]
+    fn test_group_threshold_recovery() {
+        let seed = MasterSeed::from_bytes(rand::random());
+        // Board: 2 of 3, engineers: 3 of 5, escrow: its single member
+        let groups = seed
+            .split_groups(2, &[GroupSpec::new(2, 3), GroupSpec::new(3, 5), GroupSpec::new(1, 1)])
+            .unwrap();
+        assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), [3, 5, 1]);
+
+        let board = [groups[0][2].clone(), groups[0][0].clone()];
+        let engineers = [groups[1][4].clone(), groups[1][1].clone(), groups[1][3].clone()];
+        let escrow = [groups[2][0].clone()];
+
+        let recovered = MasterSeed::from_group_shares(&[&board[..], &escrow[..]].concat()).unwrap();
+        assert_eq!(recovered.as_bytes(), seed.as_bytes());
+        let recovered = MasterSeed::from_group_shares(&[&engineers[..], &board[..1], &escrow[..]].concat()).unwrap();
+        assert_eq!(recovered.as_bytes(), seed.as_bytes());
+
+        // One board member and two engineers complete no group
+        let short = [board[0].clone(), engineers[0].clone(), engineers[1].clone()];
+        assert!(matches!(
+            combine_group_shares(&short),
+            Err(ShamirError::NotEnoughGroups { needed: 2, found: 0 })
+        ));
+
+        let text = engineers[0].to_text();
+        assert!(text.starts_with("cimkeys-share:v2:"));
+        assert_eq!(GroupShare::from_text(&text).unwrap(), engineers[0]);
+        assert_eq!(escrow[0].share_id(), format!("{:08x}-g3-m1", escrow[0].set_id));
+        assert!(!format!("{:?}", escrow[0]).contains(&hex::encode(&escrow[0].data)));
+
+        assert!(split_secret_groups(b"seed", 3, &[GroupSpec::new(2, 3), GroupSpec::new(2, 2)]).is_err());
+        assert!(split_secret_groups(b"seed", 1, &[GroupSpec::new(1, 2)]).is_err());
+    }
+
+    #[test]
+    fn test_wrong_share_fails_the_digest() {
+        let seed = MasterSeed::from_bytes(rand::random());
+        let shares = seed.split(2, 3).unwrap();
+
+        // Same split and index, different data: only the digest notices
+        let mut wrong = shares[1].clone();
+        wrong.data[0] ^= 0x01;
+        assert!(SecretShare::from_text(&wrong.to_text()).is_ok());
+        assert!(matches!(
+            MasterSeed::from_shares(&[shares[0].clone(), wrong]),
+            Err(ShamirError::DigestMismatch)
+        ));
+
+        let groups = seed.split_groups(1, &[GroupSpec::new(2, 2)]).unwrap();
+        let mut wrong = groups[0][1].clone();
+        wrong.data[5] ^= 0x80;
+        assert!(matches!(
+            MasterSeed::from_group_shares(&[groups[0][0].clone(), wrong]),
+            Err(ShamirError::DigestMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_gf_arithmetic() {
+        // Known AES field products
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        for b in 1..=255u8 {
+            assert_eq!(gf_mul(gf_div(1, b), b), 1);
+        }
+    }
+}
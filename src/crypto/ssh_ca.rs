@@ -0,0 +1,467 @@
+//! OpenSSH Certificate Authority
+//!
+//! Signs OpenSSH user and host certificates with a designated CA key:
+//!
+//! ```text
+//! SSH CA key (offline)
+//!   ├─ User certificates  → Person      (principals, force-command, source-address)
+//!   └─ Host certificates  → Location    (hostnames)
+//! ```
+//!
+//! Servers trust the CA once (`TrustedUserCAKeys` / `@cert-authority` in
+//! known_hosts) instead of every individual key. Each issued certificate
+//! yields an `SshCertificateIssued` event linking it to the Person or
+//! Location it was issued for.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ssh_key::certificate::{Builder, CertType};
+use ssh_key::rand_core::OsRng;
+use ssh_key::{HashAlg, PrivateKey, PublicKey};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use uuid::Uuid;
+
+use crate::events::key::{SshCaDesignatedEvent, SshCertificateIssuedEvent};
+use crate::events::{DomainEvent, KeyEvents};
+use crate::types::SshCertificateType;
+use crate::value_objects::ActorId;
+
+/// Extensions OpenSSH grants to user certificates by default (`ssh-keygen -s`)
+pub const DEFAULT_USER_EXTENSIONS: &[&str] = &[
+    "permit-X11-forwarding",
+    "permit-agent-forwarding",
+    "permit-port-forwarding",
+    "permit-pty",
+    "permit-user-rc",
+];
+
+/// Backdating applied to `valid_after` to tolerate clock skew
+const CLOCK_SKEW_MINUTES: i64 = 5;
+
+/// Request for an OpenSSH user certificate
+#[derive(Debug, Clone)]
+pub struct UserCertificateRequest {
+    pub person_id: Uuid,
+    /// Key identity logged by sshd (e.g. "alice@example.com")
+    pub key_identity: String,
+    /// Login names the certificate is valid for
+    pub principals: Vec<String>,
+    pub valid_after: DateTime<Utc>,
+    pub valid_before: DateTime<Utc>,
+    pub force_command: Option<String>,
+    /// Comma-separated CIDR list the certificate may be used from
+    pub source_address: Option<String>,
+    pub extensions: Vec<String>,
+}
+
+impl UserCertificateRequest {
+    /// Request a certificate valid from now for `validity`
+    pub fn new(
+        person_id: Uuid,
+        key_identity: impl Into<String>,
+        principals: Vec<String>,
+        validity: Duration,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            person_id,
+            key_identity: key_identity.into(),
+            principals,
+            valid_after: now - Duration::minutes(CLOCK_SKEW_MINUTES),
+            valid_before: now + validity,
+            force_command: None,
+            source_address: None,
+            extensions: DEFAULT_USER_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    /// Restrict the certificate to a single command
+    pub fn with_force_command(mut self, command: impl Into<String>) -> Self {
+        self.force_command = Some(command.into());
+        self
+    }
+
+    /// Restrict the source addresses the certificate may be used from
+    pub fn with_source_address(mut self, cidrs: impl Into<String>) -> Self {
+        self.source_address = Some(cidrs.into());
+        self
+    }
+
+    /// Replace the default extensions
+    pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+        self.extensions = extensions;
+        self
+    }
+}
+
+/// Request for an OpenSSH host certificate
+#[derive(Debug, Clone)]
+pub struct HostCertificateRequest {
+    pub location_id: Uuid,
+    pub key_identity: String,
+    /// Hostnames the certificate is valid for
+    pub hostnames: Vec<String>,
+    pub valid_after: DateTime<Utc>,
+    pub valid_before: DateTime<Utc>,
+}
+
+impl HostCertificateRequest {
+    /// Request a certificate valid from now for `validity`
+    pub fn new(location_id: Uuid, hostnames: Vec<String>, validity: Duration) -> Self {
+        let now = Utc::now();
+        let key_identity = hostnames.first().cloned().unwrap_or_default();
+        Self {
+            location_id,
+            key_identity,
+            hostnames,
+            valid_after: now - Duration::minutes(CLOCK_SKEW_MINUTES),
+            valid_before: now + validity,
+        }
+    }
+}
+
+/// A signed OpenSSH certificate
+#[derive(Debug, Clone)]
+pub struct IssuedSshCertificate {
+    pub certificate_id: Uuid,
+    pub cert_type: SshCertificateType,
+    pub serial: u64,
+    pub key_identity: String,
+    pub principals: Vec<String>,
+    pub person_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
+    pub valid_after: DateTime<Utc>,
+    pub valid_before: DateTime<Utc>,
+    pub critical_options: BTreeMap<String, String>,
+    pub subject_fingerprint: String,
+    /// Certificate in `*-cert-v01@openssh.com` format (the `-cert.pub` file)
+    pub openssh: String,
+}
+
+/// OpenSSH certificate authority backed by a designated CA key
+pub struct SshCaManager {
+    ca_key_id: Uuid,
+    ca_key: PrivateKey,
+    next_serial: AtomicU64,
+}
+
+impl SshCaManager {
+    /// Designate `ca_key` as the SSH CA
+    pub fn new(ca_key_id: Uuid, ca_key: PrivateKey) -> Result<Self, String> {
+        if ca_key.is_encrypted() {
+            return Err("SSH CA key must be decrypted before signing".to_string());
+        }
+
+        Ok(Self {
+            ca_key_id,
+            ca_key,
+            next_serial: AtomicU64::new(initial_serial()),
+        })
+    }
+
+    /// Designate a CA key given in OpenSSH private key format
+    pub fn from_openssh(ca_key_id: Uuid, pem: &str) -> Result<Self, String> {
+        let ca_key = PrivateKey::from_openssh(pem)
+            .map_err(|e| format!("Failed to parse SSH CA key: {}", e))?;
+        Self::new(ca_key_id, ca_key)
+    }
+
+    /// Continue serial numbering from a recorded serial instead of the clock
+    pub fn with_next_serial(self, serial: u64) -> Self {
+        self.next_serial.store(serial.max(1), Ordering::SeqCst);
+        self
+    }
+
+    pub fn ca_key_id(&self) -> Uuid {
+        self.ca_key_id
+    }
+
+    /// CA public key in OpenSSH format (for TrustedUserCAKeys / @cert-authority)
+    pub fn ca_public_key_openssh(&self) -> Result<String, String> {
+        self.ca_key
+            .public_key()
+            .to_openssh()
+            .map_err(|e| format!("Failed to encode SSH CA public key: {}", e))
+    }
+
+    /// SHA-256 fingerprint of the CA key
+    pub fn ca_fingerprint(&self) -> String {
+        self.ca_key.public_key().fingerprint(HashAlg::Sha256).to_string()
+    }
+
+    /// Event recording the CA designation
+    pub fn designation_event(
+        &self,
+        designated_by: ActorId,
+        correlation_id: Uuid,
+    ) -> Result<DomainEvent, String> {
+        Ok(DomainEvent::Key(KeyEvents::SshCaDesignated(SshCaDesignatedEvent {
+            ca_key_id: self.ca_key_id,
+            ca_fingerprint: self.ca_fingerprint(),
+            public_key: self.ca_public_key_openssh()?,
+            designated_at: Utc::now(),
+            designated_by,
+            correlation_id,
+            causation_id: None,
+        })))
+    }
+
+    /// Sign an OpenSSH user certificate for a Person's key
+    pub fn sign_user_certificate(
+        &self,
+        subject: &PublicKey,
+        request: &UserCertificateRequest,
+    ) -> Result<IssuedSshCertificate, String> {
+        if request.principals.is_empty() {
+            return Err("User certificate requires at least one principal".to_string());
+        }
+
+        let mut critical_options = BTreeMap::new();
+        if let Some(command) = &request.force_command {
+            critical_options.insert("force-command".to_string(), command.clone());
+        }
+        if let Some(cidrs) = &request.source_address {
+            critical_options.insert("source-address".to_string(), cidrs.clone());
+        }
+
+        let mut issued = self.sign(
+            subject,
+            CertType::User,
+            &request.key_identity,
+            &request.principals,
+            request.valid_after,
+            request.valid_before,
+            &critical_options,
+            &request.extensions,
+        )?;
+        issued.person_id = Some(request.person_id);
+        Ok(issued)
+    }
+
+    /// Sign an OpenSSH host certificate for a Location's host key
+    pub fn sign_host_certificate(
+        &self,
+        subject: &PublicKey,
+        request: &HostCertificateRequest,
+    ) -> Result<IssuedSshCertificate, String> {
+        if request.hostnames.is_empty() {
+            return Err("Host certificate requires at least one hostname".to_string());
+        }
+
+        let mut issued = self.sign(
+            subject,
+            CertType::Host,
+            &request.key_identity,
+            &request.hostnames,
+            request.valid_after,
+            request.valid_before,
+            &BTreeMap::new(),
+            &[],
+        )?;
+        issued.location_id = Some(request.location_id);
+        Ok(issued)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn sign(
+        &self,
+        subject: &PublicKey,
+        cert_type: CertType,
+        key_identity: &str,
+        principals: &[String],
+        valid_after: DateTime<Utc>,
+        valid_before: DateTime<Utc>,
+        critical_options: &BTreeMap<String, String>,
+        extensions: &[String],
+    ) -> Result<IssuedSshCertificate, String> {
+        if valid_before <= valid_after {
+            return Err("Certificate validity window is empty".to_string());
+        }
+
+        let serial = self.next_serial.fetch_add(1, Ordering::SeqCst);
+        let field = |e: ssh_key::Error| format!("Invalid certificate field: {}", e);
+
+        let mut builder = Builder::new_with_random_nonce(
+            &mut OsRng,
+            subject.key_data().clone(),
+            unix_seconds(valid_after),
+            unix_seconds(valid_before),
+        )
+        .map_err(field)?;
+        builder.serial(serial).map_err(field)?;
+        builder.key_id(key_identity).map_err(field)?;
+        builder.cert_type(cert_type).map_err(field)?;
+        for principal in principals {
+            builder.valid_principal(principal.as_str()).map_err(field)?;
+        }
+        for (name, value) in critical_options {
+            builder.critical_option(name.as_str(), value.as_str()).map_err(field)?;
+        }
+        for extension in extensions {
+            builder.extension(extension.as_str(), "").map_err(field)?;
+        }
+
+        let certificate = builder
+            .sign(&self.ca_key)
+            .map_err(|e| format!("Failed to sign SSH certificate: {}", e))?;
+        let openssh = certificate
+            .to_openssh()
+            .map_err(|e| format!("Failed to encode SSH certificate: {}", e))?;
+
+        Ok(IssuedSshCertificate {
+            certificate_id: Uuid::now_v7(),
+            cert_type: match cert_type {
+                CertType::User => SshCertificateType::User,
+                CertType::Host => SshCertificateType::Host,
+            },
+            serial,
+            key_identity: key_identity.to_string(),
+            principals: principals.to_vec(),
+            person_id: None,
+            location_id: None,
+            valid_after: from_unix_seconds(certificate.valid_after()),
+            valid_before: from_unix_seconds(certificate.valid_before()),
+            critical_options: critical_options.clone(),
+            subject_fingerprint: subject.fingerprint(HashAlg::Sha256).to_string(),
+            openssh,
+        })
+    }
+
+    /// Event recording an issued certificate
+    pub fn issued_event(
+        &self,
+        issued: &IssuedSshCertificate,
+        subject_key_id: Option<Uuid>,
+        issued_by: ActorId,
+        correlation_id: Uuid,
+    ) -> DomainEvent {
+        DomainEvent::Key(KeyEvents::SshCertificateIssued(SshCertificateIssuedEvent {
+            certificate_id: issued.certificate_id,
+            ca_key_id: self.ca_key_id,
+            subject_key_id,
+            cert_type: issued.cert_type,
+            serial: issued.serial,
+            key_identity: issued.key_identity.clone(),
+            principals: issued.principals.clone(),
+            person_id: issued.person_id,
+            location_id: issued.location_id,
+            subject_fingerprint: issued.subject_fingerprint.clone(),
+            ca_fingerprint: self.ca_fingerprint(),
+            valid_after: issued.valid_after,
+            valid_before: issued.valid_before,
+            critical_options: issued.critical_options.clone(),
+            issued_at: Utc::now(),
+            issued_by,
+            correlation_id,
+            causation_id: None,
+        }))
+    }
+}
+
+/// First serial of a new manager
+///
+/// Microseconds since the epoch, so a manager created in a later session
+/// continues above the serials of earlier ones instead of restarting at 1.
+fn initial_serial() -> u64 {
+    Utc::now().timestamp_micros().max(1) as u64
+}
+
+fn unix_seconds(time: DateTime<Utc>) -> u64 {
+    time.timestamp().max(0) as u64
+}
+
+fn from_unix_seconds(seconds: u64) -> DateTime<Utc> {
+    Utc.timestamp_opt(seconds as i64, 0).single().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ssh_key::{Algorithm, Certificate};
+
+    fn ed25519_key() -> PrivateKey {
+        PrivateKey::random(&mut OsRng, Algorithm::Ed25519).unwrap()
+    }
+
+    #[test]
+    fn test_user_certificate_validates_against_ca() {
+        let ca = SshCaManager::new(Uuid::now_v7(), ed25519_key()).unwrap();
+        let user_key = ed25519_key();
+        let person_id = Uuid::now_v7();
+
+        let request = UserCertificateRequest::new(
+            person_id,
+            "alice@example.com",
+            vec!["alice".to_string(), "deploy".to_string()],
+            Duration::hours(8),
+        )
+        .with_force_command("/usr/bin/deploy")
+        .with_source_address("10.0.0.0/8");
+
+        let issued = ca.sign_user_certificate(user_key.public_key(), &request).unwrap();
+        assert_eq!(issued.person_id, Some(person_id));
+        assert_eq!(issued.critical_options["force-command"], "/usr/bin/deploy");
+
+        let certificate = Certificate::from_openssh(&issued.openssh).unwrap();
+        let ca_fingerprint = ca.ca_key.public_key().fingerprint(HashAlg::Sha256);
+        certificate
+            .validate_at(unix_seconds(Utc::now()), &[ca_fingerprint])
+            .unwrap();
+        assert_eq!(certificate.cert_type(), CertType::User);
+        assert_eq!(certificate.valid_principals(), &["alice".to_string(), "deploy".to_string()]);
+    }
+
+    #[test]
+    fn test_serials_do_not_restart_across_managers() {
+        let ca_key = ed25519_key();
+        let request = UserCertificateRequest::new(
+            Uuid::now_v7(),
+            "alice@example.com",
+            vec!["alice".to_string()],
+            Duration::hours(8),
+        );
+
+        let first = SshCaManager::new(Uuid::now_v7(), ca_key.clone()).unwrap();
+        let a = first.sign_user_certificate(ed25519_key().public_key(), &request).unwrap();
+        let b = first.sign_user_certificate(ed25519_key().public_key(), &request).unwrap();
+        assert_eq!(b.serial, a.serial + 1);
+
+        let second = SshCaManager::new(Uuid::now_v7(), ca_key).unwrap();
+        let c = second.sign_user_certificate(ed25519_key().public_key(), &request).unwrap();
+        assert!(c.serial > b.serial);
+    }
+
+    #[test]
+    fn test_host_certificate_links_location() {
+        let ca = SshCaManager::new(Uuid::now_v7(), ed25519_key())
+            .unwrap()
+            .with_next_serial(42);
+        let host_key = ed25519_key();
+        let location_id = Uuid::now_v7();
+
+        let request = HostCertificateRequest::new(
+            location_id,
+            vec!["bastion.example.com".to_string()],
+            Duration::days(365),
+        );
+        let issued = ca.sign_host_certificate(host_key.public_key(), &request).unwrap();
+        assert_eq!(issued.serial, 42);
+        assert!(issued.openssh.starts_with("ssh-ed25519-cert-v01@openssh.com "));
+
+        match ca.issued_event(&issued, None, ActorId::system("ssh-ca"), Uuid::now_v7()) {
+            DomainEvent::Key(KeyEvents::SshCertificateIssued(evt)) => {
+                assert_eq!(evt.location_id, Some(location_id));
+                assert!(matches!(evt.cert_type, SshCertificateType::Host));
+            }
+            _ => panic!("Expected SshCertificateIssued event"),
+        }
+    }
+
+    #[test]
+    fn test_user_certificate_requires_principals() {
+        let ca = SshCaManager::new(Uuid::now_v7(), ed25519_key()).unwrap();
+        let request = UserCertificateRequest::new(Uuid::now_v7(), "nobody", vec![], Duration::hours(1));
+
+        assert!(ca.sign_user_certificate(ed25519_key().public_key(), &request).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+//! Deterministic OpenSSH key derivation from the master seed
+//!
+//! SSH keys generated by `ssh-keygen` are tied to the machine that created
+//! them. Deriving the Ed25519 key from the `MasterSeed` means a lost key can
+//! be rebuilt from the master passphrase alone, exactly like the OpenPGP
+//! keysets in [`super::gpg_derivation`].
+//!
+//! ## Derivation Path
+//!
+//! ```text
+//! cim-keys/ssh/v1/{organization}/{person}/{purpose}[/{index}]  → Ed25519
+//! ```
+//!
+//! The index is omitted while it is 0, so keys derived before indices
+//! existed keep their path. See [`super::derivation_path`] for the scheme
+//! shared by all key families.
+//!
+//! Unlike OpenPGP, an OpenSSH public key carries no creation time, so the
+//! path alone determines the key and its SHA256 fingerprint.
+
+use der::zeroize::Zeroize;
+use ssh_key::private::{Ed25519Keypair, KeypairData};
+use ssh_key::{HashAlg, LineEnding, PrivateKey, PublicKey};
+
+use super::fingerprint::{FingerprintKind, KeyFingerprint};
+use super::seed_derivation::MasterSeed;
+
+/// Derivation scheme version, part of every path
+const SSH_DERIVATION_VERSION: &str = "v1";
+
+/// Derivation path for an OpenSSH key
+///
+/// Components must be non-empty and must not contain `/`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SshDerivationPath {
+    pub organization: String,
+    pub person: String,
+    pub purpose: String,
+    /// Rotation index within the purpose, 0 for the first key
+    pub index: u32,
+}
+
+impl SshDerivationPath {
+    /// Create a validated derivation path
+    pub fn new(
+        organization: impl Into<String>,
+        person: impl Into<String>,
+        purpose: impl Into<String>,
+    ) -> Result<Self, String> {
+        let path = Self {
+            organization: organization.into(),
+            person: person.into(),
+            purpose: purpose.into(),
+            index: 0,
+        };
+
+        for (name, value) in [
+            ("organization", &path.organization),
+            ("person", &path.person),
+            ("purpose", &path.purpose),
+        ] {
+            if value.is_empty() {
+                return Err(format!("SSH derivation path {} must not be empty", name));
+            }
+            if value.contains('/') {
+                return Err(format!("SSH derivation path {} must not contain '/'", name));
+            }
+        }
+
+        Ok(path)
+    }
+
+    /// Derive the `index`-th key of the purpose, e.g. after a rotation
+    pub fn with_index(mut self, index: u32) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Parse an HKDF info string produced by [`to_info`](Self::to_info)
+    pub fn from_info(info: &str) -> Result<Self, String> {
+        let invalid = || format!("Not an SSH derivation path: {}", info);
+        let rest = info
+            .strip_prefix("cim-keys/ssh/")
+            .and_then(|rest| rest.strip_prefix(SSH_DERIVATION_VERSION))
+            .and_then(|rest| rest.strip_prefix('/'))
+            .ok_or_else(invalid)?;
+        match rest.split('/').collect::<Vec<_>>()[..] {
+            [organization, person, purpose] => Self::new(organization, person, purpose),
+            [organization, person, purpose, index] => {
+                let index = index.parse::<u32>().ok().filter(|&i| i > 0).ok_or_else(invalid)?;
+                Ok(Self::new(organization, person, purpose)?.with_index(index))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// HKDF info string for this path
+    pub fn to_info(&self) -> String {
+        let info = format!(
+            "cim-keys/ssh/{}/{}/{}/{}",
+            SSH_DERIVATION_VERSION, self.organization, self.person, self.purpose
+        );
+        match self.index {
+            0 => info,
+            index => format!("{}/{}", info, index),
+        }
+    }
+}
+
+impl std::fmt::Display for SshDerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_info())
+    }
+}
+
+/// An Ed25519 SSH key derived for one path
+///
+/// Security: the secret seed is zeroized on drop and redacted from Debug.
+#[derive(Clone)]
+pub struct DerivedSshKey {
+    pub path: SshDerivationPath,
+    /// Raw Ed25519 public key
+    pub public_key: [u8; 32],
+    /// OpenSSH SHA256 fingerprint (`SHA256:...`)
+    pub fingerprint: String,
+    /// Ed25519 seed
+    secret_key: [u8; 32],
+}
+
+impl DerivedSshKey {
+    /// Secret key bytes (use with caution!)
+    pub fn secret_key_bytes(&self) -> &[u8; 32] {
+        &self.secret_key
+    }
+
+    /// Public key in `authorized_keys` format (`ssh-ed25519 AAAA... comment`)
+    pub fn to_openssh_public(&self, comment: &str) -> Result<String, String> {
+        let mut public = PublicKey::from(self.keypair().public);
+        public.set_comment(comment);
+        public
+            .to_openssh()
+            .map_err(|e| format!("Failed to encode SSH public key: {}", e))
+    }
+
+    /// Unencrypted OpenSSH private key PEM
+    pub fn to_openssh_private(&self, comment: &str) -> Result<String, String> {
+        let private = PrivateKey::new(KeypairData::Ed25519(self.keypair()), comment)
+            .map_err(|e| format!("Failed to build SSH private key: {}", e))?;
+        private
+            .to_openssh(LineEnding::LF)
+            .map(|pem| pem.to_string())
+            .map_err(|e| format!("Failed to encode SSH private key: {}", e))
+    }
+
+    fn keypair(&self) -> Ed25519Keypair {
+        Ed25519Keypair::from_seed(&self.secret_key)
+    }
+}
+
+impl std::fmt::Debug for DerivedSshKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DerivedSshKey")
+            .field("path", &self.path)
+            .field("fingerprint", &self.fingerprint)
+            .field("secret_key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl Zeroize for DerivedSshKey {
+    fn zeroize(&mut self) {
+        self.secret_key.zeroize();
+    }
+}
+
+impl Drop for DerivedSshKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Derive an Ed25519 SSH key from the master seed
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let path = SshDerivationPath::new("cowboyai", "alice", "login")?;
+/// let key = derive_ssh_key(&master_seed, &path);
+/// println!("{}", key.to_openssh_public("alice@cowboyai")?);
+/// ```
+pub fn derive_ssh_key(master_seed: &MasterSeed, path: &SshDerivationPath) -> DerivedSshKey {
+    let seed = master_seed.derive_child(&path.to_info());
+    let keypair = Ed25519Keypair::from_seed(seed.as_bytes());
+    let public_key = keypair.public.0;
+    let fingerprint = PublicKey::from(keypair.public)
+        .fingerprint(HashAlg::Sha256)
+        .to_string();
+
+    DerivedSshKey {
+        path: path.clone(),
+        public_key,
+        fingerprint,
+        secret_key: *seed.as_bytes(),
+    }
+}
+
+/// Re-derive an SSH key and prove it matches a previously recorded fingerprint
+///
+/// Used during recovery: the fingerprint stored in the manifest is the only
+/// input besides the master seed, and a mismatch means the passphrase,
+/// organization or path differs from the one used originally.
+pub fn regenerate_from_seed(
+    master_seed: &MasterSeed,
+    path: &SshDerivationPath,
+    expected_fingerprint: &str,
+) -> Result<DerivedSshKey, String> {
+    let key = derive_ssh_key(master_seed, path);
+    let fingerprint = KeyFingerprint::parse(FingerprintKind::SshSha256, &key.fingerprint)
+        .map_err(|e| e.to_string())?;
+    if !fingerprint.matches(expected_fingerprint) {
+        return Err(format!(
+            "SSH key regenerated at {} has fingerprint {}, expected {}",
+            path, key.fingerprint, expected_fingerprint
+        ));
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_seed() -> MasterSeed {
+        MasterSeed::from_bytes([7u8; 32])
+    }
+
+    fn alice_login() -> SshDerivationPath {
+        SshDerivationPath::new("cowboyai", "alice", "login").unwrap()
+    }
+
+    #[test]
+    fn test_derivation_is_deterministic() {
+        let first = derive_ssh_key(&test_seed(), &alice_login());
+        let second = derive_ssh_key(&test_seed(), &alice_login());
+
+        assert_eq!(first.public_key, second.public_key);
+        assert_eq!(first.fingerprint, second.fingerprint);
+        assert!(first.fingerprint.starts_with("SHA256:"));
+        assert_eq!(
+            first.to_openssh_public("alice").unwrap(),
+            second.to_openssh_public("alice").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_info_string_roundtrip() {
+        for path in [alice_login(), alice_login().with_index(3)] {
+            assert_eq!(SshDerivationPath::from_info(&path.to_info()).unwrap(), path);
+        }
+        assert!(SshDerivationPath::from_info("cim-keys/gpg/v1/cowboyai/alice/login").is_err());
+        assert!(SshDerivationPath::from_info("cim-keys/ssh/v1/cowboyai/alice/login/0").is_err());
+    }
+
+    #[test]
+    fn test_different_paths_produce_different_keys() {
+        let login = derive_ssh_key(&test_seed(), &alice_login());
+        let deploy = derive_ssh_key(
+            &test_seed(),
+            &SshDerivationPath::new("cowboyai", "alice", "deploy").unwrap(),
+        );
+        let other_seed = derive_ssh_key(&MasterSeed::from_bytes([8u8; 32]), &alice_login());
+
+        assert_ne!(login.public_key, deploy.public_key);
+        assert_ne!(login.fingerprint, other_seed.fingerprint);
+    }
+
+    #[test]
+    fn test_regenerate_from_seed_checks_fingerprint() {
+        let original = derive_ssh_key(&test_seed(), &alice_login());
+
+        let regenerated = regenerate_from_seed(&test_seed(), &alice_login(), &original.fingerprint).unwrap();
+        assert_eq!(regenerated.public_key, original.public_key);
+
+        let wrong_seed = MasterSeed::from_bytes([9u8; 32]);
+        assert!(regenerate_from_seed(&wrong_seed, &alice_login(), &original.fingerprint).is_err());
+    }
+
+    #[test]
+    fn test_private_key_round_trips_through_openssh() {
+        let key = derive_ssh_key(&test_seed(), &alice_login());
+        let pem = key.to_openssh_private("alice").unwrap();
+
+        let parsed = PrivateKey::from_openssh(&pem).unwrap();
+        assert_eq!(
+            parsed.public_key().fingerprint(HashAlg::Sha256).to_string(),
+            key.fingerprint
+        );
+    }
+
+    #[test]
+    fn test_path_validation_and_debug() {
+        assert!(SshDerivationPath::new("cowboyai", "", "login").is_err());
+        assert!(SshDerivationPath::new("cowboyai", "alice", "a/b").is_err());
+        assert_eq!(alice_login().to_info(), "cim-keys/ssh/v1/cowboyai/alice/login");
+
+        let key = derive_ssh_key(&test_seed(), &alice_login());
+        assert!(format!("{:?}", key).contains("<redacted>"));
+    }
+}
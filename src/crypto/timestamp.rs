@@ -0,0 +1,664 @@
+//! Time-Stamp Authority (RFC 3161)
+//!
+//! Timestamps let code signatures outlive the signing certificate: a
+//! verifier accepts a signature made while the certificate was valid, as
+//! proven by a token from a trusted TSA.
+//!
+//! ```text
+//! TimestampAuthority policy CA
+//!   └─ TSA certificate
+//!        ├─ keyUsage: digitalSignature, nonRepudiation
+//!        └─ extendedKeyUsage: timeStamping (critical, sole purpose)
+//!
+//! data (e.g. a code signature)
+//!   ↓ SHA-256 message imprint
+//! TSTInfo (policy, serial, genTime, nonce)
+//!   ↓ CMS SignedData, signed attributes:
+//!   ↓   contentType, messageDigest, signingCertificateV2 (RFC 5816)
+//! TimeStampToken (DER ContentInfo)
+//! ```
+//!
+//! Tokens are produced offline from data the caller hands in; there is no
+//! TimeStampReq/TimeStampResp transport here.
+
+use chrono::{DateTime, SubsecRound, Utc};
+use der::asn1::{Any, GeneralizedTime, ObjectIdentifier, OctetString, SetOfVec, Uint};
+use der::oid::db::{rfc5280, rfc5911, rfc5912, rfc8410};
+use der::{Decode, DecodeOwned, Encode, Sequence, ValueOrd};
+use rcgen::{
+    CertificateParams, CustomExtension, DistinguishedName, DnType, IsCa, Issuer,
+    KeyPair as RcgenKeyPair, KeyUsagePurpose,
+};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+use x509_cert::attr::{Attribute, Attributes};
+use x509_cert::ext::pkix::ExtendedKeyUsage as EkuExtension;
+use x509_cert::name::Name;
+use x509_cert::serial_number::SerialNumber;
+use x509_cert::spki::AlgorithmIdentifierOwned;
+use x509_cert::Certificate as X509CertDer;
+
+use super::ocsp::{parse_certificate, signature_algorithm_identifier};
+use super::x509::{
+    calculate_fingerprint, certificate_serial, certificate_validity, issuer_path_len,
+    signature_algorithm_name, signature_algorithm_of,
+};
+use crate::value_objects::x509::{
+    BasicConstraints as BasicConstraintsVO, CertificateSignatureAlgorithm, CommonName,
+    ExtendedKeyUsage, KeyUsage, OrganizationName, SubjectName,
+};
+
+/// id-ct-TSTInfo (1.2.840.113549.1.9.16.1.4)
+const ID_CT_TST_INFO: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.1.4");
+
+/// id-kp-timeStamping (1.3.6.1.5.5.7.3.8)
+const TIME_STAMPING_OID: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 3, 8];
+
+// ============================================================================
+// ASN.1 structures (RFC 3161, RFC 5652, RFC 5816)
+// ============================================================================
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct MessageImprint {
+    hash_algorithm: AlgorithmIdentifierOwned,
+    hashed_message: OctetString,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct TstInfo {
+    version: u8,
+    policy: ObjectIdentifier,
+    message_imprint: MessageImprint,
+    serial_number: Uint,
+    gen_time: GeneralizedTime,
+    #[asn1(default = "Default::default")]
+    ordering: bool,
+    #[asn1(optional = "true")]
+    nonce: Option<Uint>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct EncapsulatedContentInfo {
+    e_content_type: ObjectIdentifier,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    e_content: Option<OctetString>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+struct IssuerAndSerialNumber {
+    issuer: Name,
+    serial_number: SerialNumber,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence, ValueOrd)]
+struct SignerInfo {
+    version: u8,
+    sid: IssuerAndSerialNumber,
+    digest_algorithm: AlgorithmIdentifierOwned,
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    signed_attrs: Option<Attributes>,
+    signature_algorithm: AlgorithmIdentifierOwned,
+    signature: OctetString,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct SignedData {
+    version: u8,
+    digest_algorithms: SetOfVec<AlgorithmIdentifierOwned>,
+    encap_content_info: EncapsulatedContentInfo,
+    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
+    certificates: Option<SetOfVec<X509CertDer>>,
+    signer_infos: SetOfVec<SignerInfo>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct ContentInfo {
+    content_type: ObjectIdentifier,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT")]
+    content: SignedData,
+}
+
+/// ESSCertIDv2 with the default SHA-256 hash algorithm and no issuerSerial
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct EssCertIdV2 {
+    cert_hash: OctetString,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct SigningCertificateV2 {
+    certs: Vec<EssCertIdV2>,
+}
+
+// ============================================================================
+// TSA certificate
+// ============================================================================
+
+/// Parameters for a time-stamp authority certificate
+#[derive(Debug, Clone)]
+pub struct TimestampAuthorityParams {
+    pub organization: String,
+    pub common_name: String,
+    /// Tokens stay verifiable after expiry, so this only bounds how long
+    /// the key may issue new ones
+    pub validity_days: u32,
+}
+
+impl Default for TimestampAuthorityParams {
+    fn default() -> Self {
+        Self {
+            organization: "CowboyAI".to_string(),
+            common_name: "CowboyAI Time-Stamp Authority".to_string(),
+            validity_days: 365,
+        }
+    }
+}
+
+/// Time-stamp authority certificate and its signing key
+#[derive(Debug, Clone)]
+pub struct TimestampAuthorityCertificate {
+    pub certificate_pem: String,
+    pub private_key_pem: String,
+    pub fingerprint: String,
+    pub generation_event: crate::events::CertificateGeneratedEvent,
+    pub signing_event: crate::events::CertificateSignedEvent,
+}
+
+/// Issue a time-stamp authority certificate under `issuer_cert_pem`
+///
+/// The TSA gets a fresh P-256 key. As RFC 3161 §2.3 requires, the
+/// certificate's extended key usage is critical and names timeStamping
+/// only, so the key cannot double as a code-signing or TLS key.
+pub fn generate_timestamp_authority_certificate<S: rcgen::SigningKey>(
+    params: TimestampAuthorityParams,
+    issuer_cert_pem: &str,
+    issuer_signer: S,
+    issuer_ca_id: Uuid,
+    correlation_id: Uuid,
+    causation_id: Option<Uuid>,
+) -> Result<TimestampAuthorityCertificate, String> {
+    issuer_path_len(issuer_cert_pem)?;
+    if params.validity_days == 0 {
+        return Err("TSA certificate validity must be at least one day".to_string());
+    }
+
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::CommonName, params.common_name.clone());
+    dn.push(DnType::OrganizationName, params.organization.clone());
+
+    let eku_der = EkuExtension(vec![rfc5280::ID_KP_TIME_STAMPING])
+        .to_der()
+        .map_err(|e| format!("Failed to encode extended key usage: {}", e))?;
+    let mut eku = CustomExtension::from_oid_content(&[2, 5, 29, 37], eku_der);
+    eku.set_criticality(true);
+
+    let mut cert_params = CertificateParams::new(vec![])
+        .map_err(|e| format!("Failed to create certificate params: {}", e))?;
+    cert_params.distinguished_name = dn;
+    cert_params.is_ca = IsCa::ExplicitNoCa;
+    cert_params.key_usages = vec![KeyUsagePurpose::DigitalSignature, KeyUsagePurpose::ContentCommitment];
+    cert_params.custom_extensions = vec![eku];
+    cert_params.use_authority_key_identifier_extension = true;
+
+    let cert_id = Uuid::now_v7();
+    let not_before = OffsetDateTime::now_utc();
+    let not_after = not_before + Duration::days(params.validity_days as i64);
+    cert_params.not_before = not_before;
+    cert_params.not_after = not_after;
+    cert_params.serial_number = Some(certificate_serial(cert_id));
+
+    let key_pair = RcgenKeyPair::generate()
+        .map_err(|e| format!("Failed to generate key pair: {}", e))?;
+
+    let issuer_algorithm = signature_algorithm_of(issuer_signer.algorithm());
+    let signature_algorithm = signature_algorithm_name(issuer_algorithm, issuer_signer.algorithm());
+    let issuer = Issuer::from_ca_cert_pem(issuer_cert_pem, issuer_signer)
+        .map_err(|e| format!("Failed to load issuer CA: {}", e))?;
+    let cert = cert_params.signed_by(&key_pair, &issuer)
+        .map_err(|e| format!("Failed to sign TSA certificate: {}", e))?;
+
+    let validity = certificate_validity(not_before, not_after)?;
+
+    let generation_event = crate::events::CertificateGeneratedEvent {
+        cert_id,
+        key_id: Uuid::now_v7(),
+        subject_name: SubjectName::new(CommonName::new_unchecked(&params.common_name))
+            .with_organization(OrganizationName::new_unchecked(&params.organization)),
+        subject_alt_name: None,
+        key_usage: KeyUsage::time_stamping(),
+        extended_key_usage: Some(ExtendedKeyUsage::time_stamping()),
+        validity,
+        basic_constraints: BasicConstraintsVO::end_entity(),
+        issuer: Some(issuer_ca_id),
+        signature_algorithm: issuer_algorithm,
+        correlation_id,
+        causation_id,
+    };
+
+    let signing_event = crate::events::CertificateSignedEvent {
+        cert_id,
+        signed_by: issuer_ca_id,
+        signature_algorithm,
+        signed_at: Utc::now(),
+        correlation_id,
+        causation_id: Some(cert_id), // Signing caused by certificate generation
+    };
+
+    Ok(TimestampAuthorityCertificate {
+        certificate_pem: cert.pem(),
+        private_key_pem: key_pair.serialize_pem(),
+        fingerprint: calculate_fingerprint(cert.der()),
+        generation_event,
+        signing_event,
+    })
+}
+
+// ============================================================================
+// Tokens
+// ============================================================================
+
+/// A time-stamp token and the facts it attests
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimestampToken {
+    /// DER-encoded TimeStampToken (CMS ContentInfo)
+    pub token_der: Vec<u8>,
+    /// Token serial number
+    pub serial: Uuid,
+    /// When the TSA saw the data (whole seconds)
+    pub gen_time: DateTime<Utc>,
+    /// TSA policy the token was issued under (dotted OID)
+    pub policy: String,
+    /// Nonce supplied by the requester, if any
+    pub nonce: Option<u64>,
+}
+
+fn sha256(data: &[u8]) -> Vec<u8> {
+    ring::digest::digest(&ring::digest::SHA256, data).as_ref().to_vec()
+}
+
+fn sha256_algorithm() -> AlgorithmIdentifierOwned {
+    AlgorithmIdentifierOwned { oid: rfc5912::ID_SHA_256, parameters: None }
+}
+
+/// Digest used in the CMS signer info: the hash the signature algorithm
+/// already applies (SHA-512 for Ed25519, per RFC 8419)
+fn signer_digest(algorithm: &rcgen::SignatureAlgorithm) -> Result<(&'static ring::digest::Algorithm, ObjectIdentifier), String> {
+    use CertificateSignatureAlgorithm::*;
+    match signature_algorithm_of(algorithm) {
+        Some(EcdsaP256Sha256 | RsaPkcs1Sha256) => Ok((&ring::digest::SHA256, rfc5912::ID_SHA_256)),
+        Some(EcdsaP384Sha384 | RsaPkcs1Sha384) => Ok((&ring::digest::SHA384, rfc5912::ID_SHA_384)),
+        Some(Ed25519 | RsaPkcs1Sha512) => Ok((&ring::digest::SHA512, rfc5912::ID_SHA_512)),
+        None => Err(format!("Unsupported TSA signature algorithm: {:?}", algorithm)),
+    }
+}
+
+fn generalized_time(at: DateTime<Utc>) -> Result<GeneralizedTime, String> {
+    let secs = u64::try_from(at.timestamp()).map_err(|_| format!("Time before epoch: {}", at))?;
+    GeneralizedTime::from_unix_duration(std::time::Duration::from_secs(secs))
+        .map_err(|e| format!("Invalid time {}: {}", at, e))
+}
+
+fn attribute(oid: ObjectIdentifier, value: &impl Encode) -> Result<Attribute, String> {
+    let mut values = SetOfVec::new();
+    let encode_err = |e: der::Error| format!("Failed to encode attribute: {}", e);
+    values
+        .insert(Any::from_der(&value.to_der().map_err(encode_err)?).map_err(encode_err)?)
+        .map_err(encode_err)?;
+    Ok(Attribute { oid, values })
+}
+
+fn attribute_value<T: DecodeOwned>(attrs: &Attributes, oid: ObjectIdentifier) -> Result<T, String> {
+    let attr = attrs
+        .iter()
+        .find(|a| a.oid == oid)
+        .ok_or_else(|| format!("Time-stamp token lacks signed attribute {}", oid))?;
+    match attr.values.as_slice() {
+        [value] => value
+            .to_der()
+            .and_then(|der| T::from_der(&der))
+            .map_err(|e| format!("Malformed signed attribute {}: {}", oid, e)),
+        _ => Err(format!("Signed attribute {} must have exactly one value", oid)),
+    }
+}
+
+fn has_critical_time_stamping_eku(cert: &X509CertDer) -> bool {
+    cert.tbs_certificate
+        .extensions
+        .iter()
+        .flatten()
+        .filter(|ext| ext.extn_id == rfc5280::ID_CE_EXT_KEY_USAGE)
+        .any(|ext| {
+            ext.critical
+                && EkuExtension::from_der(ext.extn_value.as_bytes())
+                    .map(|eku| eku.0 == [rfc5280::ID_KP_TIME_STAMPING])
+                    .unwrap_or(false)
+        })
+}
+
+/// Issues RFC 3161 time-stamp tokens with a TSA key
+///
+/// The TSA certificate must come from
+/// [`generate_timestamp_authority_certificate`] (or carry the same
+/// critical, timeStamping-only EKU) and match the signing key.
+pub struct TimestampAuthority<S> {
+    certificate: X509CertDer,
+    certificate_hash: Vec<u8>,
+    signer: S,
+    policy: ObjectIdentifier,
+    digest: &'static ring::digest::Algorithm,
+    digest_algorithm: AlgorithmIdentifierOwned,
+    signature_algorithm: AlgorithmIdentifierOwned,
+}
+
+impl<S: rcgen::SigningKey> TimestampAuthority<S> {
+    /// TSA issuing tokens under `policy_oid` (dotted form)
+    pub fn new(tsa_cert_pem: &str, signer: S, policy_oid: &str) -> Result<Self, String> {
+        let certificate = parse_certificate(tsa_cert_pem)?;
+        if !has_critical_time_stamping_eku(&certificate) {
+            return Err("TSA certificate must carry a critical, timeStamping-only extended key usage".to_string());
+        }
+        let key_bits = certificate.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes();
+        if key_bits != signer.der_bytes() {
+            return Err("Signing key does not match the TSA certificate".to_string());
+        }
+        let policy = ObjectIdentifier::new(policy_oid)
+            .map_err(|e| format!("Invalid TSA policy OID {}: {}", policy_oid, e))?;
+        let (digest, digest_oid) = signer_digest(signer.algorithm())?;
+
+        Ok(Self {
+            certificate_hash: sha256(&certificate.to_der().map_err(|e| format!("Failed to encode TSA certificate: {}", e))?),
+            certificate,
+            signature_algorithm: signature_algorithm_identifier(signer.algorithm())?,
+            signer,
+            policy,
+            digest,
+            digest_algorithm: AlgorithmIdentifierOwned { oid: digest_oid, parameters: None },
+        })
+    }
+
+    /// Timestamp `data`, e.g. the signature value of a code signature
+    ///
+    /// The token's message imprint is the SHA-256 of `data`; `nonce` lets a
+    /// requester tie the token to its request. The TSA certificate is
+    /// embedded so verifiers need only the trust anchor.
+    pub fn timestamp(&self, data: &[u8], nonce: Option<u64>) -> Result<TimestampToken, String> {
+        let serial = Uuid::now_v7();
+        let gen_time = Utc::now().trunc_subsecs(0);
+        let encode_err = |e: der::Error| format!("Failed to encode time-stamp token: {}", e);
+
+        let tst_info = TstInfo {
+            version: 1,
+            policy: self.policy,
+            message_imprint: MessageImprint {
+                hash_algorithm: sha256_algorithm(),
+                hashed_message: OctetString::new(sha256(data)).map_err(encode_err)?,
+            },
+            serial_number: Uint::new(serial.as_bytes()).map_err(encode_err)?,
+            gen_time: generalized_time(gen_time)?,
+            ordering: false,
+            nonce: nonce.map(|n| Uint::new(&n.to_be_bytes())).transpose().map_err(encode_err)?,
+        };
+        let e_content = tst_info.to_der().map_err(encode_err)?;
+
+        let mut signed_attrs = Attributes::new();
+        signed_attrs.insert(attribute(rfc5911::ID_CONTENT_TYPE, &ID_CT_TST_INFO)?).map_err(encode_err)?;
+        signed_attrs
+            .insert(attribute(
+                rfc5911::ID_MESSAGE_DIGEST,
+                &OctetString::new(ring::digest::digest(self.digest, &e_content).as_ref()).map_err(encode_err)?,
+            )?)
+            .map_err(encode_err)?;
+        signed_attrs
+            .insert(attribute(
+                rfc5911::ID_AA_SIGNING_CERTIFICATE_V_2,
+                &SigningCertificateV2 {
+                    certs: vec![EssCertIdV2 {
+                        cert_hash: OctetString::new(self.certificate_hash.clone()).map_err(encode_err)?,
+                    }],
+                },
+            )?)
+            .map_err(encode_err)?;
+
+        // The signature covers the DER SET OF, not the [0] IMPLICIT form
+        let signature = self.signer
+            .sign(&signed_attrs.to_der().map_err(encode_err)?)
+            .map_err(|e| format!("Failed to sign time-stamp token: {}", e))?;
+
+        let signer_info = SignerInfo {
+            version: 1,
+            sid: IssuerAndSerialNumber {
+                issuer: self.certificate.tbs_certificate.issuer.clone(),
+                serial_number: self.certificate.tbs_certificate.serial_number.clone(),
+            },
+            digest_algorithm: self.digest_algorithm.clone(),
+            signed_attrs: Some(signed_attrs),
+            signature_algorithm: self.signature_algorithm.clone(),
+            signature: OctetString::new(signature).map_err(encode_err)?,
+        };
+
+        let mut digest_algorithms = SetOfVec::new();
+        digest_algorithms.insert(self.digest_algorithm.clone()).map_err(encode_err)?;
+        let mut certificates = SetOfVec::new();
+        certificates.insert(self.certificate.clone()).map_err(encode_err)?;
+        let mut signer_infos = SetOfVec::new();
+        signer_infos.insert(signer_info).map_err(encode_err)?;
+
+        let token = ContentInfo {
+            content_type: rfc5911::ID_SIGNED_DATA,
+            content: SignedData {
+                // v3: the encapsulated content is not id-data
+                version: 3,
+                digest_algorithms,
+                encap_content_info: EncapsulatedContentInfo {
+                    e_content_type: ID_CT_TST_INFO,
+                    e_content: Some(OctetString::new(e_content).map_err(encode_err)?),
+                },
+                certificates: Some(certificates),
+                signer_infos,
+            },
+        };
+
+        Ok(TimestampToken {
+            token_der: token.to_der().map_err(encode_err)?,
+            serial,
+            gen_time,
+            policy: self.policy.to_string(),
+            nonce,
+        })
+    }
+}
+
+/// Verify a time-stamp token over `data` against the TSA certificate
+///
+/// Checks the message imprint, the signed content type and digest, that
+/// the signing certificate attribute names `tsa_cert_pem`, and the TSA
+/// signature. Trust in the TSA certificate itself (chain, validity at
+/// genTime) is the caller's to establish.
+pub fn verify_timestamp_token(token_der: &[u8], data: &[u8], tsa_cert_pem: &str) -> Result<TimestampToken, String> {
+    let tsa_cert = parse_certificate(tsa_cert_pem)?;
+    let token = ContentInfo::from_der(token_der).map_err(|e| format!("Malformed time-stamp token: {}", e))?;
+    if token.content_type != rfc5911::ID_SIGNED_DATA {
+        return Err("Time-stamp token is not CMS signed data".to_string());
+    }
+    let signed = &token.content;
+    if signed.encap_content_info.e_content_type != ID_CT_TST_INFO {
+        return Err("Time-stamp token does not encapsulate a TSTInfo".to_string());
+    }
+    let e_content = signed.encap_content_info.e_content.as_ref()
+        .ok_or_else(|| "Time-stamp token has no TSTInfo content".to_string())?
+        .as_bytes();
+    let tst_info = TstInfo::from_der(e_content).map_err(|e| format!("Malformed TSTInfo: {}", e))?;
+
+    if tst_info.message_imprint.hash_algorithm.oid != rfc5912::ID_SHA_256
+        || tst_info.message_imprint.hashed_message.as_bytes() != sha256(data).as_slice()
+    {
+        return Err("Time-stamp token does not cover this data".to_string());
+    }
+
+    let [signer_info] = signed.signer_infos.as_slice() else {
+        return Err("Time-stamp token must have exactly one signer".to_string());
+    };
+    let signed_attrs = signer_info.signed_attrs.as_ref()
+        .ok_or_else(|| "Time-stamp token has no signed attributes".to_string())?;
+
+    let content_type: ObjectIdentifier = attribute_value(signed_attrs, rfc5911::ID_CONTENT_TYPE)?;
+    if content_type != ID_CT_TST_INFO {
+        return Err("Signed content type is not TSTInfo".to_string());
+    }
+    let digest = if signer_info.digest_algorithm.oid == rfc5912::ID_SHA_256 {
+        &ring::digest::SHA256
+    } else if signer_info.digest_algorithm.oid == rfc5912::ID_SHA_384 {
+        &ring::digest::SHA384
+    } else if signer_info.digest_algorithm.oid == rfc5912::ID_SHA_512 {
+        &ring::digest::SHA512
+    } else {
+        return Err(format!("Unsupported digest algorithm {}", signer_info.digest_algorithm.oid));
+    };
+    let message_digest: OctetString = attribute_value(signed_attrs, rfc5911::ID_MESSAGE_DIGEST)?;
+    if message_digest.as_bytes() != ring::digest::digest(digest, e_content).as_ref() {
+        return Err("TSTInfo does not match the signed message digest".to_string());
+    }
+    let signing_cert: SigningCertificateV2 = attribute_value(signed_attrs, rfc5911::ID_AA_SIGNING_CERTIFICATE_V_2)?;
+    let tsa_cert_hash = sha256(&tsa_cert.to_der().map_err(|e| format!("Failed to encode TSA certificate: {}", e))?);
+    if signing_cert.certs.first().map(|c| c.cert_hash.as_bytes()) != Some(tsa_cert_hash.as_slice()) {
+        return Err("Time-stamp token was not signed with this TSA certificate".to_string());
+    }
+
+    let sig_oid = signer_info.signature_algorithm.oid;
+    let verification: &dyn ring::signature::VerificationAlgorithm = if sig_oid == rfc5912::ECDSA_WITH_SHA_256 {
+        &ring::signature::ECDSA_P256_SHA256_ASN1
+    } else if sig_oid == rfc5912::ECDSA_WITH_SHA_384 {
+        &ring::signature::ECDSA_P384_SHA384_ASN1
+    } else if sig_oid == rfc8410::ID_ED_25519 {
+        &ring::signature::ED25519
+    } else if sig_oid == rfc5912::SHA_256_WITH_RSA_ENCRYPTION {
+        &ring::signature::RSA_PKCS1_2048_8192_SHA256
+    } else if sig_oid == rfc5912::SHA_384_WITH_RSA_ENCRYPTION {
+        &ring::signature::RSA_PKCS1_2048_8192_SHA384
+    } else if sig_oid == rfc5912::SHA_512_WITH_RSA_ENCRYPTION {
+        &ring::signature::RSA_PKCS1_2048_8192_SHA512
+    } else {
+        return Err(format!("Unsupported signature algorithm {}", sig_oid));
+    };
+    let signed_attrs_der = signed_attrs.to_der().map_err(|e| format!("Failed to encode signed attributes: {}", e))?;
+    ring::signature::UnparsedPublicKey::new(
+        verification,
+        tsa_cert.tbs_certificate.subject_public_key_info.subject_public_key.raw_bytes(),
+    )
+    .verify(&signed_attrs_der, signer_info.signature.as_bytes())
+    .map_err(|_| "Time-stamp token signature is invalid".to_string())?;
+
+    let mut serial = [0u8; 16];
+    let serial_bytes = tst_info.serial_number.as_bytes();
+    if serial_bytes.len() > serial.len() {
+        return Err("Time-stamp token serial is not a CIM token id".to_string());
+    }
+    serial[16 - serial_bytes.len()..].copy_from_slice(serial_bytes);
+    let nonce = tst_info.nonce
+        .map(|n| {
+            let bytes = n.as_bytes();
+            if bytes.len() > 8 {
+                return Err("Time-stamp nonce exceeds 64 bits".to_string());
+            }
+            let mut buf = [0u8; 8];
+            buf[8 - bytes.len()..].copy_from_slice(bytes);
+            Ok(u64::from_be_bytes(buf))
+        })
+        .transpose()?;
+    let gen_secs = tst_info.gen_time.to_unix_duration().as_secs();
+
+    Ok(TimestampToken {
+        token_der: token_der.to_vec(),
+        serial: Uuid::from_bytes(serial),
+        gen_time: DateTime::from_timestamp(gen_secs as i64, 0)
+            .ok_or_else(|| "Time-stamp genTime out of range".to_string())?,
+        policy: tst_info.policy.to_string(),
+        nonce,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::seed_derivation::derive_master_seed;
+    use crate::crypto::x509::{generate_root_ca, RootCAParams};
+
+    const TEST_POLICY: &str = "1.2.3.4.1";
+
+    fn tsa() -> (TimestampAuthorityCertificate, RcgenKeyPair) {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let (root, _) = generate_root_ca(&master_seed.derive_child("root-ca"), RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = RcgenKeyPair::from_pem(&root.private_key_pem).unwrap();
+        let tsa_cert = generate_timestamp_authority_certificate(
+            TimestampAuthorityParams::default(),
+            &root.certificate_pem,
+            &root_key,
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        )
+        .unwrap();
+        let tsa_key = RcgenKeyPair::from_pem(&tsa_cert.private_key_pem).unwrap();
+        (tsa_cert, tsa_key)
+    }
+
+    #[test]
+    fn test_tsa_certificate_has_critical_time_stamping_eku() {
+        use x509_parser::prelude::*;
+
+        let (tsa_cert, _) = tsa();
+        let (_, pem) = parse_x509_pem(tsa_cert.certificate_pem.as_bytes()).unwrap();
+        let cert = pem.parse_x509().unwrap();
+        let eku = cert.extended_key_usage().unwrap().unwrap();
+        assert!(eku.critical);
+        assert!(eku.value.time_stamping && !eku.value.code_signing && !eku.value.server_auth);
+        assert!(!cert.is_ca());
+        assert!(tsa_cert.generation_event.extended_key_usage.as_ref().unwrap().critical);
+    }
+
+    #[test]
+    fn test_timestamp_token_round_trips() {
+        let (tsa_cert, tsa_key) = tsa();
+        let authority = TimestampAuthority::new(&tsa_cert.certificate_pem, &tsa_key, TEST_POLICY).unwrap();
+
+        let signature = b"code signature bytes";
+        let token = authority.timestamp(signature, Some(42)).unwrap();
+        let verified = verify_timestamp_token(&token.token_der, signature, &tsa_cert.certificate_pem).unwrap();
+
+        assert_eq!(verified, token);
+        assert_eq!(verified.policy, TEST_POLICY);
+        assert_eq!(verified.nonce, Some(42));
+        assert!((Utc::now() - verified.gen_time).num_seconds() < 5);
+    }
+
+    #[test]
+    fn test_timestamp_token_rejects_other_data_tampering_and_wrong_tsa() {
+        let (tsa_cert, tsa_key) = tsa();
+        let authority = TimestampAuthority::new(&tsa_cert.certificate_pem, &tsa_key, TEST_POLICY).unwrap();
+        let token = authority.timestamp(b"original", None).unwrap();
+
+        let err = verify_timestamp_token(&token.token_der, b"altered", &tsa_cert.certificate_pem).unwrap_err();
+        assert!(err.contains("does not cover"));
+
+        // Flip a byte of the signature (the token's last bytes)
+        let mut tampered = token.token_der.clone();
+        *tampered.last_mut().unwrap() ^= 0x01;
+        assert!(verify_timestamp_token(&tampered, b"original", &tsa_cert.certificate_pem).is_err());
+
+        let (other_tsa, _) = tsa();
+        let err = verify_timestamp_token(&token.token_der, b"original", &other_tsa.certificate_pem).unwrap_err();
+        assert!(err.contains("not signed with this TSA"));
+    }
+
+    #[test]
+    fn test_timestamp_authority_requires_tsa_certificate_and_matching_key() {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let (root, _) = generate_root_ca(&master_seed.derive_child("root-ca"), RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = RcgenKeyPair::from_pem(&root.private_key_pem).unwrap();
+        let err = TimestampAuthority::new(&root.certificate_pem, &root_key, TEST_POLICY).err().unwrap();
+        assert!(err.contains("timeStamping"));
+
+        let (tsa_cert, _) = tsa();
+        let err = TimestampAuthority::new(&tsa_cert.certificate_pem, &root_key, TEST_POLICY).err().unwrap();
+        assert!(err.contains("does not match"));
+    }
+}
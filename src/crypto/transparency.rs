@@ -0,0 +1,163 @@
+//! Merkle tree primitives for the certificate transparency log
+//!
+//! Implements the tree hash, audit paths and inclusion verification of
+//! RFC 6962 §2.1 (restated in RFC 9162 §2.1) over SHA-256. Leaves and
+//! interior nodes use distinct prefixes so a leaf can never be passed off
+//! as a subtree.
+//!
+//! ```text
+//! leaf_hash  = SHA-256(0x00 || entry)
+//! node_hash  = SHA-256(0x01 || left || right)
+//! MTH(D[n])  = node_hash(MTH(D[0..k]), MTH(D[k..n]))   k = largest power of 2 < n
+//! ```
+
+use sha2::{Digest, Sha256};
+
+/// SHA-256 digest of a leaf or tree node
+pub type MerkleHash = [u8; 32];
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash a log entry as a Merkle leaf
+pub fn leaf_hash(entry: &[u8]) -> MerkleHash {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(entry);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &MerkleHash, right: &MerkleHash) -> MerkleHash {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Largest power of two strictly less than `n` (n > 1)
+fn split_point(n: usize) -> usize {
+    let mut k = 1;
+    while k << 1 < n {
+        k <<= 1;
+    }
+    k
+}
+
+/// Root hash of the tree over `leaves` (already leaf-hashed)
+///
+/// The empty tree hashes to SHA-256 of the empty string.
+pub fn merkle_root(leaves: &[MerkleHash]) -> MerkleHash {
+    match leaves.len() {
+        0 => Sha256::digest([]).into(),
+        1 => leaves[0],
+        n => {
+            let k = split_point(n);
+            node_hash(&merkle_root(&leaves[..k]), &merkle_root(&leaves[k..]))
+        }
+    }
+}
+
+/// Audit path proving `leaves[index]` is in the tree, sibling nearest the leaf first
+///
+/// Returns `None` when `index` is out of range.
+pub fn inclusion_proof(leaves: &[MerkleHash], index: usize) -> Option<Vec<MerkleHash>> {
+    if index >= leaves.len() {
+        return None;
+    }
+    let mut subtree = leaves;
+    let mut m = index;
+    let mut siblings = Vec::new();
+    while subtree.len() > 1 {
+        let k = split_point(subtree.len());
+        if m < k {
+            siblings.push(merkle_root(&subtree[k..]));
+            subtree = &subtree[..k];
+        } else {
+            siblings.push(merkle_root(&subtree[..k]));
+            subtree = &subtree[k..];
+            m -= k;
+        }
+    }
+    // Collected root-first; the proof is consumed leaf-first
+    siblings.reverse();
+    Some(siblings)
+}
+
+/// Check an audit path from `inclusion_proof` against a tree head
+///
+/// Follows the verification algorithm of RFC 9162 §2.1.3.2, so proofs
+/// produced by other RFC 6962 logs verify as well.
+pub fn verify_inclusion(
+    leaf: &MerkleHash,
+    index: u64,
+    tree_size: u64,
+    proof: &[MerkleHash],
+    root: &MerkleHash,
+) -> bool {
+    if index >= tree_size {
+        return false;
+    }
+    let mut f_n = index;
+    let mut s_n = tree_size - 1;
+    let mut r = *leaf;
+    for p in proof {
+        if s_n == 0 {
+            return false;
+        }
+        if f_n & 1 == 1 || f_n == s_n {
+            r = node_hash(p, &r);
+            while f_n & 1 == 0 && f_n != 0 {
+                f_n >>= 1;
+                s_n >>= 1;
+            }
+        } else {
+            r = node_hash(&r, p);
+        }
+        f_n >>= 1;
+        s_n >>= 1;
+    }
+    s_n == 0 && r == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: usize) -> Vec<MerkleHash> {
+        (0..n).map(|i| leaf_hash(format!("certificate {}", i).as_bytes())).collect()
+    }
+
+    #[test]
+    fn test_every_leaf_proves_inclusion_for_every_tree_size() {
+        for n in 1..=17 {
+            let leaves = leaves(n);
+            let root = merkle_root(&leaves);
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = inclusion_proof(&leaves, i).unwrap();
+                assert!(verify_inclusion(leaf, i as u64, n as u64, &proof, &root), "n={} i={}", n, i);
+            }
+            assert!(inclusion_proof(&leaves, n).is_none());
+        }
+    }
+
+    #[test]
+    fn test_matches_rfc6962_tree_shape() {
+        let l = leaves(3);
+        let expected = node_hash(&node_hash(&l[0], &l[1]), &l[2]);
+        assert_eq!(merkle_root(&l), expected);
+        assert_eq!(inclusion_proof(&l, 2).unwrap(), vec![node_hash(&l[0], &l[1])]);
+    }
+
+    #[test]
+    fn test_proof_rejects_wrong_leaf_index_or_root() {
+        let leaves = leaves(7);
+        let root = merkle_root(&leaves);
+        let proof = inclusion_proof(&leaves, 3).unwrap();
+
+        assert!(!verify_inclusion(&leaves[4], 3, 7, &proof, &root));
+        assert!(!verify_inclusion(&leaves[3], 2, 7, &proof, &root));
+        assert!(!verify_inclusion(&leaves[3], 3, 4, &proof, &root));
+        assert!(!verify_inclusion(&leaves[3], 3, 7, &proof, &merkle_root(&leaves[..6])));
+    }
+}
@@ -24,15 +24,19 @@ use rcgen::{
     CertificateParams, DistinguishedName, DnType,
     KeyUsagePurpose, ExtendedKeyUsagePurpose, IsCa,
     BasicConstraints as RcgenBasicConstraints,
-    KeyPair as RcgenKeyPair, Issuer,
+    KeyPair as RcgenKeyPair, Issuer, CertificateSigningRequestParams, DnValue, SanType,
 };
 use time::{Duration, OffsetDateTime};
 
+use crate::domain::PolicyConstraint;
+use crate::types::KeyPurpose;
+
 // Import our X.509 value objects for event construction
 use crate::value_objects::x509::{
     SubjectName, CommonName, OrganizationName, OrganizationalUnitName,
     KeyUsage, ExtendedKeyUsage, CertificateValidity,
     BasicConstraints as BasicConstraintsVO, SubjectAlternativeName,
+    CertificateSignatureAlgorithm,
 };
 
 /// X.509 certificate with associated keypair
@@ -69,6 +73,9 @@ pub struct RootCAParams {
     /// Default: 1 (standard single-intermediate chain)
     /// Use 2 for CowboyAI hosting scenario (hosting intermediate + client intermediate)
     pub pathlen: u8,
+    /// Algorithm of the root key, used for its self-signature and every
+    /// certificate it issues (default: ECDSA P-256 / SHA-256)
+    pub signature_algorithm: CertificateSignatureAlgorithm,
 }
 
 /// Parameters for Intermediate CA generation
@@ -87,6 +94,9 @@ pub struct IntermediateCAParams {
     /// Default: 0 (can only sign leaf certificates, not other CAs)
     /// Use 1 for CowboyAI hosting intermediate (can sign one more intermediate level)
     pub pathlen: u8,
+    /// Algorithm of the intermediate key, used for the certificates it
+    /// issues. Must not be stronger than the root's algorithm.
+    pub signature_algorithm: CertificateSignatureAlgorithm,
 }
 
 /// Parameters for Server certificate generation
@@ -101,6 +111,9 @@ pub struct ServerCertParams {
     pub organizational_unit: Option<String>,
     /// Validity in days (default: 90)
     pub validity_days: u32,
+    /// Algorithm of the server key. Must not be stronger than the
+    /// intermediate CA's algorithm.
+    pub signature_algorithm: CertificateSignatureAlgorithm,
 }
 
 impl Default for RootCAParams {
@@ -113,6 +126,7 @@ impl Default for RootCAParams {
             locality: None,
             validity_years: 20,
             pathlen: 1, // Standard single-intermediate chain
+            signature_algorithm: CertificateSignatureAlgorithm::default(),
         }
     }
 }
@@ -126,6 +140,7 @@ impl Default for IntermediateCAParams {
             country: Some("US".to_string()),
             validity_years: 3,
             pathlen: 0, // Can only sign leaf certificates by default
+            signature_algorithm: CertificateSignatureAlgorithm::default(),
         }
     }
 }
@@ -138,8 +153,127 @@ impl Default for ServerCertParams {
             organization: "CIM Organization".to_string(),
             organizational_unit: None,
             validity_days: 90,
+            signature_algorithm: CertificateSignatureAlgorithm::default(),
+        }
+    }
+}
+
+/// rcgen algorithm for a certificate signature algorithm
+pub fn rcgen_signature_algorithm(algorithm: CertificateSignatureAlgorithm) -> &'static rcgen::SignatureAlgorithm {
+    match algorithm {
+        CertificateSignatureAlgorithm::EcdsaP256Sha256 => &rcgen::PKCS_ECDSA_P256_SHA256,
+        CertificateSignatureAlgorithm::EcdsaP384Sha384 => &rcgen::PKCS_ECDSA_P384_SHA384,
+        CertificateSignatureAlgorithm::Ed25519 => &rcgen::PKCS_ED25519,
+        CertificateSignatureAlgorithm::RsaPkcs1Sha256 => &rcgen::PKCS_RSA_SHA256,
+        CertificateSignatureAlgorithm::RsaPkcs1Sha384 => &rcgen::PKCS_RSA_SHA384,
+        CertificateSignatureAlgorithm::RsaPkcs1Sha512 => &rcgen::PKCS_RSA_SHA512,
+    }
+}
+
+/// Certificate signature algorithm of an rcgen algorithm, if we support it
+pub fn signature_algorithm_of(algorithm: &rcgen::SignatureAlgorithm) -> Option<CertificateSignatureAlgorithm> {
+    CertificateSignatureAlgorithm::all()
+        .iter()
+        .copied()
+        .find(|a| rcgen_signature_algorithm(*a) == algorithm)
+}
+
+/// Generate a fresh key pair for `algorithm`
+///
+/// RSA keys cannot be generated with rcgen's ring backend; RSA issuers
+/// must bring an existing key (e.g. a YubiKey slot via `ExternalCaSigner`).
+fn generate_key_pair(algorithm: CertificateSignatureAlgorithm) -> Result<RcgenKeyPair, String> {
+    if algorithm.is_rsa() {
+        return Err(format!(
+            "Cannot generate a {} key; RSA issuers must use an existing key",
+            algorithm
+        ));
+    }
+    RcgenKeyPair::generate_for(rcgen_signature_algorithm(algorithm))
+        .map_err(|e| format!("Failed to generate {} key pair: {}", algorithm, e))
+}
+
+/// Key pair for `algorithm` derived from `seed`
+///
+/// The same seed always gives the same key, so the certificate can be
+/// rebuilt from the master seed. Ed25519 keys use the seed as RFC 8410
+/// private key, P-256 keys as scalar. Other algorithms cannot be derived
+/// here and get a fresh key (see `generate_key_pair`).
+fn seeded_key_pair(seed: &MasterSeed, algorithm: CertificateSignatureAlgorithm) -> Result<RcgenKeyPair, String> {
+    use der::zeroize::Zeroizing;
+
+    let pkcs8 = match algorithm {
+        CertificateSignatureAlgorithm::Ed25519 => {
+            // OneAsymmetricKey v1 header for a 32-byte Ed25519 private key
+            const ED25519_PKCS8_PREFIX: [u8; 16] =
+                [0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x04, 0x22, 0x04, 0x20];
+            let mut der = Zeroizing::new(ED25519_PKCS8_PREFIX.to_vec());
+            der.extend_from_slice(seed.as_bytes());
+            der
+        }
+        CertificateSignatureAlgorithm::EcdsaP256Sha256 => {
+            use p256::pkcs8::EncodePrivateKey;
+            let secret = p256::SecretKey::from_slice(seed.as_bytes())
+                .map_err(|e| format!("Seed is not a valid P-256 scalar: {}", e))?;
+            let der = secret
+                .to_pkcs8_der()
+                .map_err(|e| format!("Failed to encode P-256 key: {}", e))?;
+            Zeroizing::new(der.as_bytes().to_vec())
+        }
+        _ => return generate_key_pair(algorithm),
+    };
+    let pkcs8 = rustls::pki_types::PrivatePkcs8KeyDer::from(pkcs8.as_slice());
+    RcgenKeyPair::from_pkcs8_der_and_sign_algo(&pkcs8, rcgen_signature_algorithm(algorithm))
+        .map_err(|e| format!("Failed to load {} key pair: {}", algorithm, e))
+}
+
+/// Check that an issuer signing with `issuer` may certify a `subject` key
+///
+/// Unrecognised issuer algorithms (external signers) are accepted as-is.
+fn check_issuer_algorithm(
+    issuer: &rcgen::SignatureAlgorithm,
+    subject: CertificateSignatureAlgorithm,
+) -> Result<Option<CertificateSignatureAlgorithm>, String> {
+    let issuer = signature_algorithm_of(issuer);
+    if let Some(issuer) = issuer {
+        if !issuer.can_sign(subject) {
+            return Err(format!(
+                "Issuer algorithm {} ({}-bit) is weaker than requested subject algorithm {} ({}-bit)",
+                issuer,
+                issuer.security_bits(),
+                subject,
+                subject.security_bits()
+            ));
         }
     }
+    Ok(issuer)
+}
+
+/// Name recorded in `CertificateSignedEvent::signature_algorithm`
+pub(crate) fn signature_algorithm_name(
+    algorithm: Option<CertificateSignatureAlgorithm>,
+    raw: &rcgen::SignatureAlgorithm,
+) -> String {
+    match algorithm {
+        Some(algorithm) => algorithm.to_string(),
+        None => format!("{:?}", raw),
+    }
+}
+
+/// Validity recorded in `CertificateGeneratedEvent` for a certificate's
+/// `not_before`/`not_after`
+///
+/// Fails rather than panics when a bound is outside chrono's range.
+pub(crate) fn certificate_validity(
+    not_before: OffsetDateTime,
+    not_after: OffsetDateTime,
+) -> Result<CertificateValidity, String> {
+    let to_chrono = |t: OffsetDateTime| {
+        chrono::DateTime::from_timestamp(t.unix_timestamp(), 0)
+            .ok_or_else(|| format!("Validity bound {} is out of range", t))
+    };
+    CertificateValidity::new(to_chrono(not_before)?, to_chrono(not_after)?)
+        .map_err(|e| format!("Invalid validity period: {}", e))
 }
 
 /// Generate a Root CA certificate from a seed
@@ -208,8 +342,7 @@ pub fn generate_root_ca(
     cert_params.not_after = not_after;
 
     // Generate rcgen's own keypair
-    let key_pair = RcgenKeyPair::generate()
-        .map_err(|e| format!("Failed to generate key pair: {}", e))?;
+    let key_pair = generate_key_pair(params.signature_algorithm)?;
 
     // Create self-signed certificate
     let cert = cert_params.self_signed(&key_pair)
@@ -240,10 +373,7 @@ pub fn generate_root_ca(
         .with_organization(OrganizationName::new_unchecked(&organization));
 
     // Build validity value object
-    let not_before_chrono = chrono::DateTime::from_timestamp(not_before.unix_timestamp(), 0).unwrap();
-    let not_after_chrono = chrono::DateTime::from_timestamp(not_after.unix_timestamp(), 0).unwrap();
-    let validity = CertificateValidity::new(not_before_chrono, not_after_chrono)
-        .map_err(|e| format!("Invalid validity period: {}", e))?;
+    let validity = certificate_validity(not_before, not_after)?;
 
     let event = crate::events::CertificateGeneratedEvent {
         cert_id,
@@ -255,6 +385,7 @@ pub fn generate_root_ca(
         validity,
         basic_constraints: BasicConstraintsVO::root_ca(),
         issuer: None, // Self-signed root CA
+        signature_algorithm: Some(params.signature_algorithm),
         correlation_id,
         causation_id,
     };
@@ -262,14 +393,267 @@ pub fn generate_root_ca(
     Ok((x509_cert, event))
 }
 
-/// Generate an Intermediate CA certificate (SIGNING ONLY)
+/// Intermediate CA issued by a root, together with its chain
+#[derive(Clone, Debug)]
+pub struct IntermediateCA {
+    /// The intermediate certificate and its keypair
+    pub certificate: X509Certificate,
+    /// Intermediate certificate followed by the root certificate (PEM)
+    pub chain_pem: String,
+    /// US-021: Certificate generation event
+    pub generation_event: crate::events::CertificateGeneratedEvent,
+    /// US-021: Certificate signing event (signed by the root)
+    pub signing_event: crate::events::CertificateSignedEvent,
+}
+
+/// Root CA key held outside this process (e.g. in a YubiKey PIV slot)
+///
+/// Wraps a signing function so rcgen can use a key whose private half never
+/// leaves the device. `public_key_der` is the raw subjectPublicKey (for
+/// ECDSA, the uncompressed point) and `sign` must return a signature in the
+/// encoding `algorithm` expects (DER for ECDSA).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let signer = ExternalCaSigner::new(slot_public_key, &rcgen::PKCS_ECDSA_P256_SHA256, |tbs| {
+///     let digest = sha2::Sha256::digest(tbs);
+///     runtime.block_on(yubikey.sign_with_slot(&serial, PivSlot::Signature, &digest, &pin))
+///         .map(|sig| sig.data)
+///         .map_err(|e| e.to_string())
+/// });
+/// ```
+pub struct ExternalCaSigner<F>
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>, String>,
+{
+    public_key_der: Vec<u8>,
+    algorithm: &'static rcgen::SignatureAlgorithm,
+    sign: F,
+}
+
+impl<F> ExternalCaSigner<F>
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>, String>,
+{
+    pub fn new(public_key_der: Vec<u8>, algorithm: &'static rcgen::SignatureAlgorithm, sign: F) -> Self {
+        Self { public_key_der, algorithm, sign }
+    }
+}
+
+impl<F> rcgen::PublicKeyData for ExternalCaSigner<F>
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>, String>,
+{
+    fn der_bytes(&self) -> &[u8] {
+        &self.public_key_der
+    }
+
+    fn algorithm(&self) -> &'static rcgen::SignatureAlgorithm {
+        self.algorithm
+    }
+}
+
+impl<F> rcgen::SigningKey for ExternalCaSigner<F>
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>, String>,
+{
+    fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, rcgen::Error> {
+        (self.sign)(msg).map_err(|_| rcgen::Error::RemoteKeyError)
+    }
+}
+
+/// Certificate signing request for a key held outside this process
+///
+/// rcgen signs synchronously, but a YubiKey slot signs over an async port.
+/// The request is therefore built twice: [`ExternalKeyCsr::prepare`]
+/// captures the CertificationRequestInfo to be signed, the caller has the
+/// device sign it, and [`ExternalKeyCsr::finish`] rebuilds the identical
+/// request around that signature. `public_key_der` and `algorithm` follow
+/// [`ExternalCaSigner`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let csr = ExternalKeyCsr::prepare(&subject, slot_public_key, &rcgen::PKCS_ECDSA_P256_SHA256)?;
+/// let digest = sha2::Sha256::digest(csr.to_be_signed());
+/// let signature = yubikey.sign_with_slot(&serial, slot, &digest, &pin).await?;
+/// let csr_pem = csr.finish(signature.data)?;
+/// ```
+pub struct ExternalKeyCsr {
+    params: CertificateParams,
+    public_key_der: Vec<u8>,
+    algorithm: &'static rcgen::SignatureAlgorithm,
+    to_be_signed: Vec<u8>,
+}
+
+impl ExternalKeyCsr {
+    /// Build the request and capture the bytes the key must sign
+    ///
+    /// The subject's email, if any, becomes an RFC 822 SAN.
+    pub fn prepare(
+        subject: &crate::ports::x509::CertificateSubject,
+        public_key_der: Vec<u8>,
+        algorithm: &'static rcgen::SignatureAlgorithm,
+    ) -> Result<Self, String> {
+        let mut params = CertificateParams::default();
+        params.distinguished_name = DistinguishedName::new();
+        params.distinguished_name.push(DnType::CommonName, subject.common_name.as_str());
+        let optional = [
+            (DnType::OrganizationName, &subject.organization),
+            (DnType::OrganizationalUnitName, &subject.organizational_unit),
+            (DnType::CountryName, &subject.country),
+            (DnType::StateOrProvinceName, &subject.state),
+            (DnType::LocalityName, &subject.locality),
+        ];
+        for (ty, value) in optional {
+            if let Some(value) = value {
+                params.distinguished_name.push(ty, value.as_str());
+            }
+        }
+        if let Some(email) = &subject.email {
+            let email = email
+                .as_str()
+                .try_into()
+                .map_err(|e| format!("Invalid email address {}: {}", email, e))?;
+            params.subject_alt_names.push(SanType::Rfc822Name(email));
+        }
+
+        let captured = std::cell::RefCell::new(Vec::new());
+        let capture = ExternalCaSigner::new(public_key_der.clone(), algorithm, |tbs| {
+            *captured.borrow_mut() = tbs.to_vec();
+            Ok(Vec::new())
+        });
+        params
+            .serialize_request(&capture)
+            .map_err(|e| format!("Failed to build certificate request: {}", e))?;
+
+        Ok(Self {
+            params,
+            public_key_der,
+            algorithm,
+            to_be_signed: captured.into_inner(),
+        })
+    }
+
+    /// DER CertificationRequestInfo the external key must sign
+    pub fn to_be_signed(&self) -> &[u8] {
+        &self.to_be_signed
+    }
+
+    /// Complete the request with the external key's signature (PEM)
+    ///
+    /// The signature is not checked here; `issue_certificate` verifies it
+    /// when it parses the request.
+    pub fn finish(self, signature: Vec<u8>) -> Result<String, String> {
+        let Self { params, public_key_der, algorithm, to_be_signed } = self;
+        let signer = ExternalCaSigner::new(public_key_der, algorithm, |tbs| {
+            if tbs == to_be_signed.as_slice() {
+                Ok(signature.clone())
+            } else {
+                Err("Certificate request changed between passes".to_string())
+            }
+        });
+        params
+            .serialize_request(&signer)
+            .and_then(|csr| csr.pem())
+            .map_err(|e| format!("Failed to build certificate request: {}", e))
+    }
+}
+
+/// Serial number for a certificate issued with the given ID
+///
+/// Issued certificates carry their `cert_id` as serial so a CRL can be
+/// built from `CertificateRevoked` events alone.
+pub(crate) fn certificate_serial(cert_id: uuid::Uuid) -> rcgen::SerialNumber {
+    rcgen::SerialNumber::from_slice(&cert_id.as_u128().to_be_bytes())
+}
+
+/// Inverse of [`certificate_serial`] for DER serial bytes
+///
+/// Tolerates the leading zero DER adds to keep the integer positive.
+pub(crate) fn certificate_id_from_serial(serial: &[u8]) -> Option<uuid::Uuid> {
+    let start = serial.iter().position(|b| *b != 0).unwrap_or(serial.len());
+    let significant = &serial[start..];
+    if significant.len() > 16 {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    bytes[16 - significant.len()..].copy_from_slice(significant);
+    Some(uuid::Uuid::from_u128(u128::from_be_bytes(bytes)))
+}
+
+/// Parse an issuer certificate and return its pathlen constraint
+///
+/// Fails unless the certificate is a CA. `Ok(None)` means unconstrained.
+pub(crate) fn issuer_path_len(issuer_cert_pem: &str) -> Result<Option<u32>, String> {
+    let (_, pem) = x509_parser::pem::parse_x509_pem(issuer_cert_pem.as_bytes())
+        .map_err(|e| format!("Failed to parse issuer PEM: {}", e))?;
+    let issuer = pem
+        .parse_x509()
+        .map_err(|e| format!("Failed to parse issuer certificate: {}", e))?;
+
+    let basic_constraints = issuer
+        .basic_constraints()
+        .map_err(|e| format!("Invalid issuer basic constraints: {}", e))?
+        .map(|ext| ext.value.clone());
+    match basic_constraints {
+        Some(bc) if bc.ca => Ok(bc.path_len_constraint),
+        _ => Err("Issuer certificate is not a CA".to_string()),
+    }
+}
+
+/// Check the requested intermediate pathlen against the root and policy
+///
+/// The root's own pathlen must leave room for one more CA level, and every
+/// `PolicyConstraint::MaxPathLength` caps the intermediate's pathlen.
+fn check_intermediate_path_length(
+    root_ca_cert_pem: &str,
+    pathlen: u8,
+    constraints: &[PolicyConstraint],
+) -> Result<(), String> {
+    if let Some(root_pathlen) = issuer_path_len(root_ca_cert_pem)? {
+        if u32::from(pathlen) >= root_pathlen {
+            return Err(format!(
+                "Root CA pathlen {} does not allow an intermediate CA with pathlen {}",
+                root_pathlen, pathlen
+            ));
+        }
+    }
+
+    for constraint in constraints {
+        if let PolicyConstraint::MaxPathLength(max) = constraint {
+            if u32::from(pathlen) > *max {
+                return Err(format!(
+                    "Intermediate CA pathlen {} exceeds policy MaxPathLength({})",
+                    pathlen, max
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Create an Intermediate CA certificate chained to the root (SIGNING ONLY)
+///
+/// The root key is any `rcgen::SigningKey`: an in-memory `rcgen::KeyPair`
+/// or an [`ExternalCaSigner`] backed by a YubiKey. The issuer name and
+/// authority key identifier are taken from `root_ca_cert_pem`, so the
+/// returned chain verifies against the real root.
+///
+/// The intermediate key is derived from `seed` for Ed25519 and P-256, so
+/// the same seed rebuilds the same key; P-384 keys are generated fresh.
 ///
 /// **CRITICAL**: This intermediate CA is for SIGNING ONLY.
-/// - pathlen: 0 (cannot sign other CAs)
+/// - pathlen: 0 by default (cannot sign other CAs)
 /// - keyUsage: keyCertSign, cRLSign ONLY (NO digitalSignature!)
 /// - Does NOT serve as a server identity
 /// - Can be rotated without affecting root trust
 ///
+/// Fails if the root's pathlen leaves no room for another CA level or if
+/// `params.pathlen` exceeds any `PolicyConstraint::MaxPathLength`.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -280,23 +664,30 @@ pub fn generate_root_ca(
 ///     common_name: "CowboyAI Engineering Intermediate CA".to_string(),
 ///     ..Default::default()
 /// };
-/// let intermediate_ca = generate_intermediate_ca(
+/// let intermediate = create_intermediate_ca(
 ///     &intermediate_seed,
 ///     params,
-///     &root_ca_cert,
+///     &root_ca.certificate_pem,
+///     &yubikey_signer,
+///     &[PolicyConstraint::MaxPathLength(0)],
+///     root_ca_id,
+///     correlation_id,
+///     None,
 /// )?;
+/// pki.register_intermediate_ca(unit.id.as_uuid(), intermediate.generation_event.cert_id);
 /// ```
-pub fn generate_intermediate_ca(
+#[allow(clippy::too_many_arguments)]
+pub fn create_intermediate_ca<S: rcgen::SigningKey>(
     seed: &MasterSeed,
     params: IntermediateCAParams,
     root_ca_cert_pem: &str,
-    root_ca_key_pem: &str,
+    root_signer: S,
+    constraints: &[PolicyConstraint],
     root_ca_id: uuid::Uuid,
     correlation_id: uuid::Uuid,
     causation_id: Option<uuid::Uuid>,
-) -> Result<(X509Certificate, crate::events::CertificateGeneratedEvent, crate::events::CertificateSignedEvent), String> {
-    // Generate our deterministic Ed25519 keypair from seed (for reference/storage)
-    let ed25519_keypair = KeyPair::from_seed(seed);
+) -> Result<IntermediateCA, String> {
+    check_intermediate_path_length(root_ca_cert_pem, params.pathlen, constraints)?;
 
     // Build distinguished name
     let mut dn = DistinguishedName::new();
@@ -331,28 +722,17 @@ pub fn generate_intermediate_ca(
     cert_params.not_before = not_before;
     cert_params.not_after = not_after;
 
-    // Generate rcgen's own keypair
-    let key_pair = RcgenKeyPair::generate()
-        .map_err(|e| format!("Failed to generate key pair: {}", e))?;
-
-    // Parse root CA key pair for signing
-    let root_key_pair = RcgenKeyPair::from_pem(root_ca_key_pem)
-        .map_err(|e| format!("Failed to parse root CA key: {}", e))?;
-
-    // Parse root CA certificate to get parameters
-    // Note: We're using x509-parser to extract info from the existing cert
-    // This is used for validation purposes to ensure the root CA is properly formatted
-    let _root_pem_data = pem::parse(root_ca_cert_pem)
-        .map_err(|e| format!("Failed to parse root CA PEM: {}", e))?;
+    let cert_id = uuid::Uuid::now_v7();
+    cert_params.serial_number = Some(certificate_serial(cert_id));
 
-    // Create a minimal CertificateParams for the issuer
-    // In rcgen 0.14, we just need enough to identify the issuer
-    let root_params = CertificateParams::new(vec![])
-        .map_err(|e| format!("Failed to create params for issuer: {}", e))?;
+    // The intermediate key is derived from the seed, so it can be rebuilt
+    let key_pair = seeded_key_pair(seed, params.signature_algorithm)?;
 
-    // The signing will use the actual certificate data from the PEM
-    // Create issuer with these params
-    let issuer = Issuer::new(root_params, root_key_pair);
+    // Issuer DN and key identifier come from the real root certificate
+    let issuer_algorithm = check_issuer_algorithm(root_signer.algorithm(), params.signature_algorithm)?;
+    let signature_algorithm = signature_algorithm_name(issuer_algorithm, root_signer.algorithm());
+    let issuer = Issuer::from_ca_cert_pem(root_ca_cert_pem, root_signer)
+        .map_err(|e| format!("Failed to load root CA as issuer: {}", e))?;
 
     // Sign the intermediate certificate with root CA
     let cert = cert_params.signed_by(&key_pair, &issuer)
@@ -361,6 +741,7 @@ pub fn generate_intermediate_ca(
     // Get PEM representations
     let certificate_pem = cert.pem();
     let private_key_pem = key_pair.serialize_pem();
+    let chain_pem = format!("{}{}", certificate_pem, root_ca_cert_pem);
 
     // Calculate fingerprint
     let cert_der = cert.der();
@@ -369,13 +750,12 @@ pub fn generate_intermediate_ca(
     let x509_cert = X509Certificate {
         certificate_pem,
         private_key_pem,
-        public_key_bytes: ed25519_keypair.public_key_bytes().to_vec(),
+        public_key_bytes: key_pair.public_key_raw().to_vec(),
         fingerprint,
         seed_path: format!("intermediate-{}", params.organizational_unit),
     };
 
     // US-021: Emit certificate generation and signing events for audit trail
-    let cert_id = uuid::Uuid::now_v7();
     let key_id = uuid::Uuid::now_v7();
     let signed_at = chrono::Utc::now();
 
@@ -385,10 +765,7 @@ pub fn generate_intermediate_ca(
         .with_organizational_unit(OrganizationalUnitName::new_unchecked(&params.organizational_unit));
 
     // Build validity value object
-    let not_before_chrono = chrono::DateTime::from_timestamp(not_before.unix_timestamp(), 0).unwrap();
-    let not_after_chrono = chrono::DateTime::from_timestamp(not_after.unix_timestamp(), 0).unwrap();
-    let validity = CertificateValidity::new(not_before_chrono, not_after_chrono)
-        .map_err(|e| format!("Invalid validity period: {}", e))?;
+    let validity = certificate_validity(not_before, not_after)?;
 
     let generation_event = crate::events::CertificateGeneratedEvent {
         cert_id,
@@ -400,6 +777,7 @@ pub fn generate_intermediate_ca(
         validity,
         basic_constraints: BasicConstraintsVO::ca_with_path_len(params.pathlen as u32),
         issuer: Some(root_ca_id),
+        signature_algorithm: issuer_algorithm,
         correlation_id,
         causation_id,
     };
@@ -407,13 +785,368 @@ pub fn generate_intermediate_ca(
     let signing_event = crate::events::CertificateSignedEvent {
         cert_id,
         signed_by: root_ca_id,
-        signature_algorithm: "Ed25519".to_string(),
+        signature_algorithm,
         signed_at,
         correlation_id,
         causation_id: Some(cert_id), // Signing caused by certificate generation
     };
 
-    Ok((x509_cert, generation_event, signing_event))
+    Ok(IntermediateCA {
+        certificate: x509_cert,
+        chain_pem,
+        generation_event,
+        signing_event,
+    })
+}
+
+/// Generate an Intermediate CA certificate signed by a root key in PEM form
+///
+/// Convenience wrapper around [`create_intermediate_ca`] for roots whose
+/// private key is available in memory. No policy constraints are applied
+/// beyond the root's own pathlen.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let intermediate_seed = root_ca_seed.derive_child("intermediate-engineering");
+/// let intermediate_ca = generate_intermediate_ca(
+///     &intermediate_seed,
+///     params,
+///     &root_ca.certificate_pem,
+///     &root_ca.private_key_pem,
+///     root_ca_id,
+///     correlation_id,
+///     None,
+/// )?;
+/// ```
+pub fn generate_intermediate_ca(
+    seed: &MasterSeed,
+    params: IntermediateCAParams,
+    root_ca_cert_pem: &str,
+    root_ca_key_pem: &str,
+    root_ca_id: uuid::Uuid,
+    correlation_id: uuid::Uuid,
+    causation_id: Option<uuid::Uuid>,
+) -> Result<(X509Certificate, crate::events::CertificateGeneratedEvent, crate::events::CertificateSignedEvent), String> {
+    // Parse root CA key pair for signing
+    let root_key_pair = RcgenKeyPair::from_pem(root_ca_key_pem)
+        .map_err(|e| format!("Failed to parse root CA key: {}", e))?;
+
+    let intermediate = create_intermediate_ca(
+        seed,
+        params,
+        root_ca_cert_pem,
+        root_key_pair,
+        &[],
+        root_ca_id,
+        correlation_id,
+        causation_id,
+    )?;
+
+    Ok((intermediate.certificate, intermediate.generation_event, intermediate.signing_event))
+}
+
+/// Parameters for issuing a leaf certificate from a CSR
+pub struct LeafCertificateParams {
+    /// Intended use; selects key usage and extended key usage
+    pub purpose: KeyPurpose,
+    /// Requested validity in days (capped by `ValidityPeriodMax`)
+    pub validity_days: u32,
+    /// Key the CSR was created for
+    pub key_id: uuid::Uuid,
+}
+
+/// Leaf certificate signed from a CSR, together with its chain
+#[derive(Clone, Debug)]
+pub struct IssuedCertificate {
+    /// The signed certificate in PEM format
+    pub certificate_pem: String,
+    /// Leaf certificate followed by the issuer chain (PEM)
+    pub chain_pem: String,
+    /// Certificate fingerprint (SHA-256)
+    pub fingerprint: String,
+    /// Subject Alternative Names copied from the CSR
+    pub san: Vec<String>,
+    /// US-021: Certificate generation event
+    pub generation_event: crate::events::CertificateGeneratedEvent,
+    /// US-021: Certificate signing event (signed by the issuer CA)
+    pub signing_event: crate::events::CertificateSignedEvent,
+}
+
+/// Key usage and EKUs a leaf certificate gets for its purpose
+fn leaf_profile(
+    purpose: KeyPurpose,
+) -> Result<(KeyUsage, ExtendedKeyUsage, Vec<KeyUsagePurpose>, Vec<ExtendedKeyUsagePurpose>), String> {
+    match purpose {
+        KeyPurpose::Authentication => Ok((
+            KeyUsage::tls_server(),
+            ExtendedKeyUsage::tls_server_client(),
+            vec![KeyUsagePurpose::DigitalSignature, KeyUsagePurpose::KeyEncipherment],
+            vec![ExtendedKeyUsagePurpose::ServerAuth, ExtendedKeyUsagePurpose::ClientAuth],
+        )),
+        KeyPurpose::Signing => Ok((
+            KeyUsage::code_signing(),
+            ExtendedKeyUsage::code_signing(),
+            vec![KeyUsagePurpose::DigitalSignature],
+            vec![ExtendedKeyUsagePurpose::CodeSigning],
+        )),
+        KeyPurpose::Encryption => Ok((
+            KeyUsage::email_protection(),
+            ExtendedKeyUsage::email_protection(),
+            vec![
+                KeyUsagePurpose::DigitalSignature,
+                KeyUsagePurpose::ContentCommitment,
+                KeyUsagePurpose::KeyEncipherment,
+            ],
+            vec![ExtendedKeyUsagePurpose::EmailProtection],
+        )),
+        other => Err(format!("No leaf certificate profile for purpose {:?}", other)),
+    }
+}
+
+/// Whether `name` equals a constraint domain or is a subdomain of it
+fn name_within(name: &str, constraint: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    let constraint = constraint.trim_start_matches('.').to_ascii_lowercase();
+    name == constraint || name.ends_with(&format!(".{}", constraint))
+}
+
+fn csr_common_name(params: &CertificateParams) -> Result<String, String> {
+    match params.distinguished_name.get(&DnType::CommonName) {
+        Some(DnValue::Utf8String(cn)) => Ok(cn.clone()),
+        Some(DnValue::PrintableString(cn)) => Ok(cn.as_str().to_string()),
+        Some(DnValue::Ia5String(cn)) => Ok(cn.as_str().to_string()),
+        _ => Err("CSR subject must contain a common name".to_string()),
+    }
+}
+
+/// Parse a CSR and return its subject common name and the names subject to
+/// name constraints (see `constrained_names`)
+pub(crate) fn csr_constrained_names(csr_pem: &str) -> Result<(String, Vec<String>), String> {
+    let csr = CertificateSigningRequestParams::from_pem(csr_pem)
+        .map_err(|e| format!("Invalid certificate signing request: {}", e))?;
+    let common_name = csr_common_name(&csr.params)?;
+    let names = constrained_names(&common_name, &csr.params.subject_alt_names);
+    Ok((common_name, names))
+}
+
+/// Names in a CSR subject to `PolicyConstraint::NameConstraints`
+///
+/// DNS names and the domain part of email addresses are checked; IP and URI
+/// entries are not constrained. The subject common name is included when it
+/// is a host name or email address, since relying parties still match it.
+pub(crate) fn constrained_names(common_name: &str, san: &[SanType]) -> Vec<String> {
+    let mut names: Vec<String> = san
+        .iter()
+        .filter_map(|entry| match entry {
+            SanType::DnsName(dns) => Some(dns.as_str().to_string()),
+            SanType::Rfc822Name(email) => email.as_str().rsplit('@').next().map(str::to_string),
+            _ => None,
+        })
+        .collect();
+
+    let cn_domain = common_name.rsplit('@').next().unwrap_or(common_name);
+    let looks_like_host = cn_domain.contains('.')
+        && cn_domain.parse::<std::net::IpAddr>().is_err()
+        && cn_domain.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '*');
+    if looks_like_host && !names.iter().any(|n| n.eq_ignore_ascii_case(cn_domain)) {
+        names.insert(0, cn_domain.to_string());
+    }
+
+    names
+}
+
+/// The subset of `names` that `NameConstraints` exclude or don't permit,
+/// each paired with the reason
+pub(crate) fn name_constraint_violations(names: &[String], constraints: &[PolicyConstraint]) -> Vec<(String, String)> {
+    let mut violations = Vec::new();
+    for constraint in constraints {
+        if let PolicyConstraint::NameConstraints { permitted, excluded } = constraint {
+            for name in names {
+                if violations.iter().any(|(n, _)| n == name) {
+                    continue;
+                }
+                if let Some(c) = excluded.iter().find(|c| name_within(name, c)) {
+                    violations.push((name.clone(), format!("{} is excluded by policy ({})", name, c)));
+                } else if !permitted.is_empty() && !permitted.iter().any(|c| name_within(name, c)) {
+                    violations.push((
+                        name.clone(),
+                        format!("{} is not permitted by policy (permitted: {})", name, permitted.join(", ")),
+                    ));
+                }
+            }
+        }
+    }
+    violations
+}
+
+/// Issue a leaf certificate for a CSR, signed by an intermediate CA
+///
+/// The CSR supplies the subject, public key and SANs. Everything else is
+/// policy: key usage and EKUs follow `params.purpose` (the CSR's own
+/// requests are ignored), validity is capped by `ValidityPeriodMax`, EKUs
+/// must be allowed by `KeyUsageRestriction`, and SANs must satisfy
+/// `NameConstraints`. The result is never a CA.
+///
+/// `issuer_chain_pem` starts with the issuing CA certificate and may be
+/// followed by the rest of its chain; it is appended to the leaf to form
+/// `chain_pem`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let params = LeafCertificateParams {
+///     purpose: KeyPurpose::Authentication,
+///     validity_days: 365,
+///     key_id,
+/// };
+/// let issued = issue_certificate(
+///     &csr_pem,
+///     params,
+///     &intermediate.chain_pem,
+///     &intermediate_key,
+///     &[PolicyConstraint::ValidityPeriodMax { days: 90 }],
+///     intermediate_ca_id,
+///     correlation_id,
+///     None,
+/// )?;
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn issue_certificate<S: rcgen::SigningKey>(
+    csr_pem: &str,
+    params: LeafCertificateParams,
+    issuer_chain_pem: &str,
+    issuer_signer: S,
+    constraints: &[PolicyConstraint],
+    issuer_ca_id: uuid::Uuid,
+    correlation_id: uuid::Uuid,
+    causation_id: Option<uuid::Uuid>,
+) -> Result<IssuedCertificate, String> {
+    issuer_path_len(issuer_chain_pem)?;
+
+    // Parsing also verifies the CSR's self-signature
+    let mut csr = CertificateSigningRequestParams::from_pem(csr_pem)
+        .map_err(|e| format!("Invalid certificate signing request: {}", e))?;
+
+    let common_name = csr_common_name(&csr.params)?;
+    let dn_string = |ty: DnType| match csr.params.distinguished_name.get(&ty) {
+        Some(DnValue::Utf8String(v)) => Some(v.clone()),
+        Some(DnValue::PrintableString(v)) => Some(v.as_str().to_string()),
+        _ => None,
+    };
+    let organization = dn_string(DnType::OrganizationName);
+    let organizational_unit = dn_string(DnType::OrganizationalUnitName);
+
+    let names = constrained_names(&common_name, &csr.params.subject_alt_names);
+    if let Some((_, reason)) = name_constraint_violations(&names, constraints).into_iter().next() {
+        return Err(reason);
+    }
+
+    let (key_usage, extended_key_usage, key_usages, extended_key_usages) = leaf_profile(params.purpose)?;
+    for constraint in constraints {
+        if let PolicyConstraint::KeyUsageRestriction(allowed) = constraint {
+            for purpose in extended_key_usage.purposes() {
+                if !allowed.iter().any(|a| a.eq_ignore_ascii_case(purpose.name())) {
+                    return Err(format!("Extended key usage {} is not allowed by policy", purpose.name()));
+                }
+            }
+        }
+    }
+
+    let validity_days = constraints
+        .iter()
+        .filter_map(|c| match c {
+            PolicyConstraint::ValidityPeriodMax { days } => Some(*days),
+            _ => None,
+        })
+        .fold(params.validity_days, u32::min);
+    if validity_days == 0 {
+        return Err("Leaf certificate validity must be at least one day".to_string());
+    }
+
+    let cert_id = uuid::Uuid::now_v7();
+    let not_before = OffsetDateTime::now_utc();
+    let not_after = not_before + Duration::days(validity_days as i64);
+
+    csr.params.is_ca = IsCa::NoCa;
+    csr.params.key_usages = key_usages;
+    csr.params.extended_key_usages = extended_key_usages;
+    csr.params.not_before = not_before;
+    csr.params.not_after = not_after;
+    csr.params.serial_number = Some(certificate_serial(cert_id));
+    csr.params.use_authority_key_identifier_extension = true;
+
+    // CSR keys we don't recognise (e.g. P-521) are refused rather than
+    // signed by a possibly weaker issuer
+    let subject_algorithm = signature_algorithm_of(csr.public_key.algorithm())
+        .ok_or_else(|| format!("Unsupported CSR key algorithm {:?}", csr.public_key.algorithm()))?;
+    let issuer_algorithm = check_issuer_algorithm(issuer_signer.algorithm(), subject_algorithm)?;
+    let signature_algorithm = signature_algorithm_name(issuer_algorithm, issuer_signer.algorithm());
+    let issuer = Issuer::from_ca_cert_pem(issuer_chain_pem, issuer_signer)
+        .map_err(|e| format!("Failed to load issuer CA: {}", e))?;
+    let cert = csr.signed_by(&issuer)
+        .map_err(|e| format!("Failed to sign certificate: {}", e))?;
+
+    let certificate_pem = cert.pem();
+    let chain_pem = format!("{}{}", certificate_pem, issuer_chain_pem);
+    let fingerprint = calculate_fingerprint(cert.der());
+
+    // US-021: Emit certificate generation and signing events for audit trail
+    let mut subject_name = SubjectName::new(CommonName::new_unchecked(&common_name));
+    if let Some(org) = &organization {
+        subject_name = subject_name.with_organization(OrganizationName::new_unchecked(org));
+    }
+    if let Some(ou) = &organizational_unit {
+        subject_name = subject_name.with_organizational_unit(OrganizationalUnitName::new_unchecked(ou));
+    }
+
+    let mut subject_alt_name = SubjectAlternativeName::new();
+    for entry in &csr.params.subject_alt_names {
+        let updated = match entry {
+            SanType::DnsName(dns) => subject_alt_name.clone().with_dns_name(dns.as_str()),
+            SanType::Rfc822Name(email) => subject_alt_name.clone().with_email(email.as_str()),
+            SanType::URI(uri) => subject_alt_name.clone().with_uri(uri.as_str()),
+            SanType::IpAddress(ip) => subject_alt_name.clone().with_ip_address(&ip.to_string()),
+            _ => continue,
+        };
+        subject_alt_name = updated.map_err(|e| format!("Invalid subject alternative name: {}", e))?;
+    }
+    let san = subject_alt_name.to_string_list();
+
+    let validity = certificate_validity(not_before, not_after)?;
+
+    let generation_event = crate::events::CertificateGeneratedEvent {
+        cert_id,
+        key_id: params.key_id,
+        subject_name,
+        subject_alt_name: if subject_alt_name.is_empty() { None } else { Some(subject_alt_name) },
+        key_usage,
+        extended_key_usage: Some(extended_key_usage),
+        validity,
+        basic_constraints: BasicConstraintsVO::end_entity(),
+        issuer: Some(issuer_ca_id),
+        signature_algorithm: issuer_algorithm,
+        correlation_id,
+        causation_id,
+    };
+
+    let signing_event = crate::events::CertificateSignedEvent {
+        cert_id,
+        signed_by: issuer_ca_id,
+        signature_algorithm,
+        signed_at: chrono::Utc::now(),
+        correlation_id,
+        causation_id: Some(cert_id), // Signing caused by certificate generation
+    };
+
+    Ok(IssuedCertificate {
+        certificate_pem,
+        chain_pem,
+        fingerprint,
+        san,
+        generation_event,
+        signing_event,
+    })
 }
 
 /// Generate a Server/Leaf certificate
@@ -495,12 +1228,13 @@ pub fn generate_server_certificate(
     cert_params.not_after = not_after;
 
     // Generate rcgen's own keypair
-    let key_pair = RcgenKeyPair::generate()
-        .map_err(|e| format!("Failed to generate key pair: {}", e))?;
+    let key_pair = generate_key_pair(params.signature_algorithm)?;
 
     // Parse intermediate CA key pair for signing
     let intermediate_key_pair = RcgenKeyPair::from_pem(intermediate_ca_key_pem)
         .map_err(|e| format!("Failed to parse intermediate CA key: {}", e))?;
+    let issuer_algorithm = check_issuer_algorithm(intermediate_key_pair.algorithm(), params.signature_algorithm)?;
+    let signature_algorithm = signature_algorithm_name(issuer_algorithm, intermediate_key_pair.algorithm());
 
     // Parse intermediate CA certificate to get parameters
     // This is used for validation purposes to ensure the intermediate CA is properly formatted
@@ -561,10 +1295,7 @@ pub fn generate_server_certificate(
     };
 
     // Build validity value object
-    let not_before_chrono = chrono::DateTime::from_timestamp(not_before.unix_timestamp(), 0).unwrap();
-    let not_after_chrono = chrono::DateTime::from_timestamp(not_after.unix_timestamp(), 0).unwrap();
-    let validity = CertificateValidity::new(not_before_chrono, not_after_chrono)
-        .map_err(|e| format!("Invalid validity period: {}", e))?;
+    let validity = certificate_validity(not_before, not_after)?;
 
     let generation_event = crate::events::CertificateGeneratedEvent {
         cert_id,
@@ -576,6 +1307,7 @@ pub fn generate_server_certificate(
         validity,
         basic_constraints: BasicConstraintsVO::end_entity(),
         issuer: Some(intermediate_ca_id),
+        signature_algorithm: issuer_algorithm,
         correlation_id,
         causation_id,
     };
@@ -583,7 +1315,7 @@ pub fn generate_server_certificate(
     let signing_event = crate::events::CertificateSignedEvent {
         cert_id,
         signed_by: intermediate_ca_id,
-        signature_algorithm: "Ed25519".to_string(),
+        signature_algorithm,
         signed_at,
         correlation_id,
         causation_id: Some(cert_id), // Signing caused by certificate generation
@@ -593,7 +1325,7 @@ pub fn generate_server_certificate(
 }
 
 /// Calculate SHA-256 fingerprint of DER-encoded certificate
-fn calculate_fingerprint(cert_der: &[u8]) -> String {
+pub(crate) fn calculate_fingerprint(cert_der: &[u8]) -> String {
     use sha2::{Sha256, Digest};
     let mut hasher = Sha256::new();
     hasher.update(cert_der);
@@ -676,6 +1408,263 @@ mod tests {
         assert!(!intermediate_ca.fingerprint.is_empty());
     }
 
+    #[test]
+    fn test_seeded_key_pair_is_deterministic() {
+        let seed = derive_master_seed("test passphrase", "test-org").unwrap().derive_child("intermediate-test");
+        for algorithm in [CertificateSignatureAlgorithm::Ed25519, CertificateSignatureAlgorithm::EcdsaP256Sha256] {
+            let first = seeded_key_pair(&seed, algorithm).unwrap();
+            let second = seeded_key_pair(&seed, algorithm).unwrap();
+            assert_eq!(first.public_key_raw(), second.public_key_raw());
+        }
+
+        // The Ed25519 key is the one ed25519-dalek derives from the seed
+        let ed25519 = seeded_key_pair(&seed, CertificateSignatureAlgorithm::Ed25519).unwrap();
+        assert_eq!(ed25519.public_key_raw(), KeyPair::from_seed(&seed).public_key_bytes());
+    }
+
+    #[test]
+    fn test_create_intermediate_ca_chains_to_root_with_external_signer() {
+        use rcgen::PublicKeyData;
+        use x509_parser::prelude::*;
+
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_ca_seed = master_seed.derive_child("root-ca");
+        let (root_ca, _root_event) = generate_root_ca(&root_ca_seed, RootCAParams::default(), uuid::Uuid::now_v7(), None).unwrap();
+
+        // Stand-in for a YubiKey: only the signing closure sees the key
+        let root_key = RcgenKeyPair::from_pem(&root_ca.private_key_pem).unwrap();
+        let signer = ExternalCaSigner::new(
+            root_key.der_bytes().to_vec(),
+            root_key.algorithm(),
+            move |msg| rcgen::SigningKey::sign(&root_key, msg).map_err(|e| e.to_string()),
+        );
+
+        let root_ca_id = uuid::Uuid::now_v7();
+        let intermediate = create_intermediate_ca(
+            &root_ca_seed.derive_child("intermediate-engineering"),
+            IntermediateCAParams::default(),
+            &root_ca.certificate_pem,
+            signer,
+            &[PolicyConstraint::MaxPathLength(0)],
+            root_ca_id,
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap();
+
+        assert_eq!(intermediate.chain_pem.matches("BEGIN CERTIFICATE").count(), 2);
+        assert!(intermediate.chain_pem.starts_with(&intermediate.certificate.certificate_pem));
+        assert_eq!(intermediate.generation_event.issuer, Some(root_ca_id));
+        assert_eq!(intermediate.signing_event.cert_id, intermediate.generation_event.cert_id);
+
+        let (_, root_pem) = parse_x509_pem(root_ca.certificate_pem.as_bytes()).unwrap();
+        let root = root_pem.parse_x509().unwrap();
+        let (_, int_pem) = parse_x509_pem(intermediate.certificate.certificate_pem.as_bytes()).unwrap();
+        let int_cert = int_pem.parse_x509().unwrap();
+
+        // Issuer is the real root, not an empty placeholder name
+        assert_eq!(int_cert.issuer().to_string(), root.subject().to_string());
+    }
+
+    #[test]
+    fn test_create_intermediate_ca_enforces_path_length() {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_ca_seed = master_seed.derive_child("root-ca");
+
+        let issue = |root_pathlen: u8, pathlen: u8, constraints: &[PolicyConstraint]| {
+            let root_params = RootCAParams { pathlen: root_pathlen, ..Default::default() };
+            let (root_ca, _) = generate_root_ca(&root_ca_seed, root_params, uuid::Uuid::now_v7(), None).unwrap();
+            let root_key = RcgenKeyPair::from_pem(&root_ca.private_key_pem).unwrap();
+            create_intermediate_ca(
+                &root_ca_seed.derive_child("intermediate-test"),
+                IntermediateCAParams { pathlen, ..Default::default() },
+                &root_ca.certificate_pem,
+                &root_key,
+                constraints,
+                uuid::Uuid::now_v7(),
+                uuid::Uuid::now_v7(),
+                None,
+            )
+        };
+
+        // Root pathlen 1 leaves room for exactly one CA level (pathlen 0)
+        let err = issue(1, 1, &[]).unwrap_err();
+        assert!(err.contains("Root CA pathlen"));
+        let err = issue(0, 0, &[]).unwrap_err();
+        assert!(err.contains("Root CA pathlen"));
+
+        // Policy caps the intermediate below what the root would allow
+        let err = issue(2, 1, &[PolicyConstraint::MaxPathLength(0)]).unwrap_err();
+        assert!(err.contains("MaxPathLength"));
+
+        assert!(issue(2, 1, &[PolicyConstraint::MaxPathLength(1)]).is_ok());
+        assert!(issue(1, 0, &[PolicyConstraint::MaxPathLength(0)]).is_ok());
+    }
+
+    /// Root → intermediate chain for leaf issuance tests
+    fn test_intermediate() -> (IntermediateCA, RcgenKeyPair) {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_ca_seed = master_seed.derive_child("root-ca");
+        let (root_ca, _) = generate_root_ca(&root_ca_seed, RootCAParams::default(), uuid::Uuid::now_v7(), None).unwrap();
+        let root_key = RcgenKeyPair::from_pem(&root_ca.private_key_pem).unwrap();
+
+        let intermediate = create_intermediate_ca(
+            &root_ca_seed.derive_child("intermediate-engineering"),
+            IntermediateCAParams::default(),
+            &root_ca.certificate_pem,
+            &root_key,
+            &[],
+            uuid::Uuid::now_v7(),
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap();
+        let intermediate_key = RcgenKeyPair::from_pem(&intermediate.certificate.private_key_pem).unwrap();
+        (intermediate, intermediate_key)
+    }
+
+    fn test_csr(common_name: &str, san: &[&str]) -> String {
+        let key = RcgenKeyPair::generate().unwrap();
+        let mut params = CertificateParams::new(san.iter().map(|s| s.to_string()).collect::<Vec<_>>()).unwrap();
+        params.distinguished_name.push(DnType::CommonName, common_name);
+        // Requests for CA rights or other EKUs are overridden by policy
+        params.is_ca = IsCa::Ca(RcgenBasicConstraints::Unconstrained);
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::CodeSigning];
+        params.serialize_request(&key).unwrap().pem().unwrap()
+    }
+
+    #[test]
+    fn test_issue_certificate_from_csr() {
+        use x509_parser::prelude::*;
+
+        let (intermediate, intermediate_key) = test_intermediate();
+        let csr = test_csr("api.example.com", &["api.example.com", "10.0.0.5"]);
+
+        let issued = issue_certificate(
+            &csr,
+            LeafCertificateParams {
+                purpose: KeyPurpose::Authentication,
+                validity_days: 365,
+                key_id: uuid::Uuid::now_v7(),
+            },
+            &intermediate.chain_pem,
+            &intermediate_key,
+            &[PolicyConstraint::ValidityPeriodMax { days: 90 }],
+            intermediate.generation_event.cert_id,
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap();
+
+        // Leaf + intermediate + root
+        assert_eq!(issued.chain_pem.matches("BEGIN CERTIFICATE").count(), 3);
+        assert!(issued.san.contains(&"DNS:api.example.com".to_string()));
+        assert_eq!(issued.generation_event.issuer, Some(intermediate.generation_event.cert_id));
+
+        let (_, pem) = parse_x509_pem(issued.certificate_pem.as_bytes()).unwrap();
+        let cert = pem.parse_x509().unwrap();
+        let (_, int_pem) = parse_x509_pem(intermediate.certificate.certificate_pem.as_bytes()).unwrap();
+        let int_cert = int_pem.parse_x509().unwrap();
+
+        assert_eq!(cert.issuer().to_string(), int_cert.subject().to_string());
+        assert!(!cert.is_ca());
+
+        let eku = cert.extended_key_usage().unwrap().unwrap().value;
+        assert!(eku.server_auth && eku.client_auth);
+        assert!(!eku.code_signing);
+
+        // Validity capped by policy
+        let validity = cert.validity();
+        let days = (validity.not_after.timestamp() - validity.not_before.timestamp()) / 86_400;
+        assert_eq!(days, 90);
+    }
+
+    #[test]
+    fn test_external_key_csr_is_signed_outside_rcgen() {
+        use p256::ecdsa::signature::hazmat::PrehashSigner;
+        use sha2::{Digest, Sha256};
+
+        let (intermediate, intermediate_key) = test_intermediate();
+        let device_key = p256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+        let public_key = device_key.verifying_key().to_encoded_point(false).as_bytes().to_vec();
+
+        let subject = crate::ports::x509::CertificateSubject {
+            common_name: "Alice Engineer".to_string(),
+            organization: Some("CIM".to_string()),
+            organizational_unit: None,
+            country: None,
+            state: None,
+            locality: None,
+            email: Some("alice@example.com".to_string()),
+        };
+        let csr = ExternalKeyCsr::prepare(&subject, public_key, &rcgen::PKCS_ECDSA_P256_SHA256).unwrap();
+
+        let digest = Sha256::digest(csr.to_be_signed());
+        let signature: p256::ecdsa::Signature = device_key.sign_prehash(&digest).unwrap();
+        let csr_pem = csr.finish(signature.to_der().as_bytes().to_vec()).unwrap();
+
+        let issued = issue_certificate(
+            &csr_pem,
+            LeafCertificateParams {
+                purpose: KeyPurpose::Signing,
+                validity_days: 365,
+                key_id: uuid::Uuid::now_v7(),
+            },
+            &intermediate.chain_pem,
+            &intermediate_key,
+            &[],
+            intermediate.generation_event.cert_id,
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap();
+        assert!(issued.san.contains(&"email:alice@example.com".to_string()));
+
+        // A signature over anything else is rejected when the CSR is parsed
+        let csr = ExternalKeyCsr::prepare(
+            &subject,
+            device_key.verifying_key().to_encoded_point(false).as_bytes().to_vec(),
+            &rcgen::PKCS_ECDSA_P256_SHA256,
+        ).unwrap();
+        let wrong: p256::ecdsa::Signature = device_key.sign_prehash(&Sha256::digest(b"other")).unwrap();
+        let csr_pem = csr.finish(wrong.to_der().as_bytes().to_vec()).unwrap();
+        assert!(CertificateSigningRequestParams::from_pem(&csr_pem).is_err());
+    }
+
+    #[test]
+    fn test_issue_certificate_enforces_policy() {
+        let (intermediate, intermediate_key) = test_intermediate();
+
+        let issue = |csr: &str, purpose: KeyPurpose, constraints: &[PolicyConstraint]| {
+            issue_certificate(
+                csr,
+                LeafCertificateParams { purpose, validity_days: 90, key_id: uuid::Uuid::now_v7() },
+                &intermediate.chain_pem,
+                &intermediate_key,
+                constraints,
+                uuid::Uuid::now_v7(),
+                uuid::Uuid::now_v7(),
+                None,
+            )
+        };
+
+        let names = [PolicyConstraint::NameConstraints {
+            permitted: vec!["example.com".to_string()],
+            excluded: vec!["internal.example.com".to_string()],
+        }];
+        assert!(issue(&test_csr("api", &["api.example.com"]), KeyPurpose::Authentication, &names).is_ok());
+        assert!(issue(&test_csr("api", &["api.example.org"]), KeyPurpose::Authentication, &names).is_err());
+        assert!(issue(&test_csr("db", &["db.internal.example.com"]), KeyPurpose::Authentication, &names).is_err());
+
+        let csr = test_csr("builder", &[]);
+        let code_signing_only = [PolicyConstraint::KeyUsageRestriction(vec!["codeSigning".to_string()])];
+        assert!(issue(&csr, KeyPurpose::Signing, &code_signing_only).is_ok());
+        assert!(issue(&csr, KeyPurpose::Authentication, &code_signing_only).is_err());
+
+        // Leaves are never CAs
+        assert!(issue(&csr, KeyPurpose::CertificateAuthority, &[]).is_err());
+
+        // Tampered CSRs are rejected
+        let tampered = csr.replacen("MII", "MIJ", 1);
+        assert!(issue(&tampered, KeyPurpose::Signing, &[]).is_err());
+    }
+
     #[test]
     fn test_root_ca_basic_constraints() {
         use x509_parser::prelude::*;
@@ -811,6 +1800,7 @@ mod tests {
             country: Some("US".to_string()),
             validity_years: 10,
             pathlen: 0,
+            ..Default::default()
         };
         let intermediate_correlation_id = uuid::Uuid::now_v7();
         let root_ca_id = uuid::Uuid::now_v7();
@@ -830,6 +1820,7 @@ mod tests {
             organization: "Test Organization".to_string(),
             organizational_unit: Some("Engineering".to_string()),
             validity_days: 90,
+            ..Default::default()
         };
         let server_correlation_id = uuid::Uuid::now_v7();
         let intermediate_ca_id = uuid::Uuid::now_v7();
@@ -893,4 +1884,136 @@ mod tests {
             actual_seconds, expected_seconds
         );
     }
+
+    #[test]
+    fn test_p384_hierarchy_records_signature_algorithm() {
+        use x509_parser::prelude::*;
+
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_ca_seed = master_seed.derive_child("root-ca");
+        let root_params = RootCAParams {
+            signature_algorithm: CertificateSignatureAlgorithm::EcdsaP384Sha384,
+            ..Default::default()
+        };
+        let (root_ca, root_event) = generate_root_ca(&root_ca_seed, root_params, uuid::Uuid::now_v7(), None).unwrap();
+        assert_eq!(root_event.signature_algorithm, Some(CertificateSignatureAlgorithm::EcdsaP384Sha384));
+
+        let root_key = RcgenKeyPair::from_pem(&root_ca.private_key_pem).unwrap();
+        let intermediate = create_intermediate_ca(
+            &root_ca_seed.derive_child("intermediate-engineering"),
+            IntermediateCAParams {
+                signature_algorithm: CertificateSignatureAlgorithm::EcdsaP384Sha384,
+                ..Default::default()
+            },
+            &root_ca.certificate_pem,
+            &root_key,
+            &[],
+            uuid::Uuid::now_v7(),
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap();
+
+        assert_eq!(
+            intermediate.generation_event.signature_algorithm,
+            Some(CertificateSignatureAlgorithm::EcdsaP384Sha384)
+        );
+        assert_eq!(intermediate.signing_event.signature_algorithm, "ecdsa-with-SHA384");
+
+        let (_, pem) = parse_x509_pem(intermediate.certificate.certificate_pem.as_bytes()).unwrap();
+        let cert = pem.parse_x509().unwrap();
+        assert_eq!(cert.signature_algorithm.algorithm, x509_parser::oid_registry::OID_SIG_ECDSA_WITH_SHA384);
+
+        // A P-256 leaf is within the P-384 intermediate's strength
+        let intermediate_key = RcgenKeyPair::from_pem(&intermediate.certificate.private_key_pem).unwrap();
+        let issued = issue_certificate(
+            &test_csr("api.example.com", &["api.example.com"]),
+            LeafCertificateParams {
+                purpose: KeyPurpose::Authentication,
+                validity_days: 90,
+                key_id: uuid::Uuid::now_v7(),
+            },
+            &intermediate.chain_pem,
+            &intermediate_key,
+            &[],
+            intermediate.generation_event.cert_id,
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap();
+        assert_eq!(
+            issued.generation_event.signature_algorithm,
+            Some(CertificateSignatureAlgorithm::EcdsaP384Sha384)
+        );
+    }
+
+    #[test]
+    fn test_issuer_weaker_than_subject_algorithm_is_rejected() {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_ca_seed = master_seed.derive_child("root-ca");
+        let (root_ca, _) = generate_root_ca(&root_ca_seed, RootCAParams::default(), uuid::Uuid::now_v7(), None).unwrap();
+        let root_key = RcgenKeyPair::from_pem(&root_ca.private_key_pem).unwrap();
+
+        let err = create_intermediate_ca(
+            &root_ca_seed.derive_child("intermediate-engineering"),
+            IntermediateCAParams {
+                signature_algorithm: CertificateSignatureAlgorithm::EcdsaP384Sha384,
+                ..Default::default()
+            },
+            &root_ca.certificate_pem,
+            &root_key,
+            &[],
+            uuid::Uuid::now_v7(),
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap_err();
+        assert!(err.contains("weaker"), "{}", err);
+
+        // Same rule for a P-384 CSR presented to the P-256 intermediate
+        let (intermediate, intermediate_key) = test_intermediate();
+        let csr_key = RcgenKeyPair::generate_for(&rcgen::PKCS_ECDSA_P384_SHA384).unwrap();
+        let mut csr_params = CertificateParams::new(vec!["api.example.com".to_string()]).unwrap();
+        csr_params.distinguished_name.push(DnType::CommonName, "api.example.com");
+        let csr = csr_params.serialize_request(&csr_key).unwrap().pem().unwrap();
+        let err = issue_certificate(
+            &csr,
+            LeafCertificateParams {
+                purpose: KeyPurpose::Authentication,
+                validity_days: 90,
+                key_id: uuid::Uuid::now_v7(),
+            },
+            &intermediate.chain_pem,
+            &intermediate_key,
+            &[],
+            intermediate.generation_event.cert_id,
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap_err();
+        assert!(err.contains("weaker"), "{}", err);
+    }
+
+    #[test]
+    fn test_ed25519_root_and_rsa_generation() {
+        let master_seed = derive_master_seed("test passphrase", "test-org").unwrap();
+        let root_ca_seed = master_seed.derive_child("root-ca");
+
+        let (_, event) = generate_root_ca(
+            &root_ca_seed,
+            RootCAParams { signature_algorithm: CertificateSignatureAlgorithm::Ed25519, ..Default::default() },
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap();
+        assert_eq!(event.signature_algorithm, Some(CertificateSignatureAlgorithm::Ed25519));
+
+        // RSA issuers must bring their own key
+        let err = generate_root_ca(
+            &root_ca_seed,
+            RootCAParams { signature_algorithm: CertificateSignatureAlgorithm::RsaPkcs1Sha512, ..Default::default() },
+            uuid::Uuid::now_v7(),
+            None,
+        ).unwrap_err();
+        assert!(err.contains("existing key"), "{}", err);
+
+        for algorithm in CertificateSignatureAlgorithm::all() {
+            assert_eq!(signature_algorithm_of(rcgen_signature_algorithm(*algorithm)), Some(*algorithm));
+        }
+    }
 }
@@ -468,12 +468,18 @@ pub struct NatsSecurityAggregate {
     pub organization_id: BootstrapOrgId,
     /// Operator name
     pub name: String,
+    /// Operator identity public key (`O...`), set when the operator is created
+    #[serde(default)]
+    pub public_key: String,
     /// System account ID (if configured)
     pub system_account_id: Option<NatsAccountId>,
     /// Accounts (indexed by account ID)
     pub accounts: HashMap<NatsAccountId, NatsAccountState>,
     /// Users (indexed by user ID)
     pub users: HashMap<NatsUserId, NatsUserState>,
+    /// Operator signing keys (public)
+    #[serde(default)]
+    pub signing_keys: Vec<String>,
 }
 
 /// State of a NATS account within the aggregate
@@ -481,8 +487,17 @@ pub struct NatsSecurityAggregate {
 pub struct NatsAccountState {
     pub id: NatsAccountId,
     pub name: String,
+    /// Account identity public key (`A...`), the `sub` of its JWT
+    #[serde(default)]
+    pub public_key: String,
     pub unit_id: Option<Uuid>,
     pub is_system_account: bool,
+    /// Revoked user public keys and when they were revoked
+    #[serde(default)]
+    pub revocations: HashMap<String, chrono::DateTime<chrono::Utc>>,
+    /// Account signing keys (public)
+    #[serde(default)]
+    pub signing_keys: Vec<String>,
 }
 
 /// State of a NATS user within the aggregate
@@ -490,6 +505,9 @@ pub struct NatsAccountState {
 pub struct NatsUserState {
     pub id: NatsUserId,
     pub name: String,
+    /// User public key (`U...`)
+    #[serde(default)]
+    pub public_key: String,
     pub account_id: NatsAccountId,
     pub person_id: Option<Uuid>,
     pub is_service_account: bool,
@@ -503,9 +521,11 @@ impl NatsSecurityAggregate {
             version: 0,
             organization_id,
             name,
+            public_key: String::new(),
             system_account_id: None,
             accounts: HashMap::new(),
             users: HashMap::new(),
+            signing_keys: Vec::new(),
         }
     }
 
@@ -545,6 +565,50 @@ impl NatsSecurityAggregate {
         Ok(())
     }
 
+    /// Validate a user can be revoked by its account
+    pub fn can_revoke_user(&self, account_id: NatsAccountId, user_id: NatsUserId) -> Result<(), String> {
+        if !self.account_exists(account_id) {
+            return Err(format!("Account {} does not exist", account_id));
+        }
+        match self.users.get(&user_id) {
+            Some(user) if user.account_id == account_id => Ok(()),
+            Some(_) => Err(format!("User {} was not issued by account {}", user_id, account_id)),
+            None => Err(format!("User {} does not exist", user_id)),
+        }
+    }
+
+    /// Whether the account has revoked `user_public_key`
+    pub fn is_user_revoked(&self, account_id: NatsAccountId, user_public_key: &str) -> bool {
+        self.accounts
+            .get(&account_id)
+            .is_some_and(|account| account.revocations.contains_key(user_public_key))
+    }
+
+    /// Signing keys currently trusted for an operator or account
+    pub fn signing_keys_of(&self, entity_id: Uuid) -> Option<&[String]> {
+        if entity_id == self.id.as_uuid() {
+            return Some(&self.signing_keys);
+        }
+        self.accounts
+            .get(&NatsAccountId::from_uuid(entity_id))
+            .map(|account| account.signing_keys.as_slice())
+    }
+
+    fn signing_keys_mut(&mut self, entity_id: Uuid) -> Option<&mut Vec<String>> {
+        if entity_id == self.id.as_uuid() {
+            return Some(&mut self.signing_keys);
+        }
+        self.accounts
+            .get_mut(&NatsAccountId::from_uuid(entity_id))
+            .map(|account| &mut account.signing_keys)
+    }
+
+    /// Whether `public_key` is the operator's identity key or one of its
+    /// signing keys
+    pub fn is_operator_key(&self, public_key: &str) -> bool {
+        !public_key.is_empty() && (public_key == self.public_key || self.signing_keys.iter().any(|k| k == public_key))
+    }
+
     /// Apply a NATS event to update state
     ///
     /// Note: Detailed event application is handled by projections.
@@ -554,8 +618,21 @@ impl NatsSecurityAggregate {
             DomainEvent::NatsOperator(op_event) => {
                 use crate::events::nats_operator::NatsOperatorEvents;
                 match op_event {
-                    NatsOperatorEvents::NatsOperatorCreated(_) => {
-                        // Initial state set in constructor
+                    NatsOperatorEvents::NatsOperatorCreated(e) => {
+                        // Everything else is set in the constructor
+                        self.public_key = e.public_key.clone();
+                    }
+                    NatsOperatorEvents::NatsSigningKeyGenerated(e) => {
+                        if let Some(keys) = self.signing_keys_mut(e.entity_id) {
+                            if !keys.contains(&e.public_key) {
+                                keys.push(e.public_key.clone());
+                            }
+                        }
+                    }
+                    NatsOperatorEvents::NatsSigningKeyRetired(e) => {
+                        if let Some(keys) = self.signing_keys_mut(e.entity_id) {
+                            keys.retain(|key| key != &e.public_key);
+                        }
                     }
                     _ => {}
                 }
@@ -569,14 +646,23 @@ impl NatsSecurityAggregate {
                         self.accounts.insert(account_id, NatsAccountState {
                             id: account_id,
                             name: e.name.clone(),
+                            public_key: e.public_key.clone(),
                             unit_id: e.organization_unit_id,
                             is_system_account: e.is_system,
+                            revocations: HashMap::new(),
+                            signing_keys: Vec::new(),
                         });
                     }
                     NatsAccountEvents::NatsAccountDeleted(e) => {
                         let account_id = NatsAccountId::from_uuid(e.account_id);
                         self.accounts.remove(&account_id);
                     }
+                    NatsAccountEvents::NatsUserRevoked(e) => {
+                        let account_id = NatsAccountId::from_uuid(e.account_id);
+                        if let Some(account) = self.accounts.get_mut(&account_id) {
+                            account.revocations.insert(e.user_public_key.clone(), e.revoked_at);
+                        }
+                    }
                     _ => {}
                 }
                 self.increment_version();
@@ -590,6 +676,7 @@ impl NatsSecurityAggregate {
                         self.users.insert(user_id, NatsUserState {
                             id: user_id,
                             name: e.name.clone(),
+                            public_key: e.public_key.clone(),
                             account_id,
                             person_id: e.person_id,
                             is_service_account: e.person_id.is_none(),
@@ -877,6 +964,13 @@ pub struct DelegationState {
     pub revocation_reason: Option<String>,
 }
 
+impl DelegationState {
+    /// Whether the delegation confers its permissions at `at`
+    pub fn is_active_at(&self, at: chrono::DateTime<chrono::Utc>) -> bool {
+        !self.revoked && self.valid_from <= at && self.valid_until.is_none_or(|until| at < until)
+    }
+}
+
 impl DelegationAggregate {
     /// Create a new delegation aggregate for an organization
     pub fn new(organization_id: BootstrapOrgId) -> Self {
@@ -1047,6 +1141,70 @@ impl DelegationAggregate {
         revocations
     }
 
+    /// Earliest `valid_until` among delegations not yet revoked
+    ///
+    /// A scheduler sleeps until this instant, then calls `expire_delegations`.
+    pub fn next_expiration(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.delegations.values()
+            .filter(|d| !d.revoked)
+            .filter_map(|d| d.valid_until)
+            .min()
+    }
+
+    /// Events revoking every delegation whose `valid_until` has passed at `now`
+    ///
+    /// Each expired delegation gets a `DelegationExpired`; delegations
+    /// derived from it that have not expired themselves are cascade-revoked.
+    /// Applying the events removes them all from permission checks.
+    pub fn expire_delegations(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+        correlation_id: Uuid,
+    ) -> Vec<crate::events::DomainEvent> {
+        use crate::events::delegation::{
+            DelegationCascadeRevokedEvent, DelegationEvents, DelegationExpiredEvent,
+        };
+
+        let mut expired: Vec<&DelegationState> = self.delegations.values()
+            .filter(|d| !d.revoked && d.valid_until.is_some_and(|until| until <= now))
+            .collect();
+        expired.sort_by_key(|d| d.valid_until);
+
+        let mut events = Vec::new();
+        let mut ended: std::collections::HashSet<DelegationId> = expired.iter().map(|d| d.id).collect();
+        for delegation in &expired {
+            events.push(crate::events::DomainEvent::Delegation(DelegationEvents::DelegationExpired(
+                DelegationExpiredEvent {
+                    delegation_id: delegation.id.as_uuid(),
+                    delegate_id: delegation.delegate_id,
+                    valid_until: delegation.valid_until.unwrap_or(now),
+                    expired_at: now,
+                    correlation_id,
+                    causation_id: None,
+                },
+            )));
+        }
+        for delegation in &expired {
+            for (dependent_id, reason) in self.prepare_revocation_cascade(delegation.id, "expired") {
+                if ended.insert(dependent_id) {
+                    events.push(crate::events::DomainEvent::Delegation(
+                        DelegationEvents::DelegationCascadeRevoked(DelegationCascadeRevokedEvent {
+                            delegation_id: dependent_id.as_uuid(),
+                            caused_by_delegation_id: delegation.id.as_uuid(),
+                            root_revocation_id: delegation.id.as_uuid(),
+                            reason,
+                            revoked_at: now,
+                            correlation_id,
+                            causation_id: None,
+                        }),
+                    ));
+                }
+            }
+        }
+
+        events
+    }
+
     /// Apply a delegation event to update state
     pub fn apply(&mut self, event: &crate::events::DomainEvent) -> Result<(), String> {
         match event {
@@ -1110,6 +1268,16 @@ impl DelegationAggregate {
                             del.valid_until = e.new_valid_until;
                         }
                     }
+                    DelegationEvents::DelegationExpired(e) => {
+                        let del_id = DelegationId::from_uuid(e.delegation_id);
+                        if let Some(del) = self.delegations.get_mut(&del_id) {
+                            del.revoked = true;
+                            del.revoked_at = Some(e.valid_until);
+                            del.revocation_reason = Some(
+                                crate::events::delegation::RevocationReason::Expired.to_string(),
+                            );
+                        }
+                    }
                     DelegationEvents::DelegationPermissionsModified(e) => {
                         let del_id = DelegationId::from_uuid(e.delegation_id);
                         if let Some(del) = self.delegations.get_mut(&del_id) {
@@ -1134,13 +1302,21 @@ impl DelegationAggregate {
     }
 
     /// Get active delegations for a person (as delegate)
+    ///
+    /// Expired delegations are excluded even before `DelegationExpired`
+    /// has been recorded for them.
     pub fn get_delegations_for(&self, person_id: Uuid) -> Vec<&DelegationState> {
+        self.delegations_for_at(person_id, chrono::Utc::now())
+    }
+
+    /// Get delegations a person (as delegate) holds at `at`
+    pub fn delegations_for_at(&self, person_id: Uuid, at: chrono::DateTime<chrono::Utc>) -> Vec<&DelegationState> {
         self.by_delegate
             .get(&person_id)
             .map(|ids| {
                 ids.iter()
                     .filter_map(|id| self.delegations.get(id))
-                    .filter(|d| !d.revoked)
+                    .filter(|d| d.is_active_at(at))
                     .collect()
             })
             .unwrap_or_default()
@@ -1148,12 +1324,13 @@ impl DelegationAggregate {
 
     /// Get active delegations from a person (as delegator)
     pub fn get_delegations_from(&self, person_id: Uuid) -> Vec<&DelegationState> {
+        let now = chrono::Utc::now();
         self.by_delegator
             .get(&person_id)
             .map(|ids| {
                 ids.iter()
                     .filter_map(|id| self.delegations.get(id))
-                    .filter(|d| !d.revoked)
+                    .filter(|d| d.is_active_at(now))
                     .collect()
             })
             .unwrap_or_default()
@@ -1165,7 +1342,17 @@ impl DelegationAggregate {
         person_id: Uuid,
         permission: &crate::domain::KeyPermission,
     ) -> bool {
-        self.get_delegations_for(person_id)
+        self.has_delegated_permission_at(person_id, permission, chrono::Utc::now())
+    }
+
+    /// Check if a person has a specific permission through delegations at `at`
+    pub fn has_delegated_permission_at(
+        &self,
+        person_id: Uuid,
+        permission: &crate::domain::KeyPermission,
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        self.delegations_for_at(person_id, at)
             .iter()
             .any(|d| d.permissions.contains(permission))
     }
@@ -1270,8 +1457,11 @@ mod tests {
         aggregate.accounts.insert(acc_id, NatsAccountState {
             id: acc_id,
             name: "engineering".to_string(),
+            public_key: String::new(),
             unit_id: None,
             is_system_account: false,
+            revocations: HashMap::new(),
+            signing_keys: Vec::new(),
         });
 
         // Same name should no longer be unique
@@ -1501,4 +1691,52 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("cycle"));
     }
+
+    #[test]
+    fn test_delegation_expiry_revokes_and_cascades() {
+        use crate::events::delegation::{DelegationCreatedEvent, DelegationEvents};
+        use crate::events::DomainEvent;
+
+        let mut aggregate = DelegationAggregate::new(BootstrapOrgId::new());
+        let now = chrono::Utc::now();
+        let (alice, bob, charlie) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let (expiring_id, derived_id) = (Uuid::now_v7(), Uuid::now_v7());
+
+        // Alice → Bob until tomorrow, Bob → Charlie without end
+        for (delegation_id, delegator_id, delegate_id, derives_from, valid_until) in [
+            (expiring_id, alice, bob, None, Some(now + chrono::Duration::days(1))),
+            (derived_id, bob, charlie, Some(expiring_id), None),
+        ] {
+            aggregate.apply(&DomainEvent::Delegation(DelegationEvents::DelegationCreated(DelegationCreatedEvent {
+                delegation_id,
+                delegator_id,
+                delegate_id,
+                permissions: vec![crate::domain::KeyPermission::Sign],
+                derives_from,
+                valid_from: now,
+                valid_until,
+                created_at: now,
+                created_by: delegator_id,
+                correlation_id: Uuid::now_v7(),
+                causation_id: None,
+            }))).unwrap();
+        }
+
+        let later = now + chrono::Duration::days(2);
+        assert_eq!(aggregate.next_expiration(), Some(now + chrono::Duration::days(1)));
+        assert!(aggregate.expire_delegations(now, Uuid::now_v7()).is_empty());
+        assert!(!aggregate.has_delegated_permission_at(bob, &crate::domain::KeyPermission::Sign, later));
+
+        let events = aggregate.expire_delegations(later, Uuid::now_v7());
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], DomainEvent::Delegation(DelegationEvents::DelegationExpired(e)) if e.delegation_id == expiring_id));
+        assert!(matches!(&events[1], DomainEvent::Delegation(DelegationEvents::DelegationCascadeRevoked(e)) if e.delegation_id == derived_id));
+
+        for event in &events {
+            aggregate.apply(event).unwrap();
+        }
+        assert!(aggregate.expire_delegations(later, Uuid::now_v7()).is_empty());
+        assert_eq!(aggregate.next_expiration(), None);
+        assert!(!aggregate.has_delegated_permission_at(charlie, &crate::domain::KeyPermission::Sign, now));
+    }
 }
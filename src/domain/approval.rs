@@ -0,0 +1,283 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Approval Quorum for Sensitive Commands
+//!
+//! Root CA issuance, secret key export and policy changes are not executed
+//! on a single person's say-so. A matching [`ApprovalRule`] parks such a
+//! command as a [`PendingApproval`] aggregate until `required_approvals`
+//! distinct people, each holding every claim in `approver_claims`, approve
+//! it. Only then may the command be executed, exactly once.
+//!
+//! ## Lifecycle
+//!
+//! ```text
+//! RequestApproval ──▶ Pending ──(k × GrantApproval)──▶ Approved ──▶ Executed
+//!                        │
+//!                        └──(RejectApproval)──▶ Rejected
+//! ```
+//!
+//! The aggregate only holds a SHA-256 digest of the parked command. Export
+//! commands carry key material, so the command itself is resubmitted at
+//! execution time and must match the digest that was approved.
+
+use cim_domain::AggregateRoot;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::PolicyClaim;
+use crate::commands::KeyCommand;
+use crate::events::approval::{ApprovalEvents, ApprovalRequestedEvent};
+
+/// Category of command that may require approval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SensitiveCommandKind {
+    /// Generating a root certificate authority
+    RootCaIssuance,
+    /// Exporting secret key material
+    KeyExport,
+    /// Changing which policies are bound
+    PolicyChange,
+}
+
+impl SensitiveCommandKind {
+    /// Category of a command, or `None` if it never needs approval
+    pub fn of(command: &KeyCommand) -> Option<Self> {
+        match command {
+            KeyCommand::GenerateRootCA(_) => Some(Self::RootCaIssuance),
+            KeyCommand::ExportKeys(_)
+            | KeyCommand::ExportGpgSecretKey(_)
+            | KeyCommand::ExportSshPrivateKey(_) => Some(Self::KeyExport),
+            KeyCommand::BindPolicy(_) | KeyCommand::UnbindPolicy(_) | KeyCommand::SupersedePolicy(_) => {
+                Some(Self::PolicyChange)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SensitiveCommandKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RootCaIssuance => write!(f, "root CA issuance"),
+            Self::KeyExport => write!(f, "key export"),
+            Self::PolicyChange => write!(f, "policy change"),
+        }
+    }
+}
+
+/// How many approvals a kind of command needs, and from whom
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRule {
+    pub kind: SensitiveCommandKind,
+    /// Number of distinct approvers required (k)
+    pub required_approvals: u16,
+    /// Claims an approver must hold, all of them
+    pub approver_claims: Vec<PolicyClaim>,
+}
+
+impl ApprovalRule {
+    pub fn new(kind: SensitiveCommandKind, required_approvals: u16) -> Self {
+        Self {
+            kind,
+            required_approvals,
+            approver_claims: Vec::new(),
+        }
+    }
+
+    /// Require approvers to hold a claim
+    pub fn with_approver_claim(mut self, claim: PolicyClaim) -> Self {
+        self.approver_claims.push(claim);
+        self
+    }
+
+    /// Whether someone holding `claims` may approve under this rule
+    pub fn qualifies(&self, claims: &[PolicyClaim]) -> bool {
+        self.approver_claims.iter().all(|required| claims.contains(required))
+    }
+}
+
+/// State of a parked command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ApprovalStatus {
+    /// Collecting approvals
+    Pending,
+    /// Quorum reached, the command may be executed
+    Approved,
+    /// Rejected, the command will not be executed
+    Rejected,
+    /// The command was executed
+    Executed,
+}
+
+/// Pending Approval Aggregate Root
+///
+/// A sensitive command waiting for approval.
+///
+/// ## Invariants
+/// - The requester cannot approve their own command
+/// - Each approver counts once
+/// - Only a Pending approval accepts approvals or rejections
+/// - Only an Approved approval may be executed, and only once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub approval_id: Uuid,
+    pub version: u64,
+    pub command_id: Uuid,
+    pub kind: SensitiveCommandKind,
+    pub command_digest: String,
+    pub required_approvals: u16,
+    pub approver_claims: Vec<PolicyClaim>,
+    pub requested_by: Uuid,
+    pub approvers: Vec<Uuid>,
+    pub status: ApprovalStatus,
+}
+
+impl PendingApproval {
+    /// Initial state from the request event
+    pub fn from_requested(event: &ApprovalRequestedEvent) -> Self {
+        Self {
+            approval_id: event.approval_id,
+            version: 1,
+            command_id: event.command_id,
+            kind: event.command_kind,
+            command_digest: event.command_digest.clone(),
+            required_approvals: event.required_approvals,
+            approver_claims: event.approver_claims.clone(),
+            requested_by: event.requested_by,
+            approvers: Vec::new(),
+            status: ApprovalStatus::Pending,
+        }
+    }
+
+    /// Fold an event into the state
+    pub fn apply(&mut self, event: &ApprovalEvents) {
+        match event {
+            ApprovalEvents::ApprovalRequested(_) => return,
+            ApprovalEvents::ApprovalGranted(e) => {
+                if !self.approvers.contains(&e.approver_id) {
+                    self.approvers.push(e.approver_id);
+                }
+            }
+            ApprovalEvents::ApprovalQuorumReached(_) => self.status = ApprovalStatus::Approved,
+            ApprovalEvents::ApprovalRejected(_) => self.status = ApprovalStatus::Rejected,
+            ApprovalEvents::ApprovedCommandExecuted(_) => self.status = ApprovalStatus::Executed,
+        }
+        self.increment_version();
+    }
+
+    /// Whether enough approvals have been collected
+    pub fn has_quorum(&self) -> bool {
+        self.approvers.len() >= usize::from(self.required_approvals)
+    }
+
+    /// Whether someone holding `claims` may approve
+    pub fn qualifies(&self, claims: &[PolicyClaim]) -> bool {
+        self.approver_claims.iter().all(|required| claims.contains(required))
+    }
+}
+
+impl AggregateRoot for PendingApproval {
+    type Id = Uuid;
+
+    fn id(&self) -> Self::Id {
+        self.approval_id
+    }
+
+    fn version(&self) -> u64 {
+        self.version
+    }
+
+    fn increment_version(&mut self) {
+        self.version += 1;
+    }
+}
+
+/// SHA-256 of a command's canonical JSON, hex encoded
+///
+/// `serde_json::Value` keeps object keys sorted, so the digest does not
+/// depend on field order.
+pub fn command_digest(command: &KeyCommand) -> Result<String, String> {
+    let value = serde_json::to_value(command).map_err(|e| format!("Failed to serialize command: {}", e))?;
+    let bytes = serde_json::to_vec(&value).map_err(|e| format!("Failed to serialize command: {}", e))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::organization::UnbindPolicy;
+    use crate::events::approval::ApprovalGrantedEvent;
+    use chrono::Utc;
+
+    fn unbind() -> KeyCommand {
+        KeyCommand::UnbindPolicy(UnbindPolicy {
+            command_id: Uuid::now_v7(),
+            relationship_id: Uuid::now_v7(),
+            policy_id: Uuid::now_v7(),
+            entity_id: Uuid::now_v7(),
+            unbound_by: "admin".to_string(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+            timestamp: Utc::now(),
+        })
+    }
+
+    fn pending(required_approvals: u16) -> PendingApproval {
+        PendingApproval::from_requested(&ApprovalRequestedEvent {
+            approval_id: Uuid::now_v7(),
+            command_id: Uuid::now_v7(),
+            command_kind: SensitiveCommandKind::PolicyChange,
+            command_digest: "00".to_string(),
+            required_approvals,
+            approver_claims: vec![PolicyClaim::CanManagePolicies],
+            requested_by: Uuid::now_v7(),
+            requested_at: Utc::now(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        })
+    }
+
+    #[test]
+    fn test_sensitive_command_kinds() {
+        assert_eq!(SensitiveCommandKind::of(&unbind()), Some(SensitiveCommandKind::PolicyChange));
+    }
+
+    #[test]
+    fn test_rule_requires_all_claims() {
+        let rule = ApprovalRule::new(SensitiveCommandKind::KeyExport, 2)
+            .with_approver_claim(PolicyClaim::CanExportKeys)
+            .with_approver_claim(PolicyClaim::CanAccessProduction);
+
+        assert!(rule.qualifies(&[PolicyClaim::CanAccessProduction, PolicyClaim::CanExportKeys]));
+        assert!(!rule.qualifies(&[PolicyClaim::CanExportKeys]));
+    }
+
+    #[test]
+    fn test_command_digest_is_stable() {
+        let command = unbind();
+        assert_eq!(command_digest(&command).unwrap(), command_digest(&command.clone()).unwrap());
+        assert_ne!(command_digest(&command).unwrap(), command_digest(&unbind()).unwrap());
+    }
+
+    #[test]
+    fn test_duplicate_grant_counts_once() {
+        let mut approval = pending(2);
+        let approver_id = Uuid::now_v7();
+        let granted = ApprovalEvents::ApprovalGranted(ApprovalGrantedEvent {
+            approval_id: approval.approval_id,
+            approver_id,
+            approvals: 1,
+            comment: None,
+            granted_at: Utc::now(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        });
+
+        approval.apply(&granted);
+        approval.apply(&granted);
+
+        assert_eq!(approval.approvers, vec![approver_id]);
+        assert!(!approval.has_quorum());
+    }
+}
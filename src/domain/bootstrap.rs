@@ -550,6 +550,30 @@ pub struct PgpConfig {
     pub expiration: String,     // e.g., "5y"
 }
 
+impl PgpConfig {
+    /// Key lifetime in days, using GnuPG's expiration syntax
+    ///
+    /// Accepts `N` (days), `Nd`, `Nw`, `Nm` and `Ny`. Returns `None` for
+    /// `"0"`/`"never"` (no expiration) or an unparseable value.
+    pub fn expiration_days(&self) -> Option<u32> {
+        let value = self.expiration.trim().to_lowercase();
+        if value == "never" {
+            return None;
+        }
+
+        let (number, multiplier) = match value.chars().last()? {
+            'd' => (&value[..value.len() - 1], 1),
+            'w' => (&value[..value.len() - 1], 7),
+            'm' => (&value[..value.len() - 1], 30),
+            'y' => (&value[..value.len() - 1], 365),
+            _ => (value.as_str(), 1),
+        };
+
+        let days = number.parse::<u32>().ok()?.checked_mul(multiplier)?;
+        (days > 0).then_some(days)
+    }
+}
+
 /// FIDO/U2F configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FidoConfig {
@@ -846,7 +870,7 @@ pub struct OrganizationalPKI {
     /// Root CA for the organization
     pub root_ca_org_id: Uuid,
 
-    /// Intermediate CAs for organizational units
+    /// Intermediate CAs for organizational units, as (unit_id, cert_id)
     pub intermediate_cas: Vec<(Uuid, Uuid)>,
 
     /// Policy CA for special purposes
@@ -856,6 +880,25 @@ pub struct OrganizationalPKI {
     pub cross_certifications: Vec<(Uuid, Uuid)>,
 }
 
+impl OrganizationalPKI {
+    /// Map an organization unit to its intermediate CA certificate
+    ///
+    /// A unit has at most one active intermediate; registering a new one
+    /// (e.g. after rotation) replaces the previous mapping.
+    pub fn register_intermediate_ca(&mut self, unit_id: Uuid, cert_id: Uuid) {
+        self.intermediate_cas.retain(|(unit, _)| *unit != unit_id);
+        self.intermediate_cas.push((unit_id, cert_id));
+    }
+
+    /// Intermediate CA certificate issued for an organization unit
+    pub fn intermediate_ca_for(&self, unit_id: Uuid) -> Option<Uuid> {
+        self.intermediate_cas
+            .iter()
+            .find(|(unit, _)| *unit == unit_id)
+            .map(|(_, cert)| *cert)
+    }
+}
+
 /// Policy-specific certificate authority
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PolicyCA {
@@ -969,17 +1012,35 @@ impl std::fmt::Display for KeyOwnerRole {
 /// Policy entity with claims-based permissions - a domain entity.
 ///
 /// Policies define what actions are permitted based on conditions.
-/// Multiple policies can be composed (claims are unioned).
+/// Multiple policies can be composed (claims are unioned) and can subtract
+/// claims again with `denied_claims` ("never allow X for interns").
 /// Policies are evaluated in priority order (higher priority wins).
+///
+/// A policy is never edited in place. Changing it records a new version
+/// with the same `id` (see `next_version`), in force from its
+/// `effective_from`, so an evaluation at a past instant sees the version
+/// that was in force then.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Policy {
     pub id: super::ids::BootstrapPolicyId,
     pub name: String,
     pub description: String,
     pub claims: Vec<PolicyClaim>,
+    /// Claims this policy explicitly denies
+    #[serde(default)]
+    pub denied_claims: Vec<PolicyClaim>,
     pub conditions: Vec<PolicyCondition>,
     pub priority: i32,
     pub enabled: bool,
+    /// Version number, starting at 1; shared `id` across versions
+    #[serde(default = "first_policy_version")]
+    pub version: u32,
+    /// First instant this version is in force (`None`: always was)
+    #[serde(default)]
+    pub effective_from: Option<DateTime<Utc>>,
+    /// Instant this version stops being in force (`None`: until superseded)
+    #[serde(default)]
+    pub effective_until: Option<DateTime<Utc>>,
     pub created_by: super::ids::BootstrapPersonId,
     pub metadata: HashMap<String, String>,
 }
@@ -996,20 +1057,66 @@ impl Policy {
             name: name.into(),
             description: description.into(),
             claims: Vec::new(),
+            denied_claims: Vec::new(),
             conditions: Vec::new(),
             priority: 0,
             enabled: true,
+            version: 1,
+            effective_from: None,
+            effective_until: None,
             created_by,
             metadata: HashMap::new(),
         }
     }
 
+    /// Successor version of this policy, in force from `effective_from`.
+    ///
+    /// Starts as a copy of this version; adjust it with the builders.
+    pub fn next_version(&self, effective_from: DateTime<Utc>) -> Self {
+        Self {
+            version: self.version + 1,
+            effective_from: Some(effective_from),
+            effective_until: None,
+            ..self.clone()
+        }
+    }
+
+    /// Builder: first instant this version is in force.
+    pub fn with_effective_from(mut self, at: DateTime<Utc>) -> Self {
+        self.effective_from = Some(at);
+        self
+    }
+
+    /// Builder: instant this version stops being in force.
+    pub fn with_effective_until(mut self, at: DateTime<Utc>) -> Self {
+        self.effective_until = Some(at);
+        self
+    }
+
+    /// Whether `at` lies within this version's effective period.
+    pub fn is_effective_at(&self, at: DateTime<Utc>) -> bool {
+        self.effective_from.map_or(true, |from| from <= at)
+            && self.effective_until.map_or(true, |until| at < until)
+    }
+
     /// Builder: add a claim.
     pub fn with_claim(mut self, claim: PolicyClaim) -> Self {
         self.claims.push(claim);
         self
     }
 
+    /// Builder: deny a claim, whatever lower-priority policies grant.
+    pub fn with_denied_claim(mut self, claim: PolicyClaim) -> Self {
+        self.denied_claims.push(claim);
+        self
+    }
+
+    /// Builder: add a condition.
+    pub fn with_condition(mut self, condition: PolicyCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
     /// Builder: set priority.
     pub fn with_priority(mut self, priority: i32) -> Self {
         self.priority = priority;
@@ -1017,10 +1124,39 @@ impl Policy {
     }
 }
 
+fn first_policy_version() -> u32 {
+    1
+}
+
+/// Versions of each policy in force at `at`
+///
+/// For each policy ID this is the highest version that had started by
+/// `at`, provided it has not expired. A later version supersedes earlier
+/// ones from its `effective_from`; there is no fallback to them.
+pub fn effective_policies(policies: &[Policy], at: DateTime<Utc>) -> Vec<&Policy> {
+    let mut current: HashMap<Uuid, &Policy> = HashMap::new();
+    for policy in policies {
+        if policy.effective_from.map_or(false, |from| from > at) {
+            continue;
+        }
+        let entry = current.entry(policy.id.as_uuid()).or_insert(policy);
+        if policy.version > entry.version {
+            *entry = policy;
+        }
+    }
+    // Keep the input order for stable evaluation output
+    policies
+        .iter()
+        .filter(|p| current.get(&p.id.as_uuid()).is_some_and(|c| std::ptr::eq(*c, *p)))
+        .filter(|p| p.is_effective_at(at))
+        .collect()
+}
+
 /// Individual claim (capability/permission)
 ///
 /// Claims represent atomic permissions. They compose additively:
 /// Policy A: [CanSignCode] + Policy B: [CanAccessProd] = [CanSignCode, CanAccessProd]
+/// A denied claim is subtracted unless a higher-priority policy grants it.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PolicyClaim {
     // ===== Key Management Claims =====
@@ -1211,6 +1347,12 @@ pub enum PolicyCondition {
         end_hour: u8,
     },
 
+    /// Exported secret key material must be passphrase-encrypted
+    ///
+    /// An obligation rather than a gate: it never deactivates the policy,
+    /// it constrains how the granted export claims may be exercised.
+    RequiresEncryptedExport,
+
     /// Custom condition (evaluated by external system)
     Custom {
         name: String,
@@ -1257,7 +1399,7 @@ pub struct PolicyBinding {
 }
 
 /// Types of entities that policies can govern
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PolicyEntityType {
     Organization,
     OrganizationalUnit,
@@ -1269,7 +1411,8 @@ pub enum PolicyEntityType {
 
 /// Result of policy evaluation for an entity
 ///
-/// Collects all active claims from all applicable policies.
+/// Collects all active claims from all applicable policies, minus the
+/// claims denied by them.
 #[derive(Debug, Clone)]
 pub struct PolicyEvaluation {
     /// Entity being evaluated
@@ -1279,13 +1422,19 @@ pub struct PolicyEvaluation {
     /// All active policies that apply
     pub active_policies: Vec<Uuid>,
 
+    /// Version of each applicable policy in force at `evaluated_at`
+    pub policy_versions: Vec<(Uuid, u32)>,
+
     /// All inactive policies (conditions not met)
     pub inactive_policies: Vec<(Uuid, Vec<String>)>, // (policy_id, reasons)
 
-    /// Union of all claims from active policies
+    /// Union of all claims from active policies, without denied claims
     pub granted_claims: Vec<PolicyClaim>,
 
-    /// Timestamp of evaluation
+    /// Claims withheld by a deny of active policies
+    pub denied_claims: Vec<PolicyClaim>,
+
+    /// Instant the policies were evaluated at (the context's current time)
     pub evaluated_at: DateTime<Utc>,
 }
 
@@ -1305,6 +1454,16 @@ impl Policy {
             Vec::new()
         }
     }
+
+    /// Whether this policy grants key export only in encrypted form
+    pub fn requires_encrypted_export(&self) -> bool {
+        self.enabled
+            && self.claims.contains(&PolicyClaim::CanExportKeys)
+            && self
+                .conditions
+                .iter()
+                .any(|c| matches!(c, PolicyCondition::RequiresEncryptedExport))
+    }
 }
 
 impl PolicyCondition {
@@ -1377,6 +1536,8 @@ impl PolicyCondition {
                 hour >= *start_hour as u32 && hour < *end_hour as u32
             }
 
+            PolicyCondition::RequiresEncryptedExport => true,
+
             PolicyCondition::Custom { name: _, parameters: _ } => {
                 // Custom conditions evaluated by external system
                 // For now, return false (requires external evaluation)
@@ -1408,6 +1569,29 @@ pub struct PolicyEvaluationContext {
     pub witnesses: Vec<WitnessInfo>,
 }
 
+impl PolicyEvaluationContext {
+    /// Context of a person acting now, with no further security context
+    ///
+    /// Callers set MFA, YubiKey presence and witnesses as they verify them.
+    pub fn for_person(person_id: Uuid) -> Self {
+        let now = Utc::now();
+        Self {
+            person_id,
+            person_clearance: SecurityClearance::Public,
+            person_units: Vec::new(),
+            person_roles: Vec::new(),
+            employment_start_date: now,
+            completed_training: Vec::new(),
+            current_time: now,
+            current_location: None,
+            source_ip: None,
+            mfa_verified: false,
+            yubikey_present: false,
+            witnesses: Vec::new(),
+        }
+    }
+}
+
 /// Information about a witness
 #[derive(Debug, Clone)]
 pub struct WitnessInfo {
@@ -1416,6 +1600,15 @@ pub struct WitnessInfo {
 }
 
 /// Evaluate all policies applicable to an entity
+///
+/// Each claim is decided by the highest-priority active policy that grants
+/// or denies it; at equal priority a deny wins. A deny therefore subtracts
+/// a claim granted by lower-priority policies, and a higher-priority grant
+/// overrides a deny.
+///
+/// Only the policy versions in force at `context.current_time` take part
+/// (see `effective_policies`), so evaluating the same context again later
+/// reproduces the same result.
 pub fn evaluate_policies(
     policies: &[Policy],
     bindings: &[PolicyBinding],
@@ -1430,10 +1623,14 @@ pub fn evaluate_policies(
         .map(|b| b.policy_id)
         .collect();
 
-    let applicable_policies: Vec<&Policy> = policies
-        .iter()
+    let applicable_policies: Vec<&Policy> = effective_policies(policies, context.current_time)
+        .into_iter()
         .filter(|p| applicable_policy_ids.contains(&p.id.as_uuid()))
         .collect();
+    let policy_versions = applicable_policies
+        .iter()
+        .map(|p| (p.id.as_uuid(), p.version))
+        .collect();
 
     // Sort by priority (higher priority first)
     let mut sorted_policies = applicable_policies.clone();
@@ -1441,13 +1638,23 @@ pub fn evaluate_policies(
 
     let mut active_policies = Vec::new();
     let mut inactive_policies = Vec::new();
-    let mut all_claims = Vec::new();
+    // Claim → (priority of the deciding policy, granted)
+    let mut decisions: HashMap<PolicyClaim, (i32, bool)> = HashMap::new();
 
     for policy in sorted_policies {
         if policy.enabled {
             if policy.evaluate_conditions(context) {
                 active_policies.push(policy.id.as_uuid());
-                all_claims.extend(policy.claims.clone());
+                let grants = policy.claims.iter().map(|claim| (claim, true));
+                let denies = policy.denied_claims.iter().map(|claim| (claim, false));
+                for (claim, granted) in grants.chain(denies) {
+                    // Policies come in descending priority, so a decision
+                    // only changes for a deny at the deciding priority
+                    let decision = decisions.entry(claim.clone()).or_insert((policy.priority, granted));
+                    if decision.0 == policy.priority && !granted {
+                        decision.1 = false;
+                    }
+                }
             } else {
                 let reasons = policy.conditions
                     .iter()
@@ -1459,17 +1666,22 @@ pub fn evaluate_policies(
         }
     }
 
-    // Deduplicate claims (union)
-    all_claims.sort_by_key(|c| format!("{:?}", c));
-    all_claims.dedup();
+    let (granted, denied): (Vec<_>, Vec<_>) = decisions.into_iter().partition(|(_, (_, granted))| *granted);
+    let sorted = |decided: Vec<(PolicyClaim, (i32, bool))>| {
+        let mut claims: Vec<PolicyClaim> = decided.into_iter().map(|(claim, _)| claim).collect();
+        claims.sort_by_key(|c| format!("{:?}", c));
+        claims
+    };
 
     PolicyEvaluation {
         entity_id,
         entity_type,
         active_policies,
+        policy_versions,
         inactive_policies,
-        granted_claims: all_claims,
-        evaluated_at: Utc::now(),
+        granted_claims: sorted(granted),
+        denied_claims: sorted(denied),
+        evaluated_at: context.current_time,
     }
 }
 
@@ -253,9 +253,13 @@ mod tests {
             name: "Admin Policy".to_string(),
             description: "Administrative access".to_string(),
             claims: vec![],
+            denied_claims: vec![],
             conditions: vec![],
             priority: 100,
             enabled: true,
+            version: 1,
+            effective_from: None,
+            effective_until: None,
             created_by: BootstrapPersonId::new(),
             metadata: std::collections::HashMap::new(),
         };
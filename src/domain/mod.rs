@@ -69,6 +69,15 @@ pub mod graph;
 #[cfg(feature = "gui")]
 pub mod foldable_impls;
 
+/// Approval Quorum for Sensitive Commands
+pub mod approval;
+
+/// Memoized Policy Evaluation
+pub mod policy_cache;
+
+/// Separation of Duties Between Claims
+pub mod separation_of_duties;
+
 /// Cross-Context Invariants for Domain Integrity
 pub mod invariants;
 
@@ -117,6 +126,7 @@ pub use sagas::{
     CompleteBootstrapSaga,
     PersonOnboardingSaga,
     CertificateProvisioningSaga,
+    SshKeyRotationSaga,
 };
 
 // Re-export graph types for domain relationships
@@ -133,6 +143,28 @@ pub use graph::{
     GraphDomainEvent,
 };
 
+// Re-export approval quorum types
+pub use approval::{
+    ApprovalRule,
+    ApprovalStatus,
+    PendingApproval,
+    SensitiveCommandKind,
+};
+
+// Re-export policy evaluation cache types
+pub use policy_cache::{
+    PolicyCacheKey,
+    PolicyCacheMetrics,
+    PolicyEvaluationCache,
+    DEFAULT_CACHE_CAPACITY,
+};
+
+// Re-export separation of duties types
+pub use separation_of_duties::{
+    separation_violations,
+    SeparationOfDutiesRule,
+};
+
 // Re-export cross-context invariant types
 pub use invariants::{
     // Core invariant types
@@ -0,0 +1,869 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! NATS JWT Claims
+//!
+//! Builds, signs and verifies the operator, account and user JWTs that
+//! nats-server resolves, in the v2 format `nsc` writes, so the NSC store
+//! projection (`projection::nscstore`) can be fed without shelling out to
+//! `nsc`.
+//!
+//! ```text
+//! base64url({"typ":"JWT","alg":"ed25519-nkey"})
+//!   . base64url({"jti":…,"iat":…,"iss":…,"name":…,"sub":…,"exp":…,"nats":{…,"type":"user","version":2}})
+//!   . base64url(ed25519 signature of the two parts above)
+//! ```
+//!
+//! The `jti` is the base32 SHA-512/256 of the claims serialized without it,
+//! as in nats-io/jwt.
+//!
+//! | Claims   | Subject         | Signed by                                       |
+//! |----------|-----------------|-------------------------------------------------|
+//! | operator | operator key    | the operator key itself                         |
+//! | account  | account key     | operator key or operator signing key            |
+//! | user     | user key        | account key, or account signing key with        |
+//! |          |                 | `issuer_account` set to the account             |
+//! | activation | importing account | exporting account key (or its signing key)    |
+//!
+//! Accounts share subjects through `exports` and use other accounts'
+//! subjects through `imports`; importing a private export (`token_req`)
+//! needs an activation JWT issued by the exporting account.
+//!
+//! ```ignore
+//! let jwt = NatsClaims::user("alice", alice.public_key_string(), account.public_key_string())
+//!     .with_permissions(ClaimPermissions::default().allow_publish("orders.>"))
+//!     .with_expiry(Utc::now() + Duration::days(90))
+//!     .encode(&account)?;
+//! ```
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512_256};
+use thiserror::Error;
+
+use crate::value_objects::{NKeyPair, NKeyType};
+
+/// JWT `alg` for nkey-signed claims
+pub const NKEY_JWT_ALGORITHM: &str = "ed25519-nkey";
+
+/// Claims format version understood by nats-server 2.2+
+pub const NATS_CLAIMS_VERSION: u32 = 2;
+
+/// `allowed_connection_types` entry restricting a user to leafnode connections
+pub const CONNECTION_TYPE_LEAFNODE: &str = "LEAFNODE";
+
+/// Claims errors
+#[derive(Debug, Error)]
+pub enum ClaimsError {
+    #[error("{claim_type:?} claims cannot be signed by a {signer:?} key")]
+    InvalidSigner {
+        claim_type: ClaimType,
+        signer: NKeyType,
+    },
+
+    #[error("Operator claims must be self-signed")]
+    NotSelfSigned,
+
+    #[error("Invalid {role} public key: {key}")]
+    InvalidPublicKey { role: &'static str, key: String },
+
+    #[error("Expected {expected:?} claims, found {found:?}")]
+    WrongClaimType {
+        expected: ClaimType,
+        found: ClaimType,
+    },
+
+    #[error("Malformed JWT: {0}")]
+    Malformed(String),
+
+    #[error("Signing failed: {0}")]
+    Signing(String),
+
+    #[error("Signature does not verify against issuer {0}")]
+    BadSignature(String),
+}
+
+/// `nats.type` of a JWT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaimType {
+    Operator,
+    Account,
+    User,
+    Activation,
+}
+
+/// JOSE header of a NATS JWT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct JwtHeader {
+    typ: String,
+    alg: String,
+}
+
+/// Registered claims plus the `nats` section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NatsClaims<T> {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub jti: String,
+    pub iat: i64,
+    pub iss: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub sub: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    pub nats: T,
+}
+
+/// The `nats` section of one kind of claims
+pub trait ClaimData: Serialize + DeserializeOwned + Clone {
+    const CLAIM_TYPE: ClaimType;
+
+    /// Key type that signs these claims (identity or signing key)
+    const SIGNER: NKeyType;
+
+    /// Adjust the section when `signer` is not the declared issuer
+    fn bind_issuer(&mut self, _subject: &str, _issuer: &str, _signer: &str) -> Result<(), ClaimsError> {
+        Ok(())
+    }
+}
+
+/// Publish or subscribe allow/deny subjects
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Permission {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl Permission {
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+}
+
+/// Publish and subscribe permissions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimPermissions {
+    #[serde(rename = "pub", default, skip_serializing_if = "Permission::is_empty")]
+    pub publish: Permission,
+    #[serde(rename = "sub", default, skip_serializing_if = "Permission::is_empty")]
+    pub subscribe: Permission,
+}
+
+impl ClaimPermissions {
+    pub fn allow_publish(mut self, subject: impl Into<String>) -> Self {
+        self.publish.allow.push(subject.into());
+        self
+    }
+
+    pub fn deny_publish(mut self, subject: impl Into<String>) -> Self {
+        self.publish.deny.push(subject.into());
+        self
+    }
+
+    pub fn allow_subscribe(mut self, subject: impl Into<String>) -> Self {
+        self.subscribe.allow.push(subject.into());
+        self
+    }
+
+    pub fn deny_subscribe(mut self, subject: impl Into<String>) -> Self {
+        self.subscribe.deny.push(subject.into());
+        self
+    }
+}
+
+/// Subscription, data and payload limits (-1 = unlimited)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimLimits {
+    pub subs: i64,
+    pub data: i64,
+    pub payload: i64,
+}
+
+impl Default for ClaimLimits {
+    fn default() -> Self {
+        Self {
+            subs: -1,
+            data: -1,
+            payload: -1,
+        }
+    }
+}
+
+/// JetStream quotas of an account (-1 = unlimited, all 0 = JetStream disabled)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JetStreamClaimLimits {
+    pub mem_storage: i64,
+    pub disk_storage: i64,
+    pub streams: i64,
+    pub consumer: i64,
+}
+
+impl JetStreamClaimLimits {
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+/// Account limits set by the operator (-1 = unlimited)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountClaimLimits {
+    #[serde(flatten)]
+    pub nats: ClaimLimits,
+    pub imports: i64,
+    pub exports: i64,
+    pub wildcards: bool,
+    pub conn: i64,
+    pub leaf: i64,
+    #[serde(flatten)]
+    pub jetstream: JetStreamClaimLimits,
+}
+
+impl Default for AccountClaimLimits {
+    fn default() -> Self {
+        Self {
+            nats: ClaimLimits::default(),
+            imports: -1,
+            exports: -1,
+            wildcards: true,
+            conn: -1,
+            leaf: -1,
+            jetstream: JetStreamClaimLimits::default(),
+        }
+    }
+}
+
+/// What an export shares: messages (stream) or request/reply (service)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportType {
+    Stream,
+    Service,
+}
+
+/// Subjects an account shares with other accounts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimExport {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub subject: String,
+    #[serde(rename = "type")]
+    pub export_type: ExportType,
+    /// Importers need an activation JWT from this account
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub token_req: bool,
+}
+
+/// Subjects an account uses from another account's export
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimImport {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    /// Subject as exported
+    pub subject: String,
+    /// Exporting account public key
+    pub account: String,
+    /// Activation JWT for a private export
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Subject the import appears under in this account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_subject: Option<String>,
+    #[serde(rename = "type")]
+    pub export_type: ExportType,
+}
+
+/// `nats` section of an operator JWT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperatorClaimData {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signing_keys: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_server_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operator_service_urls: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_account: Option<String>,
+    #[serde(rename = "type")]
+    pub claim_type: ClaimType,
+    pub version: u32,
+}
+
+/// `nats` section of an account JWT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountClaimData {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signing_keys: Vec<String>,
+    pub limits: AccountClaimLimits,
+    #[serde(default)]
+    pub default_permissions: ClaimPermissions,
+    /// User public key → unix time; user JWTs issued at or before it are revoked
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub revocations: BTreeMap<String, i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exports: Vec<ClaimExport>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<ClaimImport>,
+    #[serde(rename = "type")]
+    pub claim_type: ClaimType,
+    pub version: u32,
+}
+
+/// `nats` section of a user JWT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserClaimData {
+    #[serde(flatten)]
+    pub permissions: ClaimPermissions,
+    #[serde(flatten)]
+    pub limits: ClaimLimits,
+    /// Account the user belongs to, when signed by an account signing key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_account: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bearer_token: bool,
+    /// Connection types the user may use (`STANDARD`, `LEAFNODE`, …); any if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_connection_types: Vec<String>,
+    #[serde(rename = "type")]
+    pub claim_type: ClaimType,
+    pub version: u32,
+}
+
+/// `nats` section of an activation JWT: permission to import a private export
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ActivationClaimData {
+    /// Exported subject the importer may use
+    pub subject: String,
+    pub kind: ExportType,
+    /// Exporting account, when signed by an account signing key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer_account: Option<String>,
+    #[serde(rename = "type")]
+    pub claim_type: ClaimType,
+    pub version: u32,
+}
+
+impl ClaimData for OperatorClaimData {
+    const CLAIM_TYPE: ClaimType = ClaimType::Operator;
+    const SIGNER: NKeyType = NKeyType::Operator;
+
+    fn bind_issuer(&mut self, subject: &str, _issuer: &str, signer: &str) -> Result<(), ClaimsError> {
+        if signer != subject {
+            return Err(ClaimsError::NotSelfSigned);
+        }
+        Ok(())
+    }
+}
+
+impl ClaimData for AccountClaimData {
+    const CLAIM_TYPE: ClaimType = ClaimType::Account;
+    const SIGNER: NKeyType = NKeyType::Operator;
+}
+
+impl ClaimData for UserClaimData {
+    const CLAIM_TYPE: ClaimType = ClaimType::User;
+    const SIGNER: NKeyType = NKeyType::Account;
+
+    fn bind_issuer(&mut self, _subject: &str, issuer: &str, signer: &str) -> Result<(), ClaimsError> {
+        self.issuer_account = (signer != issuer).then(|| issuer.to_string());
+        Ok(())
+    }
+}
+
+impl ClaimData for ActivationClaimData {
+    const CLAIM_TYPE: ClaimType = ClaimType::Activation;
+    const SIGNER: NKeyType = NKeyType::Account;
+
+    fn bind_issuer(&mut self, _subject: &str, issuer: &str, signer: &str) -> Result<(), ClaimsError> {
+        self.issuer_account = (signer != issuer).then(|| issuer.to_string());
+        Ok(())
+    }
+}
+
+impl<T> NatsClaims<T> {
+    fn with_subject(name: impl Into<String>, subject: &str, issuer: &str, nats: T) -> Self {
+        Self {
+            jti: String::new(),
+            iat: Utc::now().timestamp(),
+            iss: issuer.to_string(),
+            name: name.into(),
+            sub: subject.to_string(),
+            exp: None,
+            nbf: None,
+            nats,
+        }
+    }
+
+    /// Set expiry
+    pub fn with_expiry(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.exp = Some(expires_at.timestamp());
+        self
+    }
+
+    /// Set the time before which the JWT is not valid
+    pub fn with_not_before(mut self, not_before: DateTime<Utc>) -> Self {
+        self.nbf = Some(not_before.timestamp());
+        self
+    }
+
+    /// Set issued-at (now by default)
+    pub fn with_issued_at(mut self, issued_at: DateTime<Utc>) -> Self {
+        self.iat = issued_at.timestamp();
+        self
+    }
+
+    /// Whether the JWT is expired or not yet valid at `now`
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        let now = now.timestamp();
+        self.exp.is_none_or(|exp| now < exp) && self.nbf.is_none_or(|nbf| now >= nbf)
+    }
+}
+
+impl NatsClaims<OperatorClaimData> {
+    /// Self-signed operator claims
+    pub fn operator(name: impl Into<String>, operator_public_key: &str) -> Self {
+        Self::with_subject(
+            name,
+            operator_public_key,
+            operator_public_key,
+            OperatorClaimData {
+                signing_keys: Vec::new(),
+                account_server_url: None,
+                operator_service_urls: Vec::new(),
+                system_account: None,
+                claim_type: ClaimType::Operator,
+                version: NATS_CLAIMS_VERSION,
+            },
+        )
+    }
+
+    /// Add an operator signing key (may sign account JWTs)
+    pub fn with_signing_key(mut self, public_key: impl Into<String>) -> Self {
+        self.nats.signing_keys.push(public_key.into());
+        self
+    }
+
+    /// Set the system account
+    pub fn with_system_account(mut self, account_public_key: impl Into<String>) -> Self {
+        self.nats.system_account = Some(account_public_key.into());
+        self
+    }
+
+    /// Set the account resolver URL
+    pub fn with_account_server_url(mut self, url: impl Into<String>) -> Self {
+        self.nats.account_server_url = Some(url.into());
+        self
+    }
+
+    /// Add a server URL clients of this operator connect to
+    pub fn with_operator_service_url(mut self, url: impl Into<String>) -> Self {
+        self.nats.operator_service_urls.push(url.into());
+        self
+    }
+}
+
+impl NatsClaims<AccountClaimData> {
+    /// Account claims issued by `operator_public_key`
+    pub fn account(name: impl Into<String>, account_public_key: &str, operator_public_key: &str) -> Self {
+        Self::with_subject(
+            name,
+            account_public_key,
+            operator_public_key,
+            AccountClaimData {
+                signing_keys: Vec::new(),
+                limits: AccountClaimLimits::default(),
+                default_permissions: ClaimPermissions::default(),
+                revocations: BTreeMap::new(),
+                exports: Vec::new(),
+                imports: Vec::new(),
+                claim_type: ClaimType::Account,
+                version: NATS_CLAIMS_VERSION,
+            },
+        )
+    }
+
+    /// Add an account signing key (may sign user JWTs)
+    pub fn with_signing_key(mut self, public_key: impl Into<String>) -> Self {
+        self.nats.signing_keys.push(public_key.into());
+        self
+    }
+
+    /// Set account limits
+    pub fn with_limits(mut self, limits: AccountClaimLimits) -> Self {
+        self.nats.limits = limits;
+        self
+    }
+
+    /// Set permissions of users that have none of their own
+    pub fn with_default_permissions(mut self, permissions: ClaimPermissions) -> Self {
+        self.nats.default_permissions = permissions;
+        self
+    }
+
+    /// Revoke user JWTs of `user_public_key` issued at or before `revoked_at`
+    pub fn with_revocation(mut self, user_public_key: impl Into<String>, revoked_at: DateTime<Utc>) -> Self {
+        self.nats.revocations.insert(user_public_key.into(), revoked_at.timestamp());
+        self
+    }
+
+    /// Share subjects with other accounts
+    pub fn with_export(mut self, export: ClaimExport) -> Self {
+        self.nats.exports.push(export);
+        self
+    }
+
+    /// Use subjects exported by another account
+    pub fn with_import(mut self, import: ClaimImport) -> Self {
+        self.nats.imports.push(import);
+        self
+    }
+
+    /// Whether these account claims revoke `user`
+    pub fn revokes(&self, user: &NatsClaims<UserClaimData>) -> bool {
+        self.nats
+            .revocations
+            .get(&user.sub)
+            .is_some_and(|&revoked_at| user.iat <= revoked_at)
+    }
+}
+
+impl NatsClaims<UserClaimData> {
+    /// User claims of the account `account_public_key`
+    pub fn user(name: impl Into<String>, user_public_key: &str, account_public_key: &str) -> Self {
+        Self::with_subject(
+            name,
+            user_public_key,
+            account_public_key,
+            UserClaimData {
+                permissions: ClaimPermissions::default(),
+                limits: ClaimLimits::default(),
+                issuer_account: None,
+                bearer_token: false,
+                allowed_connection_types: Vec::new(),
+                claim_type: ClaimType::User,
+                version: NATS_CLAIMS_VERSION,
+            },
+        )
+    }
+
+    /// Set publish and subscribe permissions
+    pub fn with_permissions(mut self, permissions: ClaimPermissions) -> Self {
+        self.nats.permissions = permissions;
+        self
+    }
+
+    /// Set subscription, data and payload limits
+    pub fn with_limits(mut self, limits: ClaimLimits) -> Self {
+        self.nats.limits = limits;
+        self
+    }
+
+    /// Allow connecting with the JWT alone, without proving the seed
+    pub fn with_bearer_token(mut self, bearer: bool) -> Self {
+        self.nats.bearer_token = bearer;
+        self
+    }
+
+    /// Restrict the connection types the user may connect with
+    pub fn with_allowed_connection_types<I, S>(mut self, connection_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.nats.allowed_connection_types = connection_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Account the user belongs to, whoever signed it
+    pub fn account_public_key(&self) -> &str {
+        self.nats.issuer_account.as_deref().unwrap_or(&self.iss)
+    }
+}
+
+impl NatsClaims<ActivationClaimData> {
+    /// Activation of `exporter_public_key`'s export of `subject` for the
+    /// importing account `importer_public_key`
+    pub fn activation(
+        subject: impl Into<String>,
+        kind: ExportType,
+        importer_public_key: &str,
+        exporter_public_key: &str,
+    ) -> Self {
+        let subject = subject.into();
+        Self::with_subject(
+            subject.clone(),
+            importer_public_key,
+            exporter_public_key,
+            ActivationClaimData {
+                subject,
+                kind,
+                issuer_account: None,
+                claim_type: ClaimType::Activation,
+                version: NATS_CLAIMS_VERSION,
+            },
+        )
+    }
+
+    /// Account that issued the activation, whoever signed it
+    pub fn exporter_public_key(&self) -> &str {
+        self.nats.issuer_account.as_deref().unwrap_or(&self.iss)
+    }
+}
+
+impl<T: ClaimData> NatsClaims<T> {
+    /// Sign with `signer` and return the encoded JWT
+    ///
+    /// `signer` becomes `iss`. Account and user claims may be signed by a
+    /// signing key of their issuer; user claims then record the account in
+    /// `issuer_account`.
+    pub fn encode(&self, signer: &NKeyPair) -> Result<String, ClaimsError> {
+        if signer.key_type != T::SIGNER {
+            return Err(ClaimsError::InvalidSigner {
+                claim_type: T::CLAIM_TYPE,
+                signer: signer.key_type,
+            });
+        }
+        check_public_key("subject", &self.sub)?;
+        check_public_key("issuer", &self.iss)?;
+
+        let signer_key = signer.public_key_string();
+        let mut claims = self.clone();
+        claims.nats.bind_issuer(&self.sub, &self.iss, signer_key)?;
+        claims.iss = signer_key.to_string();
+        claims.jti = String::new();
+        claims.jti = base32_no_pad(&Sha512_256::digest(to_json(&claims)?));
+
+        let header = JwtHeader {
+            typ: "JWT".to_string(),
+            alg: NKEY_JWT_ALGORITHM.to_string(),
+        };
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(to_json(&header)?),
+            URL_SAFE_NO_PAD.encode(to_json(&claims)?)
+        );
+        let signature = signer.sign(signing_input.as_bytes()).map_err(ClaimsError::Signing)?;
+
+        Ok(format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature)))
+    }
+
+    /// Decode a JWT and verify its type and signature against `iss`
+    ///
+    /// Does not check expiry or that `iss` is trusted; see
+    /// [`NatsClaims::is_valid_at`].
+    pub fn decode(token: &str) -> Result<Self, ClaimsError> {
+        let parts: Vec<&str> = token.trim().split('.').collect();
+        let [header, payload, signature] = parts[..] else {
+            return Err(ClaimsError::Malformed("expected three dot-separated parts".to_string()));
+        };
+
+        let header: JwtHeader = from_base64_json(header)?;
+        if header.alg != NKEY_JWT_ALGORITHM {
+            return Err(ClaimsError::Malformed(format!("unsupported alg {}", header.alg)));
+        }
+
+        let raw: serde_json::Value = from_base64_json(payload)?;
+        let found: ClaimType = serde_json::from_value(raw["nats"]["type"].clone())
+            .map_err(|e| ClaimsError::Malformed(format!("nats.type: {}", e)))?;
+        if found != T::CLAIM_TYPE {
+            return Err(ClaimsError::WrongClaimType {
+                expected: T::CLAIM_TYPE,
+                found,
+            });
+        }
+        let claims: Self =
+            serde_json::from_value(raw).map_err(|e| ClaimsError::Malformed(e.to_string()))?;
+
+        let issuer = check_public_key("issuer", &claims.iss)?;
+        if !claims.iss.starts_with(T::SIGNER.prefix()) {
+            return Err(ClaimsError::InvalidPublicKey {
+                role: "issuer",
+                key: claims.iss,
+            });
+        }
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|e| ClaimsError::Malformed(format!("signature: {}", e)))?;
+        let signing_input = &token.trim()[..header_and_payload_len(token.trim())];
+        issuer
+            .verify(signing_input.as_bytes(), &signature)
+            .map_err(|_| ClaimsError::BadSignature(claims.iss.clone()))?;
+
+        Ok(claims)
+    }
+}
+
+fn check_public_key(role: &'static str, key: &str) -> Result<nkeys::KeyPair, ClaimsError> {
+    nkeys::KeyPair::from_public_key(key).map_err(|_| ClaimsError::InvalidPublicKey {
+        role,
+        key: key.to_string(),
+    })
+}
+
+fn header_and_payload_len(token: &str) -> usize {
+    token.rfind('.').unwrap_or(token.len())
+}
+
+fn to_json<S: Serialize>(value: &S) -> Result<Vec<u8>, ClaimsError> {
+    serde_json::to_vec(value).map_err(|e| ClaimsError::Malformed(e.to_string()))
+}
+
+fn from_base64_json<D: DeserializeOwned>(part: &str) -> Result<D, ClaimsError> {
+    let json = URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|e| ClaimsError::Malformed(e.to_string()))?;
+    serde_json::from_slice(&json).map_err(|e| ClaimsError::Malformed(e.to_string()))
+}
+
+/// RFC 4648 base32, upper case, no padding
+fn base32_no_pad(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projection::nscstore::{
+        credentials_to_nscstore, AccountCredentials, DomainNatsCredentials, OperatorCredentials, UserCredentials,
+    };
+    use crate::projection::Projection;
+    use std::collections::HashMap;
+
+    fn key(key_type: NKeyType) -> NKeyPair {
+        NKeyPair::generate(key_type, None).unwrap()
+    }
+
+    #[test]
+    fn test_base32_matches_rfc4648_vectors() {
+        assert_eq!(base32_no_pad(b"f"), "MY");
+        assert_eq!(base32_no_pad(b"fooba"), "MZXW6YTB");
+        assert_eq!(base32_no_pad(b"foobar"), "MZXW6YTBOI");
+    }
+
+    #[test]
+    fn test_hierarchy_signs_and_verifies() {
+        let operator = key(NKeyType::Operator);
+        let account = key(NKeyType::Account);
+        let account_signer = key(NKeyType::Account);
+        let user = key(NKeyType::User);
+
+        let operator_jwt = NatsClaims::operator("cowboyai", operator.public_key_string())
+            .with_system_account(account.public_key_string())
+            .encode(&operator)
+            .unwrap();
+        let account_jwt = NatsClaims::account("engineering", account.public_key_string(), operator.public_key_string())
+            .with_signing_key(account_signer.public_key_string())
+            .encode(&operator)
+            .unwrap();
+        let user_jwt = NatsClaims::user("alice", user.public_key_string(), account.public_key_string())
+            .with_permissions(ClaimPermissions::default().allow_publish("orders.>").allow_subscribe("_INBOX.>"))
+            .with_expiry(Utc::now() + chrono::Duration::days(30))
+            .encode(&account_signer)
+            .unwrap();
+
+        let decoded = NatsClaims::<OperatorClaimData>::decode(&operator_jwt).unwrap();
+        assert_eq!(decoded.sub, operator.public_key_string());
+        assert_eq!(decoded.jti.len(), 52);
+
+        let decoded = NatsClaims::<UserClaimData>::decode(&user_jwt).unwrap();
+        assert_eq!(decoded.iss, account_signer.public_key_string());
+        assert_eq!(decoded.account_public_key(), account.public_key_string());
+        assert_eq!(decoded.nats.permissions.publish.allow, vec!["orders.>".to_string()]);
+        assert!(decoded.is_valid_at(Utc::now()));
+
+        // The NSC store projection writes the JWTs as issued
+        let credentials = DomainNatsCredentials {
+            organization_id: uuid::Uuid::now_v7(),
+            organization_name: "Cowboy AI".to_string(),
+            operator: OperatorCredentials {
+                name: "cowboyai".to_string(),
+                jwt: operator_jwt,
+                public_key: operator.public_key_string().to_string(),
+                signing_keys: vec![],
+                system_account: Some(account.public_key_string().to_string()),
+            },
+            accounts: HashMap::from([(
+                "engineering".to_string(),
+                AccountCredentials {
+                    name: "engineering".to_string(),
+                    jwt: account_jwt.clone(),
+                    public_key: account.public_key_string().to_string(),
+                    operator_public_key: operator.public_key_string().to_string(),
+                    signing_keys: vec![account_signer.public_key_string().to_string()],
+                },
+            )]),
+            users: HashMap::from([(
+                "engineering".to_string(),
+                vec![UserCredentials {
+                    name: "alice".to_string(),
+                    jwt: user_jwt,
+                    public_key: user.public_key_string().to_string(),
+                    seed: None,
+                    account_public_key: account.public_key_string().to_string(),
+                    person_id: None,
+                }],
+            )]),
+            generated_at: Utc::now(),
+        };
+        let store = credentials_to_nscstore().project(credentials).unwrap();
+        assert!(store.files.iter().any(|f| f.content == account_jwt));
+    }
+
+    #[test]
+    fn test_wrong_signer_and_tampering_rejected() {
+        let operator = key(NKeyType::Operator);
+        let account = key(NKeyType::Account);
+        let user = key(NKeyType::User);
+
+        let claims = NatsClaims::user("alice", user.public_key_string(), account.public_key_string());
+        assert!(matches!(
+            claims.encode(&operator),
+            Err(ClaimsError::InvalidSigner { signer: NKeyType::Operator, .. })
+        ));
+        assert!(matches!(
+            NatsClaims::operator("cowboyai", operator.public_key_string()).encode(&key(NKeyType::Operator)),
+            Err(ClaimsError::NotSelfSigned)
+        ));
+
+        let jwt = claims.encode(&account).unwrap();
+        assert!(matches!(
+            NatsClaims::<AccountClaimData>::decode(&jwt),
+            Err(ClaimsError::WrongClaimType { found: ClaimType::User, .. })
+        ));
+
+        let forged = NatsClaims::user("mallory", user.public_key_string(), account.public_key_string())
+            .encode(&account)
+            .unwrap();
+        let spliced = format!(
+            "{}.{}",
+            &forged[..header_and_payload_len(&forged)],
+            &jwt[header_and_payload_len(&jwt) + 1..]
+        );
+        assert!(matches!(
+            NatsClaims::<UserClaimData>::decode(&spliced),
+            Err(ClaimsError::BadSignature(_))
+        ));
+    }
+}
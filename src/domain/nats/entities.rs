@@ -197,7 +197,10 @@ pub struct NatsImport {
 }
 
 /// Account resource limits
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `None` leaves a limit unset (unlimited). JetStream is enabled for the
+/// account only when a memory or storage quota is given.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AccountLimits {
     pub max_connections: Option<i64>,
     pub max_subscriptions: Option<i64>,
@@ -205,6 +208,16 @@ pub struct AccountLimits {
     pub max_data: Option<i64>,
     pub max_imports: Option<i64>,
     pub max_exports: Option<i64>,
+    /// JetStream memory storage in bytes
+    #[serde(default)]
+    pub max_jetstream_memory: Option<i64>,
+    /// JetStream file storage in bytes
+    #[serde(default)]
+    pub max_jetstream_storage: Option<i64>,
+    #[serde(default)]
+    pub max_streams: Option<i64>,
+    #[serde(default)]
+    pub max_consumers: Option<i64>,
 }
 
 impl Default for AccountLimits {
@@ -216,10 +229,138 @@ impl Default for AccountLimits {
             max_data: None,
             max_imports: Some(10),
             max_exports: Some(10),
+            max_jetstream_memory: None,
+            max_jetstream_storage: None,
+            max_streams: None,
+            max_consumers: None,
+        }
+    }
+}
+
+impl AccountLimits {
+    /// Whether the account gets JetStream
+    pub fn jetstream_enabled(&self) -> bool {
+        self.max_jetstream_memory.is_some() || self.max_jetstream_storage.is_some()
+    }
+
+    /// Named limits, for policy checks and display
+    fn entries(&self) -> [(&'static str, Option<i64>); 10] {
+        [
+            ("max_connections", self.max_connections),
+            ("max_subscriptions", self.max_subscriptions),
+            ("max_payload", self.max_payload),
+            ("max_data", self.max_data),
+            ("max_imports", self.max_imports),
+            ("max_exports", self.max_exports),
+            ("max_jetstream_memory", self.max_jetstream_memory),
+            ("max_jetstream_storage", self.max_jetstream_storage),
+            ("max_streams", self.max_streams),
+            ("max_consumers", self.max_consumers),
+        ]
+    }
+
+    /// Limits as encoded in the account JWT
+    pub fn to_claim_limits(&self) -> super::claims::AccountClaimLimits {
+        use super::claims::{AccountClaimLimits, ClaimLimits, JetStreamClaimLimits};
+
+        let unlimited = |limit: Option<i64>| limit.unwrap_or(-1);
+        let jetstream = if self.jetstream_enabled() {
+            JetStreamClaimLimits {
+                mem_storage: self.max_jetstream_memory.unwrap_or(0),
+                disk_storage: self.max_jetstream_storage.unwrap_or(0),
+                streams: unlimited(self.max_streams),
+                consumer: unlimited(self.max_consumers),
+            }
+        } else {
+            JetStreamClaimLimits::default()
+        };
+
+        AccountClaimLimits {
+            nats: ClaimLimits {
+                subs: unlimited(self.max_subscriptions),
+                data: unlimited(self.max_data),
+                payload: unlimited(self.max_payload),
+            },
+            imports: unlimited(self.max_imports),
+            exports: unlimited(self.max_exports),
+            wildcards: true,
+            conn: unlimited(self.max_connections),
+            leaf: -1,
+            jetstream,
         }
     }
 }
 
+/// Organization ceilings for account limits
+///
+/// Each `Some` is the largest value an account may be given; an account
+/// leaving that limit unset (unlimited) exceeds it. The default caps
+/// accounts at [`AccountLimits::default`] without JetStream.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountLimitPolicy {
+    pub ceilings: AccountLimits,
+    /// Whether accounts may have JetStream at all
+    pub allow_jetstream: bool,
+}
+
+impl AccountLimitPolicy {
+    /// No ceilings, JetStream allowed
+    pub fn permissive() -> Self {
+        Self {
+            ceilings: AccountLimits {
+                max_connections: None,
+                max_subscriptions: None,
+                max_payload: None,
+                max_data: None,
+                max_imports: None,
+                max_exports: None,
+                max_jetstream_memory: None,
+                max_jetstream_storage: None,
+                max_streams: None,
+                max_consumers: None,
+            },
+            allow_jetstream: true,
+        }
+    }
+
+    /// Check `limits` against the ceilings, listing every violation
+    pub fn validate(&self, limits: &AccountLimits) -> Result<(), Vec<String>> {
+        let mut violations = Vec::new();
+
+        if limits.jetstream_enabled() && !self.allow_jetstream {
+            violations.push("JetStream is not allowed for accounts".to_string());
+        }
+
+        for ((name, requested), (_, ceiling)) in limits.entries().into_iter().zip(self.ceilings.entries()) {
+            // JetStream quotas only matter when JetStream is on
+            let applies = limits.jetstream_enabled() || !is_jetstream_limit(name);
+            match (requested, ceiling) {
+                (Some(value), _) if value < 0 => violations.push(format!("{} must not be negative", name)),
+                (Some(value), Some(ceiling)) if value > ceiling => {
+                    violations.push(format!("{} {} exceeds organization limit {}", name, value, ceiling))
+                }
+                (None, Some(ceiling)) if applies => {
+                    violations.push(format!("{} must be set (organization limit {})", name, ceiling))
+                }
+                _ => {}
+            }
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+fn is_jetstream_limit(name: &str) -> bool {
+    matches!(
+        name,
+        "max_jetstream_memory" | "max_jetstream_storage" | "max_streams" | "max_consumers"
+    )
+}
+
 impl std::fmt::Display for ExportType {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
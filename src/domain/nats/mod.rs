@@ -5,6 +5,7 @@
 //! This module organizes the NATS bounded context with proper separation:
 //! - **Entities**: NATS hierarchy types (Operator, Account, User)
 //! - **Subjects**: Type-safe NATS subject naming algebra
+//! - **Claims**: Operator, account and user JWTs signed in-crate
 //!
 //! ## Subject Algebra
 //!
@@ -17,6 +18,7 @@
 //! // => "cowboyai.security.keys.certificate.generate.root"
 //! ```
 
+pub mod claims;
 pub mod entities;
 pub mod headers;
 pub mod jetstream;
@@ -25,10 +27,31 @@ pub mod replay;
 pub mod saga_command_handler;
 pub mod saga_executor;
 pub mod subjects;
+pub mod tenancy;
 
 // Re-export entity types for backward compatibility
 pub use entities::*;
 
+// Re-export JWT claims
+pub use claims::{
+    NatsClaims,
+    CONNECTION_TYPE_LEAFNODE,
+    ClaimData,
+    ClaimsError,
+    ClaimType,
+    ClaimPermissions,
+    ClaimLimits,
+    AccountClaimLimits,
+    JetStreamClaimLimits,
+    OperatorClaimData,
+    AccountClaimData,
+    UserClaimData,
+    ActivationClaimData,
+    ClaimExport,
+    ClaimImport,
+    ExportType,
+};
+
 // Re-export header types for event publishing
 pub use headers::{
     CimHeaders,
@@ -94,6 +117,7 @@ pub use saga_executor::{
     PersistedSagaState,
     AsyncSagaExecutor,
     StepExecutionResult,
+    CompensationOutcome,
     SagaRecovery,
 };
 
@@ -106,8 +130,12 @@ pub use saga_command_handler::{
     SagaCommandHandlerError,
     CertificateProvisioningResult,
     CertificateProvisioningExecutor,
+    ProvisioningIssuer,
 };
 
+// Re-export organization scoping
+pub use tenancy::{OrganizationScope, TenancyError, ORGANIZATION_SUBJECT_ROOT};
+
 // Re-export subject algebra at module level
 pub use subjects::{
     Subject,
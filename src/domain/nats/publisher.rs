@@ -29,6 +29,7 @@
 //! publisher.publish_batch(&events, correlation_id).await?;
 //! ```
 
+use super::tenancy::OrganizationScope;
 use crate::events::DomainEvent;
 use crate::ports::{JetStreamError, JetStreamHeaders, JetStreamPort, PublishAck};
 use uuid::Uuid;
@@ -40,6 +41,7 @@ use uuid::Uuid;
 pub struct EventPublisher<P: JetStreamPort> {
     port: P,
     source: String,
+    organization: Option<OrganizationScope>,
 }
 
 impl<P: JetStreamPort> EventPublisher<P> {
@@ -48,6 +50,7 @@ impl<P: JetStreamPort> EventPublisher<P> {
         Self {
             port,
             source: "cim-keys".to_string(),
+            organization: None,
         }
     }
 
@@ -56,9 +59,19 @@ impl<P: JetStreamPort> EventPublisher<P> {
         Self {
             port,
             source: source.into(),
+            organization: None,
         }
     }
 
+    /// Publish under the subjects of one organization
+    ///
+    /// Every subject is prefixed with `organization.{org_id}`, so the events
+    /// land in that organization's stream only.
+    pub fn for_organization(mut self, scope: OrganizationScope) -> Self {
+        self.organization = Some(scope);
+        self
+    }
+
     /// Publish a domain event to JetStream
     ///
     /// The event is serialized to JSON, CIM headers are added, and the
@@ -69,8 +82,24 @@ impl<P: JetStreamPort> EventPublisher<P> {
         correlation_id: Uuid,
         causation_id: Uuid,
     ) -> Result<PublishAck, EventPublishError> {
-        let event_id = Uuid::now_v7();
-        let subject = self.event_to_subject(event);
+        self.publish_with_event_id(event, Uuid::now_v7(), correlation_id, causation_id)
+            .await
+    }
+
+    /// Publish a domain event under a caller-chosen event ID
+    ///
+    /// The event ID doubles as the JetStream message ID, so republishing
+    /// the same event with the same ID is dropped by the stream's duplicate
+    /// window. Used when retrying publication, e.g. from the projection
+    /// outbox.
+    pub async fn publish_with_event_id(
+        &self,
+        event: &DomainEvent,
+        event_id: Uuid,
+        correlation_id: Uuid,
+        causation_id: Uuid,
+    ) -> Result<PublishAck, EventPublishError> {
+        let subject = self.subject_for(event);
         let event_type = event.event_type();
 
         // Serialize event payload
@@ -114,7 +143,7 @@ impl<P: JetStreamPort> EventPublisher<P> {
             // First event uses correlation_id as causation, subsequent events use previous event
             let causation_id = previous_event_id.unwrap_or(correlation_id);
 
-            let subject = self.event_to_subject(event);
+            let subject = self.subject_for(event);
             let event_type = event.event_type();
 
             let payload = serde_json::to_vec(event)
@@ -141,6 +170,15 @@ impl<P: JetStreamPort> EventPublisher<P> {
         Ok(acks)
     }
 
+    /// Subject for an event, scoped to the organization if one is set
+    fn subject_for(&self, event: &DomainEvent) -> String {
+        let subject = self.event_to_subject(event);
+        match &self.organization {
+            Some(scope) => scope.scope_str(&subject),
+            None => subject,
+        }
+    }
+
     /// Determine the JetStream subject for an event
     fn event_to_subject(&self, event: &DomainEvent) -> String {
         use crate::domain::nats::jetstream::events;
@@ -159,6 +197,17 @@ impl<P: JetStreamPort> EventPublisher<P> {
                     KeyEvents::SshKeyGenerated(_) => "keys.events.key.ssh-generated".to_string(),
                     KeyEvents::GpgKeyGenerated(_) => "keys.events.key.gpg-generated".to_string(),
                     KeyEvents::TotpSecretGenerated(_) => "keys.events.key.totp-generated".to_string(),
+                    KeyEvents::KeyExpirationExtended(_) => "keys.events.key.expiration-extended".to_string(),
+                    KeyEvents::KeyMovedToCard(_) => "keys.events.key.moved-to-card".to_string(),
+                    KeyEvents::GpgKeyCertified(_) => "keys.events.key.gpg-certified".to_string(),
+                    KeyEvents::SshCaDesignated(_) => "keys.events.key.ssh-ca-designated".to_string(),
+                    KeyEvents::SshCertificateIssued(_) => "keys.events.key.ssh-certificate-issued".to_string(),
+                    KeyEvents::MasterSeedSplit(_) => "keys.events.key.master-seed-split".to_string(),
+                    KeyEvents::SeedShareAssigned(_) => "keys.events.key.seed-share-assigned".to_string(),
+                    KeyEvents::MasterSeedRecovered(_) => "keys.events.key.master-seed-recovered".to_string(),
+                    KeyEvents::MasterSeedDerived(_) => "keys.events.key.master-seed-derived".to_string(),
+                    KeyEvents::KeyRotationReported(_) => "keys.events.key.rotation-reported".to_string(),
+                    KeyEvents::RecoveryRehearsalCompleted(_) => "keys.events.key.recovery-rehearsal-completed".to_string(),
                 }
             }
             DomainEvent::Certificate(cert_event) => {
@@ -183,6 +232,12 @@ impl<P: JetStreamPort> EventPublisher<P> {
                     CertificateEvents::PkiHierarchyCreated(_) => {
                         "keys.events.certificate.pki-hierarchy-created".to_string()
                     }
+                    CertificateEvents::CrlPublished(_) => {
+                        "keys.events.certificate.crl-published".to_string()
+                    }
+                    CertificateEvents::CertificateIssuanceRejected(_) => {
+                        "keys.events.certificate.issuance-rejected".to_string()
+                    }
                     CertificateEvents::CertificateActivated(_) => {
                         "keys.events.certificate.activated".to_string()
                     }
@@ -266,11 +321,55 @@ impl<P: JetStreamPort> EventPublisher<P> {
                     DelegationEvents::DelegationCascadeRevoked(_) => "keys.events.delegation.cascade-revoked".to_string(),
                     DelegationEvents::DelegationExtended(_) => "keys.events.delegation.extended".to_string(),
                     DelegationEvents::DelegationPermissionsModified(_) => "keys.events.delegation.permissions-modified".to_string(),
+                    DelegationEvents::DelegationExpired(_) => "keys.events.delegation.expired".to_string(),
                 }
             }
             DomainEvent::Relationship(_) => "keys.events.relationship.updated".to_string(),
             DomainEvent::Manifest(_) => "keys.events.manifest.updated".to_string(),
             DomainEvent::Saga(saga_event) => format!("keys.events.{}", saga_event.event_type()),
+            DomainEvent::ThresholdSigning(threshold_event) => {
+                use crate::events::ThresholdSigningEvents;
+                match threshold_event {
+                    ThresholdSigningEvents::ThresholdKeyGenerationStarted(_) => "keys.events.threshold.dkg-started".to_string(),
+                    ThresholdSigningEvents::DkgRound1Completed(_) => "keys.events.threshold.dkg-round1-completed".to_string(),
+                    ThresholdSigningEvents::DkgRound2Completed(_) => "keys.events.threshold.dkg-round2-completed".to_string(),
+                    ThresholdSigningEvents::ThresholdKeyGenerated(_) => "keys.events.threshold.key-generated".to_string(),
+                    ThresholdSigningEvents::ThresholdSigningStarted(_) => "keys.events.threshold.signing-started".to_string(),
+                    ThresholdSigningEvents::SigningCommitmentsCollected(_) => "keys.events.threshold.commitments-collected".to_string(),
+                    ThresholdSigningEvents::SignatureSharesCollected(_) => "keys.events.threshold.shares-collected".to_string(),
+                    ThresholdSigningEvents::ThresholdSignatureAggregated(_) => "keys.events.threshold.signature-aggregated".to_string(),
+                    ThresholdSigningEvents::ThresholdOperationFailed(_) => "keys.events.threshold.failed".to_string(),
+                }
+            }
+            DomainEvent::Approval(approval_event) => {
+                use crate::events::ApprovalEvents;
+                match approval_event {
+                    ApprovalEvents::ApprovalRequested(_) => "keys.events.approval.requested".to_string(),
+                    ApprovalEvents::ApprovalGranted(_) => "keys.events.approval.granted".to_string(),
+                    ApprovalEvents::ApprovalQuorumReached(_) => "keys.events.approval.quorum-reached".to_string(),
+                    ApprovalEvents::ApprovalRejected(_) => "keys.events.approval.rejected".to_string(),
+                    ApprovalEvents::ApprovedCommandExecuted(_) => "keys.events.approval.executed".to_string(),
+                }
+            }
+            DomainEvent::Authorization(authorization_event) => {
+                use crate::events::AuthorizationEvents;
+                match authorization_event {
+                    AuthorizationEvents::CommandDenied(_) => "keys.events.authorization.command-denied".to_string(),
+                    AuthorizationEvents::SeparationOfDutiesViolated(_) => {
+                        "keys.events.authorization.separation-of-duties-violated".to_string()
+                    }
+                    AuthorizationEvents::TransitionDenied(_) => {
+                        "keys.events.authorization.transition-denied".to_string()
+                    }
+                }
+            }
+            DomainEvent::Maintenance(maintenance_event) => {
+                use crate::events::MaintenanceEvents;
+                match maintenance_event {
+                    MaintenanceEvents::MaintenanceDue(_) => "keys.events.maintenance.due".to_string(),
+                    MaintenanceEvents::MaintenanceScanCompleted(_) => "keys.events.maintenance.scan-completed".to_string(),
+                }
+            }
         }
     }
 
@@ -441,8 +540,11 @@ mod tests {
                 jwt_kid: None,
                 jwt_alg: None,
                 jwt_use: None,
+                hardware_serial: None,
+                tpm: None,
             },
             ownership: None,
+            expires_at: None,
             correlation_id: Uuid::now_v7(),
             causation_id: None,
         }))
@@ -510,6 +612,20 @@ mod tests {
         assert_eq!(subject, "keys.events.key.generated");
     }
 
+    #[tokio::test]
+    async fn test_organization_scoped_subject() {
+        let port = MockJetStreamPort::new();
+        let organization_id = Uuid::now_v7();
+        let publisher = EventPublisher::new(port)
+            .for_organization(OrganizationScope::new(organization_id));
+
+        let event = create_test_key_event();
+        assert_eq!(
+            publisher.subject_for(&event),
+            format!("organization.{}.keys.events.key.generated", organization_id)
+        );
+    }
+
     #[tokio::test]
     async fn test_custom_source() {
         let port = MockJetStreamPort::new();
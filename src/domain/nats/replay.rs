@@ -73,6 +73,10 @@ impl DomainEventExt for DomainEvent {
             DomainEvent::Relationship(e) => e.aggregate_id(),
             DomainEvent::Manifest(e) => e.aggregate_id(),
             DomainEvent::Saga(e) => e.saga_id(),
+            DomainEvent::ThresholdSigning(e) => e.aggregate_id(),
+            DomainEvent::Approval(e) => e.aggregate_id(),
+            DomainEvent::Authorization(e) => e.aggregate_id(),
+            DomainEvent::Maintenance(e) => e.aggregate_id(),
         }
     }
 }
@@ -836,6 +840,10 @@ fn get_event_type(event: &DomainEvent) -> String {
         DomainEvent::Relationship(e) => format!("Relationship.{}", std::any::type_name_of_val(e).split("::").last().unwrap_or("Unknown")),
         DomainEvent::Manifest(e) => format!("Manifest.{}", std::any::type_name_of_val(e).split("::").last().unwrap_or("Unknown")),
         DomainEvent::Saga(e) => format!("Saga.{}", e.event_type()),
+        DomainEvent::ThresholdSigning(e) => format!("ThresholdSigning.{}", e.event_type()),
+        DomainEvent::Approval(e) => format!("Approval.{}", e.event_type()),
+        DomainEvent::Authorization(e) => format!("Authorization.{}", e.event_type()),
+        DomainEvent::Maintenance(e) => format!("Maintenance.{}", e.event_type()),
     }
 }
 
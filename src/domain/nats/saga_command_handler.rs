@@ -11,9 +11,9 @@
 //! ```text
 //! Command → SagaCommandHandler → JetStreamSagaExecutor
 //!                    ↓                    ↓
-//!              Create Saga         Persist State
+//!              Start Run           Persist State
 //!                    ↓                    ↓
-//!              Execute Steps       Publish Events
+//!          SagaEngine Steps        Publish Events
 //!                    ↓                    ↓
 //!              Return Result       Update KV
 //! ```
@@ -26,24 +26,30 @@
 //! let handler = SagaCommandHandler::new(jetstream_port, publisher);
 //!
 //! // Handle a saga-triggering command
-//! let result = handler.handle_provision_certificate(request).await?;
+//! let executor = CertificateProvisioningExecutor::new(yubikey, issuer, pin, management_key);
+//! let result = handler.handle_provision_certificate(request, Arc::new(executor)).await?;
 //! ```
 
 use crate::domain::sagas::{
-    CertificateProvisioningSaga, ProvisioningRequest, ProvisioningState,
-    SagaState, SagaError, CompensationResult, CertificatePurpose,
+    CertificateProvisioning, ProvisioningArtifacts, ProvisioningRequest, ProvisioningSteps,
+    SagaEngine, SagaState, SagaError, CompensationResult, CertificatePurpose,
 };
 use crate::value_objects::ActorId;
 use crate::domain::nats::saga_executor::{
-    JetStreamSagaExecutor, SagaExecutorConfig, SagaExecutorError,
-    AsyncSagaExecutor, StepExecutionResult, PersistedSagaState,
+    JetStreamSagaExecutor, SagaExecutorConfig, SagaExecutorError, PersistedSagaState,
 };
+use crate::events::{DomainEvent, SagaEvents};
 use crate::domain::nats::publisher::{EventPublisher, EventPublishError};
+use crate::domain::yubikey::PIVSlot;
+use crate::domain::PolicyConstraint;
+use crate::events::KeyAlgorithm;
+use crate::ports::yubikey::{KeyAlgorithm as SlotAlgorithm, PivSlot, SecureString, YubiKeyPort};
 use crate::ports::JetStreamPort;
+use crate::types::KeyPurpose;
 
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
-use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Saga command handler for orchestrating multi-aggregate workflows
 ///
@@ -71,8 +77,6 @@ pub struct SagaCommandHandler<P: JetStreamPort> {
 pub struct SagaHandlerConfig {
     /// Maximum retries per step
     pub max_step_retries: u32,
-    /// Whether to auto-compensate on failure
-    pub auto_compensate: bool,
     /// Timeout for saga execution in seconds
     pub saga_timeout_secs: u64,
 }
@@ -81,7 +85,6 @@ impl Default for SagaHandlerConfig {
     fn default() -> Self {
         Self {
             max_step_retries: 3,
-            auto_compensate: true,
             saga_timeout_secs: 300, // 5 minutes
         }
     }
@@ -166,185 +169,82 @@ impl<P: JetStreamPort + Clone> SagaCommandHandler<P> {
 
     /// Handle a certificate provisioning command
     ///
-    /// This command triggers the CertificateProvisioningSaga which:
-    /// 1. Generates a key pair
-    /// 2. Generates a certificate
-    /// 3. Provisions to YubiKey
-    /// 4. Verifies provisioning
+    /// This command runs the CertificateProvisioningSaga, which:
+    /// 1. Generates a key pair in the YubiKey slot
+    /// 2. Issues a certificate from a CSR the slot signs
+    /// 3. Imports the certificate into the slot
+    /// 4. Verifies the slot holds it
+    ///
+    /// If a step fails, the engine compensates the completed ones and the
+    /// resulting revocations are published like any other step's events.
     pub async fn handle_provision_certificate(
         &self,
         request: ProvisioningRequest,
+        saga_executor: Arc<CertificateProvisioningExecutor>,
     ) -> Result<SagaCommandResult<CertificateProvisioningResult>, SagaCommandHandlerError> {
-        // Create the saga
-        let mut saga = CertificateProvisioningSaga::new(request);
-        let saga_id = saga.saga_id();
-        let correlation_id = saga.correlation_id();
+        let context = CertificateProvisioning::new(request);
+        context.validate().map_err(|e| SagaCommandHandlerError::SagaStartFailed(e.message))?;
 
-        // Start the saga
-        saga.start().map_err(|e| SagaCommandHandlerError::SagaStartFailed(e.message))?;
+        let engine = SagaEngine::new(CertificateProvisioning::definition(saga_executor));
+        let (saga_id, correlation_id) = (context.saga_id, context.correlation_id);
+        let (mut saga, started) =
+            engine.start_with_id(saga_id, context, "certificate_provisioning", correlation_id);
 
         // Persist initial state
         self.executor
             .start_saga(saga.clone())
             .await
             .map_err(|e| SagaCommandHandlerError::PersistenceFailed(e.to_string()))?;
+        self.publish_saga_events(started, correlation_id).await?;
+
+        while !saga.is_terminal() {
+            let saga_events = engine.advance(&mut saga).await;
+
+            // Publish domain events produced by the step (A6: explicit routing)
+            let domain_events = std::mem::take(&mut saga.context.pending_events);
+            self.publish_domain_events(&domain_events, correlation_id).await?;
+            self.publish_saga_events(saga_events, correlation_id).await?;
+
+            // Save progress
+            let persisted = PersistedSagaState::new(saga.clone());
+            self.executor
+                .save_saga(&persisted)
+                .await
+                .map_err(|e| SagaCommandHandlerError::PersistenceFailed(e.to_string()))?;
+        }
 
-        // Create executor for this saga type
-        let saga_executor = CertificateProvisioningExecutor::new();
-
-        // Execute the saga to completion
-        let result = self.execute_saga_to_completion(
-            &mut saga,
-            &saga_executor,
-        ).await;
-
-        match result {
-            Ok(output) => Ok(SagaCommandResult {
+        if saga.is_completed() {
+            return Ok(SagaCommandResult {
                 saga_id,
                 correlation_id,
                 status: SagaCommandStatus::Completed,
-                result: Some(output),
+                result: Some(CertificateProvisioningResult::from(&saga.context.artifacts)),
                 error: None,
-            }),
-            Err(SagaCommandHandlerError::SagaStepFailed { step, message }) => {
-                // Attempt compensation if configured
-                if self.config.auto_compensate {
-                    let comp_result = self.compensate_saga(&mut saga, &saga_executor).await;
-                    let status = match comp_result {
-                        Ok(CompensationResult::FullyCompensated) => SagaCommandStatus::FailedAndCompensated,
-                        Ok(CompensationResult::NotNeeded) => SagaCommandStatus::FailedAndCompensated,
-                        _ => SagaCommandStatus::FailedCompensationFailed,
-                    };
-                    Ok(SagaCommandResult {
-                        saga_id,
-                        correlation_id,
-                        status,
-                        result: None,
-                        error: Some(format!("Step '{}' failed: {}", step, message)),
-                    })
-                } else {
-                    Ok(SagaCommandResult {
-                        saga_id,
-                        correlation_id,
-                        status: SagaCommandStatus::FailedCompensationFailed,
-                        result: None,
-                        error: Some(format!("Step '{}' failed: {}", step, message)),
-                    })
-                }
-            }
-            Err(e) => Err(e),
+            });
         }
-    }
-
-    /// Execute a saga to completion, handling retries and publishing domain events
-    ///
-    /// ## FRP Axiom Compliance
-    ///
-    /// - A3 (Decoupled): Domain events published AFTER step completes
-    /// - A6 (Explicit Routing): Composition: `execute_step >>> publish_domain_events`
-    /// - A7 (Change Prefixes): All events share correlation_id for causal chain
-    /// - A9 (Semantic Preservation): Event publishing preserves saga semantics
-    async fn execute_saga_to_completion<S, E>(
-        &self,
-        saga: &mut S,
-        executor: &E,
-    ) -> Result<E::Output, SagaCommandHandlerError>
-    where
-        S: SagaState + Serialize + for<'de> Deserialize<'de>,
-        E: AsyncSagaExecutor<S>,
-    {
-        let mut retry_count = 0;
-        let correlation_id = saga.correlation_id();
-
-        loop {
-            if saga.is_terminal() {
-                if saga.is_completed() {
-                    // Return a default output - the actual result is in the saga state
-                    break;
-                } else {
-                    return Err(SagaCommandHandlerError::SagaStepFailed {
-                        step: "terminal".to_string(),
-                        message: "Saga reached failed terminal state".to_string(),
-                    });
-                }
-            }
 
-            match executor.execute_next_step(saga).await {
-                Ok(StepExecutionResult::Completed { output, domain_events }) => {
-                    // Publish domain events produced by final step (A6: explicit routing)
-                    self.publish_domain_events(&domain_events, correlation_id).await?;
-
-                    // Update persisted state
-                    let persisted = PersistedSagaState::new(saga.clone());
-                    self.executor
-                        .save_saga(&persisted)
-                        .await
-                        .map_err(|e| SagaCommandHandlerError::PersistenceFailed(e.to_string()))?;
-                    return Ok(output);
-                }
-                Ok(StepExecutionResult::Continue { domain_events }) => {
-                    // Publish domain events produced by this step (A6: explicit routing)
-                    self.publish_domain_events(&domain_events, correlation_id).await?;
-
-                    // Save progress and continue
-                    let persisted = PersistedSagaState::new(saga.clone());
-                    self.executor
-                        .save_saga(&persisted)
-                        .await
-                        .map_err(|e| SagaCommandHandlerError::PersistenceFailed(e.to_string()))?;
-                    retry_count = 0;
-                }
-                Ok(StepExecutionResult::Retry { retry_in_ms, reason }) => {
-                    retry_count += 1;
-                    if retry_count > self.config.max_step_retries {
-                        return Err(SagaCommandHandlerError::SagaStepFailed {
-                            step: saga.status_description(),
-                            message: format!("Max retries exceeded: {}", reason),
-                        });
-                    }
-                    tokio::time::sleep(tokio::time::Duration::from_millis(retry_in_ms)).await;
-                }
-                Ok(StepExecutionResult::Failed(err)) => {
-                    return Err(SagaCommandHandlerError::SagaStepFailed {
-                        step: saga.status_description(),
-                        message: err.message,
-                    });
-                }
-                Err(e) => {
-                    return Err(SagaCommandHandlerError::ExecutorError(e.to_string()));
-                }
-            }
-        }
-
-        // If we exit the loop, saga completed
-        Err(SagaCommandHandlerError::SagaStepFailed {
-            step: "completion".to_string(),
-            message: "Saga did not produce output".to_string(),
+        let error = saga.error.clone().unwrap_or_else(|| SagaError::new("Unknown error", "unknown"));
+        let status = match error.compensation_result {
+            Some(CompensationResult::PartiallyCompensated { .. }) => SagaCommandStatus::FailedCompensationFailed,
+            _ => SagaCommandStatus::FailedAndCompensated,
+        };
+        Ok(SagaCommandResult {
+            saga_id,
+            correlation_id,
+            status,
+            result: None,
+            error: Some(format!("Step '{}' failed: {}", error.failed_step, error.message)),
         })
     }
 
-    /// Compensate a failed saga
-    async fn compensate_saga<S, E>(
+    /// Publish the saga events of a run's latest transition
+    async fn publish_saga_events(
         &self,
-        saga: &mut S,
-        executor: &E,
-    ) -> Result<CompensationResult, SagaCommandHandlerError>
-    where
-        S: SagaState + Serialize + for<'de> Deserialize<'de>,
-        E: AsyncSagaExecutor<S>,
-    {
-        match executor.compensate(saga).await {
-            Ok(result) => {
-                // Save final state
-                let persisted = PersistedSagaState::new(saga.clone());
-                self.executor
-                    .save_saga(&persisted)
-                    .await
-                    .map_err(|e| SagaCommandHandlerError::PersistenceFailed(e.to_string()))?;
-                Ok(result)
-            }
-            Err(e) => Err(SagaCommandHandlerError::CompensationFailed(e.to_string())),
-        }
+        events: Vec<SagaEvents>,
+        correlation_id: Uuid,
+    ) -> Result<(), SagaCommandHandlerError> {
+        let events: Vec<DomainEvent> = events.into_iter().map(DomainEvent::Saga).collect();
+        self.publish_domain_events(&events, correlation_id).await
     }
 
     /// Resume a saga from persisted state
@@ -383,236 +283,349 @@ pub struct CertificateProvisioningResult {
     pub fingerprint: String,
 }
 
+impl From<&ProvisioningArtifacts> for CertificateProvisioningResult {
+    fn from(artifacts: &ProvisioningArtifacts) -> Self {
+        Self {
+            key_id: artifacts.key_id.map(|k| k.as_uuid()).unwrap_or_default(),
+            certificate_id: artifacts.certificate_id.map(|c| c.as_uuid()).unwrap_or_default(),
+            slot: artifacts.provisioned_slot.clone().unwrap_or_default(),
+            fingerprint: artifacts.certificate_fingerprint.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Issuing CA the provisioning executor signs certificates with
+#[derive(Clone)]
+pub struct ProvisioningIssuer {
+    /// Issuing CA certificate followed by the rest of its chain (PEM)
+    pub chain_pem: String,
+    /// Issuing CA private key (PKCS#8 PEM)
+    pub key_pem: String,
+    /// Policy applied to every certificate issued
+    pub constraints: Vec<PolicyConstraint>,
+}
+
 /// Executor for the CertificateProvisioningSaga
+///
+/// Generates the key in the target YubiKey slot, has the slot sign a CSR,
+/// issues the certificate from `issuer` and imports it into the slot. The
+/// private key never leaves the YubiKey.
 pub struct CertificateProvisioningExecutor {
-    _phantom: PhantomData<()>,
+    yubikey: Arc<dyn YubiKeyPort>,
+    issuer: ProvisioningIssuer,
+    pin: SecureString,
+    management_key: SecureString,
 }
 
 impl CertificateProvisioningExecutor {
-    pub fn new() -> Self {
-        Self { _phantom: PhantomData }
+    pub fn new(
+        yubikey: Arc<dyn YubiKeyPort>,
+        issuer: ProvisioningIssuer,
+        pin: SecureString,
+        management_key: SecureString,
+    ) -> Self {
+        Self { yubikey, issuer, pin, management_key }
     }
 }
 
-impl Default for CertificateProvisioningExecutor {
-    fn default() -> Self {
-        Self::new()
+/// PIV slot on the port for a domain slot
+///
+/// Domain retired slots count from 0 (82h), port retired slots from 1.
+fn port_slot(slot: &PIVSlot) -> PivSlot {
+    match slot {
+        PIVSlot::Authentication => PivSlot::Authentication,
+        PIVSlot::Signature => PivSlot::Signature,
+        PIVSlot::KeyManagement => PivSlot::KeyManagement,
+        PIVSlot::CardAuth => PivSlot::CardAuth,
+        PIVSlot::Retired(n) => PivSlot::Retired(n + 1),
+    }
+}
+
+/// Slot algorithm and CSR signature algorithm for a requested key
+///
+/// Only ECDSA slots can sign a CSR through the port: they sign a digest
+/// computed here, whereas RSA slots expect a padded block and Ed25519
+/// slots the whole message.
+fn slot_algorithm(algorithm: &KeyAlgorithm) -> Result<(SlotAlgorithm, &'static rcgen::SignatureAlgorithm), String> {
+    match algorithm {
+        KeyAlgorithm::Ecdsa { curve } if curve == "P-256" => {
+            Ok((SlotAlgorithm::EccP256, &rcgen::PKCS_ECDSA_P256_SHA256))
+        }
+        KeyAlgorithm::Ecdsa { curve } if curve == "P-384" => {
+            Ok((SlotAlgorithm::EccP384, &rcgen::PKCS_ECDSA_P384_SHA384))
+        }
+        other => Err(format!("{:?} keys cannot be provisioned with a certificate on a YubiKey slot", other)),
+    }
+}
+
+/// Digest the slot signs for a CSR
+fn csr_digest(algorithm: SlotAlgorithm, to_be_signed: &[u8]) -> Vec<u8> {
+    use sha2::{Digest, Sha256, Sha384};
+
+    match algorithm {
+        SlotAlgorithm::EccP384 => Sha384::digest(to_be_signed).to_vec(),
+        _ => Sha256::digest(to_be_signed).to_vec(),
+    }
+}
+
+/// Key purpose a certificate is issued for
+///
+/// Key management slots hold encryption certificates.
+fn issuance_purpose(purpose: &CertificatePurpose) -> KeyPurpose {
+    match purpose {
+        CertificatePurpose::KeyManagement => KeyPurpose::Encryption,
+        other => other.to_key_purpose(),
+    }
+}
+
+/// DER contents of data a device returned as either PEM or DER
+fn der_contents(data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.starts_with(b"-----BEGIN") {
+        pem::parse(data)
+            .map(|pem| pem.into_contents())
+            .map_err(|e| format!("Invalid PEM: {}", e))
+    } else {
+        Ok(data.to_vec())
     }
 }
 
+/// Raw subjectPublicKey from the SPKI a slot returned
+fn slot_public_key(spki: &[u8]) -> Result<Vec<u8>, String> {
+    use x509_parser::prelude::FromDer;
+
+    let der = der_contents(spki)?;
+    let (_, spki) = x509_parser::x509::SubjectPublicKeyInfo::from_der(&der)
+        .map_err(|e| format!("Invalid slot public key: {}", e))?;
+    Ok(spki.subject_public_key.data.to_vec())
+}
+
+/// Actor the compensating revocations are attributed to
+const SAGA_ACTOR: &str = "certificate_provisioning";
+
+/// Step bodies of the CertificateProvisioningSaga
+///
+/// ## FRP Axiom Compliance
+///
+/// - A3 (Decoupled): Events created AFTER each operation completes
+/// - A6 (Explicit Routing): Steps queue events; the handler publishes them
+/// - A7 (Change Prefixes): Events use correlation_id from saga
 #[async_trait::async_trait]
-impl AsyncSagaExecutor<CertificateProvisioningSaga> for CertificateProvisioningExecutor {
-    type Output = CertificateProvisioningResult;
+impl ProvisioningSteps for CertificateProvisioningExecutor {
+    async fn generate_key(&self, saga: &mut CertificateProvisioning) -> Result<(), String> {
+        use crate::domain::ids::KeyId;
+        use crate::events::{KeyEvents, key::KeyGeneratedEvent};
+        use crate::types::KeyMetadata;
+        use chrono::Utc;
 
-    /// Execute the next step of the saga, returning domain events
-    ///
-    /// ## FRP Axiom Compliance
-    ///
-    /// - A3 (Decoupled): Events created AFTER operation completes
-    /// - A6 (Explicit Routing): Caller composes step execution with event publishing
-    /// - A7 (Change Prefixes): Events use correlation_id from saga
-    async fn execute_next_step(
-        &self,
-        state: &mut CertificateProvisioningSaga,
-    ) -> Result<StepExecutionResult<Self::Output>, SagaExecutorError> {
-        use crate::events::{DomainEvent, KeyEvents, CertificateEvents, YubiKeyEvents};
-        use crate::events::key::KeyGeneratedEvent;
-        use crate::events::certificate::CertificateGeneratedEvent;
-        use crate::events::yubikey::YubiKeyProvisionedEvent;
-        use crate::types::{KeyMetadata, YubiKeySlot};
-        use crate::value_objects::x509::{
-            BasicConstraints, CertificateValidity, ExtendedKeyUsage, KeyUsage,
-            SubjectAlternativeName, SubjectName,
+        let serial = saga.request.yubikey_serial.clone();
+        let (algorithm, _) = slot_algorithm(&saga.request.key_algorithm)?;
+        let generated = self.yubikey
+            .generate_key_in_slot(&serial, port_slot(&saga.request.target_slot), algorithm, &self.management_key)
+            .await
+            .map_err(|e| format!("Key generation failed: {}", e))?;
+        let public_key = slot_public_key(&generated.spki)?;
+
+        let key_id = KeyId::new();
+        saga.record_key(key_id);
+        saga.record_public_key(public_key);
+
+        // Emit KeyGenerated domain event (A7: event log)
+        saga.pending_events.push(DomainEvent::Key(KeyEvents::KeyGenerated(KeyGeneratedEvent {
+            key_id: key_id.as_uuid(),
+            algorithm: saga.request.key_algorithm.clone(),
+            purpose: saga.request.purpose.to_key_purpose(),
+            generated_at: Utc::now(),
+            generated_by: ActorId::legacy(&saga.request.person_email),
+            hardware_backed: true,
+            metadata: KeyMetadata {
+                label: format!("{} - {:?}", saga.request.person_name, saga.request.purpose),
+                description: Some(format!("Generated for saga {}", saga.saga_id)),
+                tags: vec!["saga-generated".to_string()],
+                attributes: std::collections::HashMap::new(),
+                jwt_kid: None,
+                jwt_alg: None,
+                jwt_use: None,
+                hardware_serial: Some(serial),
+                tpm: None,
+            },
+            ownership: None,
+            expires_at: None,
+            correlation_id: saga.correlation_id,
+            causation_id: Some(saga.saga_id),
+        })));
+        Ok(())
+    }
+
+    async fn issue_certificate(&self, saga: &mut CertificateProvisioning) -> Result<(), String> {
+        use crate::crypto::x509::{issue_certificate, ExternalKeyCsr, LeafCertificateParams};
+        use crate::domain::ids::CertificateId;
+        use crate::events::CertificateEvents;
+        use crate::ports::x509::CertificateSubject;
+
+        let key_id = saga.artifacts.key_id.ok_or("Key should exist after GenerateKey step")?;
+        let public_key = saga.artifacts.public_key.clone()
+            .ok_or("Public key should exist after GenerateKey step")?;
+        let (algorithm, csr_algorithm) = slot_algorithm(&saga.request.key_algorithm)?;
+
+        // The slot signs the CSR, proving possession of the on-card key
+        let subject = CertificateSubject {
+            common_name: saga.request.person_name.clone(),
+            organization: Some("CIM".to_string()),
+            organizational_unit: Some(format!("{:?}", saga.request.purpose)),
+            country: None,
+            state: None,
+            locality: None,
+            email: Some(saga.request.person_email.clone()),
         };
+        let csr = ExternalKeyCsr::prepare(&subject, public_key, csr_algorithm)?;
+        let digest = csr_digest(algorithm, csr.to_be_signed());
+        let signature = self.yubikey
+            .sign_with_slot(&saga.request.yubikey_serial, port_slot(&saga.request.target_slot), &digest, &self.pin)
+            .await
+            .map_err(|e| format!("Slot could not sign CSR: {}", e))?;
+        let csr_pem = csr.finish(signature.data)?;
+
+        let issuer_key = rcgen::KeyPair::from_pem(&self.issuer.key_pem)
+            .map_err(|e| format!("Invalid issuer key: {}", e))?;
+        let issued = issue_certificate(
+            &csr_pem,
+            LeafCertificateParams {
+                purpose: issuance_purpose(&saga.request.purpose),
+                validity_days: saga.request.validity_days,
+                key_id: key_id.as_uuid(),
+            },
+            &self.issuer.chain_pem,
+            issuer_key,
+            &self.issuer.constraints,
+            saga.request.issuing_ca_id.as_uuid(),
+            saga.correlation_id,
+            Some(saga.saga_id),
+        )
+        .map_err(|e| format!("Certificate issuance failed: {}", e))?;
+
+        saga.record_certificate(
+            CertificateId::from_uuid(issued.generation_event.cert_id),
+            issued.fingerprint.clone(),
+        );
+        saga.record_certificate_pem(issued.certificate_pem);
+        saga.pending_events.extend([
+            DomainEvent::Certificate(CertificateEvents::CertificateGenerated(issued.generation_event)),
+            DomainEvent::Certificate(CertificateEvents::CertificateSigned(issued.signing_event)),
+        ]);
+        Ok(())
+    }
+
+    async fn provision_to_yubikey(&self, saga: &mut CertificateProvisioning) -> Result<(), String> {
+        use crate::events::{YubiKeyEvents, yubikey::YubiKeyProvisionedEvent};
+        use crate::types::YubiKeySlot;
         use chrono::Utc;
 
-        let correlation_id = state.correlation_id();
+        let serial = saga.request.yubikey_serial.clone();
+        let certificate_pem = saga.artifacts.certificate_pem.clone()
+            .ok_or("Certificate should exist after IssueCertificate step")?;
+        self.yubikey
+            .import_certificate(&serial, port_slot(&saga.request.target_slot), certificate_pem.as_bytes(), &self.pin)
+            .await
+            .map_err(|e| format!("Certificate import failed: {}", e))?;
+
+        let slot_str = format!("{:?}", saga.request.target_slot);
+        saga.record_provisioning(slot_str.clone());
+
+        // Emit YubiKeyProvisioned domain event
+        let key_id = saga.artifacts.key_id.ok_or("Key should exist after GenerateKey step")?;
+        saga.pending_events.push(DomainEvent::YubiKey(YubiKeyEvents::YubiKeyProvisioned(YubiKeyProvisionedEvent {
+            event_id: Uuid::now_v7(),
+            yubikey_serial: serial,
+            slots_configured: vec![YubiKeySlot {
+                slot_id: slot_str,
+                key_id: key_id.as_uuid(),
+                purpose: saga.request.purpose.to_key_purpose(),
+            }],
+            provisioned_at: Utc::now(),
+            provisioned_by: saga.request.person_email.clone(),
+            correlation_id: saga.correlation_id,
+            causation_id: Some(saga.saga_id),
+        })));
+        Ok(())
+    }
 
-        match &state.state {
-            ProvisioningState::Initial => {
-                // Already started, advance to key generation
-                state.advance();
-                Ok(StepExecutionResult::continue_empty())
-            }
-            ProvisioningState::GeneratingKey => {
-                // In a real implementation, this would call the key generation port
-                // For now, simulate success
-                let key_id = crate::domain::ids::KeyId::new();
-                state.record_key(key_id);
-                state.advance();
-
-                // Emit KeyGenerated domain event (A7: event log)
-                let event = DomainEvent::Key(KeyEvents::KeyGenerated(KeyGeneratedEvent {
-                    key_id: key_id.as_uuid(),
-                    algorithm: state.request.key_algorithm.clone(),
-                    purpose: state.request.purpose.to_key_purpose(),
-                    generated_at: Utc::now(),
-                    generated_by: ActorId::legacy(&state.request.person_email),
-                    hardware_backed: true,
-                    metadata: KeyMetadata {
-                        label: format!("{} - {:?}", state.request.person_name, state.request.purpose),
-                        description: Some(format!("Generated for saga {}", state.saga_id())),
-                        tags: vec!["saga-generated".to_string()],
-                        attributes: std::collections::HashMap::new(),
-                        jwt_kid: None,
-                        jwt_alg: None,
-                        jwt_use: None,
-                    },
-                    ownership: None,
-                    correlation_id,
-                    causation_id: Some(state.saga_id()),
-                }));
-
-                Ok(StepExecutionResult::continue_with_events(vec![event]))
-            }
-            ProvisioningState::GeneratingCertificate => {
-                // In a real implementation, this would call the certificate generation port
-                let cert_id = crate::domain::ids::CertificateId::new();
-                let fingerprint = format!("SHA256:{}", Uuid::now_v7());
-                state.record_certificate(cert_id, fingerprint.clone());
-                state.advance();
-
-                // Emit CertificateGenerated domain event
-                let key_id = state.artifacts.key_id.expect("Key should exist after GeneratingKey step");
-
-                use crate::value_objects::x509::{CommonName, OrganizationName, OrganizationalUnitName};
-                let common_name = CommonName::new_unchecked(&state.request.person_name);
-                let subject_name = SubjectName::new(common_name)
-                    .with_organization(OrganizationName::new_unchecked("CIM"))
-                    .with_organizational_unit(OrganizationalUnitName::new_unchecked(
-                        &format!("{:?}", state.request.purpose)
-                    ));
-
-                let validity = CertificateValidity::new(
-                    Utc::now(),
-                    Utc::now() + chrono::Duration::days(state.request.validity_days as i64),
-                ).expect("Valid certificate validity");
-
-                let extended_key_usage = match state.request.purpose {
-                    CertificatePurpose::Authentication => Some(ExtendedKeyUsage::tls_client()),
-                    CertificatePurpose::DigitalSignature => Some(ExtendedKeyUsage::code_signing()),
-                    CertificatePurpose::KeyManagement => Some(ExtendedKeyUsage::email_protection()),
-                    CertificatePurpose::CardAuthentication => None, // No standard EKU for smart card
-                };
-
-                let subject_alt_name = SubjectAlternativeName::new()
-                    .with_email(&state.request.person_email)
-                    .ok();
-
-                let cert_gen_event = CertificateGeneratedEvent {
-                    cert_id: cert_id.as_uuid(),
-                    key_id: key_id.as_uuid(),
-                    subject_name,
-                    subject_alt_name,
-                    key_usage: KeyUsage::code_signing(),
-                    extended_key_usage,
-                    validity,
-                    basic_constraints: BasicConstraints::end_entity(),
-                    issuer: Some(state.request.issuing_ca_id.as_uuid()),
-                    correlation_id,
-                    causation_id: Some(state.saga_id()),
-                };
-                let event = DomainEvent::Certificate(CertificateEvents::CertificateGenerated(cert_gen_event));
-
-                Ok(StepExecutionResult::continue_with_events(vec![event]))
-            }
-            ProvisioningState::ProvisioningToYubiKey => {
-                // In a real implementation, this would call the YubiKey port
-                let slot_str = format!("{:?}", state.request.target_slot);
-                state.record_provisioning(slot_str.clone());
-                state.advance();
-
-                // Emit YubiKeyProvisioned domain event
-                let key_id = state.artifacts.key_id.expect("Key should exist after GeneratingKey step");
-                let event = DomainEvent::YubiKey(YubiKeyEvents::YubiKeyProvisioned(YubiKeyProvisionedEvent {
-                    event_id: Uuid::now_v7(),
-                    yubikey_serial: state.request.yubikey_serial.clone(),
-                    slots_configured: vec![YubiKeySlot {
-                        slot_id: slot_str,
-                        key_id: key_id.as_uuid(),
-                        purpose: state.request.purpose.to_key_purpose(),
-                    }],
-                    provisioned_at: Utc::now(),
-                    provisioned_by: state.request.person_email.clone(),
-                    correlation_id,
-                    causation_id: Some(state.saga_id()),
-                }));
-
-                Ok(StepExecutionResult::continue_with_events(vec![event]))
-            }
-            ProvisioningState::VerifyingProvisioning => {
-                // In a real implementation, this would verify the certificate is in the slot
-                state.record_verification(crate::domain::sagas::VerificationStatus::Verified);
-                state.advance();
-
-                // Build result
-                let result = CertificateProvisioningResult {
-                    key_id: state.artifacts.key_id.map(|k| k.as_uuid()).unwrap_or_default(),
-                    certificate_id: state.artifacts.certificate_id.map(|c| c.as_uuid()).unwrap_or_default(),
-                    slot: state.artifacts.provisioned_slot.clone().unwrap_or_default(),
-                    fingerprint: state.artifacts.certificate_fingerprint.clone().unwrap_or_default(),
-                };
-
-                // Verification complete - no additional domain event needed
-                // (The saga completion event from the executor covers this)
-                Ok(StepExecutionResult::completed_with_events(result, vec![]))
-            }
-            ProvisioningState::Completed => {
-                // Already complete
-                let result = CertificateProvisioningResult {
-                    key_id: state.artifacts.key_id.map(|k| k.as_uuid()).unwrap_or_default(),
-                    certificate_id: state.artifacts.certificate_id.map(|c| c.as_uuid()).unwrap_or_default(),
-                    slot: state.artifacts.provisioned_slot.clone().unwrap_or_default(),
-                    fingerprint: state.artifacts.certificate_fingerprint.clone().unwrap_or_default(),
-                };
-                Ok(StepExecutionResult::completed_with_events(result, vec![]))
-            }
-            ProvisioningState::Failed => {
-                let err = state.error.clone().unwrap_or_else(|| SagaError::new("Unknown error", "unknown"));
-                Ok(StepExecutionResult::Failed(err))
-            }
-            ProvisioningState::Compensating(_) => {
-                // In compensating state, treat as failed
-                let err = state.error.clone().unwrap_or_else(|| SagaError::new("Compensating", "compensation"));
-                Ok(StepExecutionResult::Failed(err))
-            }
+    async fn verify_provisioning(&self, saga: &mut CertificateProvisioning) -> Result<(), String> {
+        use crate::crypto::x509::calculate_fingerprint;
+        use crate::domain::sagas::VerificationStatus;
+
+        // Read the certificate back and compare it with the one issued
+        let stored = self.yubikey
+            .read_certificate(&saga.request.yubikey_serial, port_slot(&saga.request.target_slot))
+            .await;
+        let status = match stored {
+            Ok(Some(stored)) => match der_contents(&stored).map(|der| calculate_fingerprint(&der)) {
+                Ok(f) if Some(&f) == saga.artifacts.certificate_fingerprint.as_ref() => VerificationStatus::Verified,
+                Ok(_) => VerificationStatus::FingerprintMismatch,
+                Err(e) => VerificationStatus::Error(e),
+            },
+            Ok(None) => VerificationStatus::NotFound,
+            Err(e) => VerificationStatus::Error(e.to_string()),
+        };
+        saga.record_verification(status.clone());
+        if status != VerificationStatus::Verified {
+            return Err(format!("Slot verification failed: {:?}", status));
         }
+        Ok(())
     }
 
-    async fn compensate(
-        &self,
-        state: &mut CertificateProvisioningSaga,
-    ) -> Result<CompensationResult, SagaExecutorError> {
-        use crate::domain::sagas::ProvisioningCompensationStep;
-
-        // Start compensation
-        let mut current_step = state.start_compensation();
-
-        loop {
-            match current_step {
-                ProvisioningCompensationStep::ClearYubiKeySlot => {
-                    // In a real implementation, clear the YubiKey slot
-                    // For now, simulate success
-                }
-                ProvisioningCompensationStep::RevokeCertificate => {
-                    // In a real implementation, revoke the certificate
-                }
-                ProvisioningCompensationStep::RevokeKey => {
-                    // In a real implementation, revoke the key
-                }
-            }
+    /// Revoke the slot key announced by `KeyGenerated`
+    ///
+    /// The slot key itself stays until the slot is next generated, since
+    /// PIV offers no key deletion on most firmware.
+    async fn revoke_key(&self, saga: &mut CertificateProvisioning) -> Result<(), String> {
+        use crate::events::{KeyEvents, KeyRevokedEvent};
+        use crate::types::RevocationReason;
+        use chrono::Utc;
 
-            match state.advance_compensation() {
-                Some(next) => current_step = next,
-                None => break,
-            }
+        if let Some(key_id) = saga.artifacts.key_id {
+            saga.pending_events.push(DomainEvent::Key(KeyEvents::KeyRevoked(KeyRevokedEvent {
+                key_id: key_id.as_uuid(),
+                reason: RevocationReason::CessationOfOperation,
+                revoked_at: Utc::now(),
+                revoked_by: ActorId::system(SAGA_ACTOR),
+                correlation_id: saga.correlation_id,
+                causation_id: Some(saga.saga_id),
+            })));
         }
+        saga.artifacts.public_key = None;
+        Ok(())
+    }
 
-        Ok(CompensationResult::FullyCompensated)
+    /// Revoke the issued certificate so the issuer's next CRL lists it
+    async fn revoke_certificate(&self, saga: &mut CertificateProvisioning) -> Result<(), String> {
+        use crate::events::certificate::CertificateRevokedEvent;
+        use crate::events::CertificateEvents;
+        use chrono::Utc;
+
+        if let Some(cert_id) = saga.artifacts.certificate_id {
+            saga.pending_events.push(DomainEvent::Certificate(CertificateEvents::CertificateRevoked(
+                CertificateRevokedEvent {
+                    cert_id: cert_id.as_uuid(),
+                    reason: "cessationOfOperation".to_string(),
+                    revoked_at: Utc::now(),
+                    revoked_by: ActorId::system(SAGA_ACTOR),
+                    crl_distribution_point: None,
+                    correlation_id: saga.correlation_id,
+                    causation_id: Some(saga.saga_id),
+                },
+            )));
+        }
+        saga.artifacts.certificate_pem = None;
+        Ok(())
     }
 
-    fn saga_type_name(&self) -> &'static str {
-        "certificate_provisioning"
+    async fn clear_yubikey_slot(&self, saga: &mut CertificateProvisioning) -> Result<(), String> {
+        self.yubikey
+            .delete_certificate(&saga.request.yubikey_serial, port_slot(&saga.request.target_slot), &self.management_key)
+            .await
+            .map_err(|e| format!("ClearYubiKeySlot: {}", e))?;
+        saga.artifacts.provisioned_slot = None;
+        Ok(())
     }
 }
 
@@ -659,10 +672,11 @@ impl From<EventPublishError> for SagaCommandHandlerError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::sagas::{CertificatePurpose, ProvisioningRequest};
+    use crate::domain::sagas::{CertificateProvisioningSaga, CertificatePurpose, ProvisioningRequest};
     use crate::domain::ids::*;
     use crate::domain::yubikey::PIVSlot;
     use crate::events::KeyAlgorithm;
+    use crate::adapters::MockYubiKeyAdapter;
     use crate::ports::{JetStreamHeaders, JetStreamError, PublishAck};
     use std::sync::Arc;
     use tokio::sync::Mutex;
@@ -794,7 +808,7 @@ mod tests {
             person_name: "Test User".to_string(),
             person_email: "test@example.com".to_string(),
             purpose: CertificatePurpose::Authentication,
-            key_algorithm: KeyAlgorithm::Ed25519,
+            key_algorithm: KeyAlgorithm::Ecdsa { curve: "P-256".to_string() },
             issuing_ca_id: CertificateId::new(),
             yubikey_device_id: YubiKeyDeviceId::new(),
             yubikey_serial: "12345678".to_string(),
@@ -803,21 +817,74 @@ mod tests {
         }
     }
 
+    /// Executor backed by a mock YubiKey and a freshly generated intermediate CA
+    fn create_test_executor(yubikey: MockYubiKeyAdapter) -> CertificateProvisioningExecutor {
+        use crate::crypto::seed_derivation::derive_master_seed;
+        use crate::crypto::x509::{create_intermediate_ca, generate_root_ca, IntermediateCAParams, RootCAParams};
+
+        let root_seed = derive_master_seed("test passphrase", "test-org").unwrap().derive_child("root-ca");
+        let (root_ca, _) = generate_root_ca(&root_seed, RootCAParams::default(), Uuid::now_v7(), None).unwrap();
+        let root_key = rcgen::KeyPair::from_pem(&root_ca.private_key_pem).unwrap();
+        let intermediate = create_intermediate_ca(
+            &root_seed.derive_child("intermediate-engineering"),
+            IntermediateCAParams::default(),
+            &root_ca.certificate_pem,
+            &root_key,
+            &[],
+            Uuid::now_v7(),
+            Uuid::now_v7(),
+            None,
+        ).unwrap();
+
+        CertificateProvisioningExecutor::new(
+            Arc::new(yubikey),
+            ProvisioningIssuer {
+                chain_pem: intermediate.chain_pem,
+                key_pem: intermediate.certificate.private_key_pem,
+                constraints: vec![],
+            },
+            SecureString::new("123456"),
+            // The mock authenticates key generation with the PIN
+            SecureString::new("123456"),
+        )
+    }
+
     #[tokio::test]
     async fn test_saga_handler_config_default() {
         let config = SagaHandlerConfig::default();
         assert_eq!(config.max_step_retries, 3);
-        assert!(config.auto_compensate);
         assert_eq!(config.saga_timeout_secs, 300);
     }
 
+    /// Engine and a started run over the given executor
+    fn start_provisioning(
+        executor: CertificateProvisioningExecutor,
+        request: ProvisioningRequest,
+    ) -> (SagaEngine<CertificateProvisioning>, CertificateProvisioningSaga) {
+        let context = CertificateProvisioning::new(request);
+        let engine = SagaEngine::new(CertificateProvisioning::definition(Arc::new(executor)));
+        let (saga_id, correlation_id) = (context.saga_id, context.correlation_id);
+        let (saga, _) = engine.start_with_id(saga_id, context, "test", correlation_id);
+        (engine, saga)
+    }
+
+    /// Run one step, returning the domain events it queued
+    async fn step(
+        engine: &SagaEngine<CertificateProvisioning>,
+        saga: &mut CertificateProvisioningSaga,
+    ) -> Vec<DomainEvent> {
+        engine.advance(saga).await;
+        std::mem::take(&mut saga.context.pending_events)
+    }
+
     #[tokio::test]
     async fn test_handle_provision_certificate() {
         let port = MockJetStreamPort::new();
         let handler = SagaCommandHandler::new(port, SagaHandlerConfig::default());
 
         let request = create_test_request();
-        let result = handler.handle_provision_certificate(request).await;
+        let executor = create_test_executor(MockYubiKeyAdapter::default());
+        let result = handler.handle_provision_certificate(request, Arc::new(executor)).await;
 
         assert!(result.is_ok());
         let saga_result = result.unwrap();
@@ -827,36 +894,116 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_certificate_provisioning_executor() {
-        let executor = CertificateProvisioningExecutor::new();
-        let request = create_test_request();
-        let mut saga = CertificateProvisioningSaga::new(request);
+    async fn test_handle_provision_certificate_compensates_failure() {
+        let port = MockJetStreamPort::new();
+        let handler = SagaCommandHandler::new(port, SagaHandlerConfig::default());
 
-        // Start the saga - transitions to GeneratingKey
-        saga.start().unwrap();
+        let mut executor = create_test_executor(MockYubiKeyAdapter::default());
+        executor.issuer.key_pem = "not a key".to_string();
+        let result = handler
+            .handle_provision_certificate(create_test_request(), Arc::new(executor))
+            .await
+            .unwrap();
 
-        // Step 1: GeneratingKey -> GeneratingCertificate (emits KeyGenerated event)
-        let step1 = executor.execute_next_step(&mut saga).await.unwrap();
-        assert!(matches!(step1, StepExecutionResult::Continue { .. }));
-        assert_eq!(step1.domain_events().len(), 1);
+        assert_eq!(result.status, SagaCommandStatus::FailedAndCompensated);
+        assert!(result.error.unwrap().contains("IssueCertificate"));
+    }
 
-        // Step 2: GeneratingCertificate -> ProvisioningToYubiKey (emits CertificateGenerated)
-        let step2 = executor.execute_next_step(&mut saga).await.unwrap();
-        assert!(matches!(step2, StepExecutionResult::Continue { .. }));
-        assert_eq!(step2.domain_events().len(), 1);
+    #[tokio::test]
+    async fn test_certificate_provisioning_executor() {
+        let executor = create_test_executor(MockYubiKeyAdapter::default());
+        let (engine, mut saga) = start_provisioning(executor, create_test_request());
+
+        // Step 1: GenerateKey (emits KeyGenerated event)
+        assert_eq!(step(&engine, &mut saga).await.len(), 1);
 
-        // Step 3: ProvisioningToYubiKey -> VerifyingProvisioning (emits YubiKeyProvisioned)
-        let step3 = executor.execute_next_step(&mut saga).await.unwrap();
-        assert!(matches!(step3, StepExecutionResult::Continue { .. }));
-        assert_eq!(step3.domain_events().len(), 1);
+        // Step 2: IssueCertificate (emits CertificateGenerated, CertificateSigned)
+        assert_eq!(step(&engine, &mut saga).await.len(), 2);
 
-        // Step 4: VerifyingProvisioning -> Completed (with result, no additional events)
-        let step4 = executor.execute_next_step(&mut saga).await.unwrap();
-        assert!(matches!(step4, StepExecutionResult::Completed { .. }));
-        assert_eq!(step4.domain_events().len(), 0);
+        // Step 3: ProvisionToYubiKey (emits YubiKeyProvisioned)
+        assert_eq!(step(&engine, &mut saga).await.len(), 1);
+
+        // Step 4: VerifyProvisioning (no additional events)
+        assert!(step(&engine, &mut saga).await.is_empty());
 
         // Verify saga is in completed state
         assert!(saga.is_completed());
+        assert!(saga.context.is_verification_successful());
+    }
+
+    #[tokio::test]
+    async fn test_slot_certificate_is_issued_for_on_card_key() {
+        use x509_parser::prelude::*;
+
+        let yubikey = MockYubiKeyAdapter::default();
+        let (engine, mut saga) = start_provisioning(create_test_executor(yubikey.clone()), create_test_request());
+
+        engine.run_to_completion(&mut saga).await;
+        assert!(saga.is_completed());
+
+        // The slot holds the issued certificate, bound to the slot key
+        let stored = yubikey.read_certificate("12345678", PivSlot::Authentication).await.unwrap().unwrap();
+        let (_, pem) = parse_x509_pem(&stored).unwrap();
+        let cert = pem.parse_x509().unwrap();
+        assert_eq!(
+            cert.public_key().subject_public_key.data.as_ref(),
+            saga.context.artifacts.public_key.as_deref().unwrap()
+        );
+        assert!(cert.subject().to_string().contains("Test User"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_verification_clears_slot() {
+        use crate::events::{CertificateEvents, KeyEvents};
+
+        let yubikey = MockYubiKeyAdapter::default();
+        let (engine, mut saga) = start_provisioning(create_test_executor(yubikey.clone()), create_test_request());
+
+        for _ in 0..3 {
+            step(&engine, &mut saga).await;
+        }
+        let key_id = saga.context.artifacts.key_id.unwrap().as_uuid();
+        let cert_id = saga.context.artifacts.certificate_id.unwrap().as_uuid();
+
+        // Something else overwrote the slot before verification
+        let pin = SecureString::new("123456");
+        yubikey.import_certificate("12345678", PivSlot::Authentication, b"other", &pin).await.unwrap();
+
+        let events = step(&engine, &mut saga).await;
+        assert!(saga.is_failed());
+        assert_eq!(
+            saga.context.artifacts.verification_status,
+            Some(crate::domain::sagas::VerificationStatus::FingerprintMismatch)
+        );
+        let error = saga.error.clone().unwrap();
+        assert_eq!(error.failed_step, "VerifyProvisioning");
+        assert!(matches!(error.compensation_result, Some(CompensationResult::FullyCompensated)));
+
+        // Completed steps are undone in reverse: slot, certificate, key
+        assert!(saga.context.artifacts.certificate_pem.is_none());
+        assert_eq!(yubikey.read_certificate("12345678", PivSlot::Authentication).await.unwrap(), None);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            DomainEvent::Certificate(CertificateEvents::CertificateRevoked(e)) if e.cert_id == cert_id
+        ));
+        assert!(matches!(
+            &events[1],
+            DomainEvent::Key(KeyEvents::KeyRevoked(e)) if e.key_id == key_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_key_algorithm_fails_before_touching_slot() {
+        let mut request = create_test_request();
+        request.key_algorithm = KeyAlgorithm::Ed25519;
+        let (engine, mut saga) = start_provisioning(create_test_executor(MockYubiKeyAdapter::default()), request);
+
+        let events = step(&engine, &mut saga).await;
+        assert!(saga.is_failed());
+        assert!(saga.context.artifacts.key_id.is_none());
+        // Nothing was generated, so there is nothing to revoke
+        assert!(events.is_empty());
     }
 
     #[tokio::test]
@@ -874,18 +1021,24 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_compensation() {
-        let executor = CertificateProvisioningExecutor::new();
-        let request = create_test_request();
-        let mut saga = CertificateProvisioningSaga::new(request);
-
-        saga.start().unwrap();
-        saga.advance(); // GeneratingKey
-        saga.advance(); // GeneratingCertificate
-        saga.fail("Test failure", "GeneratingCertificate");
-
-        let result = executor.compensate(&mut saga).await.unwrap();
-        assert!(matches!(result, CompensationResult::FullyCompensated));
+    async fn test_failed_issuance_revokes_generated_key() {
+        use crate::events::KeyEvents;
+
+        let mut executor = create_test_executor(MockYubiKeyAdapter::default());
+        executor.issuer.key_pem = "not a key".to_string();
+        let (engine, mut saga) = start_provisioning(executor, create_test_request());
+
+        step(&engine, &mut saga).await;
+        let key_id = saga.context.artifacts.key_id.unwrap().as_uuid();
+
+        // No certificate was issued, so only the key is revoked
+        let events = step(&engine, &mut saga).await;
+        assert!(saga.is_failed());
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            DomainEvent::Key(KeyEvents::KeyRevoked(e)) if e.key_id == key_id
+        ));
     }
 
     #[tokio::test]
@@ -906,20 +1059,13 @@ mod tests {
     /// - A7 (Change Prefixes): Events form causal chain via correlation_id
     #[tokio::test]
     async fn test_domain_event_formation_and_causality() {
-        use crate::events::DomainEvent;
-
-        let executor = CertificateProvisioningExecutor::new();
-        let request = create_test_request();
-        let mut saga = CertificateProvisioningSaga::new(request);
+        let (engine, mut saga) =
+            start_provisioning(create_test_executor(MockYubiKeyAdapter::default()), create_test_request());
         let correlation_id = saga.correlation_id();
         let saga_id = saga.saga_id();
 
-        // Start the saga
-        saga.start().unwrap();
-
         // Step 1: KeyGenerated event
-        let step1 = executor.execute_next_step(&mut saga).await.unwrap();
-        let events1 = step1.domain_events();
+        let events1 = step(&engine, &mut saga).await;
         assert_eq!(events1.len(), 1);
 
         // Verify KeyGenerated event structure
@@ -939,10 +1085,9 @@ mod tests {
             _ => panic!("Expected Key domain event"),
         }
 
-        // Step 2: CertificateGenerated event
-        let step2 = executor.execute_next_step(&mut saga).await.unwrap();
-        let events2 = step2.domain_events();
-        assert_eq!(events2.len(), 1);
+        // Step 2: CertificateGenerated and CertificateSigned events
+        let events2 = step(&engine, &mut saga).await;
+        assert_eq!(events2.len(), 2);
 
         match &events2[0] {
             DomainEvent::Certificate(cert_event) => {
@@ -959,10 +1104,13 @@ mod tests {
             }
             _ => panic!("Expected Certificate domain event"),
         }
+        assert!(matches!(
+            &events2[1],
+            DomainEvent::Certificate(crate::events::CertificateEvents::CertificateSigned(_))
+        ));
 
         // Step 3: YubiKeyProvisioned event
-        let step3 = executor.execute_next_step(&mut saga).await.unwrap();
-        let events3 = step3.domain_events();
+        let events3 = step(&engine, &mut saga).await;
         assert_eq!(events3.len(), 1);
 
         match &events3[0] {
@@ -982,9 +1130,8 @@ mod tests {
         }
 
         // Step 4: Completed (no additional events)
-        let step4 = executor.execute_next_step(&mut saga).await.unwrap();
-        assert!(matches!(step4, StepExecutionResult::Completed { .. }));
-        assert!(step4.domain_events().is_empty());
+        assert!(step(&engine, &mut saga).await.is_empty());
+        assert!(saga.is_completed());
     }
 
     /// Test FRP Axiom A9: Semantic Preservation Under Composition
@@ -992,26 +1139,23 @@ mod tests {
     /// Verify that the order of events respects the saga step sequence
     #[tokio::test]
     async fn test_axiom_a9_composition_semantics() {
-        let executor = CertificateProvisioningExecutor::new();
-        let request = create_test_request();
-        let mut saga = CertificateProvisioningSaga::new(request);
-        saga.start().unwrap();
+        let (engine, mut saga) =
+            start_provisioning(create_test_executor(MockYubiKeyAdapter::default()), create_test_request());
 
         // Collect all events in order
         let mut all_events = Vec::new();
 
-        for _ in 0..4 {
-            if saga.is_terminal() { break; }
-            let result = executor.execute_next_step(&mut saga).await.unwrap();
-            all_events.extend(result.domain_events().to_vec());
+        while !saga.is_terminal() {
+            all_events.extend(step(&engine, &mut saga).await);
         }
 
-        // Total should be 3 domain events (Key, Certificate, YubiKey)
-        assert_eq!(all_events.len(), 3);
+        // Total should be 4 domain events (Key, Certificate generated + signed, YubiKey)
+        assert_eq!(all_events.len(), 4);
 
         // Verify correct event order (composition semantics preserved)
-        assert!(matches!(&all_events[0], crate::events::DomainEvent::Key(_)));
-        assert!(matches!(&all_events[1], crate::events::DomainEvent::Certificate(_)));
-        assert!(matches!(&all_events[2], crate::events::DomainEvent::YubiKey(_)));
+        assert!(matches!(&all_events[0], DomainEvent::Key(_)));
+        assert!(matches!(&all_events[1], DomainEvent::Certificate(_)));
+        assert!(matches!(&all_events[2], DomainEvent::Certificate(_)));
+        assert!(matches!(&all_events[3], DomainEvent::YubiKey(_)));
     }
 }
@@ -242,6 +242,31 @@ impl<T> StepExecutionResult<T> {
     }
 }
 
+/// Outcome of compensating a saga
+///
+/// Like a step, compensation can produce domain events, e.g. revoking a
+/// certificate an earlier step announced; the caller publishes them.
+#[derive(Debug, Clone)]
+pub struct CompensationOutcome {
+    /// How far compensation got
+    pub result: CompensationResult,
+    /// Domain events produced by the compensation steps
+    pub domain_events: Vec<crate::events::DomainEvent>,
+}
+
+impl CompensationOutcome {
+    /// Create an outcome with domain events
+    pub fn with_events(result: CompensationResult, events: Vec<crate::events::DomainEvent>) -> Self {
+        Self { result, domain_events: events }
+    }
+}
+
+impl From<CompensationResult> for CompensationOutcome {
+    fn from(result: CompensationResult) -> Self {
+        Self::with_events(result, vec![])
+    }
+}
+
 /// Async saga executor trait
 #[async_trait::async_trait]
 pub trait AsyncSagaExecutor<S: SagaState>: Send + Sync {
@@ -251,8 +276,8 @@ pub trait AsyncSagaExecutor<S: SagaState>: Send + Sync {
     /// Execute the next step of the saga
     async fn execute_next_step(&self, state: &mut S) -> SagaExecutorResult<StepExecutionResult<Self::Output>>;
 
-    /// Compensate (rollback) the saga
-    async fn compensate(&self, state: &mut S) -> SagaExecutorResult<CompensationResult>;
+    /// Compensate (rollback) the saga, returning domain events
+    async fn compensate(&self, state: &mut S) -> SagaExecutorResult<CompensationOutcome>;
 
     /// Get the saga type name
     fn saga_type_name(&self) -> &'static str;
@@ -513,7 +538,10 @@ impl<P: JetStreamPort> JetStreamSagaExecutor<P> {
     }
 
     /// Compensate a failed saga
-    pub async fn compensate<S, E>(&self, saga_id: Uuid, executor: &E) -> SagaExecutorResult<CompensationResult>
+    ///
+    /// Domain events produced by the compensation are returned to the
+    /// caller for publication, as with `execute_step`.
+    pub async fn compensate<S, E>(&self, saga_id: Uuid, executor: &E) -> SagaExecutorResult<CompensationOutcome>
     where
         S: SagaState + Serialize + for<'de> Deserialize<'de>,
         E: AsyncSagaExecutor<S>,
@@ -529,7 +557,7 @@ impl<P: JetStreamPort> JetStreamSagaExecutor<P> {
         self.publish_saga_event(saga_id, &comp_started).await?;
 
         // Execute compensation
-        let result = executor.compensate(&mut persisted.state).await?;
+        let outcome = executor.compensate(&mut persisted.state).await?;
 
         // Save updated state
         persisted.increment_version();
@@ -538,12 +566,12 @@ impl<P: JetStreamPort> JetStreamSagaExecutor<P> {
         // Publish compensation completed
         let comp_completed = SagaEvent::CompensationCompleted {
             saga_id,
-            result: result.clone(),
+            result: outcome.result.clone(),
             completed_at: Utc::now(),
         };
         self.publish_saga_event(saga_id, &comp_completed).await?;
 
-        Ok(result)
+        Ok(outcome)
     }
 
     /// Publish a saga event to JetStream
@@ -0,0 +1,198 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Organization-Scoped Streams for Multi-Tenant Workstations
+//!
+//! One operator workstation may manage keys for several client
+//! organizations. Each organization gets its own subject namespace,
+//! JetStream streams and offline projection, so events and manifests of
+//! different clients never mix.
+//!
+//! ## Layout
+//!
+//! ```text
+//! organization.{org_id}.>                    all subjects of one client
+//! ├── organization.{org_id}.keys.events.>    → KEYS_EVENTS_{ORG_ID}
+//! └── organization.{org_id}.keys.commands.>  → KEYS_COMMANDS_{ORG_ID}
+//!
+//! {root}/organizations/{org_id}/             OfflineKeyProjection
+//! ```
+//!
+//! The organization ID rather than its name is the subject token, so two
+//! clients with the same name cannot share a namespace.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use cim_keys::domain::nats::tenancy::OrganizationScope;
+//!
+//! let scope = OrganizationScope::new(org_id);
+//! jetstream.create_stream(&scope.events_stream()).await?;
+//!
+//! let publisher = EventPublisher::new(port).for_organization(scope.clone());
+//! publisher.publish(&event, correlation_id, causation_id).await?;
+//! // => organization.{org_id}.keys.events.key.generated
+//! ```
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::jetstream::{ConsumerConfig, StreamConfig, KEYS_COMMANDS_STREAM, KEYS_EVENTS_STREAM, KEYS_SUBJECT_PREFIX};
+use super::subjects::Subject;
+
+/// First token of every organization-scoped subject
+pub const ORGANIZATION_SUBJECT_ROOT: &str = "organization";
+
+/// Subject namespace and streams of one organization
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OrganizationScope {
+    organization_id: Uuid,
+}
+
+impl OrganizationScope {
+    pub fn new(organization_id: Uuid) -> Self {
+        Self { organization_id }
+    }
+
+    pub fn organization_id(&self) -> Uuid {
+        self.organization_id
+    }
+
+    /// `organization.{org_id}`
+    pub fn root(&self) -> Subject {
+        Subject::new(ORGANIZATION_SUBJECT_ROOT).unit(self.organization_id.to_string())
+    }
+
+    /// `organization.{org_id}.>`, the pattern for every subject of the organization
+    pub fn all(&self) -> Subject {
+        self.root().wildcard_suffix()
+    }
+
+    /// Place an unscoped subject, e.g. `keys.events.key.generated`, under
+    /// the organization
+    pub fn scope(&self, subject: &Subject) -> Subject {
+        self.root().concat(subject.clone())
+    }
+
+    /// String form of [`scope`](Self::scope)
+    pub fn scope_str(&self, subject: &str) -> String {
+        format!("{}.{}", self.root(), subject)
+    }
+
+    /// Whether `subject` lies strictly inside the organization's namespace
+    ///
+    /// Patterns are never contained: `organization.>` or
+    /// `organization.*.keys.>` would reach other organizations.
+    pub fn contains(&self, subject: &str) -> bool {
+        Subject::parse(subject).is_ok_and(|subject| subject.matches(&self.all()))
+    }
+
+    /// Fail unless `subject` lies inside the organization's namespace
+    pub fn require(&self, subject: &str) -> Result<(), TenancyError> {
+        if self.contains(subject) {
+            Ok(())
+        } else {
+            Err(TenancyError::SubjectOutOfScope {
+                subject: subject.to_string(),
+                organization_id: self.organization_id,
+            })
+        }
+    }
+
+    /// `organization.{org_id}.keys.events.>`
+    pub fn events_subject(&self) -> Subject {
+        self.scope(&Subject::new(KEYS_SUBJECT_PREFIX).unit("events").wildcard_suffix())
+    }
+
+    /// `organization.{org_id}.keys.commands.>`
+    pub fn commands_subject(&self) -> Subject {
+        self.scope(&Subject::new(KEYS_SUBJECT_PREFIX).unit("commands").wildcard_suffix())
+    }
+
+    /// Event stream of the organization, `KEYS_EVENTS_{ORG_ID}`
+    pub fn events_stream(&self) -> StreamConfig {
+        StreamConfig {
+            name: self.stream_name(KEYS_EVENTS_STREAM),
+            subjects: vec![self.events_subject().as_str()],
+            description: Some(format!("CIM Keys domain events of organization {}", self.organization_id)),
+            ..StreamConfig::keys_events()
+        }
+    }
+
+    /// Command queue of the organization, `KEYS_COMMANDS_{ORG_ID}`
+    pub fn commands_stream(&self) -> StreamConfig {
+        StreamConfig {
+            name: self.stream_name(KEYS_COMMANDS_STREAM),
+            subjects: vec![self.commands_subject().as_str()],
+            description: Some(format!("CIM Keys command queue of organization {}", self.organization_id)),
+            ..StreamConfig::keys_commands()
+        }
+    }
+
+    /// Durable event processor bound to the organization's events
+    pub fn events_consumer(&self) -> ConsumerConfig {
+        let name = format!("keys-processor-{}", self.organization_id.simple());
+        ConsumerConfig {
+            durable_name: Some(name.clone()),
+            name,
+            ..ConsumerConfig::key_events_processor()
+        }
+        .with_filter(self.events_subject().as_str())
+    }
+
+    fn stream_name(&self, base: &str) -> String {
+        // Stream names cannot contain dots; the simple UUID form is plain hex
+        format!("{}_{}", base, self.organization_id.simple().to_string().to_uppercase())
+    }
+}
+
+/// Errors for organization scoping
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TenancyError {
+    #[error("Subject {subject} is outside organization {organization_id}")]
+    SubjectOutOfScope { subject: String, organization_id: Uuid },
+
+    #[error("Event of organization {found} does not belong to organization {expected}")]
+    OrganizationMismatch { expected: Uuid, found: Uuid },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subjects_are_prefixed_by_organization() {
+        let org = Uuid::now_v7();
+        let scope = OrganizationScope::new(org);
+
+        assert_eq!(scope.all().as_str(), format!("organization.{}.>", org));
+        assert_eq!(
+            scope.scope_str("keys.events.key.generated"),
+            format!("organization.{}.keys.events.key.generated", org)
+        );
+        assert_eq!(scope.events_stream().subjects, vec![format!("organization.{}.keys.events.>", org)]);
+        assert_eq!(
+            scope.events_consumer().filter_subject,
+            Some(format!("organization.{}.keys.events.>", org))
+        );
+    }
+
+    #[test]
+    fn test_scope_is_strict() {
+        let (ours, theirs) = (OrganizationScope::new(Uuid::now_v7()), OrganizationScope::new(Uuid::now_v7()));
+
+        assert!(ours.contains(&ours.scope_str("keys.events.key.generated")));
+        assert!(!ours.contains(&theirs.scope_str("keys.events.key.generated")));
+        // Unscoped subjects, the bare root and wildcards are all rejected
+        assert!(!ours.contains("keys.events.key.generated"));
+        assert!(!ours.contains(&ours.root().as_str()));
+        assert!(!ours.contains("organization.>"));
+        assert!(!ours.contains("organization.*.keys.events.key.generated"));
+        assert!(matches!(
+            ours.require(&theirs.scope_str("keys.events.key.generated")),
+            Err(TenancyError::SubjectOutOfScope { .. })
+        ));
+
+        assert_ne!(ours.events_stream().name, theirs.events_stream().name);
+        assert!(ours.events_stream().name.starts_with("KEYS_EVENTS_"));
+    }
+}
@@ -0,0 +1,485 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Policy Evaluation Cache
+//!
+//! `evaluate_policies` walks every bound policy and its conditions on each
+//! call, and a command is checked against dozens of them. The
+//! [`PolicyEvaluationCache`] memoizes evaluations per person and entity,
+//! keyed by a digest of the entity's bindings and a fingerprint of the
+//! evaluation context.
+//!
+//! ## Invalidation
+//!
+//! - The bindings digest covers the entity's active bindings and the
+//!   version and enabled flag of each bound policy, so a rebinding or a new
+//!   policy version misses the cache by itself.
+//! - The context fingerprint covers every context field but `current_time`.
+//!   Time is handled by expiry instead: an entry expires after the TTL, or
+//!   earlier at the next instant a bound policy could evaluate differently
+//!   (an effective date, a time window edge, the top of the hour for
+//!   business hours, a minimum employment duration being reached).
+//! - `apply_event` drops entries affected by policy and binding events,
+//!   which covers changes the digest cannot see, such as `PolicyUpdated`.
+//!
+//! A hit returns the evaluation as computed, with its original
+//! `evaluated_at`.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::{
+    evaluate_policies, Policy, PolicyBinding, PolicyCondition, PolicyEntityType, PolicyEvaluation,
+    PolicyEvaluationContext,
+};
+use crate::events::{DomainEvent, OrganizationEvents, RelationshipEvents};
+
+/// Entries kept before the soonest-expiring one is evicted
+pub const DEFAULT_CACHE_CAPACITY: usize = 10_000;
+
+/// What a cached evaluation was computed from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PolicyCacheKey {
+    pub person_id: Uuid,
+    pub entity_id: Uuid,
+    pub entity_type: PolicyEntityType,
+    /// SHA-256 of the entity's active bindings and bound policy versions, hex
+    pub bindings_digest: String,
+    /// SHA-256 of the evaluation context without `current_time`, hex
+    pub context_fingerprint: String,
+}
+
+/// Cache effectiveness counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyCacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped because they expired
+    pub expirations: u64,
+    /// Entries dropped by `apply_event` or an `invalidate_*` call
+    pub invalidations: u64,
+    /// Entries dropped to stay within capacity
+    pub evictions: u64,
+}
+
+impl PolicyCacheMetrics {
+    pub fn lookups(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    /// Fraction of lookups served from the cache, 0.0 before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        match self.lookups() {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CachedEvaluation {
+    evaluation: PolicyEvaluation,
+    binding_ids: Vec<Uuid>,
+    policy_ids: Vec<Uuid>,
+    expires_at: DateTime<Utc>,
+}
+
+impl CachedEvaluation {
+    fn is_fresh_at(&self, at: DateTime<Utc>) -> bool {
+        self.evaluation.evaluated_at <= at && at < self.expires_at
+    }
+}
+
+/// Memoizing layer over `evaluate_policies`
+#[derive(Debug, Clone)]
+pub struct PolicyEvaluationCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: HashMap<PolicyCacheKey, CachedEvaluation>,
+    metrics: PolicyCacheMetrics,
+}
+
+impl Default for PolicyEvaluationCache {
+    fn default() -> Self {
+        Self::new(Duration::minutes(5))
+    }
+}
+
+impl PolicyEvaluationCache {
+    /// Create a cache whose entries live at most `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            capacity: DEFAULT_CACHE_CAPACITY,
+            entries: HashMap::new(),
+            metrics: PolicyCacheMetrics::default(),
+        }
+    }
+
+    /// Limit the number of cached evaluations
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn metrics(&self) -> PolicyCacheMetrics {
+        self.metrics
+    }
+
+    /// `evaluate_policies`, served from the cache when a fresh entry exists
+    pub fn evaluate(
+        &mut self,
+        policies: &[Policy],
+        bindings: &[PolicyBinding],
+        entity_id: Uuid,
+        entity_type: PolicyEntityType,
+        context: &PolicyEvaluationContext,
+    ) -> PolicyEvaluation {
+        let bound: Vec<&PolicyBinding> = bindings
+            .iter()
+            .filter(|b| b.entity_id == entity_id && b.entity_type == entity_type && b.active)
+            .collect();
+        let key = PolicyCacheKey {
+            person_id: context.person_id,
+            entity_id,
+            entity_type,
+            bindings_digest: bindings_digest(&bound, policies),
+            context_fingerprint: context_fingerprint(context),
+        };
+        let now = context.current_time;
+
+        match self.entries.get(&key).map(|entry| entry.is_fresh_at(now)) {
+            Some(true) => {
+                self.metrics.hits += 1;
+                return self.entries[&key].evaluation.clone();
+            }
+            Some(false) => {
+                self.entries.remove(&key);
+                self.metrics.expirations += 1;
+            }
+            None => {}
+        }
+        self.metrics.misses += 1;
+
+        let evaluation = evaluate_policies(policies, bindings, entity_id, entity_type, context);
+        let policy_ids: Vec<Uuid> = bound.iter().map(|b| b.policy_id).collect();
+        let expires_at = match next_boundary(policies, &policy_ids, context) {
+            Some(boundary) => boundary.min(now + self.ttl),
+            None => now + self.ttl,
+        };
+
+        self.make_room(now);
+        self.entries.insert(
+            key,
+            CachedEvaluation {
+                evaluation: evaluation.clone(),
+                binding_ids: bound.iter().map(|b| b.id).collect(),
+                policy_ids,
+                expires_at,
+            },
+        );
+        evaluation
+    }
+
+    /// Drop evaluations that involve a policy
+    pub fn invalidate_policy(&mut self, policy_id: Uuid) {
+        self.invalidate(|_, entry| entry.policy_ids.contains(&policy_id));
+    }
+
+    /// Drop evaluations that involve a binding
+    pub fn invalidate_binding(&mut self, binding_id: Uuid) {
+        self.invalidate(|_, entry| entry.binding_ids.contains(&binding_id));
+    }
+
+    /// Drop evaluations of an entity, or made for a person
+    pub fn invalidate_entity(&mut self, entity_id: Uuid) {
+        self.invalidate(|key, _| key.entity_id == entity_id || key.person_id == entity_id);
+    }
+
+    /// Drop every evaluation
+    pub fn clear(&mut self) {
+        self.invalidate(|_, _| true);
+    }
+
+    /// Drop evaluations made stale by a policy or binding event
+    ///
+    /// Bindings are `PolicyGovernsEntity` relationships, so a terminated
+    /// relationship is an unbinding.
+    pub fn apply_event(&mut self, event: &DomainEvent) {
+        match event {
+            DomainEvent::Organization(event) => {
+                if let Some(policy_id) = changed_policy(event) {
+                    self.invalidate_policy(policy_id);
+                }
+            }
+            DomainEvent::Relationship(RelationshipEvents::RelationshipTerminated(e)) => {
+                self.invalidate_binding(e.relationship_id);
+            }
+            _ => {}
+        }
+    }
+
+    fn invalidate(&mut self, stale: impl Fn(&PolicyCacheKey, &CachedEvaluation) -> bool) {
+        let before = self.entries.len();
+        self.entries.retain(|key, entry| !stale(key, entry));
+        self.metrics.invalidations += (before - self.entries.len()) as u64;
+    }
+
+    fn make_room(&mut self, now: DateTime<Utc>) {
+        if self.entries.len() < self.capacity {
+            return;
+        }
+
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| entry.is_fresh_at(now));
+        self.metrics.expirations += (before - self.entries.len()) as u64;
+
+        if self.entries.len() >= self.capacity {
+            let soonest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.expires_at)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = soonest {
+                self.entries.remove(&key);
+                self.metrics.evictions += 1;
+            }
+        }
+    }
+}
+
+/// Policy whose content or lifecycle an organization event changes
+fn changed_policy(event: &OrganizationEvents) -> Option<Uuid> {
+    match event {
+        OrganizationEvents::PolicyCreated(e) => Some(e.policy_id),
+        OrganizationEvents::PolicyUpdated(e) => Some(e.policy_id),
+        OrganizationEvents::PolicyRevoked(e) => Some(e.policy_id),
+        OrganizationEvents::PolicyActivated(e) => Some(e.policy_id),
+        OrganizationEvents::PolicyAmended(e) => Some(e.policy_id),
+        OrganizationEvents::PolicySuspended(e) => Some(e.policy_id),
+        OrganizationEvents::PolicySuperseded(e) => Some(e.policy_id),
+        _ => None,
+    }
+}
+
+fn bindings_digest(bound: &[&PolicyBinding], policies: &[Policy]) -> String {
+    let mut lines: Vec<String> = bound
+        .iter()
+        .map(|b| format!("binding {} {}", b.id, b.policy_id))
+        .collect();
+    lines.extend(
+        policies
+            .iter()
+            .filter(|p| bound.iter().any(|b| b.policy_id == p.id.as_uuid()))
+            .map(|p| format!("policy {} {} {}", p.id.as_uuid(), p.version, p.enabled)),
+    );
+    lines.sort();
+    digest_lines(&lines)
+}
+
+fn context_fingerprint(context: &PolicyEvaluationContext) -> String {
+    let sorted = |mut items: Vec<String>| {
+        items.sort();
+        items.join(",")
+    };
+    let lines = [
+        format!("person {}", context.person_id),
+        format!("clearance {:?}", context.person_clearance),
+        format!("units {}", sorted(context.person_units.iter().map(|u| u.to_string()).collect())),
+        format!("roles {}", sorted(context.person_roles.iter().map(|r| r.to_string()).collect())),
+        format!("employed {}", context.employment_start_date.to_rfc3339()),
+        format!("training {}", sorted(context.completed_training.clone())),
+        format!("location {:?}", context.current_location),
+        format!("ip {:?}", context.source_ip),
+        format!("mfa {}", context.mfa_verified),
+        format!("yubikey {}", context.yubikey_present),
+        format!(
+            "witnesses {}",
+            sorted(context.witnesses.iter().map(|w| format!("{}:{:?}", w.person_id, w.clearance)).collect())
+        ),
+    ];
+    digest_lines(&lines)
+}
+
+fn digest_lines(lines: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for line in lines {
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Earliest instant after `context.current_time` at which a bound policy
+/// could evaluate differently
+fn next_boundary(
+    policies: &[Policy],
+    policy_ids: &[Uuid],
+    context: &PolicyEvaluationContext,
+) -> Option<DateTime<Utc>> {
+    let now = context.current_time;
+    let mut instants = Vec::new();
+
+    for policy in policies.iter().filter(|p| policy_ids.contains(&p.id.as_uuid())) {
+        instants.extend(policy.effective_from);
+        instants.extend(policy.effective_until);
+        for condition in &policy.conditions {
+            match condition {
+                PolicyCondition::TimeWindow { start, end } => {
+                    instants.extend([*start, *end + Duration::nanoseconds(1)]);
+                }
+                PolicyCondition::BusinessHoursOnly { .. } => {
+                    instants.extend(now.duration_trunc(Duration::hours(1)).ok().map(|hour| hour + Duration::hours(1)));
+                }
+                PolicyCondition::MinimumEmploymentDuration { days } => {
+                    instants.push(context.employment_start_date + Duration::days(i64::from(*days)));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    instants.into_iter().filter(|instant| *instant > now).min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::ids::BootstrapPersonId;
+    use crate::domain::{PolicyClaim, SecurityClearance};
+    use crate::events::relationship::RelationshipTerminatedEvent;
+
+    fn policy() -> Policy {
+        Policy::new("Signing", "Code signing", BootstrapPersonId::new()).with_claim(PolicyClaim::CanSignCode)
+    }
+
+    fn binding(policy: &Policy, person_id: Uuid) -> PolicyBinding {
+        PolicyBinding {
+            id: Uuid::now_v7(),
+            policy_id: policy.id.as_uuid(),
+            entity_id: person_id,
+            entity_type: PolicyEntityType::Person,
+            bound_at: Utc::now(),
+            bound_by: Uuid::now_v7(),
+            active: true,
+        }
+    }
+
+    fn context(person_id: Uuid, current_time: DateTime<Utc>) -> PolicyEvaluationContext {
+        PolicyEvaluationContext {
+            person_id,
+            person_clearance: SecurityClearance::Internal,
+            person_units: vec![],
+            person_roles: vec![],
+            employment_start_date: current_time - Duration::days(365),
+            completed_training: vec![],
+            current_time,
+            current_location: None,
+            source_ip: None,
+            mfa_verified: false,
+            yubikey_present: false,
+            witnesses: vec![],
+        }
+    }
+
+    #[test]
+    fn test_repeated_evaluation_hits() {
+        let person_id = Uuid::now_v7();
+        let policies = vec![policy()];
+        let bindings = vec![binding(&policies[0], person_id)];
+        let now = Utc::now();
+        let mut cache = PolicyEvaluationCache::default();
+
+        let first = cache.evaluate(&policies, &bindings, person_id, PolicyEntityType::Person, &context(person_id, now));
+        let second = cache.evaluate(
+            &policies,
+            &bindings,
+            person_id,
+            PolicyEntityType::Person,
+            &context(person_id, now + Duration::seconds(30)),
+        );
+
+        assert_eq!(first.granted_claims, second.granted_claims);
+        assert_eq!(second.evaluated_at, now);
+        assert_eq!(cache.metrics().hits, 1);
+        assert_eq!(cache.metrics().hit_rate(), 0.5);
+    }
+
+    #[test]
+    fn test_context_and_binding_changes_miss() {
+        let person_id = Uuid::now_v7();
+        let policies = vec![policy()];
+        let mut bindings = vec![binding(&policies[0], person_id)];
+        let now = Utc::now();
+        let mut cache = PolicyEvaluationCache::default();
+
+        cache.evaluate(&policies, &bindings, person_id, PolicyEntityType::Person, &context(person_id, now));
+        let mut verified = context(person_id, now);
+        verified.mfa_verified = true;
+        cache.evaluate(&policies, &bindings, person_id, PolicyEntityType::Person, &verified);
+        bindings[0].active = false;
+        let unbound = cache.evaluate(&policies, &bindings, person_id, PolicyEntityType::Person, &verified);
+
+        assert!(unbound.granted_claims.is_empty());
+        assert_eq!(cache.metrics().misses, 3);
+    }
+
+    #[test]
+    fn test_entry_expires_at_time_window_edge() {
+        let person_id = Uuid::now_v7();
+        let now = Utc::now();
+        let policies = vec![policy().with_condition(PolicyCondition::TimeWindow {
+            start: now - Duration::hours(1),
+            end: now + Duration::minutes(1),
+        })];
+        let bindings = vec![binding(&policies[0], person_id)];
+        let mut cache = PolicyEvaluationCache::new(Duration::minutes(5));
+
+        let inside = cache.evaluate(&policies, &bindings, person_id, PolicyEntityType::Person, &context(person_id, now));
+        let after = cache.evaluate(
+            &policies,
+            &bindings,
+            person_id,
+            PolicyEntityType::Person,
+            &context(person_id, now + Duration::minutes(2)),
+        );
+
+        assert_eq!(inside.granted_claims, vec![PolicyClaim::CanSignCode]);
+        assert!(after.granted_claims.is_empty());
+        assert_eq!(cache.metrics().expirations, 1);
+    }
+
+    #[test]
+    fn test_unbinding_event_invalidates() {
+        let person_id = Uuid::now_v7();
+        let policies = vec![policy()];
+        let bindings = vec![binding(&policies[0], person_id)];
+        let mut cache = PolicyEvaluationCache::default();
+        cache.evaluate(&policies, &bindings, person_id, PolicyEntityType::Person, &context(person_id, Utc::now()));
+
+        cache.apply_event(&DomainEvent::Relationship(RelationshipEvents::RelationshipTerminated(
+            RelationshipTerminatedEvent {
+                relationship_id: bindings[0].id,
+                reason: "Policy unbound".to_string(),
+                terminated_at: Utc::now(),
+                terminated_by: "admin".to_string(),
+                correlation_id: Uuid::now_v7(),
+                causation_id: None,
+            },
+        )));
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.metrics().invalidations, 1);
+    }
+}
@@ -0,0 +1,480 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Certificate Renewal Saga
+//!
+//! Replaces an expiring certificate with a new one from the same issuing CA:
+//! 1. Generate a fresh key pair (rekey only)
+//! 2. Issue the replacement certificate
+//! 3. Re-provision the YubiKey slot (hardware-backed certificates only)
+//! 4. Record the renewal, linking the new certificate to the old one
+//!
+//! Reusing the key keeps the public key (and anything pinned to it) stable;
+//! rekeying is required when the old key may be compromised or its
+//! algorithm is being retired. For a reused hardware key only the
+//! certificate in the slot changes.
+//!
+//! ## State Machine
+//!
+//! ```text
+//! Initial → GeneratingKey (rekey) → IssuingCertificate → ProvisioningToYubiKey (hardware)
+//!     ↓            ↓                       ↓                       ↓
+//!   Failed      Failed                  Failed                  Failed
+//!                                                                  ↓
+//!                                                         RecordingRenewal → Completed
+//! ```
+//!
+//! Steps that don't apply to a request are skipped by `advance`.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use super::{SagaState, SagaError};
+use crate::domain::ids::*;
+use crate::domain::yubikey::PIVSlot;
+use crate::events::certificate::CertificateRenewedEvent;
+use crate::events::KeyAlgorithm;
+use crate::value_objects::ActorId;
+
+/// Certificate Renewal Saga state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateRenewalSaga {
+    /// Unique saga ID
+    pub saga_id: Uuid,
+    /// Correlation ID for all events
+    pub correlation_id: Uuid,
+    /// Current state
+    pub state: RenewalState,
+    /// State at which failure occurred (for compensation)
+    failed_at_state: Option<RenewalState>,
+    /// Started at timestamp
+    pub started_at: DateTime<Utc>,
+    /// Completed at timestamp (if completed)
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Renewal request details
+    pub request: RenewalRequest,
+    /// Generated artifacts
+    pub artifacts: RenewalArtifacts,
+    /// Error if failed
+    pub error: Option<SagaError>,
+}
+
+/// Renewal state machine states
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RenewalState {
+    /// Saga not started
+    Initial,
+    /// Generating the replacement key pair (rekey only)
+    GeneratingKey,
+    /// Issuing the replacement certificate
+    IssuingCertificate,
+    /// Writing the new certificate (and key, when rekeying) to the YubiKey slot
+    ProvisioningToYubiKey,
+    /// Emitting CertificateRenewed
+    RecordingRenewal,
+    /// Successfully completed
+    Completed,
+    /// Failed (see error field)
+    Failed,
+    /// Compensating (rolling back)
+    Compensating(RenewalCompensationStep),
+}
+
+/// Compensation sub-steps
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RenewalCompensationStep {
+    /// Put the old certificate back into the YubiKey slot
+    RestoreYubiKeySlot,
+    /// Revoke the replacement certificate
+    RevokeNewCertificate,
+    /// Revoke the replacement key (rekey only)
+    RevokeNewKey,
+}
+
+/// Whether the renewed certificate keeps the old key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RenewalKeyStrategy {
+    /// Re-sign the existing public key
+    ReuseKey,
+    /// Generate a fresh key pair
+    Rekey { algorithm: KeyAlgorithm },
+}
+
+/// YubiKey slot holding a hardware-backed certificate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenewalHardwareTarget {
+    pub yubikey_device_id: YubiKeyDeviceId,
+    pub yubikey_serial: String,
+    pub slot: PIVSlot,
+}
+
+/// Renewal request details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenewalRequest {
+    /// Certificate being renewed
+    pub old_certificate_id: CertificateId,
+    /// Key the old certificate was issued for
+    pub old_key_id: KeyId,
+    /// When the old certificate expires
+    pub old_not_after: DateTime<Utc>,
+    /// Issuing CA of the old certificate (and of the replacement)
+    pub issuing_ca_id: CertificateId,
+    /// Key reuse or rekey
+    pub key_strategy: RenewalKeyStrategy,
+    /// Validity of the replacement in days
+    pub validity_days: u32,
+    /// Set when the certificate lives on a YubiKey
+    pub hardware: Option<RenewalHardwareTarget>,
+}
+
+/// Artifacts generated during renewal
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RenewalArtifacts {
+    /// Key generated when rekeying
+    pub new_key_id: Option<KeyId>,
+    /// Replacement certificate
+    pub new_certificate_id: Option<CertificateId>,
+    /// Replacement certificate fingerprint (for slot verification)
+    pub new_certificate_fingerprint: Option<String>,
+    /// Whether the YubiKey slot now holds the replacement
+    pub slot_reprovisioned: bool,
+}
+
+impl CertificateRenewalSaga {
+    /// Create a new certificate renewal saga
+    pub fn new(request: RenewalRequest) -> Self {
+        Self {
+            saga_id: Uuid::now_v7(),
+            correlation_id: Uuid::now_v7(),
+            state: RenewalState::Initial,
+            failed_at_state: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            request,
+            artifacts: RenewalArtifacts::default(),
+            error: None,
+        }
+    }
+
+    /// Create with explicit correlation ID
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Whether a fresh key pair is generated
+    pub fn is_rekey(&self) -> bool {
+        matches!(self.request.key_strategy, RenewalKeyStrategy::Rekey { .. })
+    }
+
+    /// Whether the certificate lives on a YubiKey
+    pub fn is_hardware_backed(&self) -> bool {
+        self.request.hardware.is_some()
+    }
+
+    /// Start the saga
+    pub fn start(&mut self) -> Result<(), SagaError> {
+        if self.request.validity_days == 0 {
+            return Err(SagaError::new("Validity days must be > 0", "Initial"));
+        }
+        if self.request.hardware.as_ref().is_some_and(|hw| hw.yubikey_serial.is_empty()) {
+            return Err(SagaError::new("YubiKey serial required", "Initial"));
+        }
+        self.state = self.after_initial();
+        Ok(())
+    }
+
+    fn after_initial(&self) -> RenewalState {
+        if self.is_rekey() {
+            RenewalState::GeneratingKey
+        } else {
+            RenewalState::IssuingCertificate
+        }
+    }
+
+    fn after_issuing(&self) -> RenewalState {
+        if self.is_hardware_backed() {
+            RenewalState::ProvisioningToYubiKey
+        } else {
+            RenewalState::RecordingRenewal
+        }
+    }
+
+    /// Transition to the next state, skipping steps the request doesn't need
+    pub fn advance(&mut self) -> RenewalState {
+        self.state = match &self.state {
+            RenewalState::Initial => self.after_initial(),
+            RenewalState::GeneratingKey => RenewalState::IssuingCertificate,
+            RenewalState::IssuingCertificate => self.after_issuing(),
+            RenewalState::ProvisioningToYubiKey => RenewalState::RecordingRenewal,
+            RenewalState::RecordingRenewal => {
+                self.completed_at = Some(Utc::now());
+                RenewalState::Completed
+            }
+            RenewalState::Completed => RenewalState::Completed,
+            RenewalState::Failed => RenewalState::Failed,
+            RenewalState::Compensating(_) => RenewalState::Failed,
+        };
+        self.state.clone()
+    }
+
+    /// Mark the saga as failed
+    pub fn fail(&mut self, message: impl Into<String>, step: impl Into<String>) {
+        self.failed_at_state = Some(self.state.clone());
+        self.error = Some(SagaError::new(message, step));
+        self.state = RenewalState::Failed;
+    }
+
+    /// Compensation steps that apply, in order
+    ///
+    /// Only work that happened is undone: the slot is restored once it may
+    /// have been written, the new certificate revoked once issued, and a
+    /// reused key is never revoked since the old certificate still needs it.
+    fn compensation_plan(&self) -> Vec<RenewalCompensationStep> {
+        let failed_state = self.failed_at_state.as_ref().unwrap_or(&self.state);
+        let mut plan = Vec::new();
+        if matches!(failed_state, RenewalState::ProvisioningToYubiKey | RenewalState::RecordingRenewal)
+            && self.is_hardware_backed()
+        {
+            plan.push(RenewalCompensationStep::RestoreYubiKeySlot);
+        }
+        if self.artifacts.new_certificate_id.is_some() {
+            plan.push(RenewalCompensationStep::RevokeNewCertificate);
+        }
+        if self.is_rekey() {
+            plan.push(RenewalCompensationStep::RevokeNewKey);
+        }
+        plan
+    }
+
+    /// Start compensation
+    ///
+    /// Returns `None` when nothing needs undoing (e.g. issuance failed for a
+    /// reused key); the saga stays `Failed`.
+    pub fn start_compensation(&mut self) -> Option<RenewalCompensationStep> {
+        let step = self.compensation_plan().into_iter().next()?;
+        self.state = RenewalState::Compensating(step.clone());
+        Some(step)
+    }
+
+    /// Advance compensation to next step
+    pub fn advance_compensation(&mut self) -> Option<RenewalCompensationStep> {
+        if let RenewalState::Compensating(current) = &self.state {
+            let plan = self.compensation_plan();
+            let next = plan
+                .iter()
+                .position(|step| step == current)
+                .and_then(|i| plan.get(i + 1).cloned());
+
+            if let Some(step) = next.clone() {
+                self.state = RenewalState::Compensating(step);
+            } else {
+                self.state = RenewalState::Failed;
+            }
+
+            next
+        } else {
+            None
+        }
+    }
+
+    /// Record generation of the replacement key
+    pub fn record_new_key(&mut self, key_id: KeyId) {
+        self.artifacts.new_key_id = Some(key_id);
+    }
+
+    /// Record issuance of the replacement certificate
+    pub fn record_certificate(&mut self, cert_id: CertificateId, fingerprint: String) {
+        self.artifacts.new_certificate_id = Some(cert_id);
+        self.artifacts.new_certificate_fingerprint = Some(fingerprint);
+    }
+
+    /// Record that the YubiKey slot holds the replacement
+    pub fn record_slot_reprovisioned(&mut self) {
+        self.artifacts.slot_reprovisioned = true;
+    }
+
+    /// Key the replacement certificate is issued for
+    pub fn renewed_key_id(&self) -> Option<KeyId> {
+        match self.request.key_strategy {
+            RenewalKeyStrategy::ReuseKey => Some(self.request.old_key_id),
+            RenewalKeyStrategy::Rekey { .. } => self.artifacts.new_key_id,
+        }
+    }
+
+    /// The CertificateRenewed event for the RecordingRenewal step
+    ///
+    /// Available once the replacement is issued. The saga ID is the
+    /// causation so the renewal can be traced back to this run.
+    pub fn renewed_event(&self, renewed_by: ActorId) -> Option<CertificateRenewedEvent> {
+        let new_cert_id = self.artifacts.new_certificate_id?;
+        Some(CertificateRenewedEvent {
+            old_cert_id: self.request.old_certificate_id.as_uuid(),
+            new_cert_id: new_cert_id.as_uuid(),
+            renewed_at: Utc::now(),
+            renewed_by,
+            key_reused: !self.is_rekey(),
+            correlation_id: self.correlation_id,
+            causation_id: Some(self.saga_id),
+        })
+    }
+
+    /// Get current step name for logging
+    pub fn current_step_name(&self) -> String {
+        match &self.state {
+            RenewalState::Initial => "Initial".to_string(),
+            RenewalState::GeneratingKey => "GeneratingKey".to_string(),
+            RenewalState::IssuingCertificate => "IssuingCertificate".to_string(),
+            RenewalState::ProvisioningToYubiKey => "ProvisioningToYubiKey".to_string(),
+            RenewalState::RecordingRenewal => "RecordingRenewal".to_string(),
+            RenewalState::Completed => "Completed".to_string(),
+            RenewalState::Failed => "Failed".to_string(),
+            RenewalState::Compensating(step) => format!("Compensating:{:?}", step),
+        }
+    }
+}
+
+impl SagaState for CertificateRenewalSaga {
+    fn saga_id(&self) -> Uuid {
+        self.saga_id
+    }
+
+    fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self.state, RenewalState::Completed | RenewalState::Failed)
+    }
+
+    fn is_completed(&self) -> bool {
+        matches!(self.state, RenewalState::Completed)
+    }
+
+    fn is_failed(&self) -> bool {
+        matches!(self.state, RenewalState::Failed)
+    }
+
+    fn status_description(&self) -> String {
+        match &self.state {
+            RenewalState::Initial => "Not started".to_string(),
+            RenewalState::GeneratingKey => match &self.request.key_strategy {
+                RenewalKeyStrategy::Rekey { algorithm } => {
+                    format!("Generating {:?} key for renewal", algorithm)
+                }
+                RenewalKeyStrategy::ReuseKey => "Reusing existing key".to_string(),
+            },
+            RenewalState::IssuingCertificate => format!(
+                "Issuing replacement for certificate {}",
+                self.request.old_certificate_id
+            ),
+            RenewalState::ProvisioningToYubiKey => match &self.request.hardware {
+                Some(hw) => format!("Writing {:?} slot on YubiKey {}", hw.slot, hw.yubikey_serial),
+                None => "Writing YubiKey slot".to_string(),
+            },
+            RenewalState::RecordingRenewal => "Recording renewal".to_string(),
+            RenewalState::Completed => format!(
+                "Renewed certificate {} ({})",
+                self.request.old_certificate_id,
+                if self.is_rekey() { "new key" } else { "same key" }
+            ),
+            RenewalState::Failed => format!(
+                "Certificate renewal failed: {}",
+                self.error.as_ref().map_or("Unknown error", |e| &e.message)
+            ),
+            RenewalState::Compensating(step) => format!("Rolling back: {:?}", step),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn create_test_request(key_strategy: RenewalKeyStrategy, hardware: bool) -> RenewalRequest {
+        RenewalRequest {
+            old_certificate_id: CertificateId::new(),
+            old_key_id: KeyId::new(),
+            old_not_after: Utc::now() + Duration::days(14),
+            issuing_ca_id: CertificateId::new(),
+            key_strategy,
+            validity_days: 365,
+            hardware: hardware.then(|| RenewalHardwareTarget {
+                yubikey_device_id: YubiKeyDeviceId::new(),
+                yubikey_serial: "12345678".to_string(),
+                slot: PIVSlot::Authentication,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_reuse_key_software_certificate_skips_key_and_slot() {
+        let mut saga = CertificateRenewalSaga::new(create_test_request(RenewalKeyStrategy::ReuseKey, false));
+        saga.start().unwrap();
+        assert_eq!(saga.state, RenewalState::IssuingCertificate);
+
+        let new_cert = CertificateId::new();
+        saga.record_certificate(new_cert, "ab:cd".to_string());
+        assert_eq!(saga.advance(), RenewalState::RecordingRenewal);
+        assert_eq!(saga.renewed_key_id(), Some(saga.request.old_key_id));
+
+        let event = saga.renewed_event(ActorId::system("renewal")).unwrap();
+        assert_eq!(event.old_cert_id, saga.request.old_certificate_id.as_uuid());
+        assert_eq!(event.new_cert_id, new_cert.as_uuid());
+        assert!(event.key_reused);
+        assert_eq!(event.causation_id, Some(saga.saga_id));
+
+        saga.advance();
+        assert!(saga.is_completed());
+    }
+
+    #[test]
+    fn test_rekey_hardware_certificate_visits_every_step() {
+        let mut saga = CertificateRenewalSaga::new(create_test_request(
+            RenewalKeyStrategy::Rekey { algorithm: KeyAlgorithm::Ed25519 },
+            true,
+        ));
+        saga.start().unwrap();
+        assert_eq!(saga.state, RenewalState::GeneratingKey);
+
+        let new_key = KeyId::new();
+        saga.record_new_key(new_key);
+        assert_eq!(saga.advance(), RenewalState::IssuingCertificate);
+        saga.record_certificate(CertificateId::new(), "ab:cd".to_string());
+        assert_eq!(saga.advance(), RenewalState::ProvisioningToYubiKey);
+        saga.record_slot_reprovisioned();
+        assert_eq!(saga.advance(), RenewalState::RecordingRenewal);
+
+        assert_eq!(saga.renewed_key_id(), Some(new_key));
+        assert!(!saga.renewed_event(ActorId::system("renewal")).unwrap().key_reused);
+    }
+
+    #[test]
+    fn test_slot_failure_restores_old_certificate_and_keeps_reused_key() {
+        let mut saga = CertificateRenewalSaga::new(create_test_request(RenewalKeyStrategy::ReuseKey, true));
+        saga.start().unwrap();
+        saga.record_certificate(CertificateId::new(), "ab:cd".to_string());
+        saga.advance(); // ProvisioningToYubiKey
+
+        saga.fail("PIN blocked", "ProvisioningToYubiKey");
+        assert_eq!(saga.start_compensation(), Some(RenewalCompensationStep::RestoreYubiKeySlot));
+        assert_eq!(saga.advance_compensation(), Some(RenewalCompensationStep::RevokeNewCertificate));
+        // The reused key still backs the old certificate
+        assert_eq!(saga.advance_compensation(), None);
+        assert!(saga.is_failed());
+    }
+
+    #[test]
+    fn test_start_validation_and_early_failure() {
+        let mut request = create_test_request(RenewalKeyStrategy::ReuseKey, false);
+        request.validity_days = 0;
+        assert!(CertificateRenewalSaga::new(request).start().is_err());
+
+        let mut saga = CertificateRenewalSaga::new(create_test_request(RenewalKeyStrategy::ReuseKey, false));
+        saga.start().unwrap();
+        saga.fail("CA offline", "IssuingCertificate");
+        assert_eq!(saga.start_compensation(), None);
+        assert!(saga.renewed_event(ActorId::system("renewal")).is_none());
+    }
+}
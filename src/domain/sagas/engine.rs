@@ -0,0 +1,1128 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Declarative Saga Engine
+//!
+//! A saga declared with [`SagaDefinition`] is an ordered list of steps,
+//! each with an execute closure, an optional compensate closure and a
+//! retry policy, all working on a shared context `C`, instead of a
+//! hand-rolled state machine:
+//!
+//! ```text
+//! SagaDefinition::new("CertificateProvisioning")
+//!     .step(SagaStep::new("GenerateKey", generate).with_compensation(destroy_key))
+//!     .step(SagaStep::new("IssueCertificate", issue).with_retry(RetryPolicy::attempts(3)))
+//!     .step(SagaStep::new("ProvisionYubiKey", provision).skip_when(|c| !c.needs_yubikey))
+//! ```
+//!
+//! [`SagaEngine`] runs a definition and emits the same [`SagaEvents`] for
+//! every saga:
+//!
+//! ```text
+//! start    → SagaStarted
+//! step ok  → StepStarted, StepCompleted        (SagaCompleted after the last)
+//! step err → StepStarted, StepFailed per attempt; when attempts run out:
+//!            CompensationStarted, CompensationStepCompleted per completed
+//!            step in reverse, CompensationCompleted, SagaFailed
+//! ```
+//!
+//! Steps run asynchronously, so a step may await a port such as a YubiKey
+//! or JetStream; [`SagaStep::new`] wraps a synchronous closure for steps
+//! that don't. Independent steps can be declared as a parallel group.
+//! Steps built with [`SagaStep::concurrent`] read the context and return a
+//! [`StepOutput`] that is merged afterwards, so their futures can be polled
+//! together, up to `max_concurrency` at a time, on the caller's task:
+//!
+//! ```text
+//! SagaDefinition::new("PersonOnboarding")
+//!     .step(SagaStep::new("CreatePerson", create))
+//!     .parallel(vec![
+//!         SagaStep::concurrent("GenerateSshKey", ssh_key),
+//!         SagaStep::concurrent("GenerateGpgKey", gpg_key),
+//!     ])
+//!     .with_max_concurrency(8)
+//! ```
+//!
+//! Whatever order a group's steps finish in, their outputs are merged and
+//! their events emitted in declaration order, so a run's event stream does
+//! not depend on scheduling. If any step of a group fails, the others still
+//! complete and are compensated with the rest. Only parallel groups need a
+//! `Sync` context, since their steps share it while they run.
+//!
+//! The progress of a run lives in [`SagaRun`], which serializes when the
+//! context does, so runs can be checkpointed like any other saga. For the
+//! GUI, [`SagaEngine::progress_signal`] exposes a run as a step signal and
+//! [`SagaEngine::run_with_progress`] moves that signal after every step:
+//!
+//! ```text
+//! start              → Step(0%  GenerateKey)
+//! advance            → Step(33% IssueCertificate)
+//! advance            → Step(100% Completed)
+//! ```
+
+use futures::future::{self, BoxFuture};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+use super::{CompensationResult, SagaError, SagaState};
+use crate::signals::{Signal, StepKind};
+use crate::events::saga::{
+    CompensationCompletedEvent, CompensationOutcome, CompensationStartedEvent,
+    CompensationStepCompletedEvent, SagaCompletedEvent, SagaFailedEvent, SagaStartedEvent,
+    StepCompletedEvent, StepFailedEvent, StepStartedEvent,
+};
+use crate::events::SagaEvents;
+
+type ExecuteFn<C> =
+    Box<dyn for<'a> Fn(&'a mut C) -> BoxFuture<'a, Result<Option<String>, String>> + Send + Sync>;
+type ConcurrentFn<C> =
+    Box<dyn for<'a> Fn(&'a C) -> BoxFuture<'a, Result<StepOutput<C>, String>> + Send + Sync>;
+type CompensateFn<C> = Box<dyn for<'a> Fn(&'a mut C) -> BoxFuture<'a, Result<(), String>> + Send + Sync>;
+type SkipFn<C> = Box<dyn Fn(&C) -> bool + Send + Sync>;
+
+/// An operation a step awaits on a port its saga's steps share
+pub type PortOperation<P, C> = for<'a> fn(&'a P, &'a mut C) -> BoxFuture<'a, Result<(), String>>;
+
+/// How often a failing step is attempted before the saga compensates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Attempts in total, at least 1
+    pub max_attempts: u32,
+}
+
+impl RetryPolicy {
+    /// A single attempt
+    pub fn none() -> Self {
+        Self { max_attempts: 1 }
+    }
+
+    /// Up to `max_attempts` attempts
+    pub fn attempts(max_attempts: u32) -> Self {
+        Self { max_attempts: max_attempts.max(1) }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Parallel groups run at most this many steps at once unless the
+/// definition says otherwise
+pub const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+/// Result of a concurrent step, merged into the context after its group
+pub struct StepOutput<C> {
+    apply: Box<dyn FnOnce(&mut C) + Send>,
+    artifacts: Option<String>,
+}
+
+impl<C> StepOutput<C> {
+    /// Output applying `apply` to the context when merged
+    pub fn new(apply: impl FnOnce(&mut C) + Send + 'static) -> Self {
+        Self {
+            apply: Box::new(apply),
+            artifacts: None,
+        }
+    }
+
+    /// Builder: artifacts to record with the step
+    pub fn with_artifacts(mut self, artifacts: impl Into<String>) -> Self {
+        self.artifacts = Some(artifacts.into());
+        self
+    }
+
+    fn merge(self, context: &mut C) -> Option<String> {
+        (self.apply)(context);
+        self.artifacts
+    }
+}
+
+enum StepWork<C> {
+    /// Needs the context to itself
+    Exclusive(ExecuteFn<C>),
+    /// Reads the context; its output is merged afterwards
+    Concurrent(ConcurrentFn<C>),
+}
+
+/// One step of a declared saga
+pub struct SagaStep<C> {
+    name: String,
+    work: StepWork<C>,
+    compensate: Option<CompensateFn<C>>,
+    skip: Option<SkipFn<C>>,
+    retry: RetryPolicy,
+    /// Parallel group the step belongs to, if any
+    group: Option<usize>,
+}
+
+impl<C> SagaStep<C> {
+    /// A step running `execute`, which may return artifacts to record
+    pub fn new(
+        name: impl Into<String>,
+        execute: impl Fn(&mut C) -> Result<Option<String>, String> + Send + Sync + 'static,
+    ) -> Self {
+        Self::new_async(name, move |context| Box::pin(future::ready(execute(context))))
+    }
+
+    /// A step awaiting `execute`, e.g. one that talks to a device or a server
+    pub fn new_async(
+        name: impl Into<String>,
+        execute: impl for<'a> Fn(&'a mut C) -> BoxFuture<'a, Result<Option<String>, String>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self::with_work(name, StepWork::Exclusive(Box::new(execute)))
+    }
+
+    fn with_work(name: impl Into<String>, work: StepWork<C>) -> Self {
+        Self {
+            name: name.into(),
+            work,
+            compensate: None,
+            skip: None,
+            retry: RetryPolicy::none(),
+            group: None,
+        }
+    }
+
+    /// Builder: undo the step when a later step fails
+    pub fn with_compensation(
+        self,
+        compensate: impl Fn(&mut C) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.with_async_compensation(move |context| Box::pin(future::ready(compensate(context))))
+    }
+
+    /// Builder: undo the step by awaiting `compensate` when a later step fails
+    pub fn with_async_compensation(
+        mut self,
+        compensate: impl for<'a> Fn(&'a mut C) -> BoxFuture<'a, Result<(), String>> + Send + Sync + 'static,
+    ) -> Self {
+        self.compensate = Some(Box::new(compensate));
+        self
+    }
+
+    /// Builder: attempt the step more than once
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Builder: skip the step when `skip` holds for the context
+    pub fn skip_when(mut self, skip: impl Fn(&C) -> bool + Send + Sync + 'static) -> Self {
+        self.skip = Some(Box::new(skip));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<C: Send> SagaStep<C> {
+    /// A step awaiting `operation` on `port`
+    ///
+    /// Sagas declare their effects as a trait of operations on their
+    /// context; each step then names the operation it runs:
+    ///
+    /// ```text
+    /// SagaStep::on_port("GenerateKey", &steps, |s, c| s.generate_key(c))
+    ///     .compensated_on_port(&steps, |s, c| s.revoke_key(c))
+    /// ```
+    pub fn on_port<P: ?Sized + Send + Sync + 'static>(
+        name: impl Into<String>,
+        port: &Arc<P>,
+        operation: PortOperation<P, C>,
+    ) -> Self {
+        let port = Arc::clone(port);
+        Self::new_async(name, move |context| {
+            let port = Arc::clone(&port);
+            Box::pin(async move { operation(&*port, context).await.map(|()| None) })
+        })
+    }
+
+    /// Builder: undo the step by awaiting `operation` on `port`
+    pub fn compensated_on_port<P: ?Sized + Send + Sync + 'static>(
+        self,
+        port: &Arc<P>,
+        operation: PortOperation<P, C>,
+    ) -> Self {
+        let port = Arc::clone(port);
+        self.with_async_compensation(move |context| {
+            let port = Arc::clone(&port);
+            Box::pin(async move { operation(&*port, context).await })
+        })
+    }
+}
+
+impl<C: Sync> SagaStep<C> {
+    /// A step that only reads the context and can run alongside others
+    ///
+    /// Its changes to the context are returned as a [`StepOutput`] and
+    /// merged once its parallel group has finished. In a group, steps
+    /// built with [`SagaStep::new`] run one at a time as the outputs are
+    /// merged.
+    pub fn concurrent(
+        name: impl Into<String>,
+        execute: impl for<'a> Fn(&'a C) -> BoxFuture<'a, Result<StepOutput<C>, String>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self::with_work(name, StepWork::Concurrent(Box::new(execute)))
+    }
+}
+
+/// A saga declared as ordered steps
+pub struct SagaDefinition<C> {
+    saga_type: String,
+    steps: Vec<SagaStep<C>>,
+    groups: usize,
+    max_concurrency: usize,
+}
+
+impl<C> SagaDefinition<C> {
+    pub fn new(saga_type: impl Into<String>) -> Self {
+        Self {
+            saga_type: saga_type.into(),
+            steps: Vec::new(),
+            groups: 0,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+        }
+    }
+
+    /// Builder: append a step
+    pub fn step(mut self, step: SagaStep<C>) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Builder: run at most `max_concurrency` steps of a group at once
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    pub fn saga_type(&self) -> &str {
+        &self.saga_type
+    }
+
+    pub fn steps(&self) -> &[SagaStep<C>] {
+        &self.steps
+    }
+}
+
+impl<C: Sync> SagaDefinition<C> {
+    /// Builder: append independent steps that run as one parallel group
+    pub fn parallel(mut self, steps: Vec<SagaStep<C>>) -> Self {
+        let group = self.groups;
+        self.groups += 1;
+        self.steps.extend(steps.into_iter().map(|mut step| {
+            step.group = Some(group);
+            step
+        }));
+        self
+    }
+}
+
+/// Where a run of a declared saga stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunStatus {
+    /// Steps remain
+    Running,
+    /// Every step completed or was skipped
+    Completed,
+    /// A step failed and completed steps were compensated
+    Failed,
+}
+
+/// Progress of one run of a declared saga
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SagaRun<C> {
+    pub saga_id: Uuid,
+    pub correlation_id: Uuid,
+    pub saga_type: String,
+    pub status: RunStatus,
+    /// Index of the next step to execute
+    pub next_step: usize,
+    /// Name of the step the run resumes from, or the step it failed at
+    #[serde(default)]
+    pub current_step: Option<String>,
+    /// Steps executed successfully, in order (skipped steps excluded)
+    pub completed_steps: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub error: Option<SagaError>,
+    /// State shared by the steps
+    pub context: C,
+}
+
+/// How far a run has come, for progress bars and status lines
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SagaProgress {
+    pub saga_id: Uuid,
+    pub saga_type: String,
+    /// Step to run next, if any remain
+    pub current_step: Option<String>,
+    /// Steps completed or skipped
+    pub steps_done: usize,
+    pub steps_total: usize,
+    pub status: RunStatus,
+}
+
+impl SagaProgress {
+    /// Share of the steps done, 0–100
+    pub fn percent(&self) -> u8 {
+        if self.steps_total == 0 {
+            return 100;
+        }
+        (self.steps_done * 100 / self.steps_total) as u8
+    }
+}
+
+impl<C> SagaRun<C> {
+    /// Step name for logging and checkpoints
+    pub fn current_step_name(&self) -> String {
+        match (&self.current_step, self.status) {
+            (_, RunStatus::Completed) => "Completed".to_string(),
+            (Some(step), _) => step.clone(),
+            (None, RunStatus::Running) => "Initial".to_string(),
+            (None, RunStatus::Failed) => "Failed".to_string(),
+        }
+    }
+}
+
+impl<C: Clone + Send + Sync> SagaState for SagaRun<C> {
+    fn saga_id(&self) -> Uuid {
+        self.saga_id
+    }
+
+    fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.status != RunStatus::Running
+    }
+
+    fn is_completed(&self) -> bool {
+        self.status == RunStatus::Completed
+    }
+
+    fn is_failed(&self) -> bool {
+        self.status == RunStatus::Failed
+    }
+
+    fn status_description(&self) -> String {
+        match self.status {
+            RunStatus::Running => format!(
+                "{}: {} steps completed",
+                self.saga_type,
+                self.completed_steps.len()
+            ),
+            RunStatus::Completed => format!("{} completed", self.saga_type),
+            RunStatus::Failed => format!(
+                "{} failed: {}",
+                self.saga_type,
+                self.error.as_ref().map_or("Unknown error", |e| e.message.as_str())
+            ),
+        }
+    }
+}
+
+/// Runs declared sagas, emitting consistent saga events
+///
+/// Events name the saga as their causation; a step's events are caused by
+/// the saga run, not by each other.
+pub struct SagaEngine<C> {
+    definition: SagaDefinition<C>,
+}
+
+impl<C> SagaEngine<C> {
+    pub fn new(definition: SagaDefinition<C>) -> Self {
+        Self { definition }
+    }
+
+    pub fn definition(&self) -> &SagaDefinition<C> {
+        &self.definition
+    }
+
+    /// Begin a run over `context`
+    pub fn start(
+        &self,
+        context: C,
+        initiated_by: impl Into<String>,
+        correlation_id: Uuid,
+    ) -> (SagaRun<C>, Vec<SagaEvents>) {
+        self.start_with_id(Uuid::now_v7(), context, initiated_by, correlation_id)
+    }
+
+    /// Begin a run with a known id, e.g. one the context's steps stamp on
+    /// the domain events they produce
+    pub fn start_with_id(
+        &self,
+        saga_id: Uuid,
+        context: C,
+        initiated_by: impl Into<String>,
+        correlation_id: Uuid,
+    ) -> (SagaRun<C>, Vec<SagaEvents>) {
+        let run = SagaRun {
+            saga_id,
+            correlation_id,
+            saga_type: self.definition.saga_type.clone(),
+            status: RunStatus::Running,
+            next_step: 0,
+            current_step: self.definition.steps.first().map(|step| step.name.clone()),
+            completed_steps: Vec::new(),
+            started_at: Utc::now(),
+            completed_at: None,
+            error: None,
+            context,
+        };
+        let started = SagaEvents::SagaStarted(SagaStartedEvent {
+            saga_id: run.saga_id,
+            saga_type: run.saga_type.clone(),
+            correlation_id,
+            triggered_by_command_id: None,
+            initiated_by: initiated_by.into(),
+            started_at: run.started_at,
+            context: None,
+        });
+        (run, vec![started])
+    }
+
+    /// Execute the next step, or the next parallel group, retrying per
+    /// step policy
+    ///
+    /// Skipped steps are passed over without events. Returns no events once
+    /// the run is terminal.
+    pub async fn advance(&self, run: &mut SagaRun<C>) -> Vec<SagaEvents> {
+        let mut events = Vec::new();
+        if run.status != RunStatus::Running {
+            return events;
+        }
+
+        while let Some(step) = self.definition.steps.get(run.next_step) {
+            if !self.skipped(step, &run.context) {
+                break;
+            }
+            run.next_step += 1;
+        }
+        let Some(step) = self.definition.steps.get(run.next_step) else {
+            self.complete(run, &mut events);
+            return events;
+        };
+
+        let end = match step.group {
+            Some(group) => {
+                run.next_step
+                    + self.definition.steps[run.next_step..]
+                        .iter()
+                        .take_while(|s| s.group == Some(group))
+                        .count()
+            }
+            None => run.next_step + 1,
+        };
+        let indices: Vec<usize> = (run.next_step..end)
+            .filter(|i| !self.skipped(&self.definition.steps[*i], &run.context))
+            .collect();
+
+        let step_runs = self.execute(&mut run.context, &indices).await;
+
+        // Failing steps compensate their completed siblings too
+        let requires_compensation =
+            !run.completed_steps.is_empty() || step_runs.iter().any(|(_, r)| r.result.is_ok());
+        let mut failure = None;
+        for (index, step_run) in step_runs {
+            if let Err(message) = self.record(run, index, step_run, requires_compensation, &mut events) {
+                failure.get_or_insert((index, message));
+            }
+        }
+        if let Some((index, message)) = failure {
+            self.fail(run, &self.definition.steps[index].name, message, &mut events).await;
+            return events;
+        }
+
+        run.next_step = end;
+        run.current_step = self.definition.steps.get(end).map(|step| step.name.clone());
+        if self.remaining(run) == 0 {
+            self.complete(run, &mut events);
+        }
+        events
+    }
+
+    /// Advance until the run completes or fails
+    pub async fn run_to_completion(&self, run: &mut SagaRun<C>) -> Vec<SagaEvents> {
+        let mut events = Vec::new();
+        while run.status == RunStatus::Running {
+            events.extend(self.advance(run).await);
+        }
+        events
+    }
+
+    pub fn progress(&self, run: &SagaRun<C>) -> SagaProgress {
+        let total = self.definition.steps.len();
+        SagaProgress {
+            saga_id: run.saga_id,
+            saga_type: run.saga_type.clone(),
+            current_step: self
+                .definition
+                .steps
+                .get(run.next_step)
+                .filter(|_| run.status == RunStatus::Running)
+                .map(|step| step.name.clone()),
+            steps_done: run.next_step.min(total),
+            steps_total: total,
+            status: run.status,
+        }
+    }
+
+    /// The run's progress as a step signal
+    ///
+    /// The signal holds its value until the run advances; render it as a
+    /// progress bar rather than polling `status_description`.
+    pub fn progress_signal(&self, run: &SagaRun<C>) -> Signal<StepKind, SagaProgress> {
+        Signal::<StepKind, SagaProgress>::step(self.progress(run))
+    }
+
+    /// Advance until the run completes or fails, reporting each step
+    ///
+    /// `on_progress` sees the signal once before the first step and again
+    /// after every advance, so a listener can stream it to the GUI.
+    pub async fn run_with_progress(
+        &self,
+        run: &mut SagaRun<C>,
+        mut on_progress: impl FnMut(&Signal<StepKind, SagaProgress>),
+    ) -> Vec<SagaEvents> {
+        let mut signal = self.progress_signal(run);
+        on_progress(&signal);
+
+        let mut events = Vec::new();
+        while run.status == RunStatus::Running {
+            events.extend(self.advance(run).await);
+            signal = signal.with_value(self.progress(run));
+            on_progress(&signal);
+        }
+        events
+    }
+
+    /// Steps not yet executed or skipped
+    fn remaining(&self, run: &SagaRun<C>) -> usize {
+        self.definition.steps[run.next_step..]
+            .iter()
+            .filter(|step| !self.skipped(step, &run.context))
+            .count()
+    }
+
+    fn skipped(&self, step: &SagaStep<C>, context: &C) -> bool {
+        step.skip.as_ref().is_some_and(|skip| skip(context))
+    }
+
+    /// Run the steps at `indices`, returning their attempts in that order
+    ///
+    /// Concurrent steps run first, polled together against the context as
+    /// it is now, at most `max_concurrency` at once; their outputs are then
+    /// merged in declaration order, with exclusive steps running in their
+    /// place.
+    async fn execute(&self, context: &mut C, indices: &[usize]) -> Vec<(usize, StepRun<Option<String>>)> {
+        let steps = &self.definition.steps;
+        let shared: &C = context;
+        let concurrent: Vec<StepRun<StepOutput<C>>> = stream::iter(indices.iter().filter_map(|i| {
+            match &steps[*i].work {
+                StepWork::Concurrent(execute) => Some(attempt(steps[*i].retry, move || execute(shared))),
+                StepWork::Exclusive(_) => None,
+            }
+        }))
+        .buffered(self.definition.max_concurrency)
+        .collect()
+        .await;
+        let mut outputs = concurrent.into_iter();
+
+        let mut step_runs = Vec::with_capacity(indices.len());
+        for &index in indices {
+            let step = &steps[index];
+            let step_run = match &step.work {
+                StepWork::Exclusive(execute) => {
+                    let mut attempts = Attempts::new(step.retry);
+                    loop {
+                        let started_at = Utc::now();
+                        let result = execute(context).await;
+                        if let Some(step_run) = attempts.finish(started_at, result) {
+                            break step_run;
+                        }
+                    }
+                }
+                StepWork::Concurrent(_) => outputs
+                    .next()
+                    .expect("one run per concurrent step")
+                    .map(|output| output.merge(context)),
+            };
+            step_runs.push((index, step_run));
+        }
+        step_runs
+    }
+
+    /// Emit the events for one step's attempts and note it if completed
+    ///
+    /// Returns the final error if the step failed.
+    fn record(
+        &self,
+        run: &mut SagaRun<C>,
+        index: usize,
+        step_run: StepRun<Option<String>>,
+        requires_compensation: bool,
+        events: &mut Vec<SagaEvents>,
+    ) -> Result<(), String> {
+        let step = &self.definition.steps[index];
+        let step_number = index as u32 + 1;
+        let (saga_id, correlation_id) = (run.saga_id, run.correlation_id);
+        let started = |started_at| {
+            SagaEvents::StepStarted(StepStartedEvent {
+                saga_id,
+                step_name: step.name.clone(),
+                step_number,
+                correlation_id,
+                causation_id: Some(saga_id),
+                started_at,
+            })
+        };
+        let failed = |failed_at, error_message, requires_compensation, retry_count| {
+            SagaEvents::StepFailed(StepFailedEvent {
+                saga_id,
+                step_name: step.name.clone(),
+                step_number,
+                correlation_id,
+                causation_id: saga_id,
+                failed_at,
+                error_message,
+                error_code: None,
+                requires_compensation,
+                retry_count,
+            })
+        };
+
+        let retry_count = step_run.retried.len() as u32;
+        for (n, attempt) in step_run.retried.into_iter().enumerate() {
+            events.push(started(attempt.started_at));
+            events.push(failed(attempt.failed_at, attempt.message, false, n as u32));
+        }
+
+        events.push(started(step_run.started_at));
+        match step_run.result {
+            Ok(artifacts) => {
+                events.push(SagaEvents::StepCompleted(StepCompletedEvent {
+                    saga_id,
+                    step_name: step.name.clone(),
+                    step_number,
+                    correlation_id,
+                    causation_id: saga_id,
+                    completed_at: step_run.finished_at,
+                    duration_ms: elapsed_ms(step_run.started_at, step_run.finished_at),
+                    artifacts,
+                }));
+                run.completed_steps.push(step.name.clone());
+                Ok(())
+            }
+            Err(message) => {
+                events.push(failed(step_run.finished_at, message.clone(), requires_compensation, retry_count));
+                Err(message)
+            }
+        }
+    }
+
+    fn complete(&self, run: &mut SagaRun<C>, events: &mut Vec<SagaEvents>) {
+        let completed_at = Utc::now();
+        run.next_step = self.definition.steps.len();
+        run.current_step = None;
+        run.status = RunStatus::Completed;
+        run.completed_at = Some(completed_at);
+        events.push(SagaEvents::SagaCompleted(SagaCompletedEvent {
+            saga_id: run.saga_id,
+            saga_type: run.saga_type.clone(),
+            correlation_id: run.correlation_id,
+            causation_id: run.saga_id,
+            completed_at,
+            total_duration_ms: elapsed_ms(run.started_at, completed_at),
+            steps_executed: run.completed_steps.len() as u32,
+            result: None,
+        }));
+    }
+
+    /// Compensate completed steps in reverse and mark the run failed
+    async fn fail(&self, run: &mut SagaRun<C>, failed_step: &str, message: String, events: &mut Vec<SagaEvents>) {
+        let to_compensate: Vec<&SagaStep<C>> = run
+            .completed_steps
+            .iter()
+            .rev()
+            .filter_map(|name| self.definition.steps.iter().find(|s| &s.name == name))
+            .filter(|step| step.compensate.is_some())
+            .collect();
+
+        let mut compensated = Vec::new();
+        let mut failed = Vec::new();
+        if !to_compensate.is_empty() {
+            events.push(SagaEvents::CompensationStarted(CompensationStartedEvent {
+                saga_id: run.saga_id,
+                correlation_id: run.correlation_id,
+                causation_id: run.saga_id,
+                started_at: Utc::now(),
+                steps_to_compensate: to_compensate.iter().map(|s| s.name.clone()).collect(),
+            }));
+            for step in to_compensate {
+                let Some(compensate) = &step.compensate else { continue };
+                let error_message = compensate(&mut run.context).await.err();
+                if error_message.is_none() {
+                    compensated.push(step.name.clone());
+                } else {
+                    failed.push(step.name.clone());
+                }
+                events.push(SagaEvents::CompensationStepCompleted(CompensationStepCompletedEvent {
+                    saga_id: run.saga_id,
+                    step_name: step.name.clone(),
+                    correlation_id: run.correlation_id,
+                    causation_id: run.saga_id,
+                    completed_at: Utc::now(),
+                    success: error_message.is_none(),
+                    error_message,
+                }));
+            }
+        }
+
+        let (outcome, result) = match (compensated.is_empty() && failed.is_empty(), failed.is_empty()) {
+            (true, _) => (CompensationOutcome::NotNeeded, CompensationResult::NotNeeded),
+            (false, true) => (CompensationOutcome::FullyCompensated, CompensationResult::FullyCompensated),
+            (false, false) if compensated.is_empty() => (
+                CompensationOutcome::Failed,
+                CompensationResult::PartiallyCompensated { failed_steps: failed.clone() },
+            ),
+            (false, false) => (
+                CompensationOutcome::PartiallyCompensated,
+                CompensationResult::PartiallyCompensated { failed_steps: failed.clone() },
+            ),
+        };
+        let compensation_attempted = outcome != CompensationOutcome::NotNeeded;
+        if compensation_attempted {
+            events.push(SagaEvents::CompensationCompleted(CompensationCompletedEvent {
+                saga_id: run.saga_id,
+                correlation_id: run.correlation_id,
+                causation_id: run.saga_id,
+                completed_at: Utc::now(),
+                outcome: outcome.clone(),
+                compensated_steps: compensated,
+                failed_steps: failed,
+            }));
+        }
+
+        let failed_at = Utc::now();
+        events.push(SagaEvents::SagaFailed(SagaFailedEvent {
+            saga_id: run.saga_id,
+            saga_type: run.saga_type.clone(),
+            correlation_id: run.correlation_id,
+            causation_id: run.saga_id,
+            failed_at,
+            failed_at_step: failed_step.to_string(),
+            error_message: message.clone(),
+            compensation_attempted,
+            compensation_result: compensation_attempted.then(|| format!("{:?}", outcome)),
+        }));
+
+        run.current_step = Some(failed_step.to_string());
+        let error = SagaError::new(message, failed_step);
+        run.error = Some(if compensation_attempted { error.with_compensation(result) } else { error });
+        run.status = RunStatus::Failed;
+        run.completed_at = Some(failed_at);
+    }
+}
+
+/// An attempt at a step that failed and was retried
+struct FailedAttempt {
+    started_at: DateTime<Utc>,
+    failed_at: DateTime<Utc>,
+    message: String,
+}
+
+/// Attempts made at one step, recorded as events once merged
+struct StepRun<T> {
+    retried: Vec<FailedAttempt>,
+    /// Start and end of the final attempt
+    started_at: DateTime<Utc>,
+    finished_at: DateTime<Utc>,
+    result: Result<T, String>,
+}
+
+impl<T> StepRun<T> {
+    fn map<U>(self, f: impl FnOnce(T) -> U) -> StepRun<U> {
+        StepRun {
+            retried: self.retried,
+            started_at: self.started_at,
+            finished_at: self.finished_at,
+            result: self.result.map(f),
+        }
+    }
+}
+
+/// Attempts made so far at one step
+struct Attempts {
+    retry: RetryPolicy,
+    retried: Vec<FailedAttempt>,
+}
+
+impl Attempts {
+    fn new(retry: RetryPolicy) -> Self {
+        Self { retry, retried: Vec::new() }
+    }
+
+    /// Record an attempt that started at `started_at`, returning the
+    /// step's run unless the policy allows another attempt
+    fn finish<T>(&mut self, started_at: DateTime<Utc>, result: Result<T, String>) -> Option<StepRun<T>> {
+        let finished_at = Utc::now();
+        match result {
+            Err(message) if self.retried.len() as u32 + 1 < self.retry.max_attempts => {
+                self.retried.push(FailedAttempt { started_at, failed_at: finished_at, message });
+                None
+            }
+            result => Some(StepRun {
+                retried: std::mem::take(&mut self.retried),
+                started_at,
+                finished_at,
+                result,
+            }),
+        }
+    }
+}
+
+/// Await `execute` until it succeeds or `retry` runs out
+async fn attempt<'a, T>(
+    retry: RetryPolicy,
+    execute: impl Fn() -> BoxFuture<'a, Result<T, String>>,
+) -> StepRun<T> {
+    let mut attempts = Attempts::new(retry);
+    loop {
+        let started_at = Utc::now();
+        let result = execute().await;
+        if let Some(step_run) = attempts.finish(started_at, result) {
+            return step_run;
+        }
+    }
+}
+
+fn elapsed_ms(from: DateTime<Utc>, to: DateTime<Utc>) -> u64 {
+    (to - from).num_milliseconds().max(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Debug, Clone, Default)]
+    struct Provisioning {
+        needs_yubikey: bool,
+        key_generated: bool,
+        certificate_attempts: u32,
+        certificate_failures: u32,
+        log: Vec<&'static str>,
+    }
+
+    fn definition() -> SagaDefinition<Provisioning> {
+        SagaDefinition::new("CertificateProvisioning")
+            .step(
+                SagaStep::new("GenerateKey", |c: &mut Provisioning| {
+                    c.key_generated = true;
+                    c.log.push("generate");
+                    Ok(Some("key-1".to_string()))
+                })
+                .with_compensation(|c: &mut Provisioning| {
+                    c.key_generated = false;
+                    c.log.push("destroy");
+                    Ok(())
+                }),
+            )
+            .step(
+                SagaStep::new("IssueCertificate", |c: &mut Provisioning| {
+                    c.certificate_attempts += 1;
+                    if c.certificate_attempts <= c.certificate_failures {
+                        return Err("CA busy".to_string());
+                    }
+                    c.log.push("issue");
+                    Ok(None)
+                })
+                .with_retry(RetryPolicy::attempts(3)),
+            )
+            .step(
+                SagaStep::new("ProvisionYubiKey", |c: &mut Provisioning| {
+                    c.log.push("provision");
+                    Ok(None)
+                })
+                .skip_when(|c: &Provisioning| !c.needs_yubikey),
+            )
+    }
+
+    fn count(events: &[SagaEvents], event_type: &str) -> usize {
+        events.iter().filter(|e| e.event_type() == event_type).count()
+    }
+
+    #[tokio::test]
+    async fn test_retries_then_completes_skipping_optional_step() {
+        let engine = SagaEngine::new(definition());
+        let context = Provisioning { certificate_failures: 2, ..Default::default() };
+        let (mut run, started) = engine.start(context, "operator", Uuid::now_v7());
+
+        let events = engine.run_to_completion(&mut run).await;
+
+        assert_eq!(count(&started, "saga.started"), 1);
+        assert!(run.is_completed());
+        assert_eq!(run.context.log, vec!["generate", "issue"]);
+        assert_eq!(count(&events, "saga.step.failed"), 2);
+        assert_eq!(count(&events, "saga.step.completed"), 2);
+        assert_eq!(count(&events, "saga.completed"), 1);
+        assert_eq!(engine.progress(&run).percent(), 100);
+    }
+
+    #[tokio::test]
+    async fn test_progress_signal_moves_with_each_step() {
+        let engine = SagaEngine::new(definition());
+        let (mut run, _) = engine.start(Provisioning::default(), "operator", Uuid::now_v7());
+
+        let mut seen = Vec::new();
+        engine.run_with_progress(&mut run, |signal| {
+            let progress = signal.sample(0.0);
+            seen.push((progress.percent(), progress.current_step, progress.status));
+        })
+        .await;
+
+        assert_eq!(
+            seen,
+            vec![
+                (0, Some("GenerateKey".to_string()), RunStatus::Running),
+                (33, Some("IssueCertificate".to_string()), RunStatus::Running),
+                (100, None, RunStatus::Completed),
+            ]
+        );
+        assert_eq!(engine.progress_signal(&run).sample(0.0).saga_id, run.saga_id);
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct Onboarding {
+        active: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+        fail: Option<&'static str>,
+        keys: Vec<&'static str>,
+        revoked: Vec<&'static str>,
+    }
+
+    /// A key generation step that takes `delay_ms` and tracks concurrency
+    fn key_step(name: &'static str, delay_ms: u64) -> SagaStep<Onboarding> {
+        SagaStep::concurrent(name, move |c: &Onboarding| {
+            Box::pin(async move {
+                let active = c.active.fetch_add(1, Ordering::SeqCst) + 1;
+                c.peak.fetch_max(active, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                c.active.fetch_sub(1, Ordering::SeqCst);
+
+                if c.fail == Some(name) {
+                    return Err(format!("{} failed", name));
+                }
+                Ok(StepOutput::new(move |c: &mut Onboarding| c.keys.push(name)).with_artifacts(name))
+            })
+        })
+        .with_compensation(move |c: &mut Onboarding| {
+            c.revoked.push(name);
+            Ok(())
+        })
+    }
+
+    fn onboarding() -> SagaDefinition<Onboarding> {
+        SagaDefinition::new("PersonOnboarding")
+            .parallel(vec![
+                key_step("alice-ssh", 60),
+                key_step("alice-gpg", 10),
+                key_step("bob-ssh", 40),
+                key_step("bob-gpg", 20),
+            ])
+            .with_max_concurrency(2)
+    }
+
+    #[tokio::test]
+    async fn test_parallel_group_merges_in_declaration_order() {
+        let engine = SagaEngine::new(onboarding());
+        let (mut run, _) = engine.start(Onboarding::default(), "operator", Uuid::now_v7());
+
+        let events = engine.advance(&mut run).await;
+
+        assert!(run.is_completed());
+        assert_eq!(run.context.peak.load(Ordering::SeqCst), 2);
+        assert_eq!(run.context.keys, vec!["alice-ssh", "alice-gpg", "bob-ssh", "bob-gpg"]);
+
+        // Started/completed pairs in declaration order, whichever finished first
+        let steps: Vec<(&str, u32)> = events
+            .iter()
+            .filter_map(|e| match e {
+                SagaEvents::StepStarted(s) => Some(("started", s.step_number)),
+                SagaEvents::StepCompleted(s) => Some(("completed", s.step_number)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("started", 1), ("completed", 1), ("started", 2), ("completed", 2),
+                ("started", 3), ("completed", 3), ("started", 4), ("completed", 4),
+            ]
+        );
+        assert!(matches!(events.last(), Some(SagaEvents::SagaCompleted(_))));
+    }
+
+    #[tokio::test]
+    async fn test_failed_parallel_step_compensates_its_siblings() {
+        let engine = SagaEngine::new(onboarding());
+        let context = Onboarding { fail: Some("alice-gpg"), ..Default::default() };
+        let (mut run, _) = engine.start(context, "operator", Uuid::now_v7());
+
+        let events = engine.advance(&mut run).await;
+
+        assert!(run.is_failed());
+        assert_eq!(run.context.keys, vec!["alice-ssh", "bob-ssh", "bob-gpg"]);
+        assert_eq!(run.context.revoked, vec!["bob-gpg", "bob-ssh", "alice-ssh"]);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SagaEvents::StepFailed(f) if f.step_name == "alice-gpg" && f.requires_compensation
+        )));
+        assert!(matches!(events.last(), Some(SagaEvents::SagaFailed(f)) if f.failed_at_step == "alice-gpg"));
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_compensate_completed_steps() {
+        let engine = SagaEngine::new(definition());
+        let context = Provisioning { needs_yubikey: true, certificate_failures: 5, ..Default::default() };
+        let (mut run, _) = engine.start(context, "operator", Uuid::now_v7());
+
+        engine.advance(&mut run).await;
+        let progress = engine.progress(&run);
+        assert_eq!(progress.current_step.as_deref(), Some("IssueCertificate"));
+        assert_eq!((progress.steps_done, progress.steps_total), (1, 3));
+
+        let events = engine.advance(&mut run).await;
+
+        assert!(run.is_failed());
+        assert!(!run.context.key_generated);
+        assert_eq!(run.context.log, vec!["generate", "destroy"]);
+        assert_eq!(count(&events, "saga.step.failed"), 3);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SagaEvents::CompensationCompleted(c) if c.outcome == CompensationOutcome::FullyCompensated
+        )));
+        assert!(matches!(events.last(), Some(SagaEvents::SagaFailed(f)) if f.failed_at_step == "IssueCertificate"));
+        assert!(engine.advance(&mut run).await.is_empty());
+    }
+}
@@ -18,6 +18,32 @@
 //! - **CompleteBootstrapSaga**: Full CIM infrastructure bootstrap
 //! - **PersonOnboardingSaga**: Person + Keys + NATS User + YubiKey
 //! - **CertificateProvisioningSaga**: Key + Certificate + YubiKey slot
+//! - **SshKeyRotationSaga**: New SSH key + overlap window + old key revocation
+//! - **CertificateRenewalSaga**: Reused or fresh key + replacement certificate + YubiKey slot
+//! - **RotateNatsSigningKeySaga**: New signing key + re-issued JWTs + grace period + old key retirement
+//! - **OrganizationKeyRotationSaga**: Every active key of one kind, signers first + re-issued artifacts + report
+//! - **RecoveryRehearsalSaga**: Seed from backups + scratch re-derivation + diff against the manifest
+//!
+//! ## Persistence
+//!
+//! Sagas checkpoint through [`SagaRepository`] into a `SagaStore` after each
+//! transition, so an interrupted ceremony resumes from its last recorded step.
+//! [`SagaStepMonitor`] puts deadlines and heartbeats on the step each saga is
+//! waiting in and reports the ones that stall.
+//!
+//! ## Declarative Sagas
+//!
+//! Sagas can be declared as ordered steps with execute and compensate
+//! closures and run by [`SagaEngine`], which handles retries, compensation,
+//! progress and `SagaEvents` emission instead of a hand-rolled state machine.
+//! Independent steps, such as per-person key generation, can be grouped to
+//! run concurrently while their events stay in declaration order.
+//!
+//! The bootstrap, onboarding and provisioning sagas are declared this way:
+//! each has a context type, a ports trait of the operations its steps run
+//! (`BootstrapSteps`, `OnboardingSteps`, `ProvisioningSteps`) and a
+//! `definition` wiring those operations into steps with
+//! [`SagaStep::on_port`].
 //!
 //! ## State Machine Pattern
 //!
@@ -33,10 +59,31 @@
 pub mod bootstrap;
 pub mod person_onboarding;
 pub mod certificate_provisioning;
+pub mod ssh_key_rotation;
+pub mod certificate_renewal;
+pub mod nats_signing_key_rotation;
+pub mod organization_key_rotation;
+pub mod recovery_rehearsal;
+pub mod persistence;
+pub mod step_deadlines;
+pub mod engine;
 
 pub use bootstrap::*;
 pub use person_onboarding::*;
 pub use certificate_provisioning::*;
+pub use ssh_key_rotation::*;
+pub use certificate_renewal::*;
+pub use nats_signing_key_rotation::*;
+pub use organization_key_rotation::*;
+pub use recovery_rehearsal::*;
+pub use persistence::{PersistentSaga, SagaRepository};
+pub use engine::{
+    PortOperation, RetryPolicy, RunStatus, SagaDefinition, SagaEngine, SagaProgress, SagaRun,
+    SagaStep, StepOutput,
+};
+pub use step_deadlines::{
+    SagaStepMonitor, SagaTimeoutConfig, StallNotifier, StepStatus, StepTimeouts, StepWatch,
+};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
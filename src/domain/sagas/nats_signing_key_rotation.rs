@@ -0,0 +1,474 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! NATS Signing Key Rotation Saga
+//!
+//! Coordinates replacing an operator or account signing key without
+//! invalidating the JWTs it issued:
+//! 1. Generate the new signing key
+//! 2. Re-sign the issuer JWT listing both the old and the new signing key
+//! 3. Re-issue every downstream JWT (accounts of an operator, users of an
+//!    account) signed by the new key
+//! 4. Wait out the grace period so every server resolves the new JWTs
+//! 5. Re-sign the issuer JWT listing only the new key, retiring the old one
+//!
+//! Any failure rolls back to the old key: the issuer JWT is restored to the
+//! old signing keys and the new key is discarded. Downstream JWTs signed by
+//! the old key stay valid throughout, so rollback needs no re-issue.
+//!
+//! ## State Machine
+//!
+//! ```text
+//! Initial → GeneratingSigningKey → PublishingIssuer → ReissuingDownstream → GracePeriod
+//!     ↓             ↓                     ↓                   ↓                  ↓ (grace elapsed)
+//!   Failed        Failed               Failed              Failed        RetiringOldKey → Completed
+//!                                                                              ↓
+//!                                                                           Failed
+//! ```
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+
+use super::{SagaState, SagaError};
+use crate::types::NatsEntityType;
+
+/// NATS Signing Key Rotation Saga state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotateNatsSigningKeySaga {
+    /// Unique saga ID
+    pub saga_id: Uuid,
+    /// Correlation ID for all events
+    pub correlation_id: Uuid,
+    /// Current state
+    pub state: SigningKeyRotationState,
+    /// State at which failure occurred (for compensation)
+    failed_at_state: Option<SigningKeyRotationState>,
+    /// Started at timestamp
+    pub started_at: DateTime<Utc>,
+    /// Completed at timestamp (if completed)
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Rotation request details
+    pub request: SigningKeyRotationRequest,
+    /// Generated artifacts
+    pub artifacts: SigningKeyRotationArtifacts,
+    /// Error if failed
+    pub error: Option<SagaError>,
+}
+
+/// Rotation state machine states
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SigningKeyRotationState {
+    /// Saga not started
+    Initial,
+    /// Generating the replacement signing key
+    GeneratingSigningKey,
+    /// Re-signing the issuer JWT with old and new signing key
+    PublishingIssuer,
+    /// Re-issuing downstream JWTs signed by the new key
+    ReissuingDownstream,
+    /// Both keys trusted until the grace period ends
+    GracePeriod,
+    /// Re-signing the issuer JWT with the new key only
+    RetiringOldKey,
+    /// Successfully completed
+    Completed,
+    /// Failed (see error field)
+    Failed,
+    /// Compensating (rolling back)
+    Compensating(SigningKeyRotationCompensationStep),
+}
+
+/// Compensation sub-steps
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SigningKeyRotationCompensationStep {
+    /// Re-sign the issuer JWT with the old signing keys
+    RestoreIssuer,
+    /// Discard the new signing key
+    DiscardNewKey,
+}
+
+/// A JWT issued under the rotating signing key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DownstreamJwt {
+    /// Account or user ID
+    pub entity_id: Uuid,
+    pub name: String,
+    /// Subject (`sub`) of the JWT
+    pub subject_public_key: String,
+}
+
+/// Rotation request details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningKeyRotationRequest {
+    /// Operator or account owning the signing key
+    pub issuer_id: Uuid,
+    pub issuer_type: NatsEntityType,
+    pub issuer_public_key: String,
+    /// Signing key being replaced
+    pub old_signing_key: String,
+    /// JWTs to re-issue under the new key
+    pub downstream: Vec<DownstreamJwt>,
+    /// How long both signing keys stay trusted
+    pub grace_hours: u32,
+    /// Why the key is rotated (recorded on retirement)
+    pub reason: String,
+}
+
+/// Artifacts generated during rotation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SigningKeyRotationArtifacts {
+    /// Replacement signing key (public)
+    pub new_signing_key: Option<String>,
+    /// Downstream entities whose JWT was re-issued
+    pub reissued: Vec<Uuid>,
+    /// When the grace period ends
+    pub grace_ends_at: Option<DateTime<Utc>>,
+    /// Whether the old key has been retired
+    pub old_key_retired: bool,
+}
+
+impl RotateNatsSigningKeySaga {
+    /// Create a new signing key rotation saga
+    pub fn new(request: SigningKeyRotationRequest) -> Self {
+        Self {
+            saga_id: Uuid::now_v7(),
+            correlation_id: Uuid::now_v7(),
+            state: SigningKeyRotationState::Initial,
+            failed_at_state: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            request,
+            artifacts: SigningKeyRotationArtifacts::default(),
+            error: None,
+        }
+    }
+
+    /// Create with explicit correlation ID
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Start the saga
+    pub fn start(&mut self) -> Result<(), SagaError> {
+        let prefix = match self.request.issuer_type {
+            NatsEntityType::Operator => 'O',
+            NatsEntityType::Account => 'A',
+            NatsEntityType::User => {
+                return Err(SagaError::new("Users have no signing keys", "Initial"));
+            }
+        };
+        if !self.request.old_signing_key.starts_with(prefix) {
+            return Err(SagaError::new(
+                format!("{:?} signing keys start with {}", self.request.issuer_type, prefix),
+                "Initial",
+            ));
+        }
+        if self.request.old_signing_key == self.request.issuer_public_key {
+            return Err(SagaError::new("The identity key is not a signing key", "Initial"));
+        }
+        if self.request.grace_hours == 0 {
+            return Err(SagaError::new("Grace period must be > 0 hours", "Initial"));
+        }
+        self.state = SigningKeyRotationState::GeneratingSigningKey;
+        Ok(())
+    }
+
+    /// Transition to the next state
+    ///
+    /// Re-issuing is left only once every downstream JWT has been
+    /// re-issued, and the grace period only through `expire_grace_period`.
+    pub fn advance(&mut self) -> SigningKeyRotationState {
+        self.state = match &self.state {
+            SigningKeyRotationState::Initial => SigningKeyRotationState::GeneratingSigningKey,
+            SigningKeyRotationState::GeneratingSigningKey => SigningKeyRotationState::PublishingIssuer,
+            SigningKeyRotationState::PublishingIssuer => SigningKeyRotationState::ReissuingDownstream,
+            SigningKeyRotationState::ReissuingDownstream if self.pending_downstream().is_empty() => {
+                SigningKeyRotationState::GracePeriod
+            }
+            SigningKeyRotationState::ReissuingDownstream => SigningKeyRotationState::ReissuingDownstream,
+            SigningKeyRotationState::GracePeriod => SigningKeyRotationState::GracePeriod,
+            SigningKeyRotationState::RetiringOldKey => {
+                self.completed_at = Some(Utc::now());
+                SigningKeyRotationState::Completed
+            }
+            SigningKeyRotationState::Completed => SigningKeyRotationState::Completed,
+            SigningKeyRotationState::Failed => SigningKeyRotationState::Failed,
+            SigningKeyRotationState::Compensating(_) => SigningKeyRotationState::Failed,
+        };
+        self.state.clone()
+    }
+
+    /// Record generation of the replacement signing key
+    pub fn record_new_signing_key(&mut self, public_key: impl Into<String>) {
+        self.artifacts.new_signing_key = Some(public_key.into());
+    }
+
+    /// Record that the issuer JWT trusting both keys was published at `now`
+    pub fn record_issuer_published(&mut self, now: DateTime<Utc>) {
+        self.artifacts.grace_ends_at = Some(now + Duration::hours(i64::from(self.request.grace_hours)));
+    }
+
+    /// Record the re-issue of a downstream JWT
+    pub fn record_reissued(&mut self, entity_id: Uuid) {
+        if !self.artifacts.reissued.contains(&entity_id) {
+            self.artifacts.reissued.push(entity_id);
+        }
+    }
+
+    /// Downstream JWTs not yet re-issued
+    pub fn pending_downstream(&self) -> Vec<&DownstreamJwt> {
+        self.request
+            .downstream
+            .iter()
+            .filter(|jwt| !self.artifacts.reissued.contains(&jwt.entity_id))
+            .collect()
+    }
+
+    /// Leave the grace period once it has elapsed
+    ///
+    /// Returns whether the saga moved on to `RetiringOldKey`.
+    pub fn expire_grace_period(&mut self, now: DateTime<Utc>) -> bool {
+        let elapsed = self.state == SigningKeyRotationState::GracePeriod
+            && self.artifacts.grace_ends_at.is_some_and(|ends| now >= ends);
+        if elapsed {
+            self.state = SigningKeyRotationState::RetiringOldKey;
+        }
+        elapsed
+    }
+
+    /// Record retirement of the old signing key
+    pub fn record_old_key_retired(&mut self) {
+        self.artifacts.old_key_retired = true;
+    }
+
+    /// Mark the saga as failed
+    pub fn fail(&mut self, message: impl Into<String>, step: impl Into<String>) {
+        self.failed_at_state = Some(self.state.clone());
+        self.error = Some(SagaError::new(message, step));
+        self.state = SigningKeyRotationState::Failed;
+    }
+
+    /// Start compensation
+    pub fn start_compensation(&mut self) -> SigningKeyRotationCompensationStep {
+        // Use the state at which failure occurred, not the current (Failed) state
+        let failed_state = self.failed_at_state.as_ref().unwrap_or(&self.state);
+        let step = match failed_state {
+            SigningKeyRotationState::Initial | SigningKeyRotationState::GeneratingSigningKey => {
+                SigningKeyRotationCompensationStep::DiscardNewKey
+            }
+            _ => SigningKeyRotationCompensationStep::RestoreIssuer,
+        };
+        self.state = SigningKeyRotationState::Compensating(step.clone());
+        step
+    }
+
+    /// Advance compensation to next step
+    pub fn advance_compensation(&mut self) -> Option<SigningKeyRotationCompensationStep> {
+        if let SigningKeyRotationState::Compensating(current) = &self.state {
+            let next = match current {
+                SigningKeyRotationCompensationStep::RestoreIssuer => {
+                    Some(SigningKeyRotationCompensationStep::DiscardNewKey)
+                }
+                SigningKeyRotationCompensationStep::DiscardNewKey => None,
+            };
+
+            if let Some(step) = next.clone() {
+                self.state = SigningKeyRotationState::Compensating(step);
+            } else {
+                self.state = SigningKeyRotationState::Failed;
+            }
+
+            next
+        } else {
+            None
+        }
+    }
+
+    /// Rotating signing keys the issuer JWT lists in the current state
+    ///
+    /// Old key until the new one is published, both keys through the grace
+    /// period, the new key afterwards. While compensating (and after a
+    /// failed rotation) only the old key is kept.
+    pub fn signing_keys(&self) -> Vec<&str> {
+        let old = self.request.old_signing_key.as_str();
+        let new = self.artifacts.new_signing_key.as_deref();
+        match (&self.state, new) {
+            (
+                SigningKeyRotationState::PublishingIssuer
+                | SigningKeyRotationState::ReissuingDownstream
+                | SigningKeyRotationState::GracePeriod,
+                Some(new),
+            ) => vec![old, new],
+            (SigningKeyRotationState::RetiringOldKey | SigningKeyRotationState::Completed, Some(new)) => {
+                vec![new]
+            }
+            _ => vec![old],
+        }
+    }
+
+    /// Get current step name for logging
+    pub fn current_step_name(&self) -> String {
+        match &self.state {
+            SigningKeyRotationState::Initial => "Initial".to_string(),
+            SigningKeyRotationState::GeneratingSigningKey => "GeneratingSigningKey".to_string(),
+            SigningKeyRotationState::PublishingIssuer => "PublishingIssuer".to_string(),
+            SigningKeyRotationState::ReissuingDownstream => "ReissuingDownstream".to_string(),
+            SigningKeyRotationState::GracePeriod => "GracePeriod".to_string(),
+            SigningKeyRotationState::RetiringOldKey => "RetiringOldKey".to_string(),
+            SigningKeyRotationState::Completed => "Completed".to_string(),
+            SigningKeyRotationState::Failed => "Failed".to_string(),
+            SigningKeyRotationState::Compensating(step) => format!("Compensating:{:?}", step),
+        }
+    }
+}
+
+impl SagaState for RotateNatsSigningKeySaga {
+    fn saga_id(&self) -> Uuid {
+        self.saga_id
+    }
+
+    fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(
+            self.state,
+            SigningKeyRotationState::Completed | SigningKeyRotationState::Failed
+        )
+    }
+
+    fn is_completed(&self) -> bool {
+        matches!(self.state, SigningKeyRotationState::Completed)
+    }
+
+    fn is_failed(&self) -> bool {
+        matches!(self.state, SigningKeyRotationState::Failed)
+    }
+
+    fn status_description(&self) -> String {
+        match &self.state {
+            SigningKeyRotationState::Initial => "Not started".to_string(),
+            SigningKeyRotationState::GeneratingSigningKey => format!(
+                "Generating {:?} signing key to replace {}",
+                self.request.issuer_type, self.request.old_signing_key
+            ),
+            SigningKeyRotationState::PublishingIssuer => {
+                "Publishing issuer JWT with old and new signing key".to_string()
+            }
+            SigningKeyRotationState::ReissuingDownstream => format!(
+                "Re-issuing JWTs ({} of {} done)",
+                self.artifacts.reissued.len(),
+                self.request.downstream.len()
+            ),
+            SigningKeyRotationState::GracePeriod => match self.artifacts.grace_ends_at {
+                Some(ends) => format!("Both signing keys trusted until {}", ends),
+                None => "Both signing keys trusted".to_string(),
+            },
+            SigningKeyRotationState::RetiringOldKey => {
+                format!("Retiring signing key {}", self.request.old_signing_key)
+            }
+            SigningKeyRotationState::Completed => format!(
+                "Rotated signing key {} ({})",
+                self.request.old_signing_key, self.request.reason
+            ),
+            SigningKeyRotationState::Failed => format!(
+                "Signing key rotation failed: {}",
+                self.error.as_ref().map_or("Unknown error", |e| &e.message)
+            ),
+            SigningKeyRotationState::Compensating(step) => format!("Rolling back: {:?}", step),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_request() -> SigningKeyRotationRequest {
+        SigningKeyRotationRequest {
+            issuer_id: Uuid::now_v7(),
+            issuer_type: NatsEntityType::Account,
+            issuer_public_key: "AIDENTITY".to_string(),
+            old_signing_key: "AOLDSIGNER".to_string(),
+            downstream: vec![
+                DownstreamJwt {
+                    entity_id: Uuid::now_v7(),
+                    name: "alice".to_string(),
+                    subject_public_key: "UALICE".to_string(),
+                },
+                DownstreamJwt {
+                    entity_id: Uuid::now_v7(),
+                    name: "bob".to_string(),
+                    subject_public_key: "UBOB".to_string(),
+                },
+            ],
+            grace_hours: 48,
+            reason: "Scheduled rotation".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_saga_start_validation() {
+        let mut request = create_test_request();
+        request.old_signing_key = "OWRONGTYPE".to_string();
+        assert!(RotateNatsSigningKeySaga::new(request).start().is_err());
+
+        let mut request = create_test_request();
+        request.old_signing_key = request.issuer_public_key.clone();
+        assert!(RotateNatsSigningKeySaga::new(request).start().is_err());
+
+        let mut saga = RotateNatsSigningKeySaga::new(create_test_request());
+        assert!(saga.start().is_ok());
+        assert_eq!(saga.state, SigningKeyRotationState::GeneratingSigningKey);
+    }
+
+    #[test]
+    fn test_full_rotation_waits_for_reissue_and_grace() {
+        let request = create_test_request();
+        let downstream = request.downstream.clone();
+        let mut saga = RotateNatsSigningKeySaga::new(request);
+        let now = Utc::now();
+
+        saga.start().unwrap();
+        saga.record_new_signing_key("ANEWSIGNER");
+        assert_eq!(saga.signing_keys(), vec!["AOLDSIGNER"]);
+
+        saga.advance(); // PublishingIssuer
+        assert_eq!(saga.signing_keys(), vec!["AOLDSIGNER", "ANEWSIGNER"]);
+        saga.record_issuer_published(now);
+
+        // Every downstream JWT must be re-issued first
+        assert_eq!(saga.advance(), SigningKeyRotationState::ReissuingDownstream);
+        saga.record_reissued(downstream[0].entity_id);
+        assert_eq!(saga.advance(), SigningKeyRotationState::ReissuingDownstream);
+        saga.record_reissued(downstream[1].entity_id);
+        assert_eq!(saga.advance(), SigningKeyRotationState::GracePeriod);
+
+        assert!(!saga.expire_grace_period(now + Duration::hours(47)));
+        assert_eq!(saga.signing_keys(), vec!["AOLDSIGNER", "ANEWSIGNER"]);
+        assert!(saga.expire_grace_period(now + Duration::hours(48)));
+        assert_eq!(saga.signing_keys(), vec!["ANEWSIGNER"]);
+
+        saga.record_old_key_retired();
+        saga.advance();
+        assert!(saga.is_completed());
+    }
+
+    #[test]
+    fn test_failure_while_reissuing_restores_issuer() {
+        let mut saga = RotateNatsSigningKeySaga::new(create_test_request());
+        saga.start().unwrap();
+        saga.record_new_signing_key("ANEWSIGNER");
+        saga.advance(); // PublishingIssuer
+        saga.advance(); // ReissuingDownstream
+
+        saga.fail("Resolver rejected user JWT", "ReissuingDownstream");
+        assert_eq!(saga.start_compensation(), SigningKeyRotationCompensationStep::RestoreIssuer);
+        assert_eq!(saga.signing_keys(), vec!["AOLDSIGNER"]);
+        assert_eq!(saga.advance_compensation(), Some(SigningKeyRotationCompensationStep::DiscardNewKey));
+        assert_eq!(saga.advance_compensation(), None);
+        assert!(saga.is_failed());
+    }
+}
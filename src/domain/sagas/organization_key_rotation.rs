@@ -0,0 +1,546 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Organization-Wide Key Rotation Saga
+//!
+//! Rotates every active key of one kind across the organization, e.g. all
+//! NATS account signing keys or all SSH CA keys:
+//! 1. Select the active keys in the rotation scope
+//! 2. Order them so a signing key rotates before the keys it signs
+//! 3. For each key: generate the replacement, then re-issue the artifacts
+//!    signed by the old key (user JWTs, certificates) under the new one
+//! 4. Revoke the old keys
+//! 5. Report the rotation with a `KeyRotationReported` event
+//!
+//! A failure rolls back: re-issued artifacts are restored to their
+//! previous versions and the replacement keys are revoked.
+//!
+//! ## State Machine
+//!
+//! ```text
+//! Initial → RotatingKey(0) → RegeneratingArtifacts(0) → RotatingKey(1) → ...
+//!     ↓           ↓                    ↓
+//!   Failed     Failed               Failed
+//!
+//! ... → RegeneratingArtifacts(n-1) → RevokingOldKeys → Reporting → Completed
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use super::{SagaState, SagaError};
+use crate::events::{KeyAlgorithm, KeyRotationReportedEvent, RotatedKeyReport};
+
+/// Organization-wide key rotation saga state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrganizationKeyRotationSaga {
+    /// Unique saga ID
+    pub saga_id: Uuid,
+    /// Correlation ID for all events
+    pub correlation_id: Uuid,
+    /// Current state
+    pub state: OrgRotationState,
+    /// Started at timestamp
+    pub started_at: DateTime<Utc>,
+    /// Completed at timestamp (if completed)
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Rotation request details
+    pub request: OrgRotationRequest,
+    /// Keys to rotate, in rotation order (set by `start`)
+    pub targets: Vec<RotationTarget>,
+    /// Generated artifacts
+    pub artifacts: OrgRotationArtifacts,
+    /// Error if failed
+    pub error: Option<SagaError>,
+}
+
+/// Rotation state machine states
+///
+/// Indices refer to `OrganizationKeyRotationSaga::targets`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OrgRotationState {
+    /// Saga not started
+    Initial,
+    /// Generating the replacement for a key
+    RotatingKey(usize),
+    /// Re-issuing the artifacts signed by a key under its replacement
+    RegeneratingArtifacts(usize),
+    /// Revoking the replaced keys
+    RevokingOldKeys,
+    /// Publishing the rotation report
+    Reporting,
+    /// Successfully completed
+    Completed,
+    /// Failed (see error field)
+    Failed,
+    /// Compensating (rolling back)
+    Compensating(OrgRotationCompensationStep),
+}
+
+/// Compensation sub-steps
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OrgRotationCompensationStep {
+    /// Restore the artifacts re-issued so far to their previous versions
+    RestoreArtifacts,
+    /// Revoke the replacement keys generated so far
+    RevokeNewKeys,
+}
+
+/// Kind of key an organization-wide rotation covers
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum RotationScope {
+    /// Signing keys of NATS accounts
+    NatsAccountSigning,
+    /// OpenSSH certificate authority keys
+    SshCertificateAuthority,
+    /// X.509 root and intermediate CA keys
+    X509CertificateAuthority,
+    /// JWT/JWS signing keys
+    JwtSigning,
+}
+
+/// Something signed by a key that must be re-issued when the key rotates
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DependentArtifact {
+    pub artifact_id: Uuid,
+    pub kind: DependentArtifactKind,
+    /// What the artifact is, for the operator, e.g. `user alice`
+    pub description: String,
+}
+
+/// Kinds of artifacts signed by rotated keys
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DependentArtifactKind {
+    NatsUserJwt,
+    SshCertificate,
+    X509Certificate,
+    Jwks,
+}
+
+/// A key known to the organization, considered for rotation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationCandidate {
+    pub key_id: Uuid,
+    pub label: String,
+    pub scope: RotationScope,
+    /// Whether the key is in use; inactive keys are not rotated
+    pub active: bool,
+    /// Key that signs this key's certificate or JWT, if any
+    pub signed_by: Option<Uuid>,
+    /// Artifacts signed by this key
+    pub dependents: Vec<DependentArtifact>,
+}
+
+/// A key selected for rotation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RotationTarget {
+    pub key_id: Uuid,
+    pub label: String,
+    pub signed_by: Option<Uuid>,
+    pub dependents: Vec<DependentArtifact>,
+}
+
+/// Rotation request details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrgRotationRequest {
+    pub organization_id: Uuid,
+    /// Kind of key to rotate
+    pub scope: RotationScope,
+    /// Algorithm of the replacement keys
+    pub new_key_algorithm: KeyAlgorithm,
+    /// Why the keys are rotated (recorded on revocation and in the report)
+    pub reason: String,
+}
+
+/// Artifacts generated during rotation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrgRotationArtifacts {
+    /// Replacement key per old key
+    pub new_keys: HashMap<Uuid, Uuid>,
+    /// Re-issued artifacts per old key, as (old artifact, replacement)
+    pub regenerated: HashMap<Uuid, Vec<(Uuid, Uuid)>>,
+    /// Whether the old keys have been revoked
+    pub old_keys_revoked: bool,
+}
+
+impl OrganizationKeyRotationSaga {
+    /// Create a rotation of the active `candidates` in the request's scope
+    pub fn new(request: OrgRotationRequest, candidates: &[RotationCandidate]) -> Self {
+        let targets = candidates
+            .iter()
+            .filter(|c| c.active && c.scope == request.scope)
+            .map(|c| RotationTarget {
+                key_id: c.key_id,
+                label: c.label.clone(),
+                signed_by: c.signed_by,
+                dependents: c.dependents.clone(),
+            })
+            .collect();
+
+        Self {
+            saga_id: Uuid::now_v7(),
+            correlation_id: Uuid::now_v7(),
+            state: OrgRotationState::Initial,
+            started_at: Utc::now(),
+            completed_at: None,
+            request,
+            targets,
+            artifacts: OrgRotationArtifacts::default(),
+            error: None,
+        }
+    }
+
+    /// Create with explicit correlation ID
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Start the saga, putting the targets in dependency order
+    pub fn start(&mut self) -> Result<(), SagaError> {
+        if self.targets.is_empty() {
+            return Err(SagaError::new(
+                format!("No active {:?} keys to rotate", self.request.scope),
+                "Initial",
+            ));
+        }
+        self.targets = dependency_order(&self.targets)
+            .ok_or_else(|| SagaError::new("Signing relationships between keys form a cycle", "Initial"))?;
+        self.state = OrgRotationState::RotatingKey(0);
+        Ok(())
+    }
+
+    /// Transition to the next state
+    pub fn advance(&mut self) -> OrgRotationState {
+        self.state = match &self.state {
+            OrgRotationState::Initial => OrgRotationState::RotatingKey(0),
+            OrgRotationState::RotatingKey(i) => OrgRotationState::RegeneratingArtifacts(*i),
+            OrgRotationState::RegeneratingArtifacts(i) if i + 1 < self.targets.len() => {
+                OrgRotationState::RotatingKey(i + 1)
+            }
+            OrgRotationState::RegeneratingArtifacts(_) => OrgRotationState::RevokingOldKeys,
+            OrgRotationState::RevokingOldKeys => OrgRotationState::Reporting,
+            OrgRotationState::Reporting => {
+                self.completed_at = Some(Utc::now());
+                OrgRotationState::Completed
+            }
+            OrgRotationState::Completed => OrgRotationState::Completed,
+            OrgRotationState::Failed => OrgRotationState::Failed,
+            OrgRotationState::Compensating(_) => OrgRotationState::Failed,
+        };
+        self.state.clone()
+    }
+
+    /// Key being rotated or having its artifacts re-issued
+    pub fn current_target(&self) -> Option<&RotationTarget> {
+        match self.state {
+            OrgRotationState::RotatingKey(i) | OrgRotationState::RegeneratingArtifacts(i) => {
+                self.targets.get(i)
+            }
+            _ => None,
+        }
+    }
+
+    /// Key that must sign the target's replacement
+    ///
+    /// A signer rotated earlier in this saga signs with its replacement.
+    pub fn signer_for(&self, target: &RotationTarget) -> Option<Uuid> {
+        target
+            .signed_by
+            .map(|signer| self.artifacts.new_keys.get(&signer).copied().unwrap_or(signer))
+    }
+
+    /// Mark the saga as failed
+    pub fn fail(&mut self, message: impl Into<String>, step: impl Into<String>) {
+        self.error = Some(SagaError::new(message, step));
+        self.state = OrgRotationState::Failed;
+    }
+
+    /// Start compensation
+    pub fn start_compensation(&mut self) -> OrgRotationCompensationStep {
+        let step = if self.artifacts.regenerated.values().any(|r| !r.is_empty()) {
+            OrgRotationCompensationStep::RestoreArtifacts
+        } else {
+            OrgRotationCompensationStep::RevokeNewKeys
+        };
+        self.state = OrgRotationState::Compensating(step.clone());
+        step
+    }
+
+    /// Advance compensation to next step
+    pub fn advance_compensation(&mut self) -> Option<OrgRotationCompensationStep> {
+        if let OrgRotationState::Compensating(current) = &self.state {
+            let next = match current {
+                OrgRotationCompensationStep::RestoreArtifacts => {
+                    Some(OrgRotationCompensationStep::RevokeNewKeys)
+                }
+                OrgRotationCompensationStep::RevokeNewKeys => None,
+            };
+
+            if let Some(step) = next.clone() {
+                self.state = OrgRotationState::Compensating(step);
+            } else {
+                self.state = OrgRotationState::Failed;
+            }
+
+            next
+        } else {
+            None
+        }
+    }
+
+    /// Record generation of the replacement for `old_key_id`
+    pub fn record_new_key(&mut self, old_key_id: Uuid, new_key_id: Uuid) {
+        self.artifacts.new_keys.insert(old_key_id, new_key_id);
+    }
+
+    /// Record re-issuance of an artifact signed by `old_key_id`
+    pub fn record_regenerated(&mut self, old_key_id: Uuid, artifact_id: Uuid, replacement_id: Uuid) {
+        self.artifacts
+            .regenerated
+            .entry(old_key_id)
+            .or_default()
+            .push((artifact_id, replacement_id));
+    }
+
+    /// Record revocation of the old keys
+    pub fn record_old_keys_revoked(&mut self) {
+        self.artifacts.old_keys_revoked = true;
+    }
+
+    /// The rotation report, in rotation order
+    ///
+    /// After a rollback every target is listed as not rotated.
+    pub fn report(&self, reported_at: DateTime<Utc>) -> KeyRotationReportedEvent {
+        let rolled_back = self.is_failed();
+        let mut rotated = Vec::new();
+        let mut not_rotated = Vec::new();
+        for target in &self.targets {
+            match self.artifacts.new_keys.get(&target.key_id) {
+                Some(&new_key_id) if !rolled_back => rotated.push(RotatedKeyReport {
+                    old_key_id: target.key_id,
+                    new_key_id,
+                    label: target.label.clone(),
+                    regenerated: self.artifacts.regenerated.get(&target.key_id).cloned().unwrap_or_default(),
+                }),
+                _ => not_rotated.push(target.key_id),
+            }
+        }
+
+        KeyRotationReportedEvent {
+            rotation_id: self.saga_id,
+            organization_id: self.request.organization_id,
+            scope: format!("{:?}", self.request.scope),
+            reason: self.request.reason.clone(),
+            succeeded: !rolled_back && not_rotated.is_empty() && self.artifacts.old_keys_revoked,
+            rotated,
+            not_rotated,
+            started_at: self.started_at,
+            reported_at,
+            correlation_id: self.correlation_id,
+            causation_id: Some(self.saga_id),
+        }
+    }
+
+    /// Get current step name for logging
+    pub fn current_step_name(&self) -> String {
+        match &self.state {
+            OrgRotationState::Initial => "Initial".to_string(),
+            OrgRotationState::RotatingKey(i) => format!("RotatingKey:{}", i),
+            OrgRotationState::RegeneratingArtifacts(i) => format!("RegeneratingArtifacts:{}", i),
+            OrgRotationState::RevokingOldKeys => "RevokingOldKeys".to_string(),
+            OrgRotationState::Reporting => "Reporting".to_string(),
+            OrgRotationState::Completed => "Completed".to_string(),
+            OrgRotationState::Failed => "Failed".to_string(),
+            OrgRotationState::Compensating(step) => format!("Compensating:{:?}", step),
+        }
+    }
+}
+
+/// Targets ordered so every key comes after the key that signs it
+///
+/// Keys keep their given order where no signing relationship decides.
+/// `None` if the relationships form a cycle.
+fn dependency_order(targets: &[RotationTarget]) -> Option<Vec<RotationTarget>> {
+    let ids: HashSet<Uuid> = targets.iter().map(|t| t.key_id).collect();
+    let mut placed: HashSet<Uuid> = HashSet::new();
+    let mut ordered = Vec::with_capacity(targets.len());
+
+    while ordered.len() < targets.len() {
+        let ready = targets.iter().find(|t| {
+            !placed.contains(&t.key_id)
+                && t.signed_by.is_none_or(|signer| !ids.contains(&signer) || placed.contains(&signer))
+        })?;
+        placed.insert(ready.key_id);
+        ordered.push(ready.clone());
+    }
+    Some(ordered)
+}
+
+impl SagaState for OrganizationKeyRotationSaga {
+    fn saga_id(&self) -> Uuid {
+        self.saga_id
+    }
+
+    fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(
+            self.state,
+            OrgRotationState::Completed | OrgRotationState::Failed
+        )
+    }
+
+    fn is_completed(&self) -> bool {
+        matches!(self.state, OrgRotationState::Completed)
+    }
+
+    fn is_failed(&self) -> bool {
+        matches!(self.state, OrgRotationState::Failed)
+    }
+
+    fn status_description(&self) -> String {
+        let label = |i: usize| self.targets.get(i).map_or("?", |t| t.label.as_str());
+        match &self.state {
+            OrgRotationState::Initial => "Not started".to_string(),
+            OrgRotationState::RotatingKey(i) => format!(
+                "Rotating {} ({} of {})",
+                label(*i), i + 1, self.targets.len()
+            ),
+            OrgRotationState::RegeneratingArtifacts(i) => {
+                format!("Re-issuing artifacts signed by {}", label(*i))
+            }
+            OrgRotationState::RevokingOldKeys => {
+                format!("Revoking {} replaced keys", self.targets.len())
+            }
+            OrgRotationState::Reporting => "Reporting rotation".to_string(),
+            OrgRotationState::Completed => format!(
+                "Rotated {} {:?} keys ({})",
+                self.targets.len(), self.request.scope, self.request.reason
+            ),
+            OrgRotationState::Failed => format!(
+                "Organization key rotation failed: {}",
+                self.error.as_ref().map_or("Unknown error", |e| &e.message)
+            ),
+            OrgRotationState::Compensating(step) => format!("Rolling back: {:?}", step),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(label: &str, signed_by: Option<Uuid>, scope: RotationScope, active: bool) -> RotationCandidate {
+        RotationCandidate {
+            key_id: Uuid::now_v7(),
+            label: label.to_string(),
+            scope,
+            active,
+            signed_by,
+            dependents: vec![DependentArtifact {
+                artifact_id: Uuid::now_v7(),
+                kind: DependentArtifactKind::X509Certificate,
+                description: format!("certificate signed by {}", label),
+            }],
+        }
+    }
+
+    fn request() -> OrgRotationRequest {
+        OrgRotationRequest {
+            organization_id: Uuid::now_v7(),
+            scope: RotationScope::X509CertificateAuthority,
+            new_key_algorithm: KeyAlgorithm::Ed25519,
+            reason: "Annual rotation".to_string(),
+        }
+    }
+
+    /// Walk the saga to completion, re-issuing every dependent artifact
+    fn run(saga: &mut OrganizationKeyRotationSaga) {
+        while let Some(target) = saga.current_target().cloned() {
+            match saga.state {
+                OrgRotationState::RotatingKey(_) => saga.record_new_key(target.key_id, Uuid::now_v7()),
+                _ => {
+                    for artifact in &target.dependents {
+                        saga.record_regenerated(target.key_id, artifact.artifact_id, Uuid::now_v7());
+                    }
+                }
+            }
+            saga.advance();
+        }
+    }
+
+    #[test]
+    fn test_signers_rotate_before_the_keys_they_sign() {
+        let root = candidate("root", None, RotationScope::X509CertificateAuthority, true);
+        let intermediate = candidate("intermediate", Some(root.key_id), RotationScope::X509CertificateAuthority, true);
+        let retired = candidate("retired", None, RotationScope::X509CertificateAuthority, false);
+        let ssh_ca = candidate("ssh-ca", None, RotationScope::SshCertificateAuthority, true);
+
+        let mut saga = OrganizationKeyRotationSaga::new(
+            request(),
+            &[intermediate.clone(), retired, ssh_ca, root.clone()],
+        );
+        saga.start().unwrap();
+
+        let order: Vec<_> = saga.targets.iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(order, vec!["root", "intermediate"]);
+
+        run(&mut saga);
+        assert_eq!(saga.state, OrgRotationState::RevokingOldKeys);
+        let new_root = saga.artifacts.new_keys[&root.key_id];
+        assert_eq!(saga.signer_for(&saga.targets[1].clone()), Some(new_root));
+
+        saga.record_old_keys_revoked();
+        saga.advance(); // Reporting
+        let report = saga.report(Utc::now());
+        saga.advance();
+
+        assert!(saga.is_completed());
+        assert!(report.succeeded);
+        assert_eq!(report.rotated.len(), 2);
+        assert_eq!(report.rotated[0].old_key_id, root.key_id);
+        assert_eq!(report.rotated[1].regenerated.len(), 1);
+        assert!(report.not_rotated.is_empty());
+    }
+
+    #[test]
+    fn test_cycle_or_empty_scope_is_refused() {
+        let mut a = candidate("a", None, RotationScope::JwtSigning, true);
+        let b = candidate("b", Some(a.key_id), RotationScope::JwtSigning, true);
+        a.signed_by = Some(b.key_id);
+        let mut jwt_request = request();
+        jwt_request.scope = RotationScope::JwtSigning;
+
+        assert!(OrganizationKeyRotationSaga::new(jwt_request, &[a.clone(), b]).start().is_err());
+        assert!(OrganizationKeyRotationSaga::new(request(), &[a]).start().is_err());
+    }
+
+    #[test]
+    fn test_failure_rolls_back_and_reports_nothing_rotated() {
+        let root = candidate("root", None, RotationScope::X509CertificateAuthority, true);
+        let other = candidate("other-root", None, RotationScope::X509CertificateAuthority, true);
+        let mut saga = OrganizationKeyRotationSaga::new(request(), &[root.clone(), other]);
+        saga.start().unwrap();
+
+        saga.record_new_key(root.key_id, Uuid::now_v7());
+        saga.advance(); // RegeneratingArtifacts(0)
+        saga.record_regenerated(root.key_id, root.dependents[0].artifact_id, Uuid::now_v7());
+        saga.advance(); // RotatingKey(1)
+        saga.fail("HSM unavailable", "RotatingKey:1");
+
+        assert_eq!(saga.start_compensation(), OrgRotationCompensationStep::RestoreArtifacts);
+        assert_eq!(saga.advance_compensation(), Some(OrgRotationCompensationStep::RevokeNewKeys));
+        assert_eq!(saga.advance_compensation(), None);
+        assert!(saga.is_failed());
+
+        let report = saga.report(Utc::now());
+        assert!(!report.succeeded);
+        assert!(report.rotated.is_empty());
+        assert_eq!(report.not_rotated.len(), 2);
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Saga Persistence and Resumption
+//!
+//! [`SagaRepository`] checkpoints a saga through a [`SagaStore`] after
+//! every transition and brings it back with [`SagaRepository::resume`]:
+//!
+//! ```text
+//! start(saga, SagaStarted)          → v1
+//! checkpoint(saga, StepCompleted)   → v2, v3, ...
+//! ...crash / power cycle...
+//! incomplete()                      → [saga_id]
+//! resume::<PersonOnboardingSaga>()  → saga at vN + SagaResumed (vN+1)
+//! ```
+//!
+//! Because the state and the events are saved together, a resumed saga
+//! never re-runs a step whose completion was recorded, and never skips
+//! one whose completion was not.
+
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::{
+    CertificateProvisioningSaga, CertificateRenewalSaga, CompleteBootstrapSaga,
+    OrganizationKeyRotationSaga, PersonOnboardingSaga, RecoveryRehearsalSaga,
+    RotateNatsSigningKeySaga, SagaState, SshKeyRotationSaga,
+};
+use crate::events::saga::SagaResumedEvent;
+use crate::events::SagaEvents;
+use crate::ports::saga_store::{SagaRecord, SagaStore, SagaStoreError};
+
+/// A saga whose state can be stored and resumed
+pub trait PersistentSaga: SagaState + Serialize + DeserializeOwned {
+    /// Stable type name recorded with the state
+    const SAGA_TYPE: &'static str;
+
+    /// Step the saga is at, recorded as the resume point
+    fn current_step(&self) -> String;
+}
+
+macro_rules! persistent_saga {
+    ($saga:ty, $name:literal) => {
+        impl PersistentSaga for $saga {
+            const SAGA_TYPE: &'static str = $name;
+
+            fn current_step(&self) -> String {
+                self.current_step_name()
+            }
+        }
+    };
+}
+
+persistent_saga!(CompleteBootstrapSaga, "CompleteBootstrap");
+persistent_saga!(PersonOnboardingSaga, "PersonOnboarding");
+persistent_saga!(CertificateProvisioningSaga, "CertificateProvisioning");
+persistent_saga!(SshKeyRotationSaga, "SshKeyRotation");
+persistent_saga!(CertificateRenewalSaga, "CertificateRenewal");
+persistent_saga!(RotateNatsSigningKeySaga, "RotateNatsSigningKey");
+persistent_saga!(OrganizationKeyRotationSaga, "OrganizationKeyRotation");
+persistent_saga!(RecoveryRehearsalSaga, "RecoveryRehearsal");
+
+/// Checkpoints and resumes sagas through a [`SagaStore`]
+pub struct SagaRepository<St: SagaStore> {
+    store: St,
+}
+
+impl<St: SagaStore> SagaRepository<St> {
+    pub fn new(store: St) -> Self {
+        Self { store }
+    }
+
+    /// The underlying store
+    pub fn store(&self) -> &St {
+        &self.store
+    }
+
+    /// Save the saga's state and the events its latest transition emitted
+    ///
+    /// Returns the version written.
+    pub async fn checkpoint<S: PersistentSaga>(
+        &self,
+        saga: &S,
+        events: &[SagaEvents],
+    ) -> Result<u64, SagaStoreError> {
+        let stored = match self.store.load(saga.saga_id()).await? {
+            Some(record) => {
+                check_type::<S>(&record)?;
+                record.version
+            }
+            None => 0,
+        };
+
+        let record = Self::record(saga, stored + 1)?;
+        self.store.save(&record, events).await?;
+        Ok(record.version)
+    }
+
+    /// Load a stored saga as it was last checkpointed
+    pub async fn load<S: PersistentSaga>(&self, saga_id: Uuid) -> Result<S, SagaStoreError> {
+        let record = self.store.load(saga_id).await?
+            .ok_or(SagaStoreError::NotFound(saga_id))?;
+        check_type::<S>(&record)?;
+        Self::state(&record)
+    }
+
+    /// Continue an interrupted saga from its last checkpoint
+    ///
+    /// Records a `SagaResumed` event naming the step the saga resumes
+    /// from; a completed or failed saga cannot be resumed.
+    pub async fn resume<S: PersistentSaga>(
+        &self,
+        saga_id: Uuid,
+        reason: impl Into<String>,
+    ) -> Result<(S, SagaResumedEvent), SagaStoreError> {
+        let record = self.store.load(saga_id).await?
+            .ok_or(SagaStoreError::NotFound(saga_id))?;
+        check_type::<S>(&record)?;
+        if record.terminal {
+            return Err(SagaStoreError::Terminated(saga_id));
+        }
+
+        let saga: S = Self::state(&record)?;
+        let resumed = SagaResumedEvent {
+            saga_id,
+            saga_type: record.saga_type.clone(),
+            correlation_id: saga.correlation_id(),
+            resumed_at: Utc::now(),
+            state_version: record.version,
+            resume_from_step: saga.current_step(),
+            resume_reason: reason.into(),
+        };
+
+        let next = Self::record(&saga, record.version + 1)?;
+        self.store.save(&next, &[SagaEvents::SagaResumed(resumed.clone())]).await?;
+        Ok((saga, resumed))
+    }
+
+    /// Sagas left unfinished, e.g. to offer resuming them at startup
+    pub async fn incomplete(&self) -> Result<Vec<SagaRecord>, SagaStoreError> {
+        self.store.incomplete().await
+    }
+
+    /// Events recorded for a saga, oldest first
+    pub async fn events(&self, saga_id: Uuid) -> Result<Vec<SagaEvents>, SagaStoreError> {
+        self.store.events(saga_id).await
+    }
+
+    fn record<S: PersistentSaga>(saga: &S, version: u64) -> Result<SagaRecord, SagaStoreError> {
+        Ok(SagaRecord {
+            saga_id: saga.saga_id(),
+            saga_type: S::SAGA_TYPE.to_string(),
+            correlation_id: saga.correlation_id(),
+            version,
+            current_step: saga.current_step(),
+            status: saga.status_description(),
+            terminal: saga.is_terminal(),
+            updated_at: Utc::now(),
+            state: serde_json::to_value(saga)
+                .map_err(|e| SagaStoreError::SerializationError(e.to_string()))?,
+        })
+    }
+
+    fn state<S: PersistentSaga>(record: &SagaRecord) -> Result<S, SagaStoreError> {
+        serde_json::from_value(record.state.clone())
+            .map_err(|e| SagaStoreError::SerializationError(e.to_string()))
+    }
+}
+
+fn check_type<S: PersistentSaga>(record: &SagaRecord) -> Result<(), SagaStoreError> {
+    if record.saga_type == S::SAGA_TYPE {
+        Ok(())
+    } else {
+        Err(SagaStoreError::TypeMismatch {
+            expected: S::SAGA_TYPE.to_string(),
+            found: record.saga_type.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemorySagaStore;
+    use crate::domain::bootstrap::KeyOwnerRole;
+    use crate::domain::ids::{BootstrapOrgId, CertificateId, NatsAccountId, UnitId};
+    use crate::domain::sagas::{PersonOnboarding, PersonOnboardingData, SagaDefinition, SagaEngine, SagaStep};
+    use crate::events::saga::StepCompletedEvent;
+
+    /// Onboarding steps that all succeed without touching any aggregate
+    fn onboarding_engine() -> SagaEngine<PersonOnboarding> {
+        let definition = ["CreatePerson", "GenerateKey", "GenerateCertificate", "CreateNatsUser"]
+            .into_iter()
+            .fold(SagaDefinition::new("PersonOnboarding"), |definition, name| {
+                definition.step(SagaStep::new(name, |_: &mut PersonOnboarding| Ok(None)))
+            });
+        SagaEngine::new(definition)
+    }
+
+    fn onboarding_saga(engine: &SagaEngine<PersonOnboarding>) -> PersonOnboardingSaga {
+        let person = PersonOnboarding::new(PersonOnboardingData {
+            person_id: Uuid::now_v7(),
+            organization_id: BootstrapOrgId::new(),
+            name: "Alice Engineer".to_string(),
+            email: "alice@test.com".to_string(),
+            role: KeyOwnerRole::Developer,
+            unit_ids: vec![UnitId::new()],
+            needs_yubikey: true,
+            yubikey_serial: None,
+            issuing_ca_id: Some(CertificateId::new()),
+            nats_account_id: Some(NatsAccountId::new()),
+        });
+        engine.start(person, "operator", Uuid::now_v7()).0
+    }
+
+    fn step_completed(saga: &PersonOnboardingSaga, step_number: u32) -> SagaEvents {
+        SagaEvents::StepCompleted(StepCompletedEvent {
+            saga_id: saga.saga_id(),
+            step_name: saga.current_step(),
+            step_number,
+            correlation_id: saga.correlation_id(),
+            causation_id: saga.correlation_id(),
+            completed_at: Utc::now(),
+            duration_ms: 10,
+            artifacts: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_resume_continues_from_last_checkpoint() {
+        let repository = SagaRepository::new(InMemorySagaStore::new());
+        let engine = onboarding_engine();
+        let mut saga = onboarding_saga(&engine);
+        engine.advance(&mut saga).await;
+        repository.checkpoint(&saga, &[step_completed(&saga, 1)]).await.unwrap();
+        engine.advance(&mut saga).await;
+        let version = repository.checkpoint(&saga, &[step_completed(&saga, 2)]).await.unwrap();
+
+        // Power cycle: only the store survives
+        let incomplete = repository.incomplete().await.unwrap();
+        assert_eq!(incomplete.len(), 1);
+
+        let (resumed, event): (PersonOnboardingSaga, _) =
+            repository.resume(saga.saga_id(), "restart").await.unwrap();
+
+        assert_eq!(resumed.current_step_name(), "GenerateCertificate");
+        assert_eq!(resumed.next_step, 2);
+        assert_eq!(event.state_version, version);
+        assert_eq!(event.resume_from_step, saga.current_step());
+        let events = repository.events(saga.saga_id()).await.unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events.last(), Some(SagaEvents::SagaResumed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_terminal_or_mistyped_saga_is_not_resumed() {
+        let repository = SagaRepository::new(InMemorySagaStore::new());
+        let engine = onboarding_engine();
+        let mut saga = onboarding_saga(&engine);
+        repository.checkpoint(&saga, &[]).await.unwrap();
+
+        let wrong_type = repository.resume::<CertificateRenewalSaga>(saga.saga_id(), "restart").await;
+        assert!(matches!(wrong_type, Err(SagaStoreError::TypeMismatch { .. })));
+
+        engine.run_to_completion(&mut saga).await;
+        repository.checkpoint(&saga, &[]).await.unwrap();
+
+        let result = repository.resume::<PersonOnboardingSaga>(saga.saga_id(), "restart").await;
+        assert_eq!(result.err(), Some(SagaStoreError::Terminated(saga.saga_id())));
+        assert!(repository.incomplete().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,437 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Disaster Recovery Rehearsal Saga
+//!
+//! Proves the backups would restore the organization's keys, without
+//! touching real key material:
+//! 1. Recover the master seed from backup holders' shares or the passphrase
+//! 2. Re-derive every key at its recorded path into a scratch projection
+//! 3. Diff the derived identities against the production manifest
+//! 4. Report with a `RecoveryRehearsalCompleted` event
+//!
+//! The recovered seed is handed back to the caller only to drive step 2
+//! and is never stored in the saga, so a persisted rehearsal holds public
+//! identities alone. Nothing is written to the production projection and
+//! no `MasterSeedRecovered` event is emitted, so there is nothing to
+//! compensate: a failed rehearsal simply reports the failure.
+//!
+//! ## State Machine
+//!
+//! ```text
+//! Initial → RecoveringSeed → DerivingKeys → ComparingManifest → Reporting → Completed
+//!     ↓            ↓               ↓                ↓
+//!   Failed      Failed          Failed           Failed
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+use super::{SagaState, SagaError};
+use crate::crypto::seed_derivation::{derive_master_seed, MasterSeed};
+use crate::crypto::shamir::GroupShare;
+use crate::crypto::DerivationRegistry;
+use crate::events::{RecoveryDiscrepancy, RecoveryRehearsalCompletedEvent};
+use crate::value_objects::ActorId;
+
+/// Disaster recovery rehearsal saga state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryRehearsalSaga {
+    /// Unique saga ID
+    pub saga_id: Uuid,
+    /// Correlation ID for all events
+    pub correlation_id: Uuid,
+    /// Current state
+    pub state: RehearsalState,
+    /// Started at timestamp
+    pub started_at: DateTime<Utc>,
+    /// Completed at timestamp (if completed)
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Rehearsal request details
+    pub request: RehearsalRequest,
+    /// Scratch projection of re-derived keys
+    pub scratch: RehearsalScratch,
+    /// Error if failed
+    pub error: Option<SagaError>,
+}
+
+/// Rehearsal state machine states
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RehearsalState {
+    /// Saga not started
+    Initial,
+    /// Recombining the master seed from backups
+    RecoveringSeed,
+    /// Re-deriving keys from the recovered seed
+    DerivingKeys,
+    /// Diffing derived identities against the manifest
+    ComparingManifest,
+    /// Publishing the rehearsal report
+    Reporting,
+    /// Successfully completed (discrepancies are a result, not a failure)
+    Completed,
+    /// Failed (see error field)
+    Failed,
+}
+
+/// Backup the seed is recovered from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum RehearsalSource {
+    /// Group shares of a master seed split
+    Shares { split_id: Uuid },
+    /// Passphrase (mnemonic) the seed was derived from, salted with the
+    /// organization label used at derivation time
+    Passphrase { organization_label: String },
+}
+
+/// Rehearsal request details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RehearsalRequest {
+    pub organization_id: Uuid,
+    pub source: RehearsalSource,
+    /// Derivation paths and identities recorded in production
+    pub expected: DerivationRegistry,
+    /// Every key the production manifest lists
+    pub manifest_key_ids: Vec<Uuid>,
+    pub performed_by: ActorId,
+}
+
+/// A key re-derived during the rehearsal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScratchKey {
+    pub path: String,
+    /// Identity derived from the recovered seed
+    pub derived: Option<String>,
+    /// Why the key could not be derived
+    pub error: Option<String>,
+}
+
+/// Scratch projection, discarded with the saga
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RehearsalScratch {
+    /// IDs of the shares presented
+    pub share_ids: Vec<String>,
+    /// Re-derived keys by key ID
+    pub keys: BTreeMap<Uuid, ScratchKey>,
+    /// Differences from the manifest
+    pub discrepancies: Vec<RecoveryDiscrepancy>,
+}
+
+impl RecoveryRehearsalSaga {
+    /// Create a new recovery rehearsal saga
+    pub fn new(request: RehearsalRequest) -> Self {
+        Self {
+            saga_id: Uuid::now_v7(),
+            correlation_id: Uuid::now_v7(),
+            state: RehearsalState::Initial,
+            started_at: Utc::now(),
+            completed_at: None,
+            request,
+            scratch: RehearsalScratch::default(),
+            error: None,
+        }
+    }
+
+    /// Create with explicit correlation ID
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Start the saga
+    pub fn start(&mut self) -> Result<(), SagaError> {
+        if self.request.expected.records().next().is_none() {
+            return Err(SagaError::new("No derivation records to rehearse against", "Initial"));
+        }
+        self.state = RehearsalState::RecoveringSeed;
+        Ok(())
+    }
+
+    /// Recover the seed from group shares
+    ///
+    /// The seed is returned for [`derive_keys`](Self::derive_keys) and is
+    /// not kept by the saga.
+    pub fn recover_from_shares(&mut self, shares: &[GroupShare]) -> Result<MasterSeed, SagaError> {
+        self.expect_state(RehearsalState::RecoveringSeed)?;
+        if !matches!(self.request.source, RehearsalSource::Shares { .. }) {
+            return Err(SagaError::new("Rehearsal does not recover from shares", "RecoveringSeed"));
+        }
+        self.scratch.share_ids = shares.iter().map(GroupShare::share_id).collect();
+        let seed = MasterSeed::from_group_shares(shares)
+            .map_err(|e| self.failed(format!("Master seed recovery failed: {}", e)))?;
+        self.state = RehearsalState::DerivingKeys;
+        Ok(seed)
+    }
+
+    /// Recover the seed from the passphrase it was derived from
+    pub fn recover_from_passphrase(&mut self, passphrase: &str) -> Result<MasterSeed, SagaError> {
+        self.expect_state(RehearsalState::RecoveringSeed)?;
+        let RehearsalSource::Passphrase { organization_label } = self.request.source.clone() else {
+            return Err(SagaError::new("Rehearsal does not recover from a passphrase", "RecoveringSeed"));
+        };
+        let seed = derive_master_seed(passphrase, &organization_label)
+            .map_err(|e| self.failed(format!("Master seed derivation failed: {}", e)))?;
+        self.state = RehearsalState::DerivingKeys;
+        Ok(seed)
+    }
+
+    /// Re-derive every recorded key into the scratch projection
+    pub fn derive_keys(&mut self, seed: &MasterSeed) -> Result<(), SagaError> {
+        self.expect_state(RehearsalState::DerivingKeys)?;
+        self.scratch.keys = self
+            .request
+            .expected
+            .records()
+            .map(|record| {
+                let derived = record.derive_public_id(seed);
+                let key = ScratchKey {
+                    path: record.path.to_string(),
+                    derived: derived.as_ref().ok().cloned(),
+                    error: derived.err(),
+                };
+                (record.key_id, key)
+            })
+            .collect();
+        self.state = RehearsalState::ComparingManifest;
+        Ok(())
+    }
+
+    /// Diff the scratch projection against the manifest
+    pub fn compare_manifest(&mut self) -> Result<&[RecoveryDiscrepancy], SagaError> {
+        self.expect_state(RehearsalState::ComparingManifest)?;
+        let mut discrepancies = Vec::new();
+        for record in self.request.expected.records() {
+            let Some(scratch) = self.scratch.keys.get(&record.key_id) else { continue };
+            match (&scratch.derived, &scratch.error) {
+                (Some(derived), _) if *derived == record.public_id => {}
+                (Some(derived), _) => discrepancies.push(RecoveryDiscrepancy::Mismatch {
+                    key_id: record.key_id,
+                    path: scratch.path.clone(),
+                    expected: record.public_id.clone(),
+                    derived: derived.clone(),
+                }),
+                (None, error) => discrepancies.push(RecoveryDiscrepancy::DerivationFailed {
+                    key_id: record.key_id,
+                    path: scratch.path.clone(),
+                    error: error.clone().unwrap_or_default(),
+                }),
+            }
+        }
+        for key_id in &self.request.manifest_key_ids {
+            if self.request.expected.get(*key_id).is_none() {
+                discrepancies.push(RecoveryDiscrepancy::NotDerivable { key_id: *key_id });
+            }
+        }
+        self.scratch.discrepancies = discrepancies;
+        self.state = RehearsalState::Reporting;
+        Ok(&self.scratch.discrepancies)
+    }
+
+    /// The rehearsal report; completes the saga
+    pub fn report(&mut self, completed_at: DateTime<Utc>) -> Result<RecoveryRehearsalCompletedEvent, SagaError> {
+        self.expect_state(RehearsalState::Reporting)?;
+        let keys_matched = self
+            .scratch
+            .keys
+            .keys()
+            .filter(|key_id| {
+                !self.scratch.discrepancies.iter().any(|d| match d {
+                    RecoveryDiscrepancy::Mismatch { key_id: k, .. }
+                    | RecoveryDiscrepancy::DerivationFailed { key_id: k, .. } => k == *key_id,
+                    RecoveryDiscrepancy::NotDerivable { .. } => false,
+                })
+            })
+            .count();
+        self.completed_at = Some(completed_at);
+        self.state = RehearsalState::Completed;
+
+        Ok(RecoveryRehearsalCompletedEvent {
+            rehearsal_id: self.saga_id,
+            organization_id: self.request.organization_id,
+            split_id: match self.request.source {
+                RehearsalSource::Shares { split_id } => Some(split_id),
+                RehearsalSource::Passphrase { .. } => None,
+            },
+            share_ids: self.scratch.share_ids.clone(),
+            keys_checked: self.scratch.keys.len(),
+            keys_matched,
+            discrepancies: self.scratch.discrepancies.clone(),
+            started_at: self.started_at,
+            completed_at,
+            performed_by: self.request.performed_by.clone(),
+            correlation_id: self.correlation_id,
+            causation_id: Some(self.saga_id),
+        })
+    }
+
+    /// Mark the saga as failed
+    pub fn fail(&mut self, message: impl Into<String>, step: impl Into<String>) {
+        self.error = Some(SagaError::new(message, step));
+        self.state = RehearsalState::Failed;
+    }
+
+    /// Get current step name for logging
+    pub fn current_step_name(&self) -> String {
+        format!("{:?}", self.state)
+    }
+
+    fn expect_state(&self, expected: RehearsalState) -> Result<(), SagaError> {
+        if self.state == expected {
+            Ok(())
+        } else {
+            Err(SagaError::new(
+                format!("Expected {:?}, rehearsal is {:?}", expected, self.state),
+                self.current_step_name(),
+            ))
+        }
+    }
+
+    /// Fail the saga and return the error for the caller
+    fn failed(&mut self, message: String) -> SagaError {
+        let step = self.current_step_name();
+        self.fail(message, step);
+        self.error.clone().unwrap_or_else(|| SagaError::new("Unknown error", "Failed"))
+    }
+}
+
+impl SagaState for RecoveryRehearsalSaga {
+    fn saga_id(&self) -> Uuid {
+        self.saga_id
+    }
+
+    fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(self.state, RehearsalState::Completed | RehearsalState::Failed)
+    }
+
+    fn is_completed(&self) -> bool {
+        matches!(self.state, RehearsalState::Completed)
+    }
+
+    fn is_failed(&self) -> bool {
+        matches!(self.state, RehearsalState::Failed)
+    }
+
+    fn status_description(&self) -> String {
+        match &self.state {
+            RehearsalState::Initial => "Not started".to_string(),
+            RehearsalState::RecoveringSeed => "Recovering master seed from backups".to_string(),
+            RehearsalState::DerivingKeys => "Re-deriving keys into scratch projection".to_string(),
+            RehearsalState::ComparingManifest => "Comparing derived keys with the manifest".to_string(),
+            RehearsalState::Reporting => format!(
+                "Reporting {} discrepancies",
+                self.scratch.discrepancies.len()
+            ),
+            RehearsalState::Completed => format!(
+                "Rehearsal completed: {} of {} keys restorable",
+                self.scratch.keys.len().saturating_sub(
+                    self.scratch.discrepancies.iter()
+                        .filter(|d| !matches!(d, RecoveryDiscrepancy::NotDerivable { .. }))
+                        .count()
+                ),
+                self.scratch.keys.len()
+            ),
+            RehearsalState::Failed => format!(
+                "Recovery rehearsal failed: {}",
+                self.error.as_ref().map_or("Unknown error", |e| &e.message)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::shamir::GroupSpec;
+    use crate::crypto::{derive_tls_keypair, DerivationPath, DerivationRecord, KeyFamily};
+
+    fn production_seed() -> MasterSeed {
+        MasterSeed::from_bytes([9u8; 32])
+    }
+
+    fn tls_record(seed: &MasterSeed, person: &str) -> DerivationRecord {
+        let path = DerivationPath::new(KeyFamily::Tls, "cowboyai", person, "server").unwrap();
+        DerivationRecord {
+            key_id: Uuid::now_v7(),
+            public_id: hex::encode(derive_tls_keypair(seed, &path).unwrap().public_key_bytes()),
+            path,
+            created_at: None,
+        }
+    }
+
+    fn request(expected: DerivationRegistry, manifest_key_ids: Vec<Uuid>) -> RehearsalRequest {
+        RehearsalRequest {
+            organization_id: Uuid::now_v7(),
+            source: RehearsalSource::Shares { split_id: Uuid::now_v7() },
+            expected,
+            manifest_key_ids,
+            performed_by: ActorId::system("dr-rehearsal"),
+        }
+    }
+
+    #[test]
+    fn test_rehearsal_reports_mismatches_and_underivable_keys() {
+        let seed = production_seed();
+        let good = tls_record(&seed, "web-1");
+        let mut tampered = tls_record(&seed, "web-2");
+        tampered.public_id = "00".repeat(32);
+        let imported = Uuid::now_v7();
+
+        let mut registry = DerivationRegistry::new();
+        registry.register(good.clone()).unwrap();
+        registry.register(tampered.clone()).unwrap();
+        let mut saga = RecoveryRehearsalSaga::new(request(registry, vec![good.key_id, tampered.key_id, imported]));
+
+        let shares: Vec<GroupShare> = seed
+            .split_groups(1, &[GroupSpec::new(2, 3)])
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .take(2)
+            .collect();
+
+        saga.start().unwrap();
+        let recovered = saga.recover_from_shares(&shares).unwrap();
+        saga.derive_keys(&recovered).unwrap();
+        let discrepancies = saga.compare_manifest().unwrap().to_vec();
+        let report = saga.report(Utc::now()).unwrap();
+
+        assert!(saga.is_completed());
+        assert_eq!(discrepancies.len(), 2);
+        assert!(discrepancies.iter().any(|d| matches!(d, RecoveryDiscrepancy::Mismatch { key_id, .. } if *key_id == tampered.key_id)));
+        assert!(discrepancies.contains(&RecoveryDiscrepancy::NotDerivable { key_id: imported }));
+        assert_eq!(report.keys_checked, 2);
+        assert_eq!(report.keys_matched, 1);
+        assert_eq!(report.share_ids.len(), 2);
+
+        // The persisted saga carries no secret material
+        let json = serde_json::to_string(&saga).unwrap();
+        assert!(!json.contains(&hex::encode(seed.as_bytes())));
+    }
+
+    #[test]
+    fn test_insufficient_shares_fail_the_rehearsal() {
+        let seed = production_seed();
+        let mut registry = DerivationRegistry::new();
+        registry.register(tls_record(&seed, "web-1")).unwrap();
+        let mut saga = RecoveryRehearsalSaga::new(request(registry, vec![]));
+
+        let shares: Vec<GroupShare> = seed
+            .split_groups(1, &[GroupSpec::new(2, 3)])
+            .unwrap()
+            .into_iter()
+            .flatten()
+            .take(1)
+            .collect();
+
+        saga.start().unwrap();
+        assert!(saga.recover_from_shares(&shares).is_err());
+        assert!(saga.is_failed());
+        assert!(saga.derive_keys(&seed).is_err());
+    }
+}
@@ -0,0 +1,435 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! SSH Key Rotation Saga
+//!
+//! Coordinates replacing an SSH key without locking its holder out:
+//! 1. Generate the new key
+//! 2. Publish authorized_keys containing both the old and the new key
+//! 3. Wait out the overlap window so every host picks up the new key
+//! 4. Publish authorized_keys containing only the new key
+//! 5. Revoke the old key
+//!
+//! Any failure rolls back to the old key: authorized_keys is restored to
+//! the old key alone and the new key is revoked.
+//!
+//! ## State Machine
+//!
+//! ```text
+//! Initial → GeneratingNewKey → PublishingOverlap → GracePeriod
+//!     ↓            ↓                  ↓                 ↓ (overlap elapsed)
+//!   Failed      Failed             Failed         PublishingFinal → RevokingOldKey → Completed
+//!                                                       ↓                ↓
+//!                                                    Failed           Failed
+//! ```
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, Duration, Utc};
+
+use super::{SagaState, SagaError};
+use crate::events::KeyAlgorithm;
+
+/// SSH Key Rotation Saga state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshKeyRotationSaga {
+    /// Unique saga ID
+    pub saga_id: Uuid,
+    /// Correlation ID for all events
+    pub correlation_id: Uuid,
+    /// Current state
+    pub state: SshRotationState,
+    /// State at which failure occurred (for compensation)
+    failed_at_state: Option<SshRotationState>,
+    /// Started at timestamp
+    pub started_at: DateTime<Utc>,
+    /// Completed at timestamp (if completed)
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Rotation request details
+    pub request: SshRotationRequest,
+    /// Generated artifacts
+    pub artifacts: SshRotationArtifacts,
+    /// Error if failed
+    pub error: Option<SagaError>,
+}
+
+/// Rotation state machine states
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SshRotationState {
+    /// Saga not started
+    Initial,
+    /// Generating the replacement key
+    GeneratingNewKey,
+    /// Publishing authorized_keys with old and new key
+    PublishingOverlap,
+    /// Both keys accepted until the overlap window ends
+    GracePeriod,
+    /// Publishing authorized_keys with the new key only
+    PublishingFinal,
+    /// Revoking the old key
+    RevokingOldKey,
+    /// Successfully completed
+    Completed,
+    /// Failed (see error field)
+    Failed,
+    /// Compensating (rolling back)
+    Compensating(SshRotationCompensationStep),
+}
+
+/// Compensation sub-steps
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SshRotationCompensationStep {
+    /// Publish authorized_keys with the old key only
+    RestoreAuthorizedKeys,
+    /// Revoke the new key
+    RevokeNewKey,
+}
+
+/// A key taking part in the rotation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SshRotationKey {
+    pub key_id: Uuid,
+    /// Public key in OpenSSH format
+    pub public_key: String,
+}
+
+/// Rotation request details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshRotationRequest {
+    /// Key being replaced
+    pub old_key: SshRotationKey,
+    /// Algorithm of the replacement key
+    pub new_key_algorithm: KeyAlgorithm,
+    /// How long both keys stay in authorized_keys
+    pub overlap_hours: u32,
+    /// Why the key is rotated (recorded on revocation)
+    pub reason: String,
+}
+
+/// Artifacts generated during rotation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SshRotationArtifacts {
+    /// Replacement key
+    pub new_key: Option<SshRotationKey>,
+    /// When the overlap window ends
+    pub grace_ends_at: Option<DateTime<Utc>>,
+    /// Whether the old key has been revoked
+    pub old_key_revoked: bool,
+}
+
+impl SshKeyRotationSaga {
+    /// Create a new SSH key rotation saga
+    pub fn new(request: SshRotationRequest) -> Self {
+        Self {
+            saga_id: Uuid::now_v7(),
+            correlation_id: Uuid::now_v7(),
+            state: SshRotationState::Initial,
+            failed_at_state: None,
+            started_at: Utc::now(),
+            completed_at: None,
+            request,
+            artifacts: SshRotationArtifacts::default(),
+            error: None,
+        }
+    }
+
+    /// Create with explicit correlation ID
+    pub fn with_correlation_id(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    /// Start the saga
+    pub fn start(&mut self) -> Result<(), SagaError> {
+        if self.request.old_key.public_key.trim().is_empty() {
+            return Err(SagaError::new("Old public key required", "Initial"));
+        }
+        if self.request.overlap_hours == 0 {
+            return Err(SagaError::new("Overlap window must be > 0 hours", "Initial"));
+        }
+        self.state = SshRotationState::GeneratingNewKey;
+        Ok(())
+    }
+
+    /// Transition to the next state
+    ///
+    /// The grace period is left only through `expire_grace_period`, so the
+    /// overlap window cannot be skipped.
+    pub fn advance(&mut self) -> SshRotationState {
+        self.state = match &self.state {
+            SshRotationState::Initial => SshRotationState::GeneratingNewKey,
+            SshRotationState::GeneratingNewKey => SshRotationState::PublishingOverlap,
+            SshRotationState::PublishingOverlap => SshRotationState::GracePeriod,
+            SshRotationState::GracePeriod => SshRotationState::GracePeriod,
+            SshRotationState::PublishingFinal => SshRotationState::RevokingOldKey,
+            SshRotationState::RevokingOldKey => {
+                self.completed_at = Some(Utc::now());
+                SshRotationState::Completed
+            }
+            SshRotationState::Completed => SshRotationState::Completed,
+            SshRotationState::Failed => SshRotationState::Failed,
+            SshRotationState::Compensating(_) => SshRotationState::Failed,
+        };
+        self.state.clone()
+    }
+
+    /// Record that the overlap authorized_keys was published at `now`
+    pub fn record_overlap_published(&mut self, now: DateTime<Utc>) {
+        self.artifacts.grace_ends_at =
+            Some(now + Duration::hours(i64::from(self.request.overlap_hours)));
+    }
+
+    /// Leave the grace period once the overlap window has elapsed
+    ///
+    /// Returns whether the saga moved on to `PublishingFinal`.
+    pub fn expire_grace_period(&mut self, now: DateTime<Utc>) -> bool {
+        let elapsed = self.state == SshRotationState::GracePeriod
+            && self.artifacts.grace_ends_at.is_some_and(|ends| now >= ends);
+        if elapsed {
+            self.state = SshRotationState::PublishingFinal;
+        }
+        elapsed
+    }
+
+    /// Mark the saga as failed
+    pub fn fail(&mut self, message: impl Into<String>, step: impl Into<String>) {
+        self.failed_at_state = Some(self.state.clone());
+        self.error = Some(SagaError::new(message, step));
+        self.state = SshRotationState::Failed;
+    }
+
+    /// Start compensation
+    pub fn start_compensation(&mut self) -> SshRotationCompensationStep {
+        // Use the state at which failure occurred, not the current (Failed) state
+        let failed_state = self.failed_at_state.as_ref().unwrap_or(&self.state);
+        let step = match failed_state {
+            SshRotationState::Initial | SshRotationState::GeneratingNewKey => {
+                SshRotationCompensationStep::RevokeNewKey
+            }
+            _ => SshRotationCompensationStep::RestoreAuthorizedKeys,
+        };
+        self.state = SshRotationState::Compensating(step.clone());
+        step
+    }
+
+    /// Advance compensation to next step
+    pub fn advance_compensation(&mut self) -> Option<SshRotationCompensationStep> {
+        if let SshRotationState::Compensating(current) = &self.state {
+            let next = match current {
+                SshRotationCompensationStep::RestoreAuthorizedKeys => {
+                    Some(SshRotationCompensationStep::RevokeNewKey)
+                }
+                SshRotationCompensationStep::RevokeNewKey => None,
+            };
+
+            if let Some(step) = next.clone() {
+                self.state = SshRotationState::Compensating(step);
+            } else {
+                self.state = SshRotationState::Failed;
+            }
+
+            next
+        } else {
+            None
+        }
+    }
+
+    /// Record generation of the replacement key
+    pub fn record_new_key(&mut self, key: SshRotationKey) {
+        self.artifacts.new_key = Some(key);
+    }
+
+    /// Record revocation of the old key
+    pub fn record_old_key_revoked(&mut self) {
+        self.artifacts.old_key_revoked = true;
+    }
+
+    /// Keys that belong in authorized_keys in the current state
+    ///
+    /// Old key until the overlap is published, both keys during the overlap
+    /// window, the new key afterwards. While compensating (and after a
+    /// failed rotation) only the old key is kept.
+    pub fn authorized_keys(&self) -> Vec<&SshRotationKey> {
+        let old = &self.request.old_key;
+        let new = self.artifacts.new_key.as_ref();
+        match (&self.state, new) {
+            (SshRotationState::PublishingOverlap | SshRotationState::GracePeriod, Some(new)) => {
+                vec![old, new]
+            }
+            (
+                SshRotationState::PublishingFinal
+                | SshRotationState::RevokingOldKey
+                | SshRotationState::Completed,
+                Some(new),
+            ) => vec![new],
+            _ => vec![old],
+        }
+    }
+
+    /// Get current step name for logging
+    pub fn current_step_name(&self) -> String {
+        match &self.state {
+            SshRotationState::Initial => "Initial".to_string(),
+            SshRotationState::GeneratingNewKey => "GeneratingNewKey".to_string(),
+            SshRotationState::PublishingOverlap => "PublishingOverlap".to_string(),
+            SshRotationState::GracePeriod => "GracePeriod".to_string(),
+            SshRotationState::PublishingFinal => "PublishingFinal".to_string(),
+            SshRotationState::RevokingOldKey => "RevokingOldKey".to_string(),
+            SshRotationState::Completed => "Completed".to_string(),
+            SshRotationState::Failed => "Failed".to_string(),
+            SshRotationState::Compensating(step) => format!("Compensating:{:?}", step),
+        }
+    }
+}
+
+impl SagaState for SshKeyRotationSaga {
+    fn saga_id(&self) -> Uuid {
+        self.saga_id
+    }
+
+    fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    fn is_terminal(&self) -> bool {
+        matches!(
+            self.state,
+            SshRotationState::Completed | SshRotationState::Failed
+        )
+    }
+
+    fn is_completed(&self) -> bool {
+        matches!(self.state, SshRotationState::Completed)
+    }
+
+    fn is_failed(&self) -> bool {
+        matches!(self.state, SshRotationState::Failed)
+    }
+
+    fn status_description(&self) -> String {
+        match &self.state {
+            SshRotationState::Initial => "Not started".to_string(),
+            SshRotationState::GeneratingNewKey => format!(
+                "Generating {:?} key to replace {}",
+                self.request.new_key_algorithm, self.request.old_key.key_id
+            ),
+            SshRotationState::PublishingOverlap => {
+                "Publishing authorized_keys with old and new key".to_string()
+            }
+            SshRotationState::GracePeriod => match self.artifacts.grace_ends_at {
+                Some(ends) => format!("Both keys accepted until {}", ends),
+                None => "Both keys accepted".to_string(),
+            },
+            SshRotationState::PublishingFinal => {
+                "Publishing authorized_keys with the new key only".to_string()
+            }
+            SshRotationState::RevokingOldKey => {
+                format!("Revoking old key {}", self.request.old_key.key_id)
+            }
+            SshRotationState::Completed => format!(
+                "Rotated SSH key {} ({})",
+                self.request.old_key.key_id, self.request.reason
+            ),
+            SshRotationState::Failed => format!(
+                "SSH key rotation failed: {}",
+                self.error.as_ref().map_or("Unknown error", |e| &e.message)
+            ),
+            SshRotationState::Compensating(step) => format!("Rolling back: {:?}", step),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(public_key: &str) -> SshRotationKey {
+        SshRotationKey {
+            key_id: Uuid::now_v7(),
+            public_key: public_key.to_string(),
+        }
+    }
+
+    fn create_test_request() -> SshRotationRequest {
+        SshRotationRequest {
+            old_key: key("ssh-ed25519 AAAAold alice"),
+            new_key_algorithm: KeyAlgorithm::Ed25519,
+            overlap_hours: 24,
+            reason: "Scheduled rotation".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_saga_start_validation() {
+        let mut request = create_test_request();
+        request.overlap_hours = 0;
+        assert!(SshKeyRotationSaga::new(request).start().is_err());
+
+        let mut saga = SshKeyRotationSaga::new(create_test_request());
+        assert!(saga.start().is_ok());
+        assert_eq!(saga.state, SshRotationState::GeneratingNewKey);
+    }
+
+    #[test]
+    fn test_full_rotation_keeps_both_keys_during_overlap() {
+        let request = create_test_request();
+        let old = request.old_key.clone();
+        let new = key("ssh-ed25519 AAAAnew alice");
+        let mut saga = SshKeyRotationSaga::new(request);
+        let now = Utc::now();
+
+        saga.start().unwrap();
+        saga.record_new_key(new.clone());
+        assert_eq!(saga.authorized_keys(), vec![&old]);
+
+        saga.advance(); // PublishingOverlap
+        assert_eq!(saga.authorized_keys(), vec![&old, &new]);
+        saga.record_overlap_published(now);
+
+        // The overlap window cannot be skipped
+        assert_eq!(saga.advance(), SshRotationState::GracePeriod);
+        assert_eq!(saga.advance(), SshRotationState::GracePeriod);
+        assert!(!saga.expire_grace_period(now + Duration::hours(23)));
+        assert_eq!(saga.authorized_keys(), vec![&old, &new]);
+
+        assert!(saga.expire_grace_period(now + Duration::hours(24)));
+        assert_eq!(saga.state, SshRotationState::PublishingFinal);
+        assert_eq!(saga.authorized_keys(), vec![&new]);
+
+        saga.advance(); // RevokingOldKey
+        saga.record_old_key_revoked();
+        saga.advance();
+        assert!(saga.is_completed());
+        assert!(saga.artifacts.old_key_revoked);
+    }
+
+    #[test]
+    fn test_failure_during_overlap_restores_old_key() {
+        let request = create_test_request();
+        let old = request.old_key.clone();
+        let mut saga = SshKeyRotationSaga::new(request);
+
+        saga.start().unwrap();
+        saga.record_new_key(key("ssh-ed25519 AAAAnew alice"));
+        saga.advance(); // PublishingOverlap
+        saga.advance(); // GracePeriod
+
+        saga.fail("Host web1 rejected authorized_keys", "GracePeriod");
+        assert!(saga.is_failed());
+
+        assert_eq!(saga.start_compensation(), SshRotationCompensationStep::RestoreAuthorizedKeys);
+        assert_eq!(saga.authorized_keys(), vec![&old]);
+        assert_eq!(saga.advance_compensation(), Some(SshRotationCompensationStep::RevokeNewKey));
+        assert_eq!(saga.advance_compensation(), None);
+        assert!(saga.is_failed());
+    }
+
+    #[test]
+    fn test_failure_generating_key_only_revokes_new_key() {
+        let mut saga = SshKeyRotationSaga::new(create_test_request());
+        saga.start().unwrap();
+
+        saga.fail("Key generation failed", "GeneratingNewKey");
+        assert_eq!(saga.start_compensation(), SshRotationCompensationStep::RevokeNewKey);
+        assert_eq!(saga.advance_compensation(), None);
+    }
+}
@@ -0,0 +1,397 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Step Deadlines and Heartbeats
+//!
+//! Steps that wait on a person (insert the YubiKey, approve the request)
+//! must not hang a ceremony forever. [`SagaStepMonitor`] watches the
+//! current step of each running saga:
+//!
+//! ```text
+//! watch(saga)        → Running, deadline = now + timeout for the step
+//! heartbeat(...)     → StepHeartbeat, step proven alive
+//! tick(now)          → StepStalled when the deadline passed or
+//!                      `missed_heartbeats` intervals went by silently
+//! extend_deadline()  → Running again, SagaRecovered
+//! ```
+//!
+//! Timeouts are configured per saga type and per step; steps named
+//! `Step:detail` (e.g. `GeneratingPKI:GeneratingRootCA`) fall back to the
+//! timeout of `Step`. Stalls are announced to the registered
+//! [`StallNotifier`]s, e.g. to page the ceremony officer.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use super::PersistentSaga;
+use crate::events::saga::{
+    RecoveryAction, SagaRecoveredEvent, StallReason, StepHeartbeatEvent, StepStalledEvent,
+};
+use crate::events::SagaEvents;
+
+/// Deadlines and heartbeat expectations for the steps of one saga type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepTimeouts {
+    /// Deadline of steps without their own
+    pub default_timeout: Duration,
+    /// How often a waiting step is expected to send a heartbeat
+    pub heartbeat_interval: Duration,
+    /// Silent intervals after which a step counts as stalled
+    pub missed_heartbeats: u32,
+    /// Deadlines by step name
+    pub steps: HashMap<String, Duration>,
+}
+
+impl Default for StepTimeouts {
+    fn default() -> Self {
+        Self {
+            default_timeout: Duration::hours(1),
+            heartbeat_interval: Duration::minutes(5),
+            missed_heartbeats: 3,
+            steps: HashMap::new(),
+        }
+    }
+}
+
+impl StepTimeouts {
+    pub fn new(default_timeout: Duration) -> Self {
+        Self {
+            default_timeout,
+            ..Self::default()
+        }
+    }
+
+    /// Builder: deadline for one step
+    pub fn with_step(mut self, step: impl Into<String>, timeout: Duration) -> Self {
+        self.steps.insert(step.into(), timeout);
+        self
+    }
+
+    /// Builder: expected heartbeat interval
+    pub fn with_heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.heartbeat_interval = interval;
+        self
+    }
+
+    /// Builder: silent intervals tolerated before a step stalls
+    pub fn with_missed_heartbeats(mut self, missed: u32) -> Self {
+        self.missed_heartbeats = missed;
+        self
+    }
+
+    /// Deadline for a step, falling back from `Step:detail` to `Step`
+    pub fn timeout_for(&self, step: &str) -> Duration {
+        self.steps
+            .get(step)
+            .or_else(|| step.split_once(':').and_then(|(base, _)| self.steps.get(base)))
+            .copied()
+            .unwrap_or(self.default_timeout)
+    }
+
+    /// How long a step may stay silent
+    pub fn heartbeat_timeout(&self) -> Duration {
+        self.heartbeat_interval * self.missed_heartbeats as i32
+    }
+}
+
+/// Step timeouts by saga type
+#[derive(Debug, Clone, Default)]
+pub struct SagaTimeoutConfig {
+    /// Timeouts of saga types without their own
+    pub default: StepTimeouts,
+    /// Timeouts by `PersistentSaga::SAGA_TYPE`
+    pub by_saga_type: HashMap<String, StepTimeouts>,
+}
+
+impl SagaTimeoutConfig {
+    /// Builder: timeouts for one saga type
+    pub fn with_saga_type(mut self, saga_type: impl Into<String>, timeouts: StepTimeouts) -> Self {
+        self.by_saga_type.insert(saga_type.into(), timeouts);
+        self
+    }
+
+    pub fn timeouts_for(&self, saga_type: &str) -> &StepTimeouts {
+        self.by_saga_type.get(saga_type).unwrap_or(&self.default)
+    }
+}
+
+/// Whether a watched step is making progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+    Running,
+    Stalled { since: DateTime<Utc>, reason: StallReason },
+}
+
+/// The step a saga is currently on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepWatch {
+    pub saga_id: Uuid,
+    pub saga_type: String,
+    pub correlation_id: Uuid,
+    pub step_name: String,
+    pub started_at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+    pub status: StepStatus,
+}
+
+impl StepWatch {
+    /// Why the step should be considered stalled at `now`, if it should
+    fn stall_reason(&self, now: DateTime<Utc>, heartbeat_timeout: Duration) -> Option<StallReason> {
+        if now >= self.deadline {
+            return Some(StallReason::DeadlineExceeded);
+        }
+        let last_sign_of_life = self.last_heartbeat_at.unwrap_or(self.started_at);
+        (now - last_sign_of_life >= heartbeat_timeout).then_some(StallReason::HeartbeatsMissed)
+    }
+}
+
+/// Receives stalled steps, e.g. to alert an operator
+pub trait StallNotifier: Send + Sync {
+    fn step_stalled(&self, event: &StepStalledEvent);
+}
+
+impl<F> StallNotifier for F
+where
+    F: Fn(&StepStalledEvent) + Send + Sync,
+{
+    fn step_stalled(&self, event: &StepStalledEvent) {
+        self(event)
+    }
+}
+
+/// Watches the current step of running sagas for stalls
+#[derive(Default)]
+pub struct SagaStepMonitor {
+    config: SagaTimeoutConfig,
+    watches: HashMap<Uuid, StepWatch>,
+    notifiers: Vec<Box<dyn StallNotifier>>,
+}
+
+impl SagaStepMonitor {
+    pub fn new(config: SagaTimeoutConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Builder: notify `notifier` of every stall
+    pub fn with_notifier(mut self, notifier: impl StallNotifier + 'static) -> Self {
+        self.notifiers.push(Box::new(notifier));
+        self
+    }
+
+    /// Start watching the step the saga is on, replacing any earlier watch
+    ///
+    /// Terminal sagas are no longer watched.
+    pub fn watch<S: PersistentSaga>(&mut self, saga: &S, now: DateTime<Utc>) {
+        if saga.is_terminal() {
+            self.watches.remove(&saga.saga_id());
+            return;
+        }
+        let step_name = saga.current_step();
+        let timeout = self.config.timeouts_for(S::SAGA_TYPE).timeout_for(&step_name);
+        self.watches.insert(saga.saga_id(), StepWatch {
+            saga_id: saga.saga_id(),
+            saga_type: S::SAGA_TYPE.to_string(),
+            correlation_id: saga.correlation_id(),
+            step_name,
+            started_at: now,
+            deadline: now + timeout,
+            last_heartbeat_at: None,
+            status: StepStatus::Running,
+        });
+    }
+
+    /// Stop watching a saga, e.g. once its step completed
+    pub fn unwatch(&mut self, saga_id: Uuid) -> Option<StepWatch> {
+        self.watches.remove(&saga_id)
+    }
+
+    /// Record that the saga's step is still alive
+    ///
+    /// A step stalled only for missing heartbeats runs again; one past its
+    /// deadline stays stalled until the deadline is extended.
+    pub fn heartbeat(
+        &mut self,
+        saga_id: Uuid,
+        now: DateTime<Utc>,
+        detail: Option<String>,
+    ) -> Option<SagaEvents> {
+        let watch = self.watches.get_mut(&saga_id)?;
+        watch.last_heartbeat_at = Some(now);
+        if matches!(watch.status, StepStatus::Stalled { reason: StallReason::HeartbeatsMissed, .. })
+            && now < watch.deadline
+        {
+            watch.status = StepStatus::Running;
+        }
+
+        Some(SagaEvents::StepHeartbeat(StepHeartbeatEvent {
+            saga_id,
+            step_name: watch.step_name.clone(),
+            correlation_id: watch.correlation_id,
+            heartbeat_at: now,
+            deadline: watch.deadline,
+            detail,
+        }))
+    }
+
+    /// Give a step more time, reviving it if it stalled
+    ///
+    /// Returns `SagaRecovered` when the step had stalled.
+    pub fn extend_deadline(
+        &mut self,
+        saga_id: Uuid,
+        additional: Duration,
+        now: DateTime<Utc>,
+    ) -> Option<SagaEvents> {
+        let watch = self.watches.get_mut(&saga_id)?;
+        watch.deadline = watch.deadline.max(now) + additional;
+        watch.last_heartbeat_at = Some(now);
+
+        let StepStatus::Stalled { since, .. } = watch.status else {
+            return None;
+        };
+        watch.status = StepStatus::Running;
+        Some(SagaEvents::SagaRecovered(SagaRecoveredEvent {
+            saga_id,
+            saga_type: watch.saga_type.clone(),
+            correlation_id: watch.correlation_id,
+            recovered_at: now,
+            stalled_duration_ms: (now - since).num_milliseconds().max(0) as u64,
+            recovery_action: RecoveryAction::Resumed,
+        }))
+    }
+
+    /// Detect steps that stalled since the last tick
+    ///
+    /// Each stall is reported once, as an event and to every notifier.
+    pub fn tick(&mut self, now: DateTime<Utc>) -> Vec<SagaEvents> {
+        let mut stalled = Vec::new();
+        for watch in self.watches.values_mut() {
+            if watch.status != StepStatus::Running {
+                continue;
+            }
+            let heartbeat_timeout = self.config.timeouts_for(&watch.saga_type).heartbeat_timeout();
+            let Some(reason) = watch.stall_reason(now, heartbeat_timeout) else { continue };
+
+            watch.status = StepStatus::Stalled { since: now, reason };
+            stalled.push(StepStalledEvent {
+                saga_id: watch.saga_id,
+                saga_type: watch.saga_type.clone(),
+                step_name: watch.step_name.clone(),
+                correlation_id: watch.correlation_id,
+                stalled_at: now,
+                step_started_at: watch.started_at,
+                deadline: watch.deadline,
+                last_heartbeat_at: watch.last_heartbeat_at,
+                reason,
+            });
+        }
+
+        for event in &stalled {
+            for notifier in &self.notifiers {
+                notifier.step_stalled(event);
+            }
+        }
+        stalled.into_iter().map(SagaEvents::StepStalled).collect()
+    }
+
+    /// The watched step of a saga
+    pub fn watch_of(&self, saga_id: Uuid) -> Option<&StepWatch> {
+        self.watches.get(&saga_id)
+    }
+
+    /// Steps currently stalled
+    pub fn stalled(&self) -> impl Iterator<Item = &StepWatch> {
+        self.watches
+            .values()
+            .filter(|w| matches!(w.status, StepStatus::Stalled { .. }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use crate::domain::sagas::{SshKeyRotationSaga, SshRotationKey, SshRotationRequest};
+    use crate::events::KeyAlgorithm;
+
+    fn rotation_saga() -> SshKeyRotationSaga {
+        let mut saga = SshKeyRotationSaga::new(SshRotationRequest {
+            old_key: SshRotationKey {
+                key_id: Uuid::now_v7(),
+                public_key: "ssh-ed25519 AAAAold alice".to_string(),
+            },
+            new_key_algorithm: KeyAlgorithm::Ed25519,
+            overlap_hours: 24,
+            reason: "Scheduled rotation".to_string(),
+        });
+        saga.start().unwrap();
+        saga
+    }
+
+    fn config() -> SagaTimeoutConfig {
+        SagaTimeoutConfig::default().with_saga_type(
+            "SshKeyRotation",
+            StepTimeouts::new(Duration::hours(2))
+                .with_step("GeneratingNewKey", Duration::minutes(30))
+                .with_heartbeat_interval(Duration::minutes(5))
+                .with_missed_heartbeats(2),
+        )
+    }
+
+    #[test]
+    fn test_step_past_deadline_stalls_once_and_notifies() {
+        let alerts = Arc::new(Mutex::new(Vec::new()));
+        let sink = alerts.clone();
+        let mut monitor = SagaStepMonitor::new(config())
+            .with_notifier(move |e: &StepStalledEvent| sink.lock().unwrap().push(e.step_name.clone()));
+        let saga = rotation_saga();
+        let now = Utc::now();
+        monitor.watch(&saga, now);
+
+        // Heartbeats keep the step alive until its 30 minute deadline
+        for minutes in [4, 8, 12, 16, 20, 24, 28] {
+            monitor.heartbeat(saga.saga_id, now + Duration::minutes(minutes), None);
+            assert!(monitor.tick(now + Duration::minutes(minutes)).is_empty());
+        }
+
+        let events = monitor.tick(now + Duration::minutes(30));
+        assert!(matches!(
+            events.as_slice(),
+            [SagaEvents::StepStalled(StepStalledEvent { reason: StallReason::DeadlineExceeded, .. })]
+        ));
+        assert!(monitor.tick(now + Duration::minutes(31)).is_empty());
+        assert_eq!(*alerts.lock().unwrap(), vec!["GeneratingNewKey".to_string()]);
+
+        // A heartbeat does not revive a step past its deadline; more time does
+        monitor.heartbeat(saga.saga_id, now + Duration::minutes(32), None);
+        assert_eq!(monitor.stalled().count(), 1);
+        let recovered = monitor.extend_deadline(saga.saga_id, Duration::minutes(15), now + Duration::minutes(40));
+        assert!(matches!(recovered, Some(SagaEvents::SagaRecovered(_))));
+        assert_eq!(monitor.stalled().count(), 0);
+    }
+
+    #[test]
+    fn test_silent_step_stalls_and_heartbeat_revives_it() {
+        let mut monitor = SagaStepMonitor::new(config());
+        let saga = rotation_saga();
+        let now = Utc::now();
+        monitor.watch(&saga, now);
+
+        assert!(monitor.tick(now + Duration::minutes(9)).is_empty());
+        let events = monitor.tick(now + Duration::minutes(10));
+        assert!(matches!(
+            events.as_slice(),
+            [SagaEvents::StepStalled(StepStalledEvent { reason: StallReason::HeartbeatsMissed, .. })]
+        ));
+
+        let heartbeat = monitor.heartbeat(saga.saga_id, now + Duration::minutes(11), Some("waiting for YubiKey".into()));
+        assert!(matches!(heartbeat, Some(SagaEvents::StepHeartbeat(_))));
+        assert_eq!(monitor.watch_of(saga.saga_id).unwrap().status, StepStatus::Running);
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Separation of Duties Between Claims
+//!
+//! A [`SeparationOfDutiesRule`] names two claims no single person or entity
+//! may hold at once, e.g. `CanSignCertificates` and `CanPerformAudits`:
+//! whoever issues certificates must not also audit the issuance.
+//!
+//! Rules are checked when a policy is bound or a role is assigned, against
+//! the claims the entity already holds plus the claims the assignment adds.
+//! Conditions are ignored: a claim held only under MFA is still held.
+//! A violating assignment is refused with a `SeparationOfDutiesViolated`
+//! event instead of being carried out.
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::PolicyClaim;
+
+/// Two claims that must not be held by the same entity
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeparationOfDutiesRule {
+    pub id: Uuid,
+    pub name: String,
+    pub claim_a: PolicyClaim,
+    pub claim_b: PolicyClaim,
+    /// Why the claims conflict, for the compliance report
+    pub reason: Option<String>,
+}
+
+impl SeparationOfDutiesRule {
+    pub fn new(name: impl Into<String>, claim_a: PolicyClaim, claim_b: PolicyClaim) -> Self {
+        Self {
+            id: Uuid::now_v7(),
+            name: name.into(),
+            claim_a,
+            claim_b,
+            reason: None,
+        }
+    }
+
+    /// Builder: explain the conflict
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Whether holding `claims` breaks this rule
+    pub fn is_violated_by(&self, claims: &[PolicyClaim]) -> bool {
+        claims.contains(&self.claim_a) && claims.contains(&self.claim_b)
+    }
+}
+
+/// Rules an assignment would break
+///
+/// Only conflicts the assignment introduces count: a rule both claims of
+/// which are already held is not blamed on adding `added`.
+pub fn separation_violations<'a>(
+    rules: &'a [SeparationOfDutiesRule],
+    held: &[PolicyClaim],
+    added: &[PolicyClaim],
+) -> Vec<&'a SeparationOfDutiesRule> {
+    let combined: Vec<PolicyClaim> = held.iter().chain(added).cloned().collect();
+    rules
+        .iter()
+        .filter(|rule| rule.is_violated_by(&combined) && !rule.is_violated_by(held))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issuer_auditor() -> SeparationOfDutiesRule {
+        SeparationOfDutiesRule::new("Issuer/auditor", PolicyClaim::CanSignCertificates, PolicyClaim::CanPerformAudits)
+    }
+
+    #[test]
+    fn test_assignment_adding_conflicting_claim_violates() {
+        let rules = vec![issuer_auditor()];
+
+        let violations = separation_violations(
+            &rules,
+            &[PolicyClaim::CanSignCertificates],
+            &[PolicyClaim::CanPerformAudits, PolicyClaim::CanViewAuditLogs],
+        );
+
+        assert_eq!(violations, vec![&rules[0]]);
+        assert!(separation_violations(&rules, &[PolicyClaim::CanSignCertificates], &[PolicyClaim::CanViewAuditLogs]).is_empty());
+    }
+
+    #[test]
+    fn test_existing_conflict_is_not_blamed_on_assignment() {
+        let rules = vec![issuer_auditor()];
+        let held = [PolicyClaim::CanSignCertificates, PolicyClaim::CanPerformAudits];
+
+        assert!(separation_violations(&rules, &held, &[PolicyClaim::CanPerformAudits]).is_empty());
+    }
+}
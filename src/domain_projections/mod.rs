@@ -12,11 +12,13 @@
 // - certificate: Domain → CSR → X509 params
 // - yubikey: Domain → PIV provisioning params
 // - nats: Domain → JWT claims / NATS config
+// - nats_permissions: Org structure → NATS subject permissions
 // - ssi: Domain → DID documents / Verifiable Credentials
 
 pub mod certificate;
 pub mod yubikey;
 pub mod nats;
+pub mod nats_permissions;
 pub mod ssi;
 
 // Re-export key types
@@ -39,8 +41,12 @@ pub use nats::{
     NKeyGenerationParams,
     NKeyProjection,
     OrganizationBootstrap,
+    MONITORING_USER_NAME,
+    SYSTEM_ACCOUNT_NAME,
 };
 
+pub use nats_permissions::SubjectPermissionProjection;
+
 pub use ssi::{
     DidDocumentProjection,
     VerifiableCredentialProjection,
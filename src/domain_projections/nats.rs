@@ -7,6 +7,8 @@
 //   Organization → NKey (Operator) → OperatorClaims → JWT
 //   OrganizationUnit → NKey (Account) → AccountClaims → JWT (signed by Operator)
 //   Person → NKey (User) → UserClaims → JWT (signed by Account)
+//   SYS → NKey (Account) → JWT (signed by Operator, named in the Operator JWT)
+//   Location → NKey (User) → UserClaims → JWT (leafnode only, signed by Account)
 //
 // Each projection step emits events for audit trail.
 
@@ -14,10 +16,12 @@ use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
 use uuid::Uuid;
 
+use crate::domain::nats::{ClaimData, ClaimPermissions, NatsClaims, CONNECTION_TYPE_LEAFNODE};
 use crate::domain::{
     AccountIdentity, Organization, OrganizationUnit, Person, ServiceAccount, UserIdentity,
     ids::UnitId,
 };
+use super::nats_permissions::SubjectPermissionProjection;
 use crate::events::nats_operator::{NKeyGeneratedEvent, JwtClaimsCreatedEvent, JwtSignedEvent};
 use crate::value_objects::{
     AccountClaims, AccountData, AccountLimits, NatsCredential, NatsJwt, NKeyPair, NKeyPublic,
@@ -25,6 +29,12 @@ use crate::value_objects::{
     UserLimits,
 };
 
+/// Name of the system account, as nats-server and nsc expect it
+pub const SYSTEM_ACCOUNT_NAME: &str = "SYS";
+
+/// Name of the system account's monitoring user
+pub const MONITORING_USER_NAME: &str = "sys-monitor";
+
 // ============================================================================
 // NKeys Integration
 // ============================================================================
@@ -194,32 +204,14 @@ impl NKeyProjection {
         let nkey_id = Uuid::now_v7();
         let generated_at = Utc::now();
 
-        // Generate real Ed25519 key pair using nkeys crate
-        let kp = nkeys::KeyPair::new(params.key_type.into());
-
-        // Extract seed and public key
-        let seed_string = kp.seed().expect("Failed to extract seed from KeyPair");
-        let public_key_string = kp.public_key();
-
-        // Wrap in our domain value objects
-        let seed = NKeySeed::new(
+        // Ed25519 from the OS RNG; nkeys adds the O/A/U prefix and checksum
+        let mut nkey = NKeyPair::generate_with(
             params.key_type,
-            seed_string.clone(), // Clone here so we can use seed_string in the event
-            generated_at,
-        );
-
-        let public_key = NKeyPublic::new(
-            params.key_type,
-            public_key_string.clone(),
-        );
-
-        // Create NKeyPair with optional expiration
-        let mut nkey = NKeyPair::new(
-            params.key_type,
-            seed,
-            public_key,
-            Some(params.name.clone())
-        );
+            Some(params.name.clone()),
+            &crate::ports::rng::OsCryptoRng,
+        )
+        .expect("Failed to generate NKey pair");
+        let public_key_string = nkey.public_key_string().to_string();
 
         let expires_at = if let Some(days) = params.expires_after_days {
             let expiration = generated_at + Duration::days(days);
@@ -234,7 +226,6 @@ impl NKeyProjection {
             nkey_id,
             key_type: format!("{:?}", params.key_type),
             public_key: public_key_string,
-            seed: seed_string,
             purpose: params.description.clone().unwrap_or_else(|| params.name.clone()),
             expires_at,
             generated_at,
@@ -998,6 +989,245 @@ impl NatsProjection {
         }
     }
 
+    /// System account: SYS NKey + JWT signed by the operator
+    ///
+    /// nats-server publishes server events and answers monitoring requests
+    /// in the system account, and resolvers receive account updates there.
+    ///
+    /// Emits:
+    /// - AccountNKeyGeneratedEvent
+    /// - AccountJwtClaimsCreatedEvent
+    /// - AccountJwtSignedEvent
+    pub fn project_system_account(
+        operator_nkey: &NKeyPair,
+        correlation_id: Uuid,
+        causation_id: Option<Uuid>,
+    ) -> Result<NatsIdentityProjection, String> {
+        let params = NKeyGenerationParams {
+            key_type: NKeyType::Account,
+            name: SYSTEM_ACCOUNT_NAME.to_string(),
+            description: Some("NATS system account".to_string()),
+            expires_after_days: None,
+        };
+        let (nkey, nkey_event) = NKeyProjection::generate_nkey(&params, correlation_id, causation_id);
+
+        let claims = NatsClaims::account(
+            SYSTEM_ACCOUNT_NAME,
+            nkey.public_key_string(),
+            operator_nkey.public_key_string(),
+        );
+        let (jwt, mut events) = Self::sign_claims(
+            NKeyType::Account,
+            claims,
+            operator_nkey,
+            "Account: system".to_string(),
+            correlation_id,
+        )?;
+        events.insert(0, crate::events::DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NKeyGenerated(nkey_event)));
+
+        Ok(NatsIdentityProjection {
+            nkey,
+            jwt,
+            credential: None,
+            events,
+        })
+    }
+
+    /// Monitoring user of the system account (NKey + JWT + Credential)
+    ///
+    /// Subscribes to `$SYS.>` and may only ping servers; see
+    /// [`SubjectPermissionProjection::project_system_monitoring_permissions`].
+    ///
+    /// Emits:
+    /// - UserNKeyGeneratedEvent
+    /// - UserJwtClaimsCreatedEvent
+    /// - UserJwtSignedEvent
+    pub fn project_monitoring_user(
+        system_account_nkey: &NKeyPair,
+        correlation_id: Uuid,
+        causation_id: Option<Uuid>,
+    ) -> Result<NatsIdentityProjection, String> {
+        let params = NKeyGenerationParams {
+            key_type: NKeyType::User,
+            name: MONITORING_USER_NAME.to_string(),
+            description: Some("NATS system account monitoring user".to_string()),
+            expires_after_days: None,
+        };
+        let (nkey, nkey_event) = NKeyProjection::generate_nkey(&params, correlation_id, causation_id);
+
+        let permissions = SubjectPermissionProjection::project_system_monitoring_permissions();
+        let claims = NatsClaims::user(
+            MONITORING_USER_NAME,
+            nkey.public_key_string(),
+            system_account_nkey.public_key_string(),
+        )
+        .with_permissions(Self::claim_permissions(&permissions));
+        let (jwt, mut events) = Self::sign_claims(
+            NKeyType::User,
+            claims,
+            system_account_nkey,
+            format!("User: {} | {}", MONITORING_USER_NAME, serde_json::to_string(&permissions).unwrap_or_default()),
+            correlation_id,
+        )?;
+        events.insert(0, crate::events::DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NKeyGenerated(nkey_event)));
+
+        let credential = NatsCredential::new(jwt.clone(), nkey.seed.clone(), Some(MONITORING_USER_NAME.to_string()));
+
+        Ok(NatsIdentityProjection {
+            nkey,
+            jwt,
+            credential: Some(credential),
+            events,
+        })
+    }
+
+    /// Leafnode user of an edge site, signed by the account it extends
+    ///
+    /// The JWT only allows `LEAFNODE` connections, so the credentials are
+    /// useless to ordinary clients if they leak off the site.
+    pub fn project_leafnode_user(
+        account_nkey: &NKeyPair,
+        name: &str,
+        permissions: &Permissions,
+        correlation_id: Uuid,
+        causation_id: Option<Uuid>,
+    ) -> Result<NatsIdentityProjection, String> {
+        let params = NKeyGenerationParams {
+            key_type: NKeyType::User,
+            name: name.to_string(),
+            description: Some(format!("Leafnode user for {}", name)),
+            expires_after_days: None,
+        };
+        let (nkey, nkey_event) = NKeyProjection::generate_nkey(&params, correlation_id, causation_id);
+
+        let claims = NatsClaims::user(name, nkey.public_key_string(), account_nkey.public_key_string())
+            .with_permissions(Self::claim_permissions(permissions))
+            .with_allowed_connection_types([CONNECTION_TYPE_LEAFNODE]);
+        let (jwt, mut events) = Self::sign_claims(
+            NKeyType::User,
+            claims,
+            account_nkey,
+            format!("Leafnode: {} | {}", name, serde_json::to_string(permissions).unwrap_or_default()),
+            correlation_id,
+        )?;
+        events.insert(0, crate::events::DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NKeyGenerated(nkey_event)));
+
+        let credential = NatsCredential::new(jwt.clone(), nkey.seed.clone(), Some(name.to_string()));
+
+        Ok(NatsIdentityProjection {
+            nkey,
+            jwt,
+            credential: Some(credential),
+            events,
+        })
+    }
+
+    /// Operator JWT naming the system account, re-signed by the operator
+    ///
+    /// The operator JWT has to exist before the system account can be
+    /// signed, so the system account is designated by re-issuing it.
+    ///
+    /// Emits:
+    /// - OperatorJwtClaimsCreatedEvent
+    /// - OperatorJwtSignedEvent
+    pub fn project_operator_system_account(
+        organization: &Organization,
+        operator_nkey: &NKeyPair,
+        system_account_nkey: &NKeyPair,
+        correlation_id: Uuid,
+    ) -> Result<NatsIdentityProjection, String> {
+        let mut claims = NatsClaims::operator(&organization.name, operator_nkey.public_key_string())
+            .with_system_account(system_account_nkey.public_key_string());
+        if let Some(url) = organization.metadata.get("nats_account_server") {
+            claims = claims.with_account_server_url(url);
+        }
+        let service_urls: Vec<String> = organization
+            .metadata
+            .get("nats_service_urls")
+            .and_then(|urls| serde_json::from_str(urls).ok())
+            .unwrap_or_default();
+        for url in service_urls {
+            claims = claims.with_operator_service_url(url);
+        }
+
+        let (jwt, events) = Self::sign_claims(
+            NKeyType::Operator,
+            claims,
+            operator_nkey,
+            format!("Operator: system_account={}", system_account_nkey.public_key_string()),
+            correlation_id,
+        )?;
+
+        Ok(NatsIdentityProjection {
+            nkey: operator_nkey.clone(),
+            jwt,
+            credential: None,
+            events,
+        })
+    }
+
+    /// Claim permissions from allow lists
+    fn claim_permissions(permissions: &Permissions) -> ClaimPermissions {
+        let mut claim_permissions = ClaimPermissions::default();
+        for subject in permissions.pub_allow.iter().flatten() {
+            claim_permissions = claim_permissions.allow_publish(subject);
+        }
+        for subject in permissions.sub_allow.iter().flatten() {
+            claim_permissions = claim_permissions.allow_subscribe(subject);
+        }
+        claim_permissions
+    }
+
+    /// Encode claims with `signer` and record the claims and signature events
+    fn sign_claims<T: ClaimData>(
+        jwt_type: NKeyType,
+        claims: NatsClaims<T>,
+        signer: &NKeyPair,
+        permissions: String,
+        correlation_id: Uuid,
+    ) -> Result<(NatsJwt, Vec<crate::events::DomainEvent>), String> {
+        let subject = claims.sub.clone();
+        let token = claims.encode(signer).map_err(|e| e.to_string())?;
+        let signed_at = Utc::now();
+        let claims_id = Uuid::now_v7();
+
+        let events = vec![
+            crate::events::DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::JwtClaimsCreated(JwtClaimsCreatedEvent {
+                claims_id,
+                issuer: signer.public_key_string().to_string(),
+                subject: subject.clone(),
+                audience: None,
+                permissions,
+                not_before: signed_at,
+                expires_at: None,
+                correlation_id,
+                causation_id: Some(correlation_id),
+            })),
+            crate::events::DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::JwtSigned(JwtSignedEvent {
+                jwt_id: Uuid::now_v7(),
+                claims_id,
+                signed_by: signer.id,
+                signer_public_key: signer.public_key_string().to_string(),
+                jwt_token: token.clone(),
+                signature_algorithm: crate::domain::nats::claims::NKEY_JWT_ALGORITHM.to_string(),
+                signature_verification_data: None,
+                signed_at,
+                correlation_id,
+                causation_id: Some(claims_id),
+            })),
+        ];
+
+        let jwt = NatsJwt::new(
+            jwt_type,
+            token,
+            signer.public_key.clone(),
+            NKeyPublic::new(jwt_type, subject),
+            signed_at,
+            None,
+        );
+        Ok((jwt, events))
+    }
+
     /// **Organization Bootstrap Projection**
     ///
     /// Complete functor mapping from Organization domain to full NATS infrastructure.
@@ -1109,11 +1339,13 @@ impl NatsProjection {
                 &accounts.iter().next().unwrap().1.1.nkey
             };
 
+            // Permissions follow the org chart (units × roles)
+            let permissions = SubjectPermissionProjection::project_person_permissions(organization, person);
             let user = Self::project_user(
                 person,
                 organization,
                 account_nkey,
-                None, // Default permissions
+                Some(permissions),
                 None, // Default limits
                 correlation_id,
                 Some(correlation_id), // Caused by operator/account creation
@@ -0,0 +1,249 @@
+// NATS Subject Permission Projections
+//
+// Projects the organizational structure (OrganizationUnit hierarchy ×
+// person roles) into NATS subject permissions for user JWTs.
+//
+// Functor:
+//   Organization             → `{org}.>`
+//   OrganizationUnit         → `{org}.{parent…}.{unit}.>` (nested under parents)
+//   Person (units × roles)   → Permissions (union of the subjects above)
+//
+// Membership of a unit grants publish and subscribe on the unit's subjects.
+// Roles widen that to their scope: auditors and executives read
+// (subscribe), every other role reads and writes. Every user may subscribe
+// to `_INBOX.>` for request/reply. The system account's monitoring user
+// watches `$SYS.>` and may only ping servers.
+
+use std::collections::BTreeSet;
+
+use uuid::Uuid;
+
+use crate::domain::{Organization, Person, RoleScope, RoleType, UserIdentity};
+use crate::value_objects::Permissions;
+
+/// Subject users receive request/reply responses on
+pub const INBOX_SUBJECT: &str = "_INBOX.>";
+
+/// Server events, advisories and monitoring replies of the system account
+pub const SYSTEM_SUBJECT: &str = "$SYS.>";
+
+/// Requests a monitoring user may send (`$SYS.REQ.SERVER.PING[.VARZ|…]`)
+pub const SYSTEM_PING_SUBJECTS: [&str; 2] = ["$SYS.REQ.SERVER.PING", "$SYS.REQ.SERVER.PING.>"];
+
+/// Projection functor: Organizational structure → NATS subject permissions
+pub struct SubjectPermissionProjection;
+
+impl SubjectPermissionProjection {
+    /// Subject token for a name: lowercase, `[a-z0-9_-]` only
+    pub fn subject_token(name: &str) -> String {
+        name.trim()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c.to_ascii_lowercase() } else { '-' })
+            .collect()
+    }
+
+    /// Subject prefix of an organization
+    pub fn organization_subject(organization: &Organization) -> String {
+        Self::subject_token(&organization.name)
+    }
+
+    /// Subject prefix of a unit, nested under its parent units
+    ///
+    /// Returns `None` if the unit is not part of the organization.
+    pub fn unit_subject(organization: &Organization, unit_id: Uuid) -> Option<String> {
+        let mut tokens = Vec::new();
+        let mut current = Some(unit_id);
+        while let Some(id) = current {
+            let unit = organization.units.iter().find(|u| u.id.as_uuid() == id)?;
+            tokens.push(Self::subject_token(&unit.name));
+            current = unit.parent_unit_id.map(|parent| parent.as_uuid());
+            // A parent cycle cannot nest deeper than there are units
+            if tokens.len() > organization.units.len() {
+                return None;
+            }
+        }
+        tokens.push(Self::organization_subject(organization));
+        tokens.reverse();
+        Some(tokens.join("."))
+    }
+
+    /// Permissions of a member of one unit: publish and subscribe below it
+    pub fn project_unit_permissions(organization: &Organization, unit_id: Uuid) -> Permissions {
+        let mut grants = Grants::default();
+        if let Some(subject) = Self::unit_subject(organization, unit_id) {
+            grants.read_write(&subject);
+        }
+        grants.into_permissions()
+    }
+
+    /// Permissions of a person from their unit memberships and roles
+    pub fn project_person_permissions(organization: &Organization, person: &Person) -> Permissions {
+        let mut grants = Grants::default();
+
+        for unit_id in &person.unit_ids {
+            if let Some(subject) = Self::unit_subject(organization, unit_id.as_uuid()) {
+                grants.read_write(&subject);
+            }
+        }
+
+        for role in &person.roles {
+            let subject = match role.scope {
+                RoleScope::Organization | RoleScope::System => Some(Self::organization_subject(organization)),
+                RoleScope::Unit(unit_id) => Self::unit_subject(organization, unit_id),
+            };
+            let Some(subject) = subject else { continue };
+            match role.role_type {
+                RoleType::Auditor | RoleType::Executive => grants.read(&subject),
+                RoleType::Administrator | RoleType::Developer | RoleType::Operator | RoleType::Service => {
+                    grants.read_write(&subject)
+                }
+            }
+        }
+
+        // Explicit grants of service persons are kept on top of the org chart
+        if let Some(explicit) = &person.nats_permissions {
+            grants.publish.extend(explicit.publish.iter().cloned());
+            grants.subscribe.extend(explicit.subscribe.iter().cloned());
+        }
+
+        grants.into_permissions()
+    }
+
+    /// Permissions of any user identity
+    ///
+    /// Service accounts get the permissions of their owning unit.
+    pub fn project_user_identity_permissions(organization: &Organization, user: &UserIdentity) -> Permissions {
+        match user {
+            UserIdentity::Person(person) => Self::project_person_permissions(organization, person),
+            UserIdentity::ServiceAccount(service_account) => {
+                Self::project_unit_permissions(organization, service_account.owning_unit_id)
+            }
+            #[cfg(feature = "cim-domain-agent")]
+            UserIdentity::Agent(_) => Grants::default().into_permissions(),
+        }
+    }
+
+    /// Permissions of the system account's monitoring user
+    ///
+    /// Reads everything below `$SYS` but cannot publish account updates,
+    /// kick connections or reload servers.
+    pub fn project_system_monitoring_permissions() -> Permissions {
+        Permissions {
+            pub_allow: Some(SYSTEM_PING_SUBJECTS.iter().map(|s| s.to_string()).collect()),
+            pub_deny: None,
+            sub_allow: Some(vec![SYSTEM_SUBJECT.to_string(), INBOX_SUBJECT.to_string()]),
+            sub_deny: None,
+        }
+    }
+}
+
+/// Subjects collected for one user
+#[derive(Default)]
+struct Grants {
+    publish: BTreeSet<String>,
+    subscribe: BTreeSet<String>,
+}
+
+impl Grants {
+    fn read(&mut self, prefix: &str) {
+        self.subscribe.insert(format!("{}.>", prefix));
+    }
+
+    fn read_write(&mut self, prefix: &str) {
+        self.read(prefix);
+        self.publish.insert(format!("{}.>", prefix));
+    }
+
+    /// Allow lists; narrower subjects covered by a wider grant are dropped
+    fn into_permissions(mut self) -> Permissions {
+        self.subscribe.insert(INBOX_SUBJECT.to_string());
+        let non_empty = |subjects: Vec<String>| (!subjects.is_empty()).then_some(subjects);
+        Permissions {
+            pub_allow: non_empty(without_covered(&self.publish)),
+            pub_deny: None,
+            sub_allow: non_empty(without_covered(&self.subscribe)),
+            sub_deny: None,
+        }
+    }
+}
+
+/// Drop subjects matched by another `prefix.>` subject in the set
+fn without_covered(subjects: &BTreeSet<String>) -> Vec<String> {
+    subjects
+        .iter()
+        .filter(|subject| {
+            !subjects.iter().any(|other| {
+                other != *subject
+                    && other
+                        .strip_suffix('>')
+                        .is_some_and(|prefix| subject.starts_with(prefix))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{OrganizationUnit, OrganizationUnitType, PersonRole};
+
+    fn organization() -> (Organization, OrganizationUnit, OrganizationUnit) {
+        let engineering = OrganizationUnit::new("Engineering", OrganizationUnitType::Division);
+        let platform = OrganizationUnit::new("Platform Team", OrganizationUnitType::Team).with_parent(engineering.id);
+        let organization = Organization::new("CowboyAI", "Cowboy AI")
+            .with_unit(engineering.clone())
+            .with_unit(platform.clone());
+        (organization, engineering, platform)
+    }
+
+    #[test]
+    fn test_unit_subjects_follow_hierarchy() {
+        let (organization, engineering, platform) = organization();
+        assert_eq!(
+            SubjectPermissionProjection::unit_subject(&organization, engineering.id.as_uuid()).as_deref(),
+            Some("cowboyai.engineering")
+        );
+        assert_eq!(
+            SubjectPermissionProjection::unit_subject(&organization, platform.id.as_uuid()).as_deref(),
+            Some("cowboyai.engineering.platform-team")
+        );
+        assert_eq!(SubjectPermissionProjection::unit_subject(&organization, Uuid::now_v7()), None);
+    }
+
+    #[test]
+    fn test_person_permissions_from_units_and_roles() {
+        let (organization, engineering, platform) = organization();
+        let auditor = PersonRole {
+            role_type: RoleType::Auditor,
+            scope: RoleScope::Organization,
+            permissions: Vec::new(),
+        };
+        let developer = PersonRole {
+            role_type: RoleType::Developer,
+            scope: RoleScope::Unit(engineering.id.as_uuid()),
+            permissions: Vec::new(),
+        };
+        let person = Person::new("Alice", "alice@cowboy.ai", organization.id)
+            .in_unit(platform.id)
+            .with_role(auditor)
+            .with_role(developer);
+
+        let permissions = SubjectPermissionProjection::project_person_permissions(&organization, &person);
+        // The developer role on Engineering covers the Platform Team membership
+        assert_eq!(permissions.pub_allow, Some(vec!["cowboyai.engineering.>".to_string()]));
+        assert_eq!(
+            permissions.sub_allow,
+            Some(vec![INBOX_SUBJECT.to_string(), "cowboyai.>".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_person_without_units_only_gets_inbox() {
+        let (organization, _, _) = organization();
+        let person = Person::new("Bob", "bob@cowboy.ai", organization.id);
+        let permissions = SubjectPermissionProjection::project_person_permissions(&organization, &person);
+        assert_eq!(permissions.pub_allow, None);
+        assert_eq!(permissions.sub_allow, Some(vec![INBOX_SUBJECT.to_string()]));
+    }
+}
@@ -7,6 +7,15 @@
 //! - Integrity verification (CID is cryptographic hash of content)
 //! - Immutable event log (content cannot change without changing CID)
 //! - Merkle DAG structure for causality chains
+//! - Hash-chained log: each record carries its predecessor's CID and a
+//!   link CID over both, so [`CidEventStore::verify_chain`] detects
+//!   deleted, inserted, reordered, or re-pointed events
+//! - Archival: [`CidEventStore::compact`] moves old events into compressed,
+//!   chained segments (see [`archive`])
+
+pub mod archive;
+
+pub use archive::{ArchiveIndex, ArchivePolicy, CompactionReport, SegmentInfo};
 
 use std::path::{Path, PathBuf};
 use std::fs;
@@ -15,7 +24,7 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::events::EventEnvelope;
-use crate::ipld_support::IpldError;
+use crate::ipld_support::{ChainLink, IpldError};
 
 /// Error types for the CID-based event store
 #[derive(Debug, Error)]
@@ -37,6 +46,12 @@ pub enum EventStoreError {
 
     #[error("Duplicate event (CID already exists): {0}")]
     DuplicateEvent(String),
+
+    #[error("Signature error: {0}")]
+    SignatureError(#[from] crate::events::EventSignatureError),
+
+    #[error("Archive corrupted: {0}")]
+    ArchiveCorrupted(String),
 }
 
 /// CID-indexed event stored on disk
@@ -53,6 +68,17 @@ pub struct StoredEventRecord {
 
     /// Optional CID of causation event (for Merkle DAG)
     pub causation_cid: Option<String>,
+
+    /// CID of the event stored before this one (`None` for the first),
+    /// chaining the log in storage order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_cid: Option<String>,
+
+    /// CID over `cid`, `previous_cid` and the previous record's link
+    /// (see [`crate::ipld_support::generate_link_cid`]); `None` only for
+    /// records stored before links were introduced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
 }
 
 /// CID-based event store that stores events by content address
@@ -62,6 +88,9 @@ pub struct CidEventStore {
 
     /// Cache of known CIDs (for fast deduplication)
     known_cids: HashSet<String>,
+
+    /// Storage order of events; its last entry is the chain head
+    index: EventIndex,
 }
 
 impl CidEventStore {
@@ -77,10 +106,12 @@ impl CidEventStore {
 
         // Load existing CIDs from directory
         let known_cids = Self::load_known_cids(&by_cid_path)?;
+        let index = EventIndex::load_or_create(&root_path)?;
 
         Ok(Self {
             root_path,
             known_cids,
+            index,
         })
     }
 
@@ -122,12 +153,23 @@ impl CidEventStore {
             return Err(EventStoreError::DuplicateEvent(cid_string));
         }
 
+        // Chain the record to the one stored before it
+        let previous = self.last_record()?;
+        let previous_cid = previous.as_ref().map(|record| record.cid.clone());
+        let link = crate::ipld_support::generate_link_cid(
+            &cid_string,
+            previous_cid.as_deref(),
+            previous.as_ref().and_then(|record| record.link.as_deref()),
+        )?;
+
         // Create stored record
         let record = StoredEventRecord {
             cid: cid_string.clone(),
             envelope,
             stored_at: chrono::Utc::now(),
             causation_cid: None, // TODO: Look up causation CID from causation_id
+            previous_cid,
+            link: Some(link),
         };
 
         // Write to disk
@@ -142,8 +184,10 @@ impl CidEventStore {
         fs::write(&event_path, json)
             .map_err(|e| EventStoreError::IoError(format!("Failed to write event: {}", e)))?;
 
-        // Update cache
+        // Update cache and advance the chain head
         self.known_cids.insert(cid_string.clone());
+        self.index.append(cid_string.clone());
+        self.index.save(&self.root_path)?;
 
         Ok(cid_string)
     }
@@ -170,6 +214,102 @@ impl CidEventStore {
         Err(EventStoreError::IpldError(IpldError::FeatureNotEnabled))
     }
 
+    /// CID of the most recently stored event
+    pub fn head(&self) -> Option<&str> {
+        self.index.events.last().map(String::as_str)
+    }
+
+    /// Most recently stored event, archived if the live log is empty
+    #[cfg(feature = "ipld")]
+    fn last_record(&self) -> Result<Option<StoredEventRecord>, EventStoreError> {
+        match self.head() {
+            Some(cid) => self.get(cid).map(Some),
+            None => Ok(self.archived_events()?.pop()),
+        }
+    }
+
+    /// Verify the hash chain over the whole log
+    ///
+    /// Walks the events in storage order, archived ones first, and checks
+    /// that each record is present, matches its CID, links to the event
+    /// stored before it and matches its link CID, and that no stored event
+    /// sits outside the chain. Returns the number of chained events.
+    ///
+    /// The chain catches partial edits; a log rewritten end to end can
+    /// only be caught through signed envelopes
+    /// ([`CidEventStore::list_verified_events`]).
+    pub fn verify_chain(&self) -> Result<usize, EventStoreError> {
+        let broken = |position: usize, reason: String| {
+            EventStoreError::IpldError(IpldError::ChainBroken { position, reason })
+        };
+
+        // Archived segments are checked against their checksums instead
+        let mut records = self.archived_events()?;
+        let archived = records.len();
+        for (position, cid) in self.index.events.iter().enumerate() {
+            let position = archived + position;
+            let record = match self.get(cid) {
+                Err(EventStoreError::NotFound(_)) => {
+                    return Err(broken(position, format!("{} is missing", cid)));
+                }
+                result => result?,
+            };
+            if record.cid != *cid || !self.verify(cid)? {
+                return Err(broken(position, format!("{} does not match its content", cid)));
+            }
+            records.push(record);
+        }
+        let links: Vec<ChainLink> = records
+            .iter()
+            .map(|record| ChainLink {
+                cid: record.cid.clone(),
+                previous_cid: record.previous_cid.clone(),
+            })
+            .collect();
+        crate::ipld_support::verify_chain_links(&links)?;
+        Self::verify_link_cids(&records)?;
+
+        if let Some(orphan) = self.known_cids.iter().find(|cid| !self.index.events.contains(cid)) {
+            return Err(broken(links.len(), format!("{} is not part of the chain", orphan)));
+        }
+
+        Ok(links.len())
+    }
+
+    /// Check each record's link CID against its place in the chain
+    ///
+    /// Records from before links were introduced may only precede the
+    /// first linked record.
+    #[cfg(feature = "ipld")]
+    fn verify_link_cids(records: &[StoredEventRecord]) -> Result<(), EventStoreError> {
+        let mut previous_link: Option<&str> = None;
+        for (position, record) in records.iter().enumerate() {
+            let broken = |reason: String| EventStoreError::IpldError(IpldError::ChainBroken { position, reason });
+            let Some(link) = record.link.as_deref() else {
+                if previous_link.is_some() {
+                    return Err(broken(format!("{} has no link", record.cid)));
+                }
+                continue;
+            };
+            let expected = crate::ipld_support::generate_link_cid(
+                &record.cid,
+                record.previous_cid.as_deref(),
+                previous_link,
+            )?;
+            if link != expected {
+                return Err(broken(format!("{} does not match its link", record.cid)));
+            }
+            previous_link = Some(link);
+        }
+        Ok(())
+    }
+
+    /// Check link CIDs (stub when IPLD disabled)
+    #[cfg(not(feature = "ipld"))]
+    fn verify_link_cids(_records: &[StoredEventRecord]) -> Result<(), EventStoreError> {
+        Ok(()) // No verification without IPLD
+    }
+
     /// Check if an event with the given CID exists
     pub fn exists(&self, cid: &str) -> bool {
         self.known_cids.contains(cid)
@@ -225,6 +365,33 @@ impl CidEventStore {
         Ok(events)
     }
 
+    /// Get all stored events in chronological order, rejecting any whose
+    /// envelope signature does not verify
+    ///
+    /// Use when replaying an offline log that may have been edited while
+    /// the partition was out of custody.
+    pub fn list_verified_events(
+        &self,
+        verifier: &crate::events::EventVerifier,
+    ) -> Result<Vec<StoredEventRecord>, EventStoreError> {
+        let events = self.list_events()?;
+        for record in &events {
+            verifier.verify(&record.envelope)?;
+        }
+        Ok(events)
+    }
+
+    /// Stored envelopes matching `query`, in causal order
+    pub fn query(&self, query: &crate::events::EnvelopeQuery) -> Result<Vec<EventEnvelope>, EventStoreError> {
+        Ok(query.run(self.list_events()?.into_iter().map(|record| record.envelope)))
+    }
+
+    /// Causation tree of every event in one business process
+    pub fn causation_tree(&self, correlation_id: uuid::Uuid) -> Result<crate::events::CausationTree, EventStoreError> {
+        let query = crate::events::EnvelopeQuery::new().with_correlation(correlation_id);
+        Ok(crate::events::CausationTree::build(self.query(&query)?))
+    }
+
     /// Get event count
     pub fn count(&self) -> usize {
         self.known_cids.len()
@@ -451,4 +618,108 @@ mod tests {
 
         assert!(store.verify(&cid).unwrap());
     }
+
+    #[cfg(feature = "ipld")]
+    #[test]
+    fn test_event_store_hash_chain() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = CidEventStore::new(temp_dir.path()).unwrap();
+
+        let cids: Vec<String> = (0..3)
+            .map(|_| store.store(create_test_envelope()).unwrap())
+            .collect();
+        assert_eq!(store.head(), Some(cids[2].as_str()));
+        assert_eq!(store.get(&cids[0]).unwrap().previous_cid, None);
+        assert_eq!(store.get(&cids[2]).unwrap().previous_cid.as_deref(), Some(cids[1].as_str()));
+        assert_eq!(store.verify_chain().unwrap(), 3);
+
+        // The chain survives reopening the store
+        let mut store = CidEventStore::new(temp_dir.path()).unwrap();
+        let fourth = store.store(create_test_envelope()).unwrap();
+        assert_eq!(store.get(&fourth).unwrap().previous_cid.as_deref(), Some(cids[2].as_str()));
+        assert_eq!(store.verify_chain().unwrap(), 4);
+
+        // Reordering the log
+        let mut index = EventIndex::load_or_create(temp_dir.path()).unwrap();
+        index.events.swap(1, 2);
+        index.save(temp_dir.path()).unwrap();
+        let reordered = CidEventStore::new(temp_dir.path()).unwrap();
+        assert!(matches!(
+            reordered.verify_chain(),
+            Err(EventStoreError::IpldError(IpldError::ChainBroken { position: 1, .. }))
+        ));
+        index.events.swap(1, 2);
+        index.save(temp_dir.path()).unwrap();
+
+        // Inserting an event file outside the chain
+        let by_cid = temp_dir.path().join("events").join("by_cid");
+        let mut stray = CidEventStore::new(temp_dir.path().join("stray")).unwrap();
+        let stray_cid = stray.store(create_test_envelope()).unwrap();
+        fs::copy(
+            temp_dir.path().join("stray").join("events").join("by_cid").join(format!("{}.json", stray_cid)),
+            by_cid.join(format!("{}.json", stray_cid)),
+        )
+        .unwrap();
+        let mut store = CidEventStore::new(temp_dir.path()).unwrap();
+        assert!(store.verify_chain().is_err());
+
+        // Deleting an event
+        store.delete(&stray_cid).unwrap();
+        assert_eq!(store.verify_chain().unwrap(), 4);
+        store.delete(&cids[1]).unwrap();
+        assert!(matches!(
+            store.verify_chain(),
+            Err(EventStoreError::IpldError(IpldError::ChainBroken { position: 1, .. }))
+        ));
+    }
+
+    #[cfg(feature = "ipld")]
+    #[test]
+    fn test_event_store_detects_repointed_previous_cid() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = CidEventStore::new(temp_dir.path()).unwrap();
+        let cids: Vec<String> = (0..3)
+            .map(|_| store.store(create_test_envelope()).unwrap())
+            .collect();
+
+        // Drop the middle event and re-point its successor past it
+        store.delete(&cids[1]).unwrap();
+        let mut index = EventIndex::load_or_create(temp_dir.path()).unwrap();
+        index.events.retain(|cid| *cid != cids[1]);
+        index.save(temp_dir.path()).unwrap();
+        let path = temp_dir.path().join("events").join("by_cid").join(format!("{}.json", cids[2]));
+        let mut record: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        record["previous_cid"] = serde_json::json!(cids[0]);
+        fs::write(&path, record.to_string()).unwrap();
+
+        let store = CidEventStore::new(temp_dir.path()).unwrap();
+        assert!(store.verify(&cids[2]).unwrap());
+        assert!(matches!(
+            store.verify_chain(),
+            Err(EventStoreError::IpldError(IpldError::ChainBroken { position: 1, .. }))
+        ));
+    }
+
+    #[cfg(feature = "ipld")]
+    #[test]
+    fn test_event_store_detects_edited_envelope() {
+        use crate::events::{EventSigner, EventVerifier};
+
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = CidEventStore::new(temp_dir.path()).unwrap();
+        let signer = EventSigner::new(Uuid::now_v7(), ed25519_dalek::SigningKey::from_bytes(&rand::random()));
+        let verifier = EventVerifier::new().trust(signer.key_id(), signer.verifying_key());
+
+        let cid = store.store(signer.sign(create_test_envelope()).unwrap()).unwrap();
+        assert_eq!(store.list_verified_events(&verifier).unwrap().len(), 1);
+
+        // Re-pointing the causal chain leaves the event's CID intact
+        let path = temp_dir.path().join("events").join("by_cid").join(format!("{}.json", cid));
+        let mut record: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        record["envelope"]["causation_id"] = serde_json::json!(Uuid::now_v7());
+        fs::write(&path, record.to_string()).unwrap();
+
+        assert!(store.verify(&cid).unwrap());
+        assert!(matches!(store.list_verified_events(&verifier), Err(EventStoreError::SignatureError(_))));
+    }
 }
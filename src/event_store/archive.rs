@@ -0,0 +1,357 @@
+//! Event archival and compaction
+//!
+//! Keeps the live log of a long-lived organization small by moving events
+//! older than a horizon into compressed archive segments:
+//!
+//! ```text
+//! events/
+//! ├── by_cid/           live events
+//! ├── index.json        live chain, oldest first
+//! └── archive/
+//!     ├── index.json    ArchiveIndex: one SegmentInfo per segment
+//!     ├── 000001.json.gz
+//!     └── 000002.json.gz
+//! ```
+//!
+//! Only a prefix of the log is ever archived, so the archive followed by
+//! the live log is still the whole log in storage order, and the event
+//! hash chain runs unbroken from the first archived event to the head.
+//! Segments are chained as well: each names the SHA-256 of the segment
+//! before it, and the index records every segment's checksum.
+//!
+//! Archiving never discards anything. Aggregate snapshots and projection
+//! manifests live outside the event store and are left untouched, and
+//! [`CidEventStore::list_all_events`] still replays every event, so all
+//! projections remain rebuildable from scratch.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{CidEventStore, EventStoreError, StoredEventRecord};
+
+/// Directory of the archive, below `events/`
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// Which events [`CidEventStore::compact`] moves into the archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchivePolicy {
+    /// Events stored longer ago than this are archived
+    pub horizon: Duration,
+    /// Number of most recent events that always stay live
+    pub keep_latest: usize,
+}
+
+impl ArchivePolicy {
+    /// Archive every event stored more than `horizon` ago
+    pub fn older_than(horizon: Duration) -> Self {
+        Self { horizon, keep_latest: 0 }
+    }
+
+    /// Keep at least the `count` most recent events live
+    pub fn keep_latest(mut self, count: usize) -> Self {
+        self.keep_latest = count;
+        self
+    }
+}
+
+/// One archive segment as stored, before compression
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArchiveSegment {
+    number: u32,
+    previous_checksum: Option<String>,
+    records: Vec<StoredEventRecord>,
+}
+
+/// Index entry of one archive segment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentInfo {
+    /// Segment number, starting at 1
+    pub number: u32,
+    /// File name inside the archive directory
+    pub file: String,
+    pub event_count: usize,
+    /// CID of the first archived event
+    pub first_cid: String,
+    /// CID of the last archived event; the first event after the segment
+    /// links to it
+    pub last_cid: String,
+    pub first_stored_at: DateTime<Utc>,
+    pub last_stored_at: DateTime<Utc>,
+    /// SHA-256 of the compressed segment file, hex
+    pub checksum: String,
+    /// Checksum of the segment before this one
+    pub previous_checksum: Option<String>,
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Index of all archive segments, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveIndex {
+    pub segments: Vec<SegmentInfo>,
+}
+
+impl ArchiveIndex {
+    /// Load the archive index of the store at `root`, empty if none exists
+    pub fn load_or_create(root: &Path) -> Result<Self, EventStoreError> {
+        let path = archive_dir(root).join("index.json");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path).map_err(|e| EventStoreError::IoError(e.to_string()))?;
+        serde_json::from_str(&content).map_err(|e| EventStoreError::SerializationError(e.to_string()))
+    }
+
+    /// Save the archive index
+    pub fn save(&self, root: &Path) -> Result<(), EventStoreError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| EventStoreError::SerializationError(e.to_string()))?;
+        fs::write(archive_dir(root).join("index.json"), json).map_err(|e| EventStoreError::IoError(e.to_string()))
+    }
+
+    /// Number of archived events
+    pub fn event_count(&self) -> usize {
+        self.segments.iter().map(|segment| segment.event_count).sum()
+    }
+}
+
+/// Outcome of a compaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionReport {
+    /// Events moved into the archive
+    pub archived: usize,
+    /// Segment written, `None` when nothing was old enough
+    pub segment: Option<SegmentInfo>,
+    /// Events left in the live log
+    pub live: usize,
+}
+
+impl CidEventStore {
+    /// Move events older than the policy's horizon into a new archive segment
+    ///
+    /// Events are archived oldest first and only up to the first event
+    /// that is still within the horizon, so the live log always continues
+    /// where the archive ends.
+    pub fn compact(&mut self, policy: &ArchivePolicy) -> Result<CompactionReport, EventStoreError> {
+        let cutoff = Utc::now() - policy.horizon;
+        let archivable = self.index.events.len().saturating_sub(policy.keep_latest);
+
+        let mut records = Vec::new();
+        for cid in self.index.events.iter().take(archivable) {
+            let record = self.get(cid)?;
+            if record.stored_at > cutoff {
+                break;
+            }
+            records.push(record);
+        }
+        if records.is_empty() {
+            return Ok(CompactionReport { archived: 0, segment: None, live: self.count() });
+        }
+
+        fs::create_dir_all(archive_dir(&self.root_path))
+            .map_err(|e| EventStoreError::IoError(format!("Failed to create archive directory: {}", e)))?;
+        let mut archive = ArchiveIndex::load_or_create(&self.root_path)?;
+        let segment = ArchiveSegment {
+            number: archive.segments.last().map_or(1, |last| last.number + 1),
+            previous_checksum: archive.segments.last().map(|last| last.checksum.clone()),
+            records,
+        };
+        let info = write_segment(&self.root_path, &segment)?;
+        archive.segments.push(info.clone());
+        archive.save(&self.root_path)?;
+
+        // The archive is durable; only now drop the events from the live log
+        let archived = segment.records.len();
+        self.index.events.drain(..archived);
+        self.index.updated_at = Utc::now();
+        self.index.save(&self.root_path)?;
+        for record in &segment.records {
+            self.delete(&record.cid)?;
+        }
+
+        Ok(CompactionReport { archived, segment: Some(info), live: self.count() })
+    }
+
+    /// Index of the archive segments
+    pub fn archive_index(&self) -> Result<ArchiveIndex, EventStoreError> {
+        ArchiveIndex::load_or_create(&self.root_path)
+    }
+
+    /// All archived events in storage order
+    ///
+    /// Fails with [`EventStoreError::ArchiveCorrupted`] if a segment is
+    /// missing, does not match its checksum, or is out of chain.
+    pub fn archived_events(&self) -> Result<Vec<StoredEventRecord>, EventStoreError> {
+        let archive = self.archive_index()?;
+        let mut records = Vec::with_capacity(archive.event_count());
+        let mut previous_checksum = None;
+
+        for info in &archive.segments {
+            if info.previous_checksum != previous_checksum {
+                return Err(EventStoreError::ArchiveCorrupted(format!(
+                    "segment {} does not follow the segment before it",
+                    info.number
+                )));
+            }
+            let segment = read_segment(&self.root_path, info)?;
+            if segment.number != info.number || segment.records.len() != info.event_count {
+                return Err(EventStoreError::ArchiveCorrupted(format!(
+                    "segment {} does not match its index entry",
+                    info.number
+                )));
+            }
+            records.extend(segment.records);
+            previous_checksum = Some(info.checksum.clone());
+        }
+
+        Ok(records)
+    }
+
+    /// Every event, archived and live, in storage order
+    ///
+    /// Use this rather than [`CidEventStore::list_events`] to rebuild
+    /// projections from scratch.
+    pub fn list_all_events(&self) -> Result<Vec<StoredEventRecord>, EventStoreError> {
+        let mut events = self.archived_events()?;
+        events.extend(self.list_events()?);
+        Ok(events)
+    }
+}
+
+fn archive_dir(root: &Path) -> PathBuf {
+    root.join("events").join(ARCHIVE_DIR)
+}
+
+fn write_segment(root: &Path, segment: &ArchiveSegment) -> Result<SegmentInfo, EventStoreError> {
+    let json = serde_json::to_vec(segment).map_err(|e| EventStoreError::SerializationError(e.to_string()))?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder
+        .write_all(&json)
+        .map_err(|e| EventStoreError::IoError(format!("Failed to compress archive segment: {}", e)))?;
+    let compressed = encoder
+        .finish()
+        .map_err(|e| EventStoreError::IoError(format!("Failed to compress archive segment: {}", e)))?;
+
+    let file = format!("{:06}.json.gz", segment.number);
+    fs::write(archive_dir(root).join(&file), &compressed)
+        .map_err(|e| EventStoreError::IoError(format!("Failed to write archive segment: {}", e)))?;
+
+    let (first, last) = match (segment.records.first(), segment.records.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(EventStoreError::ArchiveCorrupted("empty archive segment".to_string())),
+    };
+    Ok(SegmentInfo {
+        number: segment.number,
+        file,
+        event_count: segment.records.len(),
+        first_cid: first.cid.clone(),
+        last_cid: last.cid.clone(),
+        first_stored_at: first.stored_at,
+        last_stored_at: last.stored_at,
+        checksum: hex::encode(Sha256::digest(&compressed)),
+        previous_checksum: segment.previous_checksum.clone(),
+        archived_at: Utc::now(),
+    })
+}
+
+fn read_segment(root: &Path, info: &SegmentInfo) -> Result<ArchiveSegment, EventStoreError> {
+    let compressed = fs::read(archive_dir(root).join(&info.file))
+        .map_err(|e| EventStoreError::ArchiveCorrupted(format!("segment {} is unreadable: {}", info.number, e)))?;
+    if hex::encode(Sha256::digest(&compressed)) != info.checksum {
+        return Err(EventStoreError::ArchiveCorrupted(format!(
+            "segment {} does not match its checksum",
+            info.number
+        )));
+    }
+
+    let mut json = Vec::new();
+    GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut json)
+        .map_err(|e| EventStoreError::ArchiveCorrupted(format!("segment {}: {}", info.number, e)))?;
+    serde_json::from_slice(&json).map_err(|e| EventStoreError::SerializationError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(feature = "ipld")]
+    fn envelope() -> crate::events::EventEnvelope {
+        use crate::events::person::PersonCreatedEvent;
+        use crate::events::{DomainEvent, EventEnvelope, PersonEvents};
+        use crate::value_objects::ActorId;
+        use uuid::Uuid;
+
+        let event = DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+            person_id: Uuid::now_v7(),
+            name: "Archived Person".to_string(),
+            email: None,
+            title: None,
+            department: None,
+            organization_id: Uuid::now_v7(),
+            created_by: ActorId::system("test"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }));
+        EventEnvelope::new(event, Uuid::now_v7(), None)
+    }
+
+    #[test]
+    fn test_compact_empty_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = CidEventStore::new(temp_dir.path()).unwrap();
+
+        let report = store.compact(&ArchivePolicy::older_than(Duration::zero())).unwrap();
+        assert_eq!(report, CompactionReport { archived: 0, segment: None, live: 0 });
+        assert!(store.archived_events().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "ipld")]
+    #[test]
+    fn test_compact_archives_chained_segments() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = CidEventStore::new(temp_dir.path()).unwrap();
+        let cids: Vec<String> = (0..4)
+            .map(|_| store.store(envelope()).unwrap())
+            .collect();
+
+        // Nothing is old enough yet
+        let report = store.compact(&ArchivePolicy::older_than(Duration::days(365))).unwrap();
+        assert_eq!(report.archived, 0);
+
+        let policy = ArchivePolicy::older_than(Duration::zero()).keep_latest(2);
+        let first = store.compact(&policy).unwrap();
+        assert_eq!((first.archived, first.live), (2, 2));
+        let first = first.segment.unwrap();
+        assert_eq!((first.first_cid.as_str(), first.last_cid.as_str()), (cids[0].as_str(), cids[1].as_str()));
+        assert!(temp_dir.path().join("events").join(ARCHIVE_DIR).join(&first.file).exists());
+        assert!(!store.exists(&cids[0]));
+
+        // The hash chain runs through the archive into the live log
+        assert_eq!(store.verify_chain().unwrap(), 4);
+        let all: Vec<String> = store.list_all_events().unwrap().into_iter().map(|r| r.cid).collect();
+        assert_eq!(all, cids);
+
+        let second = store.compact(&ArchivePolicy::older_than(Duration::zero())).unwrap().segment.unwrap();
+        assert_eq!(second.previous_checksum.as_deref(), Some(first.checksum.as_str()));
+        let store = CidEventStore::new(temp_dir.path()).unwrap();
+        assert_eq!(store.count(), 0);
+        assert_eq!(store.archived_events().unwrap().len(), 4);
+
+        // A modified segment is rejected
+        let path = temp_dir.path().join("events").join(ARCHIVE_DIR).join(&first.file);
+        let mut bytes = fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        fs::write(&path, bytes).unwrap();
+        assert!(matches!(store.archived_events(), Err(EventStoreError::ArchiveCorrupted(_))));
+    }
+}
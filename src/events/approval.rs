@@ -0,0 +1,140 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Approval Events
+//!
+//! Events for the PendingApproval aggregate (see `domain::approval`): a
+//! sensitive command parked until enough qualified people approve it.
+//!
+//! Events identify the parked command by ID and SHA-256 digest only.
+//! Export commands carry key material, which must not enter the event log.
+//!
+//! ## Event Flow
+//!
+//! ```text
+//! ApprovalRequested
+//!       │
+//!       ▼
+//! ApprovalGranted (× required approvals)
+//!       │
+//!       ▼
+//! ApprovalQuorumReached
+//!       │
+//!       ▼
+//! ApprovedCommandExecuted
+//!
+//! (while pending) ──▶ ApprovalRejected
+//! ```
+
+use chrono::{DateTime, Utc};
+use cim_domain::DomainEvent;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::approval::SensitiveCommandKind;
+use crate::domain::PolicyClaim;
+
+/// Events for approval of sensitive commands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event_type")]
+pub enum ApprovalEvents {
+    /// A sensitive command was parked for approval
+    ApprovalRequested(ApprovalRequestedEvent),
+
+    /// A qualified person approved the command
+    ApprovalGranted(ApprovalGrantedEvent),
+
+    /// The command has all required approvals
+    ApprovalQuorumReached(ApprovalQuorumReachedEvent),
+
+    /// The command was rejected and will not be executed
+    ApprovalRejected(ApprovalRejectedEvent),
+
+    /// The approved command was executed
+    ApprovedCommandExecuted(ApprovedCommandExecutedEvent),
+}
+
+/// A sensitive command was parked for approval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRequestedEvent {
+    pub approval_id: Uuid,
+    /// ID of the parked command
+    pub command_id: Uuid,
+    pub command_kind: SensitiveCommandKind,
+    /// SHA-256 of the parked command's canonical JSON, hex
+    pub command_digest: String,
+    /// Approvals needed before the command may run
+    pub required_approvals: u16,
+    /// Claims every approver must hold
+    pub approver_claims: Vec<PolicyClaim>,
+    pub requested_by: Uuid,
+    pub requested_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A qualified person approved the command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalGrantedEvent {
+    pub approval_id: Uuid,
+    pub approver_id: Uuid,
+    /// Approvals collected, including this one
+    pub approvals: u16,
+    pub comment: Option<String>,
+    pub granted_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The command has all required approvals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalQuorumReachedEvent {
+    pub approval_id: Uuid,
+    pub approvers: Vec<Uuid>,
+    pub reached_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The command was rejected and will not be executed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRejectedEvent {
+    pub approval_id: Uuid,
+    /// Qualified approver, or the requester withdrawing the request
+    pub rejected_by: Uuid,
+    pub reason: String,
+    pub rejected_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The approved command was executed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovedCommandExecutedEvent {
+    pub approval_id: Uuid,
+    pub command_id: Uuid,
+    pub executed_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl DomainEvent for ApprovalEvents {
+    fn aggregate_id(&self) -> Uuid {
+        match self {
+            ApprovalEvents::ApprovalRequested(e) => e.approval_id,
+            ApprovalEvents::ApprovalGranted(e) => e.approval_id,
+            ApprovalEvents::ApprovalQuorumReached(e) => e.approval_id,
+            ApprovalEvents::ApprovalRejected(e) => e.approval_id,
+            ApprovalEvents::ApprovedCommandExecuted(e) => e.approval_id,
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        match self {
+            ApprovalEvents::ApprovalRequested(_) => "ApprovalRequested",
+            ApprovalEvents::ApprovalGranted(_) => "ApprovalGranted",
+            ApprovalEvents::ApprovalQuorumReached(_) => "ApprovalQuorumReached",
+            ApprovalEvents::ApprovalRejected(_) => "ApprovalRejected",
+            ApprovalEvents::ApprovedCommandExecuted(_) => "ApprovedCommandExecuted",
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Authorization Events
+//!
+//! Events recording the outcome of checking a command against the claims
+//! its actor's policies grant (see `CommandAuthorizer::authorize`).
+//! A denied command emits only `CommandDenied`; nothing else happens.
+//! Likewise, a policy binding or role assignment that would break a
+//! separation-of-duties rule emits only `SeparationOfDutiesViolated`, and
+//! a lifecycle transition refused by a `TransitionGuard` (see
+//! `state_machines::guards`) emits only `TransitionDenied`.
+
+use chrono::{DateTime, Utc};
+use cim_domain::DomainEvent;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{PolicyClaim, PolicyEntityType};
+
+/// Events for command authorization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event_type")]
+pub enum AuthorizationEvents {
+    /// A command was refused because its actor lacks required claims
+    CommandDenied(CommandDeniedEvent),
+
+    /// An assignment was refused because it would break a separation-of-duties rule
+    SeparationOfDutiesViolated(SeparationOfDutiesViolatedEvent),
+
+    /// A state transition was refused because its actor lacks required claims
+    TransitionDenied(TransitionDeniedEvent),
+}
+
+/// A command was refused because its actor lacks required claims
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandDeniedEvent {
+    pub command_id: Uuid,
+    /// `KeyCommand` variant name
+    pub command_type: String,
+    /// Person or service account the command was issued by
+    pub actor_id: Uuid,
+    pub required_claims: Vec<PolicyClaim>,
+    pub missing_claims: Vec<PolicyClaim>,
+    /// Unmet conditions of policies that would have granted a missing claim
+    pub failing_conditions: Vec<String>,
+    pub denied_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// An assignment was refused because it would break a separation-of-duties rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeparationOfDutiesViolatedEvent {
+    pub command_id: Uuid,
+    /// Entity that would have held both claims
+    pub entity_id: Uuid,
+    pub entity_type: PolicyEntityType,
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    /// The two claims the rule keeps apart
+    pub conflicting_claims: Vec<PolicyClaim>,
+    /// What was being assigned, e.g. `policy <id>` or `role <name>`
+    pub assignment: String,
+    pub detected_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A state transition was refused because its actor lacks required claims
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionDeniedEvent {
+    /// Aggregate whose state machine refused the transition
+    pub entity_id: Uuid,
+    /// State machine type, e.g. `KeyState`
+    pub state_machine: String,
+    pub from_state: String,
+    pub to_state: String,
+    pub actor_id: Uuid,
+    pub required_claims: Vec<PolicyClaim>,
+    pub missing_claims: Vec<PolicyClaim>,
+    pub denied_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl DomainEvent for AuthorizationEvents {
+    fn aggregate_id(&self) -> Uuid {
+        match self {
+            AuthorizationEvents::CommandDenied(e) => e.command_id,
+            AuthorizationEvents::SeparationOfDutiesViolated(e) => e.command_id,
+            AuthorizationEvents::TransitionDenied(e) => e.entity_id,
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        match self {
+            AuthorizationEvents::CommandDenied(_) => "CommandDenied",
+            AuthorizationEvents::SeparationOfDutiesViolated(_) => "SeparationOfDutiesViolated",
+            AuthorizationEvents::TransitionDenied(_) => "TransitionDenied",
+        }
+    }
+}
@@ -12,7 +12,7 @@ use chrono::{DateTime, Utc};
 
 use crate::value_objects::ActorId;
 use crate::value_objects::x509::{
-    BasicConstraints, CertificateValidity, ExtendedKeyUsage, KeyUsage,
+    BasicConstraints, CertificateSignatureAlgorithm, CertificateValidity, ExtendedKeyUsage, KeyUsage,
     SubjectAlternativeName, SubjectName,
 };
 
@@ -41,6 +41,12 @@ pub enum CertificateEvents {
     /// PKI hierarchy was created
     PkiHierarchyCreated(PkiHierarchyCreatedEvent),
 
+    /// A certificate revocation list was published by an issuing CA
+    CrlPublished(CrlPublishedEvent),
+
+    /// A policy CA refused to issue a certificate
+    CertificateIssuanceRejected(CertificateIssuanceRejectedEvent),
+
     // Lifecycle State Transitions (Phase 11)
     /// Certificate activated
     CertificateActivated(CertificateActivatedEvent),
@@ -81,6 +87,12 @@ pub struct CertificateGeneratedEvent {
     /// Issuer certificate ID (None for self-signed root)
     pub issuer: Option<Uuid>,
 
+    /// Algorithm the certificate is signed with (None when the issuer's
+    /// algorithm is not one we recognise, or for events recorded before
+    /// it was tracked)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_algorithm: Option<CertificateSignatureAlgorithm>,
+
     pub correlation_id: Uuid,
     pub causation_id: Option<Uuid>,
 }
@@ -115,6 +127,9 @@ pub struct CertificateRenewedEvent {
     pub new_cert_id: Uuid,
     pub renewed_at: DateTime<Utc>,
     pub renewed_by: ActorId,
+    /// Whether the new certificate re-signs the old public key (false when rekeyed)
+    #[serde(default)]
+    pub key_reused: bool,
     pub correlation_id: Uuid,
     pub causation_id: Option<Uuid>,
 }
@@ -154,6 +169,43 @@ pub struct PkiHierarchyCreatedEvent {
     pub causation_id: Option<Uuid>,
 }
 
+/// A certificate revocation list was published by an issuing CA
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrlPublishedEvent {
+    pub crl_id: Uuid,
+    pub issuer_ca_id: Uuid,
+    /// Monotonic per issuer (cRLNumber extension)
+    pub crl_number: u64,
+    pub this_update: DateTime<Utc>,
+    pub next_update: DateTime<Utc>,
+    pub revoked_cert_ids: Vec<Uuid>,
+    /// PEM-encoded CRL signed by the issuing CA
+    pub crl_pem: String,
+    pub published_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A policy CA refused to issue a certificate
+///
+/// Recorded so denied requests are auditable alongside issued certificates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateIssuanceRejectedEvent {
+    pub rejection_id: Uuid,
+    pub issuer_ca_id: Uuid,
+    /// Name of the policy CA whose constraints were applied
+    pub policy_ca: String,
+    /// Subject common name requested by the CSR
+    pub subject: String,
+    /// Names outside the policy CA's permitted namespaces (empty when the
+    /// request was refused for another reason)
+    pub rejected_names: Vec<String>,
+    pub reason: String,
+    pub rejected_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
 // ============================================================================
 // Certificate Lifecycle State Transitions (Phase 11)
 // ============================================================================
@@ -199,6 +251,8 @@ impl DomainEvent for CertificateEvents {
             CertificateEvents::CertificateValidated(e) => e.cert_id,
             CertificateEvents::CertificateExported(e) => e.cert_id,
             CertificateEvents::PkiHierarchyCreated(e) => e.root_ca_id,
+            CertificateEvents::CrlPublished(e) => e.issuer_ca_id,
+            CertificateEvents::CertificateIssuanceRejected(e) => e.issuer_ca_id,
             CertificateEvents::CertificateActivated(e) => e.cert_id,
             CertificateEvents::CertificateSuspended(e) => e.cert_id,
             CertificateEvents::CertificateExpired(e) => e.cert_id,
@@ -214,6 +268,8 @@ impl DomainEvent for CertificateEvents {
             CertificateEvents::CertificateValidated(_) => "CertificateValidated",
             CertificateEvents::CertificateExported(_) => "CertificateExported",
             CertificateEvents::PkiHierarchyCreated(_) => "PkiHierarchyCreated",
+            CertificateEvents::CrlPublished(_) => "CrlPublished",
+            CertificateEvents::CertificateIssuanceRejected(_) => "CertificateIssuanceRejected",
             CertificateEvents::CertificateActivated(_) => "CertificateActivated",
             CertificateEvents::CertificateSuspended(_) => "CertificateSuspended",
             CertificateEvents::CertificateExpired(_) => "CertificateExpired",
@@ -31,6 +31,9 @@ pub enum DelegationEvents {
 
     /// A delegation's permissions were modified
     DelegationPermissionsModified(DelegationPermissionsModifiedEvent),
+
+    /// A delegation reached its expiration and was automatically revoked
+    DelegationExpired(DelegationExpiredEvent),
 }
 
 /// A new delegation was created
@@ -177,6 +180,32 @@ pub struct DelegationPermissionsModifiedEvent {
     pub causation_id: Option<Uuid>,
 }
 
+/// A delegation reached its expiration
+///
+/// Emitted by `DelegationAggregate::expire_delegations` once `valid_until`
+/// has passed. Delegations derived from the expired one are
+/// cascade-revoked alongside it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationExpiredEvent {
+    /// The delegation that expired
+    pub delegation_id: Uuid,
+
+    /// Person who held the delegation
+    pub delegate_id: Uuid,
+
+    /// When the delegation stopped being valid
+    pub valid_until: DateTime<Utc>,
+
+    /// When the expiration was detected
+    pub expired_at: DateTime<Utc>,
+
+    /// Correlation ID for event chain tracking
+    pub correlation_id: Uuid,
+
+    /// Causation ID linking to what triggered this event
+    pub causation_id: Option<Uuid>,
+}
+
 /// Reasons for delegation revocation
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RevocationReason {
@@ -232,6 +261,7 @@ impl DomainEvent for DelegationEvents {
             DelegationEvents::DelegationCascadeRevoked(e) => e.delegation_id,
             DelegationEvents::DelegationExtended(e) => e.delegation_id,
             DelegationEvents::DelegationPermissionsModified(e) => e.delegation_id,
+            DelegationEvents::DelegationExpired(e) => e.delegation_id,
         }
     }
 
@@ -242,6 +272,7 @@ impl DomainEvent for DelegationEvents {
             DelegationEvents::DelegationCascadeRevoked(_) => "DelegationCascadeRevoked",
             DelegationEvents::DelegationExtended(_) => "DelegationExtended",
             DelegationEvents::DelegationPermissionsModified(_) => "DelegationPermissionsModified",
+            DelegationEvents::DelegationExpired(_) => "DelegationExpired",
         }
     }
 }
@@ -10,8 +10,10 @@ use chrono::{DateTime, Utc};
 
 use crate::types::{
     KeyAlgorithm, KeyPurpose, KeyMetadata,
-    ImportSource, KeyFormat, ExportDestination, RevocationReason,
+    ImportSource, KeyFormat, ExportDestination, RevocationReason, OpenPgpCardSlot,
+    SshCertificateType, TrustLevel,
 };
+use crate::crypto::entropy::EntropyReport;
 use crate::domain::KeyOwnership;
 use crate::value_objects::ActorId;
 
@@ -48,6 +50,39 @@ pub enum KeyEvents {
 
     /// TOTP secret was generated
     TotpSecretGenerated(TotpSecretGeneratedEvent),
+
+    /// Key expiration was extended (binding signatures re-issued)
+    KeyExpirationExtended(KeyExpirationExtendedEvent),
+
+    /// Key material was moved onto a hardware token (keytocard)
+    KeyMovedToCard(KeyMovedToCardEvent),
+
+    /// A GPG key was certified by another key (web of trust)
+    GpgKeyCertified(GpgKeyCertifiedEvent),
+
+    /// A key was designated as the OpenSSH certificate authority
+    SshCaDesignated(SshCaDesignatedEvent),
+
+    /// An OpenSSH user or host certificate was issued
+    SshCertificateIssued(SshCertificateIssuedEvent),
+
+    /// The master seed was split into group shares for backup holders
+    MasterSeedSplit(MasterSeedSplitEvent),
+
+    /// A share of the master seed was handed to a backup holder
+    SeedShareAssigned(SeedShareAssignedEvent),
+
+    /// The master seed was recombined from shares
+    MasterSeedRecovered(MasterSeedRecoveredEvent),
+
+    /// A random master seed was mixed from ceremony entropy sources
+    MasterSeedDerived(MasterSeedDerivedEvent),
+
+    /// An organization-wide rotation of one kind of key finished
+    KeyRotationReported(KeyRotationReportedEvent),
+
+    /// Keys were re-derived from backups and compared with the manifest
+    RecoveryRehearsalCompleted(RecoveryRehearsalCompletedEvent),
 }
 
 /// A new key was generated
@@ -61,6 +96,9 @@ pub struct KeyGeneratedEvent {
     pub hardware_backed: bool,
     pub metadata: KeyMetadata,
     pub ownership: Option<KeyOwnership>,
+    /// When the key expires (None = no expiration)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
     pub correlation_id: Uuid,
     pub causation_id: Option<Uuid>,
 }
@@ -74,6 +112,8 @@ pub struct KeyImportedEvent {
     pub imported_at: DateTime<Utc>,
     pub imported_by: ActorId,
     pub metadata: KeyMetadata,
+    #[serde(default)]
+    pub ownership: Option<KeyOwnership>,
     pub correlation_id: Uuid,
     pub causation_id: Option<Uuid>,
 }
@@ -164,6 +204,233 @@ pub struct GpgKeyGeneratedEvent {
     pub causation_id: Option<Uuid>,
 }
 
+/// Key expiration was extended
+///
+/// `new_expires_at = None` means the key no longer expires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyExpirationExtendedEvent {
+    pub key_id: Uuid,
+    pub fingerprint: String,
+    pub previous_expires_at: Option<DateTime<Utc>>,
+    pub new_expires_at: Option<DateTime<Utc>>,
+    pub extended_at: DateTime<Utc>,
+    pub extended_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// Key material was moved onto a hardware token
+///
+/// The private key now lives only in the token's OpenPGP applet; the
+/// keyring keeps stubs. `metadata.hardware_serial` records the binding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyMovedToCardEvent {
+    pub key_id: Uuid,
+    pub fingerprint: String,
+    pub yubikey_serial: String,
+    pub slots: Vec<OpenPgpCardSlot>,
+    pub metadata: KeyMetadata,
+    pub moved_at: DateTime<Utc>,
+    pub moved_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A GPG key was certified by another Person's key
+///
+/// `trust_amount` is set when the certification is a trust signature that
+/// makes the subject an introducer at `trust_depth`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GpgKeyCertifiedEvent {
+    pub certification_id: Uuid,
+    pub certifier_key_id: Uuid,
+    pub certifier_person_id: Uuid,
+    pub subject_key_id: Uuid,
+    pub subject_person_id: Uuid,
+    pub subject_user_id: String,
+    pub trust_level: TrustLevel,
+    pub trust_depth: u8,
+    pub trust_amount: Option<u8>,
+    pub scope: Option<String>,
+    pub certified_at: DateTime<Utc>,
+    pub certified_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A key was designated as the OpenSSH certificate authority
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshCaDesignatedEvent {
+    pub ca_key_id: Uuid,
+    pub ca_fingerprint: String,
+    /// CA public key in OpenSSH format
+    pub public_key: String,
+    pub designated_at: DateTime<Utc>,
+    pub designated_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// An OpenSSH certificate was issued by the SSH CA
+///
+/// User certificates link to a Person, host certificates to a Location.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshCertificateIssuedEvent {
+    pub certificate_id: Uuid,
+    pub ca_key_id: Uuid,
+    pub subject_key_id: Option<Uuid>,
+    pub cert_type: SshCertificateType,
+    pub serial: u64,
+    pub key_identity: String,
+    pub principals: Vec<String>,
+    pub person_id: Option<Uuid>,
+    pub location_id: Option<Uuid>,
+    pub subject_fingerprint: String,
+    pub ca_fingerprint: String,
+    pub valid_after: DateTime<Utc>,
+    pub valid_before: DateTime<Utc>,
+    pub critical_options: std::collections::BTreeMap<String, String>,
+    pub issued_at: DateTime<Utc>,
+    pub issued_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The master seed was split into group shares
+///
+/// Records the custody structure only; share data never appears in events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MasterSeedSplitEvent {
+    pub split_id: Uuid,
+    pub organization_id: Uuid,
+    /// Set ID printed on every share of this split (8 hex digits)
+    pub set_id: String,
+    pub group_threshold: u8,
+    pub groups: Vec<SeedShareGroupRecord>,
+    pub split_at: DateTime<Utc>,
+    pub split_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// One group of a master seed split
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeedShareGroupRecord {
+    pub name: String,
+    pub member_threshold: u8,
+    pub member_count: u8,
+}
+
+/// A backup holder received one share of the master seed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedShareAssignedEvent {
+    pub split_id: Uuid,
+    /// Public share identifier (`{set_id}-g{group}-m{member}`), never the share
+    pub share_id: String,
+    pub group_name: String,
+    pub person_id: Uuid,
+    pub assigned_at: DateTime<Utc>,
+    pub assigned_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The master seed was recombined from backup holders' shares
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MasterSeedRecoveredEvent {
+    pub split_id: Uuid,
+    /// Shares presented for the recovery
+    pub share_ids: Vec<String>,
+    pub recovered_at: DateTime<Utc>,
+    pub recovered_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A random master seed was created at a key ceremony
+///
+/// Records how the entropy was collected; the seed itself never appears
+/// in events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MasterSeedDerivedEvent {
+    pub seed_id: Uuid,
+    pub organization_id: Uuid,
+    pub entropy: EntropyReport,
+    pub derived_at: DateTime<Utc>,
+    pub derived_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// Outcome of an organization-wide key rotation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyRotationReportedEvent {
+    /// The rotation saga
+    pub rotation_id: Uuid,
+    pub organization_id: Uuid,
+    /// Which keys were rotated, e.g. `NatsAccountSigning`
+    pub scope: String,
+    pub reason: String,
+    /// Keys in the order they were rotated
+    pub rotated: Vec<RotatedKeyReport>,
+    /// Keys selected for rotation but left unrotated
+    pub not_rotated: Vec<Uuid>,
+    /// Whether every selected key was rotated and the old keys revoked
+    pub succeeded: bool,
+    pub started_at: DateTime<Utc>,
+    pub reported_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// One key of an organization-wide rotation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RotatedKeyReport {
+    pub old_key_id: Uuid,
+    pub new_key_id: Uuid,
+    pub label: String,
+    /// Artifacts re-issued under the new key, as (old, replacement)
+    pub regenerated: Vec<(Uuid, Uuid)>,
+}
+
+/// A disaster recovery rehearsal finished
+///
+/// The rehearsal recovers the master seed from backups and re-derives
+/// keys in scratch memory only; nothing here reflects a change to real key
+/// material.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryRehearsalCompletedEvent {
+    /// The rehearsal saga
+    pub rehearsal_id: Uuid,
+    pub organization_id: Uuid,
+    /// Split the shares were issued by, when recovering from shares
+    pub split_id: Option<Uuid>,
+    /// IDs of the shares presented (never the share data)
+    pub share_ids: Vec<String>,
+    /// Keys re-derived from the recovered seed
+    pub keys_checked: usize,
+    /// Keys whose derived identity matched the manifest
+    pub keys_matched: usize,
+    pub discrepancies: Vec<RecoveryDiscrepancy>,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    pub performed_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A key the backups would not restore as recorded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum RecoveryDiscrepancy {
+    /// The recovered seed derives a different key at the recorded path
+    Mismatch { key_id: Uuid, path: String, expected: String, derived: String },
+    /// The key could not be derived at its recorded path
+    DerivationFailed { key_id: Uuid, path: String, error: String },
+    /// The manifest lists a key with no derivation path, so backups cannot restore it
+    NotDerivable { key_id: Uuid },
+}
+
 /// TOTP secret was generated
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TotpSecretGeneratedEvent {
@@ -190,6 +457,17 @@ impl DomainEvent for KeyEvents {
             KeyEvents::SshKeyGenerated(e) => e.key_id,
             KeyEvents::GpgKeyGenerated(e) => e.key_id,
             KeyEvents::TotpSecretGenerated(e) => e.secret_id,
+            KeyEvents::KeyExpirationExtended(e) => e.key_id,
+            KeyEvents::KeyMovedToCard(e) => e.key_id,
+            KeyEvents::GpgKeyCertified(e) => e.subject_key_id,
+            KeyEvents::SshCaDesignated(e) => e.ca_key_id,
+            KeyEvents::SshCertificateIssued(e) => e.certificate_id,
+            KeyEvents::MasterSeedSplit(e) => e.split_id,
+            KeyEvents::SeedShareAssigned(e) => e.split_id,
+            KeyEvents::MasterSeedRecovered(e) => e.split_id,
+            KeyEvents::MasterSeedDerived(e) => e.seed_id,
+            KeyEvents::KeyRotationReported(e) => e.rotation_id,
+            KeyEvents::RecoveryRehearsalCompleted(e) => e.rehearsal_id,
         }
     }
 
@@ -205,6 +483,17 @@ impl DomainEvent for KeyEvents {
             KeyEvents::SshKeyGenerated(_) => "SshKeyGenerated",
             KeyEvents::GpgKeyGenerated(_) => "GpgKeyGenerated",
             KeyEvents::TotpSecretGenerated(_) => "TotpSecretGenerated",
+            KeyEvents::KeyExpirationExtended(_) => "KeyExpirationExtended",
+            KeyEvents::KeyMovedToCard(_) => "KeyMovedToCard",
+            KeyEvents::GpgKeyCertified(_) => "GpgKeyCertified",
+            KeyEvents::SshCaDesignated(_) => "SshCaDesignated",
+            KeyEvents::SshCertificateIssued(_) => "SshCertificateIssued",
+            KeyEvents::MasterSeedSplit(_) => "MasterSeedSplit",
+            KeyEvents::SeedShareAssigned(_) => "SeedShareAssigned",
+            KeyEvents::MasterSeedRecovered(_) => "MasterSeedRecovered",
+            KeyEvents::MasterSeedDerived(_) => "MasterSeedDerived",
+            KeyEvents::KeyRotationReported(_) => "KeyRotationReported",
+            KeyEvents::RecoveryRehearsalCompleted(_) => "RecoveryRehearsalCompleted",
         }
     }
 }
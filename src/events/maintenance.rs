@@ -0,0 +1,156 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Maintenance Events
+//!
+//! Events announcing periodic maintenance work found by the
+//! `MaintenanceScheduler` (see `domain::maintenance`): certificates and
+//! keys nearing expiry, delegations about to lapse, CRLs due for
+//! republication and YubiKeys on firmware with a known advisory.
+//! Each piece of work is announced once with `MaintenanceDue`; every
+//! scan closes with `MaintenanceScanCompleted`.
+
+use chrono::{DateTime, Utc};
+use cim_domain::DomainEvent;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Events for scheduled maintenance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event_type")]
+pub enum MaintenanceEvents {
+    /// A piece of maintenance work became due
+    MaintenanceDue(MaintenanceDueEvent),
+
+    /// A maintenance scan finished
+    MaintenanceScanCompleted(MaintenanceScanCompletedEvent),
+}
+
+/// What needs doing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum MaintenanceWork {
+    /// Renew a certificate before `not_after`
+    CertificateExpiring {
+        cert_id: Uuid,
+        subject: String,
+        is_ca: bool,
+        not_after: DateTime<Utc>,
+    },
+    /// Rotate or extend a key before it expires
+    KeyExpiring {
+        key_id: Uuid,
+        label: String,
+        expires_at: DateTime<Utc>,
+    },
+    /// Extend or let lapse a delegation before `valid_until`
+    DelegationExpiring {
+        delegation_id: Uuid,
+        delegate_id: Uuid,
+        valid_until: DateTime<Utc>,
+    },
+    /// Publish a new CRL before `next_update`
+    CrlUpdateDue {
+        issuer_ca_id: Uuid,
+        crl_number: u64,
+        next_update: DateTime<Utc>,
+    },
+    /// Update or replace a YubiKey affected by a firmware advisory
+    YubiKeyFirmwareAdvisory {
+        yubikey_serial: String,
+        firmware_version: String,
+        advisory_id: String,
+        summary: String,
+    },
+}
+
+impl MaintenanceWork {
+    /// Identifies the work independently of when it was found
+    ///
+    /// Two scans finding the same certificate expiring at the same time
+    /// yield the same key; an extended delegation yields a new one.
+    pub fn dedup_key(&self) -> String {
+        match self {
+            MaintenanceWork::CertificateExpiring { cert_id, not_after, .. } => {
+                format!("certificate:{}:{}", cert_id, not_after.timestamp())
+            }
+            MaintenanceWork::KeyExpiring { key_id, expires_at, .. } => {
+                format!("key:{}:{}", key_id, expires_at.timestamp())
+            }
+            MaintenanceWork::DelegationExpiring { delegation_id, valid_until, .. } => {
+                format!("delegation:{}:{}", delegation_id, valid_until.timestamp())
+            }
+            MaintenanceWork::CrlUpdateDue { issuer_ca_id, crl_number, .. } => {
+                format!("crl:{}:{}", issuer_ca_id, crl_number)
+            }
+            MaintenanceWork::YubiKeyFirmwareAdvisory { yubikey_serial, advisory_id, .. } => {
+                format!("firmware:{}:{}", yubikey_serial, advisory_id)
+            }
+        }
+    }
+
+    /// When the work must be done by, if it has a deadline
+    pub fn due_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            MaintenanceWork::CertificateExpiring { not_after, .. } => Some(*not_after),
+            MaintenanceWork::KeyExpiring { expires_at, .. } => Some(*expires_at),
+            MaintenanceWork::DelegationExpiring { valid_until, .. } => Some(*valid_until),
+            MaintenanceWork::CrlUpdateDue { next_update, .. } => Some(*next_update),
+            MaintenanceWork::YubiKeyFirmwareAdvisory { .. } => None,
+        }
+    }
+}
+
+/// How pressing a piece of work is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MaintenanceUrgency {
+    /// No deadline; act at the next maintenance window
+    Advisory,
+    /// Deadline ahead, inside the scheduler's horizon
+    Upcoming,
+    /// Deadline already passed
+    Overdue,
+}
+
+/// A piece of maintenance work became due
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceDueEvent {
+    pub work_item_id: Uuid,
+    /// Scan that found the work
+    pub scan_id: Uuid,
+    pub work: MaintenanceWork,
+    pub urgency: MaintenanceUrgency,
+    pub due_at: Option<DateTime<Utc>>,
+    pub detected_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A maintenance scan finished
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceScanCompletedEvent {
+    pub scan_id: Uuid,
+    /// Work items announced by this scan
+    pub items_due: Vec<Uuid>,
+    /// Work found again that an earlier scan already announced
+    pub already_announced: usize,
+    pub scanned_at: DateTime<Utc>,
+    pub next_scan_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl DomainEvent for MaintenanceEvents {
+    fn aggregate_id(&self) -> Uuid {
+        match self {
+            MaintenanceEvents::MaintenanceDue(e) => e.work_item_id,
+            MaintenanceEvents::MaintenanceScanCompleted(e) => e.scan_id,
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        match self {
+            MaintenanceEvents::MaintenanceDue(_) => "MaintenanceDue",
+            MaintenanceEvents::MaintenanceScanCompleted(_) => "MaintenanceScanCompleted",
+        }
+    }
+}
@@ -23,6 +23,9 @@ pub enum ManifestEvents {
 
     /// Projection was applied
     ProjectionApplied(ProjectionAppliedEvent),
+
+    /// An export was written to primary and backup media and verified
+    RedundantExportCompleted(RedundantExportCompletedEvent),
 }
 
 /// A manifest was created
@@ -77,6 +80,31 @@ pub struct ProjectionAppliedEvent {
     pub causation_id: Option<Uuid>,
 }
 
+/// An export was written to primary and backup media and verified
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedundantExportCompletedEvent {
+    pub export_id: Uuid,
+    pub organization_name: String,
+    pub primary: ExportMediaRecord,
+    pub backup: ExportMediaRecord,
+    pub exported_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// One medium of a redundant export and its verification result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportMediaRecord {
+    /// Identifier of the medium (LUKS UUID, device serial or label)
+    pub media_id: String,
+    pub path: String,
+    pub files_written: usize,
+    /// Every file read back with the checksum it was exported with
+    pub verified: bool,
+    /// Files that were missing or differed when read back
+    pub mismatched_files: Vec<String>,
+}
+
 impl DomainEvent for ManifestEvents {
     fn aggregate_id(&self) -> Uuid {
         match self {
@@ -84,6 +112,7 @@ impl DomainEvent for ManifestEvents {
             ManifestEvents::ManifestUpdated(e) => e.manifest_id,
             ManifestEvents::JwksExported(e) => e.export_id,
             ManifestEvents::ProjectionApplied(e) => e.projection_id,
+            ManifestEvents::RedundantExportCompleted(e) => e.export_id,
         }
     }
 
@@ -93,6 +122,7 @@ impl DomainEvent for ManifestEvents {
             ManifestEvents::ManifestUpdated(_) => "ManifestUpdated",
             ManifestEvents::JwksExported(_) => "JwksExported",
             ManifestEvents::ProjectionApplied(_) => "ProjectionApplied",
+            ManifestEvents::RedundantExportCompleted(_) => "RedundantExportCompleted",
         }
     }
 }
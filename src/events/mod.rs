@@ -16,6 +16,10 @@
 //! - **YubiKey** - Hardware security module operations
 //! - **Relationship** - Connections between domain entities
 //! - **Manifest** - Export tracking and metadata
+//! - **ThresholdSigning** - FROST threshold key generation and signing rounds
+//! - **Approval** - M-of-N approval of sensitive commands
+//! - **Authorization** - Commands refused for missing claims
+//! - **Maintenance** - Scheduled maintenance work found due
 
 // Re-export shared domain ontologies for convenience
 pub use crate::types::*;
@@ -35,6 +39,12 @@ pub mod yubikey;
 pub mod relationship;
 pub mod manifest;
 pub mod saga;
+pub mod threshold_signing;
+pub mod approval;
+pub mod authorization;
+pub mod maintenance;
+pub mod signing;
+pub mod query;
 
 // Re-export all aggregate event enums at module level for convenience
 pub use person::PersonEvents;
@@ -51,19 +61,25 @@ pub use yubikey::YubiKeyEvents;
 pub use relationship::RelationshipEvents;
 pub use manifest::ManifestEvents;
 pub use saga::SagaEvents;
+pub use threshold_signing::ThresholdSigningEvents;
+pub use approval::ApprovalEvents;
+pub use authorization::AuthorizationEvents;
+pub use maintenance::MaintenanceEvents;
+pub use signing::{EventSignature, EventSigner, EventVerifier, EventSignatureError};
+pub use query::{causal_order, CausationNode, CausationTree, EnvelopeQuery};
 
 // Re-export delegation event types
 pub use delegation::{
-    DelegationCreatedEvent, DelegationRevokedEvent, DelegationCascadeRevokedEvent,
+    DelegationCreatedEvent, DelegationRevokedEvent, DelegationCascadeRevokedEvent, DelegationExpiredEvent,
     RevocationReason as DelegationRevocationReason,
 };
 
 // Re-export commonly used event structs for convenience
 // This allows using crate::events::CertificateGeneratedEvent instead of
 // crate::events::certificate::CertificateGeneratedEvent
-pub use certificate::{CertificateGeneratedEvent, CertificateSignedEvent, CertificateRenewedEvent, PkiHierarchyCreatedEvent};
+pub use certificate::{CertificateGeneratedEvent, CertificateSignedEvent, CertificateRenewedEvent, PkiHierarchyCreatedEvent, CertificateRevokedEvent, CrlPublishedEvent, CertificateIssuanceRejectedEvent};
 pub use yubikey::{YubiKeyProvisionedEvent, YubiKeyDetectedEvent};
-pub use key::{KeyGeneratedEvent, KeyRevokedEvent, KeyStoredOfflineEvent};
+pub use key::{KeyGeneratedEvent, KeyRevokedEvent, KeyStoredOfflineEvent, KeyRotationReportedEvent, RotatedKeyReport, RecoveryRehearsalCompletedEvent, RecoveryDiscrepancy};
 
 use serde::{Deserialize, Serialize};
 
@@ -96,6 +112,64 @@ pub enum DomainEvent {
     Relationship(RelationshipEvents),
     Manifest(ManifestEvents),
     Saga(SagaEvents),
+    ThresholdSigning(ThresholdSigningEvents),
+    Approval(ApprovalEvents),
+    Authorization(AuthorizationEvents),
+    Maintenance(MaintenanceEvents),
+}
+
+impl DomainEvent {
+    /// Name of the aggregate this event belongs to
+    pub fn aggregate_type(&self) -> &'static str {
+        match self {
+            DomainEvent::Person(_) => "Person",
+            DomainEvent::Organization(_) => "Organization",
+            DomainEvent::Location(_) => "Location",
+            DomainEvent::Certificate(_) => "Certificate",
+            DomainEvent::CertificateImport(_) => "CertificateImport",
+            DomainEvent::Key(_) => "Key",
+            DomainEvent::Delegation(_) => "Delegation",
+            DomainEvent::NatsOperator(_) => "NatsOperator",
+            DomainEvent::NatsAccount(_) => "NatsAccount",
+            DomainEvent::NatsUser(_) => "NatsUser",
+            DomainEvent::YubiKey(_) => "YubiKey",
+            DomainEvent::Relationship(_) => "Relationship",
+            DomainEvent::Manifest(_) => "Manifest",
+            DomainEvent::Saga(_) => "Saga",
+            DomainEvent::ThresholdSigning(_) => "ThresholdSigning",
+            DomainEvent::Approval(_) => "Approval",
+            DomainEvent::Authorization(_) => "Authorization",
+            DomainEvent::Maintenance(_) => "Maintenance",
+        }
+    }
+
+    /// Organization the event names, for events that carry one
+    ///
+    /// Events without an organization (most key, certificate and NATS
+    /// account events) belong to whichever organization's stream they are in.
+    pub fn organization_id(&self) -> Option<uuid::Uuid> {
+        match self {
+            DomainEvent::Organization(event) => match event {
+                OrganizationEvents::OrganizationCreated(e) => Some(e.organization_id),
+                OrganizationEvents::OrganizationUpdated(e) => Some(e.organization_id),
+                OrganizationEvents::OrganizationalUnitCreated(e) => Some(e.organization_id),
+                OrganizationEvents::RoleCreated(e) => e.organization_id,
+                OrganizationEvents::PolicyCreated(e) => e.organization_id,
+                OrganizationEvents::OrganizationActivated(e) => Some(e.organization_id),
+                OrganizationEvents::OrganizationSuspended(e) => Some(e.organization_id),
+                OrganizationEvents::OrganizationDissolved(e) => Some(e.organization_id),
+                _ => None,
+            },
+            DomainEvent::Person(PersonEvents::PersonCreated(e)) => Some(e.organization_id),
+            DomainEvent::Location(LocationEvents::LocationCreated(e)) => e.organization_id,
+            DomainEvent::Certificate(CertificateEvents::PkiHierarchyCreated(e)) => Some(e.organization_id),
+            DomainEvent::NatsOperator(NatsOperatorEvents::NatsOperatorCreated(e)) => e.organization_id,
+            DomainEvent::NatsUser(NatsUserEvents::AgentCreated(e)) => Some(e.organization_id),
+            DomainEvent::Manifest(ManifestEvents::ManifestCreated(e)) => Some(e.organization_id),
+            DomainEvent::Manifest(ManifestEvents::JwksExported(e)) => Some(e.organization_id),
+            _ => None,
+        }
+    }
 }
 
 /// Event envelope that wraps domain events with routing and correlation metadata
@@ -106,6 +180,7 @@ pub enum DomainEvent {
 /// - **NATS routing**: Specify the subject for event publication
 /// - **Temporal ordering**: Events have a timestamp for ordering
 /// - **Content addressing**: Optional CID for immutable event identity (with `ipld` feature)
+/// - **Authenticity**: Optional Ed25519 signature by an operator or ceremony key
 ///
 /// # NATS Subject Patterns
 ///
@@ -170,6 +245,11 @@ pub struct EventEnvelope {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub domain_cid: Option<String>,
 
+    /// Ed25519 signature over the rest of the envelope
+    /// Added by an `EventSigner`; checked with an `EventVerifier` on load.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<EventSignature>,
+
     /// The wrapped domain event
     #[serde(flatten)]
     pub event: DomainEvent,
@@ -190,6 +270,7 @@ impl EventEnvelope {
             timestamp: chrono::Utc::now(),
             cid: None,
             domain_cid: None,
+            signature: None,
             event,
         }
     }
@@ -286,6 +367,11 @@ impl EventEnvelope {
         self.domain_cid.as_deref().or(self.cid.as_deref())
     }
 
+    /// Check if this envelope carries a signature
+    pub fn is_signed(&self) -> bool {
+        self.signature.is_some()
+    }
+
     /// Set a custom NATS subject for this event
     pub fn with_subject(mut self, subject: impl Into<String>) -> Self {
         self.nats_subject = subject.into();
@@ -315,27 +401,16 @@ impl EventEnvelope {
             DomainEvent::Relationship(_) => "cim.relationship.event".to_string(),
             DomainEvent::Manifest(_) => "cim.manifest.event".to_string(),
             DomainEvent::Saga(e) => format!("cim.{}", e.event_type()),
+            DomainEvent::ThresholdSigning(_) => "cim.threshold.signing.event".to_string(),
+            DomainEvent::Approval(_) => "cim.approval.event".to_string(),
+            DomainEvent::Authorization(_) => "cim.authorization.event".to_string(),
+            DomainEvent::Maintenance(_) => "cim.maintenance.event".to_string(),
         }
     }
 
     /// Get the aggregate type from the event
     pub fn aggregate_type(&self) -> &'static str {
-        match &self.event {
-            DomainEvent::Person(_) => "Person",
-            DomainEvent::Organization(_) => "Organization",
-            DomainEvent::Location(_) => "Location",
-            DomainEvent::Certificate(_) => "Certificate",
-            DomainEvent::CertificateImport(_) => "CertificateImport",
-            DomainEvent::Key(_) => "Key",
-            DomainEvent::Delegation(_) => "Delegation",
-            DomainEvent::NatsOperator(_) => "NatsOperator",
-            DomainEvent::NatsAccount(_) => "NatsAccount",
-            DomainEvent::NatsUser(_) => "NatsUser",
-            DomainEvent::YubiKey(_) => "YubiKey",
-            DomainEvent::Relationship(_) => "Relationship",
-            DomainEvent::Manifest(_) => "Manifest",
-            DomainEvent::Saga(_) => "Saga",
-        }
+        self.event.aggregate_type()
     }
 
     /// Check if this event is part of the same correlation chain
@@ -10,6 +10,7 @@ use chrono::{DateTime, Utc};
 
 // Import shared types from legacy module
 use crate::types::NatsPermissions;
+use crate::domain::nats::{AccountLimits, ClaimExport, ClaimImport};
 
 /// Events for the NATS Account aggregate
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +25,18 @@ pub enum NatsAccountEvents {
     /// NATS account permissions were set
     NatsPermissionsSet(NatsPermissionsSetEvent),
 
+    /// NATS account limits and JetStream quotas were set
+    NatsAccountLimitsSet(NatsAccountLimitsSetEvent),
+
+    /// A user of the account was revoked
+    NatsUserRevoked(NatsUserRevokedEvent),
+
+    /// The account shares subjects with other accounts
+    NatsAccountExportDeclared(NatsAccountExportDeclaredEvent),
+
+    /// The account uses subjects exported by another account
+    NatsAccountImportDeclared(NatsAccountImportDeclaredEvent),
+
     /// NATS account was suspended
     NatsAccountSuspended(NatsAccountSuspendedEvent),
 
@@ -77,6 +90,68 @@ pub struct NatsPermissionsSetEvent {
     pub causation_id: Option<Uuid>,
 }
 
+/// NATS account limits were set and the account JWT re-signed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsAccountLimitsSetEvent {
+    pub account_id: Uuid,
+    pub public_key: String,
+    pub limits: AccountLimits,
+    /// Account JWT carrying the limits, signed by the operator
+    pub account_jwt: String,
+    pub set_at: DateTime<Utc>,
+    pub set_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A user was added to the account's revocation list
+///
+/// Belongs to the issuing account: the account JWT is re-signed with the
+/// updated `revocations` map so servers reject the user's JWT.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsUserRevokedEvent {
+    pub account_id: Uuid,
+    pub account_public_key: String,
+    pub user_id: Uuid,
+    pub user_public_key: String,
+    pub reason: String,
+    /// User JWTs issued at or before this time are revoked
+    pub revoked_at: DateTime<Utc>,
+    pub revoked_by: String,
+    /// Account JWT carrying the revocation, signed by the operator
+    pub account_jwt: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The account shares subjects with other accounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsAccountExportDeclaredEvent {
+    pub account_id: Uuid,
+    pub public_key: String,
+    pub export: ClaimExport,
+    /// Account JWT carrying the export, signed by the operator
+    pub account_jwt: String,
+    pub declared_at: DateTime<Utc>,
+    pub declared_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The account uses subjects exported by another account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsAccountImportDeclaredEvent {
+    pub account_id: Uuid,
+    pub public_key: String,
+    pub import: ClaimImport,
+    /// Account JWT carrying the import, signed by the operator
+    pub account_jwt: String,
+    pub declared_at: DateTime<Utc>,
+    pub declared_by: String,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
 /// NATS account was suspended
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NatsAccountSuspendedEvent {
@@ -127,6 +202,10 @@ impl DomainEvent for NatsAccountEvents {
             NatsAccountEvents::NatsAccountCreated(e) => e.account_id,
             NatsAccountEvents::NatsAccountUpdated(e) => e.account_id,
             NatsAccountEvents::NatsPermissionsSet(e) => e.account_id,
+            NatsAccountEvents::NatsAccountLimitsSet(e) => e.account_id,
+            NatsAccountEvents::NatsUserRevoked(e) => e.account_id,
+            NatsAccountEvents::NatsAccountExportDeclared(e) => e.account_id,
+            NatsAccountEvents::NatsAccountImportDeclared(e) => e.account_id,
             NatsAccountEvents::NatsAccountSuspended(e) => e.account_id,
             NatsAccountEvents::NatsAccountReactivated(e) => e.account_id,
             NatsAccountEvents::NatsAccountActivated(e) => e.account_id,
@@ -139,6 +218,10 @@ impl DomainEvent for NatsAccountEvents {
             NatsAccountEvents::NatsAccountCreated(_) => "NatsAccountCreated",
             NatsAccountEvents::NatsAccountUpdated(_) => "NatsAccountUpdated",
             NatsAccountEvents::NatsPermissionsSet(_) => "NatsPermissionsSet",
+            NatsAccountEvents::NatsAccountLimitsSet(_) => "NatsAccountLimitsSet",
+            NatsAccountEvents::NatsUserRevoked(_) => "NatsUserRevoked",
+            NatsAccountEvents::NatsAccountExportDeclared(_) => "NatsAccountExportDeclared",
+            NatsAccountEvents::NatsAccountImportDeclared(_) => "NatsAccountImportDeclared",
             NatsAccountEvents::NatsAccountSuspended(_) => "NatsAccountSuspended",
             NatsAccountEvents::NatsAccountReactivated(_) => "NatsAccountReactivated",
             NatsAccountEvents::NatsAccountActivated(_) => "NatsAccountActivated",
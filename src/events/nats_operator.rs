@@ -24,6 +24,9 @@ pub enum NatsOperatorEvents {
     /// NATS signing key was generated
     NatsSigningKeyGenerated(NatsSigningKeyGeneratedEvent),
 
+    /// NATS signing key was retired
+    NatsSigningKeyRetired(NatsSigningKeyRetiredEvent),
+
     /// NATS configuration was exported
     NatsConfigExported(NatsConfigExportedEvent),
 
@@ -89,6 +92,22 @@ pub struct NatsSigningKeyGeneratedEvent {
     pub causation_id: Option<Uuid>,
 }
 
+/// NATS signing key was retired
+///
+/// The issuer JWT no longer lists the key; JWTs it signed have been
+/// re-issued under `replaced_by`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsSigningKeyRetiredEvent {
+    pub entity_id: Uuid,
+    pub entity_type: NatsEntityType,
+    pub public_key: String,
+    pub replaced_by: Option<String>,
+    pub reason: String,
+    pub retired_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
 /// NATS configuration was exported
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NatsConfigExportedEvent {
@@ -102,12 +121,14 @@ pub struct NatsConfigExportedEvent {
 }
 
 /// NKey was generated
+///
+/// Carries only the public key; the seed is kept wrapped in storage
+/// (`crypto::nkey_store`).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NKeyGeneratedEvent {
     pub nkey_id: Uuid,
     pub key_type: String,
     pub public_key: String,
-    pub seed: String,
     pub purpose: String,
     pub expires_at: Option<DateTime<Utc>>,
     pub generated_at: DateTime<Utc>,
@@ -205,6 +226,7 @@ impl DomainEvent for NatsOperatorEvents {
             NatsOperatorEvents::NatsOperatorCreated(e) => e.operator_id,
             NatsOperatorEvents::NatsOperatorUpdated(e) => e.operator_id,
             NatsOperatorEvents::NatsSigningKeyGenerated(e) => e.entity_id,
+            NatsOperatorEvents::NatsSigningKeyRetired(e) => e.entity_id,
             NatsOperatorEvents::NatsConfigExported(e) => e.operator_id,
             NatsOperatorEvents::NKeyGenerated(e) => e.nkey_id,
             NatsOperatorEvents::JwtClaimsCreated(e) => e.claims_id,
@@ -222,6 +244,7 @@ impl DomainEvent for NatsOperatorEvents {
             NatsOperatorEvents::NatsOperatorCreated(_) => "NatsOperatorCreated",
             NatsOperatorEvents::NatsOperatorUpdated(_) => "NatsOperatorUpdated",
             NatsOperatorEvents::NatsSigningKeyGenerated(_) => "NatsSigningKeyGenerated",
+            NatsOperatorEvents::NatsSigningKeyRetired(_) => "NatsSigningKeyRetired",
             NatsOperatorEvents::NatsConfigExported(_) => "NatsConfigExported",
             NatsOperatorEvents::NKeyGenerated(_) => "NKeyGenerated",
             NatsOperatorEvents::JwtClaimsCreated(_) => "JwtClaimsCreated",
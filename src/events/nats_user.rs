@@ -44,6 +44,12 @@ pub enum NatsUserEvents {
 
     /// TOTP secret was generated for user
     TotpSecretGenerated(TotpSecretGeneratedEvent),
+
+    /// A person's .creds files were delivered in an encrypted bundle
+    NatsCredentialsDelivered(NatsCredentialsDeliveredEvent),
+
+    /// Leafnode credentials and TLS material were issued for an edge site
+    EdgeSiteCredentialsIssued(EdgeSiteCredentialsIssuedEvent),
 }
 
 /// A new NATS user was created
@@ -162,6 +168,50 @@ pub struct TotpSecretGeneratedEvent {
     pub causation_id: Option<Uuid>,
 }
 
+/// A person's .creds files were delivered in an encrypted bundle
+///
+/// Records who received which users and how the bundle was protected;
+/// never the seeds, JWTs or passphrase themselves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsCredentialsDeliveredEvent {
+    pub delivery_id: Uuid,
+    pub person_id: Uuid,
+    /// Public keys of the users whose credentials were delivered
+    pub user_public_keys: Vec<String>,
+    /// "recipients" or "passphrase"
+    pub protection: String,
+    /// age recipients able to open the bundle (empty for a passphrase)
+    pub recipients: Vec<String>,
+    /// SHA-256 over the encrypted files of the bundle
+    pub bundle_checksum: String,
+    pub delivered_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// Leafnode credentials and TLS material were issued for an edge site
+///
+/// Ties the leafnode user and the site's server certificate to the
+/// Location they were issued for. Carries no seeds or private keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeSiteCredentialsIssuedEvent {
+    pub user_id: Uuid,
+    pub location_id: Uuid,
+    pub location_name: String,
+    /// Account the leafnode connection binds to
+    pub account_id: Uuid,
+    pub account_public_key: String,
+    pub user_public_key: String,
+    pub certificate_id: Uuid,
+    /// SHA-256 fingerprint of the site's server certificate
+    pub certificate_fingerprint: String,
+    /// DNS names and addresses the certificate is valid for
+    pub server_names: Vec<String>,
+    pub issued_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
 impl DomainEvent for NatsUserEvents {
     fn aggregate_id(&self) -> Uuid {
         match self {
@@ -175,6 +225,8 @@ impl DomainEvent for NatsUserEvents {
             NatsUserEvents::NatsUserActivated(e) => e.user_id,
             NatsUserEvents::NatsUserDeleted(e) => e.user_id,
             NatsUserEvents::TotpSecretGenerated(e) => e.user_id,
+            NatsUserEvents::NatsCredentialsDelivered(e) => e.delivery_id,
+            NatsUserEvents::EdgeSiteCredentialsIssued(e) => e.user_id,
         }
     }
 
@@ -190,6 +242,8 @@ impl DomainEvent for NatsUserEvents {
             NatsUserEvents::NatsUserActivated(_) => "NatsUserActivated",
             NatsUserEvents::NatsUserDeleted(_) => "NatsUserDeleted",
             NatsUserEvents::TotpSecretGenerated(_) => "TotpSecretGenerated",
+            NatsUserEvents::NatsCredentialsDelivered(_) => "NatsCredentialsDelivered",
+            NatsUserEvents::EdgeSiteCredentialsIssued(_) => "EdgeSiteCredentialsIssued",
         }
     }
 }
@@ -67,6 +67,9 @@ pub enum OrganizationEvents {
 
     /// Policy suspended
     PolicySuspended(PolicySuspendedEvent),
+
+    /// A new version of a policy was recorded
+    PolicySuperseded(PolicySupersededEvent),
 }
 
 /// A new organization was created
@@ -274,6 +277,21 @@ pub struct PolicySuspendedEvent {
     pub causation_id: Option<Uuid>,
 }
 
+/// A new version of a policy was recorded
+///
+/// The successor is stored in full; earlier versions stay unchanged and
+/// remain in force until the successor's `effective_from`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicySupersededEvent {
+    pub policy_id: Uuid,
+    pub superseded_version: u32,
+    pub successor: crate::domain::Policy,
+    pub superseded_at: DateTime<Utc>,
+    pub superseded_by: ActorId,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
 impl DomainEvent for OrganizationEvents {
     fn aggregate_id(&self) -> Uuid {
         match self {
@@ -294,6 +312,7 @@ impl DomainEvent for OrganizationEvents {
             OrganizationEvents::PolicyActivated(e) => e.policy_id,
             OrganizationEvents::PolicyAmended(e) => e.policy_id,
             OrganizationEvents::PolicySuspended(e) => e.policy_id,
+            OrganizationEvents::PolicySuperseded(e) => e.policy_id,
         }
     }
 
@@ -316,6 +335,7 @@ impl DomainEvent for OrganizationEvents {
             OrganizationEvents::PolicyActivated(_) => "PolicyActivated",
             OrganizationEvents::PolicyAmended(_) => "PolicyAmended",
             OrganizationEvents::PolicySuspended(_) => "PolicySuspended",
+            OrganizationEvents::PolicySuperseded(_) => "PolicySuperseded",
         }
     }
 }
@@ -82,6 +82,9 @@ pub struct RoleAssignedEvent {
     pub person_id: Uuid,
     pub role_id: Uuid,
     pub role_name: String,
+    /// Policies the role requires, whose claims the person gains
+    #[serde(default)]
+    pub required_policies: Vec<Uuid>,
     pub assigned_at: DateTime<Utc>,
     pub assigned_by: Uuid,
     pub correlation_id: Uuid,
@@ -0,0 +1,288 @@
+//! Correlation-aware queries over stored envelopes
+//!
+//! Selects envelopes by correlation, aggregate type and time window, and
+//! orders them causally: an event always comes after the event named by
+//! its `causation_id`, with timestamps deciding between unrelated events.
+//! [`CausationTree`] keeps the parent/child structure for forensics views.
+//!
+//! ```text
+//! CertificateGenerated (root: caused by a command)
+//! ├── KeyStoredOffline
+//! └── YubiKeyProvisioned
+//!     └── NatsUserCreated
+//! ```
+//!
+//! Queries are pure; load the envelopes from a store first, e.g. with
+//! `CidEventStore::query`.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::EventEnvelope;
+
+/// Filter over envelopes; unset criteria match everything
+#[derive(Debug, Clone, Default)]
+pub struct EnvelopeQuery {
+    pub correlation_id: Option<Uuid>,
+    /// Aggregate types as named by [`EventEnvelope::aggregate_type`]
+    pub aggregate_types: Vec<String>,
+    /// Inclusive lower bound on the envelope timestamp
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on the envelope timestamp
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl EnvelopeQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only events of this business process
+    pub fn with_correlation(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Only events of this aggregate type; repeat to allow several
+    pub fn with_aggregate_type(mut self, aggregate_type: impl Into<String>) -> Self {
+        self.aggregate_types.push(aggregate_type.into());
+        self
+    }
+
+    /// Only events at or after `time`
+    pub fn from_time(mut self, time: DateTime<Utc>) -> Self {
+        self.from = Some(time);
+        self
+    }
+
+    /// Only events before `time`
+    pub fn to_time(mut self, time: DateTime<Utc>) -> Self {
+        self.to = Some(time);
+        self
+    }
+
+    /// Check a single envelope against the filter
+    pub fn matches(&self, envelope: &EventEnvelope) -> bool {
+        self.correlation_id.is_none_or(|id| envelope.correlation_id == id)
+            && (self.aggregate_types.is_empty()
+                || self.aggregate_types.iter().any(|t| t == envelope.aggregate_type()))
+            && self.from.is_none_or(|from| envelope.timestamp >= from)
+            && self.to.is_none_or(|to| envelope.timestamp < to)
+    }
+
+    /// Matching envelopes in causal order
+    pub fn run(&self, envelopes: impl IntoIterator<Item = EventEnvelope>) -> Vec<EventEnvelope> {
+        causal_order(envelopes.into_iter().filter(|e| self.matches(e)).collect())
+    }
+}
+
+/// Sort envelopes so every event follows the event that caused it
+///
+/// Among events whose causes are already placed, the earliest timestamp
+/// goes first. A cause outside the set (a command, or an event filtered
+/// out) does not hold its effects back. Envelopes caught in a causation
+/// cycle, which only a corrupted log can contain, go last in timestamp
+/// order.
+pub fn causal_order(envelopes: Vec<EventEnvelope>) -> Vec<EventEnvelope> {
+    let ids: HashSet<Uuid> = envelopes.iter().map(|e| e.event_id).collect();
+    let mut children: HashMap<Uuid, Vec<usize>> = HashMap::new();
+    let mut ready = BinaryHeap::new();
+    for (i, envelope) in envelopes.iter().enumerate() {
+        match envelope.causation_id.filter(|cause| ids.contains(cause) && *cause != envelope.event_id) {
+            Some(cause) => children.entry(cause).or_default().push(i),
+            None => ready.push(Reverse((envelope.timestamp, envelope.event_id, i))),
+        }
+    }
+
+    let mut placed = vec![false; envelopes.len()];
+    let mut order = Vec::with_capacity(envelopes.len());
+    while let Some(Reverse((_, event_id, i))) = ready.pop() {
+        placed[i] = true;
+        order.push(i);
+        for &child in children.get(&event_id).into_iter().flatten() {
+            ready.push(Reverse((envelopes[child].timestamp, envelopes[child].event_id, child)));
+        }
+    }
+
+    let mut cyclic: Vec<usize> = (0..envelopes.len()).filter(|&i| !placed[i]).collect();
+    cyclic.sort_by_key(|&i| (envelopes[i].timestamp, envelopes[i].event_id));
+    order.extend(cyclic);
+
+    let mut slots: Vec<Option<EventEnvelope>> = envelopes.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+/// Envelopes arranged by causation
+#[derive(Debug, Clone, Default)]
+pub struct CausationTree {
+    /// Events whose cause is not in the tree, earliest first
+    pub roots: Vec<CausationNode>,
+}
+
+/// One event and the events it caused, earliest first
+#[derive(Debug, Clone)]
+pub struct CausationNode {
+    pub envelope: EventEnvelope,
+    pub children: Vec<CausationNode>,
+}
+
+impl CausationTree {
+    /// Build the tree of `envelopes`
+    ///
+    /// A causation cycle is cut at its earliest event, which becomes a
+    /// root, so no envelope is dropped.
+    pub fn build(envelopes: Vec<EventEnvelope>) -> Self {
+        let ordered = causal_order(envelopes);
+        let position: HashMap<Uuid, usize> = ordered.iter().enumerate().map(|(i, e)| (e.event_id, i)).collect();
+
+        // A parent always precedes its children in causal order, so
+        // attaching nodes from the back builds each subtree before its parent
+        let mut nodes: Vec<Option<CausationNode>> = Vec::with_capacity(ordered.len());
+        let mut parents = Vec::with_capacity(ordered.len());
+        for (i, envelope) in ordered.into_iter().enumerate() {
+            parents.push(
+                envelope
+                    .causation_id
+                    .and_then(|cause| position.get(&cause).copied())
+                    .filter(|&parent| parent < i),
+            );
+            nodes.push(Some(CausationNode { envelope, children: Vec::new() }));
+        }
+
+        let mut roots = Vec::new();
+        for i in (0..nodes.len()).rev() {
+            let Some(node) = nodes[i].take() else { continue };
+            match parents[i] {
+                Some(parent) => {
+                    if let Some(parent) = nodes[parent].as_mut() {
+                        parent.children.insert(0, node);
+                    }
+                }
+                None => roots.insert(0, node),
+            }
+        }
+
+        Self { roots }
+    }
+
+    /// Number of events in the tree
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Events depth first, each with its depth (roots at 0)
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &EventEnvelope)> {
+        let mut stack: Vec<(usize, &CausationNode)> = self.roots.iter().rev().map(|n| (0, n)).collect();
+        std::iter::from_fn(move || {
+            let (depth, node) = stack.pop()?;
+            stack.extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+            Some((depth, &node.envelope))
+        })
+    }
+
+    /// Find the node of an event
+    pub fn find(&self, event_id: Uuid) -> Option<&CausationNode> {
+        let mut stack: Vec<&CausationNode> = self.roots.iter().collect();
+        while let Some(node) = stack.pop() {
+            if node.envelope.event_id == event_id {
+                return Some(node);
+            }
+            stack.extend(node.children.iter());
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{DomainEvent, KeyEvents, KeyStoredOfflineEvent};
+    use chrono::Duration;
+
+    fn envelope(correlation_id: Uuid, causation_id: Option<Uuid>, at: DateTime<Utc>) -> EventEnvelope {
+        let mut envelope = EventEnvelope::new(
+            DomainEvent::Key(KeyEvents::KeyStoredOffline(KeyStoredOfflineEvent {
+                key_id: Uuid::now_v7(),
+                partition_id: Uuid::now_v7(),
+                encrypted: true,
+                stored_at: at,
+                checksum: "sha256:00".to_string(),
+                correlation_id,
+                causation_id,
+            })),
+            correlation_id,
+            causation_id,
+        );
+        envelope.timestamp = at;
+        envelope
+    }
+
+    #[test]
+    fn test_effects_follow_causes_despite_clock_skew() {
+        let (correlation_id, t0) = (Uuid::now_v7(), Utc::now());
+        let root = envelope(correlation_id, Some(Uuid::now_v7()), t0);
+        // Written on a machine whose clock runs behind
+        let child = envelope(correlation_id, Some(root.event_id), t0 - Duration::seconds(30));
+        let grandchild = envelope(correlation_id, Some(child.event_id), t0 + Duration::seconds(1));
+        let sibling = envelope(correlation_id, Some(root.event_id), t0 + Duration::seconds(5));
+        let unrelated = envelope(Uuid::now_v7(), None, t0 + Duration::seconds(2));
+
+        let all = vec![grandchild.clone(), sibling.clone(), unrelated, child.clone(), root.clone()];
+        let ids: Vec<Uuid> = EnvelopeQuery::new()
+            .with_correlation(correlation_id)
+            .run(all)
+            .iter()
+            .map(|e| e.event_id)
+            .collect();
+        assert_eq!(ids, vec![root.event_id, child.event_id, grandchild.event_id, sibling.event_id]);
+    }
+
+    #[test]
+    fn test_aggregate_type_and_time_window() {
+        let (correlation_id, t0) = (Uuid::now_v7(), Utc::now());
+        let events = vec![
+            envelope(correlation_id, None, t0 - Duration::hours(2)),
+            envelope(correlation_id, None, t0),
+            envelope(correlation_id, None, t0 + Duration::hours(2)),
+        ];
+
+        let window = EnvelopeQuery::new()
+            .with_aggregate_type("Key")
+            .from_time(t0 - Duration::hours(1))
+            .to_time(t0 + Duration::hours(1));
+        let matched = window.run(events.clone());
+        assert_eq!(matched.len(), 1);
+        assert_eq!(matched[0].event_id, events[1].event_id);
+
+        assert!(EnvelopeQuery::new().with_aggregate_type("Certificate").run(events).is_empty());
+    }
+
+    #[test]
+    fn test_causation_tree() {
+        let (correlation_id, t0) = (Uuid::now_v7(), Utc::now());
+        let root = envelope(correlation_id, None, t0);
+        let first = envelope(correlation_id, Some(root.event_id), t0 + Duration::seconds(1));
+        let second = envelope(correlation_id, Some(root.event_id), t0 + Duration::seconds(2));
+        let nested = envelope(correlation_id, Some(first.event_id), t0 + Duration::seconds(3));
+
+        // A corrupted pair naming each other as cause is cut, not dropped
+        let mut looped_a = envelope(correlation_id, None, t0 + Duration::seconds(4));
+        let looped_b = envelope(correlation_id, Some(looped_a.event_id), t0 + Duration::seconds(5));
+        looped_a.causation_id = Some(looped_b.event_id);
+
+        let tree = CausationTree::build(vec![nested.clone(), second.clone(), looped_b, first.clone(), root.clone(), looped_a]);
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.roots[0].envelope.event_id, root.event_id);
+
+        let walk: Vec<(usize, Uuid)> = tree.iter().take(4).map(|(depth, e)| (depth, e.event_id)).collect();
+        assert_eq!(walk, vec![(0, root.event_id), (1, first.event_id), (2, nested.event_id), (1, second.event_id)]);
+        assert_eq!(tree.find(first.event_id).unwrap().children.len(), 1);
+    }
+}
@@ -12,6 +12,7 @@
 //! - **Progress**: StepStarted, StepCompleted, StepFailed
 //! - **Compensation**: CompensationStarted, CompensationCompleted
 //! - **Recovery**: SagaResumed, SagaRecovered
+//! - **Supervision**: StepHeartbeat, StepStalled
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -53,6 +54,12 @@ pub enum SagaEvents {
 
     /// Saga was recovered after failure
     SagaRecovered(SagaRecoveredEvent),
+
+    /// A long-running step reported that it is still alive
+    StepHeartbeat(StepHeartbeatEvent),
+
+    /// A step passed its deadline or stopped sending heartbeats
+    StepStalled(StepStalledEvent),
 }
 
 /// A saga was started
@@ -283,6 +290,55 @@ pub struct SagaRecoveredEvent {
     pub recovery_action: RecoveryAction,
 }
 
+/// A long-running step is still alive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepHeartbeatEvent {
+    /// Saga ID
+    pub saga_id: Uuid,
+    /// Step being waited on
+    pub step_name: String,
+    /// Correlation ID
+    pub correlation_id: Uuid,
+    /// When the heartbeat was received
+    pub heartbeat_at: DateTime<Utc>,
+    /// When the step times out
+    pub deadline: DateTime<Utc>,
+    /// What the step is waiting for, e.g. "waiting for YubiKey insertion"
+    pub detail: Option<String>,
+}
+
+/// A step stalled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepStalledEvent {
+    /// Saga ID
+    pub saga_id: Uuid,
+    /// Saga type
+    pub saga_type: String,
+    /// Step that stalled
+    pub step_name: String,
+    /// Correlation ID
+    pub correlation_id: Uuid,
+    /// When the stall was detected
+    pub stalled_at: DateTime<Utc>,
+    /// When the step started
+    pub step_started_at: DateTime<Utc>,
+    /// When the step was due
+    pub deadline: DateTime<Utc>,
+    /// Last heartbeat received, if any
+    pub last_heartbeat_at: Option<DateTime<Utc>>,
+    /// Why the step is considered stalled
+    pub reason: StallReason,
+}
+
+/// Why a step is considered stalled
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum StallReason {
+    /// The step ran past its deadline
+    DeadlineExceeded,
+    /// The step stopped sending heartbeats
+    HeartbeatsMissed,
+}
+
 /// Action taken during saga recovery
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum RecoveryAction {
@@ -311,6 +367,8 @@ impl SagaEvents {
             SagaEvents::CompensationCompleted(_) => "saga.compensation.completed",
             SagaEvents::SagaResumed(_) => "saga.resumed",
             SagaEvents::SagaRecovered(_) => "saga.recovered",
+            SagaEvents::StepHeartbeat(_) => "saga.step.heartbeat",
+            SagaEvents::StepStalled(_) => "saga.step.stalled",
         }
     }
 
@@ -328,6 +386,8 @@ impl SagaEvents {
             SagaEvents::CompensationCompleted(e) => e.saga_id,
             SagaEvents::SagaResumed(e) => e.saga_id,
             SagaEvents::SagaRecovered(e) => e.saga_id,
+            SagaEvents::StepHeartbeat(e) => e.saga_id,
+            SagaEvents::StepStalled(e) => e.saga_id,
         }
     }
 
@@ -345,6 +405,8 @@ impl SagaEvents {
             SagaEvents::CompensationCompleted(e) => e.correlation_id,
             SagaEvents::SagaResumed(e) => e.correlation_id,
             SagaEvents::SagaRecovered(e) => e.correlation_id,
+            SagaEvents::StepHeartbeat(e) => e.correlation_id,
+            SagaEvents::StepStalled(e) => e.correlation_id,
         }
     }
 }
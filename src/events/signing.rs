@@ -0,0 +1,288 @@
+//! Event envelope signing
+//!
+//! Envelopes are signed with an Ed25519 operator or ceremony key when they
+//! are written and verified when they are loaded, so an event edited after
+//! the fact — in an offline projection carried between machines, or in the
+//! event stream itself — is rejected instead of replayed.
+//!
+//! ## Signed Bytes
+//!
+//! The signature covers the whole envelope except its `signature` field,
+//! serialized as canonical JSON (object keys sorted, no whitespace) behind
+//! a domain-separation tag:
+//!
+//! ```text
+//! "cim-keys/event-envelope/v1\n" || canonical_json(envelope - signature)
+//! ```
+//!
+//! Canonical form keeps the signed bytes stable across a JSON round trip,
+//! including maps whose in-memory iteration order differs between
+//! processes.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::EventEnvelope;
+
+/// Domain-separation tag prepended to the signed bytes
+const SIGNING_CONTEXT: &[u8] = b"cim-keys/event-envelope/v1\n";
+
+/// Signature carried in an [`EventEnvelope`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventSignature {
+    /// ID of the Ed25519 key that signed the envelope
+    pub signer_key_id: Uuid,
+    /// Ed25519 signature, base64
+    pub signature: String,
+}
+
+/// Signs envelopes with an operator or ceremony key
+pub struct EventSigner {
+    key_id: Uuid,
+    signing_key: SigningKey,
+}
+
+impl EventSigner {
+    /// Signer for the Ed25519 key identified by `key_id`
+    pub fn new(key_id: Uuid, signing_key: SigningKey) -> Self {
+        Self { key_id, signing_key }
+    }
+
+    /// ID recorded as the signer of every envelope
+    pub fn key_id(&self) -> Uuid {
+        self.key_id
+    }
+
+    /// Public key verifiers need to trust this signer
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    /// Sign an envelope, replacing any existing signature
+    pub fn sign(&self, mut envelope: EventEnvelope) -> Result<EventEnvelope, EventSignatureError> {
+        let signature = self.signing_key.sign(&signing_payload(&envelope)?);
+        envelope.signature = Some(EventSignature {
+            signer_key_id: self.key_id,
+            signature: STANDARD.encode(signature.to_bytes()),
+        });
+        Ok(envelope)
+    }
+}
+
+/// Verifies envelope signatures against a set of trusted keys
+#[derive(Debug, Clone, Default)]
+pub struct EventVerifier {
+    trusted: HashMap<Uuid, VerifyingKey>,
+    allow_unsigned: bool,
+}
+
+impl EventVerifier {
+    /// Verifier that trusts no keys and rejects unsigned envelopes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust signatures made by `key_id`
+    pub fn trust(mut self, key_id: Uuid, key: VerifyingKey) -> Self {
+        self.trusted.insert(key_id, key);
+        self
+    }
+
+    /// Accept envelopes without a signature
+    ///
+    /// Only for streams written before signing was enabled; a signature
+    /// that is present is still checked.
+    pub fn allow_unsigned(mut self) -> Self {
+        self.allow_unsigned = true;
+        self
+    }
+
+    /// Check an envelope's signature
+    pub fn verify(&self, envelope: &EventEnvelope) -> Result<(), EventSignatureError> {
+        let event_id = envelope.event_id;
+        let Some(signed) = &envelope.signature else {
+            return if self.allow_unsigned {
+                Ok(())
+            } else {
+                Err(EventSignatureError::Unsigned { event_id })
+            };
+        };
+
+        let key = self.trusted.get(&signed.signer_key_id).ok_or(EventSignatureError::UnknownSigner {
+            event_id,
+            key_id: signed.signer_key_id,
+        })?;
+        let bytes: [u8; 64] = STANDARD
+            .decode(&signed.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or(EventSignatureError::InvalidSignature { event_id })?;
+
+        key.verify(&signing_payload(envelope)?, &Signature::from_bytes(&bytes))
+            .map_err(|_| EventSignatureError::InvalidSignature { event_id })
+    }
+}
+
+/// Errors for envelope signing and verification
+#[derive(Debug, thiserror::Error)]
+pub enum EventSignatureError {
+    #[error("Event {event_id} is not signed")]
+    Unsigned { event_id: Uuid },
+
+    #[error("Event {event_id} is signed by untrusted key {key_id}")]
+    UnknownSigner { event_id: Uuid, key_id: Uuid },
+
+    #[error("Event {event_id} has an invalid signature")]
+    InvalidSignature { event_id: Uuid },
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+}
+
+/// Bytes covered by an envelope's signature
+fn signing_payload(envelope: &EventEnvelope) -> Result<Vec<u8>, EventSignatureError> {
+    let mut value = serde_json::to_value(envelope)
+        .map_err(|e| EventSignatureError::SerializationError(e.to_string()))?;
+    if let Value::Object(fields) = &mut value {
+        fields.remove("signature");
+    }
+
+    let mut payload = SIGNING_CONTEXT.to_vec();
+    write_canonical(&value, &mut payload)
+        .map_err(|e| EventSignatureError::SerializationError(e.to_string()))?;
+    Ok(payload)
+}
+
+/// Write JSON with object keys sorted and no whitespace
+pub(crate) fn write_canonical(value: &Value, out: &mut Vec<u8>) -> Result<(), serde_json::Error> {
+    match value {
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_canonical(item, out)?;
+            }
+            out.push(b']');
+        }
+        Value::Object(fields) => {
+            let mut fields: Vec<_> = fields.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            out.push(b'{');
+            for (i, (name, field)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                serde_json::to_writer(&mut *out, name)?;
+                out.push(b':');
+                write_canonical(field, out)?;
+            }
+            out.push(b'}');
+        }
+        scalar => serde_json::to_writer(out, scalar)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{DomainEvent, KeyEvents, KeyGeneratedEvent};
+    use crate::types::{KeyAlgorithm, KeyMetadata, KeyPurpose};
+    use crate::value_objects::ActorId;
+
+    fn signer() -> EventSigner {
+        EventSigner::new(Uuid::now_v7(), SigningKey::from_bytes(&rand::random()))
+    }
+
+    fn key_generated() -> EventEnvelope {
+        let correlation_id = Uuid::now_v7();
+        let attributes = (0..8).map(|i| (format!("attr-{}", i), i.to_string())).collect();
+        EventEnvelope::new(
+            DomainEvent::Key(KeyEvents::KeyGenerated(KeyGeneratedEvent {
+                key_id: Uuid::now_v7(),
+                algorithm: KeyAlgorithm::Ed25519,
+                purpose: KeyPurpose::Signing,
+                generated_at: chrono::Utc::now(),
+                generated_by: ActorId::system("ceremony"),
+                hardware_backed: false,
+                metadata: KeyMetadata {
+                    label: "Operator Signing Key".to_string(),
+                    description: None,
+                    tags: vec!["operator".to_string()],
+                    attributes,
+                    jwt_kid: None,
+                    jwt_alg: None,
+                    jwt_use: None,
+                    hardware_serial: None,
+                    tpm: None,
+                },
+                ownership: None,
+                expires_at: None,
+                correlation_id,
+                causation_id: None,
+            })),
+            correlation_id,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_signature_survives_json_round_trip() {
+        let signer = signer();
+        let verifier = EventVerifier::new().trust(signer.key_id(), signer.verifying_key());
+
+        let signed = signer.sign(key_generated()).unwrap();
+        assert_eq!(signed.signature.as_ref().unwrap().signer_key_id, signer.key_id());
+
+        // Reloaded envelopes rebuild their attribute map in a new order
+        let json = serde_json::to_string_pretty(&signed).unwrap();
+        let reloaded: EventEnvelope = serde_json::from_str(&json).unwrap();
+        verifier.verify(&reloaded).unwrap();
+    }
+
+    #[test]
+    fn test_tampered_envelope_is_rejected() {
+        let signer = signer();
+        let verifier = EventVerifier::new().trust(signer.key_id(), signer.verifying_key());
+        let signed = signer.sign(key_generated()).unwrap();
+
+        let mut json = serde_json::to_value(&signed).unwrap();
+        json["event"]["hardware_backed"] = Value::Bool(true);
+        let tampered: EventEnvelope = serde_json::from_value(json).unwrap();
+        assert!(matches!(
+            verifier.verify(&tampered),
+            Err(EventSignatureError::InvalidSignature { event_id }) if event_id == signed.event_id
+        ));
+
+        let mut rerouted = signed.clone();
+        rerouted.causation_id = Some(Uuid::now_v7());
+        assert!(verifier.verify(&rerouted).is_err());
+    }
+
+    #[test]
+    fn test_untrusted_and_unsigned_envelopes() {
+        let (trusted, rogue) = (signer(), signer());
+        let verifier = EventVerifier::new().trust(trusted.key_id(), trusted.verifying_key());
+
+        let forged = rogue.sign(key_generated()).unwrap();
+        assert!(matches!(
+            verifier.verify(&forged),
+            Err(EventSignatureError::UnknownSigner { key_id, .. }) if key_id == rogue.key_id()
+        ));
+
+        // A trusted key ID does not help a signature made with another key
+        let mut impersonated = forged;
+        impersonated.signature.as_mut().unwrap().signer_key_id = trusted.key_id();
+        assert!(matches!(verifier.verify(&impersonated), Err(EventSignatureError::InvalidSignature { .. })));
+
+        let unsigned = key_generated();
+        assert!(matches!(verifier.verify(&unsigned), Err(EventSignatureError::Unsigned { .. })));
+        assert!(verifier.clone().allow_unsigned().verify(&unsigned).is_ok());
+    }
+}
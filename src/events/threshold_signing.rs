@@ -0,0 +1,198 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Threshold Signing Events
+//!
+//! Events for FROST threshold keys (see `crypto::frost`): the distributed
+//! key generation that creates a root CA key shared among root
+//! authorities and backup holders, and each signing operation that needs
+//! `min_signers` of them.
+//!
+//! Events only carry public data — participant IDs, the group public key,
+//! message digests and the final signature. Round packages and shares never
+//! enter the event log.
+//!
+//! ## Event Flow
+//!
+//! ```text
+//! ThresholdKeyGenerationStarted
+//!       │
+//!       ▼
+//! DkgRound1Completed
+//!       │
+//!       ▼
+//! DkgRound2Completed
+//!       │
+//!       ▼
+//! ThresholdKeyGenerated
+//!
+//! ThresholdSigningStarted
+//!       │
+//!       ▼
+//! SigningCommitmentsCollected
+//!       │
+//!       ▼
+//! SignatureSharesCollected
+//!       │
+//!       ▼
+//! ThresholdSignatureAggregated
+//!
+//! (any step) ──▶ ThresholdOperationFailed
+//! ```
+
+use chrono::{DateTime, Utc};
+use cim_domain::DomainEvent;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Events for threshold key generation and signing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event_type")]
+pub enum ThresholdSigningEvents {
+    /// A distributed key generation was started
+    ThresholdKeyGenerationStarted(ThresholdKeyGenerationStartedEvent),
+
+    /// Every participant broadcast its round 1 package
+    DkgRound1Completed(DkgRoundCompletedEvent),
+
+    /// Every participant sent its round 2 packages
+    DkgRound2Completed(DkgRoundCompletedEvent),
+
+    /// Every participant derived its share and the group public key
+    ThresholdKeyGenerated(ThresholdKeyGeneratedEvent),
+
+    /// A threshold signing operation was started
+    ThresholdSigningStarted(ThresholdSigningStartedEvent),
+
+    /// Enough signers committed (signing round 1)
+    SigningCommitmentsCollected(SigningRoundCompletedEvent),
+
+    /// Every committed signer produced a signature share (signing round 2)
+    SignatureSharesCollected(SigningRoundCompletedEvent),
+
+    /// Signature shares were aggregated into an Ed25519 signature
+    ThresholdSignatureAggregated(ThresholdSignatureAggregatedEvent),
+
+    /// Key generation or signing was abandoned
+    ThresholdOperationFailed(ThresholdOperationFailedEvent),
+}
+
+/// A distributed key generation was started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdKeyGenerationStartedEvent {
+    /// Key being generated; also identifies the ceremony
+    pub key_id: Uuid,
+    /// Person IDs of all share holders
+    pub participants: Vec<Uuid>,
+    /// Participants needed to sign
+    pub min_signers: u16,
+    /// What the key is for, e.g. "root-ca"
+    pub purpose: String,
+    pub started_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A DKG round finished for every participant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DkgRoundCompletedEvent {
+    pub key_id: Uuid,
+    /// Participants whose packages were received
+    pub participants: Vec<Uuid>,
+    pub completed_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The threshold key exists
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdKeyGeneratedEvent {
+    pub key_id: Uuid,
+    /// Ed25519 group public key, hex
+    pub group_public_key: String,
+    pub participants: Vec<Uuid>,
+    pub min_signers: u16,
+    pub generated_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A signing operation with a threshold key was started
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdSigningStartedEvent {
+    pub session_id: Uuid,
+    pub key_id: Uuid,
+    /// SHA-256 of the message being signed, hex
+    pub message_digest: String,
+    /// What is being signed, e.g. "root-ca-certificate"
+    pub purpose: String,
+    /// Person coordinating the rounds
+    pub coordinator_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// A signing round finished for every signer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningRoundCompletedEvent {
+    pub session_id: Uuid,
+    /// Persons who took part in this round
+    pub signers: Vec<Uuid>,
+    pub completed_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// The threshold signature was produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdSignatureAggregatedEvent {
+    pub session_id: Uuid,
+    pub key_id: Uuid,
+    /// Ed25519 signature, hex
+    pub signature: String,
+    pub signers: Vec<Uuid>,
+    pub aggregated_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+/// Key generation or signing was abandoned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThresholdOperationFailedEvent {
+    /// Key ID for key generation, session ID for signing
+    pub operation_id: Uuid,
+    pub reason: String,
+    pub failed_at: DateTime<Utc>,
+    pub correlation_id: Uuid,
+    pub causation_id: Option<Uuid>,
+}
+
+impl DomainEvent for ThresholdSigningEvents {
+    fn aggregate_id(&self) -> Uuid {
+        match self {
+            ThresholdSigningEvents::ThresholdKeyGenerationStarted(e) => e.key_id,
+            ThresholdSigningEvents::DkgRound1Completed(e) => e.key_id,
+            ThresholdSigningEvents::DkgRound2Completed(e) => e.key_id,
+            ThresholdSigningEvents::ThresholdKeyGenerated(e) => e.key_id,
+            ThresholdSigningEvents::ThresholdSigningStarted(e) => e.session_id,
+            ThresholdSigningEvents::SigningCommitmentsCollected(e) => e.session_id,
+            ThresholdSigningEvents::SignatureSharesCollected(e) => e.session_id,
+            ThresholdSigningEvents::ThresholdSignatureAggregated(e) => e.session_id,
+            ThresholdSigningEvents::ThresholdOperationFailed(e) => e.operation_id,
+        }
+    }
+
+    fn event_type(&self) -> &'static str {
+        match self {
+            ThresholdSigningEvents::ThresholdKeyGenerationStarted(_) => "ThresholdKeyGenerationStarted",
+            ThresholdSigningEvents::DkgRound1Completed(_) => "DkgRound1Completed",
+            ThresholdSigningEvents::DkgRound2Completed(_) => "DkgRound2Completed",
+            ThresholdSigningEvents::ThresholdKeyGenerated(_) => "ThresholdKeyGenerated",
+            ThresholdSigningEvents::ThresholdSigningStarted(_) => "ThresholdSigningStarted",
+            ThresholdSigningEvents::SigningCommitmentsCollected(_) => "SigningCommitmentsCollected",
+            ThresholdSigningEvents::SignatureSharesCollected(_) => "SignatureSharesCollected",
+            ThresholdSigningEvents::ThresholdSignatureAggregated(_) => "ThresholdSignatureAggregated",
+            ThresholdSigningEvents::ThresholdOperationFailed(_) => "ThresholdOperationFailed",
+        }
+    }
+}
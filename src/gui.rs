@@ -147,6 +147,7 @@ pub struct CimKeysApp {
     organization_domain: String,
     organization_id: Option<Uuid>,  // Set when domain is created
     admin_email: String,  // Admin contact email for the organization
+    operator_id: Uuid,  // Person issuing commands; their policies authorize them
 
     // Master passphrase for encryption
     master_passphrase: String,
@@ -2002,6 +2003,10 @@ impl CimKeysApp {
                 organization_domain: String::new(),
                 organization_id: None,
                 admin_email: String::from("admin@example.com"),
+                operator_id: std::env::var("CIM_KEYS_OPERATOR_ID")
+                    .ok()
+                    .and_then(|id| Uuid::parse_str(&id).ok())
+                    .unwrap_or_else(Uuid::now_v7),
                 master_passphrase: String::new(),
                 master_passphrase_confirm: String::new(),
                 bootstrap_config: None,
@@ -4178,6 +4183,7 @@ impl CimKeysApp {
                 // Clone for async and GUI update
                 let aggregate = self.aggregate.clone();
                 let projection = self.projection.clone();
+                let actor = self.operator_context();
                 let person_id = cmd.person_id;
 
                 // Clear form fields optimistically
@@ -4196,8 +4202,7 @@ impl CimKeysApp {
                             KeyCommand::CreatePerson(cmd),
                             &projection_read,
                             None, // No NATS port in offline mode
-                            #[cfg(feature = "policy")]
-                            None  // No policy engine in GUI yet
+                            &actor,
                         ).await
                         .map_err(|e| e.to_string())?;
 
@@ -4378,6 +4383,7 @@ impl CimKeysApp {
                 // Clone for async
                 let aggregate = self.aggregate.clone();
                 let projection = self.projection.clone();
+                let actor = self.operator_context();
                 let location_name = self.new_location_name.clone();
 
                 // Clear form fields optimistically
@@ -4401,8 +4407,7 @@ impl CimKeysApp {
                             KeyCommand::CreateLocation(cmd),
                             &projection_read,
                             None, // No NATS port in offline mode
-                            #[cfg(feature = "policy")]
-                            None  // No policy engine in GUI yet
+                            &actor,
                         ).await
                         .map_err(|e| e.to_string())?;
 
@@ -4663,10 +4668,11 @@ impl CimKeysApp {
                 // For now, using legacy aggregate/projection pattern
                 let aggregate = self.aggregate.clone();
                 let projection = self.projection.clone();
+                let actor = self.operator_context();
 
                 Task::perform(
                     async move {
-                        generate_root_ca(aggregate, projection).await
+                        generate_root_ca(aggregate, projection, actor).await
                     },
                     |result| match result {
                         Ok(cert_id) => Message::UpdateStatus(format!("Root CA generated: {}", cert_id)),
@@ -5937,6 +5943,7 @@ impl CimKeysApp {
                 // Clone for async
                 let aggregate = self.aggregate.clone();
                 let projection = self.projection.clone();
+                let actor = self.operator_context();
                 let responsible_person_id = self.new_unit_responsible_person;
                 let nats_account_name = if self.new_unit_nats_account.is_empty() {
                     None
@@ -5962,8 +5969,7 @@ impl CimKeysApp {
                             KeyCommand::CreateOrganizationalUnit(cmd),
                             &projection_read,
                             None, // No NATS port in offline mode
-                            #[cfg(feature = "policy")]
-                            None  // No policy engine in GUI yet
+                            &actor,
                         ).await
                         .map_err(|e| e.to_string())?;
 
@@ -6093,6 +6099,7 @@ impl CimKeysApp {
                 // Clone for async
                 let aggregate = self.aggregate.clone();
                 let projection = self.projection.clone();
+                let actor = self.operator_context();
 
                 // Clear form fields optimistically
                 self.new_service_account_name.clear();
@@ -6111,8 +6118,7 @@ impl CimKeysApp {
                             KeyCommand::CreateServiceAccount(cmd),
                             &projection_read,
                             None, // No NATS port in offline mode
-                            #[cfg(feature = "policy")]
-                            None  // No policy engine in GUI yet
+                            &actor,
                         ).await
                         .map_err(|e| e.to_string())?;
 
@@ -6296,6 +6302,7 @@ impl CimKeysApp {
                 // Clone Arc references for async task
                 let aggregate = self.aggregate.clone();
                 let projection = self.projection.clone();
+                let actor = self.operator_context();
 
                 // Process command through aggregate (CQRS pattern)
                 Task::perform(
@@ -6307,8 +6314,7 @@ impl CimKeysApp {
                             KeyCommand::CreateDelegation(cmd.clone()),
                             &projection_read,
                             None, // No NATS port in offline mode
-                            #[cfg(feature = "policy")]
-                            None  // No policy engine in GUI yet
+                            &actor,
                         ).await
                         .map_err(|e| e.to_string())?;
 
@@ -6384,6 +6390,7 @@ impl CimKeysApp {
                 // Clone Arc references for async task
                 let aggregate = self.aggregate.clone();
                 let projection = self.projection.clone();
+                let actor = self.operator_context();
 
                 // Process command through aggregate (CQRS pattern)
                 Task::perform(
@@ -6395,8 +6402,7 @@ impl CimKeysApp {
                             KeyCommand::RevokeDelegation(cmd),
                             &projection_read,
                             None, // No NATS port in offline mode
-                            #[cfg(feature = "policy")]
-                            None  // No policy engine in GUI yet
+                            &actor,
                         ).await
                         .map_err(|e| e.to_string())?;
 
@@ -6966,6 +6972,7 @@ impl CimKeysApp {
 
                 let aggregate = self.aggregate.clone();
                 let projection = self.projection.clone();
+                let actor = self.operator_context();
                 let mut emitter = self.event_emitter.clone();
 
                 // Process commands through the aggregate
@@ -6983,8 +6990,7 @@ impl CimKeysApp {
                                 domain_command.command,
                                 &projection,
                                 None,  // No NATS port in offline mode
-                                #[cfg(feature = "policy")]
-                                None   // No policy engine in GUI yet
+                                &actor,
                             ).await {
                                 Ok(events) => {
                                     total_keys += events.len();
@@ -7089,12 +7095,23 @@ impl CimKeysApp {
                     keys: self.loaded_keys.clone(),
                     certificates: self.loaded_certificates.clone(),
                     pki_hierarchies: vec![], // TODO: Populate from projection
+                    crls: vec![],
+                    certificate_log: vec![],
                     yubikeys: vec![],        // TODO: Populate from projection
                     nats_operators: vec![],  // TODO: Populate from projection
                     nats_accounts: vec![],
                     nats_users: vec![],
+                    relationships: vec![],
+                    processed_commands: vec![],
+                    outbox: vec![],
+                    dead_letters: vec![],
+                    journal_position: 0,
                     event_count: 0, // TODO: Get from projection
                     checksum: String::new(),
+                    file_checksums: Default::default(),
+                    signature: None,
+                    luks_uuid: None,
+                    kdf_salts: Vec::new(),
                 };
 
                 // Create the composed projection pipeline
@@ -8235,9 +8252,13 @@ impl CimKeysApp {
                             name: "New Policy".to_string(),
                             description: "Define policy claims and conditions".to_string(),
                             claims: Vec::new(),
+                            denied_claims: Vec::new(),
                             conditions: Vec::new(),
                             priority: 0,
                             enabled: true,
+                            version: 1,
+                            effective_from: None,
+                            effective_until: None,
                             created_by: creator_id,
                             metadata: std::collections::HashMap::new(),
                         };
@@ -8844,9 +8865,13 @@ impl CimKeysApp {
                                     name: "New Policy".to_string(),
                                     description: "Policy description".to_string(),
                                     claims: vec![],
+                                    denied_claims: vec![],
                                     conditions: vec![],
                                     priority: 0,
                                     enabled: true,
+                                    version: 1,
+                                    effective_from: None,
+                                    effective_until: None,
                                     created_by: BootstrapPersonId::from_uuid(node_id), // TODO: Get actual user
                                     metadata: HashMap::new(),
                                 };
@@ -9259,6 +9284,7 @@ impl CimKeysApp {
                                                 locality: None,
                                                 validity_years: 20,
                                                 pathlen: 1, // Allow one intermediate CA level
+                                                ..Default::default()
                                             };
 
                                             // Generate Root CA certificate (US-021: with event emission)
@@ -9306,6 +9332,7 @@ impl CimKeysApp {
                                                     organization: org_name.clone(),
                                                     organizational_unit: Some("Personal".to_string()),
                                                     validity_days: 365, // 1 year for personal certs
+                                                    ..Default::default()
                                                 };
 
                                                 // Generate leaf certificate signed by intermediate CA
@@ -9380,6 +9407,7 @@ impl CimKeysApp {
                                                     country: Some("US".to_string()),
                                                     validity_years: 3, // Intermediate CAs typically 3-5 years
                                                     pathlen: 0, // Can only sign leaf certs
+                                                    ..Default::default()
                                                 };
 
                                                 // Generate Intermediate CA certificate
@@ -11563,6 +11591,14 @@ impl CimKeysApp {
         CowboyCustomTheme::dark()
     }
 
+    /// Policy evaluation context of the operator, for authorizing commands
+    fn operator_context(&self) -> crate::domain::PolicyEvaluationContext {
+        crate::domain::PolicyEvaluationContext {
+            yubikey_present: !self.detected_yubikeys.is_empty(),
+            ..crate::domain::PolicyEvaluationContext::for_person(self.operator_id)
+        }
+    }
+
     /// Returns a mutable reference to the currently active graph based on graph_view
     fn active_graph_mut(&mut self) -> &mut graph::OrganizationConcept {
         match self.graph_view {
@@ -16074,6 +16110,10 @@ impl CimKeysApp {
                                                             crate::events::DomainEvent::Relationship(_) => "Relationship",
                                                             crate::events::DomainEvent::Manifest(_) => "Manifest",
                                                             crate::events::DomainEvent::Saga(_) => "Saga",
+                                                            crate::events::DomainEvent::ThresholdSigning(_) => "Threshold",
+                                                            crate::events::DomainEvent::Approval(_) => "Approval",
+                                                            crate::events::DomainEvent::Authorization(_) => "Authorization",
+                                                            crate::events::DomainEvent::Maintenance(_) => "Maintenance",
                                                         };
                                                         event_list = event_list.push(
                                                             button(
@@ -17858,6 +17898,7 @@ async fn load_config_wasm() -> Result<BootstrapConfig, String> {
 async fn generate_all_keys(
     aggregate: Arc<RwLock<KeyManagementAggregate>>,
     projection: Arc<RwLock<OfflineKeyProjection>>,
+    actor: crate::domain::PolicyEvaluationContext,
 ) -> Result<usize, String> {
     use crate::commands::{KeyCommand, GenerateSshKeyCommand};
     use crate::events::DomainEvent;
@@ -17882,8 +17923,7 @@ async fn generate_all_keys(
         KeyCommand::GenerateSshKey(ssh_cmd),
         &projection_read,
         None,  // No NATS port in offline mode
-        #[cfg(feature = "policy")]
-        None   // No policy engine in GUI yet
+        &actor,
     ).await
     .map_err(|e| format!("Failed to generate SSH key: {}", e))?;
 
@@ -17935,6 +17975,7 @@ async fn generate_all_keys(
 async fn generate_root_ca(
     aggregate: Arc<RwLock<KeyManagementAggregate>>,
     projection: Arc<RwLock<OfflineKeyProjection>>,
+    actor: crate::domain::PolicyEvaluationContext,
 ) -> Result<Uuid, String> {
     use crate::commands::{KeyCommand, GenerateCertificateCommand, CertificateSubject};
     use crate::events::DomainEvent;
@@ -17963,6 +18004,7 @@ async fn generate_root_ca(
     };
 
     // Process through aggregate to get event
+    let command_id = *root_ca_cmd.command_id.as_uuid();
     let aggregate = aggregate.read().await;
     let projection_read = projection.read().await;
 
@@ -17970,8 +18012,7 @@ async fn generate_root_ca(
         KeyCommand::GenerateCertificate(root_ca_cmd),
         &projection_read,
         None,
-        #[cfg(feature = "policy")]
-        None
+        &actor,
     ).await
     .map_err(|e| format!("Failed to generate Root CA: {}", e))?;
 
@@ -17981,7 +18022,7 @@ async fn generate_root_ca(
     // Process the certificate generation event
     let mut cert_id = Uuid::nil();
     if !events.is_empty() {
-        let projection_write = projection.write().await;
+        let mut projection_write = projection.write().await;
 
         for event in events {
             if let DomainEvent::Certificate(crate::events::CertificateEvents::CertificateGenerated(e)) = event {
@@ -18027,6 +18068,9 @@ async fn generate_root_ca(
                     .map_err(|e| format!("Failed to update manifest: {}", e))?;
             }
         }
+
+        projection_write.record_processed_command(command_id)
+            .map_err(|e| format!("Failed to record command: {}", e))?;
     }
 
     Ok(cert_id)
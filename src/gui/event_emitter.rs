@@ -263,6 +263,17 @@ impl DomainEvent {
             DomainEvent::Key(crate::events::KeyEvents::KeyRevoked(e)) => e.key_id,
             DomainEvent::Key(crate::events::KeyEvents::KeyRotationInitiated(e)) => e.rotation_id,
             DomainEvent::Key(crate::events::KeyEvents::KeyRotationCompleted(e)) => e.rotation_id,
+            DomainEvent::Key(crate::events::KeyEvents::KeyExpirationExtended(e)) => e.key_id,
+            DomainEvent::Key(crate::events::KeyEvents::KeyMovedToCard(e)) => e.key_id,
+            DomainEvent::Key(crate::events::KeyEvents::GpgKeyCertified(e)) => e.subject_key_id,
+            DomainEvent::Key(crate::events::KeyEvents::SshCaDesignated(e)) => e.ca_key_id,
+            DomainEvent::Key(crate::events::KeyEvents::SshCertificateIssued(e)) => e.certificate_id,
+            DomainEvent::Key(crate::events::KeyEvents::MasterSeedSplit(e)) => e.split_id,
+            DomainEvent::Key(crate::events::KeyEvents::SeedShareAssigned(e)) => e.split_id,
+            DomainEvent::Key(crate::events::KeyEvents::MasterSeedRecovered(e)) => e.split_id,
+            DomainEvent::Key(crate::events::KeyEvents::MasterSeedDerived(e)) => e.seed_id,
+            DomainEvent::Key(crate::events::KeyEvents::KeyRotationReported(e)) => e.rotation_id,
+            DomainEvent::Key(crate::events::KeyEvents::RecoveryRehearsalCompleted(e)) => e.rehearsal_id,
             // Certificate aggregate events
             DomainEvent::Certificate(crate::events::CertificateEvents::CertificateGenerated(e)) => e.cert_id,
             DomainEvent::Certificate(crate::events::CertificateEvents::CertificateSigned(e)) => e.cert_id,
@@ -273,6 +284,8 @@ impl DomainEvent {
             DomainEvent::Certificate(crate::events::CertificateEvents::CertificateRenewed(e)) => e.new_cert_id,
             DomainEvent::Certificate(crate::events::CertificateEvents::CertificateExported(e)) => e.export_id,
             DomainEvent::Certificate(crate::events::CertificateEvents::PkiHierarchyCreated(e)) => e.root_ca_id,
+            DomainEvent::Certificate(crate::events::CertificateEvents::CrlPublished(e)) => e.crl_id,
+            DomainEvent::Certificate(crate::events::CertificateEvents::CertificateIssuanceRejected(e)) => e.rejection_id,
             // YubiKey aggregate events
             DomainEvent::YubiKey(crate::events::YubiKeyEvents::YubiKeyProvisioned(_e)) => Uuid::now_v7(),
             DomainEvent::YubiKey(crate::events::YubiKeyEvents::PinConfigured(e)) => e.event_id,
@@ -293,6 +306,7 @@ impl DomainEvent {
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsOperatorReactivated(e)) => e.operator_id,
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsOperatorRevoked(e)) => e.operator_id,
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsSigningKeyGenerated(_e)) => Uuid::now_v7(),
+            DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsSigningKeyRetired(e)) => e.entity_id,
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NKeyGenerated(e)) => e.nkey_id,
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::JwtClaimsCreated(e)) => e.claims_id,
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::JwtSigned(e)) => e.jwt_id,
@@ -304,11 +318,17 @@ impl DomainEvent {
             DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountSuspended(e)) => e.account_id,
             DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountReactivated(e)) => e.account_id,
             DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsPermissionsSet(_e)) => Uuid::now_v7(),
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountLimitsSet(e)) => e.account_id,
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsUserRevoked(e)) => e.account_id,
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountExportDeclared(e)) => e.account_id,
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountImportDeclared(e)) => e.account_id,
             // NATS User aggregate events
             DomainEvent::NatsUser(crate::events::NatsUserEvents::NatsUserCreated(e)) => e.user_id,
             DomainEvent::NatsUser(crate::events::NatsUserEvents::ServiceAccountCreated(e)) => e.service_account_id,
             DomainEvent::NatsUser(crate::events::NatsUserEvents::AgentCreated(e)) => e.agent_id,
             DomainEvent::NatsUser(crate::events::NatsUserEvents::TotpSecretGenerated(e)) => e.secret_id,
+            DomainEvent::NatsUser(crate::events::NatsUserEvents::NatsCredentialsDelivered(e)) => e.delivery_id,
+            DomainEvent::NatsUser(crate::events::NatsUserEvents::EdgeSiteCredentialsIssued(e)) => e.user_id,
             // Person aggregate events
             DomainEvent::Person(crate::events::PersonEvents::PersonCreated(e)) => e.person_id,
             DomainEvent::Person(crate::events::PersonEvents::PersonActivated(e)) => e.person_id,
@@ -326,6 +346,7 @@ impl DomainEvent {
             DomainEvent::Organization(crate::events::OrganizationEvents::OrganizationalUnitCreated(e)) => e.unit_id,
             DomainEvent::Organization(crate::events::OrganizationEvents::RoleCreated(e)) => e.role_id,
             DomainEvent::Organization(crate::events::OrganizationEvents::PolicyCreated(e)) => e.policy_id,
+            DomainEvent::Organization(crate::events::OrganizationEvents::PolicySuperseded(e)) => e.policy_id,
             // Manifest aggregate events
             DomainEvent::Manifest(crate::events::ManifestEvents::ManifestCreated(e)) => e.manifest_id,
             // Catch-all for any events not explicitly handled
@@ -345,6 +366,17 @@ impl DomainEvent {
             DomainEvent::Key(crate::events::KeyEvents::KeyRevoked(_)) => "KeyRevoked",
             DomainEvent::Key(crate::events::KeyEvents::KeyRotationInitiated(_)) => "KeyRotationInitiated",
             DomainEvent::Key(crate::events::KeyEvents::KeyRotationCompleted(_)) => "KeyRotationCompleted",
+            DomainEvent::Key(crate::events::KeyEvents::KeyExpirationExtended(_)) => "KeyExpirationExtended",
+            DomainEvent::Key(crate::events::KeyEvents::KeyMovedToCard(_)) => "KeyMovedToCard",
+            DomainEvent::Key(crate::events::KeyEvents::GpgKeyCertified(_)) => "GpgKeyCertified",
+            DomainEvent::Key(crate::events::KeyEvents::SshCaDesignated(_)) => "SshCaDesignated",
+            DomainEvent::Key(crate::events::KeyEvents::SshCertificateIssued(_)) => "SshCertificateIssued",
+            DomainEvent::Key(crate::events::KeyEvents::MasterSeedSplit(_)) => "MasterSeedSplit",
+            DomainEvent::Key(crate::events::KeyEvents::SeedShareAssigned(_)) => "SeedShareAssigned",
+            DomainEvent::Key(crate::events::KeyEvents::MasterSeedRecovered(_)) => "MasterSeedRecovered",
+            DomainEvent::Key(crate::events::KeyEvents::MasterSeedDerived(_)) => "MasterSeedDerived",
+            DomainEvent::Key(crate::events::KeyEvents::KeyRotationReported(_)) => "KeyRotationReported",
+            DomainEvent::Key(crate::events::KeyEvents::RecoveryRehearsalCompleted(_)) => "RecoveryRehearsalCompleted",
             // Certificate aggregate
             DomainEvent::Certificate(crate::events::CertificateEvents::CertificateGenerated(_)) => "CertificateGenerated",
             DomainEvent::Certificate(crate::events::CertificateEvents::CertificateSigned(_)) => "CertificateSigned",
@@ -355,6 +387,8 @@ impl DomainEvent {
             DomainEvent::Certificate(crate::events::CertificateEvents::CertificateRenewed(_)) => "CertificateRenewed",
             DomainEvent::Certificate(crate::events::CertificateEvents::CertificateExported(_)) => "CertificateExported",
             DomainEvent::Certificate(crate::events::CertificateEvents::PkiHierarchyCreated(_)) => "PkiHierarchyCreated",
+            DomainEvent::Certificate(crate::events::CertificateEvents::CrlPublished(_)) => "CrlPublished",
+            DomainEvent::Certificate(crate::events::CertificateEvents::CertificateIssuanceRejected(_)) => "CertificateIssuanceRejected",
             // YubiKey aggregate
             DomainEvent::YubiKey(crate::events::YubiKeyEvents::YubiKeyProvisioned(_)) => "YubiKeyProvisioned",
             DomainEvent::YubiKey(crate::events::YubiKeyEvents::PinConfigured(_)) => "PinConfigured",
@@ -375,6 +409,7 @@ impl DomainEvent {
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsOperatorReactivated(_)) => "NatsOperatorReactivated",
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsOperatorRevoked(_)) => "NatsOperatorRevoked",
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsSigningKeyGenerated(_)) => "NatsSigningKeyGenerated",
+            DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NatsSigningKeyRetired(_)) => "NatsSigningKeyRetired",
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::NKeyGenerated(_)) => "NKeyGenerated",
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::JwtClaimsCreated(_)) => "JwtClaimsCreated",
             DomainEvent::NatsOperator(crate::events::NatsOperatorEvents::JwtSigned(_)) => "JwtSigned",
@@ -386,11 +421,17 @@ impl DomainEvent {
             DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountSuspended(_)) => "NatsAccountSuspended",
             DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountReactivated(_)) => "NatsAccountReactivated",
             DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsPermissionsSet(_)) => "NatsPermissionsSet",
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountLimitsSet(_)) => "NatsAccountLimitsSet",
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsUserRevoked(_)) => "NatsUserRevoked",
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountExportDeclared(_)) => "NatsAccountExportDeclared",
+            DomainEvent::NatsAccount(crate::events::NatsAccountEvents::NatsAccountImportDeclared(_)) => "NatsAccountImportDeclared",
             // NATS User aggregate
             DomainEvent::NatsUser(crate::events::NatsUserEvents::NatsUserCreated(_)) => "NatsUserCreated",
             DomainEvent::NatsUser(crate::events::NatsUserEvents::ServiceAccountCreated(_)) => "ServiceAccountCreated",
             DomainEvent::NatsUser(crate::events::NatsUserEvents::AgentCreated(_)) => "AgentCreated",
             DomainEvent::NatsUser(crate::events::NatsUserEvents::TotpSecretGenerated(_)) => "TotpSecretGenerated",
+            DomainEvent::NatsUser(crate::events::NatsUserEvents::NatsCredentialsDelivered(_)) => "NatsCredentialsDelivered",
+            DomainEvent::NatsUser(crate::events::NatsUserEvents::EdgeSiteCredentialsIssued(_)) => "EdgeSiteCredentialsIssued",
             // Person aggregate
             DomainEvent::Person(crate::events::PersonEvents::PersonCreated(_)) => "PersonCreated",
             DomainEvent::Person(crate::events::PersonEvents::PersonActivated(_)) => "PersonActivated",
@@ -408,6 +449,7 @@ impl DomainEvent {
             DomainEvent::Organization(crate::events::OrganizationEvents::OrganizationalUnitCreated(_)) => "OrganizationalUnitCreated",
             DomainEvent::Organization(crate::events::OrganizationEvents::RoleCreated(_)) => "RoleCreated",
             DomainEvent::Organization(crate::events::OrganizationEvents::PolicyCreated(_)) => "PolicyCreated",
+            DomainEvent::Organization(crate::events::OrganizationEvents::PolicySuperseded(_)) => "PolicySuperseded",
             // Manifest aggregate
             DomainEvent::Manifest(crate::events::ManifestEvents::ManifestCreated(_)) => "ManifestCreated",
             // Catch-all for any events not explicitly handled
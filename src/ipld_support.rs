@@ -6,8 +6,9 @@
 //! - Cryptographic integrity verification
 //! - Deduplication across event streams
 //! - Merkle DAG traversal for event causality chains
+//! - Hash-chained event logs: each event links to its predecessor's CID
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[cfg(feature = "ipld")]
@@ -111,6 +112,72 @@ impl<T: Serialize> ContentAddressedEvent<T> {
     }
 }
 
+/// An event's place in a hash-chained log
+///
+/// Each persisted event records its own CID and the CID of the event
+/// persisted before it; the first event has no predecessor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainLink {
+    /// CID of the event
+    pub cid: String,
+    /// CID of the preceding event, `None` for the first
+    pub previous_cid: Option<String>,
+}
+
+/// Check that links, in log order, form a single unbroken chain
+///
+/// A deleted event leaves its successor pointing at a CID that is not
+/// the one before it; an inserted or reordered event does the same, and a
+/// repeated CID is rejected outright.
+pub fn verify_chain_links(links: &[ChainLink]) -> Result<(), IpldError> {
+    let mut seen = std::collections::HashSet::new();
+    let mut previous: Option<&str> = None;
+
+    for (position, link) in links.iter().enumerate() {
+        if !seen.insert(link.cid.as_str()) {
+            return Err(IpldError::ChainBroken {
+                position,
+                reason: format!("{} appears more than once", link.cid),
+            });
+        }
+        if link.previous_cid.as_deref() != previous {
+            return Err(IpldError::ChainBroken {
+                position,
+                reason: format!(
+                    "{} links to {}, but the preceding event is {}",
+                    link.cid,
+                    link.previous_cid.as_deref().unwrap_or("nothing"),
+                    previous.unwrap_or("nothing"),
+                ),
+            });
+        }
+        previous = Some(&link.cid);
+    }
+
+    Ok(())
+}
+
+/// CID protecting an event's place in a hash-chained log
+///
+/// Covers the event's CID, its predecessor's CID and the predecessor's own
+/// link CID. Re-pointing `previous_cid` therefore no longer matches the
+/// link, and recomputing the link changes every link after it.
+#[cfg(feature = "ipld")]
+pub fn generate_link_cid(
+    cid: &str,
+    previous_cid: Option<&str>,
+    previous_link: Option<&str>,
+) -> Result<String, IpldError> {
+    #[derive(Serialize)]
+    struct LinkContent<'a> {
+        cid: &'a str,
+        previous_cid: Option<&'a str>,
+        previous_link: Option<&'a str>,
+    }
+
+    Ok(generate_cid(&LinkContent { cid, previous_cid, previous_link })?.to_string())
+}
+
 /// IPLD-related errors
 #[derive(Debug, Error)]
 pub enum IpldError {
@@ -128,12 +195,14 @@ pub enum IpldError {
 
     #[error("IPLD feature not enabled")]
     FeatureNotEnabled,
+
+    #[error("Event chain broken at position {position}: {reason}")]
+    ChainBroken { position: usize, reason: String },
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde::{Deserialize, Serialize};
     use uuid::Uuid;
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -196,4 +265,41 @@ mod tests {
         let ca_event = ContentAddressedEvent::new(event).unwrap();
         assert!(ca_event.verify().unwrap());
     }
+
+    fn chain(cids: &[&str]) -> Vec<ChainLink> {
+        cids.iter()
+            .enumerate()
+            .map(|(i, cid)| ChainLink {
+                cid: cid.to_string(),
+                previous_cid: i.checked_sub(1).map(|p| cids[p].to_string()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_chain_links() {
+        let links = chain(&["a", "b", "c", "d"]);
+        assert!(verify_chain_links(&links).is_ok());
+        assert!(verify_chain_links(&[]).is_ok());
+
+        // Deletion: "c" now follows "a" but still points at "b"
+        let mut deleted = links.clone();
+        deleted.remove(1);
+        assert!(matches!(verify_chain_links(&deleted), Err(IpldError::ChainBroken { position: 1, .. })));
+
+        // Reordering
+        let mut reordered = links.clone();
+        reordered.swap(2, 3);
+        assert!(matches!(verify_chain_links(&reordered), Err(IpldError::ChainBroken { position: 2, .. })));
+
+        // Insertion of an event claiming an existing predecessor
+        let mut inserted = links.clone();
+        inserted.insert(2, ChainLink { cid: "x".to_string(), previous_cid: Some("b".to_string()) });
+        assert!(matches!(verify_chain_links(&inserted), Err(IpldError::ChainBroken { position: 3, .. })));
+
+        // A second genesis or a replayed event
+        let mut replayed = links.clone();
+        replayed.push(links[0].clone());
+        assert!(verify_chain_links(&replayed).is_err());
+    }
 }
@@ -26,9 +26,10 @@
 //! // Create projection to encrypted partition
 //! let mut projection = OfflineKeyProjection::new("/mnt/encrypted")?;
 //!
-//! // Process a command to generate events
+//! // Process a command, issued by the operator, to generate events
 //! let command = GenerateKeyCommand { ... };
-//! let events = aggregate.handle_command(command)?;
+//! let actor = PolicyEvaluationContext::for_person(operator_id);
+//! let events = aggregate.handle_command(command, &projection, None, &actor).await?;
 //!
 //! // Apply events to update projection (writes to disk)
 //! for event in events {
@@ -164,6 +165,10 @@ pub mod icons;
 #[cfg(feature = "policy")]
 pub mod policy;
 
+// Given/When/Then harness for aggregate tests (also for downstream domains)
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+
 // Re-export core types
 pub use events::DomainEvent;
 pub use types::KeyMetadata;
@@ -13,10 +13,15 @@
 //! - `domain.organization.*` - Organization domain events
 //! - `domain.node.*` - Graph node domain events
 //! - `domain.edge.*` - Graph edge domain events
+//! - `domain.saga.*` - Saga progress
 
-use super::{Model, HandlerResult};
+use super::{Intent, Model, HandlerResult};
+use crate::domain::sagas::{RunStatus, SagaEngine, SagaProgress, SagaRun};
 use crate::mvi::model::{DomainStatus, Tab};
+use futures::channel::mpsc;
+use futures::stream::{self, Stream, StreamExt};
 use iced::Task;
+use std::sync::Arc;
 
 /// Handle domain created event
 pub fn handle_domain_created(
@@ -252,3 +257,49 @@ pub fn handle_policy_bound(
     ));
     (updated, Task::none())
 }
+
+/// Handle saga progress
+///
+/// Moves the saga's progress signal; the view renders it as a progress
+/// bar, so no status message is set. A saga that completed or failed is
+/// dropped from the model, and the outcome goes to the status line.
+pub fn handle_saga_progressed(model: Model, progress: SagaProgress) -> HandlerResult {
+    let updated = match progress.status {
+        RunStatus::Running => model.with_saga_progress(progress),
+        RunStatus::Completed => model
+            .without_saga_progress(progress.saga_id)
+            .with_status_message(format!("{} completed", progress.saga_type)),
+        RunStatus::Failed => model
+            .without_saga_progress(progress.saga_id)
+            .with_status_message(format!("{} failed", progress.saga_type)),
+    };
+    (updated, Task::none())
+}
+
+/// Run a saga in the background, reporting its progress to the update loop
+///
+/// Every signal `SagaEngine::run_with_progress` emits becomes a
+/// `DomainSagaProgressed` intent, ending with the run's terminal status.
+pub fn saga_task<C: Send + Sync + 'static>(engine: Arc<SagaEngine<C>>, run: SagaRun<C>) -> Task<Intent> {
+    Task::run(saga_progress_intents(engine, run), |intent| intent)
+}
+
+/// The `DomainSagaProgressed` intents of running `run` to its end
+pub fn saga_progress_intents<C: Send + Sync + 'static>(
+    engine: Arc<SagaEngine<C>>,
+    mut run: SagaRun<C>,
+) -> impl Stream<Item = Intent> + Send + 'static {
+    let (sender, receiver) = mpsc::unbounded();
+    let run_saga = async move {
+        engine
+            .run_with_progress(&mut run, |signal| {
+                let progress = signal.sample(0.0);
+                // The receiver only goes away with the GUI
+                let _ = sender.unbounded_send(Intent::DomainSagaProgressed { progress });
+            })
+            .await;
+    };
+
+    // The receiver ends once the finished run drops the sender
+    stream::select(receiver, stream::once(run_saga).filter_map(|()| async { None }))
+}
@@ -151,6 +151,7 @@ fn route_domain_intent(model: Model, intent: Intent) -> HandlerResult {
         Intent::DomainPolicyBound { policy_id, entity_id, entity_type } => {
             domain::handle_policy_bound(model, policy_id, entity_id, entity_type)
         }
+        Intent::DomainSagaProgressed { progress } => domain::handle_saga_progressed(model, progress),
 
         // Fallback
         _ => (model, Task::none()),
@@ -305,4 +306,39 @@ mod tests {
         }.category(), IntentCategory::Error);
         assert_eq!(Intent::SystemFilePickerCancelled.category(), IntentCategory::System);
     }
+
+    #[tokio::test]
+    async fn test_saga_progress_flows_through_update_loop() {
+        use super::domain::saga_progress_intents;
+        use crate::adapters::{InMemoryStorageAdapter, MockSshKeyAdapter, MockX509Adapter, MockYubiKeyAdapter};
+        use crate::domain::sagas::{SagaDefinition, SagaEngine, SagaStep};
+        use futures::StreamExt;
+        use std::sync::Arc;
+        use uuid::Uuid;
+
+        let ports = Ports {
+            storage: Arc::new(InMemoryStorageAdapter::new()),
+            x509: Arc::new(MockX509Adapter::new()),
+            ssh: Arc::new(MockSshKeyAdapter::new()),
+            yubikey: Arc::new(MockYubiKeyAdapter::new()),
+        };
+        let engine = Arc::new(SagaEngine::new(
+            SagaDefinition::new("Demo")
+                .step(SagaStep::new("First", |_: &mut ()| Ok(None)))
+                .step(SagaStep::new("Second", |_: &mut ()| Ok(None))),
+        ));
+        let (run, _) = engine.start((), "operator", Uuid::now_v7());
+        let saga_id = run.saga_id;
+
+        let intents: Vec<Intent> = saga_progress_intents(engine, run).collect().await;
+        assert_eq!(intents.len(), 3);
+
+        let mut model = Model::default();
+        for (i, intent) in intents.into_iter().enumerate() {
+            model = route_intent(model, intent, &ports).0;
+            // Running progress is shown until the terminal signal drops it
+            assert_eq!(model.saga_progress.contains_key(&saga_id), i < 2);
+        }
+        assert_eq!(model.status_message, "Demo completed");
+    }
 }
@@ -220,6 +220,7 @@ pub fn handle_generate_root_ca(model: Model) -> HandlerResult {
                 locality: None,
                 validity_years: 20,
                 pathlen: 1,
+                ..Default::default()
             };
 
             let correlation_id = uuid::Uuid::now_v7();
@@ -292,6 +293,7 @@ pub fn handle_generate_intermediate_ca(model: Model, name: String) -> HandlerRes
                 country: Some("US".to_string()),
                 validity_years: 10,
                 pathlen: 0,
+                ..Default::default()
             };
 
             let correlation_id = uuid::Uuid::now_v7();
@@ -369,6 +371,7 @@ pub fn handle_generate_server_cert(
                 organization: org_name.clone(),
                 organizational_unit: Some(intermediate_ca_name_clone.clone()),
                 validity_days: 365,
+                ..Default::default()
             };
 
             let correlation_id = uuid::Uuid::now_v7();
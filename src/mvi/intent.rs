@@ -273,6 +273,11 @@ pub enum Intent {
         entity_type: String,
     },
 
+    /// A running saga advanced; holds its latest progress
+    DomainSagaProgressed {
+        progress: crate::domain::sagas::SagaProgress,
+    },
+
     // ===== Port-Originated Intents (Async Responses) =====
     /// Storage port completed write operation
     PortStorageWriteCompleted {
@@ -494,6 +499,7 @@ impl Intent {
             Intent::DomainRoleCreated { .. } => "domain.role.created".to_string(),
             Intent::DomainPolicyCreated { .. } => "domain.policy.created".to_string(),
             Intent::DomainPolicyBound { .. } => "domain.policy.bound".to_string(),
+            Intent::DomainSagaProgressed { .. } => "domain.saga.progressed".to_string(),
 
             // ===== Port Intents =====
             Intent::PortStorageWriteCompleted { .. } => "port.storage.write.completed".to_string(),
@@ -647,6 +653,7 @@ impl Intent {
                 | Intent::DomainRoleCreated { .. }
                 | Intent::DomainPolicyCreated { .. }
                 | Intent::DomainPolicyBound { .. }
+                | Intent::DomainSagaProgressed { .. }
         )
     }
 
@@ -690,6 +697,7 @@ impl Intent {
             | Intent::UiPassphraseChanged(_)
             | Intent::UiPassphraseConfirmChanged(_)
             | Intent::UiGraphPropertyChanged { .. }
+            | Intent::DomainSagaProgressed { .. }
         ) == false
     }
 
@@ -706,6 +714,7 @@ impl Intent {
     /// - UiPassphraseChanged
     /// - UiPassphraseConfirmChanged
     /// - UiGraphPropertyChanged
+    /// - DomainSagaProgressed (a saga's progress holds until it advances)
     pub fn is_step_signal(&self) -> bool {
         matches!(
             self,
@@ -716,6 +725,7 @@ impl Intent {
             | Intent::UiPassphraseChanged(_)
             | Intent::UiPassphraseConfirmChanged(_)
             | Intent::UiGraphPropertyChanged { .. }
+            | Intent::DomainSagaProgressed { .. }
         )
     }
 
@@ -842,6 +852,26 @@ mod tests {
         }.is_step_signal());
     }
 
+    fn saga_progress() -> crate::domain::sagas::SagaProgress {
+        crate::domain::sagas::SagaProgress {
+            saga_id: uuid::Uuid::now_v7(),
+            saga_type: "CertificateProvisioning".into(),
+            current_step: Some("IssueCertificate".into()),
+            steps_done: 1,
+            steps_total: 3,
+            status: crate::domain::sagas::RunStatus::Running,
+        }
+    }
+
+    #[test]
+    fn test_saga_progress_is_domain_step() {
+        let intent = Intent::DomainSagaProgressed { progress: saga_progress() };
+
+        assert!(intent.is_step_signal());
+        assert!(intent.is_domain_originated());
+        assert_eq!(intent.to_subject(), "domain.saga.progressed");
+    }
+
     #[test]
     fn test_step_intents_not_events() {
         // Step signals should NOT be event signals
@@ -946,10 +976,11 @@ mod tests {
                 property: "".into(),
                 value: "".into(),
             },
+            Intent::DomainSagaProgressed { progress: saga_progress() },
         ];
 
-        // Verify all 7 step intents are classified correctly
-        assert_eq!(all_step_intents.len(), 7);
+        // Verify all 8 step intents are classified correctly
+        assert_eq!(all_step_intents.len(), 8);
         for intent in &all_step_intents {
             assert!(
                 intent.is_step_signal(),
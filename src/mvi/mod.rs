@@ -32,5 +32,6 @@ pub use signals_aliases::{
     EventIntent, StepValue, ModelSignal,
     UpdateInputs, UpdateOutputs,
     OrganizationNameSignal, PersonEmailSignal, PassphraseSignal,
+    SagaProgressSignal,
     AnimationTime, ProgressSignal,
 };
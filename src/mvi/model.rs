@@ -3,8 +3,13 @@
 //! The Model contains ONLY UI state and display projections.
 //! NO communication state, NO port instances, NO async operations.
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use iced::Point;
+use uuid::Uuid;
+
+use crate::domain::sagas::SagaProgress;
+use super::signals_aliases::SagaProgressSignal;
 
 /// Pure Display Model for MVI Layer
 #[derive(Debug, Clone)]
@@ -33,6 +38,8 @@ pub struct Model {
     pub status_message: String,
     pub error_message: Option<String>,
     pub key_generation_progress: f32,
+    /// Progress of each saga reported so far, keyed by saga ID (start order)
+    pub saga_progress: BTreeMap<Uuid, SagaProgressSignal>,
 
     // ===== Output Configuration =====
     pub output_directory: PathBuf,
@@ -140,6 +147,7 @@ impl Default for Model {
             status_message: "Welcome to CIM Keys".to_string(),
             error_message: None,
             key_generation_progress: 0.0,
+            saga_progress: BTreeMap::new(),
             output_directory: PathBuf::from("/tmp/cim-keys-output"),
             // Graph UI state
             graph_context_menu_visible: false,
@@ -278,6 +286,25 @@ impl Model {
         self
     }
 
+    /// Record a saga's latest progress (pure function)
+    ///
+    /// Moves the saga's step signal to the new value, creating the signal
+    /// the first time the saga reports.
+    pub fn with_saga_progress(mut self, progress: SagaProgress) -> Self {
+        let signal = match self.saga_progress.get(&progress.saga_id) {
+            Some(signal) => signal.with_value(progress.clone()),
+            None => SagaProgressSignal::step(progress.clone()),
+        };
+        self.saga_progress.insert(progress.saga_id, signal);
+        self
+    }
+
+    /// Forget a finished saga's progress (pure function)
+    pub fn without_saga_progress(mut self, saga_id: Uuid) -> Self {
+        self.saga_progress.remove(&saga_id);
+        self
+    }
+
     /// Update passphrase (pure function)
     pub fn with_passphrase(mut self, passphrase: String) -> Self {
         self.passphrase = passphrase;
@@ -32,6 +32,7 @@
 
 use crate::signals::{Signal, SignalVec2, EventKind, StepKind, ContinuousKind};
 use super::{Intent, Model};
+use crate::domain::sagas::SagaProgress;
 
 // =============================================================================
 // Intent Signals
@@ -179,6 +180,20 @@ pub type PersonEmailSignal = StepValue<String>;
 /// Holds the current passphrase value (note: should be cleared after use).
 pub type PassphraseSignal = StepValue<String>;
 
+/// Progress of one running saga as a step signal
+///
+/// Holds the saga's last reported progress until the engine advances it
+/// (see `SagaEngine::progress_signal`). The model keeps one per saga and
+/// the view renders each as a progress bar.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let signal = engine.progress_signal(&run);
+/// assert_eq!(signal.sample(0.0).current_step.as_deref(), Some("GenerateKey"));
+/// ```
+pub type SagaProgressSignal = StepValue<SagaProgress>;
+
 // =============================================================================
 // Future: Continuous Signals
 // =============================================================================
@@ -12,6 +12,7 @@ use iced::{
     Element, Length, Color, Border, Font, Theme, Background, Alignment,
 };
 use crate::icons::verified;
+use crate::domain::sagas::RunStatus;
 
 /// Pure view function for MVI layer
 ///
@@ -438,6 +439,8 @@ fn view_keys(model: &Model) -> Element<'_, Intent> {
     .spacing(10)
     .padding(20);
 
+    let saga_progress = view_saga_progress(model);
+
     // Step 2: Intermediate CA Generation
     let intermediate_ca_section = {
         let mut ca_list_items = vec![
@@ -568,6 +571,7 @@ fn view_keys(model: &Model) -> Element<'_, Intent> {
             container(text("")).height(Length::Fixed(20.0)), // Spacer
             key_status,
             progress,
+            saga_progress,
             intermediate_ca_section,
             server_cert_section,
             actions,
@@ -578,6 +582,33 @@ fn view_keys(model: &Model) -> Element<'_, Intent> {
     .into()
 }
 
+/// One progress bar per saga, sampled from its progress signal
+fn view_saga_progress(model: &Model) -> Element<'_, Intent> {
+    let mut sagas = Column::new().spacing(10).padding(20);
+
+    for signal in model.saga_progress.values() {
+        let progress = signal.sample(0.0);
+        let step = match (&progress.current_step, progress.status) {
+            (Some(step), _) => step.clone(),
+            (None, RunStatus::Completed) => "Completed".to_string(),
+            (None, _) => "Failed".to_string(),
+        };
+
+        sagas = sagas.push(column![
+            text(format!("{} - {}", progress.saga_type, step)).size(14),
+            progress_bar(0.0..=1.0, progress.percent() as f32 / 100.0),
+            text(format!(
+                "{}/{} steps ({}%)",
+                progress.steps_done,
+                progress.steps_total,
+                progress.percent()
+            )).size(12),
+        ].spacing(5));
+    }
+
+    sagas.into()
+}
+
 /// Projections tab view - bidirectional domain state mappings
 fn view_projections(model: &Model) -> Element<'_, Intent> {
     // SD Card projection status (the original export functionality)
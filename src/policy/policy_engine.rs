@@ -10,7 +10,10 @@ use cim_domain_policy::{
     PolicyError as DomainPolicyError,
 };
 use cim_domain_policy::services::{PolicyTemplateEngine, EvaluationError};
-use crate::domain::{Organization, Person};
+use crate::commands::authorization::CommandAuthorizer;
+use crate::commands::KeyCommand;
+use crate::domain::{Organization, Person, PolicyClaim, PolicyEvaluation};
+use crate::events::authorization::CommandDeniedEvent;
 use crate::policy::PkiPolicySet;
 use std::collections::HashMap;
 use thiserror::Error;
@@ -48,6 +51,8 @@ pub struct KeyPolicyEngine {
     conflict_resolver: PolicyConflictResolver,
     /// Template engine
     template_engine: PolicyTemplateEngine,
+    /// Claims required per `KeyCommand` type
+    authorizer: CommandAuthorizer,
 }
 
 impl KeyPolicyEngine {
@@ -59,9 +64,37 @@ impl KeyPolicyEngine {
             exemptions: Vec::new(),
             conflict_resolver: PolicyConflictResolver::new(ConflictResolution::MostRestrictive),
             template_engine: PolicyTemplateEngine::new(),
+            authorizer: CommandAuthorizer::default(),
         }
     }
 
+    /// Require `claims` for a command type instead of the default
+    pub fn require_claims(&mut self, command_type: &'static str, claims: Vec<PolicyClaim>) {
+        self.authorizer.require_claims(command_type, claims);
+    }
+
+    /// Claims an actor needs to issue a command
+    pub fn required_claims(&self, command: &KeyCommand) -> Vec<PolicyClaim> {
+        self.authorizer.required_claims(command)
+    }
+
+    /// The command claims table, for `KeyManagementAggregate::with_authorizer`
+    pub fn authorizer(&self) -> &CommandAuthorizer {
+        &self.authorizer
+    }
+
+    /// Check a command against the claims granted to its actor
+    ///
+    /// See `CommandAuthorizer::authorize`.
+    pub fn authorize_command(
+        &self,
+        command: &KeyCommand,
+        actor: &PolicyEvaluation,
+        policies: &[crate::domain::Policy],
+    ) -> Result<(), Box<CommandDeniedEvent>> {
+        self.authorizer.authorize(command, actor, policies)
+    }
+
     /// Register an exemption
     pub fn add_exemption(&mut self, exemption: PolicyExemption) {
         if exemption.is_valid() {
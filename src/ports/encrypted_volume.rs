@@ -0,0 +1,98 @@
+//! Encrypted volume port for the partitions exports are written to
+//!
+//! Offline projections live on an encrypted partition. This port creates
+//! such a partition on a target device and opens it only for as long as an
+//! export runs:
+//!
+//! ```text
+//! format(device, key, label)          once per card → LUKS UUID
+//! open(device, key, mount_point)      unlock + mount → OpenVolume
+//!   (write the export, record OpenVolume::luks_uuid in the manifest)
+//! close(volume)                       sync + unmount + lock
+//! ```
+
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::yubikey::SecureString;
+use crate::crypto::kdf::{DerivedKey, KdfPurpose};
+
+/// Port for encrypted volume operations
+#[async_trait]
+pub trait EncryptedVolumePort: Send + Sync {
+    /// Erase `device` and create an encrypted volume with an empty
+    /// filesystem labelled `label`, returning the volume's UUID
+    async fn format(&self, device: &Path, key: &VolumeKey, label: &str) -> Result<String, EncryptedVolumeError>;
+
+    /// Unlock the volume on `device` and mount it at `mount_point`
+    async fn open(
+        &self,
+        device: &Path,
+        key: &VolumeKey,
+        mount_point: &Path,
+    ) -> Result<OpenVolume, EncryptedVolumeError>;
+
+    /// Flush, unmount and lock a volume opened with [`open`](Self::open)
+    async fn close(&self, volume: &OpenVolume) -> Result<(), EncryptedVolumeError>;
+}
+
+/// Secret unlocking a volume
+#[derive(Debug, Clone)]
+pub enum VolumeKey {
+    Passphrase(SecureString),
+    /// File whose whole contents are the key
+    Keyfile(PathBuf),
+}
+
+impl VolumeKey {
+    /// Write a key derived for [`KdfPurpose::LuksKeyfile`] to `path`
+    /// (owner-only on Unix) and use it as the keyfile
+    ///
+    /// The file should live on tmpfs and be removed once the volume is
+    /// formatted or opened; the key can always be derived again from the
+    /// passphrase and the salt in the manifest.
+    pub fn keyfile_from(key: &DerivedKey, path: &Path) -> Result<VolumeKey, EncryptedVolumeError> {
+        let bytes = key
+            .bytes_for(KdfPurpose::LuksKeyfile)
+            .map_err(|e| EncryptedVolumeError::CommandFailed(e.to_string()))?;
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(path)
+            .and_then(|mut file| std::io::Write::write_all(&mut file, bytes))
+            .map_err(|e| EncryptedVolumeError::CommandFailed(format!("Failed to write keyfile {}: {}", path.display(), e)))?;
+        Ok(VolumeKey::Keyfile(path.to_path_buf()))
+    }
+}
+
+/// An unlocked and mounted volume
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpenVolume {
+    /// Encrypted block device, e.g. `/dev/sdb1`
+    pub device: PathBuf,
+    /// UUID of the volume, stable across hosts
+    pub luks_uuid: String,
+    /// Name of the device-mapper mapping
+    pub mapper_name: String,
+    /// Where the decrypted filesystem is mounted
+    pub mount_point: PathBuf,
+}
+
+/// Encrypted volume errors
+#[derive(Debug, Error)]
+pub enum EncryptedVolumeError {
+    #[error("Device not found: {0}")]
+    DeviceNotFound(String),
+
+    #[error("Device {0} is not an encrypted volume")]
+    NotEncrypted(String),
+
+    #[error("Wrong passphrase or keyfile for {0}")]
+    WrongKey(String),
+
+    #[error("Command failed: {0}")]
+    CommandFailed(String),
+}
@@ -0,0 +1,113 @@
+//! Event store port
+//!
+//! Durable, append-only storage of [`EventEnvelope`]s grouped into one
+//! stream per aggregate. Aggregates are rehydrated by replaying their
+//! stream; concurrent writers are serialized with optimistic concurrency.
+//!
+//! ## Sequences
+//!
+//! Every stored event gets a sequence number that increases across the
+//! whole store (the JetStream stream sequence). An aggregate's *version* is
+//! the sequence of its last event, `0` while it has none. Writers pass the
+//! version they loaded as [`ExpectedSequence::Exact`]; if another writer
+//! appended in between, the append fails with
+//! [`EventStoreError::ConcurrencyConflict`] and the command is retried on
+//! fresh state.
+//!
+//! ```text
+//! load(aggregate) → events, version
+//!   ↓ handle command
+//! append(aggregate, Exact(version), new events)
+//!   ├─ Ok(new version)
+//!   └─ ConcurrencyConflict → reload and retry
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::events::EventEnvelope;
+
+/// Port for durable event storage
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    /// Append events to an aggregate's stream
+    ///
+    /// `expected` is checked against the aggregate's version before the
+    /// first event is written. Returns the aggregate's new version.
+    /// Appending no events only checks `expected`.
+    async fn append(
+        &self,
+        aggregate_id: Uuid,
+        expected: ExpectedSequence,
+        events: &[EventEnvelope],
+    ) -> Result<u64, EventStoreError>;
+
+    /// Load an aggregate's events in order
+    async fn load(&self, aggregate_id: Uuid) -> Result<Vec<StoredEvent>, EventStoreError> {
+        self.load_after(aggregate_id, 0).await
+    }
+
+    /// Load an aggregate's events with a sequence greater than `sequence`
+    async fn load_after(
+        &self,
+        aggregate_id: Uuid,
+        sequence: u64,
+    ) -> Result<Vec<StoredEvent>, EventStoreError>;
+
+    /// Current version of an aggregate (`0` if it has no events)
+    async fn version(&self, aggregate_id: Uuid) -> Result<u64, EventStoreError> {
+        Ok(self.load(aggregate_id).await?.last().map_or(0, |e| e.sequence))
+    }
+}
+
+/// Version an append expects the aggregate to be at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExpectedSequence {
+    /// Append unconditionally
+    Any,
+    /// The aggregate must have no events yet
+    NoEvents,
+    /// The aggregate's last event must have this sequence
+    Exact(u64),
+}
+
+impl ExpectedSequence {
+    /// Sequence the aggregate's last event must have, if checked
+    /// (`Some(0)` for an aggregate without events)
+    pub fn sequence(&self) -> Option<u64> {
+        match self {
+            ExpectedSequence::Any => None,
+            ExpectedSequence::NoEvents => Some(0),
+            ExpectedSequence::Exact(sequence) => Some(*sequence),
+        }
+    }
+}
+
+/// An event as stored, with its position in the store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredEvent {
+    /// Store-wide sequence number
+    pub sequence: u64,
+    /// The stored envelope
+    pub envelope: EventEnvelope,
+}
+
+/// Errors for event store operations
+#[derive(Debug, thiserror::Error)]
+pub enum EventStoreError {
+    #[error("Concurrency conflict on aggregate {aggregate_id}: expected version {expected}")]
+    ConcurrencyConflict { aggregate_id: Uuid, expected: u64 },
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
+    #[error("Connection error: {0}")]
+    ConnectionError(String),
+
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    #[error("Signature error: {0}")]
+    SignatureError(#[from] crate::events::EventSignatureError),
+}
@@ -13,7 +13,9 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::crypto::SecretBytes;
 use crate::ports::yubikey::SecureString;
+use crate::types::{EncryptionFormat, OpenPgpCardSlot};
 
 /// Port for GPG/PGP operations
 ///
@@ -48,12 +50,26 @@ pub trait GpgPort: Send + Sync {
     ) -> Result<Vec<u8>, GpgError>;
 
     /// Export private key (encrypted)
+    ///
+    /// The secret key material is always protected with the passphrase
+    /// (OpenPGP S2K). An empty passphrase is rejected with
+    /// `GpgError::InvalidPassphrase`.
     async fn export_private_key(
         &self,
         key_id: &GpgKeyId,
         passphrase: &SecureString,
     ) -> Result<Vec<u8>, GpgError>;
 
+    /// Import a passphrase-protected private key
+    ///
+    /// **Functor Mapping**: (encrypted TSK, passphrase) → KeyId
+    /// Inverse of export_private_key: import(export(k, p), p) = k
+    async fn import_private_key(
+        &self,
+        key_data: &[u8],
+        passphrase: &SecureString,
+    ) -> Result<GpgKeyId, GpgError>;
+
     /// Sign data
     ///
     /// **Functor Mapping**: (key, data) → Signature
@@ -92,6 +108,45 @@ pub trait GpgPort: Send + Sync {
         encrypted_data: &[u8],
     ) -> Result<Vec<u8>, GpgError>;
 
+    /// Encrypt data with a passphrase (OpenPGP SKESK)
+    ///
+    /// **Functor Mapping**: (passphrase, data) → CiphertextMessage
+    /// An empty passphrase is rejected with `GpgError::InvalidPassphrase`.
+    async fn encrypt_symmetric(
+        &self,
+        data: &[u8],
+        passphrase: &SecureString,
+    ) -> Result<Vec<u8>, GpgError>;
+
+    /// Decrypt passphrase-encrypted data
+    ///
+    /// **Functor Mapping**: (passphrase, ciphertext) → PlaintextMessage
+    /// Inverse of encrypt_symmetric: decrypt_symmetric(encrypt_symmetric(m, p), p) = m
+    async fn decrypt_symmetric(
+        &self,
+        encrypted_data: &[u8],
+        passphrase: &SecureString,
+    ) -> Result<Vec<u8>, GpgError>;
+
+    /// Encrypt an artifact in the selected format
+    ///
+    /// `PgpPublicKey` requires recipients, `PgpSymmetric` requires a passphrase.
+    async fn encrypt_with_format(
+        &self,
+        format: EncryptionFormat,
+        recipient_keys: &[GpgKeyId],
+        passphrase: Option<&SecureString>,
+        data: &[u8],
+    ) -> Result<Vec<u8>, GpgError> {
+        match format {
+            EncryptionFormat::PgpPublicKey => self.encrypt(recipient_keys, data).await,
+            EncryptionFormat::PgpSymmetric => {
+                let passphrase = passphrase.ok_or(GpgError::InvalidPassphrase)?;
+                self.encrypt_symmetric(data, passphrase).await
+            }
+        }
+    }
+
     /// List keys in keyring
     async fn list_keys(&self, secret: bool) -> Result<Vec<GpgKeyInfo>, GpgError>;
 
@@ -102,6 +157,43 @@ pub trait GpgPort: Send + Sync {
         reason: RevocationReason,
     ) -> Result<Vec<u8>, GpgError>;
 
+    /// Set the expiration of a key and its subkeys
+    ///
+    /// Re-issues the self-signature and subkey binding signatures with the
+    /// new expiration and returns the updated public certificate.
+    /// `None` removes the expiration.
+    async fn set_expiration(
+        &self,
+        key_id: &GpgKeyId,
+        expires_in_days: Option<u32>,
+    ) -> Result<Vec<u8>, GpgError>;
+
+    /// Move a (sub)key's secret material onto an OpenPGP card (keytocard)
+    ///
+    /// **Functor Mapping**: (key, card slot) → CardStub
+    /// The keyring keeps only a stub that diverts secret key operations to
+    /// the card with the given serial. Requires the card's admin PIN.
+    async fn move_to_card(
+        &self,
+        key_id: &GpgKeyId,
+        slot: OpenPgpCardSlot,
+        card_serial: &str,
+        admin_pin: &SecureString,
+    ) -> Result<GpgCardStub, GpgError>;
+
+    /// Certify another key's user ID, optionally as a trust signature (tsig)
+    ///
+    /// **Functor Mapping**: (certifier, target, user ID) → CertificationSignature
+    /// With `trust` set the signature also delegates introducer trust
+    /// (OpenPGP trust signature subpacket: depth, amount, optional scope).
+    async fn certify_key(
+        &self,
+        certifier_key_id: &GpgKeyId,
+        target_key_id: &GpgKeyId,
+        user_id: &str,
+        trust: Option<&GpgTrustSignature>,
+    ) -> Result<Vec<u8>, GpgError>;
+
     /// Add subkey
     async fn add_subkey(
         &self,
@@ -120,7 +212,7 @@ pub struct GpgKeyId(pub String);
 pub struct GpgKeypair {
     pub key_id: GpgKeyId,
     pub public_key: Vec<u8>,
-    pub private_key: Vec<u8>,
+    pub private_key: SecretBytes,
     pub fingerprint: String,
     pub user_id: String,
 }
@@ -156,6 +248,25 @@ pub struct GpgKeyInfo {
     pub is_expired: bool,
 }
 
+/// Trust signature parameters (RFC 4880 §5.2.3.13)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GpgTrustSignature {
+    /// 0 = plain certification, 1 = trusted introducer, 2+ = meta-introducer
+    pub depth: u8,
+    /// 60 = partial trust, 120 = complete trust
+    pub amount: u8,
+    /// Optional regular expression limiting the user IDs the trust applies to
+    pub scope: Option<String>,
+}
+
+/// Keyring stub left behind after a key was moved to a card
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GpgCardStub {
+    pub key_id: GpgKeyId,
+    pub slot: OpenPgpCardSlot,
+    pub card_serial: String,
+}
+
 /// Signature verification result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GpgVerification {
@@ -0,0 +1,143 @@
+//! Hardware security module port
+//!
+//! Root and intermediate CA keys can live in a PKCS#11 token (a network
+//! HSM, or SoftHSM for rehearsals) instead of a YubiKey. Keys never leave
+//! the token: generation returns a reference (slot, label and CKA_ID) plus
+//! the public key, and signing goes through the token.
+//!
+//! HSM keys are recorded like YubiKey keys: a `KeyGeneratedEvent` with
+//! `hardware_backed: true`, the token serial in `metadata.hardware_serial`
+//! and the slot and label in the metadata attributes (see
+//! `commands::hsm`).
+//!
+//! **Category Theory Perspective:**
+//! - **Source Category**: PKCS#11 (tokens, key objects, mechanisms)
+//! - **Target Category**: Domain (hardware-backed CA keys)
+//! - **Functor**: HsmPort maps token operations to domain operations
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::yubikey::SecureString;
+use crate::types::KeyAlgorithm;
+
+/// Port for keys held in a hardware security module
+#[async_trait]
+pub trait HsmPort: Send + Sync {
+    /// List slots with an initialized token
+    async fn list_tokens(&self) -> Result<Vec<HsmToken>, HsmError>;
+
+    /// Generate a non-extractable key pair labelled `label` on the token
+    /// in `slot_id`
+    ///
+    /// **Functor Mapping**: (token, algorithm) → KeyPair
+    async fn generate_key_pair(
+        &self,
+        slot_id: u64,
+        label: &str,
+        algorithm: HsmKeyAlgorithm,
+        pin: &SecureString,
+    ) -> Result<HsmKeyPair, HsmError>;
+
+    /// Find the key pair labelled `label` on the token in `slot_id`
+    async fn find_key_pair(
+        &self,
+        slot_id: u64,
+        label: &str,
+        pin: &SecureString,
+    ) -> Result<Option<HsmKeyPair>, HsmError>;
+
+    /// Sign `data` with the private key behind `key`
+    ///
+    /// Ed25519 signs `data` itself, ECDSA returns `r || s` over SHA-256 or
+    /// SHA-384 of `data`, RSA returns PKCS#1 v1.5 over SHA-256 of `data`.
+    ///
+    /// **Functor Mapping**: (key, data) → Signature
+    async fn sign(&self, key: &HsmKeyRef, data: &[u8], pin: &SecureString) -> Result<Vec<u8>, HsmError>;
+}
+
+/// Initialized token in a slot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HsmToken {
+    pub slot_id: u64,
+    pub label: String,
+    pub serial: String,
+    pub manufacturer: String,
+    pub model: String,
+}
+
+/// Key algorithms supported for HSM keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HsmKeyAlgorithm {
+    Ed25519,
+    EcdsaP256,
+    EcdsaP384,
+    Rsa3072,
+    Rsa4096,
+}
+
+impl HsmKeyAlgorithm {
+    /// Domain algorithm recorded in events
+    pub fn key_algorithm(&self) -> KeyAlgorithm {
+        match self {
+            HsmKeyAlgorithm::Ed25519 => KeyAlgorithm::Ed25519,
+            HsmKeyAlgorithm::EcdsaP256 => KeyAlgorithm::Ecdsa { curve: "P-256".to_string() },
+            HsmKeyAlgorithm::EcdsaP384 => KeyAlgorithm::Ecdsa { curve: "P-384".to_string() },
+            HsmKeyAlgorithm::Rsa3072 => KeyAlgorithm::Rsa { bits: 3072 },
+            HsmKeyAlgorithm::Rsa4096 => KeyAlgorithm::Rsa { bits: 4096 },
+        }
+    }
+}
+
+/// Where a key lives on an HSM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HsmKeyRef {
+    /// Serial of the token holding the key
+    pub token_serial: String,
+    pub slot_id: u64,
+    /// CKA_LABEL of the key objects
+    pub label: String,
+    /// CKA_ID of the key objects, hex
+    pub object_id: String,
+}
+
+/// Public half and location of a key pair generated on an HSM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HsmKeyPair {
+    pub key_ref: HsmKeyRef,
+    pub algorithm: HsmKeyAlgorithm,
+    /// Raw Ed25519 key, SEC1 uncompressed point, or PKCS#1 DER for RSA
+    pub public_key: Vec<u8>,
+}
+
+/// HSM operation errors
+#[derive(Debug, Error)]
+pub enum HsmError {
+    #[error("Failed to load PKCS#11 module: {0}")]
+    ModuleLoadFailed(String),
+
+    #[error("No token in slot {0}")]
+    TokenNotFound(u64),
+
+    #[error("Key {0} not found")]
+    KeyNotFound(String),
+
+    #[error("Key labelled {0} already exists")]
+    KeyExists(String),
+
+    #[error("Login failed: {0}")]
+    LoginFailed(String),
+
+    #[error("Algorithm {0:?} not supported by the token")]
+    UnsupportedAlgorithm(HsmKeyAlgorithm),
+
+    #[error("Key generation failed: {0}")]
+    GenerationFailed(String),
+
+    #[error("Signing failed: {0}")]
+    SigningFailed(String),
+
+    #[error("PKCS#11 error: {0}")]
+    Pkcs11(String),
+}
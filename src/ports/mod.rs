@@ -9,12 +9,20 @@
 //! to the Domain category, preserving structure and composition laws.
 
 pub mod nats;
+pub mod event_store;
+pub mod snapshot_store;
+pub mod saga_store;
 pub mod storage;
 pub mod yubikey;
 pub mod x509;
 pub mod gpg;
 pub mod ssh;
 pub mod neo4j;
+pub mod removable_media;
+pub mod encrypted_volume;
+pub mod rng;
+pub mod hsm;
+pub mod tpm;
 
 pub use nats::{
     // Key management port
@@ -27,6 +35,9 @@ pub use nats::{
     // KV store
     KvBucketConfig,
 };
+pub use event_store::{EventStore, EventStoreError, ExpectedSequence, StoredEvent};
+pub use snapshot_store::{SnapshotStore, AggregateSnapshot};
+pub use saga_store::{SagaStore, SagaRecord, SagaStoreError};
 pub use storage::{StoragePort, StorageConfig, StorageMetadata, StorageError, SyncMode};
 pub use yubikey::{
     YubiKeyPort, YubiKeyDevice, YubiKeyError, PivSlot, KeyAlgorithm,
@@ -60,4 +71,11 @@ pub use neo4j::{
     ToGraphNode, ToGraphEdge,
     // Configuration and errors
     Neo4jConfig, Neo4jError,
-};
\ No newline at end of file
+};
+pub use removable_media::{
+    RemovableMediaPort, RemovableDevice, MediaRequirements, RemovableMediaError,
+};
+pub use encrypted_volume::{EncryptedVolumePort, VolumeKey, OpenVolume, EncryptedVolumeError};
+pub use rng::{CryptoRngPort, OsCryptoRng, PortRng};
+pub use hsm::{HsmPort, HsmToken, HsmKeyAlgorithm, HsmKeyRef, HsmKeyPair, HsmError};
+pub use tpm::{TpmPort, TpmIdentity, PcrBank, PcrSelection, PcrValue, TpmKeyAlgorithm, TpmKey, TpmError};
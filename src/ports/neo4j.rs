@@ -31,7 +31,7 @@ use thiserror::Error;
 ///
 /// This is the interface that our domain uses for graph persistence.
 /// Implementations could be:
-/// - Neo4rs adapter (real Neo4j connection)
+/// - `Neo4jBoltAdapter` (real Neo4j connection, `neo4j` feature)
 /// - File adapter (writes .cypher files)
 /// - Mock adapter (for testing)
 #[async_trait]
@@ -434,23 +434,18 @@ impl GraphNode {
     }
 
     /// Generate MERGE query for this node
+    ///
+    /// Matches on the label and `id` only and sets the properties
+    /// afterwards, so running it again with changed properties updates the
+    /// node instead of creating a second one.
     pub fn to_merge_query(&self) -> CypherQuery {
-        let props: Vec<String> = self
-            .properties
-            .iter()
-            .map(|(k, v)| format!("{}: {}", k, v.to_cypher_literal()))
-            .collect();
-
-        let props_str = if props.is_empty() {
-            String::new()
-        } else {
-            format!(", {}", props.join(", "))
-        };
-
-        CypherQuery::new(format!(
-            "MERGE (n:{} {{id: '{}'{}}})",
-            self.label, self.id, props_str
-        ))
+        let mut query = CypherQuery::new(format!("MERGE (n:{} {{id: $id}})", self.label))
+            .with_param("id", self.id.to_string());
+        if !self.properties.is_empty() {
+            query.query.push_str(" SET n += $properties");
+            query = query.with_param("properties", CypherValue::Map(self.properties.clone()));
+        }
+        query
     }
 }
 
@@ -479,23 +474,21 @@ impl GraphEdge {
     }
 
     /// Generate MERGE query for this relationship
+    ///
+    /// At most one relationship of the type between the two nodes; the
+    /// properties are set on it rather than being part of the match.
     pub fn to_merge_query(&self) -> CypherQuery {
-        let props: Vec<String> = self
-            .properties
-            .iter()
-            .map(|(k, v)| format!("{}: {}", k, v.to_cypher_literal()))
-            .collect();
-
-        let props_str = if props.is_empty() {
-            String::new()
-        } else {
-            format!(" {{{}}}", props.join(", "))
-        };
-
-        CypherQuery::new(format!(
-            "MATCH (a {{id: '{}'}}), (b {{id: '{}'}}) MERGE (a)-[:{}{}]->(b)",
-            self.from_id, self.to_id, self.relationship_type, props_str
+        let mut query = CypherQuery::new(format!(
+            "MATCH (a {{id: $from}}), (b {{id: $to}}) MERGE (a)-[r:{}]->(b)",
+            self.relationship_type
         ))
+        .with_param("from", self.from_id.to_string())
+        .with_param("to", self.to_id.to_string());
+        if !self.properties.is_empty() {
+            query.query.push_str(" SET r += $properties");
+            query = query.with_param("properties", CypherValue::Map(self.properties.clone()));
+        }
+        query
     }
 }
 
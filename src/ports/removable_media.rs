@@ -0,0 +1,152 @@
+//! Removable media port for locating and preparing export targets
+//!
+//! Exports are written to a path, but the card behind that path has to be
+//! found, checked and mounted first, and flushed and unmounted afterwards
+//! before it is pulled. This port covers that guided flow:
+//!
+//! ```text
+//! list_devices()                      candidate removable devices
+//!   ↓ operator picks one
+//! prepare_export(device, requirements)
+//!   ├─ read-only / wrong filesystem   → error
+//!   ├─ mount if not mounted
+//!   └─ not enough free space          → error
+//!   ↓ mount point
+//! (write the export)
+//!   ↓
+//! finish_export(device)               sync, unmount; safe to remove
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Port for removable media discovery and mounting
+#[async_trait]
+pub trait RemovableMediaPort: Send + Sync {
+    /// Removable block devices that can hold an export
+    async fn list_devices(&self) -> Result<Vec<RemovableDevice>, RemovableMediaError>;
+
+    /// Check `device` against `requirements`, mounting it if needed, and
+    /// return the mount point to export to
+    async fn prepare_export(
+        &self,
+        device: &RemovableDevice,
+        requirements: &MediaRequirements,
+    ) -> Result<PathBuf, RemovableMediaError>;
+
+    /// Flush pending writes and unmount `device` so it can be removed
+    async fn finish_export(&self, device: &RemovableDevice) -> Result<(), RemovableMediaError>;
+}
+
+/// A removable block device, usually a partition of an SD card or USB stick
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemovableDevice {
+    /// Device node, e.g. `/dev/sdb1`
+    pub path: PathBuf,
+    /// Whole-disk device node, e.g. `/dev/sdb`
+    pub disk: PathBuf,
+    /// Model of the disk, if reported
+    pub model: Option<String>,
+    /// Serial number of the disk, if reported
+    pub serial: Option<String>,
+    /// Size in bytes
+    pub size_bytes: u64,
+    /// Filesystem type (`ext4`, `vfat`, `crypto_LUKS`, ...), `None` if unformatted
+    pub filesystem: Option<String>,
+    /// Filesystem label
+    pub label: Option<String>,
+    /// Filesystem UUID
+    pub uuid: Option<String>,
+    /// Where the device is mounted, if it is
+    pub mount_point: Option<PathBuf>,
+    /// Free space in bytes, only known while mounted
+    pub free_bytes: Option<u64>,
+    pub read_only: bool,
+}
+
+impl RemovableDevice {
+    /// Check the parts of `requirements` that do not need the device mounted
+    pub fn check_format(&self, requirements: &MediaRequirements) -> Result<(), RemovableMediaError> {
+        let device = self.path.display().to_string();
+        if self.read_only {
+            return Err(RemovableMediaError::ReadOnly(device));
+        }
+        match &self.filesystem {
+            Some(filesystem) if requirements.filesystems.iter().any(|allowed| allowed == filesystem) => Ok(()),
+            filesystem => Err(RemovableMediaError::UnsupportedFilesystem {
+                device,
+                filesystem: filesystem.clone().unwrap_or_else(|| "none".to_string()),
+            }),
+        }
+    }
+
+    /// Check the free space of the mounted device
+    pub fn check_free_space(&self, requirements: &MediaRequirements) -> Result<(), RemovableMediaError> {
+        let device = self.path.display().to_string();
+        let available = self.free_bytes.ok_or_else(|| RemovableMediaError::NotMounted(device.clone()))?;
+        if available < requirements.min_free_bytes {
+            return Err(RemovableMediaError::InsufficientSpace {
+                device,
+                required: requirements.min_free_bytes,
+                available,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// What an export needs from its target device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaRequirements {
+    /// Free space needed, in bytes
+    pub min_free_bytes: u64,
+    /// Accepted filesystem types
+    pub filesystems: Vec<String>,
+}
+
+impl MediaRequirements {
+    /// Requirements for an export of `bytes`, with room for the event log
+    /// and manifest to grow
+    pub fn for_export(bytes: u64) -> Self {
+        Self {
+            min_free_bytes: bytes.saturating_mul(2),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for MediaRequirements {
+    fn default() -> Self {
+        Self {
+            min_free_bytes: 0,
+            filesystems: vec!["ext4".to_string(), "vfat".to_string(), "exfat".to_string()],
+        }
+    }
+}
+
+/// Removable media errors
+#[derive(Debug, Error)]
+pub enum RemovableMediaError {
+    #[error("No removable device found")]
+    NoDeviceFound,
+
+    #[error("Device {0} is read-only")]
+    ReadOnly(String),
+
+    #[error("Device {device} has unsupported filesystem {filesystem}")]
+    UnsupportedFilesystem { device: String, filesystem: String },
+
+    #[error("Device {device} has {available} bytes free, {required} required")]
+    InsufficientSpace { device: String, required: u64, available: u64 },
+
+    #[error("Device {0} is not mounted")]
+    NotMounted(String),
+
+    #[error("Command failed: {0}")]
+    CommandFailed(String),
+
+    #[error("Parse error: {0}")]
+    ParseError(String),
+}
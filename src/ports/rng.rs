@@ -0,0 +1,72 @@
+//! Randomness port for key generation
+//!
+//! Every path that generates key material or other ceremony secrets draws
+//! its randomness from a [`CryptoRngPort`] instead of calling the OS RNG
+//! directly. Production code uses [`OsCryptoRng`]; tests and rehearsal
+//! ceremonies inject a seeded generator
+//! ([`SeededCryptoRng`](crate::adapters::SeededCryptoRng)) so the same seed
+//! reproduces the same keys, shares and salts byte for byte.
+//!
+//! Libraries that expect a `rand_core` generator (`ssh-key`, `x25519-dalek`,
+//! `frost-ed25519`, ...) are given [`PortRng`], which borrows the port.
+
+use rand::{CryptoRng, RngCore};
+
+/// Port for cryptographically secure random bytes
+pub trait CryptoRngPort: Send + Sync + std::fmt::Debug {
+    /// Fill `dest` with random bytes
+    fn fill_bytes(&self, dest: &mut [u8]);
+}
+
+impl<'r> dyn CryptoRngPort + 'r {
+    /// `N` random bytes
+    pub fn array<const N: usize>(&self) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        self.fill_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Random `u32`
+    pub fn next_u32(&self) -> u32 {
+        u32::from_le_bytes(self.array())
+    }
+
+    /// `rand_core` view of this port for library APIs
+    pub fn rng(&self) -> PortRng<'_> {
+        PortRng(self)
+    }
+}
+
+/// Operating system RNG (`getrandom`), the production default
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsCryptoRng;
+
+impl CryptoRngPort for OsCryptoRng {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        rand::rngs::OsRng.fill_bytes(dest);
+    }
+}
+
+/// `rand_core` generator drawing from a [`CryptoRngPort`]
+pub struct PortRng<'a>(&'a dyn CryptoRngPort);
+
+impl RngCore for PortRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.0.array())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for PortRng<'_> {}
@@ -0,0 +1,101 @@
+//! Saga store port
+//!
+//! Persists a saga's serialized state together with the [`SagaEvents`] it
+//! emitted, so a ceremony interrupted by a crash or a power cycle on the
+//! air-gapped machine can be resumed where it left off:
+//!
+//! ```text
+//! step completes → save(record v, events)   state + events, atomically
+//! ...crash...
+//! incomplete()                              → sagas not yet terminal
+//! load(saga_id)                             → state at version v
+//! ```
+//!
+//! Each save carries the next version; a store refuses a save whose
+//! version does not follow the stored one, so two executors cannot both
+//! advance the same saga.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::events::SagaEvents;
+
+/// Port for persistent saga state
+#[async_trait]
+pub trait SagaStore: Send + Sync {
+    /// Store the saga's state and append the events emitted since the last save
+    ///
+    /// `record.version` must be one more than the stored version (1 for a
+    /// new saga).
+    async fn save(&self, record: &SagaRecord, events: &[SagaEvents]) -> Result<(), SagaStoreError>;
+
+    /// Latest state of a saga, if stored
+    async fn load(&self, saga_id: Uuid) -> Result<Option<SagaRecord>, SagaStoreError>;
+
+    /// Every event stored for a saga, oldest first
+    async fn events(&self, saga_id: Uuid) -> Result<Vec<SagaEvents>, SagaStoreError>;
+
+    /// Sagas that have not reached a terminal state
+    async fn incomplete(&self) -> Result<Vec<SagaRecord>, SagaStoreError>;
+
+    /// Remove a saga and its events
+    async fn delete(&self, saga_id: Uuid) -> Result<(), SagaStoreError>;
+}
+
+/// Serialized saga state at a version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaRecord {
+    /// Saga the state belongs to
+    pub saga_id: Uuid,
+    /// Saga type, e.g. `PersonOnboarding`
+    pub saga_type: String,
+    /// Correlation ID shared by the saga's events
+    pub correlation_id: Uuid,
+    /// Number of saves, starting at 1
+    pub version: u64,
+    /// Step the saga is at
+    pub current_step: String,
+    /// Human-readable status
+    pub status: String,
+    /// Whether the saga completed or failed for good
+    pub terminal: bool,
+    /// When the record was saved
+    pub updated_at: DateTime<Utc>,
+    /// The saga, serialized as JSON
+    pub state: serde_json::Value,
+}
+
+/// Errors from saga storage
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SagaStoreError {
+    #[error("Saga not found: {0}")]
+    NotFound(Uuid),
+
+    #[error("Saga {saga_id} version conflict: stored {stored}, saving {saving}")]
+    VersionConflict { saga_id: Uuid, stored: u64, saving: u64 },
+
+    #[error("Saga type mismatch: expected {expected}, found {found}")]
+    TypeMismatch { expected: String, found: String },
+
+    #[error("Saga {0} is in a terminal state")]
+    Terminated(Uuid),
+
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+
+    #[error("Storage error: {0}")]
+    StorageError(String),
+}
+
+impl SagaStoreError {
+    /// Refuse `saving` unless it directly follows `stored`
+    pub fn check_version(saga_id: Uuid, stored: u64, saving: u64) -> Result<(), Self> {
+        if saving == stored + 1 {
+            Ok(())
+        } else {
+            Err(Self::VersionConflict { saga_id, stored, saving })
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Snapshot store port
+//!
+//! Holds the latest serialized state of an aggregate so rehydration can
+//! start from the snapshot and replay only the events stored after it,
+//! instead of the aggregate's whole stream.
+//!
+//! ```text
+//! load_latest(aggregate)        → state at sequence N
+//! EventStore::load_after(N)     → events N+1..
+//! apply each                    → current state
+//! ```
+//!
+//! Snapshots are a cache: losing one, or finding one written in an older
+//! [`AggregateSnapshot::format_version`], only costs a full replay.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::event_store::EventStoreError;
+
+/// Port for aggregate snapshot storage
+#[async_trait]
+pub trait SnapshotStore: Send + Sync {
+    /// Store a snapshot, replacing any older one for the aggregate
+    async fn save(&self, snapshot: &AggregateSnapshot) -> Result<(), EventStoreError>;
+
+    /// Latest snapshot of an aggregate, if any
+    async fn load_latest(&self, aggregate_id: Uuid) -> Result<Option<AggregateSnapshot>, EventStoreError>;
+}
+
+/// Serialized aggregate state at a point in its event stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregateSnapshot {
+    /// Aggregate the state belongs to
+    pub aggregate_id: Uuid,
+    /// Aggregate type, e.g. `KeyManagement`
+    pub aggregate_type: String,
+    /// Sequence of the last event folded into the state
+    pub sequence: u64,
+    /// Layout of `state`; bumped whenever the aggregate's serialized
+    /// fields change incompatibly
+    pub format_version: u32,
+    /// When the snapshot was taken
+    pub taken_at: DateTime<Utc>,
+    /// The aggregate, serialized as JSON
+    pub state: serde_json::Value,
+}
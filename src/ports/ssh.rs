@@ -13,7 +13,8 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::ports::yubikey::SecureString;
+use crate::crypto::SecretBytes;
+use crate::ports::yubikey::{FidoCredential, SecureString};
 
 /// Port for SSH key operations
 ///
@@ -36,6 +37,17 @@ pub trait SshKeyPort: Send + Sync {
         comment: Option<String>,
     ) -> Result<SshKeypair, SshError>;
 
+    /// Build a security key (sk-*) keypair from a FIDO credential
+    ///
+    /// **Functor Mapping**: FidoCredential → Keypair
+    /// The private key holds only the key handle and flags, so signing
+    /// needs the authenticator that created the credential.
+    async fn security_key_keypair(
+        &self,
+        credential: &FidoCredential,
+        comment: Option<String>,
+    ) -> Result<SshKeypair, SshError>;
+
     /// Parse SSH public key
     ///
     /// **Functor Mapping**: bytes → PublicKey (object construction)
@@ -145,7 +157,7 @@ pub struct SshPublicKey {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SshPrivateKey {
     pub key_type: SshKeyType,
-    pub data: Vec<u8>,
+    pub data: SecretBytes,
     pub public_key: SshPublicKey,
     pub is_encrypted: bool,
 }
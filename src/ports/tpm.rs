@@ -0,0 +1,164 @@
+//! TPM 2.0 port for workstation and service identities
+//!
+//! Workstations and services get keys that never leave their TPM: the key
+//! is created under the storage hierarchy with `fixedTPM`/`fixedParent`,
+//! and its use is bound to a PCR policy, so the key only signs while the
+//! machine boots into the measured state it was enrolled in.
+//!
+//! ```text
+//! identity()                       EK and AK names of this TPM
+//! read_pcrs(selection)             current measurements
+//! generate_key(label, alg, pcrs)   → TpmKey (TPM-wrapped blobs + public key)
+//! sign(key, data)                  PolicyPCR session → signature
+//! ```
+//!
+//! The private blob of a [`TpmKey`] is encrypted by the TPM's storage root
+//! key and can be stored anywhere; only the same TPM can load it. Keys are
+//! recorded in `KeyMetadata::tpm` as a [`TpmKeyReference`].
+//!
+//! **Category Theory Perspective:**
+//! - **Source Category**: TPM 2.0 (objects, policy sessions, PCRs)
+//! - **Target Category**: Domain (machine identities)
+//! - **Functor**: TpmPort maps TPM operations to domain operations
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::types::{KeyAlgorithm, TpmKeyReference};
+
+/// Port for TPM-resident keys
+#[async_trait]
+pub trait TpmPort: Send + Sync {
+    /// Names of the endorsement and attestation keys
+    async fn identity(&self) -> Result<TpmIdentity, TpmError>;
+
+    /// Current values of the selected PCRs, in index order
+    async fn read_pcrs(&self, selection: &PcrSelection) -> Result<Vec<PcrValue>, TpmError>;
+
+    /// Create a non-exportable signing key usable only while the selected
+    /// PCRs hold their current values
+    ///
+    /// **Functor Mapping**: (algorithm, PCR state) → Key
+    async fn generate_key(
+        &self,
+        label: &str,
+        algorithm: TpmKeyAlgorithm,
+        selection: &PcrSelection,
+    ) -> Result<TpmKey, TpmError>;
+
+    /// Sign SHA-256 of `data` with `key`
+    ///
+    /// Fails with [`TpmError::PolicyCheckFailed`] when the PCRs no longer
+    /// match the key's policy. ECDSA returns `r || s`, RSA PKCS#1 v1.5.
+    ///
+    /// **Functor Mapping**: (key, data) → Signature
+    async fn sign(&self, key: &TpmKey, data: &[u8]) -> Result<Vec<u8>, TpmError>;
+}
+
+/// Endorsement and attestation key names of a TPM
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TpmIdentity {
+    /// Name of the endorsement key (hex)
+    pub ek_name: String,
+    /// Name of the attestation key (hex)
+    pub ak_name: String,
+}
+
+/// PCR bank
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PcrBank {
+    Sha256,
+}
+
+impl PcrBank {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PcrBank::Sha256 => "sha256",
+        }
+    }
+}
+
+/// PCRs a key's policy covers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcrSelection {
+    pub bank: PcrBank,
+    /// PCR indices (0-23)
+    pub pcrs: Vec<u8>,
+}
+
+impl PcrSelection {
+    /// Firmware, boot loader and Secure Boot state (PCRs 0, 2, 4, 7)
+    pub fn boot_state() -> Self {
+        Self {
+            bank: PcrBank::Sha256,
+            pcrs: vec![0, 2, 4, 7],
+        }
+    }
+}
+
+/// One PCR value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PcrValue {
+    pub index: u8,
+    /// Digest, hex
+    pub value: String,
+}
+
+/// Key algorithms TPMs commonly support
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TpmKeyAlgorithm {
+    EcdsaP256,
+    Rsa2048,
+}
+
+impl TpmKeyAlgorithm {
+    /// Domain algorithm recorded in events
+    pub fn key_algorithm(&self) -> KeyAlgorithm {
+        match self {
+            TpmKeyAlgorithm::EcdsaP256 => KeyAlgorithm::Ecdsa { curve: "P-256".to_string() },
+            TpmKeyAlgorithm::Rsa2048 => KeyAlgorithm::Rsa { bits: 2048 },
+        }
+    }
+}
+
+/// Key created inside a TPM
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TpmKey {
+    pub label: String,
+    pub algorithm: TpmKeyAlgorithm,
+    /// SEC1 uncompressed point, or PKCS#1 DER for RSA
+    pub public_key: Vec<u8>,
+    /// Marshalled TPM2B_PUBLIC
+    pub public_blob: Vec<u8>,
+    /// TPM2B_PRIVATE, encrypted by the storage root key
+    pub private_blob: Vec<u8>,
+    pub selection: PcrSelection,
+    /// Where the key lives and which policy binds it
+    pub reference: TpmKeyReference,
+}
+
+/// TPM operation errors
+#[derive(Debug, Error)]
+pub enum TpmError {
+    #[error("TPM not available: {0}")]
+    Unavailable(String),
+
+    #[error("Invalid PCR selection: {0}")]
+    InvalidPcrSelection(String),
+
+    #[error("Key generation failed: {0}")]
+    GenerationFailed(String),
+
+    #[error("Key {0} cannot be loaded by this TPM")]
+    KeyNotLoadable(String),
+
+    #[error("PCR policy check failed for key {0}")]
+    PolicyCheckFailed(String),
+
+    #[error("Signing failed: {0}")]
+    SigningFailed(String),
+
+    #[error("TPM error: {0}")]
+    Tss(String),
+}
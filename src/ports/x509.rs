@@ -10,9 +10,12 @@
 //! - **Morphisms Preserved**: sign_csr ∘ generate_csr maintains certificate hierarchy
 
 use async_trait::async_trait;
+use der::zeroize::Zeroize;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::crypto::SecretBytes;
+
 /// Port for X.509 certificate operations
 ///
 /// This is a **Functor** F: X509_PKI → Domain where:
@@ -182,6 +185,26 @@ pub struct Certificate {
 
     /// Public key algorithm
     pub key_algorithm: String,
+
+    /// Subject Alternative Names (`DNS:`, `IP:`, `email:`, `URI:` prefixed)
+    #[serde(default)]
+    pub san: Vec<String>,
+
+    /// Key usage extension
+    #[serde(default)]
+    pub key_usage: Vec<KeyUsage>,
+
+    /// Extended key usages this port understands
+    #[serde(default)]
+    pub extended_key_usage: Vec<ExtendedKeyUsage>,
+
+    /// Every extended key usage OID, including ones without a variant above
+    #[serde(default)]
+    pub extended_key_usage_oids: Vec<String>,
+
+    /// BasicConstraints pathLenConstraint (CA certificates only)
+    #[serde(default)]
+    pub path_len_constraint: Option<u32>,
 }
 
 /// Certificate Signing Request
@@ -201,18 +224,26 @@ pub struct CertificateSigningRequest {
 }
 
 /// Private key (abstract)
+///
+/// Both encodings are wiped on drop.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PrivateKey {
     /// Key algorithm
     pub algorithm: String,
 
     /// Key in DER format
-    pub der: Vec<u8>,
+    pub der: SecretBytes,
 
     /// Key in PEM format
     pub pem: String,
 }
 
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.pem.zeroize();
+    }
+}
+
 /// Key usage extensions
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum KeyUsage {
@@ -238,6 +269,20 @@ pub enum ExtendedKeyUsage {
     OcspSigning,
 }
 
+impl ExtendedKeyUsage {
+    /// Dotted OID of this key purpose (RFC 5280 4.2.1.12)
+    pub fn oid(&self) -> &'static str {
+        match self {
+            ExtendedKeyUsage::ServerAuth => "1.3.6.1.5.5.7.3.1",
+            ExtendedKeyUsage::ClientAuth => "1.3.6.1.5.5.7.3.2",
+            ExtendedKeyUsage::CodeSigning => "1.3.6.1.5.5.7.3.3",
+            ExtendedKeyUsage::EmailProtection => "1.3.6.1.5.5.7.3.4",
+            ExtendedKeyUsage::TimeStamping => "1.3.6.1.5.5.7.3.8",
+            ExtendedKeyUsage::OcspSigning => "1.3.6.1.5.5.7.3.9",
+        }
+    }
+}
+
 /// Certificate format
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum CertificateFormat {
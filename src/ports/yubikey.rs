@@ -13,6 +13,8 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::crypto::SecretBytes;
+
 /// Port for YubiKey hardware token operations
 ///
 /// This is a **Functor** F: YubiKey → Domain where:
@@ -56,6 +58,27 @@ pub trait YubiKeyPort: Send + Sync {
         pin: &SecureString,
     ) -> Result<(), YubiKeyError>;
 
+    /// Read the certificate stored in a PIV slot
+    ///
+    /// **Functor Mapping**: (device, slot) → Option<Certificate>
+    /// Inverse of import_certificate; `None` when the slot holds no certificate
+    async fn read_certificate(
+        &self,
+        serial: &str,
+        slot: PivSlot,
+    ) -> Result<Option<Vec<u8>>, YubiKeyError>;
+
+    /// Delete the certificate stored in a PIV slot
+    ///
+    /// **Functor Mapping**: (device, slot) → ()
+    /// Undoes import_certificate; the slot's key is left in place
+    async fn delete_certificate(
+        &self,
+        serial: &str,
+        slot: PivSlot,
+        management_key: &SecureString,
+    ) -> Result<(), YubiKeyError>;
+
     /// Sign data using PIV key
     ///
     /// **Functor Mapping**: (device, slot, data) → Signature
@@ -123,6 +146,20 @@ pub trait YubiKeyPort: Send + Sync {
         ccc: &[u8],
         pin: &SecureString,
     ) -> Result<(), YubiKeyError>;
+
+    /// Create a FIDO2 credential for an OpenSSH security key
+    ///
+    /// **Functor Mapping**: (device, algorithm, application) → FidoCredential
+    /// The private key never leaves the authenticator; OpenSSH only keeps
+    /// the returned key handle. With a PIN the credential also requires
+    /// user verification.
+    async fn make_fido_credential(
+        &self,
+        serial: &str,
+        algorithm: FidoAlgorithm,
+        application: &str,
+        pin: Option<&SecureString>,
+    ) -> Result<FidoCredential, YubiKeyError>;
 }
 
 /// YubiKey device information
@@ -200,6 +237,56 @@ pub enum KeyAlgorithm {
     Ed25519,
 }
 
+/// FIDO2 credential algorithms usable as OpenSSH security keys
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum FidoAlgorithm {
+    /// EdDSA over Ed25519 (sk-ssh-ed25519@openssh.com)
+    EdDsa,
+
+    /// ECDSA P-256 (sk-ecdsa-sha2-nistp256@openssh.com)
+    Es256,
+}
+
+/// FIDO2 credential created on a YubiKey
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FidoCredential {
+    /// Serial of the YubiKey holding the credential
+    pub serial: String,
+
+    /// Credential algorithm
+    pub algorithm: FidoAlgorithm,
+
+    /// Relying party ID; OpenSSH requires the "ssh:" prefix
+    pub application: String,
+
+    /// Key handle (credential ID) stored in place of a private key
+    pub credential_id: Vec<u8>,
+
+    /// Raw public key (32-byte Ed25519 key or uncompressed SEC1 P-256 point)
+    pub public_key: Vec<u8>,
+
+    /// OpenSSH security key flags
+    pub flags: u8,
+}
+
+impl FidoCredential {
+    /// Relying party ID used by `ssh-keygen` when none is given
+    pub const DEFAULT_APPLICATION: &'static str = "ssh:";
+
+    /// Flag: touch required for every signature
+    pub const USER_PRESENCE_REQUIRED: u8 = 0x01;
+
+    /// Flag: PIN (or biometric) required for every signature
+    pub const USER_VERIFICATION_REQUIRED: u8 = 0x04;
+
+    /// Flag: credential is resident (discoverable) on the authenticator
+    pub const RESIDENT_KEY: u8 = 0x20;
+
+    pub fn requires_user_verification(&self) -> bool {
+        self.flags & Self::USER_VERIFICATION_REQUIRED != 0
+    }
+}
+
 /// Public key returned from key generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PublicKey {
@@ -225,11 +312,11 @@ pub struct Signature {
 
 /// Secure string wrapper (zeroized on drop)
 #[derive(Clone)]
-pub struct SecureString(Vec<u8>);
+pub struct SecureString(SecretBytes);
 
 impl SecureString {
     pub fn new(s: impl AsRef<[u8]>) -> Self {
-        Self(s.as_ref().to_vec())
+        Self(SecretBytes::from_slice(s.as_ref()))
     }
 
     pub fn as_bytes(&self) -> &[u8] {
@@ -237,13 +324,6 @@ impl SecureString {
     }
 }
 
-impl Drop for SecureString {
-    fn drop(&mut self) {
-        // Zero out memory
-        self.0.iter_mut().for_each(|b| *b = 0);
-    }
-}
-
 impl std::fmt::Debug for SecureString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("[REDACTED]")
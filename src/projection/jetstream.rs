@@ -29,8 +29,12 @@
 //! - cowboyai.organization.person.joined
 //! - cowboyai.nats.operator.created
 //! ```
+//!
+//! Every subject is validated against a [`SubjectRegistry`] before the
+//! message is produced; see [`subject_schema`](super::subject_schema).
 
 use crate::projection::{Projection, ProjectionError};
+use crate::projection::subject_schema::SubjectRegistry;
 use crate::ports::nats::{JetStreamHeaders, PublishAck};
 use crate::events::DomainEvent;
 use cim_domain::DomainEvent as DomainEventTrait;  // Import trait for method access
@@ -201,12 +205,14 @@ impl Default for SubjectConfig {
 /// Transforms domain events into JetStream messages ready for publishing.
 pub struct EventsToMessagesProjection {
     config: SubjectConfig,
+    registry: SubjectRegistry,
 }
 
 impl Default for EventsToMessagesProjection {
     fn default() -> Self {
         Self {
             config: SubjectConfig::default(),
+            registry: SubjectRegistry::domain_events(),
         }
     }
 }
@@ -231,6 +237,12 @@ impl EventsToMessagesProjection {
         self
     }
 
+    /// Validate subjects against `registry` instead of the default schema
+    pub fn with_registry(mut self, registry: SubjectRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
     /// Derive subject from event type
     fn event_to_subject(&self, event: &DomainEvent) -> String {
         use crate::events::DomainEvent as DE;
@@ -319,6 +331,7 @@ impl EventsToMessagesProjection {
     /// Create message from event
     fn event_to_message(&self, event: &DomainEvent) -> Result<JetStreamMessageOut, ProjectionError> {
         let subject = self.event_to_subject(event);
+        self.registry.validate(&self.config, &subject)?;
         let event_type = self.get_event_type(event);
         let aggregate_id = self.event_aggregate_id(event);
         let message_id = Uuid::now_v7().to_string();
@@ -470,8 +483,11 @@ mod tests {
                 jwt_kid: None,
                 jwt_alg: None,
                 jwt_use: None,
+                hardware_serial: None,
+                tpm: None,
             },
             ownership: None,
+            expires_at: None,
             correlation_id: Uuid::now_v7(),
             causation_id: None,
         }))
@@ -546,4 +562,18 @@ mod tests {
 
         assert!(message.subject.starts_with("myorg.mydomain."));
     }
+
+    #[test]
+    fn test_subjects_validated_against_registry() {
+        let result = events_for_org("Cowboy AI", "keys").project(vec![sample_key_event()]);
+        assert!(matches!(result, Err(ProjectionError::ValidationFailed { .. })));
+
+        let message = events_to_messages().event_to_message(&sample_key_event()).unwrap();
+        assert_eq!(message.subject, "cim.keys.key.key_generated");
+
+        let result = events_to_messages()
+            .with_registry(SubjectRegistry::new())
+            .project(vec![sample_key_event()]);
+        assert!(result.is_err());
+    }
 }
@@ -84,6 +84,14 @@ pub mod sdcard;
 /// - Batch publishing with metadata
 pub mod jetstream;
 
+/// JetStream subject schema - registry of published subjects per aggregate.
+///
+/// Keeps subjects and downstream consumers aligned:
+/// - Wildcard pattern (and optionally allowed actions) per aggregate
+/// - Validation of every subject before a message is produced
+/// - Subject documentation (Markdown + JSON) included in SD card exports
+pub mod subject_schema;
+
 /// NSC Store projection - NATS credentials → NSC directory structure.
 ///
 /// Transforms domain NATS credentials for NSC (NATS Security CLI):
@@ -93,6 +101,77 @@ pub mod jetstream;
 /// - Seed files (.nk) for key backup
 pub mod nscstore;
 
+/// NSC store import - existing nsc directory → DomainNatsCredentials → NATS events.
+///
+/// Adopts NATS deployments managed with `nsc`:
+/// - Reads operator/account/user JWTs, `.nk` seeds and `.creds` files
+/// - Verifies every JWT signature and the operator → account → user chain
+/// - Emits the creation events of the corresponding NATS aggregates
+pub mod nsc_import;
+
+/// SSH access projection - domain SSH state → authorized_keys/known_hosts.
+///
+/// Renders deployable OpenSSH files for the SD card export:
+/// - Per-host authorized_keys for granted people and service accounts
+/// - `cert-authority` lines for principals holding user certificates
+/// - Org-wide known_hosts with an `@cert-authority` line for host certificates
+pub mod ssh_access;
+
+/// SSH client config projection - domain SSH state → per-person ~/.ssh/config.
+///
+/// Renders a bundle per person for the SD card export:
+/// - Host blocks for every host the person is granted, grouped by location
+/// - IdentityFile entries pointing at exported public keys
+/// - CertificateFile entries for user certificates valid on each host
+pub mod ssh_config;
+
+/// Kubernetes projection - selected credentials → Secret/ConfigMap YAML manifests.
+///
+/// Renders manifests for GitOps pipelines into the export tree:
+/// - `kubernetes.io/tls` Secrets from certificate and key pairs
+/// - Opaque Secrets holding NATS `.creds` files
+/// - ConfigMaps with SSH CA public keys
+/// - A kustomization.yaml listing every manifest
+pub mod kubernetes;
+
+/// Nix secrets projection - admin age keys + host keys + secrets → agenix/sops-nix files.
+///
+/// Encrypts deployment secrets for NixOS hosts:
+/// - Armored `.age` files per host and per location
+/// - agenix `secrets.nix` rules and per-host `age.secrets` modules
+/// - sops `.sops.yaml` creation rules with the age form of SSH host keys
+pub mod nix_secrets;
+
+/// Terraform projection - CA bundles + NATS identities → `.tfvars.json` and module skeleton.
+///
+/// Hands public ceremony output to infrastructure pipelines:
+/// - `cim-keys.auto.tfvars.json` with CA PEM bundles, operator JWT and account keys
+/// - A module declaring and re-exporting those variables
+pub mod terraform;
+
+/// NATS resolver projection - operator + account JWTs → server resolver configuration.
+///
+/// Lets a NATS cluster be bootstrapped from the SD card export:
+/// - `resolver.conf` with the trusted operator and system account
+/// - A full or cache account resolver
+/// - `resolver_preload` with every verified account JWT
+pub mod nats_resolver;
+
+/// NATS edge site projection - leafnode credentials + TLS material → site bundle.
+///
+/// Everything an edge Location needs to join the hub:
+/// - `leafnode.conf` with hub remotes and an optional gateway block
+/// - The leafnode-only user `.creds`
+/// - The site's server certificate, key and issuing CA, optionally age-encrypted
+pub mod nats_edge;
+
+/// Paper backup projection - Shamir shares + root CA fingerprints → printable PDFs.
+///
+/// The non-digital recovery path alongside the SD card:
+/// - One sheet per share of the master seed, with a QR code and recovery steps
+/// - A public sheet of root CA fingerprints with a QR code per CA
+pub mod paper_backup;
+
 // Re-export domain projections for convenience
 pub use domain::{
     // Key projections
@@ -125,10 +204,15 @@ pub use neo4j::{
 pub use sdcard::{
     // Export types
     SDCardExport, ExportMetadata, ExportFile, ExportSummary, WriteResult,
+    ExportDestination, RedundantWriteResult,
+    // Export profiles
+    ArtifactClass, ExportProfile,
+    // Artifact encryption
+    RecoveryBundle,
     // Projections
-    ManifestToExportProjection, ExportToFilesystemProjection,
+    ManifestToExportProjection, ExportToFilesystemProjection, RedundantExportProjection,
     // Factory functions
-    manifest_to_export, sdcard_export_pipeline,
+    manifest_to_export, sdcard_export_pipeline, redundant_sdcard_export_pipeline, verify_export,
 };
 
 // Re-export JetStream projections
@@ -143,6 +227,11 @@ pub use jetstream::{
     events_to_messages, single_event, events_for_org,
 };
 
+// Re-export JetStream subject schema
+pub use subject_schema::{
+    SubjectRegistry, SubjectSchema, SubjectDocumentation, DocumentedSubject,
+};
+
 // Re-export NSC Store projections
 pub use nscstore::{
     // Credential types
@@ -155,6 +244,112 @@ pub use nscstore::{
     credentials_to_nscstore, credentials_to_nscstore_with_seeds, operator_to_nscstore,
 };
 
+// Re-export NSC store import
+pub use nsc_import::{
+    // Import types
+    NscImport,
+    // Projections
+    NscStoreToCredentialsProjection, NscImportToEventsProjection,
+    // Factory functions
+    read_nsc_store, nscstore_to_credentials, nsc_import_to_events, import_nsc_store,
+};
+
+// Re-export SSH access projections
+pub use ssh_access::{
+    // Domain types
+    SshAccessInput, SshAuthorizedKey, SshHost, SshKeyHolder, SshServiceAccount,
+    // Output types
+    SshAccessExport,
+    // Projections
+    SshAccessProjection,
+    // Factory functions
+    ssh_access,
+};
+
+// Re-export SSH client config projections
+pub use ssh_config::{
+    // Domain types
+    SshCertificateFile, SshConfigInput,
+    // Output types
+    SshConfigExport,
+    // Projections
+    SshConfigProjection,
+    // Factory functions
+    ssh_config,
+};
+
+// Re-export Kubernetes projections
+pub use kubernetes::{
+    // Domain types
+    KubernetesInput, KubernetesTlsSecret, KubernetesNatsCreds, KubernetesSshCa,
+    // Output types
+    KubernetesExport,
+    // Projections
+    KubernetesProjection,
+    // Factory functions
+    kubernetes,
+};
+
+// Re-export Nix secrets projections
+pub use nix_secrets::{
+    // Domain types
+    NixSecretsInput, NixAdmin, NixHost, NixSecret, NixSecretScope,
+    // Output types
+    NixSecretsExport,
+    // Projections
+    NixSecretsProjection,
+    // Factory functions
+    nix_secrets,
+};
+
+// Re-export Terraform projections
+pub use terraform::{
+    // Domain types
+    TerraformInput, TerraformCaBundle, TerraformNatsOperator, TerraformNatsAccount,
+    // Output types
+    TerraformExport,
+    // Projections
+    TerraformProjection,
+    // Factory functions
+    terraform,
+};
+
+// Re-export NATS resolver projections
+pub use nats_resolver::{
+    // Domain types
+    NatsResolverType,
+    // Output types
+    NatsResolverExport,
+    // Projections
+    NatsResolverProjection,
+    // Factory functions
+    nats_resolver,
+};
+
+// Re-export NATS edge site projections
+pub use nats_edge::{
+    // Domain types
+    EdgeSiteBundle, EdgeGateway, GatewayRemote,
+    // Output types
+    EdgeSiteExport,
+    // Projections
+    EdgeSiteProjection,
+    // Factory functions
+    nats_edge,
+};
+
+// Re-export paper backup projections
+pub use paper_backup::{
+    // Domain types
+    PaperBackupInput, RootCaFingerprint,
+    // Output types
+    PaperBackupExport, PaperSheet,
+    // Projections
+    PaperBackupProjection,
+    // Factory functions
+    paper_backup,
+};
+
 // ============================================================================
 // CORE PROJECTION TRAIT
 // ============================================================================
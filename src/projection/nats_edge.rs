@@ -0,0 +1,311 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # NATS Edge Site Projection
+//!
+//! Composable projection for an edge site's NATS identity and TLS material
+//! → a single bundle the site's servers are installed from.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! Location + Account
+//!     ↓ via
+//! handle_issue_edge_site_credentials (leafnode user + server certificate)
+//!     ↓ produces
+//! EdgeSiteBundle
+//!     ↓ via
+//! EdgeSiteProjection (pure)
+//!     ↓ produces
+//! EdgeSiteExport (leafnode.conf, .creds, TLS files)
+//! ```
+//!
+//! ## Directory Structure on SD Card
+//!
+//! ```text
+//! edge/{location}/
+//! ├── site.json          # Location, account, user key, certificate fingerprint
+//! ├── leafnode.conf      # `include "leafnode.conf"` from nats-server.conf
+//! ├── leafnode.creds     # Leafnode user JWT + seed (sensitive)
+//! └── tls/
+//!     ├── server.pem     # Site server certificate
+//!     ├── server-key.pem # Its private key (sensitive)
+//!     └── ca.pem         # Issuing CA, to verify the hub and peer sites
+//! ```
+//!
+//! The leafnode user JWT only allows `LEAFNODE` connections. With
+//! `with_protection`, sensitive files are age-encrypted (`.age` suffix) and
+//! must be decrypted in place on the site before the server starts.
+
+use crate::crypto::age_encryption::{encrypt_armored, AgeProtection, AGE_FILE_SUFFIX};
+use crate::domain::nats::{NatsClaims, UserClaimData, CONNECTION_TYPE_LEAFNODE};
+use crate::domain_projections::SubjectPermissionProjection;
+use crate::projection::nscstore::creds_content;
+use crate::projection::sdcard::ExportFile;
+use crate::projection::{Projection, ProjectionError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+// ============================================================================
+// DOMAIN TYPES
+// ============================================================================
+
+/// Remote gateway a site's cluster connects to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatewayRemote {
+    pub name: String,
+    /// Gateway URLs (`tls://hub.example.com:7222`)
+    pub urls: Vec<String>,
+}
+
+/// Gateway settings of a site that runs its own cluster
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeGateway {
+    /// Name of the site's cluster in the super-cluster
+    pub name: String,
+    pub port: u16,
+    pub remotes: Vec<GatewayRemote>,
+}
+
+/// Everything an edge site needs to join the hub
+///
+/// Contains the leafnode seed and the server private key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeSiteBundle {
+    pub location_id: Uuid,
+    pub location_name: String,
+    pub account_name: String,
+    pub account_public_key: String,
+    pub user_public_key: String,
+    pub user_jwt: String,
+    pub user_seed: String,
+    pub certificate_pem: String,
+    pub private_key_pem: String,
+    /// Certificate of the issuing CA
+    pub ca_certificate_pem: String,
+    pub certificate_fingerprint: String,
+    /// DNS names and addresses of the site's servers
+    pub server_names: Vec<String>,
+    /// Hub leafnode URLs (`tls://hub.example.com:7422`)
+    pub leafnode_remotes: Vec<String>,
+    pub gateway: Option<EdgeGateway>,
+}
+
+/// Rendered edge site bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeSiteExport {
+    pub directories: Vec<PathBuf>,
+    pub files: Vec<ExportFile>,
+    /// Whether sensitive files were age-encrypted
+    pub encrypted: bool,
+}
+
+// ============================================================================
+// PROJECTION
+// ============================================================================
+
+/// Projection: EdgeSiteBundle → EdgeSiteExport
+#[derive(Debug, Clone)]
+pub struct EdgeSiteProjection {
+    install_dir: String,
+    protection: Option<AgeProtection>,
+}
+
+impl Default for EdgeSiteProjection {
+    fn default() -> Self {
+        Self {
+            install_dir: "/etc/nats".to_string(),
+            protection: None,
+        }
+    }
+}
+
+impl EdgeSiteProjection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory the bundle is installed to on the site's servers
+    pub fn with_install_dir(mut self, install_dir: impl Into<String>) -> Self {
+        self.install_dir = install_dir.into();
+        self
+    }
+
+    /// Age-encrypt the leafnode seed and server key
+    pub fn with_protection(mut self, protection: AgeProtection) -> Self {
+        self.protection = Some(protection);
+        self
+    }
+
+    /// Verify the leafnode JWT belongs to the bundle's user and account
+    fn verify_user(bundle: &EdgeSiteBundle) -> Result<(), ProjectionError> {
+        let claims = NatsClaims::<UserClaimData>::decode(&bundle.user_jwt).map_err(|e| {
+            ProjectionError::ValidationFailed {
+                field: "user_jwt".to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+        if claims.sub != bundle.user_public_key {
+            return Err(ProjectionError::ValidationFailed {
+                field: "user_jwt".to_string(),
+                reason: format!("JWT subject {} is not the leafnode user key", claims.sub),
+            });
+        }
+        if claims.account_public_key() != bundle.account_public_key {
+            return Err(ProjectionError::ValidationFailed {
+                field: "user_jwt".to_string(),
+                reason: format!("JWT is not issued by account {}", bundle.account_name),
+            });
+        }
+        if !claims.nats.allowed_connection_types.iter().any(|t| t == CONNECTION_TYPE_LEAFNODE) {
+            return Err(ProjectionError::ValidationFailed {
+                field: "user_jwt".to_string(),
+                reason: "user is not restricted to leafnode connections".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn tls_block(&self, indent: &str) -> String {
+        let mut block = format!("{}tls: {{\n", indent);
+        block.push_str(&format!("{}    cert_file: \"{}/tls/server.pem\"\n", indent, self.install_dir));
+        block.push_str(&format!("{}    key_file: \"{}/tls/server-key.pem\"\n", indent, self.install_dir));
+        block.push_str(&format!("{}    ca_file: \"{}/tls/ca.pem\"\n", indent, self.install_dir));
+        block.push_str(&format!("{}}}\n", indent));
+        block
+    }
+
+    fn leafnode_conf(&self, bundle: &EdgeSiteBundle) -> String {
+        let quoted = |urls: &[String]| urls.iter().map(|u| format!("\"{}\"", u)).collect::<Vec<_>>().join(", ");
+
+        let mut conf = String::from("# Managed by cim-keys - do not edit\n");
+        conf.push_str(&format!("# Edge site {} ({})\n", bundle.location_name, bundle.location_id));
+        conf.push_str("# Include from nats-server.conf: include \"leafnode.conf\"\n\n");
+        conf.push_str("leafnodes: {\n");
+        conf.push_str("    remotes: [\n");
+        conf.push_str("        {\n");
+        conf.push_str(&format!("            # Account {} ({})\n", bundle.account_name, bundle.account_public_key));
+        conf.push_str(&format!("            urls: [{}]\n", quoted(&bundle.leafnode_remotes)));
+        conf.push_str(&format!("            credentials: \"{}/leafnode.creds\"\n", self.install_dir));
+        conf.push_str(&self.tls_block("            "));
+        conf.push_str("        }\n");
+        conf.push_str("    ]\n");
+        conf.push_str("}\n");
+
+        if let Some(gateway) = &bundle.gateway {
+            conf.push_str("\ngateway: {\n");
+            conf.push_str(&format!("    name: \"{}\"\n", gateway.name));
+            conf.push_str(&format!("    port: {}\n", gateway.port));
+            conf.push_str(&self.tls_block("    "));
+            conf.push_str("    gateways: [\n");
+            for remote in &gateway.remotes {
+                conf.push_str(&format!(
+                    "        {{ name: \"{}\", urls: [{}] }}\n",
+                    remote.name,
+                    quoted(&remote.urls)
+                ));
+            }
+            conf.push_str("    ]\n");
+            conf.push_str("}\n");
+        }
+        conf
+    }
+
+    /// Plain or age-encrypted file, depending on the configured protection
+    fn sensitive_file(&self, path: String, content: String) -> Result<ExportFile, ProjectionError> {
+        match &self.protection {
+            None => Ok(ExportFile::new(path, content, true)),
+            Some(protection) => {
+                let armored = encrypt_armored(content.as_bytes(), protection).map_err(|e| {
+                    ProjectionError::ExternalError {
+                        system: "age".to_string(),
+                        error: format!("{} ({})", e, path),
+                    }
+                })?;
+                Ok(ExportFile::new(format!("{}{}", path, AGE_FILE_SUFFIX), armored, true))
+            }
+        }
+    }
+}
+
+impl Projection<EdgeSiteBundle, EdgeSiteExport, ProjectionError> for EdgeSiteProjection {
+    fn project(&self, bundle: EdgeSiteBundle) -> Result<EdgeSiteExport, ProjectionError> {
+        if self.install_dir.is_empty() || self.install_dir.contains('"') {
+            return Err(ProjectionError::ValidationFailed {
+                field: "install_dir".to_string(),
+                reason: "must be a non-empty path without quotes".to_string(),
+            });
+        }
+        if bundle.leafnode_remotes.is_empty() {
+            return Err(ProjectionError::PrerequisiteNotMet {
+                name: "leafnode_remotes".to_string(),
+                description: format!("edge site {} has no hub to connect to", bundle.location_name),
+            });
+        }
+        let gateway_values = bundle.gateway.iter().flat_map(|g| {
+            std::iter::once(&g.name).chain(g.remotes.iter().flat_map(|r| std::iter::once(&r.name).chain(&r.urls)))
+        });
+        if bundle.leafnode_remotes.iter().chain(gateway_values).any(|v| v.is_empty() || v.contains('"')) {
+            return Err(ProjectionError::ValidationFailed {
+                field: "leafnode_remotes".to_string(),
+                reason: "URLs and gateway names must be non-empty and without quotes".to_string(),
+            });
+        }
+        if bundle.certificate_pem.is_empty() || bundle.private_key_pem.is_empty() || bundle.ca_certificate_pem.is_empty() {
+            return Err(ProjectionError::PrerequisiteNotMet {
+                name: "tls".to_string(),
+                description: format!("edge site {} has no server certificate", bundle.location_name),
+            });
+        }
+        Self::verify_user(&bundle)?;
+
+        let site_dir = format!("edge/{}", SubjectPermissionProjection::subject_token(&bundle.location_name));
+        let site_info = serde_json::json!({
+            "location_id": bundle.location_id,
+            "location_name": bundle.location_name,
+            "account_name": bundle.account_name,
+            "account_public_key": bundle.account_public_key,
+            "user_public_key": bundle.user_public_key,
+            "certificate_fingerprint": bundle.certificate_fingerprint,
+            "server_names": bundle.server_names,
+            "leafnode_remotes": bundle.leafnode_remotes,
+            "gateway": bundle.gateway,
+            "encrypted": self.protection.is_some(),
+        });
+
+        let files = vec![
+            ExportFile::new(
+                format!("{}/site.json", site_dir),
+                serde_json::to_string_pretty(&site_info).map_err(|e| ProjectionError::SerializationError(e.to_string()))?,
+                false,
+            ),
+            ExportFile::new(format!("{}/leafnode.conf", site_dir), self.leafnode_conf(&bundle), false),
+            self.sensitive_file(
+                format!("{}/leafnode.creds", site_dir),
+                creds_content(&bundle.user_jwt, &bundle.user_seed),
+            )?,
+            ExportFile::new(format!("{}/tls/server.pem", site_dir), bundle.certificate_pem.clone(), false),
+            self.sensitive_file(format!("{}/tls/server-key.pem", site_dir), bundle.private_key_pem.clone())?,
+            ExportFile::new(format!("{}/tls/ca.pem", site_dir), bundle.ca_certificate_pem.clone(), false),
+        ];
+
+        Ok(EdgeSiteExport {
+            directories: vec![PathBuf::from(&site_dir), PathBuf::from(format!("{}/tls", site_dir))],
+            files,
+            encrypted: self.protection.is_some(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "EdgeSite"
+    }
+}
+
+// ============================================================================
+// FACTORY FUNCTIONS
+// ============================================================================
+
+/// Create an edge site projection installing to `/etc/nats`
+pub fn nats_edge() -> EdgeSiteProjection {
+    EdgeSiteProjection::new()
+}
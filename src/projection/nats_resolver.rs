@@ -0,0 +1,363 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # NATS Resolver Projection
+//!
+//! Composable projection for NATS credentials → the server-side account
+//! resolver configuration, so a NATS cluster can be bootstrapped straight
+//! from ceremony output.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! DomainNatsCredentials (operator + account JWTs)
+//!     ↓ via
+//! NatsResolverProjection (pure)
+//!     ↓ produces
+//! NatsResolverExport (resolver.conf + operator.jwt)
+//!     ↓ via
+//! ManifestToExportProjection::with_nats_resolver
+//!     ↓ produces
+//! SDCardExport
+//! ```
+//!
+//! ## Directory Structure on SD Card
+//!
+//! ```text
+//! nats/server/
+//! ├── resolver.conf     # `include "resolver.conf"` from nats-server.conf
+//! └── operator.jwt      # Operator JWT, also inlined in resolver.conf
+//! ```
+//!
+//! `resolver.conf` sets the trusted operator, the system account and the
+//! resolver (`full` or `cache`), and preloads every account JWT so servers
+//! start with the system account and all accounts resolvable. Every JWT is
+//! verified before it is written. No seeds or user credentials are included.
+
+use crate::domain::nats::{AccountClaimData, NatsClaims, OperatorClaimData};
+use crate::projection::nscstore::{AccountCredentials, DomainNatsCredentials};
+use crate::projection::sdcard::ExportFile;
+use crate::projection::{Projection, ProjectionError};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+// ============================================================================
+// DOMAIN TYPES
+// ============================================================================
+
+/// Account resolver run by the servers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NatsResolverType {
+    /// Stores every account JWT; accepts pushed updates
+    Full {
+        /// Whether accounts may be deleted through the system account
+        allow_delete: bool,
+    },
+    /// Keeps up to `limit` account JWTs fetched from full resolvers
+    Cache { limit: u32, ttl_secs: u64 },
+}
+
+/// Rendered resolver configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NatsResolverExport {
+    pub directories: Vec<PathBuf>,
+    pub files: Vec<ExportFile>,
+    /// Accounts preloaded in `resolver_preload`
+    pub preloaded_accounts: usize,
+}
+
+// ============================================================================
+// PROJECTION
+// ============================================================================
+
+/// Projection: DomainNatsCredentials → NatsResolverExport
+#[derive(Debug, Clone)]
+pub struct NatsResolverProjection {
+    resolver_type: NatsResolverType,
+    jwt_dir: String,
+}
+
+impl Default for NatsResolverProjection {
+    fn default() -> Self {
+        Self {
+            resolver_type: NatsResolverType::Full { allow_delete: false },
+            jwt_dir: "./jwt".to_string(),
+        }
+    }
+}
+
+impl NatsResolverProjection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configure a cache resolver instead of a full one
+    pub fn cache(mut self, limit: u32, ttl_secs: u64) -> Self {
+        self.resolver_type = NatsResolverType::Cache { limit, ttl_secs };
+        self
+    }
+
+    /// Allow deleting accounts through the system account (full resolver)
+    pub fn with_allow_delete(mut self, allow_delete: bool) -> Self {
+        if let NatsResolverType::Full { allow_delete: allow } = &mut self.resolver_type {
+            *allow = allow_delete;
+        }
+        self
+    }
+
+    /// Directory the server keeps resolved account JWTs in
+    pub fn with_jwt_dir(mut self, jwt_dir: impl Into<String>) -> Self {
+        self.jwt_dir = jwt_dir.into();
+        self
+    }
+
+    /// Accounts of the operator with verified JWTs, sorted by name
+    fn verified_accounts(
+        credentials: &DomainNatsCredentials,
+        operator: &NatsClaims<OperatorClaimData>,
+    ) -> Result<Vec<AccountCredentials>, ProjectionError> {
+        let mut accounts: Vec<AccountCredentials> = credentials.accounts.values().cloned().collect();
+        accounts.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for account in &accounts {
+            let field = format!("accounts.{}.jwt", account.name);
+            let claims = NatsClaims::<AccountClaimData>::decode(&account.jwt).map_err(|e| {
+                ProjectionError::ValidationFailed {
+                    field: field.clone(),
+                    reason: e.to_string(),
+                }
+            })?;
+            if claims.sub != account.public_key {
+                return Err(ProjectionError::ValidationFailed {
+                    field,
+                    reason: format!("JWT subject {} is not the account key", claims.sub),
+                });
+            }
+            if claims.iss != operator.sub && !operator.nats.signing_keys.contains(&claims.iss) {
+                return Err(ProjectionError::ValidationFailed {
+                    field,
+                    reason: format!("issuer {} is not trusted by operator {}", claims.iss, operator.name),
+                });
+            }
+        }
+        Ok(accounts)
+    }
+
+    fn resolver_block(&self) -> String {
+        let mut block = String::from("resolver: {\n");
+        match &self.resolver_type {
+            NatsResolverType::Full { allow_delete } => {
+                block.push_str("    type: full\n");
+                block.push_str(&format!("    dir: \"{}\"\n", self.jwt_dir));
+                block.push_str(&format!("    allow_delete: {}\n", allow_delete));
+                block.push_str("    interval: \"2m\"\n");
+                block.push_str("    timeout: \"1.9s\"\n");
+            }
+            NatsResolverType::Cache { limit, ttl_secs } => {
+                block.push_str("    type: cache\n");
+                block.push_str(&format!("    dir: \"{}\"\n", self.jwt_dir));
+                block.push_str(&format!("    limit: {}\n", limit));
+                block.push_str(&format!("    ttl: \"{}s\"\n", ttl_secs));
+                block.push_str("    timeout: \"1.9s\"\n");
+            }
+        }
+        block.push_str("}\n");
+        block
+    }
+}
+
+impl Projection<DomainNatsCredentials, NatsResolverExport, ProjectionError> for NatsResolverProjection {
+    fn project(&self, credentials: DomainNatsCredentials) -> Result<NatsResolverExport, ProjectionError> {
+        if self.jwt_dir.is_empty() || self.jwt_dir.contains('"') {
+            return Err(ProjectionError::ValidationFailed {
+                field: "jwt_dir".to_string(),
+                reason: "must be a non-empty path without quotes".to_string(),
+            });
+        }
+        if let NatsResolverType::Cache { limit: 0, .. } | NatsResolverType::Cache { ttl_secs: 0, .. } =
+            self.resolver_type
+        {
+            return Err(ProjectionError::ValidationFailed {
+                field: "resolver".to_string(),
+                reason: "cache limit and ttl must be > 0".to_string(),
+            });
+        }
+
+        let operator = NatsClaims::<OperatorClaimData>::decode(&credentials.operator.jwt).map_err(|e| {
+            ProjectionError::ValidationFailed {
+                field: "operator.jwt".to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+        if operator.sub != credentials.operator.public_key {
+            return Err(ProjectionError::ValidationFailed {
+                field: "operator.jwt".to_string(),
+                reason: format!("JWT subject {} is not the operator key", operator.sub),
+            });
+        }
+
+        // Servers need the system account for resolver updates and monitoring
+        let system_account = credentials
+            .operator
+            .system_account
+            .clone()
+            .or_else(|| operator.nats.system_account.clone())
+            .ok_or_else(|| ProjectionError::PrerequisiteNotMet {
+                name: "system_account".to_string(),
+                description: format!("operator {} has no system account", credentials.operator.name),
+            })?;
+        if operator.nats.system_account.as_ref().is_some_and(|key| key != &system_account) {
+            return Err(ProjectionError::ValidationFailed {
+                field: "operator.system_account".to_string(),
+                reason: "differs from the system account in the operator JWT".to_string(),
+            });
+        }
+
+        let accounts = Self::verified_accounts(&credentials, &operator)?;
+        if !accounts.iter().any(|account| account.public_key == system_account) {
+            return Err(ProjectionError::PrerequisiteNotMet {
+                name: "system_account".to_string(),
+                description: format!("no JWT for system account {}", system_account),
+            });
+        }
+
+        let operator_jwt = credentials.operator.jwt.trim();
+        let mut conf = String::from("# Managed by cim-keys - do not edit\n");
+        conf.push_str(&format!("# Operator {} ({})\n", credentials.operator.name, credentials.organization_name));
+        conf.push_str("# Include from nats-server.conf: include \"resolver.conf\"\n\n");
+        conf.push_str(&format!("operator: \"{}\"\n", operator_jwt));
+        conf.push_str(&format!("system_account: \"{}\"\n\n", system_account));
+        conf.push_str(&self.resolver_block());
+        conf.push_str("\nresolver_preload: {\n");
+        for account in &accounts {
+            conf.push_str(&format!("    # {}\n", account.name));
+            conf.push_str(&format!("    {}: \"{}\"\n", account.public_key, account.jwt.trim()));
+        }
+        conf.push_str("}\n");
+
+        Ok(NatsResolverExport {
+            directories: vec![PathBuf::from("nats/server")],
+            files: vec![
+                ExportFile::new("nats/server/resolver.conf", conf, false),
+                ExportFile::new("nats/server/operator.jwt", format!("{}\n", operator_jwt), false),
+            ],
+            preloaded_accounts: accounts.len(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "NatsResolver"
+    }
+}
+
+// ============================================================================
+// FACTORY FUNCTIONS
+// ============================================================================
+
+/// Create a full resolver projection keeping JWTs in `./jwt`
+pub fn nats_resolver() -> NatsResolverProjection {
+    NatsResolverProjection::new()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projection::nscstore::OperatorCredentials;
+    use crate::value_objects::{NKeyPair, NKeyType};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn credentials() -> (DomainNatsCredentials, String) {
+        let operator = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let signing_key = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let system = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let orders = NKeyPair::generate(NKeyType::Account, None).unwrap();
+        let operator_key = operator.public_key_string();
+
+        let account = |name: &str, key: &NKeyPair, signer: &NKeyPair| AccountCredentials {
+            name: name.to_string(),
+            jwt: NatsClaims::account(name, key.public_key_string(), operator_key).encode(signer).unwrap(),
+            public_key: key.public_key_string().to_string(),
+            operator_public_key: operator_key.to_string(),
+            signing_keys: Vec::new(),
+        };
+        let accounts = HashMap::from([
+            ("SYS".to_string(), account("SYS", &system, &operator)),
+            ("orders".to_string(), account("orders", &orders, &signing_key)),
+        ]);
+
+        let credentials = DomainNatsCredentials {
+            organization_id: Uuid::now_v7(),
+            organization_name: "Cowboy AI".to_string(),
+            operator: OperatorCredentials {
+                name: "cowboyai".to_string(),
+                jwt: NatsClaims::operator("cowboyai", operator_key)
+                    .with_signing_key(signing_key.public_key_string())
+                    .with_system_account(system.public_key_string())
+                    .encode(&operator)
+                    .unwrap(),
+                public_key: operator_key.to_string(),
+                signing_keys: vec![signing_key.public_key_string().to_string()],
+                system_account: Some(system.public_key_string().to_string()),
+            },
+            accounts,
+            users: HashMap::new(),
+            generated_at: Utc::now(),
+        };
+        (credentials, system.public_key_string().to_string())
+    }
+
+    #[test]
+    fn test_full_resolver_preloads_every_account() {
+        let (credentials, system) = credentials();
+        let operator_jwt = credentials.operator.jwt.clone();
+        let export = nats_resolver().project(credentials).unwrap();
+
+        let paths: Vec<String> = export.files.iter().map(|f| f.path.display().to_string()).collect();
+        assert_eq!(paths, ["nats/server/resolver.conf", "nats/server/operator.jwt"]);
+        assert!(export.files.iter().all(|f| !f.sensitive));
+        assert_eq!(export.preloaded_accounts, 2);
+
+        let conf = &export.files[0].content;
+        assert!(conf.contains(&format!("operator: \"{}\"", operator_jwt)));
+        assert!(conf.contains(&format!("system_account: \"{}\"", system)));
+        assert!(conf.contains("type: full"));
+        assert!(conf.contains("allow_delete: false"));
+        assert!(conf.contains(&format!("    {}: \"eyJ", system)));
+        // Accounts are listed by name
+        assert!(conf.find("# SYS").unwrap() < conf.find("# orders").unwrap());
+    }
+
+    #[test]
+    fn test_cache_resolver_and_validation() {
+        let (credentials, _) = credentials();
+        let export = nats_resolver().cache(500, 120).with_jwt_dir("/var/lib/nats/jwt").project(credentials.clone()).unwrap();
+        let conf = &export.files[0].content;
+        assert!(conf.contains("type: cache"));
+        assert!(conf.contains("limit: 500"));
+        assert!(conf.contains("ttl: \"120s\""));
+        assert!(conf.contains("dir: \"/var/lib/nats/jwt\""));
+
+        let mut no_system = credentials.clone();
+        no_system.accounts.remove("SYS");
+        assert!(matches!(
+            nats_resolver().project(no_system),
+            Err(ProjectionError::PrerequisiteNotMet { .. })
+        ));
+
+        // An account signed by an untrusted operator key is rejected
+        let mut untrusted = credentials.clone();
+        let stranger = NKeyPair::generate(NKeyType::Operator, None).unwrap();
+        let orders = untrusted.accounts.get_mut("orders").unwrap();
+        orders.jwt = NatsClaims::account("orders", &orders.public_key, &orders.operator_public_key)
+            .encode(&stranger)
+            .unwrap();
+        assert!(nats_resolver().project(untrusted).is_err());
+
+        assert!(nats_resolver().cache(0, 120).project(credentials).is_err());
+    }
+}
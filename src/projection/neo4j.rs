@@ -365,6 +365,19 @@ mod tests {
         assert!(file_content.contains(":Person"));
     }
 
+    #[test]
+    fn test_merge_matches_on_id_only() {
+        let person_id = Uuid::now_v7();
+        let cypher = GraphNode::new(person_id, "Person")
+            .with_property("name", "Alice")
+            .to_merge_query()
+            .to_inline_cypher();
+
+        // Renaming Alice must update the node, not create a second one
+        assert!(cypher.starts_with(&format!("MERGE (n:Person {{id: '{}'}}) SET n += {{", person_id)));
+        assert!(cypher.contains("name: 'Alice'"));
+    }
+
     #[test]
     fn test_domain_graph_builder() {
         let person_id = Uuid::now_v7();
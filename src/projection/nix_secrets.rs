@@ -0,0 +1,544 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # Nix Secrets Projection
+//!
+//! Composable projection for NixOS deployments: age-encrypted secret files
+//! for agenix, plus the `secrets.nix` and `.sops.yaml` wiring that lets
+//! agenix and sops-nix find the keys of every host and location.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! Admin age recipients (derive_age_key) + host keys + plaintext secrets
+//!     ↓ via
+//! NixSecretsProjection (encrypts; no other effects)
+//!     ↓ produces
+//! NixSecretsExport
+//!     ↓ via
+//! ManifestToExportProjection::with_nix_secrets
+//!     ↓ produces
+//! SDCardExport
+//! ```
+//!
+//! ## Directory Structure on SD Card
+//!
+//! ```text
+//! nix/
+//! ├── secrets.nix                          # agenix rules
+//! ├── .sops.yaml                           # sops creation rules
+//! ├── secrets/
+//! │   ├── hosts/{host}/{name}.age          # Admins + that host
+//! │   └── locations/{location}/{name}.age  # Admins + every host there
+//! └── hosts/{host}/secrets.nix             # NixOS module declaring age.secrets
+//! ```
+//!
+//! Every secret is encrypted to all admins, so any admin can re-key or
+//! edit it with their derived identity. Hosts are identified by their
+//! `ssh-ed25519` host key (as agenix expects) or an `age1...` recipient;
+//! `.sops.yaml` lists the age form of either, since sops cannot encrypt to
+//! SSH keys. The `.age` files are armored and safe to commit.
+
+use crate::crypto::age_derivation::ssh_ed25519_to_age_recipient;
+use crate::crypto::age_encryption::{encrypt_to_recipients, parse_recipient};
+use crate::projection::sdcard::ExportFile;
+use crate::projection::{Projection, ProjectionError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// ============================================================================
+// DOMAIN TYPES
+// ============================================================================
+
+/// An admin able to decrypt every secret
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NixAdmin {
+    pub name: String,
+    /// Age recipient (`age1...`), usually from `derive_age_key`
+    pub recipient: String,
+}
+
+/// A NixOS host receiving secrets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NixHost {
+    /// Host name, as in `nixosConfigurations`
+    pub name: String,
+    /// Location the host is deployed at, for location-wide secrets
+    pub location: Option<String>,
+    /// `ssh-ed25519` host public key or `age1...` recipient
+    pub public_key: String,
+}
+
+/// Hosts a secret is deployed to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum NixSecretScope {
+    Host(String),
+    /// Every host at the location
+    Location(String),
+}
+
+/// One secret to encrypt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NixSecret {
+    /// File and `age.secrets` attribute name
+    pub name: String,
+    pub scope: NixSecretScope,
+    pub content: String,
+    /// Owner of the decrypted file on the host (agenix default: root)
+    pub owner: Option<String>,
+    /// Octal mode of the decrypted file (agenix default: 0400)
+    pub mode: Option<String>,
+}
+
+/// Keys and secrets of a deployment
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NixSecretsInput {
+    pub organization: String,
+    pub admins: Vec<NixAdmin>,
+    pub hosts: Vec<NixHost>,
+    pub secrets: Vec<NixSecret>,
+}
+
+/// Encrypted secrets and their wiring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NixSecretsExport {
+    pub directories: Vec<PathBuf>,
+    pub files: Vec<ExportFile>,
+    pub secret_count: usize,
+}
+
+/// A host with its key parsed once
+struct Host<'a> {
+    host: &'a NixHost,
+    /// Key as written to `secrets.nix`, without comment
+    public_key: String,
+    /// Age recipient for `.sops.yaml`
+    age_recipient: String,
+}
+
+// ============================================================================
+// PROJECTION
+// ============================================================================
+
+/// Projection: NixSecretsInput → NixSecretsExport
+#[derive(Debug, Clone, Default)]
+pub struct NixSecretsProjection;
+
+impl NixSecretsProjection {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn hosts(input: &NixSecretsInput) -> Result<BTreeMap<&str, Host<'_>>, ProjectionError> {
+        let mut hosts = BTreeMap::new();
+        for host in &input.hosts {
+            validate_name("host", &host.name)?;
+            if let Some(location) = &host.location {
+                validate_name("location", location)?;
+            }
+            let public_key = host.public_key.split_whitespace().take(2).collect::<Vec<_>>().join(" ");
+            let age_recipient = if public_key.starts_with("age1") {
+                age::x25519::Recipient::from_str(&public_key).map_err(|e| invalid_key(&host.name, e))?;
+                public_key.clone()
+            } else {
+                ssh_ed25519_to_age_recipient(&public_key).map_err(|e| invalid_key(&host.name, &e))?
+            };
+            let parsed = Host { host, public_key, age_recipient };
+            if hosts.insert(host.name.as_str(), parsed).is_some() {
+                return Err(ProjectionError::ValidationFailed {
+                    field: "hosts".to_string(),
+                    reason: format!("host {} appears twice", host.name),
+                });
+            }
+        }
+        Ok(hosts)
+    }
+
+    /// Hosts a scope deploys to; never empty
+    fn scope_hosts<'h, 'a>(
+        hosts: &'h BTreeMap<&str, Host<'a>>,
+        scope: &NixSecretScope,
+    ) -> Result<Vec<&'h Host<'a>>, ProjectionError> {
+        let (selected, missing): (Vec<_>, _) = match scope {
+            NixSecretScope::Host(name) => (
+                hosts.get(name.as_str()).into_iter().collect(),
+                format!("unknown host {}", name),
+            ),
+            NixSecretScope::Location(location) => (
+                hosts
+                    .values()
+                    .filter(|host| host.host.location.as_deref() == Some(location.as_str()))
+                    .collect(),
+                format!("no hosts at location {}", location),
+            ),
+        };
+        if selected.is_empty() {
+            return Err(ProjectionError::ValidationFailed {
+                field: "secrets.scope".to_string(),
+                reason: missing,
+            });
+        }
+        Ok(selected)
+    }
+
+    fn encrypt(
+        admins: &[age::x25519::Recipient],
+        hosts: &[&Host<'_>],
+        secret: &NixSecret,
+    ) -> Result<String, ProjectionError> {
+        let mut recipients: Vec<Box<dyn age::Recipient>> =
+            admins.iter().map(|admin| Box::new(admin.clone()) as Box<dyn age::Recipient>).collect();
+        for host in hosts {
+            recipients.push(parse_recipient(&host.public_key).map_err(|e| invalid_key(&host.host.name, &e))?);
+        }
+
+        encrypt_to_recipients(secret.content.as_bytes(), &recipients).map_err(|e| ProjectionError::ExternalError {
+            system: "age".to_string(),
+            error: format!("{} ({})", e, secret.name),
+        })
+    }
+}
+
+impl Projection<NixSecretsInput, NixSecretsExport, ProjectionError> for NixSecretsProjection {
+    fn project(&self, input: NixSecretsInput) -> Result<NixSecretsExport, ProjectionError> {
+        if input.admins.is_empty() {
+            return Err(ProjectionError::ValidationFailed {
+                field: "admins".to_string(),
+                reason: "secrets need at least one admin recipient".to_string(),
+            });
+        }
+        let mut admin_recipients = Vec::new();
+        for admin in &input.admins {
+            validate_name("admin", &admin.name)?;
+            admin_recipients.push(
+                age::x25519::Recipient::from_str(&admin.recipient).map_err(|e| invalid_key(&admin.name, e))?,
+            );
+        }
+        let hosts = Self::hosts(&input)?;
+
+        let mut secrets: Vec<&NixSecret> = input.secrets.iter().collect();
+        // Sorted so the wiring files are byte-identical across exports
+        secrets.sort_by(|a, b| (&a.scope, &a.name).cmp(&(&b.scope, &b.name)));
+
+        let mut directories = BTreeSet::from([PathBuf::from("nix"), PathBuf::from("nix/secrets")]);
+        let mut files = Vec::new();
+        let mut rules = Vec::new();
+        let mut modules: BTreeMap<&str, Vec<(&NixSecret, String)>> = BTreeMap::new();
+
+        for secret in &secrets {
+            validate_name("secret", &secret.name)?;
+            if let Some(mode) = &secret.mode {
+                if !(3..=4).contains(&mode.len()) || !mode.chars().all(|c| ('0'..='7').contains(&c)) {
+                    return Err(ProjectionError::ValidationFailed {
+                        field: format!("{}.mode", secret.name),
+                        reason: format!("{:?} is not an octal file mode", mode),
+                    });
+                }
+            }
+            if let Some(owner) = &secret.owner {
+                validate_name("owner", owner)?;
+            }
+
+            let scope_hosts = Self::scope_hosts(&hosts, &secret.scope)?;
+            let directory = scope_directory(&secret.scope);
+            let relative = format!("{}/{}.age", directory, secret.name);
+            directories.insert(PathBuf::from("nix").join(&directory));
+            files.push(ExportFile::new(
+                PathBuf::from("nix").join(&relative),
+                Self::encrypt(&admin_recipients, &scope_hosts, secret)?,
+                false,
+            ));
+
+            let host_refs: Vec<String> = scope_hosts.iter().map(|host| format!("hosts.{}", quote(&host.host.name))).collect();
+            rules.push(format!("  {}.publicKeys = admins ++ [ {} ];\n", quote(&relative), host_refs.join(" ")));
+
+            for host in scope_hosts {
+                let module = modules.entry(host.host.name.as_str()).or_default();
+                if module.iter().any(|(existing, _)| existing.name == secret.name) {
+                    return Err(ProjectionError::ValidationFailed {
+                        field: secret.name.clone(),
+                        reason: format!("host {} receives two secrets named {}", host.host.name, secret.name),
+                    });
+                }
+                module.push((secret, relative.clone()));
+            }
+        }
+
+        files.push(ExportFile::new("nix/secrets.nix", render_agenix_rules(&input, &hosts, &rules), false));
+        files.push(ExportFile::new("nix/.sops.yaml", render_sops_config(&input, &hosts), false));
+
+        for (host, secrets) in &modules {
+            let location = hosts[host].host.location.as_deref().map(|l| format!(" ({})", l)).unwrap_or_default();
+            let mut module = format!(
+                "# Managed by cim-keys for {} - do not edit\n\
+                 # Import from the NixOS configuration of {}{}\n\
+                 {{\n",
+                single_line(&input.organization),
+                host,
+                location
+            );
+            for (secret, relative) in secrets {
+                module.push_str(&format!("  age.secrets.{} = {{\n    file = ../../{};\n", quote(&secret.name), relative));
+                if let Some(owner) = &secret.owner {
+                    module.push_str(&format!("    owner = {};\n", quote(owner)));
+                }
+                if let Some(mode) = &secret.mode {
+                    module.push_str(&format!("    mode = {};\n", quote(mode)));
+                }
+                module.push_str("  };\n");
+            }
+            module.push_str("}\n");
+
+            let directory = PathBuf::from("nix/hosts").join(host);
+            files.push(ExportFile::new(directory.join("secrets.nix"), module, false));
+            directories.insert(directory);
+        }
+
+        Ok(NixSecretsExport {
+            directories: directories.into_iter().collect(),
+            files,
+            secret_count: secrets.len(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "NixSecrets"
+    }
+}
+
+// ============================================================================
+// RENDERING AND VALIDATION HELPERS
+// ============================================================================
+
+fn render_agenix_rules(input: &NixSecretsInput, hosts: &BTreeMap<&str, Host<'_>>, rules: &[String]) -> String {
+    let mut nix = format!(
+        "# Managed by cim-keys for {} - do not edit\n\
+         # agenix rules: the keys each secret is encrypted to\n\
+         let\n  admins = [\n",
+        single_line(&input.organization)
+    );
+    for admin in &input.admins {
+        nix.push_str(&format!("    {} # {}\n", quote(&admin.recipient), admin.name));
+    }
+    nix.push_str("  ];\n  hosts = {\n");
+    for (name, host) in hosts {
+        let location = host.host.location.as_deref().map(|l| format!(" # {}", l)).unwrap_or_default();
+        nix.push_str(&format!("    {} = {};{}\n", quote(name), quote(&host.public_key), location));
+    }
+    nix.push_str("  };\nin\n{\n");
+    for rule in rules {
+        nix.push_str(rule);
+    }
+    nix.push_str("}\n");
+    nix
+}
+
+/// One creation rule per host and per location, for files under
+/// `sops/hosts/{host}/` and `sops/locations/{location}/`
+fn render_sops_config(input: &NixSecretsInput, hosts: &BTreeMap<&str, Host<'_>>) -> String {
+    let mut yaml = format!("# Managed by cim-keys for {} - do not edit\nkeys:\n", single_line(&input.organization));
+    for admin in &input.admins {
+        yaml.push_str(&format!("  - &admin_{} {}\n", admin.name, admin.recipient));
+    }
+    for (name, host) in hosts {
+        yaml.push_str(&format!("  - &host_{} {}\n", name, host.age_recipient));
+    }
+
+    let mut groups: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for (name, host) in hosts {
+        groups.entry(format!("hosts/{}", name)).or_default().push(name);
+        if let Some(location) = &host.host.location {
+            groups.entry(format!("locations/{}", location)).or_default().push(name);
+        }
+    }
+
+    yaml.push_str("creation_rules:\n");
+    for (directory, members) in &groups {
+        yaml.push_str(&format!(
+            "  - path_regex: sops/{}/[^/]+$\n    key_groups:\n      - age:\n",
+            directory.replace('.', "\\.")
+        ));
+        for admin in &input.admins {
+            yaml.push_str(&format!("          - *admin_{}\n", admin.name));
+        }
+        for member in members {
+            yaml.push_str(&format!("          - *host_{}\n", member));
+        }
+    }
+    yaml
+}
+
+fn scope_directory(scope: &NixSecretScope) -> String {
+    match scope {
+        NixSecretScope::Host(host) => format!("secrets/hosts/{}", host),
+        NixSecretScope::Location(location) => format!("secrets/locations/{}", location),
+    }
+}
+
+/// Names end up in file paths, Nix attribute names and YAML anchors
+fn validate_name(field: &str, name: &str) -> Result<(), ProjectionError> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(ProjectionError::ValidationFailed {
+            field: field.to_string(),
+            reason: format!("{:?} must be letters, digits, '-', '_' or '.'", name),
+        })
+    }
+}
+
+fn invalid_key(name: &str, error: impl std::fmt::Display) -> ProjectionError {
+    ProjectionError::ValidationFailed {
+        field: format!("{}.public_key", name),
+        reason: error.to_string(),
+    }
+}
+
+/// Nix string literal; names are validated, so only keys need escaping
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace("${", "\\${"))
+}
+
+fn single_line(value: &str) -> String {
+    value.replace('\n', " ")
+}
+
+// ============================================================================
+// FACTORY FUNCTIONS
+// ============================================================================
+
+/// Create a Nix secrets projection
+pub fn nix_secrets() -> NixSecretsProjection {
+    NixSecretsProjection::new()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::age_derivation::{derive_age_key, AgeDerivationPath};
+    use crate::crypto::ssh_derivation::{derive_ssh_key, SshDerivationPath};
+    use crate::crypto::MasterSeed;
+
+    fn seed() -> MasterSeed {
+        MasterSeed::from_bytes([7u8; 32])
+    }
+
+    fn host_key(host: &str) -> String {
+        let path = SshDerivationPath::new("cowboyai", host, "host").unwrap();
+        derive_ssh_key(&seed(), &path).to_openssh_public(&format!("root@{}", host)).unwrap()
+    }
+
+    fn input() -> NixSecretsInput {
+        let alice = derive_age_key(&seed(), &AgeDerivationPath::new("cowboyai", "alice").unwrap());
+        let secret = |name: &str, scope| NixSecret {
+            name: name.to_string(),
+            scope,
+            content: format!("{}-plaintext", name),
+            owner: None,
+            mode: None,
+        };
+        NixSecretsInput {
+            organization: "Cowboy AI".to_string(),
+            admins: vec![NixAdmin { name: "alice".to_string(), recipient: alice.recipient.clone() }],
+            hosts: vec![
+                NixHost { name: "web-1".to_string(), location: Some("dc-east".to_string()), public_key: host_key("web-1") },
+                NixHost { name: "web-2".to_string(), location: Some("dc-east".to_string()), public_key: host_key("web-2") },
+            ],
+            secrets: vec![
+                NixSecret {
+                    owner: Some("postgres".to_string()),
+                    mode: Some("0440".to_string()),
+                    ..secret("db-password", NixSecretScope::Host("web-1".to_string()))
+                },
+                secret("wifi", NixSecretScope::Location("dc-east".to_string())),
+            ],
+        }
+    }
+
+    fn file<'a>(export: &'a NixSecretsExport, path: &str) -> &'a ExportFile {
+        export.files.iter().find(|f| f.path.as_path() == std::path::Path::new(path)).unwrap()
+    }
+
+    #[test]
+    fn test_admins_and_hosts_decrypt_their_secrets() {
+        let export = nix_secrets().project(input()).unwrap();
+        assert_eq!(export.secret_count, 2);
+
+        let db = file(&export, "nix/secrets/hosts/web-1/db-password.age");
+        assert!(!db.sensitive);
+        assert!(db.content.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+        assert!(!db.content.contains("db-password-plaintext"));
+
+        let admin = derive_age_key(&seed(), &AgeDerivationPath::new("cowboyai", "alice").unwrap());
+        assert_eq!(age::decrypt(&admin.identity(), db.content.as_bytes()).unwrap(), b"db-password-plaintext");
+
+        // web-2 shares the location secret but not web-1's database password
+        let web_2 = derive_ssh_key(&seed(), &SshDerivationPath::new("cowboyai", "web-2", "host").unwrap());
+        let pem = web_2.to_openssh_private("root@web-2").unwrap();
+        let identity = age::ssh::Identity::from_buffer(pem.as_bytes(), None).unwrap();
+        let wifi = file(&export, "nix/secrets/locations/dc-east/wifi.age");
+        assert_eq!(age::decrypt(&identity, wifi.content.as_bytes()).unwrap(), b"wifi-plaintext");
+        assert!(age::decrypt(&identity, db.content.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_renders_agenix_rules_modules_and_sops_config() {
+        let export = nix_secrets().project(input()).unwrap();
+
+        let rules = &file(&export, "nix/secrets.nix").content;
+        assert!(rules.contains("  \"secrets/hosts/web-1/db-password.age\".publicKeys = admins ++ [ hosts.\"web-1\" ];\n"));
+        assert!(rules.contains(
+            "  \"secrets/locations/dc-east/wifi.age\".publicKeys = admins ++ [ hosts.\"web-1\" hosts.\"web-2\" ];\n"
+        ));
+        // Host key comments are dropped
+        assert!(rules.contains(&format!("    \"web-1\" = \"{}\"; # dc-east\n", host_key("web-1").rsplit_once(' ').unwrap().0)));
+
+        let module = &file(&export, "nix/hosts/web-1/secrets.nix").content;
+        assert!(module.contains("  age.secrets.\"db-password\" = {\n    file = ../../secrets/hosts/web-1/db-password.age;\n"));
+        assert!(module.contains("    owner = \"postgres\";\n    mode = \"0440\";\n"));
+        assert!(module.contains("  age.secrets.\"wifi\" = {\n"));
+        assert!(!file(&export, "nix/hosts/web-2/secrets.nix").content.contains("db-password"));
+
+        let sops = &file(&export, "nix/.sops.yaml").content;
+        let web_1_age = ssh_ed25519_to_age_recipient(&host_key("web-1")).unwrap();
+        assert!(sops.contains(&format!("  - &host_web-1 {}\n", web_1_age)));
+        assert!(sops.contains(
+            "  - path_regex: sops/locations/dc-east/[^/]+$\n    key_groups:\n      - age:\n          \
+             - *admin_alice\n          - *host_web-1\n          - *host_web-2\n"
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_input() {
+        let mut unknown_host = input();
+        unknown_host.secrets[0].scope = NixSecretScope::Host("db-1".to_string());
+        assert!(nix_secrets().project(unknown_host).is_err());
+
+        let mut empty_location = input();
+        empty_location.secrets[1].scope = NixSecretScope::Location("dc-west".to_string());
+        assert!(nix_secrets().project(empty_location).is_err());
+
+        let mut rsa_host = input();
+        rsa_host.hosts[0].public_key = "ssh-rsa AAAAB3NzaC1yc2E root@web-1".to_string();
+        assert!(nix_secrets().project(rsa_host).is_err());
+
+        let mut clashing = input();
+        clashing.secrets[1].name = "db-password".to_string();
+        assert!(nix_secrets().project(clashing).is_err());
+
+        let mut bad_mode = input();
+        bad_mode.secrets[0].mode = Some("rw-r".to_string());
+        assert!(nix_secrets().project(bad_mode).is_err());
+
+        let mut no_admins = input();
+        no_admins.admins.clear();
+        assert!(nix_secrets().project(no_admins).is_err());
+    }
+}
@@ -0,0 +1,742 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # NSC Store Import
+//!
+//! The inverse of the NSC store projection: adopts an existing NATS
+//! deployment managed with `nsc` by reading its operator/account/user tree
+//! and reconstructing the domain credentials and creation events.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! nsc directory (stores/ + keys/)
+//!     ↓ via
+//! read_nsc_store (I/O)
+//!     ↓ produces
+//! NscStore
+//!     ↓ via
+//! NscStoreToCredentialsProjection (pure, verifies the JWT chain)
+//!     ↓ produces
+//! NscImport (DomainNatsCredentials + seeds)
+//!     ↓ via
+//! NscImportToEventsProjection
+//!     ↓ produces
+//! Vec<DomainEvent>  (NatsOperatorCreated, NatsAccountCreated,
+//!                    NatsUserCreated, NatsSigningKeyGenerated)
+//! ```
+//!
+//! Both layouts are understood: the one written by
+//! [`CredentialsToNscStoreProjection`](super::nscstore::CredentialsToNscStoreProjection),
+//! with `.creds` and `.nk` files beside the user JWTs, and the one `nsc`
+//! itself keeps, with seeds under `keys/keys/` and credentials under
+//! `keys/creds/`. Seeds are matched to entities by the public key they
+//! derive, never by file name.
+//!
+//! Every JWT signature is verified, and every account and user must be
+//! issued by the operator or account it sits under (directly or through
+//! one of its signing keys), so a tampered tree is rejected rather than
+//! adopted.
+
+use crate::events::nats_account::NatsAccountCreatedEvent;
+use crate::events::nats_operator::{NatsOperatorCreatedEvent, NatsSigningKeyGeneratedEvent};
+use crate::events::nats_user::NatsUserCreatedEvent;
+use crate::events::{DomainEvent, NatsAccountEvents, NatsOperatorEvents, NatsUserEvents};
+use crate::projection::nscstore::{
+    AccountCredentials, DomainNatsCredentials, NscFile, NscFileType, NscStore, OperatorCredentials,
+    UserCredentials,
+};
+use crate::projection::{Projection, ProjectionError};
+use crate::types::NatsEntityType;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::Utc;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Marker preceding the seed in a `.creds` file
+const CREDS_SEED_MARKER: &str = "NKEY SEED-----";
+
+// ============================================================================
+// DOMAIN TYPES
+// ============================================================================
+
+/// Credentials reconstructed from an NSC store
+#[derive(Debug, Clone)]
+pub struct NscImport {
+    /// Operator, accounts and users; user seeds are filled in when found
+    pub credentials: DomainNatsCredentials,
+    /// Seeds of the operator, accounts and their signing keys found in the
+    /// store, keyed by public key
+    pub seeds: HashMap<String, String>,
+}
+
+impl NscImport {
+    /// Seed for `public_key`, if the store held it
+    pub fn seed_for(&self, public_key: &str) -> Option<&str> {
+        self.seeds.get(public_key).map(String::as_str)
+    }
+}
+
+/// The parts of a NATS JWT the import needs
+#[derive(Debug, Clone)]
+struct NatsJwtClaims {
+    subject: String,
+    issuer: String,
+    name: String,
+    kind: Option<String>,
+    signing_keys: Vec<String>,
+    system_account: Option<String>,
+    issuer_account: Option<String>,
+}
+
+// ============================================================================
+// FILESYSTEM READER
+// ============================================================================
+
+/// Read an NSC directory into an [`NscStore`]
+///
+/// `root` is the directory holding `stores/` (and, for stores managed by
+/// `nsc`, `keys/`), e.g. `~/.local/share/nats/nsc`.
+pub fn read_nsc_store(root: &Path, organization_id: Uuid) -> Result<NscStore, ProjectionError> {
+    if !root.join("stores").is_dir() {
+        return Err(ProjectionError::IoError(format!("No NSC stores directory in {}", root.display())));
+    }
+
+    let store_name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "nsc".to_string());
+    let mut store = NscStore::new(store_name, organization_id);
+    store.metadata.source = "nsc-import".to_string();
+
+    let mut paths = Vec::new();
+    for dir in ["stores", "keys"] {
+        collect_files(&root.join(dir), &mut paths)?;
+    }
+    if root.join("nsc.json").is_file() {
+        paths.push(root.join("nsc.json"));
+    }
+
+    for path in paths {
+        let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+        let Some(file_type) = classify(&relative) else {
+            continue;
+        };
+        let content = fs::read_to_string(&path)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to read {}: {}", path.display(), e)))?;
+        if let Some(parent) = relative.parent() {
+            store.add_directory(parent.to_path_buf());
+        }
+        store.add_file(NscFile::new(relative, content, file_type));
+    }
+    Ok(store)
+}
+
+/// All files below `dir`, in a stable order
+fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), ProjectionError> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(dir)
+        .map_err(|e| ProjectionError::IoError(format!("Failed to read {}: {}", dir.display(), e)))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ProjectionError::IoError(format!("Failed to read {}: {}", dir.display(), e)))?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, paths)?;
+        } else {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// File type from a path relative to the NSC root
+fn classify(relative: &Path) -> Option<NscFileType> {
+    let parts: Vec<String> = relative.iter().map(|part| part.to_string_lossy().into_owned()).collect();
+    let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+    match (parts.as_slice(), relative.extension()?.to_str()?) {
+        (["nsc.json"], _) => Some(NscFileType::Config),
+        (["stores", _, _], "jwt") => Some(NscFileType::OperatorJwt),
+        (["stores", _, "accounts", _, _], "jwt") => Some(NscFileType::AccountJwt),
+        (["stores", _, "accounts", _, "users", _], "jwt") => Some(NscFileType::UserJwt),
+        (_, "creds") => Some(NscFileType::Credentials),
+        (_, "nk") => Some(NscFileType::Seed),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// PROJECTIONS
+// ============================================================================
+
+/// Projection: NscStore → NscImport
+///
+/// Decodes and verifies the JWTs of one operator and collects the seeds
+/// belonging to it.
+#[derive(Debug, Clone, Default)]
+pub struct NscStoreToCredentialsProjection {
+    /// Operator to import when the store holds several
+    operator: Option<String>,
+}
+
+impl NscStoreToCredentialsProjection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Import the operator named `name` (JWT name or directory name)
+    pub fn with_operator(mut self, name: impl Into<String>) -> Self {
+        self.operator = Some(name.into());
+        self
+    }
+
+    /// Operator JWT file to import
+    fn select_operator<'a>(&self, store: &'a NscStore) -> Result<&'a NscFile, ProjectionError> {
+        let operators: Vec<&NscFile> =
+            store.files.iter().filter(|file| file.file_type == NscFileType::OperatorJwt).collect();
+        let directory = |file: &NscFile| store_directory(&file.path).unwrap_or_default();
+
+        let selected: Vec<&NscFile> = match &self.operator {
+            Some(name) => operators
+                .iter()
+                .copied()
+                .filter(|file| {
+                    directory(file) == *name || decode_jwt(file).map(|claims| claims.name == *name).unwrap_or(false)
+                })
+                .collect(),
+            None => operators.clone(),
+        };
+
+        match selected.as_slice() {
+            [operator] => Ok(operator),
+            [] => Err(ProjectionError::PrerequisiteNotMet {
+                name: "operator".to_string(),
+                description: match &self.operator {
+                    Some(name) => format!("No operator {} in the NSC store", name),
+                    None => "No operator JWT in the NSC store".to_string(),
+                },
+            }),
+            _ => Err(ProjectionError::ValidationFailed {
+                field: "operator".to_string(),
+                reason: format!(
+                    "The NSC store holds several operators ({}); choose one",
+                    operators.iter().map(|file| directory(file)).collect::<Vec<_>>().join(", ")
+                ),
+            }),
+        }
+    }
+}
+
+impl Projection<NscStore, NscImport, ProjectionError> for NscStoreToCredentialsProjection {
+    fn project(&self, store: NscStore) -> Result<NscImport, ProjectionError> {
+        let mut seeds = collect_seeds(&store)?;
+
+        let operator_file = self.select_operator(&store)?;
+        let operator_dir = store_directory(&operator_file.path).unwrap_or_default();
+        let operator = decode_jwt(operator_file)?;
+        expect_kind(operator_file, &operator, "operator")?;
+        if operator.issuer != operator.subject && !operator.signing_keys.contains(&operator.issuer) {
+            return Err(invalid(operator_file, "operator JWT is not self-signed"));
+        }
+
+        // Accounts, each issued by the operator or one of its signing keys
+        let in_operator = |file: &&NscFile| store_directory(&file.path).as_deref() == Some(operator_dir.as_str());
+        let mut accounts = HashMap::new();
+        for file in store.files.iter().filter(|f| f.file_type == NscFileType::AccountJwt).filter(in_operator) {
+            let account = decode_jwt(file)?;
+            expect_kind(file, &account, "account")?;
+            if account.issuer != operator.subject && !operator.signing_keys.contains(&account.issuer) {
+                return Err(invalid(file, "account JWT is not issued by the operator"));
+            }
+            accounts.insert(
+                account.name.clone(),
+                AccountCredentials {
+                    name: account.name,
+                    jwt: file.content.trim().to_string(),
+                    public_key: account.subject,
+                    operator_public_key: operator.subject.clone(),
+                    signing_keys: account.signing_keys,
+                },
+            );
+        }
+
+        // Users, each issued by its account or one of the account's signing keys
+        let mut users: HashMap<String, Vec<UserCredentials>> = HashMap::new();
+        for file in store.files.iter().filter(|f| f.file_type == NscFileType::UserJwt).filter(in_operator) {
+            let user = decode_jwt(file)?;
+            expect_kind(file, &user, "user")?;
+            let account_key = user.issuer_account.clone().unwrap_or_else(|| user.issuer.clone());
+            let account = accounts
+                .values()
+                .find(|account| account.public_key == account_key)
+                .ok_or_else(|| invalid(file, "user JWT is issued by an account outside the operator"))?;
+            if user.issuer != account.public_key && !account.signing_keys.contains(&user.issuer) {
+                return Err(invalid(file, "user JWT is not issued by its account"));
+            }
+            users.entry(account.name.clone()).or_default().push(UserCredentials {
+                name: user.name,
+                jwt: file.content.trim().to_string(),
+                seed: seeds.remove(&user.subject),
+                public_key: user.subject,
+                account_public_key: account.public_key.clone(),
+                person_id: None,
+            });
+        }
+        for account_users in users.values_mut() {
+            account_users.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        // Keep only the seeds of this hierarchy; nsc shares its keys
+        // directory between operators
+        let hierarchy_keys: Vec<&String> = std::iter::once(&operator.subject)
+            .chain(&operator.signing_keys)
+            .chain(accounts.values().flat_map(|account| std::iter::once(&account.public_key).chain(&account.signing_keys)))
+            .collect();
+        seeds.retain(|public_key, _| hierarchy_keys.contains(&public_key));
+
+        let credentials = DomainNatsCredentials {
+            organization_id: store.organization_id,
+            organization_name: operator.name.clone(),
+            operator: OperatorCredentials {
+                name: operator.name,
+                jwt: operator_file.content.trim().to_string(),
+                public_key: operator.subject,
+                signing_keys: operator.signing_keys,
+                system_account: operator.system_account,
+            },
+            accounts,
+            users,
+            generated_at: Utc::now(),
+        };
+        Ok(NscImport { credentials, seeds })
+    }
+
+    fn name(&self) -> &'static str {
+        "NscStoreToCredentials"
+    }
+}
+
+/// Projection: NscImport → creation events for the NATS aggregates
+///
+/// The events are applied like any other, so an adopted deployment is
+/// indistinguishable from one bootstrapped by cim-keys.
+#[derive(Debug, Clone)]
+pub struct NscImportToEventsProjection {
+    correlation_id: Uuid,
+    created_by: String,
+}
+
+impl Default for NscImportToEventsProjection {
+    fn default() -> Self {
+        Self {
+            correlation_id: Uuid::now_v7(),
+            created_by: "nsc-import".to_string(),
+        }
+    }
+}
+
+impl NscImportToEventsProjection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_correlation(mut self, correlation_id: Uuid) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
+    pub fn with_created_by(mut self, created_by: impl Into<String>) -> Self {
+        self.created_by = created_by.into();
+        self
+    }
+
+    fn signing_key_events(&self, entity_id: Uuid, entity_type: NatsEntityType, keys: &[String]) -> Vec<DomainEvent> {
+        keys.iter()
+            .map(|public_key| {
+                DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyGenerated(NatsSigningKeyGeneratedEvent {
+                    key_id: Uuid::now_v7(),
+                    entity_id,
+                    entity_type,
+                    public_key: public_key.clone(),
+                    generated_at: Utc::now(),
+                    correlation_id: self.correlation_id,
+                    causation_id: None,
+                }))
+            })
+            .collect()
+    }
+}
+
+impl Projection<NscImport, Vec<DomainEvent>, ProjectionError> for NscImportToEventsProjection {
+    fn project(&self, import: NscImport) -> Result<Vec<DomainEvent>, ProjectionError> {
+        let credentials = import.credentials;
+        let operator_id = Uuid::now_v7();
+        let mut events = vec![DomainEvent::NatsOperator(NatsOperatorEvents::NatsOperatorCreated(
+            NatsOperatorCreatedEvent {
+                operator_id,
+                name: credentials.operator.name.clone(),
+                public_key: credentials.operator.public_key.clone(),
+                created_by: self.created_by.clone(),
+                organization_id: Some(credentials.organization_id),
+                correlation_id: self.correlation_id,
+                causation_id: None,
+            },
+        ))];
+        events.extend(self.signing_key_events(operator_id, NatsEntityType::Operator, &credentials.operator.signing_keys));
+
+        let mut accounts: Vec<&AccountCredentials> = credentials.accounts.values().collect();
+        accounts.sort_by(|a, b| a.name.cmp(&b.name));
+        for account in accounts {
+            let account_id = Uuid::now_v7();
+            events.push(DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountCreated(NatsAccountCreatedEvent {
+                account_id,
+                operator_id,
+                name: account.name.clone(),
+                public_key: account.public_key.clone(),
+                is_system: credentials.operator.system_account.as_ref() == Some(&account.public_key),
+                created_by: self.created_by.clone(),
+                organization_unit_id: None,
+                correlation_id: self.correlation_id,
+                causation_id: None,
+            })));
+            events.extend(self.signing_key_events(account_id, NatsEntityType::Account, &account.signing_keys));
+
+            for user in credentials.users.get(&account.name).into_iter().flatten() {
+                events.push(DomainEvent::NatsUser(NatsUserEvents::NatsUserCreated(NatsUserCreatedEvent {
+                    user_id: Uuid::now_v7(),
+                    account_id,
+                    name: user.name.clone(),
+                    public_key: user.public_key.clone(),
+                    created_by: self.created_by.clone(),
+                    person_id: user.person_id,
+                    correlation_id: self.correlation_id,
+                    causation_id: None,
+                })));
+            }
+        }
+        Ok(events)
+    }
+
+    fn name(&self) -> &'static str {
+        "NscImportToEvents"
+    }
+}
+
+// ============================================================================
+// JWT AND SEED DECODING
+// ============================================================================
+
+/// Decode a NATS JWT and verify its signature against its issuer
+///
+/// Accepts both `nsc` claims (`name` at the top level, `nats.type`) and
+/// the claims cim-keys issues (`nats.name`).
+fn decode_jwt(file: &NscFile) -> Result<NatsJwtClaims, ProjectionError> {
+    let token = file.content.trim();
+    let [header, body, signature] = token.split('.').collect::<Vec<_>>()[..] else {
+        return Err(invalid(file, "not a JWT"));
+    };
+    let claims: Value = URL_SAFE_NO_PAD
+        .decode(body)
+        .ok()
+        .and_then(|json| serde_json::from_slice(&json).ok())
+        .ok_or_else(|| invalid(file, "undecodable JWT claims"))?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| invalid(file, "undecodable JWT signature"))?;
+
+    let text = |value: &Value| value.as_str().map(String::from);
+    let issuer = text(&claims["iss"]).ok_or_else(|| invalid(file, "JWT has no issuer"))?;
+    let subject = text(&claims["sub"]).ok_or_else(|| invalid(file, "JWT has no subject"))?;
+
+    nkeys::KeyPair::from_public_key(&issuer)
+        .and_then(|issuer_key| issuer_key.verify(format!("{}.{}", header, body).as_bytes(), &signature))
+        .map_err(|_| invalid(file, "JWT signature does not verify"))?;
+
+    let nats = &claims["nats"];
+    let signing_keys = nats["signing_keys"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        // Account signing keys may be scoped: {"kind": "user_scope", "key": ...}
+        .filter_map(|key| text(key).or_else(|| text(&key["key"])))
+        .collect();
+    let name = text(&claims["name"])
+        .or_else(|| text(&nats["name"]))
+        .or_else(|| file.path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| subject.clone());
+
+    Ok(NatsJwtClaims {
+        subject,
+        issuer,
+        name,
+        kind: text(&nats["type"]),
+        signing_keys,
+        system_account: text(&nats["system_account"]),
+        issuer_account: text(&nats["issuer_account"]),
+    })
+}
+
+fn expect_kind(file: &NscFile, claims: &NatsJwtClaims, kind: &str) -> Result<(), ProjectionError> {
+    match &claims.kind {
+        Some(found) if found != kind => Err(invalid(file, &format!("expected an {} JWT, found {}", kind, found))),
+        _ => Ok(()),
+    }
+}
+
+/// Seeds from `.nk` and `.creds` files, keyed by the public key they derive
+fn collect_seeds(store: &NscStore) -> Result<HashMap<String, String>, ProjectionError> {
+    let mut seeds = HashMap::new();
+    for file in &store.files {
+        let seed = match file.file_type {
+            NscFileType::Seed => file.content.trim(),
+            NscFileType::Credentials => creds_seed(&file.content).ok_or_else(|| invalid(file, "no seed in credentials"))?,
+            _ => continue,
+        };
+        let key_pair = nkeys::KeyPair::from_seed(seed).map_err(|_| invalid(file, "invalid nkey seed"))?;
+        seeds.insert(key_pair.public_key(), seed.to_string());
+    }
+    Ok(seeds)
+}
+
+/// The seed in a `.creds` file: the first line after the seed marker
+fn creds_seed(content: &str) -> Option<&str> {
+    let mut lines = content.lines().map(str::trim);
+    lines.find(|line| line.starts_with("-----BEGIN") && line.ends_with(CREDS_SEED_MARKER))?;
+    lines.find(|line| !line.is_empty())
+}
+
+/// Operator directory of a file under `stores/<operator>/`
+fn store_directory(path: &Path) -> Option<String> {
+    let mut parts = path.iter();
+    if parts.next()? != "stores" {
+        return None;
+    }
+    parts.next().map(|part| part.to_string_lossy().into_owned())
+}
+
+fn invalid(file: &NscFile, reason: &str) -> ProjectionError {
+    ProjectionError::ValidationFailed {
+        field: file.path.display().to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+// ============================================================================
+// FACTORY FUNCTIONS
+// ============================================================================
+
+/// Create an NSC-store-to-credentials projection
+pub fn nscstore_to_credentials() -> NscStoreToCredentialsProjection {
+    NscStoreToCredentialsProjection::new()
+}
+
+/// Create an NSC-import-to-events projection
+pub fn nsc_import_to_events() -> NscImportToEventsProjection {
+    NscImportToEventsProjection::new()
+}
+
+/// Read the NSC directory at `root` and reconstruct the events of its
+/// single operator
+pub fn import_nsc_store(root: &Path, organization_id: Uuid) -> Result<(NscImport, Vec<DomainEvent>), ProjectionError> {
+    let import = nscstore_to_credentials().project(read_nsc_store(root, organization_id)?)?;
+    let events = nsc_import_to_events().project(import.clone())?;
+    Ok((import, events))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projection::nscstore::credentials_to_nscstore_with_seeds;
+    use nkeys::KeyPair;
+
+    fn jwt(issuer: &KeyPair, subject: &KeyPair, claims: Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ed25519-nkey"}"#);
+        let mut claims = claims;
+        claims["iss"] = issuer.public_key().into();
+        claims["sub"] = subject.public_key().into();
+        let body = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let signature = issuer.sign(format!("{}.{}", header, body).as_bytes()).unwrap();
+        format!("{}.{}.{}", header, body, URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    struct Deployment {
+        operator: KeyPair,
+        account: KeyPair,
+        account_signing: KeyPair,
+        user: KeyPair,
+        credentials: DomainNatsCredentials,
+    }
+
+    /// An nsc-style deployment: the user is issued by an account signing key
+    fn deployment() -> Deployment {
+        let operator = KeyPair::new_operator();
+        let account = KeyPair::new_account();
+        let account_signing = KeyPair::new_account();
+        let user = KeyPair::new_user();
+
+        let operator_jwt = jwt(
+            &operator,
+            &operator,
+            serde_json::json!({"name": "CowboyAI", "nats": {"type": "operator", "version": 2}}),
+        );
+        let account_jwt = jwt(
+            &operator,
+            &account,
+            serde_json::json!({"name": "engineering", "nats": {
+                "type": "account", "version": 2,
+                "signing_keys": [{"kind": "user_scope", "key": account_signing.public_key()}]
+            }}),
+        );
+        let user_jwt = jwt(
+            &account_signing,
+            &user,
+            serde_json::json!({"name": "alice", "nats": {
+                "type": "user", "version": 2, "issuer_account": account.public_key()
+            }}),
+        );
+
+        let credentials = DomainNatsCredentials {
+            organization_id: Uuid::now_v7(),
+            organization_name: "CowboyAI".to_string(),
+            operator: OperatorCredentials {
+                name: "CowboyAI".to_string(),
+                jwt: operator_jwt,
+                public_key: operator.public_key(),
+                signing_keys: vec![],
+                system_account: None,
+            },
+            accounts: HashMap::from([(
+                "engineering".to_string(),
+                AccountCredentials {
+                    name: "engineering".to_string(),
+                    jwt: account_jwt,
+                    public_key: account.public_key(),
+                    operator_public_key: operator.public_key(),
+                    signing_keys: vec![account_signing.public_key()],
+                },
+            )]),
+            users: HashMap::from([(
+                "engineering".to_string(),
+                vec![UserCredentials {
+                    name: "alice".to_string(),
+                    jwt: user_jwt,
+                    public_key: user.public_key(),
+                    seed: Some(user.seed().unwrap()),
+                    account_public_key: account.public_key(),
+                    person_id: None,
+                }],
+            )]),
+            generated_at: Utc::now(),
+        };
+        Deployment { operator, account, account_signing, user, credentials }
+    }
+
+    #[test]
+    fn test_export_then_import_round_trips() {
+        let deployment = deployment();
+        let store = credentials_to_nscstore_with_seeds().project(deployment.credentials.clone()).unwrap();
+
+        let import = nscstore_to_credentials().project(store).unwrap();
+        let credentials = &import.credentials;
+        assert_eq!(credentials.organization_id, deployment.credentials.organization_id);
+        assert_eq!(credentials.operator.public_key, deployment.operator.public_key());
+        assert_eq!(credentials.operator.jwt, deployment.credentials.operator.jwt);
+
+        let account = &credentials.accounts["engineering"];
+        assert_eq!(account.public_key, deployment.account.public_key());
+        assert_eq!(account.signing_keys, [deployment.account_signing.public_key()]);
+
+        let alice = &credentials.users["engineering"][0];
+        assert_eq!(alice.public_key, deployment.user.public_key());
+        assert_eq!(alice.account_public_key, deployment.account.public_key());
+        assert_eq!(alice.seed, Some(deployment.user.seed().unwrap()));
+
+        let events = nsc_import_to_events().project(import).unwrap();
+        let kinds: Vec<&str> = events
+            .iter()
+            .map(|event| match event {
+                DomainEvent::NatsOperator(NatsOperatorEvents::NatsOperatorCreated(_)) => "operator",
+                DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyGenerated(_)) => "signing key",
+                DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountCreated(_)) => "account",
+                DomainEvent::NatsUser(NatsUserEvents::NatsUserCreated(_)) => "user",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(kinds, ["operator", "account", "signing key", "user"]);
+    }
+
+    #[test]
+    fn test_reads_nsc_layout_with_keys_directory() {
+        let deployment = deployment();
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |relative: &str, content: &str| {
+            let path = root.join(relative);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+
+        let credentials = &deployment.credentials;
+        write("stores/CowboyAI/CowboyAI.jwt", &credentials.operator.jwt);
+        write("stores/CowboyAI/accounts/engineering/engineering.jwt", &credentials.accounts["engineering"].jwt);
+        write("stores/CowboyAI/accounts/engineering/users/alice.jwt", &credentials.users["engineering"][0].jwt);
+        // nsc shards seeds by key prefix; names do not matter to the import
+        let operator_key = deployment.operator.public_key();
+        write(&format!("keys/keys/O/{}/{}.nk", &operator_key[1..3], operator_key), &deployment.operator.seed().unwrap());
+        write(
+            "keys/creds/CowboyAI/engineering/alice.creds",
+            &format!(
+                "-----BEGIN NATS USER JWT-----\n{}\n------END NATS USER JWT------\n\n\
+                 -----BEGIN USER NKEY SEED-----\n{}\n------END USER NKEY SEED------\n",
+                credentials.users["engineering"][0].jwt,
+                deployment.user.seed().unwrap()
+            ),
+        );
+        // Seed of an unrelated operator sharing the keys directory
+        write("keys/keys/O/XX/other.nk", &KeyPair::new_operator().seed().unwrap());
+
+        let (import, events) = import_nsc_store(root, Uuid::now_v7()).unwrap();
+        assert_eq!(import.seed_for(&operator_key), Some(deployment.operator.seed().unwrap().as_str()));
+        assert_eq!(import.seeds.len(), 1);
+        assert_eq!(import.credentials.users["engineering"][0].seed, Some(deployment.user.seed().unwrap()));
+        assert_eq!(events.len(), 4);
+    }
+
+    #[test]
+    fn test_rejects_forged_and_foreign_jwts() {
+        let mut deployment = deployment();
+
+        // Account claims altered after signing
+        let account = deployment.credentials.accounts.get_mut("engineering").unwrap();
+        let parts: Vec<&str> = account.jwt.split('.').collect();
+        let forged = URL_SAFE_NO_PAD.encode(
+            String::from_utf8(URL_SAFE_NO_PAD.decode(parts[1]).unwrap()).unwrap().replace("engineering", "admins"),
+        );
+        account.jwt = format!("{}.{}.{}", parts[0], forged, parts[2]);
+        let store = credentials_to_nscstore_with_seeds().project(deployment.credentials.clone()).unwrap();
+        assert!(matches!(
+            nscstore_to_credentials().project(store),
+            Err(ProjectionError::ValidationFailed { reason, .. }) if reason.contains("signature")
+        ));
+
+        // Account validly signed, but by another operator
+        let account = deployment.credentials.accounts.get_mut("engineering").unwrap();
+        let stranger = KeyPair::new_operator();
+        account.jwt = jwt(&stranger, &deployment.account, serde_json::json!({"name": "engineering", "nats": {}}));
+        let store = credentials_to_nscstore_with_seeds().project(deployment.credentials.clone()).unwrap();
+        assert!(matches!(
+            nscstore_to_credentials().project(store),
+            Err(ProjectionError::ValidationFailed { reason, .. }) if reason.contains("not issued by the operator")
+        ));
+    }
+}
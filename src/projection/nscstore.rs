@@ -32,7 +32,23 @@
 //!                 └── users/
 //!                     └── <user>.jwt  # User JWTs
 //! ```
+//!
+//! ## Delivery Bundles
+//!
+//! `.creds` files carry the user's seed in plaintext. For people with a
+//! delivery bundle the plaintext `.creds` are left out of the store and
+//! written age-encrypted to them instead:
+//!
+//! ```text
+//! deliveries/<person-id>/<account>-<user>.creds.age
+//! ```
+//!
+//! Each bundle yields a `NatsCredentialsDelivered` event recording the
+//! delivery without any key material.
 
+use crate::crypto::age_encryption::{encrypt_armored, AgeProtection, AGE_FILE_SUFFIX};
+use crate::events::{DomainEvent, NatsUserEvents};
+use crate::events::nats_user::NatsCredentialsDeliveredEvent;
 use crate::projection::{Projection, ProjectionError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -109,6 +125,30 @@ pub struct DomainNatsCredentials {
     pub generated_at: DateTime<Utc>,
 }
 
+impl DomainNatsCredentials {
+    /// Apply a user revocation before (re)projecting the store
+    ///
+    /// Replaces the issuing account's JWT with the re-signed one carrying
+    /// the revocation and drops the revoked user's credentials, so the
+    /// exported store neither trusts nor hands out the user's JWT.
+    pub fn apply_user_revocation(&mut self, event: &crate::events::nats_account::NatsUserRevokedEvent) -> bool {
+        let Some(account) = self
+            .accounts
+            .values_mut()
+            .find(|account| account.public_key == event.account_public_key)
+        else {
+            return false;
+        };
+        account.jwt = event.account_jwt.clone();
+        let account_name = account.name.clone();
+
+        if let Some(users) = self.users.get_mut(&account_name) {
+            users.retain(|user| user.public_key != event.user_public_key);
+        }
+        true
+    }
+}
+
 // ============================================================================
 // NSC STORE STRUCTURE
 // ============================================================================
@@ -175,6 +215,8 @@ pub enum NscFileType {
     Credentials,
     /// NKey seed file
     Seed,
+    /// Credentials file encrypted for delivery to a person (.creds.age)
+    EncryptedCredentials,
 }
 
 /// Complete NSC store ready for export
@@ -190,6 +232,9 @@ pub struct NscStore {
     pub directories: Vec<PathBuf>,
     /// Store metadata
     pub metadata: NscStoreMetadata,
+    /// Encrypted per-person credentials deliveries
+    #[serde(default)]
+    pub deliveries: Vec<CredsDelivery>,
 }
 
 /// Metadata about the NSC store
@@ -204,6 +249,40 @@ pub struct NscStoreMetadata {
     pub source: String,
 }
 
+/// Encrypted `.creds` files delivered to one person
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredsDelivery {
+    pub delivery_id: Uuid,
+    pub person_id: Uuid,
+    /// Encrypted files within the store
+    pub files: Vec<PathBuf>,
+    /// Public keys of the delivered users
+    pub user_public_keys: Vec<String>,
+    /// "recipients" or "passphrase"
+    pub protection: String,
+    /// age recipients able to open the bundle (empty for a passphrase)
+    pub recipients: Vec<String>,
+    /// SHA-256 over the checksums of the encrypted files
+    pub checksum: String,
+}
+
+impl CredsDelivery {
+    /// Event recording this delivery
+    pub fn delivered_event(&self, correlation_id: Uuid, causation_id: Option<Uuid>) -> DomainEvent {
+        DomainEvent::NatsUser(NatsUserEvents::NatsCredentialsDelivered(NatsCredentialsDeliveredEvent {
+            delivery_id: self.delivery_id,
+            person_id: self.person_id,
+            user_public_keys: self.user_public_keys.clone(),
+            protection: self.protection.clone(),
+            recipients: self.recipients.clone(),
+            bundle_checksum: self.checksum.clone(),
+            delivered_at: Utc::now(),
+            correlation_id,
+            causation_id,
+        }))
+    }
+}
+
 impl NscStore {
     pub fn new(store_name: impl Into<String>, organization_id: Uuid) -> Self {
         Self {
@@ -220,6 +299,7 @@ impl NscStore {
                 user_count: 0,
                 source: "cim-keys".to_string(),
             },
+            deliveries: Vec::new(),
         }
     }
 
@@ -236,6 +316,14 @@ impl NscStore {
     pub fn total_files(&self) -> usize {
         self.files.len()
     }
+
+    /// Events recording every delivery bundle of this store
+    pub fn delivered_events(&self, correlation_id: Uuid, causation_id: Option<Uuid>) -> Vec<DomainEvent> {
+        self.deliveries
+            .iter()
+            .map(|delivery| delivery.delivered_event(correlation_id, causation_id))
+            .collect()
+    }
 }
 
 /// Compute SHA-256 checksum
@@ -258,6 +346,8 @@ pub struct CredentialsToNscStoreProjection {
     include_seeds: bool,
     /// Include .creds files (combined JWT + seed)
     include_creds: bool,
+    /// People receiving their .creds encrypted instead of in plaintext
+    deliveries: Vec<(Uuid, AgeProtection)>,
 }
 
 impl Default for CredentialsToNscStoreProjection {
@@ -265,6 +355,7 @@ impl Default for CredentialsToNscStoreProjection {
         Self {
             include_seeds: false,
             include_creds: true,
+            deliveries: Vec::new(),
         }
     }
 }
@@ -284,6 +375,18 @@ impl CredentialsToNscStoreProjection {
         self
     }
 
+    /// Deliver a person's .creds age-encrypted instead of in plaintext
+    pub fn with_delivery_bundle(mut self, person_id: Uuid, protection: AgeProtection) -> Self {
+        self.deliveries.retain(|(id, _)| *id != person_id);
+        self.deliveries.push((person_id, protection));
+        self
+    }
+
+    fn is_delivered(&self, user: &UserCredentials) -> bool {
+        user.person_id
+            .is_some_and(|person_id| self.deliveries.iter().any(|(id, _)| *id == person_id))
+    }
+
     /// Build the directory structure
     fn build_directories(&self, credentials: &DomainNatsCredentials) -> Vec<PathBuf> {
         let mut dirs = Vec::new();
@@ -374,21 +477,12 @@ impl CredentialsToNscStoreProjection {
                     NscFileType::UserJwt,
                 ));
 
-                // Credentials file (JWT + seed)
-                if self.include_creds {
+                // Credentials file (JWT + seed), unless delivered encrypted
+                if self.include_creds && !self.is_delivered(user) {
                     if let Some(ref seed) = user.seed {
-                        let creds_content = format!(
-                            "-----BEGIN NATS USER JWT-----\n{}\n------END NATS USER JWT------\n\n\
-                             ************************* IMPORTANT *************************\n\
-                             NKEY Seed printed below can be used to sign and prove identity.\n\
-                             NKEYs are sensitive and should be treated as secrets.\n\n\
-                             -----BEGIN USER NKEY SEED-----\n{}\n------END USER NKEY SEED------\n\n\
-                             *************************************************************\n",
-                            user.jwt, seed
-                        );
                         files.push(NscFile::new(
                             format!("{}.creds", user_path),
-                            creds_content,
+                            creds_content(&user.jwt, seed),
                             NscFileType::Credentials,
                         ));
                     }
@@ -409,6 +503,86 @@ impl CredentialsToNscStoreProjection {
 
         files
     }
+
+    /// Build the encrypted delivery bundle of each person
+    fn build_deliveries(
+        &self,
+        credentials: &DomainNatsCredentials,
+    ) -> Result<Vec<(CredsDelivery, Vec<NscFile>)>, ProjectionError> {
+        let mut accounts: Vec<_> = credentials.users.iter().collect();
+        accounts.sort_by(|a, b| a.0.cmp(b.0));
+
+        self.deliveries
+            .iter()
+            .map(|(person_id, protection)| {
+                let mut files = Vec::new();
+                let mut user_public_keys = Vec::new();
+
+                for (account_name, users) in &accounts {
+                    for user in users.iter().filter(|u| u.person_id == Some(*person_id)) {
+                        let Some(ref seed) = user.seed else { continue };
+                        let content = creds_content(&user.jwt, seed);
+                        let armored = encrypt_armored(content.as_bytes(), protection).map_err(|e| {
+                            ProjectionError::ExternalError {
+                                system: "age".to_string(),
+                                error: format!("{} (credentials delivery of {})", e, person_id),
+                            }
+                        })?;
+                        files.push(NscFile::new(
+                            format!(
+                                "deliveries/{}/{}-{}.creds{}",
+                                person_id,
+                                sanitize_name(account_name),
+                                sanitize_name(&user.name),
+                                AGE_FILE_SUFFIX
+                            ),
+                            armored,
+                            NscFileType::EncryptedCredentials,
+                        ));
+                        user_public_keys.push(user.public_key.clone());
+                    }
+                }
+
+                if files.is_empty() {
+                    return Err(ProjectionError::ValidationFailed {
+                        field: "deliveries".to_string(),
+                        reason: format!("person {} has no user credentials with a seed", person_id),
+                    });
+                }
+
+                let (protection, recipients) = match protection {
+                    AgeProtection::Recipients(recipients) => ("recipients", recipients.clone()),
+                    AgeProtection::Passphrase(_) => ("passphrase", Vec::new()),
+                };
+                let checksum = compute_checksum(
+                    &files.iter().map(|f| f.checksum.as_str()).collect::<Vec<_>>().join("\n"),
+                );
+                let delivery = CredsDelivery {
+                    delivery_id: Uuid::now_v7(),
+                    person_id: *person_id,
+                    files: files.iter().map(|f| f.path.clone()).collect(),
+                    user_public_keys,
+                    protection: protection.to_string(),
+                    recipients,
+                    checksum,
+                };
+                Ok((delivery, files))
+            })
+            .collect()
+    }
+}
+
+/// Content of a .creds file: the user JWT followed by its seed
+pub(crate) fn creds_content(jwt: &str, seed: &str) -> String {
+    format!(
+        "-----BEGIN NATS USER JWT-----\n{}\n------END NATS USER JWT------\n\n\
+         ************************* IMPORTANT *************************\n\
+         NKEY Seed printed below can be used to sign and prove identity.\n\
+         NKEYs are sensitive and should be treated as secrets.\n\n\
+         -----BEGIN USER NKEY SEED-----\n{}\n------END USER NKEY SEED------\n\n\
+         *************************************************************\n",
+        jwt, seed
+    )
 }
 
 impl Projection<DomainNatsCredentials, NscStore, ProjectionError> for CredentialsToNscStoreProjection {
@@ -433,6 +607,14 @@ impl Projection<DomainNatsCredentials, NscStore, ProjectionError> for Credential
             store.add_file(file);
         }
 
+        for (delivery, files) in self.build_deliveries(&credentials)? {
+            store.add_directory(PathBuf::from(format!("deliveries/{}", delivery.person_id)));
+            for file in files {
+                store.add_file(file);
+            }
+            store.deliveries.push(delivery);
+        }
+
         // Update metadata
         store.metadata.operator_name = credentials.operator.name.clone();
         store.metadata.operator_public_key = credentials.operator.public_key.clone();
@@ -687,6 +869,56 @@ mod tests {
         assert_eq!(seed_files.len(), 1);
     }
 
+    #[test]
+    fn test_delivery_bundle_replaces_plaintext_creds() {
+        use crate::crypto::age_encryption::decrypt_with_passphrase;
+        use age::secrecy::SecretString;
+
+        let credentials = sample_credentials();
+        let alice = credentials.users["engineering"][0].clone();
+        let person_id = alice.person_id.unwrap();
+
+        let store = credentials_to_nscstore()
+            .with_delivery_bundle(person_id, AgeProtection::passphrase("alice's passphrase"))
+            .project(credentials)
+            .unwrap();
+
+        // The seed only leaves the projection encrypted
+        assert!(store.files.iter().all(|f| f.file_type != NscFileType::Credentials));
+        assert!(store.files.iter().all(|f| !f.content.contains("SUACDEMOTEST12345")));
+
+        let encrypted = store
+            .files
+            .iter()
+            .find(|f| f.file_type == NscFileType::EncryptedCredentials)
+            .unwrap();
+        assert_eq!(
+            encrypted.path,
+            PathBuf::from(format!("deliveries/{}/engineering-alice.creds.age", person_id))
+        );
+        let plaintext =
+            decrypt_with_passphrase(&encrypted.content, &SecretString::from("alice's passphrase".to_string())).unwrap();
+        assert_eq!(String::from_utf8(plaintext).unwrap(), creds_content(&alice.jwt, "SUACDEMOTEST12345"));
+
+        let events = store.delivered_events(Uuid::now_v7(), None);
+        assert_eq!(events.len(), 1);
+        let DomainEvent::NatsUser(NatsUserEvents::NatsCredentialsDelivered(event)) = &events[0] else {
+            panic!("expected a credentials delivery event");
+        };
+        assert_eq!(event.person_id, person_id);
+        assert_eq!(event.user_public_keys, vec![alice.public_key]);
+        assert_eq!(event.protection, "passphrase");
+        assert!(!serde_json::to_string(event).unwrap().contains("SUACDEMOTEST12345"));
+    }
+
+    #[test]
+    fn test_delivery_bundle_requires_credentials() {
+        let result = credentials_to_nscstore()
+            .with_delivery_bundle(Uuid::now_v7(), AgeProtection::passphrase("x"))
+            .project(sample_credentials());
+        assert!(matches!(result, Err(ProjectionError::ValidationFailed { .. })));
+    }
+
     #[test]
     fn test_metadata() {
         let credentials = sample_credentials();
@@ -0,0 +1,544 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # Paper Backup Projection
+//!
+//! Composable projection for the non-digital recovery path: printable PDF
+//! sheets carrying the Shamir shares of the master seed, the root CA
+//! fingerprints and recovery instructions, each with QR codes.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! MasterSeed::split(k, n) + root CA fingerprints
+//!     ↓ via
+//! PaperBackupProjection (pure)
+//!     ↓ produces
+//! PaperBackupExport
+//!     ├── share-{i}-of-{n}.pdf       # One per custodian, sensitive
+//!     └── root-ca-fingerprints.pdf   # Public, may be filed anywhere
+//! ```
+//!
+//! A share sheet holds one share as a QR code and as text, the root CA
+//! fingerprints to check a recovered hierarchy against, and the recovery
+//! steps. The PDFs use only the standard Helvetica and Courier fonts and
+//! vector QR modules, so they print sharply on any printer without
+//! embedding anything.
+
+use crate::crypto::shamir::SecretShare;
+use crate::projection::{Projection, ProjectionError};
+use qrcode::{Color, EcLevel, QrCode};
+use std::path::{Path, PathBuf};
+
+/// A4 page size in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 56.0;
+
+/// Root CAs per page of the fingerprint sheet
+const FINGERPRINTS_PER_PAGE: usize = 4;
+
+/// Lowest baseline for body text, keeping clear of the page footer
+const BODY_BOTTOM: f32 = MARGIN + 24.0;
+
+/// Footer printed on every page of a share sheet
+const SHARE_FOOTER: &str = "SECRET - store apart from the other shares and from the SD card.";
+
+// ============================================================================
+// DOMAIN TYPES
+// ============================================================================
+
+/// Fingerprint of a root CA certificate, as recorded at the ceremony
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootCaFingerprint {
+    /// Name of the hierarchy or CA subject
+    pub name: String,
+    /// SHA-256 fingerprint of the certificate, hex
+    pub fingerprint: String,
+}
+
+impl RootCaFingerprint {
+    pub fn new(name: impl Into<String>, fingerprint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            fingerprint: fingerprint.into(),
+        }
+    }
+}
+
+/// Everything printed on the sheets
+#[derive(Debug, Clone)]
+pub struct PaperBackupInput {
+    pub organization: String,
+    /// All shares of one split of the master seed
+    pub shares: Vec<SecretShare>,
+    pub root_cas: Vec<RootCaFingerprint>,
+}
+
+/// One printable PDF
+#[derive(Debug, Clone)]
+pub struct PaperSheet {
+    pub file_name: String,
+    pub title: String,
+    /// Whether the sheet carries secret material
+    pub sensitive: bool,
+    pub pdf: Vec<u8>,
+}
+
+/// Rendered sheets ready for printing
+#[derive(Debug, Clone)]
+pub struct PaperBackupExport {
+    pub sheets: Vec<PaperSheet>,
+}
+
+impl PaperBackupExport {
+    /// Write every sheet to `dir`, returning the written paths
+    ///
+    /// Print them from an air-gapped machine and remove the share files
+    /// afterwards; the paper is the backup.
+    pub fn write_to(&self, dir: &Path) -> Result<Vec<PathBuf>, ProjectionError> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create {}: {}", dir.display(), e)))?;
+        self.sheets
+            .iter()
+            .map(|sheet| {
+                let path = dir.join(&sheet.file_name);
+                std::fs::write(&path, &sheet.pdf)
+                    .map_err(|e| ProjectionError::IoError(format!("Failed to write {}: {}", path.display(), e)))?;
+                Ok(path)
+            })
+            .collect()
+    }
+}
+
+// ============================================================================
+// PROJECTION
+// ============================================================================
+
+/// Projection: PaperBackupInput → PaperBackupExport
+#[derive(Debug, Clone, Default)]
+pub struct PaperBackupProjection;
+
+impl PaperBackupProjection {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn share_sheet(input: &PaperBackupInput, share: &SecretShare) -> Result<PaperSheet, ProjectionError> {
+        let count = input.shares.len();
+        let text = share.to_text();
+        let continued = format!("{} - Share {} of {} (continued)", input.organization, share.index, count);
+        let mut pages = Vec::new();
+        let mut page = PdfPage::default();
+        let mut y = PAGE_HEIGHT - MARGIN;
+
+        page.text(MARGIN, y, Font::Bold, 18.0, &format!("{} - Master Seed Recovery Share", input.organization));
+        y -= 24.0;
+        page.text(
+            MARGIN,
+            y,
+            Font::Regular,
+            12.0,
+            &format!(
+                "Share {} of {}. Any {} shares recover the master seed; fewer reveal nothing.",
+                share.index, count, share.threshold
+            ),
+        );
+        y -= 16.0;
+        page.text(MARGIN, y, Font::Regular, 10.0, &format!("Share set {:08x}", share.set_id));
+
+        // Share QR code with the text form beside it for manual entry
+        y -= 16.0;
+        let qr_size = 200.0;
+        page.qr(MARGIN, y - qr_size, qr_size, &text)?;
+        let mut line_y = y - 12.0;
+        for line in wrap(&text, 40) {
+            page.text(MARGIN + qr_size + 16.0, line_y, Font::Mono, 9.0, &line);
+            line_y -= 12.0;
+        }
+        y -= qr_size + 28.0;
+
+        page.text(MARGIN, y, Font::Bold, 13.0, "Root CA fingerprints (SHA-256)");
+        y -= 18.0;
+        for root_ca in &input.root_cas {
+            let lines = wrap(&colon_groups(&root_ca.fingerprint), 72);
+            break_page(&mut pages, &mut page, &mut y, 13.0 + 11.0 * lines.len() as f32, &continued);
+            page.text(MARGIN, y, Font::Regular, 10.0, &root_ca.name);
+            y -= 13.0;
+            for line in lines {
+                page.text(MARGIN + 12.0, y, Font::Mono, 8.0, &line);
+                y -= 11.0;
+            }
+            y -= 4.0;
+        }
+
+        y -= 10.0;
+        break_page(&mut pages, &mut page, &mut y, 18.0 + 2.0 * 13.0, &continued);
+        page.text(MARGIN, y, Font::Bold, 13.0, "Recovery");
+        y -= 18.0;
+        for (step, instruction) in recovery_steps(share.threshold).iter().enumerate() {
+            let lines = wrap(instruction, 88);
+            break_page(&mut pages, &mut page, &mut y, 13.0 * lines.len() as f32, &continued);
+            for (i, line) in lines.into_iter().enumerate() {
+                let prefix = if i == 0 { format!("{}. ", step + 1) } else { "   ".to_string() };
+                page.text(MARGIN, y, Font::Regular, 10.0, &format!("{}{}", prefix, line));
+                y -= 13.0;
+            }
+        }
+
+        pages.push(page);
+        let page_count = pages.len();
+        for (i, page) in pages.iter_mut().enumerate() {
+            page.text(MARGIN, MARGIN, Font::Bold, 10.0, SHARE_FOOTER);
+            if page_count > 1 {
+                page.text(PAGE_WIDTH - MARGIN - 48.0, MARGIN, Font::Regular, 10.0, &format!("{} / {}", i + 1, page_count));
+            }
+        }
+
+        Ok(PaperSheet {
+            file_name: format!("share-{}-of-{}.pdf", share.index, count),
+            title: format!("Recovery share {} of {}", share.index, count),
+            sensitive: true,
+            pdf: render_pdf(&pages),
+        })
+    }
+
+    fn fingerprint_sheet(input: &PaperBackupInput) -> Result<PaperSheet, ProjectionError> {
+        let mut pages = Vec::new();
+        for (page_index, root_cas) in input.root_cas.chunks(FINGERPRINTS_PER_PAGE).enumerate() {
+            let mut page = PdfPage::default();
+            let mut y = PAGE_HEIGHT - MARGIN;
+            page.text(MARGIN, y, Font::Bold, 18.0, &format!("{} - Root CA Fingerprints", input.organization));
+            y -= 18.0;
+            page.text(
+                MARGIN,
+                y,
+                Font::Regular,
+                10.0,
+                &format!("Page {}. Compare before trusting a recovered or received root CA.", page_index + 1),
+            );
+            y -= 24.0;
+
+            let qr_size = 120.0;
+            for root_ca in root_cas {
+                page.qr(MARGIN, y - qr_size, qr_size, &root_ca.fingerprint)?;
+                page.text(MARGIN + qr_size + 16.0, y - 14.0, Font::Bold, 12.0, &root_ca.name);
+                let mut line_y = y - 32.0;
+                for line in wrap(&colon_groups(&root_ca.fingerprint), 48) {
+                    page.text(MARGIN + qr_size + 16.0, line_y, Font::Mono, 9.0, &line);
+                    line_y -= 12.0;
+                }
+                y -= qr_size + 24.0;
+            }
+            pages.push(page);
+        }
+
+        Ok(PaperSheet {
+            file_name: "root-ca-fingerprints.pdf".to_string(),
+            title: "Root CA fingerprints".to_string(),
+            sensitive: false,
+            pdf: render_pdf(&pages),
+        })
+    }
+}
+
+impl Projection<PaperBackupInput, PaperBackupExport, ProjectionError> for PaperBackupProjection {
+    fn project(&self, input: PaperBackupInput) -> Result<PaperBackupExport, ProjectionError> {
+        let Some(first) = input.shares.first() else {
+            return Err(ProjectionError::ValidationFailed {
+                field: "shares".to_string(),
+                reason: "no shares to print".to_string(),
+            });
+        };
+        if input.shares.iter().any(|share| share.set_id != first.set_id) {
+            return Err(ProjectionError::ValidationFailed {
+                field: "shares".to_string(),
+                reason: "shares belong to different splits".to_string(),
+            });
+        }
+
+        let mut sheets = input
+            .shares
+            .iter()
+            .map(|share| Self::share_sheet(&input, share))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !input.root_cas.is_empty() {
+            sheets.push(Self::fingerprint_sheet(&input)?);
+        }
+        Ok(PaperBackupExport { sheets })
+    }
+
+    fn name(&self) -> &'static str {
+        "PaperBackup"
+    }
+}
+
+/// Create a paper backup projection
+pub fn paper_backup() -> PaperBackupProjection {
+    PaperBackupProjection::new()
+}
+
+/// Move on to a fresh page, headed by `heading`, unless `needed` points
+/// of body text still fit above the footer
+fn break_page(pages: &mut Vec<PdfPage>, page: &mut PdfPage, y: &mut f32, needed: f32, heading: &str) {
+    if *y - needed >= BODY_BOTTOM {
+        return;
+    }
+    pages.push(std::mem::take(page));
+    *y = PAGE_HEIGHT - MARGIN;
+    page.text(MARGIN, *y, Font::Bold, 13.0, heading);
+    *y -= 24.0;
+}
+
+fn recovery_steps(threshold: u8) -> Vec<String> {
+    vec![
+        format!("Collect at least {} share sheets of this share set from their custodians.", threshold),
+        "On an air-gapped machine, scan each share QR code or type its text exactly as printed; \
+         the check digits at the end reject mistyped shares."
+            .to_string(),
+        "Recover the master seed from the shares with cim-keys and regenerate the key hierarchy from it."
+            .to_string(),
+        "Compare the regenerated root CA fingerprints with the ones printed here before trusting them.".to_string(),
+        "Return the sheets to their custodians and destroy any digital copy of the shares.".to_string(),
+    ]
+}
+
+/// `AB:CD:EF…` form of a hex fingerprint, as tools print it
+fn colon_groups(hex: &str) -> String {
+    let hex: Vec<char> = hex.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    hex.chunks(2)
+        .map(|pair| pair.iter().collect::<String>().to_uppercase())
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Break `text` into lines of at most `width` characters, at spaces where
+/// possible
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ') {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+        while line.len() > width {
+            let rest = line.split_off(width);
+            lines.push(std::mem::replace(&mut line, rest));
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// ============================================================================
+// PDF RENDERING
+// ============================================================================
+
+#[derive(Debug, Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+    Mono,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Mono => "F3",
+        }
+    }
+}
+
+/// Content stream of one page
+#[derive(Debug, Default)]
+struct PdfPage {
+    content: String,
+}
+
+impl PdfPage {
+    fn text(&mut self, x: f32, y: f32, font: Font, size: f32, text: &str) {
+        let escaped: String = text
+            .chars()
+            .map(|c| match c {
+                '(' | ')' | '\\' => format!("\\{}", c),
+                ' '..='~' => c.to_string(),
+                _ => "?".to_string(),
+            })
+            .collect();
+        self.content.push_str(&format!(
+            "BT /{} {:.1} Tf {:.2} {:.2} Td ({}) Tj ET\n",
+            font.resource(),
+            size,
+            x,
+            y,
+            escaped
+        ));
+    }
+
+    /// Draw `data` as a QR code filling the square at (`x`, `y`) with
+    /// side `size`, including the quiet zone
+    fn qr(&mut self, x: f32, y: f32, size: f32, data: &str) -> Result<(), ProjectionError> {
+        let code = QrCode::with_error_correction_level(data, EcLevel::M).map_err(|e| ProjectionError::ProcessFailed {
+            step: "qr".to_string(),
+            reason: e.to_string(),
+        })?;
+        let width = code.width();
+        let module = size / (width + 8) as f32;
+        let top = y + size - 4.0 * module;
+
+        self.content.push_str("0 g\n");
+        for (i, color) in code.to_colors().into_iter().enumerate() {
+            if color == Color::Dark {
+                let (row, column) = (i / width, i % width);
+                self.content.push_str(&format!(
+                    "{:.2} {:.2} {:.2} {:.2} re\n",
+                    x + (4 + column) as f32 * module,
+                    top - (row + 1) as f32 * module,
+                    module,
+                    module
+                ));
+            }
+        }
+        self.content.push_str("f\n");
+        Ok(())
+    }
+}
+
+/// Assemble a PDF 1.4 document from page content streams
+fn render_pdf(pages: &[PdfPage]) -> Vec<u8> {
+    // Objects 1-5 are fixed; each page adds a page and a content object
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| 6 + 2 * i).collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+    for (page, id) in pages.iter().zip(&page_ids) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R /F3 5 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            id + 1
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", page.content.len(), page.content));
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!("trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n", objects.len() + 1, xref).as_bytes(),
+    );
+    pdf
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::MasterSeed;
+
+    fn input() -> (MasterSeed, PaperBackupInput) {
+        let seed = MasterSeed::from_bytes([7u8; 32]);
+        let input = PaperBackupInput {
+            organization: "Cowboy AI".to_string(),
+            shares: seed.split(2, 3).unwrap(),
+            root_cas: vec![RootCaFingerprint::new("Cowboy AI Root CA", "ab".repeat(32))],
+        };
+        (seed, input)
+    }
+
+    #[test]
+    fn test_one_sheet_per_share_plus_fingerprints() {
+        let (_, input) = input();
+        let export = paper_backup().project(input).unwrap();
+
+        let names: Vec<_> = export.sheets.iter().map(|sheet| sheet.file_name.as_str()).collect();
+        assert_eq!(names, ["share-1-of-3.pdf", "share-2-of-3.pdf", "share-3-of-3.pdf", "root-ca-fingerprints.pdf"]);
+        assert!(export.sheets[..3].iter().all(|sheet| sheet.sensitive));
+        assert!(!export.sheets[3].sensitive);
+
+        for sheet in &export.sheets {
+            assert!(sheet.pdf.starts_with(b"%PDF-1.4\n"));
+            assert!(sheet.pdf.ends_with(b"%%EOF\n"));
+        }
+        let share_pdf = String::from_utf8_lossy(&export.sheets[0].pdf);
+        assert!(share_pdf.contains("Share 1 of 3. Any 2 shares recover the master seed"));
+        assert!(share_pdf.contains("AB:AB:AB"));
+    }
+
+    #[test]
+    fn test_printed_share_text_recovers_the_seed() {
+        let (seed, input) = input();
+        let texts: Vec<String> = input.shares.iter().map(SecretShare::to_text).collect();
+        let export = paper_backup().project(input).unwrap();
+
+        // The text form is printed, wrapped, next to the QR code
+        let pdf = String::from_utf8_lossy(&export.sheets[2].pdf);
+        assert!(wrap(&texts[2], 40).iter().all(|line| pdf.contains(line.as_str())));
+
+        let scanned = [SecretShare::from_text(&texts[2]).unwrap(), SecretShare::from_text(&texts[0]).unwrap()];
+        assert_eq!(MasterSeed::from_shares(&scanned).unwrap().as_bytes(), seed.as_bytes());
+    }
+
+    #[test]
+    fn test_many_root_cas_continue_on_further_pages() {
+        let (_, mut input) = input();
+        input.root_cas = (0..20)
+            .map(|i| RootCaFingerprint::new(format!("Root CA {}", i), format!("{:02x}", i).repeat(32)))
+            .collect();
+        let export = paper_backup().project(input).unwrap();
+        let pdf = String::from_utf8_lossy(&export.sheets[0].pdf).to_string();
+
+        let pages = pdf.matches("/Type /Page ").count();
+        assert!(pages > 1);
+        assert_eq!(pdf.matches(SHARE_FOOTER).count(), pages);
+        assert!(pdf.contains("Root CA 19") && pdf.contains("(continued)"));
+
+        // Only the footer sits below the body area
+        for line in pdf.lines().filter(|line| line.contains(" Td (")) {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let td = tokens.iter().position(|token| *token == "Td").unwrap();
+            let y: f32 = tokens[td - 1].parse().unwrap();
+            assert!(y >= BODY_BOTTOM || y == MARGIN, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_xref_offsets_point_at_objects() {
+        let (_, input) = input();
+        let pdf = &paper_backup().project(input).unwrap().sheets[0].pdf;
+        let text = String::from_utf8_lossy(pdf);
+        let xref = text.rfind("xref\n").unwrap();
+
+        for (i, line) in text[xref..].lines().skip(3).take_while(|line| line.ends_with(" n ")).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(text[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+}
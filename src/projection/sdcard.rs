@@ -16,6 +16,26 @@
 //! StoragePort (encryption, write)
 //! ```
 //!
+//! Ceremonies write a primary and a backup card in one pass with
+//! [`redundant_sdcard_export_pipeline`], which reads both back against the
+//! file checksums and records the outcome as a `RedundantExportCompleted`
+//! manifest event.
+//!
+//! Not every medium should carry everything: an [`ExportProfile`] names the
+//! artifact classes an export may contain (`root-ceremony`,
+//! `operator-workstation`, `developer-bundle`, or the default `full`).
+//! Classes outside the profile are left off the card, and the profile is
+//! recorded in the export metadata and `manifest.json`.
+//!
+//! Sensitive files need not rely on the card being an encrypted partition:
+//! with [`ManifestToExportProjection::with_encryption`] they are written as
+//! armored `.age` files that any of the listed people can open, and
+//! [`RecoveryBundle`]s give single people files only they can open (by
+//! their own key or a passphrase), such as their seed share. Sensitive
+//! files can instead be sealed to key-agreement keys with
+//! [`ManifestToExportProjection::with_envelope_encryption`], written as
+//! `{path}.envelope.json`.
+//!
 //! ## Directory Structure on SD Card
 //!
 //! ```text
@@ -32,27 +52,65 @@
 //! ├── certificates/
 //! │   ├── root-ca/
 //! │   ├── intermediate-ca/
-//! │   └── leaf/
+//! │   ├── leaf/
+//! │   └── crl/{issuer-ca-id}.crl.pem  # Latest CRL per issuing CA
 //! ├── nats/
 //! │   ├── operator/
 //! │   ├── accounts/
-//! │   └── users/
+//! │   ├── users/
+//! │   ├── subjects.{md,json}  # JetStream subject schema documentation
+//! │   └── server/             # Optional, see nats_resolver projection
+//! │       ├── resolver.conf
+//! │       └── operator.jwt
+//! ├── ssh/                    # Optional, see ssh_access/ssh_config projections
+//! │   ├── known_hosts
+//! │   ├── hosts/{hostname}/authorized_keys
+//! │   └── people/{person-id}/config
+//! ├── kubernetes/             # Optional, see kubernetes projection
+//! │   ├── kustomization.yaml
+//! │   └── {namespace}/{kind}-{name}.yaml
+//! ├── nix/                    # Optional, see nix_secrets projection
+//! │   ├── secrets.nix
+//! │   ├── .sops.yaml
+//! │   ├── secrets/{hosts,locations}/{name}/*.age
+//! │   └── hosts/{host}/secrets.nix
+//! ├── terraform/              # Optional, see terraform projection
+//! │   ├── cim-keys.auto.tfvars.json
+//! │   └── modules/{module}/*.tf
+//! ├── recovery/               # Optional, see RecoveryBundle
+//! │   └── {person-id}/{name}.age
 //! └── events/
 //!     └── {date}/             # Daily event logs
 //! ```
 
+use crate::crypto::age_encryption::{encrypt_armored, AgeProtection, PersonRecipient, AGE_FILE_SUFFIX};
+use crate::crypto::envelope::Encryptor;
+use crate::domain::ids::KeyId;
+use crate::events::manifest::{ExportMediaRecord, ManifestEvents, RedundantExportCompletedEvent};
+use crate::projection::ssh_access::SshAccessExport;
+use crate::projection::ssh_config::SshConfigExport;
+use crate::projection::kubernetes::KubernetesExport;
+use crate::projection::nix_secrets::NixSecretsExport;
+use crate::projection::terraform::TerraformExport;
+use crate::projection::nats_resolver::NatsResolverExport;
+use crate::projection::jetstream::SubjectConfig;
+use crate::projection::subject_schema::SubjectRegistry;
 use crate::projection::{Projection, ProjectionError};
 use crate::projections::KeyManifest;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::path::PathBuf;
+use std::sync::Arc;
 use uuid::Uuid;
 
 // ============================================================================
 // EXPORT TYPES
 // ============================================================================
 
+/// File name suffix of files sealed with envelope encryption
+const ENVELOPE_FILE_SUFFIX: &str = ".envelope.json";
+
 /// Complete SD card export package
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SDCardExport {
@@ -74,6 +132,15 @@ pub struct ExportMetadata {
     pub source: String,
     pub version: String,
     pub checksum: String,
+    /// Profile the export was restricted to
+    #[serde(default)]
+    pub profile: ExportProfile,
+    /// People sensitive files are age-encrypted to, empty if stored as plaintext
+    #[serde(default)]
+    pub encrypted_to: Vec<Uuid>,
+    /// Key-agreement keys sensitive files are sealed to (envelope encryption)
+    #[serde(default)]
+    pub sealed_for: Vec<KeyId>,
 }
 
 /// A file to be written to the SD card
@@ -89,6 +156,19 @@ pub struct ExportFile {
     pub checksum: String,
 }
 
+impl ExportFile {
+    /// Create an export file, computing its checksum
+    pub fn new(path: impl Into<PathBuf>, content: String, sensitive: bool) -> Self {
+        let checksum = ManifestToExportProjection::calculate_checksum(&content);
+        Self {
+            path: path.into(),
+            content,
+            sensitive,
+            checksum,
+        }
+    }
+}
+
 /// Summary of what's being exported
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ExportSummary {
@@ -100,10 +180,214 @@ pub struct ExportSummary {
     pub nats_operator_count: usize,
     pub nats_account_count: usize,
     pub nats_user_count: usize,
+    #[serde(default)]
+    pub ssh_host_count: usize,
+    #[serde(default)]
+    pub crl_count: usize,
+    #[serde(default)]
+    pub recovery_bundle_count: usize,
     pub total_files: usize,
     pub total_bytes: usize,
 }
 
+// ============================================================================
+// EXPORT PROFILES
+// ============================================================================
+
+/// A class of artifacts an export can contain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactClass {
+    /// `domain/`: organization, people, locations
+    Domain,
+    /// `keys/{key-id}/metadata.json`
+    KeyMetadata,
+    /// `certificates/{root-ca,intermediate-ca,leaf}/`
+    Certificates,
+    /// `certificates/crl/`
+    Crls,
+    /// `nats/{operator,accounts,users}/`
+    NatsCredentials,
+    /// `nats/subjects.{md,json}`
+    NatsSubjects,
+    /// `nats/server/`
+    NatsResolver,
+    SshAccess,
+    SshConfig,
+    Kubernetes,
+    NixSecrets,
+    Terraform,
+    /// `recovery/{person-id}/`
+    RecoveryBundles,
+}
+
+impl ArtifactClass {
+    pub const ALL: [ArtifactClass; 13] = [
+        ArtifactClass::Domain,
+        ArtifactClass::KeyMetadata,
+        ArtifactClass::Certificates,
+        ArtifactClass::Crls,
+        ArtifactClass::NatsCredentials,
+        ArtifactClass::NatsSubjects,
+        ArtifactClass::NatsResolver,
+        ArtifactClass::SshAccess,
+        ArtifactClass::SshConfig,
+        ArtifactClass::Kubernetes,
+        ArtifactClass::NixSecrets,
+        ArtifactClass::Terraform,
+        ArtifactClass::RecoveryBundles,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArtifactClass::Domain => "domain",
+            ArtifactClass::KeyMetadata => "key-metadata",
+            ArtifactClass::Certificates => "certificates",
+            ArtifactClass::Crls => "crls",
+            ArtifactClass::NatsCredentials => "nats-credentials",
+            ArtifactClass::NatsSubjects => "nats-subjects",
+            ArtifactClass::NatsResolver => "nats-resolver",
+            ArtifactClass::SshAccess => "ssh-access",
+            ArtifactClass::SshConfig => "ssh-config",
+            ArtifactClass::Kubernetes => "kubernetes",
+            ArtifactClass::NixSecrets => "nix-secrets",
+            ArtifactClass::Terraform => "terraform",
+            ArtifactClass::RecoveryBundles => "recovery-bundles",
+        }
+    }
+}
+
+impl std::fmt::Display for ArtifactClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Named set of artifact classes allowed on one kind of medium
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportProfile {
+    pub name: String,
+    pub artifacts: BTreeSet<ArtifactClass>,
+}
+
+impl Default for ExportProfile {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+impl ExportProfile {
+    pub fn new(name: impl Into<String>, artifacts: impl IntoIterator<Item = ArtifactClass>) -> Self {
+        Self {
+            name: name.into(),
+            artifacts: artifacts.into_iter().collect(),
+        }
+    }
+
+    /// Everything, as exports were before profiles existed
+    pub fn full() -> Self {
+        Self::new("full", ArtifactClass::ALL)
+    }
+
+    /// The card kept in the safe after the key ceremony: key and NATS
+    /// material and recovery bundles, no deployment artifacts
+    pub fn root_ceremony() -> Self {
+        use ArtifactClass::*;
+        Self::new(
+            "root-ceremony",
+            [Domain, KeyMetadata, Certificates, Crls, NatsCredentials, NatsSubjects, RecoveryBundles],
+        )
+    }
+
+    /// An operator's machine: credentials and deployment artifacts, no key
+    /// metadata
+    pub fn operator_workstation() -> Self {
+        use ArtifactClass::*;
+        Self::new(
+            "operator-workstation",
+            [
+                Domain, Certificates, Crls, NatsCredentials, NatsSubjects, NatsResolver, SshAccess, SshConfig,
+                Kubernetes, NixSecrets, Terraform,
+            ],
+        )
+    }
+
+    /// What developers need to talk to the infrastructure: public trust
+    /// material and documentation only
+    pub fn developer_bundle() -> Self {
+        use ArtifactClass::*;
+        Self::new("developer-bundle", [Domain, Certificates, Crls, NatsSubjects, SshConfig, Terraform])
+    }
+
+    /// Look up a built-in profile by name
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "full" => Some(Self::full()),
+            "root-ceremony" => Some(Self::root_ceremony()),
+            "operator-workstation" => Some(Self::operator_workstation()),
+            "developer-bundle" => Some(Self::developer_bundle()),
+            _ => None,
+        }
+    }
+
+    pub fn includes(&self, class: ArtifactClass) -> bool {
+        self.artifacts.contains(&class)
+    }
+}
+
+// ============================================================================
+// RECOVERY BUNDLES
+// ============================================================================
+
+/// Files for one person, encrypted so that only they can open them
+///
+/// Written to `recovery/{person-id}/{name}.age`, e.g. a backup holder's
+/// seed share encrypted to their passphrase.
+#[derive(Debug, Clone)]
+pub struct RecoveryBundle {
+    pub person_id: Uuid,
+    pub protection: AgeProtection,
+    /// File name -> plaintext content
+    pub files: BTreeMap<String, String>,
+}
+
+impl RecoveryBundle {
+    pub fn new(person_id: Uuid, protection: AgeProtection) -> Self {
+        Self {
+            person_id,
+            protection,
+            files: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_file(mut self, name: impl Into<String>, content: impl Into<String>) -> Self {
+        self.files.insert(name.into(), content.into());
+        self
+    }
+
+    fn encrypt(&self) -> Result<Vec<ExportFile>, ProjectionError> {
+        let dir = PathBuf::from(format!("recovery/{}", self.person_id));
+        self.files
+            .iter()
+            .map(|(name, content)| {
+                if name.is_empty() || name.contains('/') {
+                    return Err(ProjectionError::ValidationFailed {
+                        field: "recovery_bundle.files".to_string(),
+                        reason: format!("{:?} is not a file name", name),
+                    });
+                }
+                let armored = encrypt_armored(content.as_bytes(), &self.protection).map_err(|e| {
+                    ProjectionError::ExternalError {
+                        system: "age".to_string(),
+                        error: format!("{} (recovery bundle of {})", e, self.person_id),
+                    }
+                })?;
+                Ok(ExportFile::new(dir.join(format!("{}{}", name, AGE_FILE_SUFFIX)), armored, true))
+            })
+            .collect()
+    }
+}
+
 // ============================================================================
 // PROJECTIONS
 // ============================================================================
@@ -116,6 +400,18 @@ pub struct ManifestToExportProjection {
     include_public_keys: bool,
     include_certificates: bool,
     include_nats_config: bool,
+    ssh_access: Option<SshAccessExport>,
+    ssh_config: Option<SshConfigExport>,
+    kubernetes: Option<KubernetesExport>,
+    nix_secrets: Option<NixSecretsExport>,
+    terraform: Option<TerraformExport>,
+    nats_resolver: Option<NatsResolverExport>,
+    subject_config: SubjectConfig,
+    subject_registry: SubjectRegistry,
+    profile: ExportProfile,
+    encrypt_to: Vec<PersonRecipient>,
+    envelope: Option<(Arc<dyn Encryptor>, Vec<KeyId>)>,
+    recovery_bundles: Vec<RecoveryBundle>,
 }
 
 impl Default for ManifestToExportProjection {
@@ -124,6 +420,18 @@ impl Default for ManifestToExportProjection {
             include_public_keys: true,
             include_certificates: true,
             include_nats_config: true,
+            ssh_access: None,
+            ssh_config: None,
+            kubernetes: None,
+            nix_secrets: None,
+            terraform: None,
+            nats_resolver: None,
+            subject_config: SubjectConfig::default(),
+            subject_registry: SubjectRegistry::domain_events(),
+            profile: ExportProfile::full(),
+            encrypt_to: Vec::new(),
+            envelope: None,
+            recovery_bundles: Vec::new(),
         }
     }
 }
@@ -148,6 +456,156 @@ impl ManifestToExportProjection {
         self
     }
 
+    /// Include rendered authorized_keys/known_hosts (see `SshAccessProjection`)
+    pub fn with_ssh_access(mut self, ssh_access: SshAccessExport) -> Self {
+        self.ssh_access = Some(ssh_access);
+        self
+    }
+
+    /// Include per-person ~/.ssh/config bundles (see `SshConfigProjection`)
+    pub fn with_ssh_config(mut self, ssh_config: SshConfigExport) -> Self {
+        self.ssh_config = Some(ssh_config);
+        self
+    }
+
+    /// Include Kubernetes Secret/ConfigMap manifests (see `KubernetesProjection`)
+    pub fn with_kubernetes(mut self, kubernetes: KubernetesExport) -> Self {
+        self.kubernetes = Some(kubernetes);
+        self
+    }
+
+    /// Include age-encrypted NixOS secrets and their wiring (see `NixSecretsProjection`)
+    pub fn with_nix_secrets(mut self, nix_secrets: NixSecretsExport) -> Self {
+        self.nix_secrets = Some(nix_secrets);
+        self
+    }
+
+    /// Include Terraform/OpenTofu variables and module skeleton (see `TerraformProjection`)
+    pub fn with_terraform(mut self, terraform: TerraformExport) -> Self {
+        self.terraform = Some(terraform);
+        self
+    }
+
+    /// Include the NATS server resolver configuration (see `NatsResolverProjection`)
+    pub fn with_nats_resolver(mut self, nats_resolver: NatsResolverExport) -> Self {
+        self.nats_resolver = Some(nats_resolver);
+        self
+    }
+
+    /// Document JetStream subjects for `config` (see `SubjectRegistry`)
+    pub fn with_subjects(mut self, config: SubjectConfig, registry: SubjectRegistry) -> Self {
+        self.subject_config = config;
+        self.subject_registry = registry;
+        self
+    }
+
+    /// Restrict the export to the artifact classes of `profile`
+    ///
+    /// Manifest content outside the profile is left out; attaching an
+    /// optional export the profile excludes fails the projection.
+    pub fn with_profile(mut self, profile: ExportProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Encrypt every sensitive file to `people`
+    ///
+    /// Sensitive files are written as `{path}.age`, armored, so that any one
+    /// of the people can open them with their age identity.
+    pub fn with_encryption(mut self, people: Vec<PersonRecipient>) -> Self {
+        self.encrypt_to = people;
+        self
+    }
+
+    /// Seal every sensitive file to the key-agreement keys `recipients`
+    ///
+    /// Sensitive files are written as `{path}.envelope.json` (see
+    /// `crypto::envelope`). Cannot be combined with [`Self::with_encryption`].
+    pub fn with_envelope_encryption(mut self, encryptor: Arc<dyn Encryptor>, recipients: Vec<KeyId>) -> Self {
+        self.envelope = Some((encryptor, recipients));
+        self
+    }
+
+    /// Add a recovery bundle for one person (see [`RecoveryBundle`])
+    pub fn with_recovery_bundle(mut self, bundle: RecoveryBundle) -> Self {
+        self.recovery_bundles.push(bundle);
+        self
+    }
+
+    /// Whether `class` goes on the card, by profile and `with_*` flags
+    fn includes(&self, class: ArtifactClass) -> bool {
+        let enabled = match class {
+            ArtifactClass::Certificates | ArtifactClass::Crls => self.include_certificates,
+            ArtifactClass::NatsCredentials | ArtifactClass::NatsSubjects => self.include_nats_config,
+            _ => true,
+        };
+        enabled && self.profile.includes(class)
+    }
+
+    /// Reject attached exports the profile does not allow
+    fn check_attached(&self) -> Result<(), ProjectionError> {
+        let attached = [
+            (ArtifactClass::SshAccess, self.ssh_access.is_some()),
+            (ArtifactClass::SshConfig, self.ssh_config.is_some()),
+            (ArtifactClass::Kubernetes, self.kubernetes.is_some()),
+            (ArtifactClass::NixSecrets, self.nix_secrets.is_some()),
+            (ArtifactClass::Terraform, self.terraform.is_some()),
+            (ArtifactClass::NatsResolver, self.nats_resolver.is_some()),
+            (ArtifactClass::RecoveryBundles, !self.recovery_bundles.is_empty()),
+        ];
+        if !self.encrypt_to.is_empty() && self.envelope.is_some() {
+            return Err(ProjectionError::ValidationFailed {
+                field: "encryption".to_string(),
+                reason: "sensitive files are either age-encrypted or sealed in envelopes, not both".to_string(),
+            });
+        }
+        match attached.iter().find(|(class, present)| *present && !self.profile.includes(*class)) {
+            Some((class, _)) => Err(ProjectionError::ValidationFailed {
+                field: "profile".to_string(),
+                reason: format!("{} exports do not include {}", self.profile.name, class),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Replace sensitive files with their age-encrypted or sealed form
+    fn encrypt_sensitive(&self, files: Vec<ExportFile>) -> Result<Vec<ExportFile>, ProjectionError> {
+        if self.encrypt_to.is_empty() && self.envelope.is_none() {
+            return Ok(files);
+        }
+        let protection = AgeProtection::to_people(&self.encrypt_to);
+        files
+            .into_iter()
+            .map(|file| {
+                if !file.sensitive {
+                    return Ok(file);
+                }
+                let failed = |system: &str, e: &dyn std::fmt::Display| ProjectionError::ExternalError {
+                    system: system.to_string(),
+                    error: format!("{} ({})", e, file.path.display()),
+                };
+                let (suffix, content) = match &self.envelope {
+                    Some((encryptor, recipients)) => {
+                        let envelope = encryptor
+                            .encrypt(file.content.as_bytes(), recipients)
+                            .map_err(|e| failed("envelope", &e))?;
+                        let json = serde_json::to_string_pretty(&envelope)
+                            .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
+                        (ENVELOPE_FILE_SUFFIX, json)
+                    }
+                    None => {
+                        let armored =
+                            encrypt_armored(file.content.as_bytes(), &protection).map_err(|e| failed("age", &e))?;
+                        (AGE_FILE_SUFFIX, armored)
+                    }
+                };
+                let mut path = file.path.into_os_string();
+                path.push(suffix);
+                Ok(Self::create_file(path, content, true))
+            })
+            .collect()
+    }
+
     /// Calculate SHA-256 checksum of content
     fn calculate_checksum(content: &str) -> String {
         use sha2::{Sha256, Digest};
@@ -158,18 +616,21 @@ impl ManifestToExportProjection {
 
     /// Create an export file with checksum
     fn create_file(path: impl Into<PathBuf>, content: String, sensitive: bool) -> ExportFile {
-        let checksum = Self::calculate_checksum(&content);
-        ExportFile {
-            path: path.into(),
-            content,
-            sensitive,
-            checksum,
-        }
+        ExportFile::new(path, content, sensitive)
+    }
+
+    fn encrypted_to(&self) -> Vec<Uuid> {
+        self.encrypt_to.iter().map(|p| p.person_id).collect()
+    }
+
+    fn sealed_for(&self) -> Vec<KeyId> {
+        self.envelope.as_ref().map(|(_, recipients)| recipients.clone()).unwrap_or_default()
     }
 }
 
 impl Projection<KeyManifest, SDCardExport, ProjectionError> for ManifestToExportProjection {
     fn project(&self, manifest: KeyManifest) -> Result<SDCardExport, ProjectionError> {
+        self.check_attached()?;
         let export_id = Uuid::now_v7();
         let created_at = Utc::now();
         let mut files = Vec::new();
@@ -177,53 +638,72 @@ impl Projection<KeyManifest, SDCardExport, ProjectionError> for ManifestToExport
         let mut total_bytes = 0usize;
 
         // Create directory structure
-        directories.push(PathBuf::from("domain"));
-        directories.push(PathBuf::from("keys"));
-        directories.push(PathBuf::from("certificates"));
-        directories.push(PathBuf::from("certificates/root-ca"));
-        directories.push(PathBuf::from("certificates/intermediate-ca"));
-        directories.push(PathBuf::from("certificates/leaf"));
-        directories.push(PathBuf::from("nats"));
-        directories.push(PathBuf::from("nats/operator"));
-        directories.push(PathBuf::from("nats/accounts"));
-        directories.push(PathBuf::from("nats/users"));
+        if self.includes(ArtifactClass::Domain) {
+            directories.push(PathBuf::from("domain"));
+        }
+        if self.includes(ArtifactClass::KeyMetadata) {
+            directories.push(PathBuf::from("keys"));
+        }
+        if self.includes(ArtifactClass::Certificates) || self.includes(ArtifactClass::Crls) {
+            directories.push(PathBuf::from("certificates"));
+        }
+        if self.includes(ArtifactClass::Certificates) {
+            directories.push(PathBuf::from("certificates/root-ca"));
+            directories.push(PathBuf::from("certificates/intermediate-ca"));
+            directories.push(PathBuf::from("certificates/leaf"));
+        }
+        if self.includes(ArtifactClass::Crls) {
+            directories.push(PathBuf::from("certificates/crl"));
+        }
+        if self.includes(ArtifactClass::NatsCredentials) || self.includes(ArtifactClass::NatsSubjects) {
+            directories.push(PathBuf::from("nats"));
+        }
+        if self.includes(ArtifactClass::NatsCredentials) {
+            directories.push(PathBuf::from("nats/operator"));
+            directories.push(PathBuf::from("nats/accounts"));
+            directories.push(PathBuf::from("nats/users"));
+        }
         directories.push(PathBuf::from("events"));
 
-        // Export organization info
-        let org_content = serde_json::to_string_pretty(&manifest.organization)
-            .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
-        total_bytes += org_content.len();
-        files.push(Self::create_file("domain/organization.json", org_content, false));
+        if self.includes(ArtifactClass::Domain) {
+            // Export organization info
+            let org_content = serde_json::to_string_pretty(&manifest.organization)
+                .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
+            total_bytes += org_content.len();
+            files.push(Self::create_file("domain/organization.json", org_content, false));
 
-        // Export people
-        let people_content = serde_json::to_string_pretty(&manifest.people)
-            .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
-        total_bytes += people_content.len();
-        files.push(Self::create_file("domain/people.json", people_content, false));
+            // Export people
+            let people_content = serde_json::to_string_pretty(&manifest.people)
+                .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
+            total_bytes += people_content.len();
+            files.push(Self::create_file("domain/people.json", people_content, false));
 
-        // Export locations
-        let locations_content = serde_json::to_string_pretty(&manifest.locations)
-            .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
-        total_bytes += locations_content.len();
-        files.push(Self::create_file("domain/locations.json", locations_content, false));
+            // Export locations
+            let locations_content = serde_json::to_string_pretty(&manifest.locations)
+                .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
+            total_bytes += locations_content.len();
+            files.push(Self::create_file("domain/locations.json", locations_content, false));
+        }
 
         // Export keys
-        for key in &manifest.keys {
-            let key_dir = PathBuf::from(format!("keys/{}", key.key_id));
-            directories.push(key_dir.clone());
+        if self.includes(ArtifactClass::KeyMetadata) {
+            for key in &manifest.keys {
+                let key_dir = PathBuf::from(format!("keys/{}", key.key_id));
+                directories.push(key_dir.clone());
 
-            let key_content = serde_json::to_string_pretty(&key)
-                .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
-            total_bytes += key_content.len();
-            files.push(Self::create_file(
-                key_dir.join("metadata.json"),
-                key_content,
-                true, // Key metadata is sensitive
-            ));
+                let key_content = serde_json::to_string_pretty(&key)
+                    .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
+                total_bytes += key_content.len();
+                files.push(Self::create_file(
+                    key_dir.join("metadata.json"),
+                    key_content,
+                    true, // Key metadata is sensitive
+                ));
+            }
         }
 
         // Export certificates
-        if self.include_certificates {
+        if self.includes(ArtifactClass::Certificates) {
             for cert in &manifest.certificates {
                 let cert_dir = if cert.is_ca {
                     if cert.issuer.is_none() {
@@ -246,8 +726,20 @@ impl Projection<KeyManifest, SDCardExport, ProjectionError> for ManifestToExport
             }
         }
 
+        // CRLs are public revocation data for relying parties
+        if self.includes(ArtifactClass::Crls) {
+            for crl in &manifest.crls {
+                total_bytes += crl.crl_pem.len();
+                files.push(Self::create_file(
+                    format!("certificates/crl/{}.crl.pem", crl.issuer_ca_id),
+                    crl.crl_pem.clone(),
+                    false,
+                ));
+            }
+        }
+
         // Export NATS config
-        if self.include_nats_config {
+        if self.includes(ArtifactClass::NatsCredentials) {
             // Operators
             for operator in &manifest.nats_operators {
                 let op_content = serde_json::to_string_pretty(&operator)
@@ -285,6 +777,81 @@ impl Projection<KeyManifest, SDCardExport, ProjectionError> for ManifestToExport
             }
         }
 
+        // Subject documentation keeps downstream consumers aligned
+        if self.includes(ArtifactClass::NatsSubjects) {
+            let subjects = self.subject_registry.document(&self.subject_config);
+            let subject_files = [
+                ("nats/subjects.md", subjects.to_markdown()),
+                ("nats/subjects.json", subjects.to_json()?),
+            ];
+            for (path, content) in subject_files {
+                total_bytes += content.len();
+                files.push(Self::create_file(path, content, false));
+            }
+        }
+
+        // Export SSH access files
+        let mut ssh_host_count = 0;
+        if let Some(ssh_access) = &self.ssh_access {
+            directories.extend(ssh_access.directories.iter().cloned());
+            for file in &ssh_access.files {
+                total_bytes += file.content.len();
+                files.push(file.clone());
+            }
+            ssh_host_count = ssh_access.host_count;
+        }
+        if let Some(ssh_config) = &self.ssh_config {
+            directories.extend(ssh_config.directories.iter().cloned());
+            for file in &ssh_config.files {
+                total_bytes += file.content.len();
+                files.push(file.clone());
+            }
+        }
+        if let Some(kubernetes) = &self.kubernetes {
+            directories.extend(kubernetes.directories.iter().cloned());
+            for file in &kubernetes.files {
+                total_bytes += file.content.len();
+                files.push(file.clone());
+            }
+        }
+        if let Some(nix_secrets) = &self.nix_secrets {
+            directories.extend(nix_secrets.directories.iter().cloned());
+            for file in &nix_secrets.files {
+                total_bytes += file.content.len();
+                files.push(file.clone());
+            }
+        }
+        if let Some(terraform) = &self.terraform {
+            directories.extend(terraform.directories.iter().cloned());
+            for file in &terraform.files {
+                total_bytes += file.content.len();
+                files.push(file.clone());
+            }
+        }
+        if let Some(nats_resolver) = &self.nats_resolver {
+            directories.extend(nats_resolver.directories.iter().cloned());
+            for file in &nats_resolver.files {
+                total_bytes += file.content.len();
+                files.push(file.clone());
+            }
+        }
+
+        // Encrypt before adding recovery bundles, which are encrypted per person
+        let mut files = self.encrypt_sensitive(files)?;
+        if !self.encrypt_to.is_empty() || self.envelope.is_some() {
+            total_bytes = files.iter().map(|f| f.content.len()).sum();
+        }
+        if !self.recovery_bundles.is_empty() {
+            directories.push(PathBuf::from("recovery"));
+        }
+        for bundle in &self.recovery_bundles {
+            directories.push(PathBuf::from(format!("recovery/{}", bundle.person_id)));
+            for file in bundle.encrypt()? {
+                total_bytes += file.content.len();
+                files.push(file);
+            }
+        }
+
         // Build summary
         let summary = ExportSummary {
             organization_name: manifest.organization.name.clone(),
@@ -295,6 +862,9 @@ impl Projection<KeyManifest, SDCardExport, ProjectionError> for ManifestToExport
             nats_operator_count: manifest.nats_operators.len(),
             nats_account_count: manifest.nats_accounts.len(),
             nats_user_count: manifest.nats_users.len(),
+            ssh_host_count,
+            crl_count: if self.includes(ArtifactClass::Crls) { manifest.crls.len() } else { 0 },
+            recovery_bundle_count: self.recovery_bundles.len(),
             total_files: files.len(),
             total_bytes,
         };
@@ -309,6 +879,9 @@ impl Projection<KeyManifest, SDCardExport, ProjectionError> for ManifestToExport
                 .map(|f| (f.path.display().to_string(), f.checksum.clone()))
                 .collect(),
             summary: summary.clone(),
+            profile: self.profile.clone(),
+            encrypted_to: self.encrypted_to(),
+            sealed_for: self.sealed_for(),
         };
 
         let manifest_content = serde_json::to_string_pretty(&manifest_export)
@@ -323,6 +896,9 @@ impl Projection<KeyManifest, SDCardExport, ProjectionError> for ManifestToExport
             source: "cim-keys".to_string(),
             version: manifest.version,
             checksum: manifest_checksum,
+            profile: self.profile.clone(),
+            encrypted_to: self.encrypted_to(),
+            sealed_for: self.sealed_for(),
         };
 
         Ok(SDCardExport {
@@ -347,6 +923,11 @@ struct ManifestExport {
     organization: String,
     file_checksums: HashMap<String, String>,
     summary: ExportSummary,
+    profile: ExportProfile,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    encrypted_to: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sealed_for: Vec<KeyId>,
 }
 
 // ============================================================================
@@ -443,6 +1024,134 @@ impl Projection<SDCardExport, WriteResult, ProjectionError> for ExportToFilesyst
     }
 }
 
+// ============================================================================
+// REDUNDANT WRITE PROJECTION
+// ============================================================================
+
+/// A medium an export is written to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportDestination {
+    /// Identifier recorded for the medium (LUKS UUID, device serial or label)
+    pub media_id: String,
+    pub base_path: PathBuf,
+}
+
+impl ExportDestination {
+    pub fn new(media_id: impl Into<String>, base_path: impl Into<PathBuf>) -> Self {
+        Self {
+            media_id: media_id.into(),
+            base_path: base_path.into(),
+        }
+    }
+}
+
+/// Projection: SDCardExport → RedundantWriteResult
+///
+/// Writes the same export to a primary and a backup medium, then reads
+/// every file back from both and compares it with its checksum.
+pub struct RedundantExportProjection {
+    primary: ExportDestination,
+    backup: ExportDestination,
+    correlation_id: Uuid,
+    causation_id: Option<Uuid>,
+}
+
+impl RedundantExportProjection {
+    pub fn new(primary: ExportDestination, backup: ExportDestination) -> Self {
+        Self {
+            primary,
+            backup,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }
+    }
+
+    /// Correlate the emitted event with the ceremony that ran the export
+    pub fn with_correlation(mut self, correlation_id: Uuid, causation_id: Option<Uuid>) -> Self {
+        self.correlation_id = correlation_id;
+        self.causation_id = causation_id;
+        self
+    }
+
+    fn write(
+        destination: &ExportDestination,
+        export: &SDCardExport,
+    ) -> Result<(WriteResult, ExportMediaRecord), ProjectionError> {
+        let written = ExportToFilesystemProjection::new(&destination.base_path)
+            .project(export.clone())
+            .map_err(|e| ProjectionError::CompositionError {
+                stage: format!("write {}", destination.media_id),
+                inner: Box::new(e),
+            })?;
+        let mismatched_files = verify_export(&destination.base_path, export);
+        let record = ExportMediaRecord {
+            media_id: destination.media_id.clone(),
+            path: destination.base_path.display().to_string(),
+            files_written: written.files_written,
+            verified: mismatched_files.is_empty(),
+            mismatched_files,
+        };
+        Ok((written, record))
+    }
+}
+
+/// Result of writing an export to primary and backup media
+#[derive(Debug, Clone)]
+pub struct RedundantWriteResult {
+    pub primary: WriteResult,
+    pub backup: WriteResult,
+    /// `RedundantExportCompleted` recording both media and their verification
+    pub event: ManifestEvents,
+}
+
+impl RedundantWriteResult {
+    /// Whether both media read back exactly as exported
+    pub fn is_verified(&self) -> bool {
+        match &self.event {
+            ManifestEvents::RedundantExportCompleted(e) => e.primary.verified && e.backup.verified,
+            _ => false,
+        }
+    }
+}
+
+impl Projection<SDCardExport, RedundantWriteResult, ProjectionError> for RedundantExportProjection {
+    fn project(&self, export: SDCardExport) -> Result<RedundantWriteResult, ProjectionError> {
+        let (primary, primary_record) = Self::write(&self.primary, &export)?;
+        let (backup, backup_record) = Self::write(&self.backup, &export)?;
+
+        let event = ManifestEvents::RedundantExportCompleted(RedundantExportCompletedEvent {
+            export_id: export.metadata.export_id,
+            organization_name: export.summary.organization_name,
+            primary: primary_record,
+            backup: backup_record,
+            exported_at: Utc::now(),
+            correlation_id: self.correlation_id,
+            causation_id: self.causation_id,
+        });
+
+        Ok(RedundantWriteResult { primary, backup, event })
+    }
+
+    fn name(&self) -> &'static str {
+        "RedundantExport"
+    }
+}
+
+/// Files of `export` that are missing under `base_path` or differ from
+/// their checksum
+pub fn verify_export(base_path: &std::path::Path, export: &SDCardExport) -> Vec<String> {
+    export
+        .files
+        .iter()
+        .filter(|file| {
+            std::fs::read_to_string(base_path.join(&file.path))
+                .map(|content| ManifestToExportProjection::calculate_checksum(&content) != file.checksum)
+                .unwrap_or(true)
+        })
+        .map(|file| file.path.display().to_string())
+        .collect()
+}
+
 // ============================================================================
 // FACTORY FUNCTIONS
 // ============================================================================
@@ -457,6 +1166,14 @@ pub fn sdcard_export_pipeline(base_path: impl Into<PathBuf>) -> impl Projection<
     manifest_to_export().then(ExportToFilesystemProjection::new(base_path))
 }
 
+/// Create an SD card export pipeline writing to primary and backup media
+pub fn redundant_sdcard_export_pipeline(
+    primary: ExportDestination,
+    backup: ExportDestination,
+) -> impl Projection<KeyManifest, RedundantWriteResult, ProjectionError> {
+    manifest_to_export().then(RedundantExportProjection::new(primary, backup))
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -481,12 +1198,24 @@ mod tests {
             keys: vec![],
             certificates: vec![],
             pki_hierarchies: vec![],
+            crls: vec![],
+            certificate_log: vec![],
             yubikeys: vec![],
             nats_operators: vec![],
             nats_accounts: vec![],
             nats_users: vec![],
+            relationships: vec![],
+            delegations: vec![],
+            processed_commands: vec![],
+            outbox: vec![],
+            dead_letters: vec![],
+            journal_position: 0,
             event_count: 0,
             checksum: String::new(),
+            file_checksums: Default::default(),
+            signature: None,
+            luks_uuid: None,
+            kdf_salts: Vec::new(),
         }
     }
 
@@ -550,6 +1279,243 @@ mod tests {
         assert_eq!(export.summary.organization_name, "Test Org");
     }
 
+    #[test]
+    fn test_ssh_access_files_included_in_manifest() {
+        use crate::projection::ssh_access::{ssh_access, SshAccessInput, SshHost};
+
+        let ssh_files = ssh_access()
+            .project(SshAccessInput {
+                organization: "Test Org".to_string(),
+                people: vec![],
+                service_accounts: vec![],
+                keys: vec![],
+                hosts: vec![SshHost {
+                    hostname: "web1".to_string(),
+                    aliases: vec![],
+                    location_id: None,
+                    host_key: Some("ssh-ed25519 AAAAweb1".to_string()),
+                    authorized: vec![],
+                }],
+                ca_public_key: None,
+                certificates: vec![],
+                as_of: Utc::now(),
+            })
+            .unwrap();
+
+        let export = manifest_to_export()
+            .with_ssh_access(ssh_files)
+            .project(sample_manifest())
+            .unwrap();
+
+        assert_eq!(export.summary.ssh_host_count, 1);
+        assert!(export.files.iter().any(|f| f.path == "ssh/known_hosts"));
+        let manifest_file = export.files.iter()
+            .find(|f| f.path == "manifest.json")
+            .unwrap();
+        assert!(manifest_file.content.contains("ssh/hosts/web1/authorized_keys"));
+    }
+
+    #[test]
+    fn test_crls_exported_with_certificates() {
+        use crate::projections::CrlManifestEntry;
+
+        let issuer_ca_id = Uuid::now_v7();
+        let mut manifest = sample_manifest();
+        manifest.crls = vec![CrlManifestEntry {
+            issuer_ca_id,
+            crl_number: 3,
+            this_update: Utc::now(),
+            next_update: Utc::now() + chrono::Duration::days(7),
+            revoked_cert_ids: vec![Uuid::now_v7()],
+            crl_pem: "-----BEGIN X509 CRL-----\n-----END X509 CRL-----\n".to_string(),
+            file_path: format!("certificates/{}/crl.pem", issuer_ca_id),
+        }];
+        let crl_path = PathBuf::from(format!("certificates/crl/{}.crl.pem", issuer_ca_id));
+
+        let export = manifest_to_export().project(manifest.clone()).unwrap();
+        let crl_file = export.files.iter().find(|f| f.path == crl_path).unwrap();
+        assert!(crl_file.content.starts_with("-----BEGIN X509 CRL-----"));
+        assert!(!crl_file.sensitive);
+        assert_eq!(export.summary.crl_count, 1);
+
+        let export = manifest_to_export()
+            .with_certificates(false)
+            .project(manifest)
+            .unwrap();
+        assert!(!export.files.iter().any(|f| f.path == crl_path));
+        assert_eq!(export.summary.crl_count, 0);
+    }
+
+    #[test]
+    fn test_profile_limits_export_and_is_recorded() {
+        use crate::projection::terraform::TerraformExport;
+        use crate::projections::KeyEntry;
+        use crate::types::{KeyAlgorithm, KeyPurpose};
+
+        let mut manifest = sample_manifest();
+        manifest.keys = vec![KeyEntry {
+            key_id: Uuid::now_v7(),
+            algorithm: KeyAlgorithm::Ed25519,
+            purpose: KeyPurpose::Signing,
+            label: "root".to_string(),
+            hardware_backed: false,
+            yubikey_serial: None,
+            yubikey_slot: None,
+            revoked: false,
+            file_path: String::new(),
+            state: None,
+            expires_at: None,
+            owner_id: None,
+        }];
+        let terraform = TerraformExport {
+            directories: vec![PathBuf::from("terraform")],
+            files: vec![ExportFile::new("terraform/cim-keys.auto.tfvars.json", "{}".to_string(), false)],
+        };
+
+        let export = manifest_to_export()
+            .with_profile(ExportProfile::developer_bundle())
+            .with_terraform(terraform.clone())
+            .project(manifest.clone())
+            .unwrap();
+        assert!(!export.directories.contains(&PathBuf::from("keys")));
+        assert!(!export.files.iter().any(|f| f.sensitive));
+        assert!(export.files.iter().any(|f| f.path == "nats/subjects.md"));
+        assert!(export.files.iter().any(|f| f.path == "terraform/cim-keys.auto.tfvars.json"));
+        assert_eq!(export.metadata.profile.name, "developer-bundle");
+        let manifest_file = export.files.iter().find(|f| f.path == "manifest.json").unwrap();
+        assert!(manifest_file.content.contains("\"developer-bundle\""));
+
+        // The ceremony card keeps key metadata but refuses deployment artifacts
+        let ceremony = ExportProfile::builtin("root-ceremony").unwrap();
+        let export = manifest_to_export().with_profile(ceremony.clone()).project(manifest.clone()).unwrap();
+        assert!(export.files.iter().any(|f| f.path.starts_with("keys")));
+        assert!(matches!(
+            manifest_to_export().with_profile(ceremony).with_terraform(terraform).project(manifest),
+            Err(ProjectionError::ValidationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sensitive_files_encrypted_to_people_and_bundles_per_person() {
+        use crate::crypto::age_derivation::{derive_age_key, AgeDerivationPath};
+        use crate::crypto::age_encryption::{decrypt_with_identity, decrypt_with_passphrase};
+        use crate::crypto::seed_derivation::MasterSeed;
+        use crate::projections::KeyEntry;
+        use crate::types::{KeyAlgorithm, KeyPurpose};
+
+        let seed = MasterSeed::from_bytes([7u8; 32]);
+        let alice = derive_age_key(&seed, &AgeDerivationPath::new("cowboyai", "alice").unwrap());
+        let (alice_id, bob_id) = (Uuid::now_v7(), Uuid::now_v7());
+        let key_id = Uuid::now_v7();
+        let mut manifest = sample_manifest();
+        manifest.keys = vec![KeyEntry {
+            key_id,
+            algorithm: KeyAlgorithm::Ed25519,
+            purpose: KeyPurpose::Signing,
+            label: "root".to_string(),
+            hardware_backed: false,
+            yubikey_serial: None,
+            yubikey_slot: None,
+            revoked: false,
+            file_path: String::new(),
+            state: None,
+            expires_at: None,
+            owner_id: None,
+        }];
+
+        let export = manifest_to_export()
+            .with_encryption(vec![PersonRecipient::new(alice_id, alice.recipient.clone())])
+            .with_recovery_bundle(
+                RecoveryBundle::new(bob_id, AgeProtection::passphrase("bob's passphrase"))
+                    .with_file("seed-share.txt", "share-text"),
+            )
+            .project(manifest)
+            .unwrap();
+
+        // No sensitive file is left in plaintext
+        assert!(export.files.iter().filter(|f| f.sensitive).all(|f| f.path.extension() == Some("age".as_ref())));
+        let metadata = export
+            .files
+            .iter()
+            .find(|f| f.path == PathBuf::from(format!("keys/{}/metadata.json.age", key_id)))
+            .unwrap();
+        let plaintext = decrypt_with_identity(&metadata.content, &alice.identity()).unwrap();
+        assert!(String::from_utf8(plaintext).unwrap().contains("\"root\""));
+        assert_eq!(export.metadata.encrypted_to, vec![alice_id]);
+
+        let share = export
+            .files
+            .iter()
+            .find(|f| f.path == PathBuf::from(format!("recovery/{}/seed-share.txt.age", bob_id)))
+            .unwrap();
+        let passphrase = age::secrecy::SecretString::from("bob's passphrase".to_string());
+        assert_eq!(decrypt_with_passphrase(&share.content, &passphrase).unwrap(), b"share-text");
+        assert!(decrypt_with_identity(&share.content, &alice.identity()).is_err());
+        assert_eq!(export.summary.recovery_bundle_count, 1);
+
+        // Developer bundles never carry recovery material
+        let bundle = RecoveryBundle::new(bob_id, AgeProtection::passphrase("x")).with_file("a", "b");
+        assert!(manifest_to_export()
+            .with_profile(ExportProfile::developer_bundle())
+            .with_recovery_bundle(bundle)
+            .project(sample_manifest())
+            .is_err());
+    }
+
+    #[test]
+    fn test_sensitive_files_sealed_in_envelopes() {
+        use crate::crypto::envelope::{Envelope, X25519Encryptor};
+        use crate::crypto::key_generation::KeyAgreementKeyPair;
+        use crate::crypto::seed_derivation::MasterSeed;
+        use crate::projections::KeyEntry;
+        use crate::types::{KeyAlgorithm, KeyPurpose};
+
+        let alice = KeyAgreementKeyPair::from_seed(&MasterSeed::from_bytes([7u8; 32]));
+        let alice_key = KeyId::new();
+        let encryptor = Arc::new(X25519Encryptor::new().with_recipient(alice_key, alice.public_key_bytes()));
+        let key_id = Uuid::now_v7();
+        let mut manifest = sample_manifest();
+        manifest.keys = vec![KeyEntry {
+            key_id,
+            algorithm: KeyAlgorithm::Ed25519,
+            purpose: KeyPurpose::Signing,
+            label: "root".to_string(),
+            hardware_backed: false,
+            yubikey_serial: None,
+            yubikey_slot: None,
+            revoked: false,
+            file_path: String::new(),
+            state: None,
+            expires_at: None,
+            owner_id: None,
+        }];
+
+        let export = manifest_to_export()
+            .with_envelope_encryption(encryptor.clone(), vec![alice_key])
+            .project(manifest.clone())
+            .unwrap();
+
+        let sealed = export
+            .files
+            .iter()
+            .find(|f| f.path == PathBuf::from(format!("keys/{}/metadata.json.envelope.json", key_id)))
+            .unwrap();
+        let envelope: Envelope = serde_json::from_str(&sealed.content).unwrap();
+        let plaintext = envelope.open(alice_key, &alice).unwrap();
+        assert!(String::from_utf8(plaintext).unwrap().contains("\"root\""));
+        assert_eq!(export.metadata.sealed_for, vec![alice_key]);
+        assert!(export.metadata.encrypted_to.is_empty());
+
+        // age and envelope encryption are mutually exclusive
+        assert!(matches!(
+            manifest_to_export()
+                .with_encryption(vec![PersonRecipient::new(Uuid::now_v7(), "age1ignored")])
+                .with_envelope_encryption(encryptor, vec![alice_key])
+                .project(manifest),
+            Err(ProjectionError::ValidationFailed { .. })
+        ));
+    }
+
     #[test]
     fn test_checksum_calculation() {
         let content = "test content";
@@ -573,4 +1539,28 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_dir_all(&temp_dir);
     }
+
+    #[test]
+    fn test_redundant_pipeline_verifies_both_media() {
+        let temp_dir = std::env::temp_dir().join(format!("cim-keys-test-{}", Uuid::now_v7()));
+        let primary = ExportDestination::new("primary-card", temp_dir.join("primary"));
+        let backup = ExportDestination::new("backup-card", temp_dir.join("backup"));
+
+        let result = redundant_sdcard_export_pipeline(primary, backup).project(sample_manifest()).unwrap();
+        assert!(result.is_verified());
+        let ManifestEvents::RedundantExportCompleted(event) = &result.event else {
+            panic!("unexpected event {:?}", result.event);
+        };
+        assert_eq!(event.primary.media_id, "primary-card");
+        assert_eq!(event.backup.media_id, "backup-card");
+        assert_eq!(event.backup.files_written, result.backup.files_written);
+
+        // A file changed on one medium is reported
+        let export = manifest_to_export().project(sample_manifest()).unwrap();
+        ExportToFilesystemProjection::new(temp_dir.join("backup")).project(export.clone()).unwrap();
+        std::fs::write(temp_dir.join("backup").join("domain/people.json"), "tampered").unwrap();
+        assert_eq!(verify_export(&temp_dir.join("backup"), &export), vec!["domain/people.json"]);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
 }
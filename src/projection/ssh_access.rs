@@ -0,0 +1,575 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # SSH Access Projection
+//!
+//! Composable projection for domain SSH state → deployable OpenSSH files.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! Domain (people, service accounts, SSH keys, SSH CA + certificates)
+//!     ↓ via
+//! SshAccessProjection (pure)
+//!     ↓ produces
+//! SshAccessExport (authorized_keys per host + known_hosts)
+//!     ↓ via
+//! ManifestToExportProjection::with_ssh_access
+//!     ↓ produces
+//! SDCardExport
+//! ```
+//!
+//! ## Directory Structure on SD Card
+//!
+//! ```text
+//! ssh/
+//! ├── known_hosts              # Org-wide host keys and @cert-authority line
+//! └── hosts/
+//!     └── {hostname}/
+//!         └── authorized_keys  # Keys of everyone granted access to the host
+//! ```
+//!
+//! Suspended, deactivated and archived people are left out, so re-running the
+//! export after an offboarding removes their access on the next deployment.
+
+use crate::domain::sagas::SshKeyRotationSaga;
+use crate::events::key::SshCertificateIssuedEvent;
+use crate::projection::sdcard::ExportFile;
+use crate::projection::{Projection, ProjectionError};
+use crate::projections::PersonEntry;
+use crate::state_machines::PersonState;
+use crate::types::SshCertificateType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+// ============================================================================
+// DOMAIN TYPES
+// ============================================================================
+
+/// Who an SSH key authenticates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SshKeyHolder {
+    Person(Uuid),
+    ServiceAccount(Uuid),
+}
+
+/// A public key that may be placed in authorized_keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshAuthorizedKey {
+    pub key_id: Uuid,
+    pub holder: SshKeyHolder,
+    /// Public key in OpenSSH format ("ssh-ed25519 AAAA... comment")
+    pub public_key: String,
+    /// authorized_keys options (e.g. `from="10.0.0.0/8"`, `no-pty`)
+    pub options: Vec<String>,
+}
+
+/// Service account that may hold SSH keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshServiceAccount {
+    pub service_account_id: Uuid,
+    pub name: String,
+}
+
+/// A host that receives an authorized_keys file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshHost {
+    pub hostname: String,
+    /// Additional names/addresses for known_hosts
+    pub aliases: Vec<String>,
+    pub location_id: Option<Uuid>,
+    /// Host public key in OpenSSH format; hosts with a host certificate
+    /// are covered by the @cert-authority line instead
+    pub host_key: Option<String>,
+    /// Holders granted access to this host
+    pub authorized: Vec<SshKeyHolder>,
+}
+
+/// Everything the projection walks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshAccessInput {
+    pub organization: String,
+    pub people: Vec<PersonEntry>,
+    pub service_accounts: Vec<SshServiceAccount>,
+    pub keys: Vec<SshAuthorizedKey>,
+    pub hosts: Vec<SshHost>,
+    /// SSH CA public key in OpenSSH format, if one was designated
+    pub ca_public_key: Option<String>,
+    /// Issued SSH certificates (user and host)
+    pub certificates: Vec<SshCertificateIssuedEvent>,
+    /// Certificates expired at this time are ignored
+    pub as_of: DateTime<Utc>,
+}
+
+impl SshAccessInput {
+    /// Replace the keys taking part in a rotation with the ones the saga
+    /// currently authorizes
+    ///
+    /// The replacement keys inherit the holder and options of the old key.
+    /// Inputs that do not contain the old key are returned unchanged.
+    pub fn with_key_rotation(mut self, saga: &SshKeyRotationSaga) -> Self {
+        let old_key_id = saga.request.old_key.key_id;
+        let Some(old) = self.keys.iter().find(|k| k.key_id == old_key_id).cloned() else {
+            return self;
+        };
+
+        let new_key_id = saga.artifacts.new_key.as_ref().map(|k| k.key_id);
+        self.keys
+            .retain(|k| k.key_id != old_key_id && Some(k.key_id) != new_key_id);
+        self.keys.extend(saga.authorized_keys().into_iter().map(|k| SshAuthorizedKey {
+            key_id: k.key_id,
+            holder: old.holder,
+            public_key: k.public_key.clone(),
+            options: old.options.clone(),
+        }));
+        self
+    }
+}
+
+/// Rendered OpenSSH files ready for the SD card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshAccessExport {
+    pub directories: Vec<PathBuf>,
+    pub files: Vec<ExportFile>,
+    pub host_count: usize,
+}
+
+// ============================================================================
+// PROJECTION
+// ============================================================================
+
+/// Projection: SshAccessInput → SshAccessExport
+#[derive(Debug, Clone, Default)]
+pub struct SshAccessProjection;
+
+impl SshAccessProjection {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub(crate) fn person_has_access(person: &PersonEntry) -> bool {
+        matches!(
+            person.state,
+            None | Some(PersonState::Created { .. }) | Some(PersonState::Active { .. })
+        )
+    }
+
+    pub(crate) fn validate_hostname(hostname: &str) -> Result<(), ProjectionError> {
+        let valid = !hostname.is_empty()
+            && hostname != "."
+            && hostname != ".."
+            && !hostname.contains(|c: char| c == '/' || c == '\\' || c.is_whitespace());
+        if valid {
+            Ok(())
+        } else {
+            Err(ProjectionError::ValidationFailed {
+                field: "hostname".to_string(),
+                reason: format!("{:?} cannot be used as an SSH host name", hostname),
+            })
+        }
+    }
+
+    fn header(organization: &str, scope: &str) -> String {
+        format!(
+            "# Managed by cim-keys for {} - do not edit\n# {}\n",
+            organization, scope
+        )
+    }
+
+    /// Render an authorized_keys line, labelling keys that carry no comment
+    fn authorized_key_line(key: &SshAuthorizedKey, label: &str) -> String {
+        let public_key = key.public_key.trim();
+        let mut line = String::new();
+        if !key.options.is_empty() {
+            line.push_str(&key.options.join(","));
+            line.push(' ');
+        }
+        line.push_str(public_key);
+        if public_key.split_whitespace().count() < 3 {
+            line.push(' ');
+            line.push_str(label);
+        }
+        line
+    }
+
+    fn is_current(cert: &SshCertificateIssuedEvent, as_of: DateTime<Utc>) -> bool {
+        cert.valid_after <= as_of && as_of < cert.valid_before
+    }
+}
+
+impl Projection<SshAccessInput, SshAccessExport, ProjectionError> for SshAccessProjection {
+    fn project(&self, input: SshAccessInput) -> Result<SshAccessExport, ProjectionError> {
+        // Labels of holders that currently have access
+        let mut labels: HashMap<SshKeyHolder, String> = HashMap::new();
+        for person in input.people.iter().filter(|p| Self::person_has_access(p)) {
+            labels.insert(SshKeyHolder::Person(person.person_id), person.email.clone());
+        }
+        for account in &input.service_accounts {
+            labels.insert(
+                SshKeyHolder::ServiceAccount(account.service_account_id),
+                account.name.clone(),
+            );
+        }
+
+        let current_certs: Vec<_> = input
+            .certificates
+            .iter()
+            .filter(|c| Self::is_current(c, input.as_of))
+            .collect();
+
+        let mut directories = vec![PathBuf::from("ssh"), PathBuf::from("ssh/hosts")];
+        let mut files = Vec::new();
+
+        // Hosts sorted by name so repeated exports are byte-identical
+        let hosts: BTreeMap<&str, &SshHost> = input
+            .hosts
+            .iter()
+            .map(|h| (h.hostname.as_str(), h))
+            .collect();
+        if hosts.len() != input.hosts.len() {
+            return Err(ProjectionError::ValidationFailed {
+                field: "hosts".to_string(),
+                reason: "Each host may appear only once".to_string(),
+            });
+        }
+
+        for (hostname, host) in &hosts {
+            Self::validate_hostname(hostname)?;
+
+            let mut content = Self::header(&input.organization, &format!("host: {}", hostname));
+            let mut principals = BTreeSet::new();
+
+            for holder in &host.authorized {
+                let Some(label) = labels.get(holder) else {
+                    continue;
+                };
+
+                for key in input.keys.iter().filter(|k| k.holder == *holder) {
+                    content.push_str(&Self::authorized_key_line(key, label));
+                    content.push('\n');
+                }
+
+                if let SshKeyHolder::Person(person_id) = holder {
+                    principals.extend(
+                        current_certs
+                            .iter()
+                            .filter(|c| c.cert_type == SshCertificateType::User)
+                            .filter(|c| c.person_id == Some(*person_id))
+                            .flat_map(|c| c.principals.iter().cloned()),
+                    );
+                }
+            }
+
+            // Certificates only authenticate the principals granted this host
+            if let Some(ca_public_key) = &input.ca_public_key {
+                if !principals.is_empty() {
+                    let principals: Vec<_> = principals.into_iter().collect();
+                    content.push_str(&format!(
+                        "cert-authority,principals=\"{}\" {}\n",
+                        principals.join(","),
+                        ca_public_key.trim()
+                    ));
+                }
+            }
+
+            let host_dir = PathBuf::from("ssh/hosts").join(hostname);
+            files.push(ExportFile::new(host_dir.join("authorized_keys"), content, false));
+            directories.push(host_dir);
+        }
+
+        // Org-wide known_hosts
+        let mut known_hosts = Self::header(&input.organization, "known_hosts");
+        if let Some(ca_public_key) = &input.ca_public_key {
+            let patterns: BTreeSet<&str> = current_certs
+                .iter()
+                .filter(|c| c.cert_type == SshCertificateType::Host)
+                .flat_map(|c| c.principals.iter().map(String::as_str))
+                .collect();
+            if !patterns.is_empty() {
+                let patterns: Vec<_> = patterns.into_iter().collect();
+                known_hosts.push_str(&format!(
+                    "@cert-authority {} {}\n",
+                    patterns.join(","),
+                    ca_public_key.trim()
+                ));
+            }
+        }
+        for host in hosts.values() {
+            if let Some(host_key) = &host.host_key {
+                let names: Vec<&str> = std::iter::once(host.hostname.as_str())
+                    .chain(host.aliases.iter().map(String::as_str))
+                    .collect();
+                known_hosts.push_str(&format!("{} {}\n", names.join(","), host_key.trim()));
+            }
+        }
+        files.push(ExportFile::new("ssh/known_hosts", known_hosts, false));
+
+        Ok(SshAccessExport {
+            directories,
+            files,
+            host_count: hosts.len(),
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "SshAccess"
+    }
+}
+
+// ============================================================================
+// FACTORY FUNCTIONS
+// ============================================================================
+
+/// Create an SSH access projection
+pub fn ssh_access() -> SshAccessProjection {
+    SshAccessProjection::new()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ActorId;
+    use chrono::Duration;
+
+    const CA_KEY: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIMcaCAKEYcaKEYcaKEYcaKEYcaKEYcaKEYcaKEY ca";
+
+    fn person(email: &str, state: Option<PersonState>) -> PersonEntry {
+        PersonEntry {
+            person_id: Uuid::now_v7(),
+            name: email.to_string(),
+            email: email.to_string(),
+            role: "Developer".to_string(),
+            organization_id: Uuid::now_v7(),
+            state,
+        }
+    }
+
+    fn key(holder: SshKeyHolder, public_key: &str) -> SshAuthorizedKey {
+        SshAuthorizedKey {
+            key_id: Uuid::now_v7(),
+            holder,
+            public_key: public_key.to_string(),
+            options: vec![],
+        }
+    }
+
+    fn certificate(
+        cert_type: SshCertificateType,
+        person_id: Option<Uuid>,
+        principals: &[&str],
+        now: DateTime<Utc>,
+    ) -> SshCertificateIssuedEvent {
+        SshCertificateIssuedEvent {
+            certificate_id: Uuid::now_v7(),
+            ca_key_id: Uuid::now_v7(),
+            subject_key_id: None,
+            cert_type,
+            serial: 1,
+            key_identity: principals[0].to_string(),
+            principals: principals.iter().map(|p| p.to_string()).collect(),
+            person_id,
+            location_id: None,
+            subject_fingerprint: "SHA256:subject".to_string(),
+            ca_fingerprint: "SHA256:ca".to_string(),
+            valid_after: now - Duration::hours(1),
+            valid_before: now + Duration::hours(8),
+            critical_options: BTreeMap::new(),
+            issued_at: now,
+            issued_by: ActorId::system("test"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }
+    }
+
+    fn file<'a>(export: &'a SshAccessExport, path: &str) -> &'a str {
+        &export
+            .files
+            .iter()
+            .find(|f| f.path == std::path::Path::new(path))
+            .unwrap_or_else(|| panic!("{} not exported", path))
+            .content
+    }
+
+    #[test]
+    fn test_authorized_keys_per_host_excludes_suspended_people() {
+        let now = Utc::now();
+        let alice = person("alice@example.com", None);
+        let bob = person(
+            "bob@example.com",
+            Some(PersonState::Suspended {
+                reason: "leave".to_string(),
+                suspended_at: now,
+                suspended_by: Uuid::now_v7(),
+                previous_roles: vec![],
+            }),
+        );
+        let deployer = SshServiceAccount {
+            service_account_id: Uuid::now_v7(),
+            name: "deployer".to_string(),
+        };
+        let alice_holder = SshKeyHolder::Person(alice.person_id);
+        let bob_holder = SshKeyHolder::Person(bob.person_id);
+        let deployer_holder = SshKeyHolder::ServiceAccount(deployer.service_account_id);
+
+        let mut deploy_key = key(deployer_holder, "ssh-ed25519 AAAAdeploy");
+        deploy_key.options = vec!["no-pty".to_string(), "from=\"10.0.0.0/8\"".to_string()];
+
+        let input = SshAccessInput {
+            organization: "Example".to_string(),
+            people: vec![alice.clone(), bob],
+            service_accounts: vec![deployer],
+            keys: vec![
+                key(alice_holder, "ssh-ed25519 AAAAalice"),
+                key(bob_holder, "ssh-ed25519 AAAAbob bob@laptop"),
+                deploy_key,
+            ],
+            hosts: vec![SshHost {
+                hostname: "web1.example.com".to_string(),
+                aliases: vec!["10.0.0.5".to_string()],
+                location_id: None,
+                host_key: Some("ssh-ed25519 AAAAweb1".to_string()),
+                authorized: vec![alice_holder, bob_holder, deployer_holder],
+            }],
+            ca_public_key: Some(CA_KEY.to_string()),
+            certificates: vec![certificate(
+                SshCertificateType::User,
+                Some(alice.person_id),
+                &["alice"],
+                now,
+            )],
+            as_of: now,
+        };
+
+        let export = ssh_access().project(input).unwrap();
+        let authorized_keys = file(&export, "ssh/hosts/web1.example.com/authorized_keys");
+
+        assert!(authorized_keys.contains("ssh-ed25519 AAAAalice alice@example.com\n"));
+        assert!(authorized_keys.contains("no-pty,from=\"10.0.0.0/8\" ssh-ed25519 AAAAdeploy deployer\n"));
+        assert!(authorized_keys.contains(&format!("cert-authority,principals=\"alice\" {}\n", CA_KEY)));
+        assert!(!authorized_keys.contains("AAAAbob"));
+        assert!(export.directories.contains(&PathBuf::from("ssh/hosts/web1.example.com")));
+    }
+
+    #[test]
+    fn test_known_hosts_trusts_ca_for_certified_hosts() {
+        let now = Utc::now();
+        let mut expired = certificate(SshCertificateType::Host, None, &["old.example.com"], now);
+        expired.valid_before = now - Duration::minutes(1);
+
+        let input = SshAccessInput {
+            organization: "Example".to_string(),
+            people: vec![],
+            service_accounts: vec![],
+            keys: vec![],
+            hosts: vec![SshHost {
+                hostname: "legacy.example.com".to_string(),
+                aliases: vec![],
+                location_id: None,
+                host_key: Some("ssh-ed25519 AAAAlegacy".to_string()),
+                authorized: vec![],
+            }],
+            ca_public_key: Some(CA_KEY.to_string()),
+            certificates: vec![
+                certificate(SshCertificateType::Host, None, &["db1.example.com", "db1"], now),
+                expired,
+            ],
+            as_of: now,
+        };
+
+        let export = ssh_access().project(input).unwrap();
+        let known_hosts = file(&export, "ssh/known_hosts");
+
+        assert!(known_hosts.contains(&format!("@cert-authority db1,db1.example.com {}\n", CA_KEY)));
+        assert!(known_hosts.contains("legacy.example.com ssh-ed25519 AAAAlegacy\n"));
+        assert!(!known_hosts.contains("old.example.com"));
+    }
+
+    #[test]
+    fn test_rejects_path_like_hostnames() {
+        let input = SshAccessInput {
+            organization: "Example".to_string(),
+            people: vec![],
+            service_accounts: vec![],
+            keys: vec![],
+            hosts: vec![SshHost {
+                hostname: "../etc".to_string(),
+                aliases: vec![],
+                location_id: None,
+                host_key: None,
+                authorized: vec![],
+            }],
+            ca_public_key: None,
+            certificates: vec![],
+            as_of: Utc::now(),
+        };
+
+        let result = ssh_access().project(input);
+        assert!(matches!(result, Err(ProjectionError::ValidationFailed { .. })));
+    }
+
+    #[test]
+    fn test_key_rotation_overlap_lists_both_keys() {
+        use crate::domain::sagas::{SshRotationKey, SshRotationRequest};
+        use crate::events::KeyAlgorithm;
+
+        let alice = person("alice@example.com", None);
+        let holder = SshKeyHolder::Person(alice.person_id);
+        let old = key(holder, "ssh-ed25519 AAAAold");
+
+        let mut saga = SshKeyRotationSaga::new(SshRotationRequest {
+            old_key: SshRotationKey {
+                key_id: old.key_id,
+                public_key: old.public_key.clone(),
+            },
+            new_key_algorithm: KeyAlgorithm::Ed25519,
+            overlap_hours: 24,
+            reason: "Scheduled rotation".to_string(),
+        });
+        saga.start().unwrap();
+        saga.record_new_key(SshRotationKey {
+            key_id: Uuid::now_v7(),
+            public_key: "ssh-ed25519 AAAAnew".to_string(),
+        });
+        saga.advance(); // PublishingOverlap
+
+        let input = |keys: Vec<SshAuthorizedKey>| SshAccessInput {
+            organization: "Example".to_string(),
+            people: vec![alice.clone()],
+            service_accounts: vec![],
+            keys,
+            hosts: vec![SshHost {
+                hostname: "web1".to_string(),
+                aliases: vec![],
+                location_id: None,
+                host_key: None,
+                authorized: vec![holder],
+            }],
+            ca_public_key: None,
+            certificates: vec![],
+            as_of: Utc::now(),
+        };
+
+        let export = ssh_access()
+            .project(input(vec![old.clone()]).with_key_rotation(&saga))
+            .unwrap();
+        let authorized_keys = file(&export, "ssh/hosts/web1/authorized_keys");
+        assert!(authorized_keys.contains("AAAAold"));
+        assert!(authorized_keys.contains("AAAAnew"));
+
+        saga.advance(); // GracePeriod
+        saga.fail("Host unreachable", "GracePeriod");
+        saga.start_compensation();
+        let export = ssh_access()
+            .project(input(vec![old]).with_key_rotation(&saga))
+            .unwrap();
+        let authorized_keys = file(&export, "ssh/hosts/web1/authorized_keys");
+        assert!(authorized_keys.contains("AAAAold"));
+        assert!(!authorized_keys.contains("AAAAnew"));
+    }
+}
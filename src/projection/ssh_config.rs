@@ -0,0 +1,399 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # SSH Client Config Projection
+//!
+//! Composable projection for domain SSH state → per-person `~/.ssh/config`
+//! snippets, the client-side counterpart of the [`ssh_access`] projection.
+//!
+//! [`ssh_access`]: crate::projection::ssh_access
+//!
+//! ## Architecture
+//!
+//! ```text
+//! Domain (people, locations, hosts, SSH keys, user certificates)
+//!     ↓ via
+//! SshConfigProjection (pure)
+//!     ↓ produces
+//! SshConfigExport (one bundle per person)
+//!     ↓ via
+//! ManifestToExportProjection::with_ssh_config
+//!     ↓ produces
+//! SDCardExport
+//! ```
+//!
+//! ## Directory Structure on SD Card
+//!
+//! ```text
+//! ssh/people/{person-id}/
+//! ├── config                 # Host blocks for every host the person may reach
+//! ├── {key-id}.pub           # Public half of each identity
+//! └── {certificate-id}-cert.pub
+//! ```
+//!
+//! The bundle is copied to `~/.ssh/cim-keys/` and pulled in with
+//! `Include ~/.ssh/cim-keys/config`. Identities are referenced by their
+//! public key, so the private half stays wherever it already lives (agent,
+//! YubiKey, security key) and never appears on the card.
+
+use crate::events::key::SshCertificateIssuedEvent;
+use crate::projection::sdcard::ExportFile;
+use crate::projection::ssh_access::{SshAccessProjection, SshAuthorizedKey, SshHost, SshKeyHolder};
+use crate::projection::{Projection, ProjectionError};
+use crate::projections::{LocationEntry, PersonEntry};
+use crate::types::SshCertificateType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Directory the bundle is installed to on the person's machine
+pub const SSH_CONFIG_INSTALL_DIR: &str = "~/.ssh/cim-keys";
+
+// ============================================================================
+// DOMAIN TYPES
+// ============================================================================
+
+/// An issued user certificate together with its `-cert.pub` content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshCertificateFile {
+    pub certificate: SshCertificateIssuedEvent,
+    /// Certificate in `*-cert-v01@openssh.com` format
+    pub openssh: String,
+}
+
+/// Everything the projection walks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshConfigInput {
+    pub organization: String,
+    pub people: Vec<PersonEntry>,
+    pub locations: Vec<LocationEntry>,
+    /// Identities; only keys held by people are used
+    pub keys: Vec<SshAuthorizedKey>,
+    pub hosts: Vec<SshHost>,
+    /// User certificates; one bound to a location only applies to its hosts
+    pub certificates: Vec<SshCertificateFile>,
+    /// Certificates expired at this time are ignored
+    pub as_of: DateTime<Utc>,
+}
+
+/// Rendered per-person bundles ready for the SD card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SshConfigExport {
+    pub directories: Vec<PathBuf>,
+    pub files: Vec<ExportFile>,
+    pub person_count: usize,
+}
+
+// ============================================================================
+// PROJECTION
+// ============================================================================
+
+/// Projection: SshConfigInput → SshConfigExport
+#[derive(Debug, Clone, Default)]
+pub struct SshConfigProjection;
+
+impl SshConfigProjection {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn applies_to(cert: &SshCertificateIssuedEvent, host: &SshHost) -> bool {
+        cert.location_id.is_none() || cert.location_id == host.location_id
+    }
+
+    fn render_host(
+        host: &SshHost,
+        user: Option<&str>,
+        identities: &[&SshAuthorizedKey],
+        certificates: &[&SshCertificateFile],
+    ) -> String {
+        let mut block = String::from("Host ");
+        block.push_str(&host.hostname);
+        for alias in &host.aliases {
+            block.push(' ');
+            block.push_str(alias);
+        }
+        block.push('\n');
+        block.push_str(&format!("    HostName {}\n", host.hostname));
+        if let Some(user) = user {
+            block.push_str(&format!("    User {}\n", user));
+        }
+        block.push_str("    IdentitiesOnly yes\n");
+        for key in identities {
+            block.push_str(&format!(
+                "    IdentityFile {}/{}.pub\n",
+                SSH_CONFIG_INSTALL_DIR, key.key_id
+            ));
+        }
+        for cert in certificates {
+            block.push_str(&format!(
+                "    CertificateFile {}/{}-cert.pub\n",
+                SSH_CONFIG_INSTALL_DIR, cert.certificate.certificate_id
+            ));
+        }
+        block
+    }
+}
+
+impl Projection<SshConfigInput, SshConfigExport, ProjectionError> for SshConfigProjection {
+    fn project(&self, input: SshConfigInput) -> Result<SshConfigExport, ProjectionError> {
+        let location_names: HashMap<Uuid, &str> = input
+            .locations
+            .iter()
+            .map(|l| (l.location_id, l.name.as_str()))
+            .collect();
+
+        // Hosts sorted by name so repeated exports are byte-identical
+        let hosts: BTreeMap<&str, &SshHost> = input
+            .hosts
+            .iter()
+            .map(|h| (h.hostname.as_str(), h))
+            .collect();
+        if hosts.len() != input.hosts.len() {
+            return Err(ProjectionError::ValidationFailed {
+                field: "hosts".to_string(),
+                reason: "Each host may appear only once".to_string(),
+            });
+        }
+        for hostname in hosts.keys() {
+            SshAccessProjection::validate_hostname(hostname)?;
+        }
+
+        let mut directories = vec![PathBuf::from("ssh/people")];
+        let mut files = Vec::new();
+        let mut person_count = 0;
+
+        for person in input.people.iter().filter(|p| SshAccessProjection::person_has_access(p)) {
+            let holder = SshKeyHolder::Person(person.person_id);
+            let reachable: Vec<&SshHost> = hosts
+                .values()
+                .filter(|h| h.authorized.contains(&holder))
+                .copied()
+                .collect();
+            if reachable.is_empty() {
+                continue;
+            }
+
+            let identities: Vec<&SshAuthorizedKey> =
+                input.keys.iter().filter(|k| k.holder == holder).collect();
+            let certificates: Vec<&SshCertificateFile> = input
+                .certificates
+                .iter()
+                .filter(|c| c.certificate.cert_type == SshCertificateType::User)
+                .filter(|c| c.certificate.person_id == Some(person.person_id))
+                .filter(|c| c.certificate.valid_after <= input.as_of && input.as_of < c.certificate.valid_before)
+                .collect();
+
+            let mut config = format!(
+                "# Managed by cim-keys for {} - do not edit\n# person: {}\n# Install to {} and add \"Include {}/config\" to ~/.ssh/config\n",
+                input.organization, person.email, SSH_CONFIG_INSTALL_DIR, SSH_CONFIG_INSTALL_DIR
+            );
+
+            // Group host blocks by location, hosts without one last
+            let mut by_location: BTreeMap<(bool, &str), Vec<&SshHost>> = BTreeMap::new();
+            for host in reachable {
+                let location = host.location_id.and_then(|id| location_names.get(&id).copied());
+                by_location
+                    .entry((location.is_none(), location.unwrap_or("")))
+                    .or_default()
+                    .push(host);
+            }
+
+            for ((_, location), hosts) in by_location {
+                config.push('\n');
+                if !location.is_empty() {
+                    config.push_str(&format!("# Location: {}\n", location));
+                }
+                for host in hosts {
+                    let host_certs: Vec<&SshCertificateFile> = certificates
+                        .iter()
+                        .filter(|c| Self::applies_to(&c.certificate, host))
+                        .copied()
+                        .collect();
+                    let user = host_certs
+                        .first()
+                        .and_then(|c| c.certificate.principals.first())
+                        .map(String::as_str);
+                    config.push_str(&Self::render_host(host, user, &identities, &host_certs));
+                }
+            }
+
+            let person_dir = PathBuf::from("ssh/people").join(person.person_id.to_string());
+            for key in &identities {
+                files.push(ExportFile::new(
+                    person_dir.join(format!("{}.pub", key.key_id)),
+                    format!("{}\n", key.public_key.trim()),
+                    false,
+                ));
+            }
+            for cert in &certificates {
+                files.push(ExportFile::new(
+                    person_dir.join(format!("{}-cert.pub", cert.certificate.certificate_id)),
+                    format!("{}\n", cert.openssh.trim()),
+                    false,
+                ));
+            }
+            files.push(ExportFile::new(person_dir.join("config"), config, false));
+            directories.push(person_dir);
+            person_count += 1;
+        }
+
+        Ok(SshConfigExport {
+            directories,
+            files,
+            person_count,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "SshConfig"
+    }
+}
+
+// ============================================================================
+// FACTORY FUNCTIONS
+// ============================================================================
+
+/// Create an SSH client config projection
+pub fn ssh_config() -> SshConfigProjection {
+    SshConfigProjection::new()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value_objects::ActorId;
+    use chrono::Duration;
+
+    fn person(email: &str) -> PersonEntry {
+        PersonEntry {
+            person_id: Uuid::now_v7(),
+            name: email.to_string(),
+            email: email.to_string(),
+            role: "Developer".to_string(),
+            organization_id: Uuid::now_v7(),
+            state: None,
+        }
+    }
+
+    fn location(name: &str) -> LocationEntry {
+        LocationEntry {
+            location_id: Uuid::now_v7(),
+            name: name.to_string(),
+            location_type: "Physical".to_string(),
+            organization_id: Uuid::now_v7(),
+            street: None,
+            city: None,
+            region: None,
+            country: None,
+            postal_code: None,
+            virtual_url: None,
+            state: None,
+        }
+    }
+
+    fn host(hostname: &str, location_id: Option<Uuid>, authorized: Vec<SshKeyHolder>) -> SshHost {
+        SshHost {
+            hostname: hostname.to_string(),
+            aliases: vec![],
+            location_id,
+            host_key: None,
+            authorized,
+        }
+    }
+
+    fn user_certificate(person_id: Uuid, location_id: Option<Uuid>, now: DateTime<Utc>) -> SshCertificateFile {
+        SshCertificateFile {
+            certificate: SshCertificateIssuedEvent {
+                certificate_id: Uuid::now_v7(),
+                ca_key_id: Uuid::now_v7(),
+                subject_key_id: None,
+                cert_type: SshCertificateType::User,
+                serial: 1,
+                key_identity: "alice".to_string(),
+                principals: vec!["alice".to_string()],
+                person_id: Some(person_id),
+                location_id,
+                subject_fingerprint: "SHA256:subject".to_string(),
+                ca_fingerprint: "SHA256:ca".to_string(),
+                valid_after: now - Duration::hours(1),
+                valid_before: now + Duration::hours(8),
+                critical_options: BTreeMap::new(),
+                issued_at: now,
+                issued_by: ActorId::system("test"),
+                correlation_id: Uuid::now_v7(),
+                causation_id: None,
+            },
+            openssh: "ssh-ed25519-cert-v01@openssh.com AAAAcert alice".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_config_per_person_with_identities_and_scoped_certificates() {
+        let now = Utc::now();
+        let alice = person("alice@example.com");
+        let bob = person("bob@example.com");
+        let dc = location("Datacenter A");
+        let alice_holder = SshKeyHolder::Person(alice.person_id);
+
+        let identity = SshAuthorizedKey {
+            key_id: Uuid::now_v7(),
+            holder: alice_holder,
+            public_key: "ssh-ed25519 AAAAalice alice@laptop".to_string(),
+            options: vec![],
+        };
+        let cert = user_certificate(alice.person_id, Some(dc.location_id), now);
+        let cert_id = cert.certificate.certificate_id;
+
+        let input = SshConfigInput {
+            organization: "Example".to_string(),
+            people: vec![alice.clone(), bob],
+            locations: vec![dc.clone()],
+            keys: vec![identity.clone()],
+            hosts: vec![
+                host("web1.example.com", Some(dc.location_id), vec![alice_holder]),
+                host("lab.example.com", None, vec![alice_holder]),
+            ],
+            certificates: vec![cert],
+            as_of: now,
+        };
+
+        let export = ssh_config().project(input).unwrap();
+        assert_eq!(export.person_count, 1);
+
+        let dir = PathBuf::from("ssh/people").join(alice.person_id.to_string());
+        let config = &export.files.iter().find(|f| f.path == dir.join("config")).unwrap().content;
+
+        assert!(config.contains("# Location: Datacenter A\nHost web1.example.com\n"));
+        assert!(config.contains(&format!("IdentityFile ~/.ssh/cim-keys/{}.pub", identity.key_id)));
+        assert_eq!(config.matches(&format!("CertificateFile ~/.ssh/cim-keys/{}-cert.pub", cert_id)).count(), 1);
+        assert_eq!(config.matches("User alice").count(), 1);
+        assert!(config.find("web1.example.com").unwrap() < config.find("lab.example.com").unwrap());
+
+        assert!(export.files.iter().any(|f| f.path == dir.join(format!("{}-cert.pub", cert_id))));
+        assert!(export.files.iter().all(|f| !f.content.contains("PRIVATE KEY")));
+    }
+
+    #[test]
+    fn test_people_without_hosts_get_no_bundle() {
+        let alice = person("alice@example.com");
+        let input = SshConfigInput {
+            organization: "Example".to_string(),
+            people: vec![alice],
+            locations: vec![],
+            keys: vec![],
+            hosts: vec![host("web1", None, vec![])],
+            certificates: vec![],
+            as_of: Utc::now(),
+        };
+
+        let export = ssh_config().project(input).unwrap();
+        assert_eq!(export.person_count, 0);
+        assert!(export.files.is_empty());
+    }
+}
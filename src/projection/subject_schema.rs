@@ -0,0 +1,315 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # JetStream Subject Schema
+//!
+//! Registry of the subjects the JetStream projection publishes to, so that
+//! subjects are checked against a declared schema instead of following the
+//! naming convention by accident.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! SubjectRegistry (one SubjectSchema per aggregate)
+//!     ├── validate(config, subject)   # EventsToMessagesProjection, per message
+//!     └── document(config)            # SubjectDocumentation
+//!             ↓
+//!         nats/subjects.md + nats/subjects.json in the SD card export
+//! ```
+//!
+//! ## Schema
+//!
+//! Each aggregate declares a wildcard pattern below
+//! `{organization}.{domain}`, e.g. `key.*` or `saga.>` for sagas whose
+//! event types span several tokens. It may also list the actions it
+//! publishes; a subject outside the list is rejected even when it matches
+//! the pattern, so a renamed event cannot silently change its subject
+//! under downstream consumers.
+
+use crate::domain::nats::subjects::Subject;
+use crate::projection::jetstream::SubjectConfig;
+use crate::projection::ProjectionError;
+use serde::{Deserialize, Serialize};
+
+/// Aggregates published by `EventsToMessagesProjection`: subject token(s),
+/// pattern below the domain, and description
+///
+/// Keep in sync with `EventsToMessagesProjection::event_to_subject`.
+const DOMAIN_EVENT_AGGREGATES: &[(&str, &str, &str)] = &[
+    ("key", "key.*", "Key generation, import, export, rotation and revocation"),
+    ("certificate", "certificate.*", "Certificate issuance, renewal, revocation and CRLs"),
+    ("certificate.import", "certificate.import.*", "Import of externally issued certificates"),
+    ("person", "person.*", "People and their lifecycle"),
+    ("location", "location.*", "Physical and logical storage locations"),
+    ("delegation", "delegation.*", "Delegated authority between people"),
+    ("nats.operator", "nats.operator.*", "NATS operators, signing keys and JWTs"),
+    ("nats.account", "nats.account.*", "NATS accounts and their permissions"),
+    ("nats.user", "nats.user.*", "NATS users, service accounts and agents"),
+    ("yubikey", "yubikey.*", "YubiKey provisioning and slot management"),
+    ("relationship", "relationship.*", "Relationships between domain entities"),
+    ("organization", "organization.*", "Organization, units, roles and policies"),
+    ("manifest", "manifest.*", "Manifest and export lifecycle"),
+    ("saga", "saga.>", "Saga and compensation steps; event types span several tokens"),
+];
+
+/// Subject schema of one aggregate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubjectSchema {
+    /// Aggregate token(s), e.g. `nats.operator`
+    pub aggregate: String,
+    /// Wildcard pattern below `{organization}.{domain}`, e.g. `nats.operator.*`
+    pub pattern: String,
+    /// Actions (subject tokens after the aggregate) allowed; empty allows any
+    pub actions: Vec<String>,
+    pub description: String,
+}
+
+impl SubjectSchema {
+    pub fn new(aggregate: impl Into<String>, pattern: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            aggregate: aggregate.into(),
+            pattern: pattern.into(),
+            actions: Vec::new(),
+            description: description.into(),
+        }
+    }
+
+    /// Restrict the aggregate to the given actions
+    pub fn with_actions<S: Into<String>>(mut self, actions: impl IntoIterator<Item = S>) -> Self {
+        self.actions = actions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Full wildcard pattern under `config`
+    pub fn full_pattern(&self, config: &SubjectConfig) -> String {
+        format!("{}.{}.{}", config.organization, config.domain, self.pattern)
+    }
+}
+
+/// Registry of subject schemas
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubjectRegistry {
+    schemas: Vec<SubjectSchema>,
+}
+
+impl SubjectRegistry {
+    /// Empty registry; every subject is rejected
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry of every aggregate domain events are published for
+    pub fn domain_events() -> Self {
+        DOMAIN_EVENT_AGGREGATES
+            .iter()
+            .fold(Self::new(), |registry, (aggregate, pattern, description)| {
+                registry.register(SubjectSchema::new(*aggregate, *pattern, *description))
+            })
+    }
+
+    /// Add `schema`, replacing any schema of the same aggregate
+    pub fn register(mut self, schema: SubjectSchema) -> Self {
+        self.schemas.retain(|existing| existing.aggregate != schema.aggregate);
+        self.schemas.push(schema);
+        self
+    }
+
+    pub fn schemas(&self) -> &[SubjectSchema] {
+        &self.schemas
+    }
+
+    pub fn schema(&self, aggregate: &str) -> Option<&SubjectSchema> {
+        self.schemas.iter().find(|schema| schema.aggregate == aggregate)
+    }
+
+    /// Check that `subject` is publishable and declared by a schema,
+    /// returning the schema it belongs to
+    ///
+    /// The most specific pattern wins, so `certificate.import.*` claims
+    /// `certificate.import.certificate_imported` even though
+    /// `certificate.*` could not match it anyway.
+    pub fn validate(&self, config: &SubjectConfig, subject: &str) -> Result<&SubjectSchema, ProjectionError> {
+        let invalid = |reason: String| ProjectionError::ValidationFailed {
+            field: "subject".to_string(),
+            reason: format!("{}: {}", subject, reason),
+        };
+
+        let parsed = Subject::parse(subject).map_err(|e| invalid(e.to_string()))?;
+        if !parsed.is_publishable() {
+            return Err(invalid("wildcards cannot be published to".to_string()));
+        }
+        if let Some(token) = subject.split('.').find(|token| !is_schema_token(token)) {
+            return Err(invalid(format!(
+                "token '{}' must be lowercase letters, digits, '_' or '-'",
+                token
+            )));
+        }
+
+        let schema = self
+            .schemas
+            .iter()
+            .filter(|schema| {
+                Subject::parse(&schema.full_pattern(config))
+                    .map(|pattern| parsed.matches(&pattern))
+                    .unwrap_or(false)
+            })
+            .max_by_key(|schema| schema.aggregate.len())
+            .ok_or_else(|| invalid("no subject schema declares it".to_string()))?;
+
+        if !schema.actions.is_empty() {
+            let prefix = format!("{}.{}.{}.", config.organization, config.domain, schema.aggregate);
+            let action = subject.strip_prefix(&prefix).unwrap_or_default();
+            if !schema.actions.iter().any(|allowed| allowed == action) {
+                return Err(invalid(format!("'{}' is not an action of {}", action, schema.aggregate)));
+            }
+        }
+        Ok(schema)
+    }
+
+    /// Documentation of the registry under `config`
+    pub fn document(&self, config: &SubjectConfig) -> SubjectDocumentation {
+        SubjectDocumentation {
+            organization: config.organization.clone(),
+            domain: config.domain.clone(),
+            subscribe_all: format!("{}.{}.>", config.organization, config.domain),
+            subjects: self
+                .schemas
+                .iter()
+                .map(|schema| DocumentedSubject {
+                    aggregate: schema.aggregate.clone(),
+                    pattern: schema.full_pattern(config),
+                    actions: schema.actions.clone(),
+                    description: schema.description.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn is_schema_token(token: &str) -> bool {
+    token
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+// ============================================================================
+// DOCUMENTATION ARTIFACT
+// ============================================================================
+
+/// Subject documentation for downstream consumers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubjectDocumentation {
+    pub organization: String,
+    pub domain: String,
+    /// Pattern subscribing to every event of the domain
+    pub subscribe_all: String,
+    pub subjects: Vec<DocumentedSubject>,
+}
+
+/// One aggregate's subjects, with the pattern fully expanded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentedSubject {
+    pub aggregate: String,
+    pub pattern: String,
+    pub actions: Vec<String>,
+    pub description: String,
+}
+
+impl SubjectDocumentation {
+    /// Machine-readable form, for consumers generating their subscriptions
+    pub fn to_json(&self) -> Result<String, ProjectionError> {
+        serde_json::to_string_pretty(self).map_err(|e| ProjectionError::SerializationError(e.to_string()))
+    }
+
+    /// Human-readable form
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "# JetStream Subjects: {}.{}\n\n\
+             Events are published as `{}.{}.<aggregate>.<event_type>`, with the event type in \
+             snake_case. Subscribe to `{}` for every event.\n\n\
+             | Aggregate | Pattern | Actions | Description |\n\
+             |-----------|---------|---------|-------------|\n",
+            self.organization, self.domain, self.organization, self.domain, self.subscribe_all
+        );
+        for subject in &self.subjects {
+            let actions = if subject.actions.is_empty() {
+                "any".to_string()
+            } else {
+                subject.actions.iter().map(|action| format!("`{}`", action)).collect::<Vec<_>>().join(", ")
+            };
+            markdown.push_str(&format!(
+                "| {} | `{}` | {} | {} |\n",
+                subject.aggregate, subject.pattern, actions, subject.description
+            ));
+        }
+        markdown
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SubjectConfig {
+        SubjectConfig {
+            organization: "cowboyai".to_string(),
+            domain: "keys".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_domain_event_subjects_validate() {
+        let registry = SubjectRegistry::domain_events();
+
+        let schema = registry.validate(&config(), "cowboyai.keys.key.key_generated").unwrap();
+        assert_eq!(schema.aggregate, "key");
+        let schema = registry
+            .validate(&config(), "cowboyai.keys.certificate.import.certificate_imported")
+            .unwrap();
+        assert_eq!(schema.aggregate, "certificate.import");
+        let schema = registry.validate(&config(), "cowboyai.keys.saga.saga.step.started").unwrap();
+        assert_eq!(schema.aggregate, "saga");
+    }
+
+    #[test]
+    fn test_invalid_subjects_are_rejected() {
+        let registry = SubjectRegistry::domain_events();
+        let rejected = |subject: &str| registry.validate(&config(), subject).is_err();
+
+        assert!(rejected("cowboyai.keys.key.*"));
+        assert!(rejected("cowboyai.keys.unknown.created"));
+        assert!(rejected("otherorg.keys.key.key_generated"));
+        assert!(rejected("Cowboy AI.keys.key.key_generated"));
+        assert!(rejected("cowboyai.keys.key.KeyGenerated"));
+        assert!(rejected("cowboyai.keys.key.key.generated"));
+    }
+
+    #[test]
+    fn test_actions_restrict_an_aggregate() {
+        let registry = SubjectRegistry::domain_events()
+            .register(SubjectSchema::new("key", "key.*", "Keys").with_actions(["key_generated", "key_revoked"]));
+
+        assert_eq!(registry.schemas().len(), DOMAIN_EVENT_AGGREGATES.len());
+        assert!(registry.validate(&config(), "cowboyai.keys.key.key_revoked").is_ok());
+        assert!(matches!(
+            registry.validate(&config(), "cowboyai.keys.key.key_exported"),
+            Err(ProjectionError::ValidationFailed { reason, .. }) if reason.contains("not an action of key")
+        ));
+    }
+
+    #[test]
+    fn test_documentation_lists_expanded_patterns() {
+        let docs = SubjectRegistry::domain_events().document(&config());
+        assert_eq!(docs.subscribe_all, "cowboyai.keys.>");
+
+        let markdown = docs.to_markdown();
+        assert!(markdown.contains("| nats.operator | `cowboyai.keys.nats.operator.*` | any |"));
+        assert!(markdown.contains("`cowboyai.keys.saga.>`"));
+
+        let json: SubjectDocumentation = serde_json::from_str(&docs.to_json().unwrap()).unwrap();
+        assert_eq!(json, docs);
+    }
+}
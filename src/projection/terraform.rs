@@ -0,0 +1,415 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! # Terraform Projection
+//!
+//! Composable projection for infrastructure-relevant ceremony output →
+//! a Terraform/OpenTofu variable file and a module skeleton declaring
+//! those variables, so pipelines consume CA bundles and NATS identities
+//! without hand-copying them.
+//!
+//! ## Architecture
+//!
+//! ```text
+//! CA PEM bundles, NATS operator JWT, NATS account public keys
+//!     ↓ via
+//! TerraformProjection (pure)
+//!     ↓ produces
+//! TerraformExport (.tfvars.json + module skeleton)
+//!     ↓ via
+//! ManifestToExportProjection::with_terraform
+//!     ↓ produces
+//! SDCardExport
+//! ```
+//!
+//! ## Directory Structure on SD Card
+//!
+//! ```text
+//! terraform/
+//! ├── cim-keys.auto.tfvars.json     # Values, loaded automatically by terraform/tofu
+//! └── modules/{module}/
+//!     ├── variables.tf              # Declarations matching the tfvars
+//!     ├── outputs.tf                # Pass-through outputs for other modules
+//!     └── versions.tf
+//! ```
+//!
+//! Everything exported here is public material; private keys and user
+//! credentials never reach the variable file.
+
+use crate::projection::sdcard::ExportFile;
+use crate::projection::{Projection, ProjectionError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+// ============================================================================
+// DOMAIN TYPES
+// ============================================================================
+
+/// A CA certificate chain, exported as one PEM bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerraformCaBundle {
+    /// Map key in `ca_bundles`, e.g. `root` or `engineering-intermediate`
+    pub name: String,
+    /// PEM certificates, concatenated in order
+    pub certificates_pem: Vec<String>,
+}
+
+/// The NATS operator identity resolvers and servers are configured with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerraformNatsOperator {
+    pub name: String,
+    pub public_key: String,
+    pub jwt: String,
+}
+
+/// A NATS account public key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerraformNatsAccount {
+    pub name: String,
+    pub public_key: String,
+}
+
+/// Material selected for infrastructure pipelines
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TerraformInput {
+    pub organization: String,
+    pub ca_bundles: Vec<TerraformCaBundle>,
+    pub nats_operator: Option<TerraformNatsOperator>,
+    pub nats_accounts: Vec<TerraformNatsAccount>,
+}
+
+/// Rendered variable file and module skeleton
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerraformExport {
+    pub directories: Vec<PathBuf>,
+    pub files: Vec<ExportFile>,
+}
+
+/// Contents of the `.tfvars.json` file; field names are variable names
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TerraformVariables {
+    organization: String,
+    ca_bundles: BTreeMap<String, String>,
+    nats_operator: Option<NatsOperatorVariable>,
+    nats_accounts: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NatsOperatorVariable {
+    name: String,
+    public_key: String,
+    jwt: String,
+}
+
+// ============================================================================
+// PROJECTION
+// ============================================================================
+
+/// Projection: TerraformInput → TerraformExport
+#[derive(Debug, Clone)]
+pub struct TerraformProjection {
+    module_name: String,
+}
+
+impl Default for TerraformProjection {
+    fn default() -> Self {
+        Self {
+            module_name: "cim_keys".to_string(),
+        }
+    }
+}
+
+impl TerraformProjection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Directory name of the module skeleton under `terraform/modules`
+    pub fn with_module_name(mut self, module_name: impl Into<String>) -> Self {
+        self.module_name = module_name.into();
+        self
+    }
+
+    fn variables(input: TerraformInput) -> Result<TerraformVariables, ProjectionError> {
+        let mut ca_bundles = BTreeMap::new();
+        for bundle in input.ca_bundles {
+            validate_key("ca_bundles", &bundle.name)?;
+            if bundle.certificates_pem.is_empty()
+                || !bundle.certificates_pem.iter().all(|pem| pem.contains("-----BEGIN CERTIFICATE-----"))
+            {
+                return Err(ProjectionError::ValidationFailed {
+                    field: format!("ca_bundles.{}", bundle.name),
+                    reason: "expected one or more PEM certificates".to_string(),
+                });
+            }
+            let pem: String = bundle
+                .certificates_pem
+                .iter()
+                .map(|pem| format!("{}\n", pem.trim_end()))
+                .collect();
+            insert_unique(&mut ca_bundles, "ca_bundles", bundle.name, pem)?;
+        }
+
+        let nats_operator = match input.nats_operator {
+            Some(operator) => {
+                require_nkey("nats_operator.public_key", &operator.public_key, 'O')?;
+                if operator.jwt.split('.').count() != 3 {
+                    return Err(ProjectionError::ValidationFailed {
+                        field: "nats_operator.jwt".to_string(),
+                        reason: "not a JWT".to_string(),
+                    });
+                }
+                Some(NatsOperatorVariable {
+                    name: operator.name,
+                    public_key: operator.public_key,
+                    jwt: operator.jwt.trim().to_string(),
+                })
+            }
+            None => None,
+        };
+
+        let mut nats_accounts = BTreeMap::new();
+        for account in input.nats_accounts {
+            validate_key("nats_accounts", &account.name)?;
+            require_nkey(&format!("nats_accounts.{}", account.name), &account.public_key, 'A')?;
+            insert_unique(&mut nats_accounts, "nats_accounts", account.name, account.public_key)?;
+        }
+
+        Ok(TerraformVariables {
+            organization: input.organization,
+            ca_bundles,
+            nats_operator,
+            nats_accounts,
+        })
+    }
+}
+
+impl Projection<TerraformInput, TerraformExport, ProjectionError> for TerraformProjection {
+    fn project(&self, input: TerraformInput) -> Result<TerraformExport, ProjectionError> {
+        validate_key("module_name", &self.module_name)?;
+        let variables = Self::variables(input)?;
+        let tfvars = serde_json::to_string_pretty(&variables)
+            .map_err(|e| ProjectionError::SerializationError(e.to_string()))?;
+
+        let module = PathBuf::from("terraform/modules").join(&self.module_name);
+        Ok(TerraformExport {
+            directories: vec![PathBuf::from("terraform"), PathBuf::from("terraform/modules"), module.clone()],
+            files: vec![
+                ExportFile::new("terraform/cim-keys.auto.tfvars.json", format!("{}\n", tfvars), false),
+                ExportFile::new(module.join("variables.tf"), VARIABLES_TF.to_string(), false),
+                ExportFile::new(module.join("outputs.tf"), OUTPUTS_TF.to_string(), false),
+                ExportFile::new(module.join("versions.tf"), VERSIONS_TF.to_string(), false),
+            ],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "Terraform"
+    }
+}
+
+// ============================================================================
+// MODULE SKELETON
+// ============================================================================
+
+const VARIABLES_TF: &str = r#"# Managed by cim-keys - do not edit
+# Values come from cim-keys.auto.tfvars.json in the export
+
+variable "organization" {
+  description = "Organization the key ceremony was run for"
+  type        = string
+}
+
+variable "ca_bundles" {
+  description = "PEM certificate bundles by CA name"
+  type        = map(string)
+  default     = {}
+}
+
+variable "nats_operator" {
+  description = "NATS operator identity for resolver and server configuration"
+  type = object({
+    name       = string
+    public_key = string
+    jwt        = string
+  })
+  default = null
+}
+
+variable "nats_accounts" {
+  description = "NATS account public keys by account name"
+  type        = map(string)
+  default     = {}
+}
+"#;
+
+const OUTPUTS_TF: &str = r#"# Managed by cim-keys - do not edit
+
+output "organization" {
+  value = var.organization
+}
+
+output "ca_bundles" {
+  value = var.ca_bundles
+}
+
+output "nats_operator_jwt" {
+  value = var.nats_operator == null ? null : var.nats_operator.jwt
+}
+
+output "nats_operator_public_key" {
+  value = var.nats_operator == null ? null : var.nats_operator.public_key
+}
+
+output "nats_accounts" {
+  value = var.nats_accounts
+}
+"#;
+
+const VERSIONS_TF: &str = r#"# Managed by cim-keys - do not edit
+
+terraform {
+  # optional() defaults and nullable object variables
+  required_version = ">= 1.3"
+}
+"#;
+
+// ============================================================================
+// VALIDATION HELPERS
+// ============================================================================
+
+/// Map keys become HCL identifiers in pipelines (`for_each`, resource names)
+fn validate_key(field: &str, key: &str) -> Result<(), ProjectionError> {
+    let valid = key.starts_with(|c: char| c.is_ascii_alphabetic())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(ProjectionError::ValidationFailed {
+            field: field.to_string(),
+            reason: format!("{:?} must start with a letter and contain only letters, digits, '_' or '-'", key),
+        })
+    }
+}
+
+/// NATS public nkeys are 56 base32 characters with a role prefix
+fn require_nkey(field: &str, key: &str, prefix: char) -> Result<(), ProjectionError> {
+    let valid = key.len() == 56
+        && key.starts_with(prefix)
+        && key.chars().all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c));
+    if valid {
+        Ok(())
+    } else {
+        Err(ProjectionError::ValidationFailed {
+            field: field.to_string(),
+            reason: format!("expected a public nkey starting with '{}'", prefix),
+        })
+    }
+}
+
+fn insert_unique(
+    map: &mut BTreeMap<String, String>,
+    field: &str,
+    key: String,
+    value: String,
+) -> Result<(), ProjectionError> {
+    if map.contains_key(&key) {
+        return Err(ProjectionError::ValidationFailed {
+            field: field.to_string(),
+            reason: format!("{} appears twice", key),
+        });
+    }
+    map.insert(key, value);
+    Ok(())
+}
+
+// ============================================================================
+// FACTORY FUNCTIONS
+// ============================================================================
+
+/// Create a Terraform projection with the `cim_keys` module skeleton
+pub fn terraform() -> TerraformProjection {
+    TerraformProjection::new()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CERT: &str = "-----BEGIN CERTIFICATE-----\nMIIB\n-----END CERTIFICATE-----";
+
+    fn nkey(prefix: char) -> String {
+        format!("{}{}", prefix, "A".repeat(55))
+    }
+
+    fn input() -> TerraformInput {
+        TerraformInput {
+            organization: "Cowboy AI".to_string(),
+            ca_bundles: vec![TerraformCaBundle {
+                name: "engineering".to_string(),
+                certificates_pem: vec![CERT.to_string(), format!("{}\n", CERT)],
+            }],
+            nats_operator: Some(TerraformNatsOperator {
+                name: "cowboyai".to_string(),
+                public_key: nkey('O'),
+                jwt: "eyJ0.eyJq.c2ln\n".to_string(),
+            }),
+            nats_accounts: vec![TerraformNatsAccount {
+                name: "orders".to_string(),
+                public_key: nkey('A'),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_tfvars_match_declared_variables() {
+        let export = terraform().project(input()).unwrap();
+        let paths: Vec<String> = export.files.iter().map(|f| f.path.display().to_string()).collect();
+        assert_eq!(
+            paths,
+            [
+                "terraform/cim-keys.auto.tfvars.json",
+                "terraform/modules/cim_keys/variables.tf",
+                "terraform/modules/cim_keys/outputs.tf",
+                "terraform/modules/cim_keys/versions.tf",
+            ]
+        );
+        assert!(export.files.iter().all(|f| !f.sensitive));
+
+        let tfvars: serde_json::Value = serde_json::from_str(&export.files[0].content).unwrap();
+        assert_eq!(tfvars["ca_bundles"]["engineering"], format!("{}\n{}\n", CERT, CERT));
+        assert_eq!(tfvars["nats_operator"]["jwt"], "eyJ0.eyJq.c2ln");
+        assert_eq!(tfvars["nats_accounts"]["orders"], nkey('A'));
+
+        // Every tfvars key is declared in the module skeleton
+        for key in tfvars.as_object().unwrap().keys() {
+            assert!(VARIABLES_TF.contains(&format!("variable \"{}\"", key)));
+        }
+    }
+
+    #[test]
+    fn test_optional_material_and_validation() {
+        let minimal = TerraformInput { organization: "Cowboy AI".to_string(), ..Default::default() };
+        let export = terraform().with_module_name("pki").project(minimal).unwrap();
+        assert!(export.files[0].content.contains("\"nats_operator\": null"));
+        assert_eq!(export.files[1].path, PathBuf::from("terraform/modules/pki/variables.tf"));
+
+        let mut wrong_role = input();
+        wrong_role.nats_accounts[0].public_key = nkey('U');
+        assert!(terraform().project(wrong_role).is_err());
+
+        let mut duplicate = input();
+        duplicate.ca_bundles.push(duplicate.ca_bundles[0].clone());
+        assert!(terraform().project(duplicate).is_err());
+
+        let mut not_pem = input();
+        not_pem.ca_bundles[0].certificates_pem = vec!["MIIB".to_string()];
+        assert!(terraform().project(not_pem).is_err());
+
+        assert!(terraform().with_module_name("../escape").project(input()).is_err());
+    }
+}
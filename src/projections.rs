@@ -3,15 +3,35 @@
 //! Projections write immutable JSON files to an encrypted partition.
 //! This is designed for offline key management where the SD card IS the state.
 
+pub mod dead_letter;
+pub mod integrity;
+pub mod journal;
+pub mod maintenance;
+pub mod outbox;
+pub mod projector;
+pub mod rebuild;
+pub mod separation_of_duties;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::fs;
+use std::io::Write;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json;
 
+use crate::crypto::{EventLogCipher, KdfPurpose, KdfSalt, open_event_file};
 use crate::events::DomainEvent;
 use crate::types::{KeyAlgorithm, KeyPurpose, KeyMetadata};
+use crate::value_objects::ActorId;
+use crate::commands::organization::RelationshipType;
+
+use dead_letter::DeadLetter;
+use integrity::ManifestSignature;
+use outbox::OutboxEntry;
 
 // Import state machines for lifecycle tracking
 use crate::state_machines::{
@@ -19,6 +39,12 @@ use crate::state_machines::{
     LocationState, YubiKeyState,
 };
 
+/// Number of processed command IDs kept for deduplication
+///
+/// A retry older than this many commands is no longer recognised; the
+/// window only has to outlast a crash-and-resubmit, not the whole history.
+pub const PROCESSED_COMMAND_WINDOW: usize = 4096;
+
 /// Offline key storage projection
 ///
 /// This projection writes all state as JSON files to an encrypted partition.
@@ -26,7 +52,7 @@ use crate::state_machines::{
 /// ```text
 /// /mnt/keys/
 /// ├── manifest.json           # Master index of all keys
-/// ├── events/                 # Event log (append-only)
+/// ├── events/                 # Event log (append-only, optionally encrypted)
 /// │   └── {timestamp}_{event_id}.json
 /// ├── keys/                   # Key material
 /// │   └── {key_id}/
@@ -34,10 +60,16 @@ use crate::state_machines::{
 /// │       ├── public.pem
 /// │       └── private.pem (encrypted)
 /// ├── certificates/           # X.509 certificates
+/// │   ├── transparency.jsonl  # Append-only log of every issued certificate
 /// │   └── {cert_id}/
 /// │       ├── metadata.json
 /// │       ├── cert.pem
-/// │       └── chain.pem
+/// │       ├── chain.pem
+/// │       └── crl.pem         # Issuing CAs only, latest published CRL
+/// ├── people/
+/// │   └── {person_id}/
+/// │       └── smime/
+/// │           └── {cert_id}.p12  # Passphrase-protected S/MIME bundle
 /// ├── yubikeys/              # YubiKey configurations
 /// │   └── {serial}/
 /// │       └── config.json
@@ -53,6 +85,12 @@ pub struct OfflineKeyProjection {
 
     /// Current manifest state
     manifest: KeyManifest,
+
+    /// Encrypts event log files when set
+    event_cipher: Option<EventLogCipher>,
+
+    /// Organization this projection is restricted to, if any
+    organization_id: Option<Uuid>,
 }
 
 /// Master manifest of all keys and certificates
@@ -84,6 +122,14 @@ pub struct KeyManifest {
     /// PKI hierarchies
     pub pki_hierarchies: Vec<PkiHierarchyEntry>,
 
+    /// Latest published CRL per issuing CA
+    #[serde(default)]
+    pub crls: Vec<CrlManifestEntry>,
+
+    /// Append-only, hash-chained log of issued certificates (Merkle tree leaves)
+    #[serde(default)]
+    pub certificate_log: Vec<CertificateLogEntry>,
+
     /// YubiKey serials
     pub yubikeys: Vec<YubiKeyEntry>,
 
@@ -96,11 +142,53 @@ pub struct KeyManifest {
     /// NATS users
     pub nats_users: Vec<NatsUserEntry>,
 
+    /// Relationships between entities, including terminated ones
+    #[serde(default)]
+    pub relationships: Vec<RelationshipEntry>,
+
+    /// Key delegations, including revoked and expired ones
+    #[serde(default)]
+    pub delegations: Vec<DelegationManifestEntry>,
+
+    /// IDs of the most recently processed commands, oldest first
+    /// (bounded by `PROCESSED_COMMAND_WINDOW`)
+    #[serde(default)]
+    pub processed_commands: Vec<Uuid>,
+
+    /// Applied events and their JetStream publication status, oldest first
+    #[serde(default)]
+    pub outbox: Vec<OutboxEntry>,
+
+    /// Logged events that could not be applied, oldest first
+    #[serde(default)]
+    pub dead_letters: Vec<DeadLetter>,
+
+    /// Sequence of the last journal record this manifest contains
+    #[serde(default)]
+    pub journal_position: u64,
+
     /// Event count for consistency checking
     pub event_count: u64,
 
     /// Checksum of all content
     pub checksum: String,
+
+    /// SHA-256 of every file in the tree when it was sealed, by path
+    /// relative to the root
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub file_checksums: BTreeMap<String, String>,
+
+    /// Signature over the rest of the manifest by the ceremony key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ManifestSignature>,
+
+    /// UUID of the LUKS2 volume the projection was exported to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub luks_uuid: Option<String>,
+
+    /// Salts and Argon2id costs of passphrase-derived keys (public)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kdf_salts: Vec<KdfSalt>,
 }
 
 /// Entry for a key in the manifest
@@ -118,6 +206,12 @@ pub struct KeyEntry {
     /// Lifecycle state machine for this key
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub state: Option<KeyState>,
+    /// When the key expires (None = no known expiration)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Person or entity the key belongs to, from the key's ownership
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_id: Option<Uuid>,
 }
 
 /// Entry for a certificate in the manifest
@@ -146,6 +240,100 @@ pub struct PkiHierarchyEntry {
     pub directory_path: String,
 }
 
+/// Entry for the latest CRL published by an issuing CA
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrlManifestEntry {
+    pub issuer_ca_id: Uuid,
+    pub crl_number: u64,
+    pub this_update: DateTime<Utc>,
+    pub next_update: DateTime<Utc>,
+    pub revoked_cert_ids: Vec<Uuid>,
+    pub crl_pem: String,
+    pub file_path: String,
+}
+
+/// Entry in the certificate transparency log
+///
+/// Each entry commits to the previous leaf hash, so the log is a hash chain
+/// as well as the leaf sequence of an RFC 6962 Merkle tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateLogEntry {
+    pub index: u64,
+    pub cert_id: Uuid,
+    pub key_id: Uuid,
+    pub subject: String,
+    pub issuer: Option<String>,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    pub is_ca: bool,
+    /// Leaf hash of the preceding entry (hex), empty for the first entry
+    pub previous_hash: String,
+    /// Leaf hash of this entry (hex)
+    pub leaf_hash: String,
+}
+
+impl CertificateLogEntry {
+    /// Bytes hashed into the Merkle leaf; everything except `leaf_hash`
+    fn leaf_input(&self) -> Vec<u8> {
+        format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+            self.index,
+            self.cert_id,
+            self.key_id,
+            self.subject,
+            self.issuer.as_deref().unwrap_or(""),
+            self.not_before.to_rfc3339(),
+            self.not_after.to_rfc3339(),
+            self.is_ca,
+            self.previous_hash,
+        )
+        .into_bytes()
+    }
+
+    /// Recompute the leaf hash from the entry contents
+    pub fn compute_leaf_hash(&self) -> crate::crypto::MerkleHash {
+        crate::crypto::leaf_hash(&self.leaf_input())
+    }
+}
+
+/// Tree head of the certificate log: size and Merkle root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CertificateLogHead {
+    pub tree_size: u64,
+    /// Merkle root (hex)
+    pub root_hash: String,
+}
+
+/// Proof that a certificate is included in the log at a given tree head
+///
+/// Self-contained so an auditor can check it without the projection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificateInclusionProof {
+    pub cert_id: Uuid,
+    pub leaf_index: u64,
+    /// Leaf hash of the certificate's log entry (hex)
+    pub leaf_hash: String,
+    /// Sibling hashes from the leaf up to the root (hex)
+    pub audit_path: Vec<String>,
+    pub tree_head: CertificateLogHead,
+}
+
+impl CertificateInclusionProof {
+    /// Check the audit path against the tree head
+    pub fn verify(&self) -> bool {
+        fn decode(hex_hash: &str) -> Option<crate::crypto::MerkleHash> {
+            hex::decode(hex_hash).ok()?.try_into().ok()
+        }
+        let (Some(leaf), Some(root)) = (decode(&self.leaf_hash), decode(&self.tree_head.root_hash)) else {
+            return false;
+        };
+        let Some(path) = self.audit_path.iter().map(|h| decode(h)).collect::<Option<Vec<_>>>() else {
+            return false;
+        };
+        crate::crypto::verify_inclusion(&leaf, self.leaf_index, self.tree_head.tree_size, &path, &root)
+    }
+}
+
 /// Entry for YubiKey
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YubiKeyEntry {
@@ -156,6 +344,9 @@ pub struct YubiKeyEntry {
     /// Lifecycle state machine for this YubiKey
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub state: Option<YubiKeyState>,
+    /// Firmware version reported when the device was detected
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub firmware_version: Option<String>,
 }
 
 /// Organization information
@@ -234,6 +425,37 @@ pub struct NatsUserEntry {
     pub created_by: String,
 }
 
+/// Entry for a relationship between two entities in the manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationshipEntry {
+    pub relationship_id: Uuid,
+    pub from_id: Uuid,
+    pub to_id: Uuid,
+    pub relationship_type: RelationshipType,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: Option<DateTime<Utc>>,
+    /// When the relationship was terminated (None = still holds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminated_at: Option<DateTime<Utc>>,
+}
+
+/// Entry for a key delegation in the manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationManifestEntry {
+    pub delegation_id: Uuid,
+    pub delegator_id: Uuid,
+    pub delegate_id: Uuid,
+    pub permissions: Vec<crate::domain::KeyPermission>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: Option<DateTime<Utc>>,
+    /// When the delegation was revoked or expired (None = still active)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+    /// Whether the delegation ended by reaching `valid_until`
+    #[serde(default)]
+    pub expired: bool,
+}
+
 impl OfflineKeyProjection {
     /// Create a new projection targeting an encrypted partition
     pub fn new<P: AsRef<Path>>(root_path: P) -> Result<Self, ProjectionError> {
@@ -248,9 +470,60 @@ impl OfflineKeyProjection {
         Ok(Self {
             root_path,
             manifest,
+            event_cipher: None,
+            organization_id: None,
         })
     }
 
+    /// Open the projection of one organization under a shared root
+    ///
+    /// Each organization lives in `{root}/organizations/{organization_id}`
+    /// with its own manifest and event log. Events that name a different
+    /// organization are rejected instead of being mixed in.
+    pub fn for_organization<P: AsRef<Path>>(root_path: P, organization_id: Uuid) -> Result<Self, ProjectionError> {
+        let mut projection = Self::new(
+            root_path.as_ref().join("organizations").join(organization_id.to_string()),
+        )?;
+        projection.organization_id = Some(organization_id);
+        Ok(projection)
+    }
+
+    /// Organizations with a projection under `root_path`
+    pub fn organizations<P: AsRef<Path>>(root_path: P) -> Result<Vec<Uuid>, ProjectionError> {
+        let dir = root_path.as_ref().join("organizations");
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut organizations: Vec<Uuid> = fs::read_dir(&dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to read organizations directory: {}", e)))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join("manifest.json").exists())
+            .filter_map(|entry| Uuid::parse_str(&entry.file_name().to_string_lossy()).ok())
+            .collect();
+        organizations.sort();
+        Ok(organizations)
+    }
+
+    /// Organization this projection is restricted to
+    pub fn organization_id(&self) -> Option<Uuid> {
+        self.organization_id
+    }
+
+    /// Encrypt event log files written from now on
+    ///
+    /// Files already on disk are left as they are, but replay then rejects
+    /// plaintext files unless `cipher` was built with
+    /// [`EventLogCipher::with_plaintext_migration`].
+    pub fn with_event_encryption(mut self, cipher: EventLogCipher) -> Self {
+        self.event_cipher = Some(cipher);
+        self
+    }
+
+    /// Cipher used for the event log, if encryption is enabled
+    pub fn event_cipher(&self) -> Option<&EventLogCipher> {
+        self.event_cipher.as_ref()
+    }
+
     /// Ensure the directory structure exists on the partition
     fn ensure_directory_structure(root: &Path) -> Result<(), ProjectionError> {
         let dirs = [
@@ -281,7 +554,7 @@ impl OfflineKeyProjection {
 
             // Try to parse existing manifest
             match serde_json::from_str(&content) {
-                Ok(manifest) => Ok(manifest),
+                Ok(manifest) => journal::replay_journal(root, manifest),
                 Err(e) => {
                     // Manifest format is outdated or corrupted - back it up and create fresh
                     eprintln!("⚠️  Warning: Existing manifest is outdated/invalid: {}", e);
@@ -291,6 +564,11 @@ impl OfflineKeyProjection {
                     } else {
                         eprintln!("✓  Old manifest backed up to: {}", backup_path.display());
                     }
+                    // Its journal does not apply to the fresh manifest
+                    let journal_path = root.join(journal::JOURNAL_FILE);
+                    if journal_path.exists() {
+                        let _ = fs::rename(&journal_path, journal_path.with_extension("journal.backup"));
+                    }
 
                     // Create fresh manifest
                     let manifest = KeyManifest {
@@ -302,12 +580,24 @@ impl OfflineKeyProjection {
                         keys: Vec::new(),
                         certificates: Vec::new(),
                         pki_hierarchies: Vec::new(),
+                        crls: Vec::new(),
+                        certificate_log: Vec::new(),
                         yubikeys: Vec::new(),
                         nats_operators: Vec::new(),
                         nats_accounts: Vec::new(),
                         nats_users: Vec::new(),
+                        relationships: Vec::new(),
+                        delegations: Vec::new(),
+                        processed_commands: Vec::new(),
+                        outbox: Vec::new(),
+                        dead_letters: Vec::new(),
+                        journal_position: 0,
                         event_count: 0,
                         checksum: String::new(),
+                        file_checksums: BTreeMap::new(),
+                        signature: None,
+                        luks_uuid: None,
+                        kdf_salts: Vec::new(),
                     };
 
                     Ok(manifest)
@@ -323,26 +613,71 @@ impl OfflineKeyProjection {
                 keys: Vec::new(),
                 certificates: Vec::new(),
                 pki_hierarchies: Vec::new(),
+                crls: Vec::new(),
+                certificate_log: Vec::new(),
                 yubikeys: Vec::new(),
                 nats_operators: Vec::new(),
                 nats_accounts: Vec::new(),
                 nats_users: Vec::new(),
+                relationships: Vec::new(),
+                delegations: Vec::new(),
+                processed_commands: Vec::new(),
+                outbox: Vec::new(),
+                dead_letters: Vec::new(),
+                journal_position: 0,
                 event_count: 0,
                 checksum: String::new(),
+                file_checksums: BTreeMap::new(),
+                signature: None,
+                luks_uuid: None,
+                kdf_salts: Vec::new(),
             };
 
-            Ok(manifest)
+            // Records appended before the first checkpoint was written
+            journal::replay_journal(root, manifest)
         }
     }
 
     /// Apply an event and update the projection files
+    ///
+    /// The event is appended to the log and queued in the outbox for
+    /// publication by [`flush_outbox`](Self::flush_outbox). An event that
+    /// cannot be applied is parked in the dead letters rather than failing
+    /// (see [`retry_dead_letters`](Self::retry_dead_letters)).
     pub fn apply(&mut self, event: &DomainEvent) -> Result<(), ProjectionError> {
+        self.check_organization(event)?;
+
         // First, append event to the event log
-        self.append_event(event)?;
+        let (event_id, event_file) = self.append_event(event)?;
+        self.manifest.outbox.push(OutboxEntry::pending(event, event_id, event_file.clone()));
 
         // Then update the specific projections
+        self.project_or_dead_letter(event, event_file)
+    }
+
+    /// Apply an event that was already published, e.g. from a JetStream
+    /// replay; it is logged but not queued in the outbox
+    pub fn replay(&mut self, event: &DomainEvent) -> Result<(), ProjectionError> {
+        self.check_organization(event)?;
+        let (_, event_file) = self.append_event(event)?;
+        self.project_or_dead_letter(event, event_file)
+    }
+
+    /// Reject events of another organization in an organization projection
+    fn check_organization(&self, event: &DomainEvent) -> Result<(), ProjectionError> {
+        match (self.organization_id, event.organization_id()) {
+            (Some(expected), Some(found)) if expected != found => Err(ProjectionError::OrganizationMismatch(
+                crate::domain::nats::TenancyError::OrganizationMismatch { expected, found }.to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Update the projection files and manifest for an event
+    fn project(&mut self, event: &DomainEvent) -> Result<(), ProjectionError> {
         use crate::events::{KeyEvents, CertificateEvents, PersonEvents, LocationEvents, OrganizationEvents,
-                           NatsOperatorEvents, NatsAccountEvents, NatsUserEvents, YubiKeyEvents};
+                           NatsOperatorEvents, NatsAccountEvents, NatsUserEvents, YubiKeyEvents,
+                           RelationshipEvents, DelegationEvents};
         match event {
             // Key aggregate events
             DomainEvent::Key(KeyEvents::KeyGenerated(e)) => self.project_key_generated(e)?,
@@ -352,6 +687,8 @@ impl OfflineKeyProjection {
             DomainEvent::Key(KeyEvents::KeyRevoked(e)) => self.project_key_revoked(e)?,
             DomainEvent::Key(KeyEvents::KeyRotationInitiated(e)) => self.project_key_rotation_initiated(e)?,
             DomainEvent::Key(KeyEvents::KeyRotationCompleted(e)) => self.project_key_rotation_completed(e)?,
+            DomainEvent::Key(KeyEvents::KeyExpirationExtended(e)) => self.project_key_expiration_extended(e)?,
+            DomainEvent::Key(KeyEvents::KeyMovedToCard(e)) => self.project_key_moved_to_card(e)?,
 
             // Certificate aggregate events
             DomainEvent::Certificate(CertificateEvents::CertificateGenerated(e)) => self.project_certificate_generated(e)?,
@@ -362,6 +699,7 @@ impl OfflineKeyProjection {
             DomainEvent::Certificate(CertificateEvents::CertificateRevoked(e)) => self.project_certificate_revoked(e)?,
             DomainEvent::Certificate(CertificateEvents::CertificateExpired(e)) => self.project_certificate_expired(e)?,
             DomainEvent::Certificate(CertificateEvents::CertificateRenewed(e)) => self.project_certificate_renewed(e)?,
+            DomainEvent::Certificate(CertificateEvents::CrlPublished(e)) => self.project_crl_published(e)?,
 
             // YubiKey aggregate events
             DomainEvent::YubiKey(YubiKeyEvents::YubiKeyDetected(e)) => self.project_yubikey_detected(e)?,
@@ -372,6 +710,7 @@ impl OfflineKeyProjection {
             DomainEvent::Person(PersonEvents::PersonActivated(e)) => self.project_person_activated(e)?,
             DomainEvent::Person(PersonEvents::PersonSuspended(e)) => self.project_person_suspended(e)?,
             DomainEvent::Person(PersonEvents::PersonReactivated(e)) => self.project_person_reactivated(e)?,
+            DomainEvent::Person(PersonEvents::PersonDeactivated(e)) => self.project_person_deactivated(e)?,
             DomainEvent::Person(PersonEvents::PersonArchived(e)) => self.project_person_archived(e)?,
 
             // Location aggregate events
@@ -384,12 +723,33 @@ impl OfflineKeyProjection {
             // Organization aggregate events
             DomainEvent::Organization(OrganizationEvents::OrganizationCreated(e)) => self.project_organization_created(e)?,
 
+            // Relationship aggregate events
+            DomainEvent::Relationship(RelationshipEvents::RelationshipEstablished(e)) => self.project_relationship_established(e)?,
+            DomainEvent::Relationship(RelationshipEvents::RelationshipTerminated(e)) => self.project_relationship_terminated(e)?,
+
+            // Delegation aggregate events
+            DomainEvent::Delegation(DelegationEvents::DelegationCreated(e)) => self.project_delegation_created(e)?,
+            DomainEvent::Delegation(DelegationEvents::DelegationRevoked(e)) => {
+                self.project_delegation_ended(e.delegation_id, e.revoked_at, false)?
+            }
+            DomainEvent::Delegation(DelegationEvents::DelegationCascadeRevoked(e)) => {
+                self.project_delegation_ended(e.delegation_id, e.revoked_at, false)?
+            }
+            DomainEvent::Delegation(DelegationEvents::DelegationExpired(e)) => {
+                self.project_delegation_ended(e.delegation_id, e.valid_until, true)?
+            }
+            DomainEvent::Delegation(DelegationEvents::DelegationExtended(e)) => self.project_delegation_extended(e)?,
+            DomainEvent::Delegation(DelegationEvents::DelegationPermissionsModified(e)) => {
+                self.project_delegation_permissions_modified(e)?
+            }
+
             // NATS Operator aggregate events
             DomainEvent::NatsOperator(NatsOperatorEvents::NatsOperatorCreated(e)) => self.project_nats_operator_created(e)?,
             DomainEvent::NatsOperator(NatsOperatorEvents::NatsOperatorSuspended(e)) => self.project_nats_operator_suspended(e)?,
             DomainEvent::NatsOperator(NatsOperatorEvents::NatsOperatorReactivated(e)) => self.project_nats_operator_reactivated(e)?,
             DomainEvent::NatsOperator(NatsOperatorEvents::NatsOperatorRevoked(e)) => self.project_nats_operator_revoked(e)?,
             DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyGenerated(e)) => self.project_nats_signing_key_generated(e)?,
+            DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyRetired(e)) => self.project_nats_signing_key_retired(e)?,
             DomainEvent::NatsOperator(NatsOperatorEvents::NatsConfigExported(e)) => self.project_nats_config_exported(e)?,
             DomainEvent::NatsOperator(NatsOperatorEvents::NKeyGenerated(e)) => self.project_nkey_generated(e)?,
             DomainEvent::NatsOperator(NatsOperatorEvents::JwtClaimsCreated(e)) => self.project_jwt_claims_created(e)?,
@@ -402,6 +762,14 @@ impl OfflineKeyProjection {
             DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountReactivated(e)) => self.project_nats_account_reactivated(e)?,
             DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountDeleted(e)) => self.project_nats_account_deleted(e)?,
             DomainEvent::NatsAccount(NatsAccountEvents::NatsPermissionsSet(e)) => self.project_nats_permissions_set(e)?,
+            DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountLimitsSet(e)) => self.project_nats_account_limits_set(e)?,
+            DomainEvent::NatsAccount(NatsAccountEvents::NatsUserRevoked(e)) => self.project_nats_user_revoked(e)?,
+            DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountExportDeclared(e)) => {
+                self.project_nats_account_shared_subject(e.account_id, "exports.json", &e.export.subject, serde_json::json!(e.export), &e.account_jwt)?
+            }
+            DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountImportDeclared(e)) => {
+                self.project_nats_account_shared_subject(e.account_id, "imports.json", &e.import.subject, serde_json::json!(e.import), &e.account_jwt)?
+            }
 
             // NATS User aggregate events
             DomainEvent::NatsUser(NatsUserEvents::NatsUserCreated(e)) => self.project_nats_user_created(e)?,
@@ -411,37 +779,72 @@ impl OfflineKeyProjection {
             DomainEvent::NatsUser(NatsUserEvents::NatsUserDeleted(e)) => self.project_nats_user_deleted(e)?,
             DomainEvent::NatsUser(NatsUserEvents::ServiceAccountCreated(e)) => self.project_service_account_created(e)?,
             DomainEvent::NatsUser(NatsUserEvents::AgentCreated(e)) => self.project_agent_created(e)?,
+            DomainEvent::NatsUser(NatsUserEvents::NatsCredentialsDelivered(e)) => self.project_nats_credentials_delivered(e)?,
+            DomainEvent::NatsUser(NatsUserEvents::EdgeSiteCredentialsIssued(e)) => self.project_edge_site_credentials_issued(e)?,
 
             _ => {} // Handle other events as needed
         }
 
-        // Update manifest
+        // Update manifest; the caller journals the change
         self.manifest.updated_at = Utc::now();
         self.manifest.event_count += 1;
-        self.save_manifest()?;
 
         Ok(())
     }
 
+    /// Current manifest
+    pub fn manifest(&self) -> &KeyManifest {
+        &self.manifest
+    }
+
     /// Check if a key exists in the projection
     pub fn key_exists(&self, key_id: &Uuid) -> bool {
         self.manifest.keys.iter().any(|k| k.key_id == *key_id)
     }
 
-    /// Append event to the event log
-    fn append_event(&self, event: &DomainEvent) -> Result<(), ProjectionError> {
+    /// Check if a command's events were already applied
+    pub fn was_processed(&self, command_id: Uuid) -> bool {
+        self.manifest.processed_commands.contains(&command_id)
+    }
+
+    /// Record that a command's events have been applied
+    ///
+    /// Call after applying the events, so a crash in between leads to a
+    /// retry rather than a lost command. The oldest IDs fall out once
+    /// `PROCESSED_COMMAND_WINDOW` is reached.
+    pub fn record_processed_command(&mut self, command_id: Uuid) -> Result<(), ProjectionError> {
+        if self.was_processed(command_id) {
+            return Ok(());
+        }
+
+        let processed = &mut self.manifest.processed_commands;
+        processed.push(command_id);
+        if processed.len() > PROCESSED_COMMAND_WINDOW {
+            processed.drain(..processed.len() - PROCESSED_COMMAND_WINDOW);
+        }
+
+        self.manifest.updated_at = Utc::now();
+        self.commit_processed_command(command_id)
+    }
+
+    /// Append event to the event log, returning its event ID and file name
+    fn append_event(&self, event: &DomainEvent) -> Result<(Uuid, String), ProjectionError> {
         let event_id = Uuid::now_v7();
         let timestamp = Utc::now().timestamp_nanos_opt().unwrap_or(0);
         let filename = format!("{}_{}.json", timestamp, event_id);
-        let event_path = self.root_path.join("events").join(filename);
+        let event_path = self.root_path.join("events").join(&filename);
 
-        let event_json = serde_json::to_string_pretty(event)
+        let mut event_json = serde_json::to_string_pretty(event)
             .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize event: {}", e)))?;
+        if let Some(cipher) = &self.event_cipher {
+            event_json = cipher.encrypt(event_json.as_bytes(), &filename)
+                .map_err(|e| ProjectionError::EncryptionError(e.to_string()))?;
+        }
 
-        fs::write(&event_path, event_json)
+        journal::write_atomic(&event_path, event_json.as_bytes())
             .map_err(|e| ProjectionError::IoError(format!("Failed to write event: {}", e)))?;
 
-        Ok(())
+        Ok((event_id, filename))
     }
 
     /// Project a key generation event
@@ -476,8 +879,12 @@ impl OfflineKeyProjection {
             purpose: event.purpose.clone(),
             label: event.metadata.label.clone(),
             hardware_backed: event.hardware_backed,
-            yubikey_serial: None,
-            yubikey_slot: None,
+            yubikey_serial: event.metadata.hardware_serial.clone(),
+            // HSM keys: "pkcs11-{slot}/{label}"
+            yubikey_slot: event.metadata.attributes.get(crate::commands::hsm::HSM_SLOT_ATTRIBUTE).map(|slot| {
+                let label = event.metadata.attributes.get(crate::commands::hsm::HSM_LABEL_ATTRIBUTE);
+                format!("pkcs11-{}/{}", slot, label.map(String::as_str).unwrap_or_default())
+            }),
             revoked: false,
             file_path: format!("keys/{}", event.key_id),
             // Initialize state machine
@@ -486,6 +893,8 @@ impl OfflineKeyProjection {
                 generated_at: event.generated_at,
                 generated_by: Uuid::now_v7(), // TODO: Get from event ownership
             }),
+            expires_at: event.expires_at,
+            owner_id: event.ownership.as_ref().map(|o| o.owner_id),
         });
 
         Ok(())
@@ -507,6 +916,7 @@ impl OfflineKeyProjection {
             "imported_at": event.imported_at,
             "imported_by": event.imported_by,
             "metadata": event.metadata,
+            "ownership": event.ownership,
         });
 
         let metadata_json = serde_json::to_string_pretty(&import_info)
@@ -526,11 +936,38 @@ impl OfflineKeyProjection {
         fs::write(&import_marker_path, serde_json::to_string_pretty(&import_marker).unwrap())
             .map_err(|e| ProjectionError::IoError(format!("Failed to write import marker: {}", e)))?;
 
+        // Imported SSH public keys: write the authorized_keys line (never private material)
+        let ssh_public_key = match event.format {
+            crate::types::KeyFormat::SshPublicKey => event
+                .metadata
+                .attributes
+                .get("public_key")
+                .and_then(|line| ssh_key::PublicKey::from_openssh(line).ok()),
+            _ => None,
+        };
+        if let Some(public_key) = &ssh_public_key {
+            let line = public_key.to_openssh()
+                .map_err(|e| ProjectionError::SerializationError(format!("Failed to encode SSH public key: {}", e)))?;
+            fs::write(key_dir.join("id.pub"), format!("{}\n", line))
+                .map_err(|e| ProjectionError::IoError(format!("Failed to write SSH public key: {}", e)))?;
+        }
+
+        let (algorithm, purpose) = match ssh_public_key
+            .as_ref()
+            .and_then(crate::commands::ssh::ssh_key_algorithm)
+        {
+            Some(algorithm) => (algorithm, KeyPurpose::Authentication),
+            None => (
+                crate::events::KeyAlgorithm::Ed25519,  // TODO: Get from event.metadata
+                crate::events::KeyPurpose::Signing,    // TODO: Get from event.metadata
+            ),
+        };
+
         // Add to manifest with Imported state
         self.manifest.keys.push(KeyEntry {
             key_id: event.key_id,
-            algorithm: crate::events::KeyAlgorithm::Ed25519,  // TODO: Get from event.metadata
-            purpose: crate::events::KeyPurpose::Signing,      // TODO: Get from event.metadata
+            algorithm,
+            purpose,
             label: event.metadata.label.clone(),
             hardware_backed: false,  // Imported keys are typically not hardware-backed
             yubikey_serial: None,
@@ -563,6 +1000,8 @@ impl OfflineKeyProjection {
                 imported_at: event.imported_at,
                 imported_by: Uuid::now_v7(),  // TODO: Parse imported_by string to UUID
             }),
+            expires_at: None,
+            owner_id: event.ownership.as_ref().map(|o| o.owner_id),
         });
 
         Ok(())
@@ -720,6 +1159,86 @@ impl OfflineKeyProjection {
         Ok(())
     }
 
+    /// Project a relationship establishment (replaces an earlier entry with the same ID)
+    fn project_relationship_established(&mut self, event: &crate::events::relationship::RelationshipEstablishedEvent) -> Result<(), ProjectionError> {
+        self.manifest.relationships.retain(|r| r.relationship_id != event.relationship_id);
+        self.manifest.relationships.push(RelationshipEntry {
+            relationship_id: event.relationship_id,
+            from_id: event.from_id,
+            to_id: event.to_id,
+            relationship_type: event.relationship_type.clone(),
+            valid_from: event.valid_from,
+            valid_until: event.valid_until,
+            terminated_at: None,
+        });
+
+        Ok(())
+    }
+
+    /// Project a relationship termination (the entry is kept for history)
+    fn project_relationship_terminated(&mut self, event: &crate::events::relationship::RelationshipTerminatedEvent) -> Result<(), ProjectionError> {
+        let entry = self.manifest.relationships
+            .iter_mut()
+            .find(|r| r.relationship_id == event.relationship_id)
+            .ok_or_else(|| ProjectionError::NotFound(format!("Relationship {} not found", event.relationship_id)))?;
+        entry.terminated_at = Some(event.terminated_at);
+
+        Ok(())
+    }
+
+    /// Project a delegation creation (replaces an earlier entry with the same ID)
+    fn project_delegation_created(&mut self, event: &crate::events::delegation::DelegationCreatedEvent) -> Result<(), ProjectionError> {
+        self.manifest.delegations.retain(|d| d.delegation_id != event.delegation_id);
+        self.manifest.delegations.push(DelegationManifestEntry {
+            delegation_id: event.delegation_id,
+            delegator_id: event.delegator_id,
+            delegate_id: event.delegate_id,
+            permissions: event.permissions.clone(),
+            valid_from: event.valid_from,
+            valid_until: event.valid_until,
+            revoked_at: None,
+            expired: false,
+        });
+
+        Ok(())
+    }
+
+    /// Project the end of a delegation by revocation or expiry (the entry is kept for history)
+    fn project_delegation_ended(&mut self, delegation_id: Uuid, ended_at: DateTime<Utc>, expired: bool) -> Result<(), ProjectionError> {
+        let entry = self.delegation_entry(delegation_id)?;
+        entry.revoked_at = Some(ended_at);
+        entry.expired = expired;
+
+        Ok(())
+    }
+
+    /// Project a delegation's new validity period
+    fn project_delegation_extended(&mut self, event: &crate::events::delegation::DelegationExtendedEvent) -> Result<(), ProjectionError> {
+        self.delegation_entry(event.delegation_id)?.valid_until = event.new_valid_until;
+
+        Ok(())
+    }
+
+    /// Project a change to a delegation's permissions
+    fn project_delegation_permissions_modified(&mut self, event: &crate::events::delegation::DelegationPermissionsModifiedEvent) -> Result<(), ProjectionError> {
+        let entry = self.delegation_entry(event.delegation_id)?;
+        entry.permissions.retain(|p| !event.permissions_removed.contains(p));
+        for permission in &event.permissions_added {
+            if !entry.permissions.contains(permission) {
+                entry.permissions.push(permission.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn delegation_entry(&mut self, delegation_id: Uuid) -> Result<&mut DelegationManifestEntry, ProjectionError> {
+        self.manifest.delegations
+            .iter_mut()
+            .find(|d| d.delegation_id == delegation_id)
+            .ok_or_else(|| ProjectionError::NotFound(format!("Delegation {} not found", delegation_id)))
+    }
+
     /// Project a NATS operator creation event (initialize operator entry)
     fn project_nats_operator_created(&mut self, event: &crate::events::nats_operator::NatsOperatorCreatedEvent) -> Result<(), ProjectionError> {
         // Create NATS operator directory
@@ -1109,7 +1628,197 @@ impl OfflineKeyProjection {
         Ok(())
     }
 
+    /// Project a NATS signing key retirement event (operational)
+    ///
+    /// Marks the key's metadata file (written on generation) as retired.
+    fn project_nats_signing_key_retired(&mut self, event: &crate::events::nats_operator::NatsSigningKeyRetiredEvent) -> Result<(), ProjectionError> {
+        let entity_dir = match event.entity_type {
+            crate::types::NatsEntityType::Operator => {
+                self.root_path.join("nats").join("operators").join(event.entity_id.to_string())
+            }
+            crate::types::NatsEntityType::Account => {
+                self.root_path.join("nats").join("accounts").join(event.entity_id.to_string())
+            }
+            crate::types::NatsEntityType::User => {
+                self.root_path.join("nats").join("users").join(event.entity_id.to_string())
+            }
+        };
+        let signing_keys_dir = entity_dir.join("signing_keys");
+        let Ok(entries) = fs::read_dir(&signing_keys_dir) else {
+            return Ok(());
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(mut key_info) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            else {
+                continue;
+            };
+            if key_info["public_key"] != event.public_key.as_str() {
+                continue;
+            }
+
+            key_info["retired_at"] = serde_json::json!(event.retired_at);
+            key_info["replaced_by"] = serde_json::json!(event.replaced_by);
+            key_info["retirement_reason"] = serde_json::json!(event.reason);
+            fs::write(&path, serde_json::to_string_pretty(&key_info).unwrap())
+                .map_err(|e| ProjectionError::IoError(format!("Failed to write signing key metadata: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    /// Project a credentials delivery: who received which users, no material
+    fn project_nats_credentials_delivered(&mut self, event: &crate::events::nats_user::NatsCredentialsDeliveredEvent) -> Result<(), ProjectionError> {
+        let delivery_dir = self.root_path
+            .join("nats")
+            .join("deliveries")
+            .join(event.person_id.to_string());
+        fs::create_dir_all(&delivery_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create delivery directory: {}", e)))?;
+
+        let delivery_info = serde_json::json!({
+            "delivery_id": event.delivery_id,
+            "person_id": event.person_id,
+            "user_public_keys": event.user_public_keys,
+            "protection": event.protection,
+            "recipients": event.recipients,
+            "bundle_checksum": event.bundle_checksum,
+            "delivered_at": event.delivered_at,
+            "correlation_id": event.correlation_id,
+        });
+        fs::write(
+            delivery_dir.join(format!("{}.json", event.delivery_id)),
+            serde_json::to_string_pretty(&delivery_info).unwrap(),
+        )
+        .map_err(|e| ProjectionError::IoError(format!("Failed to write delivery record: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Project an edge site issuance: which identity and certificate a Location holds
+    fn project_edge_site_credentials_issued(&mut self, event: &crate::events::nats_user::EdgeSiteCredentialsIssuedEvent) -> Result<(), ProjectionError> {
+        let site_dir = self.root_path
+            .join("nats")
+            .join("edge")
+            .join(event.location_id.to_string());
+        fs::create_dir_all(&site_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create edge site directory: {}", e)))?;
+
+        let site_info = serde_json::json!({
+            "location_id": event.location_id,
+            "location_name": event.location_name,
+            "user_id": event.user_id,
+            "user_public_key": event.user_public_key,
+            "account_id": event.account_id,
+            "account_public_key": event.account_public_key,
+            "certificate_id": event.certificate_id,
+            "certificate_fingerprint": event.certificate_fingerprint,
+            "server_names": event.server_names,
+            "issued_at": event.issued_at,
+            "correlation_id": event.correlation_id,
+        });
+        fs::write(
+            site_dir.join(format!("{}.json", event.user_id)),
+            serde_json::to_string_pretty(&site_info).unwrap(),
+        )
+        .map_err(|e| ProjectionError::IoError(format!("Failed to write edge site record: {}", e)))?;
+
+        Ok(())
+    }
+
     /// Project a NATS permissions set event (operational)
+    fn project_nats_account_limits_set(&mut self, event: &crate::events::nats_account::NatsAccountLimitsSetEvent) -> Result<(), ProjectionError> {
+        let account_dir = self.root_path
+            .join("nats")
+            .join("accounts")
+            .join(event.account_id.to_string());
+        fs::create_dir_all(&account_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create account directory: {}", e)))?;
+
+        // Write limits file (overwrite previous)
+        let limits_info = serde_json::json!({
+            "limits": event.limits,
+            "jetstream_enabled": event.limits.jetstream_enabled(),
+            "set_at": event.set_at,
+            "set_by": event.set_by,
+        });
+        fs::write(account_dir.join("limits.json"), serde_json::to_string_pretty(&limits_info).unwrap())
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write limits file: {}", e)))?;
+
+        // The re-signed JWT supersedes the previous one
+        fs::write(account_dir.join("account.jwt"), &event.account_jwt)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write account JWT: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Project an account export or import: upsert by subject, replace the JWT
+    fn project_nats_account_shared_subject(
+        &mut self,
+        account_id: Uuid,
+        file_name: &str,
+        subject: &str,
+        entry: serde_json::Value,
+        account_jwt: &str,
+    ) -> Result<(), ProjectionError> {
+        let account_dir = self.root_path
+            .join("nats")
+            .join("accounts")
+            .join(account_id.to_string());
+        fs::create_dir_all(&account_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create account directory: {}", e)))?;
+
+        let path = account_dir.join(file_name);
+        let mut entries: Vec<serde_json::Value> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        entries.retain(|existing| existing["subject"] != subject);
+        entries.push(entry);
+        fs::write(&path, serde_json::to_string_pretty(&entries).unwrap())
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write {}: {}", file_name, e)))?;
+
+        // The re-signed JWT supersedes the previous one
+        fs::write(account_dir.join("account.jwt"), account_jwt)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write account JWT: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn project_nats_user_revoked(&mut self, event: &crate::events::nats_account::NatsUserRevokedEvent) -> Result<(), ProjectionError> {
+        let account_dir = self.root_path
+            .join("nats")
+            .join("accounts")
+            .join(event.account_id.to_string());
+        fs::create_dir_all(&account_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create account directory: {}", e)))?;
+
+        // Append to the account's revocation list
+        let revocations_path = account_dir.join("revocations.json");
+        let mut revocations: Vec<serde_json::Value> = fs::read_to_string(&revocations_path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        revocations.push(serde_json::json!({
+            "user_id": event.user_id,
+            "user_public_key": event.user_public_key,
+            "reason": event.reason,
+            "revoked_at": event.revoked_at,
+            "revoked_by": event.revoked_by,
+        }));
+        fs::write(&revocations_path, serde_json::to_string_pretty(&revocations).unwrap())
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write revocations file: {}", e)))?;
+
+        // The re-signed JWT supersedes the previous one
+        fs::write(account_dir.join("account.jwt"), &event.account_jwt)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write account JWT: {}", e)))?;
+
+        Ok(())
+    }
+
     fn project_nats_permissions_set(&mut self, event: &crate::events::nats_account::NatsPermissionsSetEvent) -> Result<(), ProjectionError> {
         // Determine entity directory based on type
         let entity_dir = match event.entity_type {
@@ -1445,6 +2154,19 @@ impl OfflineKeyProjection {
             }),
         });
 
+        // Append to the transparency log
+        let cert = self.manifest.certificates[self.manifest.certificates.len() - 1].clone();
+        let log_entry = self.manifest.log_certificate(&cert);
+        let log_line = serde_json::to_string(log_entry)
+            .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize log entry: {}", e)))?;
+        let mut log_file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.root_path.join("certificates").join("transparency.jsonl"))
+            .map_err(|e| ProjectionError::IoError(format!("Failed to open certificate log: {}", e)))?;
+        writeln!(log_file, "{}", log_line)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to append to certificate log: {}", e)))?;
+
         Ok(())
     }
 
@@ -1553,6 +2275,46 @@ impl OfflineKeyProjection {
         fs::write(&state_path, serde_json::to_string_pretty(&state_info).unwrap())
             .map_err(|e| ProjectionError::IoError(format!("Failed to write state file: {}", e)))?;
 
+        // Track the revocation in the manifest so the next CRL picks it up
+        if let Some(cert) = self.manifest.certificates.iter_mut().find(|c| c.cert_id == event.cert_id) {
+            let revoked_by = match &event.revoked_by {
+                ActorId::Person { id } | ActorId::ServiceAccount { id, .. } => *id,
+                _ => Uuid::nil(),
+            };
+            cert.state = Some(CertificateState::Revoked {
+                reason: crate::crypto::parse_revocation_reason(&event.reason),
+                revoked_at: event.revoked_at,
+                revoked_by,
+                crl_published: false,
+                ocsp_updated: false,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Project CRL published event
+    ///
+    /// Writes `certificates/{issuer_ca_id}/crl.pem`, replaces the issuer's
+    /// manifest CRL entry and marks the listed revocations as published.
+    fn project_crl_published(&mut self, event: &crate::events::CrlPublishedEvent) -> Result<(), ProjectionError> {
+        let issuer_dir = self.root_path
+            .join("certificates")
+            .join(event.issuer_ca_id.to_string());
+        fs::create_dir_all(&issuer_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create certificate directory: {}", e)))?;
+        fs::write(issuer_dir.join("crl.pem"), &event.crl_pem)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write CRL: {}", e)))?;
+
+        for cert in self.manifest.certificates.iter_mut() {
+            if let Some(CertificateState::Revoked { crl_published, .. }) = &mut cert.state {
+                if event.revoked_cert_ids.contains(&cert.cert_id) {
+                    *crl_published = true;
+                }
+            }
+        }
+        self.manifest.record_crl(event);
+
         Ok(())
     }
 
@@ -1607,6 +2369,7 @@ impl OfflineKeyProjection {
             "state": "Active",
             "renewed_from": event.old_cert_id,
             "renewed_at": event.renewed_at,
+            "key_reused": event.key_reused,
             "correlation_id": event.correlation_id,
         });
         fs::write(&new_state_path, serde_json::to_string_pretty(&new_state_info).unwrap())
@@ -1674,6 +2437,24 @@ impl OfflineKeyProjection {
         Ok(())
     }
 
+    fn project_person_deactivated(&mut self, event: &crate::events::person::PersonDeactivatedEvent) -> Result<(), ProjectionError> {
+        let person_dir = self.root_path
+            .join("people")
+            .join(event.person_id.to_string());
+
+        let state_path = person_dir.join("state.json");
+        let state_info = serde_json::json!({
+            "state": "Deactivated",
+            "reason": event.reason,
+            "deactivated_at": event.deactivated_at,
+            "deactivated_by": event.deactivated_by,
+            "correlation_id": event.correlation_id,
+        });
+        fs::write(&state_path, serde_json::to_string_pretty(&state_info).unwrap())
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write person state: {}", e)))?;
+        Ok(())
+    }
+
     fn project_person_archived(&mut self, event: &crate::events::person::PersonArchivedEvent) -> Result<(), ProjectionError> {
         let person_dir = self.root_path
             .join("people")
@@ -1805,6 +2586,7 @@ impl OfflineKeyProjection {
                 detected_at: event.detected_at,
                 detected_by: Uuid::now_v7(), // TODO: Get from event
             }),
+            firmware_version: Some(event.firmware_version.clone()),
         });
 
         Ok(())
@@ -1861,6 +2643,7 @@ impl OfflineKeyProjection {
                     pin_changed: true, // Assume changed during provisioning
                     puk_changed: true, // Assume changed during provisioning
                 }),
+                firmware_version: None,
             });
         }
 
@@ -1936,21 +2719,7 @@ impl OfflineKeyProjection {
             key.revoked = true;
 
             // Convert event revocation reason to state machine revocation reason
-            use crate::types::RevocationReason as EventReason;
-            use crate::state_machines::key::RevocationReason as StateReason;
-
-            let state_reason = match &event.reason {
-                EventReason::KeyCompromise => StateReason::Compromised,
-                EventReason::CaCompromise => StateReason::Administrative {
-                    reason: "CA compromised".to_string(),
-                },
-                EventReason::AffiliationChanged => StateReason::EmployeeTermination,
-                EventReason::Superseded => StateReason::Superseded,
-                EventReason::CessationOfOperation => StateReason::CessationOfOperation,
-                EventReason::Unspecified => StateReason::Administrative {
-                    reason: "Unspecified".to_string(),
-                },
-            };
+            let state_reason = crate::state_machines::key::RevocationReason::from(&event.reason);
 
             // Transition state machine to Revoked
             if let Some(current_state) = &key.state {
@@ -1991,6 +2760,55 @@ impl OfflineKeyProjection {
         Ok(())
     }
 
+    /// Project a key expiration extension onto the key's `expires_at`
+    fn project_key_expiration_extended(&mut self, event: &crate::events::key::KeyExpirationExtendedEvent) -> Result<(), ProjectionError> {
+        let key = self.manifest.keys.iter_mut()
+            .find(|k| k.key_id == event.key_id)
+            .ok_or_else(|| ProjectionError::NotFound(format!("Key {} not found", event.key_id)))?;
+        key.expires_at = event.new_expires_at;
+
+        // Write expiration record to key directory
+        let key_dir = self.root_path.join("keys").join(event.key_id.to_string());
+        fs::create_dir_all(&key_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create key directory: {}", e)))?;
+
+        let expiration_json = serde_json::to_string_pretty(&event)
+            .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize expiration: {}", e)))?;
+
+        fs::write(key_dir.join("EXPIRATION.json"), expiration_json)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write expiration: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Project a key moved to a hardware token (record hardware binding)
+    fn project_key_moved_to_card(&mut self, event: &crate::events::key::KeyMovedToCardEvent) -> Result<(), ProjectionError> {
+        let key = self.manifest.keys.iter_mut()
+            .find(|k| k.key_id == event.key_id)
+            .ok_or_else(|| ProjectionError::NotFound(format!("Key {} not found", event.key_id)))?;
+        key.hardware_backed = true;
+        key.yubikey_serial = Some(event.yubikey_serial.clone());
+        key.yubikey_slot = Some(
+            event.slots.iter()
+                .map(|slot| format!("openpgp-{:?}", slot).to_lowercase())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+
+        // Write hardware binding to key directory
+        let key_dir = self.root_path.join("keys").join(event.key_id.to_string());
+        fs::create_dir_all(&key_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create key directory: {}", e)))?;
+
+        let binding_json = serde_json::to_string_pretty(&event)
+            .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize hardware binding: {}", e)))?;
+
+        fs::write(key_dir.join("HARDWARE.json"), binding_json)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write hardware binding: {}", e)))?;
+
+        Ok(())
+    }
+
     /// Save the manifest to disk
     /// Update organization information
     pub fn set_organization(&mut self, name: String, domain: String, country: String, admin_email: String) -> Result<(), ProjectionError> {
@@ -2010,6 +2828,30 @@ impl OfflineKeyProjection {
         &self.manifest.organization
     }
 
+    /// Record the LUKS2 volume this projection is stored on
+    ///
+    /// Call while the volume is open, with `OpenVolume::luks_uuid`, so the
+    /// card can later be matched to its manifest.
+    pub fn record_luks_volume(&mut self, luks_uuid: impl Into<String>) -> Result<(), ProjectionError> {
+        self.manifest.luks_uuid = Some(luks_uuid.into());
+        self.manifest.updated_at = Utc::now();
+        self.save_manifest()
+    }
+
+    /// Record the salt of a passphrase-derived key, replacing any earlier
+    /// salt for the same purpose
+    pub fn record_kdf_salt(&mut self, salt: KdfSalt) -> Result<(), ProjectionError> {
+        self.manifest.kdf_salts.retain(|s| s.purpose != salt.purpose);
+        self.manifest.kdf_salts.push(salt);
+        self.manifest.updated_at = Utc::now();
+        self.save_manifest()
+    }
+
+    /// Salt for re-deriving the key for `purpose`, if one was recorded
+    pub fn kdf_salt(&self, purpose: KdfPurpose) -> Option<&KdfSalt> {
+        self.manifest.kdf_salts.iter().find(|s| s.purpose == purpose)
+    }
+
     /// Add a person to the organization
     pub fn add_person(&mut self, person_id: Uuid, name: String, email: String, role: String, organization_id: Uuid) -> Result<(), ProjectionError> {
         let person_entry = PersonEntry {
@@ -2090,11 +2932,115 @@ impl OfflineKeyProjection {
         &self.manifest.keys
     }
 
+    /// Get non-revoked keys expiring within `days` of `now`
+    ///
+    /// Already expired keys are included so the dashboard can flag them.
+    /// Results are ordered by expiration, soonest first.
+    pub fn keys_expiring_within(&self, days: i64, now: DateTime<Utc>) -> Vec<&KeyEntry> {
+        let horizon = now + chrono::Duration::days(days);
+        let mut expiring: Vec<&KeyEntry> = self.manifest.keys.iter()
+            .filter(|k| !k.revoked)
+            .filter(|k| k.expires_at.is_some_and(|expires| expires <= horizon))
+            .collect();
+        expiring.sort_by_key(|k| k.expires_at);
+        expiring
+    }
+
+    /// Get all delegations, including revoked and expired ones
+    pub fn get_delegations(&self) -> &[DelegationManifestEntry] {
+        &self.manifest.delegations
+    }
+
+    /// Get active delegations expiring within `days` of `now`
+    ///
+    /// Delegations past `valid_until` whose `DelegationExpired` has not been
+    /// recorded yet are included so the GUI can flag them. Results are
+    /// ordered by expiration, soonest first.
+    pub fn delegations_expiring_within(&self, days: i64, now: DateTime<Utc>) -> Vec<&DelegationManifestEntry> {
+        let horizon = now + chrono::Duration::days(days);
+        let mut expiring: Vec<&DelegationManifestEntry> = self.manifest.delegations.iter()
+            .filter(|d| d.revoked_at.is_none())
+            .filter(|d| d.valid_until.is_some_and(|until| until <= horizon))
+            .collect();
+        expiring.sort_by_key(|d| d.valid_until);
+        expiring
+    }
+
     /// Get all YubiKeys
     pub fn get_yubikeys(&self) -> &[YubiKeyEntry] {
         &self.manifest.yubikeys
     }
 
+    /// Store an issued certificate and its chain
+    ///
+    /// Writes `certificates/{cert_id}/cert.pem` and `chain.pem` (leaf first,
+    /// root last). Certificate events carry no PEM, so callers that issue
+    /// certificates store the material here after applying the events.
+    pub fn store_certificate_chain(&self, cert_id: Uuid, certificate_pem: &str, chain_pem: &str) -> Result<(), ProjectionError> {
+        let cert_dir = self.root_path.join("certificates").join(cert_id.to_string());
+        fs::create_dir_all(&cert_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create certificate directory: {}", e)))?;
+
+        fs::write(cert_dir.join("cert.pem"), certificate_pem)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write certificate: {}", e)))?;
+        fs::write(cert_dir.join("chain.pem"), chain_pem)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write certificate chain: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Export an issued certificate as a passphrase-protected PKCS#12 bundle
+    ///
+    /// Bundles the stored `cert.pem` and `chain.pem` with `private_key_pem`.
+    /// Private keys are never written to the projection, so the caller
+    /// supplies the key (e.g. regenerated from the master seed). The
+    /// certificate's subject becomes the bundle's friendly name.
+    pub fn export_pkcs12(&self, cert_id: Uuid, private_key_pem: &str, passphrase: &str) -> Result<Vec<u8>, ProjectionError> {
+        let cert_dir = self.root_path.join("certificates").join(cert_id.to_string());
+        let certificate_pem = fs::read_to_string(cert_dir.join("cert.pem"))
+            .map_err(|_| ProjectionError::NotFound(format!("Certificate {} has not been stored", cert_id)))?;
+        let chain_pem = fs::read_to_string(cert_dir.join("chain.pem")).unwrap_or_default();
+
+        let friendly_name = self.manifest.certificates.iter()
+            .find(|c| c.cert_id == cert_id)
+            .map(|c| c.subject.clone())
+            .unwrap_or_else(|| cert_id.to_string());
+
+        crate::crypto::pkcs12::build_pkcs12(&certificate_pem, &chain_pem, private_key_pem, passphrase, &friendly_name)
+            .map_err(ProjectionError::SerializationError)
+    }
+
+    /// Store a person's S/MIME PKCS#12 bundle
+    ///
+    /// Writes `people/{person_id}/smime/{cert_id}.p12` for handing to the
+    /// person's mail client. The bundle is already passphrase protected, so
+    /// it may live on the partition unlike bare private keys.
+    pub fn store_person_pkcs12(&self, person_id: Uuid, cert_id: Uuid, pkcs12: &[u8]) -> Result<PathBuf, ProjectionError> {
+        if !self.manifest.people.iter().any(|p| p.person_id == person_id) {
+            return Err(ProjectionError::NotFound(format!("Person {} not found", person_id)));
+        }
+
+        let smime_dir = self.root_path.join("people").join(person_id.to_string()).join("smime");
+        fs::create_dir_all(&smime_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create S/MIME directory: {}", e)))?;
+
+        let path = smime_dir.join(format!("{}.p12", cert_id));
+        fs::write(&path, pkcs12)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to write PKCS#12 bundle: {}", e)))?;
+
+        Ok(path)
+    }
+
+    /// Current size and Merkle root of the certificate transparency log
+    pub fn certificate_log_head(&self) -> CertificateLogHead {
+        self.manifest.certificate_log_head()
+    }
+
+    /// Inclusion proof for an issued certificate, `None` if it was never logged
+    pub fn certificate_inclusion_proof(&self, cert_id: Uuid) -> Option<CertificateInclusionProof> {
+        self.manifest.certificate_inclusion_proof(cert_id)
+    }
+
     /// Remove a location from the organization
     pub fn remove_location(&mut self, location_id: Uuid) -> Result<(), ProjectionError> {
         let initial_len = self.manifest.locations.len();
@@ -2109,16 +3055,9 @@ impl OfflineKeyProjection {
         Ok(())
     }
 
+    /// Checkpoint the manifest, emptying the change journal
     pub fn save_manifest(&self) -> Result<(), ProjectionError> {
-        let manifest_path = self.root_path.join("manifest.json");
-
-        let manifest_json = serde_json::to_string_pretty(&self.manifest)
-            .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize manifest: {}", e)))?;
-
-        fs::write(&manifest_path, manifest_json)
-            .map_err(|e| ProjectionError::IoError(format!("Failed to write manifest: {}", e)))?;
-
-        Ok(())
+        journal::checkpoint(&self.root_path, &self.manifest)
     }
 
     /// Rebuild projection from event log
@@ -2145,29 +3084,77 @@ impl OfflineKeyProjection {
             keys: Vec::new(),
             certificates: Vec::new(),
             pki_hierarchies: Vec::new(),
+            crls: Vec::new(),
+            certificate_log: Vec::new(),
             yubikeys: Vec::new(),
             nats_operators: Vec::new(),
             nats_accounts: Vec::new(),
             nats_users: Vec::new(),
+            relationships: Vec::new(),
+            delegations: Vec::new(),
+            // Commands are not events; replay cannot recover them
+            processed_commands: self.manifest.processed_commands.clone(),
+            // Publication status belongs to the events, which stay in the log
+            outbox: self.manifest.outbox.clone(),
+            // Dead letters are found again while replaying
+            dead_letters: Vec::new(),
+            // Journal sequences keep counting across the rebuild
+            journal_position: self.manifest.journal_position,
             event_count: 0,
             checksum: String::new(),
+            // The rebuilt manifest has to be sealed again
+            file_checksums: BTreeMap::new(),
+            signature: None,
+            // The volume is not part of the event history
+            luks_uuid: self.manifest.luks_uuid.clone(),
+            // Salts are needed to re-derive keys and are not events either
+            kdf_salts: self.manifest.kdf_salts.clone(),
         };
 
+        // The certificate log file is re-derived from the replayed events
+        let log_path = self.root_path.join("certificates").join("transparency.jsonl");
+        if log_path.exists() {
+            fs::remove_file(&log_path)
+                .map_err(|e| ProjectionError::IoError(format!("Failed to reset certificate log: {}", e)))?;
+        }
+
+        // Start the journal from the empty manifest, so a crash while
+        // replaying cannot mix old and rebuilt entries
+        self.save_manifest()?;
+
         // Replay all events
         for entry in event_files {
-            let content = fs::read_to_string(entry.path())
-                .map_err(|e| ProjectionError::IoError(format!("Failed to read event file: {}", e)))?;
-
-            let event: DomainEvent = serde_json::from_str(&content)
-                .map_err(|e| ProjectionError::ParseError(format!("Invalid event JSON: {}", e)))?;
+            let event_file = entry.file_name().to_string_lossy().into_owned();
+            let event = match read_event_file(&entry.path(), self.event_cipher.as_ref()) {
+                Ok(event) => event,
+                Err(e) => {
+                    let touched = self.touch([event_file.clone()])?;
+                    self.dead_letter(event_file, None, e)?;
+                    self.commit(touched)?;
+                    continue;
+                }
+            };
 
-            self.apply(&event)?;
+            // The event is already in the log; only the projection is rebuilt
+            self.project_or_dead_letter(&event, event_file)?;
         }
 
-        Ok(())
+        self.save_manifest()
     }
 }
 
+/// Read one file of an event log, decrypting it if it was encrypted
+pub fn read_event_file(path: &Path, cipher: Option<&EventLogCipher>) -> Result<DomainEvent, ProjectionError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ProjectionError::IoError(format!("Failed to read event file: {}", e)))?;
+    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let content = open_event_file(&content, &file_name, cipher)
+        .map_err(|e| ProjectionError::EncryptionError(format!("{}: {}", path.display(), e)))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| ProjectionError::ParseError(format!("Invalid event JSON: {}", e)))
+}
+
 // Supporting types for file storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct KeyMetadataFile {
@@ -2212,6 +3199,12 @@ pub enum ProjectionError {
 
     #[error("Invalid state transition: {0}")]
     InvalidStateTransition(String),
+
+    #[error("Encryption error: {0}")]
+    EncryptionError(String),
+
+    #[error("Organization mismatch: {0}")]
+    OrganizationMismatch(String),
 }
 
 // ============================================================================
@@ -2228,6 +3221,144 @@ pub enum ProjectionError {
 /// - Safe concurrent projection rebuilds
 /// - Deterministic event replay
 impl KeyManifest {
+    /// Revocations to list on the next CRL of `issuer_ca_id`
+    ///
+    /// Covers every certificate issued by the CA whose manifest state is
+    /// `Revoked`, whether or not an earlier CRL already listed it.
+    pub fn revocations_for_issuer(&self, issuer_ca_id: Uuid) -> Vec<crate::crypto::CrlEntry> {
+        let issuer = issuer_ca_id.to_string();
+        self.certificates
+            .iter()
+            .filter(|c| c.issuer.as_deref() == Some(issuer.as_str()))
+            .filter_map(|c| match &c.state {
+                Some(CertificateState::Revoked { reason, revoked_at, .. }) => Some(crate::crypto::CrlEntry {
+                    cert_id: c.cert_id,
+                    revoked_at: *revoked_at,
+                    reason: reason.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// OCSP status of `cert_id` as a certificate issued by `issuer_ca_id`
+    ///
+    /// Pass this to `crypto::OcspResponder::respond` to answer requests from
+    /// the projected state.
+    pub fn ocsp_status(&self, issuer_ca_id: Uuid, cert_id: Uuid) -> crate::crypto::OcspCertStatus {
+        let issuer = issuer_ca_id.to_string();
+        match self.certificates.iter().find(|c| c.cert_id == cert_id && c.issuer.as_deref() == Some(issuer.as_str())) {
+            Some(CertificateEntry { state: Some(CertificateState::Revoked { reason, revoked_at, .. }), .. }) => {
+                crate::crypto::OcspCertStatus::Revoked { revoked_at: *revoked_at, reason: reason.clone() }
+            }
+            Some(_) => crate::crypto::OcspCertStatus::Good,
+            None => crate::crypto::OcspCertStatus::Unknown,
+        }
+    }
+
+    /// cRLNumber for the next CRL published by `issuer_ca_id`
+    pub fn next_crl_number(&self, issuer_ca_id: Uuid) -> u64 {
+        self.crls
+            .iter()
+            .find(|c| c.issuer_ca_id == issuer_ca_id)
+            .map_or(1, |c| c.crl_number + 1)
+    }
+
+    /// Append a newly generated certificate to the transparency log
+    fn log_certificate(&mut self, cert: &CertificateEntry) -> &CertificateLogEntry {
+        let previous_hash = self.certificate_log.last().map(|e| e.leaf_hash.clone()).unwrap_or_default();
+        let mut entry = CertificateLogEntry {
+            index: self.certificate_log.len() as u64,
+            cert_id: cert.cert_id,
+            key_id: cert.key_id,
+            subject: cert.subject.clone(),
+            issuer: cert.issuer.clone(),
+            not_before: cert.not_before,
+            not_after: cert.not_after,
+            is_ca: cert.is_ca,
+            previous_hash,
+            leaf_hash: String::new(),
+        };
+        entry.leaf_hash = hex::encode(entry.compute_leaf_hash());
+        self.certificate_log.push(entry);
+        &self.certificate_log[self.certificate_log.len() - 1]
+    }
+
+    fn certificate_log_leaves(&self) -> Vec<crate::crypto::MerkleHash> {
+        self.certificate_log.iter().map(CertificateLogEntry::compute_leaf_hash).collect()
+    }
+
+    /// Current size and Merkle root of the certificate log
+    pub fn certificate_log_head(&self) -> CertificateLogHead {
+        CertificateLogHead {
+            tree_size: self.certificate_log.len() as u64,
+            root_hash: hex::encode(crate::crypto::merkle_root(&self.certificate_log_leaves())),
+        }
+    }
+
+    /// Inclusion proof for `cert_id` against the current log head
+    ///
+    /// `None` means the certificate was never logged, i.e. it was not
+    /// issued through this projection.
+    pub fn certificate_inclusion_proof(&self, cert_id: Uuid) -> Option<CertificateInclusionProof> {
+        let index = self.certificate_log.iter().position(|e| e.cert_id == cert_id)?;
+        let leaves = self.certificate_log_leaves();
+        let audit_path = crate::crypto::inclusion_proof(&leaves, index)?;
+        Some(CertificateInclusionProof {
+            cert_id,
+            leaf_index: index as u64,
+            leaf_hash: hex::encode(leaves[index]),
+            audit_path: audit_path.iter().map(hex::encode).collect(),
+            tree_head: CertificateLogHead {
+                tree_size: leaves.len() as u64,
+                root_hash: hex::encode(crate::crypto::merkle_root(&leaves)),
+            },
+        })
+    }
+
+    /// Audit the certificate log
+    ///
+    /// Checks that entries are numbered consecutively, that every stored
+    /// leaf hash matches its contents and chains to its predecessor, and
+    /// that every certificate in the manifest has a log entry.
+    pub fn verify_certificate_log(&self) -> Result<CertificateLogHead, String> {
+        let mut previous_hash = String::new();
+        for (i, entry) in self.certificate_log.iter().enumerate() {
+            if entry.index != i as u64 {
+                return Err(format!("Log entry {} is numbered {}", i, entry.index));
+            }
+            if entry.previous_hash != previous_hash {
+                return Err(format!("Log entry {} breaks the hash chain", i));
+            }
+            if hex::encode(entry.compute_leaf_hash()) != entry.leaf_hash {
+                return Err(format!("Log entry {} (certificate {}) was modified", i, entry.cert_id));
+            }
+            previous_hash = entry.leaf_hash.clone();
+        }
+        if let Some(unlogged) = self
+            .certificates
+            .iter()
+            .find(|c| !self.certificate_log.iter().any(|e| e.cert_id == c.cert_id))
+        {
+            return Err(format!("Certificate {} ({}) is missing from the log", unlogged.cert_id, unlogged.subject));
+        }
+        Ok(self.certificate_log_head())
+    }
+
+    /// Replace the issuer's CRL entry with the one from `event`
+    fn record_crl(&mut self, event: &crate::events::CrlPublishedEvent) {
+        self.crls.retain(|c| c.issuer_ca_id != event.issuer_ca_id);
+        self.crls.push(CrlManifestEntry {
+            issuer_ca_id: event.issuer_ca_id,
+            crl_number: event.crl_number,
+            this_update: event.this_update,
+            next_update: event.next_update,
+            revoked_cert_ids: event.revoked_cert_ids.clone(),
+            crl_pem: event.crl_pem.clone(),
+            file_path: format!("certificates/{}/crl.pem", event.issuer_ca_id),
+        });
+    }
+
     /// Apply an event purely, returning a new manifest
     ///
     /// This is the FRP-compliant version of `apply_event`. Instead of
@@ -2262,6 +3393,8 @@ impl KeyManifest {
                     revoked: false,
                     file_path: format!("keys/{}/metadata.json", e.key_id),
                     state: None,
+                    expires_at: e.expires_at,
+                    owner_id: e.ownership.as_ref().map(|o| o.owner_id),
                 });
             }
             DomainEvent::Key(KeyEvents::KeyRevoked(e)) => {
@@ -2276,7 +3409,7 @@ impl KeyManifest {
                     cert_id: e.cert_id,
                     key_id: e.key_id,
                     subject: e.subject_name.to_rfc4514(),
-                    issuer: e.issuer.map(|_| "issuer".to_string()),
+                    issuer: e.issuer.map(|id| id.to_string()),
                     serial_number: e.cert_id.to_string(),
                     not_before: e.validity.not_before(),
                     not_after: e.validity.not_after(),
@@ -2284,6 +3417,11 @@ impl KeyManifest {
                     file_path: format!("certificates/{}/cert.pem", e.cert_id),
                     state: None,
                 });
+                let cert = result.certificates[result.certificates.len() - 1].clone();
+                result.log_certificate(&cert);
+            }
+            DomainEvent::Certificate(CertificateEvents::CrlPublished(e)) => {
+                result.record_crl(e);
             }
 
             // Person aggregate events
@@ -2323,6 +3461,7 @@ impl KeyManifest {
                     slots_used: e.slots_configured.iter().map(|s| format!("{:?}", s)).collect(),
                     config_path: format!("yubikeys/{}/config.json", e.yubikey_serial),
                     state: None,
+                    firmware_version: None,
                 });
             }
 
@@ -2415,6 +3554,8 @@ impl Rebuildable for KeyManifest {
                     revoked: false,
                     file_path: format!("keys/{}/metadata.json", e.key_id),
                     state: None, // State machine state set separately
+                    expires_at: e.expires_at,
+                    owner_id: e.ownership.as_ref().map(|o| o.owner_id),
                 });
             }
             DomainEvent::Key(KeyEvents::KeyRevoked(e)) => {
@@ -2430,7 +3571,7 @@ impl Rebuildable for KeyManifest {
                     cert_id: e.cert_id,
                     key_id: e.key_id,
                     subject: e.subject_name.to_rfc4514(),
-                    issuer: e.issuer.map(|_| "issuer".to_string()),
+                    issuer: e.issuer.map(|id| id.to_string()),
                     serial_number: e.cert_id.to_string(),
                     not_before: e.validity.not_before(),
                     not_after: e.validity.not_after(),
@@ -2438,6 +3579,8 @@ impl Rebuildable for KeyManifest {
                     file_path: format!("certificates/{}/cert.pem", e.cert_id),
                     state: None, // State machine state set separately
                 });
+                let cert = self.certificates[self.certificates.len() - 1].clone();
+                self.log_certificate(&cert);
             }
             DomainEvent::Certificate(CertificateEvents::CertificateRevoked(e)) => {
                 if let Some(entry) = self.certificates.iter_mut().find(|c| c.cert_id == e.cert_id) {
@@ -2445,6 +3588,9 @@ impl Rebuildable for KeyManifest {
                     let _ = entry; // Mark as used
                 }
             }
+            DomainEvent::Certificate(CertificateEvents::CrlPublished(e)) => {
+                self.record_crl(e);
+            }
 
             // Person aggregate events
             DomainEvent::Person(PersonEvents::PersonCreated(e)) => {
@@ -2483,6 +3629,7 @@ impl Rebuildable for KeyManifest {
                     slots_used: e.slots_configured.iter().map(|s| format!("{:?}", s)).collect(),
                     config_path: format!("yubikeys/{}/config.json", e.yubikey_serial),
                     state: None, // State machine state set separately
+                    firmware_version: None,
                 });
             }
 
@@ -0,0 +1,270 @@
+//! Dead letters for events the projection cannot apply
+//!
+//! An event can be valid and still not apply to this projection: its file
+//! was written by a newer schema version, it references an entity the
+//! projection has never seen, or it asks for a state transition the
+//! projection's current state does not allow. Instead of failing the whole
+//! apply or rebuild, such events are parked in the manifest:
+//!
+//! ```text
+//! apply(event) / rebuild_from_events()
+//!   ├─ projected          → done
+//!   ├─ unapplicable       → manifest.dead_letters += DeadLetter { reason }
+//!   └─ I/O, key, tenancy  → error, as before
+//!
+//! retry_dead_letters()    (after the decoder or the missing data is fixed)
+//!   for each dead letter, oldest first:
+//!     ├─ projected        → removed
+//!     └─ still failing    → attempts += 1, reason and error updated
+//! ```
+//!
+//! The event itself stays in `events/` and keeps its outbox entry; only
+//! its effect on the projection is deferred.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{journal, read_event_file, OfflineKeyProjection, ProjectionError};
+use crate::events::DomainEvent;
+
+/// An event parked because it could not be applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// File name of the event under `events/`
+    pub event_file: String,
+    /// Aggregate type, `None` when the file could not be decoded
+    pub aggregate_type: Option<String>,
+    pub reason: DeadLetterReason,
+    /// Error of the most recent attempt
+    pub error: String,
+    /// Attempts to apply the event, including the first
+    pub attempts: u32,
+    pub dead_lettered_at: DateTime<Utc>,
+    pub last_attempt_at: DateTime<Utc>,
+}
+
+/// Why an event could not be applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeadLetterReason {
+    /// The event file does not decode as any known event version
+    UnknownVersion,
+    /// The event references an entity the projection does not hold
+    MissingEntity,
+    /// The event's transition is not allowed from the entity's state
+    InvalidTransition,
+}
+
+impl DeadLetterReason {
+    /// Reason for parking an event that failed with `error`
+    ///
+    /// `None` for failures that are not about the event, such as I/O, a
+    /// missing decryption key or an event of another organization; those
+    /// are still returned as errors.
+    pub fn classify(error: &ProjectionError) -> Option<Self> {
+        match error {
+            ProjectionError::ParseError(_) => Some(DeadLetterReason::UnknownVersion),
+            ProjectionError::NotFound(_) => Some(DeadLetterReason::MissingEntity),
+            ProjectionError::InvalidStateTransition(_) => Some(DeadLetterReason::InvalidTransition),
+            _ => None,
+        }
+    }
+}
+
+/// Result of one [`OfflineKeyProjection::retry_dead_letters`] run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeadLetterRetryReport {
+    /// Dead letters applied during this run
+    pub applied: usize,
+    /// Dead letters still parked
+    pub remaining: usize,
+}
+
+impl OfflineKeyProjection {
+    /// Events parked because they could not be applied, oldest first
+    pub fn dead_letters(&self) -> &[DeadLetter] {
+        &self.manifest.dead_letters
+    }
+
+    /// Try to apply every dead letter again, oldest first
+    ///
+    /// Run after installing what the events were missing: a decoder for a
+    /// newer event version, or the entities they reference. Applied events
+    /// leave the queue; the rest record the new attempt. A failure that is
+    /// not about the event stops the run with that error.
+    pub fn retry_dead_letters(&mut self) -> Result<DeadLetterRetryReport, ProjectionError> {
+        let mut report = DeadLetterRetryReport::default();
+        let mut index = 0;
+
+        while index < self.manifest.dead_letters.len() {
+            let event_file = self.manifest.dead_letters[index].event_file.clone();
+            let event_path = self.root_path.join("events").join(&event_file);
+            let event = read_event_file(&event_path, self.event_cipher.as_ref());
+            let ids = event.as_ref().map(journal::event_ids).unwrap_or_default();
+            let touched = self.touch(ids.into_iter().chain([event_file]))?;
+
+            // Leave the queue first, so the change journaled with the
+            // projected event no longer holds it
+            let mut letter = self.manifest.dead_letters.remove(index);
+            let result = event.and_then(|event| {
+                letter.aggregate_type = Some(event.aggregate_type().to_string());
+                self.check_organization(&event)?;
+                self.project(&event)
+            });
+
+            let Err(e) = result else {
+                self.commit(touched)?;
+                report.applied += 1;
+                continue;
+            };
+            let reason = DeadLetterReason::classify(&e);
+            letter.attempts += 1;
+            letter.last_attempt_at = Utc::now();
+            letter.error = e.to_string();
+            letter.reason = reason.unwrap_or(letter.reason);
+            self.manifest.dead_letters.insert(index, letter);
+            self.commit(touched)?;
+            if reason.is_none() {
+                return Err(e);
+            }
+            index += 1;
+        }
+
+        report.remaining = self.manifest.dead_letters.len();
+        Ok(report)
+    }
+
+    /// Project `event`, or park it when it cannot be applied
+    pub(super) fn project_or_dead_letter(
+        &mut self,
+        event: &DomainEvent,
+        event_file: String,
+    ) -> Result<(), ProjectionError> {
+        let touched = self.touch(journal::event_ids(event).into_iter().chain([event_file.clone()]))?;
+        match self.project(event) {
+            Ok(()) => {}
+            Err(e) => self.dead_letter(event_file, Some(event.aggregate_type().to_string()), e)?,
+        }
+        self.commit(touched)
+    }
+
+    /// Park an event that failed with `error`, or return the error if it
+    /// is not about the event
+    pub(super) fn dead_letter(
+        &mut self,
+        event_file: String,
+        aggregate_type: Option<String>,
+        error: ProjectionError,
+    ) -> Result<(), ProjectionError> {
+        let Some(reason) = DeadLetterReason::classify(&error) else {
+            return Err(error);
+        };
+        tracing::warn!("Dead-lettered event {}: {}", event_file, error);
+
+        let now = Utc::now();
+        self.manifest.dead_letters.push(DeadLetter {
+            event_file,
+            aggregate_type,
+            reason,
+            error: error.to_string(),
+            attempts: 1,
+            dead_lettered_at: now,
+            last_attempt_at: now,
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::key::KeyExpirationExtendedEvent;
+    use crate::events::{KeyEvents, KeyGeneratedEvent};
+    use crate::types::{KeyAlgorithm, KeyMetadata, KeyPurpose};
+    use crate::value_objects::ActorId;
+    use std::fs;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn key_generated(key_id: Uuid) -> DomainEvent {
+        DomainEvent::Key(KeyEvents::KeyGenerated(KeyGeneratedEvent {
+            key_id,
+            algorithm: KeyAlgorithm::Ed25519,
+            purpose: KeyPurpose::Signing,
+            generated_at: Utc::now(),
+            generated_by: ActorId::system("test"),
+            hardware_backed: false,
+            metadata: KeyMetadata {
+                label: "gpg".to_string(),
+                description: None,
+                tags: vec![],
+                attributes: Default::default(),
+                jwt_kid: None,
+                jwt_alg: None,
+                jwt_use: None,
+                hardware_serial: None,
+                tpm: None,
+            },
+            ownership: None,
+            expires_at: None,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))
+    }
+
+    fn expiration_extended(key_id: Uuid) -> DomainEvent {
+        DomainEvent::Key(KeyEvents::KeyExpirationExtended(KeyExpirationExtendedEvent {
+            key_id,
+            fingerprint: "A".repeat(40),
+            previous_expires_at: None,
+            new_expires_at: Some(Utc::now() + chrono::Duration::days(365)),
+            extended_at: Utc::now(),
+            extended_by: ActorId::system("test"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))
+    }
+
+    #[test]
+    fn test_missing_entity_is_parked_and_retried() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        let key_id = Uuid::now_v7();
+
+        // Extending a key the projection has never seen
+        projection.apply(&expiration_extended(key_id)).unwrap();
+        let letter = &projection.dead_letters()[0];
+        assert_eq!(letter.reason, DeadLetterReason::MissingEntity);
+        assert_eq!(letter.aggregate_type.as_deref(), Some("Key"));
+        // The event is still logged and queued for publication
+        assert_eq!(projection.pending_outbox().count(), 1);
+
+        let report = projection.retry_dead_letters().unwrap();
+        assert_eq!(report, DeadLetterRetryReport { applied: 0, remaining: 1 });
+        assert_eq!(projection.dead_letters()[0].attempts, 2);
+
+        projection.apply(&key_generated(key_id)).unwrap();
+        let report = projection.retry_dead_letters().unwrap();
+        assert_eq!(report, DeadLetterRetryReport { applied: 1, remaining: 0 });
+        assert!(projection.manifest().keys[0].expires_at.is_some());
+
+        let reopened = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        assert!(reopened.dead_letters().is_empty());
+    }
+
+    #[test]
+    fn test_undecodable_event_file_is_parked_on_rebuild() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        fs::write(
+            temp_dir.path().join("events").join("0_future.json"),
+            r#"{"aggregate":"Person","event":{"event_type":"PersonTeleported","schema":9}}"#,
+        )
+        .unwrap();
+
+        projection.rebuild_from_events().unwrap();
+        let letter = &projection.dead_letters()[0];
+        assert_eq!(letter.reason, DeadLetterReason::UnknownVersion);
+        assert_eq!(letter.event_file, "0_future.json");
+        assert_eq!(letter.aggregate_type, None);
+    }
+}
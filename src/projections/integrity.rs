@@ -0,0 +1,246 @@
+//! Integrity verification of a projection tree
+//!
+//! Sealing records the SHA-256 of every file under the projection root in
+//! the manifest and signs the manifest with the ceremony key. An SD card
+//! carried between machines can then be checked before it is trusted:
+//!
+//! ```text
+//! seal(key_id, ceremony_key)
+//!   manifest.file_checksums = { "events/…json": sha256, "keys/…/public.pem": sha256, … }
+//!   manifest.signature      = Ed25519("cim-keys/manifest/v1\n" || canonical_json(manifest - signature))
+//!
+//! verify_integrity(ceremony_public_key)
+//!   re-hash the tree → modified / missing / extra files
+//!   check the signature → Valid / Unsigned / Invalid
+//! ```
+//!
+//! The manifest itself, its journal and local caches such as the SQLite
+//! read model are not part of the sealed tree; the manifest is covered by
+//! the signature instead. Applying events after sealing changes both the
+//! tree and the manifest, so seal again before handing the card over.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::journal::JOURNAL_FILE;
+use super::{KeyManifest, OfflineKeyProjection, ProjectionError};
+use crate::events::signing::write_canonical;
+
+/// Domain-separation tag prepended to the signed bytes
+const SIGNING_CONTEXT: &[u8] = b"cim-keys/manifest/v1\n";
+
+/// Top-level entries under the root that are not sealed
+///
+/// Organization projections are sealed on their own; the read model
+/// (`sqlite::READ_MODEL_FILE` and its `-wal`/`-journal` files) is a
+/// rebuildable cache.
+const UNSEALED: &[&str] = &["manifest.json", JOURNAL_FILE, "organizations"];
+const UNSEALED_PREFIXES: &[&str] = &["read_model.sqlite"];
+
+/// Signature of a manifest by the ceremony key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    /// ID of the Ed25519 key that signed the manifest
+    pub signer_key_id: Uuid,
+    /// Ed25519 signature, base64
+    pub signature: String,
+}
+
+/// Outcome of checking the manifest signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Signed by the given ceremony key and unchanged since
+    Valid,
+    /// The manifest was never sealed
+    Unsigned,
+    /// Changed after sealing, or signed by another key
+    Invalid,
+}
+
+/// Result of [`OfflineKeyProjection::verify_integrity`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Files whose contents differ from their sealed checksum
+    pub modified: Vec<String>,
+    /// Sealed files no longer in the tree
+    pub missing: Vec<String>,
+    /// Files in the tree that were not sealed
+    pub extra: Vec<String>,
+    pub signature: SignatureStatus,
+}
+
+impl IntegrityReport {
+    /// Whether the tree and manifest are exactly as sealed
+    pub fn is_intact(&self) -> bool {
+        self.modified.is_empty()
+            && self.missing.is_empty()
+            && self.extra.is_empty()
+            && self.signature == SignatureStatus::Valid
+    }
+}
+
+impl OfflineKeyProjection {
+    /// Record the checksum of every file and sign the manifest
+    pub fn seal(&mut self, key_id: Uuid, ceremony_key: &SigningKey) -> Result<(), ProjectionError> {
+        self.manifest.file_checksums = hash_tree(&self.root_path)?;
+        let signature = ceremony_key.sign(&signing_payload(&self.manifest)?);
+        self.manifest.signature = Some(ManifestSignature {
+            signer_key_id: key_id,
+            signature: STANDARD.encode(signature.to_bytes()),
+        });
+        self.save_manifest()
+    }
+
+    /// Re-hash the tree and check the manifest signature against the
+    /// ceremony key
+    ///
+    /// Only failing to read the tree is an error; any difference is
+    /// reported.
+    pub fn verify_integrity(&self, ceremony_key: &VerifyingKey) -> Result<IntegrityReport, ProjectionError> {
+        let sealed = &self.manifest.file_checksums;
+        let current = hash_tree(&self.root_path)?;
+
+        let modified = sealed
+            .iter()
+            .filter(|(path, checksum)| current.get(*path).is_some_and(|found| found != *checksum))
+            .map(|(path, _)| path.clone())
+            .collect();
+        let missing = sealed.keys().filter(|path| !current.contains_key(*path)).cloned().collect();
+        let extra = current.keys().filter(|path| !sealed.contains_key(*path)).cloned().collect();
+
+        let signature = match &self.manifest.signature {
+            None => SignatureStatus::Unsigned,
+            Some(signed) => {
+                let payload = signing_payload(&self.manifest)?;
+                let valid = STANDARD
+                    .decode(&signed.signature)
+                    .ok()
+                    .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+                    .is_some_and(|bytes| ceremony_key.verify(&payload, &Signature::from_bytes(&bytes)).is_ok());
+                if valid {
+                    SignatureStatus::Valid
+                } else {
+                    SignatureStatus::Invalid
+                }
+            }
+        };
+
+        Ok(IntegrityReport { modified, missing, extra, signature })
+    }
+}
+
+/// Bytes covered by a manifest's signature
+fn signing_payload(manifest: &KeyManifest) -> Result<Vec<u8>, ProjectionError> {
+    let mut value = serde_json::to_value(manifest)
+        .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize manifest: {}", e)))?;
+    if let Value::Object(fields) = &mut value {
+        fields.remove("signature");
+    }
+
+    let mut payload = SIGNING_CONTEXT.to_vec();
+    write_canonical(&value, &mut payload)
+        .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize manifest: {}", e)))?;
+    Ok(payload)
+}
+
+/// SHA-256 of every sealed file under `root`, by `/`-separated relative path
+fn hash_tree(root: &Path) -> Result<BTreeMap<String, String>, ProjectionError> {
+    fn walk(dir: &Path, prefix: &str, checksums: &mut BTreeMap<String, String>) -> Result<(), ProjectionError> {
+        let entries = fs::read_dir(dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to read directory {:?}: {}", dir, e)))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| ProjectionError::IoError(format!("Failed to read directory entry: {}", e)))?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if prefix.is_empty()
+                && (UNSEALED.contains(&name.as_str()) || UNSEALED_PREFIXES.iter().any(|p| name.starts_with(p)))
+            {
+                continue;
+            }
+            // Left behind by an interrupted atomic write
+            if name.ends_with(".tmp") {
+                continue;
+            }
+
+            let path = entry.path();
+            let relative = format!("{}{}", prefix, name);
+            if path.is_dir() {
+                walk(&path, &format!("{}/", relative), checksums)?;
+            } else {
+                let contents = fs::read(&path)
+                    .map_err(|e| ProjectionError::IoError(format!("Failed to read {}: {}", relative, e)))?;
+                checksums.insert(relative, hex::encode(Sha256::digest(&contents)));
+            }
+        }
+        Ok(())
+    }
+
+    let mut checksums = BTreeMap::new();
+    walk(root, "", &mut checksums)?;
+    Ok(checksums)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sealed_projection() -> (TempDir, OfflineKeyProjection, SigningKey) {
+        let temp_dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        fs::write(temp_dir.path().join("keys").join("a.pem"), "A").unwrap();
+        fs::write(temp_dir.path().join("pki").join("b.pem"), "B").unwrap();
+
+        let ceremony_key = SigningKey::from_bytes(&rand::random());
+        projection.seal(Uuid::now_v7(), &ceremony_key).unwrap();
+        (temp_dir, projection, ceremony_key)
+    }
+
+    #[test]
+    fn test_sealed_tree_verifies_after_reopen() {
+        let (temp_dir, projection, ceremony_key) = sealed_projection();
+        assert_eq!(projection.manifest().file_checksums.len(), 2);
+
+        let reopened = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        let report = reopened.verify_integrity(&ceremony_key.verifying_key()).unwrap();
+        assert!(report.is_intact(), "{:?}", report);
+
+        let other_key = SigningKey::from_bytes(&rand::random());
+        let report = reopened.verify_integrity(&other_key.verifying_key()).unwrap();
+        assert_eq!(report.signature, SignatureStatus::Invalid);
+    }
+
+    #[test]
+    fn test_changed_files_are_reported() {
+        let (temp_dir, projection, ceremony_key) = sealed_projection();
+        fs::write(temp_dir.path().join("keys").join("a.pem"), "tampered").unwrap();
+        fs::remove_file(temp_dir.path().join("pki").join("b.pem")).unwrap();
+        fs::write(temp_dir.path().join("nats").join("c.jwt"), "C").unwrap();
+
+        let report = projection.verify_integrity(&ceremony_key.verifying_key()).unwrap();
+        assert_eq!(report.modified, vec!["keys/a.pem"]);
+        assert_eq!(report.missing, vec!["pki/b.pem"]);
+        assert_eq!(report.extra, vec!["nats/c.jwt"]);
+        assert_eq!(report.signature, SignatureStatus::Valid);
+    }
+
+    #[test]
+    fn test_edited_manifest_invalidates_signature() {
+        let (temp_dir, _projection, ceremony_key) = sealed_projection();
+        let manifest_path = temp_dir.path().join("manifest.json");
+        let mut manifest: Value = serde_json::from_str(&fs::read_to_string(&manifest_path).unwrap()).unwrap();
+        manifest["file_checksums"]["keys/a.pem"] = hex::encode(Sha256::digest(b"tampered")).into();
+        fs::write(&manifest_path, serde_json::to_string(&manifest).unwrap()).unwrap();
+
+        let reopened = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        let report = reopened.verify_integrity(&ceremony_key.verifying_key()).unwrap();
+        assert_eq!(report.signature, SignatureStatus::Invalid);
+    }
+}
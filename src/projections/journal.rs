@@ -0,0 +1,514 @@
+//! Append-only change journal for the manifest
+//!
+//! Rewriting `manifest.json` after every event makes applying `n` events
+//! cost `O(n²)` bytes of disk writes. Instead, each change appends the
+//! manifest entries it touched to `manifest.journal`, and the manifest is
+//! only rewritten (checkpointed) once the journal has grown as large as
+//! the manifest itself:
+//!
+//! ```text
+//! apply(event)
+//!   ├─ events/{ts}_{id}.json        written to a temp file, fsynced, renamed
+//!   ├─ project into memory
+//!   └─ manifest.journal += { sequence, Set { section, id, entries } } …  (fsynced)
+//!        └─ journal ≥ manifest → checkpoint:
+//!             manifest.json (journal_position = last sequence)  temp + fsync + rename
+//!             manifest.journal truncated
+//!
+//! open()
+//!   manifest.json + journal records with sequence > journal_position
+//! ```
+//!
+//! Entries are identified by their ID within a manifest section. The
+//! entries a change may touch are the ones whose ID the event names, plus
+//! its outbox entry and dead letter; all current entries with such an ID
+//! are journaled, and an empty set records a removal. A crash can leave at
+//! most a torn last line, which is ignored on open, or a stale journal
+//! next to a newer checkpoint, whose records are skipped by sequence.
+
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::dead_letter::DeadLetter;
+use super::outbox::OutboxEntry;
+use super::{
+    CertificateEntry, CertificateLogEntry, CrlManifestEntry, KeyEntry, KeyManifest, LocationEntry, NatsAccountEntry,
+    NatsOperatorEntry, NatsUserEntry, OfflineKeyProjection, OrganizationInfo, PersonEntry, PkiHierarchyEntry,
+    ProjectionError, RelationshipEntry, YubiKeyEntry, PROCESSED_COMMAND_WINDOW,
+};
+use crate::events::DomainEvent;
+
+/// File name of the journal next to `manifest.json`
+pub const JOURNAL_FILE: &str = "manifest.journal";
+
+/// Manifest sections made of entries with an ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Section {
+    People,
+    Locations,
+    Keys,
+    Certificates,
+    PkiHierarchies,
+    Crls,
+    CertificateLog,
+    Yubikeys,
+    NatsOperators,
+    NatsAccounts,
+    NatsUsers,
+    Relationships,
+    Outbox,
+    DeadLetters,
+}
+
+impl Section {
+    /// Field of the section in the manifest JSON
+    fn field(self) -> &'static str {
+        match self {
+            Section::People => "people",
+            Section::Locations => "locations",
+            Section::Keys => "keys",
+            Section::Certificates => "certificates",
+            Section::PkiHierarchies => "pki_hierarchies",
+            Section::Crls => "crls",
+            Section::CertificateLog => "certificate_log",
+            Section::Yubikeys => "yubikeys",
+            Section::NatsOperators => "nats_operators",
+            Section::NatsAccounts => "nats_accounts",
+            Section::NatsUsers => "nats_users",
+            Section::Relationships => "relationships",
+            Section::Outbox => "outbox",
+            Section::DeadLetters => "dead_letters",
+        }
+    }
+
+    /// Field identifying an entry of the section
+    fn id_field(self) -> &'static str {
+        match self {
+            Section::People => "person_id",
+            Section::Locations => "location_id",
+            Section::Keys => "key_id",
+            Section::Certificates | Section::CertificateLog => "cert_id",
+            Section::PkiHierarchies => "root_ca_id",
+            Section::Crls => "issuer_ca_id",
+            Section::Yubikeys => "serial",
+            Section::NatsOperators => "operator_id",
+            Section::NatsAccounts => "account_id",
+            Section::NatsUsers => "user_id",
+            Section::Relationships => "relationship_id",
+            Section::Outbox | Section::DeadLetters => "event_file",
+        }
+    }
+}
+
+/// One change to the manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op")]
+enum Change {
+    /// Replace all entries with `id` in `section`; empty when removed
+    Set { section: Section, id: String, entries: Vec<Value> },
+    /// A command was recorded as processed
+    CommandProcessed { command_id: Uuid },
+    /// Manifest fields outside the sections
+    Header { updated_at: DateTime<Utc>, event_count: u64, organization: OrganizationInfo },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JournalRecord {
+    sequence: u64,
+    #[serde(flatten)]
+    change: Change,
+}
+
+/// Entries a change may touch, captured before the change
+pub(super) struct Touched {
+    ids: HashSet<String>,
+    before: HashSet<(Section, String)>,
+}
+
+impl OfflineKeyProjection {
+    /// Start a change to the entries identified by `ids`
+    pub(super) fn touch(&self, ids: impl IntoIterator<Item = String>) -> Result<Touched, ProjectionError> {
+        let ids: HashSet<String> = ids.into_iter().collect();
+        let before = matching(&self.manifest, &ids)?.into_iter().map(|(section, id, _)| (section, id)).collect();
+        Ok(Touched { ids, before })
+    }
+
+    /// Journal the entries changed since [`touch`](Self::touch)
+    pub(super) fn commit(&mut self, touched: Touched) -> Result<(), ProjectionError> {
+        let mut changes: Vec<Change> = Vec::new();
+        let mut after: Vec<(Section, String, Vec<Value>)> = Vec::new();
+        for (section, id, entry) in matching(&self.manifest, &touched.ids)? {
+            match after.iter_mut().find(|(s, i, _)| *s == section && *i == id) {
+                Some((_, _, entries)) => entries.push(entry),
+                None => after.push((section, id, vec![entry])),
+            }
+        }
+        for (section, id) in touched.before {
+            if !after.iter().any(|(s, i, _)| *s == section && *i == id) {
+                changes.push(Change::Set { section, id, entries: Vec::new() });
+            }
+        }
+        changes.extend(after.into_iter().map(|(section, id, entries)| Change::Set { section, id, entries }));
+        changes.push(self.header());
+        self.append_journal(changes)
+    }
+
+    /// Journal that `command_id` was recorded as processed
+    pub(super) fn commit_processed_command(&mut self, command_id: Uuid) -> Result<(), ProjectionError> {
+        let header = self.header();
+        self.append_journal(vec![Change::CommandProcessed { command_id }, header])
+    }
+
+    fn header(&self) -> Change {
+        Change::Header {
+            updated_at: self.manifest.updated_at,
+            event_count: self.manifest.event_count,
+            organization: self.manifest.organization.clone(),
+        }
+    }
+
+    fn append_journal(&mut self, changes: Vec<Change>) -> Result<(), ProjectionError> {
+        let mut lines = String::new();
+        for change in changes {
+            self.manifest.journal_position += 1;
+            let record = JournalRecord { sequence: self.manifest.journal_position, change };
+            let line = serde_json::to_string(&record)
+                .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize journal record: {}", e)))?;
+            lines.push_str(&line);
+            lines.push('\n');
+        }
+
+        let journal_path = self.root_path.join(JOURNAL_FILE);
+        let mut journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&journal_path)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to open manifest journal: {}", e)))?;
+        journal
+            .write_all(lines.as_bytes())
+            .and_then(|()| journal.sync_data())
+            .map_err(|e| ProjectionError::IoError(format!("Failed to append to manifest journal: {}", e)))?;
+
+        // Checkpoint once rewriting the manifest costs no more than the
+        // journal already written, keeping the writes per event constant
+        let journal_len = journal.metadata().map(|m| m.len()).unwrap_or(0);
+        let manifest_len = fs::metadata(self.root_path.join("manifest.json")).map(|m| m.len()).unwrap_or(0);
+        if journal_len >= manifest_len {
+            self.save_manifest()?;
+        }
+        Ok(())
+    }
+}
+
+/// Checkpoint `manifest` and empty the journal it now contains
+pub(super) fn checkpoint(root: &Path, manifest: &KeyManifest) -> Result<(), ProjectionError> {
+    let manifest_json = serde_json::to_string_pretty(manifest)
+        .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize manifest: {}", e)))?;
+    write_atomic(&root.join("manifest.json"), manifest_json.as_bytes())
+        .map_err(|e| ProjectionError::IoError(format!("Failed to write manifest: {}", e)))?;
+
+    // A crash before this leaves records the checkpoint already holds;
+    // they are skipped by sequence on open
+    let journal_path = root.join(JOURNAL_FILE);
+    if journal_path.exists() {
+        File::create(&journal_path)
+            .and_then(|journal| journal.sync_all())
+            .map_err(|e| ProjectionError::IoError(format!("Failed to truncate manifest journal: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Apply the journal records newer than the checkpoint in `manifest`
+pub(super) fn replay_journal(root: &Path, manifest: KeyManifest) -> Result<KeyManifest, ProjectionError> {
+    let Ok(content) = fs::read_to_string(root.join(JOURNAL_FILE)) else {
+        return Ok(manifest);
+    };
+
+    let mut records = Vec::new();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<JournalRecord>(line) {
+            Ok(record) if record.sequence > manifest.journal_position => records.push(record),
+            Ok(_) => {}
+            Err(e) => {
+                // Only the last append can be torn; nothing valid follows it
+                tracing::warn!("Ignoring torn manifest journal record: {}", e);
+                break;
+            }
+        }
+    }
+    if records.is_empty() {
+        return Ok(manifest);
+    }
+
+    let mut value = serde_json::to_value(&manifest)
+        .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize manifest: {}", e)))?;
+    for record in records {
+        apply_change(&mut value, record.change);
+        value["journal_position"] = record.sequence.into();
+    }
+    serde_json::from_value(value).map_err(|e| ProjectionError::ParseError(format!("Invalid manifest journal: {}", e)))
+}
+
+fn apply_change(manifest: &mut Value, change: Change) {
+    match change {
+        Change::Set { section, id, entries } => {
+            let Some(Value::Array(items)) = manifest.get_mut(section.field()) else {
+                return;
+            };
+            let id_field = section.id_field();
+            let position = items.iter().position(|item| item[id_field].as_str() == Some(id.as_str()));
+            items.retain(|item| item[id_field].as_str() != Some(id.as_str()));
+            let position = position.unwrap_or(items.len()).min(items.len());
+            items.splice(position..position, entries);
+        }
+        Change::CommandProcessed { command_id } => {
+            if let Some(Value::Array(processed)) = manifest.get_mut("processed_commands") {
+                let command_id = Value::String(command_id.to_string());
+                if !processed.contains(&command_id) {
+                    processed.push(command_id);
+                }
+                if processed.len() > PROCESSED_COMMAND_WINDOW {
+                    processed.drain(..processed.len() - PROCESSED_COMMAND_WINDOW);
+                }
+            }
+        }
+        Change::Header { updated_at, event_count, organization } => {
+            manifest["updated_at"] = serde_json::to_value(updated_at).unwrap_or(Value::Null);
+            manifest["event_count"] = event_count.into();
+            manifest["organization"] = serde_json::to_value(organization).unwrap_or(Value::Null);
+        }
+    }
+}
+
+/// IDs named anywhere in `event`: every string it carries
+pub(super) fn event_ids(event: &DomainEvent) -> HashSet<String> {
+    fn collect(value: Value, ids: &mut HashSet<String>) {
+        match value {
+            Value::String(s) => {
+                ids.insert(s);
+            }
+            Value::Array(items) => items.into_iter().for_each(|item| collect(item, ids)),
+            Value::Object(fields) => fields.into_iter().for_each(|(_, field)| collect(field, ids)),
+            _ => {}
+        }
+    }
+    let mut ids = HashSet::new();
+    if let Ok(value) = serde_json::to_value(event) {
+        collect(value, &mut ids);
+    }
+    ids
+}
+
+/// Write `contents` so that `path` holds either the old or the new
+/// contents after a crash: temp file, fsync, rename, fsync directory
+pub(super) fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let mut tmp = File::create(&tmp_path)?;
+    tmp.write_all(contents)?;
+    tmp.sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    // Persist the rename; directories cannot be opened on every platform
+    if let Some(dir) = path.parent().and_then(|parent| File::open(parent).ok()) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// Entries of a manifest section
+trait Journaled: Serialize {
+    fn journal_id(&self) -> String;
+}
+
+macro_rules! journaled {
+    ($($entry:ty => $id:ident),* $(,)?) => {
+        $(impl Journaled for $entry {
+            fn journal_id(&self) -> String {
+                self.$id.to_string()
+            }
+        })*
+    };
+}
+
+journaled! {
+    PersonEntry => person_id,
+    LocationEntry => location_id,
+    KeyEntry => key_id,
+    CertificateEntry => cert_id,
+    PkiHierarchyEntry => root_ca_id,
+    CrlManifestEntry => issuer_ca_id,
+    CertificateLogEntry => cert_id,
+    YubiKeyEntry => serial,
+    NatsOperatorEntry => operator_id,
+    NatsAccountEntry => account_id,
+    NatsUserEntry => user_id,
+    RelationshipEntry => relationship_id,
+    OutboxEntry => event_file,
+    DeadLetter => event_file,
+}
+
+/// Entries of every section whose ID is in `ids`, in manifest order
+fn matching(manifest: &KeyManifest, ids: &HashSet<String>) -> Result<Vec<(Section, String, Value)>, ProjectionError> {
+    fn section<T: Journaled>(
+        section: Section,
+        entries: &[T],
+        ids: &HashSet<String>,
+        found: &mut Vec<(Section, String, Value)>,
+    ) -> Result<(), ProjectionError> {
+        for entry in entries {
+            let id = entry.journal_id();
+            if ids.contains(&id) {
+                let value = serde_json::to_value(entry)
+                    .map_err(|e| ProjectionError::SerializationError(format!("Failed to serialize entry: {}", e)))?;
+                found.push((section, id, value));
+            }
+        }
+        Ok(())
+    }
+
+    let mut found = Vec::new();
+    section(Section::People, &manifest.people, ids, &mut found)?;
+    section(Section::Locations, &manifest.locations, ids, &mut found)?;
+    section(Section::Keys, &manifest.keys, ids, &mut found)?;
+    section(Section::Certificates, &manifest.certificates, ids, &mut found)?;
+    section(Section::PkiHierarchies, &manifest.pki_hierarchies, ids, &mut found)?;
+    section(Section::Crls, &manifest.crls, ids, &mut found)?;
+    section(Section::CertificateLog, &manifest.certificate_log, ids, &mut found)?;
+    section(Section::Yubikeys, &manifest.yubikeys, ids, &mut found)?;
+    section(Section::NatsOperators, &manifest.nats_operators, ids, &mut found)?;
+    section(Section::NatsAccounts, &manifest.nats_accounts, ids, &mut found)?;
+    section(Section::NatsUsers, &manifest.nats_users, ids, &mut found)?;
+    section(Section::Relationships, &manifest.relationships, ids, &mut found)?;
+    section(Section::Outbox, &manifest.outbox, ids, &mut found)?;
+    section(Section::DeadLetters, &manifest.dead_letters, ids, &mut found)?;
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::key::KeyExpirationExtendedEvent;
+    use crate::events::{KeyEvents, KeyGeneratedEvent};
+    use crate::types::{KeyAlgorithm, KeyMetadata, KeyPurpose};
+    use crate::value_objects::ActorId;
+    use tempfile::TempDir;
+
+    fn key_generated(key_id: Uuid) -> DomainEvent {
+        DomainEvent::Key(KeyEvents::KeyGenerated(KeyGeneratedEvent {
+            key_id,
+            algorithm: KeyAlgorithm::Ed25519,
+            purpose: KeyPurpose::Signing,
+            generated_at: Utc::now(),
+            generated_by: ActorId::system("test"),
+            hardware_backed: false,
+            metadata: KeyMetadata {
+                label: "ssh".to_string(),
+                description: None,
+                tags: vec![],
+                attributes: Default::default(),
+                jwt_kid: None,
+                jwt_alg: None,
+                jwt_use: None,
+                hardware_serial: None,
+                tpm: None,
+            },
+            ownership: None,
+            expires_at: None,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))
+    }
+
+    fn expiration_extended(key_id: Uuid) -> DomainEvent {
+        DomainEvent::Key(KeyEvents::KeyExpirationExtended(KeyExpirationExtendedEvent {
+            key_id,
+            fingerprint: "B".repeat(40),
+            previous_expires_at: None,
+            new_expires_at: Some(Utc::now() + chrono::Duration::days(30)),
+            extended_at: Utc::now(),
+            extended_by: ActorId::system("test"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))
+    }
+
+    fn manifest_json(projection: &OfflineKeyProjection) -> Value {
+        serde_json::to_value(projection.manifest()).unwrap()
+    }
+
+    fn checkpoint_position(root: &Path) -> u64 {
+        fs::read_to_string(root.join("manifest.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<KeyManifest>(&content).ok())
+            .map_or(0, |manifest| manifest.journal_position)
+    }
+
+    #[test]
+    fn test_reopen_replays_journal() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+
+        let key_ids: Vec<Uuid> = (0..100).map(|_| Uuid::now_v7()).collect();
+        let mut checkpoints = 0;
+        for key_id in &key_ids {
+            let before = checkpoint_position(temp_dir.path());
+            projection.apply(&key_generated(*key_id)).unwrap();
+            if checkpoint_position(temp_dir.path()) != before {
+                checkpoints += 1;
+            }
+        }
+        // The manifest is rewritten as it doubles, not on every event
+        assert!(checkpoints < 20, "{} checkpoints for 100 events", checkpoints);
+
+        // Changed, dead-lettered and command entries are journaled too
+        projection.apply(&expiration_extended(key_ids[3])).unwrap();
+        projection.apply(&expiration_extended(Uuid::now_v7())).unwrap();
+        projection.record_processed_command(Uuid::now_v7()).unwrap();
+        assert_eq!(projection.dead_letters().len(), 1);
+
+        let reopened = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        assert_eq!(manifest_json(&reopened), manifest_json(&projection));
+        assert!(reopened.manifest().keys[3].expires_at.is_some());
+    }
+
+    #[test]
+    fn test_torn_journal_record_is_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        projection.apply(&key_generated(Uuid::now_v7())).unwrap();
+        projection.apply(&key_generated(Uuid::now_v7())).unwrap();
+
+        // A crash in the middle of an append
+        let mut journal =
+            OpenOptions::new().append(true).create(true).open(temp_dir.path().join(JOURNAL_FILE)).unwrap();
+        journal.write_all(br#"{"sequence":999999,"op":"Set","sect"#).unwrap();
+
+        let reopened = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        assert_eq!(manifest_json(&reopened), manifest_json(&projection));
+    }
+
+    #[test]
+    fn test_stale_journal_records_are_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        projection.apply(&key_generated(Uuid::now_v7())).unwrap();
+        let journal_path = temp_dir.path().join(JOURNAL_FILE);
+        projection.apply(&key_generated(Uuid::now_v7())).unwrap();
+        let journal = fs::read(&journal_path).unwrap();
+
+        // Crash after the checkpoint, before the journal was truncated
+        projection.save_manifest().unwrap();
+        fs::write(&journal_path, journal).unwrap();
+
+        let reopened = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        assert_eq!(manifest_json(&reopened), manifest_json(&projection));
+        assert_eq!(reopened.manifest().keys.len(), 2);
+    }
+}
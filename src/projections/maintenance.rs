@@ -0,0 +1,391 @@
+//! Scheduled maintenance from the manifest
+//!
+//! [`MaintenanceScheduler`] scans the [`KeyManifest`] on a fixed interval
+//! and announces the periodic work it finds as `MaintenanceDue` events:
+//!
+//! ```text
+//! certificate not_after   within 30 days ─┐
+//! key expires_at          within 30 days ─┤
+//! delegation valid_until  within 14 days ─┼─▶ MaintenanceDue (once per item)
+//! CRL next_update         within  2 days ─┤
+//! YubiKey firmware        under advisory ─┘
+//!                                         └─▶ MaintenanceScanCompleted
+//! ```
+//!
+//! Work already announced is not announced again on the next scan. Work
+//! that stops being found (a certificate renewed, a delegation revoked) is
+//! dropped from [`MaintenanceScheduler::pending`], so the GUI's to-do list
+//! and downstream automation stay in step with the manifest. The
+//! scheduler is rebuilt from the event log with [`MaintenanceScheduler::apply`].
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::events::maintenance::{
+    MaintenanceDueEvent, MaintenanceEvents, MaintenanceScanCompletedEvent, MaintenanceUrgency,
+    MaintenanceWork,
+};
+use crate::events::DomainEvent;
+use crate::projections::KeyManifest;
+use crate::value_objects::FirmwareVersion;
+
+/// A published YubiKey firmware advisory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FirmwareAdvisory {
+    /// Vendor identifier, e.g. `YSA-2024-03`
+    pub advisory_id: String,
+    pub summary: String,
+    /// First affected version
+    pub affected_from: FirmwareVersion,
+    /// First version with the fix
+    pub fixed_in: FirmwareVersion,
+}
+
+impl FirmwareAdvisory {
+    pub fn new(
+        advisory_id: impl Into<String>,
+        summary: impl Into<String>,
+        affected_from: FirmwareVersion,
+        fixed_in: FirmwareVersion,
+    ) -> Self {
+        Self {
+            advisory_id: advisory_id.into(),
+            summary: summary.into(),
+            affected_from,
+            fixed_in,
+        }
+    }
+
+    /// Whether devices on `version` are affected
+    pub fn affects(&self, version: &FirmwareVersion) -> bool {
+        *version >= self.affected_from && *version < self.fixed_in
+    }
+}
+
+/// How far ahead of a deadline work is announced, in days
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceHorizons {
+    pub certificate_days: i64,
+    pub key_days: i64,
+    pub delegation_days: i64,
+    pub crl_days: i64,
+}
+
+impl Default for MaintenanceHorizons {
+    fn default() -> Self {
+        Self {
+            certificate_days: 30,
+            key_days: 30,
+            delegation_days: 14,
+            crl_days: 2,
+        }
+    }
+}
+
+/// Finds due maintenance work in the manifest on a recurring schedule
+#[derive(Debug, Clone)]
+pub struct MaintenanceScheduler {
+    interval: Duration,
+    horizons: MaintenanceHorizons,
+    advisories: Vec<FirmwareAdvisory>,
+    /// Announced work still outstanding, by `MaintenanceWork::dedup_key`
+    announced: BTreeMap<String, MaintenanceDueEvent>,
+    last_scan_at: Option<DateTime<Utc>>,
+}
+
+impl MaintenanceScheduler {
+    /// A scheduler scanning every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            horizons: MaintenanceHorizons::default(),
+            advisories: Vec::new(),
+            announced: BTreeMap::new(),
+            last_scan_at: None,
+        }
+    }
+
+    /// Builder: announce work earlier or later than the defaults
+    pub fn with_horizons(mut self, horizons: MaintenanceHorizons) -> Self {
+        self.horizons = horizons;
+        self
+    }
+
+    /// Builder: check YubiKeys against a firmware advisory
+    pub fn with_advisory(mut self, advisory: FirmwareAdvisory) -> Self {
+        self.advisories.push(advisory);
+        self
+    }
+
+    /// When the next scan should run (immediately if none has)
+    pub fn next_scan_at(&self) -> Option<DateTime<Utc>> {
+        self.last_scan_at.map(|last| last + self.interval)
+    }
+
+    pub fn is_scan_due(&self, now: DateTime<Utc>) -> bool {
+        !self.next_scan_at().is_some_and(|next| now < next)
+    }
+
+    /// Announced work not yet resolved, most urgent first
+    pub fn pending(&self) -> Vec<&MaintenanceDueEvent> {
+        let mut pending: Vec<&MaintenanceDueEvent> = self.announced.values().collect();
+        pending.sort_by(|a, b| {
+            b.urgency
+                .cmp(&a.urgency)
+                .then_with(|| a.due_at.cmp(&b.due_at))
+        });
+        pending
+    }
+
+    /// Work due in `manifest` as of `now`, soonest deadline first
+    pub fn due_work(&self, manifest: &KeyManifest, now: DateTime<Utc>) -> Vec<MaintenanceWork> {
+        let within = |days: i64| now + Duration::days(days);
+        let mut work = Vec::new();
+
+        let certificate_horizon = within(self.horizons.certificate_days);
+        for cert in &manifest.certificates {
+            let settled = cert.state.as_ref().is_some_and(|s| {
+                s.is_terminal() || s.is_renewed() || s.is_renewal_pending()
+            });
+            if !settled && cert.not_after <= certificate_horizon {
+                work.push(MaintenanceWork::CertificateExpiring {
+                    cert_id: cert.cert_id,
+                    subject: cert.subject.clone(),
+                    is_ca: cert.is_ca,
+                    not_after: cert.not_after,
+                });
+            }
+        }
+
+        let key_horizon = within(self.horizons.key_days);
+        for key in manifest.keys.iter().filter(|k| !k.revoked) {
+            if let Some(expires_at) = key.expires_at.filter(|at| *at <= key_horizon) {
+                work.push(MaintenanceWork::KeyExpiring {
+                    key_id: key.key_id,
+                    label: key.label.clone(),
+                    expires_at,
+                });
+            }
+        }
+
+        let delegation_horizon = within(self.horizons.delegation_days);
+        for delegation in manifest.delegations.iter().filter(|d| d.revoked_at.is_none()) {
+            if let Some(valid_until) = delegation.valid_until.filter(|at| *at <= delegation_horizon) {
+                work.push(MaintenanceWork::DelegationExpiring {
+                    delegation_id: delegation.delegation_id,
+                    delegate_id: delegation.delegate_id,
+                    valid_until,
+                });
+            }
+        }
+
+        let crl_horizon = within(self.horizons.crl_days);
+        for crl in manifest.crls.iter().filter(|c| c.next_update <= crl_horizon) {
+            work.push(MaintenanceWork::CrlUpdateDue {
+                issuer_ca_id: crl.issuer_ca_id,
+                crl_number: crl.crl_number,
+                next_update: crl.next_update,
+            });
+        }
+
+        for yubikey in &manifest.yubikeys {
+            let Some(reported) = &yubikey.firmware_version else { continue };
+            let Ok(version) = FirmwareVersion::parse(reported) else { continue };
+            for advisory in self.advisories.iter().filter(|a| a.affects(&version)) {
+                work.push(MaintenanceWork::YubiKeyFirmwareAdvisory {
+                    yubikey_serial: yubikey.serial.clone(),
+                    firmware_version: reported.clone(),
+                    advisory_id: advisory.advisory_id.clone(),
+                    summary: advisory.summary.clone(),
+                });
+            }
+        }
+
+        // Advisories have no deadline and sort last
+        work.sort_by_key(|w| (w.due_at().is_none(), w.due_at()));
+        work
+    }
+
+    /// Scan the manifest and announce newly found work
+    ///
+    /// Emits one `MaintenanceDue` per item not announced before, then
+    /// `MaintenanceScanCompleted`.
+    pub fn scan(
+        &mut self,
+        manifest: &KeyManifest,
+        now: DateTime<Utc>,
+        correlation_id: Uuid,
+    ) -> Vec<DomainEvent> {
+        let scan_id = Uuid::now_v7();
+        let work = self.due_work(manifest, now);
+        let found: HashSet<String> = work.iter().map(MaintenanceWork::dedup_key).collect();
+        self.announced.retain(|key, _| found.contains(key));
+
+        let mut events = Vec::new();
+        let mut items_due = Vec::new();
+        let mut already_announced = 0;
+        for work in work {
+            let key = work.dedup_key();
+            if self.announced.contains_key(&key) {
+                already_announced += 1;
+                continue;
+            }
+            let due_at = work.due_at();
+            let due = MaintenanceDueEvent {
+                work_item_id: Uuid::now_v7(),
+                scan_id,
+                urgency: match due_at {
+                    None => MaintenanceUrgency::Advisory,
+                    Some(at) if at <= now => MaintenanceUrgency::Overdue,
+                    Some(_) => MaintenanceUrgency::Upcoming,
+                },
+                due_at,
+                work,
+                detected_at: now,
+                correlation_id,
+                causation_id: Some(scan_id),
+            };
+            items_due.push(due.work_item_id);
+            self.announced.insert(key, due.clone());
+            events.push(DomainEvent::Maintenance(MaintenanceEvents::MaintenanceDue(due)));
+        }
+
+        self.last_scan_at = Some(now);
+        events.push(DomainEvent::Maintenance(MaintenanceEvents::MaintenanceScanCompleted(
+            MaintenanceScanCompletedEvent {
+                scan_id,
+                items_due,
+                already_announced,
+                scanned_at: now,
+                next_scan_at: now + self.interval,
+                correlation_id,
+                causation_id: None,
+            },
+        )));
+        events
+    }
+
+    /// Replay a recorded event, e.g. when restoring from the event log
+    pub fn apply(&mut self, event: &DomainEvent) {
+        match event {
+            DomainEvent::Maintenance(MaintenanceEvents::MaintenanceDue(e)) => {
+                self.announced.insert(e.work.dedup_key(), e.clone());
+            }
+            DomainEvent::Maintenance(MaintenanceEvents::MaintenanceScanCompleted(e)) => {
+                self.last_scan_at = Some(e.scanned_at);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projections::{CertificateEntry, DelegationManifestEntry, YubiKeyEntry};
+
+    fn manifest(now: DateTime<Utc>) -> KeyManifest {
+        KeyManifest {
+            certificates: vec![CertificateEntry {
+                cert_id: Uuid::now_v7(),
+                key_id: Uuid::now_v7(),
+                subject: "CN=api.example.com".to_string(),
+                issuer: Some("CN=Example Intermediate".to_string()),
+                serial_number: "01".to_string(),
+                not_before: now - Duration::days(335),
+                not_after: now + Duration::days(10),
+                is_ca: false,
+                file_path: String::new(),
+                state: None,
+            }],
+            delegations: vec![DelegationManifestEntry {
+                delegation_id: Uuid::now_v7(),
+                delegator_id: Uuid::now_v7(),
+                delegate_id: Uuid::now_v7(),
+                permissions: Vec::new(),
+                valid_from: now - Duration::days(30),
+                valid_until: Some(now - Duration::hours(1)),
+                revoked_at: None,
+                expired: false,
+            }],
+            yubikeys: vec![YubiKeyEntry {
+                serial: "12345678".to_string(),
+                provisioned_at: now,
+                slots_used: Vec::new(),
+                config_path: String::new(),
+                state: None,
+                firmware_version: Some("5.4.3".to_string()),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn due(events: &[DomainEvent]) -> Vec<&MaintenanceDueEvent> {
+        events
+            .iter()
+            .filter_map(|e| match e {
+                DomainEvent::Maintenance(MaintenanceEvents::MaintenanceDue(d)) => Some(d),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_announces_each_kind_of_due_work_once() {
+        let now = Utc::now();
+        let manifest = manifest(now);
+        let mut scheduler = MaintenanceScheduler::new(Duration::hours(24)).with_advisory(
+            FirmwareAdvisory::new(
+                "YSA-2024-03",
+                "ECDSA nonce side channel",
+                FirmwareVersion::new(5, 0, 0),
+                FirmwareVersion::new(5, 7, 0),
+            ),
+        );
+        assert!(scheduler.is_scan_due(now));
+
+        let events = scheduler.scan(&manifest, now, Uuid::now_v7());
+        let announced = due(&events);
+
+        assert_eq!(announced.len(), 3);
+        assert!(matches!(announced[0].work, MaintenanceWork::DelegationExpiring { .. }));
+        assert_eq!(announced[0].urgency, MaintenanceUrgency::Overdue);
+        assert!(matches!(announced[1].work, MaintenanceWork::CertificateExpiring { .. }));
+        assert_eq!(announced[1].urgency, MaintenanceUrgency::Upcoming);
+        assert_eq!(announced[2].urgency, MaintenanceUrgency::Advisory);
+        assert!(!scheduler.is_scan_due(now + Duration::hours(1)));
+
+        let rescan = scheduler.scan(&manifest, now + Duration::days(1), Uuid::now_v7());
+        assert!(due(&rescan).is_empty());
+        assert!(matches!(
+            rescan.last(),
+            Some(DomainEvent::Maintenance(MaintenanceEvents::MaintenanceScanCompleted(c)))
+                if c.already_announced == 3
+        ));
+    }
+
+    #[test]
+    fn test_resolved_work_leaves_pending_and_replay_restores_it() {
+        let now = Utc::now();
+        let mut manifest = manifest(now);
+        let mut scheduler = MaintenanceScheduler::new(Duration::hours(24));
+        let events = scheduler.scan(&manifest, now, Uuid::now_v7());
+        assert_eq!(scheduler.pending().len(), 2);
+
+        let mut restored = MaintenanceScheduler::new(Duration::hours(24));
+        events.iter().for_each(|e| restored.apply(e));
+        assert_eq!(restored.pending().len(), 2);
+        assert_eq!(restored.next_scan_at(), Some(now + Duration::hours(24)));
+
+        // The delegation is revoked, so its expiry no longer needs attention
+        manifest.delegations[0].revoked_at = Some(now);
+        scheduler.scan(&manifest, now + Duration::days(1), Uuid::now_v7());
+
+        let pending = scheduler.pending();
+        assert_eq!(pending.len(), 1);
+        assert!(matches!(pending[0].work, MaintenanceWork::CertificateExpiring { .. }));
+    }
+}
@@ -0,0 +1,428 @@
+//! Outbox for reliable event publication
+//!
+//! Every event applied to the offline projection is also recorded in the
+//! manifest's outbox, in the same manifest write that projects it. Events
+//! are published to JetStream later by [`OfflineKeyProjection::flush_outbox`],
+//! so a crash or a lost connection between writing the SD card and
+//! publishing never loses an event:
+//!
+//! ```text
+//! apply(event)
+//!   ├─→ events/{timestamp}_{event_id}.json
+//!   └─→ manifest.outbox += Pending(event_id)
+//!
+//! flush_outbox(publisher)          (any time later, repeatable)
+//!   for each Pending entry, in order:
+//!     publish with Nats-Msg-Id = event_id
+//!     ├─ ack  → Published { stream, sequence }   (manifest saved)
+//!     └─ fail → attempts += 1, last_error         (stop, retry later)
+//! ```
+//!
+//! Delivery is at least once: an event acknowledged by JetStream but not yet
+//! marked published when the process dies is sent again on the next flush
+//! with the same message ID. JetStream drops it inside its duplicate window;
+//! consumers reading older history use [`EventDeduplicator`].
+
+use std::collections::{HashSet, VecDeque};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{read_event_file, OfflineKeyProjection, ProjectionError};
+use crate::domain::nats::publisher::EventPublisher;
+use crate::events::DomainEvent;
+use crate::ports::JetStreamPort;
+
+/// One applied event awaiting or past publication
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    /// Event ID, published as the JetStream message ID
+    pub event_id: Uuid,
+    /// File name of the event under `events/`
+    pub event_file: String,
+    /// Aggregate type, e.g. `Person`
+    pub aggregate_type: String,
+    pub correlation_id: Uuid,
+    /// Cause carried by the event; the correlation ID is sent when absent
+    pub causation_id: Option<Uuid>,
+    pub status: OutboxStatus,
+    /// Failed publication attempts
+    pub attempts: u32,
+    /// Error of the most recent failed attempt
+    pub last_error: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl OutboxEntry {
+    /// Entry for an event just written to `event_file`
+    pub(super) fn pending(event: &DomainEvent, event_id: Uuid, event_file: String) -> Self {
+        // Every event struct carries its own correlation and causation IDs
+        let fields = serde_json::to_value(event).ok();
+        let id = |name: &str| {
+            fields
+                .as_ref()
+                .and_then(|value| value["event"][name].as_str())
+                .and_then(|id| Uuid::parse_str(id).ok())
+        };
+
+        Self {
+            event_id,
+            event_file,
+            aggregate_type: event.aggregate_type().to_string(),
+            correlation_id: id("correlation_id").unwrap_or(event_id),
+            causation_id: id("causation_id"),
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            recorded_at: Utc::now(),
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        matches!(self.status, OutboxStatus::Pending)
+    }
+}
+
+/// Publication status of an outbox entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OutboxStatus {
+    /// Not yet acknowledged by JetStream
+    Pending,
+    /// Acknowledged by JetStream
+    Published {
+        stream: String,
+        sequence: u64,
+        published_at: DateTime<Utc>,
+    },
+}
+
+/// Result of one [`OfflineKeyProjection::flush_outbox`] run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutboxFlushReport {
+    /// Entries acknowledged during this run
+    pub published: usize,
+    /// Of those, entries JetStream already held from an earlier attempt
+    pub duplicates: usize,
+    /// Entries still pending after this run
+    pub pending: usize,
+    /// Why the run stopped early, if it did
+    pub error: Option<String>,
+}
+
+impl OutboxFlushReport {
+    /// True when nothing is left to publish
+    pub fn is_complete(&self) -> bool {
+        self.pending == 0
+    }
+}
+
+impl OfflineKeyProjection {
+    /// Outbox entries not yet published, oldest first
+    pub fn pending_outbox(&self) -> impl Iterator<Item = &OutboxEntry> {
+        self.manifest.outbox.iter().filter(|entry| entry.is_pending())
+    }
+
+    /// Publish pending outbox entries to JetStream, in order
+    ///
+    /// Each acknowledgement is saved to the manifest before the next event
+    /// is sent. The first failed publication is recorded on its entry and
+    /// ends the run, so events are never published out of order; call again
+    /// to retry. Only reading the event log or writing the manifest returns
+    /// an error.
+    pub async fn flush_outbox<P: JetStreamPort>(
+        &mut self,
+        publisher: &EventPublisher<P>,
+    ) -> Result<OutboxFlushReport, ProjectionError> {
+        let mut report = OutboxFlushReport::default();
+
+        for index in 0..self.manifest.outbox.len() {
+            let entry = &self.manifest.outbox[index];
+            if !entry.is_pending() {
+                continue;
+            }
+            let event_path = self.root_path.join("events").join(&entry.event_file);
+            let event = read_event_file(&event_path, self.event_cipher.as_ref())?;
+            let causation_id = entry.causation_id.unwrap_or(entry.correlation_id);
+
+            let result = publisher
+                .publish_with_event_id(&event, entry.event_id, entry.correlation_id, causation_id)
+                .await;
+
+            let touched = self.touch([self.manifest.outbox[index].event_file.clone()])?;
+            let entry = &mut self.manifest.outbox[index];
+            match result {
+                Ok(ack) => {
+                    entry.status = OutboxStatus::Published {
+                        stream: ack.stream,
+                        sequence: ack.sequence,
+                        published_at: Utc::now(),
+                    };
+                    entry.last_error = None;
+                    report.published += 1;
+                    if ack.duplicate {
+                        report.duplicates += 1;
+                    }
+                    self.commit(touched)?;
+                }
+                Err(e) => {
+                    entry.attempts += 1;
+                    entry.last_error = Some(e.to_string());
+                    report.error = Some(e.to_string());
+                    self.commit(touched)?;
+                    break;
+                }
+            }
+        }
+
+        report.pending = self.pending_outbox().count();
+        Ok(report)
+    }
+
+    /// Drop published entries from the outbox, returning how many
+    ///
+    /// The event files themselves stay in the log.
+    pub fn compact_outbox(&mut self) -> Result<usize, ProjectionError> {
+        let before = self.manifest.outbox.len();
+        self.manifest.outbox.retain(|entry| entry.is_pending());
+        let removed = before - self.manifest.outbox.len();
+        if removed > 0 {
+            self.save_manifest()?;
+        }
+        Ok(removed)
+    }
+}
+
+/// Consumer-side filter for events delivered more than once
+///
+/// Remembers the last `capacity` event IDs (the `Nats-Msg-Id` header, or
+/// `StoredEvent::event_id` on replay). Size the window to cover at least
+/// the events an outbox can hold pending.
+#[derive(Debug, Clone)]
+pub struct EventDeduplicator {
+    capacity: usize,
+    seen: HashSet<Uuid>,
+    order: VecDeque<Uuid>,
+}
+
+impl EventDeduplicator {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            seen: HashSet::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    /// True the first time `event_id` is seen within the window
+    pub fn first_delivery(&mut self, event_id: Uuid) -> bool {
+        if !self.seen.insert(event_id) {
+            return false;
+        }
+        self.order.push_back(event_id);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::person::PersonCreatedEvent;
+    use crate::events::PersonEvents;
+    use crate::ports::{
+        ConsumerInfo, JetStreamConsumerConfig, JetStreamError, JetStreamHeaders,
+        JetStreamStreamConfig, JetStreamSubscription, KvBucketConfig, PublishAck, StreamInfo,
+    };
+    use crate::value_objects::ActorId;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+
+    /// Port that can be switched offline and remembers message IDs
+    #[derive(Clone, Default)]
+    struct FlakyPort {
+        offline: Arc<AtomicBool>,
+        message_ids: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl JetStreamPort for FlakyPort {
+        async fn publish(
+            &self,
+            subject: &str,
+            payload: &[u8],
+            headers: Option<&JetStreamHeaders>,
+        ) -> Result<PublishAck, JetStreamError> {
+            self.publish_with_id(subject, payload, "", headers).await
+        }
+
+        async fn publish_with_id(
+            &self,
+            _subject: &str,
+            _payload: &[u8],
+            message_id: &str,
+            _headers: Option<&JetStreamHeaders>,
+        ) -> Result<PublishAck, JetStreamError> {
+            if self.offline.load(Ordering::SeqCst) {
+                return Err(JetStreamError::ConnectionError("offline".to_string()));
+            }
+            let mut ids = self.message_ids.lock().unwrap();
+            let duplicate = ids.iter().any(|id| id == message_id);
+            ids.push(message_id.to_string());
+            Ok(PublishAck {
+                stream: "KEYS_EVENTS".to_string(),
+                sequence: ids.len() as u64,
+                duplicate,
+                domain: None,
+            })
+        }
+
+        async fn subscribe(
+            &self,
+            _stream: &str,
+            _consumer: &str,
+            _filter_subject: Option<&str>,
+        ) -> Result<Box<dyn JetStreamSubscription>, JetStreamError> {
+            Err(JetStreamError::SubscribeFailed("Not implemented".to_string()))
+        }
+
+        async fn stream_info(&self, _stream: &str) -> Result<StreamInfo, JetStreamError> {
+            Err(JetStreamError::StreamNotFound("Not implemented".to_string()))
+        }
+
+        async fn create_stream(&self, _config: &JetStreamStreamConfig) -> Result<StreamInfo, JetStreamError> {
+            Err(JetStreamError::StreamCreationFailed("Not implemented".to_string()))
+        }
+
+        async fn create_consumer(
+            &self,
+            _stream: &str,
+            _config: &JetStreamConsumerConfig,
+        ) -> Result<ConsumerInfo, JetStreamError> {
+            Err(JetStreamError::ConsumerCreationFailed("Not implemented".to_string()))
+        }
+
+        async fn is_connected(&self) -> bool {
+            !self.offline.load(Ordering::SeqCst)
+        }
+
+        async fn kv_get(&self, _bucket: &str, _key: &str) -> Result<Option<Vec<u8>>, JetStreamError> {
+            Ok(None)
+        }
+
+        async fn kv_put(&self, _bucket: &str, _key: &str, _value: &[u8]) -> Result<u64, JetStreamError> {
+            Ok(1)
+        }
+
+        async fn kv_delete(&self, _bucket: &str, _key: &str) -> Result<(), JetStreamError> {
+            Ok(())
+        }
+
+        async fn kv_keys(&self, _bucket: &str, _prefix: &str) -> Result<Vec<String>, JetStreamError> {
+            Ok(vec![])
+        }
+
+        async fn kv_create_bucket(&self, _bucket: &str, _config: &KvBucketConfig) -> Result<(), JetStreamError> {
+            Ok(())
+        }
+    }
+
+    fn person_created(name: &str) -> DomainEvent {
+        DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+            person_id: Uuid::now_v7(),
+            name: name.to_string(),
+            email: None,
+            title: None,
+            department: None,
+            organization_id: Uuid::now_v7(),
+            created_by: ActorId::system("test"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_failed_publication_stays_pending_and_retries_in_order() {
+        let dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(dir.path()).unwrap();
+        let alice = person_created("Alice");
+        projection.apply(&alice).unwrap();
+        projection.apply(&person_created("Bob")).unwrap();
+        let DomainEvent::Person(PersonEvents::PersonCreated(created)) = &alice else { unreachable!() };
+        assert_eq!(projection.manifest().outbox[0].correlation_id, created.correlation_id);
+        let event_ids: Vec<Uuid> = projection.pending_outbox().map(|e| e.event_id).collect();
+        assert_eq!(event_ids.len(), 2);
+
+        let port = FlakyPort::default();
+        let publisher = EventPublisher::new(port.clone());
+
+        port.offline.store(true, Ordering::SeqCst);
+        let report = projection.flush_outbox(&publisher).await.unwrap();
+        assert_eq!((report.published, report.pending), (0, 2));
+        assert!(report.error.is_some());
+        // Only the first entry was attempted; the second waits its turn
+        let outbox = &projection.manifest().outbox;
+        assert_eq!((outbox[0].attempts, outbox[1].attempts), (1, 0));
+
+        // Reopening from disk keeps the pending entries
+        let mut projection = OfflineKeyProjection::new(dir.path()).unwrap();
+        port.offline.store(false, Ordering::SeqCst);
+        let report = projection.flush_outbox(&publisher).await.unwrap();
+        assert!(report.is_complete());
+        assert_eq!(report.published, 2);
+        assert_eq!(
+            *port.message_ids.lock().unwrap(),
+            event_ids.iter().map(Uuid::to_string).collect::<Vec<_>>()
+        );
+
+        assert_eq!(projection.compact_outbox().unwrap(), 2);
+        assert_eq!(projection.flush_outbox(&publisher).await.unwrap(), OutboxFlushReport::default());
+    }
+
+    #[tokio::test]
+    async fn test_unmarked_publication_is_resent_with_same_id() {
+        let dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(dir.path()).unwrap();
+        projection.apply(&person_created("Alice")).unwrap();
+
+        let port = FlakyPort::default();
+        let publisher = EventPublisher::new(port.clone());
+        projection.flush_outbox(&publisher).await.unwrap();
+
+        // Simulate a crash after the ack but before the manifest was saved
+        let mut manifest = projection.manifest().clone();
+        manifest.outbox[0].status = OutboxStatus::Pending;
+        let mut projection = OfflineKeyProjection::new(dir.path()).unwrap();
+        projection.manifest = manifest;
+
+        let report = projection.flush_outbox(&publisher).await.unwrap();
+        assert_eq!((report.published, report.duplicates), (1, 1));
+
+        let mut dedup = EventDeduplicator::new(16);
+        let delivered: Vec<bool> = port
+            .message_ids
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|id| dedup.first_delivery(Uuid::parse_str(id).unwrap()))
+            .collect();
+        assert_eq!(delivered, vec![true, false]);
+    }
+
+    #[test]
+    fn test_deduplicator_window() {
+        let mut dedup = EventDeduplicator::new(2);
+        let (a, b, c) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        assert!(dedup.first_delivery(a));
+        assert!(dedup.first_delivery(b));
+        assert!(!dedup.first_delivery(a));
+        assert!(dedup.first_delivery(c));
+        // `a` has left the window
+        assert!(dedup.first_delivery(a));
+    }
+}
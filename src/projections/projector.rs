@@ -0,0 +1,692 @@
+//! Continuous projector
+//!
+//! [`rebuild`](super::rebuild) reconstructs the read models once; the
+//! projector keeps them in step with a live JetStream event stream. It
+//! replays the stream into a working offline projection and, after every
+//! batch and whenever the stream goes quiet, hands the manifest to each
+//! registered sink:
+//!
+//! ```text
+//! JetStream consumer (deliver all) ──► reader task ──► channel
+//!                                                        ↓
+//! ProjectorService::run ── replay ──► OfflineKeyProjection (working copy)
+//!     └─ flush (batch full or flush interval elapsed)
+//!          ├─ Neo4jSink   → Neo4jPort::execute_batch(graph of the manifest)
+//!          ├─ SqliteSink  → SqliteReadModel::sync(manifest)   (sqlite feature)
+//!          └─ NscSink     → NSC store per operator under a directory
+//!
+//! ProjectorMetrics, per sink: status, applied sequence, lag, last sync
+//! ```
+//!
+//! Sinks report the existing [`ProjectionStatus`] states: `Syncing` while
+//! behind the stream, `Connected` once caught up and `Error` after a failed
+//! sync. A failed sink keeps its applied sequence, so its lag grows until a
+//! later flush succeeds; the other sinks are not held back.
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::rebuild::{graph, nsc_stores, EventStream};
+use super::{KeyManifest, OfflineKeyProjection, ProjectionError};
+use crate::events::DomainEvent;
+use crate::ports::neo4j::Neo4jPort;
+use crate::ports::{
+    JetStreamConsumerConfig, JetStreamMessage, JetStreamPort, JetStreamStreamConfig, JsAckPolicy, JsDeliverPolicy,
+};
+use crate::projection::ProjectionStatus;
+
+/// Where the projector reads from and how often it flushes
+#[derive(Debug, Clone)]
+pub struct ProjectorConfig {
+    /// JetStream stream holding the domain events
+    pub stream: String,
+    /// Subject filter, e.g. one organization's events
+    pub filter_subject: Option<String>,
+    /// Events applied before the sinks are synced
+    pub batch_size: usize,
+    /// Longest time an applied event waits for the sinks
+    pub flush_interval: Duration,
+}
+
+impl Default for ProjectorConfig {
+    fn default() -> Self {
+        Self {
+            stream: JetStreamStreamConfig::default().name,
+            filter_subject: None,
+            batch_size: 100,
+            flush_interval: Duration::from_secs(2),
+        }
+    }
+}
+
+/// A read model kept in sync by the projector
+///
+/// `sync` receives the whole manifest and every event seen so far and
+/// must be idempotent: a sink that failed is handed the same state again.
+#[async_trait]
+pub trait ProjectionSink: Send {
+    /// Name shown in the metrics, e.g. `neo4j`
+    fn name(&self) -> &str;
+
+    /// Bring the read model up to date, returning the items written
+    async fn sync(&mut self, manifest: &KeyManifest, events: &EventStream) -> Result<usize, ProjectionError>;
+}
+
+// ============================================================================
+// METRICS
+// ============================================================================
+
+/// Sync state of one sink
+#[derive(Debug, Clone)]
+pub struct SinkMetrics {
+    pub name: String,
+    pub status: ProjectionStatus,
+    /// Stream sequence of the last event the sink has synced
+    pub applied_sequence: u64,
+    /// Events in the stream the sink has not synced yet
+    pub lag: u64,
+    pub last_sync_at: Option<DateTime<Utc>>,
+    pub last_sync_duration: Duration,
+    /// Items written by the last successful sync
+    pub last_items: usize,
+    pub syncs: u64,
+    pub failures: u64,
+}
+
+impl SinkMetrics {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            status: ProjectionStatus::NotConfigured,
+            applied_sequence: 0,
+            lag: 0,
+            last_sync_at: None,
+            last_sync_duration: Duration::ZERO,
+            last_items: 0,
+            syncs: 0,
+            failures: 0,
+        }
+    }
+}
+
+/// Progress of the projector and its sinks
+#[derive(Debug, Clone, Default)]
+pub struct ProjectorMetrics {
+    /// Last sequence of the stream, as last reported by JetStream
+    pub stream_sequence: u64,
+    /// Sequence of the last event applied to the working projection
+    pub received_sequence: u64,
+    pub events_applied: u64,
+    /// Messages that were not domain events of this projection
+    pub events_skipped: u64,
+    pub sinks: Vec<SinkMetrics>,
+}
+
+impl ProjectorMetrics {
+    pub fn sink(&self, name: &str) -> Option<&SinkMetrics> {
+        self.sinks.iter().find(|sink| sink.name == name)
+    }
+}
+
+/// Shared view of the metrics, readable while the projector runs
+pub type ProjectorMetricsHandle = Arc<RwLock<ProjectorMetrics>>;
+
+// ============================================================================
+// SERVICE
+// ============================================================================
+
+/// Long-running service projecting a JetStream event stream into sinks
+pub struct ProjectorService {
+    config: ProjectorConfig,
+    projection: OfflineKeyProjection,
+    events: EventStream,
+    sinks: Vec<Box<dyn ProjectionSink>>,
+    metrics: ProjectorMetricsHandle,
+}
+
+impl ProjectorService {
+    /// Projector replaying into `projection`, which should start empty:
+    /// the consumer delivers the stream from its first event
+    pub fn new(projection: OfflineKeyProjection, config: ProjectorConfig) -> Self {
+        Self {
+            config,
+            projection,
+            events: EventStream::default(),
+            sinks: Vec::new(),
+            metrics: Arc::new(RwLock::new(ProjectorMetrics::default())),
+        }
+    }
+
+    pub fn with_sink(mut self, sink: impl ProjectionSink + 'static) -> Self {
+        self.update_metrics(|metrics| metrics.sinks.push(SinkMetrics::new(sink.name())));
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Handle for reading metrics from other tasks, e.g. a status endpoint
+    pub fn metrics_handle(&self) -> ProjectorMetricsHandle {
+        Arc::clone(&self.metrics)
+    }
+
+    pub fn metrics(&self) -> ProjectorMetrics {
+        self.metrics.read().map(|m| m.clone()).unwrap_or_default()
+    }
+
+    /// Working projection the sinks are fed from
+    pub fn projection(&self) -> &OfflineKeyProjection {
+        &self.projection
+    }
+
+    /// Follow the stream until it ends or `shutdown` completes, syncing
+    /// the sinks a last time before returning their metrics
+    ///
+    /// Only errors of the working projection itself (e.g. a full disk)
+    /// stop the projector; sink failures are recorded in the metrics.
+    pub async fn run(
+        &mut self,
+        port: &dyn JetStreamPort,
+        shutdown: impl Future<Output = ()> + Send,
+    ) -> Result<ProjectorMetrics, ProjectionError> {
+        let consumer = JetStreamConsumerConfig {
+            name: format!("projector-{}", Uuid::now_v7()),
+            durable_name: None,
+            filter_subject: self.config.filter_subject.clone(),
+            ack_policy: JsAckPolicy::None,
+            deliver_policy: JsDeliverPolicy::All,
+            ack_wait_ns: None,
+            max_deliver: None,
+            description: Some("cim-keys continuous projector".to_string()),
+        };
+        let consumer = port
+            .create_consumer(&self.config.stream, &consumer)
+            .await
+            .map_err(|e| ProjectionError::IoError(format!("Failed to create projector consumer: {}", e)))?;
+        let mut subscription = port
+            .subscribe(&self.config.stream, &consumer.name, self.config.filter_subject.as_deref())
+            .await
+            .map_err(|e| ProjectionError::IoError(format!("Failed to subscribe to {}: {}", self.config.stream, e)))?;
+        info!("Projector following {} with {} sinks", self.config.stream, self.sinks.len());
+        self.update_metrics(|metrics| {
+            for sink in &mut metrics.sinks {
+                sink.status = ProjectionStatus::Syncing;
+            }
+        });
+
+        // The subscription is read on its own task so that waiting for the
+        // flush interval never drops a message half-received
+        let (sender, mut receiver) = mpsc::channel(self.config.batch_size.max(1));
+        let reader = tokio::spawn(async move {
+            while let Some(message) = subscription.next().await {
+                if sender.send(message).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let result = self.follow(port, &mut receiver, shutdown).await;
+        reader.abort();
+        result?;
+        self.flush(port).await;
+        Ok(self.metrics())
+    }
+
+    async fn follow(
+        &mut self,
+        port: &dyn JetStreamPort,
+        receiver: &mut mpsc::Receiver<JetStreamMessage>,
+        shutdown: impl Future<Output = ()> + Send,
+    ) -> Result<(), ProjectionError> {
+        tokio::pin!(shutdown);
+        let mut ticker = tokio::time::interval(self.config.flush_interval);
+        let mut pending = 0usize;
+
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => {
+                    info!("Projector shutting down");
+                    return Ok(());
+                }
+                _ = ticker.tick() => {
+                    if pending > 0 || self.sinks_behind() {
+                        self.flush(port).await;
+                        pending = 0;
+                    }
+                }
+                message = receiver.recv() => {
+                    let Some(message) = message else {
+                        info!("Event stream {} ended", self.config.stream);
+                        return Ok(());
+                    };
+                    if self.apply(&message)? {
+                        pending += 1;
+                    }
+                    if pending >= self.config.batch_size {
+                        self.flush(port).await;
+                        pending = 0;
+                        ticker.reset();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Apply one message to the working projection; false when skipped
+    fn apply(&mut self, message: &JetStreamMessage) -> Result<bool, ProjectionError> {
+        let received = self.metrics().received_sequence;
+        if message.sequence <= received {
+            debug!("Skipping redelivered sequence {}", message.sequence);
+            return Ok(false);
+        }
+
+        let skip = |reason: String| {
+            warn!("Skipping sequence {} on {}: {}", message.sequence, message.subject, reason);
+        };
+        let applied = match serde_json::from_slice::<DomainEvent>(&message.payload) {
+            Err(e) => {
+                skip(format!("not a domain event: {}", e));
+                false
+            }
+            Ok(event) => match self.projection.replay(&event) {
+                Ok(()) => {
+                    self.events.push(event);
+                    true
+                }
+                // Other organizations' events on a shared stream
+                Err(ProjectionError::OrganizationMismatch(reason)) => {
+                    skip(reason);
+                    false
+                }
+                Err(e) => return Err(e),
+            },
+        };
+
+        self.update_metrics(|metrics| {
+            metrics.received_sequence = message.sequence;
+            metrics.stream_sequence = metrics.stream_sequence.max(message.sequence);
+            if applied {
+                metrics.events_applied += 1;
+            } else {
+                metrics.events_skipped += 1;
+            }
+            for sink in &mut metrics.sinks {
+                sink.lag = metrics.stream_sequence.saturating_sub(sink.applied_sequence);
+            }
+        });
+        Ok(applied)
+    }
+
+    fn sinks_behind(&self) -> bool {
+        let metrics = self.metrics();
+        metrics.sinks.iter().any(|sink| sink.applied_sequence < metrics.received_sequence)
+    }
+
+    /// Sync every sink that is behind the working projection
+    async fn flush(&mut self, port: &dyn JetStreamPort) {
+        // Lag is measured against the stream, not just what was received
+        if let Ok(info) = port.stream_info(&self.config.stream).await {
+            self.update_metrics(|metrics| metrics.stream_sequence = metrics.stream_sequence.max(info.last_seq));
+        }
+        let received = self.metrics().received_sequence;
+        let manifest = self.projection.manifest();
+
+        for (index, sink) in self.sinks.iter_mut().enumerate() {
+            let behind = self
+                .metrics
+                .read()
+                .map(|metrics| metrics.sinks[index].applied_sequence < received)
+                .unwrap_or(true);
+            if !behind {
+                continue;
+            }
+
+            let started = Instant::now();
+            let result = sink.sync(manifest, &self.events).await;
+            let elapsed = started.elapsed();
+            if let Ok(mut metrics) = self.metrics.write() {
+                let stream_sequence = metrics.stream_sequence;
+                let state = &mut metrics.sinks[index];
+                state.last_sync_duration = elapsed;
+                match result {
+                    Ok(items) => {
+                        debug!("Synced {} items to {} in {:?}", items, state.name, elapsed);
+                        state.applied_sequence = received;
+                        state.last_items = items;
+                        state.last_sync_at = Some(Utc::now());
+                        state.syncs += 1;
+                        state.status = if received >= stream_sequence {
+                            ProjectionStatus::Connected
+                        } else {
+                            ProjectionStatus::Syncing
+                        };
+                    }
+                    Err(e) => {
+                        warn!("Projection sink {} failed: {}", state.name, e);
+                        state.failures += 1;
+                        state.status = ProjectionStatus::Error { message: e.to_string() };
+                    }
+                }
+                state.lag = stream_sequence.saturating_sub(state.applied_sequence);
+            }
+        }
+    }
+
+    fn update_metrics(&self, update: impl FnOnce(&mut ProjectorMetrics)) {
+        if let Ok(mut metrics) = self.metrics.write() {
+            update(&mut metrics);
+        }
+    }
+}
+
+// ============================================================================
+// SINKS
+// ============================================================================
+
+/// Neo4j graph, replaced by MERGE queries on every sync
+pub struct Neo4jSink {
+    port: Arc<dyn Neo4jPort>,
+}
+
+impl Neo4jSink {
+    pub fn new(port: Arc<dyn Neo4jPort>) -> Self {
+        Self { port }
+    }
+}
+
+#[async_trait]
+impl ProjectionSink for Neo4jSink {
+    fn name(&self) -> &str {
+        "neo4j"
+    }
+
+    async fn sync(&mut self, manifest: &KeyManifest, _events: &EventStream) -> Result<usize, ProjectionError> {
+        let batch = graph(manifest)
+            .to_cypher()
+            .map_err(|e| ProjectionError::SerializationError(format!("Failed to build Cypher: {}", e)))?;
+        let result = self
+            .port
+            .execute_batch(&batch)
+            .await
+            .map_err(|e| ProjectionError::IoError(format!("Neo4j: {}", e)))?;
+        Ok(result.queries_executed)
+    }
+}
+
+/// SQLite read model next to the working projection
+#[cfg(feature = "sqlite")]
+pub struct SqliteSink {
+    model: super::sqlite::SqliteReadModel,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteSink {
+    pub fn new(model: super::sqlite::SqliteReadModel) -> Self {
+        Self { model }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+#[async_trait]
+impl ProjectionSink for SqliteSink {
+    fn name(&self) -> &str {
+        "sqlite"
+    }
+
+    async fn sync(&mut self, manifest: &KeyManifest, _events: &EventStream) -> Result<usize, ProjectionError> {
+        self.model.sync(manifest)?;
+        Ok(manifest.people.len() + manifest.locations.len() + manifest.keys.len() + manifest.certificates.len())
+    }
+}
+
+/// NSC stores, one directory per operator under `root`
+///
+/// Stores are rewritten in place; JWTs come from the events, and seeds are
+/// never written.
+pub struct NscSink {
+    root: PathBuf,
+}
+
+impl NscSink {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl ProjectionSink for NscSink {
+    fn name(&self) -> &str {
+        "nsc"
+    }
+
+    async fn sync(&mut self, manifest: &KeyManifest, events: &EventStream) -> Result<usize, ProjectionError> {
+        let mut missing_jwts = Vec::new();
+        let stores = nsc_stores(manifest, events, &mut missing_jwts)?;
+        if !missing_jwts.is_empty() {
+            debug!("{} NATS entities have no signed JWT yet", missing_jwts.len());
+        }
+
+        let io = |e: std::io::Error| ProjectionError::IoError(format!("Failed to write NSC store: {}", e));
+        let mut written = 0;
+        for store in stores.values() {
+            let root = self.root.join(&store.store_name);
+            for directory in &store.directories {
+                std::fs::create_dir_all(root.join(directory)).map_err(io)?;
+            }
+            for file in &store.files {
+                let path = root.join(&file.path);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent).map_err(io)?;
+                }
+                std::fs::write(&path, &file.content).map_err(io)?;
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::person::PersonCreatedEvent;
+    use crate::events::PersonEvents;
+    use crate::ports::{
+        ConsumerInfo, JetStreamError, JetStreamHeaders, JetStreamSubscription, KvBucketConfig, PublishAck,
+        StreamInfo,
+    };
+    use crate::value_objects::ActorId;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Port whose consumer delivers a fixed list of messages, then ends
+    struct StreamPort {
+        messages: Mutex<Vec<JetStreamMessage>>,
+    }
+
+    struct ListSubscription(std::vec::IntoIter<JetStreamMessage>);
+
+    #[async_trait]
+    impl JetStreamSubscription for ListSubscription {
+        async fn next(&mut self) -> Option<JetStreamMessage> {
+            self.0.next()
+        }
+
+        async fn unsubscribe(self: Box<Self>) -> Result<(), JetStreamError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl JetStreamPort for StreamPort {
+        async fn publish(
+            &self,
+            _subject: &str,
+            _payload: &[u8],
+            _headers: Option<&JetStreamHeaders>,
+        ) -> Result<PublishAck, JetStreamError> {
+            Err(JetStreamError::PublishFailed("read only".to_string()))
+        }
+
+        async fn publish_with_id(
+            &self,
+            _subject: &str,
+            _payload: &[u8],
+            _message_id: &str,
+            _headers: Option<&JetStreamHeaders>,
+        ) -> Result<PublishAck, JetStreamError> {
+            Err(JetStreamError::PublishFailed("read only".to_string()))
+        }
+
+        async fn subscribe(
+            &self,
+            _stream: &str,
+            _consumer: &str,
+            _filter_subject: Option<&str>,
+        ) -> Result<Box<dyn JetStreamSubscription>, JetStreamError> {
+            let messages = std::mem::take(&mut *self.messages.lock().unwrap());
+            Ok(Box::new(ListSubscription(messages.into_iter())))
+        }
+
+        async fn stream_info(&self, _stream: &str) -> Result<StreamInfo, JetStreamError> {
+            Err(JetStreamError::StreamNotFound("Not implemented".to_string()))
+        }
+
+        async fn create_stream(&self, _config: &JetStreamStreamConfig) -> Result<StreamInfo, JetStreamError> {
+            Err(JetStreamError::StreamCreationFailed("Not implemented".to_string()))
+        }
+
+        async fn create_consumer(
+            &self,
+            stream: &str,
+            config: &JetStreamConsumerConfig,
+        ) -> Result<ConsumerInfo, JetStreamError> {
+            Ok(ConsumerInfo {
+                name: config.name.clone(),
+                stream: stream.to_string(),
+                num_pending: 0,
+                num_redelivered: 0,
+                delivered_seq: 0,
+                ack_floor_seq: 0,
+            })
+        }
+
+        async fn is_connected(&self) -> bool {
+            true
+        }
+
+        async fn kv_get(&self, _bucket: &str, _key: &str) -> Result<Option<Vec<u8>>, JetStreamError> {
+            Ok(None)
+        }
+
+        async fn kv_put(&self, _bucket: &str, _key: &str, _value: &[u8]) -> Result<u64, JetStreamError> {
+            Ok(1)
+        }
+
+        async fn kv_delete(&self, _bucket: &str, _key: &str) -> Result<(), JetStreamError> {
+            Ok(())
+        }
+
+        async fn kv_keys(&self, _bucket: &str, _prefix: &str) -> Result<Vec<String>, JetStreamError> {
+            Ok(vec![])
+        }
+
+        async fn kv_create_bucket(&self, _bucket: &str, _config: &KvBucketConfig) -> Result<(), JetStreamError> {
+            Ok(())
+        }
+    }
+
+    /// Sink recording the people it was synced with, failing on request
+    struct RecordingSink {
+        name: &'static str,
+        fail: bool,
+        people: Arc<Mutex<usize>>,
+    }
+
+    #[async_trait]
+    impl ProjectionSink for RecordingSink {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn sync(&mut self, manifest: &KeyManifest, _events: &EventStream) -> Result<usize, ProjectionError> {
+            if self.fail {
+                return Err(ProjectionError::IoError("database unreachable".to_string()));
+            }
+            *self.people.lock().unwrap() = manifest.people.len();
+            Ok(manifest.people.len())
+        }
+    }
+
+    fn message(sequence: u64, payload: Vec<u8>) -> JetStreamMessage {
+        JetStreamMessage {
+            subject: "keys.events.person.person_created".to_string(),
+            payload,
+            headers: JetStreamHeaders::new(),
+            sequence,
+            timestamp: 0,
+            num_delivered: 1,
+            reply: None,
+        }
+    }
+
+    fn person_created(name: &str) -> Vec<u8> {
+        serde_json::to_vec(&DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+            person_id: Uuid::now_v7(),
+            name: name.to_string(),
+            email: None,
+            title: None,
+            department: None,
+            organization_id: Uuid::now_v7(),
+            created_by: ActorId::system("test"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        })))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sinks_follow_the_stream_and_report_lag() {
+        let alice = person_created("Alice");
+        let port = StreamPort {
+            messages: Mutex::new(vec![
+                message(1, alice.clone()),
+                message(2, b"not json".to_vec()),
+                message(2, alice),
+                message(3, person_created("Bob")),
+            ]),
+        };
+        let dir = TempDir::new().unwrap();
+        let people = Arc::new(Mutex::new(0));
+        let config = ProjectorConfig { batch_size: 2, ..ProjectorConfig::default() };
+        let mut projector = ProjectorService::new(OfflineKeyProjection::new(dir.path()).unwrap(), config)
+            .with_sink(RecordingSink { name: "graph", fail: false, people: Arc::clone(&people) })
+            .with_sink(RecordingSink { name: "broken", fail: true, people: Arc::new(Mutex::new(0)) });
+        let handle = projector.metrics_handle();
+
+        let metrics = projector.run(&port, std::future::pending()).await.unwrap();
+
+        assert_eq!(*people.lock().unwrap(), 2);
+        assert_eq!(metrics.received_sequence, 3);
+        assert_eq!(metrics.events_applied, 2);
+        // The redelivered sequence 2 is dropped before it is parsed
+        assert_eq!(metrics.events_skipped, 1);
+
+        let graph = metrics.sink("graph").unwrap();
+        assert_eq!(graph.status, ProjectionStatus::Connected);
+        assert_eq!((graph.applied_sequence, graph.lag), (3, 0));
+
+        let broken = metrics.sink("broken").unwrap();
+        assert!(broken.status.is_error());
+        assert_eq!((broken.applied_sequence, broken.lag), (0, 3));
+        assert!(broken.failures >= 1);
+
+        assert_eq!(handle.read().unwrap().received_sequence, 3);
+    }
+}
@@ -0,0 +1,455 @@
+//! Projection rebuild
+//!
+//! Reconstructs every read model from an ordered event stream: the offline
+//! key projection, the Neo4j graph (as a `.cypher` script) and the NSC
+//! store. Used to recover a damaged partition and to check that the
+//! projections on disk still agree with the events they were built from.
+//!
+//! ```text
+//! EventStream (projection log, CID store, event store, JetStream replay)
+//!     ↓ rebuild()
+//! OfflineKeyProjection (fresh directory)
+//!     ├─→ Cypher script        (DomainGraphBuilder)
+//!     └─→ NscStore per operator (CredentialsToNscStoreProjection)
+//!
+//! RebuildReport: events per aggregate, entities that differ from the
+//!                existing manifest, NATS entities without a signed JWT
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::{read_event_file, KeyManifest, OfflineKeyProjection, ProjectionError};
+use crate::crypto::EventLogCipher;
+use crate::domain::nats::replay::ReplayResult;
+use crate::events::{DomainEvent, EventEnvelope, NatsOperatorEvents};
+use crate::ports::neo4j::GraphNode;
+use crate::projection::neo4j::{certificate_signs, DomainGraphBuilder};
+use crate::projection::nscstore::{
+    credentials_to_nscstore, AccountCredentials, DomainNatsCredentials, NscStore,
+    OperatorCredentials, UserCredentials,
+};
+use crate::projection::Projection;
+
+/// Ordered events to rebuild from
+#[derive(Debug, Clone, Default)]
+pub struct EventStream {
+    events: Vec<DomainEvent>,
+}
+
+impl EventStream {
+    /// Stream of events already in order
+    pub fn new(events: Vec<DomainEvent>) -> Self {
+        Self { events }
+    }
+
+    /// Stream from envelopes in order, e.g. `CidEventStore::list_all_events`
+    /// or `EventStore::load`
+    pub fn from_envelopes(envelopes: impl IntoIterator<Item = EventEnvelope>) -> Self {
+        Self::new(envelopes.into_iter().map(|envelope| envelope.event).collect())
+    }
+
+    /// Stream from a JetStream replay, in stream sequence order
+    pub fn from_replay(result: &ReplayResult) -> Self {
+        Self::new(result.events.iter().map(|stored| stored.event.clone()).collect())
+    }
+
+    /// Stream from the event log of an offline projection at `root`
+    ///
+    /// Reads `events/*.json` in filename (timestamp) order, as
+    /// [`OfflineKeyProjection::rebuild_from_events`] does.
+    pub fn from_projection_log<P: AsRef<Path>>(root: P) -> Result<Self, ProjectionError> {
+        Self::from_encrypted_projection_log(root, None)
+    }
+
+    /// Stream from an event log whose files may be encrypted
+    pub fn from_encrypted_projection_log<P: AsRef<Path>>(
+        root: P,
+        cipher: Option<&EventLogCipher>,
+    ) -> Result<Self, ProjectionError> {
+        let events_dir = root.as_ref().join("events");
+        let mut event_files: Vec<_> = fs::read_dir(&events_dir)
+            .map_err(|e| ProjectionError::IoError(format!("Failed to read events directory: {}", e)))?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        event_files.sort_by_key(|entry| entry.file_name());
+
+        let events = event_files
+            .iter()
+            .map(|entry| read_event_file(&entry.path(), cipher))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(events))
+    }
+
+    /// Append the next event of the stream
+    pub fn push(&mut self, event: DomainEvent) {
+        self.events.push(event);
+    }
+
+    /// Events in order
+    pub fn events(&self) -> &[DomainEvent] {
+        &self.events
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+/// Everything rebuilt from one event stream
+pub struct RebuildOutput {
+    /// Offline projection rebuilt in the target directory
+    pub projection: OfflineKeyProjection,
+    /// Neo4j import script for the rebuilt manifest
+    pub cypher: String,
+    /// One NSC store per NATS operator, keyed by operator ID
+    pub nsc_stores: HashMap<Uuid, NscStore>,
+    /// What was replayed and how it compares to the existing projection
+    pub report: RebuildReport,
+}
+
+/// Summary of a rebuild
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RebuildReport {
+    /// Events replayed
+    pub events_replayed: usize,
+    /// Events replayed per aggregate type
+    pub events_by_aggregate: BTreeMap<&'static str, usize>,
+    /// Entities that differ between the existing and the rebuilt manifest
+    pub divergences: Vec<Divergence>,
+    /// Public keys of NATS operators, accounts and users with no signed
+    /// JWT in the stream; their NSC store entries have an empty JWT
+    pub missing_jwts: Vec<String>,
+    /// Events the rebuilt projection parked as dead letters
+    pub dead_letters: usize,
+}
+
+impl RebuildReport {
+    /// True when the rebuilt manifest matches the existing one and every
+    /// event was applied
+    pub fn is_consistent(&self) -> bool {
+        self.divergences.is_empty() && self.dead_letters == 0
+    }
+}
+
+/// One entity that differs between the existing and the rebuilt manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Divergence {
+    /// Manifest collection, e.g. `keys` or `nats_users`
+    pub collection: &'static str,
+    /// Entity ID (YubiKey serial for `yubikeys`)
+    pub id: String,
+    pub kind: DivergenceKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum DivergenceKind {
+    /// In the existing manifest but not produced by the events
+    MissingFromRebuild,
+    /// Produced by the events but absent from the existing manifest
+    MissingFromExisting,
+    /// In both, with different contents
+    Changed,
+}
+
+/// Rebuild all projections from `stream` into the empty directory `target`
+///
+/// When `existing` is given, the rebuilt manifest is compared against it
+/// and every difference is listed in the report. The existing projection
+/// is never modified; swap directories once the report looks right.
+pub fn rebuild<P: AsRef<Path>>(
+    stream: &EventStream,
+    target: P,
+    existing: Option<&KeyManifest>,
+) -> Result<RebuildOutput, ProjectionError> {
+    rebuild_into(stream, OfflineKeyProjection::new(target)?, existing)
+}
+
+/// Rebuild into a projection the caller has set up, e.g. with
+/// [`OfflineKeyProjection::with_event_encryption`]
+///
+/// The projection's directory must not hold a projection yet.
+pub fn rebuild_into(
+    stream: &EventStream,
+    mut projection: OfflineKeyProjection,
+    existing: Option<&KeyManifest>,
+) -> Result<RebuildOutput, ProjectionError> {
+    if projection.root_path.join("manifest.json").exists() {
+        return Err(ProjectionError::InvalidStateTransition(format!(
+            "{} already holds a projection; rebuild into an empty directory",
+            projection.root_path.display()
+        )));
+    }
+
+    let mut report = RebuildReport::default();
+    // Rebuilt events are history, not new events to publish
+    for event in stream.events() {
+        projection.replay(event)?;
+        report.events_replayed += 1;
+        *report.events_by_aggregate.entry(event.aggregate_type()).or_default() += 1;
+    }
+
+    report.dead_letters = projection.dead_letters().len();
+
+    let manifest = projection.manifest();
+    let cypher = graph(manifest)
+        .to_cypher_file()
+        .map_err(|e| ProjectionError::SerializationError(format!("Failed to build Cypher: {}", e)))?;
+    let nsc_stores = nsc_stores(manifest, stream, &mut report.missing_jwts)?;
+    if let Some(existing) = existing {
+        report.divergences = diff_manifests(existing, manifest);
+    }
+
+    Ok(RebuildOutput { projection, cypher, nsc_stores, report })
+}
+
+/// Entities that differ between two manifests
+///
+/// Entities are matched by ID. Lifecycle states are compared by variant
+/// only, since the projection stamps some transitions with the time they
+/// were applied rather than a time carried by the event.
+pub fn diff_manifests(existing: &KeyManifest, rebuilt: &KeyManifest) -> Vec<Divergence> {
+    let mut divergences = Vec::new();
+    diff(&mut divergences, "people", &existing.people, &rebuilt.people, |e| e.person_id.to_string());
+    diff(&mut divergences, "locations", &existing.locations, &rebuilt.locations, |e| e.location_id.to_string());
+    diff(&mut divergences, "keys", &existing.keys, &rebuilt.keys, |e| e.key_id.to_string());
+    diff(&mut divergences, "certificates", &existing.certificates, &rebuilt.certificates, |e| e.cert_id.to_string());
+    diff(&mut divergences, "yubikeys", &existing.yubikeys, &rebuilt.yubikeys, |e| e.serial.clone());
+    diff(&mut divergences, "nats_operators", &existing.nats_operators, &rebuilt.nats_operators, |e| e.operator_id.to_string());
+    diff(&mut divergences, "nats_accounts", &existing.nats_accounts, &rebuilt.nats_accounts, |e| e.account_id.to_string());
+    diff(&mut divergences, "nats_users", &existing.nats_users, &rebuilt.nats_users, |e| e.user_id.to_string());
+    divergences
+}
+
+fn diff<T: Serialize>(
+    divergences: &mut Vec<Divergence>,
+    collection: &'static str,
+    existing: &[T],
+    rebuilt: &[T],
+    id: impl Fn(&T) -> String,
+) {
+    let rebuilt: BTreeMap<String, Value> = rebuilt.iter().map(|e| (id(e), comparable(e))).collect();
+    let mut seen = HashSet::new();
+
+    for entry in existing {
+        let entry_id = id(entry);
+        let kind = match rebuilt.get(&entry_id) {
+            None => Some(DivergenceKind::MissingFromRebuild),
+            Some(value) if *value != comparable(entry) => Some(DivergenceKind::Changed),
+            Some(_) => None,
+        };
+        if let Some(kind) = kind {
+            divergences.push(Divergence { collection, id: entry_id.clone(), kind });
+        }
+        seen.insert(entry_id);
+    }
+
+    for entry_id in rebuilt.keys().filter(|id| !seen.contains(id)) {
+        divergences.push(Divergence {
+            collection,
+            id: entry_id.clone(),
+            kind: DivergenceKind::MissingFromExisting,
+        });
+    }
+}
+
+/// Entry as JSON with its lifecycle state reduced to the variant name
+fn comparable<T: Serialize>(entry: &T) -> Value {
+    let mut value = serde_json::to_value(entry).unwrap_or(Value::Null);
+    if let Some(state) = value.get_mut("state") {
+        let variant = match state {
+            Value::Object(fields) => fields.keys().next().cloned(),
+            _ => None,
+        };
+        if let Some(variant) = variant {
+            *state = Value::String(variant);
+        }
+    }
+    value
+}
+
+/// Graph of the manifest's entities and the links between them
+pub(super) fn graph(manifest: &KeyManifest) -> DomainGraphBuilder {
+    let mut builder = DomainGraphBuilder::new();
+
+    for person in &manifest.people {
+        builder = builder.add(
+            &GraphNode::new(person.person_id, "Person")
+                .with_property("name", person.name.clone())
+                .with_property("email", person.email.clone())
+                .with_property("role", person.role.clone()),
+        );
+    }
+    for location in &manifest.locations {
+        builder = builder.add(
+            &GraphNode::new(location.location_id, "Location")
+                .with_property("name", location.name.clone())
+                .with_property("type", location.location_type.clone()),
+        );
+    }
+    for key in &manifest.keys {
+        builder = builder.add(
+            &GraphNode::new(key.key_id, "CryptographicKey")
+                .with_property("algorithm", format!("{:?}", key.algorithm))
+                .with_property("purpose", format!("{:?}", key.purpose))
+                .with_property("label", key.label.clone())
+                .with_property("revoked", key.revoked),
+        );
+    }
+    for cert in &manifest.certificates {
+        builder = builder
+            .add(
+                &GraphNode::new(cert.cert_id, "Certificate")
+                    .with_property("subject", cert.subject.clone())
+                    .with_property("is_ca", cert.is_ca)
+                    .with_property("not_before", cert.not_before.to_rfc3339())
+                    .with_property("not_after", cert.not_after.to_rfc3339()),
+            )
+            .relate(cert.cert_id, cert.key_id, "USES_KEY");
+        if let Some(issuer) = cert.issuer.as_deref().and_then(|id| Uuid::parse_str(id).ok()) {
+            builder = builder.add_edge(&certificate_signs(issuer, cert.cert_id));
+        }
+    }
+    for operator in &manifest.nats_operators {
+        builder = builder.add(
+            &GraphNode::new(operator.operator_id, "NatsOperator")
+                .with_property("name", operator.name.clone())
+                .with_property("public_key", operator.public_key.clone()),
+        );
+    }
+    for account in &manifest.nats_accounts {
+        builder = builder
+            .add(
+                &GraphNode::new(account.account_id, "NatsAccount")
+                    .with_property("name", account.name.clone())
+                    .with_property("public_key", account.public_key.clone())
+                    .with_property("is_system", account.is_system),
+            )
+            .relate(account.account_id, account.operator_id, "BELONGS_TO");
+    }
+    for user in &manifest.nats_users {
+        builder = builder
+            .add(
+                &GraphNode::new(user.user_id, "NatsUser")
+                    .with_property("name", user.name.clone())
+                    .with_property("public_key", user.public_key.clone()),
+            )
+            .relate(user.user_id, user.account_id, "BELONGS_TO");
+        if let Some(person_id) = user.person_id {
+            builder = builder.relate(person_id, user.user_id, "HAS_NATS_USER");
+        }
+    }
+
+    builder
+}
+
+/// NSC stores for every operator in the manifest
+///
+/// JWTs are taken from `JwtSigned` events, matched to entities through the
+/// subject of their `JwtClaimsCreated` claims. Seeds are never part of the
+/// rebuilt store.
+pub(super) fn nsc_stores(
+    manifest: &KeyManifest,
+    stream: &EventStream,
+    missing_jwts: &mut Vec<String>,
+) -> Result<HashMap<Uuid, NscStore>, ProjectionError> {
+    let mut claim_subjects = HashMap::new();
+    let mut jwts = HashMap::new();
+    let mut signing_keys: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for event in stream.events() {
+        match event {
+            DomainEvent::NatsOperator(NatsOperatorEvents::JwtClaimsCreated(e)) => {
+                claim_subjects.insert(e.claims_id, e.subject.clone());
+            }
+            DomainEvent::NatsOperator(NatsOperatorEvents::JwtSigned(e)) => {
+                if let Some(subject) = claim_subjects.get(&e.claims_id) {
+                    jwts.insert(subject.clone(), e.jwt_token.clone());
+                }
+            }
+            DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyGenerated(e)) => {
+                signing_keys.entry(e.entity_id).or_default().push(e.public_key.clone());
+            }
+            DomainEvent::NatsOperator(NatsOperatorEvents::NatsSigningKeyRetired(e)) => {
+                if let Some(keys) = signing_keys.get_mut(&e.entity_id) {
+                    keys.retain(|key| key != &e.public_key);
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut jwt_for = |public_key: &str| {
+        jwts.get(public_key).cloned().unwrap_or_else(|| {
+            missing_jwts.push(public_key.to_string());
+            String::new()
+        })
+    };
+
+    let mut stores = HashMap::new();
+    for operator in &manifest.nats_operators {
+        let accounts: Vec<_> = manifest
+            .nats_accounts
+            .iter()
+            .filter(|a| a.operator_id == operator.operator_id)
+            .collect();
+
+        let mut credentials = DomainNatsCredentials {
+            organization_id: operator.organization_id.unwrap_or_default(),
+            organization_name: manifest.organization.name.clone(),
+            operator: OperatorCredentials {
+                name: operator.name.clone(),
+                jwt: jwt_for(&operator.public_key),
+                public_key: operator.public_key.clone(),
+                signing_keys: signing_keys.get(&operator.operator_id).cloned().unwrap_or_default(),
+                system_account: accounts.iter().find(|a| a.is_system).map(|a| a.public_key.clone()),
+            },
+            accounts: HashMap::new(),
+            users: HashMap::new(),
+            generated_at: Utc::now(),
+        };
+
+        for account in accounts {
+            let users = manifest
+                .nats_users
+                .iter()
+                .filter(|u| u.account_id == account.account_id)
+                .map(|user| UserCredentials {
+                    name: user.name.clone(),
+                    jwt: jwt_for(&user.public_key),
+                    public_key: user.public_key.clone(),
+                    seed: None,
+                    account_public_key: account.public_key.clone(),
+                    person_id: user.person_id,
+                })
+                .collect();
+            credentials.users.insert(account.name.clone(), users);
+            credentials.accounts.insert(
+                account.name.clone(),
+                AccountCredentials {
+                    name: account.name.clone(),
+                    jwt: jwt_for(&account.public_key),
+                    public_key: account.public_key.clone(),
+                    operator_public_key: operator.public_key.clone(),
+                    signing_keys: signing_keys.get(&account.account_id).cloned().unwrap_or_default(),
+                },
+            );
+        }
+
+        let store = credentials_to_nscstore()
+            .project(credentials)
+            .map_err(|e| ProjectionError::SerializationError(format!("Failed to build NSC store: {}", e)))?;
+        stores.insert(operator.operator_id, store);
+    }
+
+    Ok(stores)
+}
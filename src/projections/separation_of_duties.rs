@@ -0,0 +1,115 @@
+//! Separation-of-duties report for compliance
+//!
+//! Folds `SeparationOfDutiesViolated` events into a report of every
+//! refused assignment, with a summary per rule:
+//!
+//! ```text
+//! SeparationOfDutiesViolated ──▶ violations (oldest first)
+//!                            └─▶ by_rule: rule → refusals, entities, last seen
+//! ```
+//!
+//! The report is rebuilt from the event log like any other projection and
+//! serializes to JSON for auditors.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::{PolicyClaim, PolicyEntityType};
+use crate::events::authorization::{AuthorizationEvents, SeparationOfDutiesViolatedEvent};
+use crate::events::DomainEvent;
+
+/// A refused assignment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeparationOfDutiesViolation {
+    pub command_id: Uuid,
+    pub entity_id: Uuid,
+    pub entity_type: PolicyEntityType,
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub conflicting_claims: Vec<PolicyClaim>,
+    pub assignment: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Refusals under one rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeparationOfDutiesRuleSummary {
+    pub rule_id: Uuid,
+    pub rule_name: String,
+    pub refusals: u64,
+    /// Distinct entities an assignment was refused for
+    pub entities: BTreeSet<Uuid>,
+    pub last_detected_at: DateTime<Utc>,
+}
+
+/// Report of refused assignments
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeparationOfDutiesReport {
+    /// Refused assignments, oldest first
+    pub violations: Vec<SeparationOfDutiesViolation>,
+    /// Per-rule summary, in order of first refusal
+    pub by_rule: Vec<SeparationOfDutiesRuleSummary>,
+}
+
+impl SeparationOfDutiesReport {
+    /// Build the report from an event log
+    pub fn from_events<'a>(events: impl IntoIterator<Item = &'a DomainEvent>) -> Self {
+        let mut report = Self::default();
+        for event in events {
+            report.apply(event);
+        }
+        report
+    }
+
+    /// Fold an event into the report; other events are ignored
+    pub fn apply(&mut self, event: &DomainEvent) {
+        if let DomainEvent::Authorization(AuthorizationEvents::SeparationOfDutiesViolated(e)) = event {
+            self.record(e);
+        }
+    }
+
+    /// Refused assignments for an entity, oldest first
+    pub fn violations_for(&self, entity_id: Uuid) -> Vec<&SeparationOfDutiesViolation> {
+        self.violations.iter().filter(|v| v.entity_id == entity_id).collect()
+    }
+
+    fn record(&mut self, event: &SeparationOfDutiesViolatedEvent) {
+        // Replays must not count a refusal twice
+        let seen = self
+            .violations
+            .iter()
+            .any(|v| v.command_id == event.command_id && v.rule_id == event.rule_id);
+        if seen {
+            return;
+        }
+
+        self.violations.push(SeparationOfDutiesViolation {
+            command_id: event.command_id,
+            entity_id: event.entity_id,
+            entity_type: event.entity_type,
+            rule_id: event.rule_id,
+            rule_name: event.rule_name.clone(),
+            conflicting_claims: event.conflicting_claims.clone(),
+            assignment: event.assignment.clone(),
+            detected_at: event.detected_at,
+        });
+
+        match self.by_rule.iter_mut().find(|s| s.rule_id == event.rule_id) {
+            Some(summary) => {
+                summary.refusals += 1;
+                summary.entities.insert(event.entity_id);
+                summary.last_detected_at = summary.last_detected_at.max(event.detected_at);
+            }
+            None => self.by_rule.push(SeparationOfDutiesRuleSummary {
+                rule_id: event.rule_id,
+                rule_name: event.rule_name.clone(),
+                refusals: 1,
+                entities: BTreeSet::from([event.entity_id]),
+                last_detected_at: event.detected_at,
+            }),
+        }
+    }
+}
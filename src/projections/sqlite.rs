@@ -0,0 +1,424 @@
+//! SQLite read model of the manifest
+//!
+//! The manifest is one JSON document, fine for replay and export but slow
+//! to search. [`SqliteReadModel`] mirrors its people, locations, keys,
+//! certificates, YubiKeys and relationships into indexed tables and
+//! answers the questions the GUI and downstream tools keep asking:
+//!
+//! ```text
+//! OfflineKeyProjection ── manifest ──► SqliteReadModel::sync
+//!                                        ├─ keys_by_owner(person)
+//!                                        ├─ certificates_expiring_within(30 days)
+//!                                        ├─ devices_at_location(location)
+//!                                        └─ relationships_of(entity)
+//! ```
+//!
+//! Each table keeps the manifest entry as JSON next to the indexed
+//! columns, so queries return the same entry types as the manifest. The
+//! model holds nothing the manifest does not: `sync` replaces every row
+//! in one transaction and the database file can be deleted at any time.
+
+use std::path::Path;
+
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::{
+    CertificateEntry, KeyEntry, KeyManifest, LocationEntry, PersonEntry, ProjectionError, RelationshipEntry,
+    YubiKeyEntry,
+};
+use crate::commands::organization::RelationshipType;
+
+/// File name of the read model next to the manifest
+pub const READ_MODEL_FILE: &str = "read_model.sqlite";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS people (
+        person_id TEXT PRIMARY KEY,
+        organization_id TEXT NOT NULL,
+        entry TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS people_organization ON people (organization_id);
+
+    CREATE TABLE IF NOT EXISTS locations (
+        location_id TEXT PRIMARY KEY,
+        organization_id TEXT NOT NULL,
+        entry TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS keys (
+        key_id TEXT PRIMARY KEY,
+        owner_id TEXT,
+        yubikey_serial TEXT,
+        revoked INTEGER NOT NULL,
+        entry TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS keys_owner ON keys (owner_id);
+    CREATE INDEX IF NOT EXISTS keys_yubikey ON keys (yubikey_serial);
+
+    CREATE TABLE IF NOT EXISTS certificates (
+        cert_id TEXT PRIMARY KEY,
+        key_id TEXT NOT NULL,
+        not_after INTEGER NOT NULL,
+        revoked INTEGER NOT NULL,
+        entry TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS certificates_not_after ON certificates (not_after);
+    CREATE INDEX IF NOT EXISTS certificates_key ON certificates (key_id);
+
+    CREATE TABLE IF NOT EXISTS yubikeys (
+        serial TEXT PRIMARY KEY,
+        entry TEXT NOT NULL
+    );
+
+    CREATE TABLE IF NOT EXISTS relationships (
+        relationship_id TEXT PRIMARY KEY,
+        from_id TEXT NOT NULL,
+        to_id TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        terminated INTEGER NOT NULL,
+        entry TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS relationships_from ON relationships (from_id, kind);
+    CREATE INDEX IF NOT EXISTS relationships_to ON relationships (to_id, kind);
+";
+
+/// Indexed, queryable copy of a [`KeyManifest`]
+pub struct SqliteReadModel {
+    conn: Connection,
+}
+
+impl SqliteReadModel {
+    /// Open or create the read model at `path`, e.g.
+    /// `{projection root}/`[`READ_MODEL_FILE`]
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, ProjectionError> {
+        Self::with_connection(Connection::open(path).map_err(db_error)?)
+    }
+
+    /// Read model that lives only as long as the value, for tools and tests
+    pub fn in_memory() -> Result<Self, ProjectionError> {
+        Self::with_connection(Connection::open_in_memory().map_err(db_error)?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, ProjectionError> {
+        conn.execute_batch(SCHEMA).map_err(db_error)?;
+        Ok(Self { conn })
+    }
+
+    /// Replace the contents with `manifest`
+    ///
+    /// Readers on other connections see either the old or the new
+    /// contents, never a mix.
+    pub fn sync(&mut self, manifest: &KeyManifest) -> Result<(), ProjectionError> {
+        let tx = self.conn.transaction().map_err(db_error)?;
+        tx.execute_batch(
+            "DELETE FROM people; DELETE FROM locations; DELETE FROM keys;
+             DELETE FROM certificates; DELETE FROM yubikeys; DELETE FROM relationships;",
+        )
+        .map_err(db_error)?;
+
+        for person in &manifest.people {
+            tx.execute(
+                "INSERT INTO people (person_id, organization_id, entry) VALUES (?1, ?2, ?3)",
+                params![person.person_id.to_string(), person.organization_id.to_string(), to_json(person)?],
+            )
+            .map_err(db_error)?;
+        }
+        for location in &manifest.locations {
+            tx.execute(
+                "INSERT INTO locations (location_id, organization_id, entry) VALUES (?1, ?2, ?3)",
+                params![location.location_id.to_string(), location.organization_id.to_string(), to_json(location)?],
+            )
+            .map_err(db_error)?;
+        }
+        for key in &manifest.keys {
+            tx.execute(
+                "INSERT INTO keys (key_id, owner_id, yubikey_serial, revoked, entry) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    key.key_id.to_string(),
+                    key.owner_id.map(|id| id.to_string()),
+                    key.yubikey_serial,
+                    key.revoked,
+                    to_json(key)?
+                ],
+            )
+            .map_err(db_error)?;
+        }
+        for cert in &manifest.certificates {
+            let revoked = cert.state.as_ref().is_some_and(|state| state.is_revoked());
+            tx.execute(
+                "INSERT INTO certificates (cert_id, key_id, not_after, revoked, entry) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![cert.cert_id.to_string(), cert.key_id.to_string(), cert.not_after.timestamp(), revoked, to_json(cert)?],
+            )
+            .map_err(db_error)?;
+        }
+        for yubikey in &manifest.yubikeys {
+            tx.execute(
+                "INSERT INTO yubikeys (serial, entry) VALUES (?1, ?2)",
+                params![yubikey.serial, to_json(yubikey)?],
+            )
+            .map_err(db_error)?;
+        }
+        for relationship in &manifest.relationships {
+            tx.execute(
+                "INSERT INTO relationships (relationship_id, from_id, to_id, kind, terminated, entry)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    relationship.relationship_id.to_string(),
+                    relationship.from_id.to_string(),
+                    relationship.to_id.to_string(),
+                    relationship_kind(&relationship.relationship_type),
+                    relationship.terminated_at.is_some(),
+                    to_json(relationship)?
+                ],
+            )
+            .map_err(db_error)?;
+        }
+
+        tx.commit().map_err(db_error)
+    }
+
+    /// A person by ID
+    pub fn person(&self, person_id: Uuid) -> Result<Option<PersonEntry>, ProjectionError> {
+        self.conn
+            .query_row("SELECT entry FROM people WHERE person_id = ?1", [person_id.to_string()], |row| {
+                row.get::<_, String>(0)
+            })
+            .optional()
+            .map_err(db_error)?
+            .map(|entry| from_json(&entry))
+            .transpose()
+    }
+
+    /// People of an organization
+    pub fn people_in_organization(&self, organization_id: Uuid) -> Result<Vec<PersonEntry>, ProjectionError> {
+        self.entries(
+            "SELECT entry FROM people WHERE organization_id = ?1 ORDER BY person_id",
+            &[&organization_id.to_string()],
+        )
+    }
+
+    /// Locations of an organization
+    pub fn locations_in_organization(&self, organization_id: Uuid) -> Result<Vec<LocationEntry>, ProjectionError> {
+        self.entries(
+            "SELECT entry FROM locations WHERE organization_id = ?1 ORDER BY location_id",
+            &[&organization_id.to_string()],
+        )
+    }
+
+    /// Keys owned by a person or entity, revoked ones included
+    pub fn keys_by_owner(&self, owner_id: Uuid) -> Result<Vec<KeyEntry>, ProjectionError> {
+        self.entries("SELECT entry FROM keys WHERE owner_id = ?1 ORDER BY key_id", &[&owner_id.to_string()])
+    }
+
+    /// Keys held on a YubiKey
+    pub fn keys_on_yubikey(&self, serial: &str) -> Result<Vec<KeyEntry>, ProjectionError> {
+        self.entries("SELECT entry FROM keys WHERE yubikey_serial = ?1 ORDER BY key_id", &[&serial])
+    }
+
+    /// Unrevoked certificates expiring between now and `window` from now,
+    /// soonest first
+    pub fn certificates_expiring_within(&self, window: Duration) -> Result<Vec<CertificateEntry>, ProjectionError> {
+        let now = Utc::now();
+        self.certificates_expiring_between(now, now + window)
+    }
+
+    /// Unrevoked certificates whose `not_after` lies in `[from, until]`,
+    /// soonest first
+    pub fn certificates_expiring_between(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<CertificateEntry>, ProjectionError> {
+        self.entries(
+            "SELECT entry FROM certificates
+             WHERE revoked = 0 AND not_after BETWEEN ?1 AND ?2
+             ORDER BY not_after",
+            &[&from.timestamp(), &until.timestamp()],
+        )
+    }
+
+    /// YubiKeys at a location
+    ///
+    /// A YubiKey is at a location when a key it holds has an active
+    /// `StoredAt` relationship to the location.
+    pub fn devices_at_location(&self, location_id: Uuid) -> Result<Vec<YubiKeyEntry>, ProjectionError> {
+        self.entries(
+            "SELECT entry FROM yubikeys WHERE serial IN (
+                 SELECT k.yubikey_serial FROM keys k
+                 JOIN relationships r ON r.from_id = k.key_id
+                 WHERE r.kind = 'StoredAt' AND r.terminated = 0 AND r.to_id = ?1
+             )
+             ORDER BY serial",
+            &[&location_id.to_string()],
+        )
+    }
+
+    /// Active relationships from or to an entity
+    pub fn relationships_of(&self, entity_id: Uuid) -> Result<Vec<RelationshipEntry>, ProjectionError> {
+        self.entries(
+            "SELECT entry FROM relationships
+             WHERE terminated = 0 AND (from_id = ?1 OR to_id = ?1)
+             ORDER BY relationship_id",
+            &[&entity_id.to_string()],
+        )
+    }
+
+    fn entries<T: DeserializeOwned>(
+        &self,
+        sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<T>, ProjectionError> {
+        let mut statement = self.conn.prepare_cached(sql).map_err(db_error)?;
+        let rows = statement
+            .query_map(params, |row| row.get::<_, String>(0))
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+        rows.iter().map(|entry| from_json(entry)).collect()
+    }
+}
+
+/// Variant name of a relationship type, e.g. `StoredAt`
+fn relationship_kind(relationship_type: &RelationshipType) -> String {
+    match serde_json::to_value(relationship_type) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(serde_json::Value::Object(fields)) => fields.keys().next().cloned().unwrap_or_default(),
+        _ => format!("{:?}", relationship_type),
+    }
+}
+
+fn to_json<T: Serialize>(entry: &T) -> Result<String, ProjectionError> {
+    serde_json::to_string(entry).map_err(|e| ProjectionError::SerializationError(e.to_string()))
+}
+
+fn from_json<T: DeserializeOwned>(entry: &str) -> Result<T, ProjectionError> {
+    serde_json::from_str(entry).map_err(|e| ProjectionError::ParseError(format!("Read model entry: {}", e)))
+}
+
+fn db_error(error: rusqlite::Error) -> ProjectionError {
+    ProjectionError::IoError(format!("SQLite read model: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::projections::OfflineKeyProjection;
+    use crate::types::{KeyAlgorithm, KeyPurpose};
+    use tempfile::TempDir;
+
+    fn key(key_id: Uuid, owner_id: Option<Uuid>, yubikey_serial: Option<&str>) -> KeyEntry {
+        KeyEntry {
+            key_id,
+            algorithm: KeyAlgorithm::Ed25519,
+            purpose: KeyPurpose::Signing,
+            label: "key".to_string(),
+            hardware_backed: yubikey_serial.is_some(),
+            yubikey_serial: yubikey_serial.map(str::to_string),
+            yubikey_slot: None,
+            revoked: false,
+            file_path: format!("keys/{}", key_id),
+            state: None,
+            expires_at: None,
+            owner_id,
+        }
+    }
+
+    fn certificate(key_id: Uuid, not_after: DateTime<Utc>) -> CertificateEntry {
+        CertificateEntry {
+            cert_id: Uuid::now_v7(),
+            key_id,
+            subject: "CN=test".to_string(),
+            issuer: None,
+            serial_number: "01".to_string(),
+            not_before: Utc::now(),
+            not_after,
+            is_ca: false,
+            file_path: String::new(),
+            state: None,
+        }
+    }
+
+    fn stored_at(key_id: Uuid, location_id: Uuid) -> RelationshipEntry {
+        RelationshipEntry {
+            relationship_id: Uuid::now_v7(),
+            from_id: key_id,
+            to_id: location_id,
+            relationship_type: RelationshipType::StoredAt,
+            valid_from: Utc::now(),
+            valid_until: None,
+            terminated_at: None,
+        }
+    }
+
+    fn yubikey(serial: &str) -> YubiKeyEntry {
+        YubiKeyEntry {
+            serial: serial.to_string(),
+            provisioned_at: Utc::now(),
+            slots_used: vec!["9a".to_string()],
+            config_path: String::new(),
+            state: None,
+            firmware_version: None,
+        }
+    }
+
+    fn manifest() -> KeyManifest {
+        let temp_dir = TempDir::new().unwrap();
+        OfflineKeyProjection::new(temp_dir.path()).unwrap().manifest().clone()
+    }
+
+    #[test]
+    fn test_queries_by_owner_expiry_and_location() {
+        let (alice, vault, office) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let (alice_key, bob_key) = (Uuid::now_v7(), Uuid::now_v7());
+
+        let mut manifest = manifest();
+        manifest.keys = vec![key(alice_key, Some(alice), Some("1001")), key(bob_key, None, Some("2002"))];
+        manifest.certificates = vec![
+            certificate(alice_key, Utc::now() + Duration::days(90)),
+            certificate(bob_key, Utc::now() + Duration::days(10)),
+        ];
+        manifest.yubikeys = vec![yubikey("1001"), yubikey("2002")];
+        manifest.relationships = vec![stored_at(alice_key, vault), stored_at(bob_key, office)];
+        manifest.relationships[1].terminated_at = Some(Utc::now());
+
+        let mut model = SqliteReadModel::in_memory().unwrap();
+        model.sync(&manifest).unwrap();
+
+        let keys = model.keys_by_owner(alice).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].key_id, alice_key);
+
+        let expiring = model.certificates_expiring_within(Duration::days(30)).unwrap();
+        assert_eq!(expiring.len(), 1);
+        assert_eq!(expiring[0].key_id, bob_key);
+
+        let devices = model.devices_at_location(vault).unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].serial, "1001");
+        // The office relationship was terminated
+        assert!(model.devices_at_location(office).unwrap().is_empty());
+        assert_eq!(model.relationships_of(alice_key).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_sync_replaces_previous_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let owner = Uuid::now_v7();
+        let mut manifest = manifest();
+        manifest.keys = vec![key(Uuid::now_v7(), Some(owner), None)];
+
+        let mut model = SqliteReadModel::open(temp_dir.path().join(READ_MODEL_FILE)).unwrap();
+        model.sync(&manifest).unwrap();
+        manifest.keys.clear();
+        model.sync(&manifest).unwrap();
+        assert!(model.keys_by_owner(owner).unwrap().is_empty());
+
+        // The schema survives reopening
+        let reopened = SqliteReadModel::open(temp_dir.path().join(READ_MODEL_FILE)).unwrap();
+        assert!(reopened.person(Uuid::now_v7()).unwrap().is_none());
+    }
+}
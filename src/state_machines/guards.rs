@@ -0,0 +1,416 @@
+//! Transition Guards
+//!
+//! `can_transition_to` checks that a transition is structurally valid. A
+//! [`TransitionGuard`] also checks that the acting identity may make it:
+//! each guarded state machine names the claims its transitions need, and
+//! the guard compares them with the claims the actor's policies grant
+//! (`PolicyEvaluation::granted_claims`).
+//!
+//! ```text
+//! KeyState          * → Active                   CanGenerateKeys
+//!                   * → RotationPending/Rotated  CanRotateKeys
+//!                   * → Revoked                  CanRevokeKeys
+//! CertificateState  * → Issued/Renewed           CanSignCertificates
+//!                   * → Revoked                  CanRevokeKeys
+//! YubiKeyState      * → Provisioned              CanGenerateKeys
+//!                   * → Retired                  CanRevokeKeys
+//! PolicyState       * → any                      CanManagePolicies
+//! PersonState       * → Active                   CanAssignRoles
+//!                   * → Suspended/Deactivated    CanDisableAccounts
+//!                   * → Archived                 CanDeleteAccounts
+//! ```
+//!
+//! A refused transition yields a [`TransitionDenied`] naming the missing
+//! claims, which converts to a `TransitionDenied` event. Hooks added with
+//! [`TransitionGuard::with_hook`] require claims beyond the defaults.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::{CertificateState, KeyState, PersonState, PolicyState, YubiKeyState};
+use crate::domain::{PolicyClaim, PolicyEvaluation};
+use crate::events::authorization::TransitionDeniedEvent;
+
+/// A state machine whose transitions require claims
+pub trait GuardedTransition: Sized {
+    /// State machine type, recorded in denials
+    const MACHINE: &'static str;
+
+    /// Name of the current state
+    fn state_name(&self) -> &'static str;
+
+    /// Is the transition structurally valid?
+    fn is_valid_transition(&self, target: &Self) -> bool;
+
+    /// Claims an actor must hold to move from this state to `target`
+    fn required_claims(&self, target: &Self) -> Vec<PolicyClaim>;
+}
+
+/// A transition refused because the actor lacks required claims
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{state_machine} transition {from_state} -> {to_state} denied for {actor_id}: missing {missing_claims:?}")]
+pub struct TransitionDenied {
+    pub entity_id: Uuid,
+    pub state_machine: &'static str,
+    pub from_state: &'static str,
+    pub to_state: &'static str,
+    pub actor_id: Uuid,
+    pub required_claims: Vec<PolicyClaim>,
+    pub missing_claims: Vec<PolicyClaim>,
+    pub denied_at: DateTime<Utc>,
+}
+
+impl TransitionDenied {
+    /// The event recording this denial
+    pub fn to_event(&self, correlation_id: Uuid, causation_id: Option<Uuid>) -> TransitionDeniedEvent {
+        TransitionDeniedEvent {
+            entity_id: self.entity_id,
+            state_machine: self.state_machine.to_string(),
+            from_state: self.from_state.to_string(),
+            to_state: self.to_state.to_string(),
+            actor_id: self.actor_id,
+            required_claims: self.required_claims.clone(),
+            missing_claims: self.missing_claims.clone(),
+            denied_at: self.denied_at,
+            correlation_id,
+            causation_id,
+        }
+    }
+}
+
+/// Why a guarded transition did not happen
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TransitionError {
+    #[error("Invalid {state_machine} transition {from_state} -> {to_state}")]
+    Invalid {
+        state_machine: &'static str,
+        from_state: &'static str,
+        to_state: &'static str,
+    },
+
+    #[error(transparent)]
+    Denied(Box<TransitionDenied>),
+}
+
+type GuardHook<S> = Box<dyn Fn(&S, &S) -> Vec<PolicyClaim> + Send + Sync>;
+
+/// Checks transitions of one state machine against an actor's claims
+pub struct TransitionGuard<S: GuardedTransition> {
+    hooks: Vec<GuardHook<S>>,
+}
+
+impl<S: GuardedTransition> Default for TransitionGuard<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: GuardedTransition> TransitionGuard<S> {
+    /// A guard requiring the state machine's default claims
+    pub fn new() -> Self {
+        Self { hooks: Vec::new() }
+    }
+
+    /// Also require the claims `hook` returns for a transition
+    pub fn with_hook(
+        mut self,
+        hook: impl Fn(&S, &S) -> Vec<PolicyClaim> + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Claims needed to move from `from` to `to`, defaults first
+    pub fn required_claims(&self, from: &S, to: &S) -> Vec<PolicyClaim> {
+        let mut claims = from.required_claims(to);
+        for claim in self.hooks.iter().flat_map(|hook| hook(from, to)) {
+            if !claims.contains(&claim) {
+                claims.push(claim);
+            }
+        }
+        claims
+    }
+
+    /// Check that `actor` may move `entity_id` from `from` to `to`
+    ///
+    /// Only authorization is checked; see [`Self::transition`] for both.
+    pub fn authorize(
+        &self,
+        entity_id: Uuid,
+        from: &S,
+        to: &S,
+        actor: &PolicyEvaluation,
+    ) -> Result<(), Box<TransitionDenied>> {
+        let required_claims = self.required_claims(from, to);
+        let missing_claims: Vec<PolicyClaim> = required_claims
+            .iter()
+            .filter(|claim| !actor.granted_claims.contains(claim))
+            .cloned()
+            .collect();
+        if missing_claims.is_empty() {
+            return Ok(());
+        }
+
+        Err(Box::new(TransitionDenied {
+            entity_id,
+            state_machine: S::MACHINE,
+            from_state: from.state_name(),
+            to_state: to.state_name(),
+            actor_id: actor.entity_id,
+            required_claims,
+            missing_claims,
+            denied_at: Utc::now(),
+        }))
+    }
+
+    /// Move `entity_id` to `to` if the transition is valid and authorized
+    pub fn transition(
+        &self,
+        entity_id: Uuid,
+        from: &S,
+        to: S,
+        actor: &PolicyEvaluation,
+    ) -> Result<S, TransitionError> {
+        if !from.is_valid_transition(&to) {
+            return Err(TransitionError::Invalid {
+                state_machine: S::MACHINE,
+                from_state: from.state_name(),
+                to_state: to.state_name(),
+            });
+        }
+        self.authorize(entity_id, from, &to, actor)
+            .map_err(TransitionError::Denied)?;
+        Ok(to)
+    }
+}
+
+// ============================================================================
+// Default claim requirements
+// ============================================================================
+
+impl GuardedTransition for KeyState {
+    const MACHINE: &'static str = "KeyState";
+
+    fn state_name(&self) -> &'static str {
+        match self {
+            KeyState::Generated { .. } => "Generated",
+            KeyState::Imported { .. } => "Imported",
+            KeyState::Active { .. } => "Active",
+            KeyState::RotationPending { .. } => "RotationPending",
+            KeyState::Rotated { .. } => "Rotated",
+            KeyState::Revoked { .. } => "Revoked",
+            KeyState::Expired { .. } => "Expired",
+            KeyState::Archived { .. } => "Archived",
+        }
+    }
+
+    fn is_valid_transition(&self, target: &Self) -> bool {
+        self.can_transition_to(target)
+    }
+
+    fn required_claims(&self, target: &Self) -> Vec<PolicyClaim> {
+        match target {
+            KeyState::Active { .. } => vec![PolicyClaim::CanGenerateKeys],
+            KeyState::RotationPending { .. } | KeyState::Rotated { .. } => {
+                vec![PolicyClaim::CanRotateKeys]
+            }
+            KeyState::Revoked { .. } => vec![PolicyClaim::CanRevokeKeys],
+            // Expiry is time-based; archival follows a terminal state
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl GuardedTransition for CertificateState {
+    const MACHINE: &'static str = "CertificateState";
+
+    fn state_name(&self) -> &'static str {
+        match self {
+            CertificateState::Pending { .. } => "Pending",
+            CertificateState::Issued { .. } => "Issued",
+            CertificateState::Active { .. } => "Active",
+            CertificateState::RenewalPending { .. } => "RenewalPending",
+            CertificateState::Renewed { .. } => "Renewed",
+            CertificateState::Revoked { .. } => "Revoked",
+            CertificateState::Expired { .. } => "Expired",
+            CertificateState::Archived { .. } => "Archived",
+        }
+    }
+
+    fn is_valid_transition(&self, target: &Self) -> bool {
+        self.can_transition_to(target)
+    }
+
+    fn required_claims(&self, target: &Self) -> Vec<PolicyClaim> {
+        match target {
+            CertificateState::Issued { .. } | CertificateState::Renewed { .. } => {
+                vec![PolicyClaim::CanSignCertificates]
+            }
+            CertificateState::Revoked { .. } => vec![PolicyClaim::CanRevokeKeys],
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl GuardedTransition for YubiKeyState {
+    const MACHINE: &'static str = "YubiKeyState";
+
+    fn state_name(&self) -> &'static str {
+        match self {
+            YubiKeyState::Detected { .. } => "Detected",
+            YubiKeyState::Provisioned { .. } => "Provisioned",
+            YubiKeyState::Active { .. } => "Active",
+            YubiKeyState::Locked { .. } => "Locked",
+            YubiKeyState::Lost { .. } => "Lost",
+            YubiKeyState::Retired { .. } => "Retired",
+        }
+    }
+
+    fn is_valid_transition(&self, target: &Self) -> bool {
+        self.can_transition_to(target)
+    }
+
+    fn required_claims(&self, target: &Self) -> Vec<PolicyClaim> {
+        match target {
+            YubiKeyState::Provisioned { .. } => vec![PolicyClaim::CanGenerateKeys],
+            YubiKeyState::Retired { .. } => vec![PolicyClaim::CanRevokeKeys],
+            // Anyone may report a lost key; lockout is the device's doing
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl GuardedTransition for PolicyState {
+    const MACHINE: &'static str = "PolicyState";
+
+    fn state_name(&self) -> &'static str {
+        match self {
+            PolicyState::Draft { .. } => "Draft",
+            PolicyState::Active { .. } => "Active",
+            PolicyState::Modified { .. } => "Modified",
+            PolicyState::Suspended { .. } => "Suspended",
+            PolicyState::Revoked { .. } => "Revoked",
+        }
+    }
+
+    fn is_valid_transition(&self, target: &Self) -> bool {
+        self.can_transition_to(target)
+    }
+
+    fn required_claims(&self, _target: &Self) -> Vec<PolicyClaim> {
+        vec![PolicyClaim::CanManagePolicies]
+    }
+}
+
+impl GuardedTransition for PersonState {
+    const MACHINE: &'static str = "PersonState";
+
+    fn state_name(&self) -> &'static str {
+        match self {
+            PersonState::Created { .. } => "Created",
+            PersonState::Active { .. } => "Active",
+            PersonState::Suspended { .. } => "Suspended",
+            PersonState::Deactivated { .. } => "Deactivated",
+            PersonState::Archived { .. } => "Archived",
+        }
+    }
+
+    fn is_valid_transition(&self, target: &Self) -> bool {
+        self.can_transition_to(target)
+    }
+
+    fn required_claims(&self, target: &Self) -> Vec<PolicyClaim> {
+        match target {
+            PersonState::Active { .. } => vec![PolicyClaim::CanAssignRoles],
+            PersonState::Suspended { .. } | PersonState::Deactivated { .. } => {
+                vec![PolicyClaim::CanDisableAccounts]
+            }
+            PersonState::Archived { .. } => vec![PolicyClaim::CanDeleteAccounts],
+            PersonState::Created { .. } => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::PolicyEntityType;
+    use crate::state_machines::key::RevocationReason;
+
+    fn actor(granted_claims: Vec<PolicyClaim>) -> PolicyEvaluation {
+        PolicyEvaluation {
+            entity_id: Uuid::now_v7(),
+            entity_type: PolicyEntityType::Person,
+            active_policies: Vec::new(),
+            policy_versions: Vec::new(),
+            inactive_policies: Vec::new(),
+            granted_claims,
+            denied_claims: Vec::new(),
+            evaluated_at: Utc::now(),
+        }
+    }
+
+    fn active_key() -> KeyState {
+        KeyState::Active {
+            activated_at: Utc::now(),
+            usage_count: 0,
+            last_used: None,
+        }
+    }
+
+    fn revoked_key(revoked_by: Uuid) -> KeyState {
+        KeyState::Revoked {
+            reason: RevocationReason::Compromised,
+            revoked_at: Utc::now(),
+            revoked_by,
+        }
+    }
+
+    #[test]
+    fn test_revocation_requires_revoke_claim() {
+        let guard = TransitionGuard::<KeyState>::new();
+        let key_id = Uuid::now_v7();
+        let operator = actor(vec![PolicyClaim::CanGenerateKeys]);
+
+        let denied = guard
+            .authorize(key_id, &active_key(), &revoked_key(operator.entity_id), &operator)
+            .unwrap_err();
+        assert_eq!((denied.from_state, denied.to_state), ("Active", "Revoked"));
+        assert_eq!(denied.missing_claims, vec![PolicyClaim::CanRevokeKeys]);
+
+        let event = denied.to_event(key_id, None);
+        assert_eq!(event.entity_id, key_id);
+        assert_eq!(event.state_machine, "KeyState");
+
+        let security_officer = actor(vec![PolicyClaim::CanRevokeKeys]);
+        let revoked = guard
+            .transition(key_id, &active_key(), revoked_key(security_officer.entity_id), &security_officer)
+            .unwrap();
+        assert!(revoked.is_revoked());
+    }
+
+    #[test]
+    fn test_hooks_add_claims_and_invalid_transitions_are_not_authorized() {
+        let guard = TransitionGuard::<KeyState>::new()
+            .with_hook(|_, to| match to {
+                KeyState::Revoked { .. } => vec![PolicyClaim::CanRevokeKeys, PolicyClaim::CanAccessProduction],
+                _ => Vec::new(),
+            });
+        let key_id = Uuid::now_v7();
+        let operator = actor(vec![PolicyClaim::CanRevokeKeys]);
+
+        assert_eq!(
+            guard.required_claims(&active_key(), &revoked_key(operator.entity_id)),
+            vec![PolicyClaim::CanRevokeKeys, PolicyClaim::CanAccessProduction]
+        );
+        let result = guard.transition(key_id, &active_key(), revoked_key(operator.entity_id), &operator);
+        assert!(matches!(
+            result,
+            Err(TransitionError::Denied(denied)) if denied.missing_claims == vec![PolicyClaim::CanAccessProduction]
+        ));
+
+        let terminal = revoked_key(operator.entity_id);
+        let result = guard.transition(key_id, &terminal, revoked_key(operator.entity_id), &operator);
+        assert!(matches!(result, Err(TransitionError::Invalid { from_state: "Revoked", .. })));
+    }
+}
@@ -226,6 +226,25 @@ pub enum RevocationReason {
     Administrative { reason: String },
 }
 
+impl From<&crate::types::RevocationReason> for RevocationReason {
+    fn from(reason: &crate::types::RevocationReason) -> Self {
+        use crate::types::RevocationReason as EventReason;
+
+        match reason {
+            EventReason::KeyCompromise => RevocationReason::Compromised,
+            EventReason::CaCompromise => RevocationReason::Administrative {
+                reason: "CA compromised".to_string(),
+            },
+            EventReason::AffiliationChanged => RevocationReason::EmployeeTermination,
+            EventReason::Superseded => RevocationReason::Superseded,
+            EventReason::CessationOfOperation => RevocationReason::CessationOfOperation,
+            EventReason::Unspecified => RevocationReason::Administrative {
+                reason: "Unspecified".to_string(),
+            },
+        }
+    }
+}
+
 /// Reason for key expiration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ExpiryReason {
@@ -26,6 +26,7 @@
 //! - NatsAccountState - NATS account lifecycle (5 states) ✅
 //! - NatsUserState - NATS user lifecycle (5 states) ✅
 //! - YubiKeyState - Hardware security module lifecycle (6 states) ✅
+//! - ThresholdKeyGenerationState / ThresholdSigningState - FROST DKG and signing rounds (5 states each)
 //!
 //! All state machines follow a common pattern:
 //! - States are enums with data
@@ -33,6 +34,9 @@
 //! - Events trigger state changes
 //! - Terminal states prevent further modifications
 //! - All state machines are serializable for event sourcing
+//!
+//! Transitions that need authorization are checked against the actor's
+//! policy claims by a `TransitionGuard` (see `guards`).
 
 // Workflow state machines (cross-aggregate workflows)
 pub mod workflows;
@@ -51,6 +55,10 @@ pub mod nats_operator;
 pub mod nats_account;
 pub mod nats_user;
 pub mod yubikey;
+pub mod threshold_signing;
+
+// Claim-based authorization of transitions
+pub mod guards;
 
 // Re-export workflow state machines
 pub use workflows::{
@@ -75,3 +83,5 @@ pub use nats_account::{NatsAccountState, NatsPermissions};
 pub use nats_user::{NatsUserState, NatsUserPermissions};
 pub use yubikey::{YubiKeyState, RetirementReason};
 pub use certificate_import::{CertificateImportState, CertificateImportError};
+pub use threshold_signing::{ThresholdKeyGenerationState, ThresholdSigningState};
+pub use guards::{GuardedTransition, TransitionDenied, TransitionError, TransitionGuard};
@@ -0,0 +1,409 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Threshold Key and Signing State Machines
+//!
+//! Round tracking for FROST threshold keys (see `crypto::frost` and the
+//! `ThresholdSigningEvents`). These machines hold no key material: they
+//! record which participants finished which round, so a coordinator can
+//! tell what is still missing and refuse steps taken out of order.
+//!
+//! ## Key Generation
+//!
+//! ```text
+//! AwaitingRound1 ──DkgRound1Completed──▶ AwaitingRound2
+//!       ──DkgRound2Completed──▶ AwaitingShares
+//!       ──ThresholdKeyGenerated──▶ Generated (terminal)
+//! ```
+//!
+//! ## Signing
+//!
+//! ```text
+//! AwaitingCommitments ──SigningCommitmentsCollected──▶ AwaitingSignatureShares
+//!       ──SignatureSharesCollected──▶ AwaitingAggregation
+//!       ──ThresholdSignatureAggregated──▶ Signed (terminal)
+//! ```
+//!
+//! Any non-terminal state can move to `Failed` (terminal).
+//!
+//! ## Invariants
+//!
+//! - Every participant must finish each DKG round
+//! - Signing needs a generated key and at least `min_signers` signers
+//! - Only signers who committed may contribute signature shares, and all
+//!   of them must
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Lifecycle of a distributed key generation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ThresholdKeyGenerationState {
+    /// Waiting for every participant's round 1 package
+    AwaitingRound1 {
+        participants: Vec<Uuid>,
+        min_signers: u16,
+        started_at: DateTime<Utc>,
+    },
+
+    /// Waiting for every participant's round 2 packages
+    AwaitingRound2 {
+        participants: Vec<Uuid>,
+        min_signers: u16,
+    },
+
+    /// Participants are deriving their shares
+    AwaitingShares {
+        participants: Vec<Uuid>,
+        min_signers: u16,
+    },
+
+    /// Key generated (TERMINAL STATE)
+    Generated {
+        /// Ed25519 group public key, hex
+        group_public_key: String,
+        participants: Vec<Uuid>,
+        min_signers: u16,
+        generated_at: DateTime<Utc>,
+    },
+
+    /// Key generation abandoned (TERMINAL STATE)
+    Failed {
+        reason: String,
+        failed_at: DateTime<Utc>,
+    },
+}
+
+impl ThresholdKeyGenerationState {
+    /// Start a key generation among `participants`
+    pub fn start(
+        participants: Vec<Uuid>,
+        min_signers: u16,
+        started_at: DateTime<Utc>,
+    ) -> Result<Self, StateError> {
+        if min_signers < 2 || usize::from(min_signers) > participants.len() {
+            return Err(StateError::ValidationFailed(format!(
+                "threshold {} of {} participants",
+                min_signers,
+                participants.len()
+            )));
+        }
+        Ok(ThresholdKeyGenerationState::AwaitingRound1 {
+            participants,
+            min_signers,
+            started_at,
+        })
+    }
+
+    // ========================================================================
+    // State Query Methods
+    // ========================================================================
+
+    /// Has the key been generated?
+    pub fn is_generated(&self) -> bool {
+        matches!(self, ThresholdKeyGenerationState::Generated { .. })
+    }
+
+    /// Is this a terminal state (no further transitions allowed)?
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            ThresholdKeyGenerationState::Generated { .. } | ThresholdKeyGenerationState::Failed { .. }
+        )
+    }
+
+    // ========================================================================
+    // State Transition Methods
+    // ========================================================================
+
+    /// All round 1 packages received
+    pub fn complete_round1(&self, received_from: &[Uuid]) -> Result<Self, StateError> {
+        match self {
+            ThresholdKeyGenerationState::AwaitingRound1 {
+                participants,
+                min_signers,
+                ..
+            } => {
+                self.require_all(participants, received_from, "complete_round1")?;
+                Ok(ThresholdKeyGenerationState::AwaitingRound2 {
+                    participants: participants.clone(),
+                    min_signers: *min_signers,
+                })
+            }
+            _ => Err(self.invalid("complete_round1", "Round 1 is not in progress")),
+        }
+    }
+
+    /// All round 2 packages received
+    pub fn complete_round2(&self, received_from: &[Uuid]) -> Result<Self, StateError> {
+        match self {
+            ThresholdKeyGenerationState::AwaitingRound2 {
+                participants,
+                min_signers,
+            } => {
+                self.require_all(participants, received_from, "complete_round2")?;
+                Ok(ThresholdKeyGenerationState::AwaitingShares {
+                    participants: participants.clone(),
+                    min_signers: *min_signers,
+                })
+            }
+            _ => Err(self.invalid("complete_round2", "Round 2 is not in progress")),
+        }
+    }
+
+    /// Every participant derived its share
+    pub fn generate(&self, group_public_key: String, generated_at: DateTime<Utc>) -> Result<Self, StateError> {
+        match self {
+            ThresholdKeyGenerationState::AwaitingShares {
+                participants,
+                min_signers,
+            } => Ok(ThresholdKeyGenerationState::Generated {
+                group_public_key,
+                participants: participants.clone(),
+                min_signers: *min_signers,
+                generated_at,
+            }),
+            _ => Err(self.invalid("generate", "Both DKG rounds must complete first")),
+        }
+    }
+
+    /// Abandon the key generation
+    pub fn fail(&self, reason: String, failed_at: DateTime<Utc>) -> Result<Self, StateError> {
+        if self.is_terminal() {
+            return Err(StateError::TerminalState(self.description().to_string()));
+        }
+        Ok(ThresholdKeyGenerationState::Failed { reason, failed_at })
+    }
+
+    // ========================================================================
+    // Metadata
+    // ========================================================================
+
+    /// Get human-readable state description
+    pub fn description(&self) -> &str {
+        match self {
+            ThresholdKeyGenerationState::AwaitingRound1 { .. } => "Awaiting DKG round 1",
+            ThresholdKeyGenerationState::AwaitingRound2 { .. } => "Awaiting DKG round 2",
+            ThresholdKeyGenerationState::AwaitingShares { .. } => "Awaiting share derivation",
+            ThresholdKeyGenerationState::Generated { .. } => "Generated (TERMINAL - threshold key exists)",
+            ThresholdKeyGenerationState::Failed { .. } => "Failed (TERMINAL - key generation abandoned)",
+        }
+    }
+
+    fn require_all(&self, participants: &[Uuid], received_from: &[Uuid], event: &str) -> Result<(), StateError> {
+        match participants.iter().find(|p| !received_from.contains(p)) {
+            Some(missing) => Err(self.invalid(event, &format!("No package from participant {}", missing))),
+            None => Ok(()),
+        }
+    }
+
+    fn invalid(&self, event: &str, reason: &str) -> StateError {
+        StateError::InvalidTransition {
+            current: self.description().to_string(),
+            event: event.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Lifecycle of one signing operation with a threshold key
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ThresholdSigningState {
+    /// Waiting for signers to commit
+    AwaitingCommitments {
+        key_id: Uuid,
+        /// Share holders of the key
+        participants: Vec<Uuid>,
+        min_signers: u16,
+        started_at: DateTime<Utc>,
+    },
+
+    /// Waiting for every committed signer's signature share
+    AwaitingSignatureShares { key_id: Uuid, signers: Vec<Uuid> },
+
+    /// All shares in, ready to aggregate
+    AwaitingAggregation { key_id: Uuid, signers: Vec<Uuid> },
+
+    /// Signature produced (TERMINAL STATE)
+    Signed {
+        key_id: Uuid,
+        /// Ed25519 signature, hex
+        signature: String,
+        signers: Vec<Uuid>,
+        signed_at: DateTime<Utc>,
+    },
+
+    /// Signing abandoned (TERMINAL STATE)
+    Failed {
+        reason: String,
+        failed_at: DateTime<Utc>,
+    },
+}
+
+impl ThresholdSigningState {
+    /// Start signing with a generated threshold key
+    pub fn start(
+        key_id: Uuid,
+        key: &ThresholdKeyGenerationState,
+        started_at: DateTime<Utc>,
+    ) -> Result<Self, StateError> {
+        match key {
+            ThresholdKeyGenerationState::Generated {
+                participants,
+                min_signers,
+                ..
+            } => Ok(ThresholdSigningState::AwaitingCommitments {
+                key_id,
+                participants: participants.clone(),
+                min_signers: *min_signers,
+                started_at,
+            }),
+            _ => Err(StateError::InvalidTransition {
+                current: key.description().to_string(),
+                event: "start_signing".to_string(),
+                reason: "Threshold key has not been generated".to_string(),
+            }),
+        }
+    }
+
+    // ========================================================================
+    // State Query Methods
+    // ========================================================================
+
+    /// Has the signature been produced?
+    pub fn is_signed(&self) -> bool {
+        matches!(self, ThresholdSigningState::Signed { .. })
+    }
+
+    /// Is this a terminal state (no further transitions allowed)?
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, ThresholdSigningState::Signed { .. } | ThresholdSigningState::Failed { .. })
+    }
+
+    /// Signers of this operation, once they committed
+    pub fn signers(&self) -> Option<&[Uuid]> {
+        match self {
+            ThresholdSigningState::AwaitingSignatureShares { signers, .. }
+            | ThresholdSigningState::AwaitingAggregation { signers, .. }
+            | ThresholdSigningState::Signed { signers, .. } => Some(signers),
+            _ => None,
+        }
+    }
+
+    // ========================================================================
+    // State Transition Methods
+    // ========================================================================
+
+    /// Signing round 1 done: `signers` committed
+    pub fn collect_commitments(&self, signers: Vec<Uuid>) -> Result<Self, StateError> {
+        match self {
+            ThresholdSigningState::AwaitingCommitments {
+                key_id,
+                participants,
+                min_signers,
+                ..
+            } => {
+                if let Some(outsider) = signers.iter().find(|s| !participants.contains(s)) {
+                    return Err(self.invalid(
+                        "collect_commitments",
+                        &format!("{} does not hold a share of this key", outsider),
+                    ));
+                }
+                if signers.len() < usize::from(*min_signers) {
+                    return Err(self.invalid(
+                        "collect_commitments",
+                        &format!("{} signers committed, {} required", signers.len(), min_signers),
+                    ));
+                }
+                Ok(ThresholdSigningState::AwaitingSignatureShares { key_id: *key_id, signers })
+            }
+            _ => Err(self.invalid("collect_commitments", "Commitments are not being collected")),
+        }
+    }
+
+    /// Signing round 2 done: shares received from `received_from`
+    pub fn collect_signature_shares(&self, received_from: &[Uuid]) -> Result<Self, StateError> {
+        match self {
+            ThresholdSigningState::AwaitingSignatureShares { key_id, signers } => {
+                if let Some(missing) = signers.iter().find(|s| !received_from.contains(s)) {
+                    return Err(self.invalid(
+                        "collect_signature_shares",
+                        &format!("No signature share from signer {}", missing),
+                    ));
+                }
+                if let Some(extra) = received_from.iter().find(|r| !signers.contains(r)) {
+                    return Err(self.invalid(
+                        "collect_signature_shares",
+                        &format!("{} did not commit in round 1", extra),
+                    ));
+                }
+                Ok(ThresholdSigningState::AwaitingAggregation {
+                    key_id: *key_id,
+                    signers: signers.clone(),
+                })
+            }
+            _ => Err(self.invalid("collect_signature_shares", "Signature shares are not being collected")),
+        }
+    }
+
+    /// Shares aggregated into the final signature
+    pub fn aggregate(&self, signature: String, signed_at: DateTime<Utc>) -> Result<Self, StateError> {
+        match self {
+            ThresholdSigningState::AwaitingAggregation { key_id, signers } => Ok(ThresholdSigningState::Signed {
+                key_id: *key_id,
+                signature,
+                signers: signers.clone(),
+                signed_at,
+            }),
+            _ => Err(self.invalid("aggregate", "All signature shares must be collected first")),
+        }
+    }
+
+    /// Abandon the signing operation
+    pub fn fail(&self, reason: String, failed_at: DateTime<Utc>) -> Result<Self, StateError> {
+        if self.is_terminal() {
+            return Err(StateError::TerminalState(self.description().to_string()));
+        }
+        Ok(ThresholdSigningState::Failed { reason, failed_at })
+    }
+
+    // ========================================================================
+    // Metadata
+    // ========================================================================
+
+    /// Get human-readable state description
+    pub fn description(&self) -> &str {
+        match self {
+            ThresholdSigningState::AwaitingCommitments { .. } => "Awaiting signer commitments",
+            ThresholdSigningState::AwaitingSignatureShares { .. } => "Awaiting signature shares",
+            ThresholdSigningState::AwaitingAggregation { .. } => "Awaiting aggregation",
+            ThresholdSigningState::Signed { .. } => "Signed (TERMINAL - signature produced)",
+            ThresholdSigningState::Failed { .. } => "Failed (TERMINAL - signing abandoned)",
+        }
+    }
+
+    fn invalid(&self, event: &str, reason: &str) -> StateError {
+        StateError::InvalidTransition {
+            current: self.description().to_string(),
+            event: event.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Errors that can occur during state transitions
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum StateError {
+    #[error("Invalid state transition from {current} on event {event}: {reason}")]
+    InvalidTransition {
+        current: String,
+        event: String,
+        reason: String,
+    },
+
+    #[error("Terminal state reached: {0}")]
+    TerminalState(String),
+
+    #[error("State validation failed: {0}")]
+    ValidationFailed(String),
+}
@@ -0,0 +1,361 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Given/When/Then Harness for Aggregate Tests
+//!
+//! Replays prior events into a fresh aggregate and offline projection,
+//! handles one command and checks exactly which events it produced:
+//!
+//! ```ignore
+//! use cim_keys::testing::AggregateScenario;
+//!
+//! AggregateScenario::new()
+//!     .given(vec![person_created])
+//!     .when(KeyCommand::DeactivatePerson(deactivate))
+//!     .await
+//!     .then_events(vec![person_deactivated]);
+//! ```
+//!
+//! Events are compared as JSON. Timestamps (`timestamp` and every field
+//! ending in `_at`) are always ignored, as they depend on the clock rather
+//! than on the command; IDs a handler generates itself can be ignored by
+//! name with [`AggregateScenario::ignoring`].
+//!
+//! Available in this crate's tests and, for downstream domains, behind the
+//! `test-utils` feature.
+
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use crate::aggregate::{KeyManagementAggregate, KeyManagementError};
+use crate::commands::KeyCommand;
+use crate::domain::PolicyEvaluationContext;
+use crate::events::{DomainEvent, EventEnvelope};
+use crate::ports::event_store::StoredEvent;
+use crate::projections::OfflineKeyProjection;
+
+/// Fields that never take part in event comparison
+const TIMESTAMP_FIELD: &str = "timestamp";
+const TIMESTAMP_SUFFIX: &str = "_at";
+
+/// Aggregate and projection built from prior events, awaiting a command
+pub struct AggregateScenario {
+    // Keeps the projection directory alive for the scenario
+    _dir: TempDir,
+    aggregate: KeyManagementAggregate,
+    projection: OfflineKeyProjection,
+    ignored: BTreeSet<String>,
+    correlation_id: Uuid,
+    actor: PolicyEvaluationContext,
+}
+
+impl AggregateScenario {
+    /// Scenario with an empty history
+    pub fn new() -> Self {
+        Self::for_aggregate(Uuid::now_v7())
+    }
+
+    /// Scenario for the aggregate `aggregate_id`
+    pub fn for_aggregate(aggregate_id: Uuid) -> Self {
+        let dir = TempDir::new().expect("Failed to create scenario directory");
+        let projection = OfflineKeyProjection::new(dir.path()).expect("Failed to create scenario projection");
+        Self {
+            _dir: dir,
+            aggregate: KeyManagementAggregate::new(aggregate_id),
+            projection,
+            ignored: BTreeSet::new(),
+            correlation_id: Uuid::now_v7(),
+            actor: PolicyEvaluationContext::for_person(Uuid::nil()),
+        }
+    }
+
+    /// Replay events that happened before the command, oldest first
+    ///
+    /// Events go into both the aggregate and the projection as history:
+    /// nothing is queued for publication.
+    pub fn given(mut self, events: impl IntoIterator<Item = DomainEvent>) -> Self {
+        for event in events {
+            let sequence = self.aggregate.version + 1;
+            self.projection.replay(&event).expect("Failed to replay given event");
+            self.aggregate.apply(&StoredEvent {
+                sequence,
+                envelope: EventEnvelope::new(event, self.correlation_id, None),
+            });
+        }
+        self
+    }
+
+    /// Mark a command as already handled, as if its events were in the history
+    pub fn given_processed(mut self, command_id: Uuid) -> Self {
+        self.projection
+            .record_processed_command(command_id)
+            .expect("Failed to record processed command");
+        self
+    }
+
+    /// Leave `field` out of the comparison wherever it appears, e.g. an ID
+    /// the handler generates
+    pub fn ignoring(mut self, field: &str) -> Self {
+        self.ignored.insert(field.to_string());
+        self
+    }
+
+    /// Issue the command as the person of `actor` instead of an operator
+    /// without bindings
+    pub fn acting_as(mut self, actor: PolicyEvaluationContext) -> Self {
+        self.actor = actor;
+        self
+    }
+
+    /// Aggregate after the given events
+    pub fn aggregate(&self) -> &KeyManagementAggregate {
+        &self.aggregate
+    }
+
+    /// Projection after the given events
+    pub fn projection(&self) -> &OfflineKeyProjection {
+        &self.projection
+    }
+
+    /// Handle `command` against the history
+    pub async fn when(self, command: KeyCommand) -> ScenarioOutcome {
+        let result = self
+            .aggregate
+            .handle_command(command, &self.projection, None, &self.actor)
+            .await;
+        ScenarioOutcome { result, ignored: self.ignored }
+    }
+}
+
+impl Default for AggregateScenario {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Result of the command, to be checked with the `then_*` methods
+#[derive(Debug)]
+pub struct ScenarioOutcome {
+    result: Result<Vec<DomainEvent>, KeyManagementError>,
+    ignored: BTreeSet<String>,
+}
+
+impl ScenarioOutcome {
+    /// Events the command produced
+    ///
+    /// # Panics
+    ///
+    /// If the command failed.
+    pub fn events(&self) -> &[DomainEvent] {
+        match &self.result {
+            Ok(events) => events,
+            Err(e) => panic!("Expected the command to succeed, but it failed: {}", e),
+        }
+    }
+
+    /// Assert the command produced exactly `expected`, in order
+    pub fn then_events(self, expected: impl IntoIterator<Item = DomainEvent>) -> Vec<DomainEvent> {
+        let expected: Vec<Value> = expected.into_iter().map(|e| self.comparable(&e)).collect();
+        let actual: Vec<Value> = self.events().iter().map(|e| self.comparable(e)).collect();
+
+        if expected != actual {
+            panic!(
+                "Unexpected events\n--- expected ({})\n{}\n--- actual ({})\n{}",
+                expected.len(),
+                pretty(&expected),
+                actual.len(),
+                pretty(&actual)
+            );
+        }
+        self.into_events()
+    }
+
+    /// Assert the command produced events of exactly these types, in order
+    pub fn then_event_types(self, expected: &[&str]) -> Vec<DomainEvent> {
+        let actual: Vec<String> = self.events().iter().map(event_type).collect();
+        assert_eq!(actual, expected, "Unexpected event types");
+        self.into_events()
+    }
+
+    /// Assert the command produced no events, e.g. a repeated command
+    pub fn then_no_events(self) {
+        let events = self.events();
+        assert!(events.is_empty(), "Expected no events, got:\n{}", pretty(events));
+    }
+
+    /// Assert the command was rejected and return its error
+    pub fn then_error(self) -> KeyManagementError {
+        match self.result {
+            Err(e) => e,
+            Ok(events) => panic!("Expected the command to fail, but it produced:\n{}", pretty(&events)),
+        }
+    }
+
+    fn into_events(self) -> Vec<DomainEvent> {
+        self.result.unwrap_or_default()
+    }
+
+    fn comparable(&self, event: &DomainEvent) -> Value {
+        let mut value = serde_json::to_value(event).expect("Failed to serialize event");
+        strip(&mut value, &self.ignored);
+        value
+    }
+}
+
+/// Type of an event as serialized, e.g. `PersonCreated`
+fn event_type(event: &DomainEvent) -> String {
+    serde_json::to_value(event)
+        .ok()
+        .and_then(|value| value["event"]["event_type"].as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", event))
+}
+
+/// Remove timestamps and ignored fields at every depth
+fn strip(value: &mut Value, ignored: &BTreeSet<String>) {
+    match value {
+        Value::Object(fields) => {
+            fields.retain(|name, _| {
+                name != TIMESTAMP_FIELD && !name.ends_with(TIMESTAMP_SUFFIX) && !ignored.contains(name)
+            });
+            fields.values_mut().for_each(|field| strip(field, ignored));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| strip(item, ignored)),
+        _ => {}
+    }
+}
+
+fn pretty<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::organization::{CreatePerson, DeactivatePerson};
+    use crate::events::person::{PersonCreatedEvent, PersonDeactivatedEvent};
+    use crate::events::PersonEvents;
+    use crate::value_objects::ActorId;
+    use chrono::Utc;
+
+    fn person_created(person_id: Uuid, organization_id: Uuid, correlation_id: Uuid) -> DomainEvent {
+        DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+            person_id,
+            name: "Alice".to_string(),
+            email: Some("alice@example.com".to_string()),
+            title: None,
+            department: None,
+            organization_id,
+            created_by: ActorId::system("organization-cmd"),
+            correlation_id,
+            causation_id: None,
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_given_when_then_exact_events() {
+        let (person_id, admin, correlation_id) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+
+        AggregateScenario::new()
+            .given(vec![person_created(person_id, Uuid::now_v7(), correlation_id)])
+            .when(KeyCommand::DeactivatePerson(DeactivatePerson {
+                command_id: Uuid::now_v7(),
+                person_id,
+                reason: "left".to_string(),
+                deactivated_by: admin,
+                correlation_id,
+                causation_id: None,
+                timestamp: Utc::now(),
+            }))
+            .await
+            .then_events(vec![DomainEvent::Person(PersonEvents::PersonDeactivated(PersonDeactivatedEvent {
+                person_id,
+                reason: "left".to_string(),
+                deactivated_at: Utc::now(),
+                deactivated_by: admin,
+                correlation_id,
+                causation_id: None,
+            }))]);
+    }
+
+    #[tokio::test]
+    async fn test_processed_command_yields_no_events() {
+        let command_id = Uuid::now_v7();
+        let command = KeyCommand::CreatePerson(CreatePerson {
+            command_id,
+            person_id: Uuid::now_v7(),
+            name: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+            title: None,
+            department: None,
+            organization_id: Some(Uuid::now_v7()),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+            timestamp: Utc::now(),
+        });
+
+        AggregateScenario::new()
+            .given_processed(command_id)
+            .when(command)
+            .await
+            .then_no_events();
+    }
+
+    #[tokio::test]
+    async fn test_generated_ids_can_be_ignored() {
+        let (command_id, person_id, correlation_id) = (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+        let mut expected = person_created(person_id, Uuid::now_v7(), correlation_id);
+        if let DomainEvent::Person(PersonEvents::PersonCreated(e)) = &mut expected {
+            e.causation_id = Some(command_id);
+        }
+
+        // Without an organization the handler generates one
+        AggregateScenario::new()
+            .ignoring("organization_id")
+            .when(KeyCommand::CreatePerson(CreatePerson {
+                command_id,
+                person_id,
+                name: "Alice".to_string(),
+                email: "alice@example.com".to_string(),
+                title: None,
+                department: None,
+                organization_id: None,
+                correlation_id,
+                causation_id: None,
+                timestamp: Utc::now(),
+            }))
+            .await
+            .then_events(vec![expected]);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_command() {
+        let error = AggregateScenario::new()
+            .when(KeyCommand::CreatePerson(CreatePerson {
+                command_id: Uuid::now_v7(),
+                person_id: Uuid::now_v7(),
+                name: "Alice".to_string(),
+                email: "not-an-email".to_string(),
+                title: None,
+                department: None,
+                organization_id: None,
+                correlation_id: Uuid::now_v7(),
+                causation_id: None,
+                timestamp: Utc::now(),
+            }))
+            .await
+            .then_error();
+        assert!(matches!(error, KeyManagementError::InvalidCommand(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unexpected events")]
+    fn test_mismatch_panics_with_diff() {
+        let outcome = ScenarioOutcome {
+            result: Ok(vec![person_created(Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7())]),
+            ignored: BTreeSet::new(),
+        };
+        outcome.then_events(vec![person_created(Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7())]);
+    }
+}
@@ -91,6 +91,7 @@ pub use nats::{
 // Re-export X.509 types (PKI bounded context)
 pub use x509::{
     BasicConstraints,
+    CertificateSignatureAlgorithm,
     CertificateValidity,
     CommonName,
     CountryCode,
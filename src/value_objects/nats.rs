@@ -214,16 +214,26 @@ impl NKeyPair {
 
     /// Generate a new NKey pair using nkeys crate
     pub fn generate(key_type: NKeyType, name: Option<String>) -> Result<Self, String> {
+        Self::generate_with(key_type, name, &crate::ports::rng::OsCryptoRng)
+    }
+
+    /// Generate a new NKey pair from 32 bytes of `rng`
+    ///
+    /// The nkeys crate adds the type prefix (O/A/U/N/C) and checksum; both
+    /// prefixes are checked before the pair is returned.
+    pub fn generate_with(
+        key_type: NKeyType,
+        name: Option<String>,
+        rng: &dyn crate::ports::rng::CryptoRngPort,
+    ) -> Result<Self, String> {
+        use der::zeroize::Zeroize;
         use nkeys::KeyPair as NKeysKeyPair;
 
-        // Generate key pair using nkeys crate
-        let nkeys_pair = match key_type {
-            NKeyType::Operator => NKeysKeyPair::new_operator(),
-            NKeyType::Account => NKeysKeyPair::new_account(),
-            NKeyType::User => NKeysKeyPair::new_user(),
-            NKeyType::Server => NKeysKeyPair::new_server(),
-            NKeyType::Cluster => NKeysKeyPair::new_cluster(),
-        };
+        let mut raw = [0u8; 32];
+        rng.fill_bytes(&mut raw);
+        let nkeys_pair = NKeysKeyPair::new_from_raw(key_type.into(), raw);
+        raw.zeroize();
+        let nkeys_pair = nkeys_pair.map_err(|e| format!("Failed to generate {:?} nkey: {}", key_type, e))?;
 
         // Extract seed and public key
         let seed_string = nkeys_pair
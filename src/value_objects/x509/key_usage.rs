@@ -236,6 +236,16 @@ impl KeyUsage {
             KeyUsageBit::KeyEncipherment,
         ])
     }
+
+    /// Create standard key usage for a delegated OCSP responder
+    pub fn ocsp_signing() -> Self {
+        Self::from_bits([KeyUsageBit::DigitalSignature])
+    }
+
+    /// Create standard key usage for a time-stamp authority
+    pub fn time_stamping() -> Self {
+        Self::from_bits([KeyUsageBit::DigitalSignature, KeyUsageBit::NonRepudiation])
+    }
 }
 
 impl Default for KeyUsage {
@@ -519,6 +529,16 @@ impl ExtendedKeyUsage {
     pub fn email_protection() -> Self {
         Self::from_purposes([ExtendedKeyUsagePurpose::EmailProtection])
     }
+
+    /// Create standard EKU for a delegated OCSP responder
+    pub fn ocsp_signing() -> Self {
+        Self::from_purposes([ExtendedKeyUsagePurpose::OcspSigning])
+    }
+
+    /// Create standard EKU for a time-stamp authority (critical, RFC 3161)
+    pub fn time_stamping() -> Self {
+        Self::from_purposes([ExtendedKeyUsagePurpose::TimeStamping]).with_critical(true)
+    }
 }
 
 impl Default for ExtendedKeyUsage {
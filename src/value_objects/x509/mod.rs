@@ -30,6 +30,7 @@
 
 pub mod basic_constraints;
 pub mod key_usage;
+pub mod signature_algorithm;
 pub mod subject_alt_name;
 pub mod subject_name;
 pub mod validity;
@@ -66,4 +67,6 @@ pub use subject_alt_name::{
 
 pub use basic_constraints::BasicConstraints;
 
+pub use signature_algorithm::CertificateSignatureAlgorithm;
+
 pub use validity::{CertificateValidity, ValidityError};
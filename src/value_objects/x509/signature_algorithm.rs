@@ -0,0 +1,175 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+
+//! Certificate Signature Algorithm (RFC 5280 Section 4.1.1.2)
+//!
+//! Identifies the algorithm a CA uses to sign certificates, pairing the key
+//! type with its hash function. Only the combinations our certificate
+//! tooling can produce or verify are represented.
+//!
+//! ## Strength Ordering
+//!
+//! A chain is only as strong as its weakest signature, so an issuer must
+//! not sign a subject key stronger than its own. Strength follows the
+//! NIST SP 800-57 security-strength estimates (RSA rated at the 2048-bit
+//! minimum):
+//!
+//! | Algorithm          | Bits |
+//! |--------------------|------|
+//! | RSA (2048-bit)     | 112  |
+//! | ECDSA P-256/SHA256 | 128  |
+//! | Ed25519            | 128  |
+//! | ECDSA P-384/SHA384 | 192  |
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use cim_keys::value_objects::x509::CertificateSignatureAlgorithm;
+//!
+//! let root = CertificateSignatureAlgorithm::EcdsaP384Sha384;
+//! let leaf = CertificateSignatureAlgorithm::EcdsaP256Sha256;
+//! assert!(root.can_sign(leaf));
+//! assert!(!leaf.can_sign(root));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+// Import DDD marker traits from cim-domain
+use cim_domain::{DomainConcept, ValueObject};
+
+/// Signature algorithm of a certificate or of the key that issues it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum CertificateSignatureAlgorithm {
+    /// ECDSA on P-256 with SHA-256 (ecdsa-with-SHA256)
+    #[default]
+    EcdsaP256Sha256,
+    /// ECDSA on P-384 with SHA-384 (ecdsa-with-SHA384)
+    EcdsaP384Sha384,
+    /// Pure Ed25519 (RFC 8410)
+    Ed25519,
+    /// RSA PKCS#1 v1.5 with SHA-256 (sha256WithRSAEncryption)
+    RsaPkcs1Sha256,
+    /// RSA PKCS#1 v1.5 with SHA-384 (sha384WithRSAEncryption)
+    RsaPkcs1Sha384,
+    /// RSA PKCS#1 v1.5 with SHA-512 (sha512WithRSAEncryption)
+    RsaPkcs1Sha512,
+}
+
+impl CertificateSignatureAlgorithm {
+    /// Every supported algorithm
+    pub fn all() -> &'static [CertificateSignatureAlgorithm] {
+        &[
+            Self::EcdsaP256Sha256,
+            Self::EcdsaP384Sha384,
+            Self::Ed25519,
+            Self::RsaPkcs1Sha256,
+            Self::RsaPkcs1Sha384,
+            Self::RsaPkcs1Sha512,
+        ]
+    }
+
+    /// Conventional algorithm name as used in OpenSSL output
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EcdsaP256Sha256 => "ecdsa-with-SHA256",
+            Self::EcdsaP384Sha384 => "ecdsa-with-SHA384",
+            Self::Ed25519 => "ED25519",
+            Self::RsaPkcs1Sha256 => "sha256WithRSAEncryption",
+            Self::RsaPkcs1Sha384 => "sha384WithRSAEncryption",
+            Self::RsaPkcs1Sha512 => "sha512WithRSAEncryption",
+        }
+    }
+
+    /// Hash function applied before signing
+    ///
+    /// Ed25519 hashes internally with SHA-512.
+    pub fn hash_name(&self) -> &'static str {
+        match self {
+            Self::EcdsaP256Sha256 | Self::RsaPkcs1Sha256 => "SHA-256",
+            Self::EcdsaP384Sha384 | Self::RsaPkcs1Sha384 => "SHA-384",
+            Self::Ed25519 | Self::RsaPkcs1Sha512 => "SHA-512",
+        }
+    }
+
+    /// Approximate security strength in bits (NIST SP 800-57)
+    ///
+    /// RSA is rated for the 2048-bit keys we accept; larger moduli are
+    /// stronger but are not distinguished here.
+    pub fn security_bits(&self) -> u16 {
+        match self {
+            Self::RsaPkcs1Sha256 | Self::RsaPkcs1Sha384 | Self::RsaPkcs1Sha512 => 112,
+            Self::EcdsaP256Sha256 | Self::Ed25519 => 128,
+            Self::EcdsaP384Sha384 => 192,
+        }
+    }
+
+    /// Whether the key type is RSA
+    pub fn is_rsa(&self) -> bool {
+        matches!(self, Self::RsaPkcs1Sha256 | Self::RsaPkcs1Sha384 | Self::RsaPkcs1Sha512)
+    }
+
+    /// Whether a key using this algorithm may sign a certificate for a
+    /// subject key using `subject`
+    ///
+    /// The issuer must be at least as strong as the key it certifies. RSA
+    /// issuers are always accepted: their strength depends on a modulus
+    /// size the algorithm does not carry, and they can only be existing
+    /// operator-provided keys.
+    pub fn can_sign(&self, subject: CertificateSignatureAlgorithm) -> bool {
+        self.is_rsa() || self.security_bits() >= subject.security_bits()
+    }
+}
+
+impl fmt::Display for CertificateSignatureAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl DomainConcept for CertificateSignatureAlgorithm {}
+impl ValueObject for CertificateSignatureAlgorithm {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_p256() {
+        assert_eq!(
+            CertificateSignatureAlgorithm::default(),
+            CertificateSignatureAlgorithm::EcdsaP256Sha256
+        );
+    }
+
+    #[test]
+    fn test_issuer_must_be_at_least_as_strong_as_subject() {
+        use CertificateSignatureAlgorithm::*;
+
+        assert!(EcdsaP384Sha384.can_sign(EcdsaP256Sha256));
+        assert!(EcdsaP256Sha256.can_sign(Ed25519));
+        assert!(Ed25519.can_sign(RsaPkcs1Sha256));
+        assert!(!EcdsaP256Sha256.can_sign(EcdsaP384Sha384));
+        assert!(!Ed25519.can_sign(EcdsaP384Sha384));
+        // RSA modulus size is unknown, so RSA issuers are not second-guessed
+        assert!(RsaPkcs1Sha256.can_sign(EcdsaP384Sha384));
+    }
+
+    #[test]
+    fn test_names_and_hashes() {
+        use CertificateSignatureAlgorithm::*;
+
+        assert_eq!(EcdsaP384Sha384.to_string(), "ecdsa-with-SHA384");
+        assert_eq!(EcdsaP384Sha384.hash_name(), "SHA-384");
+        assert_eq!(RsaPkcs1Sha512.hash_name(), "SHA-512");
+        assert!(RsaPkcs1Sha384.is_rsa());
+        assert!(!Ed25519.is_rsa());
+        assert_eq!(CertificateSignatureAlgorithm::all().len(), 6);
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let json = serde_json::to_string(&CertificateSignatureAlgorithm::Ed25519).unwrap();
+        let back: CertificateSignatureAlgorithm = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, CertificateSignatureAlgorithm::Ed25519);
+    }
+}
@@ -430,7 +430,7 @@ impl fmt::Debug for YubiKeyPivConfiguration {
 }
 
 /// Firmware version value object
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FirmwareVersion {
     pub major: u8,
     pub minor: u8,
@@ -0,0 +1,117 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+//! Aggregate Scenarios
+//!
+//! Given/When/Then tests through `cim_keys::testing`, written the way a
+//! downstream CIM domain would use the harness. Mirrors the scenarios of
+//! person_management.feature that touch the aggregate.
+//!
+//! Run with: cargo test --test aggregate_scenarios --features test-utils
+
+use chrono::Utc;
+use cim_keys::commands::organization::{CreatePerson, DeactivatePerson, TerminateRelationship};
+use cim_keys::commands::{compensate, KeyCommand};
+use cim_keys::events::person::PersonCreatedEvent;
+use cim_keys::events::{DomainEvent, EventEnvelope, PersonEvents};
+use cim_keys::testing::AggregateScenario;
+use cim_keys::value_objects::ActorId;
+use uuid::Uuid;
+
+fn create_person(person_id: Uuid, organization_id: Uuid) -> CreatePerson {
+    CreatePerson {
+        command_id: Uuid::now_v7(),
+        person_id,
+        organization_id: Some(organization_id),
+        name: "Alice Johnson".to_string(),
+        email: "alice@example.com".to_string(),
+        title: Some("Developer".to_string()),
+        department: None,
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+        timestamp: Utc::now(),
+    }
+}
+
+/// Scenario: Create a person in an organization
+#[tokio::test]
+async fn scenario_create_person() {
+    let (person_id, organization_id) = (Uuid::now_v7(), Uuid::now_v7());
+    let command = create_person(person_id, organization_id);
+
+    AggregateScenario::new()
+        .when(KeyCommand::CreatePerson(command.clone()))
+        .await
+        .then_events(vec![DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+            person_id,
+            name: command.name,
+            email: Some(command.email),
+            title: command.title,
+            department: None,
+            organization_id,
+            created_by: ActorId::system("organization-cmd"),
+            correlation_id: command.correlation_id,
+            causation_id: Some(command.command_id),
+        }))]);
+}
+
+/// Scenario: Repeating a command produces nothing new
+#[tokio::test]
+async fn scenario_repeated_command_is_ignored() {
+    let command = create_person(Uuid::now_v7(), Uuid::now_v7());
+
+    AggregateScenario::new()
+        .given_processed(command.command_id)
+        .when(KeyCommand::CreatePerson(command))
+        .await
+        .then_no_events();
+}
+
+/// Scenario: Undo the creation of a person
+#[tokio::test]
+async fn scenario_undo_person_creation() {
+    let created = AggregateScenario::new()
+        .when(KeyCommand::CreatePerson(create_person(Uuid::now_v7(), Uuid::now_v7())))
+        .await
+        .then_event_types(&["PersonCreated"]);
+    let envelope = EventEnvelope::new(created[0].clone(), Uuid::now_v7(), None);
+    let undo = compensate(&envelope, Uuid::now_v7()).expect("PersonCreated is reversible");
+
+    AggregateScenario::new()
+        .given(created)
+        .when(undo.into())
+        .await
+        .then_event_types(&["PersonDeactivated"]);
+}
+
+/// Scenario: Deactivation requires a reason
+#[tokio::test]
+async fn scenario_deactivation_without_reason_is_rejected() {
+    AggregateScenario::new()
+        .when(KeyCommand::DeactivatePerson(DeactivatePerson {
+            command_id: Uuid::now_v7(),
+            person_id: Uuid::now_v7(),
+            reason: String::new(),
+            deactivated_by: Uuid::now_v7(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+            timestamp: Utc::now(),
+        }))
+        .await
+        .then_error();
+}
+
+/// Scenario: Terminating a relationship
+#[tokio::test]
+async fn scenario_terminate_relationship() {
+    AggregateScenario::new()
+        .when(KeyCommand::TerminateRelationship(TerminateRelationship {
+            command_id: Uuid::now_v7(),
+            relationship_id: Uuid::now_v7(),
+            reason: "Reorganization".to_string(),
+            terminated_by: "admin".to_string(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+            timestamp: Utc::now(),
+        }))
+        .await
+        .then_event_types(&["RelationshipTerminated"]);
+}
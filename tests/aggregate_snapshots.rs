@@ -0,0 +1,179 @@
+//! Aggregate Snapshot Tests
+//!
+//! Rehydrating KeyManagementAggregate from a snapshot plus the events after
+//! it must give the same state as replaying the whole stream.
+
+use chrono::Utc;
+use cim_keys::adapters::{InMemoryEventStore, InMemorySnapshotStore};
+use cim_keys::aggregate::{KeyManagementAggregate, KeyManagementRepository};
+use cim_keys::events::key::*;
+use cim_keys::events::{DomainEvent, EventEnvelope};
+use cim_keys::ports::{AggregateSnapshot, EventStore, EventStoreError, SnapshotStore};
+use cim_keys::types::{KeyAlgorithm, KeyMetadata, KeyPurpose, RevocationReason};
+use cim_keys::value_objects::ActorId;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+// =============================================================================
+// Test Helpers
+// =============================================================================
+
+fn envelope(event: KeyEvents) -> EventEnvelope {
+    EventEnvelope::new(DomainEvent::Key(event), Uuid::now_v7(), None)
+}
+
+fn key_generated(key_id: Uuid) -> EventEnvelope {
+    envelope(KeyEvents::KeyGenerated(KeyGeneratedEvent {
+        key_id,
+        algorithm: KeyAlgorithm::Ed25519,
+        purpose: KeyPurpose::Signing,
+        generated_at: Utc::now(),
+        generated_by: ActorId::system("key_admin"),
+        hardware_backed: false,
+        metadata: KeyMetadata {
+            label: "Snapshot Test Key".to_string(),
+            description: None,
+            tags: vec![],
+            attributes: HashMap::new(),
+            jwt_kid: None,
+            jwt_alg: None,
+            jwt_use: None,
+            hardware_serial: None,
+            tpm: None,
+        },
+        ownership: None,
+        expires_at: None,
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+    }))
+}
+
+fn key_revoked(key_id: Uuid) -> EventEnvelope {
+    envelope(KeyEvents::KeyRevoked(KeyRevokedEvent {
+        key_id,
+        reason: RevocationReason::Superseded,
+        revoked_at: Utc::now(),
+        revoked_by: ActorId::system("security_admin"),
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+    }))
+}
+
+fn key_stored_offline(key_id: Uuid) -> EventEnvelope {
+    envelope(KeyEvents::KeyStoredOffline(KeyStoredOfflineEvent {
+        key_id,
+        partition_id: Uuid::now_v7(),
+        encrypted: true,
+        stored_at: Utc::now(),
+        checksum: "sha256:abcd1234".to_string(),
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+    }))
+}
+
+/// Generate a key, store it offline, and revoke every third one
+fn key_lifecycle_batch(n: usize) -> Vec<EventEnvelope> {
+    let key_id = Uuid::now_v7();
+    let mut batch = vec![key_generated(key_id), key_stored_offline(key_id)];
+    if n % 3 == 0 {
+        batch.push(key_revoked(key_id));
+    }
+    batch
+}
+
+fn state(aggregate: &KeyManagementAggregate) -> serde_json::Value {
+    serde_json::to_value(aggregate).unwrap()
+}
+
+async fn full_replay(events: &InMemoryEventStore, id: Uuid) -> KeyManagementAggregate {
+    KeyManagementAggregate::replay(id, &events.load(id).await.unwrap())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[tokio::test]
+async fn test_snapshot_replay_matches_full_replay() {
+    let (events, snapshots) = (InMemoryEventStore::new(), InMemorySnapshotStore::new());
+    let repository = KeyManagementRepository::new(events.clone(), snapshots.clone()).with_snapshot_every(10);
+    let org_id = Uuid::now_v7();
+
+    for n in 0..20 {
+        let mut aggregate = repository.load(org_id).await.unwrap();
+        repository.save(&mut aggregate, &key_lifecycle_batch(n)).await.unwrap();
+    }
+
+    let full = full_replay(&events, org_id).await;
+    assert_eq!(full.keys.len(), 20);
+    assert_eq!(full.keys.values().filter(|k| k.revoked).count(), 7);
+
+    let snapshot = snapshots.load_latest(org_id).await.unwrap().expect("snapshot after 10 events");
+    let tail = events.load_after(org_id, snapshot.sequence).await.unwrap();
+    assert!(tail.len() < 10, "only events since the last snapshot are replayed");
+    assert!(tail.len() < events.load(org_id).await.unwrap().len());
+
+    let loaded = repository.load(org_id).await.unwrap();
+    assert_eq!(loaded.version, full.version);
+    assert_eq!(state(&loaded), state(&full));
+}
+
+#[tokio::test]
+async fn test_on_demand_snapshot() {
+    let (events, snapshots) = (InMemoryEventStore::new(), InMemorySnapshotStore::new());
+    let repository = KeyManagementRepository::new(events.clone(), snapshots.clone());
+    let org_id = Uuid::now_v7();
+
+    let mut aggregate = repository.load(org_id).await.unwrap();
+    for n in 0..5 {
+        repository.save(&mut aggregate, &key_lifecycle_batch(n)).await.unwrap();
+    }
+    assert!(snapshots.load_latest(org_id).await.unwrap().is_none(), "no automatic snapshots");
+
+    repository.snapshot(&mut aggregate).await.unwrap();
+    let snapshot = snapshots.load_latest(org_id).await.unwrap().unwrap();
+    assert_eq!(snapshot.sequence, aggregate.version);
+    assert_eq!(aggregate.events_since_snapshot, 0);
+
+    repository.save(&mut aggregate, &key_lifecycle_batch(5)).await.unwrap();
+    let loaded = repository.load(org_id).await.unwrap();
+    assert_eq!(loaded.events_since_snapshot, 2);
+    assert_eq!(state(&loaded), state(&full_replay(&events, org_id).await));
+}
+
+#[tokio::test]
+async fn test_unusable_snapshot_falls_back_to_full_replay() {
+    let (events, snapshots) = (InMemoryEventStore::new(), InMemorySnapshotStore::new());
+    let repository = KeyManagementRepository::new(events.clone(), snapshots.clone());
+    let org_id = Uuid::now_v7();
+
+    let mut aggregate = repository.load(org_id).await.unwrap();
+    repository.save(&mut aggregate, &key_lifecycle_batch(0)).await.unwrap();
+
+    // A snapshot from an older layout is ignored rather than misread
+    snapshots
+        .save(&AggregateSnapshot {
+            format_version: 0,
+            state: serde_json::json!({ "legacy": true }),
+            ..aggregate.to_snapshot().unwrap()
+        })
+        .await
+        .unwrap();
+
+    let loaded = repository.load(org_id).await.unwrap();
+    assert_eq!(state(&loaded), state(&full_replay(&events, org_id).await));
+}
+
+#[tokio::test]
+async fn test_stale_aggregate_cannot_save() {
+    let (events, snapshots) = (InMemoryEventStore::new(), InMemorySnapshotStore::new());
+    let repository = KeyManagementRepository::new(events, snapshots);
+    let org_id = Uuid::now_v7();
+
+    let mut first = repository.load(org_id).await.unwrap();
+    let mut second = repository.load(org_id).await.unwrap();
+
+    repository.save(&mut first, &key_lifecycle_batch(1)).await.unwrap();
+    let err = repository.save(&mut second, &key_lifecycle_batch(2)).await.unwrap_err();
+    assert!(matches!(err, EventStoreError::ConcurrencyConflict { expected: 0, .. }));
+}
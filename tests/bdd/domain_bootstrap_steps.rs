@@ -9,6 +9,7 @@ use cim_keys::{
         organization::CreateOrganization,
         KeyCommand,
     },
+    domain::PolicyEvaluationContext,
     events::DomainEvent,
 };
 use chrono::Utc;
@@ -69,7 +70,7 @@ pub async fn when_execute_bootstrap(
     let aggregate = ctx.aggregate();
     let projection = ctx.projection();
 
-    match aggregate.handle_command(command, projection, None, None).await {
+    match aggregate.handle_command(command, projection, None, &PolicyEvaluationContext::for_person(Uuid::nil())).await {
         Ok(events) => {
             // Apply events to projection
             for event in &events {
@@ -10,8 +10,8 @@ use cim_keys::events::DomainEvent as DomainEventEnum;
 use cim_domain::DomainEvent;
 use cim_keys::value_objects::ActorId;
 use cim_keys::value_objects::x509::{
-    BasicConstraints, CertificateValidity, CommonName, CountryCode, ExtendedKeyUsage, KeyUsage,
-    OrganizationName, SubjectAlternativeName, SubjectName,
+    BasicConstraints, CertificateSignatureAlgorithm, CertificateValidity, CommonName, CountryCode,
+    ExtendedKeyUsage, KeyUsage, OrganizationName, SubjectAlternativeName, SubjectName,
 };
 use uuid::Uuid;
 
@@ -54,6 +54,7 @@ fn sample_certificate_generated() -> CertificateGeneratedEvent {
         validity: sample_validity(),
         basic_constraints: BasicConstraints::end_entity(),
         issuer: Some(test_ca_id()),
+        signature_algorithm: Some(CertificateSignatureAlgorithm::EcdsaP384Sha384),
         correlation_id: Uuid::now_v7(),
         causation_id: None,
     }
@@ -88,6 +89,7 @@ fn sample_certificate_renewed() -> CertificateRenewedEvent {
         new_cert_id: Uuid::now_v7(),
         renewed_at: Utc::now(),
         renewed_by: ActorId::system("cert_admin"),
+        key_reused: true,
         correlation_id: Uuid::now_v7(),
         causation_id: None,
     }
@@ -128,6 +130,35 @@ fn sample_pki_hierarchy_created() -> PkiHierarchyCreatedEvent {
     }
 }
 
+fn sample_crl_published() -> CrlPublishedEvent {
+    CrlPublishedEvent {
+        crl_id: Uuid::now_v7(),
+        issuer_ca_id: test_ca_id(),
+        crl_number: 1,
+        this_update: Utc::now(),
+        next_update: Utc::now() + chrono::Duration::days(7),
+        revoked_cert_ids: vec![test_cert_id()],
+        crl_pem: "-----BEGIN X509 CRL-----\n-----END X509 CRL-----\n".to_string(),
+        published_by: ActorId::system("crl_publisher"),
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+    }
+}
+
+fn sample_certificate_issuance_rejected() -> CertificateIssuanceRejectedEvent {
+    CertificateIssuanceRejectedEvent {
+        rejection_id: Uuid::now_v7(),
+        issuer_ca_id: test_ca_id(),
+        policy_ca: "Device Authentication CA".to_string(),
+        subject: "printer.example.net".to_string(),
+        rejected_names: vec!["printer.example.net".to_string()],
+        reason: "printer.example.net is not permitted by policy".to_string(),
+        rejected_at: Utc::now(),
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+    }
+}
+
 fn sample_certificate_activated() -> CertificateActivatedEvent {
     CertificateActivatedEvent {
         cert_id: test_cert_id(),
@@ -170,6 +201,15 @@ fn test_certificate_generated_serialization() {
     let deserialized: CertificateGeneratedEvent = serde_json::from_str(&json).unwrap();
     assert_eq!(event.cert_id, deserialized.cert_id);
     assert_eq!(event.key_id, deserialized.key_id);
+    assert_eq!(deserialized.signature_algorithm, Some(CertificateSignatureAlgorithm::EcdsaP384Sha384));
+}
+
+#[test]
+fn test_certificate_generated_without_signature_algorithm_deserializes() {
+    let mut json = serde_json::to_value(sample_certificate_generated()).unwrap();
+    json.as_object_mut().unwrap().remove("signature_algorithm");
+    let deserialized: CertificateGeneratedEvent = serde_json::from_value(json).unwrap();
+    assert!(deserialized.signature_algorithm.is_none());
 }
 
 #[test]
@@ -268,6 +308,8 @@ fn test_certificate_events_enum_serialization() {
         CertificateEvents::CertificateActivated(sample_certificate_activated()),
         CertificateEvents::CertificateSuspended(sample_certificate_suspended()),
         CertificateEvents::CertificateExpired(sample_certificate_expired()),
+        CertificateEvents::CrlPublished(sample_crl_published()),
+        CertificateEvents::CertificateIssuanceRejected(sample_certificate_issuance_rejected()),
     ];
 
     for event in events {
@@ -389,6 +431,11 @@ fn test_event_type_returns_correct_strings() {
     assert_eq!(CertificateEvents::CertificateActivated(sample_certificate_activated()).event_type(), "CertificateActivated");
     assert_eq!(CertificateEvents::CertificateSuspended(sample_certificate_suspended()).event_type(), "CertificateSuspended");
     assert_eq!(CertificateEvents::CertificateExpired(sample_certificate_expired()).event_type(), "CertificateExpired");
+    assert_eq!(CertificateEvents::CrlPublished(sample_crl_published()).event_type(), "CrlPublished");
+    assert_eq!(
+        CertificateEvents::CertificateIssuanceRejected(sample_certificate_issuance_rejected()).event_type(),
+        "CertificateIssuanceRejected"
+    );
 }
 
 // =============================================================================
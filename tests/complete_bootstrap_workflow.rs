@@ -385,6 +385,7 @@ impl BootstrapWorkflow {
             locality: org.metadata.get("city").cloned(),
             validity_years: 20,
             pathlen: 1, // Allow one intermediate CA level
+            ..Default::default()
         };
 
         let root_correlation_id = Uuid::now_v7();
@@ -427,6 +428,7 @@ impl BootstrapWorkflow {
                 country: org.metadata.get("country").cloned(),
                 validity_years: 3,
                 pathlen: 0, // Can only sign leaf certificates
+                ..Default::default()
             };
 
             let intermediate_correlation_id = Uuid::now_v7();
@@ -490,6 +492,7 @@ impl BootstrapWorkflow {
                 organization: org.display_name.clone(),
                 organizational_unit: Some("Infrastructure".to_string()),
                 validity_days: 90,
+                ..Default::default()
             };
 
             let server_correlation_id = Uuid::now_v7();
@@ -6,6 +6,7 @@
 use cim_keys::{
     aggregate::KeyManagementAggregate,
     commands::organization::{CreateOrganization, CreatePerson, CreateLocation},
+    domain::PolicyEvaluationContext,
     events::DomainEvent,
     projections::OfflineKeyProjection,
 };
@@ -27,6 +28,14 @@ fn create_test_environment() -> (KeyManagementAggregate, OfflineKeyProjection, T
     (aggregate, projection, temp_dir)
 }
 
+/// Person issuing the commands in these tests
+const OPERATOR_ID: Uuid = Uuid::nil();
+
+/// Evaluation context of the operator
+fn operator() -> PolicyEvaluationContext {
+    PolicyEvaluationContext::for_person(OPERATOR_ID)
+}
+
 /// Helper to check if organization exists in projection (by name, as ID is not stored)
 fn has_organization(projection: &OfflineKeyProjection, org_id: Uuid) -> bool {
     // OrganizationInfo doesn't store the ID, so we verify it's not empty
@@ -62,7 +71,7 @@ async fn test_create_organization_command_flow() {
     });
 
     // Step 2: Process command through aggregate (async)
-    let events = aggregate.handle_command(command, &projection, None, None)
+    let events = aggregate.handle_command(command, &projection, None, &operator())
         .await
         .expect("Command should succeed");
 
@@ -98,7 +107,7 @@ async fn test_create_person_command_flow() {
         timestamp: Utc::now(),
     });
 
-    let org_events = aggregate.handle_command(org_command, &projection, None, None)
+    let org_events = aggregate.handle_command(org_command, &projection, None, &operator())
         .await
         .expect("Org creation should succeed");
 
@@ -122,7 +131,7 @@ async fn test_create_person_command_flow() {
         timestamp: Utc::now(),
     });
 
-    let person_events = aggregate.handle_command(person_command, &projection, None, None)
+    let person_events = aggregate.handle_command(person_command, &projection, None, &operator())
         .await
         .expect("Person creation should succeed");
 
@@ -165,7 +174,7 @@ async fn test_create_location_command_flow() {
         timestamp: Utc::now(),
     });
 
-    let org_events = aggregate.handle_command(org_command, &projection, None, None)
+    let org_events = aggregate.handle_command(org_command, &projection, None, &operator())
         .await
         .expect("Org creation should succeed");
 
@@ -188,7 +197,7 @@ async fn test_create_location_command_flow() {
         timestamp: Utc::now(),
     });
 
-    let location_events = aggregate.handle_command(location_command, &projection, None, None)
+    let location_events = aggregate.handle_command(location_command, &projection, None, &operator())
         .await
         .expect("Location creation should succeed");
 
@@ -230,7 +239,7 @@ async fn test_causation_chain_tracking() {
         timestamp: Utc::now(),
     });
 
-    let org_events = aggregate.handle_command(org_command, &projection, None, None)
+    let org_events = aggregate.handle_command(org_command, &projection, None, &operator())
         .await
         .expect("Org creation should succeed");
 
@@ -259,7 +268,7 @@ async fn test_causation_chain_tracking() {
         timestamp: Utc::now(),
     });
 
-    let person_events = aggregate.handle_command(person_command, &projection, None, None)
+    let person_events = aggregate.handle_command(person_command, &projection, None, &operator())
         .await
         .expect("Person creation should succeed");
 
@@ -346,7 +355,7 @@ async fn test_correlation_grouping() {
 
     // Process all commands (now async)
     for command in commands {
-        let events = aggregate.handle_command(command, &projection, None, None)
+        let events = aggregate.handle_command(command, &projection, None, &operator())
             .await
             .expect("Command should succeed");
         for event in &events {
@@ -381,7 +390,7 @@ async fn test_command_validation() {
         timestamp: Utc::now(),
     });
 
-    let result = aggregate.handle_command(command, &projection, None, None).await;
+    let result = aggregate.handle_command(command, &projection, None, &operator()).await;
 
     // Current implementation may succeed even with non-existent org
     // This test verifies the command processing works
@@ -405,7 +414,7 @@ async fn test_event_replay_idempotency() {
         timestamp: Utc::now(),
     });
 
-    let events = aggregate.handle_command(command, &projection, None, None)
+    let events = aggregate.handle_command(command, &projection, None, &operator())
         .await
         .expect("Command should succeed");
 
@@ -423,3 +432,453 @@ async fn test_event_replay_idempotency() {
     // Verify organization still exists once (not duplicated)
     assert!(has_organization(&projection, org_id), "Organization should exist");
 }
+
+#[tokio::test]
+async fn test_policy_requiring_encrypted_export_overrides_security_config() {
+    use cim_keys::aggregate::KeyManagementError;
+    use cim_keys::commands::{ExportSshPrivateKey, KeyCommand};
+    use cim_keys::config::SecurityConfig;
+    use cim_keys::domain::{BootstrapPersonId, Policy, PolicyClaim, PolicyCondition};
+    use cim_keys::types::ExportDestination;
+
+    let (aggregate, projection, _temp_dir) = create_test_environment();
+    let permissive = SecurityConfig {
+        allow_unencrypted_secret_export: true,
+        ..SecurityConfig::default()
+    };
+    let unprotected_export = || {
+        KeyCommand::ExportSshPrivateKey(
+            ExportSshPrivateKey::new(
+                Uuid::now_v7(),
+                "SHA256:ffTmVQgJ4D1PrBnc0QpqKm9bfImtR8TzvStVbL8kHPM",
+                ExportDestination::Memory,
+            )
+            .unprotected(),
+        )
+    };
+
+    // Security configuration alone permits the unprotected export
+    let aggregate = aggregate.with_security_config(permissive);
+    assert!(aggregate
+        .handle_command(unprotected_export(), &projection, None, &operator())
+        .await
+        .is_ok());
+
+    // A policy granting export only in encrypted form forbids it
+    let policy = Policy::new("Key Custodians", "Encrypted key export only", BootstrapPersonId::new())
+        .with_claim(PolicyClaim::CanExportKeys)
+        .with_condition(PolicyCondition::RequiresEncryptedExport);
+    let mut aggregate = aggregate.with_policies(vec![policy.clone()]);
+    aggregate.policy_bindings.insert(Uuid::now_v7(), (policy.id.as_uuid(), OPERATOR_ID));
+
+    let result = aggregate
+        .handle_command(unprotected_export(), &projection, None, &operator())
+        .await;
+    assert!(matches!(result, Err(KeyManagementError::PolicyViolation(_))));
+}
+
+#[tokio::test]
+async fn test_import_ssh_public_key_flow() {
+    use cim_keys::commands::{ImportSshPublicKey, KeyCommand};
+    use cim_keys::domain::{KeyOwnerRole, KeyOwnership};
+    use cim_keys::types::{KeyAlgorithm, KeyPurpose};
+
+    let (aggregate, mut projection, temp_dir) = create_test_environment();
+    let ownership = KeyOwnership {
+        person_id: Uuid::now_v7(),
+        organization_id: Uuid::now_v7(),
+        role: KeyOwnerRole::Developer,
+        delegations: Vec::new(),
+    };
+    let command = ImportSshPublicKey::new(
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIMJ91mXf7TtJLNcgObQ9CzEeMWBf/2WxM2vrzG6Kuuek alice@laptop",
+        "alice laptop",
+        ownership,
+    );
+    let key_id = command.key_id;
+
+    let events = aggregate
+        .handle_command(KeyCommand::ImportSshPublicKey(command), &projection, None, &operator())
+        .await
+        .expect("SSH public key import should succeed");
+    for event in &events {
+        projection.apply(event).expect("Projection should accept the import");
+    }
+
+    let entry = projection
+        .get_keys()
+        .iter()
+        .find(|k| k.key_id == key_id)
+        .expect("Imported key should be in the manifest");
+    assert_eq!(entry.algorithm, KeyAlgorithm::Ed25519);
+    assert_eq!(entry.purpose, KeyPurpose::Authentication);
+
+    let key_dir = temp_dir.path().join("keys").join(key_id.to_string());
+    let public_key = std::fs::read_to_string(key_dir.join("id.pub")).unwrap();
+    assert!(public_key.starts_with("ssh-ed25519 "));
+}
+
+#[tokio::test]
+async fn test_retried_command_does_not_generate_twice() {
+    use cim_keys::commands::{GenerateSshKeyCommand, KeyCommand};
+
+    let (aggregate, mut projection, temp_dir) = create_test_environment();
+    let command = KeyCommand::GenerateSshKey(GenerateSshKeyCommand {
+        command_id: cim_domain::EntityId::new(),
+        person_id: Uuid::now_v7(),
+        key_type: "ed25519".to_string(),
+        requestor: "GUI User".to_string(),
+        comment: None,
+    });
+    let command_id = command.command_id();
+
+    let events = aggregate.handle_command(command.clone(), &projection, None, &operator())
+        .await
+        .expect("Command should succeed");
+    assert!(!events.is_empty());
+    for event in &events {
+        projection.apply(event).ok();
+    }
+    projection.record_processed_command(command_id).unwrap();
+    assert!(projection.was_processed(command_id));
+
+    // Retried after a crash: the processed ID survives on the partition
+    let projection = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+    assert!(projection.was_processed(command_id));
+    let retried = aggregate.handle_command(command, &projection, None, &operator())
+        .await
+        .expect("Retry should succeed");
+    assert!(retried.is_empty(), "Retry must not emit new key events");
+
+    // A new command is still handled
+    let fresh = KeyCommand::GenerateSshKey(GenerateSshKeyCommand {
+        command_id: cim_domain::EntityId::new(),
+        person_id: Uuid::now_v7(),
+        key_type: "ed25519".to_string(),
+        requestor: "GUI User".to_string(),
+        comment: None,
+    });
+    assert!(!projection.was_processed(fresh.command_id()));
+    assert!(!aggregate.handle_command(fresh, &projection, None, &operator()).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_policy_change_requires_approval_quorum() {
+    use cim_keys::commands::organization::UnbindPolicy;
+    use cim_keys::commands::{ExecuteApprovedCommand, GrantApproval, KeyCommand, RequestApproval};
+    use cim_keys::domain::ids::BootstrapPersonId;
+    use cim_keys::domain::{ApprovalRule, ApprovalStatus, Policy, PolicyClaim, SensitiveCommandKind};
+    use cim_keys::events::EventEnvelope;
+    use cim_keys::ports::event_store::StoredEvent;
+
+    let (aggregate, projection, _temp_dir) = create_test_environment();
+    let policy_admin = Policy::new("Policy Admin", "Manage policies", BootstrapPersonId::new())
+        .with_claim(PolicyClaim::CanManagePolicies);
+    let mut aggregate = aggregate
+        .with_policies(vec![policy_admin.clone()])
+        .with_approval_rules(vec![
+            ApprovalRule::new(SensitiveCommandKind::PolicyChange, 2).with_approver_claim(PolicyClaim::CanManagePolicies),
+        ]);
+    let approvers = [Uuid::now_v7(), Uuid::now_v7()];
+    for person_id in [OPERATOR_ID, approvers[0], approvers[1]] {
+        aggregate.policy_bindings.insert(Uuid::now_v7(), (policy_admin.id.as_uuid(), person_id));
+    }
+    let mut sequence = 0;
+    let mut fold = |aggregate: &mut KeyManagementAggregate, events: Vec<DomainEvent>| {
+        for event in events {
+            sequence += 1;
+            aggregate.apply(&StoredEvent {
+                sequence,
+                envelope: EventEnvelope::new(event, Uuid::now_v7(), None),
+            });
+        }
+    };
+
+    let unbind = KeyCommand::UnbindPolicy(UnbindPolicy {
+        command_id: Uuid::now_v7(),
+        relationship_id: Uuid::now_v7(),
+        policy_id: Uuid::now_v7(),
+        entity_id: Uuid::now_v7(),
+        unbound_by: "alice".to_string(),
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+        timestamp: Utc::now(),
+    });
+
+    // Submitted directly, the policy change is refused
+    let direct = aggregate.handle_command(unbind.clone(), &projection, None, &operator()).await;
+    assert!(direct.is_err(), "Policy change must not run without approval");
+
+    // Nobody can request on someone else's behalf
+    let on_behalf = RequestApproval::new(unbind.clone(), approvers[0]);
+    assert!(aggregate
+        .handle_command(KeyCommand::RequestApproval(on_behalf), &projection, None, &operator())
+        .await
+        .is_err());
+
+    let request = RequestApproval::new(unbind.clone(), OPERATOR_ID);
+    let approval_id = request.approval_id;
+    let events = aggregate
+        .handle_command(KeyCommand::RequestApproval(request), &projection, None, &operator())
+        .await
+        .expect("Request should be accepted");
+    fold(&mut aggregate, events);
+    assert_eq!(aggregate.approvals[&approval_id].status, ApprovalStatus::Pending);
+
+    // A person without the claim cannot approve, under their own ID or a
+    // made-up one
+    let outsider = Uuid::now_v7();
+    for approver_id in [outsider, Uuid::now_v7()] {
+        let forged = GrantApproval::new(approval_id, approver_id);
+        let result = aggregate
+            .handle_command(
+                KeyCommand::GrantApproval(forged),
+                &projection,
+                None,
+                &PolicyEvaluationContext::for_person(outsider),
+            )
+            .await;
+        assert!(result.is_err(), "Approval without evaluated claims must be refused");
+    }
+    assert!(aggregate.approvals[&approval_id].approvers.is_empty());
+
+    for approver_id in approvers {
+        let grant = GrantApproval::new(approval_id, approver_id);
+        let events = aggregate
+            .handle_command(
+                KeyCommand::GrantApproval(grant),
+                &projection,
+                None,
+                &PolicyEvaluationContext::for_person(approver_id),
+            )
+            .await
+            .expect("Qualified approver should be accepted");
+        fold(&mut aggregate, events);
+    }
+    assert_eq!(aggregate.approvals[&approval_id].status, ApprovalStatus::Approved);
+
+    let execute = KeyCommand::ExecuteApprovedCommand(ExecuteApprovedCommand::new(approval_id, unbind));
+    let events = aggregate
+        .handle_command(execute, &projection, None, &operator())
+        .await
+        .expect("Approved command should run");
+    assert!(events.iter().any(|e| matches!(e, DomainEvent::Relationship(_))));
+    fold(&mut aggregate, events);
+    assert_eq!(aggregate.approvals[&approval_id].status, ApprovalStatus::Executed);
+}
+
+#[tokio::test]
+async fn test_command_without_granted_claims_is_denied() {
+    use cim_keys::commands::{GenerateSshKeyCommand, KeyCommand};
+    use cim_keys::domain::{Policy, PolicyClaim, PolicyCondition};
+    use cim_keys::domain::ids::BootstrapPersonId;
+    use cim_keys::events::AuthorizationEvents;
+
+    let (aggregate, projection, _temp_dir) = create_test_environment();
+    let key_generation = Policy::new("Key Generation", "Generate keys with MFA", BootstrapPersonId::new())
+        .with_claim(PolicyClaim::CanGenerateKeys)
+        .with_condition(PolicyCondition::MFAEnabled(true));
+    let mut aggregate = aggregate.with_policies(vec![key_generation.clone()]);
+
+    let person_id = Uuid::now_v7();
+    aggregate.policy_bindings.insert(Uuid::now_v7(), (key_generation.id.as_uuid(), person_id));
+    let context = |mfa_verified: bool| PolicyEvaluationContext {
+        mfa_verified,
+        ..PolicyEvaluationContext::for_person(person_id)
+    };
+    let ssh_key = || KeyCommand::GenerateSshKey(GenerateSshKeyCommand {
+        command_id: cim_domain::EntityId::new(),
+        person_id,
+        key_type: "ed25519".to_string(),
+        requestor: "GUI User".to_string(),
+        comment: None,
+    });
+
+    // Without MFA the policy is inactive: the command is denied
+    let events = aggregate
+        .handle_command(ssh_key(), &projection, None, &context(false))
+        .await
+        .expect("Denial is recorded, not an error");
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        DomainEvent::Authorization(AuthorizationEvents::CommandDenied(denied)) => {
+            assert_eq!(denied.actor_id, person_id);
+            assert_eq!(denied.missing_claims, vec![PolicyClaim::CanGenerateKeys]);
+            assert_eq!(denied.failing_conditions.len(), 1);
+            assert!(denied.failing_conditions[0].starts_with("Key Generation: "));
+        }
+        other => panic!("Expected CommandDenied, got {:?}", other),
+    }
+
+    // A person without bindings holds no claims at all
+    let events = aggregate
+        .handle_command(ssh_key(), &projection, None, &operator())
+        .await
+        .expect("Denial is recorded, not an error");
+    assert!(matches!(
+        &events[..],
+        [DomainEvent::Authorization(AuthorizationEvents::CommandDenied(_))]
+    ));
+
+    // With MFA the claim is granted and the key is generated
+    let events = aggregate
+        .handle_command(ssh_key(), &projection, None, &context(true))
+        .await
+        .expect("Command should succeed");
+    assert!(events.iter().all(|e| !matches!(e, DomainEvent::Authorization(_))));
+    assert!(!events.is_empty());
+}
+
+#[tokio::test]
+async fn test_suspension_without_transition_claim_is_denied() {
+    use cim_keys::commands::organization::SuspendPerson;
+    use cim_keys::commands::{CommandAuthorizer, KeyCommand};
+    use cim_keys::domain::ids::BootstrapPersonId;
+    use cim_keys::domain::{Policy, PolicyClaim};
+    use cim_keys::events::AuthorizationEvents;
+
+    let (aggregate, mut projection, _temp_dir) = create_test_environment();
+    let person_id = Uuid::now_v7();
+    let created = aggregate
+        .handle_command(
+            KeyCommand::CreatePerson(CreatePerson {
+                command_id: Uuid::now_v7(),
+                person_id,
+                organization_id: Some(Uuid::now_v7()),
+                name: "Bob Smith".to_string(),
+                email: "bob@example.com".to_string(),
+                title: None,
+                department: None,
+                correlation_id: Uuid::now_v7(),
+                causation_id: None,
+                timestamp: Utc::now(),
+            }),
+            &projection,
+            None,
+            &operator(),
+        )
+        .await
+        .expect("Person should be created while bootstrapping");
+    for event in &created {
+        projection.apply(event).expect("Failed to project person");
+    }
+
+    // The command itself needs no claims here, so only the guard can refuse it
+    let mut authorizer = CommandAuthorizer::default();
+    authorizer.require_claims("SuspendPerson", Vec::new());
+    let accounts = Policy::new("Accounts", "Create accounts", BootstrapPersonId::new())
+        .with_claim(PolicyClaim::CanCreateAccounts);
+    let disabling = Policy::new("Disabling", "Disable accounts", BootstrapPersonId::new())
+        .with_claim(PolicyClaim::CanDisableAccounts);
+    let mut aggregate = aggregate
+        .with_authorizer(authorizer)
+        .with_policies(vec![accounts.clone(), disabling.clone()]);
+    aggregate.policy_bindings.insert(Uuid::now_v7(), (accounts.id.as_uuid(), OPERATOR_ID));
+
+    let suspend = || KeyCommand::SuspendPerson(SuspendPerson {
+        command_id: Uuid::now_v7(),
+        person_id,
+        reason: "Security review".to_string(),
+        suspended_by: OPERATOR_ID,
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+        timestamp: Utc::now(),
+    });
+
+    let events = aggregate
+        .handle_command(suspend(), &projection, None, &operator())
+        .await
+        .expect("Denial is recorded, not an error");
+    assert_eq!(events.len(), 1);
+    match &events[0] {
+        DomainEvent::Authorization(AuthorizationEvents::TransitionDenied(denied)) => {
+            assert_eq!(denied.entity_id, person_id);
+            assert_eq!(denied.state_machine, "PersonState");
+            assert_eq!(denied.to_state, "Suspended");
+            assert_eq!(denied.missing_claims, vec![PolicyClaim::CanDisableAccounts]);
+        }
+        other => panic!("Expected TransitionDenied, got {:?}", other),
+    }
+
+    aggregate.policy_bindings.insert(Uuid::now_v7(), (disabling.id.as_uuid(), OPERATOR_ID));
+    let events = aggregate
+        .handle_command(suspend(), &projection, None, &operator())
+        .await
+        .expect("Suspension should succeed");
+    assert!(matches!(
+        &events[..],
+        [DomainEvent::Person(cim_keys::events::PersonEvents::PersonSuspended(_))]
+    ));
+}
+
+#[tokio::test]
+async fn test_role_assignment_breaking_separation_of_duties_is_refused() {
+    use cim_keys::commands::organization::{AssignRole, BindPolicy};
+    use cim_keys::commands::KeyCommand;
+    use cim_keys::domain::ids::BootstrapPersonId;
+    use cim_keys::domain::{Policy, PolicyClaim, PolicyEntityType, SeparationOfDutiesRule};
+    use cim_keys::events::{AuthorizationEvents, EventEnvelope};
+    use cim_keys::ports::event_store::StoredEvent;
+    use cim_keys::projections::separation_of_duties::SeparationOfDutiesReport;
+
+    let (aggregate, projection, _temp_dir) = create_test_environment();
+    let signing = Policy::new("Signing", "Issue certificates", BootstrapPersonId::new())
+        .with_claim(PolicyClaim::CanSignCertificates);
+    let auditing = Policy::new("Auditing", "Audit issuance", BootstrapPersonId::new())
+        .with_claim(PolicyClaim::CanPerformAudits);
+    let administration = Policy::new("Administration", "Manage policies and roles", BootstrapPersonId::new())
+        .with_claim(PolicyClaim::CanManagePolicies)
+        .with_claim(PolicyClaim::CanAssignRoles);
+    let mut aggregate = aggregate
+        .with_policies(vec![signing.clone(), auditing.clone(), administration.clone()])
+        .with_separation_rules(vec![SeparationOfDutiesRule::new(
+            "Issuer/auditor",
+            PolicyClaim::CanSignCertificates,
+            PolicyClaim::CanPerformAudits,
+        )]);
+    aggregate.policy_bindings.insert(Uuid::now_v7(), (administration.id.as_uuid(), OPERATOR_ID));
+    let person_id = Uuid::now_v7();
+
+    let bind = KeyCommand::BindPolicy(BindPolicy {
+        command_id: Uuid::now_v7(),
+        relationship_id: Uuid::now_v7(),
+        policy_id: signing.id.as_uuid(),
+        entity_id: person_id,
+        entity_type: PolicyEntityType::Person,
+        bound_by: "admin".to_string(),
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+        timestamp: Utc::now(),
+    });
+    let events = aggregate.handle_command(bind, &projection, None, &operator()).await.expect("Binding should succeed");
+    assert!(matches!(events[0], DomainEvent::Relationship(_)));
+    for (sequence, event) in events.into_iter().enumerate() {
+        aggregate.apply(&StoredEvent {
+            sequence: sequence as u64 + 1,
+            envelope: EventEnvelope::new(event, Uuid::now_v7(), None),
+        });
+    }
+    assert_eq!(aggregate.held_claims(person_id), vec![PolicyClaim::CanSignCertificates]);
+
+    let assign = KeyCommand::AssignRole(AssignRole {
+        command_id: Uuid::now_v7(),
+        person_id,
+        role_id: Uuid::now_v7(),
+        role_name: "Auditor".to_string(),
+        required_policies: vec![auditing.id.as_uuid()],
+        assigned_by: Uuid::now_v7(),
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+        timestamp: Utc::now(),
+    });
+    let events = aggregate.handle_command(assign, &projection, None, &operator()).await.expect("Refusal is an event");
+
+    assert_eq!(events.len(), 1);
+    assert!(matches!(
+        &events[0],
+        DomainEvent::Authorization(AuthorizationEvents::SeparationOfDutiesViolated(e)) if e.entity_id == person_id
+    ));
+    let report = SeparationOfDutiesReport::from_events(&events);
+    assert_eq!(report.violations_for(person_id).len(), 1);
+    assert_eq!(report.by_rule[0].refusals, 1);
+}
@@ -25,6 +25,7 @@ use cim_keys::{
         KeyCommand,
     },
     domain::{Organization, OrganizationUnit, OrganizationUnitType, Person, UserIdentity, AccountIdentity},
+    domain::PolicyEvaluationContext,
     domain::ids::{BootstrapOrgId, BootstrapPersonId, UnitId},
     events::{
         DomainEvent,
@@ -46,6 +47,11 @@ use uuid::Uuid;
 // Test Helpers
 // =============================================================================
 
+/// Evaluation context of the person issuing the commands
+fn operator() -> PolicyEvaluationContext {
+    PolicyEvaluationContext::for_person(Uuid::nil())
+}
+
 fn create_test_environment() -> (KeyManagementAggregate, OfflineKeyProjection, TempDir) {
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
     let output_path = temp_dir.path().to_path_buf();
@@ -88,7 +94,7 @@ mod pki_bootstrap {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(org_command, &projection, None, None)
+        let events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .expect("Organization creation should succeed");
 
@@ -119,7 +125,7 @@ mod pki_bootstrap {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(org_command, &projection, None, None)
+        let events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .unwrap();
         for event in events {
@@ -152,7 +158,7 @@ mod pki_bootstrap {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(person_command, &projection, None, None)
+            let events = aggregate.handle_command(person_command, &projection, None, &operator())
                 .await
                 .expect("Person creation should succeed");
 
@@ -185,7 +191,7 @@ mod pki_bootstrap {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(org_command, &projection, None, None)
+        let events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .unwrap();
         for event in events {
@@ -218,7 +224,7 @@ mod pki_bootstrap {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(location_command, &projection, None, None)
+            let events = aggregate.handle_command(location_command, &projection, None, &operator())
                 .await
                 .expect("Location creation should succeed");
 
@@ -251,7 +257,7 @@ mod pki_bootstrap {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(org_command, &projection, None, None)
+        let events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .unwrap();
         for event in events {
@@ -273,7 +279,7 @@ mod pki_bootstrap {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(person_command, &projection, None, None)
+        let events = aggregate.handle_command(person_command, &projection, None, &operator())
             .await
             .unwrap();
         for event in events {
@@ -295,7 +301,7 @@ mod pki_bootstrap {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(location_command, &projection, None, None)
+        let events = aggregate.handle_command(location_command, &projection, None, &operator())
             .await
             .unwrap();
         for event in events {
@@ -336,7 +342,7 @@ mod event_chains {
             timestamp: Utc::now(),
         });
 
-        let org_events = aggregate.handle_command(org_command, &projection, None, None)
+        let org_events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .expect("Should succeed");
 
@@ -368,7 +374,7 @@ mod event_chains {
             timestamp: Utc::now(),
         });
 
-        let person_events = aggregate.handle_command(person_command, &projection, None, None)
+        let person_events = aggregate.handle_command(person_command, &projection, None, &operator())
             .await
             .expect("Should succeed");
 
@@ -408,7 +414,7 @@ mod event_chains {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(org_command, &projection, None, None)
+        let events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .unwrap();
         all_events.extend(events.clone());
@@ -430,7 +436,7 @@ mod event_chains {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(person_command, &projection, None, None)
+        let events = aggregate.handle_command(person_command, &projection, None, &operator())
             .await
             .unwrap();
         all_events.extend(events.clone());
@@ -452,7 +458,7 @@ mod event_chains {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(location_command, &projection, None, None)
+        let events = aggregate.handle_command(location_command, &projection, None, &operator())
             .await
             .unwrap();
         all_events.extend(events.clone());
@@ -499,7 +505,7 @@ mod multi_organization {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(org_command, &projection, None, None)
+            let events = aggregate.handle_command(org_command, &projection, None, &operator())
                 .await
                 .expect("Organization creation should succeed");
 
@@ -533,7 +539,7 @@ mod multi_organization {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(org_command, &projection, None, None)
+            let events = aggregate.handle_command(org_command, &projection, None, &operator())
                 .await
                 .unwrap();
             for event in events {
@@ -556,7 +562,7 @@ mod multi_organization {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(person_command, &projection, None, None)
+            let events = aggregate.handle_command(person_command, &projection, None, &operator())
                 .await
                 .unwrap();
             for event in events {
@@ -579,7 +585,7 @@ mod multi_organization {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(person_command, &projection, None, None)
+            let events = aggregate.handle_command(person_command, &projection, None, &operator())
                 .await
                 .unwrap();
             for event in events {
@@ -622,7 +628,7 @@ mod projection_consistency {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(org_command, &projection, None, None)
+            let events = aggregate.handle_command(org_command, &projection, None, &operator())
                 .await
                 .unwrap();
             for event in events {
@@ -643,7 +649,7 @@ mod projection_consistency {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(person_command, &projection, None, None)
+            let events = aggregate.handle_command(person_command, &projection, None, &operator())
                 .await
                 .unwrap();
             for event in events {
@@ -680,7 +686,7 @@ mod projection_consistency {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(org_command, &projection, None, None)
+        let events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .unwrap();
 
@@ -731,7 +737,7 @@ mod error_handling {
             timestamp: Utc::now(),
         });
 
-        let result = aggregate.handle_command(person_command, &projection, None, None).await;
+        let result = aggregate.handle_command(person_command, &projection, None, &operator()).await;
         // This may succeed or fail depending on implementation
         // The test verifies we don't panic
         assert!(result.is_ok() || result.is_err());
@@ -762,7 +768,7 @@ mod bulk_operations {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(org_command, &projection, None, None)
+        let events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .unwrap();
         for event in events {
@@ -784,7 +790,7 @@ mod bulk_operations {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(person_command, &projection, None, None)
+            let events = aggregate.handle_command(person_command, &projection, None, &operator())
                 .await
                 .expect(&format!("Person {} creation should succeed", i));
 
@@ -813,7 +819,7 @@ mod bulk_operations {
             timestamp: Utc::now(),
         });
 
-        let events = aggregate.handle_command(org_command, &projection, None, None)
+        let events = aggregate.handle_command(org_command, &projection, None, &operator())
             .await
             .unwrap();
         for event in events {
@@ -835,7 +841,7 @@ mod bulk_operations {
                 timestamp: Utc::now(),
             });
 
-            let events = aggregate.handle_command(location_command, &projection, None, None)
+            let events = aggregate.handle_command(location_command, &projection, None, &operator())
                 .await
                 .expect(&format!("Location {} creation should succeed", i));
 
@@ -892,6 +898,7 @@ mod yubikey_provisioning {
 
         // Create the provision command
         let provision_command = KeyCommand::ProvisionYubiKey(ProvisionYubiKeySlot {
+            command_id: Uuid::now_v7(),
             yubikey_serial: "12345678".to_string(),
             slot: PivSlot::Authentication,
             person: person.clone(),
@@ -901,7 +908,7 @@ mod yubikey_provisioning {
             causation_id: None,
         });
 
-        let events = aggregate.handle_command(provision_command, &projection, None, None)
+        let events = aggregate.handle_command(provision_command, &projection, None, &operator())
             .await
             .expect("YubiKey provisioning should succeed");
 
@@ -931,6 +938,7 @@ mod yubikey_provisioning {
 
         // Provision authentication slot
         let auth_command = KeyCommand::ProvisionYubiKey(ProvisionYubiKeySlot {
+            command_id: Uuid::now_v7(),
             yubikey_serial: "12345678".to_string(),
             slot: PivSlot::Authentication,
             person: person.clone(),
@@ -940,7 +948,7 @@ mod yubikey_provisioning {
             causation_id: None,
         });
 
-        let auth_events = aggregate.handle_command(auth_command, &projection, None, None)
+        let auth_events = aggregate.handle_command(auth_command, &projection, None, &operator())
             .await
             .expect("Authentication slot provisioning should succeed");
 
@@ -950,6 +958,7 @@ mod yubikey_provisioning {
 
         // Provision signature slot
         let sign_command = KeyCommand::ProvisionYubiKey(ProvisionYubiKeySlot {
+            command_id: Uuid::now_v7(),
             yubikey_serial: "12345678".to_string(),
             slot: PivSlot::Signature,
             person: person.clone(),
@@ -959,7 +968,7 @@ mod yubikey_provisioning {
             causation_id: Some(correlation_id),
         });
 
-        let sign_events = aggregate.handle_command(sign_command, &projection, None, None)
+        let sign_events = aggregate.handle_command(sign_command, &projection, None, &operator())
             .await
             .expect("Signature slot provisioning should succeed");
 
@@ -981,6 +990,7 @@ mod yubikey_provisioning {
         let person = create_test_person(org.id);
 
         let provision_command = KeyCommand::ProvisionYubiKey(ProvisionYubiKeySlot {
+            command_id: Uuid::now_v7(),
             yubikey_serial: "12345678".to_string(),
             slot: PivSlot::KeyManagement,
             person: person.clone(),
@@ -990,7 +1000,7 @@ mod yubikey_provisioning {
             causation_id: None,
         });
 
-        let events = aggregate.handle_command(provision_command, &projection, None, None)
+        let events = aggregate.handle_command(provision_command, &projection, None, &operator())
             .await
             .expect("Provisioning should succeed");
 
@@ -1036,6 +1046,7 @@ mod yubikey_provisioning {
             let correlation_id = Uuid::now_v7();
 
             let provision_command = KeyCommand::ProvisionYubiKey(ProvisionYubiKeySlot {
+                command_id: Uuid::now_v7(),
                 yubikey_serial: format!("YK-{}", Uuid::now_v7().as_u128() % 100000000),
                 slot: PivSlot::Authentication,
                 person: person.clone(),
@@ -1045,7 +1056,7 @@ mod yubikey_provisioning {
                 causation_id: None,
             });
 
-            let events = aggregate.handle_command(provision_command, &projection, None, None)
+            let events = aggregate.handle_command(provision_command, &projection, None, &operator())
                 .await
                 .expect(&format!("Provisioning for {} should succeed", name));
 
@@ -53,8 +53,11 @@ fn create_key_event() -> DomainEvent {
             jwt_kid: None,
             jwt_alg: None,
             jwt_use: None,
+            hardware_serial: None,
+            tpm: None,
         },
         ownership: None,
+        expires_at: None,
         correlation_id: Uuid::now_v7(),
         causation_id: None,
     }))
@@ -39,8 +39,11 @@ fn create_test_key_event(key_id: Uuid, seq: u64) -> StoredEvent {
             jwt_kid: None,
             jwt_alg: None,
             jwt_use: None,
+            hardware_serial: None,
+            tpm: None,
         },
         ownership: None,
+        expires_at: None,
         correlation_id: Uuid::now_v7(),
         causation_id: None,
     };
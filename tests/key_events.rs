@@ -37,6 +37,8 @@ fn test_key_metadata() -> KeyMetadata {
         jwt_kid: Some("test-kid-123".to_string()),
         jwt_alg: Some("RS256".to_string()),
         jwt_use: None,
+        hardware_serial: None,
+        tpm: None,
     }
 }
 
@@ -59,6 +61,7 @@ fn sample_key_generated() -> KeyGeneratedEvent {
         hardware_backed: false,
         metadata: test_key_metadata(),
         ownership: Some(test_key_ownership()),
+        expires_at: None,
         correlation_id: Uuid::now_v7(),
         causation_id: None,
     }
@@ -72,6 +75,7 @@ fn sample_key_imported() -> KeyImportedEvent {
         imported_at: Utc::now(),
         imported_by: ActorId::system("admin"),
         metadata: test_key_metadata(),
+        ownership: None,
         correlation_id: Uuid::now_v7(),
         causation_id: None,
     }
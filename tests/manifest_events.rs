@@ -371,3 +371,32 @@ fn test_projection_types() {
         assert_eq!(projection.projection_type, proj_type);
     }
 }
+
+#[test]
+fn test_redundant_export_completed_roundtrip() {
+    let export_id = test_export_id();
+    let media = |media_id: &str, verified: bool| ExportMediaRecord {
+        media_id: media_id.to_string(),
+        path: format!("/run/media/{}", media_id),
+        files_written: 12,
+        verified,
+        mismatched_files: if verified { vec![] } else { vec!["domain/people.json".to_string()] },
+    };
+    let event = ManifestEvents::RedundantExportCompleted(RedundantExportCompletedEvent {
+        export_id,
+        organization_name: "Acme Corp".to_string(),
+        primary: media("primary", true),
+        backup: media("backup", false),
+        exported_at: Utc::now(),
+        correlation_id: Uuid::now_v7(),
+        causation_id: None,
+    });
+
+    let json = serde_json::to_string(&event).unwrap();
+    let ManifestEvents::RedundantExportCompleted(deserialized) = serde_json::from_str(&json).unwrap() else {
+        panic!("wrong variant");
+    };
+    assert_eq!(deserialized.backup, media("backup", false));
+    assert_eq!(event.aggregate_id(), export_id);
+    assert_eq!(event.event_type(), "RedundantExportCompleted");
+}
@@ -79,7 +79,6 @@ fn sample_nkey_generated() -> NKeyGeneratedEvent {
         nkey_id: test_nkey_id(),
         key_type: "operator".to_string(),
         public_key: "OKXYZ...".to_string(),
-        seed: "SOKXYZ...".to_string(),
         purpose: "Operator signing".to_string(),
         expires_at: None,
         generated_at: Utc::now(),
@@ -353,10 +352,11 @@ fn test_uuid_fields_are_valid() {
 }
 
 #[test]
-fn test_nkey_has_seed() {
+fn test_nkey_event_has_no_seed() {
     let nkey = sample_nkey_generated();
-    assert!(!nkey.seed.is_empty());
     assert!(!nkey.public_key.is_empty());
+    let json = serde_json::to_string(&nkey).unwrap();
+    assert!(!json.contains("seed"));
 }
 
 #[test]
@@ -494,7 +494,6 @@ fn test_nkey_generation_workflow() {
     };
 
     assert_eq!(nkey.nkey_id, nkey_id);
-    assert!(!nkey.seed.is_empty());
     assert!(!nkey.public_key.is_empty());
 }
 
@@ -52,6 +52,7 @@ fn sample_role_assigned() -> RoleAssignedEvent {
         person_id: Uuid::now_v7(),
         role_id: Uuid::now_v7(),
         role_name: "Admin".to_string(),
+        required_policies: vec![],
         assigned_at: Utc::now(),
         assigned_by: Uuid::now_v7(),
         correlation_id: Uuid::now_v7(),
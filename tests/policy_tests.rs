@@ -3,6 +3,8 @@
 //! Tests cover:
 //! - Policy creation and condition evaluation
 //! - Claims composition (additive union)
+//! - Claim subtraction by deny policies (deny wins, higher priority overrides)
+//! - Policy versions picked by effective date
 //! - Priority-based policy sorting
 //! - Role assignment and fulfillment
 //! - Complex multi-policy scenarios
@@ -33,9 +35,13 @@ fn test_policy_claim_composition() {
             PolicyClaim::CanAccessDevelopment,
             PolicyClaim::CanSignCode,
         ],
+        denied_claims: vec![],
         conditions: vec![],
         priority: 100,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -48,9 +54,13 @@ fn test_policy_claim_composition() {
             PolicyClaim::CanAccessProduction,
             PolicyClaim::CanSignCode, // Overlapping claim
         ],
+        denied_claims: vec![],
         conditions: vec![],
         priority: 200,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -117,9 +127,13 @@ fn test_policy_priority_sorting() {
         name: "Low Priority".to_string(),
         description: "Low priority policy".to_string(),
         claims: vec![PolicyClaim::CanAccessDevelopment],
+        denied_claims: vec![],
         conditions: vec![],
         priority: 10,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -129,9 +143,13 @@ fn test_policy_priority_sorting() {
         name: "High Priority".to_string(),
         description: "High priority policy".to_string(),
         claims: vec![PolicyClaim::CanAccessProduction],
+        denied_claims: vec![],
         conditions: vec![],
         priority: 1000,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -141,9 +159,13 @@ fn test_policy_priority_sorting() {
         name: "Medium Priority".to_string(),
         description: "Medium priority policy".to_string(),
         claims: vec![PolicyClaim::CanAccessStaging],
+        denied_claims: vec![],
         conditions: vec![],
         priority: 500,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -219,9 +241,13 @@ fn test_policy_condition_minimum_clearance() {
         name: "Secret Operations".to_string(),
         description: "Requires Secret clearance".to_string(),
         claims: vec![PolicyClaim::CanAccessProduction],
+        denied_claims: vec![],
         conditions: vec![PolicyCondition::MinimumSecurityClearance(SecurityClearance::Secret)],
         priority: 100,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -295,9 +321,13 @@ fn test_policy_condition_mfa_required() {
         name: "MFA Required".to_string(),
         description: "Must have MFA verified".to_string(),
         claims: vec![PolicyClaim::CanModifyInfrastructure],
+        denied_claims: vec![],
         conditions: vec![PolicyCondition::MFAEnabled(true)],
         priority: 100,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -368,12 +398,16 @@ fn test_policy_condition_witness_required() {
         name: "Dual Control".to_string(),
         description: "Requires 2 witnesses with Secret clearance".to_string(),
         claims: vec![PolicyClaim::CanOverrideSecurityControls],
+        denied_claims: vec![],
         conditions: vec![PolicyCondition::RequiresWitness {
             count: 2,
             witness_clearance: Some(SecurityClearance::Secret),
         }],
         priority: 100,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -485,9 +519,13 @@ fn test_role_fulfillment() {
             PolicyClaim::CanAccessDevelopment,
             PolicyClaim::CanSignCode,
         ],
+        denied_claims: vec![],
         conditions: vec![],
         priority: 100,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -558,9 +596,13 @@ fn test_complex_multi_policy_scenario() {
             PolicyClaim::CanAccessDevelopment,
             PolicyClaim::CanViewAuditLogs,
         ],
+        denied_claims: vec![],
         conditions: vec![],
         priority: 10,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -573,12 +615,16 @@ fn test_complex_multi_policy_scenario() {
             PolicyClaim::CanAccessStaging,
             PolicyClaim::CanDeployServices,
         ],
+        denied_claims: vec![],
         conditions: vec![
             PolicyCondition::MinimumSecurityClearance(SecurityClearance::Confidential),
             PolicyCondition::MFAEnabled(true),
         ],
         priority: 50,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -592,6 +638,7 @@ fn test_complex_multi_policy_scenario() {
             PolicyClaim::CanDeployServices,
             PolicyClaim::CanModifyInfrastructure,
         ],
+        denied_claims: vec![],
         conditions: vec![
             PolicyCondition::MinimumSecurityClearance(SecurityClearance::Secret),
             PolicyCondition::MFAEnabled(true),
@@ -599,6 +646,9 @@ fn test_complex_multi_policy_scenario() {
         ],
         priority: 100,
         enabled: true,
+        version: 1,
+        effective_from: None,
+        effective_until: None,
         created_by: BootstrapPersonId::new(),
         metadata: std::collections::HashMap::new(),
     };
@@ -691,3 +741,187 @@ fn test_complex_multi_policy_scenario() {
     assert!(senior_evaluation.granted_claims.contains(&PolicyClaim::CanAccessProduction));
     assert!(senior_evaluation.granted_claims.contains(&PolicyClaim::CanModifyInfrastructure));
 }
+
+/// Bind every policy to the person
+fn bind_all(policies: &[Policy], person_id: Uuid) -> Vec<PolicyBinding> {
+    policies
+        .iter()
+        .map(|policy| PolicyBinding {
+            id: Uuid::now_v7(),
+            policy_id: policy.id.as_uuid(),
+            entity_id: person_id,
+            entity_type: PolicyEntityType::Person,
+            bound_at: Utc::now(),
+            bound_by: Uuid::now_v7(),
+            active: true,
+        })
+        .collect()
+}
+
+/// Context without clearance, MFA or witnesses
+fn plain_context(person_id: Uuid) -> PolicyEvaluationContext {
+    PolicyEvaluationContext {
+        person_id,
+        person_clearance: SecurityClearance::Internal,
+        person_units: vec![],
+        person_roles: vec![],
+        employment_start_date: Utc::now(),
+        completed_training: vec![],
+        current_time: Utc::now(),
+        current_location: None,
+        source_ip: None,
+        mfa_verified: false,
+        yubikey_present: false,
+        witnesses: vec![],
+    }
+}
+
+#[test]
+fn test_deny_policy_subtracts_claims() {
+    // Given: Developers may access production, interns never may
+    let developer = make_test_policy("Developer", "Developer access", test_person_id())
+        .with_claim(PolicyClaim::CanAccessProduction)
+        .with_claim(PolicyClaim::CanSignCode)
+        .with_priority(100);
+    let interns = make_test_policy("Interns", "Never production for interns", test_person_id())
+        .with_denied_claim(PolicyClaim::CanAccessProduction)
+        .with_priority(200);
+
+    let person_id = Uuid::now_v7();
+    let policies = [developer, interns];
+    let evaluation = evaluate_policies(
+        &policies,
+        &bind_all(&policies, person_id),
+        person_id,
+        PolicyEntityType::Person,
+        &plain_context(person_id),
+    );
+
+    // Then: The deny subtracts production access, other claims remain
+    assert_eq!(evaluation.active_policies.len(), 2);
+    assert_eq!(evaluation.granted_claims, vec![PolicyClaim::CanSignCode]);
+    assert_eq!(evaluation.denied_claims, vec![PolicyClaim::CanAccessProduction]);
+}
+
+#[test]
+fn test_deny_wins_at_equal_priority() {
+    // Given: A grant and a deny of the same claim at the same priority
+    let grant = make_test_policy("Grant", "Grants production", test_person_id())
+        .with_claim(PolicyClaim::CanAccessProduction)
+        .with_priority(100);
+    let deny = make_test_policy("Deny", "Denies production", test_person_id())
+        .with_denied_claim(PolicyClaim::CanAccessProduction)
+        .with_priority(100);
+
+    let person_id = Uuid::now_v7();
+    // Order of the policies must not matter
+    for policies in [[grant.clone(), deny.clone()], [deny, grant]] {
+        let evaluation = evaluate_policies(
+            &policies,
+            &bind_all(&policies, person_id),
+            person_id,
+            PolicyEntityType::Person,
+            &plain_context(person_id),
+        );
+        assert!(evaluation.granted_claims.is_empty());
+        assert_eq!(evaluation.denied_claims, vec![PolicyClaim::CanAccessProduction]);
+    }
+}
+
+#[test]
+fn test_higher_priority_grant_overrides_deny() {
+    // Given: Interns are denied production, but a break-glass policy with
+    // higher priority grants it while a witness is present
+    let interns = make_test_policy("Interns", "Never production for interns", test_person_id())
+        .with_denied_claim(PolicyClaim::CanAccessProduction)
+        .with_priority(200);
+    let break_glass = make_test_policy("Break Glass", "Emergency production access", test_person_id())
+        .with_claim(PolicyClaim::CanAccessProduction)
+        .with_condition(PolicyCondition::RequiresWitness { count: 1, witness_clearance: None })
+        .with_priority(300);
+
+    let person_id = Uuid::now_v7();
+    let policies = [interns, break_glass];
+    let bindings = bind_all(&policies, person_id);
+
+    // Without a witness the break-glass policy is inactive and the deny holds
+    let denied = evaluate_policies(
+        &policies,
+        &bindings,
+        person_id,
+        PolicyEntityType::Person,
+        &plain_context(person_id),
+    );
+    assert!(!denied.granted_claims.contains(&PolicyClaim::CanAccessProduction));
+    assert_eq!(denied.denied_claims, vec![PolicyClaim::CanAccessProduction]);
+
+    // With a witness the higher-priority grant overrides the deny
+    let witnessed_context = PolicyEvaluationContext {
+        witnesses: vec![WitnessInfo {
+            person_id: Uuid::now_v7(),
+            clearance: SecurityClearance::Secret,
+        }],
+        ..plain_context(person_id)
+    };
+    let granted = evaluate_policies(&policies, &bindings, person_id, PolicyEntityType::Person, &witnessed_context);
+    assert_eq!(granted.granted_claims, vec![PolicyClaim::CanAccessProduction]);
+    assert!(granted.denied_claims.is_empty());
+}
+
+#[test]
+fn test_evaluation_uses_version_effective_at_evaluation_time() {
+    // Given: Version 1 grants code signing; version 2, from a cut-over date,
+    // also grants production access
+    let cutover = Utc::now() - chrono::Duration::days(30);
+    let v1 = make_test_policy("Developer", "Developer access", test_person_id())
+        .with_claim(PolicyClaim::CanSignCode);
+    let v2 = v1.next_version(cutover).with_claim(PolicyClaim::CanAccessProduction);
+    assert_eq!(v2.id, v1.id);
+    assert_eq!(v2.version, 2);
+
+    let person_id = Uuid::now_v7();
+    let bindings = bind_all(std::slice::from_ref(&v1), person_id);
+    let policies = [v1, v2];
+
+    // Before the cut-over, version 1 is in force
+    let before = PolicyEvaluationContext {
+        current_time: cutover - chrono::Duration::days(1),
+        ..plain_context(person_id)
+    };
+    let evaluation = evaluate_policies(&policies, &bindings, person_id, PolicyEntityType::Person, &before);
+    assert_eq!(evaluation.granted_claims, vec![PolicyClaim::CanSignCode]);
+    assert_eq!(evaluation.policy_versions, vec![(policies[0].id.as_uuid(), 1)]);
+    assert_eq!(evaluation.evaluated_at, before.current_time);
+
+    // From the cut-over, version 2 is
+    let after = evaluate_policies(&policies, &bindings, person_id, PolicyEntityType::Person, &plain_context(person_id));
+    assert!(after.granted_claims.contains(&PolicyClaim::CanAccessProduction));
+    assert_eq!(after.policy_versions, vec![(policies[0].id.as_uuid(), 2)]);
+}
+
+#[test]
+fn test_expired_version_does_not_fall_back() {
+    // Given: Version 2 was a temporary grant that has since expired
+    let start = Utc::now() - chrono::Duration::days(10);
+    let v1 = make_test_policy("Contractor", "Contractor access", test_person_id())
+        .with_claim(PolicyClaim::CanAccessDevelopment);
+    let v2 = v1
+        .next_version(start)
+        .with_effective_until(start + chrono::Duration::days(7));
+
+    let person_id = Uuid::now_v7();
+    let bindings = bind_all(std::slice::from_ref(&v1), person_id);
+    let policies = [v1, v2];
+
+    let during = PolicyEvaluationContext {
+        current_time: start + chrono::Duration::days(1),
+        ..plain_context(person_id)
+    };
+    let evaluation = evaluate_policies(&policies, &bindings, person_id, PolicyEntityType::Person, &during);
+    assert_eq!(evaluation.granted_claims, vec![PolicyClaim::CanAccessDevelopment]);
+
+    // Then: Once version 2 expires, version 1 does not come back
+    let evaluation = evaluate_policies(&policies, &bindings, person_id, PolicyEntityType::Person, &plain_context(person_id));
+    assert!(evaluation.granted_claims.is_empty());
+    assert!(evaluation.active_policies.is_empty());
+}
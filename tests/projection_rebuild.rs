@@ -0,0 +1,203 @@
+// Copyright (c) 2025 - Cowboy AI, LLC.
+//! Projection Rebuild Tests
+//!
+//! Rebuilding from the event log must reproduce the offline projection,
+//! and report anything in the existing manifest the events do not explain.
+
+use cim_keys::events::nats_account::NatsAccountCreatedEvent;
+use cim_keys::events::nats_operator::{JwtClaimsCreatedEvent, JwtSignedEvent, NatsOperatorCreatedEvent};
+use cim_keys::events::nats_user::NatsUserCreatedEvent;
+use cim_keys::events::person::PersonCreatedEvent;
+use cim_keys::events::{
+    DomainEvent, NatsAccountEvents, NatsOperatorEvents, NatsUserEvents, PersonEvents,
+};
+use cim_keys::projections::rebuild::{rebuild, DivergenceKind, EventStream};
+use cim_keys::projections::{OfflineKeyProjection, ProjectionError};
+use cim_keys::value_objects::ActorId;
+use chrono::Utc;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use uuid::Uuid;
+
+// =============================================================================
+// Test Helpers
+// =============================================================================
+
+struct Organization {
+    person_id: Uuid,
+    operator_id: Uuid,
+    user_id: Uuid,
+    events: Vec<DomainEvent>,
+}
+
+/// A person with a NATS user under one operator and account; only the
+/// operator JWT has been signed
+fn organization() -> Organization {
+    let (person_id, operator_id, account_id, user_id) =
+        (Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7(), Uuid::now_v7());
+    let correlation_id = Uuid::now_v7();
+    let claims_id = Uuid::now_v7();
+
+    let events = vec![
+        DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+            person_id,
+            name: "Alice Johnson".to_string(),
+            email: Some("alice@example.com".to_string()),
+            title: Some("Operator".to_string()),
+            department: None,
+            organization_id: Uuid::now_v7(),
+            created_by: ActorId::system("admin"),
+            correlation_id,
+            causation_id: None,
+        })),
+        DomainEvent::NatsOperator(NatsOperatorEvents::NatsOperatorCreated(NatsOperatorCreatedEvent {
+            operator_id,
+            name: "CowboyAI Operator".to_string(),
+            public_key: "OPERATOR_PUBLIC_KEY".to_string(),
+            created_by: "admin".to_string(),
+            organization_id: Some(Uuid::now_v7()),
+            correlation_id,
+            causation_id: None,
+        })),
+        DomainEvent::NatsOperator(NatsOperatorEvents::JwtClaimsCreated(JwtClaimsCreatedEvent {
+            claims_id,
+            issuer: "OPERATOR_PUBLIC_KEY".to_string(),
+            subject: "OPERATOR_PUBLIC_KEY".to_string(),
+            audience: None,
+            permissions: "{}".to_string(),
+            not_before: Utc::now(),
+            expires_at: None,
+            correlation_id,
+            causation_id: None,
+        })),
+        DomainEvent::NatsOperator(NatsOperatorEvents::JwtSigned(JwtSignedEvent {
+            jwt_id: Uuid::now_v7(),
+            claims_id,
+            signed_by: operator_id,
+            signer_public_key: "OPERATOR_PUBLIC_KEY".to_string(),
+            jwt_token: "eyJ0eXAiOiJKV1QifQ.operator.signature".to_string(),
+            signature_algorithm: "ed25519-nkey".to_string(),
+            signature_verification_data: None,
+            signed_at: Utc::now(),
+            correlation_id,
+            causation_id: None,
+        })),
+        DomainEvent::NatsAccount(NatsAccountEvents::NatsAccountCreated(NatsAccountCreatedEvent {
+            account_id,
+            operator_id,
+            name: "Engineering".to_string(),
+            public_key: "ACCOUNT_PUBLIC_KEY".to_string(),
+            is_system: false,
+            created_by: "admin".to_string(),
+            organization_unit_id: None,
+            correlation_id,
+            causation_id: None,
+        })),
+        DomainEvent::NatsUser(NatsUserEvents::NatsUserCreated(NatsUserCreatedEvent {
+            user_id,
+            account_id,
+            name: "alice".to_string(),
+            public_key: "USER_PUBLIC_KEY".to_string(),
+            created_by: "admin".to_string(),
+            person_id: Some(person_id),
+            correlation_id,
+            causation_id: None,
+        })),
+    ];
+
+    Organization { person_id, operator_id, user_id, events }
+}
+
+fn existing_projection(events: &[DomainEvent]) -> (TempDir, OfflineKeyProjection) {
+    let dir = TempDir::new().unwrap();
+    let mut projection = OfflineKeyProjection::new(dir.path()).unwrap();
+    for event in events {
+        projection.apply(event).unwrap();
+    }
+    (dir, projection)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[test]
+fn test_rebuild_from_projection_log_matches_existing() {
+    let org = organization();
+    let (existing_dir, existing) = existing_projection(&org.events);
+    let target = TempDir::new().unwrap();
+
+    let stream = EventStream::from_projection_log(existing_dir.path()).unwrap();
+    let output = rebuild(&stream, target.path(), Some(existing.manifest())).unwrap();
+
+    let report = &output.report;
+    assert!(report.is_consistent(), "unexpected divergences: {:?}", report.divergences);
+    assert_eq!(report.events_replayed, 6);
+    assert_eq!(report.events_by_aggregate["NatsOperator"], 3);
+    assert_eq!(report.events_by_aggregate["Person"], 1);
+    assert_eq!(output.projection.get_people()[0].person_id, org.person_id);
+
+    // Graph links the person to their NATS user
+    assert!(output.cypher.contains(&org.user_id.to_string()));
+    assert!(output.cypher.contains("HAS_NATS_USER"));
+
+    // Only the operator JWT is in the stream
+    let store = &output.nsc_stores[&org.operator_id];
+    let operator_jwt = store
+        .files
+        .iter()
+        .find(|f| f.path == PathBuf::from("stores/cowboyai_operator/cowboyai_operator.jwt"))
+        .unwrap();
+    assert_eq!(operator_jwt.content, "eyJ0eXAiOiJKV1QifQ.operator.signature");
+    let mut missing = report.missing_jwts.clone();
+    missing.sort();
+    assert_eq!(missing, vec!["ACCOUNT_PUBLIC_KEY", "USER_PUBLIC_KEY"]);
+}
+
+#[test]
+fn test_rebuild_reports_divergences() {
+    let org = organization();
+    let (_existing_dir, mut existing) = existing_projection(&org.events);
+
+    // Written straight to the manifest, so no event explains it
+    let unlogged = Uuid::now_v7();
+    existing
+        .add_person(unlogged, "Bob".to_string(), "bob@example.com".to_string(), "Member".to_string(), Uuid::now_v7())
+        .unwrap();
+
+    // The rebuild also sees a user the existing projection never applied
+    let mut events = org.events.clone();
+    let DomainEvent::NatsUser(NatsUserEvents::NatsUserCreated(user)) = events[5].clone() else {
+        unreachable!()
+    };
+    let late_user_id = Uuid::now_v7();
+    events.push(DomainEvent::NatsUser(NatsUserEvents::NatsUserCreated(NatsUserCreatedEvent {
+        user_id: late_user_id,
+        name: "ci".to_string(),
+        public_key: "CI_PUBLIC_KEY".to_string(),
+        person_id: None,
+        ..user
+    })));
+
+    let mut manifest = existing.manifest().clone();
+    manifest.nats_users[0].name = "renamed".to_string();
+
+    let target = TempDir::new().unwrap();
+    let report = rebuild(&EventStream::new(events), target.path(), Some(&manifest)).unwrap().report;
+
+    let divergence = |id: Uuid| report.divergences.iter().find(|d| d.id == id.to_string()).map(|d| (d.collection, d.kind));
+    assert_eq!(divergence(unlogged), Some(("people", DivergenceKind::MissingFromRebuild)));
+    assert_eq!(divergence(late_user_id), Some(("nats_users", DivergenceKind::MissingFromExisting)));
+    assert_eq!(divergence(org.user_id), Some(("nats_users", DivergenceKind::Changed)));
+    assert_eq!(report.divergences.len(), 3);
+}
+
+#[test]
+fn test_rebuild_refuses_existing_projection() {
+    let org = organization();
+    let (existing_dir, existing) = existing_projection(&org.events);
+
+    let result = rebuild(&EventStream::new(org.events), existing_dir.path(), Some(existing.manifest()));
+    assert!(matches!(result, Err(ProjectionError::InvalidStateTransition(_))));
+    assert_eq!(OfflineKeyProjection::new(existing_dir.path()).unwrap().manifest().event_count, 6);
+}
@@ -124,6 +124,50 @@ mod application_correctness {
 
         assert!(!projection.key_exists(&unknown_id));
     }
+
+    #[test]
+    fn test_relationship_events_update_projection() {
+        use cim_keys::commands::organization::RelationshipType;
+        use cim_keys::events::relationship::{RelationshipEstablishedEvent, RelationshipTerminatedEvent};
+        use cim_keys::events::{DomainEvent, RelationshipEvents};
+
+        let (_temp_dir, mut projection) = create_temp_projection();
+        let relationship_id = Uuid::now_v7();
+
+        projection
+            .apply(&DomainEvent::Relationship(RelationshipEvents::RelationshipEstablished(
+                RelationshipEstablishedEvent {
+                    relationship_id,
+                    from_id: Uuid::now_v7(),
+                    to_id: Uuid::now_v7(),
+                    relationship_type: RelationshipType::StoredAt,
+                    established_at: Utc::now(),
+                    established_by: "admin".to_string(),
+                    valid_from: Utc::now(),
+                    valid_until: None,
+                    correlation_id: Uuid::now_v7(),
+                    causation_id: None,
+                },
+            )))
+            .unwrap();
+        assert!(projection.manifest().relationships[0].terminated_at.is_none());
+
+        projection
+            .apply(&DomainEvent::Relationship(RelationshipEvents::RelationshipTerminated(
+                RelationshipTerminatedEvent {
+                    relationship_id,
+                    reason: "moved".to_string(),
+                    terminated_at: Utc::now(),
+                    terminated_by: "admin".to_string(),
+                    correlation_id: Uuid::now_v7(),
+                    causation_id: None,
+                },
+            )))
+            .unwrap();
+        // Terminated relationships stay in the manifest as history
+        assert_eq!(projection.manifest().relationships.len(), 1);
+        assert!(projection.manifest().relationships[0].terminated_at.is_some());
+    }
 }
 
 // =============================================================================
@@ -149,12 +193,24 @@ mod idempotency {
             keys: vec![],
             certificates: vec![],
             pki_hierarchies: vec![],
+            crls: vec![],
+            certificate_log: vec![],
             yubikeys: vec![],
             nats_operators: vec![],
             nats_accounts: vec![],
             nats_users: vec![],
+            relationships: vec![],
+            delegations: vec![],
+            processed_commands: vec![],
+            outbox: vec![],
+            dead_letters: vec![],
+            journal_position: 0,
             event_count: 0,
             checksum: String::new(),
+            file_checksums: Default::default(),
+            signature: None,
+            luks_uuid: None,
+            kdf_salts: Vec::new(),
         };
 
         let json = serde_json::to_string_pretty(&manifest).unwrap();
@@ -177,6 +233,8 @@ mod idempotency {
             revoked: false,
             file_path: "/keys/test.pem".to_string(),
             state: None,
+            expires_at: None,
+            owner_id: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -257,6 +315,7 @@ mod idempotency {
             slots_used: vec!["9a".to_string(), "9c".to_string()],
             config_path: "/yubikeys/12345678/config.json".to_string(),
             state: None,
+            firmware_version: None,
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -437,6 +496,129 @@ mod filesystem_integrity {
             assert_eq!(projection.get_organization().name, "CowboyAI");
         }
     }
+
+    #[test]
+    fn test_encrypted_event_log_replays_with_key() {
+        use cim_keys::crypto::{EventLogCipher, MasterSeed};
+        use cim_keys::events::{person::PersonCreatedEvent, DomainEvent, PersonEvents};
+        use cim_keys::value_objects::ActorId;
+
+        let temp_dir = TempDir::new().unwrap();
+        let cipher = EventLogCipher::from_master_seed(&MasterSeed::from_bytes([42; 32]));
+        let mut projection = OfflineKeyProjection::new(temp_dir.path())
+            .unwrap()
+            .with_event_encryption(cipher.clone());
+        projection.apply(&DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+            person_id: Uuid::now_v7(),
+            name: "Alice Johnson".to_string(),
+            email: Some("alice@example.com".to_string()),
+            title: None,
+            department: None,
+            organization_id: Uuid::now_v7(),
+            created_by: ActorId::system("admin"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))).unwrap();
+
+        let event_file = fs::read_dir(temp_dir.path().join("events")).unwrap().next().unwrap().unwrap().path();
+        assert!(!fs::read_to_string(event_file).unwrap().contains("Alice"));
+
+        let mut without_key = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        assert!(matches!(without_key.rebuild_from_events(), Err(ProjectionError::EncryptionError(_))));
+
+        let mut with_key = OfflineKeyProjection::new(temp_dir.path())
+            .unwrap()
+            .with_event_encryption(cipher.clone());
+        with_key.rebuild_from_events().unwrap();
+        assert_eq!(with_key.get_people()[0].name, "Alice Johnson");
+
+        // A plaintext event dropped onto the card is not replayed
+        let forged = fs::read_dir(temp_dir.path().join("events")).unwrap().next().unwrap().unwrap().path();
+        fs::write(
+            forged.with_file_name("0_forged.json"),
+            serde_json::to_string(&DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+                person_id: Uuid::now_v7(),
+                name: "Mallory".to_string(),
+                email: None,
+                title: None,
+                department: None,
+                organization_id: Uuid::now_v7(),
+                created_by: ActorId::system("admin"),
+                correlation_id: Uuid::now_v7(),
+                causation_id: None,
+            })))
+            .unwrap(),
+        )
+        .unwrap();
+        let mut replayed = OfflineKeyProjection::new(temp_dir.path())
+            .unwrap()
+            .with_event_encryption(cipher);
+        let _ = replayed.rebuild_from_events();
+        assert!(replayed.get_people().iter().all(|person| person.name != "Mallory"));
+    }
+
+    #[test]
+    fn test_organization_projections_do_not_mix() {
+        use cim_keys::events::{person::PersonCreatedEvent, DomainEvent, PersonEvents};
+        use cim_keys::value_objects::ActorId;
+
+        let person_in = |organization_id: Uuid, name: &str| {
+            DomainEvent::Person(PersonEvents::PersonCreated(PersonCreatedEvent {
+                person_id: Uuid::now_v7(),
+                name: name.to_string(),
+                email: None,
+                title: None,
+                department: None,
+                organization_id,
+                created_by: ActorId::system("admin"),
+                correlation_id: Uuid::now_v7(),
+                causation_id: None,
+            }))
+        };
+
+        let temp_dir = TempDir::new().unwrap();
+        let (acme, globex) = (Uuid::now_v7(), Uuid::now_v7());
+        let mut acme_projection = OfflineKeyProjection::for_organization(temp_dir.path(), acme).unwrap();
+        let mut globex_projection = OfflineKeyProjection::for_organization(temp_dir.path(), globex).unwrap();
+
+        acme_projection.apply(&person_in(acme, "Alice")).unwrap();
+        globex_projection.apply(&person_in(globex, "Bob")).unwrap();
+        assert!(matches!(
+            acme_projection.apply(&person_in(globex, "Carol")),
+            Err(ProjectionError::OrganizationMismatch(_))
+        ));
+
+        let mut expected = vec![acme, globex];
+        expected.sort();
+        assert_eq!(OfflineKeyProjection::organizations(temp_dir.path()).unwrap(), expected);
+
+        let reopened = OfflineKeyProjection::for_organization(temp_dir.path(), acme).unwrap();
+        let names: Vec<_> = reopened.get_people().iter().map(|p| p.name.clone()).collect();
+        assert_eq!(names, vec!["Alice"]);
+        assert_eq!(reopened.manifest().event_count, 1);
+    }
+    #[test]
+    fn test_kdf_salts_survive_reopen_and_rebuild() {
+        use cim_keys::crypto::{KdfParams, KdfPurpose, KdfSalt, KeyEncryptionKey};
+
+        let temp_dir = TempDir::new().unwrap();
+        let mut projection = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        let salt = KdfSalt::generate(KdfPurpose::StorageEncryption, KdfParams::testing());
+        projection.record_kdf_salt(salt.clone()).unwrap();
+        let wrapped = KeyEncryptionKey::from_derived_key(&salt.derive("master passphrase").unwrap())
+            .unwrap()
+            .wrap_key(&[7u8; 32])
+            .unwrap();
+
+        let mut reopened = OfflineKeyProjection::new(temp_dir.path()).unwrap();
+        reopened.rebuild_from_events().unwrap();
+        let stored = reopened.kdf_salt(KdfPurpose::StorageEncryption).unwrap();
+        assert_eq!(stored, &salt);
+        assert!(reopened.kdf_salt(KdfPurpose::LuksKeyfile).is_none());
+
+        let kek = KeyEncryptionKey::from_derived_key(&stored.derive("master passphrase").unwrap()).unwrap();
+        assert_eq!(kek.unwrap_key(&wrapped).unwrap(), vec![7u8; 32]);
+    }
 }
 
 // =============================================================================
@@ -491,6 +673,237 @@ mod state_queries {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_keys_expiring_within_reflects_extension() {
+        use cim_keys::events::{DomainEvent, KeyEvents, KeyGeneratedEvent};
+        use cim_keys::events::key::KeyExpirationExtendedEvent;
+        use cim_keys::value_objects::ActorId;
+
+        let (_temp_dir, mut projection) = create_temp_projection();
+        let key_id = Uuid::now_v7();
+        let now = Utc::now();
+
+        projection.apply(&DomainEvent::Key(KeyEvents::KeyGenerated(KeyGeneratedEvent {
+            key_id,
+            algorithm: KeyAlgorithm::Ed25519,
+            purpose: KeyPurpose::Signing,
+            generated_at: now,
+            generated_by: ActorId::system("test"),
+            hardware_backed: false,
+            metadata: KeyMetadata {
+                label: "gpg".to_string(),
+                description: None,
+                tags: vec![],
+                attributes: Default::default(),
+                jwt_kid: None,
+                jwt_alg: None,
+                jwt_use: None,
+                hardware_serial: None,
+                tpm: None,
+            },
+            ownership: None,
+            expires_at: None,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))).unwrap();
+        assert!(projection.keys_expiring_within(30, now).is_empty());
+
+        let extend = |expires: chrono::DateTime<Utc>| DomainEvent::Key(KeyEvents::KeyExpirationExtended(KeyExpirationExtendedEvent {
+            key_id,
+            fingerprint: "A".repeat(40),
+            previous_expires_at: None,
+            new_expires_at: Some(expires),
+            extended_at: now,
+            extended_by: ActorId::system("test"),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }));
+
+        projection.apply(&extend(now + chrono::Duration::days(10))).unwrap();
+        assert_eq!(projection.keys_expiring_within(30, now).len(), 1);
+
+        projection.apply(&extend(now + chrono::Duration::days(365))).unwrap();
+        assert!(projection.keys_expiring_within(30, now).is_empty());
+    }
+
+    #[test]
+    fn test_key_generated_with_pgp_expiration_is_expiring() {
+        use cim_keys::commands::pki::{handle_generate_key_pair, GenerateKeyPair};
+        use cim_keys::domain::{KeyContext, KeyOwnerRole, KeyOwnership, PgpConfig};
+        use cim_keys::value_objects::AuthKeyPurpose;
+
+        let (_temp_dir, mut projection) = create_temp_projection();
+        let pgp = PgpConfig {
+            user_pin: "123456".to_string(),
+            user_pin_old: None,
+            admin_pin: "12345678".to_string(),
+            admin_pin_old: None,
+            reset_code: None,
+            key_type_auth: "ed25519".to_string(),
+            key_type_sign: "ed25519".to_string(),
+            key_type_encr: "cv25519".to_string(),
+            expiration: "10d".to_string(),
+        };
+        let cmd = GenerateKeyPair {
+            purpose: AuthKeyPurpose::GpgEncryption,
+            algorithm: None,
+            owner_context: KeyContext {
+                actor: KeyOwnership {
+                    person_id: Uuid::now_v7(),
+                    organization_id: Uuid::now_v7(),
+                    role: KeyOwnerRole::Developer,
+                    delegations: vec![],
+                },
+                org_context: None,
+                nats_identity: None,
+                audit_requirements: vec![],
+            },
+            expires_in_days: None,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }
+        .with_pgp_expiration(&pgp);
+
+        let generated = handle_generate_key_pair(cmd).unwrap();
+        for event in &generated.events {
+            projection.apply(event).unwrap();
+        }
+
+        let now = Utc::now();
+        let expiring = projection.keys_expiring_within(30, now);
+        assert_eq!(expiring.len(), 1);
+        assert_eq!(expiring[0].key_id, generated.key_id);
+        assert!(projection.keys_expiring_within(5, now).is_empty());
+    }
+
+    #[test]
+    fn test_delegations_expiring_within_until_expired() {
+        use cim_keys::domain::KeyPermission;
+        use cim_keys::events::{DelegationCreatedEvent, DelegationEvents, DelegationExpiredEvent, DomainEvent};
+
+        let (_temp_dir, mut projection) = create_temp_projection();
+        let delegation_id = Uuid::now_v7();
+        let now = Utc::now();
+        let valid_until = now + chrono::Duration::days(7);
+
+        projection.apply(&DomainEvent::Delegation(DelegationEvents::DelegationCreated(DelegationCreatedEvent {
+            delegation_id,
+            delegator_id: Uuid::now_v7(),
+            delegate_id: Uuid::now_v7(),
+            permissions: vec![KeyPermission::Sign],
+            derives_from: None,
+            valid_from: now,
+            valid_until: Some(valid_until),
+            created_at: now,
+            created_by: Uuid::now_v7(),
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))).unwrap();
+        assert!(projection.delegations_expiring_within(3, now).is_empty());
+        assert_eq!(projection.delegations_expiring_within(30, now).len(), 1);
+
+        projection.apply(&DomainEvent::Delegation(DelegationEvents::DelegationExpired(DelegationExpiredEvent {
+            delegation_id,
+            delegate_id: projection.get_delegations()[0].delegate_id,
+            valid_until,
+            expired_at: valid_until,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))).unwrap();
+        assert!(projection.delegations_expiring_within(30, now).is_empty());
+        assert!(projection.get_delegations()[0].expired);
+    }
+
+    fn certificate_generated(cert_id: Uuid, issuer: Uuid, host: &str) -> cim_keys::events::DomainEvent {
+        use cim_keys::events::{CertificateEvents, CertificateGeneratedEvent, DomainEvent};
+        use cim_keys::value_objects::x509::{
+            BasicConstraints, CertificateValidity, CommonName, KeyUsage, SubjectName,
+        };
+
+        DomainEvent::Certificate(CertificateEvents::CertificateGenerated(CertificateGeneratedEvent {
+            cert_id,
+            key_id: Uuid::now_v7(),
+            subject_name: SubjectName::new(CommonName::new_unchecked(host)),
+            subject_alt_name: None,
+            key_usage: KeyUsage::tls_server(),
+            extended_key_usage: None,
+            validity: CertificateValidity::new(Utc::now(), Utc::now() + chrono::Duration::days(90)).unwrap(),
+            basic_constraints: BasicConstraints::end_entity(),
+            issuer: Some(issuer),
+            signature_algorithm: None,
+            correlation_id: Uuid::now_v7(),
+            causation_id: None,
+        }))
+    }
+
+    #[test]
+    fn test_certificate_log_proves_inclusion_of_issued_certificates() {
+        let (temp_dir, mut projection) = create_temp_projection();
+        let ca_id = Uuid::now_v7();
+        let cert_ids: Vec<Uuid> = (0..5).map(|_| Uuid::now_v7()).collect();
+
+        for (i, cert_id) in cert_ids.iter().enumerate() {
+            projection.apply(&certificate_generated(*cert_id, ca_id, &format!("host{}.example.com", i))).unwrap();
+        }
+
+        let head = projection.certificate_log_head();
+        assert_eq!(head.tree_size, 5);
+        for cert_id in &cert_ids {
+            let proof = projection.certificate_inclusion_proof(*cert_id).unwrap();
+            assert_eq!(proof.tree_head, head);
+            assert!(proof.verify());
+        }
+        assert!(projection.certificate_inclusion_proof(Uuid::now_v7()).is_none());
+
+        let log = fs::read_to_string(temp_dir.path().join("certificates/transparency.jsonl")).unwrap();
+        assert_eq!(log.lines().count(), 5);
+
+        let mut proof = projection.certificate_inclusion_proof(cert_ids[2]).unwrap();
+        proof.leaf_index = 1;
+        assert!(!proof.verify());
+    }
+
+    #[test]
+    fn test_certificate_log_detects_tampering_and_unlogged_certificates() {
+        use cim_keys::domain::nats::replay::StoredEvent;
+
+        let ca_id = Uuid::now_v7();
+        let events: Vec<StoredEvent> = (0..3u64)
+            .map(|seq| StoredEvent {
+                sequence: seq,
+                subject: "keys.events.certificate.generated".to_string(),
+                event: certificate_generated(Uuid::now_v7(), ca_id, &format!("host{}.example.com", seq)),
+                event_id: Some(Uuid::now_v7()),
+                correlation_id: None,
+                causation_id: None,
+                timestamp: Utc::now(),
+                source: None,
+            })
+            .collect();
+        let manifest = KeyManifest::default().fold_events(&events).unwrap();
+        assert_eq!(manifest.verify_certificate_log().unwrap().tree_size, 3);
+
+        let mut tampered = manifest.clone();
+        tampered.certificate_log[1].subject = "CN=evil.example.com".to_string();
+        assert!(tampered.verify_certificate_log().unwrap_err().contains("was modified"));
+
+        // A certificate that bypassed issuance has no log entry
+        let mut off_the_books = manifest;
+        off_the_books.certificates.push(CertificateEntry {
+            cert_id: Uuid::now_v7(),
+            key_id: Uuid::now_v7(),
+            subject: "CN=shadow.example.com".to_string(),
+            issuer: Some(ca_id.to_string()),
+            serial_number: "01".to_string(),
+            not_before: Utc::now(),
+            not_after: Utc::now(),
+            is_ca: false,
+            file_path: String::new(),
+            state: None,
+        });
+        assert!(off_the_books.verify_certificate_log().unwrap_err().contains("missing from the log"));
+    }
+
     #[test]
     fn test_empty_projection_has_default_organization() {
         let (_temp_dir, projection) = create_temp_projection();
@@ -523,6 +936,33 @@ mod error_handling {
         assert!(format!("{:?}", io_error).contains("IoError"));
         assert!(format!("{:?}", serialization_error).contains("Serialization"));
     }
+
+    #[test]
+    fn test_export_pkcs12_requires_stored_certificate() {
+        let (_temp_dir, projection) = create_temp_projection();
+
+        let result = projection.export_pkcs12(Uuid::now_v7(), "", "passphrase");
+        assert!(matches!(result, Err(ProjectionError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_export_pkcs12_bundles_stored_certificate() {
+        let (_temp_dir, projection) = create_temp_projection();
+        let key = rcgen::KeyPair::generate().unwrap();
+        let cert = rcgen::CertificateParams::new(vec!["svc.example.com".to_string()])
+            .unwrap()
+            .self_signed(&key)
+            .unwrap();
+        let cert_id = Uuid::now_v7();
+        projection.store_certificate_chain(cert_id, &cert.pem(), &cert.pem()).unwrap();
+
+        let p12 = projection.export_pkcs12(cert_id, &key.serialize_pem(), "passphrase").unwrap();
+        assert!(!p12.is_empty());
+
+        // Wrong key is rejected rather than producing an unusable bundle
+        let other = rcgen::KeyPair::generate().unwrap();
+        assert!(projection.export_pkcs12(cert_id, &other.serialize_pem(), "passphrase").is_err());
+    }
 }
 
 // =============================================================================
@@ -6,7 +6,7 @@
 
 use chrono::Utc;
 use cim_keys::domain::nats::saga_executor::{
-    AsyncSagaExecutor, JetStreamSagaExecutor, PersistedSagaState, SagaExecutorConfig,
+    AsyncSagaExecutor, CompensationOutcome, JetStreamSagaExecutor, PersistedSagaState, SagaExecutorConfig,
     SagaExecutorError, SagaExecutorResult, StepExecutionResult,
 };
 use cim_keys::domain::sagas::{
@@ -230,9 +230,9 @@ impl AsyncSagaExecutor<TestSaga> for TestSagaExecutor {
         }
     }
 
-    async fn compensate(&self, state: &mut TestSaga) -> SagaExecutorResult<CompensationResult> {
+    async fn compensate(&self, state: &mut TestSaga) -> SagaExecutorResult<CompensationOutcome> {
         state.state = TestSagaState::Failed;
-        Ok(CompensationResult::FullyCompensated)
+        Ok(CompensationResult::FullyCompensated.into())
     }
 
     fn saga_type_name(&self) -> &'static str {
@@ -536,7 +536,7 @@ async fn test_compensate_saga() {
 
     // Compensate
     let result = executor.compensate::<TestSaga, _>(saga_id, &saga_executor).await;
-    assert!(matches!(result, Ok(CompensationResult::FullyCompensated)));
+    assert!(matches!(result.unwrap().result, CompensationResult::FullyCompensated));
 
     // Verify state changed to failed
     let loaded = executor.load_saga::<TestSaga>(saga_id).await.unwrap();
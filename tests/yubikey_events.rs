@@ -317,6 +317,7 @@ fn test_key_algorithm_variants() {
         KeyAlgorithm::Ecdsa { ref curve } => assert!(!curve.is_empty()),
         KeyAlgorithm::Ed25519 => {},
         KeyAlgorithm::Secp256k1 => {},
+        KeyAlgorithm::MlKem768 | KeyAlgorithm::MlDsa65 => {},
     }
 }
 